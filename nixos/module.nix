@@ -4,8 +4,13 @@ with lib;
 
 let
   cfg = config.services.hypervisor-tui;
+  loadCredential = mapAttrsToList (name: path: "${name}:${path}") cfg.credentials;
 in
 {
+  # `settings`, generated from the binary's config with
+  # `hypervisor-tui --generate-nixos-module > nixos/settings.nix`
+  imports = [ ./settings.nix ];
+
   options.services.hypervisor-tui = {
     enable = mkEnableOption "Hypervisor TUI console";
 
@@ -44,9 +49,34 @@ in
       default = true;
       description = "Automatically start TUI on specified TTY without login";
     };
+
+    credentials = mkOption {
+      type = types.attrsOf types.path;
+      default = { };
+      example = { "pagerduty-routing-key" = "/run/agenix/pagerduty"; };
+      description = ''
+        Secrets passed with systemd's LoadCredential=, by key name, for
+        `*_secret` settings looked up by the "credential" provider
+      '';
+    };
+
+    agent.enable = mkEnableOption ''
+      `hypervisor-tui --agent` as a service, serving this host's metrics to
+      central consoles over mutual TLS (configure `settings.agent`)
+    '';
   };
 
   config = mkIf cfg.enable {
+    services.hypervisor-tui.settings = {
+      general.refresh_interval = mkDefault cfg.refreshInterval;
+      general.log_buffer_size = mkDefault cfg.logBufferSize;
+      kubernetes.kubeconfig_path = mkDefault cfg.kubeconfigPath;
+    };
+
+    # Read as the lowest config layer; unset options are left out
+    environment.etc."hypervisor-tui/config.json".text =
+      builtins.toJSON (filterAttrsRecursive (_: v: v != null) cfg.settings);
+
     # Ensure required system packages are available
     environment.systemPackages = with pkgs; [
       cfg.package
@@ -68,6 +98,7 @@ in
         TTYReset = "yes";
         TTYVHangup = "yes";
         Type = "idle";
+        LoadCredential = loadCredential;
       };
       # Ensure k3s is running before starting TUI
       after = [ "k3s.service" ];
@@ -91,6 +122,7 @@ in
         ExecStart = "${cfg.package}/bin/hypervisor-tui";
         Restart = "always";
         RestartSec = "10s";
+        LoadCredential = loadCredential;
 
         # Security hardening
         PrivateTmp = true;
@@ -100,6 +132,26 @@ in
       };
     };
 
+    # Headless agent for consoles on other hosts
+    systemd.services.hypervisor-tui-agent = mkIf cfg.agent.enable {
+      description = "Hypervisor TUI Agent";
+      wantedBy = [ "multi-user.target" ];
+      after = [ "network.target" ];
+
+      environment.RUST_LOG = "hypervisor_tui=info";
+
+      serviceConfig = {
+        Type = "simple";
+        ExecStart = "${cfg.package}/bin/hypervisor-tui --agent";
+        Restart = "always";
+        RestartSec = "10s";
+        LoadCredential = loadCredential;
+
+        PrivateTmp = true;
+        ReadOnlyPaths = [ "/" ];
+      };
+    };
+
     # Grant necessary permissions for reading system information
     security.sudo.extraRules = mkIf cfg.enable [
       {
@@ -0,0 +1,348 @@
+# Generated by `hypervisor-tui --generate-nixos-module`; do not edit.
+# Typed options for `services.hypervisor-tui.settings`, mirroring config.toml.
+{ lib, pkgs, ... }:
+
+let
+  inherit (lib) mkOption types;
+  json = (pkgs.formats.json { }).type;
+  section = description: options: mkOption {
+    type = types.submodule { freeformType = json; inherit options; };
+    default = { };
+    inherit description;
+  };
+  setting = type: default: mkOption {
+    type = types.nullOr type;
+    default = null;
+    description = "Unset uses the built-in default: `${default}`";
+  };
+in
+{
+  options.services.hypervisor-tui.settings = section "hypervisor-tui settings, written to /etc/hypervisor-tui/config.json" {
+    agent = section "`[agent]` settings" {
+      interval_secs = setting types.int "5";
+      listen = setting types.str "\"0.0.0.0:7443\"";
+    };
+    alerts = section "`[alerts]` settings" {
+      actions = setting (types.listOf json) "[]";
+      anomaly_detection = setting types.bool "false";
+      anomaly_min_samples = setting types.int "60";
+      anomaly_sigma = setting types.float "3.0";
+      api_latency_critical_threshold = setting types.float "2000.0";
+      api_latency_warning_threshold = setting types.float "500.0";
+      background_interval_secs = setting types.int "10";
+      boot_slow_factor = setting types.float "3.0";
+      boot_slow_min_secs = setting types.int "60";
+      cpu_critical_threshold = setting types.float "95.0";
+      cpu_throttle_critical_threshold = setting types.float "50.0";
+      cpu_throttle_warning_threshold = setting types.float "25.0";
+      cpu_warning_threshold = setting types.float "80.0";
+      crash_window_mins = setting types.int "60";
+      dedup_window_secs = setting types.int "300";
+      dhcp_churn_threshold = setting types.int "5";
+      disk_critical_threshold = setting types.float "95.0";
+      disk_full_critical_hours = setting types.float "12.0";
+      disk_full_warning_hours = setting types.float "72.0";
+      disk_latency_critical_threshold = setting types.float "200.0";
+      disk_latency_warning_threshold = setting types.float "50.0";
+      disk_warning_threshold = setting types.float "85.0";
+      enabled = setting types.bool "true";
+      entropy_critical_bits = setting types.int "32";
+      entropy_warning_bits = setting types.int "128";
+      ephemeral_port_critical_threshold = setting types.float "90.0";
+      ephemeral_port_warning_threshold = setting types.float "70.0";
+      etcd_db_critical_threshold = setting types.float "95.0";
+      etcd_db_warning_threshold = setting types.float "80.0";
+      etcd_leader_changes_per_hour = setting types.int "3";
+      fd_critical_threshold = setting types.float "95.0";
+      fd_warning_threshold = setting types.float "80.0";
+      freq_throttle_mins = setting types.int "5";
+      freq_throttle_percent = setting types.float "70.0";
+      guest_clock_critical_secs = setting types.float "120.0";
+      guest_clock_warning_secs = setting types.float "5.0";
+      guest_fs_critical_threshold = setting types.float "95.0";
+      guest_fs_warning_threshold = setting types.float "85.0";
+      import_stall_mins = setting types.int "15";
+      ip_conflict_window_mins = setting types.int "10";
+      kernel_event_window_mins = setting types.int "60";
+      kubernetes_enabled = setting types.bool "true";
+      kubevirt_enabled = setting types.bool "true";
+      load_critical_threshold = setting types.float "20.0";
+      load_warning_threshold = setting types.float "10.0";
+      memory_critical_threshold = setting types.float "95.0";
+      memory_warning_threshold = setting types.float "85.0";
+      migration_failure_threshold = setting types.int "3";
+      overrides = setting (types.listOf json) "[]";
+      pid_critical_threshold = setting types.float "95.0";
+      pid_warning_threshold = setting types.float "80.0";
+      pod_restarts_per_hour = setting types.int "5";
+      quota_critical_threshold = setting types.float "95.0";
+      quota_warning_threshold = setting types.float "80.0";
+      rate_limit_per_minute = setting types.int "20";
+      related_logs_mins = setting types.int "5";
+      rule_rate_limit_per_minute = setting types.int "5";
+      runbooks = setting (types.listOf json) "[]";
+      sched_wait_critical_threshold = setting types.float "25.0";
+      sched_wait_warning_threshold = setting types.float "10.0";
+      snooze_mins = setting types.int "60";
+    };
+    compare = section "`[compare]` settings" {
+      baseline_mins = setting types.int "60";
+      retention_hours = setting types.int "48";
+      snapshot_interval_mins = setting types.int "5";
+    };
+    computed_metrics = setting (types.listOf json) "[]";
+    crashes = section "`[crashes]` settings" {
+      crash_dirs = setting (types.listOf types.str) "[\"/var/crash\"]";
+      enabled = setting types.bool "true";
+      history_days = setting types.int "7";
+      processes = setting (types.listOf types.str) "[\"qemu\",\"libvirtd\",\"virt-launcher\",\"virt-handler\",\"k3s\",\"rke2\",\"kubelet\",\"containerd\"]";
+      scan_interval_secs = setting types.int "30";
+    };
+    custom_commands = setting (types.listOf json) "[]";
+    dashboard = section "`[dashboard]` settings" {
+      rows = setting (types.listOf json) "[{\"cards\":[\"cpu\",\"memory\"],\"height\":25},{\"cards\":[\"disk\",\"cluster\",\"vms\"],\"height\":35},{\"cards\":[\"cgroups\"]}]";
+    };
+    display = section "`[display]` settings" {
+      accessibility = setting types.bool "false";
+      alert_styles = section "`[display.alert_styles]` settings" {
+        categories = setting (types.attrsOf json) "{}";
+        levels = setting (types.attrsOf json) "{}";
+      };
+      animation_refresh = setting types.int "100";
+      banner_cycle_secs = setting types.int "5";
+      cpu_smoothing_secs = setting types.int "10";
+      footer_log_rates = setting types.bool "true";
+      header = setting (types.listOf json) "[{\"enabled\":true,\"interval_secs\":30,\"timeout_secs\":10,\"widget\":\"hostname\"},{\"enabled\":true,\"interval_secs\":30,\"timeout_secs\":10,\"widget\":\"uptime\"},{\"enabled\":true,\"interval_secs\":30,\"timeout_secs\":10,\"widget\":\"cpu\"},{\"enabled\":true,\"interval_secs\":30,\"timeout_secs\":10,\"widget\":\"k8s\"},{\"enabled\":true,\"interval_secs\":30,\"timeout_secs\":10,\"widget\":\"memory\"},{\"enabled\":true,\"interval_secs\":30,\"timeout_secs\":10,\"widget\":\"vms\"}]";
+      locale = setting types.str "\"auto\"";
+      remember_state = setting types.bool "true";
+      show_graphs = setting types.bool "true";
+      theme = setting types.str "\"auto\"";
+      time_zone = setting types.str "\"local\"";
+      timestamp_format = setting types.str "\"default\"";
+    };
+    general = section "`[general]` settings" {
+      collector_timeout_secs = setting types.int "10";
+      idle_refresh_secs = setting types.int "30";
+      log_buffer_max_mb = setting types.int "64";
+      log_buffer_size = setting types.int "10000";
+      read_only = setting types.bool "false";
+      refresh_interval = setting types.int "2";
+      restart_on_panic = setting types.bool "false";
+      telemetry_interval_secs = setting types.int "60";
+      watched_processes = setting (types.listOf types.str) "[\"qemu-kvm\",\"qemu-system-x86_64\",\"virt-launcher\",\"virt-handler\",\"k3s\",\"k3s-server\",\"rke2\",\"kubelet\",\"containerd\"]";
+    };
+    health = section "`[health]` settings" {
+      alerts_weight = setting types.int "30";
+      cluster_weight = setting types.int "20";
+      critical_below = setting types.int "50";
+      degraded_below = setting types.int "80";
+      metrics_weight = setting types.int "30";
+      services_weight = setting types.int "20";
+    };
+    host_profile = section "`[host_profile]` settings" {
+      check_interval_secs = setting types.int "300";
+      cpu_flags = setting (types.listOf json) "[]";
+      hugepages = setting (types.attrsOf json) "{}";
+      services = setting (types.listOf json) "[]";
+      sysctls = setting (types.attrsOf json) "{}";
+    };
+    image_catalog = section "`[image_catalog]` settings" {
+      images = setting (types.listOf json) "[{\"checksum_url\":\"https://cloud-images.ubuntu.com/noble/current/SHA256SUMS\",\"name\":\"ubuntu-24.04\",\"size\":\"20Gi\",\"url\":\"https://cloud-images.ubuntu.com/noble/current/noble-server-cloudimg-amd64.img\"},{\"checksum_url\":\"https://cloud.debian.org/images/cloud/bookworm/latest/SHA512SUMS\",\"name\":\"debian-12\",\"size\":\"20Gi\",\"url\":\"https://cloud.debian.org/images/cloud/bookworm/latest/debian-12-genericcloud-amd64.qcow2\"},{\"checksum_url\":\"https://download.fedoraproject.org/pub/fedora/linux/releases/40/Cloud/x86_64/images/Fedora-Cloud-40-1.14-x86_64-CHECKSUM\",\"name\":\"fedora-40\",\"size\":\"20Gi\",\"url\":\"https://download.fedoraproject.org/pub/fedora/linux/releases/40/Cloud/x86_64/images/Fedora-Cloud-Base-Generic.x86_64-40-1.14.qcow2\"}]";
+      namespace = setting types.str "\"default\"";
+    };
+    kubernetes = section "`[kubernetes]` settings" {
+      api = section "`[kubernetes.api]` settings" {
+        burst = setting types.int "40";
+        cache_ttl_secs = setting types.int "5";
+        qps = setting types.float "20.0";
+        ttl_secs = setting (types.attrsOf json) "{}";
+      };
+      console = section "`[kubernetes.console]` settings" {
+        buffer_lines = setting types.int "2000";
+        capture_all = setting types.bool "false";
+        retry_secs = setting types.int "15";
+        vnc_address = setting types.str "\"127.0.0.1\"";
+        vnc_first_port = setting types.int "5901";
+      };
+      etcd = section "`[kubernetes.etcd]` settings" {
+        enabled = setting types.bool "true";
+      };
+      evacuation = section "`[kubernetes.evacuation]` settings" {
+        concurrency = setting types.int "2";
+        stuck_secs = setting types.int "600";
+      };
+      guest_clock = section "`[kubernetes.guest_clock]` settings" {
+        interval_secs = setting types.int "300";
+      };
+      guest_filesystems = section "`[kubernetes.guest_filesystems]` settings" {
+        interval_secs = setting types.int "300";
+      };
+      local_mode = section "`[kubernetes.local_mode]` settings" {
+        after_failures = setting types.int "3";
+        emergency_actions = setting types.bool "true";
+      };
+      watched_services = setting (types.listOf json) "[]";
+    };
+    logging = section "`[logging]` settings" {
+      archive_max_lines = setting types.int "50000";
+      collapse_repeats = setting types.bool "true";
+      context_lines = setting types.int "10";
+      forward = setting (types.listOf json) "[]";
+      highlights = setting (types.listOf json) "[]";
+      kernel_log = setting types.bool "true";
+      level_filter = setting types.str "\"INFO\"";
+      redactions = setting (types.listOf json) "[]";
+      search_lines = setting types.int "20000";
+    };
+    memory_leaks = section "`[memory_leaks]` settings" {
+      enabled = setting types.bool "true";
+      growth_mb_per_hour = setting types.float "20.0";
+      limits_mb = setting (types.attrsOf json) "{}";
+      processes = setting (types.listOf types.str) "[\"virt-handler\",\"containerd\",\"k3s-server\",\"k3s-agent\",\"rke2\",\"kubelet\",\"libvirtd\"]";
+      sample_interval_secs = setting types.int "60";
+      window_hours = setting types.int "6";
+    };
+    network = section "`[network]` settings" {
+      conflict_watch_bridges = setting (types.listOf json) "[]";
+      dns_checks_enabled = setting types.bool "true";
+      dns_cluster_names = setting (types.listOf types.str) "[\"kubernetes.default.svc.cluster.local\"]";
+      dns_failure_threshold = setting types.int "3";
+      dns_host_names = setting (types.listOf types.str) "[\"cache.nixos.org\"]";
+      dns_timeout_ms = setting types.int "1000";
+      expected_listeners = setting (types.listOf types.int) "[22,10256]";
+      gateway_failure_threshold = setting types.int "3";
+      gateway_timeout_ms = setting types.int "1000";
+      image_pull_failure_threshold = setting types.int "3";
+      interface_labels = setting (types.attrsOf json) "{}";
+      interfaces = setting (types.listOf json) "[]";
+      registries = setting (types.listOf json) "[]";
+      registry_failure_threshold = setting types.int "3";
+      registry_timeout_ms = setting types.int "3000";
+      route_checks_enabled = setting types.bool "true";
+      route_flap_threshold = setting types.int "3";
+      route_flap_window_minutes = setting types.int "10";
+      show_bridges = setting types.bool "true";
+      show_loopback = setting types.bool "false";
+      show_virtual = setting types.bool "true";
+      vip_failure_threshold = setting types.int "3";
+      vip_services = setting (types.listOf json) "[]";
+      vip_timeout_ms = setting types.int "2000";
+      vm_namespaces = setting types.bool "false";
+      watched_ports = setting (types.listOf json) "[{\"name\":\"kube-apiserver\",\"port\":6443,\"required\":false},{\"name\":\"kubelet\",\"port\":10250,\"required\":true},{\"name\":\"etcd client\",\"port\":2379,\"required\":false},{\"name\":\"etcd peer\",\"port\":2380,\"required\":false},{\"name\":\"vnc/console\",\"port\":5900,\"required\":false,\"to\":5999}]";
+    };
+    notifications = section "`[notifications]` settings" {
+      alertmanager = section "`[notifications.alertmanager]` settings" {
+        enabled = setting types.bool "false";
+        labels = setting (types.attrsOf json) "{}";
+        levels = setting (types.listOf types.str) "[\"critical\",\"error\",\"warning\"]";
+        resend_interval_secs = setting types.int "60";
+        url = setting types.str "\"\"";
+      };
+      email = section "`[notifications.email]` settings" {
+        critical_recipients = setting (types.listOf json) "[]";
+        digest_interval_secs = setting types.int "900";
+        enabled = setting types.bool "false";
+        error_recipients = setting (types.listOf json) "[]";
+        from = setting types.str "\"hypervisor-tui@localhost\"";
+        security = setting types.str "\"starttls\"";
+        smtp_port = setting types.int "587";
+        smtp_server = setting types.str "\"\"";
+        warning_recipients = setting (types.listOf json) "[]";
+      };
+      pagerduty = section "`[notifications.pagerduty]` settings" {
+        enabled = setting types.bool "false";
+        levels = setting (types.listOf types.str) "[\"critical\",\"error\"]";
+        url = setting types.str "\"https://events.pagerduty.com/v2/enqueue\"";
+      };
+      routes = setting (types.listOf json) "[]";
+    };
+    prometheus = section "`[prometheus]` settings" {
+      api_latency_query = setting types.str "\"histogram_quantile(0.99, sum by (le) (rate(apiserver_request_duration_seconds_bucket{verb!~\\\"WATCH|CONNECT|LIST\\\"}[5m])))\"";
+      etcd = setting types.bool "true";
+      interval_secs = setting types.int "30";
+      metrics = setting (types.attrsOf json) "{}";
+      queries = setting (types.attrsOf json) "{}";
+      targets = setting (types.listOf json) "[]";
+      url = setting types.str "\"\"";
+      vm_cpu_query = setting types.str "\"100 * sum by (namespace, name) (rate(kubevirt_vmi_vcpu_seconds_total[5m])) / count by (namespace, name) (count by (namespace, name, id) (kubevirt_vmi_vcpu_seconds_total))\"";
+      vm_memory_query = setting types.str "\"100 * sum by (namespace, name) (kubevirt_vmi_memory_used_bytes) / sum by (namespace, name) (kubevirt_vmi_memory_domain_bytes)\"";
+    };
+    receiver = section "`[receiver]` settings" {
+      enabled = setting types.bool "false";
+      expire_mins = setting types.int "300";
+      listen = setting types.str "\"127.0.0.1:9095\"";
+    };
+    remote_agents = section "`[remote_agents]` settings" {
+      endpoints = setting (types.listOf json) "[]";
+      failure_threshold = setting types.int "3";
+      interval_secs = setting types.int "10";
+    };
+    report = section "`[report]` settings" {
+      exit_sections = setting (types.listOf types.str) "[\"alerts\",\"metrics\"]";
+      exit_summary = setting types.bool "false";
+      max_alerts = setting types.int "10";
+      sections = setting (types.listOf types.str) "[\"host\",\"alerts\",\"metrics\",\"workloads\"]";
+    };
+    secrets = section "`[secrets]` settings" {
+      providers = setting (types.listOf types.str) "[\"credential\",\"env\",\"file\"]";
+    };
+    selftest = section "`[selftest]` settings" {
+      image = setting types.str "\"quay.io/kubevirt/cirros-container-disk-demo:latest\"";
+      namespace = setting types.str "\"default\"";
+      step_timeout_secs = setting types.int "180";
+      storage_image = setting types.str "\"busybox:1.36\"";
+    };
+    storage = section "`[storage]` settings" {
+      datastore_devices = setting (types.listOf json) "[]";
+      filesystems = setting (types.listOf json) "[]";
+      image_dirs = setting (types.listOf types.str) "[\"/var/lib/libvirt/images\"]";
+      local_path_dirs = setting (types.listOf types.str) "[\"/var/lib/rancher/k3s/storage\",\"/opt/local-path-provisioner\"]";
+    };
+    sysctl = section "`[sysctl]` settings" {
+      expected = section "`[sysctl.expected]` settings" {
+        "fs.inotify.max_user_instances" = setting types.str "\">=512\"";
+        "fs.inotify.max_user_watches" = setting types.str "\">=524288\"";
+        "net.bridge.bridge-nf-call-ip6tables" = setting types.str "\"1\"";
+        "net.bridge.bridge-nf-call-iptables" = setting types.str "\"1\"";
+        "net.ipv4.ip_forward" = setting types.str "\"1\"";
+        "vm.overcommit_memory" = setting types.str "\"1\"";
+      };
+      watch = setting (types.listOf types.str) "[\"vm.nr_hugepages\",\"vm.swappiness\"]";
+    };
+    tenants = section "`[tenants]` settings" {
+      growth_alert_percent = setting types.float "100.0";
+      growth_window_hours = setting types.int "24";
+      limits = setting (types.attrsOf json) "{}";
+    };
+    update_check = section "`[update_check]` settings" {
+      enabled = setting types.bool "false";
+      interval_hours = setting types.int "24";
+      url = setting types.str "\"https://api.github.com/repos/3whiskeywhiskey/nix-hypervisor-tui/releases/latest\"";
+    };
+    upgrade = section "`[upgrade]` settings" {
+      command = setting (types.listOf types.str) "[\"nixos-rebuild\"]";
+      extra_args = setting (types.listOf json) "[]";
+      gc_command = setting (types.listOf types.str) "[\"nix-collect-garbage\"]";
+      gc_retention = setting types.str "\"14d\"";
+      nix_env_command = setting (types.listOf types.str) "[\"nix-env\"]";
+    };
+    views = setting (types.listOf json) "[]";
+    virtualization = section "`[virtualization]` settings" {
+      backend = setting types.str "\"kubevirt\"";
+      proxmox = section "`[virtualization.proxmox]` settings" {
+        insecure = setting types.bool "false";
+        token_id = setting types.str "\"\"";
+        url = setting types.str "\"\"";
+      };
+      qmp = section "`[virtualization.qmp]` settings" {
+        hosts = setting (types.listOf types.str) "[\"localhost\"]";
+        sockets = setting types.str "\"/run/qemu/*.qmp\"";
+        ssh_options = setting (types.listOf json) "[]";
+      };
+    };
+    web = section "`[web]` settings" {
+      enabled = setting types.bool "false";
+      listen = setting types.str "\"127.0.0.1:8080\"";
+    };
+  };
+}
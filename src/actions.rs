@@ -0,0 +1,177 @@
+use crate::alerts::{Alert, AlertCategory, AlertLevel};
+use crate::audit::{AuditEntry, AuditLog};
+use crate::config::AlertAction;
+use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+const HOUR: Duration = Duration::from_secs(3600);
+/// Longest an action may run before it is killed and counted as failed
+const ACTION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A finished action, sent back from its task
+struct ActionRun {
+    rule: usize,
+    action: String,
+    reason: String,
+    result: Result<(), String>,
+}
+
+/// Runs the `[[alerts.actions]]` whose pattern matches an alert when it
+/// fires, each at most `max_per_hour` times an hour, in the background.
+/// Every run, and every one skipped over the limit, is written to the
+/// audit log and reported as an Info alert.
+pub struct AutoActions {
+    rules: Vec<AlertAction>,
+    kubeconfig: Option<PathBuf>,
+    /// Start times of each rule's runs within the last hour
+    runs: Vec<VecDeque<Instant>>,
+    /// Active alerts already acted on, so a standing alert acts once
+    handled: HashSet<String>,
+    audit: AuditLog,
+    tx: mpsc::UnboundedSender<ActionRun>,
+    rx: mpsc::UnboundedReceiver<ActionRun>,
+}
+
+impl AutoActions {
+    pub fn new(rules: Vec<AlertAction>, kubeconfig: Option<PathBuf>) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            runs: vec![VecDeque::new(); rules.len()],
+            rules,
+            kubeconfig,
+            handled: HashSet::new(),
+            audit: AuditLog::in_state_dir(),
+            tx,
+            rx,
+        }
+    }
+
+    /// Replace the rules, e.g. on an alert profile switch; run counts restart
+    pub fn set_rules(&mut self, rules: Vec<AlertAction>) {
+        self.runs = vec![VecDeque::new(); rules.len()];
+        self.rules = rules;
+    }
+
+    /// Start the actions for alerts that fired since the last call. Info
+    /// alerts, including the ones reporting actions, never trigger any.
+    /// Returns Info alerts for actions skipped over their hourly limit.
+    pub fn process(&mut self, active: &[&Alert]) -> Vec<Alert> {
+        self.handled.retain(|id| active.iter().any(|alert| &alert.id == id));
+        let mut notices = Vec::new();
+        for alert in active {
+            if alert.level == AlertLevel::Info || !self.handled.insert(alert.id.clone()) {
+                continue;
+            }
+            for (index, rule) in self.rules.iter().enumerate() {
+                if !rule.matches(&alert.metadata.source, &alert.title) {
+                    continue;
+                }
+                let action = describe(rule);
+                let reason = format!("{} ({})", alert.title, alert.metadata.source);
+
+                let now = Instant::now();
+                let runs = &mut self.runs[index];
+                while runs.front().is_some_and(|started| now.duration_since(*started) >= HOUR) {
+                    runs.pop_front();
+                }
+                if runs.len() >= rule.max_per_hour as usize {
+                    let outcome = format!("skipped: ran {} times in the last hour (max_per_hour)", runs.len());
+                    record(&self.audit, &action, &reason, &outcome);
+                    notices.push(notice(index, format!("Auto-Action Skipped: {}", action), format!("{} for {}", outcome, reason)));
+                    continue;
+                }
+                runs.push_back(now);
+
+                let command = command(rule, self.kubeconfig.as_ref(), alert);
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = run(command).await;
+                    let _ = tx.send(ActionRun { rule: index, action, reason, result });
+                });
+            }
+        }
+        notices
+    }
+
+    /// Finished runs, recorded in the audit log and returned as Info alerts
+    pub fn poll(&mut self) -> Vec<Alert> {
+        let mut notices = Vec::new();
+        while let Ok(run) = self.rx.try_recv() {
+            let (title, outcome) = match run.result {
+                Ok(()) => (format!("Auto-Action Ran: {}", run.action), "ok".to_string()),
+                Err(e) => (format!("Auto-Action Failed: {}", run.action), format!("failed: {}", e)),
+            };
+            record(&self.audit, &run.action, &run.reason, &outcome);
+            notices.push(notice(run.rule, title, format!("In response to {}: {}", run.reason, outcome)));
+        }
+        notices
+    }
+}
+
+/// The command line an action runs, for the audit log and alerts
+fn describe(rule: &AlertAction) -> String {
+    if let Some(ref unit) = rule.restart_unit {
+        format!("systemctl restart {}", unit)
+    } else if let Some((namespace, workload)) = rule.rollout_restart.as_deref().and_then(|t| t.split_once('/')) {
+        format!("kubectl rollout restart {} -n {}", workload, namespace)
+    } else {
+        rule.command.clone().unwrap_or_default()
+    }
+}
+
+fn command(rule: &AlertAction, kubeconfig: Option<&PathBuf>, alert: &Alert) -> Command {
+    let mut command = if let Some(ref unit) = rule.restart_unit {
+        let mut command = Command::new("systemctl");
+        command.args(["restart", unit]);
+        command
+    } else if let Some((namespace, workload)) = rule.rollout_restart.as_deref().and_then(|t| t.split_once('/')) {
+        let mut command = Command::new("kubectl");
+        if let Some(path) = kubeconfig {
+            command.arg("--kubeconfig").arg(path);
+        }
+        command.args(["rollout", "restart", workload, "-n", namespace]);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(rule.command.as_deref().unwrap_or_default());
+        command
+    };
+    command
+        .env("ALERT_SOURCE", &alert.metadata.source)
+        .env("ALERT_TITLE", &alert.title)
+        .env("ALERT_LEVEL", alert.level.as_str())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    command
+}
+
+async fn run(mut command: Command) -> Result<(), String> {
+    let output = match tokio::time::timeout(ACTION_TIMEOUT, command.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return Err(e.to_string()),
+        Err(_) => return Err(format!("timed out after {}s", ACTION_TIMEOUT.as_secs())),
+    };
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(stderr.lines().last().map(str::to_string).unwrap_or_else(|| output.status.to_string()))
+}
+
+fn record(audit: &AuditLog, action: &str, reason: &str, outcome: &str) {
+    let entry = AuditEntry::now("auto-action", action.to_string(), reason.to_string(), outcome.to_string());
+    if let Err(e) = audit.record(&entry) {
+        tracing::warn!("{:#}", e);
+    }
+}
+
+/// Info alert about action #`rule`; a newer one replaces it
+fn notice(rule: usize, title: String, message: String) -> Alert {
+    Alert::new(AlertLevel::Info, AlertCategory::Service, title, message, format!("auto-action-{}", rule + 1))
+}
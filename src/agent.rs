@@ -0,0 +1,187 @@
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+use tokio_rustls::rustls::crypto::{ring, CryptoProvider};
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+
+use crate::alerts::AlertManager;
+use crate::app::{system_alert_config, timed};
+use crate::collectors::{QemuCollector, SystemCollector};
+use crate::config::{AgentConfig, Config};
+use crate::http::{read_head, respond};
+use crate::types::{AgentSnapshot, AgentVm, K8sClusterInfo, KubeVirtInfo, AGENT_PROTOCOL_VERSION};
+
+/// How long a client gets for the handshake and its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Run as an agent (`--agent`): collect this host's metrics, qemu
+/// processes and host alerts every `agent.interval_secs` and serve the
+/// latest collection as JSON at `GET /v1/snapshot` to clients presenting a
+/// certificate signed by `agent.client_ca_file`. Runs until killed.
+pub async fn run(listen: Option<&str>) -> Result<()> {
+    let (config, _) = Config::load();
+    let listen = listen.unwrap_or(&config.agent.listen).to_string();
+    let acceptor = TlsAcceptor::from(Arc::new(server_config(&config.agent)?));
+    let listener = TcpListener::bind(&listen)
+        .await
+        .with_context(|| format!("Failed to listen on {}", listen))?;
+    tracing::info!("Agent listening on https://{}", listen);
+
+    let limit = Duration::from_secs(config.general.collector_timeout_secs.max(1));
+    let mut system_collector = SystemCollector::new()?.with_filesystems(config.storage.filesystems.clone());
+    let mut qemu_collector = QemuCollector::new()?;
+    let mut alert_manager = AlertManager::new()
+        .with_system_config(system_alert_config(&config.alerts))
+        .with_kubernetes_enabled(false)
+        .with_kubevirt_enabled(false);
+    let host = sysinfo::System::host_name().unwrap_or_else(|| "unknown".to_string());
+
+    let (tx, snapshot) = watch::channel(None::<Arc<String>>);
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    let acceptor = acceptor.clone();
+                    let snapshot = snapshot.borrow().clone();
+                    tokio::spawn(async move {
+                        let handled = tokio::time::timeout(REQUEST_TIMEOUT, async {
+                            let stream = acceptor.accept(stream).await.context("TLS handshake failed")?;
+                            handle(stream, snapshot.as_deref().map(String::as_str)).await
+                        });
+                        match handled.await {
+                            Ok(Ok(())) => {}
+                            Ok(Err(e)) => tracing::debug!("Agent request from {} failed: {:#}", peer, e),
+                            Err(_) => tracing::debug!("Agent request from {} timed out", peer),
+                        }
+                    });
+                }
+                Err(e) => tracing::warn!("Agent accept failed: {}", e),
+            }
+        }
+    });
+
+    // CPU usage is the difference between two refreshes
+    tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+    let mut ticks = tokio::time::interval(Duration::from_secs(config.agent.interval_secs.max(1)));
+    loop {
+        ticks.tick().await;
+        let (system, processes) = tokio::join!(
+            timed(limit, system_collector.collect()),
+            timed(limit, qemu_collector.collect()),
+        );
+        let system = match system.1 {
+            Ok(system) => system,
+            Err(e) => {
+                tracing::warn!("Agent collection failed: {:#}", e);
+                continue;
+            }
+        };
+        let vms = processes
+            .1
+            .unwrap_or_default()
+            .into_iter()
+            .map(|process| AgentVm {
+                pid: process.pid,
+                name: process.vm.unwrap_or(process.domain),
+                rss_bytes: process.rss_bytes,
+                threads: process.threads,
+                stopped: process.stopped,
+            })
+            .collect();
+        alert_manager.evaluate(&system, &K8sClusterInfo::default(), &KubeVirtInfo::default());
+
+        let collected = AgentSnapshot {
+            version: AGENT_PROTOCOL_VERSION,
+            host: host.clone(),
+            collected_at: chrono::Local::now(),
+            cpu_percent: system.cpu_usage,
+            memory_used_gb: system.memory_used_gb,
+            memory_total_gb: system.memory_total_gb,
+            disk_percent: system.disk_usage_percent,
+            load_avg: system.load_avg,
+            uptime_seconds: system.uptime_seconds,
+            vms,
+            alerts: alert_manager.get_active_alerts().into_iter().cloned().collect(),
+        };
+        match serde_json::to_string(&collected) {
+            Ok(json) => {
+                let _ = tx.send(Some(Arc::new(json)));
+            }
+            Err(e) => tracing::warn!("Failed to encode agent snapshot: {}", e),
+        }
+    }
+}
+
+/// Server side of the mutual TLS: the agent's own certificate, and only
+/// clients whose certificate chains to `client_ca_file`
+fn server_config(config: &AgentConfig) -> Result<ServerConfig> {
+    let (Some(cert_file), Some(key_file), Some(client_ca_file)) =
+        (&config.cert_file, &config.key_file, &config.client_ca_file)
+    else {
+        bail!("agent.cert_file, agent.key_file and agent.client_ca_file are all required to run as an agent");
+    };
+    let provider = tls_provider();
+    let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(load_roots(client_ca_file)?), provider.clone())
+        .build()
+        .context("Failed to set up client certificate verification")?;
+    ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(load_certs(cert_file)?, load_key(key_file)?)
+        .context("Agent certificate and key don't match")
+}
+
+async fn handle<S: AsyncRead + AsyncWrite + Unpin>(mut stream: S, snapshot: Option<&str>) -> Result<()> {
+    // Agents take no request bodies
+    let (head, _) = read_head(&mut stream).await?;
+    let mut request = head.lines().next().unwrap_or_default().split_whitespace();
+    let (method, path) = (request.next().unwrap_or_default(), request.next().unwrap_or_default());
+
+    if method != "GET" {
+        return respond(&mut stream, "405 Method Not Allowed", "text/plain", "GET only\n").await;
+    }
+    match (path, snapshot) {
+        ("/v1/health", _) => respond(&mut stream, "200 OK", "text/plain", "ok\n").await,
+        ("/v1/snapshot", Some(snapshot)) => respond(&mut stream, "200 OK", "application/json", snapshot).await,
+        ("/v1/snapshot", None) => {
+            respond(&mut stream, "503 Service Unavailable", "text/plain", "first collection still running\n").await
+        }
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "not found\n").await,
+    }
+}
+
+/// Given explicitly so no process-wide default provider has to be installed
+pub fn tls_provider() -> Arc<CryptoProvider> {
+    Arc::new(ring::default_provider())
+}
+
+pub fn load_certs(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .with_context(|| format!("Failed to read certificates: {}", path))?
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Invalid PEM certificate in {}", path))?;
+    if certs.is_empty() {
+        bail!("No certificates in {}", path);
+    }
+    Ok(certs)
+}
+
+pub fn load_key(path: &str) -> Result<PrivateKeyDer<'static>> {
+    PrivateKeyDer::from_pem_file(Path::new(path)).with_context(|| format!("Failed to read private key: {}", path))
+}
+
+pub fn load_roots(path: &str) -> Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(path)? {
+        roots.add(cert).with_context(|| format!("Unusable CA certificate in {}", path))?;
+    }
+    Ok(roots)
+}
@@ -0,0 +1,131 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{AlertsConfig, Config};
+use crate::validation::alert_threshold_problems;
+
+/// Keys that say which profile to use rather than being part of one
+const SELECTION_KEYS: [&str; 2] = ["profile", "profiles_dir"];
+
+/// Named sets of [alerts] settings ("lab", "production", "maintenance"),
+/// one TOML file each, laid over the config's [alerts] table when active.
+/// A profile only needs the keys it changes; exported ones carry them all.
+pub struct AlertProfiles {
+    dir: PathBuf,
+}
+
+impl AlertProfiles {
+    /// Profiles in `alerts.profiles_dir`, or alert-profiles/ next to the
+    /// user config
+    pub fn for_config(config: &Config) -> Self {
+        let dir = match config.alerts.profiles_dir {
+            Some(ref dir) => PathBuf::from(dir),
+            None => Config::user_config_path()
+                .parent()
+                .map(|parent| parent.join("alert-profiles"))
+                .unwrap_or_else(|| PathBuf::from("alert-profiles")),
+        };
+        Self { dir }
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.toml", name))
+    }
+
+    /// Names of the stored profiles, sorted
+    pub fn names(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|path| path.file_stem()?.to_str().map(String::from))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// `base` with the named profile laid over it; no name gives `base`
+    pub fn resolve(&self, base: &AlertsConfig, name: Option<&str>) -> Result<AlertsConfig> {
+        let Some(name) = name else {
+            return Ok(base.clone());
+        };
+        let path = self.path(name);
+        let contents = fs::read_to_string(&path).with_context(|| format!("No alert profile {:?}", path))?;
+        overlay(base, &contents).with_context(|| format!("Invalid alert profile {:?}", path))
+    }
+
+    /// Write every alert setting of `alerts` as profile `name`
+    pub fn export(&self, name: &str, alerts: &AlertsConfig) -> Result<PathBuf> {
+        if let Some(problem) = profile_name_problem(name) {
+            bail!("{}", problem);
+        }
+        let mut alerts = alerts.clone();
+        alerts.profile = None;
+        alerts.profiles_dir = None;
+        let contents = toml::to_string_pretty(&alerts).context("Failed to serialize alert settings")?;
+
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create profile directory: {:?}", self.dir))?;
+        let path = self.path(name);
+        fs::write(&path, format!("# hypervisor-tui alert profile \"{}\"; [alerts] keys\n{}", name, contents))
+            .with_context(|| format!("Failed to write alert profile: {:?}", path))?;
+        Ok(path)
+    }
+
+    /// Copy a shared profile file in under its file name, after checking it
+    /// parses and its thresholds make sense. Returns the profile's name.
+    pub fn import(&self, source: &Path) -> Result<String> {
+        let name = source
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .context("Profile file has no name")?
+            .to_string();
+        if let Some(problem) = profile_name_problem(&name) {
+            bail!("{}", problem);
+        }
+        let contents = fs::read_to_string(source).with_context(|| format!("Failed to read {:?}", source))?;
+        overlay(&AlertsConfig::default(), &contents).with_context(|| format!("Invalid alert profile {:?}", source))?;
+
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create profile directory: {:?}", self.dir))?;
+        let path = self.path(&name);
+        fs::write(&path, contents).with_context(|| format!("Failed to write alert profile: {:?}", path))?;
+        Ok(name)
+    }
+}
+
+/// Profile names become file names
+pub fn profile_name_problem(name: &str) -> Option<String> {
+    if name.is_empty() {
+        return Some("profile name is empty".to_string());
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Some(format!("profile name {:?} may only contain letters, digits, '-' and '_'", name));
+    }
+    None
+}
+
+/// Replace the top-level keys of `base` that the profile sets
+fn overlay(base: &AlertsConfig, contents: &str) -> Result<AlertsConfig> {
+    let mut profile: toml::Table = toml::from_str(contents)?;
+    for key in SELECTION_KEYS {
+        profile.remove(key);
+    }
+
+    let toml::Value::Table(mut merged) = toml::Value::try_from(base)? else {
+        bail!("alert settings did not serialize to a table");
+    };
+    merged.extend(profile);
+    let alerts: AlertsConfig = toml::Value::Table(merged).try_into()?;
+
+    let problems = alert_threshold_problems(&alerts);
+    if !problems.is_empty() {
+        let problems: Vec<String> = problems.iter().map(|(key, message)| format!("{}: {}", key, message)).collect();
+        bail!("{}", problems.join("; "));
+    }
+    Ok(alerts)
+}
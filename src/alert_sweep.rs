@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+/// Collectors that feed alert rules, in the groups the screens collect
+/// them in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepGroup {
+    /// Host, cluster and KubeVirt health: the Dashboard's collectors
+    Health,
+    /// Interfaces, MTU, DNS, routes, Services, SR-IOV and image pulls
+    Network,
+    /// Fleet VMs, migrations, tenants and remote agents
+    Fleet,
+}
+
+impl SweepGroup {
+    const ALL: [SweepGroup; 3] = [SweepGroup::Health, SweepGroup::Network, SweepGroup::Fleet];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SweepGroup::Health => "health",
+            SweepGroup::Network => "network",
+            SweepGroup::Fleet => "fleet",
+        }
+    }
+}
+
+/// Keeps alerts independent of the screen on display: each group is
+/// collected every `alerts.background_interval_secs`, by its own screen
+/// when that is showing and in the background otherwise. A tick takes on
+/// at most one overdue group on top of its screen's collectors, the most
+/// overdue, and intervals count from when a run finished, so collectors
+/// slower than the interval stretch it instead of piling up.
+pub struct AlertSweep {
+    /// Zero leaves alerts to the screen on display
+    interval: Duration,
+    last: [Option<Instant>; 3],
+}
+
+impl AlertSweep {
+    pub fn new(interval_secs: u64) -> Self {
+        Self { interval: Duration::from_secs(interval_secs), last: [None; 3] }
+    }
+
+    /// `group` was just collected, by its screen or the sweep
+    pub fn record(&mut self, group: SweepGroup) {
+        self.last[group as usize] = Some(Instant::now());
+    }
+
+    /// The group most overdue for collection, if any is
+    pub fn next_due(&self) -> Option<SweepGroup> {
+        if self.interval.is_zero() {
+            return None;
+        }
+        SweepGroup::ALL
+            .into_iter()
+            .filter(|&group| self.last[group as usize].is_none_or(|at| at.elapsed() >= self.interval))
+            // Never collected counts as the most overdue
+            .min_by_key(|&group| self.last[group as usize])
+    }
+}
@@ -0,0 +1,134 @@
+use anyhow::{Context, Result};
+use chrono::{Local, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const HOURS_PER_DAY: usize = 24;
+
+/// Exponentially weighted mean/variance for one metric in one hour-of-day bucket
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct BaselineStats {
+    pub count: u64,
+    pub mean: f64,
+    pub variance: f64,
+}
+
+impl BaselineStats {
+    /// Incorporate a sample. Once `window` samples have been seen the
+    /// statistics become a rolling (exponentially weighted) estimate.
+    fn update(&mut self, value: f64, window: u64) {
+        self.count = (self.count + 1).min(window.max(1));
+        let alpha = 1.0 / self.count as f64;
+        let diff = value - self.mean;
+        let incr = alpha * diff;
+        self.mean += incr;
+        self.variance = (1.0 - alpha) * (self.variance + diff * incr);
+    }
+
+    pub fn stddev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyConfig {
+    pub enabled: bool,
+    /// Number of standard deviations from the baseline that counts as anomalous
+    pub sigma: f64,
+    /// Samples a bucket needs before it is trusted for alerting
+    pub min_samples: u64,
+    /// Effective window of the rolling statistics, in samples
+    pub window: u64,
+    /// Where learned baselines are persisted between runs
+    pub path: PathBuf,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sigma: 3.0,
+            min_samples: 60,
+            window: 10_000,
+            path: PathBuf::from("baselines.json"),
+        }
+    }
+}
+
+/// A metric value that deviates significantly from its learned baseline
+#[derive(Debug, Clone)]
+pub struct Anomaly {
+    pub value: f64,
+    pub baseline: BaselineStats,
+    pub z_score: f64,
+}
+
+/// Learns per-metric, per-hour-of-day baselines
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BaselineTracker {
+    metrics: HashMap<String, Vec<BaselineStats>>,
+
+    #[serde(skip)]
+    updates_since_save: u32,
+}
+
+impl BaselineTracker {
+    /// Load persisted baselines, starting fresh if none exist or the file is unreadable
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring corrupt baseline file {:?}: {}", path, e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create baseline directory: {:?}", parent))?;
+        }
+
+        let contents = serde_json::to_string(self).context("Failed to serialize baselines")?;
+        fs::write(path, contents)
+            .with_context(|| format!("Failed to write baseline file: {:?}", path))?;
+
+        self.updates_since_save = 0;
+        Ok(())
+    }
+
+    /// Check a sample against the current hour's baseline, then learn from it.
+    /// Returns an anomaly if the sample deviated by more than `sigma` deviations.
+    pub fn observe(&mut self, metric: &str, value: f64, config: &AnomalyConfig) -> Option<Anomaly> {
+        let hour = Local::now().hour() as usize;
+        let buckets = self
+            .metrics
+            .entry(metric.to_string())
+            .or_insert_with(|| vec![BaselineStats::default(); HOURS_PER_DAY]);
+        let stats = &mut buckets[hour];
+
+        let anomaly = if stats.count >= config.min_samples && stats.stddev() > f64::EPSILON {
+            let z_score = (value - stats.mean) / stats.stddev();
+            (z_score.abs() >= config.sigma).then_some(Anomaly {
+                value,
+                baseline: *stats,
+                z_score,
+            })
+        } else {
+            None
+        };
+
+        stats.update(value, config.window);
+        self.updates_since_save += 1;
+
+        anomaly
+    }
+
+    /// Whether enough updates have accumulated to be worth persisting
+    pub fn needs_save(&self) -> bool {
+        self.updates_since_save >= 100
+    }
+}
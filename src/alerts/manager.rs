@@ -1,24 +1,57 @@
-use super::types::{Alert, AlertLevel, AlertStatus};
+use super::types::{Alert, AlertLevel, AlertStatus, AlertCategory};
 use super::rules::{AlertRule, SystemMetricsRule, KubernetesRule, KubeVirtRule, SystemAlert};
+use super::baseline::{AnomalyConfig, BaselineTracker};
+use super::stats::{alert_statistics, AlertStatistics};
+use crate::config::AlertRunbook;
 use crate::types::{SystemMetrics, K8sClusterInfo, KubeVirtInfo};
-use std::collections::HashMap;
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
 use chrono::{Duration, Local};
 
+/// Source of the alert summarizing those held back by the rate limits
+pub const DIGEST_SOURCE: &str = "alert-digest";
+
 pub struct AlertManager {
     // Active alerts
     active_alerts: HashMap<String, Alert>,
 
     // Alert history (dismissed/resolved)
     history: Vec<Alert>,
+    // Where history is persisted between runs, and whether it has changed since
+    history_path: Option<PathBuf>,
+    history_dirty: bool,
 
     // Configuration
     system_alerts_config: SystemAlert,
     kubernetes_enabled: bool,
     kubevirt_enabled: bool,
 
+    // Baseline learning / anomaly detection
+    anomaly_config: AnomalyConfig,
+    baselines: BaselineTracker,
+    anomalous_sources: HashSet<String>,
+
+    // Runbook links attached to alerts as they are raised
+    runbooks: Vec<AlertRunbook>,
+
+    // Sources each standalone rule fired on its last evaluation
+    rule_sources: HashMap<String, HashSet<String>>,
+
     // Alert deduplication tracking
     last_triggered: HashMap<String, chrono::DateTime<Local>>,
 
+    // Sources silenced from the alert panel, until when
+    snoozed: HashMap<String, chrono::DateTime<Local>>,
+
+    // New alerts raised in the last minute and the rule of each, for the
+    // rate limits; alerts held back by them are folded into one digest
+    recent: VecDeque<(chrono::DateTime<Local>, Option<String>)>,
+    suppressed: Vec<Alert>,
+    rate_limit: usize,
+    rule_rate_limit: usize,
+
     // Settings
     max_history_size: usize,
     dedup_window_seconds: i64,
@@ -29,11 +62,24 @@ impl AlertManager {
         Self {
             active_alerts: HashMap::new(),
             history: Vec::new(),
+            history_path: None,
+            history_dirty: false,
             system_alerts_config: SystemAlert::default(),
             kubernetes_enabled: true,
             kubevirt_enabled: true,
+            anomaly_config: AnomalyConfig::default(),
+            baselines: BaselineTracker::default(),
+            anomalous_sources: HashSet::new(),
+            runbooks: Vec::new(),
+            rule_sources: HashMap::new(),
             last_triggered: HashMap::new(),
-            max_history_size: 1000,
+            snoozed: HashMap::new(),
+            recent: VecDeque::new(),
+            suppressed: Vec::new(),
+            rate_limit: 0,
+            rule_rate_limit: 0,
+            // A week of a rule firing dozens of times a day, for the statistics
+            max_history_size: 5000,
             dedup_window_seconds: 300, // 5 minutes
         }
     }
@@ -53,6 +99,41 @@ impl AlertManager {
         self
     }
 
+    pub fn with_dedup_window(mut self, seconds: u64) -> Self {
+        self.dedup_window_seconds = seconds as i64;
+        self
+    }
+
+    /// Most new alerts a minute, overall and from any one rule; 0 for no
+    /// limit. The rest wait in the digest until there is room again.
+    pub fn with_rate_limit(mut self, per_minute: u32, per_rule_per_minute: u32) -> Self {
+        self.rate_limit = per_minute as usize;
+        self.rule_rate_limit = per_rule_per_minute as usize;
+        self
+    }
+
+    /// Persist resolved and dismissed alerts to `path`, starting from what
+    /// an earlier run left there
+    pub fn with_history_file(mut self, path: PathBuf) -> Self {
+        self.history = load_history(&path);
+        self.history_path = Some(path);
+        self.cleanup_history();
+        self
+    }
+
+    pub fn with_runbooks(mut self, runbooks: Vec<AlertRunbook>) -> Self {
+        self.runbooks = runbooks;
+        self
+    }
+
+    pub fn with_anomaly_detection(mut self, config: AnomalyConfig) -> Self {
+        if config.enabled {
+            self.baselines = BaselineTracker::load(&config.path);
+        }
+        self.anomaly_config = config;
+        self
+    }
+
     /// Evaluate all rules and generate alerts
     pub fn evaluate(
         &mut self,
@@ -68,7 +149,7 @@ impl AlertManager {
             metrics: system_metrics.clone(),
             config: self.system_alerts_config.clone(),
         };
-        new_alerts.extend(system_rule.evaluate());
+        new_alerts.extend(system_rule.evaluate().into_iter().map(|a| a.with_rule(system_rule.name())));
 
         // Kubernetes alerts
         if self.kubernetes_enabled {
@@ -76,7 +157,7 @@ impl AlertManager {
                 cluster_info: k8s_info.clone(),
                 enabled: true,
             };
-            new_alerts.extend(k8s_rule.evaluate());
+            new_alerts.extend(k8s_rule.evaluate().into_iter().map(|a| a.with_rule(k8s_rule.name())));
         }
 
         // KubeVirt alerts
@@ -85,7 +166,13 @@ impl AlertManager {
                 kubevirt_info: kubevirt_info.clone(),
                 enabled: true,
             };
-            new_alerts.extend(kubevirt_rule.evaluate());
+            new_alerts.extend(kubevirt_rule.evaluate().into_iter().map(|a| a.with_rule(kubevirt_rule.name())));
+        }
+
+        // Baseline deviation alerts
+        if self.anomaly_config.enabled {
+            let anomalies = self.evaluate_anomalies(system_metrics);
+            new_alerts.extend(anomalies.into_iter().map(|a| a.with_rule("anomaly")));
         }
 
         // Process new alerts with deduplication
@@ -95,30 +182,276 @@ impl AlertManager {
 
         // Auto-resolve alerts that are no longer triggering
         self.auto_resolve_alerts(system_metrics, k8s_info, kubevirt_info);
+        self.release_suppressed();
 
         // Clean up old history
         self.cleanup_history();
     }
 
-    fn add_alert_with_dedup(&mut self, alert: Alert) {
-        let dedup_key = format!("{}-{}", alert.category.as_str(), alert.metadata.source);
+    /// Evaluate a standalone rule that runs on its own data source.
+    /// Alerts the rule no longer produces are resolved automatically.
+    pub fn evaluate_rule(&mut self, rule: &dyn AlertRule) {
+        let alerts = rule.evaluate();
+        let firing: HashSet<String> = alerts.iter().map(|a| a.metadata.source.clone()).collect();
+
+        for alert in alerts {
+            self.add_alert_with_dedup(alert.with_rule(rule.name()));
+        }
+
+        let previous = self
+            .rule_sources
+            .insert(rule.name().to_string(), firing.clone())
+            .unwrap_or_default();
+        self.suppressed
+            .retain(|a| a.metadata.rule.as_deref() != Some(rule.name()) || firing.contains(&a.metadata.source));
+
+        let to_resolve: Vec<String> = self
+            .active_alerts
+            .iter()
+            .filter(|(_, alert)| {
+                previous.contains(&alert.metadata.source) && !firing.contains(&alert.metadata.source)
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in to_resolve {
+            if let Some(mut alert) = self.active_alerts.remove(&id) {
+                alert.resolve();
+                self.archive(alert);
+            }
+        }
+
+        self.release_suppressed();
+        self.cleanup_history();
+    }
+
+    /// Raise an alert produced outside the rule engine (e.g. by a
+    /// notification channel), subject to the usual deduplication
+    pub fn add_alert(&mut self, alert: Alert) {
+        self.add_alert_with_dedup(alert);
+        self.refresh_digest();
+    }
+
+    /// Compare current metrics against their learned hour-of-day baselines
+    fn evaluate_anomalies(&mut self, system_metrics: &SystemMetrics) -> Vec<Alert> {
+        let memory_percent = if system_metrics.memory_total_gb > 0.0 {
+            (system_metrics.memory_used_gb / system_metrics.memory_total_gb) * 100.0
+        } else {
+            0.0
+        };
+
+        let samples = [
+            ("cpu", "CPU Usage", system_metrics.cpu_usage),
+            ("memory", "Memory Usage", memory_percent),
+            ("load", "Load Average", system_metrics.load_avg),
+        ];
+
+        self.anomalous_sources.clear();
+        let mut alerts = Vec::new();
+
+        for (metric, label, value) in samples {
+            let Some(anomaly) = self.baselines.observe(metric, value, &self.anomaly_config) else {
+                continue;
+            };
+
+            let source = format!("{}-anomaly", metric);
+            let direction = if anomaly.z_score > 0.0 { "above" } else { "below" };
+            let bound = anomaly.baseline.mean
+                + anomaly.z_score.signum() * self.anomaly_config.sigma * anomaly.baseline.stddev();
+
+            alerts.push(
+                Alert::new(
+                    AlertLevel::Warning,
+                    AlertCategory::System,
+                    format!("Anomalous {}", label),
+                    format!(
+                        "{} of {:.1} is {:.1} standard deviations {} the usual {:.1} (stddev {:.1}) for this hour",
+                        label,
+                        anomaly.value,
+                        anomaly.z_score.abs(),
+                        direction,
+                        anomaly.baseline.mean,
+                        anomaly.baseline.stddev()
+                    ),
+                    source.clone(),
+                )
+                .with_value(anomaly.value, bound),
+            );
+            self.anomalous_sources.insert(source);
+        }
+
+        if self.baselines.needs_save() {
+            if let Err(e) = self.baselines.save(&self.anomaly_config.path) {
+                tracing::warn!("Failed to persist alert baselines: {}", e);
+            }
+        }
+
+        alerts
+    }
+
+    /// Identity of an alert for deduplication: the same source can be
+    /// tracked independently at each severity
+    pub fn dedup_key(alert: &Alert) -> String {
+        format!("{}-{}", alert.metadata.source, alert.level.as_str())
+    }
+
+    fn add_alert_with_dedup(&mut self, mut alert: Alert) {
+        let now = Local::now();
+        if let Some(runbook) = self
+            .runbooks
+            .iter()
+            .find(|r| r.matches(&alert.metadata.source, &alert.title))
+        {
+            alert.metadata.runbook_url = runbook.url.clone();
+            alert.metadata.remediation = runbook.remediation.clone();
+        }
+        let dedup_key = Self::dedup_key(&alert);
+
+        if self.snoozed_until(&alert.metadata.source).is_some() {
+            return;
+        }
+
+        // The same condition still firing refreshes the existing alert in place
+        let existing = self
+            .active_alerts
+            .values_mut()
+            .find(|a| a.metadata.source == alert.metadata.source && a.level == alert.level);
+        if let Some(existing) = existing {
+            existing.message = alert.message;
+            existing.metadata.value = alert.metadata.value;
+            existing.metadata.threshold = alert.metadata.threshold;
+            self.last_triggered.insert(dedup_key, now);
+            return;
+        }
+
+        // A more severe alert for this source is already active
+        let outranked = self.active_alerts.values().any(|a| {
+            a.metadata.source == alert.metadata.source && (a.level as u8) > (alert.level as u8)
+        });
+        if outranked {
+            return;
+        }
 
-        // Check if we've seen this alert recently (deduplication)
+        // Skip alerts recently dismissed or resolved at this severity. Upgrades
+        // are never held back by the window, since the key includes the level.
         if let Some(last_time) = self.last_triggered.get(&dedup_key) {
-            let elapsed = (Local::now() - *last_time).num_seconds();
-            if elapsed < self.dedup_window_seconds {
-                // Skip duplicate alert within dedup window
+            if (now - *last_time).num_seconds() < self.dedup_window_seconds {
                 return;
             }
         }
 
-        // Update last triggered time
-        self.last_triggered.insert(dedup_key, Local::now());
+        // Past the rate limit the alert waits in the digest, newest details kept
+        self.suppressed.retain(|a| Self::dedup_key(a) != dedup_key);
+        if self.rate_limited(&alert, now) {
+            self.suppressed.push(alert);
+            return;
+        }
+        self.recent.push_back((now, alert.metadata.rule.clone()));
+        self.last_triggered.insert(dedup_key, now);
+
+        // Severity upgrade: the new alert replaces the less severe one
+        let replaced: Vec<String> = self
+            .active_alerts
+            .iter()
+            .filter(|(_, a)| a.metadata.source == alert.metadata.source)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in replaced {
+            if let Some(mut old) = self.active_alerts.remove(&id) {
+                old.resolve();
+                self.archive(old);
+            }
+        }
 
-        // Add or update alert
         self.active_alerts.insert(alert.id.clone(), alert);
     }
 
+    /// Whether raising `alert` now would exceed the global or its rule's
+    /// limit of new alerts over the last minute
+    fn rate_limited(&mut self, alert: &Alert, now: chrono::DateTime<Local>) -> bool {
+        let minute_ago = now - Duration::minutes(1);
+        while self.recent.front().is_some_and(|(at, _)| *at <= minute_ago) {
+            self.recent.pop_front();
+        }
+        if self.rate_limit > 0 && self.recent.len() >= self.rate_limit {
+            return true;
+        }
+        let Some(ref rule) = alert.metadata.rule else {
+            return false;
+        };
+        self.rule_rate_limit > 0
+            && self.recent.iter().filter(|(_, r)| r.as_ref() == Some(rule)).count() >= self.rule_rate_limit
+    }
+
+    /// Raise held-back alerts as the limits allow, most severe first, and
+    /// bring the digest up to date with the rest
+    fn release_suppressed(&mut self) {
+        let mut pending = std::mem::take(&mut self.suppressed);
+        pending.sort_by(|a, b| (b.level as u8).cmp(&(a.level as u8)).then(a.triggered_at.cmp(&b.triggered_at)));
+        for alert in pending {
+            self.add_alert_with_dedup(alert);
+        }
+        self.refresh_digest();
+    }
+
+    /// One alert standing for everything the rate limits hold back, at the
+    /// level of the most severe of them; resolved once none are left
+    fn refresh_digest(&mut self) {
+        let digest_id = self
+            .active_alerts
+            .values()
+            .find(|a| a.metadata.source == DIGEST_SOURCE)
+            .map(|a| a.id.clone());
+        let Some(level) = self.suppressed.iter().map(|a| a.level).max_by_key(|level| *level as u8) else {
+            if let Some(mut digest) = digest_id.and_then(|id| self.active_alerts.remove(&id)) {
+                digest.resolve();
+                self.archive(digest);
+            }
+            return;
+        };
+        if self.snoozed_until(DIGEST_SOURCE).is_some() {
+            return;
+        }
+
+        let mut by_rule: Vec<(&str, usize)> = Vec::new();
+        for alert in &self.suppressed {
+            let rule = alert.metadata.rule.as_deref().unwrap_or("other");
+            match by_rule.iter_mut().find(|(r, _)| *r == rule) {
+                Some((_, count)) => *count += 1,
+                None => by_rule.push((rule, 1)),
+            }
+        }
+        let title = format!("{} additional alerts suppressed", self.suppressed.len());
+        let message = format!(
+            "More new alerts than the rate limit allows ({}); held back until there is room: {}",
+            self.describe_limits(),
+            by_rule.iter().map(|(rule, count)| format!("{} {}", rule, count)).collect::<Vec<_>>().join(", ")
+        );
+        match digest_id.and_then(|id| self.active_alerts.get_mut(&id)) {
+            Some(digest) => {
+                digest.level = level;
+                digest.title = title;
+                digest.message = message;
+            }
+            None => {
+                let digest = Alert::new(level, AlertCategory::System, title, message, DIGEST_SOURCE.to_string());
+                self.active_alerts.insert(digest.id.clone(), digest);
+            }
+        }
+    }
+
+    /// "20/min overall, 5/min per rule"
+    fn describe_limits(&self) -> String {
+        let mut limits = Vec::new();
+        if self.rate_limit > 0 {
+            limits.push(format!("{}/min overall", self.rate_limit));
+        }
+        if self.rule_rate_limit > 0 {
+            limits.push(format!("{}/min per rule", self.rule_rate_limit));
+        }
+        limits.join(", ")
+    }
+
     fn auto_resolve_alerts(
         &mut self,
         system_metrics: &SystemMetrics,
@@ -170,6 +503,10 @@ impl AlertManager {
                     // Resolve if cluster is reachable
                     k8s_info.nodes_total > 0 || k8s_info.pods_running > 0
                 }
+                source if source.ends_with("-anomaly") => {
+                    // Resolve once the metric is back within its baseline band
+                    !self.anomalous_sources.contains(source)
+                }
                 _ => false,
             };
 
@@ -182,11 +519,16 @@ impl AlertManager {
         for id in to_resolve {
             if let Some(mut alert) = self.active_alerts.remove(&id) {
                 alert.resolve();
-                self.history.push(alert);
+                self.archive(alert);
             }
         }
     }
 
+    fn archive(&mut self, alert: Alert) {
+        self.history.push(alert);
+        self.history_dirty = true;
+    }
+
     fn cleanup_history(&mut self) {
         // Keep only the most recent alerts in history
         if self.history.len() > self.max_history_size {
@@ -197,6 +539,13 @@ impl AlertManager {
         // Remove very old alerts (older than 7 days)
         let cutoff = Local::now() - Duration::days(7);
         self.history.retain(|alert| alert.triggered_at > cutoff);
+
+        if let (true, Some(path)) = (self.history_dirty, self.history_path.as_ref()) {
+            match save_history(path, &self.history) {
+                Ok(()) => self.history_dirty = false,
+                Err(e) => tracing::warn!("Failed to persist alert history: {:#}", e),
+            }
+        }
     }
 
     /// Get all active alerts
@@ -244,11 +593,24 @@ impl AlertManager {
         self.active_alerts.len()
     }
 
+    /// Alerts the rate limits are holding back, most severe first
+    pub fn suppressed_alerts(&self) -> Vec<&Alert> {
+        let mut alerts: Vec<&Alert> = self.suppressed.iter().collect();
+        alerts.sort_by(|a, b| (b.level as u8).cmp(&(a.level as u8)).then(a.triggered_at.cmp(&b.triggered_at)));
+        alerts
+    }
+
     /// Get alert history
     pub fn get_history(&self) -> &[Alert] {
         &self.history
     }
 
+    /// Per-source firing counts, resolve times and flappiness over the
+    /// persisted history plus what is active now
+    pub fn statistics(&self) -> Vec<AlertStatistics> {
+        alert_statistics(self.history.iter().chain(self.active_alerts.values()), Local::now())
+    }
+
     /// Acknowledge an alert
     pub fn acknowledge_alert(&mut self, id: &str) {
         if let Some(alert) = self.active_alerts.get_mut(id) {
@@ -256,19 +618,50 @@ impl AlertManager {
         }
     }
 
-    /// Dismiss an alert
+    /// Dismiss an alert; dismissing the digest drops what it held back
     pub fn dismiss_alert(&mut self, id: &str) {
         if let Some(mut alert) = self.active_alerts.remove(id) {
+            if alert.metadata.source == DIGEST_SOURCE {
+                self.drop_suppressed();
+            }
+            alert.dismiss();
+            self.archive(alert);
+        }
+    }
+
+    /// Dismiss an alert and keep its source quiet, at any severity, for `minutes`
+    pub fn snooze_alert(&mut self, id: &str, minutes: u64) {
+        if let Some(mut alert) = self.active_alerts.remove(id) {
+            let until = Local::now() + Duration::minutes(minutes as i64);
+            self.snoozed.insert(alert.metadata.source.clone(), until);
             alert.dismiss();
-            self.history.push(alert);
+            self.archive(alert);
+        }
+    }
+
+    /// When a snoozed source may alert again, if it is still snoozed
+    pub fn snoozed_until(&self, source: &str) -> Option<chrono::DateTime<Local>> {
+        self.snoozed.get(source).copied().filter(|until| *until > Local::now())
+    }
+
+    /// Held-back alerts are dropped as if dismissed: recorded as just
+    /// raised, so deduplication keeps the conditions still firing from
+    /// being held back again, and the digest from returning, for the
+    /// dedup window
+    fn drop_suppressed(&mut self) {
+        let now = Local::now();
+        for alert in std::mem::take(&mut self.suppressed) {
+            self.last_triggered.insert(Self::dedup_key(&alert), now);
         }
     }
 
     /// Dismiss all alerts
     pub fn dismiss_all(&mut self) {
-        for (_, mut alert) in self.active_alerts.drain() {
+        self.drop_suppressed();
+        let alerts: Vec<Alert> = self.active_alerts.drain().map(|(_, alert)| alert).collect();
+        for mut alert in alerts {
             alert.dismiss();
-            self.history.push(alert);
+            self.archive(alert);
         }
     }
 
@@ -290,6 +683,26 @@ impl AlertManager {
     }
 }
 
+/// Alerts an earlier run archived, or none if the file is missing or unreadable
+fn load_history(path: &Path) -> Vec<Alert> {
+    match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring corrupt alert history {:?}: {}", path, e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn save_history(path: &Path, history: &[Alert]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create alert history directory: {:?}", parent))?;
+    }
+    let contents = serde_json::to_string(history).context("Failed to serialize alert history")?;
+    fs::write(path, contents).with_context(|| format!("Failed to write alert history: {:?}", path))
+}
+
 impl Default for AlertManager {
     fn default() -> Self {
         Self::new()
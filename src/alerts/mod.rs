@@ -1,7 +1,11 @@
 mod types;
 mod rules;
 mod manager;
+mod baseline;
+mod stats;
 
-pub use types::{Alert, AlertLevel};
-pub use rules::SystemAlert;
-pub use manager::AlertManager;
+pub use types::{Alert, AlertCategory, AlertLevel, AlertStatus};
+pub use rules::{AgentRule, ApiHealthRule, BridgeConflictRule, ComputedMetricRule, CrashRule, DataVolumeRule, DiskForecastRule, DiskLatencyRule, DnsRule, EtcdRule, ExternalAlertRule, FirmwareRule, FreqThrottleRule, GuestClockRule, GuestFilesystemRule, HostProfileRule, ImagePullRule, IoSchedulerRule, KernelRule, KubeletRule, LimitsRule, MemoryLeakRule, MigrationRule, MtuRule, MultipathRule, NodeConditionRule, OomRule, PodRule, QuotaRule, RouteRule, SchedLatencyRule, ServiceEndpointRule, SlowBootRule, SocketRule, SriovRule, SysctlRule, SystemAlert, TenantRule, UnitRule, UpgradeRule, VipRule};
+pub use manager::{AlertManager, DIGEST_SOURCE};
+pub use baseline::AnomalyConfig;
+pub use stats::AlertStatistics;
@@ -1,6 +1,12 @@
 use super::types::{Alert, AlertLevel, AlertCategory};
-use crate::types::{SystemMetrics, K8sClusterInfo, KubeVirtInfo};
+use crate::metrics_history::{MemoryGrowth, TimeToFull};
+use crate::migrations::NodeMigrationFailures;
+use crate::types::{AgentStatus, ApiHealth, CoreDump, SlowBoot, ExternalAlert, DataVolume, DiskLatency, EtcdStatus, HardwareInfo, HardwareErrorKind, HardwareEvent, InterfaceLabels, IpConflict, KubeletStatus, LeaseChurn, OomKill, SocketUsage, FilesystemUsage, HostLimits, ImagePull, PodHealth, PowerInfo, ProcessMemory, ProfileDeviation, RegistryStatus, RouteStatus, SystemMetrics, K8sClusterInfo, KubeVirtInfo, DnsResolverStatus, MtuMismatch, MultipathInfo, GuestClock, GuestFilesystem, NodeReachability, NodeStatus, QemuProcess, QuotaUsage, ServiceEndpoints, SriovNic, Sysctl, TenantUsage, VipStatus};
+use crate::computed::ComputedMetric;
+use crate::config::TenantLimits;
+use crate::i18n::{t, tf};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThresholdRule {
@@ -101,11 +107,8 @@ impl AlertRule for SystemMetricsRule {
                     Alert::new(
                         AlertLevel::Critical,
                         AlertCategory::System,
-                        "Critical CPU Usage".to_string(),
-                        format!(
-                            "CPU usage is critically high at {:.1}%",
-                            self.metrics.cpu_usage
-                        ),
+                        t("alert.cpu.critical.title").to_string(),
+                        tf("alert.cpu.critical.message", &[&format!("{:.1}", self.metrics.cpu_usage)]),
                         "cpu".to_string(),
                     )
                     .with_value(self.metrics.cpu_usage, self.config.cpu_critical_threshold),
@@ -115,8 +118,8 @@ impl AlertRule for SystemMetricsRule {
                     Alert::new(
                         AlertLevel::Warning,
                         AlertCategory::System,
-                        "High CPU Usage".to_string(),
-                        format!("CPU usage is high at {:.1}%", self.metrics.cpu_usage),
+                        t("alert.cpu.high.title").to_string(),
+                        tf("alert.cpu.high.message", &[&format!("{:.1}", self.metrics.cpu_usage)]),
                         "cpu".to_string(),
                     )
                     .with_value(self.metrics.cpu_usage, self.config.cpu_warning_threshold),
@@ -131,19 +134,19 @@ impl AlertRule for SystemMetricsRule {
             } else {
                 0.0
             };
+            let memory_args: [&dyn std::fmt::Display; 3] = [
+                &format!("{:.1}", memory_percent),
+                &format!("{:.1}", self.metrics.memory_used_gb),
+                &format!("{:.1}", self.metrics.memory_total_gb),
+            ];
 
             if memory_percent >= self.config.memory_critical_threshold {
                 alerts.push(
                     Alert::new(
                         AlertLevel::Critical,
                         AlertCategory::System,
-                        "Critical Memory Usage".to_string(),
-                        format!(
-                            "Memory usage is critically high at {:.1}% ({:.1}/{:.1} GB)",
-                            memory_percent,
-                            self.metrics.memory_used_gb,
-                            self.metrics.memory_total_gb
-                        ),
+                        t("alert.memory.critical.title").to_string(),
+                        tf("alert.memory.critical.message", &memory_args),
                         "memory".to_string(),
                     )
                     .with_value(memory_percent, self.config.memory_critical_threshold),
@@ -153,13 +156,8 @@ impl AlertRule for SystemMetricsRule {
                     Alert::new(
                         AlertLevel::Warning,
                         AlertCategory::System,
-                        "High Memory Usage".to_string(),
-                        format!(
-                            "Memory usage is high at {:.1}% ({:.1}/{:.1} GB)",
-                            memory_percent,
-                            self.metrics.memory_used_gb,
-                            self.metrics.memory_total_gb
-                        ),
+                        t("alert.memory.high.title").to_string(),
+                        tf("alert.memory.high.message", &memory_args),
                         "memory".to_string(),
                     )
                     .with_value(memory_percent, self.config.memory_warning_threshold),
@@ -174,11 +172,8 @@ impl AlertRule for SystemMetricsRule {
                     Alert::new(
                         AlertLevel::Critical,
                         AlertCategory::System,
-                        "Critical Disk Usage".to_string(),
-                        format!(
-                            "Disk usage is critically high at {:.1}%",
-                            self.metrics.disk_usage_percent
-                        ),
+                        t("alert.disk.critical.title").to_string(),
+                        tf("alert.disk.critical.message", &[&format!("{:.1}", self.metrics.disk_usage_percent)]),
                         "disk".to_string(),
                     )
                     .with_value(
@@ -191,11 +186,8 @@ impl AlertRule for SystemMetricsRule {
                     Alert::new(
                         AlertLevel::Warning,
                         AlertCategory::System,
-                        "High Disk Usage".to_string(),
-                        format!(
-                            "Disk usage is high at {:.1}%",
-                            self.metrics.disk_usage_percent
-                        ),
+                        t("alert.disk.high.title").to_string(),
+                        tf("alert.disk.high.message", &[&format!("{:.1}", self.metrics.disk_usage_percent)]),
                         "disk".to_string(),
                     )
                     .with_value(
@@ -213,8 +205,8 @@ impl AlertRule for SystemMetricsRule {
                     Alert::new(
                         AlertLevel::Critical,
                         AlertCategory::System,
-                        "Critical Load Average".to_string(),
-                        format!("Load average is critically high at {:.2}", self.metrics.load_avg),
+                        t("alert.load.critical.title").to_string(),
+                        tf("alert.load.critical.message", &[&format!("{:.2}", self.metrics.load_avg)]),
                         "load".to_string(),
                     )
                     .with_value(self.metrics.load_avg, self.config.load_critical_threshold),
@@ -224,8 +216,8 @@ impl AlertRule for SystemMetricsRule {
                     Alert::new(
                         AlertLevel::Warning,
                         AlertCategory::System,
-                        "High Load Average".to_string(),
-                        format!("Load average is high at {:.2}", self.metrics.load_avg),
+                        t("alert.load.high.title").to_string(),
+                        tf("alert.load.high.message", &[&format!("{:.2}", self.metrics.load_avg)]),
                         "load".to_string(),
                     )
                     .with_value(self.metrics.load_avg, self.config.load_warning_threshold),
@@ -269,11 +261,8 @@ impl AlertRule for KubernetesRule {
                 alerts.push(Alert::new(
                     level,
                     AlertCategory::Kubernetes,
-                    format!("{} Nodes Not Ready", unhealthy_nodes),
-                    format!(
-                        "{} of {} cluster nodes are not in Ready state",
-                        unhealthy_nodes, self.cluster_info.nodes_total
-                    ),
+                    tf("alert.nodes_not_ready.title", &[&unhealthy_nodes]),
+                    tf("alert.nodes_not_ready.message", &[&unhealthy_nodes, &self.cluster_info.nodes_total]),
                     "k8s-nodes".to_string(),
                 ));
             }
@@ -284,8 +273,8 @@ impl AlertRule for KubernetesRule {
             alerts.push(Alert::new(
                 AlertLevel::Critical,
                 AlertCategory::Kubernetes,
-                "Cluster Unreachable".to_string(),
-                "Unable to connect to Kubernetes cluster or cluster has no nodes".to_string(),
+                t("alert.cluster_unreachable.title").to_string(),
+                t("alert.cluster_unreachable.message").to_string(),
                 "k8s-cluster".to_string(),
             ));
         }
@@ -317,11 +306,8 @@ impl AlertRule for KubeVirtRule {
             alerts.push(Alert::new(
                 AlertLevel::Info,
                 AlertCategory::KubeVirt,
-                format!("{} VMs Migrating", self.kubevirt_info.vms_migrating),
-                format!(
-                    "{} virtual machines are currently migrating",
-                    self.kubevirt_info.vms_migrating
-                ),
+                tf("alert.vms_migrating.title", &[&self.kubevirt_info.vms_migrating]),
+                tf("alert.vms_migrating.message", &[&self.kubevirt_info.vms_migrating]),
                 "kubevirt-migration".to_string(),
             ));
         }
@@ -338,3 +324,2227 @@ impl AlertRule for KubeVirtRule {
         "kubevirt_vms"
     }
 }
+
+// DNS resolver health rules
+/// Fires when a watched Service has no ready endpoints left
+pub struct ServiceEndpointRule {
+    pub services: Vec<ServiceEndpoints>,
+}
+
+impl AlertRule for ServiceEndpointRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.services
+            .iter()
+            .filter(|svc| svc.ready == 0)
+            .map(|svc| {
+                Alert::new(
+                    AlertLevel::Critical,
+                    AlertCategory::Kubernetes,
+                    tf("alert.svc_endpoints.title", &[&svc.namespace, &svc.name]),
+                    tf("alert.svc_endpoints.message", &[&svc.namespace, &svc.name, &svc.not_ready]),
+                    format!("svc-endpoints-{}/{}", svc.namespace, svc.name),
+                )
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "service_endpoints"
+    }
+}
+
+/// Fires when a watched Service's VIP stops accepting connections while it
+/// still has ready endpoints; with none left ServiceEndpointRule covers it
+pub struct VipRule {
+    pub vips: Vec<VipStatus>,
+    pub failure_threshold: u32,  // Consecutive failed probes before alerting
+}
+
+impl AlertRule for VipRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.vips
+            .iter()
+            .filter(|vip| vip.consecutive_failures >= self.failure_threshold && vip.ready_endpoints > 0)
+            .map(|vip| {
+                Alert::new(
+                    AlertLevel::Critical,
+                    AlertCategory::Network,
+                    tf("alert.svc_vip.title", &[&vip.service()]),
+                    tf(
+                        "alert.svc_vip.message",
+                        &[
+                            &vip.address,
+                            &vip.consecutive_failures,
+                            &vip.last_error.as_deref().unwrap_or(t("alert.no_response")),
+                            &vip.ready_endpoints,
+                        ],
+                    ),
+                    format!("svc-vip-{}", vip.address),
+                )
+                .with_value(vip.consecutive_failures as f64, self.failure_threshold as f64)
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "service_vips"
+    }
+}
+
+/// Per-node readiness and pressure conditions, attributed to the node so the
+/// Fleet view can count them
+pub struct NodeConditionRule {
+    pub nodes: Vec<NodeStatus>,
+}
+
+impl AlertRule for NodeConditionRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        for node in &self.nodes {
+            if node.reachability != NodeReachability::Ready {
+                alerts.push(
+                    Alert::new(
+                        AlertLevel::Critical,
+                        AlertCategory::Kubernetes,
+                        tf("alert.node_condition.title", &[&node.name, &node.reachability.label()]),
+                        tf(
+                            "alert.node_unreachable.message",
+                            &[&node.name, &node.reachability.label(), &node.vms.len()],
+                        ),
+                        format!("node-{}", node.name),
+                    )
+                    .with_node(node.name.clone()),
+                );
+            }
+
+            for pressure in &node.pressures {
+                alerts.push(
+                    Alert::new(
+                        AlertLevel::Warning,
+                        AlertCategory::Kubernetes,
+                        tf("alert.node_condition.title", &[&node.name, pressure]),
+                        tf("alert.node_pressure.message", &[&node.name, pressure]),
+                        format!("node-{}-{}", node.name, pressure),
+                    )
+                    .with_node(node.name.clone()),
+                );
+            }
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "node_conditions"
+    }
+}
+
+/// Pressure and other bad conditions the local kubelet reports, with the
+/// reason, how long it has held and the eviction threshold involved.
+/// Ready is left to the node condition rule.
+pub struct KubeletRule {
+    pub status: KubeletStatus,
+}
+
+impl AlertRule for KubeletRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let node = &self.status.node;
+        let mut alerts = Vec::new();
+
+        for condition in &self.status.conditions {
+            if condition.kind == "Ready" || !condition.is_problem() {
+                continue;
+            }
+            let level = if condition.kind == "NetworkUnavailable" { AlertLevel::Error } else { AlertLevel::Warning };
+            let mut description = tf("alert.kubelet.message", &[node, &condition.kind]);
+            if let Some(ref reason) = condition.reason {
+                description.push_str(&format!(" ({})", reason));
+            }
+            if let Some(ref message) = condition.message {
+                description.push_str(&format!(": {}", message));
+            }
+            if let Some(since) = condition.last_transition {
+                description.push_str(&tf("alert.kubelet.since", &[&since.format("%Y-%m-%d %H:%M")]));
+            }
+            let signals: &[&str] = match condition.kind.as_str() {
+                "MemoryPressure" => &["memory.available"],
+                "DiskPressure" => &["nodefs.available", "nodefs.inodesFree", "imagefs.available"],
+                "PIDPressure" => &["pid.available"],
+                _ => &[],
+            };
+            let thresholds: Vec<String> = signals
+                .iter()
+                .filter_map(|signal| Some(format!("{}<{}", signal, self.status.eviction_hard.get(*signal)?)))
+                .collect();
+            if !thresholds.is_empty() {
+                description.push_str(&tf("alert.kubelet.evicted_at", &[&thresholds.join(", ")]));
+            }
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::Kubernetes,
+                    tf("alert.node_condition.title", &[node, &condition.kind]),
+                    description,
+                    format!("kubelet-{}", condition.kind),
+                )
+                .with_node(node.clone()),
+            );
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "kubelet"
+    }
+}
+
+/// Namespace ResourceQuota resources close to their hard limit
+pub struct QuotaRule {
+    pub quotas: Vec<QuotaUsage>,
+    pub warning_threshold: f64,
+    pub critical_threshold: f64,
+}
+
+impl AlertRule for QuotaRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        for quota in &self.quotas {
+            let usage = quota.percent();
+            let (level, threshold) = if usage >= self.critical_threshold {
+                (AlertLevel::Critical, self.critical_threshold)
+            } else if usage >= self.warning_threshold {
+                (AlertLevel::Warning, self.warning_threshold)
+            } else {
+                continue;
+            };
+
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::Kubernetes,
+                    tf("alert.quota.title", &[&quota.namespace, &quota.resource]),
+                    tf(
+                        "alert.quota.message",
+                        &[
+                            &quota.namespace,
+                            &format!("{:.1}", usage),
+                            &quota.resource,
+                            &quota.quota,
+                            &format!("{:.1}", threshold),
+                        ],
+                    ),
+                    format!("quota-{}-{}-{}", quota.namespace, quota.quota, quota.resource),
+                )
+                .with_value(usage, threshold),
+            );
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "resource_quotas"
+    }
+}
+
+/// Tenants that reached one of their configured limits, or whose vCPUs or
+/// memory grew past the growth threshold within its window
+pub struct TenantRule {
+    pub tenants: Vec<TenantUsage>,
+    pub limits: BTreeMap<String, TenantLimits>,
+    pub growth_percent: f64,  // 0 disables growth alerts
+}
+
+impl AlertRule for TenantRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+        let mut alerts = Vec::new();
+
+        for tenant in &self.tenants {
+            if let Some(limits) = self.limits.get(&tenant.tenant) {
+                let checks = [
+                    ("alert.tenant.vcpus", "vcpus", tenant.cpu_cores(), limits.vcpus, ""),
+                    ("alert.tenant.memory", "memory", tenant.total_memory_bytes() as f64 / GIB, limits.memory_gib, " GiB"),
+                    ("alert.tenant.storage", "storage", tenant.storage_bytes as f64 / GIB, limits.storage_gib, " GiB"),
+                ];
+                for (title, key, used, limit, unit) in checks {
+                    let Some(limit) = limit.filter(|&limit| used >= limit) else {
+                        continue;
+                    };
+                    alerts.push(
+                        Alert::new(
+                            AlertLevel::Warning,
+                            AlertCategory::Kubernetes,
+                            tf("alert.tenant_limit.title", &[&tenant.tenant, &t(title)]),
+                            tf(
+                                "alert.tenant_limit.message",
+                                &[
+                                    &tenant.tenant,
+                                    &format!("{:.1}{}", used, unit),
+                                    &t(title),
+                                    &format!("{:.1}{}", limit, unit),
+                                    &tenant.vms,
+                                    &tenant.pods,
+                                ],
+                            ),
+                            format!("tenant-{}-{}", tenant.tenant, key),
+                        )
+                        .with_value(used, limit),
+                    );
+                }
+            }
+
+            if self.growth_percent <= 0.0 {
+                continue;
+            }
+            let Some(growth) = tenant.growth_percent.filter(|&growth| growth >= self.growth_percent) else {
+                continue;
+            };
+            alerts.push(
+                Alert::new(
+                    AlertLevel::Warning,
+                    AlertCategory::Kubernetes,
+                    tf("alert.tenant_growth.title", &[&tenant.tenant, &format!("{:.0}", growth)]),
+                    tf(
+                        "alert.tenant_growth.message",
+                        &[
+                            &tenant.tenant,
+                            &tenant.vms,
+                            &format!("{:.1}", tenant.cpu_cores()),
+                            &format!("{:.1}", tenant.total_memory_bytes() as f64 / GIB),
+                            &format!("{:.0}", growth),
+                        ],
+                    ),
+                    format!("tenant-{}-growth", tenant.tenant),
+                )
+                .with_value(growth, self.growth_percent),
+            );
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "tenants"
+    }
+}
+
+/// File descriptors and ephemeral ports running out, system-wide and for
+/// each watched process (qemu, containerd, the kubelet), and required
+/// service ports nothing listens on
+pub struct SocketRule {
+    pub usage: SocketUsage,
+    pub fd_warning_threshold: f64,
+    pub fd_critical_threshold: f64,
+    pub port_warning_threshold: f64,
+    pub port_critical_threshold: f64,
+}
+
+impl SocketRule {
+    fn level(percent: f64, warning: f64, critical: f64) -> Option<(AlertLevel, f64)> {
+        if percent >= critical {
+            Some((AlertLevel::Critical, critical))
+        } else if percent >= warning {
+            Some((AlertLevel::Warning, warning))
+        } else {
+            None
+        }
+    }
+}
+
+impl AlertRule for SocketRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        let usage = &self.usage;
+
+        let percent = usage.fd_percent();
+        if let Some((level, threshold)) = Self::level(percent, self.fd_warning_threshold, self.fd_critical_threshold) {
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::System,
+                    t("alert.fds_system.title").to_string(),
+                    tf(
+                        "alert.fds_system.message",
+                        &[&usage.fds_allocated, &usage.fds_max, &format!("{:.1}", percent)],
+                    ),
+                    "fds-system".to_string(),
+                )
+                .with_value(percent, threshold),
+            );
+        }
+
+        let percent = usage.ephemeral_percent();
+        if let Some((level, threshold)) = Self::level(percent, self.port_warning_threshold, self.port_critical_threshold) {
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::Network,
+                    t("alert.ephemeral_ports.title").to_string(),
+                    tf(
+                        "alert.ephemeral_ports.message",
+                        &[
+                            &usage.ephemeral_in_use,
+                            &usage.ephemeral_range.0,
+                            &usage.ephemeral_range.1,
+                            &format!("{:.1}", percent),
+                            &usage.time_wait,
+                        ],
+                    ),
+                    "ephemeral-ports".to_string(),
+                )
+                .with_value(percent, threshold),
+            );
+        }
+
+        for process in &usage.processes {
+            let (Some(percent), Some(limit)) = (process.percent(), process.limit) else {
+                continue;
+            };
+            let Some((level, threshold)) = Self::level(percent, self.fd_warning_threshold, self.fd_critical_threshold) else {
+                continue;
+            };
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::System,
+                    tf("alert.fds_process.title", &[&process.name]),
+                    tf(
+                        "alert.fds_process.message",
+                        &[&process.name, &process.pid, &process.open, &limit, &format!("{:.1}", percent)],
+                    ),
+                    format!("fds-{}-{}", process.name, process.pid),
+                )
+                .with_value(percent, threshold),
+            );
+        }
+
+        for port in usage.ports.iter().filter(|port| port.required && port.listeners.is_empty()) {
+            let message = if port.stopped { "alert.port_stopped.message" } else { "alert.port_closed.message" };
+            alerts.push(Alert::new(
+                AlertLevel::Critical,
+                AlertCategory::Service,
+                tf("alert.port.title", &[&port.name]),
+                tf(message, &[&port.name, &port.ports]),
+                format!("port-{}", port.ports),
+            ));
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "sockets"
+    }
+}
+
+/// The PID space filling up, where fork() and thread creation start
+/// failing, and the random pool running dry, where reads from /dev/random
+/// and early TLS handshakes block
+pub struct LimitsRule {
+    pub limits: HostLimits,
+    pub pid_warning_threshold: f64,
+    pub pid_critical_threshold: f64,
+    pub entropy_warning_bits: u64,
+    pub entropy_critical_bits: u64,
+}
+
+impl AlertRule for LimitsRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        let limits = &self.limits;
+
+        let percent = limits.task_percent();
+        let level = if percent >= self.pid_critical_threshold {
+            Some((AlertLevel::Critical, self.pid_critical_threshold))
+        } else if percent >= self.pid_warning_threshold {
+            Some((AlertLevel::Warning, self.pid_warning_threshold))
+        } else {
+            None
+        };
+        if let Some((level, threshold)) = level {
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::System,
+                    t("alert.pids.title").to_string(),
+                    tf(
+                        "alert.pids.message",
+                        &[&limits.tasks, &limits.task_limit, &format!("{:.1}", percent), &limits.task_limit_name],
+                    ),
+                    "pids-system".to_string(),
+                )
+                .with_value(percent, threshold),
+            );
+        }
+
+        if let Some(bits) = limits.entropy_bits {
+            let level = if bits < self.entropy_critical_bits {
+                Some((AlertLevel::Critical, self.entropy_critical_bits))
+            } else if bits < self.entropy_warning_bits {
+                Some((AlertLevel::Warning, self.entropy_warning_bits))
+            } else {
+                None
+            };
+            if let Some((level, threshold)) = level {
+                alerts.push(
+                    Alert::new(
+                        level,
+                        AlertCategory::System,
+                        t("alert.entropy.title").to_string(),
+                        tf("alert.entropy.message", &[&bits]),
+                        "entropy".to_string(),
+                    )
+                    .with_value(bits as f64, threshold as f64),
+                );
+            }
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "limits"
+    }
+}
+
+/// Busy cores held well below base clock by heat or a power limit, which
+/// VMs feel as unexplained slowness while CPU usage looks normal
+pub struct FreqThrottleRule {
+    pub power: PowerInfo,
+    /// Percent of base clock below which a busy core counts as throttled
+    pub percent: f64,
+    /// Minutes the host has been throttled so far; None when it isn't
+    pub throttled_mins: Option<u64>,
+    pub sustain_mins: u64,
+}
+
+impl AlertRule for FreqThrottleRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let Some(minutes) = self.throttled_mins.filter(|m| *m >= self.sustain_mins) else {
+            return Vec::new();
+        };
+        let throttled = self.power.throttled_cores(self.percent);
+        let average = throttled.iter().map(|core| core.cur_mhz).sum::<f64>() / throttled.len().max(1) as f64;
+        let reference = match self.power.base_mhz {
+            Some(base) => tf("alert.cpu_freq.base_clock", &[&format!("{:.0}", base)]),
+            None => t("alert.cpu_freq.max_clock").to_string(),
+        };
+        let cause = if self.power.throttle_events > 0 {
+            tf("alert.cpu_freq.thermal", &[&self.power.throttle_events])
+        } else if self.power.governor.as_deref() == Some("powersave") && self.power.base_mhz.is_none() {
+            t("alert.cpu_freq.powersave").to_string()
+        } else {
+            t("alert.cpu_freq.power_limit").to_string()
+        };
+
+        let alert = Alert::new(
+            AlertLevel::Warning,
+            AlertCategory::System,
+            t("alert.cpu_freq.title").to_string(),
+            tf(
+                "alert.cpu_freq.message",
+                &[
+                    &throttled.len(),
+                    &self.power.busy_cores(),
+                    &format!("{:.0}", self.percent),
+                    &reference,
+                    &minutes,
+                    &format!("{:.0}", average),
+                    &cause,
+                ],
+            ),
+            "cpu-frequency".to_string(),
+        );
+        match self.power.base_mhz {
+            Some(base) => vec![alert.with_value(average, base * self.percent / 100.0)],
+            None => vec![alert],
+        }
+    }
+
+    fn name(&self) -> &str {
+        "cpu_frequency"
+    }
+}
+
+/// A slow or failing API server: the latency of its probes, /healthz and
+/// the etcd check it exposes
+pub struct ApiHealthRule {
+    pub health: ApiHealth,
+    pub warning_ms: f64,
+    pub critical_ms: f64,
+}
+
+impl AlertRule for ApiHealthRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        let error = self.health.error.as_deref().unwrap_or(t("alert.no_response"));
+
+        if self.health.healthz_ms.is_none() {
+            alerts.push(Alert::new(
+                AlertLevel::Critical,
+                AlertCategory::Kubernetes,
+                t("alert.api_healthz.title").to_string(),
+                tf("alert.api_healthz.message", &[&error]),
+                "k8s-api-healthz".to_string(),
+            ));
+        }
+
+        if self.health.etcd_ok == Some(false) {
+            alerts.push(Alert::new(
+                AlertLevel::Critical,
+                AlertCategory::Kubernetes,
+                t("alert.api_etcd.title").to_string(),
+                tf("alert.api_etcd.message", &[&error]),
+                "k8s-etcd".to_string(),
+            ));
+        }
+
+        if let Some(latency) = self.health.latency_ms() {
+            let level = if latency >= self.critical_ms {
+                Some((AlertLevel::Critical, self.critical_ms))
+            } else if latency >= self.warning_ms {
+                Some((AlertLevel::Warning, self.warning_ms))
+            } else {
+                None
+            };
+            if let Some((level, threshold)) = level {
+                alerts.push(
+                    Alert::new(
+                        level,
+                        AlertCategory::Kubernetes,
+                        t("alert.api_latency.title").to_string(),
+                        tf(
+                            "alert.api_latency.message",
+                            &[
+                                &format!("{:.0}", latency),
+                                &format_ms(self.health.healthz_ms),
+                                &format_ms(self.health.list_ms),
+                                &self.health.p99_ms.map(|ms| format!(", p99 {:.0} ms", ms)).unwrap_or_default(),
+                                &format!("{:.0}", threshold),
+                            ],
+                        ),
+                        "k8s-api-latency".to_string(),
+                    )
+                    .with_value(latency, threshold),
+                );
+            }
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "api_health"
+    }
+}
+
+/// The cluster datastore filling its backend quota, without a leader, or
+/// electing and failing proposals often enough to stall the API server
+pub struct EtcdRule {
+    pub status: EtcdStatus,
+    pub warning_percent: f64,
+    pub critical_percent: f64,
+    pub leader_changes_per_hour: u64,
+}
+
+impl AlertRule for EtcdRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        let status = &self.status;
+        let mib = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+
+        if let (Some(percent), Some(quota)) = (status.quota_percent(), status.quota_bytes) {
+            let level = if percent >= self.critical_percent {
+                Some((AlertLevel::Critical, self.critical_percent))
+            } else if percent >= self.warning_percent {
+                Some((AlertLevel::Warning, self.warning_percent))
+            } else {
+                None
+            };
+            if let Some((level, threshold)) = level {
+                let hint = match status.fragmentation_percent() {
+                    Some(fragmented) if fragmented >= 25.0 => {
+                        tf("alert.etcd_quota.fragmented", &[&format!("{:.0}", fragmented)])
+                    }
+                    _ => t("alert.etcd_quota.defrag").to_string(),
+                };
+                alerts.push(
+                    Alert::new(
+                        level,
+                        AlertCategory::Kubernetes,
+                        t("alert.etcd_quota.title").to_string(),
+                        tf(
+                            "alert.etcd_quota.message",
+                            &[
+                                &format!("{:.0}", mib(status.db_size_bytes)),
+                                &format!("{:.0}", mib(quota)),
+                                &format!("{:.1}", percent),
+                                &format!("{:.0}", threshold),
+                                &hint,
+                            ],
+                        ),
+                        "etcd-quota".to_string(),
+                    )
+                    .with_value(percent, threshold),
+                );
+            }
+        }
+
+        if status.has_leader == Some(false) {
+            alerts.push(Alert::new(
+                AlertLevel::Critical,
+                AlertCategory::Kubernetes,
+                t("alert.etcd_leader.title").to_string(),
+                tf("alert.etcd_leader.message", &[&status.endpoint]),
+                "etcd-leader".to_string(),
+            ));
+        }
+
+        if status.leader_changes_last_hour >= self.leader_changes_per_hour {
+            alerts.push(
+                Alert::new(
+                    AlertLevel::Warning,
+                    AlertCategory::Kubernetes,
+                    t("alert.etcd_churn.title").to_string(),
+                    tf(
+                        "alert.etcd_churn.message",
+                        &[&status.leader_changes_last_hour, &self.leader_changes_per_hour],
+                    ),
+                    "etcd-leader-changes".to_string(),
+                )
+                .with_value(status.leader_changes_last_hour as f64, self.leader_changes_per_hour as f64),
+            );
+        }
+
+        if status.proposals_failed_recent > 0 {
+            alerts.push(Alert::new(
+                AlertLevel::Warning,
+                AlertCategory::Kubernetes,
+                t("alert.etcd_proposals.title").to_string(),
+                tf(
+                    "alert.etcd_proposals.message",
+                    &[&status.proposals_failed_recent, &status.proposals_failed_total],
+                ),
+                "etcd-proposals".to_string(),
+            ));
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "etcd"
+    }
+}
+
+fn format_ms(ms: Option<f64>) -> String {
+    ms.map(|ms| format!("{:.0} ms", ms)).unwrap_or_else(|| t("alert.failed").to_string())
+}
+
+/// Nodes that live migrations keep failing to or from, as found by the
+/// migration history
+pub struct MigrationRule {
+    pub nodes: Vec<NodeMigrationFailures>,
+    pub threshold: usize,
+}
+
+impl AlertRule for MigrationRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.nodes
+            .iter()
+            .map(|node| {
+                let reason = node
+                    .last_reason
+                    .as_deref()
+                    .map(|r| tf("alert.migrations.last_failure", &[&r]))
+                    .unwrap_or_default();
+                Alert::new(
+                    AlertLevel::Warning,
+                    AlertCategory::KubeVirt,
+                    tf("alert.migrations.title", &[&node.node]),
+                    tf("alert.migrations.message", &[&node.consecutive_failures, &node.node, &reason]),
+                    format!("migrations-{}", node.node),
+                )
+                .with_value(node.consecutive_failures as f64, self.threshold as f64)
+                .with_node(node.node.clone())
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "migrations"
+    }
+}
+
+/// VMs booting much slower than they usually do, still starting or
+/// started within the last hour
+pub struct SlowBootRule {
+    pub boots: Vec<SlowBoot>,
+    pub factor: f64,
+}
+
+impl AlertRule for SlowBootRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let secs = |d: chrono::Duration| d.num_seconds().max(0);
+        self.boots
+            .iter()
+            .map(|boot| {
+                let node = boot.node.as_deref().map(|n| tf("alert.slow_boot.on_node", &[&n])).unwrap_or_default();
+                let message = if boot.still_booting { "alert.slow_boot.starting" } else { "alert.slow_boot.message" };
+                let message = tf(message, &[&boot.vm, &secs(boot.took), &node, &secs(boot.baseline), &boot.boots]);
+                let mut alert = Alert::new(
+                    AlertLevel::Warning,
+                    AlertCategory::KubeVirt,
+                    tf("alert.slow_boot.title", &[&boot.vm]),
+                    message,
+                    format!("boot-{}", boot.vm),
+                )
+                .with_value(secs(boot.took) as f64, secs(boot.baseline) as f64 * self.factor)
+                .with_vm(boot.vm.clone());
+                if let Some(ref node) = boot.node {
+                    alert = alert.with_node(node.clone());
+                }
+                alert
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "slow-boot"
+    }
+}
+
+/// Importer or cloner pod restarts before a DataVolume is reported as
+/// failing; CDI retries a bad source forever without changing phase
+const DATA_VOLUME_RESTART_THRESHOLD: u32 = 3;
+
+/// CDI DataVolumes that failed, keep restarting their importer, or haven't
+/// moved for `stall_mins`; otherwise the VM just never starts
+pub struct DataVolumeRule {
+    pub volumes: Vec<DataVolume>,
+    pub stall_mins: u64,
+}
+
+impl AlertRule for DataVolumeRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let now = chrono::Local::now();
+        self.volumes
+            .iter()
+            .filter(|dv| dv.phase != "Succeeded")
+            .filter_map(|dv| {
+                let key = dv.key();
+                let detail = dv.message.as_deref().map(|m| format!(": {}", m)).unwrap_or_default();
+                let alert = if dv.phase == "Failed" {
+                    Alert::new(
+                        AlertLevel::Error,
+                        AlertCategory::KubeVirt,
+                        tf("alert.datavolume_failed.title", &[&key]),
+                        tf("alert.datavolume_failed.message", &[&key, &dv.source, &detail]),
+                        format!("datavolume-{}", key),
+                    )
+                } else if dv.restarts >= DATA_VOLUME_RESTART_THRESHOLD {
+                    Alert::new(
+                        AlertLevel::Error,
+                        AlertCategory::KubeVirt,
+                        tf("alert.datavolume_failing.title", &[&key]),
+                        tf("alert.datavolume_failing.message", &[&dv.source, &key, &dv.restarts, &detail]),
+                        format!("datavolume-{}", key),
+                    )
+                    .with_value(dv.restarts as f64, DATA_VOLUME_RESTART_THRESHOLD as f64)
+                } else {
+                    let idle = now - dv.changed?;
+                    if dv.is_waiting() || idle < chrono::Duration::minutes(self.stall_mins as i64) {
+                        return None;
+                    }
+                    let progress = dv
+                        .progress
+                        .map(|p| tf("alert.datavolume_stuck.progress", &[&format!("{:.1}", p)]))
+                        .unwrap_or_default();
+                    Alert::new(
+                        AlertLevel::Warning,
+                        AlertCategory::KubeVirt,
+                        tf("alert.datavolume_stuck.title", &[&key]),
+                        tf(
+                            "alert.datavolume_stuck.message",
+                            &[&key, &dv.phase, &progress, &idle.num_minutes(), &detail],
+                        ),
+                        format!("datavolume-{}", key),
+                    )
+                };
+                Some(match dv.owner {
+                    Some(ref vm) => alert.with_vm(vm.clone()),
+                    None => alert,
+                })
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "data_volumes"
+    }
+}
+
+/// VMs whose vCPUs keep waiting for physical CPUs, or whose launcher
+/// cgroup keeps hitting its CPU limit; the first means the host is
+/// overcommitted, the second that the VM's own limit is too low
+pub struct SchedLatencyRule {
+    pub processes: Vec<QemuProcess>,
+    pub wait_warning_threshold: f64,
+    pub wait_critical_threshold: f64,
+    pub throttle_warning_threshold: f64,
+    pub throttle_critical_threshold: f64,
+}
+
+impl SchedLatencyRule {
+    fn level(percent: f64, warning: f64, critical: f64) -> Option<(AlertLevel, f64)> {
+        if percent >= critical {
+            Some((AlertLevel::Critical, critical))
+        } else if percent >= warning {
+            Some((AlertLevel::Warning, warning))
+        } else {
+            None
+        }
+    }
+}
+
+impl AlertRule for SchedLatencyRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for process in &self.processes {
+            let Some(sched) = process.sched else {
+                continue;
+            };
+            let vm = process.vm.clone().unwrap_or_else(|| process.domain.clone());
+
+            let wait = sched.sustained_wait_percent;
+            if let Some((level, threshold)) = Self::level(wait, self.wait_warning_threshold, self.wait_critical_threshold) {
+                alerts.push(
+                    Alert::new(
+                        level,
+                        AlertCategory::KubeVirt,
+                        tf("alert.sched_wait.title", &[&vm]),
+                        tf("alert.sched_wait.message", &[&sched.vcpus, &vm, &format!("{:.1}", wait)]),
+                        format!("sched-wait-{}", vm),
+                    )
+                    .with_value(wait, threshold)
+                    .with_vm(vm.clone()),
+                );
+            }
+
+            let throttled = sched.sustained_throttled_percent;
+            if let Some((level, threshold)) =
+                Self::level(throttled, self.throttle_warning_threshold, self.throttle_critical_threshold)
+            {
+                alerts.push(
+                    Alert::new(
+                        level,
+                        AlertCategory::KubeVirt,
+                        tf("alert.cpu_throttle.title", &[&vm]),
+                        tf("alert.cpu_throttle.message", &[&vm, &format!("{:.1}", throttled)]),
+                        format!("cpu-throttle-{}", vm),
+                    )
+                    .with_value(throttled, threshold)
+                    .with_vm(vm),
+                );
+            }
+        }
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "sched_latency"
+    }
+}
+
+/// SR-IOV NICs with every created VF assigned; new VMs asking for an
+/// SR-IOV network would fail to schedule on this node
+pub struct SriovRule {
+    pub nics: Vec<SriovNic>,
+    pub labels: InterfaceLabels,
+}
+
+impl AlertRule for SriovRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.nics
+            .iter()
+            .filter(|nic| !nic.vfs.is_empty() && nic.free() == 0)
+            .map(|nic| {
+                Alert::new(
+                    AlertLevel::Warning,
+                    AlertCategory::Network,
+                    tf("alert.sriov.title", &[&self.labels.get(&nic.name).unwrap_or(&nic.name)]),
+                    tf(
+                        "alert.sriov.message",
+                        &[&nic.vfs.len(), &self.labels.describe(&nic.name), &nic.pci_address, &nic.total_vfs],
+                    ),
+                    format!("sriov-{}", nic.name),
+                )
+                .with_value(nic.assigned() as f64, nic.vfs.len() as f64)
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "sriov_vfs"
+    }
+}
+
+/// Multipath LUNs that have lost paths. I/O keeps flowing over the rest, so
+/// nothing else notices until the last one goes: a warning while most
+/// paths remain, an error once half are gone, critical with none left.
+pub struct MultipathRule {
+    pub info: MultipathInfo,
+}
+
+impl AlertRule for MultipathRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for map in &self.info.maps {
+            let failed = map.failed_paths();
+            if failed == 0 {
+                continue;
+            }
+            let total = map.paths().count();
+            let (level, title) = if failed == total {
+                (AlertLevel::Critical, tf("alert.multipath_none.title", &[&map.name]))
+            } else if failed * 2 >= total {
+                (AlertLevel::Error, tf("alert.multipath_half.title", &[&map.name]))
+            } else {
+                (AlertLevel::Warning, tf("alert.multipath_degraded.title", &[&map.name]))
+            };
+
+            let paths: Vec<String> = map
+                .paths()
+                .filter(|path| path.failed())
+                .map(|path| {
+                    let host = path.hctl.split(':').next().map(|h| format!("host{}", h)).unwrap_or_default();
+                    match self.info.hba_ports.iter().find(|port| port.host == host && port.state != "Online") {
+                        Some(port) => tf("alert.multipath.hba_port", &[&path.device, &path.hctl, &port.state]),
+                        None => format!("{} ({}, {} {})", path.device, path.hctl, path.checker_state, path.device_state),
+                    }
+                })
+                .collect();
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::System,
+                    title,
+                    tf(
+                        "alert.multipath.message",
+                        &[
+                            &failed,
+                            &total,
+                            &map.degraded_groups(),
+                            &map.groups.len(),
+                            &map.wwid,
+                            &map.dm,
+                            &map.vendor,
+                            &paths.join(", "),
+                        ],
+                    ),
+                    format!("multipath-{}", map.name),
+                )
+                .with_value(failed as f64, total as f64),
+            );
+        }
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "multipath"
+    }
+}
+
+/// Block devices whose requests have been slow for every scan of the last
+/// 30 seconds, while they were busy
+pub struct DiskLatencyRule {
+    pub disks: Vec<DiskLatency>,
+    pub warning_ms: f64,
+    pub critical_ms: f64,
+}
+
+impl AlertRule for DiskLatencyRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for disk in &self.disks {
+            let latency = disk.sustained_ms;
+            let (level, threshold) = if latency >= self.critical_ms {
+                (AlertLevel::Critical, self.critical_ms)
+            } else if latency >= self.warning_ms {
+                (AlertLevel::Warning, self.warning_ms)
+            } else {
+                continue;
+            };
+            let p99 = disk
+                .p99_ms
+                .map(|ms| tf("alert.disk_latency.p99", &[&format!("{:.0}", ms)]))
+                .unwrap_or_default();
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::System,
+                    tf("alert.disk_latency.title", &[&disk.name()]),
+                    tf(
+                        "alert.disk_latency.message",
+                        &[
+                            &disk.name(),
+                            &format!("{:.0}", latency),
+                            &format!("{:.0}", disk.util_percent),
+                            &format!("{:.1}", disk.read_mb_s),
+                            &format!("{:.1}", disk.write_mb_s),
+                            &p99,
+                        ],
+                    ),
+                    format!("disk-latency-{}", disk.device),
+                )
+                .with_value(latency, threshold),
+            );
+        }
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "disk-latency"
+    }
+}
+
+/// VM datastore devices left on an I/O scheduler that suits them poorly,
+/// most often mq-deadline on NVMe after a default install
+pub struct IoSchedulerRule {
+    pub disks: Vec<DiskLatency>,
+}
+
+impl AlertRule for IoSchedulerRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.disks
+            .iter()
+            .filter(|disk| disk.datastore)
+            .filter_map(|disk| {
+                let better = disk.queue.better_scheduler(&disk.device)?;
+                let current = disk.queue.scheduler.as_deref().unwrap_or_default();
+                Some(Alert::new(
+                    AlertLevel::Warning,
+                    AlertCategory::System,
+                    tf("alert.io_scheduler.title", &[&disk.name()]),
+                    tf(
+                        "alert.io_scheduler.message",
+                        &[
+                            &disk.name(),
+                            &if disk.queue.rotational {
+                                t("alert.io_scheduler.rotational")
+                            } else if disk.device.starts_with("nvme") {
+                                "NVMe"
+                            } else {
+                                "SSD"
+                            },
+                            &current,
+                            &better,
+                            &disk.device,
+                        ],
+                    ),
+                    format!("io-scheduler-{}", disk.device),
+                ))
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "io-scheduler"
+    }
+}
+
+/// IPv4 addresses claimed by two MACs on a watched VM bridge, and MACs
+/// cycling through DHCP leases, as seen by the bridge watcher
+pub struct BridgeConflictRule {
+    pub conflicts: Vec<IpConflict>,
+    pub churn: Vec<LeaseChurn>,
+    pub window_mins: u64,
+    pub labels: InterfaceLabels,
+}
+
+impl AlertRule for BridgeConflictRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let conflicts = self.conflicts.iter().map(|conflict| {
+            Alert::new(
+                AlertLevel::Error,
+                AlertCategory::Network,
+                tf("alert.ip_conflict.title", &[&conflict.ip]),
+                tf(
+                    "alert.ip_conflict.message",
+                    &[
+                        &conflict.ip,
+                        &conflict.macs.join(", "),
+                        &self.labels.describe(&conflict.bridge),
+                        &conflict.last_seen.format("%H:%M:%S"),
+                    ],
+                ),
+                format!("ip-conflict-{}", conflict.ip),
+            )
+        });
+        let churn = self.churn.iter().map(|churn| {
+            let ips: Vec<String> = churn.ips.iter().map(|ip| ip.to_string()).collect();
+            Alert::new(
+                AlertLevel::Warning,
+                AlertCategory::Network,
+                tf("alert.dhcp_churn.title", &[&churn.mac]),
+                tf(
+                    "alert.dhcp_churn.message",
+                    &[
+                        &churn.leases,
+                        &churn.mac,
+                        &self.labels.describe(&churn.bridge),
+                        &self.window_mins,
+                        &ips.join(", "),
+                    ],
+                ),
+                format!("dhcp-churn-{}", churn.mac),
+            )
+        });
+        conflicts.chain(churn).collect()
+    }
+
+    fn name(&self) -> &str {
+        "bridge_conflicts"
+    }
+}
+
+/// Links along the VM data path whose MTU differs from the bridge, bond,
+/// parent or CNI they have to match: large frames get dropped silently
+/// while small ones (ping, DNS, SSH logins) still pass. One alert per link
+/// matched against.
+pub struct MtuRule {
+    pub mismatches: Vec<MtuMismatch>,
+    pub labels: InterfaceLabels,
+}
+
+impl AlertRule for MtuRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut by_against: BTreeMap<&str, Vec<&MtuMismatch>> = BTreeMap::new();
+        for mismatch in &self.mismatches {
+            by_against.entry(&mismatch.against).or_default().push(mismatch);
+        }
+        by_against
+            .into_iter()
+            .map(|(against, mismatches)| {
+                let expected = mismatches[0].expected;
+                let links: Vec<String> = mismatches
+                    .iter()
+                    .map(|m| format!("{} ({})", self.labels.describe(&m.interface), m.mtu))
+                    .collect();
+                let against_name = match against.split_once(' ') {
+                    Some((kind, name)) => format!("{} {}", kind, self.labels.describe(name)),
+                    None => against.to_string(),
+                };
+                Alert::new(
+                    AlertLevel::Error,
+                    AlertCategory::Network,
+                    tf("alert.mtu.title", &[&against_name]),
+                    tf(
+                        if mismatches.len() == 1 { "alert.mtu.message_one" } else { "alert.mtu.message" },
+                        &[&against_name, &expected, &mismatches.len(), &links.join(", ")],
+                    ),
+                    format!("mtu-{}", against.replace(' ', "-").to_lowercase()),
+                )
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "mtu"
+    }
+}
+
+/// Virtualization extensions or the IOMMU gone missing, typically after a
+/// firmware update reset its setup options to defaults
+pub struct FirmwareRule {
+    pub info: HardwareInfo,
+}
+
+impl AlertRule for FirmwareRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let info = &self.info;
+        let Some(ref baseline) = info.baseline else {
+            return Vec::new();
+        };
+        let firmware = match (info.previous_bios(), info.bios_version.as_deref()) {
+            (Some(previous), Some(current)) => tf("alert.firmware.changed", &[&previous, &current]),
+            _ => String::new(),
+        };
+
+        let mut alerts = Vec::new();
+        // Without the extensions nothing runs accelerated, so this alerts
+        // whether or not they were ever seen on
+        if info.virtualization.is_none() {
+            let message = if baseline.virtualization {
+                "alert.virtualization.lost"
+            } else {
+                "alert.virtualization.message"
+            };
+            alerts.push(Alert::new(
+                AlertLevel::Error,
+                AlertCategory::System,
+                t("alert.virtualization.title").to_string(),
+                tf(message, &[&firmware]),
+                "firmware-virtualization".to_string(),
+            ));
+        }
+        if !info.iommu && baseline.iommu {
+            alerts.push(Alert::new(
+                AlertLevel::Warning,
+                AlertCategory::System,
+                t("alert.iommu.title").to_string(),
+                tf("alert.iommu.message", &[&firmware]),
+                "firmware-iommu".to_string(),
+            ));
+        }
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "firmware"
+    }
+}
+
+/// Sysctls whose value misses the expected one in [sysctl]; most of these
+/// break pod networking or VM startup in ways that are hard to trace back
+pub struct SysctlRule {
+    pub sysctls: Vec<Sysctl>,
+}
+
+impl SysctlRule {
+    fn hint(name: &str) -> Option<&'static str> {
+        Some(t(match name {
+            "net.ipv4.ip_forward" => "alert.sysctl.hint.ip_forward",
+            n if n.starts_with("net.bridge.bridge-nf-call") => "alert.sysctl.hint.bridge_nf_call",
+            "vm.overcommit_memory" => "alert.sysctl.hint.overcommit_memory",
+            n if n.starts_with("fs.inotify.") => "alert.sysctl.hint.inotify",
+            _ => return None,
+        }))
+    }
+}
+
+impl AlertRule for SysctlRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.sysctls
+            .iter()
+            .filter(|sysctl| sysctl.mismatched())
+            .filter_map(|sysctl| {
+                let expected = sysctl.expected.as_ref()?;
+                let mut message = match sysctl.value {
+                    Some(ref value) => tf("alert.sysctl.message", &[&sysctl.name, value, expected]),
+                    None => tf("alert.sysctl.missing", &[&sysctl.name, expected]),
+                };
+                if let Some(hint) = Self::hint(&sysctl.name) {
+                    message.push_str("; ");
+                    message.push_str(hint);
+                }
+                Some(Alert::new(
+                    AlertLevel::Warning,
+                    AlertCategory::System,
+                    tf("alert.sysctl.title", &[&sysctl.name]),
+                    message,
+                    format!("sysctl-{}", sysctl.name),
+                ))
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "sysctls"
+    }
+}
+
+/// Watched systemd units (the cluster's services) that systemd reports as
+/// failed, i.e. crashed past their restart policy
+pub struct UnitRule {
+    /// Unit name and `systemctl is-active` state
+    pub services: Vec<(String, String)>,
+}
+
+impl AlertRule for UnitRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.services
+            .iter()
+            .filter(|(_, state)| state == "failed")
+            .map(|(unit, _)| {
+                Alert::new(
+                    AlertLevel::Error,
+                    AlertCategory::Service,
+                    tf("alert.unit.title", &[unit]),
+                    tf("alert.unit.message", &[unit]),
+                    format!("unit-{}", unit),
+                )
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "systemd_units"
+    }
+}
+
+/// Outcome of the last host upgrade; a failed rebuild or activation stays
+/// alerted until a later upgrade succeeds
+pub struct UpgradeRule {
+    pub failure: Option<String>,
+}
+
+impl AlertRule for UpgradeRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.failure
+            .iter()
+            .map(|reason| {
+                Alert::new(
+                    AlertLevel::Critical,
+                    AlertCategory::System,
+                    t("alert.upgrade.title").to_string(),
+                    reason.clone(),
+                    "nixos-upgrade".to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "nixos_upgrade"
+    }
+}
+
+pub struct DnsRule {
+    pub resolvers: Vec<DnsResolverStatus>,
+    pub failure_threshold: u32,  // Consecutive failed probe rounds before alerting
+}
+
+impl AlertRule for DnsRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        for resolver in &self.resolvers {
+            if resolver.consecutive_failures < self.failure_threshold {
+                continue;
+            }
+
+            let (level, title) = if resolver.label == "coredns" {
+                (AlertLevel::Critical, t("alert.coredns.title").to_string())
+            } else {
+                (AlertLevel::Warning, tf("alert.dns.title", &[&resolver.server]))
+            };
+
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::Network,
+                    title,
+                    tf(
+                        "alert.dns.message",
+                        &[
+                            &resolver.label,
+                            &resolver.server,
+                            &resolver.consecutive_failures,
+                            &resolver.last_error.as_deref().unwrap_or(t("alert.no_response")),
+                        ],
+                    ),
+                    format!("dns-{}", resolver.server),
+                )
+                .with_value(resolver.success_rate, 100.0),
+            );
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "dns_resolvers"
+    }
+}
+
+/// The host's default route went away, keeps changing, or its gateway
+/// stopped answering pings
+pub struct RouteRule {
+    pub status: RouteStatus,
+    pub gateway_failure_threshold: u32,  // Consecutive failed pings before alerting
+    pub flap_threshold: u32,  // Default route changes within the window before alerting
+}
+
+impl AlertRule for RouteRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        if self.status.default_routes().next().is_none() {
+            let (level, title, message) = if self.status.had_default {
+                (AlertLevel::Critical, "alert.route_lost.title", "alert.route_lost.message")
+            } else {
+                (AlertLevel::Warning, "alert.route_none.title", "alert.route_none.message")
+            };
+            alerts.push(Alert::new(
+                level,
+                AlertCategory::Network,
+                t(title).to_string(),
+                t(message).to_string(),
+                "route-default".to_string(),
+            ));
+        }
+
+        if self.status.default_changes >= self.flap_threshold {
+            let current: Vec<String> = self.status.default_routes().map(|route| route.describe()).collect();
+            alerts.push(
+                Alert::new(
+                    AlertLevel::Warning,
+                    AlertCategory::Network,
+                    t("alert.route_flap.title").to_string(),
+                    tf(
+                        "alert.route_flap.message",
+                        &[
+                            &self.status.default_changes,
+                            &if current.is_empty() { t("alert.none").to_string() } else { current.join(", ") },
+                        ],
+                    ),
+                    "route-flap".to_string(),
+                )
+                .with_value(self.status.default_changes as f64, self.flap_threshold as f64),
+            );
+        }
+
+        for gateway in &self.status.gateways {
+            if gateway.consecutive_failures < self.gateway_failure_threshold {
+                continue;
+            }
+            alerts.push(
+                Alert::new(
+                    AlertLevel::Error,
+                    AlertCategory::Network,
+                    tf("alert.gateway.title", &[&gateway.address]),
+                    tf(
+                        "alert.gateway.message",
+                        &[
+                            &gateway.address,
+                            &gateway.device,
+                            &gateway.consecutive_failures,
+                            &gateway.last_error.as_deref().unwrap_or(t("alert.no_reply")),
+                        ],
+                    ),
+                    format!("gateway-{}", gateway.address),
+                )
+                .with_value(gateway.consecutive_failures as f64, self.gateway_failure_threshold as f64),
+            );
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "routes"
+    }
+}
+
+// Filesystems whose usage trend says they fill up soon, well before a
+// fixed percentage threshold would notice on a large disk
+pub struct DiskForecastRule {
+    pub forecasts: Vec<(FilesystemUsage, TimeToFull)>,
+    pub warning_hours: f64,
+    pub critical_hours: f64,
+}
+
+impl AlertRule for DiskForecastRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        for (fs, forecast) in &self.forecasts {
+            let level = if forecast.hours <= self.critical_hours {
+                AlertLevel::Critical
+            } else if forecast.hours <= self.warning_hours {
+                AlertLevel::Warning
+            } else {
+                continue;
+            };
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::System,
+                    tf("alert.disk_forecast.title", &[&fs.mount_point]),
+                    tf(
+                        "alert.disk_forecast.message",
+                        &[
+                            &fs.mount_point,
+                            &fs.device,
+                            &format!("{:.1}", fs.used_percent()),
+                            &forecast.model.label(),
+                            &forecast.eta(),
+                        ],
+                    ),
+                    format!("disk-forecast-{}", fs.mount_point),
+                )
+                .with_value(forecast.hours, self.warning_hours),
+            );
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "disk_forecast"
+    }
+}
+
+// Container registry reachability and repeated image pull failures
+pub struct ImagePullRule {
+    pub registries: Vec<RegistryStatus>,
+    pub pulls: Vec<ImagePull>,
+    pub registry_failure_threshold: u32,  // Consecutive failed probes
+    pub pull_failure_threshold: u32,  // Failed pulls with no success since
+}
+
+impl AlertRule for ImagePullRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        for registry in &self.registries {
+            if registry.consecutive_failures < self.registry_failure_threshold {
+                continue;
+            }
+            alerts.push(Alert::new(
+                AlertLevel::Critical,
+                AlertCategory::Network,
+                tf("alert.registry.title", &[&registry.registry]),
+                tf(
+                    "alert.registry.message",
+                    &[
+                        &registry.registry,
+                        &registry.consecutive_failures,
+                        &registry.last_error.as_deref().unwrap_or(t("alert.no_response")),
+                    ],
+                ),
+                format!("registry-{}", registry.registry),
+            ));
+        }
+
+        for pull in &self.pulls {
+            if !pull.is_failing() || pull.failures < self.pull_failure_threshold {
+                continue;
+            }
+            // Point at the registry when it is known to be down, which the
+            // pod's ErrImagePull message rarely makes obvious
+            let registry_down = self
+                .registries
+                .iter()
+                .any(|r| !r.reachable && registry_host(&r.registry) == pull.registry);
+            let cause = if registry_down {
+                tf("alert.image_pull.registry_down", &[&pull.registry])
+            } else {
+                pull.last_error.clone().unwrap_or_else(|| t("alert.unknown_error").to_string())
+            };
+            alerts.push(
+                Alert::new(
+                    AlertLevel::Warning,
+                    AlertCategory::Kubernetes,
+                    t("alert.image_pull.title").to_string(),
+                    tf("alert.image_pull.message", &[&pull.image, &pull.failures, &cause]),
+                    format!("image-pull-{}", pull.image),
+                )
+                .with_value(pull.failures as f64, self.pull_failure_threshold as f64),
+            );
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "image_pulls"
+    }
+}
+
+/// Pods stuck in CrashLoopBackOff or an image pull backoff, or restarting
+/// often; nodes report Ready while the workloads on them keep failing
+pub struct PodRule {
+    pub pods: Vec<PodHealth>,
+    pub restarts_per_hour: u64,
+}
+
+impl AlertRule for PodRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.pods
+            .iter()
+            .filter_map(|pod| {
+                let key = pod.key();
+                let alert = match pod.waiting {
+                    Some(ref waiting) => {
+                        let level = if waiting.reason == "CrashLoopBackOff" {
+                            AlertLevel::Error
+                        } else {
+                            AlertLevel::Warning
+                        };
+                        let detail = waiting.message.as_deref().map(|m| format!(": {}", m)).unwrap_or_default();
+                        Alert::new(
+                            level,
+                            AlertCategory::Kubernetes,
+                            tf("alert.pod_waiting.title", &[&key, &waiting.reason]),
+                            tf(
+                                "alert.pod_waiting.message",
+                                &[
+                                    &waiting.container,
+                                    &key,
+                                    &waiting.reason,
+                                    &pod.restarts,
+                                    &pod.recent_restarts,
+                                    &detail,
+                                ],
+                            ),
+                            format!("pod-{}", key),
+                        )
+                    }
+                    None if pod.recent_restarts as u64 >= self.restarts_per_hour => Alert::new(
+                        AlertLevel::Warning,
+                        AlertCategory::Kubernetes,
+                        tf("alert.pod_restarts.title", &[&key]),
+                        tf("alert.pod_restarts.message", &[&key, &pod.recent_restarts, &pod.restarts]),
+                        format!("pod-restarts-{}", key),
+                    )
+                    .with_value(pod.recent_restarts as f64, self.restarts_per_hour as f64),
+                    None => return None,
+                };
+                let alert = alert.with_pod(pod.namespace.clone(), pod.name.clone());
+                Some(match pod.node {
+                    Some(ref node) => alert.with_node(node.clone()),
+                    None => alert,
+                })
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "pods"
+    }
+}
+
+/// Host part of a configured registry, which may be given as a URL
+fn registry_host(registry: &str) -> &str {
+    let host = registry.split_once("://").map_or(registry, |(_, rest)| rest);
+    host.split('/').next().unwrap_or(host)
+}
+
+// In-guest filesystem usage reported by qemu-guest-agent
+pub struct GuestFilesystemRule {
+    pub vm: String,  // namespace/name
+    pub filesystems: Vec<GuestFilesystem>,
+    pub warning_threshold: f64,
+    pub critical_threshold: f64,
+    rule_name: String,
+}
+
+impl GuestFilesystemRule {
+    pub fn new(vm: String, filesystems: Vec<GuestFilesystem>, warning_threshold: f64, critical_threshold: f64) -> Self {
+        // One rule instance per VM so auto-resolution is scoped to that VM
+        let rule_name = format!("guest_filesystems:{}", vm);
+        Self { vm, filesystems, warning_threshold, critical_threshold, rule_name }
+    }
+}
+
+impl AlertRule for GuestFilesystemRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        for fs in &self.filesystems {
+            let usage = fs.usage_percent();
+            let (level, threshold) = if usage >= self.critical_threshold {
+                (AlertLevel::Critical, self.critical_threshold)
+            } else if usage >= self.warning_threshold {
+                (AlertLevel::Warning, self.warning_threshold)
+            } else {
+                continue;
+            };
+
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::KubeVirt,
+                    tf("alert.guest_fs.title", &[&fs.mount_point]),
+                    tf(
+                        "alert.guest_fs.message",
+                        &[
+                            &self.vm,
+                            &fs.mount_point,
+                            &fs.disk_name,
+                            &format!("{:.1}", usage),
+                            &format!("{:.1}", threshold),
+                        ],
+                    ),
+                    format!("guest-fs-{}-{}", self.vm, fs.mount_point),
+                )
+                .with_value(usage, threshold)
+                .with_vm(self.vm.clone()),
+            );
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        &self.rule_name
+    }
+}
+
+/// Guests whose clock is off from the host's, net of how uncertain the
+/// reading is; guests without an agent are skipped
+pub struct GuestClockRule {
+    pub clocks: Vec<GuestClock>,
+    pub warning_secs: f64,
+    pub critical_secs: f64,
+}
+
+impl AlertRule for GuestClockRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        for clock in &self.clocks {
+            let Some(drift) = clock.drift_secs else {
+                continue;
+            };
+            let skew = (drift.abs() - clock.uncertainty_secs).max(0.0);
+            let (level, threshold) = if skew >= self.critical_secs {
+                (AlertLevel::Critical, self.critical_secs)
+            } else if skew >= self.warning_secs {
+                (AlertLevel::Warning, self.warning_secs)
+            } else {
+                continue;
+            };
+
+            alerts.push(
+                Alert::new(
+                    level,
+                    AlertCategory::KubeVirt,
+                    tf("alert.guest_clock.title", &[&clock.vm]),
+                    tf(
+                        if drift > 0.0 { "alert.guest_clock.ahead" } else { "alert.guest_clock.behind" },
+                        &[&clock.vm, &format!("{:.1}", drift.abs()), &format!("{:.0}", threshold)],
+                    ),
+                    format!("guest-clock-{}", clock.vm),
+                )
+                .with_value(skew, threshold)
+                .with_vm(clock.vm.clone()),
+            );
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "guest_clocks"
+    }
+}
+
+/// Hardware errors and NIC resets seen in the kernel log within the
+/// collector's window, one alert per kind with the latest line attached.
+/// OOM kills are raised per victim by `OomRule`.
+pub struct KernelRule {
+    pub events: Vec<HardwareEvent>,
+    /// Name the NICs a reset message is about
+    pub labels: InterfaceLabels,
+}
+
+impl AlertRule for KernelRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        HardwareErrorKind::ALL
+            .iter()
+            .filter(|&&kind| kind != HardwareErrorKind::OomKill)
+            .filter_map(|&kind| {
+                let events: Vec<&HardwareEvent> = self.events.iter().filter(|e| e.kind == kind).collect();
+                let latest = events.last()?;
+                let level = match kind {
+                    HardwareErrorKind::MachineCheck | HardwareErrorKind::UncorrectedMemory => AlertLevel::Critical,
+                    HardwareErrorKind::Io | HardwareErrorKind::OomKill => AlertLevel::Error,
+                    HardwareErrorKind::CorrectedMemory | HardwareErrorKind::NicReset => AlertLevel::Warning,
+                };
+                Some(Alert::new(
+                    level,
+                    AlertCategory::System,
+                    kind.label().to_string(),
+                    tf(
+                        "alert.kernel.message",
+                        &[&events.len(), &latest.at.format("%H:%M:%S"), &self.labels.annotate(&latest.line)],
+                    ),
+                    kind.source().to_string(),
+                ))
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "kernel"
+    }
+}
+
+/// OOM kills within the window, one Critical alert per victim naming the
+/// pod, VM, unit or cgroup it belonged to
+pub struct OomRule {
+    /// Newest first
+    pub kills: Vec<OomKill>,
+    pub window_mins: u64,
+}
+
+impl AlertRule for OomRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let cutoff = chrono::Local::now() - chrono::Duration::minutes(self.window_mins as i64);
+        let mut by_victim: BTreeMap<String, Vec<&OomKill>> = BTreeMap::new();
+        for kill in self.kills.iter().filter(|kill| kill.at >= cutoff) {
+            by_victim.entry(kill.victim()).or_default().push(kill);
+        }
+
+        by_victim
+            .into_iter()
+            .map(|(victim, kills)| {
+                let latest = kills[0];
+                let freed = latest
+                    .anon_rss_kb
+                    .map(|kb| tf("alert.oom.freed", &[&format!("{:.0}", kb as f64 / 1024.0)]))
+                    .unwrap_or_default();
+                let cause = match latest.cgroup_limit {
+                    Some(true) => t("alert.oom.cgroup_limit"),
+                    Some(false) => t("alert.oom.host_memory"),
+                    None => "",
+                };
+                let cgroup = if latest.cgroup.is_empty() { String::new() } else { format!(" (cgroup {})", latest.cgroup) };
+                let mut alert = Alert::new(
+                    AlertLevel::Critical,
+                    AlertCategory::System,
+                    tf("alert.oom.title", &[&victim]),
+                    tf(
+                        "alert.oom.message",
+                        &[
+                            &kills.len(),
+                            &self.window_mins,
+                            &latest.process(),
+                            &latest.at.format("%H:%M:%S"),
+                            &cause,
+                            &freed,
+                            &cgroup,
+                        ],
+                    ),
+                    format!("oom-{}", victim.replace(' ', "-")),
+                );
+                if let Some((namespace, pod)) = latest.pod.as_ref().and_then(|pod| pod.split_once('/')) {
+                    alert = alert.with_pod(namespace.to_string(), pod.to_string());
+                }
+                if let Some(ref vm) = latest.vm {
+                    alert = alert.with_vm(vm.clone());
+                }
+                alert
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "oom"
+    }
+}
+
+/// `[[computed_metrics]]` past their warning or critical thresholds
+pub struct ComputedMetricRule {
+    pub metrics: Vec<ComputedMetric>,
+}
+
+impl AlertRule for ComputedMetricRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.metrics
+            .iter()
+            .filter_map(|metric| {
+                let (level, threshold) = match metric.severity() {
+                    2 => (AlertLevel::Critical, metric.config.critical?),
+                    1 => (AlertLevel::Warning, metric.config.warning?),
+                    _ => return None,
+                };
+                let unit = metric.config.unit.as_deref().unwrap_or_default();
+                Some(Alert::new(
+                    level,
+                    AlertCategory::System,
+                    tf(
+                        if metric.config.below { "alert.computed.low.title" } else { "alert.computed.high.title" },
+                        &[&metric.config.name],
+                    ),
+                    tf(
+                        if metric.config.below { "alert.computed.low.message" } else { "alert.computed.high.message" },
+                        &[
+                            &metric.config.name,
+                            &metric.display(),
+                            &format!("{:.2}{}", threshold, unit),
+                            &metric.config.expr,
+                        ],
+                    ),
+                    format!("computed-{}", metric.config.name),
+                ))
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "computed"
+    }
+}
+
+/// Core dumps of the watched processes from within the window, one alert
+/// per crashing binary
+pub struct CrashRule {
+    /// Newest first
+    pub dumps: Vec<CoreDump>,
+    pub window_mins: u64,
+}
+
+impl AlertRule for CrashRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let cutoff = chrono::Local::now() - chrono::Duration::minutes(self.window_mins as i64);
+        let mut by_binary: BTreeMap<&str, Vec<&CoreDump>> = BTreeMap::new();
+        for dump in self.dumps.iter().filter(|dump| dump.critical && dump.at >= cutoff) {
+            by_binary.entry(dump.name()).or_default().push(dump);
+        }
+
+        by_binary
+            .into_iter()
+            .map(|(name, dumps)| {
+                let latest = dumps[0];
+                let pid = latest.pid.map(|pid| format!(" (pid {})", pid)).unwrap_or_default();
+                let signal = latest
+                    .signal_name()
+                    .map(|signal| tf("alert.crash.signal", &[&signal]))
+                    .unwrap_or_default();
+                let core = latest.core.as_ref().map(|core| format!(", core {}", core)).unwrap_or_default();
+                Alert::new(
+                    AlertLevel::Error,
+                    AlertCategory::System,
+                    tf("alert.crash.title", &[&name]),
+                    tf(
+                        "alert.crash.message",
+                        &[
+                            &dumps.len(),
+                            &self.window_mins,
+                            &latest.binary,
+                            &pid,
+                            &signal,
+                            &latest.at.format("%H:%M:%S"),
+                            &core,
+                            &latest.origin,
+                        ],
+                    ),
+                    format!("crash-{}", name),
+                )
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "crashes"
+    }
+}
+
+/// Firing alerts posted to the webhook receiver, one alert each. The level
+/// comes from the `severity` label and the category from a `category`
+/// label naming one of ours, otherwise External.
+pub struct ExternalAlertRule {
+    pub alerts: Vec<ExternalAlert>,
+}
+
+impl AlertRule for ExternalAlertRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.alerts
+            .iter()
+            .filter(|alert| alert.firing)
+            .map(|alert| {
+                let level = match alert.labels.get("severity").map(|s| s.to_ascii_lowercase()).as_deref() {
+                    Some("critical" | "page" | "disaster") => AlertLevel::Critical,
+                    Some("error" | "high" | "major") => AlertLevel::Error,
+                    Some("info" | "informational" | "low" | "none") => AlertLevel::Info,
+                    _ => AlertLevel::Warning,
+                };
+                let category = match alert.labels.get("category").map(|s| s.to_ascii_lowercase()).as_deref() {
+                    Some("system") => AlertCategory::System,
+                    Some("network") => AlertCategory::Network,
+                    Some("kubernetes") => AlertCategory::Kubernetes,
+                    Some("kubevirt") => AlertCategory::KubeVirt,
+                    Some("service") => AlertCategory::Service,
+                    _ => AlertCategory::External,
+                };
+                // Where it fired, from the labels Prometheus usually attaches
+                let place: Vec<&str> = ["namespace", "pod", "node", "instance", "job"]
+                    .iter()
+                    .filter_map(|key| alert.labels.get(*key).map(String::as_str))
+                    .take(2)
+                    .collect();
+                let place = if place.is_empty() {
+                    String::new()
+                } else {
+                    tf("alert.external.place", &[&place.join("/")])
+                };
+                let summary = if alert.summary.is_empty() {
+                    String::new()
+                } else {
+                    format!(": {}", alert.summary)
+                };
+                let mut raised = Alert::new(
+                    level,
+                    category,
+                    alert.name.clone(),
+                    tf("alert.external.message", &[&alert.sender, &place, &summary]),
+                    format!("external-{}", alert.key),
+                );
+                if let Some(node) = alert.labels.get("node") {
+                    raised = raised.with_node(node.clone());
+                }
+                if let (Some(namespace), Some(pod)) = (alert.labels.get("namespace"), alert.labels.get("pod")) {
+                    raised = raised.with_pod(namespace.clone(), pod.clone());
+                }
+                raised
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "external"
+    }
+}
+
+/// Agents in `[remote_agents]` that stopped answering, and the host alerts
+/// the others report, attributed to the agent's host
+pub struct AgentRule {
+    pub agents: Vec<AgentStatus>,
+    pub failure_threshold: u32,
+}
+
+impl AlertRule for AgentRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for agent in &self.agents {
+            if agent.consecutive_failures >= self.failure_threshold {
+                alerts.push(
+                    Alert::new(
+                        AlertLevel::Warning,
+                        AlertCategory::System,
+                        tf("alert.agent.title", &[&agent.endpoint]),
+                        tf(
+                            "alert.agent.message",
+                            &[&agent.consecutive_failures, &agent.error.as_deref().unwrap_or(t("alert.no_response"))],
+                        ),
+                        format!("agent-{}", agent.endpoint),
+                    )
+                    .with_value(agent.consecutive_failures as f64, self.failure_threshold as f64),
+                );
+                continue;
+            }
+            // A stale snapshot's alerts may have cleared since
+            let Some(snapshot) = agent.snapshot.as_ref().filter(|_| agent.error.is_none()) else {
+                continue;
+            };
+            for alert in &snapshot.alerts {
+                let mut raised = Alert::new(
+                    alert.level,
+                    alert.category,
+                    format!("{}: {}", snapshot.host, alert.title),
+                    alert.message.clone(),
+                    format!("agent-{}-{}", snapshot.host, alert.metadata.source),
+                )
+                .with_node(snapshot.host.clone());
+                if let (Some(value), Some(threshold)) = (alert.metadata.value, alert.metadata.threshold) {
+                    raised = raised.with_value(value, threshold);
+                }
+                alerts.push(raised);
+            }
+        }
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "remote_agents"
+    }
+}
+
+/// Ways the host differs from `[host_profile]`, one alert per check
+pub struct HostProfileRule {
+    pub deviations: Vec<ProfileDeviation>,
+}
+
+impl AlertRule for HostProfileRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        self.deviations
+            .iter()
+            .map(|deviation| {
+                Alert::new(
+                    AlertLevel::Warning,
+                    AlertCategory::System,
+                    tf("alert.host_profile.title", &[&deviation.check]),
+                    tf("alert.host_profile.message", &[&deviation.check, &deviation.actual, &deviation.expected]),
+                    format!("profile-{}", deviation.check.replace(' ', "-")),
+                )
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "host-profile"
+    }
+}
+
+/// Daemons from `[memory_leaks]` whose resident memory keeps climbing, or
+/// is past its configured limit
+pub struct MemoryLeakRule {
+    pub processes: Vec<(ProcessMemory, Option<MemoryGrowth>)>,
+    pub growth_mb_per_hour: f64,
+    pub window_hours: u64,
+    pub limits_mb: BTreeMap<String, u64>,
+}
+
+impl AlertRule for MemoryLeakRule {
+    fn evaluate(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        for (process, growth) in &self.processes {
+            let source = format!("leak-{}", process.name);
+            let rss_mb = process.rss_mb();
+            let leaking = growth.filter(|g| g.steady && g.mb_per_hour >= self.growth_mb_per_hour);
+
+            if let Some(limit) = self.limits_mb.get(&process.name).filter(|limit| rss_mb >= **limit as f64) {
+                let trend = match growth.filter(|g| g.mb_per_hour > 0.0) {
+                    Some(g) => tf("alert.memory_limit.growing", &[&format!("{:.1}", g.mb_per_hour)]),
+                    None => String::new(),
+                };
+                alerts.push(
+                    Alert::new(
+                        AlertLevel::Error,
+                        AlertCategory::System,
+                        tf("alert.memory_limit.title", &[&process.name]),
+                        tf("alert.memory_limit.message", &[&process.name, &format!("{:.0}", rss_mb), limit, &trend]),
+                        source,
+                    )
+                    .with_value(rss_mb, *limit as f64),
+                );
+            } else if let Some(growth) = leaking {
+                alerts.push(
+                    Alert::new(
+                        AlertLevel::Warning,
+                        AlertCategory::System,
+                        tf("alert.memory_leak.title", &[&process.name]),
+                        tf(
+                            "alert.memory_leak.message",
+                            &[
+                                &process.name,
+                                &format!("{:.0}", growth.start_mb),
+                                &format!("{:.0}", growth.current_mb),
+                                &self.window_hours,
+                                &format!("{:.1}", growth.mb_per_hour),
+                                &format!("{:.0}", growth.mb_per_hour * 24.0),
+                            ],
+                        ),
+                        source,
+                    )
+                    .with_value(growth.mb_per_hour, self.growth_mb_per_hour),
+                );
+            }
+        }
+
+        alerts
+    }
+
+    fn name(&self) -> &str {
+        "memory-leak"
+    }
+}
@@ -0,0 +1,107 @@
+use super::types::{Alert, AlertLevel};
+use chrono::{DateTime, Duration, Local};
+use std::collections::HashMap;
+
+/// A fire this soon after the previous resolution counts as a flap
+const FLAP_GAP_MINUTES: i64 = 30;
+/// Flaps over the week before a source is called out as flapping
+const FLAPPING_MIN_FLAPS: usize = 3;
+
+/// How often one alert source fired and how it behaved, for tuning
+/// thresholds that are too tight
+#[derive(Debug, Clone)]
+pub struct AlertStatistics {
+    pub source: String,
+    /// Title of the most recent alert from this source
+    pub title: String,
+    /// Most severe level the source reached
+    pub level: AlertLevel,
+    pub fires_24h: usize,
+    pub fires_7d: usize,
+    /// Over the fires of the last 7 days that resolved on their own
+    pub mean_time_to_resolve: Option<Duration>,
+    /// Fires within `FLAP_GAP_MINUTES` of the previous one resolving
+    pub flaps_7d: usize,
+    /// Share of fires that were flaps, 0.0 to 1.0
+    pub flappiness: f64,
+}
+
+impl AlertStatistics {
+    pub fn is_flapping(&self) -> bool {
+        self.flaps_7d >= FLAPPING_MIN_FLAPS && self.flappiness >= 0.5
+    }
+}
+
+/// One firing of a source: severity upgrades replace the alert but
+/// continue the same episode
+struct Episode {
+    start: DateTime<Local>,
+    /// When it auto-resolved; None while active or if it was dismissed
+    resolved: Option<DateTime<Local>>,
+    open: bool,
+}
+
+/// Statistics per source over the last 7 days, flappiest first
+pub fn alert_statistics<'a>(alerts: impl Iterator<Item = &'a Alert>, now: DateTime<Local>) -> Vec<AlertStatistics> {
+    let week_ago = now - Duration::days(7);
+    let day_ago = now - Duration::days(1);
+
+    let mut by_source: HashMap<&str, Vec<&Alert>> = HashMap::new();
+    for alert in alerts.filter(|a| a.triggered_at > week_ago) {
+        by_source.entry(alert.metadata.source.as_str()).or_default().push(alert);
+    }
+
+    let mut stats: Vec<AlertStatistics> = by_source
+        .into_iter()
+        .map(|(source, mut alerts)| {
+            alerts.sort_by_key(|a| a.triggered_at);
+
+            let mut episodes: Vec<Episode> = Vec::new();
+            for alert in &alerts {
+                let continues = episodes
+                    .last()
+                    .is_some_and(|last| last.open || last.resolved.is_some_and(|end| alert.triggered_at <= end));
+                let open = alert.is_active();
+                if let (true, Some(last)) = (continues, episodes.last_mut()) {
+                    last.resolved = alert.resolved_at;
+                    last.open = open;
+                } else {
+                    episodes.push(Episode { start: alert.triggered_at, resolved: alert.resolved_at, open });
+                }
+            }
+
+            let flaps_7d = episodes
+                .windows(2)
+                .filter(|pair| {
+                    pair[0]
+                        .resolved
+                        .is_some_and(|end| pair[1].start - end <= Duration::minutes(FLAP_GAP_MINUTES))
+                })
+                .count();
+            let resolve_times: Vec<Duration> = episodes.iter().filter_map(|e| Some(e.resolved? - e.start)).collect();
+            let mean_time_to_resolve = (!resolve_times.is_empty()).then(|| {
+                resolve_times.iter().fold(Duration::zero(), |sum, d| sum + *d) / resolve_times.len() as i32
+            });
+
+            let latest = alerts.last().expect("grouped sources have alerts");
+            AlertStatistics {
+                source: source.to_string(),
+                title: latest.title.clone(),
+                level: alerts.iter().map(|a| a.level).max_by_key(|l| *l as u8).unwrap_or(latest.level),
+                fires_24h: episodes.iter().filter(|e| e.start > day_ago).count(),
+                fires_7d: episodes.len(),
+                mean_time_to_resolve,
+                flaps_7d,
+                flappiness: flaps_7d as f64 / episodes.len() as f64,
+            }
+        })
+        .collect();
+
+    stats.sort_by(|a, b| {
+        b.flappiness
+            .total_cmp(&a.flappiness)
+            .then(b.fires_7d.cmp(&a.fires_7d))
+            .then(a.source.cmp(&b.source))
+    });
+    stats
+}
@@ -27,6 +27,7 @@ pub enum AlertCategory {
     Kubernetes,  // K8s cluster issues
     KubeVirt,    // VM issues
     Service,     // Service failures
+    External,    // Posted by Alertmanager or Grafana
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -58,7 +59,19 @@ pub struct AlertMetadata {
     pub threshold: Option<f64>,
     pub node_name: Option<String>,
     pub pod_name: Option<String>,
+    /// Namespace of the pod
+    #[serde(default)]
+    pub namespace: Option<String>,
     pub vm_name: Option<String>,
+    /// Where the procedure for this alert is documented, from config
+    #[serde(default)]
+    pub runbook_url: Option<String>,
+    #[serde(default)]
+    pub remediation: Option<String>,
+    /// Name of the rule that raised it; None for alerts from outside the
+    /// rule engine (e.g. notification delivery failures)
+    #[serde(default)]
+    pub rule: Option<String>,
 }
 
 impl Alert {
@@ -92,7 +105,11 @@ impl Alert {
                 threshold: None,
                 node_name: None,
                 pod_name: None,
+                namespace: None,
                 vm_name: None,
+                runbook_url: None,
+                remediation: None,
+                rule: None,
             },
         }
     }
@@ -108,7 +125,13 @@ impl Alert {
         self
     }
 
-    pub fn with_pod(mut self, pod_name: String) -> Self {
+    pub fn with_rule(mut self, rule: &str) -> Self {
+        self.metadata.rule = Some(rule.to_string());
+        self
+    }
+
+    pub fn with_pod(mut self, namespace: String, pod_name: String) -> Self {
+        self.metadata.namespace = Some(namespace);
         self.metadata.pod_name = Some(pod_name);
         self
     }
@@ -152,6 +175,7 @@ impl AlertCategory {
             AlertCategory::Kubernetes => "kubernetes",
             AlertCategory::KubeVirt => "kubevirt",
             AlertCategory::Service => "service",
+            AlertCategory::External => "external",
         }
     }
 }
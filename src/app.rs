@@ -1,92 +1,1362 @@
 use anyhow::Result;
-use crate::alerts::{AlertManager, SystemAlert};
-use crate::collectors::{LogCollector, SystemCollector, NetworkCollector, KubernetesCollector};
-use crate::config::Config;
-use crate::types::{LogEntry, SystemMetrics, NetworkInfo, K8sClusterInfo, KubeVirtInfo};
-use crate::metrics_history::MetricsHistory;
+use crate::actions::AutoActions;
+use crate::audit::{AuditEntry, AuditLog};
+use crate::alert_profiles::AlertProfiles;
+use crate::alert_sweep::{AlertSweep, SweepGroup};
+use crate::alerts::{AgentRule, Alert, AlertCategory, AlertLevel, ApiHealthRule, BridgeConflictRule, ComputedMetricRule, CrashRule, AlertManager, DataVolumeRule, AnomalyConfig, DiskForecastRule, DiskLatencyRule, DnsRule, EtcdRule, ExternalAlertRule, FirmwareRule, FreqThrottleRule, GuestClockRule, GuestFilesystemRule, HostProfileRule, ImagePullRule, IoSchedulerRule, KernelRule, KubeletRule, LimitsRule, MemoryLeakRule, MigrationRule, MtuRule, MultipathRule, NodeConditionRule, OomRule, PodRule, QuotaRule, RouteRule, SchedLatencyRule, ServiceEndpointRule, SlowBootRule, SocketRule, SriovRule, SysctlRule, SystemAlert, TenantRule, UnitRule, UpgradeRule, VipRule};
+use crate::collectors::{AgentCollector, ServiceCategory, context_window, LogCollector, SystemCollector, NetworkCollector, KubernetesCollector, DnsCollector, RouteCollector, RegistryCollector, VipCollector, CgroupCollector, HostCollector, PowerCollector, QemuCollector, ImageCollector, SocketCollector, LimitsCollector, MultipathCollector, DiskStatsCollector, SysctlCollector, FirmwareCollector, HostProfileCollector, ProcessMemoryCollector, EtcdCollector, KmsgCollector, CrashCollector, OomCollector, PrometheusCollector, BridgeWatchCollector, signal_qemu, SriovCollector, CustomCommandCollector, delete_host_image, resolve_inventory};
+use crate::batch::{BatchPrompt, BatchRun};
+use crate::compare::{sorted, CompareKind, CompareView, NodeSnapshot, SnapshotHistory};
+use crate::computed::ComputedMetric;
+use crate::cli::CliArgs;
+use crate::config::{AlertsConfig, CommandParser, Config, CustomCommandConfig, EvacuationConfig, HeaderWidget, HeaderWidgetConfig, HealthConfig, ImageCatalogConfig, ReportConfig, SelfTestConfig, TenantLimits, TenantsConfig, UpgradeConfig};
+use crate::display_text::DisplayText;
+use crate::health::HealthScore;
+use crate::highlight::LogHighlighter;
+use crate::image_catalog::{CatalogOp, ImageCatalogPanel, ImageDownload};
+use crate::i18n::{t, tf};
+use crate::redact::LogRedactor;
+use crate::log_archive::{LogArchive, LogRangePrompt};
+use crate::log_search::LogSearch;
+use crate::log_filter::{LogFilterWorker, LogQuery, BACKGROUND_FILTER_MIN, WINDOW_FORMAT};
+use crate::log_forward::LogForwarder;
+use crate::evacuate::Evacuation;
+use crate::report;
+use crate::secrets::Secrets;
+use crate::selftest::SelfTestRun;
+use crate::object_watch::ObjectWatch;
+use crate::ui_state::UiState;
+use crate::views::{save_view, SavedView};
+use crate::theme::{AlertStyles, Theme};
+use crate::palette::{CommandPalette, PaletteAction, PaletteEntry, EVACUATION_PERMISSIONS, SELFTEST_PERMISSIONS};
+use crate::panes::{Pane, PaneFocus};
+use crate::permissions::Permissions;
+use crate::presence::Presence;
+use crate::receiver::WebhookReceiver;
+use crate::validation::ConfigReport;
+use crate::types::{object_name_problem, redact_secrets, AgentStatus, ApiHealth, CloneDisk, CloudInit, ClusterFlavor, LabelSelector, LogEntry, SystemMetrics, NetworkInfo, K8sClusterInfo, KubeVirtInfo, K8sResource, ResourceKind, DnsResolverStatus, CollectorTiming, ConsoleCapture, ConsoleEndpoint, CgroupUsage, CoreDump, CpuTuning, DataVolume, VmHotplug, DiskImage, DiskImageKind, DiskLatency, DiskReferences, EtcdStatus, ExternalAlert, GuestClock, GuestInfo, HardwareInfo, HostInfo, HostLimits, ImagePull, InterfaceLabels, KubeletStatus, PodHealth, MigrationRecord, MultipathInfo, NamespacePolicies, OomKill, NodeMetadata, PrometheusMetrics, NodeStatus, NodeTaint, Permission, PowerInfo, ProcessMemory, ProfileDeviation, QemuProcess, QemuSignal, QuotaUsage, RegistryStatus, RouteStatus, ServiceHealth, SocketUsage, SriovNic, Sysctl, TenantUsage, UserDataSource, VipStatus, VmAction, VmClone, VmUsage, VncForward};
+use crate::layout::{DashboardLayout, DashboardWidget};
+use crate::boots::BootHistory;
+use crate::migrations::MigrationHistory;
+use crate::ui;
+use crate::navigation::{Motion, Navigator};
+use crate::metrics_history::{AlertMarker, ChartMetric, MetricsHistory, Zoom};
+use crate::notify::Notifier;
+use crate::telemetry::{rss_bytes, BufferUsage, CollectorSample, Telemetry, TelemetrySnapshot};
+use crate::timeline::{MetricThresholds, Timeline, TimelineEvent};
+use crate::upgrade::{UpgradeAction, UpgradeRun};
+use crate::version_check::UpdateChecker;
+use crate::nix_store::{NixStoreOp, NixStorePanel, NixStoreRun};
+use crate::web::{WebBridge, WebCluster, WebSnapshot, WebSystem};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Screen {
     Logs,
     Dashboard,
     Network,
+    Resources,
+    Timeline,
+    Charts,
+    Heatmap,
+    Fleet,
+    HostMap,
+    Tools,
+}
+
+impl Screen {
+    /// In F-key order
+    pub const ALL: [Screen; 10] = [
+        Screen::Logs,
+        Screen::Dashboard,
+        Screen::Network,
+        Screen::Resources,
+        Screen::Timeline,
+        Screen::Charts,
+        Screen::Heatmap,
+        Screen::Fleet,
+        Screen::HostMap,
+        Screen::Tools,
+    ];
+
+    /// Name in the footer and the command palette, in the UI locale
+    pub fn title(&self) -> &'static str {
+        t(match self {
+            Screen::Logs => "screen.logs",
+            Screen::Dashboard => "screen.dashboard",
+            Screen::Network => "screen.network",
+            Screen::Resources => "screen.resources",
+            Screen::Timeline => "screen.timeline",
+            Screen::Charts => "screen.charts",
+            Screen::Heatmap => "screen.heatmap",
+            Screen::Fleet => "screen.fleet",
+            Screen::HostMap => "screen.hostmap",
+            Screen::Tools => "screen.tools",
+        })
+    }
+}
+
+/// Text box open over the resource list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceInput {
+    /// Label selector the list is filtered by (`/`)
+    Selector,
+    /// Label key the list is grouped by (`l`)
+    GroupBy,
+}
+
+/// A run of resources sharing a value of the group-by label, with how
+/// many are in each status
+#[derive(Debug, Clone)]
+pub struct ResourceGroup {
+    /// None for resources without the label, listed last
+    pub value: Option<String>,
+    pub start: usize,
+    pub len: usize,
+    pub statuses: BTreeMap<String, usize>,
+}
+
+/// Host Map sub-view, cycled with Tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostMapView {
+    Processes,
+    Images,
+    /// Resident memory of the daemons watched for leaks
+    Memory,
+}
+
+/// Value the VM heatmap is colored by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeatmapMetric {
+    Cpu,
+    Memory,
+}
+
+impl HeatmapMetric {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HeatmapMetric::Cpu => "CPU",
+            HeatmapMetric::Memory => "Memory",
+        }
+    }
+
+    pub fn value(&self, usage: &VmUsage) -> Option<f64> {
+        match self {
+            HeatmapMetric::Cpu => usage.cpu_percent,
+            HeatmapMetric::Memory => usage.memory_percent,
+        }
+    }
+}
+
+/// Most toasts kept on screen at once; older ones are dropped first
+const MAX_MESSAGES: usize = 5;
+
+/// Recent log lines included in web bridge snapshots
+const WEB_LOG_LINES: usize = 50;
+
+/// Services listed in the Logs screen's noisy sources widget
+pub const NOISY_SOURCES: usize = 5;
+
+/// Tenant footprints are sampled at most this often for growth alerts
+const TENANT_SAMPLE_SECS: u64 = 300;
+
+/// How often VMIs are listed for boot times
+const BOOT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Severity of a status message, which also sets how long it stays up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl MessageLevel {
+    fn lifetime(&self) -> Duration {
+        match self {
+            MessageLevel::Info => Duration::from_secs(4),
+            MessageLevel::Warning => Duration::from_secs(6),
+            MessageLevel::Error => Duration::from_secs(10),
+        }
+    }
+}
+
+/// Transient status message shown as a toast until it expires
+pub struct Message {
+    pub level: MessageLevel,
+    pub text: String,
+    expires: Instant,
+}
+
+/// What polling a background run turned up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunProgress {
+    Unchanged,
+    Changed,
+    /// The run just ended and what it worked on has to be read again
+    Finished,
+}
+
+/// Column the Fleet table is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FleetSort {
+    Name,
+    Status,
+    Cpu,
+    Memory,
+    Vms,
+    Alerts,
+}
+
+impl FleetSort {
+    pub const ALL: [FleetSort; 6] = [
+        FleetSort::Name,
+        FleetSort::Status,
+        FleetSort::Cpu,
+        FleetSort::Memory,
+        FleetSort::Vms,
+        FleetSort::Alerts,
+    ];
+
+    fn next(&self) -> FleetSort {
+        let idx = Self::ALL.iter().position(|s| s == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// State for the YAML inspector popup
+pub struct YamlInspector {
+    pub resource: K8sResource,
+    pub content: String,
+    pub scroll: usize,
+    pub status: Option<String>,
+    /// An edit whose apply conflicted with another field manager, kept
+    /// for applying again with force
+    pub conflicted: Option<String>,
+}
+
+/// Cloud-init user and network data of a VM, shown redacted
+pub struct CloudInitViewer {
+    pub resource: K8sResource,
+    /// The VM as fetched, patched on save
+    pub vm: Option<serde_json::Value>,
+    pub cloud_init: Option<CloudInit>,
+    pub scroll: usize,
+    pub status: Option<String>,
+}
+
+impl CloudInitViewer {
+    /// What the popup shows: redacted user data, then network data
+    pub fn content(&self) -> String {
+        let Some(ref cloud_init) = self.cloud_init else {
+            return String::new();
+        };
+        let user_data = match cloud_init.source {
+            UserDataSource::Secret(ref secret) => format!("# from Secret {} (not shown)", secret),
+            _ => redact_secrets(&cloud_init.user_data),
+        };
+        match cloud_init.network_data {
+            Some(ref network) => format!("{}\n\n# networkData\n{}", user_data, redact_secrets(network)),
+            None => user_data,
+        }
+    }
+}
+
+/// Surrounding unfiltered lines from the same service as a selected log line
+pub struct LogContext {
+    pub anchor: Arc<LogEntry>,
+    pub lines: Vec<Arc<LogEntry>>,
+    /// Position of the anchor within `lines`
+    pub anchor_index: usize,
+    /// Where the context came from ("journal" or "buffer")
+    pub source: &'static str,
+}
+
+/// A log line opened with Enter: the untruncated message and, for journal
+/// lines, every field of the record
+pub struct LogDetail {
+    pub entry: Arc<LogEntry>,
+    /// Journal fields by name; empty for lines without a cursor
+    pub journal: Vec<(String, String)>,
+    /// Why there are no journal fields, when a lookup failed
+    pub journal_error: Option<String>,
+    pub scroll: usize,
+}
+
+/// One unit in the log services picker
+pub struct LogServiceEntry {
+    pub category: ServiceCategory,
+    pub name: String,
+    pub enabled: bool,
+}
+
+/// State for the log services picker: every discovered unit, grouped by
+/// category, with the ones the log collector follows checked
+pub struct LogServicePicker {
+    /// Sorted by category, then name
+    pub entries: Vec<LogServiceEntry>,
+    pub selected: usize,
+    /// Set when units couldn't be discovered and only known ones are listed
+    pub status: Option<String>,
+}
+
+/// CPU models offered by the tuning editor; None is the cluster default
+const CPU_MODELS: [Option<&str>; 3] = [None, Some("host-passthrough"), Some("host-model")];
+const HUGEPAGE_SIZES: [Option<&str>; 3] = [None, Some("2Mi"), Some("1Gi")];
+
+/// Fields of the CPU tuning editor, in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuTuningField {
+    DedicatedCpus,
+    NumaPassthrough,
+    Model,
+    Hugepages,
+}
+
+impl CpuTuningField {
+    pub const ALL: [CpuTuningField; 4] = [
+        CpuTuningField::DedicatedCpus,
+        CpuTuningField::NumaPassthrough,
+        CpuTuningField::Model,
+        CpuTuningField::Hugepages,
+    ];
+}
+
+/// State for the VM CPU/NUMA tuning editor
+pub struct CpuTuningEditor {
+    pub resource: K8sResource,
+    /// Settings as last read from the cluster
+    pub original: CpuTuning,
+    pub tuning: CpuTuning,
+    pub selected: usize,
+    pub status: Option<String>,
+}
+
+impl CpuTuningEditor {
+    pub fn selected_field(&self) -> CpuTuningField {
+        CpuTuningField::ALL[self.selected]
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.tuning != self.original
+    }
+}
+
+/// Memory is hot-plugged in steps of this many MiB
+const HOTPLUG_MEMORY_STEP_MIB: u64 = 1024;
+
+/// Fields of the hot-plug popup, in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotplugField {
+    Sockets,
+    Memory,
+    Volume,
+}
+
+impl HotplugField {
+    pub const ALL: [HotplugField; 3] = [HotplugField::Sockets, HotplugField::Memory, HotplugField::Volume];
+}
+
+/// State for the live resize popup of a running VM: sockets and guest
+/// memory to patch, and a claim to hot-attach
+pub struct HotplugEditor {
+    pub resource: K8sResource,
+    /// As last read from the cluster; None when that failed
+    pub hotplug: Option<VmHotplug>,
+    pub sockets: u32,
+    pub memory_mib: u64,
+    pub claim: String,
+    pub selected: usize,
+    pub status: Option<String>,
+}
+
+impl HotplugEditor {
+    pub fn selected_field(&self) -> HotplugField {
+        HotplugField::ALL[self.selected]
+    }
+
+    pub fn resized(&self) -> bool {
+        self.hotplug
+            .as_ref()
+            .is_some_and(|h| self.sockets != h.sockets || self.memory_mib != h.memory_mib)
+    }
+
+    /// Why the pending changes can't be applied live
+    pub fn problem(&self) -> Option<String> {
+        let hotplug = self.hotplug.as_ref()?;
+        if self.resized() {
+            if let Some(problem) = hotplug.resize_problem() {
+                return Some(problem);
+            }
+        }
+        let claim = self.claim.trim();
+        if !claim.is_empty() {
+            if let Some(problem) = hotplug.volume_problem() {
+                return Some(problem);
+            }
+            if let Some(problem) = object_name_problem(claim) {
+                return Some(format!("claim {}", problem));
+            }
+            if hotplug.hotplugged.iter().any(|v| v == claim) {
+                return Some(format!("{} is already attached", claim));
+            }
+        }
+        None
+    }
+}
+
+/// State for the clone-VM popup: the new name being typed and whether
+/// claim-backed disks are cloned or shared
+pub struct VmCloneEditor {
+    pub resource: K8sResource,
+    /// The source VM as read when the popup opened
+    pub source: Option<serde_json::Value>,
+    pub name: String,
+    pub clone_disks: bool,
+    pub status: Option<String>,
+}
+
+impl VmCloneEditor {
+    pub fn plan(&self) -> Option<VmClone> {
+        let source = self.source.as_ref()?;
+        Some(VmClone::from_vm(source, self.name.trim(), self.clone_disks))
+    }
+}
+
+/// What the node editor prompt is adding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeEditField {
+    Label,
+    Taint,
+}
+
+/// State for the node labels and taints editor
+pub struct NodeEditor {
+    pub node: String,
+    pub metadata: NodeMetadata,
+    /// Row under the cursor, labels first and then taints
+    pub selected: usize,
+    /// A new label (`key=value`) or taint (`key[=value]:Effect`) being typed
+    pub input: Option<(NodeEditField, String)>,
+    pub status: Option<String>,
+}
+
+impl NodeEditor {
+    pub fn row_count(&self) -> usize {
+        self.metadata.labels.len() + self.metadata.taints.len()
+    }
+}
+
+/// Step to the next option after `current`, keeping unknown values
+/// (set outside the editor) reachable by cycling back to them
+fn cycle_option(options: &[Option<&str>], current: &Option<String>, original: &Option<String>) -> Option<String> {
+    let mut choices: Vec<Option<String>> = options.iter().map(|o| o.map(String::from)).collect();
+    if !choices.contains(original) {
+        choices.push(original.clone());
+    }
+    let index = choices.iter().position(|c| c == current).unwrap_or(0);
+    choices[(index + 1) % choices.len()].clone()
+}
+
+/// State for the VM detail popup
+pub struct VmDetail {
+    pub resource: K8sResource,
+    pub guest: Option<GuestInfo>,
+    /// Why guest info is unavailable (e.g. no guest agent connected)
+    pub error: Option<String>,
+    /// Serial console output captured so far
+    pub console: Option<ConsoleCapture>,
+    /// Showing the console instead of guest info
+    pub show_console: bool,
+    /// Console lines scrolled back from the end; 0 follows new output
+    pub console_scroll: usize,
+    /// Host sockets and proxy ports its consoles are reachable on
+    pub endpoints: Vec<ConsoleEndpoint>,
+    /// The `virtctl vnc` port-forward started with `v`
+    pub vnc_forward: Option<VncForward>,
 }
 
 pub struct App {
     pub current_screen: Screen,
-    pub scroll_offset: usize,
+    /// Focused pane per screen and each pane's scroll position
+    pub panes: PaneFocus,
+    /// Counts, gg/G and held-key acceleration for list navigation
+    pub navigator: Navigator,
     pub search_query: String,
     pub search_active: bool,
     pub filter_level: Option<String>,
+    pub logs_collapsed: bool,
+    /// Soft-wrap long messages instead of cutting them at the edge
+    pub logs_wrapped: bool,
+    /// Columns of each message scrolled past while not wrapped
+    pub log_hscroll: usize,
+    /// (top entry, its rows scrolled past) in the wrapped list
+    log_wrap_scroll: (usize, usize),
+    pub log_context: Option<LogContext>,
+    pub log_detail: Option<LogDetail>,
+    log_context_lines: usize,
+    pub log_services: Option<LogServicePicker>,
+    pub log_highlighter: LogHighlighter,
+
+    /// ASCII glyphs, no color, textual level prefixes, redraw only on change
+    pub accessible: bool,
+
+    /// Terminal has a light background; colors are remapped for contrast
+    pub light_theme: bool,
+    /// Theme switched to from the palette, remembered over `display.theme`
+    chosen_theme: Option<Theme>,
+    /// Save the working view at quit and restore it at the next start
+    remember_state: bool,
+    /// Read the journal on every screen for the footer's log rates
+    pub footer_log_rates: bool,
+    /// Seconds per alert title in the banner; 0 doesn't cycle
+    pub banner_cycle_secs: u64,
+
+    /// Alert colors and icons from `[display.alert_styles]`
+    pub alert_styles: AlertStyles,
+
+    /// Restart the event loop after a panic instead of exiting
+    pub restart_on_panic: bool,
+    /// Whether anyone is watching, for the slow idle cadence
+    pub presence: Presence,
+
+    /// Mutating actions are disabled and their keybindings hidden
+    pub read_only: bool,
 
     // Alert system
     pub alert_manager: AlertManager,
     pub alert_panel_open: bool,
     pub alert_selected_index: usize,
+    /// Alert opened from the panel for its full metadata, by id
+    pub alert_detail: Option<String>,
+    alert_snooze_mins: u64,
+    alert_related_logs_mins: u64,
+    alert_profiles: AlertProfiles,
+    /// Profile laid over the config's [alerts] table, if any
+    pub alert_profile: Option<String>,
+    /// [alerts] as configured, what profiles are laid over
+    config_alerts: AlertsConfig,
+    /// Per-source alert statistics shown over the alert panel
+    pub alert_stats_open: bool,
+    /// Looks for newer releases when `update_check.enabled`
+    pub update_checker: Option<UpdateChecker>,
+    /// Release notes of the newer release
+    pub update_popup: bool,
+    /// What the kubeconfig may do, from the RBAC self-audit
+    pub permissions: Permissions,
+    /// Traffic light in the header; None until the first update
+    pub health: Option<HealthScore>,
+    health_config: HealthConfig,
+    notifier: Notifier,
+    /// `[[alerts.actions]]`, none in read-only mode
+    auto_actions: AutoActions,
+    /// Serves snapshots to browsers when `web.enabled`
+    web_bridge: Option<WebBridge>,
+    /// Accepts Alertmanager and Grafana webhooks when `receiver.enabled`
+    webhook_receiver: Option<WebhookReceiver>,
+    /// Firing alerts posted to the receiver, by key
+    external_alerts: BTreeMap<String, ExternalAlert>,
+    external_expiry: chrono::Duration,
+
+    // Resource browser
+    pub resource_kind: ResourceKind,
+    pub resource_selected_index: usize,
+    /// Label selector the resource list is filtered by, as typed
+    pub resource_selector: String,
+    /// Why `resource_selector` doesn't parse; the last valid one stays applied
+    pub resource_selector_error: Option<String>,
+    resource_label_selector: LabelSelector,
+    /// Label key the resource list is grouped by, empty for a flat list
+    pub resource_group_by: String,
+    pub resource_input: Option<ResourceInput>,
+    pub yaml_inspector: Option<YamlInspector>,
+    /// Live diff of the object being watched
+    pub object_watch: Option<ObjectWatch>,
+    pub cloud_init: Option<CloudInitViewer>,
+    pub vm_detail: Option<VmDetail>,
+    pub cpu_tuning: Option<CpuTuningEditor>,
+    pub vm_clone: Option<VmCloneEditor>,
+    pub hotplug: Option<HotplugEditor>,
+    pub node_editor: Option<NodeEditor>,
+
+    // VM multi-select ("namespace/name") and the batch action being chosen
+    // or running against it
+    pub vm_marked: HashSet<String>,
+    pub batch_prompt: Option<BatchPrompt>,
+    pub batch_run: Option<BatchRun>,
+
+    // Host upgrade: the action prompt and the nixos-rebuild running or
+    // last finished
+    pub upgrade_prompt: bool,
+    pub upgrade_run: Option<UpgradeRun>,
+    /// Generations panel (`G`): system generations, garbage collection
+    pub nix_store: Option<NixStorePanel>,
+    pub upgrade_config: UpgradeConfig,
+    /// Cloud image catalog (`C` on the host map's images): downloads and
+    /// DataVolume imports
+    pub image_catalog: Option<ImageCatalogPanel>,
+    image_catalog_config: ImageCatalogConfig,
+    /// `image_catalog.download_dir`, else the first image directory
+    image_download_dir: Option<PathBuf>,
+
+    /// Cluster smoke test running or last finished
+    pub selftest_run: Option<SelfTestRun>,
+    selftest_config: SelfTestConfig,
+
+    // Node evacuation: the node awaiting confirmation and the drain
+    // running or last finished
+    pub evacuation_prompt: Option<String>,
+    pub evacuation: Option<Evacuation>,
+    evacuation_config: EvacuationConfig,
+
+    /// Config-defined commands shown on the Tools screen
+    pub custom_commands: CustomCommandCollector,
+    /// Enabled `[[display.header]]` widgets, in order
+    pub header_widgets: Vec<HeaderWidgetConfig>,
+    /// Commands of the header's command widgets, in their order
+    pub header_commands: CustomCommandCollector,
+    /// Shown by the hostname widget until the node name is known
+    pub hostname: String,
+
+    // Debug overlay
+    pub debug_overlay_open: bool,
+    pub collector_timings: Vec<CollectorTiming>,
+    pub telemetry: Telemetry,
+    /// Header and footer text kept between frames
+    pub display_text: DisplayText,
+
+    report_config: ReportConfig,
+    /// Text of the last status report (`R`), for the clipboard
+    pub last_report: Option<String>,
+
+    pub cluster_flavor: ClusterFlavor,
 
     // Data collectors
     pub log_collector: LogCollector,
     pub system_collector: SystemCollector,
     pub network_collector: NetworkCollector,
     pub k8s_collector: KubernetesCollector,
+    pub dns_collector: Option<DnsCollector>,
+    pub route_collector: Option<RouteCollector>,
+    pub cgroup_collector: CgroupCollector,
+    pub host_collector: HostCollector,
+    pub power_collector: PowerCollector,
+    pub qemu_collector: QemuCollector,
+    pub image_collector: ImageCollector,
+    pub sriov_collector: SriovCollector,
+    pub registry_collector: RegistryCollector,
+    pub vip_collector: VipCollector,
+    pub socket_collector: SocketCollector,
+    pub limits_collector: LimitsCollector,
+    pub multipath_collector: MultipathCollector,
+    pub disk_stats_collector: DiskStatsCollector,
+    pub sysctl_collector: SysctlCollector,
+    pub firmware_collector: FirmwareCollector,
+    /// None when `kubernetes.etcd.enabled` is off
+    pub etcd_collector: Option<EtcdCollector>,
+    /// None while `[prometheus]` names no server or target
+    prometheus_collector: Option<PrometheusCollector>,
+    /// The last scrape, merged into the API, etcd and VM usage readings
+    pub prometheus_metrics: PrometheusMetrics,
+    /// None while `[remote_agents]` lists no endpoints
+    agent_collector: Option<AgentCollector>,
+    /// The last poll of each remote agent, shown on the Fleet screen
+    pub agents: Vec<AgentStatus>,
+    agent_failure_threshold: u32,
+    kmsg_collector: Option<KmsgCollector>,
+    /// None without `[[logging.forward]]` sinks
+    pub log_forwarder: Option<LogForwarder>,
+    /// None when `crashes.enabled` is off
+    crash_collector: Option<CrashCollector>,
+    crash_interval: Duration,
+    crash_checked: Option<Instant>,
+    crash_window_mins: u64,
+    /// Core dumps and crash reports, newest first
+    pub crash_dumps: Vec<CoreDump>,
+    oom_collector: OomCollector,
+    /// Recent OOM kills on this host, newest first
+    pub oom_kills: Vec<OomKill>,
+    oom_window_mins: u64,
+    /// None while `[host_profile]` sets no expectations
+    host_profile_collector: Option<HostProfileCollector>,
+    host_profile_interval: Duration,
+    host_profile_checked: Option<Instant>,
+    /// How this host differs from `[host_profile]`
+    pub profile_deviations: Vec<ProfileDeviation>,
+    /// None while `[memory_leaks]` is disabled
+    process_memory_collector: Option<ProcessMemoryCollector>,
+    process_memory_interval: Duration,
+    process_memory_checked: Option<Instant>,
+    /// Watched daemons' resident memory as last sampled, with its
+    /// history in `metrics_history`
+    pub process_memory: Vec<ProcessMemory>,
+    pub leak_window_hours: u64,
+    pub leak_growth_mb_per_hour: f64,
+    pub leak_limits_mb: BTreeMap<String, u64>,
+    /// `[[computed_metrics]]` with their latest values
+    pub computed_metrics: Vec<ComputedMetric>,
+    bridge_watch: Option<BridgeWatchCollector>,
+    dhcp_churn_threshold: u32,
+    ip_conflict_window_mins: u64,
+    /// Friendly names of host interfaces and bridges
+    pub interface_labels: InterfaceLabels,
 
     // Cached data
-    pub logs: Vec<LogEntry>,
-    pub filtered_logs: Vec<LogEntry>,
+    // Entries are shared with the log collector's buffer
+    pub logs: Vec<Arc<LogEntry>>,
+    pub filtered_logs: Vec<Arc<LogEntry>>,
+    /// Started the first time a large buffer is filtered
+    log_filter: Option<LogFilterWorker>,
+    /// Time range being entered for an archived log query
+    pub log_range_prompt: Option<LogRangePrompt>,
+    /// Past journal lines shown in place of the live buffer
+    pub log_archive: Option<LogArchive>,
+    log_archive_max_lines: usize,
+    /// Ctrl-F search across every followed service's journal
+    pub log_search: Option<LogSearch>,
+    log_search_lines: usize,
     pub system_metrics: SystemMetrics,
+    /// A system sample arrived that the alert rules haven't seen yet
+    system_metrics_fresh: bool,
+    pub cgroup_usage: Vec<CgroupUsage>,
+    pub host_info: HostInfo,
+    pub power_info: PowerInfo,
+    pub vm_usage: Vec<VmUsage>,
     pub network_info: NetworkInfo,
+    pub dns_status: Vec<DnsResolverStatus>,
+    /// Routing table and default gateways, when route checks are enabled
+    pub route_status: Option<RouteStatus>,
+    pub registry_status: Vec<RegistryStatus>,
+    pub vip_status: Vec<VipStatus>,
+    /// Image pulls from Pod events, failing images first
+    pub image_pulls: Vec<ImagePull>,
+    /// Pods that restarted or are stuck in a backoff
+    pub pod_health: Vec<PodHealth>,
+    /// (seen at, restart count) per pod over the past hour, for the
+    /// restart rate
+    pod_restart_history: HashMap<String, VecDeque<(Instant, u32)>>,
+    /// NetworkPolicies of namespaces running VMs, by namespace
+    pub vm_network_policies: Vec<NamespacePolicies>,
+    pub service_health: ServiceHealth,
     pub k8s_info: K8sClusterInfo,
     pub kubevirt_info: KubeVirtInfo,
+    /// Resources of the current kind passing the selector, in group order
+    pub resources: Vec<K8sResource>,
+    /// Every resource of the current kind, as collected
+    all_resources: Vec<K8sResource>,
     pub metrics_history: MetricsHistory,
+    /// Active alert ids as of the last `record_alert_markers`
+    charted_alerts: HashSet<String>,
+
+    // Charts view: selected metric, zoom level, and the sample timestamp
+    // under the cursor (None = follow live data)
+    pub chart_metric: ChartMetric,
+    pub chart_zoom: Zoom,
+    pub chart_cursor: Option<DateTime<Local>>,
+    pub timeline: Timeline,
+    /// Warning thresholds the dashboard gauges forecast breaches of
+    pub warning_thresholds: MetricThresholds,
+
+    // VM heatmap: colored metric and the VM under the cursor
+    pub heatmap_metric: HeatmapMetric,
+    pub heatmap_cursor: usize,
+    /// Terminal size, for key handling that depends on the drawn layout
+    pub terminal_width: u16,
+    pub terminal_height: u16,
+
+    // Fleet view: every node of the cluster, its sort order, the selected
+    // row and the node opened for drill-down
+    pub fleet: Vec<NodeStatus>,
+    pub fleet_sort: FleetSort,
+    pub fleet_sort_desc: bool,
+    pub fleet_selected: usize,
+    pub fleet_detail: Option<String>,
+    /// Two nodes, or this node now and earlier, side by side
+    pub compare: Option<CompareView>,
+    /// Node picked as the left side of a fleet comparison (`c`)
+    pub compare_mark: Option<String>,
+    /// Snapshots of this node on disk, for comparing against earlier
+    snapshots: SnapshotHistory,
+    compare_baseline: chrono::Duration,
+    /// This host's node name, whose full view is the other screens
+    pub local_node: Option<String>,
+    /// Conditions, allocatable and eviction thresholds of this host's node;
+    /// None until read or when the host isn't a node
+    pub kubelet_status: Option<KubeletStatus>,
+
+    /// Namespace ResourceQuota usage, one entry per quota resource
+    pub quotas: Vec<QuotaUsage>,
+    /// Latest API server probe; None until the first one completes
+    pub api_health: Option<ApiHealth>,
+    /// Host-only collection while the API server is unreachable, see
+    /// `[kubernetes.local_mode]`
+    pub local_mode: bool,
+    /// Entered with --local or 'L' rather than on failed probes, so only
+    /// 'L' leaves it
+    local_mode_pinned: bool,
+    /// Failed API probes in a row, and how many switch to local mode
+    api_failures: u32,
+    local_mode_after: u32,
+    /// Pausing and killing qemu processes is offered in local mode
+    pub emergency_actions: bool,
+    /// A signal for the selected qemu process awaiting confirmation
+    pub qemu_signal: Option<(QemuProcess, QemuSignal)>,
+    /// The cluster datastore; None where this host runs no etcd
+    pub etcd_status: Option<EtcdStatus>,
+
+    /// SR-IOV capable NICs on this host and their VF pools
+    pub sriov_nics: Vec<SriovNic>,
+    /// File descriptor and ephemeral port usage
+    pub socket_usage: SocketUsage,
+    /// Entropy and PIDs against their kernel limits
+    pub host_limits: HostLimits,
+    /// Multipath LUNs and FC HBA ports
+    pub multipath: MultipathInfo,
+    /// Throughput and request latency per block device
+    pub disk_latency: Vec<DiskLatency>,
+    /// Watched kernel tunables and their expected values
+    pub sysctls: Vec<Sysctl>,
+    /// Firmware and microcode versions and virtualization support
+    pub hardware: HardwareInfo,
+
+    // Host map: qemu processes on this host and the selected row
+    pub qemu_processes: Vec<QemuProcess>,
+    /// UIDs of virt-launcher pods, to tell VM cgroups from other pods
+    pub launcher_pods: HashSet<String>,
+    /// Live migrations seen over time, persisted across runs
+    pub migration_history: MigrationHistory,
+    pub boot_history: BootHistory,
+    boots_checked: Option<Instant>,
+    boot_slow_factor: f64,
+    boot_slow_min: chrono::Duration,
+    /// VMs, pods and their resources per tenant, when a tenant label is set
+    pub tenant_usage: Vec<TenantUsage>,
+    /// Label the rollup is by: `tenants.label` or `display.group_by_label`
+    pub tenant_label: Option<String>,
+    tenants_config: TenantsConfig,
+    /// (sampled at, cores, memory bytes) per tenant within the growth window
+    tenant_history: HashMap<String, VecDeque<(Instant, f64, u64)>>,
+    /// Last guest agent clock reading per VM
+    pub guest_clocks: HashMap<String, GuestClock>,
+    /// None when `kubernetes.guest_clock.interval_secs` is 0
+    guest_clock_interval: Option<Duration>,
+    guest_clock_checked: Option<Instant>,
+    /// Seconds of drift (warning, critical)
+    pub guest_clock_thresholds: (f64, f64),
+    migration_failure_threshold: usize,
+    pub host_map_selected: usize,
+    pub host_map_view: HostMapView,
+    /// Selected daemon in the Memory view
+    pub leak_selected: usize,
+
+    // Disk image inventory, the selected row and an orphan awaiting
+    // delete confirmation
+    pub disk_images: Vec<DiskImage>,
+    pub disk_selected: usize,
+    pub disk_delete: Option<DiskImage>,
+    /// CDI imports and clones, unfinished first
+    pub data_volumes: Vec<DataVolume>,
+    import_stall_mins: u64,
+
+    // Dashboard card grid and, while editing it, the selected (row, card)
+    pub dashboard_layout: DashboardLayout,
+    pub layout_edit: Option<(usize, usize)>,
+    pub layout_status: Option<String>,
+
+    // Toasts for failures and outcomes that would otherwise only be logged
+    pub messages: VecDeque<Message>,
+
+    /// Config problems found at startup, shown until dismissed
+    pub config_report: Option<ConfigReport>,
+
+    /// Ctrl-P command palette, while open
+    pub palette: Option<CommandPalette>,
+    /// `[[views]]`, plus those saved since starting
+    pub views: Vec<SavedView>,
+    /// Name being typed for the view to save, while asked for
+    pub view_name_prompt: Option<String>,
+
+    alerts_enabled: bool,
+    alert_sweep: AlertSweep,
+    dns_failure_threshold: u32,
+    gateway_failure_threshold: u32,
+    route_flap_threshold: u32,
+    registry_failure_threshold: u32,
+    vip_failure_threshold: u32,
+    image_pull_failure_threshold: u32,
+    watched_services: Vec<String>,
+    // Services that have had ready endpoints this session (auto-watch mode)
+    services_seen_ready: HashSet<String>,
+    collector_timeout: Duration,
+    guest_fs_thresholds: (f64, f64),
+    /// None when `kubernetes.guest_filesystems.interval_secs` is 0
+    guest_fs_interval: Option<Duration>,
+    guest_fs_checked: Option<Instant>,
+    quota_thresholds: (f64, f64),
+    /// Percent of an fd limit (warning, critical)
+    pub fd_thresholds: (f64, f64),
+    /// Percent of the ephemeral port range (warning, critical)
+    ephemeral_port_thresholds: (f64, f64),
+    /// Percent of the PID space (warning, critical)
+    pub pid_thresholds: (f64, f64),
+    /// Bits of entropy left (warning, critical)
+    pub entropy_thresholds: (u64, u64),
+    sched_wait_thresholds: (f64, f64),
+    cpu_throttle_thresholds: (f64, f64),
+    /// (percent of base clock, minutes) before a throttled host alerts
+    pub freq_throttle: (f64, u64),
+    /// When busy cores last dropped below the throttle threshold
+    freq_throttled_since: Option<Instant>,
+    /// API server latency (warning, critical) in milliseconds
+    pub api_latency_thresholds: (f64, f64),
+    /// Block device request latency (warning, critical) in milliseconds
+    pub disk_latency_thresholds: (f64, f64),
+    /// Percent of etcd's quota (warning, critical) the database may take up
+    pub etcd_db_thresholds: (f64, f64),
+    etcd_leader_changes_per_hour: u64,
+    pod_restarts_per_hour: u64,
+    /// Forecast hours to full (warning, critical) for filesystems
+    pub disk_full_hours: (f64, f64),
+}
+
+/// Run a collector future under a deadline, returning how long it took
+pub async fn timed<T>(limit: Duration, fut: impl Future<Output = Result<T>>) -> (Duration, Result<T>) {
+    let start = Instant::now();
+    let result = match tokio::time::timeout(limit, fut).await {
+        Ok(result) => result,
+        Err(_) => Err(anyhow::anyhow!("timed out after {}s", limit.as_secs_f64())),
+    };
+    (start.elapsed(), result)
+}
+
+/// The Kubernetes collector for the configured (or detected) cluster,
+/// falling back to mock data when it can't connect
+pub async fn kubernetes_collector(config: &Config) -> Result<KubernetesCollector> {
+    let cluster_flavor = config.cluster_flavor();
+    let kubeconfig_path = config
+        .kubernetes
+        .kubeconfig_path
+        .clone()
+        .unwrap_or_else(|| cluster_flavor.kubeconfig_path().to_string());
+    let mut k8s_collector = KubernetesCollector::new()
+        .with_kubeconfig(PathBuf::from(kubeconfig_path))
+        .with_flavor(cluster_flavor)
+        .with_api_config(config.kubernetes.api.clone())
+        .with_console_config(config.kubernetes.console.clone())
+        .with_virtualization(&config.virtualization);
+    k8s_collector.init().await?;
+    Ok(k8s_collector)
+}
+
+/// Thresholds for this host, with any matching per-node overrides
+pub async fn node_alerts_config(alerts: &AlertsConfig, k8s_collector: &KubernetesCollector, node: Option<&str>) -> AlertsConfig {
+    match node {
+        Some(node) if !alerts.overrides.is_empty() => {
+            let labels = k8s_collector.node_labels(node).await.unwrap_or_else(|e| {
+                tracing::warn!("Failed to read labels of node {}: {:#}", node, e);
+                BTreeMap::new()
+            });
+            alerts.for_node(node, &labels)
+        }
+        _ => alerts.clone(),
+    }
+}
+
+pub fn system_alert_config(alerts: &AlertsConfig) -> SystemAlert {
+    SystemAlert {
+        cpu_warning_threshold: alerts.cpu_warning_threshold,
+        cpu_critical_threshold: alerts.cpu_critical_threshold,
+        cpu_enabled: alerts.enabled,
+        memory_warning_threshold: alerts.memory_warning_threshold,
+        memory_critical_threshold: alerts.memory_critical_threshold,
+        memory_enabled: alerts.enabled,
+        disk_warning_threshold: alerts.disk_warning_threshold,
+        disk_critical_threshold: alerts.disk_critical_threshold,
+        disk_enabled: alerts.enabled,
+        load_warning_threshold: alerts.load_warning_threshold,
+        load_critical_threshold: alerts.load_critical_threshold,
+        load_enabled: alerts.enabled,
+    }
 }
 
 impl App {
-    pub async fn new() -> Result<Self> {
+    pub async fn new(args: &CliArgs) -> Result<Self> {
         // Load configuration
-        let config = Config::load().unwrap_or_default();
+        let (config, config_report) = Config::load();
+        crate::i18n::set_locale(config.display.locale);
+        crate::timestamps::apply(&config.display);
+
+        let k8s_collector = kubernetes_collector(&config).await?;
+        let saved_state = if config.display.remember_state { UiState::load() } else { UiState::default() };
+        Self::with_config(args, config, config_report, k8s_collector, saved_state).await
+    }
 
-        // Initialize Kubernetes collector
-        let mut k8s_collector = KubernetesCollector::new();
-        k8s_collector.init().await?;
+    /// An app on `config` as given, reading no config files; the snapshot
+    /// harness builds its apps this way with a cluster-less collector
+    pub async fn with_config(
+        args: &CliArgs,
+        config: Config,
+        config_report: ConfigReport,
+        k8s_collector: KubernetesCollector,
+        saved_state: UiState,
+    ) -> Result<Self> {
+        let cluster_flavor = config.cluster_flavor();
+        let local_node = sysinfo::System::host_name();
+        let alert_profiles = AlertProfiles::for_config(&config);
+        // A remembered profile that has since been deleted is skipped quietly
+        let saved_profile = saved_state
+            .alert_profile
+            .clone()
+            .filter(|profile| alert_profiles.names().contains(profile));
+        let mut alert_profile = args
+            .alert_profile
+            .clone()
+            .or(saved_profile)
+            .or_else(|| config.alerts.profile.clone());
+        let (profiled, profile_error) = match alert_profiles.resolve(&config.alerts, alert_profile.as_deref()) {
+            Ok(alerts) => (alerts, None),
+            Err(e) => {
+                alert_profile = None;
+                (config.alerts.clone(), Some(e))
+            }
+        };
+        let alerts = node_alerts_config(&profiled, &k8s_collector, local_node.as_deref()).await;
+        let read_only = args.read_only || config.general.read_only;
+        let auto_actions = AutoActions::new(
+            if read_only { Vec::new() } else { alerts.actions.clone() },
+            k8s_collector.kubeconfig_path().map(Path::to_path_buf),
+        );
 
         // Initialize alert manager with config
-        let alert_config = SystemAlert {
-            cpu_warning_threshold: config.alerts.cpu_warning_threshold,
-            cpu_critical_threshold: config.alerts.cpu_critical_threshold,
-            cpu_enabled: config.alerts.enabled,
-            memory_warning_threshold: config.alerts.memory_warning_threshold,
-            memory_critical_threshold: config.alerts.memory_critical_threshold,
-            memory_enabled: config.alerts.enabled,
-            disk_warning_threshold: config.alerts.disk_warning_threshold,
-            disk_critical_threshold: config.alerts.disk_critical_threshold,
-            disk_enabled: config.alerts.enabled,
-            load_warning_threshold: config.alerts.load_warning_threshold,
-            load_critical_threshold: config.alerts.load_critical_threshold,
-            load_enabled: config.alerts.enabled,
+        let alert_config = system_alert_config(&alerts);
+
+        let anomaly_config = AnomalyConfig {
+            enabled: alerts.enabled && alerts.anomaly_detection,
+            sigma: alerts.anomaly_sigma,
+            min_samples: alerts.anomaly_min_samples,
+            path: config
+                .alerts
+                .baseline_path
+                .as_ref()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| Config::state_dir().join("baselines.json")),
+            ..AnomalyConfig::default()
         };
 
         let alert_manager = AlertManager::new()
             .with_system_config(alert_config)
-            .with_kubernetes_enabled(config.alerts.kubernetes_enabled)
-            .with_kubevirt_enabled(config.alerts.kubevirt_enabled);
+            .with_anomaly_detection(anomaly_config)
+            .with_history_file(
+                config
+                    .alerts
+                    .history_path
+                    .as_ref()
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| Config::state_dir().join("alert_history.json")),
+            )
+            .with_dedup_window(alerts.dedup_window_secs)
+            .with_rate_limit(alerts.rate_limit_per_minute, alerts.rule_rate_limit_per_minute)
+            .with_runbooks(alerts.runbooks.clone())
+            .with_kubernetes_enabled(alerts.kubernetes_enabled)
+            .with_kubevirt_enabled(alerts.kubevirt_enabled);
+
+        let dns_collector = config.network.dns_checks_enabled.then(|| {
+            // The cluster DNS records of VIP-checked Services are resolved too
+            let service_names = config.network.vip_services.iter().filter_map(|service| {
+                let (namespace, name) = service.split_once('/')?;
+                Some(format!("{}.{}.svc.cluster.local", name, namespace))
+            });
+            DnsCollector::new()
+                .with_names(
+                    config.network.dns_host_names.clone(),
+                    config.network.dns_cluster_names.iter().cloned().chain(service_names).collect(),
+                )
+                .with_coredns_address(config.network.coredns_address.clone())
+                .with_timeout(config.network.dns_timeout_ms)
+        });
+        let route_collector = config.network.route_checks_enabled.then(|| {
+            RouteCollector::new()
+                .with_timeout(config.network.gateway_timeout_ms)
+                .with_flap_window(config.network.route_flap_window_minutes)
+        });
+
+        // Invalid patterns are listed in the startup config report
+        let (log_highlighter, _) = LogHighlighter::new(&config.logging.highlights);
+        let (log_redactor, _) = LogRedactor::new(&config.logging.redactions);
+        let log_forwarder = LogForwarder::from_config(&config.logging.forward, &Secrets::from_config(&config.secrets));
 
-        Ok(Self {
+        let (web_bridge, web_error) = if config.web.enabled {
+            match WebBridge::start(&config.web.listen).await {
+                Ok(bridge) => (Some(bridge), None),
+                Err(e) => (None, Some(e)),
+            }
+        } else {
+            (None, None)
+        };
+
+        let (prometheus_collector, prometheus_error) = if config.prometheus.is_empty() {
+            (None, None)
+        } else {
+            match PrometheusCollector::new(&config.prometheus, &Secrets::from_config(&config.secrets)) {
+                Ok(collector) => (Some(collector), None),
+                Err(e) => (None, Some(e)),
+            }
+        };
+
+        let (agent_collector, agents_error) = if config.remote_agents.endpoints.is_empty() {
+            (None, None)
+        } else {
+            match AgentCollector::new(&config.remote_agents) {
+                Ok(collector) => (Some(collector), None),
+                Err(e) => (None, Some(e)),
+            }
+        };
+
+        let (webhook_receiver, receiver_error) = if config.receiver.enabled {
+            match WebhookReceiver::start(&config.receiver).await {
+                Ok(receiver) => (Some(receiver), None),
+                Err(e) => (None, Some(e)),
+            }
+        } else {
+            (None, None)
+        };
+
+        let warning_thresholds = MetricThresholds {
+            cpu: alerts.cpu_warning_threshold,
+            memory: alerts.memory_warning_threshold,
+            disk: alerts.disk_warning_threshold,
+            load: alerts.load_warning_threshold,
+        };
+
+        let mut app = Self {
             current_screen: Screen::Logs,
-            scroll_offset: 0,
+            panes: PaneFocus::default(),
+            navigator: Navigator::default(),
             search_query: String::new(),
             search_active: false,
             filter_level: None,
+            logs_collapsed: false,
+            logs_wrapped: false,
+            log_hscroll: 0,
+            log_wrap_scroll: (0, 0),
+            log_context: None,
+            log_detail: None,
+            log_context_lines: config.logging.context_lines,
+            log_services: None,
+            log_highlighter,
+            accessible: args.ascii
+                || config.display.accessibility
+                || std::env::var_os("NO_COLOR").is_some(),
+            light_theme: saved_state.theme.unwrap_or_else(|| config.display.theme.resolve()) == Theme::Light,
+            chosen_theme: saved_state.theme,
+            remember_state: config.display.remember_state,
+            footer_log_rates: config.display.footer_log_rates,
+            banner_cycle_secs: config.display.banner_cycle_secs,
+            alert_styles: AlertStyles::new(&config.display.alert_styles),
+            restart_on_panic: config.general.restart_on_panic,
+            presence: Presence::new(config.general.idle_refresh_secs),
+            read_only,
             alert_manager,
             alert_panel_open: false,
             alert_selected_index: 0,
-            log_collector: LogCollector::new()?,
-            system_collector: SystemCollector::new()?,
-            network_collector: NetworkCollector::new()?,
+            alert_detail: None,
+            alert_snooze_mins: alerts.snooze_mins,
+            alert_related_logs_mins: alerts.related_logs_mins,
+            alert_profiles,
+            alert_profile,
+            config_alerts: config.alerts.clone(),
+            alert_stats_open: false,
+            update_checker: config.update_check.enabled.then(|| UpdateChecker::new(&config.update_check)),
+            update_popup: false,
+            permissions: Permissions::default(),
+            health: None,
+            health_config: config.health.clone(),
+            notifier: Notifier::from_config(&config.notifications, &Secrets::from_config(&config.secrets)),
+            auto_actions,
+            web_bridge,
+            webhook_receiver,
+            external_alerts: BTreeMap::new(),
+            external_expiry: chrono::Duration::minutes(config.receiver.expire_mins as i64),
+            resource_kind: ResourceKind::Node,
+            resource_selected_index: 0,
+            resource_selector: String::new(),
+            resource_selector_error: None,
+            resource_label_selector: LabelSelector::default(),
+            resource_group_by: config.display.group_by_label.clone().unwrap_or_default(),
+            resource_input: None,
+            yaml_inspector: None,
+            object_watch: None,
+            cloud_init: None,
+            vm_detail: None,
+            cpu_tuning: None,
+            vm_clone: None,
+            hotplug: None,
+            node_editor: None,
+            vm_marked: HashSet::new(),
+            batch_prompt: None,
+            batch_run: None,
+            upgrade_prompt: false,
+            upgrade_run: None,
+            nix_store: None,
+            upgrade_config: config.upgrade.clone(),
+            image_catalog: None,
+            image_catalog_config: config.image_catalog.clone(),
+            image_download_dir: config
+                .image_catalog
+                .download_dir
+                .as_ref()
+                .or(config.storage.image_dirs.first())
+                .map(PathBuf::from),
+            selftest_run: None,
+            selftest_config: config.selftest.clone(),
+            evacuation_prompt: None,
+            evacuation: None,
+            evacuation_config: config.kubernetes.evacuation.clone(),
+            custom_commands: CustomCommandCollector::start(config.custom_commands.clone()),
+            header_widgets: header_widgets(&config.display.header),
+            header_commands: CustomCommandCollector::start(header_commands(&config.display.header)),
+            hostname: crate::notify::hostname(),
+            debug_overlay_open: false,
+            collector_timings: Vec::new(),
+            telemetry: Telemetry::new(config.general.telemetry_interval_secs),
+            display_text: DisplayText::default(),
+            report_config: config.report.clone(),
+            last_report: None,
+            cluster_flavor,
+            log_collector: LogCollector::new()?.with_services(
+                config
+                    .logging
+                    .services
+                    .clone()
+                    .unwrap_or_else(|| cluster_flavor.services()),
+            )
+            .with_buffer_size(config.general.log_buffer_size.max(1))
+            .with_max_bytes(config.general.log_buffer_max_mb.max(1).saturating_mul(1024 * 1024))
+            .with_redactor(log_redactor)
+            .with_forwarding(log_forwarder.is_some())
+            .with_collapse_repeats(config.logging.collapse_repeats),
+            system_collector: SystemCollector::new()?.with_filesystems(config.storage.filesystems.clone()),
+            network_collector: NetworkCollector::new()?
+                .with_flavor(cluster_flavor)
+                .with_vm_namespaces(config.network.vm_namespaces)
+                .with_interface_filter(&config.network),
             k8s_collector,
+            dns_collector,
+            route_collector,
+            cgroup_collector: CgroupCollector::new()?,
+            host_collector: HostCollector::new()?.with_services(cluster_flavor.services()),
+            power_collector: PowerCollector::new()?,
+            qemu_collector: QemuCollector::new()?,
+            sriov_collector: SriovCollector::new()?,
+            socket_collector: SocketCollector::new()?
+                .with_processes(config.general.watched_processes.clone())
+                .with_ports(config.network.watched_ports.clone(), config.network.expected_listeners.clone()),
+            limits_collector: LimitsCollector::new()?,
+            multipath_collector: MultipathCollector::new()?,
+            disk_stats_collector: DiskStatsCollector::new()?.with_datastores(
+                [&config.storage.image_dirs[..], &config.storage.local_path_dirs[..]].concat(),
+                config.storage.datastore_devices.clone(),
+            ),
+            sysctl_collector: SysctlCollector::new()?.with_sysctls(config.sysctl.names(), &config.sysctl.expected),
+            firmware_collector: FirmwareCollector::new(Config::state_dir().join("hardware.json"))?,
+            etcd_collector: config
+                .kubernetes
+                .etcd
+                .enabled
+                .then(|| EtcdCollector::new(&config.kubernetes.etcd, cluster_flavor)),
+            prometheus_collector,
+            prometheus_metrics: PrometheusMetrics::default(),
+            agent_collector,
+            agents: Vec::new(),
+            agent_failure_threshold: config.remote_agents.failure_threshold,
+            kmsg_collector: if config.logging.kernel_log {
+                Some(KmsgCollector::new()?.with_window(alerts.kernel_event_window_mins))
+            } else {
+                None
+            },
+            log_forwarder,
+            crash_collector: config.crashes.enabled.then(|| {
+                CrashCollector::new()
+                    .with_processes(config.crashes.processes.clone())
+                    .with_crash_dirs(&config.crashes.crash_dirs)
+                    .with_history_days(config.crashes.history_days)
+            }),
+            crash_interval: Duration::from_secs(config.crashes.scan_interval_secs),
+            crash_checked: None,
+            crash_window_mins: alerts.crash_window_mins,
+            crash_dumps: Vec::new(),
+            oom_collector: OomCollector::new(),
+            oom_kills: Vec::new(),
+            oom_window_mins: alerts.kernel_event_window_mins,
+            host_profile_collector: (!config.host_profile.is_empty())
+                .then(|| HostProfileCollector::new(config.host_profile.clone())),
+            host_profile_interval: Duration::from_secs(config.host_profile.check_interval_secs),
+            host_profile_checked: None,
+            profile_deviations: Vec::new(),
+            process_memory_collector: (config.memory_leaks.enabled && !config.memory_leaks.processes.is_empty())
+                .then(|| ProcessMemoryCollector::new(config.memory_leaks.processes.clone())),
+            process_memory_interval: Duration::from_secs(config.memory_leaks.sample_interval_secs),
+            process_memory_checked: None,
+            process_memory: Vec::new(),
+            leak_window_hours: config.memory_leaks.window_hours,
+            leak_growth_mb_per_hour: config.memory_leaks.growth_mb_per_hour,
+            leak_limits_mb: config.memory_leaks.limits_mb.clone(),
+            computed_metrics: ComputedMetric::compile(&config.computed_metrics),
+            bridge_watch: if config.network.conflict_watch_bridges.is_empty() {
+                None
+            } else {
+                Some(
+                    BridgeWatchCollector::new()?
+                        .with_bridges(config.network.conflict_watch_bridges.clone())
+                        .with_window(alerts.ip_conflict_window_mins),
+                )
+            },
+            dhcp_churn_threshold: alerts.dhcp_churn_threshold,
+            ip_conflict_window_mins: alerts.ip_conflict_window_mins,
+            interface_labels: InterfaceLabels::new(config.network.interface_labels.clone()),
+            registry_collector: RegistryCollector::new(config.network.registries.clone())
+                .with_timeout(config.network.registry_timeout_ms),
+            vip_collector: VipCollector::new(config.network.vip_services.clone())
+                .with_timeout(config.network.vip_timeout_ms),
+            image_collector: ImageCollector::new()?
+                .with_dirs(&config.storage.image_dirs, &config.storage.local_path_dirs),
             logs: Vec::new(),
             filtered_logs: Vec::new(),
+            log_filter: None,
+            log_range_prompt: None,
+            log_archive: None,
+            log_archive_max_lines: config.logging.archive_max_lines,
+            log_search: None,
+            log_search_lines: config.logging.search_lines,
             system_metrics: SystemMetrics::default(),
+            system_metrics_fresh: false,
+            cgroup_usage: Vec::new(),
+            host_info: HostInfo::default(),
+            power_info: PowerInfo::default(),
+            vm_usage: Vec::new(),
+            heatmap_metric: HeatmapMetric::Cpu,
+            heatmap_cursor: 0,
+            terminal_width: 80,
+            terminal_height: 24,
             network_info: NetworkInfo::default(),
+            dns_status: Vec::new(),
+            route_status: None,
+            registry_status: Vec::new(),
+            vip_status: Vec::new(),
+            image_pulls: Vec::new(),
+            pod_health: Vec::new(),
+            pod_restart_history: HashMap::new(),
+            vm_network_policies: Vec::new(),
+            service_health: ServiceHealth::default(),
             k8s_info: K8sClusterInfo {
                 nodes_ready: 0,
                 nodes_total: 0,
@@ -98,151 +1368,5246 @@ impl App {
                 vms_stopped: 0,
                 vms_migrating: 0,
             },
-            metrics_history: MetricsHistory::new(),
-        })
+            resources: Vec::new(),
+            all_resources: Vec::new(),
+            metrics_history: MetricsHistory::new().with_cpu_smoothing(config.display.cpu_smoothing_secs),
+            charted_alerts: HashSet::new(),
+            chart_metric: ChartMetric::Cpu,
+            chart_zoom: Zoom::FiveMinutes,
+            chart_cursor: None,
+            timeline: Timeline::new(warning_thresholds),
+            warning_thresholds,
+            fleet: Vec::new(),
+            fleet_sort: FleetSort::Name,
+            fleet_sort_desc: false,
+            fleet_selected: 0,
+            fleet_detail: None,
+            local_node,
+            kubelet_status: None,
+            quotas: Vec::new(),
+            api_health: None,
+            local_mode: args.local,
+            local_mode_pinned: args.local,
+            api_failures: 0,
+            local_mode_after: config.kubernetes.local_mode.after_failures,
+            emergency_actions: config.kubernetes.local_mode.emergency_actions,
+            qemu_signal: None,
+            etcd_status: None,
+            qemu_processes: Vec::new(),
+            launcher_pods: HashSet::new(),
+            tenant_usage: Vec::new(),
+            tenant_label: config
+                .tenants
+                .label
+                .clone()
+                .or_else(|| config.display.group_by_label.clone())
+                .filter(|label| !label.is_empty()),
+            tenants_config: config.tenants.clone(),
+            tenant_history: HashMap::new(),
+            guest_clocks: HashMap::new(),
+            guest_clock_interval: Some(config.kubernetes.guest_clock.interval_secs)
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs),
+            guest_clock_checked: None,
+            guest_clock_thresholds: (alerts.guest_clock_warning_secs, alerts.guest_clock_critical_secs),
+            compare: None,
+            compare_mark: None,
+            snapshots: SnapshotHistory::load(
+                config
+                    .compare
+                    .path
+                    .as_ref()
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| Config::state_dir().join("snapshots.json")),
+                config.compare.snapshot_interval_mins,
+                config.compare.retention_hours,
+            ),
+            compare_baseline: chrono::Duration::minutes(config.compare.baseline_mins as i64),
+            migration_history: MigrationHistory::load(
+                config
+                    .kubernetes
+                    .migration_history_path
+                    .as_ref()
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| Config::state_dir().join("migrations.json")),
+            ),
+            boot_history: BootHistory::load(
+                config
+                    .kubernetes
+                    .boot_history_path
+                    .as_ref()
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| Config::state_dir().join("boots.json")),
+            ),
+            boots_checked: None,
+            boot_slow_factor: alerts.boot_slow_factor,
+            boot_slow_min: chrono::Duration::seconds(alerts.boot_slow_min_secs as i64),
+            migration_failure_threshold: alerts.migration_failure_threshold as usize,
+            host_map_selected: 0,
+            sriov_nics: Vec::new(),
+            socket_usage: SocketUsage::default(),
+            host_limits: HostLimits::default(),
+            multipath: MultipathInfo::default(),
+            disk_latency: Vec::new(),
+            sysctls: Vec::new(),
+            hardware: HardwareInfo::default(),
+            host_map_view: HostMapView::Processes,
+            leak_selected: 0,
+            disk_images: Vec::new(),
+            data_volumes: Vec::new(),
+            import_stall_mins: alerts.import_stall_mins,
+            disk_selected: 0,
+            disk_delete: None,
+            dashboard_layout: DashboardLayout::load(&config.dashboard),
+            layout_edit: None,
+            layout_status: None,
+            messages: VecDeque::new(),
+            config_report: (!config_report.is_clean()).then_some(config_report),
+            palette: None,
+            views: config.views.clone(),
+            view_name_prompt: None,
+            alerts_enabled: alerts.enabled,
+            alert_sweep: AlertSweep::new(alerts.background_interval_secs),
+            dns_failure_threshold: config.network.dns_failure_threshold,
+            gateway_failure_threshold: config.network.gateway_failure_threshold,
+            route_flap_threshold: config.network.route_flap_threshold,
+            registry_failure_threshold: config.network.registry_failure_threshold,
+            vip_failure_threshold: config.network.vip_failure_threshold,
+            image_pull_failure_threshold: config.network.image_pull_failure_threshold,
+            watched_services: config.kubernetes.watched_services.clone(),
+            services_seen_ready: HashSet::new(),
+            collector_timeout: Duration::from_secs(config.general.collector_timeout_secs.max(1)),
+            guest_fs_thresholds: (
+                alerts.guest_fs_warning_threshold,
+                alerts.guest_fs_critical_threshold,
+            ),
+            guest_fs_interval: Some(config.kubernetes.guest_filesystems.interval_secs)
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs),
+            guest_fs_checked: None,
+            api_latency_thresholds: (alerts.api_latency_warning_threshold, alerts.api_latency_critical_threshold),
+            disk_latency_thresholds: (alerts.disk_latency_warning_threshold, alerts.disk_latency_critical_threshold),
+            etcd_db_thresholds: (alerts.etcd_db_warning_threshold, alerts.etcd_db_critical_threshold),
+            etcd_leader_changes_per_hour: alerts.etcd_leader_changes_per_hour,
+            pod_restarts_per_hour: alerts.pod_restarts_per_hour,
+            disk_full_hours: (alerts.disk_full_warning_hours, alerts.disk_full_critical_hours),
+            quota_thresholds: (
+                alerts.quota_warning_threshold,
+                alerts.quota_critical_threshold,
+            ),
+            fd_thresholds: (alerts.fd_warning_threshold, alerts.fd_critical_threshold),
+            ephemeral_port_thresholds: (
+                alerts.ephemeral_port_warning_threshold,
+                alerts.ephemeral_port_critical_threshold,
+            ),
+            pid_thresholds: (alerts.pid_warning_threshold, alerts.pid_critical_threshold),
+            entropy_thresholds: (alerts.entropy_warning_bits, alerts.entropy_critical_bits),
+            sched_wait_thresholds: (alerts.sched_wait_warning_threshold, alerts.sched_wait_critical_threshold),
+            cpu_throttle_thresholds: (alerts.cpu_throttle_warning_threshold, alerts.cpu_throttle_critical_threshold),
+            freq_throttle: (alerts.freq_throttle_percent, alerts.freq_throttle_mins),
+            freq_throttled_since: None,
+        };
+        app.restore_ui_state(saved_state);
+        if app.k8s_collector.is_connected() {
+            app.permissions.start(&app.k8s_collector);
+        }
+        if app.local_mode {
+            app.current_screen = Screen::HostMap;
+        }
+        // A busy port shouldn't keep the console itself from starting
+        if let Some(e) = web_error {
+            app.report_error("Starting web bridge", &e);
+        }
+        if let Some(e) = receiver_error {
+            app.report_error("Starting webhook receiver", &e);
+        }
+        if let Some(e) = profile_error {
+            app.report_error("Loading alert profile", &e);
+        }
+        if let Some(e) = prometheus_error {
+            app.report_error("Setting up Prometheus", &e);
+        }
+        if let Some(e) = agents_error {
+            app.report_error("Setting up remote agents", &e);
+        }
+        Ok(app)
     }
 
     pub async fn update(&mut self) -> Result<()> {
+        if self.local_mode {
+            return self.update_local().await;
+        }
+        let limit = self.collector_timeout;
         match self.current_screen {
             Screen::Logs => {
-                self.logs = self.log_collector.collect().await?;
-                self.apply_log_filters();
+                let logs = timed(limit, self.log_collector.collect()).await;
+                self.store_logs(logs);
             }
             Screen::Dashboard => {
-                self.system_metrics = self.system_collector.collect().await?;
-                self.k8s_info = self.k8s_collector.collect_cluster_info().await?;
-                self.kubevirt_info = self.k8s_collector.collect_kubevirt_info().await?;
-
-                // Record metrics for history/sparklines
-                self.metrics_history.record_cpu(self.system_metrics.cpu_usage);
-                let memory_percent = if self.system_metrics.memory_total_gb > 0.0 {
-                    (self.system_metrics.memory_used_gb / self.system_metrics.memory_total_gb) * 100.0
-                } else {
-                    0.0
-                };
-                self.metrics_history.record_memory(memory_percent);
-                self.metrics_history.record_disk_io(
-                    self.system_metrics.disk_read_mb_s,
-                    self.system_metrics.disk_write_mb_s,
+                self.collect_health().await;
+                if self.dashboard_layout.contains(DashboardWidget::Heatmap) {
+                    let usage = timed(limit, self.k8s_collector.collect_vm_usage()).await;
+                    self.store_vm_usage(usage);
+                }
+                if self.dashboard_layout.contains(DashboardWidget::Reservation) {
+                    let pods = timed(limit, self.k8s_collector.collect_launcher_pods()).await;
+                    if let Some(pods) = self.record_timing("launcher pods", pods) {
+                        self.launcher_pods = pods.into_keys().collect();
+                    }
+                }
+                if self.dashboard_layout.contains(DashboardWidget::Logs) {
+                    let logs = timed(limit, self.log_collector.collect()).await;
+                    self.store_logs(logs);
+                }
+            }
+            Screen::Network => self.collect_network().await,
+            Screen::Fleet => self.collect_fleet().await,
+            Screen::HostMap => {
+                let (processes, pods, data_volumes) = tokio::join!(
+                    timed(limit, self.qemu_collector.collect()),
+                    timed(limit, self.k8s_collector.collect_launcher_pods()),
+                    timed(limit, self.k8s_collector.collect_data_volumes()),
                 );
-
-                // Evaluate alerts after collecting metrics
-                self.alert_manager.evaluate(
-                    &self.system_metrics,
-                    &self.k8s_info,
-                    &self.kubevirt_info,
+                let pods = self.record_timing("launcher pods", pods).unwrap_or_default();
+                self.store_qemu_processes(processes, &pods);
+                self.store_data_volumes(data_volumes);
+                self.update_guest_clocks().await;
+                self.update_guest_filesystems().await;
+                if self.host_map_view == HostMapView::Images {
+                    self.update_disk_images().await;
+                }
+                self.update_vm_detail().await;
+            }
+            Screen::Heatmap => {
+                let prometheus = self.prometheus_collector.as_mut();
+                let (usage, prometheus) = tokio::join!(
+                    timed(limit, self.k8s_collector.collect_vm_usage()),
+                    timed(limit, collect_prometheus(prometheus)),
+                );
+                self.store_prometheus(prometheus);
+                self.store_vm_usage(usage);
+                self.update_vm_detail().await;
+            }
+            Screen::Charts => {
+                let (system, power) = tokio::join!(
+                    timed(limit, self.system_collector.collect()),
+                    timed(limit, self.power_collector.collect()),
+                );
+                self.store_system(system);
+                self.store_power(power);
+            }
+            Screen::Timeline => {
+                let (logs, system) = tokio::join!(
+                    timed(limit, self.log_collector.collect()),
+                    timed(limit, self.system_collector.collect()),
                 );
+                self.store_logs(logs);
+                self.store_system(system);
+            }
+            // Custom commands run on their own intervals
+            Screen::Tools => {}
+            Screen::Resources => {
+                let resources = timed(
+                    limit,
+                    self.k8s_collector.collect_resources(self.resource_kind),
+                )
+                .await;
+                self.store_resources(resources);
+                self.update_vm_detail().await;
             }
-            Screen::Network => {
-                self.network_info = self.network_collector.collect().await?;
+        }
+        // Alerts don't wait for their screen to be shown
+        if let Some(group) = self.alert_sweep.next_due() {
+            tracing::debug!("collecting {} in the background for alerts", group.name());
+            match group {
+                SweepGroup::Health => self.collect_health().await,
+                SweepGroup::Network => self.collect_network().await,
+                SweepGroup::Fleet => self.collect_fleet().await,
             }
         }
+        self.evaluate_alerts();
+        // The catalog follows the imports it started wherever it is opened
+        if self.image_catalog.as_ref().is_some_and(|panel| !panel.hidden) && self.current_screen != Screen::HostMap {
+            let volumes = timed(limit, self.k8s_collector.collect_data_volumes()).await;
+            self.store_data_volumes(volumes);
+        }
+        // The footer's error and warning rates follow the journal wherever you are
+        if self.footer_log_rates && !self.screen_collects_logs() {
+            let logs = timed(limit, self.log_collector.collect()).await;
+            self.store_logs(logs);
+        }
+        self.update_computed_metrics();
+        self.update_kernel_log().await;
+        self.update_oom_kills().await;
+        self.update_crashes().await;
+        self.update_host_profile().await;
+        self.update_memory_leaks().await;
+        self.update_vm_boots().await;
+        self.update_external_alerts();
+        self.update_bridge_watch().await;
+        self.record_alert_markers();
+        self.update_health();
+        self.dispatch_notifications();
         Ok(())
     }
 
-    pub async fn refresh(&mut self) -> Result<()> {
-        // Force refresh all data
-        self.logs = self.log_collector.collect().await?;
-        self.system_metrics = self.system_collector.collect().await?;
-        self.network_info = self.network_collector.collect().await?;
-        self.k8s_info = self.k8s_collector.collect_cluster_info().await?;
-        self.kubevirt_info = self.k8s_collector.collect_kubevirt_info().await?;
-        Ok(())
+    /// Host, cluster and KubeVirt health, for the Dashboard and the alerts
+    /// that watch them
+    async fn collect_health(&mut self) {
+        let limit = self.collector_timeout;
+        let etcd = self.etcd_collector.as_mut();
+        let prometheus = self.prometheus_collector.as_mut();
+        let (system, cgroups, host, power, cluster, kubevirt, pod_health, quotas, api_health, etcd, prometheus, sockets, limits, multipath, disks, sysctls, hardware, kubelet) = tokio::join!(
+            timed(limit, self.system_collector.collect()),
+            timed(limit, self.cgroup_collector.collect()),
+            timed(limit, self.host_collector.collect()),
+            timed(limit, self.power_collector.collect()),
+            timed(limit, self.k8s_collector.collect_cluster_info()),
+            timed(limit, self.k8s_collector.collect_kubevirt_info()),
+            timed(limit, self.k8s_collector.collect_pod_health()),
+            timed(limit, self.k8s_collector.collect_quotas()),
+            timed(limit, self.k8s_collector.probe_api_health()),
+            timed(limit, collect_etcd(etcd)),
+            timed(limit, collect_prometheus(prometheus)),
+            timed(limit, self.socket_collector.collect()),
+            timed(limit, self.limits_collector.collect()),
+            timed(limit, self.multipath_collector.collect()),
+            timed(limit, self.disk_stats_collector.collect()),
+            timed(limit, self.sysctl_collector.collect()),
+            timed(limit, self.firmware_collector.collect()),
+            timed(limit, collect_kubelet(&self.k8s_collector, self.local_node.as_deref())),
+        );
+        self.store_system(system);
+        if let Some(usage) = self.record_timing("cgroups", cgroups) {
+            self.cgroup_usage = usage;
+        }
+        self.store_host(host);
+        self.store_power(power);
+        if let Some(info) = self.record_timing("cluster", cluster) {
+            self.k8s_info = info;
+        }
+        if let Some(info) = self.record_timing("kubevirt", kubevirt) {
+            self.kubevirt_info = info;
+        }
+        self.store_pod_health(pod_health);
+        self.store_quotas(quotas);
+        self.store_prometheus(prometheus);
+        self.store_api_health(api_health);
+        self.store_etcd(etcd);
+        self.store_sockets(sockets);
+        self.store_limits(limits);
+        self.store_multipath(multipath);
+        self.store_disk_latency(disks);
+        self.store_sysctls(sysctls);
+        self.store_hardware(hardware);
+        self.store_kubelet(kubelet);
+        self.alert_sweep.record(SweepGroup::Health);
     }
 
-    pub fn scroll_up(&mut self) {
-        self.scroll_offset = self.scroll_offset.saturating_sub(1);
+    async fn collect_network(&mut self) {
+        let limit = self.collector_timeout;
+        let dns = self.dns_collector.as_mut();
+        let routes = self.route_collector.as_mut();
+        // VIPs come from the Services listed on the previous tick
+        let (network, dns, routes, services, vips, sriov, pods, registries, pulls, policies) = tokio::join!(
+            timed(limit, self.network_collector.collect()),
+            timed(limit, collect_dns(dns, &self.k8s_collector)),
+            timed(limit, collect_routes(routes)),
+            timed(limit, self.k8s_collector.collect_service_health()),
+            timed(limit, self.vip_collector.collect(&self.service_health.endpoints)),
+            timed(limit, self.sriov_collector.collect()),
+            timed(limit, self.k8s_collector.collect_launcher_pods()),
+            timed(limit, self.registry_collector.collect()),
+            timed(limit, self.k8s_collector.collect_image_pulls()),
+            timed(limit, self.k8s_collector.collect_vm_network_policies()),
+        );
+        self.store_network(network);
+        self.store_dns(dns);
+        self.store_routes(routes);
+        self.store_service_health(services);
+        self.store_vips(vips);
+        let pods = self.record_timing("launcher pods", pods).unwrap_or_default();
+        self.store_sriov(sriov, &pods);
+        self.store_image_pulls(registries, pulls);
+        if let Some(policies) = self.record_timing("network policies", policies) {
+            self.vm_network_policies = policies;
+        }
+        self.alert_sweep.record(SweepGroup::Network);
     }
 
-    pub fn scroll_down(&mut self) {
-        self.scroll_offset = self.scroll_offset.saturating_add(1);
+    async fn collect_fleet(&mut self) {
+        let limit = self.collector_timeout;
+        let agents = self.agent_collector.as_mut();
+        let (fleet, migrations, tenants, agents) = tokio::join!(
+            timed(limit, self.k8s_collector.collect_fleet()),
+            timed(limit, self.k8s_collector.collect_migrations()),
+            timed(limit, collect_tenants(&self.k8s_collector, self.tenant_label.as_deref())),
+            timed(limit, collect_agents(agents)),
+        );
+        self.store_fleet(fleet);
+        self.store_migrations(migrations);
+        self.store_tenants(tenants);
+        self.store_agents(agents);
+        self.alert_sweep.record(SweepGroup::Fleet);
     }
 
-    pub fn apply_log_filters(&mut self) {
-        self.filtered_logs = if self.search_query.is_empty() && self.filter_level.is_none() {
-            self.logs.clone()
-        } else {
-            let mut filtered = self.logs.clone();
+    /// Whether the current screen's update already reads the journal
+    fn screen_collects_logs(&self) -> bool {
+        match self.current_screen {
+            Screen::Logs | Screen::Timeline => true,
+            Screen::Dashboard => self.dashboard_layout.contains(DashboardWidget::Logs),
+            _ => false,
+        }
+    }
 
-            // Apply search query filter
-            if !self.search_query.is_empty() {
-                let query_lower = self.search_query.to_lowercase();
-                filtered.retain(|log| {
-                    log.message.to_lowercase().contains(&query_lower)
-                        || log.service.to_lowercase().contains(&query_lower)
-                });
-            }
+    /// Force refresh all data, running every collector concurrently so a
+    /// slow or hung source only costs its own timeout
+    pub async fn refresh(&mut self) -> Result<()> {
+        self.custom_commands.refresh();
+        self.header_commands.refresh();
+        let limit = self.collector_timeout;
+        let dns = self.dns_collector.as_mut();
+        let routes = self.route_collector.as_mut();
+        let etcd = self.etcd_collector.as_mut();
+        let prometheus = self.prometheus_collector.as_mut();
+        let agents = self.agent_collector.as_mut();
+        let (logs, system, cgroups, host, power, network, cluster, kubevirt, pod_health, resources, dns, routes, services, vips, fleet, quotas, processes, pods, sriov, api_health, etcd, prometheus, registries, pulls, policies, sockets, limits, multipath, disks, sysctls, hardware, migrations, data_volumes, tenants, kubelet, agents) = tokio::join!(
+            timed(limit, self.log_collector.collect()),
+            timed(limit, self.system_collector.collect()),
+            timed(limit, self.cgroup_collector.collect()),
+            timed(limit, self.host_collector.collect()),
+            timed(limit, self.power_collector.collect()),
+            timed(limit, self.network_collector.collect()),
+            timed(limit, self.k8s_collector.collect_cluster_info()),
+            timed(limit, self.k8s_collector.collect_kubevirt_info()),
+            timed(limit, self.k8s_collector.collect_pod_health()),
+            timed(limit, self.k8s_collector.collect_resources(self.resource_kind)),
+            timed(limit, collect_dns(dns, &self.k8s_collector)),
+            timed(limit, collect_routes(routes)),
+            timed(limit, self.k8s_collector.collect_service_health()),
+            timed(limit, self.vip_collector.collect(&self.service_health.endpoints)),
+            timed(limit, self.k8s_collector.collect_fleet()),
+            timed(limit, self.k8s_collector.collect_quotas()),
+            timed(limit, self.qemu_collector.collect()),
+            timed(limit, self.k8s_collector.collect_launcher_pods()),
+            timed(limit, self.sriov_collector.collect()),
+            timed(limit, self.k8s_collector.probe_api_health()),
+            timed(limit, collect_etcd(etcd)),
+            timed(limit, collect_prometheus(prometheus)),
+            timed(limit, self.registry_collector.collect()),
+            timed(limit, self.k8s_collector.collect_image_pulls()),
+            timed(limit, self.k8s_collector.collect_vm_network_policies()),
+            timed(limit, self.socket_collector.collect()),
+            timed(limit, self.limits_collector.collect()),
+            timed(limit, self.multipath_collector.collect()),
+            timed(limit, self.disk_stats_collector.collect()),
+            timed(limit, self.sysctl_collector.collect()),
+            timed(limit, self.firmware_collector.collect()),
+            timed(limit, self.k8s_collector.collect_migrations()),
+            timed(limit, self.k8s_collector.collect_data_volumes()),
+            timed(limit, collect_tenants(&self.k8s_collector, self.tenant_label.as_deref())),
+            timed(limit, collect_kubelet(&self.k8s_collector, self.local_node.as_deref())),
+            timed(limit, collect_agents(agents)),
+        );
 
-            // Apply level filter
-            if let Some(ref level) = self.filter_level {
-                filtered.retain(|log| log.level.eq_ignore_ascii_case(level));
-            }
+        self.store_logs(logs);
+        self.store_system(system);
+        if let Some(usage) = self.record_timing("cgroups", cgroups) {
+            self.cgroup_usage = usage;
+        }
+        self.store_host(host);
+        self.store_power(power);
+        self.store_network(network);
+        if let Some(info) = self.record_timing("cluster", cluster) {
+            self.k8s_info = info;
+        }
+        if let Some(info) = self.record_timing("kubevirt", kubevirt) {
+            self.kubevirt_info = info;
+        }
+        self.store_pod_health(pod_health);
+        self.store_resources(resources);
+        self.store_dns(dns);
+        self.store_routes(routes);
+        self.store_service_health(services);
+        self.store_vips(vips);
+        self.store_kubelet(kubelet);
+        self.store_fleet(fleet);
+        self.store_quotas(quotas);
+        let pods = self.record_timing("launcher pods", pods).unwrap_or_default();
+        self.launcher_pods = pods.keys().cloned().collect();
+        self.store_qemu_processes(processes, &pods);
+        self.store_sriov(sriov, &pods);
+        self.store_prometheus(prometheus);
+        self.store_api_health(api_health);
+        self.store_etcd(etcd);
+        self.store_image_pulls(registries, pulls);
+        if let Some(policies) = self.record_timing("network policies", policies) {
+            self.vm_network_policies = policies;
+        }
+        self.store_sockets(sockets);
+        self.store_limits(limits);
+        self.store_multipath(multipath);
+        self.store_disk_latency(disks);
+        self.store_sysctls(sysctls);
+        self.store_hardware(hardware);
+        self.store_migrations(migrations);
+        self.store_tenants(tenants);
+        self.store_agents(agents);
+        self.store_data_volumes(data_volumes);
+        for group in [SweepGroup::Health, SweepGroup::Network, SweepGroup::Fleet] {
+            self.alert_sweep.record(group);
+        }
+        self.evaluate_alerts();
+        self.update_guest_clocks().await;
+        self.update_guest_filesystems().await;
+        self.update_computed_metrics();
+        self.update_kernel_log().await;
+        self.update_oom_kills().await;
+        self.update_crashes().await;
+        self.update_host_profile().await;
+        self.update_memory_leaks().await;
+        self.update_vm_boots().await;
+        self.update_external_alerts();
+        self.update_bridge_watch().await;
+        self.record_alert_markers();
+        self.update_health();
+        self.dispatch_notifications();
+        Ok(())
+    }
 
-            filtered
-        };
+    /// Score the host's health from what was just collected and chart it
+    fn update_health(&mut self) {
+        let health = crate::health::assess(self, &self.health_config);
+        self.metrics_history.record_health(health.score as f64);
+        self.health = Some(health);
     }
 
-    pub fn set_search_query(&mut self, query: String) {
-        self.search_query = query;
-        self.apply_log_filters();
+    /// Mark alerts that fired or resolved since the last update on the
+    /// metric history, for the chart view. Dismissed alerts aren't marked.
+    fn record_alert_markers(&mut self) {
+        let active: HashSet<String> = self.alert_manager.get_active_alerts().iter().map(|a| a.id.clone()).collect();
+        for alert in self.alert_manager.get_active_alerts() {
+            if !self.charted_alerts.contains(&alert.id) {
+                self.metrics_history.record_alert(AlertMarker {
+                    at: alert.triggered_at,
+                    level: alert.level,
+                    source: alert.metadata.source.clone(),
+                    title: alert.title.clone(),
+                    resolved: false,
+                });
+            }
+        }
+        for id in self.charted_alerts.difference(&active) {
+            let history = self.alert_manager.get_history();
+            let Some((alert, at)) = history.iter().rev().find(|a| &a.id == id).and_then(|a| Some((a, a.resolved_at?))) else {
+                continue;
+            };
+            self.metrics_history.record_alert(AlertMarker {
+                at,
+                level: alert.level,
+                source: alert.metadata.source.clone(),
+                title: alert.title.clone(),
+                resolved: true,
+            });
+        }
+        self.charted_alerts = active;
     }
 
-    pub fn toggle_filter_level(&mut self, level: &str) {
-        self.filter_level = if self.filter_level.as_deref() == Some(level) {
-            None
-        } else {
-            Some(level.to_string())
-        };
-        self.apply_log_filters();
+    /// Send newly fired alerts to notification channels and auto-actions,
+    /// and surface delivery failures and skipped actions as alerts
+    fn dispatch_notifications(&mut self) {
+        let mut notices = self.notifier.process(&self.alert_manager.get_active_alerts());
+        notices.extend(self.auto_actions.process(&self.alert_manager.get_active_alerts()));
+        for alert in notices {
+            self.alert_manager.add_alert(alert);
+        }
+        if let Some(ref bridge) = self.web_bridge {
+            bridge.publish(&self.web_snapshot());
+        }
     }
 
-    pub fn clear_filters(&mut self) {
-        self.search_query.clear();
-        self.filter_level = None;
-        self.apply_log_filters();
+    /// Read-only view of the current state for browsers on the web bridge
+    fn web_snapshot(&self) -> WebSnapshot {
+        let metrics = &self.system_metrics;
+        WebSnapshot {
+            host: sysinfo::System::host_name().unwrap_or_default(),
+            node: self.local_node.clone(),
+            updated: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            read_only: self.read_only,
+            system: WebSystem {
+                cpu_percent: metrics.cpu_usage,
+                memory_used_gb: metrics.memory_used_gb,
+                memory_total_gb: metrics.memory_total_gb,
+                disk_percent: metrics.disk_usage_percent,
+                load_avg: metrics.load_avg,
+                uptime_seconds: metrics.uptime_seconds,
+            },
+            cluster: WebCluster {
+                flavor: self.cluster_flavor.label().to_string(),
+                nodes_ready: self.k8s_info.nodes_ready,
+                nodes_total: self.k8s_info.nodes_total,
+                pods_running: self.k8s_info.pods_running,
+                vms_running: self.kubevirt_info.vms_running,
+                vms_stopped: self.kubevirt_info.vms_stopped,
+                vms_migrating: self.kubevirt_info.vms_migrating,
+            },
+            alerts: self.alert_manager.get_active_alerts().into_iter().cloned().collect(),
+            quotas: self.quotas.clone(),
+            // Newest first
+            logs: self.logs.iter().rev().take(WEB_LOG_LINES).map(|entry| (**entry).clone()).collect(),
+        }
     }
 
-    pub fn get_displayed_logs(&self) -> &[LogEntry] {
-        &self.filtered_logs
+    /// CPU usage for gauges, smoothed per `display.cpu_smoothing_secs`
+    /// Bring the header and footer text up to date before a draw
+    pub fn refresh_display_text(&mut self) {
+        let mut text = std::mem::take(&mut self.display_text);
+        text.refresh(self);
+        self.display_text = text;
     }
 
-    // Alert panel management
-    pub fn toggle_alert_panel(&mut self) {
-        self.alert_panel_open = !self.alert_panel_open;
-        if self.alert_panel_open {
-            self.alert_selected_index = 0;
-        }
+    pub fn displayed_cpu(&self) -> f64 {
+        self.metrics_history.smoothed_cpu().unwrap_or(self.system_metrics.cpu_usage)
     }
 
-    pub fn alert_navigate_up(&mut self) {
-        if self.alert_selected_index > 0 {
-            self.alert_selected_index -= 1;
+    /// The app's own health: collector runs, frame and update times, the
+    /// size of the in-memory buffers and log lines lost to them
+    pub fn telemetry_snapshot(&self) -> TelemetrySnapshot {
+        let (log_entries, log_bytes) = self.log_collector.buffer_usage();
+        let (timeline_entries, timeline_bytes) = self.timeline.usage();
+        let history = self.alert_manager.get_history();
+        let history_bytes = history
+            .iter()
+            .map(|alert| std::mem::size_of::<Alert>() + alert.title.len() + alert.message.len())
+            .sum();
+        let (log_lines_evicted, journal_reads_truncated) = self.log_collector.dropped();
+        TelemetrySnapshot {
+            time: Local::now().to_rfc3339(),
+            uptime_secs: self.telemetry.uptime().as_secs(),
+            rss_bytes: rss_bytes(),
+            draw: self.telemetry.draw,
+            update: self.telemetry.update,
+            collectors: self.collector_timings.iter().map(CollectorSample::from).collect(),
+            buffers: vec![
+                BufferUsage {
+                    name: "logs",
+                    entries: log_entries,
+                    bytes: log_bytes,
+                    limit_bytes: Some(self.log_collector.max_bytes()),
+                },
+                BufferUsage { name: "timeline", entries: timeline_entries, bytes: timeline_bytes, limit_bytes: None },
+                BufferUsage {
+                    name: "alert history",
+                    entries: history.len(),
+                    bytes: history_bytes,
+                    limit_bytes: None,
+                },
+            ],
+            log_lines_evicted,
+            journal_reads_truncated,
         }
     }
 
-    pub fn alert_navigate_down(&mut self) {
-        let alert_count = self.alert_manager.active_count();
-        if self.alert_selected_index < alert_count.saturating_sub(1) {
-            self.alert_selected_index += 1;
+    /// Append a telemetry snapshot
+    pub fn write_telemetry(&self) {
+        if let Err(e) = self.telemetry.write(&self.telemetry_snapshot()) {
+            tracing::warn!("{:#}", e);
         }
     }
 
-    pub fn dismiss_selected_alert(&mut self) {
-        let alerts = self.alert_manager.get_active_alerts();
-        if let Some(alert) = alerts.get(self.alert_selected_index) {
-            let id = alert.id.clone();
+    /// Record the duration and outcome of a collector run, yielding its data
+    /// on success. Failures keep the previous data on screen.
+    fn record_timing<T>(&mut self, name: &'static str, (elapsed, result): (Duration, Result<T>)) -> Option<T> {
+        let idx = match self.collector_timings.iter().position(|t| t.name == name) {
+            Some(idx) => idx,
+            None => {
+                self.collector_timings.push(CollectorTiming {
+                    name,
+                    ..CollectorTiming::default()
+                });
+                self.collector_timings.len() - 1
+            }
+        };
+        let timing = &mut self.collector_timings[idx];
+        let was_failing = timing.last_error.is_some();
+        timing.last = elapsed;
+        timing.max = timing.max.max(elapsed);
+        timing.runs += 1;
+
+        match result {
+            Ok(value) => {
+                timing.last_error = None;
+                Some(value)
+            }
+            Err(e) => {
+                tracing::warn!("{} collector failed: {:#}", name, e);
+                timing.failures += 1;
+                timing.last_error = Some(format!("{:#}", e));
+                // Only toast when a collector starts failing, not every tick
+                if !was_failing {
+                    self.push_message(MessageLevel::Warning, format!("{} collector failed: {:#}", name, e));
+                }
+                None
+            }
+        }
+    }
+
+    fn store_logs(&mut self, logs: (Duration, Result<Vec<Arc<LogEntry>>>)) {
+        if let Some(logs) = self.record_timing("logs", logs) {
+            let counts = self.log_collector.take_counts();
+            self.metrics_history.record_log_counts(&counts);
+            self.timeline.record_logs(&logs);
+            self.logs = logs;
+            self.live_logs_changed();
+        }
+    }
+
+    fn store_system(&mut self, system: (Duration, Result<SystemMetrics>)) {
+        let Some(metrics) = self.record_timing("system", system) else {
+            return;
+        };
+
+        // Record metrics for history/sparklines/charts
+        self.metrics_history.record_cpu(metrics.cpu_usage);
+        let memory_percent = if metrics.memory_total_gb > 0.0 {
+            (metrics.memory_used_gb / metrics.memory_total_gb) * 100.0
+        } else {
+            0.0
+        };
+        self.metrics_history.record_memory(memory_percent);
+        self.metrics_history.record_disk_io(metrics.disk_read_mb_s, metrics.disk_write_mb_s);
+        self.metrics_history.record_disk_usage(metrics.disk_usage_percent);
+        self.metrics_history.record_filesystems(&metrics.filesystems);
+
+        if self.alerts_enabled {
+            let forecasts = metrics
+                .filesystems
+                .iter()
+                .filter_map(|fs| Some((fs.clone(), self.metrics_history.time_to_full(&fs.mount_point)?)))
+                .collect();
+            let (warning_hours, critical_hours) = self.disk_full_hours;
+            self.alert_manager.evaluate_rule(&DiskForecastRule {
+                forecasts,
+                warning_hours,
+                critical_hours,
+            });
+        }
+
+        self.timeline.record_metrics(&metrics);
+        self.system_metrics = metrics;
+        self.system_metrics_fresh = true;
+
+        let snapshot = self.local_snapshot();
+        if let Some(CompareView { kind: CompareKind::History, ref mut right, .. }) = self.compare {
+            *right = snapshot.clone();
+        }
+        self.snapshots.record(snapshot);
+    }
+
+    /// Run the built-in rules once per fresh system sample. Off the
+    /// screens that collect it, a sample only arrives with the alert sweep,
+    /// and the anomaly baselines would otherwise take in the same one on
+    /// every tick in between.
+    fn evaluate_alerts(&mut self) {
+        if std::mem::take(&mut self.system_metrics_fresh) {
+            self.alert_manager.evaluate(&self.system_metrics, &self.k8s_info, &self.kubevirt_info);
+        }
+    }
+
+    /// This node as it stands, for the snapshot history and the compare view
+    fn local_snapshot(&self) -> NodeSnapshot {
+        let metrics = &self.system_metrics;
+        let node = self.local_node.clone().unwrap_or_else(|| "host".to_string());
+        let fleet_row = self.fleet.iter().find(|row| row.name == node);
+
+        let mut vms: Vec<String> = self.qemu_processes.iter().filter_map(|p| p.vm.clone()).collect();
+        if vms.is_empty() {
+            vms = fleet_row.map(|row| row.vms.clone()).unwrap_or_default();
+        }
+        let conditions = match self.kubelet_status {
+            Some(ref kubelet) => kubelet
+                .conditions
+                .iter()
+                .filter(|c| c.is_problem())
+                .map(|c| if c.kind == "Ready" { format!("Ready={}", c.status) } else { c.kind.clone() })
+                .collect(),
+            None => fleet_row.map(|row| row.pressures.clone()).unwrap_or_default(),
+        };
+        NodeSnapshot {
+            taken_at: chrono::Local::now(),
+            cpu_percent: Some(metrics.cpu_usage),
+            memory_percent: (metrics.memory_total_gb > 0.0)
+                .then(|| metrics.memory_used_gb / metrics.memory_total_gb * 100.0),
+            load: Some(metrics.load_avg),
+            disk_percent: Some(metrics.disk_usage_percent),
+            pods: fleet_row.map(|row| row.pods),
+            vms: sorted(vms),
+            conditions,
+            alerts: self.node_alert_titles(&node),
+            kubelet_version: self
+                .kubelet_status
+                .as_ref()
+                .map(|kubelet| kubelet.kubelet_version.clone())
+                .or_else(|| fleet_row.map(|row| row.kubelet_version.clone()))
+                .filter(|version| !version.is_empty()),
+            node,
+        }
+    }
+
+    /// Titles of the active alerts about `node`; for this host that
+    /// includes the host alerts, which carry no node
+    fn node_alert_titles(&self, node: &str) -> Vec<String> {
+        let local = self.local_node.as_deref().is_none_or(|local| local == node);
+        self.alert_manager
+            .get_active_alerts()
+            .into_iter()
+            .filter(|alert| match alert.metadata.node_name.as_deref() {
+                Some(name) => name == node,
+                None => local,
+            })
+            .map(|alert| alert.title.clone())
+            .collect()
+    }
+
+    fn store_network(&mut self, network: (Duration, Result<NetworkInfo>)) {
+        let Some(info) = self.record_timing("network", network) else {
+            return;
+        };
+        self.network_info = info;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&MtuRule {
+                mismatches: self.network_info.mtu_mismatches.clone(),
+                labels: self.interface_labels.clone(),
+            });
+        }
+    }
+
+    fn store_dns(&mut self, dns: (Duration, Result<Option<Vec<DnsResolverStatus>>>)) {
+        let Some(Some(status)) = self.record_timing("dns", dns) else {
+            return;
+        };
+        self.dns_status = status;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&DnsRule {
+                resolvers: self.dns_status.clone(),
+                failure_threshold: self.dns_failure_threshold,
+            });
+        }
+    }
+
+    fn store_routes(&mut self, routes: (Duration, Result<Option<RouteStatus>>)) {
+        let Some(Some(status)) = self.record_timing("routes", routes) else {
+            return;
+        };
+        self.route_status = Some(status);
+
+        if self.alerts_enabled {
+            if let Some(ref status) = self.route_status {
+                self.alert_manager.evaluate_rule(&RouteRule {
+                    status: status.clone(),
+                    gateway_failure_threshold: self.gateway_failure_threshold,
+                    flap_threshold: self.route_flap_threshold,
+                });
+            }
+        }
+    }
+
+    fn store_fleet(&mut self, fleet: (Duration, Result<Vec<NodeStatus>>)) {
+        let Some(fleet) = self.record_timing("fleet", fleet) else {
+            return;
+        };
+        self.fleet = fleet;
+        self.fleet_selected = self.fleet_selected.min(self.fleet.len().saturating_sub(1));
+        if let Some(CompareView { kind: CompareKind::Nodes, ref left, ref right }) = self.compare {
+            if let (Some(left), Some(right)) = (self.fleet_snapshot(&left.node), self.fleet_snapshot(&right.node)) {
+                self.compare = Some(CompareView { kind: CompareKind::Nodes, left, right });
+            }
+        }
+
+        if self.alerts_enabled {
+            // This node's pressures come from the kubelet rule, with more detail
+            let mut nodes = self.fleet.clone();
+            if let Some(ref kubelet) = self.kubelet_status {
+                for node in nodes.iter_mut().filter(|node| node.name == kubelet.node) {
+                    node.pressures.clear();
+                }
+            }
+            self.alert_manager.evaluate_rule(&NodeConditionRule { nodes });
+        }
+    }
+
+    /// Keep the local node's kubelet view and alert on its conditions
+    fn store_kubelet(&mut self, status: (Duration, Result<Option<KubeletStatus>>)) {
+        let Some(status) = self.record_timing("kubelet", status) else {
+            return;
+        };
+        self.kubelet_status = status;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&KubeletRule {
+                status: self.kubelet_status.clone().unwrap_or_default(),
+            });
+        }
+    }
+
+    /// Limits from `[tenants.limits]`, by tenant
+    pub fn tenant_limits(&self) -> &BTreeMap<String, TenantLimits> {
+        &self.tenants_config.limits
+    }
+
+    /// Keep a footprint sample per tenant every few minutes over the growth
+    /// window and alert on limits reached or fast growth
+    fn store_tenants(&mut self, tenants: (Duration, Result<Option<Vec<TenantUsage>>>)) {
+        let Some(Some(mut tenants)) = self.record_timing("tenants", tenants) else {
+            return;
+        };
+        let now = Instant::now();
+        let window = Duration::from_secs(self.tenants_config.growth_window_hours * 3600);
+        let seen: HashSet<&str> = tenants.iter().map(|tenant| tenant.tenant.as_str()).collect();
+        self.tenant_history.retain(|tenant, _| seen.contains(tenant.as_str()));
+        for tenant in &mut tenants {
+            let (cores, memory) = (tenant.cpu_cores(), tenant.total_memory_bytes());
+            let history = self.tenant_history.entry(tenant.tenant.clone()).or_default();
+            if history.back().is_none_or(|&(at, _, _)| now.duration_since(at).as_secs() >= TENANT_SAMPLE_SECS) {
+                history.push_back((now, cores, memory));
+            }
+            while history.front().is_some_and(|&(at, _, _)| now.duration_since(at) > window) {
+                history.pop_front();
+            }
+            let growth = |now: f64, then: f64| (then > 0.0).then(|| (now - then) / then * 100.0);
+            tenant.growth_percent = history.front().and_then(|&(_, old_cores, old_memory)| {
+                match (growth(cores, old_cores), growth(memory as f64, old_memory as f64)) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    (a, b) => a.or(b),
+                }
+            });
+        }
+        // Largest footprint first
+        tenants.sort_by(|a, b| b.cpu_cores().total_cmp(&a.cpu_cores()).then_with(|| a.tenant.cmp(&b.tenant)));
+        self.tenant_usage = tenants;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&TenantRule {
+                tenants: self.tenant_usage.clone(),
+                limits: self.tenants_config.limits.clone(),
+                growth_percent: self.tenants_config.growth_alert_percent,
+            });
+        }
+    }
+
+    fn store_quotas(&mut self, quotas: (Duration, Result<Vec<QuotaUsage>>)) {
+        let Some(mut quotas) = self.record_timing("quotas", quotas) else {
+            return;
+        };
+        // Closest to the limit first
+        quotas.sort_by(|a, b| b.percent().total_cmp(&a.percent()));
+        self.quotas = quotas;
+
+        if self.alerts_enabled {
+            let (warning_threshold, critical_threshold) = self.quota_thresholds;
+            self.alert_manager.evaluate_rule(&QuotaRule {
+                quotas: self.quotas.clone(),
+                warning_threshold,
+                critical_threshold,
+            });
+        }
+    }
+
+    fn store_migrations(&mut self, migrations: (Duration, Result<Vec<MigrationRecord>>)) {
+        let Some(migrations) = self.record_timing("migrations", migrations) else {
+            return;
+        };
+        self.migration_history.record(migrations);
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&MigrationRule {
+                nodes: self.migration_history.failing_nodes(self.migration_failure_threshold),
+                threshold: self.migration_failure_threshold,
+            });
+        }
+    }
+
+    /// Record VM starts every `BOOT_CHECK_INTERVAL`, whatever the screen,
+    /// and flag VMs booting much slower than they usually do
+    async fn update_vm_boots(&mut self) {
+        if self.boots_checked.is_some_and(|at| at.elapsed() < BOOT_CHECK_INTERVAL) {
+            return;
+        }
+        self.boots_checked = Some(Instant::now());
+        let boots = timed(self.collector_timeout, self.k8s_collector.collect_vm_boots()).await;
+        let Some(boots) = self.record_timing("vm boots", boots) else {
+            return;
+        };
+        self.boot_history.record(boots);
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&SlowBootRule {
+                boots: self.boot_history.slow_boots(self.boot_slow_factor, self.boot_slow_min),
+                factor: self.boot_slow_factor,
+            });
+        }
+    }
+
+    /// Carry over when each DataVolume last moved, so one sitting at the
+    /// same phase and progress can be reported stuck
+    fn store_data_volumes(&mut self, volumes: (Duration, Result<Vec<DataVolume>>)) {
+        let Some(mut volumes) = self.record_timing("data volumes", volumes) else {
+            return;
+        };
+        let now = chrono::Local::now();
+        for volume in &mut volumes {
+            let previous = self.data_volumes.iter().find(|dv| dv.namespace == volume.namespace && dv.name == volume.name);
+            volume.changed = match previous {
+                Some(dv) if dv.phase == volume.phase && dv.progress == volume.progress => dv.changed,
+                _ => Some(now),
+            };
+        }
+        self.data_volumes = volumes;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&DataVolumeRule {
+                volumes: self.data_volumes.clone(),
+                stall_mins: self.import_stall_mins,
+            });
+        }
+    }
+
+    fn store_sockets(&mut self, usage: (Duration, Result<SocketUsage>)) {
+        let Some(usage) = self.record_timing("sockets", usage) else {
+            return;
+        };
+        self.socket_usage = usage;
+
+        if self.alerts_enabled {
+            let (fd_warning_threshold, fd_critical_threshold) = self.fd_thresholds;
+            let (port_warning_threshold, port_critical_threshold) = self.ephemeral_port_thresholds;
+            self.alert_manager.evaluate_rule(&SocketRule {
+                usage: self.socket_usage.clone(),
+                fd_warning_threshold,
+                fd_critical_threshold,
+                port_warning_threshold,
+                port_critical_threshold,
+            });
+        }
+    }
+
+    fn store_limits(&mut self, limits: (Duration, Result<HostLimits>)) {
+        let Some(limits) = self.record_timing("limits", limits) else {
+            return;
+        };
+        self.host_limits = limits;
+
+        if self.alerts_enabled {
+            let (pid_warning_threshold, pid_critical_threshold) = self.pid_thresholds;
+            let (entropy_warning_bits, entropy_critical_bits) = self.entropy_thresholds;
+            self.alert_manager.evaluate_rule(&LimitsRule {
+                limits: self.host_limits.clone(),
+                pid_warning_threshold,
+                pid_critical_threshold,
+                entropy_warning_bits,
+                entropy_critical_bits,
+            });
+        }
+    }
+
+    fn store_host(&mut self, info: (Duration, Result<HostInfo>)) {
+        let Some(info) = self.record_timing("host", info) else {
+            return;
+        };
+        self.host_info = info;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&UnitRule { services: self.host_info.services.clone() });
+        }
+    }
+
+    fn store_multipath(&mut self, info: (Duration, Result<MultipathInfo>)) {
+        let Some(info) = self.record_timing("multipath", info) else {
+            return;
+        };
+        self.multipath = info;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&MultipathRule { info: self.multipath.clone() });
+        }
+    }
+
+    fn store_disk_latency(&mut self, disks: (Duration, Result<Vec<DiskLatency>>)) {
+        let Some(disks) = self.record_timing("disk latency", disks) else {
+            return;
+        };
+        self.disk_latency = disks;
+
+        if self.alerts_enabled {
+            let (warning_ms, critical_ms) = self.disk_latency_thresholds;
+            self.alert_manager.evaluate_rule(&DiskLatencyRule {
+                disks: self.disk_latency.clone(),
+                warning_ms,
+                critical_ms,
+            });
+            self.alert_manager.evaluate_rule(&IoSchedulerRule { disks: self.disk_latency.clone() });
+        }
+    }
+
+    fn store_hardware(&mut self, info: (Duration, Result<HardwareInfo>)) {
+        let Some(info) = self.record_timing("firmware", info) else {
+            return;
+        };
+        self.hardware = info;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&FirmwareRule { info: self.hardware.clone() });
+        }
+    }
+
+    fn store_sysctls(&mut self, sysctls: (Duration, Result<Vec<Sysctl>>)) {
+        let Some(sysctls) = self.record_timing("sysctls", sysctls) else {
+            return;
+        };
+        self.sysctls = sysctls;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&SysctlRule { sysctls: self.sysctls.clone() });
+        }
+    }
+
+    /// Read new kernel log records on every update, whatever the screen,
+    /// so hardware errors raise alerts without the log view being open
+    async fn update_kernel_log(&mut self) {
+        let Some(collector) = self.kmsg_collector.as_mut() else {
+            return;
+        };
+        let entries = timed(self.collector_timeout, collector.collect()).await;
+        let Some(entries) = self.record_timing("kernel log", entries) else {
+            return;
+        };
+        let kills = self.kmsg_collector.as_mut().map(KmsgCollector::take_oom_kills).unwrap_or_default();
+        self.oom_collector.record(kills);
+        if !entries.is_empty() {
+            self.logs = self.log_collector.push(entries);
+            self.timeline.record_logs(&self.logs);
+            self.live_logs_changed();
+        }
+
+        if self.alerts_enabled {
+            let events = self.kmsg_collector.iter().flat_map(|c| c.events()).cloned().collect();
+            self.alert_manager.evaluate_rule(&KernelRule { events, labels: self.interface_labels.clone() });
+        }
+    }
+
+    /// Evaluate `[[computed_metrics]]` over the latest collected values and
+    /// record them. Cluster and VM counts are left out while they are mock data.
+    fn update_computed_metrics(&mut self) {
+        if self.computed_metrics.is_empty() {
+            return;
+        }
+        let system = &self.system_metrics;
+        let mut values = BTreeMap::from([
+            ("cpu_usage", system.cpu_usage),
+            ("memory_used_gb", system.memory_used_gb),
+            ("memory_total_gb", system.memory_total_gb),
+            ("disk_usage_percent", system.disk_usage_percent),
+            ("disk_read_mb_s", system.disk_read_mb_s),
+            ("disk_write_mb_s", system.disk_write_mb_s),
+            ("load_avg", system.load_avg),
+            ("uptime_hours", system.uptime_seconds as f64 / 3600.0),
+        ]);
+        if system.memory_total_gb > 0.0 {
+            values.insert("memory_percent", system.memory_used_gb / system.memory_total_gb * 100.0);
+        }
+        if let Some(watts) = self.power_info.package_watts {
+            values.insert("package_watts", watts);
+        }
+        if self.k8s_collector.is_connected() {
+            values.insert("nodes_ready", self.k8s_info.nodes_ready as f64);
+            values.insert("nodes_total", self.k8s_info.nodes_total as f64);
+            values.insert("pods_running", self.k8s_info.pods_running as f64);
+            values.insert("services", self.k8s_info.services as f64);
+        }
+        if self.k8s_collector.is_connected() || self.k8s_collector.has_external_virt() {
+            values.insert("vms_running", self.kubevirt_info.vms_running as f64);
+            values.insert("vms_stopped", self.kubevirt_info.vms_stopped as f64);
+            values.insert("vms_migrating", self.kubevirt_info.vms_migrating as f64);
+        }
+
+        values.extend(self.prometheus_metrics.variables.iter().map(|(name, &value)| (name.as_str(), value)));
+
+        for metric in &mut self.computed_metrics {
+            metric.update(&values);
+            if let Some(value) = metric.value {
+                self.metrics_history.record_computed(&metric.config.name, value);
+            }
+        }
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&ComputedMetricRule { metrics: self.computed_metrics.clone() });
+        }
+    }
+
+    /// Count OOM kills in cgroups the kernel log didn't report, name the
+    /// pods of new ones and alert on each victim, whatever the screen
+    async fn update_oom_kills(&mut self) {
+        let scanned = timed(self.collector_timeout, self.oom_collector.collect()).await;
+        if self.record_timing("oom kills", scanned).is_none() {
+            return;
+        }
+        let uids = self.oom_collector.unresolved_pods();
+        if !uids.is_empty() {
+            let pods = timed(self.collector_timeout, self.k8s_collector.collect_pods_by_uid()).await;
+            // Not retried on failure; the kill keeps its pod UID
+            let pods = self.record_timing("pods by uid", pods).unwrap_or_default();
+            self.oom_collector.resolve_pods(uids, &pods);
+        }
+        self.oom_kills = self.oom_collector.kills().cloned().collect();
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&OomRule {
+                kills: self.oom_kills.clone(),
+                window_mins: self.oom_window_mins,
+            });
+        }
+    }
+
+    /// Look for new core dumps every scan interval, whatever the screen, so
+    /// a crashed qemu or k3s raises an alert without the card being shown
+    async fn update_crashes(&mut self) {
+        let Some(collector) = self.crash_collector.as_mut() else {
+            return;
+        };
+        if self.crash_checked.is_some_and(|at| at.elapsed() < self.crash_interval) {
+            return;
+        }
+        self.crash_checked = Some(Instant::now());
+        let dumps = timed(self.collector_timeout, collector.collect()).await;
+        let Some(dumps) = self.record_timing("crashes", dumps) else {
+            return;
+        };
+        self.crash_dumps = dumps;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&CrashRule {
+                dumps: self.crash_dumps.clone(),
+                window_mins: self.crash_window_mins,
+            });
+        }
+    }
+
+    /// Compare the host against `[host_profile]` at startup and every
+    /// check interval, whatever the screen
+    async fn update_host_profile(&mut self) {
+        let Some(collector) = self.host_profile_collector.as_mut() else {
+            return;
+        };
+        if self.host_profile_checked.is_some_and(|at| at.elapsed() < self.host_profile_interval) {
+            return;
+        }
+        self.host_profile_checked = Some(Instant::now());
+        let deviations = timed(self.collector_timeout, collector.collect()).await;
+        let Some(deviations) = self.record_timing("host profile", deviations) else {
+            return;
+        };
+        self.profile_deviations = deviations;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&HostProfileRule { deviations: self.profile_deviations.clone() });
+        }
+    }
+
+    /// Sample the daemons of `[memory_leaks]` every sample interval,
+    /// whatever the screen, and judge their growth over the leak window
+    async fn update_memory_leaks(&mut self) {
+        let Some(collector) = self.process_memory_collector.as_mut() else {
+            return;
+        };
+        if self.process_memory_checked.is_some_and(|at| at.elapsed() < self.process_memory_interval) {
+            return;
+        }
+        self.process_memory_checked = Some(Instant::now());
+        let memory = timed(self.collector_timeout, collector.collect()).await;
+        let Some(memory) = self.record_timing("daemon memory", memory) else {
+            return;
+        };
+        for process in &memory {
+            self.metrics_history.record_process_rss(&process.name, process.rss_mb());
+        }
+        self.process_memory = memory;
+        self.leak_selected = self.leak_selected.min(self.process_memory.len().saturating_sub(1));
+
+        if self.alerts_enabled {
+            let processes = self
+                .process_memory
+                .iter()
+                .map(|process| {
+                    let growth = self.metrics_history.memory_growth(&process.name, self.leak_window_hours);
+                    (process.clone(), growth)
+                })
+                .collect();
+            self.alert_manager.evaluate_rule(&MemoryLeakRule {
+                processes,
+                growth_mb_per_hour: self.leak_growth_mb_per_hour,
+                window_hours: self.leak_window_hours,
+                limits_mb: self.leak_limits_mb.clone(),
+            });
+        }
+    }
+
+    /// Take in webhook posts: firing alerts are kept until resolved, past
+    /// their endsAt, or `receiver.expire_mins` without being re-sent
+    fn update_external_alerts(&mut self) {
+        let Some(receiver) = self.webhook_receiver.as_mut() else {
+            return;
+        };
+        for alert in receiver.drain() {
+            if alert.firing {
+                self.external_alerts.insert(alert.key.clone(), alert);
+            } else {
+                self.external_alerts.remove(&alert.key);
+            }
+        }
+        let now = Local::now();
+        let expiry = self.external_expiry;
+        self.external_alerts.retain(|_, alert| {
+            alert.ends_at.is_none_or(|at| at > now) && now - alert.received_at < expiry
+        });
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&ExternalAlertRule {
+                alerts: self.external_alerts.values().cloned().collect(),
+            });
+        }
+    }
+
+    /// Read ARP and DHCP packets from the watched bridges on every update,
+    /// so short bursts of conflicting claims aren't missed
+    async fn update_bridge_watch(&mut self) {
+        let Some(collector) = self.bridge_watch.as_mut() else {
+            return;
+        };
+        let result = timed(self.collector_timeout, collector.collect()).await;
+        if self.record_timing("bridge watch", result).is_none() {
+            return;
+        }
+
+        if self.alerts_enabled {
+            let Some(ref collector) = self.bridge_watch else {
+                return;
+            };
+            self.alert_manager.evaluate_rule(&BridgeConflictRule {
+                conflicts: collector.conflicts(),
+                churn: collector.churn(self.dhcp_churn_threshold),
+                window_mins: self.ip_conflict_window_mins,
+                labels: self.interface_labels.clone(),
+            });
+        }
+    }
+
+    /// Keep the last scrape; a failed one leaves the previous values
+    fn store_prometheus(&mut self, metrics: (Duration, Result<Option<PrometheusMetrics>>)) {
+        if let Some(Some(metrics)) = self.record_timing("prometheus", metrics) {
+            self.prometheus_metrics = metrics;
+        }
+    }
+
+    fn store_agents(&mut self, agents: (Duration, Result<Option<Vec<AgentStatus>>>)) {
+        let Some(Some(agents)) = self.record_timing("remote agents", agents) else {
+            return;
+        };
+        self.agents = agents;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&AgentRule {
+                agents: self.agents.clone(),
+                failure_threshold: self.agent_failure_threshold,
+            });
+        }
+    }
+
+    /// Local mode: only what the host itself can tell, whatever the
+    /// screen, plus the API probe that notices the control plane is back.
+    /// Processes keep the launcher pods they were last attributed to.
+    async fn update_local(&mut self) -> Result<()> {
+        let limit = self.collector_timeout;
+        let (logs, system, network, processes, api_health) = tokio::join!(
+            timed(limit, self.log_collector.collect()),
+            timed(limit, self.system_collector.collect()),
+            timed(limit, self.network_collector.collect()),
+            timed(limit, self.qemu_collector.collect()),
+            timed(limit, self.k8s_collector.probe_api_health()),
+        );
+        self.store_logs(logs);
+        self.store_system(system);
+        self.store_network(network);
+        let pods: HashMap<String, String> = self
+            .qemu_processes
+            .iter()
+            .filter_map(|p| Some((p.pod_uid.clone()?, p.pod.clone()?)))
+            .collect();
+        self.store_qemu_processes(processes, &pods);
+        self.store_api_health(api_health);
+
+        self.evaluate_alerts();
+        self.update_health();
+        Ok(())
+    }
+
+    fn store_api_health(&mut self, health: (Duration, Result<ApiHealth>)) {
+        let health = self.record_timing("api health", health);
+        // A probe that timed out or had neither request answered
+        let reachable = health.as_ref().is_some_and(|h| h.healthz_ms.is_some() || h.list_ms.is_some());
+        self.track_control_plane(reachable);
+        let Some(mut health) = health else {
+            return;
+        };
+        health.p99_ms = self.prometheus_metrics.api_latency_ms;
+        if let Some(latency) = health.latency_ms() {
+            self.metrics_history.record_api_latency(latency);
+        }
+        if self.alerts_enabled {
+            let (warning_ms, critical_ms) = self.api_latency_thresholds;
+            self.alert_manager.evaluate_rule(&ApiHealthRule {
+                health: health.clone(),
+                warning_ms,
+                critical_ms,
+            });
+        }
+        self.api_health = Some(health);
+    }
+
+    /// Switch to local mode after `local_mode_after` failed probes in a
+    /// row, and back once one succeeds unless it was entered by hand
+    fn track_control_plane(&mut self, reachable: bool) {
+        if reachable {
+            self.api_failures = 0;
+            if self.local_mode && !self.local_mode_pinned {
+                self.local_mode = false;
+                self.push_message(MessageLevel::Info, "API server reachable again, leaving local mode");
+            }
+            return;
+        }
+        self.api_failures = self.api_failures.saturating_add(1);
+        if !self.local_mode && self.local_mode_after > 0 && self.api_failures >= self.local_mode_after {
+            self.enter_local_mode();
+        }
+    }
+
+    fn enter_local_mode(&mut self) {
+        self.local_mode = true;
+        self.current_screen = Screen::HostMap;
+        self.host_map_view = HostMapView::Processes;
+        self.push_message(
+            MessageLevel::Warning,
+            "API server unreachable: local mode, showing what this host sees of its VMs",
+        );
+    }
+
+    /// 'L' on the host map: enter local mode without waiting for probes to
+    /// fail, or leave it; the next failed probes can enter it again
+    pub fn toggle_local_mode(&mut self) {
+        if self.local_mode {
+            self.local_mode = false;
+            self.local_mode_pinned = false;
+            self.api_failures = 0;
+            self.push_message(MessageLevel::Info, "Left local mode");
+        } else {
+            self.local_mode_pinned = true;
+            self.enter_local_mode();
+        }
+    }
+
+    /// etcd read on this host, or else from Prometheus, so agent nodes and
+    /// kine-backed servers still see the cluster's datastore
+    fn store_etcd(&mut self, status: (Duration, Result<Option<EtcdStatus>>)) {
+        let Some(status) = self.record_timing("etcd", status) else {
+            return;
+        };
+        let status = status.or_else(|| self.prometheus_metrics.etcd.clone());
+        if self.alerts_enabled {
+            let (warning_percent, critical_percent) = self.etcd_db_thresholds;
+            self.alert_manager.evaluate_rule(&EtcdRule {
+                status: status.clone().unwrap_or_default(),
+                warning_percent,
+                critical_percent,
+                leader_changes_per_hour: self.etcd_leader_changes_per_hour,
+            });
+        }
+        self.etcd_status = status;
+    }
+
+    /// Keep the host-side qemu scan, attributing each process to its
+    /// virt-launcher pod when the cluster lookup succeeded
+    fn store_qemu_processes(
+        &mut self,
+        processes: (Duration, Result<Vec<QemuProcess>>),
+        pods: &HashMap<String, String>,
+    ) {
+        let Some(mut processes) = self.record_timing("qemu", processes) else {
+            return;
+        };
+        for process in &mut processes {
+            process.pod = process.pod_uid.as_ref().and_then(|uid| pods.get(uid)).cloned();
+        }
+        self.qemu_processes = processes;
+        self.host_map_selected = self.host_map_selected.min(self.qemu_processes.len().saturating_sub(1));
+
+        if self.alerts_enabled {
+            let (wait_warning_threshold, wait_critical_threshold) = self.sched_wait_thresholds;
+            let (throttle_warning_threshold, throttle_critical_threshold) = self.cpu_throttle_thresholds;
+            self.alert_manager.evaluate_rule(&SchedLatencyRule {
+                processes: self.qemu_processes.clone(),
+                wait_warning_threshold,
+                wait_critical_threshold,
+                throttle_warning_threshold,
+                throttle_critical_threshold,
+            });
+        }
+    }
+
+    /// Keep the SR-IOV NIC scan, naming the pods VFs are allocated to
+    fn store_sriov(&mut self, nics: (Duration, Result<Vec<SriovNic>>), pods: &HashMap<String, String>) {
+        let Some(mut nics) = self.record_timing("sriov", nics) else {
+            return;
+        };
+        for vf in nics.iter_mut().flat_map(|nic| nic.vfs.iter_mut()) {
+            vf.pod = vf.pod_uid.as_ref().and_then(|uid| pods.get(uid)).cloned();
+        }
+        self.sriov_nics = nics;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&SriovRule {
+                nics: self.sriov_nics.clone(),
+                labels: self.interface_labels.clone(),
+            });
+        }
+    }
+
+    fn store_image_pulls(
+        &mut self,
+        registries: (Duration, Result<Vec<RegistryStatus>>),
+        pulls: (Duration, Result<Vec<ImagePull>>),
+    ) {
+        if let Some(registries) = self.record_timing("registries", registries) {
+            self.registry_status = registries;
+        }
+        if let Some(pulls) = self.record_timing("image pulls", pulls) {
+            self.image_pulls = pulls;
+        }
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&ImagePullRule {
+                registries: self.registry_status.clone(),
+                pulls: self.image_pulls.clone(),
+                registry_failure_threshold: self.registry_failure_threshold,
+                pull_failure_threshold: self.image_pull_failure_threshold,
+            });
+        }
+    }
+
+    /// Keep each pod's restart counts over the past hour and alert on pods
+    /// in a backoff or restarting faster than `pod_restarts_per_hour`
+    fn store_pod_health(&mut self, pods: (Duration, Result<Vec<PodHealth>>)) {
+        let Some(mut pods) = self.record_timing("pods", pods) else {
+            return;
+        };
+        let now = Instant::now();
+        let window = Duration::from_secs(3600);
+        let seen: HashSet<String> = pods.iter().map(PodHealth::key).collect();
+        self.pod_restart_history.retain(|key, _| seen.contains(key));
+        for pod in &mut pods {
+            let history = self.pod_restart_history.entry(pod.key()).or_default();
+            history.push_back((now, pod.restarts));
+            while history.front().is_some_and(|&(at, _)| now.duration_since(at) > window) {
+                history.pop_front();
+            }
+            let oldest = history.front().map_or(pod.restarts, |&(_, restarts)| restarts);
+            pod.recent_restarts = pod.restarts.saturating_sub(oldest);
+        }
+        self.pod_health = pods;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&PodRule {
+                pods: self.pod_health.clone(),
+                restarts_per_hour: self.pod_restarts_per_hour,
+            });
+        }
+    }
+
+    /// Ask the guest agent of every VM running here for its clock, once
+    /// per interval since each reading is an exec into the launcher pod
+    async fn update_guest_clocks(&mut self) {
+        let Some(interval) = self.guest_clock_interval else {
+            return;
+        };
+        if self.guest_clock_checked.is_some_and(|at| at.elapsed() < interval) {
+            return;
+        }
+        let vms: Vec<(String, String, String)> = self
+            .qemu_processes
+            .iter()
+            .filter_map(|process| Some((process.vm.clone()?, process.pod.clone()?, process.domain.clone())))
+            .collect();
+        if vms.is_empty() {
+            return;
+        }
+        self.guest_clock_checked = Some(Instant::now());
+        let clocks = timed(self.collector_timeout, self.k8s_collector.collect_guest_clocks(&vms)).await;
+        let Some(clocks) = self.record_timing("guest clocks", clocks) else {
+            return;
+        };
+        self.guest_clocks = clocks.into_iter().map(|clock| (clock.vm.clone(), clock)).collect();
+
+        if self.alerts_enabled {
+            let (warning_secs, critical_secs) = self.guest_clock_thresholds;
+            self.alert_manager.evaluate_rule(&GuestClockRule {
+                clocks: self.guest_clocks.values().cloned().collect(),
+                warning_secs,
+                critical_secs,
+            });
+        }
+    }
+
+    /// List the filesystems of every VM running here through its guest
+    /// agent once per interval and evaluate the fill alerts for each
+    async fn update_guest_filesystems(&mut self) {
+        let Some(interval) = self.guest_fs_interval else {
+            return;
+        };
+        if !self.alerts_enabled || self.guest_fs_checked.is_some_and(|at| at.elapsed() < interval) {
+            return;
+        }
+        let vms: Vec<String> = self.qemu_processes.iter().filter_map(|process| process.vm.clone()).collect();
+        if vms.is_empty() {
+            return;
+        }
+        self.guest_fs_checked = Some(Instant::now());
+        let filesystems = timed(self.collector_timeout, self.k8s_collector.collect_guest_filesystems(&vms)).await;
+        let Some(filesystems) = self.record_timing("guest filesystems", filesystems) else {
+            return;
+        };
+
+        let (warning, critical) = self.guest_fs_thresholds;
+        for (vm, filesystems) in filesystems {
+            self.alert_manager.evaluate_rule(&GuestFilesystemRule::new(vm, filesystems, warning, critical));
+        }
+    }
+
+    async fn update_disk_images(&mut self) {
+        let limit = self.collector_timeout;
+        let (images, refs) = tokio::join!(
+            timed(limit, self.image_collector.collect()),
+            timed(limit, self.k8s_collector.collect_disk_references()),
+        );
+        self.store_disk_images(images, refs);
+    }
+
+    /// Attribute scanned images to VMs, claims and running qemu processes.
+    /// Without cluster references nothing can be called an orphan, so the
+    /// previous inventory is kept.
+    fn store_disk_images(
+        &mut self,
+        images: (Duration, Result<Vec<DiskImage>>),
+        refs: (Duration, Result<DiskReferences>),
+    ) {
+        let refs = self.record_timing("disk references", refs);
+        let (Some(images), Some(refs)) = (self.record_timing("images", images), refs) else {
+            return;
+        };
+        self.disk_images = resolve_inventory(images, &refs, &self.open_disks());
+        self.disk_selected = self.disk_selected.min(self.disk_images.len().saturating_sub(1));
+    }
+
+    /// Disk paths held open by qemu, as seen from the host, -> "qemu pid N"
+    fn open_disks(&self) -> HashMap<String, String> {
+        let mut open = HashMap::new();
+        for process in &self.qemu_processes {
+            for disk in &process.disks {
+                let owner = format!("qemu pid {}", process.pid);
+                open.insert(process.host_path(disk), owner.clone());
+                open.insert(disk.clone(), owner);
+            }
+        }
+        open
+    }
+
+    fn store_service_health(&mut self, health: (Duration, Result<ServiceHealth>)) {
+        let Some(health) = self.record_timing("services", health) else {
+            return;
+        };
+        self.service_health = health;
+
+        for svc in &self.service_health.endpoints {
+            if svc.ready > 0 {
+                self.services_seen_ready.insert(format!("{}/{}", svc.namespace, svc.name));
+            }
+        }
+
+        if self.alerts_enabled {
+            let watched = |key: &String| {
+                if self.watched_services.is_empty() {
+                    self.services_seen_ready.contains(key)
+                } else {
+                    self.watched_services.contains(key)
+                }
+            };
+            let services = self
+                .service_health
+                .endpoints
+                .iter()
+                .filter(|svc| watched(&format!("{}/{}", svc.namespace, svc.name)))
+                .cloned()
+                .collect();
+            self.alert_manager.evaluate_rule(&ServiceEndpointRule { services });
+        }
+    }
+
+    fn store_vips(&mut self, vips: (Duration, Result<Vec<VipStatus>>)) {
+        let Some(vips) = self.record_timing("service vips", vips) else {
+            return;
+        };
+        self.vip_status = vips;
+
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&VipRule {
+                vips: self.vip_status.clone(),
+                failure_threshold: self.vip_failure_threshold,
+            });
+        }
+    }
+
+    fn store_power(&mut self, power: (Duration, Result<PowerInfo>)) {
+        let Some(info) = self.record_timing("power", power) else {
+            return;
+        };
+        if let Some(watts) = info.package_watts {
+            self.metrics_history.record_power(watts);
+        }
+        self.power_info = info;
+
+        let (percent, sustain_mins) = self.freq_throttle;
+        if self.power_info.is_throttled(percent) {
+            self.freq_throttled_since.get_or_insert_with(Instant::now);
+        } else {
+            self.freq_throttled_since = None;
+        }
+        if self.alerts_enabled {
+            self.alert_manager.evaluate_rule(&FreqThrottleRule {
+                power: self.power_info.clone(),
+                percent,
+                throttled_mins: self.freq_throttled_since.map(|since| since.elapsed().as_secs() / 60),
+                sustain_mins,
+            });
+        }
+    }
+
+    /// VM usage from the launcher pods, replaced by kubevirt's own metrics
+    /// where Prometheus has them
+    fn store_vm_usage(&mut self, usage: (Duration, Result<Vec<VmUsage>>)) {
+        let Some(mut usage) = self.record_timing("vm usage", usage) else {
+            return;
+        };
+        for vm in usage.iter_mut() {
+            if let Some(&(cpu, memory)) = self.prometheus_metrics.vm_usage.get(&(vm.namespace.clone(), vm.name.clone())) {
+                vm.cpu_percent = cpu.or(vm.cpu_percent);
+                vm.memory_percent = memory.or(vm.memory_percent);
+            }
+        }
+        self.vm_usage = usage;
+        if self.heatmap_cursor >= self.vm_usage.len() {
+            self.heatmap_cursor = self.vm_usage.len().saturating_sub(1);
+        }
+    }
+
+    fn store_resources(&mut self, resources: (Duration, Result<Vec<K8sResource>>)) {
+        let Some(resources) = self.record_timing("resources", resources) else {
+            return;
+        };
+        self.all_resources = resources;
+        self.apply_resource_filters();
+        // Forget marks on VMs that have since been deleted
+        if self.resource_kind == ResourceKind::VirtualMachine {
+            let present: HashSet<String> = self.all_resources.iter().filter_map(vm_key).collect();
+            self.vm_marked.retain(|vm| present.contains(vm));
+        }
+    }
+
+    /// Rebuild `resources` from the selector and grouping, keeping the
+    /// selected resource selected when it is still listed
+    fn apply_resource_filters(&mut self) {
+        let selected = self.selected_resource().map(|r| (r.namespace.clone(), r.name.clone()));
+        let mut resources: Vec<K8sResource> = self
+            .all_resources
+            .iter()
+            .filter(|resource| self.resource_label_selector.matches(&resource.labels))
+            .cloned()
+            .collect();
+        if !self.resource_group_by.is_empty() {
+            let key = &self.resource_group_by;
+            // Stable, so namespace/name order holds within a group
+            resources.sort_by(|a, b| {
+                let (a, b) = (a.labels.get(key), b.labels.get(key));
+                (a.is_none(), a).cmp(&(b.is_none(), b))
+            });
+        }
+        self.resources = resources;
+
+        let position = selected.and_then(|(namespace, name)| {
+            self.resources.iter().position(|r| r.namespace == namespace && r.name == name)
+        });
+        self.resource_selected_index =
+            position.unwrap_or(self.resource_selected_index).min(self.resources.len().saturating_sub(1));
+    }
+
+    /// Runs of `resources` sharing a group-by label value; empty when the
+    /// list isn't grouped
+    pub fn resource_groups(&self) -> Vec<ResourceGroup> {
+        if self.resource_group_by.is_empty() {
+            return Vec::new();
+        }
+        let mut groups: Vec<ResourceGroup> = Vec::new();
+        for (i, resource) in self.resources.iter().enumerate() {
+            let value = resource.labels.get(&self.resource_group_by);
+            match groups.last_mut() {
+                Some(group) if group.value.as_ref() == value => group.len += 1,
+                _ => groups.push(ResourceGroup {
+                    value: value.cloned(),
+                    start: i,
+                    len: 1,
+                    statuses: BTreeMap::new(),
+                }),
+            }
+            if let Some(group) = groups.last_mut() {
+                *group.statuses.entry(resource.status.clone()).or_default() += 1;
+            }
+        }
+        groups
+    }
+
+    /// How many resources of the current kind there are before filtering
+    pub fn resource_total(&self) -> usize {
+        self.all_resources.len()
+    }
+
+    // Resource selector and group-by input
+    pub fn start_resource_input(&mut self, input: ResourceInput) {
+        self.resource_input = Some(input);
+    }
+
+    pub fn resource_input_char(&mut self, c: char) {
+        let mut text = self.resource_input_text();
+        text.push(c);
+        self.set_resource_input_text(text);
+    }
+
+    pub fn resource_input_backspace(&mut self) {
+        let mut text = self.resource_input_text();
+        text.pop();
+        self.set_resource_input_text(text);
+    }
+
+    /// Close the text box, keeping what was typed unless cancelled
+    pub fn finish_resource_input(&mut self, keep: bool) {
+        if !keep {
+            self.set_resource_input_text(String::new());
+        }
+        self.resource_input = None;
+    }
+
+    fn resource_input_text(&self) -> String {
+        match self.resource_input {
+            Some(ResourceInput::Selector) => self.resource_selector.clone(),
+            Some(ResourceInput::GroupBy) => self.resource_group_by.clone(),
+            None => String::new(),
+        }
+    }
+
+    /// Apply the text box as it is typed; a selector that doesn't parse
+    /// (yet) leaves the last valid one applied
+    fn set_resource_input_text(&mut self, text: String) {
+        match self.resource_input {
+            Some(ResourceInput::Selector) => {
+                match LabelSelector::parse(&text) {
+                    Ok(selector) => {
+                        self.resource_label_selector = selector;
+                        self.resource_selector_error = None;
+                    }
+                    Err(e) => self.resource_selector_error = Some(e),
+                }
+                self.resource_selector = text;
+            }
+            Some(ResourceInput::GroupBy) => self.resource_group_by = text.trim().to_string(),
+            None => return,
+        }
+        self.apply_resource_filters();
+    }
+
+    async fn update_resources(&mut self) -> Result<()> {
+        let resources = timed(
+            self.collector_timeout,
+            self.k8s_collector.collect_resources(self.resource_kind),
+        )
+        .await;
+        self.store_resources(resources);
+        Ok(())
+    }
+
+    /// Alert transitions, error logs and metric crossings in time order
+    pub fn timeline_events(&self) -> Vec<TimelineEvent> {
+        self.timeline.events(
+            self.alert_manager
+                .get_active_alerts()
+                .into_iter()
+                .chain(self.alert_manager.get_history()),
+        )
+    }
+
+    // Charts view
+    pub fn chart_cursor_left(&mut self) {
+        let samples = self.metrics_history.series(self.chart_metric, self.chart_zoom);
+        let idx = match self.chart_cursor {
+            Some(at) => samples.iter().position(|s| s.at >= at).unwrap_or(samples.len()).saturating_sub(1),
+            None => samples.len().saturating_sub(1),
+        };
+        self.chart_cursor = samples.get(idx).map(|s| s.at);
+    }
+
+    /// Moving past the newest sample returns the cursor to live mode
+    pub fn chart_cursor_right(&mut self) {
+        let Some(at) = self.chart_cursor else {
+            return;
+        };
+        let samples = self.metrics_history.series(self.chart_metric, self.chart_zoom);
+        self.chart_cursor = samples.iter().find(|s| s.at > at).map(|s| s.at);
+    }
+
+    pub fn chart_zoom_in(&mut self) {
+        self.chart_zoom = self.chart_zoom.zoom_in();
+        self.chart_cursor = None;
+    }
+
+    pub fn chart_zoom_out(&mut self) {
+        self.chart_zoom = self.chart_zoom.zoom_out();
+        self.chart_cursor = None;
+    }
+
+    pub fn next_chart_metric(&mut self) {
+        self.chart_metric = self.chart_metric.next();
+    }
+
+    pub fn toggle_debug_overlay(&mut self) {
+        self.debug_overlay_open = !self.debug_overlay_open;
+    }
+
+    // Pane focus and scrolling
+    /// Panes of the current screen that are shown, in Tab order
+    pub fn visible_panes(&self) -> Vec<Pane> {
+        if self.current_screen == Screen::Tools {
+            return (0..self.custom_commands.commands.len()).map(Pane::Command).collect();
+        }
+        Pane::for_screen(self.current_screen)
+            .iter()
+            .copied()
+            .filter(|&pane| pane != Pane::Sriov || !self.sriov_nics.is_empty())
+            .filter(|&pane| pane != Pane::Routes || self.route_collector.is_some())
+            .filter(|&pane| pane != Pane::VmInterfaces || !self.network_info.vm_namespaces.is_empty())
+            .filter(|&pane| pane != Pane::Registries || self.shows_image_pulls())
+            .filter(|&pane| pane != Pane::NetworkPolicies || !self.vm_network_policies.is_empty())
+            .collect()
+    }
+
+    /// The registry panel only takes room once there is something in it
+    pub fn shows_image_pulls(&self) -> bool {
+        !self.registry_status.is_empty() || !self.image_pulls.is_empty()
+    }
+
+    pub fn focused_pane(&self) -> Option<Pane> {
+        self.panes.focused(self.current_screen, &self.visible_panes())
+    }
+
+    /// Whether `pane` should be drawn highlighted: it has focus and there
+    /// is more than one pane to choose from
+    pub fn pane_highlighted(&self, pane: Pane) -> bool {
+        self.visible_panes().len() > 1 && self.focused_pane() == Some(pane)
+    }
+
+    pub fn cycle_pane(&mut self, forward: bool) {
+        let panes = self.visible_panes();
+        self.panes.cycle(self.current_screen, &panes, forward);
+    }
+
+    pub fn pane_offset(&self, pane: Pane) -> usize {
+        self.panes.offset(pane)
+    }
+
+    /// Rows a pane can scroll through
+    fn pane_len(&self, pane: Pane) -> usize {
+        match pane {
+            Pane::Logs => self.get_displayed_logs().len(),
+            Pane::NoisySources => self.metrics_history.noisy_sources(NOISY_SOURCES).len(),
+            Pane::Timeline => self.timeline_events().len(),
+            Pane::Interfaces => {
+                // One header row per class
+                let interfaces = &self.network_info.interfaces;
+                interfaces.len() + interfaces.windows(2).filter(|w| w[0].class != w[1].class).count() + usize::from(!interfaces.is_empty())
+            }
+            Pane::VmInterfaces => self
+                .network_info
+                .vm_namespaces
+                .iter()
+                .map(|ns| 1 + ns.interfaces.len() + usize::from(ns.error.is_some()))
+                .sum(),
+            Pane::Sriov => self
+                .sriov_nics
+                .iter()
+                .map(|nic| 1 + nic.vfs.iter().filter(|vf| vf.pod_uid.is_some()).count())
+                .sum(),
+            Pane::K8sNetwork => 6,
+            Pane::Routes => self.route_status.as_ref().map_or(0, |status| {
+                status.routes.len() + status.gateways.len() + status.recent_changes.len() + 2
+            }),
+            Pane::Dns => self
+                .dns_status
+                .iter()
+                .map(|resolver| 1 + usize::from(resolver.last_error.is_some()))
+                .sum(),
+            Pane::Registries => self.registry_status.len() + self.image_pulls.len(),
+            Pane::NetworkPolicies => self
+                .vm_network_policies
+                .iter()
+                .map(|ns| {
+                    1 + ns
+                        .vms
+                        .iter()
+                        .map(|vm| 1 + vm.ingress.row_count() + vm.egress.row_count())
+                        .sum::<usize>()
+                })
+                .sum(),
+            Pane::Endpoints => self.service_health.endpoints.len(),
+            Pane::Ingress => self.service_health.routes.len(),
+            Pane::Command(index) => self
+                .custom_commands
+                .outputs
+                .get(index)
+                .and_then(|output| output.as_ref())
+                .map_or(0, |output| match output.table {
+                    Some((_, ref rows)) => rows.len(),
+                    None => output.lines.len(),
+                }),
+        }
+    }
+
+    /// Take in a new terminal size and pull scroll offsets back so that
+    /// panes filling the whole content area don't end up scrolled past
+    /// rows that now fit
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.terminal_width = width;
+        self.terminal_height = height;
+
+        // Header, footer, alert banner and the pane's own borders
+        let banner = usize::from(!self.alert_manager.get_active_alerts().is_empty());
+        let rows = (height as usize).saturating_sub(6 + banner);
+        for pane in self.visible_panes() {
+            let len = self.pane_len(pane);
+            let max = if self.visible_panes().len() == 1 {
+                len.saturating_sub(rows)
+            } else {
+                len.saturating_sub(1)
+            };
+            self.panes.clamp_offset(pane, max);
+        }
+
+        if let Some(inspector) = self.yaml_inspector.as_mut() {
+            let len = inspector.content.lines().count();
+            inspector.scroll = inspector.scroll.min(len.saturating_sub(rows.max(1)));
+        }
+        self.heatmap_cursor = self.heatmap_cursor.min(self.vm_usage.len().saturating_sub(1));
+    }
+
+    /// Move through the current screen's list, or scroll its focused pane
+    pub fn navigate(&mut self, motion: Motion) {
+        match self.current_screen {
+            Screen::Resources => self.resource_navigate(motion),
+            Screen::Fleet => self.fleet_navigate(motion),
+            Screen::HostMap => match self.host_map_view {
+                HostMapView::Processes => self.host_map_navigate(motion),
+                HostMapView::Images => self.disk_navigate(motion),
+                HostMapView::Memory => {
+                    self.leak_selected = motion.apply(self.leak_selected, self.process_memory.len());
+                }
+            },
+            _ => self.scroll_focused(motion),
+        }
+    }
+
+    fn scroll_focused(&mut self, motion: Motion) {
+        if self.logs_wrapped && self.focused_pane() == Some(Pane::Logs) {
+            match motion {
+                Motion::Up(n) => return self.scroll_wrapped_logs(false, n),
+                Motion::Down(n) => return self.scroll_wrapped_logs(true, n),
+                _ => self.log_wrap_scroll = (0, 0),
+            }
+        }
+        if let Some(pane) = self.focused_pane() {
+            let len = self.pane_len(pane);
+            self.panes.scroll(pane, motion, len);
+        }
+    }
+
+    /// Whether the log view shows `filtered_logs` rather than every entry
+    fn logs_filtered(&self) -> bool {
+        self.logs_collapsed || !self.search_query.is_empty() || self.filter_level.is_some()
+    }
+
+    /// Rebuild `filtered_logs`. Unfiltered, the view reads `logs` directly
+    /// and nothing is copied; otherwise only matching entries are, once.
+    pub fn apply_log_filters(&mut self) {
+        if !self.logs_filtered() {
+            if let Some(worker) = self.log_filter.as_mut() {
+                worker.cancel();
+            }
+            self.filtered_logs = Vec::new();
+            return;
+        }
+
+        let query = LogQuery::parse(&self.search_query, self.filter_level.as_deref(), self.logs_collapsed);
+        if self.log_source().len() < BACKGROUND_FILTER_MIN {
+            if let Some(worker) = self.log_filter.as_mut() {
+                worker.cancel();
+            }
+            self.filtered_logs = query.apply(self.log_source());
+            return;
+        }
+        // The current results stay up until the filter thread's arrive
+        let logs = self.log_source().to_vec();
+        self.log_filter.get_or_insert_with(LogFilterWorker::start).request(logs, query);
+    }
+
+    /// Re-filter after the live buffer changed (an open archive doesn't
+    /// depend on it), and forward the new lines
+    fn live_logs_changed(&mut self) {
+        if let Some(ref forwarder) = self.log_forwarder {
+            forwarder.send(self.log_collector.take_forwarded());
+        }
+        if self.log_archive.is_none() {
+            self.apply_log_filters();
+        }
+    }
+
+    /// Take in the filter thread's results, returning whether they changed
+    /// the log view
+    pub fn poll_log_filter(&mut self) -> bool {
+        match self.log_filter.as_mut().and_then(LogFilterWorker::poll) {
+            Some(filtered) => {
+                self.filtered_logs = filtered;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether the log view is waiting on the filter thread
+    pub fn log_filtering(&self) -> bool {
+        self.log_filter.as_ref().is_some_and(LogFilterWorker::is_pending)
+    }
+
+    /// Show only the selected noisy source's lines, or everything again
+    /// when that filter is already applied, and go back to the log list
+    pub fn filter_noisy_source(&mut self) {
+        let sources = self.metrics_history.noisy_sources(NOISY_SOURCES);
+        let Some(source) = sources.get(self.pane_offset(Pane::NoisySources)) else {
+            return;
+        };
+        let query = format!("service={}", source.service);
+        self.set_search_query(if self.search_query == query { String::new() } else { query });
+        self.panes.set_offset(Pane::Logs, 0);
+        self.panes.focus(Screen::Logs, Pane::Logs);
+    }
+
+    pub fn toggle_log_collapse(&mut self) {
+        self.logs_collapsed = !self.logs_collapsed;
+        self.apply_log_filters();
+    }
+
+    pub fn toggle_log_wrap(&mut self) {
+        self.logs_wrapped = !self.logs_wrapped;
+        self.log_hscroll = 0;
+        self.log_wrap_scroll = (0, 0);
+    }
+
+    /// Scroll the unwrapped log list sideways, no further than the longest
+    /// message on screen
+    pub fn scroll_log_columns(&mut self, right: bool) {
+        if self.logs_wrapped {
+            return;
+        }
+        if !right {
+            self.log_hscroll = self.log_hscroll.saturating_sub(ui::logs::HSCROLL_STEP);
+            return;
+        }
+        let offset = self.pane_offset(Pane::Logs);
+        let longest = self
+            .get_displayed_logs()
+            .iter()
+            .skip(offset)
+            .take(self.terminal_height as usize)
+            .map(|entry| entry.message.chars().count())
+            .max()
+            .unwrap_or(0);
+        if self.log_hscroll + ui::logs::HSCROLL_STEP < longest {
+            self.log_hscroll += ui::logs::HSCROLL_STEP;
+        }
+    }
+
+    /// Rows of the top entry scrolled past in the wrapped list; stale once
+    /// another entry is on top
+    pub fn log_rows_scrolled(&self) -> usize {
+        let (entry, rows) = self.log_wrap_scroll;
+        if entry == self.pane_offset(Pane::Logs) { rows } else { 0 }
+    }
+
+    /// Move the wrapped log list by `n` screen rows rather than entries, so
+    /// an entry taller than the list can be read through. The top entry
+    /// stays the selection.
+    fn scroll_wrapped_logs(&mut self, down: bool, mut n: usize) {
+        let width = (self.terminal_width as usize).saturating_sub(2);
+        let logs = self.get_displayed_logs();
+        if logs.is_empty() {
+            return;
+        }
+        let rows = |entry: usize| ui::logs::wrapped_rows(self, &logs[entry], width);
+        let mut entry = self.pane_offset(Pane::Logs).min(logs.len() - 1);
+        let mut row = self.log_rows_scrolled();
+        while n > 0 {
+            if down {
+                let last = rows(entry).saturating_sub(1);
+                if row < last {
+                    let step = (last - row).min(n);
+                    row += step;
+                    n -= step;
+                } else if entry + 1 < logs.len() {
+                    entry += 1;
+                    row = 0;
+                    n -= 1;
+                } else {
+                    break;
+                }
+            } else if row > 0 {
+                let step = row.min(n);
+                row -= step;
+                n -= step;
+            } else if entry > 0 {
+                entry -= 1;
+                row = rows(entry).saturating_sub(1);
+                n -= 1;
+            } else {
+                break;
+            }
+        }
+        self.panes.set_offset(Pane::Logs, entry);
+        self.log_wrap_scroll = (entry, row);
+    }
+
+    pub fn set_search_query(&mut self, query: String) {
+        self.search_query = query;
+        self.apply_log_filters();
+    }
+
+    pub fn toggle_filter_level(&mut self, level: &str) {
+        self.filter_level = if self.filter_level.as_deref() == Some(level) {
+            None
+        } else {
+            Some(level.to_string())
+        };
+        self.apply_log_filters();
+    }
+
+    pub fn clear_filters(&mut self) {
+        self.search_query.clear();
+        self.filter_level = None;
+        self.apply_log_filters();
+    }
+
+    pub fn get_displayed_logs(&self) -> &[Arc<LogEntry>] {
+        if self.logs_filtered() {
+            &self.filtered_logs
+        } else {
+            self.log_source()
+        }
+    }
+
+    /// Lines the Logs screen filters and shows: an archived range while
+    /// one is open, otherwise the live buffer
+    fn log_source(&self) -> &[Arc<LogEntry>] {
+        match self.log_archive {
+            Some(ref archive) => &archive.entries,
+            None => &self.logs,
+        }
+    }
+
+    // Archived log queries
+    pub fn open_log_range_prompt(&mut self) {
+        self.log_range_prompt = Some(LogRangePrompt::new(self.log_archive.as_ref()));
+    }
+
+    pub fn close_log_range_prompt(&mut self) {
+        self.log_range_prompt = None;
+    }
+
+    /// Query the journal for the entered range; the live tail keeps
+    /// following underneath
+    pub fn confirm_log_range(&mut self) {
+        let Some(prompt) = self.log_range_prompt.take() else {
+            return;
+        };
+        let (since, until) = (prompt.since.trim().to_string(), prompt.until.trim().to_string());
+        if since.is_empty() || until.is_empty() {
+            self.push_message(MessageLevel::Warning, "Both ends of the range are needed".to_string());
+            self.log_range_prompt = Some(prompt);
+            return;
+        }
+        self.log_archive = Some(LogArchive::start(&self.log_collector, since, until, self.log_archive_max_lines));
+        self.panes.set_offset(Pane::Logs, 0);
+        self.apply_log_filters();
+    }
+
+    /// Take in the archived query's lines, returning whether they arrived
+    pub fn poll_log_archive(&mut self) -> bool {
+        let Some(archive) = self.log_archive.as_mut() else {
+            return false;
+        };
+        if !archive.poll() {
+            return false;
+        }
+        match archive.error {
+            Some(_) => {
+                let summary = format!("Archived log query failed: {}", archive.summary());
+                self.push_message(MessageLevel::Error, summary);
+            }
+            None if archive.truncated => {
+                let summary = format!(
+                    "Archive {}; narrow the range or raise logging.archive_max_lines",
+                    archive.summary()
+                );
+                self.push_message(MessageLevel::Warning, summary);
+            }
+            None => {}
+        }
+        self.apply_log_filters();
+        true
+    }
+
+    /// Back to the live tail
+    pub fn close_log_archive(&mut self) {
+        self.log_archive = None;
+        self.panes.set_offset(Pane::Logs, 0);
+        self.apply_log_filters();
+    }
+
+    // Search across services
+    /// Open the Ctrl-F search, starting from the log view's query
+    pub fn open_log_search(&mut self) {
+        self.log_search = Some(LogSearch::new(self.search_query.clone()));
+    }
+
+    pub fn run_log_search(&mut self) {
+        let Some(search) = self.log_search.as_mut() else {
+            return;
+        };
+        if search.query.trim().is_empty() {
+            return;
+        }
+        search.start(&self.log_collector, self.logs.clone(), self.log_search_lines);
+    }
+
+    /// Take in services that finished searching, returning whether any did
+    pub fn poll_log_search(&mut self) -> bool {
+        self.log_search.as_mut().is_some_and(LogSearch::poll)
+    }
+
+    /// Show the lines around the selected hit, over the results
+    pub async fn open_log_search_context(&mut self) {
+        if let Some(hit) = self.log_search.as_ref().and_then(LogSearch::selected_hit) {
+            self.open_log_context_for(hit).await;
+        }
+    }
+
+    pub fn close_log_search(&mut self) {
+        self.log_search = None;
+    }
+
+    // Log search input
+    pub fn start_search(&mut self) {
+        self.search_active = true;
+    }
+
+    pub fn search_input(&mut self, c: char) {
+        let mut query = std::mem::take(&mut self.search_query);
+        query.push(c);
+        self.set_search_query(query);
+        self.panes.set_offset(Pane::Logs, 0);
+    }
+
+    pub fn search_backspace(&mut self) {
+        let mut query = std::mem::take(&mut self.search_query);
+        query.pop();
+        self.set_search_query(query);
+        self.panes.set_offset(Pane::Logs, 0);
+    }
+
+    /// Leave search input, keeping the query as a filter unless cancelled
+    pub fn finish_search(&mut self, keep: bool) {
+        self.search_active = false;
+        if !keep {
+            self.set_search_query(String::new());
+            self.panes.set_offset(Pane::Logs, 0);
+        }
+    }
+
+    /// Lines around `anchor` from the same service, from the buffer, or
+    /// the journal re-queried around its timestamp when the buffer doesn't
+    /// hold enough of them
+    async fn open_log_context_for(&mut self, anchor: Arc<LogEntry>) {
+        let lines = self.log_context_lines;
+
+        let buffered = context_window(self.log_source(), &anchor, lines);
+        let complete = buffered
+            .as_ref()
+            .is_some_and(|(window, index)| *index >= lines && window.len() - index > lines);
+
+        let context = if complete {
+            buffered.map(|window| (window, "buffer"))
+        } else {
+            let journal = timed(self.collector_timeout, self.log_collector.context(&anchor)).await.1;
+            match journal {
+                Ok(entries) => context_window(&entries, &anchor, lines)
+                    .map(|window| (window, "journal"))
+                    .or_else(|| buffered.map(|window| (window, "buffer"))),
+                Err(e) => {
+                    tracing::debug!("Journal context query failed: {:#}", e);
+                    self.push_message(
+                        MessageLevel::Info,
+                        format!("Journal unavailable ({:#}), showing buffered lines", e),
+                    );
+                    buffered.map(|window| (window, "buffer"))
+                }
+            }
+        };
+
+        self.log_context = Some(match context {
+            Some(((lines, index), source)) => LogContext {
+                anchor,
+                lines,
+                anchor_index: index,
+                source,
+            },
+            None => LogContext {
+                lines: vec![anchor.clone()],
+                anchor,
+                anchor_index: 0,
+                source: "buffer",
+            },
+        });
+    }
+
+    pub fn close_log_context(&mut self) {
+        self.log_context = None;
+    }
+
+    /// Open the highlighted (top) log line in full. Journal lines have
+    /// their record looked up again by cursor for the fields not kept in
+    /// the buffer.
+    pub async fn open_log_detail(&mut self) {
+        let Some(entry) = self.get_displayed_logs().get(self.pane_offset(Pane::Logs)).cloned() else {
+            return;
+        };
+        let (journal, journal_error) = match entry.cursor {
+            Some(ref cursor) => match timed(self.collector_timeout, self.log_collector.journal_fields(cursor)).await.1 {
+                Ok(fields) => (fields, None),
+                Err(e) => {
+                    tracing::debug!("Journal record lookup failed: {:#}", e);
+                    (Vec::new(), Some(format!("{:#}", e)))
+                }
+            },
+            None => (Vec::new(), None),
+        };
+        self.log_detail = Some(LogDetail { entry, journal, journal_error, scroll: 0 });
+    }
+
+    pub fn close_log_detail(&mut self) {
+        self.log_detail = None;
+    }
+
+    pub fn scroll_log_detail(&mut self, down: bool, lines: usize) {
+        if let Some(ref mut detail) = self.log_detail {
+            detail.scroll = if down {
+                detail.scroll.saturating_add(lines)
+            } else {
+                detail.scroll.saturating_sub(lines)
+            };
+        }
+    }
+
+    /// Lines around the detailed one, over the detail view
+    pub async fn open_log_detail_context(&mut self) {
+        if let Some(entry) = self.log_detail.as_ref().map(|detail| detail.entry.clone()) {
+            self.open_log_context_for(entry).await;
+        }
+    }
+
+    /// Filter the log list to the detailed line's service, or its process
+    /// with `by_pid`, and close the detail view
+    pub fn filter_log_detail(&mut self, by_pid: bool) {
+        let Some(ref detail) = self.log_detail else {
+            return;
+        };
+        let query = if by_pid {
+            let pid = detail
+                .entry
+                .pid
+                .clone()
+                .or_else(|| detail.journal.iter().find(|(key, _)| key == "_PID").map(|(_, pid)| pid.clone()));
+            match pid {
+                Some(pid) => format!("pid={}", pid),
+                None => {
+                    self.push_message(MessageLevel::Info, "No PID recorded for this line");
+                    return;
+                }
+            }
+        } else {
+            format!("service={}", detail.entry.service)
+        };
+        self.log_detail = None;
+        self.set_search_query(query);
+        self.panes.set_offset(Pane::Logs, 0);
+    }
+
+    /// The detailed line as one line of text, for the clipboard
+    pub fn log_detail_text(&self) -> Option<String> {
+        let entry = &self.log_detail.as_ref()?.entry;
+        let pid = entry.pid.as_ref().map(|pid| format!("[{}]", pid)).unwrap_or_default();
+        Some(format!("{} {}{}: {}", entry.timestamp, entry.service, pid, entry.message))
+    }
+
+    // Log services picker
+    pub async fn open_log_services(&mut self) {
+        let following = self.log_collector.services().to_vec();
+        let (mut units, status) = match timed(self.collector_timeout, self.log_collector.discover_units()).await.1 {
+            Ok(units) => (units, None),
+            Err(e) => (Vec::new(), Some(format!("Unit discovery failed ({:#}), showing followed services", e))),
+        };
+        // Followed services that aren't host units (virt-launcher) stay listed
+        units.extend(following.iter().cloned());
+        units.sort();
+        units.dedup();
+
+        let mut entries: Vec<LogServiceEntry> = units
+            .into_iter()
+            .map(|name| LogServiceEntry {
+                category: ServiceCategory::classify(&name),
+                enabled: following.contains(&name),
+                name,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.category.cmp(&b.category).then_with(|| a.name.cmp(&b.name)));
+
+        self.log_services = Some(LogServicePicker { entries, selected: 0, status });
+    }
+
+    pub fn close_log_services(&mut self) {
+        self.log_services = None;
+    }
+
+    pub fn log_services_navigate(&mut self, down: bool) {
+        if let Some(picker) = self.log_services.as_mut() {
+            let count = picker.entries.len().max(1);
+            picker.selected = if down {
+                (picker.selected + 1) % count
+            } else {
+                (picker.selected + count - 1) % count
+            };
+        }
+    }
+
+    /// Check or uncheck the selected unit and re-read the journal for the
+    /// new set right away, so the log view reflects it without a restart
+    pub async fn toggle_log_service(&mut self) {
+        let Some(picker) = self.log_services.as_mut() else {
+            return;
+        };
+        let Some(entry) = picker.entries.get_mut(picker.selected) else {
+            return;
+        };
+        entry.enabled = !entry.enabled;
+        let services = picker.entries.iter().filter(|e| e.enabled).map(|e| e.name.clone()).collect();
+
+        self.log_collector.set_services(services);
+        self.reload_logs().await;
+    }
+
+    /// Read the journal again after the followed services changed
+    async fn reload_logs(&mut self) {
+        let logs = timed(self.collector_timeout, self.log_collector.collect()).await;
+        if let Some(logs) = self.record_timing("logs", logs) {
+            // The backlog just read for the new set isn't fresh log volume
+            self.log_collector.take_counts();
+            self.timeline.record_logs(&logs);
+            self.logs = logs;
+            self.live_logs_changed();
+        }
+    }
+
+    // Alert panel management
+    pub fn toggle_alert_panel(&mut self) {
+        self.alert_panel_open = !self.alert_panel_open;
+        self.alert_detail = None;
+        if self.alert_panel_open {
+            self.alert_selected_index = 0;
+        }
+    }
+
+    pub fn toggle_alert_stats(&mut self) {
+        self.alert_stats_open = !self.alert_stats_open;
+    }
+
+    pub fn alert_navigate(&mut self, motion: Motion) {
+        self.alert_selected_index = motion.apply(self.alert_selected_index, self.alert_manager.active_count());
+    }
+
+    /// The alert shown in the detail view, or else the one under the cursor
+    pub fn selected_alert(&self) -> Option<&Alert> {
+        match self.alert_detail {
+            Some(ref id) => self.alert_manager.get_alert(id),
+            None => self.alert_manager.get_active_alerts().get(self.alert_selected_index).copied(),
+        }
+    }
+
+    pub fn open_alert_detail(&mut self) {
+        self.alert_detail = self.selected_alert().map(|alert| alert.id.clone());
+    }
+
+    pub fn close_alert_detail(&mut self) {
+        self.alert_detail = None;
+    }
+
+    pub fn acknowledge_selected_alert(&mut self) {
+        if self.read_only {
+            return;
+        }
+        if let Some(id) = self.selected_alert().map(|alert| alert.id.clone()) {
+            self.alert_manager.acknowledge_alert(&id);
+        }
+    }
+
+    pub fn dismiss_selected_alert(&mut self) {
+        if self.read_only {
+            return;
+        }
+        if let Some(id) = self.selected_alert().map(|alert| alert.id.clone()) {
+            self.alert_manager.dismiss_alert(&id);
+            self.alert_removed();
+        }
+    }
+
+    /// Dismiss the selected alert and keep its source quiet for the
+    /// configured snooze period
+    pub fn snooze_selected_alert(&mut self) {
+        if self.read_only {
+            return;
+        }
+        if let Some(id) = self.selected_alert().map(|alert| alert.id.clone()) {
+            self.alert_manager.snooze_alert(&id, self.alert_snooze_mins);
+            self.push_message(MessageLevel::Info, format!("Alert snoozed for {} minutes", self.alert_snooze_mins));
+            self.alert_removed();
+        }
+    }
+
+    /// Close the detail view and keep the cursor on a listed alert
+    fn alert_removed(&mut self) {
+        self.alert_detail = None;
+        let new_count = self.alert_manager.active_count();
+        if self.alert_selected_index >= new_count && new_count > 0 {
+            self.alert_selected_index = new_count - 1;
+        }
+    }
+
+    /// Runbook URL of the alert in the detail view or under the cursor
+    pub fn selected_alert_runbook(&self) -> Option<String> {
+        self.selected_alert().and_then(|alert| alert.metadata.runbook_url.clone())
+    }
+
+    /// Close the alert panel and select the alert's pod in the resource
+    /// browser
+    pub async fn jump_to_alert_pod(&mut self) {
+        let Some((namespace, pod)) = self
+            .selected_alert()
+            .and_then(|alert| Some((alert.metadata.namespace.clone()?, alert.metadata.pod_name.clone()?)))
+        else {
+            self.push_message(MessageLevel::Info, "No pod attached to this alert");
+            return;
+        };
+        self.alert_panel_open = false;
+        self.alert_detail = None;
+        self.current_screen = Screen::Resources;
+        if self.resource_kind != ResourceKind::Pod {
+            self.resource_kind = ResourceKind::Pod;
+            self.vm_marked.clear();
+        }
+        if let Err(e) = self.update_resources().await {
+            self.report_error("Listing pods", &e);
+        }
+        let namespace = Some(namespace);
+        match self.resources.iter().position(|r| r.namespace == namespace && r.name == pod) {
+            Some(index) => self.resource_selected_index = index,
+            None => self.push_message(
+                MessageLevel::Warning,
+                format!("Pod {} is gone or hidden by the label selector", pod),
+            ),
+        }
+    }
+
+    /// Close the alert panel and filter the live logs to what the alert's
+    /// source logged around its trigger
+    pub fn show_alert_logs(&mut self) {
+        let Some(query) = self.selected_alert().map(|alert| self.alert_log_query(alert)) else {
+            return;
+        };
+        self.alert_panel_open = false;
+        self.alert_detail = None;
+        self.log_archive = None;
+        self.current_screen = Screen::Logs;
+        self.filter_level = None;
+        self.set_search_query(query);
+        self.panes.set_offset(Pane::Logs, 0);
+        self.panes.focus(Screen::Logs, Pane::Logs);
+    }
+
+    /// The log search for an alert: its unit, process or pod where the
+    /// metadata names one, within `related_logs_mins` of the trigger
+    fn alert_log_query(&self, alert: &Alert) -> String {
+        let meta = &alert.metadata;
+        let source = meta.source.as_str();
+        let service = ["unit-", "leak-", "crash-", "oom-unit-"]
+            .iter()
+            .find_map(|prefix| source.strip_prefix(prefix))
+            .map(|name| name.trim_end_matches(".service").to_string())
+            .or_else(|| source.starts_with("kubelet-").then(|| self.cluster_flavor.kubelet_service().to_string()));
+        let mut words = Vec::new();
+        match (service, &meta.pod_name, &meta.vm_name) {
+            (Some(service), _, _) => words.push(format!("service={}", service)),
+            (None, Some(pod), _) => words.push(pod.clone()),
+            (None, None, Some(vm)) => words.push(vm.clone()),
+            (None, None, None) if alert.category == AlertCategory::KubeVirt => words.push("service=virt".to_string()),
+            _ => {}
+        }
+        let window = chrono::Duration::minutes(self.alert_related_logs_mins as i64);
+        words.push(format!("since={}", (alert.triggered_at - window).format(WINDOW_FORMAT)));
+        words.push(format!("until={}", (alert.triggered_at + window).format(WINDOW_FORMAT)));
+        words.join(" ")
+    }
+
+    pub fn dismiss_all_alerts(&mut self) {
+        if self.read_only {
+            return;
+        }
+        self.alert_manager.dismiss_all();
+        self.alert_selected_index = 0;
+    }
+
+    pub fn dismiss_alerts_with_level(&mut self, level: AlertLevel) {
+        if self.read_only {
+            return;
+        }
+        let ids: Vec<String> = self
+            .alert_manager
+            .get_alerts_by_level(level)
+            .into_iter()
+            .map(|alert| alert.id.clone())
+            .collect();
+        for id in ids {
             self.alert_manager.dismiss_alert(&id);
+        }
+        self.alert_selected_index = 0;
+    }
+
+    // Resource browser
+    pub async fn next_resource_kind(&mut self) -> Result<()> {
+        self.resource_kind = self.resource_kind.next();
+        self.resource_selected_index = 0;
+        self.vm_marked.clear();
+        self.update_resources().await
+    }
+
+    pub async fn prev_resource_kind(&mut self) -> Result<()> {
+        self.resource_kind = self.resource_kind.prev();
+        self.resource_selected_index = 0;
+        self.vm_marked.clear();
+        self.update_resources().await
+    }
+
+    pub fn resource_navigate(&mut self, motion: Motion) {
+        self.resource_selected_index = motion.apply(self.resource_selected_index, self.resources.len());
+    }
+
+    pub fn selected_resource(&self) -> Option<&K8sResource> {
+        self.resources.get(self.resource_selected_index)
+    }
+
+    pub fn is_vm_marked(&self, resource: &K8sResource) -> bool {
+        vm_key(resource).is_some_and(|vm| self.vm_marked.contains(&vm))
+    }
+
+    // Batch VM operations
+    /// Mark or unmark the selected VM and move to the next one
+    pub fn toggle_vm_mark(&mut self) {
+        let Some(vm) = self.selected_resource().and_then(vm_key) else {
+            return;
+        };
+        if !self.vm_marked.remove(&vm) {
+            self.vm_marked.insert(vm);
+        }
+        self.resource_navigate(Motion::Down(1));
+    }
+
+    /// Mark every listed VM, or clear the marks if all already are
+    pub fn mark_all_vms(&mut self) {
+        let all: HashSet<String> = self.resources.iter().filter_map(vm_key).collect();
+        if !all.is_empty() && all.is_subset(&self.vm_marked) {
+            self.vm_marked.clear();
+        } else {
+            self.vm_marked = all;
+        }
+    }
+
+    pub fn open_batch_prompt(&mut self) {
+        if self.read_only {
+            return;
+        }
+        if self.batch_run.is_some() {
+            self.push_message(MessageLevel::Warning, "A batch action is still running");
+        } else if self.vm_marked.is_empty() {
+            self.push_message(MessageLevel::Info, "Mark VMs with Space (or * for all) first");
+        } else {
+            self.batch_prompt = Some(BatchPrompt::default());
+        }
+    }
+
+    pub fn close_batch_prompt(&mut self) {
+        self.batch_prompt = None;
+    }
+
+    /// Switch the prompt to typing the "key=value" label to add
+    pub fn start_batch_label(&mut self) {
+        if let Some(prompt) = self.batch_prompt.as_mut() {
+            prompt.label = Some(String::new());
+        }
+    }
+
+    pub fn batch_label_input(&mut self, c: char) {
+        if let Some(label) = self.batch_prompt.as_mut().and_then(|p| p.label.as_mut()) {
+            label.push(c);
+        }
+    }
+
+    pub fn batch_label_backspace(&mut self) {
+        if let Some(label) = self.batch_prompt.as_mut().and_then(|p| p.label.as_mut()) {
+            label.pop();
+        }
+    }
+
+    pub fn submit_batch_label(&mut self) {
+        let Some(label) = self.batch_prompt.as_ref().and_then(|p| p.label.clone()) else {
+            return;
+        };
+        match label.trim().split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => self.start_batch(VmAction::AddLabel {
+                key: key.trim().to_string(),
+                value: value.trim().to_string(),
+            }),
+            _ => self.push_message(MessageLevel::Warning, "Label must be key=value"),
+        }
+    }
+
+    /// Apply an action to every marked VM in the background
+    pub fn start_batch(&mut self, action: VmAction) {
+        self.batch_prompt = None;
+        if self.lacks(&[action.permission()]) {
+            return;
+        }
+        let mut vms: Vec<String> = self.vm_marked.iter().cloned().collect();
+        vms.sort();
+        tracing::info!("Batch {} on {} VM(s)", action.label(), vms.len());
+        self.batch_run = Some(BatchRun::start(&self.k8s_collector, action, vms));
+    }
+
+    /// Collect finished VMs from the running batch. Once the last VM is
+    /// done the outcome is toasted, the marks cleared and `Finished`
+    /// returned, for the VM list to be reloaded.
+    pub fn poll_batch(&mut self) -> RunProgress {
+        let Some(run) = self.batch_run.as_mut() else {
+            return RunProgress::Unchanged;
+        };
+        if !run.poll() {
+            return RunProgress::Unchanged;
+        }
+        if !run.is_finished() {
+            return RunProgress::Changed;
+        }
+        let (summary, failed, hidden) = (run.summary(), run.failed(), run.hidden);
+        let level = if failed > 0 { MessageLevel::Warning } else { MessageLevel::Info };
+        self.push_message(level, summary);
+        if hidden {
+            self.batch_run = None;
+        }
+        self.vm_marked.clear();
+        RunProgress::Finished
+    }
+
+    /// Reload the VM list after a batch ended
+    pub async fn reload_resources(&mut self) {
+        if let Err(e) = self.update_resources().await {
+            self.report_error("Loading resources", &e);
+        }
+    }
+
+    /// Close the progress popup; a batch still running keeps going and
+    /// reports its outcome as a toast
+    pub fn close_batch_run(&mut self) {
+        match self.batch_run.as_mut() {
+            Some(run) if !run.is_finished() => run.hidden = true,
+            _ => self.batch_run = None,
+        }
+    }
+
+    // Host upgrades
+    /// Open the upgrade prompt, or bring back the panel of a rebuild that
+    /// is still running
+    pub fn open_upgrade_prompt(&mut self) {
+        if self.read_only {
+            return;
+        }
+        match self.upgrade_run.as_mut() {
+            Some(run) if !run.is_finished() => run.hidden = false,
+            _ => self.upgrade_prompt = true,
+        }
+    }
+
+    pub fn close_upgrade_prompt(&mut self) {
+        self.upgrade_prompt = false;
+    }
+
+    /// Run nixos-rebuild in the background, streaming into the Upgrade panel
+    pub fn start_upgrade(&mut self, action: UpgradeAction) {
+        self.upgrade_prompt = false;
+        if self.upgrade_run.as_ref().is_some_and(|run| !run.is_finished()) {
+            self.push_message(MessageLevel::Warning, "An upgrade is already running");
+            return;
+        }
+        match UpgradeRun::start(&self.upgrade_config, action) {
+            Ok(run) => {
+                tracing::info!("Starting upgrade: {}", run.command);
+                self.upgrade_run = Some(run);
+            }
+            Err(e) => {
+                self.push_message(MessageLevel::Error, e.clone());
+                self.alert_manager.evaluate_rule(&UpgradeRule { failure: Some(e) });
+            }
+        }
+    }
+
+    /// Raise the outcomes of finished auto-actions as Info alerts
+    pub fn poll_auto_actions(&mut self) -> bool {
+        let notices = self.auto_actions.poll();
+        let changed = !notices.is_empty();
+        for alert in notices {
+            self.alert_manager.add_alert(alert);
+        }
+        changed
+    }
+
+    /// Forward new rebuild output to the log viewer, returning whether
+    /// anything changed. When the rebuild ends its outcome is toasted and
+    /// a failure raised as an alert (a later success resolves it).
+    pub fn poll_upgrade(&mut self) -> bool {
+        let Some(run) = self.upgrade_run.as_mut() else {
+            return false;
+        };
+        let was_finished = run.is_finished();
+        let lines = run.poll();
+        let finished = !was_finished && run.is_finished();
+        let (outcome, hidden) = (run.outcome.clone(), run.hidden);
+        if lines.is_empty() && !finished {
+            return false;
+        }
+
+        let entries = lines.into_iter().map(|(line, stderr)| {
+            let level = if stderr && line.starts_with("error") { "ERROR" } else { "INFO" };
+            LogEntry {
+                timestamp: Local::now().format("%b %d %H:%M:%S").to_string(),
+                level: level.to_string(),
+                service: "nixos-rebuild".to_string(),
+                message: line,
+                ..Default::default()
+            }
+        });
+        self.logs = self.log_collector.push(entries);
+        self.live_logs_changed();
+
+        if let Some(outcome) = outcome.filter(|_| finished) {
+            match outcome {
+                Ok(summary) => {
+                    self.push_message(MessageLevel::Info, summary);
+                    self.alert_manager.evaluate_rule(&UpgradeRule { failure: None });
+                }
+                Err(reason) => {
+                    self.push_message(MessageLevel::Error, format!("Upgrade failed: {}", reason));
+                    self.alert_manager.evaluate_rule(&UpgradeRule { failure: Some(reason) });
+                }
+            }
+            if hidden {
+                self.upgrade_run = None;
+            }
+        }
+        true
+    }
+
+    /// Close the Upgrade panel; a rebuild still running keeps going and
+    /// reports its outcome as a toast
+    pub fn close_upgrade_run(&mut self) {
+        match self.upgrade_run.as_mut() {
+            Some(run) if !run.is_finished() => run.hidden = true,
+            _ => self.upgrade_run = None,
+        }
+    }
+
+    // Nix store
+    /// Open the generations panel, reading generations and their sizes,
+    /// or bring back one whose operation is still running
+    pub async fn open_nix_store(&mut self) {
+        match self.nix_store.as_mut() {
+            Some(panel) if panel.is_running() => panel.hidden = false,
+            _ => self.nix_store = Some(NixStorePanel::load(self.collector_timeout).await),
+        }
+    }
+
+    /// Close the panel; a collection still running keeps going and reports
+    /// its outcome as a toast
+    pub fn close_nix_store(&mut self) {
+        match self.nix_store.as_mut() {
+            Some(panel) if panel.is_running() => panel.hidden = true,
+            _ => self.nix_store = None,
+        }
+    }
+
+    pub fn move_nix_store_selection(&mut self, down: bool) {
+        if let Some(panel) = self.nix_store.as_mut() {
+            panel.selected = if down {
+                (panel.selected + 1).min(panel.generations.len().saturating_sub(1))
+            } else {
+                panel.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Ask for confirmation before collecting garbage or deleting the
+    /// selected generation; generations in use are refused outright
+    pub fn request_nix_store_op(&mut self, op: NixStoreOp) {
+        if self.read_only {
+            return;
+        }
+        let Some(panel) = self.nix_store.as_mut() else {
+            return;
+        };
+        if panel.is_running() {
+            self.push_message(MessageLevel::Warning, "A Nix store operation is already running");
+            return;
+        }
+        if let NixStoreOp::DeleteGeneration(_) = op {
+            match panel.selected_generation() {
+                Some(generation) if generation.in_use() => {
+                    let number = generation.number;
+                    self.push_message(
+                        MessageLevel::Warning,
+                        format!("Generation {} is the running, booted or default system", number),
+                    );
+                    return;
+                }
+                Some(_) => {}
+                None => return,
+            }
+        }
+        panel.confirm = Some(op);
+    }
+
+    pub fn cancel_nix_store_op(&mut self) {
+        if let Some(panel) = self.nix_store.as_mut() {
+            panel.confirm = None;
+        }
+    }
+
+    pub fn confirm_nix_store_op(&mut self) {
+        let Some(panel) = self.nix_store.as_mut() else {
+            return;
+        };
+        let Some(op) = panel.confirm.take() else {
+            return;
+        };
+        match NixStoreRun::start(&self.upgrade_config, op) {
+            Ok(run) => {
+                tracing::info!("Running {}", run.command);
+                panel.run = Some(run);
+            }
+            Err(e) => self.push_message(MessageLevel::Error, e),
+        }
+    }
+
+    /// Take in output of a running collection or deletion. When it ends
+    /// the outcome is toasted, and `Finished` returned while the panel is
+    /// still shown, for the generations to be read again.
+    pub fn poll_nix_store(&mut self) -> RunProgress {
+        let Some(panel) = self.nix_store.as_mut() else {
+            return RunProgress::Unchanged;
+        };
+        let Some(run) = panel.run.as_mut() else {
+            return RunProgress::Unchanged;
+        };
+        let was_finished = run.is_finished();
+        if !run.poll() {
+            return RunProgress::Unchanged;
+        }
+        if was_finished || !run.is_finished() {
+            return RunProgress::Changed;
+        }
+
+        let description = run.op.describe(&self.upgrade_config);
+        match run.outcome.clone() {
+            Some(Ok(summary)) => self.push_message(MessageLevel::Info, format!("{}: {}", description, summary)),
+            Some(Err(reason)) => self.push_message(MessageLevel::Error, format!("{} failed: {}", description, reason)),
+            None => {}
+        }
+        if self.nix_store.as_ref().is_some_and(|panel| panel.hidden) {
+            self.nix_store = None;
+            return RunProgress::Changed;
+        }
+        RunProgress::Finished
+    }
+
+    /// Read the generations again after a collection or deletion ended
+    pub async fn reload_nix_store(&mut self) {
+        let timeout = self.collector_timeout;
+        if let Some(panel) = self.nix_store.as_mut() {
+            panel.reload(timeout).await;
+        }
+    }
+
+    // Image catalog
+    /// Open the catalog, asking each image's URL whether it answers, or
+    /// bring back one whose download is still running
+    pub async fn open_image_catalog(&mut self) {
+        match self.image_catalog.as_mut() {
+            Some(panel) if panel.is_running() => panel.hidden = false,
+            _ => {
+                let dir = self.image_download_dir.clone();
+                let panel = ImageCatalogPanel::load(&self.image_catalog_config, dir, self.collector_timeout).await;
+                self.image_catalog = Some(panel);
+            }
+        }
+    }
+
+    /// Close the catalog; a download still running keeps going and reports
+    /// its outcome as a toast
+    pub fn close_image_catalog(&mut self) {
+        match self.image_catalog.as_mut() {
+            Some(panel) if panel.is_running() => panel.hidden = true,
+            _ => self.image_catalog = None,
+        }
+    }
+
+    pub fn move_image_catalog_selection(&mut self, down: bool) {
+        if let Some(panel) = self.image_catalog.as_mut() {
+            panel.selected = if down {
+                (panel.selected + 1).min(panel.entries.len().saturating_sub(1))
+            } else {
+                panel.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Ask for confirmation before downloading or importing the selected
+    /// image
+    pub fn request_catalog_op(&mut self, import: bool) {
+        if self.read_only || (import && self.lacks(&[Permission::CreateDataVolumes])) {
+            return;
+        }
+        let Some(panel) = self.image_catalog.as_mut() else {
+            return;
+        };
+        let Some(entry) = panel.selected_entry() else {
+            return;
+        };
+        let warning = if import {
+            let key = format!("{}/{}", panel.namespace, entry.volume_name());
+            self.data_volumes.iter().any(|volume| volume.key() == key).then(|| format!("DataVolume {} already exists", key))
+        } else if panel.is_running() {
+            Some("A download is already running".to_string())
+        } else if panel.dir.is_none() {
+            Some("No download directory: set image_catalog.download_dir or storage.image_dirs".to_string())
+        } else {
+            entry.downloaded.is_some().then(|| format!("{} is already downloaded", entry.file_name()))
+        };
+        if let Some(warning) = warning {
+            self.push_message(MessageLevel::Warning, warning);
+            return;
+        }
+        let selected = panel.selected;
+        panel.confirm = Some(if import { CatalogOp::Import(selected) } else { CatalogOp::Download(selected) });
+    }
+
+    pub fn cancel_catalog_op(&mut self) {
+        if let Some(panel) = self.image_catalog.as_mut() {
+            panel.confirm = None;
+        }
+    }
+
+    pub async fn confirm_catalog_op(&mut self) {
+        let Some(panel) = self.image_catalog.as_mut() else {
+            return;
+        };
+        let Some(op) = panel.confirm.take() else {
+            return;
+        };
+        let description = panel.describe(op);
+        match op {
+            CatalogOp::Download(i) => {
+                let Some(ref dir) = panel.dir else {
+                    return;
+                };
+                tracing::info!("{}", description);
+                panel.download = Some(ImageDownload::start(&panel.entries[i], dir));
+            }
+            CatalogOp::Import(i) => {
+                let manifest = panel.data_volume_manifest(&panel.entries[i]);
+                let result = self.k8s_collector.create_object(&manifest).await;
+                let created = result.is_ok();
+                self.report_outcome(&description, result);
+                // Show the import's progress right away
+                if created {
+                    let volumes = timed(self.collector_timeout, self.k8s_collector.collect_data_volumes()).await;
+                    self.store_data_volumes(volumes);
+                }
+            }
+        }
+    }
+
+    /// Stop the running download; what it got so far is kept for a resume
+    pub fn cancel_image_download(&mut self) {
+        if let Some(panel) = self.image_catalog.as_mut() {
+            if panel.is_running() {
+                panel.download = None;
+                panel.rescan();
+                self.push_message(MessageLevel::Info, "Download stopped; starting it again resumes it");
+            }
+        }
+    }
+
+    /// Take in a running download's progress, returning whether anything
+    /// changed. When it ends the outcome is toasted and the directory read
+    /// again.
+    pub fn poll_image_catalog(&mut self) -> bool {
+        let Some(panel) = self.image_catalog.as_mut() else {
+            return false;
+        };
+        let Some(download) = panel.download.as_mut() else {
+            return false;
+        };
+        let was_finished = download.is_finished();
+        if !download.poll() {
+            return false;
+        }
+        if was_finished || !download.is_finished() {
+            return true;
+        }
+
+        let name = download.name.clone();
+        match download.outcome.clone() {
+            Some(Ok(summary)) => self.push_message(MessageLevel::Info, format!("Downloaded {}: {}", name, summary)),
+            Some(Err(reason)) => self.push_message(MessageLevel::Error, format!("Downloading {} failed: {}", name, reason)),
+            None => {}
+        }
+        if let Some(panel) = self.image_catalog.as_mut() {
+            if panel.hidden {
+                self.image_catalog = None;
+            } else {
+                panel.rescan();
+            }
+        }
+        true
+    }
+
+    // Self-test
+    /// Start the smoke test, or bring back the panel of one still running
+    pub fn start_selftest(&mut self) {
+        if self.read_only || self.lacks(SELFTEST_PERMISSIONS) {
+            return;
+        }
+        match self.selftest_run.as_mut() {
+            Some(run) if !run.is_finished() => run.hidden = false,
+            _ => {
+                let run = SelfTestRun::start(&self.k8s_collector, &self.selftest_config);
+                tracing::info!("Starting self-test {}/{}", run.namespace, run.name);
+                self.selftest_run = Some(run);
+            }
+        }
+    }
+
+    /// Take in step outcomes, returning whether any arrived. The result of
+    /// a finished test is toasted.
+    pub fn poll_selftest(&mut self) -> bool {
+        let Some(run) = self.selftest_run.as_mut() else {
+            return false;
+        };
+        let was_finished = run.is_finished();
+        if !run.poll() {
+            return false;
+        }
+        if !was_finished && run.is_finished() {
+            let (summary, failed, hidden) = (run.summary(), run.failed() > 0, run.hidden);
+            tracing::info!("{}", summary);
+            let level = if failed { MessageLevel::Error } else { MessageLevel::Info };
+            self.push_message(level, summary);
+            if hidden {
+                self.selftest_run = None;
+            }
+        }
+        true
+    }
+
+    /// Close the Self-test panel; a test still running keeps going and
+    /// reports its outcome as a toast
+    pub fn close_selftest_run(&mut self) {
+        match self.selftest_run.as_mut() {
+            Some(run) if !run.is_finished() => run.hidden = true,
+            _ => self.selftest_run = None,
+        }
+    }
+
+    // Node evacuation
+    /// Ask before draining a node, or bring back the panel of a drain that
+    /// is still running
+    pub fn open_evacuation_prompt(&mut self, node: String) {
+        if self.read_only || self.lacks(EVACUATION_PERMISSIONS) {
+            return;
+        }
+        match self.evacuation.as_mut() {
+            Some(run) if !run.is_finished() => run.hidden = false,
+            _ => self.evacuation_prompt = Some(node),
+        }
+    }
+
+    /// Evacuate the node selected on the Fleet screen
+    pub fn open_fleet_evacuation(&mut self) {
+        if let Some(name) = self.fleet_rows().get(self.fleet_selected).map(|(node, _)| node.name.clone()) {
+            self.open_evacuation_prompt(name);
+        }
+    }
+
+    /// Running VMs the Fleet view last saw on a node
+    pub fn fleet_vm_count(&self, node: &str) -> Option<usize> {
+        self.fleet.iter().find(|n| n.name == node).map(|n| n.vms.len())
+    }
+
+    pub fn cancel_evacuation(&mut self) {
+        self.evacuation_prompt = None;
+    }
+
+    /// Cordon the confirmed node and start moving its VMs and pods away
+    pub fn confirm_evacuation(&mut self) {
+        let Some(node) = self.evacuation_prompt.take() else {
+            return;
+        };
+        tracing::info!("Evacuating node {}", node);
+        self.evacuation = Some(Evacuation::start(&self.k8s_collector, node, &self.evacuation_config));
+    }
+
+    /// Take in drain progress, returning whether any arrived. Once every VM
+    /// and pod is settled the outcome is toasted, naming what was left behind.
+    pub fn poll_evacuation(&mut self) -> bool {
+        let Some(run) = self.evacuation.as_mut() else {
+            return false;
+        };
+        let was_finished = run.is_finished();
+        if !run.poll() {
+            return false;
+        }
+        if !was_finished && run.is_finished() {
+            let left = run.left_behind();
+            let failed = !left.is_empty() || matches!(run.cordon, Some(Err(_))) || matches!(run.listing, Some(Err(_)));
+            let mut summary = format!("Evacuation of {}", run.summary());
+            if !left.is_empty() {
+                summary.push_str(&format!("; still on the node: {}", left.join(", ")));
+            }
+            let hidden = run.hidden;
+            tracing::info!("{}", summary);
+            self.push_message(if failed { MessageLevel::Warning } else { MessageLevel::Info }, summary);
+            if hidden {
+                self.evacuation = None;
+            }
+        }
+        true
+    }
+
+    /// Take in a finished update check, toasting a newer release once
+    pub fn poll_update_check(&mut self) -> bool {
+        let Some(release) = self.update_checker.as_mut().and_then(UpdateChecker::poll) else {
+            return false;
+        };
+        tracing::info!("hypervisor-tui {} is available", release.version);
+        self.push_message(
+            MessageLevel::Info,
+            format!(
+                "hypervisor-tui {} is available (running {}); release notes are in the command palette",
+                release.version,
+                crate::version_check::CURRENT_VERSION
+            ),
+        );
+        true
+    }
+
+    /// Take in a finished permission audit, toasting what is missing
+    pub fn poll_permissions(&mut self) -> bool {
+        if !self.permissions.poll() {
+            return false;
+        }
+        if let Some(ref e) = self.permissions.error {
+            self.push_message(MessageLevel::Warning, format!("Permission audit failed: {}", e));
+            return true;
+        }
+        let Some(ref audit) = self.permissions.audit else {
+            return true;
+        };
+        let denied: Vec<&str> = audit.denied().map(|check| check.permission.label()).collect();
+        match denied.len() {
+            0 => self.push_message(
+                MessageLevel::Info,
+                format!("Kubeconfig allows all {} checked operations", audit.checks.len()),
+            ),
+            n => {
+                let summary = format!(
+                    "Kubeconfig lacks {} of {} permissions ({}); those actions are disabled, details in F12",
+                    n,
+                    audit.checks.len(),
+                    denied.join(", ")
+                );
+                tracing::warn!("{}", summary);
+                self.push_message(MessageLevel::Warning, summary);
+            }
+        }
+        true
+    }
+
+    /// Review the kubeconfig's permissions again
+    pub fn check_permissions(&mut self) {
+        if !self.k8s_collector.is_connected() {
+            self.push_message(MessageLevel::Warning, "Not connected to a cluster");
+            return;
+        }
+        if !self.permissions.is_running() {
+            self.permissions.start(&self.k8s_collector);
+        }
+    }
+
+    /// Refuse an action the kubeconfig isn't allowed, naming the missing
+    /// RBAC rules
+    pub fn lacks(&mut self, permissions: &[Permission]) -> bool {
+        let missing: Vec<String> = permissions
+            .iter()
+            .filter(|permission| !self.permissions.allows(**permission))
+            .map(|permission| permission.rule())
+            .collect();
+        if missing.is_empty() {
+            return false;
+        }
+        self.push_message(MessageLevel::Warning, format!("Not permitted by the kubeconfig: {}", missing.join(", ")));
+        true
+    }
+
+    /// Close the Evacuate panel; migrations still running keep going
+    pub fn close_evacuation(&mut self) {
+        match self.evacuation.as_mut() {
+            Some(run) if !run.is_finished() => run.hidden = true,
+            _ => self.evacuation = None,
+        }
+    }
+
+    // YAML inspector
+    pub async fn open_yaml_inspector(&mut self) {
+        let Some(resource) = self.selected_resource().cloned() else {
+            return;
+        };
+
+        let (content, status) = match self.k8s_collector.fetch_resource_yaml(&resource).await {
+            Ok(yaml) => (yaml, None),
+            Err(e) => (String::new(), Some(format!("Failed to fetch object: {}", e))),
+        };
+
+        self.yaml_inspector = Some(YamlInspector {
+            resource,
+            content,
+            scroll: 0,
+            status,
+            conflicted: None,
+        });
+    }
+
+    pub fn close_yaml_inspector(&mut self) {
+        self.yaml_inspector = None;
+    }
+
+    pub fn yaml_scroll_up(&mut self, lines: usize) {
+        if let Some(inspector) = self.yaml_inspector.as_mut() {
+            inspector.scroll = inspector.scroll.saturating_sub(lines);
+        }
+    }
+
+    pub fn yaml_scroll_down(&mut self, lines: usize) {
+        if let Some(inspector) = self.yaml_inspector.as_mut() {
+            let max = inspector.content.lines().count().saturating_sub(1);
+            inspector.scroll = (inspector.scroll + lines).min(max);
+        }
+    }
+
+    // Object watch
+    /// Follow the selected object's updates and diff each against the last
+    pub fn open_object_watch(&mut self) {
+        let Some(resource) = self.selected_resource().cloned() else {
+            return;
+        };
+        let command = self.k8s_collector.watch_command(&resource);
+        self.object_watch = Some(ObjectWatch::start(resource, command));
+    }
+
+    pub fn close_object_watch(&mut self) {
+        self.object_watch = None;
+    }
+
+    pub fn scroll_object_watch(&mut self, down: bool, lines: usize) {
+        if let Some(watch) = self.object_watch.as_mut() {
+            watch.scroll = if down { watch.scroll.saturating_add(lines) } else { watch.scroll.saturating_sub(lines) };
+        }
+    }
+
+    /// Take in the watched object's updates, returning whether any arrived
+    pub fn poll_object_watch(&mut self) -> bool {
+        self.object_watch.as_mut().is_some_and(ObjectWatch::poll)
+    }
+
+    /// Apply an edited manifest and reload the inspector with the server's
+    /// view. A conflict with another field manager keeps the edit for
+    /// `force_yaml_apply`.
+    pub async fn apply_yaml_edit(&mut self, manifest: String) {
+        self.apply_yaml(manifest, false).await;
+    }
+
+    /// Apply the edit that conflicted again, taking over the fields other
+    /// managers own
+    pub async fn force_yaml_apply(&mut self) {
+        let Some(manifest) = self.yaml_inspector.as_mut().and_then(|inspector| inspector.conflicted.take()) else {
+            return;
+        };
+        self.apply_yaml(manifest, true).await;
+    }
+
+    async fn apply_yaml(&mut self, manifest: String, force: bool) {
+        if self.read_only {
+            return;
+        }
+        let result = self.k8s_collector.apply_resource_yaml(&manifest, force).await;
+
+        let Some(inspector) = self.yaml_inspector.as_mut() else {
+            return;
+        };
+
+        inspector.conflicted = None;
+        match result {
+            Ok(output) => {
+                if let Ok(yaml) = self.k8s_collector.fetch_resource_yaml(&inspector.resource).await {
+                    inspector.content = yaml;
+                }
+                inspector.status = Some(output);
+            }
+            Err(e) if KubernetesCollector::is_apply_conflict(&e) => {
+                let reason = e.to_string();
+                let reason = reason.lines().next().unwrap_or_default().trim_start_matches("kubectl apply failed: ");
+                inspector.status = Some(format!("{} (F: apply anyway, taking these fields over)", reason));
+                inspector.conflicted = Some(manifest);
+            }
+            Err(e) => inspector.status = Some(e.to_string()),
+        }
+    }
+
+    // Cloud-init viewer
+    pub async fn open_cloud_init(&mut self) {
+        let Some(resource) = self
+            .selected_resource()
+            .filter(|r| r.kind == ResourceKind::VirtualMachine)
+            .cloned()
+        else {
+            return;
+        };
+
+        let namespace = resource.namespace.clone().unwrap_or_default();
+        let (vm, status) = match self.k8s_collector.fetch_vm(&namespace, &resource.name).await {
+            Ok(vm) => (Some(vm), None),
+            Err(e) => (None, Some(format!("Failed to read VM: {:#}", e))),
+        };
+        let cloud_init = vm.as_ref().and_then(CloudInit::from_vm);
+        let status = status.or_else(|| cloud_init.is_none().then(|| "VM has no cloud-init volume".to_string()));
+
+        self.cloud_init = Some(CloudInitViewer {
+            resource,
+            vm,
+            cloud_init,
+            scroll: 0,
+            status,
+        });
+    }
+
+    pub fn close_cloud_init(&mut self) {
+        self.cloud_init = None;
+    }
+
+    pub fn cloud_init_scroll(&mut self, down: bool, lines: usize) {
+        if let Some(viewer) = self.cloud_init.as_mut() {
+            viewer.scroll = if down {
+                let max = viewer.content().lines().count().saturating_sub(1);
+                (viewer.scroll + lines).min(max)
+            } else {
+                viewer.scroll.saturating_sub(lines)
+            };
+        }
+    }
+
+    /// Raw user data to hand to $EDITOR, or None (with the reason shown)
+    /// when it can't be edited here
+    pub fn cloud_init_user_data(&mut self) -> Option<String> {
+        let viewer = self.cloud_init.as_mut()?;
+        match viewer.cloud_init {
+            Some(CloudInit { source: UserDataSource::Secret(ref secret), .. }) => {
+                viewer.status = Some(format!("User data is in Secret {}; edit the Secret instead", secret));
+                None
+            }
+            Some(ref cloud_init) => Some(cloud_init.user_data.clone()),
+            None => None,
+        }
+    }
+
+    /// Save edited user data into the VM spec and reload the viewer. Like
+    /// any cloud-init change it applies from the VM's next boot.
+    pub async fn apply_cloud_init_edit(&mut self, user_data: String) {
+        if self.read_only {
+            return;
+        }
+        let Some(viewer) = self.cloud_init.as_mut() else {
+            return;
+        };
+        let Some(patch) = viewer
+            .cloud_init
+            .as_ref()
+            .zip(viewer.vm.as_ref())
+            .and_then(|(cloud_init, vm)| cloud_init.merge_patch(vm, &user_data))
+        else {
+            return;
+        };
+
+        let namespace = viewer.resource.namespace.clone().unwrap_or_default();
+        let name = viewer.resource.name.clone();
+        match self.k8s_collector.patch_vm(&namespace, &name, &patch).await {
+            Ok(_) => {
+                if let Ok(vm) = self.k8s_collector.fetch_vm(&namespace, &name).await {
+                    viewer.cloud_init = CloudInit::from_vm(&vm);
+                    viewer.vm = Some(vm);
+                }
+                viewer.status = Some("Saved. Takes effect at the VM's next boot".to_string());
+            }
+            Err(e) => viewer.status = Some(format!("{:#}", e)),
+        }
+    }
+
+    // Dashboard layout editor
+    pub fn toggle_layout_edit(&mut self) {
+        if self.read_only {
+            self.push_message(MessageLevel::Info, "Read-only: the dashboard layout can't be changed");
+            return;
+        }
+        self.layout_edit = match self.layout_edit {
+            Some(_) => None,
+            None => Some((0, 0)),
+        };
+        self.layout_status = None;
+    }
+
+    pub fn layout_navigate(&mut self, d_row: i32, d_col: i32) {
+        if let Some(selected) = self.layout_edit {
+            self.layout_edit = Some(self.dashboard_layout.navigate(selected, d_row, d_col));
+        }
+    }
+
+    pub fn layout_move_card(&mut self, d_row: i32, d_col: i32) {
+        if let Some(selected) = self.layout_edit {
+            self.layout_edit = Some(self.dashboard_layout.move_card(selected, d_row, d_col));
+        }
+    }
+
+    pub fn layout_resize_card(&mut self, grow: bool) {
+        if let Some(selected) = self.layout_edit {
+            self.dashboard_layout.resize_card(selected, grow);
+        }
+    }
+
+    pub fn layout_resize_row(&mut self, grow: bool) {
+        if let Some((row, _)) = self.layout_edit {
+            self.dashboard_layout.resize_row(row, grow);
+        }
+    }
+
+    pub fn layout_cycle_widget(&mut self) {
+        if let Some(selected) = self.layout_edit {
+            self.dashboard_layout.cycle_widget(selected);
+        }
+    }
+
+    pub fn layout_add_card(&mut self, new_row: bool) {
+        if let Some(selected) = self.layout_edit {
+            self.layout_edit = Some(self.dashboard_layout.add_card(selected, new_row));
+        }
+    }
+
+    pub fn layout_remove_card(&mut self) {
+        if let Some(selected) = self.layout_edit {
+            self.layout_edit = Some(self.dashboard_layout.remove_card(selected));
+        }
+    }
+
+    /// Persist the edited layout to the state directory
+    pub fn save_layout(&mut self) {
+        if self.read_only {
+            self.push_message(MessageLevel::Info, "Read-only: the dashboard layout can't be changed");
+            return;
+        }
+        self.layout_status = Some(match self.dashboard_layout.save() {
+            Ok(path) => format!("Saved to {}", path.display()),
+            Err(e) => {
+                self.push_message(MessageLevel::Error, format!("Failed to save layout: {:#}", e));
+                format!("{:#}", e)
+            }
+        });
+    }
+
+    // VM CPU/NUMA tuning editor
+    pub async fn open_cpu_tuning(&mut self) {
+        if self.read_only || self.lacks(&[Permission::PatchVms]) {
+            return;
+        }
+        let Some(resource) = self
+            .selected_resource()
+            .filter(|r| r.kind == ResourceKind::VirtualMachine)
+            .cloned()
+        else {
+            return;
+        };
+
+        let namespace = resource.namespace.clone().unwrap_or_default();
+        let (original, status) = match self
+            .k8s_collector
+            .fetch_vm_cpu_tuning(&namespace, &resource.name)
+            .await
+        {
+            Ok(tuning) => (tuning, None),
+            Err(e) => (CpuTuning::default(), Some(format!("Failed to read VM: {:#}", e))),
+        };
+
+        self.cpu_tuning = Some(CpuTuningEditor {
+            resource,
+            tuning: original.clone(),
+            original,
+            selected: 0,
+            status,
+        });
+    }
+
+    pub fn close_cpu_tuning(&mut self) {
+        self.cpu_tuning = None;
+    }
+
+    pub fn cpu_tuning_navigate(&mut self, down: bool) {
+        if let Some(editor) = self.cpu_tuning.as_mut() {
+            let count = CpuTuningField::ALL.len();
+            editor.selected = if down {
+                (editor.selected + 1) % count
+            } else {
+                (editor.selected + count - 1) % count
+            };
+        }
+    }
+
+    /// Toggle or cycle the value of the selected field
+    pub fn cpu_tuning_change(&mut self) {
+        let Some(editor) = self.cpu_tuning.as_mut() else {
+            return;
+        };
+        let field = editor.selected_field();
+        let tuning = &mut editor.tuning;
+        match field {
+            CpuTuningField::DedicatedCpus => tuning.dedicated_cpu_placement = !tuning.dedicated_cpu_placement,
+            CpuTuningField::NumaPassthrough => tuning.numa_passthrough = !tuning.numa_passthrough,
+            CpuTuningField::Model => {
+                tuning.model = cycle_option(&CPU_MODELS, &tuning.model, &editor.original.model)
+            }
+            CpuTuningField::Hugepages => {
+                tuning.hugepages = cycle_option(&HUGEPAGE_SIZES, &tuning.hugepages, &editor.original.hugepages)
+            }
+        }
+        editor.status = None;
+    }
+
+    /// Patch the VirtualMachine with the edited settings. They only take
+    /// effect once the VM is restarted.
+    pub async fn apply_cpu_tuning(&mut self) {
+        if self.read_only {
+            return;
+        }
+        let Some(editor) = self.cpu_tuning.as_mut() else {
+            return;
+        };
+        if !editor.is_modified() {
+            editor.status = Some("No changes to apply".to_string());
+            return;
+        }
+        if let Some(problem) = editor.tuning.validate() {
+            editor.status = Some(problem.to_string());
+            return;
+        }
+
+        let namespace = editor.resource.namespace.clone().unwrap_or_default();
+        let patch = editor.tuning.merge_patch(&editor.original);
+        match self.k8s_collector.patch_vm(&namespace, &editor.resource.name, &patch).await {
+            Ok(_) => {
+                editor.original = editor.tuning.clone();
+                editor.status = Some("Patched. Restart the VM for the changes to take effect".to_string());
+            }
+            Err(e) => editor.status = Some(format!("{:#}", e)),
+        }
+    }
+
+    // Live VM resize and volume hot-plug
+    pub async fn open_hotplug(&mut self) {
+        if self.read_only || self.lacks(&[Permission::PatchVms, Permission::HotplugVolumes]) {
+            return;
+        }
+        let Some(resource) = self
+            .selected_resource()
+            .filter(|r| r.kind == ResourceKind::VirtualMachine)
+            .cloned()
+        else {
+            return;
+        };
+
+        let namespace = resource.namespace.clone().unwrap_or_default();
+        let (hotplug, status) = match self.k8s_collector.fetch_vm_hotplug(&namespace, &resource.name).await {
+            Ok(hotplug) => (Some(hotplug), None),
+            Err(e) => (None, Some(format!("Failed to read VM: {:#}", e))),
+        };
+        self.hotplug = Some(HotplugEditor {
+            resource,
+            sockets: hotplug.as_ref().map_or(0, |h| h.sockets),
+            memory_mib: hotplug.as_ref().map_or(0, |h| h.memory_mib),
+            hotplug,
+            claim: String::new(),
+            selected: 0,
+            status,
+        });
+    }
+
+    pub fn close_hotplug(&mut self) {
+        self.hotplug = None;
+    }
+
+    pub fn hotplug_navigate(&mut self, down: bool) {
+        if let Some(editor) = self.hotplug.as_mut() {
+            let count = HotplugField::ALL.len();
+            editor.selected = if down {
+                (editor.selected + 1) % count
+            } else {
+                (editor.selected + count - 1) % count
+            };
+        }
+    }
+
+    /// Step the selected size up or down within what can be plugged in;
+    /// memory can't be unplugged, so it never goes below the current size
+    pub fn hotplug_adjust(&mut self, up: bool) {
+        let Some(editor) = self.hotplug.as_mut() else {
+            return;
+        };
+        let Some(hotplug) = editor.hotplug.as_ref() else {
+            return;
+        };
+        match editor.selected_field() {
+            HotplugField::Sockets if up => editor.sockets = (editor.sockets + 1).min(hotplug.max_sockets.max(hotplug.sockets)),
+            HotplugField::Sockets => editor.sockets = editor.sockets.saturating_sub(1).max(1),
+            HotplugField::Memory if up => {
+                editor.memory_mib = (editor.memory_mib + HOTPLUG_MEMORY_STEP_MIB).min(hotplug.max_memory_mib.max(hotplug.memory_mib))
+            }
+            HotplugField::Memory => {
+                editor.memory_mib = editor.memory_mib.saturating_sub(HOTPLUG_MEMORY_STEP_MIB).max(hotplug.memory_mib)
+            }
+            HotplugField::Volume => return,
+        }
+        editor.status = None;
+    }
+
+    pub fn hotplug_input(&mut self, c: char) {
+        if let Some(editor) = self.hotplug.as_mut().filter(|e| e.selected_field() == HotplugField::Volume) {
+            editor.claim.push(c);
+            editor.status = None;
+        }
+    }
+
+    pub fn hotplug_backspace(&mut self) {
+        if let Some(editor) = self.hotplug.as_mut() {
+            editor.claim.pop();
+            editor.status = None;
+        }
+    }
+
+    /// Patch the new sizes and attach the claim, then report whether
+    /// KubeVirt could apply the resize without a restart
+    pub async fn apply_hotplug(&mut self) {
+        if self.read_only {
+            return;
+        }
+        let Some(editor) = self.hotplug.as_mut() else {
+            return;
+        };
+        let Some(hotplug) = editor.hotplug.clone() else {
+            return;
+        };
+        let claim = editor.claim.trim().to_string();
+        if !editor.resized() && claim.is_empty() {
+            editor.status = Some("No changes to apply".to_string());
+            return;
+        }
+        if let Some(problem) = editor.problem() {
+            editor.status = Some(problem);
+            return;
+        }
+
+        let namespace = editor.resource.namespace.clone().unwrap_or_default();
+        let name = editor.resource.name.clone();
+        let mut done = Vec::new();
+        if editor.resized() {
+            let patch = hotplug.merge_patch(editor.sockets, editor.memory_mib);
+            if let Err(e) = self.k8s_collector.patch_vm(&namespace, &name, &patch).await {
+                editor.status = Some(format!("{:#}", e));
+                return;
+            }
+            tracing::info!("Resized VM {}/{} to {} sockets, {} MiB", namespace, name, editor.sockets, editor.memory_mib);
+            done.push(format!("resized to {} sockets, {} MiB", editor.sockets, editor.memory_mib));
+        }
+        if !claim.is_empty() {
+            if let Err(e) = self.k8s_collector.attach_volume(&namespace, &name, &claim).await {
+                // The resize went through even though the attach didn't
+                editor.status = Some(match done.first() {
+                    Some(resized) => format!("{}; {:#}", resized, e),
+                    None => format!("{:#}", e),
+                });
+                return;
+            }
+            tracing::info!("Hot-plugged claim {} into VM {}/{}", claim, namespace, name);
+            done.push(format!("attached {}", claim));
+            editor.claim.clear();
+        }
+
+        let mut status = format!("{}: {}", name, done.join(", "));
+        if let Ok(Some(reason)) = self.k8s_collector.vm_restart_required(&namespace, &name).await {
+            status.push_str(&format!(". Not applied live, restart required: {}", reason));
+        }
+        // Show the sizes the VM has now
+        match self.k8s_collector.fetch_vm_hotplug(&namespace, &name).await {
+            Ok(fresh) => {
+                editor.sockets = fresh.sockets;
+                editor.memory_mib = fresh.memory_mib;
+                editor.hotplug = Some(fresh);
+            }
+            Err(_) => {
+                editor.hotplug = Some(VmHotplug { sockets: editor.sockets, memory_mib: editor.memory_mib, ..hotplug });
+            }
+        }
+        editor.status = Some(status);
+    }
+
+    // VM cloning
+    pub async fn open_vm_clone(&mut self) {
+        if self.read_only || self.lacks(&[Permission::CreateVms]) {
+            return;
+        }
+        let Some(resource) = self
+            .selected_resource()
+            .filter(|r| r.kind == ResourceKind::VirtualMachine)
+            .cloned()
+        else {
+            return;
+        };
+
+        let namespace = resource.namespace.clone().unwrap_or_default();
+        let (source, status) = match self.k8s_collector.fetch_vm(&namespace, &resource.name).await {
+            Ok(vm) => (Some(vm), None),
+            Err(e) => (None, Some(format!("Failed to read VM: {:#}", e))),
+        };
+        self.vm_clone = Some(VmCloneEditor {
+            name: format!("{}-clone", resource.name),
+            resource,
+            source,
+            clone_disks: true,
+            status,
+        });
+    }
+
+    pub fn close_vm_clone(&mut self) {
+        self.vm_clone = None;
+    }
+
+    pub fn vm_clone_input(&mut self, c: char) {
+        if let Some(editor) = self.vm_clone.as_mut() {
+            editor.name.push(c);
+            editor.status = None;
+        }
+    }
+
+    pub fn vm_clone_backspace(&mut self) {
+        if let Some(editor) = self.vm_clone.as_mut() {
+            editor.name.pop();
+            editor.status = None;
+        }
+    }
+
+    pub fn toggle_vm_clone_disks(&mut self) {
+        if let Some(editor) = self.vm_clone.as_mut() {
+            editor.clone_disks = !editor.clone_disks;
+        }
+    }
+
+    /// Create the clone, closing the popup and reloading the VM list on success
+    pub async fn submit_vm_clone(&mut self) {
+        if self.read_only {
+            return;
+        }
+        let Some(editor) = self.vm_clone.as_mut() else {
+            return;
+        };
+        let name = editor.name.trim().to_string();
+        if let Some(problem) = object_name_problem(&name) {
+            editor.status = Some(problem.to_string());
+            return;
+        }
+        if name == editor.resource.name {
+            editor.status = Some("name must differ from the source VM".to_string());
+            return;
+        }
+        let Some(plan) = editor.plan() else {
+            return;
+        };
+
+        let namespace = editor.resource.namespace.clone().unwrap_or_default();
+        match self.k8s_collector.create_object(&plan.manifest).await {
+            Ok(_) => {
+                let cloning = plan.disks.iter().filter(|d| matches!(d, CloneDisk::Cloned { .. })).count();
+                tracing::info!("Cloned VM {}/{} as {}", namespace, editor.resource.name, name);
+                self.vm_clone = None;
+                self.push_message(
+                    MessageLevel::Info,
+                    format!("Created VM {}/{} (halted, {} disk(s) cloning)", namespace, name, cloning),
+                );
+                if let Err(e) = self.update_resources().await {
+                    self.report_error("Loading resources", &e);
+                }
+            }
+            Err(e) => editor.status = Some(format!("{:#}", e)),
+        }
+    }
+
+    // Node labels and taints editor
+    pub async fn open_node_editor(&mut self) {
+        let Some(node) = self
+            .selected_resource()
+            .filter(|r| r.kind == ResourceKind::Node)
+            .map(|r| r.name.clone())
+        else {
+            return;
+        };
+        let (metadata, status) = match self.k8s_collector.fetch_node_metadata(&node).await {
+            Ok(metadata) => (metadata, None),
+            Err(e) => (NodeMetadata::default(), Some(format!("{:#}", e))),
+        };
+        self.node_editor = Some(NodeEditor {
+            node,
+            metadata,
+            selected: 0,
+            input: None,
+            status,
+        });
+    }
+
+    pub fn close_node_editor(&mut self) {
+        self.node_editor = None;
+    }
+
+    pub fn node_editor_navigate(&mut self, down: bool) {
+        if let Some(editor) = self.node_editor.as_mut() {
+            let last = editor.row_count().saturating_sub(1);
+            editor.selected = if down { (editor.selected + 1).min(last) } else { editor.selected.saturating_sub(1) };
+        }
+    }
+
+    pub fn node_editor_start_input(&mut self, field: NodeEditField) {
+        if self.read_only || self.lacks(&[Permission::PatchNodes]) {
+            return;
+        }
+        if let Some(editor) = self.node_editor.as_mut() {
+            editor.input = Some((field, String::new()));
+            editor.status = None;
+        }
+    }
+
+    pub fn node_editor_cancel_input(&mut self) {
+        if let Some(editor) = self.node_editor.as_mut() {
+            editor.input = None;
+        }
+    }
+
+    pub fn node_editor_input(&mut self, c: char) {
+        if let Some((_, text)) = self.node_editor.as_mut().and_then(|e| e.input.as_mut()) {
+            text.push(c);
+        }
+    }
+
+    pub fn node_editor_backspace(&mut self) {
+        if let Some((_, text)) = self.node_editor.as_mut().and_then(|e| e.input.as_mut()) {
+            text.pop();
+        }
+    }
+
+    /// Add the typed label or taint to the node
+    pub async fn submit_node_editor_input(&mut self) {
+        let Some(editor) = self.node_editor.as_mut() else {
+            return;
+        };
+        let Some((field, text)) = editor.input.clone() else {
+            return;
+        };
+        let node = editor.node.clone();
+        let result = match field {
+            NodeEditField::Label => match text.trim().split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    let description = format!("Labeled {} {}={}", node, key.trim(), value.trim());
+                    self.k8s_collector
+                        .set_node_label(&node, key.trim(), Some(value.trim()))
+                        .await
+                        .map(|_| description)
+                }
+                _ => Err(anyhow::anyhow!("Label must be key=value")),
+            },
+            NodeEditField::Taint => match NodeTaint::parse(&text) {
+                Ok(taint) => {
+                    let description = format!("Tainted {} {}", node, taint);
+                    let mut taints = editor.metadata.taints.clone();
+                    taints.retain(|t| !(t.key == taint.key && t.effect == taint.effect));
+                    taints.push(taint);
+                    self.k8s_collector
+                        .set_node_taints(&node, &taints, editor.metadata.resource_version.as_deref())
+                        .await
+                        .map(|_| description)
+                }
+                Err(problem) => Err(anyhow::anyhow!(problem)),
+            },
+        };
+        self.finish_node_edit(result).await;
+    }
+
+    /// Remove the label or taint under the cursor
+    pub async fn remove_node_editor_selected(&mut self) {
+        if self.read_only || self.lacks(&[Permission::PatchNodes]) {
+            return;
+        }
+        let Some(editor) = self.node_editor.as_ref() else {
+            return;
+        };
+        let node = editor.node.clone();
+        let labels = editor.metadata.labels.len();
+        let result = if let Some(key) = editor.metadata.labels.keys().nth(editor.selected).cloned() {
+            self.k8s_collector
+                .set_node_label(&node, &key, None)
+                .await
+                .map(|_| format!("Removed label {} from {}", key, node))
+        } else if let Some(taint) = editor.metadata.taints.get(editor.selected - labels).cloned() {
+            let taints: Vec<NodeTaint> = editor.metadata.taints.iter().filter(|t| **t != taint).cloned().collect();
+            self.k8s_collector
+                .set_node_taints(&node, &taints, editor.metadata.resource_version.as_deref())
+                .await
+                .map(|_| format!("Removed taint {} from {}", taint, node))
+        } else {
+            return;
+        };
+        self.finish_node_edit(result).await;
+    }
+
+    /// Show the outcome of a node patch and re-read the node on success
+    async fn finish_node_edit(&mut self, result: Result<String>) {
+        let Some(editor) = self.node_editor.as_mut() else {
+            return;
+        };
+        match result {
+            Ok(description) => {
+                tracing::info!("{}", description);
+                editor.input = None;
+                editor.status = Some(description);
+                match self.k8s_collector.fetch_node_metadata(&editor.node).await {
+                    Ok(metadata) => editor.metadata = metadata,
+                    Err(e) => editor.status = Some(format!("{:#}", e)),
+                }
+                editor.selected = editor.selected.min(editor.row_count().saturating_sub(1));
+            }
+            Err(e) => editor.status = Some(format!("{:#}", e)),
+        }
+    }
+
+    // VM heatmap
+    pub fn toggle_heatmap_metric(&mut self) {
+        self.heatmap_metric = match self.heatmap_metric {
+            HeatmapMetric::Cpu => HeatmapMetric::Memory,
+            HeatmapMetric::Memory => HeatmapMetric::Cpu,
+        };
+    }
+
+    /// Move the heatmap cursor; rows are `columns` cells wide
+    pub fn heatmap_move(&mut self, d_row: i32, d_col: i32, columns: usize) {
+        if self.vm_usage.is_empty() {
+            return;
+        }
+        let last = self.vm_usage.len() as i64 - 1;
+        let target = self.heatmap_cursor as i64 + d_row as i64 * columns.max(1) as i64 + d_col as i64;
+        self.heatmap_cursor = target.clamp(0, last) as usize;
+    }
+
+    // Host map
+    pub fn host_map_navigate(&mut self, motion: Motion) {
+        self.host_map_selected = motion.apply(self.host_map_selected, self.qemu_processes.len());
+    }
+
+    pub async fn open_host_map_vm_detail(&mut self) {
+        let vm = self.qemu_processes.get(self.host_map_selected).and_then(|p| p.vm.clone());
+        if let Some((namespace, name)) = vm.as_deref().and_then(|vm| vm.split_once('/')) {
+            let resource = K8sResource {
+                kind: ResourceKind::VirtualMachine,
+                name: name.to_string(),
+                namespace: Some(namespace.to_string()),
+                status: "Running".to_string(),
+                labels: BTreeMap::new(),
+            };
+            self.open_vm_detail_for(resource).await;
+        }
+    }
+
+    pub async fn toggle_host_map_view(&mut self) {
+        self.host_map_view = match self.host_map_view {
+            HostMapView::Processes => HostMapView::Images,
+            HostMapView::Images => HostMapView::Memory,
+            HostMapView::Memory => HostMapView::Processes,
+        };
+        if self.host_map_view == HostMapView::Images {
+            self.update_disk_images().await;
+        }
+    }
+
+    pub fn disk_navigate(&mut self, motion: Motion) {
+        self.disk_selected = motion.apply(self.disk_selected, self.disk_images.len());
+    }
 
-            // Adjust selection if needed
-            let new_count = self.alert_manager.active_count();
-            if self.alert_selected_index >= new_count && new_count > 0 {
-                self.alert_selected_index = new_count - 1;
+    /// Ask for confirmation before deleting the selected image; only orphans
+    pub fn request_disk_delete(&mut self) {
+        if self.read_only || self.lacks(&[Permission::DeleteClaims]) {
+            return;
+        }
+        match self.disk_images.get(self.disk_selected) {
+            Some(image) if image.is_orphan() => self.disk_delete = Some(image.clone()),
+            Some(image) => {
+                let owner = image.used_by.clone().unwrap_or_default();
+                self.push_message(MessageLevel::Warning, format!("{} is in use by {}", image.location, owner));
             }
+            None => {}
         }
     }
 
-    pub fn dismiss_all_alerts(&mut self) {
-        self.alert_manager.dismiss_all();
-        self.alert_selected_index = 0;
+    /// Ask for confirmation before signalling the selected qemu process;
+    /// only in local mode, where KubeVirt can't do it
+    pub fn request_qemu_signal(&mut self, signal: QemuSignal) {
+        if self.read_only || !self.local_mode || !self.emergency_actions {
+            return;
+        }
+        let Some(process) = self.qemu_processes.get(self.host_map_selected) else {
+            return;
+        };
+        // 'p' pauses or resumes depending on the process's state
+        let signal = match signal {
+            QemuSignal::Pause if process.stopped => QemuSignal::Resume,
+            signal => signal,
+        };
+        self.qemu_signal = Some((process.clone(), signal));
+    }
+
+    pub fn cancel_qemu_signal(&mut self) {
+        self.qemu_signal = None;
+    }
+
+    /// Send the confirmed signal, or `escalate` it: the terminate prompt
+    /// offers SIGKILL for a qemu that hangs on SIGTERM. Recorded in the
+    /// audit log, since the cluster won't know who stopped the VM.
+    pub fn confirm_qemu_signal(&mut self, escalate: bool) {
+        let Some((process, signal)) = self.qemu_signal.take() else {
+            return;
+        };
+        if self.read_only || !self.local_mode {
+            return;
+        }
+        let signal = match signal {
+            QemuSignal::Terminate if escalate => QemuSignal::Kill,
+            _ if escalate => return,
+            signal => signal,
+        };
+
+        let target = process.vm.clone().unwrap_or_else(|| process.domain.clone());
+        let action = format!("{} {} (PID {})", signal.label(), target, process.pid);
+        let result = signal_qemu(process.pid, signal);
+        let outcome = match result {
+            Ok(()) => "ok".to_string(),
+            Err(ref e) => format!("failed: {:#}", e),
+        };
+        let entry = AuditEntry::now("local-mode", action.clone(), "API server unreachable".to_string(), outcome);
+        if let Err(e) = AuditLog::in_state_dir().record(&entry) {
+            tracing::warn!("Failed to record {} in the audit log: {:#}", action, e);
+        }
+        let signalled = result.is_ok();
+        self.report_outcome(&action, result.map(|_| String::new()));
+        if signalled {
+            if let Some(p) = self.qemu_processes.iter_mut().find(|p| p.pid == process.pid) {
+                p.stopped = signal == QemuSignal::Pause;
+            }
+        }
+    }
+
+    pub fn cancel_disk_delete(&mut self) {
+        self.disk_delete = None;
+    }
+
+    /// Delete the confirmed image after re-checking it is still an orphan,
+    /// since a VM may have claimed it while the prompt was open
+    pub async fn confirm_disk_delete(&mut self) {
+        let Some(image) = self.disk_delete.take() else {
+            return;
+        };
+        if self.read_only {
+            return;
+        }
+
+        self.update_disk_images().await;
+        let still_orphan = self
+            .disk_images
+            .iter()
+            .any(|i| i.location == image.location && i.is_orphan());
+        if !still_orphan {
+            self.push_message(
+                MessageLevel::Warning,
+                format!("{} is no longer orphaned, not deleted", image.location),
+            );
+            return;
+        }
+
+        let action = format!("Deleting {}", image.location);
+        let result = match image.kind {
+            DiskImageKind::Claim { ref namespace, ref name, ref data_volume } => {
+                self.k8s_collector.delete_claim(namespace, name, data_volume.as_deref()).await
+            }
+            _ => delete_host_image(&image).map(|_| String::new()),
+        };
+        let deleted = result.is_ok();
+        self.report_outcome(&action, result);
+        if deleted {
+            self.disk_images.retain(|i| i.location != image.location);
+            self.disk_selected = self.disk_selected.min(self.disk_images.len().saturating_sub(1));
+        }
+    }
+
+    pub async fn open_heatmap_vm_detail(&mut self) {
+        if let Some(resource) = self.vm_usage.get(self.heatmap_cursor).map(VmUsage::resource) {
+            self.open_vm_detail_for(resource).await;
+        }
+    }
+
+    // Status messages
+    pub fn push_message(&mut self, level: MessageLevel, text: impl Into<String>) {
+        let text = text.into();
+        // Repeating a visible message just keeps it up longer
+        self.messages.retain(|m| m.text != text);
+        if self.messages.len() >= MAX_MESSAGES {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(Message {
+            level,
+            text,
+            expires: Instant::now() + level.lifetime(),
+        });
+    }
+
+    /// Report a failed action as an error toast
+    pub fn report_error(&mut self, action: &str, error: &anyhow::Error) {
+        tracing::warn!("{} failed: {:#}", action, error);
+        self.push_message(MessageLevel::Error, format!("{} failed: {:#}", action, error));
+    }
+
+    /// Drop expired messages, returning whether any were removed
+    pub fn expire_messages(&mut self) -> bool {
+        let before = self.messages.len();
+        let now = Instant::now();
+        self.messages.retain(|m| m.expires > now);
+        self.messages.len() != before
+    }
+
+    pub fn dismiss_messages(&mut self) {
+        self.messages.clear();
+    }
+
+    pub fn close_config_report(&mut self) {
+        self.config_report = None;
+    }
+
+    // Command palette
+    pub fn open_palette(&mut self) {
+        let mut entries: Vec<PaletteEntry> = Screen::ALL
+            .into_iter()
+            .map(|screen| PaletteEntry::new(tf("palette.switch_to", &[&screen.title()]), PaletteAction::SwitchScreen(screen)))
+            .collect();
+
+        entries.extend([
+            PaletteEntry::new("Refresh all collectors", PaletteAction::Refresh),
+            PaletteEntry::new("Toggle alert panel", PaletteAction::ToggleAlertPanel),
+            PaletteEntry::new("Show alert statistics", PaletteAction::AlertStatistics),
+            PaletteEntry::new("Query archived logs by time range", PaletteAction::ArchivedLogs),
+            PaletteEntry::new("Search all services' logs", PaletteAction::SearchAllLogs),
+            PaletteEntry::new("Dismiss all alerts", PaletteAction::DismissAlerts(None)),
+            PaletteEntry::new("Dismiss all warnings", PaletteAction::DismissAlerts(Some(AlertLevel::Warning))),
+            PaletteEntry::new("Dismiss all info alerts", PaletteAction::DismissAlerts(Some(AlertLevel::Info))),
+            PaletteEntry::new("Dismiss status messages", PaletteAction::DismissMessages),
+            PaletteEntry::new("Edit dashboard layout", PaletteAction::EditLayout),
+            PaletteEntry::new("Toggle collector timing overlay", PaletteAction::ToggleDebugOverlay),
+            PaletteEntry::new(
+                if self.light_theme { "Switch to dark theme" } else { "Switch to light theme" },
+                PaletteAction::ToggleTheme,
+            ),
+            PaletteEntry::new("Restart virt-handler", PaletteAction::RestartVirtHandler),
+            PaletteEntry::new("Upgrade host (nixos-rebuild switch)", PaletteAction::Upgrade(UpgradeAction::Switch)),
+            PaletteEntry::new("Upgrade host on next boot (nixos-rebuild boot)", PaletteAction::Upgrade(UpgradeAction::Boot)),
+            PaletteEntry::new("Run hypervisor self-test", PaletteAction::SelfTest),
+            PaletteEntry::new("Nix store generations and garbage collection", PaletteAction::NixStore),
+            PaletteEntry::new("Cloud image catalog: download or import as DataVolume", PaletteAction::ImageCatalog),
+        ]);
+
+        // Nodes from the Fleet view, or just this host before it has loaded
+        let mut nodes: Vec<String> = self.fleet.iter().map(|n| n.name.clone()).collect();
+        if nodes.is_empty() {
+            nodes.extend(self.local_node.clone());
+        }
+        for node in nodes {
+            entries.push(PaletteEntry::new(format!("Cordon node {}", node), PaletteAction::CordonNode(node.clone())));
+            entries.push(PaletteEntry::new(format!("Uncordon node {}", node), PaletteAction::UncordonNode(node.clone())));
+            entries.push(PaletteEntry::new(
+                format!("Evacuate node {} (cordon and migrate its VMs)", node),
+                PaletteAction::EvacuateNode(node),
+            ));
+        }
+        for profile in self.alert_profiles.names() {
+            if self.alert_profile.as_deref() != Some(profile.as_str()) {
+                entries.push(PaletteEntry::new(
+                    format!("Switch alert profile to {}", profile),
+                    PaletteAction::AlertProfile(Some(profile)),
+                ));
+            }
+        }
+        if let Some(ref profile) = self.alert_profile {
+            entries.push(PaletteEntry::new(
+                format!("Leave alert profile {} (use [alerts] as configured)", profile),
+                PaletteAction::AlertProfile(None),
+            ));
+        }
+        entries.push(PaletteEntry::new("Export alert settings as a profile", PaletteAction::ExportAlertProfile));
+        for (index, view) in self.views.iter().enumerate() {
+            let label = match SavedView::shortcut(index) {
+                Some(key) => format!("View: {} ({})", view.name, key),
+                None => format!("View: {}", view.name),
+            };
+            entries.push(PaletteEntry::new(label, PaletteAction::ApplyView(index)));
+        }
+        entries.push(PaletteEntry::new("Save current view as...", PaletteAction::SaveView));
+        if let Some(release) = self.update_checker.as_ref().and_then(|checker| checker.newer.as_ref()) {
+            entries.push(PaletteEntry::new(
+                format!("Show release notes for hypervisor-tui {}", release.version),
+                PaletteAction::ShowUpdate,
+            ));
+        }
+        entries.push(PaletteEntry::new("Quit", PaletteAction::Quit));
+
+        entries.push(PaletteEntry::new("Check Kubernetes permissions", PaletteAction::CheckPermissions));
+
+        if self.read_only {
+            entries.retain(|entry| !entry.action.is_mutating());
+        }
+        entries.retain(|entry| entry.action.permissions().iter().all(|permission| self.permissions.allows(*permission)));
+        self.palette = Some(CommandPalette::new(entries));
+    }
+
+    pub fn close_palette(&mut self) {
+        self.palette = None;
+    }
+
+    /// Close the palette, yielding the selected action if anything matched
+    pub fn palette_accept(&mut self) -> Option<PaletteAction> {
+        self.palette.take().and_then(|palette| palette.selected_action())
+    }
+
+    /// Run a palette action. Quit is left to the event loop.
+    pub async fn run_palette_action(&mut self, action: PaletteAction) {
+        match action {
+            PaletteAction::SwitchScreen(screen) => self.current_screen = screen,
+            PaletteAction::Refresh => {
+                if let Err(e) = self.refresh().await {
+                    self.report_error("Refresh", &e);
+                }
+            }
+            PaletteAction::ToggleAlertPanel => self.toggle_alert_panel(),
+            PaletteAction::AlertStatistics => self.alert_stats_open = true,
+            PaletteAction::ArchivedLogs => {
+                self.current_screen = Screen::Logs;
+                self.open_log_range_prompt();
+            }
+            PaletteAction::SearchAllLogs => self.open_log_search(),
+            PaletteAction::DismissAlerts(None) => self.dismiss_all_alerts(),
+            PaletteAction::DismissAlerts(Some(level)) => self.dismiss_alerts_with_level(level),
+            PaletteAction::DismissMessages => self.dismiss_messages(),
+            PaletteAction::EditLayout => {
+                self.current_screen = Screen::Dashboard;
+                if self.layout_edit.is_none() {
+                    self.toggle_layout_edit();
+                }
+            }
+            PaletteAction::ToggleDebugOverlay => self.toggle_debug_overlay(),
+            PaletteAction::ToggleTheme => self.toggle_theme(),
+            PaletteAction::RestartVirtHandler => {
+                let result = self.k8s_collector.restart_daemonset("kubevirt", "virt-handler").await;
+                self.report_outcome("Restarting virt-handler", result);
+            }
+            PaletteAction::CordonNode(node) => {
+                let result = self.k8s_collector.set_node_schedulable(&node, false).await;
+                self.report_outcome(&format!("Cordoning {}", node), result);
+            }
+            PaletteAction::UncordonNode(node) => {
+                let result = self.k8s_collector.set_node_schedulable(&node, true).await;
+                self.report_outcome(&format!("Uncordoning {}", node), result);
+            }
+            PaletteAction::Upgrade(action) => self.start_upgrade(action),
+            PaletteAction::SelfTest => self.start_selftest(),
+            PaletteAction::NixStore => self.open_nix_store().await,
+            PaletteAction::ImageCatalog => self.open_image_catalog().await,
+            PaletteAction::EvacuateNode(node) => self.open_evacuation_prompt(node),
+            PaletteAction::AlertProfile(profile) => self.switch_alert_profile(profile).await,
+            PaletteAction::ExportAlertProfile => self.export_alert_profile(),
+            PaletteAction::ShowUpdate => self.update_popup = true,
+            PaletteAction::CheckPermissions => self.check_permissions(),
+            PaletteAction::ApplyView(index) => self.apply_view(index).await,
+            PaletteAction::SaveView => self.view_name_prompt = Some(String::new()),
+            PaletteAction::Quit => {}
+        }
+    }
+
+    // Saved UI state
+    /// Put the view back the way it was left; the alert profile and theme
+    /// were already taken from `state` while setting up
+    fn restore_ui_state(&mut self, state: UiState) {
+        if let Some(screen) = state.screen {
+            self.current_screen = screen;
+        }
+        self.search_query = state.search_query;
+        self.filter_level = state.filter_level;
+        if let Some(collapsed) = state.logs_collapsed {
+            self.logs_collapsed = collapsed;
+        }
+        if let Some(wrapped) = state.logs_wrapped {
+            self.logs_wrapped = wrapped;
+        }
+        if let Some(kind) = state.resource_kind {
+            self.resource_kind = kind;
+        }
+        if let Ok(selector) = LabelSelector::parse(&state.resource_selector) {
+            self.resource_label_selector = selector;
+            self.resource_selector = state.resource_selector;
+        }
+        if let Some(group_by) = state.resource_group_by {
+            self.resource_group_by = group_by;
+        }
+        if let Some(sort) = state.fleet_sort {
+            self.fleet_sort = sort;
+            self.fleet_sort_desc = state.fleet_sort_desc;
+        }
+        if let Some(metric) = state.heatmap_metric {
+            self.heatmap_metric = metric;
+        }
+        if let Some(metric) = state.chart_metric {
+            self.chart_metric = metric;
+        }
+        if let Some(zoom) = state.chart_zoom {
+            self.chart_zoom = zoom;
+        }
+        self.alert_panel_open = state.alert_panel_open;
+        self.alert_stats_open = state.alert_stats_open;
+    }
+
+    pub fn ui_state(&self) -> UiState {
+        UiState {
+            screen: Some(self.current_screen),
+            theme: self.chosen_theme,
+            search_query: self.search_query.clone(),
+            filter_level: self.filter_level.clone(),
+            logs_collapsed: Some(self.logs_collapsed),
+            logs_wrapped: Some(self.logs_wrapped),
+            resource_kind: Some(self.resource_kind),
+            resource_selector: self.resource_selector.clone(),
+            resource_group_by: Some(self.resource_group_by.clone()),
+            fleet_sort: Some(self.fleet_sort),
+            fleet_sort_desc: self.fleet_sort_desc,
+            heatmap_metric: Some(self.heatmap_metric),
+            chart_metric: Some(self.chart_metric),
+            chart_zoom: Some(self.chart_zoom),
+            alert_panel_open: self.alert_panel_open,
+            alert_stats_open: self.alert_stats_open,
+            alert_profile: self.alert_profile.clone(),
+        }
+    }
+
+    /// Remember the working view for the next start, unless
+    /// `display.remember_state` is off
+    pub fn save_ui_state(&self) {
+        if !self.remember_state {
+            return;
+        }
+        if let Err(e) = self.ui_state().save() {
+            tracing::warn!("Saving UI state: {:#}", e);
+        }
+    }
+
+    // Saved views
+    /// Switch to a `[[views]]` entry: its screen and filters, and the log
+    /// services it follows, read from the journal right away
+    pub async fn apply_view(&mut self, index: usize) {
+        let Some(view) = self.views.get(index).cloned() else {
+            return;
+        };
+        let kind = self.resource_kind;
+        self.restore_ui_state(view.state);
+        self.apply_log_filters();
+        self.apply_resource_filters();
+        self.fleet_selected = 0;
+        if !view.services.is_empty() && view.services != self.log_collector.services() {
+            self.log_collector.set_services(view.services);
+            self.reload_logs().await;
+        }
+        if self.resource_kind != kind {
+            self.resource_selected_index = 0;
+            self.vm_marked.clear();
+            if let Err(e) = self.update_resources().await {
+                self.report_error("Loading resources", &e);
+            }
+        }
+        self.push_message(MessageLevel::Info, format!("View: {}", view.name));
+    }
+
+    pub fn view_name_input(&mut self, c: char) {
+        if let Some(name) = self.view_name_prompt.as_mut() {
+            name.push(c);
+        }
+    }
+
+    pub fn view_name_backspace(&mut self) {
+        if let Some(name) = self.view_name_prompt.as_mut() {
+            name.pop();
+        }
+    }
+
+    pub fn close_view_name_prompt(&mut self) {
+        self.view_name_prompt = None;
+    }
+
+    /// Save the screen, filters, sort orders and followed log services as
+    /// a named view in the config file
+    pub fn submit_view_name(&mut self) {
+        let Some(name) = self.view_name_prompt.take().map(|name| name.trim().to_string()) else {
+            return;
+        };
+        if name.is_empty() {
+            self.push_message(MessageLevel::Warning, "A view needs a name");
+            return;
+        }
+        let view = SavedView {
+            name,
+            services: self.log_collector.services().to_vec(),
+            state: UiState {
+                // Not part of a view: the look and the alert setup
+                theme: None,
+                alert_profile: None,
+                alert_panel_open: false,
+                alert_stats_open: false,
+                ..self.ui_state()
+            },
+        };
+        match save_view(&view, &self.views) {
+            Ok(path) => {
+                let key = SavedView::shortcut(self.views.len()).map(|key| format!(", {}", key)).unwrap_or_default();
+                self.push_message(MessageLevel::Info, format!("Saved view {} to {}{}", view.name, path.display(), key));
+                self.views.push(view);
+            }
+            Err(e) => self.report_error("Saving view", &e),
+        }
+    }
+
+    /// Flip between the dark and light color schemes, remembered over
+    /// `display.theme` from then on
+    pub fn toggle_theme(&mut self) {
+        self.light_theme = !self.light_theme;
+        let (theme, name) = if self.light_theme { (Theme::Light, "light") } else { (Theme::Dark, "dark") };
+        self.chosen_theme = Some(theme);
+        self.push_message(MessageLevel::Info, format!("Switched to the {} theme", name));
+    }
+
+    // Alert profiles
+    /// Lay `profile` over the configured [alerts] (None for none) and
+    /// re-derive every threshold from the result
+    pub async fn switch_alert_profile(&mut self, profile: Option<String>) {
+        if self.read_only {
+            return;
+        }
+        let alerts = match self.alert_profiles.resolve(&self.config_alerts, profile.as_deref()) {
+            Ok(alerts) => alerts,
+            Err(e) => {
+                self.report_error("Switching alert profile", &e);
+                return;
+            }
+        };
+        let alerts = node_alerts_config(&alerts, &self.k8s_collector, self.local_node.as_deref()).await;
+        self.apply_alerts_config(&alerts);
+
+        let message = match profile {
+            Some(ref name) => format!("Alert profile: {}", name),
+            None => "Alert profile cleared; using [alerts] as configured".to_string(),
+        };
+        self.alert_profile = profile;
+        self.push_message(MessageLevel::Info, message);
+    }
+
+    /// Thresholds and rule switches from `alerts`. History, baselines and
+    /// notification settings stay as started.
+    fn apply_alerts_config(&mut self, alerts: &AlertsConfig) {
+        if !self.read_only {
+            self.auto_actions.set_rules(alerts.actions.clone());
+        }
+        self.alert_manager = std::mem::take(&mut self.alert_manager)
+            .with_system_config(system_alert_config(alerts))
+            .with_dedup_window(alerts.dedup_window_secs)
+            .with_rate_limit(alerts.rate_limit_per_minute, alerts.rule_rate_limit_per_minute)
+            .with_runbooks(alerts.runbooks.clone())
+            .with_kubernetes_enabled(alerts.kubernetes_enabled)
+            .with_kubevirt_enabled(alerts.kubevirt_enabled);
+        self.kmsg_collector = self
+            .kmsg_collector
+            .take()
+            .map(|collector| collector.with_window(alerts.kernel_event_window_mins));
+        self.bridge_watch = self
+            .bridge_watch
+            .take()
+            .map(|collector| collector.with_window(alerts.ip_conflict_window_mins));
+        self.dhcp_churn_threshold = alerts.dhcp_churn_threshold;
+        self.crash_window_mins = alerts.crash_window_mins;
+        self.oom_window_mins = alerts.kernel_event_window_mins;
+        self.ip_conflict_window_mins = alerts.ip_conflict_window_mins;
+
+        self.warning_thresholds = MetricThresholds {
+            cpu: alerts.cpu_warning_threshold,
+            memory: alerts.memory_warning_threshold,
+            disk: alerts.disk_warning_threshold,
+            load: alerts.load_warning_threshold,
+        };
+        self.timeline.set_thresholds(self.warning_thresholds);
+
+        self.alerts_enabled = alerts.enabled;
+        self.alert_snooze_mins = alerts.snooze_mins;
+        self.migration_failure_threshold = alerts.migration_failure_threshold as usize;
+        self.import_stall_mins = alerts.import_stall_mins;
+        self.guest_fs_thresholds = (alerts.guest_fs_warning_threshold, alerts.guest_fs_critical_threshold);
+        self.guest_clock_thresholds = (alerts.guest_clock_warning_secs, alerts.guest_clock_critical_secs);
+        self.api_latency_thresholds = (alerts.api_latency_warning_threshold, alerts.api_latency_critical_threshold);
+        self.disk_latency_thresholds = (alerts.disk_latency_warning_threshold, alerts.disk_latency_critical_threshold);
+        self.etcd_db_thresholds = (alerts.etcd_db_warning_threshold, alerts.etcd_db_critical_threshold);
+        self.etcd_leader_changes_per_hour = alerts.etcd_leader_changes_per_hour;
+        self.pod_restarts_per_hour = alerts.pod_restarts_per_hour;
+        self.disk_full_hours = (alerts.disk_full_warning_hours, alerts.disk_full_critical_hours);
+        self.quota_thresholds = (alerts.quota_warning_threshold, alerts.quota_critical_threshold);
+        self.fd_thresholds = (alerts.fd_warning_threshold, alerts.fd_critical_threshold);
+        self.ephemeral_port_thresholds =
+            (alerts.ephemeral_port_warning_threshold, alerts.ephemeral_port_critical_threshold);
+        self.pid_thresholds = (alerts.pid_warning_threshold, alerts.pid_critical_threshold);
+        self.entropy_thresholds = (alerts.entropy_warning_bits, alerts.entropy_critical_bits);
+        self.sched_wait_thresholds = (alerts.sched_wait_warning_threshold, alerts.sched_wait_critical_threshold);
+        self.cpu_throttle_thresholds = (alerts.cpu_throttle_warning_threshold, alerts.cpu_throttle_critical_threshold);
+        self.freq_throttle = (alerts.freq_throttle_percent, alerts.freq_throttle_mins);
+    }
+
+    /// Write a plain-text status report for incident handoffs and keep it
+    /// for the clipboard, which gets it even if the file can't be written
+    pub fn write_report(&mut self) {
+        let text = report::render(self, &self.report_config);
+        match report::save(&text, &self.report_config) {
+            Ok(path) => self.push_message(MessageLevel::Info, format!("Status report copied and saved to {}", path.display())),
+            Err(e) => self.report_error("Saving status report", &e),
+        }
+        self.last_report = Some(text);
+    }
+
+    /// Summary to print once the terminal is restored, when configured
+    pub fn exit_summary(&self) -> Option<String> {
+        report::exit_summary(self, &self.report_config)
+    }
+
+    /// Save the effective alert settings (before per-node overrides) as
+    /// "<host>-<date>" for sharing with other hosts
+    pub fn export_alert_profile(&mut self) {
+        let name = format!(
+            "{}-{}",
+            self.local_node.as_deref().unwrap_or("host").replace(|c: char| !c.is_ascii_alphanumeric() && c != '-', "-"),
+            Local::now().format("%Y%m%d-%H%M")
+        );
+        let result = self
+            .alert_profiles
+            .resolve(&self.config_alerts, self.alert_profile.as_deref())
+            .and_then(|alerts| self.alert_profiles.export(&name, &alerts));
+        match result {
+            Ok(path) => self.push_message(MessageLevel::Info, format!("Exported alert profile to {}", path.display())),
+            Err(e) => self.report_error("Exporting alert profile", &e),
+        }
+    }
+
+    fn report_outcome(&mut self, action: &str, result: Result<String>) {
+        match result {
+            Ok(output) if output.is_empty() => self.push_message(MessageLevel::Info, format!("{}: done", action)),
+            Ok(output) => self.push_message(MessageLevel::Info, output),
+            Err(e) => self.report_error(action, &e),
+        }
+    }
+
+    // Fleet view
+    /// Active alerts attributed to a node. Host alerts carry no node, so
+    /// they count against the local node.
+    pub fn node_alert_count(&self, node: &str) -> usize {
+        let local = self.local_node.as_deref() == Some(node);
+        self.alert_manager
+            .get_active_alerts()
+            .iter()
+            .filter(|alert| match alert.metadata.node_name.as_deref() {
+                Some(name) => name == node,
+                None => local,
+            })
+            .count()
+    }
+
+    /// Nodes in display order, with their alert counts
+    pub fn fleet_rows(&self) -> Vec<(&NodeStatus, usize)> {
+        let mut rows: Vec<(&NodeStatus, usize)> =
+            self.fleet.iter().map(|node| (node, self.node_alert_count(&node.name))).collect();
+
+        let percent = |value: Option<f64>| value.unwrap_or(-1.0);
+        rows.sort_by(|(a, a_alerts), (b, b_alerts)| {
+            let ordering = match self.fleet_sort {
+                FleetSort::Name => a.name.cmp(&b.name),
+                FleetSort::Status => a.reachability.cmp(&b.reachability),
+                FleetSort::Cpu => percent(a.cpu_percent).total_cmp(&percent(b.cpu_percent)),
+                FleetSort::Memory => percent(a.memory_percent).total_cmp(&percent(b.memory_percent)),
+                FleetSort::Vms => a.vms.len().cmp(&b.vms.len()),
+                FleetSort::Alerts => a_alerts.cmp(b_alerts),
+            };
+            let ordering = if self.fleet_sort_desc { ordering.reverse() } else { ordering };
+            ordering.then_with(|| a.name.cmp(&b.name))
+        });
+        rows
+    }
+
+    /// Sort by the next column; numeric columns start with the highest first
+    pub fn next_fleet_sort(&mut self) {
+        self.fleet_sort = self.fleet_sort.next();
+        self.fleet_sort_desc = !matches!(self.fleet_sort, FleetSort::Name | FleetSort::Status);
+        self.fleet_selected = 0;
+    }
+
+    pub fn reverse_fleet_sort(&mut self) {
+        self.fleet_sort_desc = !self.fleet_sort_desc;
+        self.fleet_selected = 0;
+    }
+
+    pub fn fleet_navigate(&mut self, motion: Motion) {
+        self.fleet_selected = motion.apply(self.fleet_selected, self.fleet.len());
+    }
+
+    /// Drill into the selected node: this host opens its Dashboard, other
+    /// nodes open a detail popup
+    pub fn open_fleet_node(&mut self) {
+        let Some(name) = self.fleet_rows().get(self.fleet_selected).map(|(node, _)| node.name.clone()) else {
+            return;
+        };
+        if self.local_node.as_deref() == Some(name.as_str()) {
+            self.current_screen = Screen::Dashboard;
+        } else {
+            self.fleet_detail = Some(name);
+        }
+    }
+
+    pub fn close_fleet_detail(&mut self) {
+        self.fleet_detail = None;
+    }
+
+    /// `c` on the Fleet screen: the first press picks the left node, the
+    /// second opens the comparison with the selected one
+    pub fn mark_fleet_compare(&mut self) {
+        let Some(name) = self.fleet_rows().get(self.fleet_selected).map(|(node, _)| node.name.clone()) else {
+            return;
+        };
+        match self.compare_mark.take() {
+            None => {
+                self.push_message(
+                    MessageLevel::Info,
+                    format!("Comparing from {}: select another node and press c", name),
+                );
+                self.compare_mark = Some(name);
+            }
+            Some(mark) if mark == name => self.push_message(MessageLevel::Info, "Comparison cleared"),
+            Some(mark) => match (self.fleet_snapshot(&mark), self.fleet_snapshot(&name)) {
+                (Some(left), Some(right)) => {
+                    self.compare = Some(CompareView { kind: CompareKind::Nodes, left, right });
+                }
+                _ => self.push_message(MessageLevel::Warning, format!("{} is no longer in the fleet", mark)),
+            },
+        }
+    }
+
+    fn fleet_snapshot(&self, name: &str) -> Option<NodeSnapshot> {
+        let node = self.fleet.iter().find(|node| node.name == name)?;
+        Some(NodeSnapshot::from_node(node, self.node_alert_titles(name)))
+    }
+
+    /// `c` on the Dashboard: this node now against the snapshot from
+    /// `compare.baseline_mins` ago
+    pub fn open_history_compare(&mut self) {
+        if !self.snapshots.is_enabled() {
+            self.push_message(MessageLevel::Info, "Snapshots are off (compare.snapshot_interval_mins = 0)");
+            return;
+        }
+        let now = self.local_snapshot();
+        let Some(past) = self.snapshots.at(now.taken_at - self.compare_baseline).cloned() else {
+            self.push_message(MessageLevel::Info, "No snapshots kept yet; the first is taken with the next metrics update");
+            return;
+        };
+        self.compare = Some(CompareView { kind: CompareKind::History, left: past, right: now });
+    }
+
+    /// Move the earlier side of a history comparison one snapshot back or forward
+    pub fn step_compare(&mut self, earlier: bool) {
+        if let Some(CompareView { kind: CompareKind::History, ref mut left, .. }) = self.compare {
+            if let Some(snapshot) = self.snapshots.step(left.taken_at, earlier) {
+                *left = snapshot.clone();
+            }
+        }
+    }
+
+    pub fn close_compare(&mut self) {
+        self.compare = None;
+    }
+
+    // VM detail popup
+    pub async fn open_vm_detail(&mut self) {
+        let Some(resource) = self
+            .selected_resource()
+            .filter(|r| r.kind == ResourceKind::VirtualMachine)
+            .cloned()
+        else {
+            return;
+        };
+        self.open_vm_detail_for(resource).await;
+    }
+
+    async fn open_vm_detail_for(&mut self, resource: K8sResource) {
+        let namespace = resource.namespace.as_deref().unwrap_or("default");
+        self.k8s_collector.capture_console(namespace, &resource.name);
+        let console = self.k8s_collector.console_capture(namespace, &resource.name);
+        self.vm_detail = Some(VmDetail {
+            resource,
+            guest: None,
+            error: None,
+            console,
+            show_console: false,
+            console_scroll: 0,
+            endpoints: Vec::new(),
+            vnc_forward: None,
+        });
+        self.poll_vnc_forward();
+        self.update_vm_detail().await;
+    }
+
+    pub fn close_vm_detail(&mut self) {
+        self.vm_detail = None;
+    }
+
+    pub fn toggle_vm_console(&mut self) {
+        if let Some(detail) = self.vm_detail.as_mut() {
+            detail.show_console = !detail.show_console;
+            detail.console_scroll = 0;
+        }
+    }
+
+    /// Scroll the console view back (up) or toward the newest output
+    pub fn scroll_vm_console(&mut self, up: bool, lines: usize) {
+        let Some(detail) = self.vm_detail.as_mut().filter(|d| d.show_console) else {
+            return;
+        };
+        let len = detail.console.as_ref().map_or(0, |c| c.lines.len());
+        detail.console_scroll = if up {
+            (detail.console_scroll + lines).min(len.saturating_sub(1))
+        } else {
+            detail.console_scroll.saturating_sub(lines)
+        };
+    }
+
+    /// Pick up new console output for the VM detail popup; true when it changed
+    pub fn poll_vm_console(&mut self) -> bool {
+        let Some(detail) = self.vm_detail.as_mut() else {
+            return false;
+        };
+        let namespace = detail.resource.namespace.as_deref().unwrap_or("default");
+        let console = self.k8s_collector.console_capture(namespace, &detail.resource.name);
+        let version = |c: &Option<ConsoleCapture>| c.as_ref().map(|c| c.version);
+        if version(&console) == version(&detail.console) {
+            return false;
+        }
+        // Keep the same lines in view while scrolled back
+        if let (Some(old), Some(new)) = (detail.console.as_ref(), console.as_ref()) {
+            if detail.console_scroll > 0 {
+                let added = new.lines.len().saturating_sub(old.lines.len());
+                detail.console_scroll = (detail.console_scroll + added).min(new.lines.len().saturating_sub(1));
+            }
+        }
+        detail.console = console;
+        true
+    }
+
+    /// Start a `virtctl vnc` port-forward for the VM in the detail popup,
+    /// or stop the running one
+    pub fn toggle_vnc_forward(&mut self) {
+        if self.read_only || self.lacks(&[Permission::VmVnc]) {
+            return;
+        }
+        let Some(resource) = self.vm_detail.as_ref().map(|d| d.resource.clone()) else {
+            return;
+        };
+        let namespace = resource.namespace.as_deref().unwrap_or("default");
+        let running = self
+            .k8s_collector
+            .vnc_forward(namespace, &resource.name)
+            .is_some_and(|forward| forward.running);
+        if running {
+            self.k8s_collector.stop_vnc_forward(namespace, &resource.name);
+            self.push_message(MessageLevel::Info, format!("Stopped VNC forward for {}", resource.name));
+        } else {
+            match self.k8s_collector.start_vnc_forward(namespace, &resource.name) {
+                Ok(address) => self.push_message(
+                    MessageLevel::Info,
+                    format!("VNC for {} on {}", resource.name, address),
+                ),
+                Err(e) => self.push_message(MessageLevel::Error, e),
+            }
+        }
+        self.poll_vnc_forward();
+    }
+
+    /// Pick up the detail popup VM's forward starting or virtctl exiting;
+    /// true when it changed
+    pub fn poll_vnc_forward(&mut self) -> bool {
+        let Some(detail) = self.vm_detail.as_mut() else {
+            return false;
+        };
+        let namespace = detail.resource.namespace.as_deref().unwrap_or("default");
+        let forward = self.k8s_collector.vnc_forward(namespace, &detail.resource.name);
+        let state = |f: &Option<VncForward>| f.as_ref().map(|f| (f.address, f.running));
+        if state(&forward) == state(&detail.vnc_forward) {
+            return false;
+        }
+        detail.vnc_forward = forward;
+        true
+    }
+
+    pub fn guest_fs_warning_threshold(&self) -> f64 {
+        self.guest_fs_thresholds.0
+    }
+
+    /// Re-query the guest agent for the VM shown in the detail popup and
+    /// evaluate guest filesystem alerts
+    async fn update_vm_detail(&mut self) {
+        let Some(resource) = self.vm_detail.as_ref().map(|d| d.resource.clone()) else {
+            return;
+        };
+        let namespace = resource.namespace.as_deref().unwrap_or("default");
+
+        let result = timed(
+            self.collector_timeout,
+            self.k8s_collector.collect_guest_info(namespace, &resource.name),
+        )
+        .await;
+        let guest = self.record_timing("guest-agent", result);
+
+        let endpoints = timed(
+            self.collector_timeout,
+            self.k8s_collector.collect_console_endpoints(namespace, &resource.name),
+        )
+        .await;
+        let endpoints = self.record_timing("console endpoints", endpoints);
+
+        if let (Some(guest), true) = (guest.as_ref(), self.alerts_enabled) {
+            let (warning, critical) = self.guest_fs_thresholds;
+            self.alert_manager.evaluate_rule(&GuestFilesystemRule::new(
+                format!("{}/{}", namespace, resource.name),
+                guest.filesystems.clone(),
+                warning,
+                critical,
+            ));
+        }
+
+        let error = match guest {
+            Some(_) => None,
+            None => self
+                .collector_timings
+                .iter()
+                .find(|t| t.name == "guest-agent")
+                .and_then(|t| t.last_error.clone()),
+        };
+
+        if let Some(detail) = self.vm_detail.as_mut() {
+            if guest.is_some() {
+                detail.guest = guest;
+            }
+            if let Some(endpoints) = endpoints {
+                detail.endpoints = endpoints;
+            }
+            detail.error = error;
+        }
+    }
+}
+
+/// The header widgets to draw; command widgets without a command are
+/// reported by config validation and left out
+fn header_widgets(config: &[HeaderWidgetConfig]) -> Vec<HeaderWidgetConfig> {
+    config
+        .iter()
+        .filter(|widget| widget.enabled && (widget.widget != HeaderWidget::Command || widget.command.is_some()))
+        .cloned()
+        .collect()
+}
+
+/// What the header's command widgets run, in widget order
+fn header_commands(config: &[HeaderWidgetConfig]) -> Vec<CustomCommandConfig> {
+    header_widgets(config)
+        .into_iter()
+        .filter_map(|widget| {
+            let command = widget.command.filter(|_| widget.widget == HeaderWidget::Command)?;
+            Some(CustomCommandConfig {
+                name: widget.label.unwrap_or_else(|| command.clone()),
+                command,
+                interval_secs: widget.interval_secs.max(1),
+                timeout_secs: widget.timeout_secs.max(1),
+                parser: CommandParser::Raw,
+            })
+        })
+        .collect()
+}
+
+/// "namespace/name" of a VirtualMachine, the key VMs are marked by
+fn vm_key(resource: &K8sResource) -> Option<String> {
+    if resource.kind != ResourceKind::VirtualMachine {
+        return None;
+    }
+    Some(format!("{}/{}", resource.namespace.as_deref()?, resource.name))
+}
+
+/// etcd monitoring can be turned off, leaving nothing to show
+async fn collect_etcd(collector: Option<&mut EtcdCollector>) -> Result<Option<EtcdStatus>> {
+    match collector {
+        Some(collector) => collector.collect().await,
+        None => Ok(None),
+    }
+}
+
+/// Prometheus is optional, so a missing collector yields no update
+async fn collect_prometheus(collector: Option<&mut PrometheusCollector>) -> Result<Option<PrometheusMetrics>> {
+    match collector {
+        Some(collector) => collector.collect().await.map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Remote agents are optional, so a missing collector yields no update
+async fn collect_agents(collector: Option<&mut AgentCollector>) -> Result<Option<Vec<AgentStatus>>> {
+    match collector {
+        Some(collector) => collector.collect().await.map(Some),
+        None => Ok(None),
+    }
+}
+
+/// DNS probing is optional, so a missing collector yields no update
+async fn collect_dns(collector: Option<&mut DnsCollector>, k8s: &KubernetesCollector) -> Result<Option<Vec<DnsResolverStatus>>> {
+    match collector {
+        Some(collector) => collector.collect(k8s).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Tenant rollups need a tenant label, so without one there is no update
+async fn collect_tenants(collector: &KubernetesCollector, label: Option<&str>) -> Result<Option<Vec<TenantUsage>>> {
+    match label {
+        Some(label) => collector.collect_tenant_usage(label).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Without a node name for this host there is no kubelet to ask
+async fn collect_kubelet(collector: &KubernetesCollector, node: Option<&str>) -> Result<Option<KubeletStatus>> {
+    match node {
+        Some(node) => collector.collect_kubelet_status(node).await,
+        None => Ok(None),
+    }
+}
+
+/// Route checks are optional, so a missing collector yields no update
+async fn collect_routes(collector: Option<&mut RouteCollector>) -> Result<Option<RouteStatus>> {
+    match collector {
+        Some(collector) => collector.collect().await.map(Some),
+        None => Ok(None),
     }
 }
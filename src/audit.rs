@@ -0,0 +1,59 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::config::Config;
+
+/// One change made to the host or cluster
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEntry {
+    /// RFC 3339 local time
+    pub time: String,
+    /// Who made the change, e.g. "auto-action"
+    pub actor: String,
+    /// What was run, e.g. "systemctl restart k3s"
+    pub action: String,
+    /// Why, e.g. the alert that triggered it
+    pub reason: String,
+    /// "ok", "skipped: ..." or "failed: ..."
+    pub outcome: String,
+}
+
+impl AuditEntry {
+    pub fn now(actor: &str, action: String, reason: String, outcome: String) -> Self {
+        Self {
+            time: chrono::Local::now().to_rfc3339(),
+            actor: actor.to_string(),
+            action,
+            reason,
+            outcome,
+        }
+    }
+}
+
+/// Append-only record of changes, one JSON object per line, in
+/// audit.log in the state directory
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn in_state_dir() -> Self {
+        Self { path: Config::state_dir().join("audit.log") }
+    }
+
+    pub fn record(&self, entry: &AuditEntry) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open audit log {:?}", self.path))?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)
+            .with_context(|| format!("Failed to write audit log {:?}", self.path))
+    }
+}
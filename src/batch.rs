@@ -0,0 +1,96 @@
+use crate::collectors::KubernetesCollector;
+use crate::types::VmAction;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Semaphore};
+
+/// VM operations in flight at once; enough to drain a host quickly without
+/// a burst of migrations all landing on the API server together
+const BATCH_CONCURRENCY: usize = 8;
+
+/// Popup choosing what to do with the marked VMs. `label` holds the
+/// "key=value" being typed once Add label was picked.
+#[derive(Debug, Clone, Default)]
+pub struct BatchPrompt {
+    pub label: Option<String>,
+}
+
+/// A batch action running against the marked VMs, with each VM's outcome
+/// as it completes
+pub struct BatchRun {
+    pub action: VmAction,
+    /// (namespace/name, None while pending, Some(Err(message)) on failure)
+    pub results: Vec<(String, Option<Result<(), String>>)>,
+    /// Progress popup dismissed while VMs are still pending
+    pub hidden: bool,
+    rx: mpsc::UnboundedReceiver<(usize, Result<(), String>)>,
+}
+
+impl BatchRun {
+    /// Spawn the action for every VM, at most `BATCH_CONCURRENCY` at a time
+    pub fn start(k8s: &KubernetesCollector, action: VmAction, vms: Vec<String>) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let permits = Arc::new(Semaphore::new(BATCH_CONCURRENCY));
+
+        for (i, vm) in vms.iter().enumerate() {
+            let (k8s, action, tx, permits) = (k8s.clone(), action.clone(), tx.clone(), permits.clone());
+            let vm = vm.clone();
+            tokio::spawn(async move {
+                let _permit = permits.acquire().await;
+                let result = match vm.split_once('/') {
+                    Some((namespace, name)) => k8s
+                        .run_vm_action(&action, namespace, name)
+                        .await
+                        .map(|_| ())
+                        .map_err(|e| format!("{:#}", e)),
+                    None => Err("not a namespaced VM".to_string()),
+                };
+                let _ = tx.send((i, result));
+            });
+        }
+
+        Self {
+            action,
+            results: vms.into_iter().map(|vm| (vm, None)).collect(),
+            hidden: false,
+            rx,
+        }
+    }
+
+    /// Take in finished VMs, returning whether any arrived
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok((i, result)) = self.rx.try_recv() {
+            if let Some(entry) = self.results.get_mut(i) {
+                entry.1 = Some(result);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    pub fn done(&self) -> usize {
+        self.results.iter().filter(|(_, r)| r.is_some()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.results.iter().filter(|(_, r)| matches!(r, Some(Err(_)))).count()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.done() == self.results.len()
+    }
+
+    /// "Stop: 38 succeeded, 2 failed"
+    pub fn summary(&self) -> String {
+        let failed = self.failed();
+        let mut summary = format!("{}: {} succeeded", self.action.label(), self.done() - failed);
+        if failed > 0 {
+            summary.push_str(&format!(", {} failed", failed));
+        }
+        let pending = self.results.len() - self.done();
+        if pending > 0 {
+            summary.push_str(&format!(", {} pending", pending));
+        }
+        summary
+    }
+}
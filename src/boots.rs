@@ -0,0 +1,157 @@
+use anyhow::{Context, Result};
+use chrono::{Duration, Local};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::types::{BootRecord, SlowBoot};
+
+/// Boots kept on disk, oldest dropped first
+const MAX_RECORDS: usize = 1000;
+
+/// Earlier boots of a VM its latest one is compared against
+const BASELINE_BOOTS: usize = 10;
+
+/// Earlier boots a VM needs before it has a baseline
+const MIN_BASELINE_BOOTS: usize = 3;
+
+/// How long a slow boot that did finish keeps its alert
+const SLOW_BOOT_ALERT_MINS: i64 = 60;
+
+/// Hours of boots the slowest are picked from for the Fleet screen
+const RECENT_HOURS: i64 = 24;
+
+/// VM starts seen over time. A VMI goes away when its VM stops, so boots
+/// are kept here (and on disk) to judge each new start against the same
+/// VM's earlier ones.
+pub struct BootHistory {
+    /// Boots that reached Running, oldest first
+    records: Vec<BootRecord>,
+    /// VMIs not Running yet as of the last collection
+    booting: Vec<BootRecord>,
+    path: PathBuf,
+}
+
+impl BootHistory {
+    /// Load persisted history, starting fresh if none exists or the file is unreadable
+    pub fn load(path: PathBuf) -> Self {
+        let records = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring corrupt boot history {:?}: {}", path, e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self { records, booting: Vec::new(), path }
+    }
+
+    /// Take in the VMIs currently in the cluster. A boot already recorded
+    /// picks up its guest agent connecting later.
+    pub fn record(&mut self, boots: Vec<BootRecord>) {
+        let mut changed = false;
+        let mut booting = Vec::new();
+        for boot in boots {
+            if boot.running.is_none() {
+                booting.push(boot);
+                continue;
+            }
+            match self.records.iter_mut().find(|r| r.uid == boot.uid) {
+                Some(record) => {
+                    if record.agent_ready.is_none() && boot.agent_ready.is_some() {
+                        record.agent_ready = boot.agent_ready;
+                        changed = true;
+                    }
+                }
+                None => {
+                    self.records.push(boot);
+                    changed = true;
+                }
+            }
+        }
+        self.booting = booting;
+
+        if changed {
+            self.records.sort_by_key(|r| r.requested);
+            let excess = self.records.len().saturating_sub(MAX_RECORDS);
+            self.records.drain(..excess);
+            if let Err(e) = self.save() {
+                tracing::warn!("Failed to persist boot history: {:#}", e);
+            }
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create boot history directory: {:?}", parent))?;
+        }
+        let contents = serde_json::to_string(&self.records).context("Failed to serialize boot history")?;
+        fs::write(&self.path, contents).with_context(|| format!("Failed to write boot history: {:?}", self.path))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty() && self.booting.is_empty()
+    }
+
+    /// VMs still starting, longest waiting first, then the boots of the
+    /// last day slowest to Running first
+    pub fn slowest_recent(&self) -> Vec<&BootRecord> {
+        let since = Local::now() - Duration::hours(RECENT_HOURS);
+        let mut booting: Vec<&BootRecord> = self.booting.iter().collect();
+        booting.sort_by_key(|b| b.requested);
+        let mut finished: Vec<&BootRecord> = self.records.iter().filter(|r| r.requested >= since).collect();
+        finished.sort_by_key(|r| std::cmp::Reverse(r.to_running()));
+        booting.into_iter().chain(finished).collect()
+    }
+
+    /// Median time to Running of the VM's last boots before `before`, and
+    /// how many there were; None until it has booted a few times
+    pub fn baseline(&self, vm: &str, before: chrono::DateTime<Local>) -> Option<(Duration, usize)> {
+        let mut durations: Vec<Duration> = self
+            .records
+            .iter()
+            .rev()
+            .filter(|r| r.vm == vm && r.requested < before)
+            .filter_map(|r| r.to_running())
+            .take(BASELINE_BOOTS)
+            .collect();
+        if durations.len() < MIN_BASELINE_BOOTS {
+            return None;
+        }
+        durations.sort();
+        Some((durations[durations.len() / 2], durations.len()))
+    }
+
+    /// VMs starting, or started within the last hour, more than `factor`
+    /// times and at least `min_extra` slower than their baseline
+    pub fn slow_boots(&self, factor: f64, min_extra: Duration) -> Vec<SlowBoot> {
+        let now = Local::now();
+        let slow = |boot: &BootRecord, took: Duration, still_booting: bool| {
+            let (baseline, boots) = self.baseline(&boot.vm, boot.requested)?;
+            let too_slow = took.num_milliseconds() as f64 > baseline.num_milliseconds() as f64 * factor
+                && took - baseline >= min_extra;
+            too_slow.then(|| SlowBoot {
+                vm: boot.vm.clone(),
+                node: boot.node.clone(),
+                took,
+                still_booting,
+                baseline,
+                boots,
+            })
+        };
+
+        let mut found: Vec<SlowBoot> = self.booting.iter().filter_map(|boot| slow(boot, now - boot.requested, true)).collect();
+        // Only each VM's latest boot counts; an earlier slow one was superseded
+        let mut seen: HashSet<&str> = self.booting.iter().map(|b| b.vm.as_str()).collect();
+        let since = now - Duration::minutes(SLOW_BOOT_ALERT_MINS);
+        for record in self.records.iter().rev() {
+            if !seen.insert(record.vm.as_str()) || record.running.is_none_or(|at| at < since) {
+                continue;
+            }
+            if let Some(boot) = record.to_running().and_then(|took| slow(record, took, false)) {
+                found.push(boot);
+            }
+        }
+        found
+    }
+}
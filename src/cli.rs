@@ -0,0 +1,141 @@
+use anyhow::Result;
+
+/// One-shot collections for scripts, run instead of the TUI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subcommand {
+    /// `alerts list`
+    AlertsList,
+    /// `metrics snapshot`
+    MetricsSnapshot,
+    /// `vms list`
+    VmsList,
+}
+
+impl Subcommand {
+    fn parse(noun: &str, verb: Option<&str>) -> Result<Self> {
+        match (noun, verb) {
+            ("alerts", Some("list")) => Ok(Self::AlertsList),
+            ("metrics", Some("snapshot")) => Ok(Self::MetricsSnapshot),
+            ("vms", Some("list")) => Ok(Self::VmsList),
+            _ => anyhow::bail!("Unknown command: {} {} (see --help)", noun, verb.unwrap_or_default()),
+        }
+    }
+}
+
+/// Command-line options
+#[derive(Debug, Clone, Default)]
+pub struct CliArgs {
+    /// ASCII-only, colorless accessibility mode
+    pub ascii: bool,
+
+    /// Disable all mutating actions
+    pub read_only: bool,
+
+    /// Start in local mode, for when the control plane is known to be down
+    pub local: bool,
+
+    /// Serve this host's collector data to central consoles instead of
+    /// running the TUI
+    pub agent: bool,
+
+    /// Address the agent listens on, over `agent.listen`
+    pub listen: Option<String>,
+
+    /// Validate the config file, print the problems and exit
+    pub check_config: bool,
+
+    /// Print the merged config with where each value came from and exit
+    pub print_config: bool,
+
+    /// Print the NixOS options for the config and exit
+    pub generate_nixos_module: bool,
+
+    /// Print a one-line summary for status bars and exit
+    pub status_line: bool,
+
+    /// Don't offer the setup wizard when no config file exists
+    pub skip_setup: bool,
+
+    /// Alert profile to start with, over `alerts.profile`
+    pub alert_profile: Option<String>,
+
+    /// Write the effective alert settings as this profile and exit
+    pub export_alert_profile: Option<String>,
+
+    /// Copy this profile file into the profile directory and exit
+    pub import_alert_profile: Option<String>,
+
+    /// Print one collection for scripts and exit
+    pub subcommand: Option<Subcommand>,
+
+    /// Print the subcommand's output as JSON
+    pub json: bool,
+}
+
+impl CliArgs {
+    pub fn parse() -> Result<Self> {
+        let mut args = Self::default();
+
+        let mut argv = std::env::args().skip(1);
+        while let Some(arg) = argv.next() {
+            let mut value = || argv.next().ok_or_else(|| anyhow::anyhow!("{} needs a value (see --help)", arg));
+            match arg.as_str() {
+                "--ascii" => args.ascii = true,
+                "--read-only" => args.read_only = true,
+                "--local" => args.local = true,
+                "--agent" => args.agent = true,
+                "--listen" => args.listen = Some(value()?),
+                "--check-config" => args.check_config = true,
+                "--print-config" => args.print_config = true,
+                "--status-line" => args.status_line = true,
+                "--generate-nixos-module" => args.generate_nixos_module = true,
+                "--skip-setup" => args.skip_setup = true,
+                "--alert-profile" => args.alert_profile = Some(value()?),
+                "--export-alert-profile" => args.export_alert_profile = Some(value()?),
+                "--import-alert-profile" => args.import_alert_profile = Some(value()?),
+                "--json" => args.json = true,
+                "-h" | "--help" => {
+                    print_help();
+                    std::process::exit(0);
+                }
+                noun if args.subcommand.is_none() && !noun.starts_with('-') => {
+                    let verb = argv.next();
+                    args.subcommand = Some(Subcommand::parse(noun, verb.as_deref())?);
+                }
+                other => anyhow::bail!("Unknown argument: {} (see --help)", other),
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+fn print_help() {
+    println!(
+        "hypervisor-tui {}\n\n\
+         USAGE:\n    hypervisor-tui [OPTIONS]\n    hypervisor-tui <COMMAND> [--json]\n\n\
+         COMMANDS (one collection with the TUI's collectors, printed for scripts):\n\
+         \x20   alerts list       Active alerts; exits 2 when any is Critical or Error\n\
+         \x20   metrics snapshot  Host CPU, memory, disk, load and filesystems, and cluster totals\n\
+         \x20   vms list          KubeVirt VMs with status and CPU/memory use against their requests\n\n\
+         OPTIONS:\n\
+         \x20   --ascii         ASCII-only, no-color accessibility mode (serial consoles, screen readers)\n\
+         \x20   --read-only     Disable all mutating actions (NOC displays, shared accounts)\n\
+         \x20   --local         Start in local mode: host-side VM view and emergency actions while the API is down\n\
+         \x20   --agent         Serve this host's metrics, qemu processes and alerts to central consoles over\n\
+         \x20                   mutual TLS instead of running the TUI (see [agent] in the config)\n\
+         \x20   --listen ADDR   Address for --agent to listen on, over agent.listen\n\
+         \x20   --check-config  Validate the config files and exit (non-zero on problems)\n\
+         \x20   --print-config  Print the effective config merged from /etc, ~/.config, the working directory\n\
+         \x20                   and these flags, noting where each value came from, and exit\n\
+         \x20   --generate-nixos-module  Print NixOS options for every config key (services.hypervisor-tui.settings)\n\
+         \x20   --status-line   Print a colorless one-line summary (cpu, mem, alerts, vms, nodes) and exit\n\
+         \x20   --skip-setup    Run on defaults without the first-run setup wizard when no config exists\n\
+         \x20   --alert-profile NAME         Start with the named alert profile instead of alerts.profile\n\
+         \x20   --export-alert-profile NAME  Save the effective [alerts] settings as a profile and exit\n\
+         \x20   --import-alert-profile FILE  Copy a shared profile file into the profile directory and exit\n\
+         \x20   --json          Print a command's output as JSON\n\
+         \x20   -h, --help      Print this help",
+        env!("CARGO_PKG_VERSION")
+    );
+}
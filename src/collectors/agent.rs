@@ -0,0 +1,132 @@
+use anyhow::{bail, Context, Result};
+use futures::future::join_all;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::{Duration, Instant};
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::ClientConfig;
+use tokio_rustls::TlsConnector;
+
+use crate::agent::{load_certs, load_key, load_roots, tls_provider};
+use crate::config::RemoteAgentsConfig;
+use crate::types::{AgentSnapshot, AgentStatus, AGENT_PROTOCOL_VERSION};
+
+/// Polls take at most this long each, well inside the collector timeout
+const REQUEST_TIMEOUT_SECS: u64 = 5;
+
+/// Upper bound on a snapshot; a host with hundreds of VMs stays far below it
+const MAX_RESPONSE: usize = 8 * 1024 * 1024;
+
+/// Snapshots from hosts running `--agent`, fetched over mutual TLS with
+/// the client certificate in `[remote_agents]`. Agents are polled
+/// concurrently at most every `interval_secs`; a failed poll keeps the
+/// agent's last snapshot and counts toward its failure streak.
+pub struct AgentCollector {
+    connector: TlsConnector,
+    interval: Duration,
+    polled_at: Option<Instant>,
+    latest: Vec<AgentStatus>,
+}
+
+impl AgentCollector {
+    pub fn new(config: &RemoteAgentsConfig) -> Result<Self> {
+        let (Some(cert_file), Some(key_file), Some(ca_file)) = (&config.cert_file, &config.key_file, &config.ca_file)
+        else {
+            bail!("remote_agents.cert_file, remote_agents.key_file and remote_agents.ca_file are all required");
+        };
+        let tls = ClientConfig::builder_with_provider(tls_provider())
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(load_roots(ca_file)?)
+            .with_client_auth_cert(load_certs(cert_file)?, load_key(key_file)?)
+            .context("Client certificate and key don't match")?;
+        Ok(Self {
+            connector: TlsConnector::from(Arc::new(tls)),
+            interval: Duration::from_secs(config.interval_secs.max(1)),
+            polled_at: None,
+            latest: config
+                .endpoints
+                .iter()
+                .map(|endpoint| AgentStatus {
+                    endpoint: endpoint.clone(),
+                    snapshot: None,
+                    error: None,
+                    consecutive_failures: 0,
+                    round_trip_ms: None,
+                })
+                .collect(),
+        })
+    }
+
+    /// Every agent's status, polling again once `interval_secs` passed
+    pub async fn collect(&mut self) -> Result<Vec<AgentStatus>> {
+        if self.polled_at.is_some_and(|at| at.elapsed() < self.interval) {
+            return Ok(self.latest.clone());
+        }
+        self.polled_at = Some(Instant::now());
+
+        let connector = &self.connector;
+        let polls = join_all(self.latest.iter().map(|agent| async move {
+            let started = Instant::now();
+            let fetched = tokio::time::timeout(
+                Duration::from_secs(REQUEST_TIMEOUT_SECS),
+                fetch_snapshot(connector, &agent.endpoint),
+            )
+            .await
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("timed out after {}s", REQUEST_TIMEOUT_SECS)));
+            (fetched, started.elapsed())
+        }))
+        .await;
+
+        for (agent, (fetched, took)) in self.latest.iter_mut().zip(polls) {
+            match fetched {
+                Ok(snapshot) => {
+                    agent.snapshot = Some(snapshot);
+                    agent.error = None;
+                    agent.consecutive_failures = 0;
+                    agent.round_trip_ms = Some(took.as_secs_f64() * 1000.0);
+                }
+                Err(e) => {
+                    tracing::debug!("Polling agent {} failed: {:#}", agent.endpoint, e);
+                    agent.error = Some(format!("{:#}", e));
+                    agent.consecutive_failures += 1;
+                }
+            }
+        }
+        Ok(self.latest.clone())
+    }
+}
+
+/// `GET /v1/snapshot` from one agent
+async fn fetch_snapshot(connector: &TlsConnector, endpoint: &str) -> Result<AgentSnapshot> {
+    let host = endpoint
+        .rsplit_once(':')
+        .map(|(host, _)| host.trim_start_matches('[').trim_end_matches(']'))
+        .with_context(|| format!("{:?} is not host:port", endpoint))?;
+    let name = ServerName::try_from(host.to_string()).with_context(|| format!("{:?} is not a valid TLS name", host))?;
+    let stream = TcpStream::connect(endpoint).await.context("connect failed")?;
+    let mut stream = connector.connect(name, stream).await.context("TLS handshake failed")?;
+
+    let request = format!("GET /v1/snapshot HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", host);
+    stream.write_all(request.as_bytes()).await?;
+    let mut response = Vec::new();
+    (&mut stream).take(MAX_RESPONSE as u64 + 1).read_to_end(&mut response).await?;
+    if response.len() > MAX_RESPONSE {
+        bail!("response larger than {} bytes", MAX_RESPONSE);
+    }
+
+    let end = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .context("malformed HTTP response")?;
+    let head = String::from_utf8_lossy(&response[..end]);
+    let status = head.lines().next().unwrap_or_default();
+    if status.split_whitespace().nth(1) != Some("200") {
+        bail!("agent answered {}", status);
+    }
+    let snapshot: AgentSnapshot = serde_json::from_slice(&response[end + 4..]).context("invalid snapshot")?;
+    if snapshot.version != AGENT_PROTOCOL_VERSION {
+        bail!("agent speaks protocol {}, this console {}", snapshot.version, AGENT_PROTOCOL_VERSION);
+    }
+    Ok(snapshot)
+}
@@ -0,0 +1,323 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::CString;
+use std::io;
+use std::net::Ipv4Addr;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+use crate::types::{IpConflict, LeaseChurn};
+
+/// Largest frame read; ARP and DHCP fit well within a standard MTU
+const PACKET_SIZE: usize = 1600;
+/// Socket receive buffer, so broadcast bursts between reads aren't dropped
+const RECV_BUFFER: libc::c_int = 1 << 20;
+const DHCP_MAGIC: [u8; 4] = [99, 130, 83, 99];
+const DHCP_REQUEST: u8 = 3;
+const DHCP_ACK: u8 = 5;
+
+/// Keeps UDP datagrams to or from the DHCP ports (67/68) on a packet
+/// socket bound to IPv4, i.e. starting at the IP header; everything else is
+/// dropped in the kernel
+fn dhcp_filter() -> [libc::sock_filter; 10] {
+    let op = |code: u32, jt: u8, jf: u8, k: u32| libc::sock_filter { code: code as u16, jt, jf, k };
+    [
+        op(libc::BPF_LD | libc::BPF_B | libc::BPF_ABS, 0, 0, 9),
+        op(libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K, 0, 7, libc::IPPROTO_UDP as u32),
+        // Later fragments have no UDP header
+        op(libc::BPF_LD | libc::BPF_H | libc::BPF_ABS, 0, 0, 6),
+        op(libc::BPF_JMP | libc::BPF_JSET | libc::BPF_K, 5, 0, 0x1fff),
+        op(libc::BPF_LDX | libc::BPF_B | libc::BPF_MSH, 0, 0, 0),
+        op(libc::BPF_LD | libc::BPF_H | libc::BPF_IND, 0, 0, 2),
+        op(libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K, 1, 0, 67),
+        op(libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K, 0, 1, 68),
+        op(libc::BPF_RET | libc::BPF_K, 0, 0, PACKET_SIZE as u32),
+        op(libc::BPF_RET | libc::BPF_K, 0, 0, 0),
+    ]
+}
+
+/// DHCP transactions and the distinct addresses leased, for one MAC
+type Leases = (HashSet<u32>, Vec<Ipv4Addr>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClaimKind {
+    /// An ARP packet sent with this address as the sender
+    Arp,
+    /// A DHCPACK leasing the address
+    Lease,
+    /// A broadcast DHCPREQUEST for the address; only counted as churn
+    Request,
+}
+
+#[derive(Debug, Clone)]
+struct Claim {
+    bridge: usize,
+    ip: Ipv4Addr,
+    mac: [u8; 6],
+    kind: ClaimKind,
+    /// DHCP transaction, so a request and its ack count as one lease
+    xid: u32,
+    at: DateTime<Local>,
+}
+
+/// Listens to the ARP and DHCP traffic reaching configured VM bridges for
+/// addresses claimed by more than one MAC (typically a VM cloned from a
+/// template without regenerating its identity) and MACs renewing leases
+/// over and over. Opening the packet sockets needs root (CAP_NET_RAW);
+/// without it, or for a bridge that doesn't exist, the collector stays idle.
+pub struct BridgeWatchCollector {
+    bridges: Vec<String>,
+    /// Bridge index and packet socket
+    sockets: Vec<(usize, OwnedFd)>,
+    opened: bool,
+    /// Claims within `window`, oldest first
+    claims: VecDeque<Claim>,
+    window: Duration,
+}
+
+impl BridgeWatchCollector {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            bridges: Vec::new(),
+            sockets: Vec::new(),
+            opened: false,
+            claims: VecDeque::new(),
+            window: Duration::minutes(10),
+        })
+    }
+
+    pub fn with_bridges(mut self, bridges: Vec<String>) -> Self {
+        self.bridges = bridges;
+        self
+    }
+
+    /// How long claims are kept for conflict and churn detection
+    pub fn with_window(mut self, minutes: u64) -> Self {
+        self.window = Duration::minutes(minutes as i64);
+        self
+    }
+
+    /// Read the packets received since the previous call
+    pub async fn collect(&mut self) -> Result<()> {
+        if !self.opened {
+            self.opened = true;
+            self.open();
+        }
+
+        let now = Local::now();
+        let mut buf = vec![0u8; PACKET_SIZE];
+        for (bridge, socket) in &self.sockets {
+            loop {
+                let n = unsafe { libc::recv(socket.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len(), 0) };
+                if n < 0 {
+                    let e = io::Error::last_os_error();
+                    if e.kind() == io::ErrorKind::WouldBlock {
+                        break;
+                    }
+                    return Err(e).with_context(|| format!("Failed to read from bridge {}", self.bridges[*bridge]));
+                }
+                let packet = &buf[..n as usize];
+                let claim = parse_arp(packet).or_else(|| parse_dhcp(packet));
+                if let Some((ip, mac, kind, xid)) = claim {
+                    self.claims.push_back(Claim { bridge: *bridge, ip, mac, kind, xid, at: now });
+                }
+            }
+        }
+
+        let cutoff = now - self.window;
+        while self.claims.front().is_some_and(|c| c.at < cutoff) {
+            self.claims.pop_front();
+        }
+        Ok(())
+    }
+
+    /// One ARP and one DHCP socket per bridge
+    fn open(&mut self) {
+        for (index, bridge) in self.bridges.iter().enumerate() {
+            let Ok(name) = CString::new(bridge.as_str()) else {
+                continue;
+            };
+            let ifindex = unsafe { libc::if_nametoindex(name.as_ptr()) };
+            if ifindex == 0 {
+                tracing::warn!("Bridge {} not found, not watching it for IP conflicts", bridge);
+                continue;
+            }
+            for (protocol, filter) in [(libc::ETH_P_ARP, None), (libc::ETH_P_IP, Some(dhcp_filter()))] {
+                match open_socket(ifindex, protocol, filter.as_ref().map(|f| &f[..])) {
+                    Ok(socket) => self.sockets.push((index, socket)),
+                    Err(e) => {
+                        tracing::warn!("Not watching {} for IP conflicts: {}", bridge, e);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Addresses claimed by more than one MAC, by bridge and address. A
+    /// single handover (a VM replaced, a virtual IP failing over) isn't a
+    /// conflict; it is once a MAC claims the address again after another.
+    pub fn conflicts(&self) -> Vec<IpConflict> {
+        let mut claims: HashMap<(usize, Ipv4Addr), Vec<&Claim>> = HashMap::new();
+        for claim in self.claims.iter().filter(|c| c.kind != ClaimKind::Request) {
+            claims.entry((claim.bridge, claim.ip)).or_default().push(claim);
+        }
+
+        let mut conflicts: Vec<IpConflict> = claims
+            .into_iter()
+            .filter_map(|((bridge, ip), claims)| {
+                // Runs of consecutive claims by the same MAC
+                let mut runs: Vec<[u8; 6]> = Vec::new();
+                for claim in &claims {
+                    if runs.last() != Some(&claim.mac) {
+                        runs.push(claim.mac);
+                    }
+                }
+                let mut macs: Vec<[u8; 6]> = Vec::new();
+                for mac in runs.iter().rev() {
+                    if !macs.contains(mac) {
+                        macs.insert(0, *mac);
+                    }
+                }
+                if runs.len() <= macs.len() {
+                    return None;
+                }
+                Some(IpConflict {
+                    ip,
+                    bridge: self.bridges[bridge].clone(),
+                    macs: macs.iter().map(format_mac).collect(),
+                    last_seen: claims.last()?.at,
+                })
+            })
+            .collect();
+        conflicts.sort_by(|a, b| a.bridge.cmp(&b.bridge).then(a.ip.cmp(&b.ip)));
+        conflicts
+    }
+
+    /// MACs with at least `threshold` DHCP leases within the window
+    pub fn churn(&self, threshold: u32) -> Vec<LeaseChurn> {
+        let mut leases: HashMap<(usize, [u8; 6]), Leases> = HashMap::new();
+        for claim in self.claims.iter().filter(|c| c.kind != ClaimKind::Arp) {
+            let (transactions, ips) = leases.entry((claim.bridge, claim.mac)).or_default();
+            transactions.insert(claim.xid);
+            if !ips.contains(&claim.ip) {
+                ips.push(claim.ip);
+            }
+        }
+
+        let mut churn: Vec<LeaseChurn> = leases
+            .into_iter()
+            .filter(|(_, (transactions, _))| transactions.len() as u32 >= threshold)
+            .map(|((bridge, mac), (transactions, ips))| LeaseChurn {
+                mac: format_mac(&mac),
+                bridge: self.bridges[bridge].clone(),
+                leases: transactions.len() as u32,
+                ips,
+            })
+            .collect();
+        churn.sort_by(|a, b| b.leases.cmp(&a.leases).then(a.mac.cmp(&b.mac)));
+        churn
+    }
+}
+
+/// A non-blocking packet socket receiving `protocol` frames from one
+/// interface, without their link-layer header
+fn open_socket(ifindex: u32, protocol: libc::c_int, filter: Option<&[libc::sock_filter]>) -> io::Result<OwnedFd> {
+    let check = |ret: libc::c_int| if ret < 0 { Err(io::Error::last_os_error()) } else { Ok(ret) };
+
+    // Protocol 0 receives nothing until bound, so no other interface's
+    // frames slip in before the bind below
+    let fd = check(unsafe { libc::socket(libc::AF_PACKET, libc::SOCK_DGRAM | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC, 0) })?;
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+    let set = |level: libc::c_int, name: libc::c_int, value: *const libc::c_void, len: usize| {
+        check(unsafe { libc::setsockopt(socket.as_raw_fd(), level, name, value, len as libc::socklen_t) })
+    };
+
+    set(
+        libc::SOL_SOCKET,
+        libc::SO_RCVBUF,
+        (&RECV_BUFFER as *const libc::c_int).cast(),
+        std::mem::size_of::<libc::c_int>(),
+    )?;
+    if let Some(filter) = filter {
+        let program = libc::sock_fprog { len: filter.len() as u16, filter: filter.as_ptr() as *mut _ };
+        set(
+            libc::SOL_SOCKET,
+            libc::SO_ATTACH_FILTER,
+            (&program as *const libc::sock_fprog).cast(),
+            std::mem::size_of::<libc::sock_fprog>(),
+        )?;
+    }
+
+    let mut addr: libc::sockaddr_ll = unsafe { std::mem::zeroed() };
+    addr.sll_family = libc::AF_PACKET as u16;
+    addr.sll_protocol = (protocol as u16).to_be();
+    addr.sll_ifindex = ifindex as i32;
+    check(unsafe {
+        libc::bind(
+            socket.as_raw_fd(),
+            (&addr as *const libc::sockaddr_ll).cast(),
+            std::mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+        )
+    })?;
+    Ok(socket)
+}
+
+/// The sender address and MAC of an Ethernet/IPv4 ARP packet. Probes,
+/// sent from 0.0.0.0 before an address is taken, claim nothing.
+fn parse_arp(packet: &[u8]) -> Option<(Ipv4Addr, [u8; 6], ClaimKind, u32)> {
+    if packet.len() < 28 || packet[..6] != [0, 1, 8, 0, 6, 4] {
+        return None;
+    }
+    let mac: [u8; 6] = packet[8..14].try_into().ok()?;
+    let ip = Ipv4Addr::new(packet[14], packet[15], packet[16], packet[17]);
+    (!ip.is_unspecified()).then_some((ip, mac, ClaimKind::Arp, 0))
+}
+
+/// The address and client MAC of a DHCPACK or DHCPREQUEST in an IPv4/UDP
+/// datagram
+fn parse_dhcp(packet: &[u8]) -> Option<(Ipv4Addr, [u8; 6], ClaimKind, u32)> {
+    if packet.first()? >> 4 != 4 {
+        return None;
+    }
+    let bootp = packet.get(usize::from(packet[0] & 0x0f) * 4 + 8..)?;
+    // Ethernet hardware addresses only
+    if bootp.len() < 240 || bootp[1..3] != [1, 6] || bootp[236..240] != DHCP_MAGIC {
+        return None;
+    }
+    let xid = u32::from_be_bytes(bootp[4..8].try_into().ok()?);
+    let ciaddr = Ipv4Addr::new(bootp[12], bootp[13], bootp[14], bootp[15]);
+    let yiaddr = Ipv4Addr::new(bootp[16], bootp[17], bootp[18], bootp[19]);
+    let mac: [u8; 6] = bootp[28..34].try_into().ok()?;
+
+    let mut message_type = None;
+    let mut requested = None;
+    let mut options = &bootp[240..];
+    while let [code, rest @ ..] = options {
+        match code {
+            0 => options = rest,
+            255 => break,
+            _ => {
+                let (&len, rest) = rest.split_first()?;
+                let value = rest.get(..usize::from(len))?;
+                match (code, value) {
+                    (53, [kind]) => message_type = Some(*kind),
+                    (50, [a, b, c, d]) => requested = Some(Ipv4Addr::new(*a, *b, *c, *d)),
+                    _ => {}
+                }
+                options = &rest[usize::from(len)..];
+            }
+        }
+    }
+
+    let (ip, kind) = match message_type? {
+        DHCP_ACK => (yiaddr, ClaimKind::Lease),
+        DHCP_REQUEST => (requested.unwrap_or(ciaddr), ClaimKind::Request),
+        _ => return None,
+    };
+    (!ip.is_unspecified()).then_some((ip, mac, kind, xid))
+}
+
+fn format_mac(mac: &[u8; 6]) -> String {
+    mac.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(":")
+}
@@ -0,0 +1,219 @@
+use anyhow::{bail, Context, Result};
+use crate::types::{CgroupKind, CgroupUsage};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Walks the unified (v2) cgroup hierarchy to attribute CPU and memory to
+/// systemd slices and kubepods pod/container cgroups
+pub struct CgroupCollector {
+    root: PathBuf,
+    use_mock: bool,
+    cpus: f64,
+    // cgroup path -> (usage_usec, sampled at)
+    previous: HashMap<String, (u64, Instant)>,
+}
+
+impl CgroupCollector {
+    pub fn new() -> Result<Self> {
+        let cpus = std::thread::available_parallelism()
+            .map(|n| n.get() as f64)
+            .unwrap_or(1.0);
+        Ok(Self {
+            root: PathBuf::from(CGROUP_ROOT),
+            use_mock: false,
+            cpus,
+            previous: HashMap::new(),
+        })
+    }
+
+    pub async fn collect(&mut self) -> Result<Vec<CgroupUsage>> {
+        match self.collect_real() {
+            Ok(usage) => Ok(usage),
+            Err(e) => {
+                if !self.use_mock {
+                    tracing::warn!("Failed to read cgroup v2 hierarchy, using mock data: {}", e);
+                    self.use_mock = true;
+                }
+                Ok(self.collect_mock())
+            }
+        }
+    }
+
+    fn collect_real(&mut self) -> Result<Vec<CgroupUsage>> {
+        if !self.root.join("cgroup.controllers").exists() {
+            bail!("{} is not a cgroup v2 mount", self.root.display());
+        }
+
+        let mut targets = Vec::new();
+        for entry in fs::read_dir(&self.root).context("Failed to read cgroup root")? {
+            let path = entry?.path();
+            let Some(name) = dir_name(&path) else { continue };
+
+            if name == "kubepods.slice" || name == "kubepods" {
+                // k3s uses the cgroupfs driver ("kubepods/burstable/pod<uid>")
+                // unless configured for systemd ("kubepods.slice/...-pod<uid>.slice")
+                targets.push((path.clone(), CgroupKind::Slice));
+                collect_pods(&path, &mut targets);
+            } else if name.ends_with(".slice") {
+                targets.push((path, CgroupKind::Slice));
+            }
+        }
+
+        let now = Instant::now();
+        let mut usage = Vec::with_capacity(targets.len());
+        for (path, kind) in targets {
+            let key = path.strip_prefix(&self.root).unwrap_or(&path).display().to_string();
+            let cpu_usec = read_cpu_usage_usec(&path).unwrap_or(0);
+            let memory_bytes = read_u64(&path.join("memory.current")).unwrap_or(0);
+
+            // First sample for a cgroup has no delta; report 0 until the next cycle
+            let cpu_percent = match self.previous.get(&key) {
+                Some(&(prev_usec, prev_at)) if cpu_usec >= prev_usec => {
+                    let elapsed = now.duration_since(prev_at).as_micros() as f64;
+                    if elapsed > 0.0 {
+                        (cpu_usec - prev_usec) as f64 / elapsed / self.cpus * 100.0
+                    } else {
+                        0.0
+                    }
+                }
+                _ => 0.0,
+            };
+            self.previous.insert(key.clone(), (cpu_usec, now));
+
+            usage.push(CgroupUsage {
+                name: display_name(&path, kind),
+                pod_uid: (kind == CgroupKind::Pod).then(|| pod_uid(&key)).flatten(),
+                path: key,
+                kind,
+                cpu_percent,
+                memory_bytes,
+            });
+        }
+
+        // Forget cgroups that have gone away (terminated pods)
+        self.previous.retain(|key, _| usage.iter().any(|u| &u.path == key));
+
+        Ok(usage)
+    }
+
+    fn collect_mock(&self) -> Vec<CgroupUsage> {
+        let gib = 1_073_741_824;
+        let entry = |path: &str, name: &str, kind, cpu_percent, memory_bytes| CgroupUsage {
+            pod_uid: (kind == CgroupKind::Pod).then(|| pod_uid(path)).flatten(),
+            path: path.to_string(),
+            name: name.to_string(),
+            kind,
+            cpu_percent,
+            memory_bytes,
+        };
+        vec![
+            entry("system.slice", "system.slice", CgroupKind::Slice, 6.2, 3 * gib),
+            entry("machine.slice", "machine.slice", CgroupKind::Slice, 31.5, 24 * gib),
+            entry("user.slice", "user.slice", CgroupKind::Slice, 0.4, gib / 4),
+            entry("kubepods", "kubepods", CgroupKind::Slice, 18.7, 9 * gib),
+            entry("kubepods/burstable/pod4f1c2a7e-93b1-4c0e-8d2a-1b6f0e4a9c11", "burstable/pod4f1c2a", CgroupKind::Pod, 12.3, 6 * gib),
+            entry("kubepods/burstable/pod4f1c2a7e-93b1-4c0e-8d2a-1b6f0e4a9c11/a81e", "a81e", CgroupKind::Container, 12.1, 6 * gib),
+            entry("kubepods/besteffort/pod9b07de", "besteffort/pod9b07de", CgroupKind::Pod, 2.8, gib),
+            entry("kubepods/besteffort/pod9b07de/3fd0", "3fd0", CgroupKind::Container, 2.8, gib),
+        ]
+    }
+}
+
+/// Find pod cgroups below the kubepods root (directly or under a QoS class)
+/// along with their container cgroups
+fn collect_pods(kubepods: &Path, targets: &mut Vec<(PathBuf, CgroupKind)>) {
+    for dir in subdirs(kubepods) {
+        let Some(name) = dir_name(&dir) else { continue };
+        if is_pod_cgroup(name) {
+            targets.push((dir.clone(), CgroupKind::Pod));
+            for container in subdirs(&dir) {
+                targets.push((container, CgroupKind::Container));
+            }
+        } else {
+            // QoS class: burstable / besteffort
+            collect_pods(&dir, targets);
+        }
+    }
+}
+
+fn is_pod_cgroup(name: &str) -> bool {
+    name.starts_with("pod") || name.contains("-pod")
+}
+
+fn subdirs(path: &Path) -> Vec<PathBuf> {
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn dir_name(path: &Path) -> Option<&str> {
+    path.file_name().and_then(|n| n.to_str())
+}
+
+/// Short human label: QoS/pod for pods, truncated ID for containers
+fn display_name(path: &Path, kind: CgroupKind) -> String {
+    let name = dir_name(path).unwrap_or_default();
+    match kind {
+        CgroupKind::Slice => name.to_string(),
+        CgroupKind::Pod => {
+            let pod = name
+                .trim_end_matches(".slice")
+                .rsplit('-')
+                .next()
+                .unwrap_or(name)
+                .replace('_', "-");
+            let qos = path
+                .parent()
+                .and_then(dir_name)
+                .map(|q| q.trim_end_matches(".slice").trim_start_matches("kubepods-"))
+                .filter(|q| *q == "burstable" || *q == "besteffort");
+            match qos {
+                Some(qos) => format!("{}/{}", qos, pod),
+                None => pod,
+            }
+        }
+        CgroupKind::Container => {
+            let id = name
+                .trim_end_matches(".scope")
+                .rsplit('-')
+                .next()
+                .unwrap_or(name);
+            id.chars().take(12).collect()
+        }
+    }
+}
+
+fn read_cpu_usage_usec(path: &Path) -> Option<u64> {
+    let stat = fs::read_to_string(path.join("cpu.stat")).ok()?;
+    stat.lines()
+        .find_map(|line| line.strip_prefix("usage_usec "))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Pod UID from a kubepods cgroup path, for both the systemd
+/// ("kubepods-burstable-pod<uid_with_underscores>.slice") and cgroupfs
+/// ("kubepods/burstable/pod<uid>") drivers
+pub(super) fn pod_uid(cgroup: &str) -> Option<String> {
+    cgroup.split('/').find_map(|segment| {
+        let segment = segment.trim_end_matches(".slice");
+        let uid = match segment.rfind("-pod") {
+            Some(i) => &segment[i + 4..],
+            None => segment.strip_prefix("pod")?,
+        };
+        (!uid.is_empty()).then(|| uid.replace('_', "-"))
+    })
+}
@@ -0,0 +1,207 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde_json::Value;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::process::Command;
+
+use crate::types::CoreDump;
+
+/// Most dumps kept for display, newest first
+const MAX_DUMPS: usize = 100;
+/// Header lines of an apport report read before giving up on a field; the
+/// base64 core follows them
+const APPORT_HEADER_LINES: usize = 200;
+
+/// Crashes recorded by systemd-coredump (through `coredumpctl`) and files
+/// left in crash directories: apport reports, plain core files and kdump
+/// vmcores. Dumps of the watched processes are marked critical.
+pub struct CrashCollector {
+    /// Binary name prefixes: "qemu" matches qemu-system-x86_64 and qemu-kvm
+    processes: Vec<String>,
+    crash_dirs: Vec<PathBuf>,
+    history_days: u64,
+    /// Set once coredumpctl turned out missing or too old for --json
+    coredumpctl_unusable: bool,
+}
+
+impl CrashCollector {
+    pub fn new() -> Self {
+        Self {
+            processes: Vec::new(),
+            crash_dirs: Vec::new(),
+            history_days: 7,
+            coredumpctl_unusable: false,
+        }
+    }
+
+    pub fn with_processes(mut self, processes: Vec<String>) -> Self {
+        self.processes = processes;
+        self
+    }
+
+    pub fn with_crash_dirs(mut self, dirs: &[String]) -> Self {
+        self.crash_dirs = dirs.iter().map(PathBuf::from).collect();
+        self
+    }
+
+    /// How far back dumps are listed
+    pub fn with_history_days(mut self, days: u64) -> Self {
+        self.history_days = days;
+        self
+    }
+
+    /// Dumps from the last `history_days`, newest first
+    pub async fn collect(&mut self) -> Result<Vec<CoreDump>> {
+        let cutoff = Local::now() - chrono::Duration::days(self.history_days as i64);
+        let mut dumps = Vec::new();
+        if !self.coredumpctl_unusable {
+            match self.coredumpctl().await {
+                Ok(found) => dumps.extend(found),
+                Err(e) => {
+                    tracing::warn!("Not reading systemd-coredump crashes: {:#}", e);
+                    self.coredumpctl_unusable = true;
+                }
+            }
+        }
+        for dir in &self.crash_dirs {
+            dumps.extend(scan_crash_dir(dir, cutoff));
+        }
+
+        for dump in &mut dumps {
+            let name = dump.name().to_string();
+            dump.critical = self.processes.iter().any(|process| name.starts_with(process.as_str()));
+        }
+        dumps.retain(|dump| dump.at >= cutoff);
+        dumps.sort_by_key(|dump| std::cmp::Reverse(dump.at));
+        dumps.truncate(MAX_DUMPS);
+        Ok(dumps)
+    }
+
+    async fn coredumpctl(&self) -> Result<Vec<CoreDump>> {
+        let output = Command::new("coredumpctl")
+            .args(["list", "--json=short", "--no-pager"])
+            .arg(format!("--since=-{}d", self.history_days))
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .context("Failed to run coredumpctl")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // Exits 1 when the journal holds no dumps in the range
+            if stderr.contains("No coredumps found") {
+                return Ok(Vec::new());
+            }
+            anyhow::bail!("coredumpctl failed: {}", stderr.trim());
+        }
+        let entries: Vec<Value> =
+            serde_json::from_slice(&output.stdout).context("Failed to parse coredumpctl output")?;
+        Ok(entries.iter().filter_map(parse_coredumpctl_entry).collect())
+    }
+}
+
+/// One entry of `coredumpctl list --json=short`; `time` is in microseconds
+fn parse_coredumpctl_entry(entry: &Value) -> Option<CoreDump> {
+    let usec = entry.get("time")?.as_i64()?;
+    let at = DateTime::from_timestamp_micros(usec)?.with_timezone(&Local);
+    Some(CoreDump {
+        at,
+        binary: entry.get("exe")?.as_str()?.to_string(),
+        pid: entry.get("pid").and_then(|v| v.as_u64()).map(|pid| pid as u32),
+        signal: entry.get("sig").and_then(|v| v.as_i64()).map(|sig| sig as i32),
+        origin: "coredumpctl".to_string(),
+        core: entry.get("corefile").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        size_bytes: entry.get("size").and_then(|v| v.as_u64()),
+        critical: false,
+    })
+}
+
+/// Apport reports (*.crash), core files (core, core.<pid>, core.<name>.<pid>)
+/// and kdump directories holding a vmcore, modified after `cutoff`
+fn scan_crash_dir(dir: &Path, cutoff: DateTime<Local>) -> Vec<CoreDump> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dumps = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let Some(at) = metadata.modified().ok().map(DateTime::<Local>::from).filter(|at| *at >= cutoff) else {
+            continue;
+        };
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let mut dump = CoreDump {
+            at,
+            binary: String::new(),
+            pid: None,
+            signal: None,
+            origin: path.display().to_string(),
+            core: Some("present".to_string()),
+            size_bytes: metadata.is_file().then_some(metadata.len()),
+            critical: false,
+        };
+
+        if metadata.is_dir() {
+            if !path.join("vmcore").exists() {
+                continue;
+            }
+            dump.binary = "kernel".to_string();
+            dump.size_bytes = fs::metadata(path.join("vmcore")).ok().map(|m| m.len());
+        } else if file_name.ends_with(".crash") {
+            let Some((binary, pid, signal)) = read_apport_header(&path) else {
+                continue;
+            };
+            dump.binary = binary;
+            dump.pid = pid;
+            dump.signal = signal;
+            dump.core = None;
+        } else if file_name == "core" || file_name.starts_with("core.") {
+            // core.<name>.<pid>, core.<pid> or just core
+            let mut parts = file_name.splitn(3, '.').skip(1);
+            let first = parts.next();
+            let second = parts.next();
+            let (name, pid) = match (first, second) {
+                (Some(name), Some(pid)) => (Some(name), pid.parse().ok()),
+                (Some(only), None) => match only.parse() {
+                    Ok(pid) => (None, Some(pid)),
+                    Err(_) => (Some(only), None),
+                },
+                _ => (None, None),
+            };
+            dump.binary = name.unwrap_or("unknown").to_string();
+            dump.pid = pid;
+        } else {
+            continue;
+        }
+        dumps.push(dump);
+    }
+    dumps
+}
+
+/// ExecutablePath, Pid and Signal from an apport report's header
+fn read_apport_header(path: &Path) -> Option<(String, Option<u32>, Option<i32>)> {
+    let file = fs::File::open(path).ok()?;
+    let (mut binary, mut pid, mut signal) = (None, None, None);
+    for line in BufReader::new(file).lines().take(APPORT_HEADER_LINES) {
+        let line = line.ok()?;
+        let Some((key, value)) = line.split_once(": ") else {
+            continue;
+        };
+        match key {
+            "ExecutablePath" => binary = Some(value.trim().to_string()),
+            "Pid" => pid = value.trim().parse().ok(),
+            "Signal" => signal = value.trim().parse().ok(),
+            "CoreDump" => break,
+            _ => {}
+        }
+        if binary.is_some() && pid.is_some() && signal.is_some() {
+            break;
+        }
+    }
+    Some((binary?, pid, signal))
+}
@@ -0,0 +1,137 @@
+use crate::config::{CommandParser, CustomCommandConfig};
+use crate::types::CommandOutput;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tokio::sync::{mpsc, Notify};
+
+/// Output lines kept per command
+const MAX_OUTPUT_LINES: usize = 1000;
+
+/// Runs the `[[custom_commands]]` from the config, each on its own
+/// interval in a background task, and keeps the latest output of each
+pub struct CustomCommandCollector {
+    pub commands: Vec<CustomCommandConfig>,
+    /// Latest output per command, in config order
+    pub outputs: Vec<Option<CommandOutput>>,
+    /// Run every command now instead of waiting for its interval
+    refresh: Arc<Notify>,
+    rx: mpsc::UnboundedReceiver<(usize, CommandOutput)>,
+}
+
+impl CustomCommandCollector {
+    pub fn start(commands: Vec<CustomCommandConfig>) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let refresh = Arc::new(Notify::new());
+        for (index, command) in commands.iter().cloned().enumerate() {
+            let tx = tx.clone();
+            let refresh = refresh.clone();
+            tokio::spawn(async move {
+                loop {
+                    let output = run(&command).await;
+                    if tx.send((index, output)).is_err() {
+                        break;
+                    }
+                    tokio::select! {
+                        _ = tokio::time::sleep(Duration::from_secs(command.interval_secs.max(1))) => {}
+                        _ = refresh.notified() => {}
+                    }
+                }
+            });
+        }
+        Self {
+            outputs: vec![None; commands.len()],
+            commands,
+            refresh,
+            rx,
+        }
+    }
+
+    pub fn refresh(&self) {
+        self.refresh.notify_waiters();
+    }
+
+    /// Take in finished runs; true when any output changed
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok((index, output)) = self.rx.try_recv() {
+            if let Some(slot) = self.outputs.get_mut(index) {
+                *slot = Some(output);
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+async fn run(config: &CustomCommandConfig) -> CommandOutput {
+    let ran_at = chrono::Local::now();
+    let started = Instant::now();
+    let child = Command::new("sh")
+        .arg("-c")
+        .arg(&config.command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn();
+
+    let result = match child {
+        Ok(child) => {
+            let timeout = Duration::from_secs(config.timeout_secs.max(1));
+            match tokio::time::timeout(timeout, child.wait_with_output()).await {
+                Ok(Ok(output)) => Ok(output),
+                Ok(Err(e)) => Err(format!("Failed to wait for command: {}", e)),
+                Err(_) => Err(format!("Killed after {}s", timeout.as_secs())),
+            }
+        }
+        Err(e) => Err(format!("Failed to start sh: {}", e)),
+    };
+
+    let (exit_code, error, mut lines) = match result {
+        Ok(output) => {
+            let lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .chain(String::from_utf8_lossy(&output.stderr).lines())
+                .map(|line| line.trim_end().to_string())
+                .collect();
+            (output.status.code(), None, lines)
+        }
+        Err(e) => (None, Some(e), Vec::new()),
+    };
+    lines.truncate(MAX_OUTPUT_LINES);
+
+    let table = match (config.parser, exit_code) {
+        (CommandParser::Table, Some(0)) => parse_table(&lines),
+        _ => None,
+    };
+    CommandOutput {
+        ran_at,
+        duration: started.elapsed(),
+        exit_code,
+        error,
+        lines,
+        table,
+    }
+}
+
+/// Split output into columns on whitespace, taking the first non-blank
+/// line as the header. Cells past the last header column are joined into
+/// it, since trailing free-text columns ("STATUS", "MESSAGE") contain spaces.
+fn parse_table(lines: &[String]) -> Option<(Vec<String>, Vec<Vec<String>>)> {
+    let mut rows = lines.iter().filter(|line| !line.trim().is_empty());
+    let header: Vec<String> = rows.next()?.split_whitespace().map(str::to_string).collect();
+    let columns = header.len();
+    let rows = rows
+        .map(|line| {
+            let mut cells: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+            if cells.len() > columns {
+                let rest = cells.split_off(columns - 1).join(" ");
+                cells.push(rest);
+            }
+            cells
+        })
+        .collect();
+    Some((header, rows))
+}
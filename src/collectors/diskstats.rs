@@ -0,0 +1,285 @@
+use anyhow::{Context, Result};
+use crate::types::{BlockQueue, DiskLatency};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const DISKSTATS: &str = "/proc/diskstats";
+const SYS_BLOCK: &str = "/sys/block";
+/// Devices that never sit under VM disks
+const IGNORED_PREFIXES: [&str; 6] = ["loop", "ram", "zram", "sr", "fd", "nbd"];
+/// Span the latency percentiles are taken over
+const PERCENTILE_WINDOW: Duration = Duration::from_secs(300);
+/// Scans a latency has to last through to count as sustained (30s at the
+/// default refresh interval)
+const SUSTAINED_SCANS: usize = 15;
+
+/// Read and write rates and request latency per block device from the
+/// deltas of /proc/diskstats. Throughput alone hides a dying or saturated
+/// disk: it keeps moving data, just with every request taking ten times
+/// as long.
+pub struct DiskStatsCollector {
+    use_mock: bool,
+    /// Counters from the previous scan, by device
+    previous: HashMap<String, DiskSample>,
+    /// (scanned at, average ms per request) of the busy scans within
+    /// `PERCENTILE_WINDOW`, by device
+    samples: HashMap<String, VecDeque<(Instant, f64)>>,
+    /// Latency of the last `SUSTAINED_SCANS` scans, None where idle
+    recent: HashMap<String, VecDeque<Option<f64>>>,
+    /// Directories whose backing devices count as VM datastores
+    datastore_dirs: Vec<String>,
+    /// Devices named as datastores outright; replaces `datastore_dirs`
+    datastore_devices: Vec<String>,
+}
+
+/// Cumulative counters of one device
+struct DiskSample {
+    at: Instant,
+    /// Reads plus writes completed
+    ios: u64,
+    /// Milliseconds spent on those reads and writes
+    io_ms: u64,
+    sectors_read: u64,
+    sectors_written: u64,
+    /// Milliseconds the device had requests in flight
+    busy_ms: u64,
+    /// Requests in flight right now
+    in_flight: u64,
+}
+
+impl DiskStatsCollector {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            use_mock: false,
+            previous: HashMap::new(),
+            samples: HashMap::new(),
+            recent: HashMap::new(),
+            datastore_dirs: Vec::new(),
+            datastore_devices: Vec::new(),
+        })
+    }
+
+    /// Mark the devices VM disks live on: `devices` by name (or dm name),
+    /// or else whatever backs `dirs`, through partitions, LVM and md
+    pub fn with_datastores(mut self, dirs: Vec<String>, devices: Vec<String>) -> Self {
+        self.datastore_dirs = dirs;
+        self.datastore_devices = devices;
+        self
+    }
+
+    pub async fn collect(&mut self) -> Result<Vec<DiskLatency>> {
+        if self.use_mock {
+            return Ok(self.collect_mock());
+        }
+        if !Path::new(DISKSTATS).exists() {
+            tracing::warn!("No {}, using mock disk latency data", DISKSTATS);
+            self.use_mock = true;
+            return Ok(self.collect_mock());
+        }
+
+        let text = fs::read_to_string(DISKSTATS).with_context(|| format!("Failed to read {}", DISKSTATS))?;
+        let now = Instant::now();
+        // Looked up every scan, since a datastore may be mounted later
+        let datastores: HashSet<String> = self.datastore_dirs.iter().flat_map(|dir| backing_devices(dir)).collect();
+        let mut disks = Vec::new();
+        for (device, sample) in parse_diskstats(&text, now) {
+            if IGNORED_PREFIXES.iter().any(|prefix| device.starts_with(prefix))
+                || !Path::new(SYS_BLOCK).join(&device).exists()
+                || sample.ios == 0
+            {
+                continue;
+            }
+            if let Some(previous) = self.previous.remove(&device) {
+                let mut disk = self.latency(&device, &previous, &sample);
+                disk.datastore = if self.datastore_devices.is_empty() {
+                    datastores.contains(&device)
+                } else {
+                    self.datastore_devices.iter().any(|name| *name == device || disk.label.as_ref() == Some(name))
+                };
+                disks.push(disk);
+            }
+            self.previous.insert(device, sample);
+        }
+
+        disks.sort_by(|a, b| a.device.cmp(&b.device));
+        Ok(disks)
+    }
+
+    fn latency(&mut self, device: &str, prev: &DiskSample, sample: &DiskSample) -> DiskLatency {
+        let elapsed = sample.at.duration_since(prev.at).as_secs_f64();
+        let per_second = |delta: u64| if elapsed > 0.0 { delta as f64 / elapsed } else { 0.0 };
+        // Sectors are always 512 bytes in diskstats, whatever the device's
+        let mb_s = |now: u64, before: u64| per_second(now.saturating_sub(before)) * 512.0 / 1_048_576.0;
+
+        let ios = sample.ios.saturating_sub(prev.ios);
+        let await_ms = (ios > 0).then(|| sample.io_ms.saturating_sub(prev.io_ms) as f64 / ios as f64);
+
+        let samples = self.samples.entry(device.to_string()).or_default();
+        if let Some(ms) = await_ms {
+            samples.push_back((sample.at, ms));
+        }
+        while samples.front().is_some_and(|&(at, _)| sample.at.duration_since(at) > PERCENTILE_WINDOW) {
+            samples.pop_front();
+        }
+        let mut window: Vec<f64> = samples.iter().map(|&(_, ms)| ms).collect();
+        window.sort_by(f64::total_cmp);
+
+        let recent = self.recent.entry(device.to_string()).or_default();
+        recent.push_back(await_ms);
+        if recent.len() > SUSTAINED_SCANS {
+            recent.pop_front();
+        }
+        // An idle scan in between means the latency didn't last
+        let sustained_ms = if recent.len() < SUSTAINED_SCANS {
+            0.0
+        } else {
+            recent.iter().map(|ms| ms.unwrap_or(0.0)).fold(f64::INFINITY, f64::min)
+        };
+
+        DiskLatency {
+            device: device.to_string(),
+            label: fs::read_to_string(Path::new(SYS_BLOCK).join(device).join("dm/name"))
+                .ok()
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty()),
+            read_mb_s: mb_s(sample.sectors_read, prev.sectors_read),
+            write_mb_s: mb_s(sample.sectors_written, prev.sectors_written),
+            util_percent: (per_second(sample.busy_ms.saturating_sub(prev.busy_ms)) / 10.0).min(100.0),
+            await_ms,
+            p50_ms: percentile(&window, 50.0),
+            p99_ms: percentile(&window, 99.0),
+            sustained_ms,
+            in_flight: sample.in_flight,
+            queue: block_queue(device),
+            datastore: false,
+        }
+    }
+
+    fn collect_mock(&self) -> Vec<DiskLatency> {
+        // Stable pseudo-random drift so values don't flicker between cycles
+        let drift = (chrono::Local::now().timestamp() / 10 % 5) as f64;
+        let queue = |scheduler: &str, depth: u64, rotational: bool| BlockQueue {
+            scheduler: Some(scheduler.to_string()),
+            available: if scheduler == "none" && !rotational {
+                vec!["none".to_string()]
+            } else {
+                ["mq-deadline", "kyber", "bfq", "none"].iter().map(|s| s.to_string()).collect()
+            },
+            depth: Some(depth),
+            rotational,
+        };
+        let disk = |device: &str, label: Option<&str>, read: f64, write: f64, util: f64, p50: f64, p99: f64| DiskLatency {
+            device: device.to_string(),
+            label: label.map(str::to_string),
+            read_mb_s: read + drift,
+            write_mb_s: write + drift / 2.0,
+            util_percent: util,
+            await_ms: Some(p50 + drift / 10.0),
+            p50_ms: Some(p50),
+            p99_ms: Some(p99),
+            sustained_ms: p50 * 0.8,
+            in_flight: (util / 10.0) as u64,
+            queue: queue("none", 256, false),
+            datastore: true,
+        };
+        vec![
+            disk("dm-2", Some("vmstore01"), 48.2, 31.7, 41.0, 1.9, 14.5),
+            disk("dm-3", Some("vmstore02"), 12.4, 6.3, 88.0, 38.0, 212.0),
+            DiskLatency { queue: queue("mq-deadline", 1023, false), ..disk("nvme0n1", None, 95.1, 60.8, 22.0, 0.2, 1.1) },
+            DiskLatency {
+                queue: queue("bfq", 64, true),
+                datastore: false,
+                ..disk("sda", None, 2.1, 0.4, 3.0, 4.2, 11.0)
+            },
+        ]
+    }
+}
+
+/// Whole-device and partition counters by name; see
+/// Documentation/admin-guide/iostats.rst for the columns
+fn parse_diskstats(text: &str, at: Instant) -> Vec<(String, DiskSample)> {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let value = |i: usize| fields.get(i).and_then(|v| v.parse::<u64>().ok());
+            let device = fields.get(2)?.to_string();
+            Some((
+                device,
+                DiskSample {
+                    at,
+                    ios: value(3)? + value(7)?,
+                    io_ms: value(6)? + value(10)?,
+                    sectors_read: value(5)?,
+                    sectors_written: value(9)?,
+                    busy_ms: value(12)?,
+                    in_flight: value(11)?,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Scheduler, queue depth and rotational flag from /sys/block/<dev>/queue
+fn block_queue(device: &str) -> BlockQueue {
+    let queue = Path::new(SYS_BLOCK).join(device).join("queue");
+    let read = |name: &str| fs::read_to_string(queue.join(name)).ok().map(|value| value.trim().to_string());
+    // "mq-deadline kyber [bfq] none": the bracketed one is active
+    let line = read("scheduler").unwrap_or_default();
+    let available: Vec<String> = line.split_whitespace().map(|s| s.trim_matches(['[', ']']).to_string()).collect();
+    BlockQueue {
+        scheduler: line
+            .split_whitespace()
+            .find(|s| s.starts_with('['))
+            .or_else(|| (available.len() == 1).then(|| line.trim()))
+            .map(|s| s.trim_matches(['[', ']']).to_string()),
+        available,
+        depth: read("nr_requests").and_then(|depth| depth.parse().ok()),
+        rotational: read("rotational").as_deref() == Some("1"),
+    }
+}
+
+/// Whole devices holding `dir`: its filesystem's device (the disk, not the
+/// partition) and, for dm and md devices, the disks beneath them too.
+/// Filesystems without a block device (tmpfs, btrfs subvolumes, NFS)
+/// have none.
+fn backing_devices(dir: &str) -> Vec<String> {
+    let Ok(meta) = fs::metadata(dir) else {
+        return Vec::new();
+    };
+    let dev = meta.dev();
+    let path = PathBuf::from(format!("/sys/dev/block/{}:{}", libc::major(dev), libc::minor(dev)));
+    let mut devices = Vec::new();
+    let mut pending = vec![path];
+    while let Some(path) = pending.pop() {
+        let Ok(mut path) = fs::canonicalize(&path) else {
+            continue;
+        };
+        // A partition's directory sits inside its disk's
+        if path.join("partition").exists() {
+            path.pop();
+        }
+        let Some(name) = path.file_name().and_then(|name| name.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if devices.contains(&name) {
+            continue;
+        }
+        if let Ok(slaves) = fs::read_dir(path.join("slaves")) {
+            pending.extend(slaves.flatten().map(|slave| slave.path()));
+        }
+        devices.push(name);
+    }
+    devices
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], percent: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.saturating_sub(1).min(sorted.len() - 1)).copied()
+}
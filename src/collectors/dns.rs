@@ -0,0 +1,204 @@
+use anyhow::{Context, Result};
+use crate::collectors::KubernetesCollector;
+use crate::types::DnsResolverStatus;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Instant;
+use tokio::net::UdpSocket;
+use tokio::time::{timeout, Duration};
+
+const PROBE_WINDOW: usize = 20; // Probe outcomes kept per resolver for failure rates
+const MAX_LABEL_LEN: usize = 63;
+const MAX_NAME_LEN: usize = 253;
+
+pub struct DnsCollector {
+    host_names: Vec<String>,
+    cluster_names: Vec<String>,
+    coredns_address: Option<String>,
+    /// kube-dns ClusterIP as last read from the cluster; read again after
+    /// a round where every CoreDNS probe failed
+    discovered_coredns: Option<IpAddr>,
+    timeout: Duration,
+    history: HashMap<String, VecDeque<bool>>,
+    consecutive_failures: HashMap<String, u32>,
+}
+
+impl DnsCollector {
+    pub fn new() -> Self {
+        Self {
+            host_names: vec!["cache.nixos.org".to_string()],
+            cluster_names: vec!["kubernetes.default.svc.cluster.local".to_string()],
+            coredns_address: None,
+            discovered_coredns: None,
+            timeout: Duration::from_millis(1000),
+            history: HashMap::new(),
+            consecutive_failures: HashMap::new(),
+        }
+    }
+
+    pub fn with_names(mut self, host_names: Vec<String>, cluster_names: Vec<String>) -> Self {
+        self.host_names = host_names;
+        self.cluster_names = cluster_names;
+        self
+    }
+
+    pub fn with_coredns_address(mut self, address: Option<String>) -> Self {
+        self.coredns_address = address;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout = Duration::from_millis(timeout_ms);
+        self
+    }
+
+    pub async fn collect(&mut self, k8s: &KubernetesCollector) -> Result<Vec<DnsResolverStatus>> {
+        let mut targets: Vec<(String, IpAddr, Vec<String>)> = read_host_resolvers()
+            .into_iter()
+            .map(|ip| ("host".to_string(), ip, self.host_names.clone()))
+            .collect();
+
+        // With no names to resolve a resolver can only fail, so leave it unprobed
+        targets.retain(|(_, _, names)| !names.is_empty());
+        if !self.cluster_names.is_empty() {
+            if let Some(ip) = self.resolve_coredns_address(k8s).await {
+                targets.push(("coredns".to_string(), ip, self.cluster_names.clone()));
+            }
+        }
+
+        let limit = self.timeout;
+        let probes = targets.iter().map(|(label, ip, names)| async move {
+            let mut latencies = Vec::new();
+            let mut errors = Vec::new();
+            for name in names.iter() {
+                match query(*ip, name, limit).await {
+                    Ok(latency) => latencies.push(latency),
+                    Err(e) => errors.push(format!("{}: {}", name, e)),
+                }
+            }
+            (label.clone(), *ip, latencies, errors)
+        });
+        let results = futures::future::join_all(probes).await;
+
+        let mut statuses = Vec::new();
+        for (label, ip, latencies, errors) in results {
+            let server = ip.to_string();
+
+            let history = self.history.entry(server.clone()).or_default();
+            let outcomes = latencies.iter().map(|_| true).chain(errors.iter().map(|_| false));
+            for ok in outcomes {
+                if history.len() >= PROBE_WINDOW {
+                    history.pop_front();
+                }
+                history.push_back(ok);
+            }
+            let success_rate = if history.is_empty() {
+                0.0
+            } else {
+                history.iter().filter(|ok| **ok).count() as f64 / history.len() as f64 * 100.0
+            };
+
+            let failures = self.consecutive_failures.entry(server.clone()).or_insert(0);
+            if latencies.is_empty() {
+                *failures += 1;
+                if label == "coredns" {
+                    self.discovered_coredns = None;
+                }
+            } else {
+                *failures = 0;
+            }
+
+            statuses.push(DnsResolverStatus {
+                label,
+                server,
+                latency_ms: (!latencies.is_empty())
+                    .then(|| latencies.iter().sum::<f64>() / latencies.len() as f64),
+                success_rate,
+                consecutive_failures: *failures,
+                last_error: errors.into_iter().next(),
+            });
+        }
+
+        Ok(statuses)
+    }
+
+    async fn resolve_coredns_address(&mut self, k8s: &KubernetesCollector) -> Option<IpAddr> {
+        if let Some(address) = &self.coredns_address {
+            return address.parse().ok();
+        }
+        if self.discovered_coredns.is_none() {
+            // Ask the cluster for the kube-dns service IP
+            self.discovered_coredns = k8s.service_cluster_ip("kube-system", "kube-dns").await.ok().flatten();
+        }
+        self.discovered_coredns
+    }
+}
+
+/// Nameservers configured for the host in /etc/resolv.conf
+fn read_host_resolvers() -> Vec<IpAddr> {
+    fs::read_to_string("/etc/resolv.conf")
+        .map(|contents| {
+            contents
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix("nameserver"))
+                .filter_map(|addr| addr.trim().parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Send a single A query and return the round-trip latency in milliseconds
+async fn query(server: IpAddr, name: &str, limit: Duration) -> Result<f64> {
+    let bind_addr = if server.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+    let socket = UdpSocket::bind(bind_addr).await.context("Failed to bind UDP socket")?;
+    socket.connect(SocketAddr::new(server, 53)).await?;
+
+    let id = rand::random::<u16>();
+    let packet = build_query(id, name)?;
+
+    let start = Instant::now();
+    socket.send(&packet).await?;
+
+    let mut buf = [0u8; 512];
+    let len = timeout(limit, socket.recv(&mut buf))
+        .await
+        .map_err(|_| anyhow::anyhow!("timed out"))??;
+    let latency = start.elapsed().as_secs_f64() * 1000.0;
+
+    if len < 12 || u16::from_be_bytes([buf[0], buf[1]]) != id {
+        anyhow::bail!("malformed response");
+    }
+
+    match buf[3] & 0x0F {
+        0 => Ok(latency),
+        2 => anyhow::bail!("SERVFAIL"),
+        3 => anyhow::bail!("NXDOMAIN"),
+        5 => anyhow::bail!("REFUSED"),
+        rcode => anyhow::bail!("rcode {}", rcode),
+    }
+}
+
+fn build_query(id: u16, name: &str) -> Result<Vec<u8>> {
+    let name = name.trim_end_matches('.');
+    if name.len() > MAX_NAME_LEN {
+        anyhow::bail!("name longer than {} bytes", MAX_NAME_LEN);
+    }
+    if let Some(label) = name.split('.').find(|label| label.is_empty() || label.len() > MAX_LABEL_LEN) {
+        anyhow::bail!("label {:?} must be 1 to {} bytes", label, MAX_LABEL_LEN);
+    }
+
+    let mut packet = Vec::with_capacity(12 + name.len() + 6);
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&[0x01, 0x00]); // Standard query, recursion desired
+    packet.extend_from_slice(&[0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]); // 1 question
+
+    for label in name.split('.') {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+
+    packet.extend_from_slice(&[0x00, 0x01, 0x00, 0x01]); // QTYPE=A, QCLASS=IN
+    Ok(packet)
+}
@@ -0,0 +1,232 @@
+use anyhow::{bail, Context, Result};
+use crate::config::EtcdConfig;
+use crate::types::{ClusterFlavor, EtcdStatus};
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::process::Stdio;
+use tokio::process::Command;
+use tokio::time::{Duration, Instant};
+
+/// etcd's plain-HTTP metrics listener, which k3s, RKE2 and kubeadm all
+/// bind on localhost (`--listen-metrics-urls`)
+const METRICS_URL: &str = "http://127.0.0.1:2381/metrics";
+/// The client port, which serves /metrics too but wants a client certificate
+const CLIENT_URL: &str = "https://127.0.0.1:2379/metrics";
+/// Span over which leader changes are counted
+const LEADER_WINDOW: Duration = Duration::from_secs(3600);
+
+/// Endpoint to scrape, with the TLS files to present when it is https
+struct Endpoint {
+    url: String,
+    ca: Option<String>,
+    cert: Option<String>,
+    key: Option<String>,
+}
+
+/// Reads the cluster datastore's health from etcd's Prometheus metrics:
+/// database size against its quota, fragmentation, leader elections and
+/// failed raft proposals. A full or leaderless etcd takes the whole
+/// control plane down with it.
+pub struct EtcdCollector {
+    endpoints: Vec<Endpoint>,
+    /// Whether an endpoint was configured rather than guessed
+    configured: bool,
+    data_dir: String,
+    timeout: Duration,
+    use_mock: bool,
+    /// The endpoint that answered last, tried first
+    working: Option<usize>,
+    /// (sampled at, leader changes total) over the last `LEADER_WINDOW`
+    leader_samples: VecDeque<(Instant, u64)>,
+    proposals_failed: Option<u64>,
+}
+
+impl EtcdCollector {
+    pub fn new(config: &EtcdConfig, flavor: ClusterFlavor) -> Self {
+        let (ca, cert, key) = flavor.etcd_client_certs();
+        let tls = |configured: &Option<String>, default: &str| {
+            Some(configured.clone().unwrap_or_else(|| default.to_string()))
+        };
+        let endpoints = match config.metrics_url {
+            Some(ref url) => vec![Endpoint {
+                url: url.clone(),
+                ca: config.ca_file.clone(),
+                cert: config.cert_file.clone(),
+                key: config.key_file.clone(),
+            }],
+            None => vec![
+                Endpoint { url: METRICS_URL.to_string(), ca: None, cert: None, key: None },
+                Endpoint {
+                    url: CLIENT_URL.to_string(),
+                    ca: tls(&config.ca_file, ca),
+                    cert: tls(&config.cert_file, cert),
+                    key: tls(&config.key_file, key),
+                },
+            ],
+        };
+
+        // Without any cluster on the host there is nothing to scrape
+        let use_mock = config.metrics_url.is_none()
+            && !Path::new("/var/lib/rancher").exists()
+            && !Path::new("/etc/kubernetes").exists();
+
+        Self {
+            endpoints,
+            configured: config.metrics_url.is_some(),
+            data_dir: flavor.etcd_data_dir().to_string(),
+            timeout: Duration::from_millis(3000),
+            use_mock,
+            working: None,
+            leader_samples: VecDeque::new(),
+            proposals_failed: None,
+        }
+    }
+
+    /// None when this host runs no etcd: an agent node, or a k3s server
+    /// on kine (SQLite) rather than embedded etcd
+    pub async fn collect(&mut self) -> Result<Option<EtcdStatus>> {
+        if self.use_mock {
+            return Ok(Some(mock_status()));
+        }
+        if !self.configured && !Path::new(&self.data_dir).exists() {
+            return Ok(None);
+        }
+
+        let order: Vec<usize> = self
+            .working
+            .into_iter()
+            .chain((0..self.endpoints.len()).filter(|&i| Some(i) != self.working))
+            .collect();
+        let mut errors = Vec::new();
+        for index in order {
+            let endpoint = &self.endpoints[index];
+            match scrape(endpoint, self.timeout).await {
+                Ok(body) => {
+                    self.working = Some(index);
+                    let url = endpoint.url.clone();
+                    return self.status(&url, &parse_metrics(&body)).map(Some);
+                }
+                Err(e) => errors.push(format!("{}: {:#}", endpoint.url, e)),
+            }
+        }
+        self.working = None;
+        bail!("etcd metrics unavailable ({})", errors.join("; "))
+    }
+
+    fn status(&mut self, endpoint: &str, metrics: &HashMap<String, f64>) -> Result<EtcdStatus> {
+        let get = |name: &str| metrics.get(name).map(|&v| v.max(0.0) as u64);
+        let db_size_bytes = get("etcd_mvcc_db_total_size_in_bytes")
+            .or_else(|| get("etcd_debugging_mvcc_db_total_size_in_bytes"))
+            .context("no etcd_mvcc_db_total_size_in_bytes in the metrics")?;
+        let leader_changes_total = get("etcd_server_leader_changes_seen_total").unwrap_or(0);
+        let proposals_failed_total = get("etcd_server_proposals_failed_total").unwrap_or(0);
+
+        // Counters start over when etcd restarts
+        let now = Instant::now();
+        if self.leader_samples.back().is_some_and(|&(_, total)| total > leader_changes_total) {
+            self.leader_samples.clear();
+        }
+        self.leader_samples.push_back((now, leader_changes_total));
+        while self.leader_samples.front().is_some_and(|&(at, _)| now.duration_since(at) > LEADER_WINDOW) {
+            self.leader_samples.pop_front();
+        }
+        let leader_changes_last_hour = self
+            .leader_samples
+            .front()
+            .map_or(0, |&(_, oldest)| leader_changes_total - oldest);
+
+        let proposals_failed_recent = self
+            .proposals_failed
+            .map_or(0, |previous| proposals_failed_total.saturating_sub(previous));
+        self.proposals_failed = Some(proposals_failed_total);
+
+        Ok(EtcdStatus {
+            endpoint: endpoint.to_string(),
+            db_size_bytes,
+            db_in_use_bytes: get("etcd_mvcc_db_total_size_in_use_in_bytes"),
+            quota_bytes: get("etcd_server_quota_backend_bytes"),
+            has_leader: get("etcd_server_has_leader").map(|v| v == 1),
+            is_leader: get("etcd_server_is_leader").map(|v| v == 1),
+            leader_changes_total,
+            leader_changes_last_hour,
+            proposals_failed_total,
+            proposals_failed_recent,
+            proposals_pending: get("etcd_server_proposals_pending"),
+        })
+    }
+}
+
+async fn scrape(endpoint: &Endpoint, timeout: Duration) -> Result<String> {
+    let mut command = Command::new("curl");
+    command
+        .args(["-sS", "--fail", "--max-time"])
+        .arg(format!("{:.1}", timeout.as_secs_f64()));
+    if let Some(ref ca) = endpoint.ca {
+        command.arg("--cacert").arg(ca);
+    }
+    if let Some(ref cert) = endpoint.cert {
+        command.arg("--cert").arg(cert);
+    }
+    if let Some(ref key) = endpoint.key {
+        command.arg("--key").arg(key);
+    }
+    let output = command
+        .arg(&endpoint.url)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .context("Failed to run curl")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error = stderr.trim().trim_start_matches("curl: ");
+        if error.is_empty() {
+            bail!("curl exited with {}", output.status);
+        }
+        bail!("{}", error);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Prometheus text format: `name{labels} value` per sample. Samples of
+/// one metric with different labels are summed, which is what the
+/// counters read here want.
+fn parse_metrics(body: &str) -> HashMap<String, f64> {
+    let mut metrics = HashMap::new();
+    for line in body.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, rest) = match line.find(['{', ' ']) {
+            Some(i) => line.split_at(i),
+            None => continue,
+        };
+        let rest = match rest.strip_prefix('{') {
+            Some(labelled) => labelled.split_once('}').map_or("", |(_, value)| value),
+            None => rest,
+        };
+        let Some(value) = rest.split_whitespace().next().and_then(|v| v.parse::<f64>().ok()) else {
+            continue;
+        };
+        *metrics.entry(name.to_string()).or_insert(0.0) += value;
+    }
+    metrics
+}
+
+fn mock_status() -> EtcdStatus {
+    EtcdStatus {
+        endpoint: METRICS_URL.to_string(),
+        db_size_bytes: 412 * 1024 * 1024,
+        db_in_use_bytes: Some(268 * 1024 * 1024),
+        quota_bytes: Some(2 * 1024 * 1024 * 1024),
+        has_leader: Some(true),
+        is_leader: Some(true),
+        leader_changes_total: 2,
+        leader_changes_last_hour: 0,
+        proposals_failed_total: 0,
+        proposals_failed_recent: 0,
+        proposals_pending: Some(0),
+    }
+}
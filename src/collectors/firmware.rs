@@ -0,0 +1,143 @@
+use anyhow::{Context, Result};
+use crate::types::{HardwareBaseline, HardwareInfo};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DMI_DIR: &str = "/sys/class/dmi/id";
+
+/// BIOS and microcode versions and virtualization support. Keeps a
+/// baseline in the state directory so features switched off by a firmware
+/// update can be told apart from ones that were never enabled.
+pub struct FirmwareCollector {
+    baseline_path: PathBuf,
+    baseline: Option<HardwareBaseline>,
+    use_mock: bool,
+}
+
+impl FirmwareCollector {
+    pub fn new(baseline_path: PathBuf) -> Result<Self> {
+        let baseline = match fs::read_to_string(&baseline_path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| tracing::warn!("Ignoring corrupt hardware baseline {:?}: {}", baseline_path, e))
+                .ok(),
+            Err(_) => None,
+        };
+        Ok(Self { baseline_path, baseline, use_mock: false })
+    }
+
+    pub async fn collect(&mut self) -> Result<HardwareInfo> {
+        let cpuinfo = match fs::read_to_string("/proc/cpuinfo") {
+            Ok(cpuinfo) => cpuinfo,
+            Err(e) => {
+                if !self.use_mock {
+                    tracing::warn!("Failed to read /proc/cpuinfo, using mock hardware info: {}", e);
+                    self.use_mock = true;
+                }
+                return Ok(mock_hardware());
+            }
+        };
+
+        // Every core lists the same values; the first one is enough
+        let first_cpu = cpuinfo.split("\n\n").next().unwrap_or_default();
+        let field = |name: &str| {
+            first_cpu.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                (key.trim() == name).then(|| value.trim().to_string())
+            })
+        };
+        let flags = field("flags").unwrap_or_default();
+        let has_flag = |flag: &str| flags.split_whitespace().any(|f| f == flag);
+        let virtualization = if has_flag("vmx") {
+            Some("VT-x")
+        } else if has_flag("svm") {
+            Some("AMD-V")
+        } else {
+            None
+        };
+
+        let system = match (dmi("sys_vendor"), dmi("product_name")) {
+            (Some(vendor), Some(product)) => Some(format!("{} {}", vendor, product)),
+            (vendor, product) => vendor.or(product),
+        };
+        let mut info = HardwareInfo {
+            system,
+            bios_vendor: dmi("bios_vendor"),
+            bios_version: dmi("bios_version"),
+            bios_date: dmi("bios_date"),
+            cpu_model: field("model name"),
+            microcode: field("microcode"),
+            virtualization,
+            kvm: Path::new("/dev/kvm").exists(),
+            iommu: iommu_enabled(),
+            nested: ["kvm_intel", "kvm_amd"].iter().find_map(|module| {
+                let value = fs::read_to_string(format!("/sys/module/{}/parameters/nested", module)).ok()?;
+                Some(matches!(value.trim(), "Y" | "1"))
+            }),
+            baseline: None,
+        };
+
+        self.update_baseline(&info);
+        info.baseline = self.baseline.clone();
+        Ok(info)
+    }
+
+    /// Record the current firmware version, and features as enabled once
+    /// seen. Written only when something changed.
+    fn update_baseline(&mut self, info: &HardwareInfo) {
+        let mut baseline = self.baseline.clone().unwrap_or_else(|| HardwareBaseline {
+            bios_version: info.bios_version.clone(),
+            ..Default::default()
+        });
+        if info.bios_version.is_some() && baseline.bios_version != info.bios_version {
+            baseline.previous_bios_version = baseline.bios_version.replace(info.bios_version.clone().unwrap_or_default());
+        }
+        baseline.virtualization |= info.virtualization.is_some();
+        baseline.iommu |= info.iommu;
+
+        if self.baseline.as_ref() != Some(&baseline) {
+            if let Err(e) = self.save(&baseline) {
+                tracing::warn!("{:#}", e);
+            }
+            self.baseline = Some(baseline);
+        }
+    }
+
+    fn save(&self, baseline: &HardwareBaseline) -> Result<()> {
+        if let Some(parent) = self.baseline_path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+        }
+        let contents = serde_json::to_string_pretty(baseline).context("Failed to serialize hardware baseline")?;
+        fs::write(&self.baseline_path, contents)
+            .with_context(|| format!("Failed to write hardware baseline: {:?}", self.baseline_path))
+    }
+}
+
+/// A DMI field, None when unreadable (non-root for some fields) or blank
+fn dmi(name: &str) -> Option<String> {
+    let value = fs::read_to_string(Path::new(DMI_DIR).join(name)).ok()?;
+    let value = value.trim();
+    (!value.is_empty() && value != "To Be Filled By O.E.M.").then(|| value.to_string())
+}
+
+/// Any IOMMU unit registered with the kernel (intel-iommu/amd-iommu), or
+/// IOMMU groups having been created
+fn iommu_enabled() -> bool {
+    let has_entries = |dir: &str| fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_some());
+    has_entries("/sys/class/iommu") || has_entries("/sys/kernel/iommu_groups")
+}
+
+fn mock_hardware() -> HardwareInfo {
+    HardwareInfo {
+        system: Some("Supermicro SYS-1029P-WTR".to_string()),
+        bios_vendor: Some("American Megatrends Inc.".to_string()),
+        bios_version: Some("3.8a".to_string()),
+        bios_date: Some("04/12/2024".to_string()),
+        cpu_model: Some("Intel(R) Xeon(R) Gold 6248R CPU @ 3.00GHz".to_string()),
+        microcode: Some("0x5003707".to_string()),
+        virtualization: Some("VT-x"),
+        kvm: true,
+        iommu: true,
+        nested: Some(false),
+        baseline: None,
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::{Context, Result};
+use crate::types::HostInfo;
+use std::fs;
+use std::process::Stdio;
+use tokio::process::Command;
+
+const PROFILES_DIR: &str = "/nix/var/nix/profiles";
+
+/// Cluster service unit states and NixOS generation details
+pub struct HostCollector {
+    services: Vec<String>,
+}
+
+impl HostCollector {
+    pub fn new() -> Result<Self> {
+        Ok(Self { services: Vec::new() })
+    }
+
+    pub fn with_services(mut self, services: Vec<String>) -> Self {
+        self.services = services;
+        self
+    }
+
+    pub async fn collect(&mut self) -> Result<HostInfo> {
+        let services = self.service_states().await?;
+        let os_release = fs::read_to_string("/etc/os-release").unwrap_or_default();
+        let is_nixos = os_release.lines().any(|l| l == "ID=nixos");
+
+        let mut info = HostInfo {
+            services,
+            ..HostInfo::default()
+        };
+        if is_nixos {
+            info.nixos_version = os_release
+                .lines()
+                .find_map(|l| l.strip_prefix("VERSION_ID="))
+                .map(|v| v.trim_matches('"').to_string());
+            info.generation = current_generation();
+            info.current_system = read_link("/run/current-system");
+            info.booted_system = read_link("/run/booted-system");
+        }
+        Ok(info)
+    }
+
+    async fn service_states(&self) -> Result<Vec<(String, String)>> {
+        if self.services.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // is-active exits non-zero when any unit is inactive; the per-unit
+        // states are still printed one per line in argument order
+        let output = Command::new("systemctl")
+            .arg("is-active")
+            .args(&self.services)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .context("Failed to execute systemctl")?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(self
+            .services
+            .iter()
+            .cloned()
+            .zip(stdout.lines().map(String::from).chain(std::iter::repeat("unknown".to_string())))
+            .collect())
+    }
+}
+
+/// Generation number the system profile points at (system -> system-42-link)
+fn current_generation() -> Option<u32> {
+    let target = fs::read_link(format!("{}/system", PROFILES_DIR)).ok()?;
+    target
+        .file_name()?
+        .to_str()?
+        .strip_prefix("system-")?
+        .strip_suffix("-link")?
+        .parse()
+        .ok()
+}
+
+fn read_link(path: &str) -> Option<String> {
+    fs::read_link(path).ok().map(|p| p.display().to_string())
+}
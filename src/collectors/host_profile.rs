@@ -0,0 +1,178 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::process::Command;
+
+use super::sysctl::sysctl_path;
+use crate::config::HostProfileConfig;
+use crate::types::{page_size_kb, ProfileDeviation, SysctlExpectation};
+
+const NET_DIR: &str = "/sys/class/net";
+const HUGEPAGES_DIR: &str = "/sys/kernel/mm/hugepages";
+
+/// Compares the live host against `[host_profile]`: kernel release,
+/// sysctls, required units, hugepage pools, physical NICs and CPU flags
+pub struct HostProfileCollector {
+    profile: HostProfileConfig,
+    use_mock: bool,
+}
+
+impl HostProfileCollector {
+    pub fn new(profile: HostProfileConfig) -> Self {
+        Self { profile, use_mock: false }
+    }
+
+    /// Every expectation the host doesn't meet, in profile order
+    pub async fn collect(&mut self) -> Result<Vec<ProfileDeviation>> {
+        if !self.use_mock && !Path::new("/proc/sys/kernel").is_dir() {
+            tracing::warn!("No /proc/sys, not checking the host profile");
+            self.use_mock = true;
+        }
+        if self.use_mock {
+            return Ok(Vec::new());
+        }
+
+        let profile = &self.profile;
+        let mut deviations = Vec::new();
+        let mut deviate = |check: String, expected: String, actual: String| {
+            deviations.push(ProfileDeviation { check, expected, actual });
+        };
+
+        if let Some(ref expected) = profile.kernel {
+            let release = read_trimmed("/proc/sys/kernel/osrelease").unwrap_or_else(|| "unknown".to_string());
+            let matches = match expected.strip_suffix('*') {
+                Some(prefix) => release.starts_with(prefix),
+                None => release == *expected,
+            };
+            if !matches {
+                deviate("kernel".to_string(), expected.clone(), release);
+            }
+        }
+
+        for (name, expected) in &profile.sysctls {
+            // Bad expectations are reported by config validation
+            let Ok(expectation) = SysctlExpectation::parse(expected) else {
+                continue;
+            };
+            match read_trimmed(sysctl_path(name)) {
+                Some(value) if expectation.holds(&value) => {}
+                value => deviate(
+                    format!("sysctl {}", name),
+                    expectation.to_string(),
+                    value.unwrap_or_else(|| "missing".to_string()),
+                ),
+            }
+        }
+
+        for (service, state) in service_states(&profile.services).await? {
+            if state != "active" {
+                deviate(format!("service {}", service), "active".to_string(), state);
+            }
+        }
+
+        for (size, &least) in &profile.hugepages {
+            let Some(kb) = page_size_kb(size) else {
+                continue;
+            };
+            let path = format!("{}/hugepages-{}kB/nr_hugepages", HUGEPAGES_DIR, kb);
+            let reserved = read_trimmed(&path).and_then(|n| n.parse::<u64>().ok());
+            if reserved.is_none_or(|n| n < least) {
+                deviate(
+                    format!("hugepages {}", size),
+                    format!(">={}", least),
+                    reserved.map_or_else(|| "size not supported".to_string(), |n| n.to_string()),
+                );
+            }
+        }
+
+        if profile.nic_count.is_some() || profile.nic_speed_mbps.is_some() {
+            let nics = physical_nics();
+            if let Some(least) = profile.nic_count {
+                if (nics.len() as u32) < least {
+                    let names: Vec<&str> = nics.iter().map(|(name, _)| name.as_str()).collect();
+                    let actual = format!("{} ({})", nics.len(), if names.is_empty() { "none".to_string() } else { names.join(", ") });
+                    deviate("NICs".to_string(), format!(">={}", least), actual);
+                }
+            }
+            if let Some(least) = profile.nic_speed_mbps {
+                for (name, speed) in &nics {
+                    if let Some(speed) = speed.filter(|speed| *speed < least) {
+                        deviate(format!("NIC {} speed", name), format!(">={} Mb/s", least), format!("{} Mb/s", speed));
+                    }
+                }
+            }
+        }
+
+        if !profile.cpu_flags.is_empty() {
+            let cpuinfo = fs::read_to_string("/proc/cpuinfo").context("Failed to read /proc/cpuinfo")?;
+            // Every core lists the same flags; the first one is enough
+            let flags: Vec<&str> = cpuinfo
+                .lines()
+                .find_map(|line| {
+                    let (key, value) = line.split_once(':')?;
+                    (key.trim() == "flags").then_some(value)
+                })
+                .unwrap_or_default()
+                .split_whitespace()
+                .collect();
+            for flag in &profile.cpu_flags {
+                if !flags.contains(&flag.as_str()) {
+                    deviate(format!("CPU flag {}", flag), "present".to_string(), "missing".to_string());
+                }
+            }
+        }
+
+        Ok(deviations)
+    }
+}
+
+fn read_trimmed(path: impl AsRef<Path>) -> Option<String> {
+    fs::read_to_string(path).ok().map(|value| value.trim().to_string())
+}
+
+/// `systemctl is-active` per unit, as the host collector reads it
+async fn service_states(services: &[String]) -> Result<Vec<(String, String)>> {
+    if services.is_empty() {
+        return Ok(Vec::new());
+    }
+    let output = Command::new("systemctl")
+        .arg("is-active")
+        .args(services)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .context("Failed to execute systemctl")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(services
+        .iter()
+        .cloned()
+        .zip(stdout.lines().map(String::from).chain(std::iter::repeat("unknown".to_string())))
+        .collect())
+}
+
+/// Interfaces backed by a device (not bridges, bonds, veths or VLANs),
+/// sorted by name, with their link speed when they have a link
+fn physical_nics() -> Vec<(String, Option<u32>)> {
+    let Ok(entries) = fs::read_dir(NET_DIR) else {
+        return Vec::new();
+    };
+    let mut nics: Vec<(String, Option<u32>)> = entries
+        .flatten()
+        .filter(|entry| entry.path().join("device").exists())
+        .map(|entry| {
+            let path = entry.path();
+            let link = read_trimmed(path.join("carrier")).as_deref() == Some("1");
+            // -1 (or unreadable) without a link
+            let speed = link
+                .then(|| read_trimmed(path.join("speed"))?.parse::<i64>().ok())
+                .flatten()
+                .filter(|speed| *speed > 0)
+                .map(|speed| speed as u32);
+            (entry.file_name().to_string_lossy().into_owned(), speed)
+        })
+        .collect();
+    nics.sort();
+    nics
+}
@@ -0,0 +1,216 @@
+use anyhow::{Context, Result};
+use crate::types::{DiskImage, DiskImageKind, DiskReferences};
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File extensions treated as VM disk images
+const IMAGE_EXTENSIONS: [&str; 7] = ["qcow2", "img", "raw", "iso", "vmdk", "vdi", "vhdx"];
+
+/// Scans image directories and local-path provisioner volumes on this host
+pub struct ImageCollector {
+    image_dirs: Vec<PathBuf>,
+    local_path_dirs: Vec<PathBuf>,
+    use_mock: bool,
+}
+
+impl ImageCollector {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            image_dirs: Vec::new(),
+            local_path_dirs: Vec::new(),
+            use_mock: false,
+        })
+    }
+
+    pub fn with_dirs(mut self, image_dirs: &[String], local_path_dirs: &[String]) -> Self {
+        self.image_dirs = image_dirs.iter().map(PathBuf::from).collect();
+        self.local_path_dirs = local_path_dirs.iter().map(PathBuf::from).collect();
+        self
+    }
+
+    /// Image files and local-path volume directories; ownership is resolved
+    /// separately against the cluster with [`resolve_inventory`]
+    pub async fn collect(&mut self) -> Result<Vec<DiskImage>> {
+        if self.use_mock {
+            return Ok(self.collect_mock());
+        }
+        let exists = self.image_dirs.iter().chain(&self.local_path_dirs).any(|d| d.is_dir());
+        if !exists {
+            tracing::warn!("No configured image directory exists, using mock data");
+            self.use_mock = true;
+            return Ok(self.collect_mock());
+        }
+        self.collect_real()
+    }
+
+    fn collect_real(&self) -> Result<Vec<DiskImage>> {
+        let mut images = Vec::new();
+
+        for dir in self.image_dirs.iter().filter(|d| d.is_dir()) {
+            let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+            for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+                let is_image = path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()));
+                let Ok(meta) = fs::metadata(&path) else { continue };
+                if !is_image || !meta.is_file() {
+                    continue;
+                }
+                images.push(DiskImage {
+                    kind: DiskImageKind::HostFile,
+                    location: path.display().to_string(),
+                    // Allocated size, so sparse images count what they really use
+                    size_bytes: allocated_bytes(&meta),
+                    modified: modified(&meta),
+                    used_by: None,
+                });
+            }
+        }
+
+        for dir in self.local_path_dirs.iter().filter(|d| d.is_dir()) {
+            let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+            for path in entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()) {
+                // "pvc-<uid>_<namespace>_<claim>"
+                let Some(pv) = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .filter(|n| n.starts_with("pvc-"))
+                    .map(|n| n.split('_').next().unwrap_or(n).to_string())
+                else {
+                    continue;
+                };
+                images.push(DiskImage {
+                    kind: DiskImageKind::LocalPathVolume { pv },
+                    location: path.display().to_string(),
+                    size_bytes: dir_size(&path),
+                    modified: fs::metadata(&path).ok().as_ref().and_then(modified),
+                    used_by: None,
+                });
+            }
+        }
+
+        Ok(images)
+    }
+
+    fn collect_mock(&self) -> Vec<DiskImage> {
+        let gib = 1_073_741_824;
+        let days_ago = |days| Some(Local::now() - chrono::Duration::days(days));
+        let image = |kind, location: &str, size_bytes, modified| DiskImage {
+            kind,
+            location: location.to_string(),
+            size_bytes,
+            modified,
+            used_by: None,
+        };
+        vec![
+            image(DiskImageKind::HostFile, "/var/lib/libvirt/images/vm-build-01.qcow2", 38 * gib, days_ago(1)),
+            image(DiskImageKind::HostFile, "/var/lib/libvirt/images/nixos-template-2023.qcow2", 120 * gib, days_ago(410)),
+            image(
+                DiskImageKind::LocalPathVolume { pv: "pvc-4c1f0a".to_string() },
+                "/var/lib/rancher/k3s/storage/pvc-4c1f0a_default_vm-webserver-01-rootdisk",
+                17 * gib,
+                days_ago(0),
+            ),
+            image(
+                DiskImageKind::LocalPathVolume { pv: "pvc-77e0b1".to_string() },
+                "/var/lib/rancher/k3s/storage/pvc-77e0b1_default_vm-retired-01-rootdisk",
+                64 * gib,
+                days_ago(95),
+            ),
+        ]
+    }
+}
+
+/// Attribute host images and VM disk claims to whatever still uses them.
+/// `open_disks` are paths held open by running qemu processes.
+pub fn resolve_inventory(
+    host: Vec<DiskImage>,
+    refs: &DiskReferences,
+    open_disks: &HashMap<String, String>,
+) -> Vec<DiskImage> {
+    let bound: HashMap<&str, String> = refs
+        .all_claims
+        .iter()
+        .filter_map(|c| c.volume.as_deref().map(|v| (v, format!("{}/{}", c.namespace, c.name))))
+        .collect();
+
+    let mut images: Vec<DiskImage> = host
+        .into_iter()
+        .map(|mut image| {
+            image.used_by = match image.kind {
+                DiskImageKind::HostFile => refs
+                    .host_paths
+                    .get(&image.location)
+                    .or_else(|| open_disks.get(&image.location))
+                    .cloned(),
+                // The claim itself is checked below; here only a deleted PV counts
+                DiskImageKind::LocalPathVolume { ref pv } => match bound.get(pv.as_str()) {
+                    Some(claim) => Some(format!("claim {}", claim)),
+                    None => refs.volumes.contains(pv).then(|| format!("volume {}", pv)),
+                },
+                DiskImageKind::Claim { .. } => image.used_by,
+            };
+            image
+        })
+        .collect();
+
+    for claim in &refs.all_claims {
+        let key = format!("{}/{}", claim.namespace, claim.name);
+        let used_by = refs.claims.get(&key).cloned().or_else(|| claim.mounted_by.clone());
+        // Only VM disks: claims a VM uses, or ones CDI created for a DataVolume
+        if used_by.is_none() && claim.data_volume.is_none() {
+            continue;
+        }
+        images.push(DiskImage {
+            kind: DiskImageKind::Claim {
+                namespace: claim.namespace.clone(),
+                name: claim.name.clone(),
+                data_volume: claim.data_volume.clone(),
+            },
+            location: key,
+            size_bytes: claim.size_bytes,
+            modified: None,
+            used_by,
+        });
+    }
+
+    // Orphans first, largest first
+    images.sort_by(|a, b| b.is_orphan().cmp(&a.is_orphan()).then(b.size_bytes.cmp(&a.size_bytes)));
+    images
+}
+
+/// Remove an orphaned host image or local-path volume directory
+pub fn delete_host_image(image: &DiskImage) -> Result<()> {
+    let path = Path::new(&image.location);
+    match image.kind {
+        DiskImageKind::HostFile => fs::remove_file(path),
+        DiskImageKind::LocalPathVolume { .. } => fs::remove_dir_all(path),
+        DiskImageKind::Claim { .. } => return Ok(()),
+    }
+    .with_context(|| format!("Failed to delete {}", path.display()))
+}
+
+fn allocated_bytes(meta: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    meta.blocks() * 512
+}
+
+fn modified(meta: &fs::Metadata) -> Option<DateTime<Local>> {
+    meta.modified().ok().map(DateTime::<Local>::from)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            Some(if meta.is_dir() { dir_size(&e.path()) } else { allocated_bytes(&meta) })
+        })
+        .sum()
+}
@@ -0,0 +1,146 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read};
+use std::os::unix::fs::OpenOptionsExt;
+
+use super::oom::parse_kernel_line;
+use crate::types::{HardwareErrorKind, HardwareEvent, LogEntry, OomKill};
+
+/// Lines of the ring buffer backlog handed to the log view on the first
+/// read; the rest is only scanned for hardware errors
+const BACKLOG_LINES: usize = 100;
+/// One record per read(), the kernel caps them well below this
+const RECORD_SIZE: usize = 8192;
+
+/// Follows the kernel ring buffer through /dev/kmsg, recognizing machine
+/// checks, memory and I/O errors, NIC resets and OOM kills as they happen.
+/// Reading it needs root (or CAP_SYSLOG with kernel.dmesg_restrict = 1);
+/// without access the collector stays idle.
+pub struct KmsgCollector {
+    file: Option<File>,
+    opened: bool,
+    /// Wall clock time of boot, to date records stamped since boot
+    boot: DateTime<Local>,
+    /// Recognized lines within `window`, oldest first
+    events: VecDeque<HardwareEvent>,
+    window: Duration,
+    /// OOM kills read since the last `take_oom_kills`, the backlog's included
+    oom_kills: Vec<OomKill>,
+}
+
+impl KmsgCollector {
+    pub fn new() -> Result<Self> {
+        let boot = DateTime::from_timestamp(sysinfo::System::boot_time() as i64, 0)
+            .map(|t| t.with_timezone(&Local))
+            .unwrap_or_else(Local::now);
+        Ok(Self {
+            file: None,
+            opened: false,
+            boot,
+            events: VecDeque::new(),
+            window: Duration::hours(1),
+            oom_kills: Vec::new(),
+        })
+    }
+
+    /// How long a recognized line keeps its alert raised
+    pub fn with_window(mut self, minutes: u64) -> Self {
+        self.window = Duration::minutes(minutes as i64);
+        self
+    }
+
+    /// Recognized lines within the window, oldest first
+    pub fn events(&self) -> impl Iterator<Item = &HardwareEvent> {
+        self.events.iter()
+    }
+
+    /// OOM kills read since the previous call
+    pub fn take_oom_kills(&mut self) -> Vec<OomKill> {
+        std::mem::take(&mut self.oom_kills)
+    }
+
+    /// Records written since the previous call (a tail of the backlog on
+    /// the first one), as log entries from the "kernel" service
+    pub async fn collect(&mut self) -> Result<Vec<LogEntry>> {
+        let backlog = !self.opened;
+        if !self.opened {
+            self.opened = true;
+            match OpenOptions::new().read(true).custom_flags(libc::O_NONBLOCK).open("/dev/kmsg") {
+                Ok(file) => self.file = Some(file),
+                Err(e) => tracing::warn!("Kernel log unavailable, not following /dev/kmsg: {}", e),
+            }
+        }
+        let Some(file) = self.file.as_mut() else {
+            return Ok(Vec::new());
+        };
+
+        let mut entries = Vec::new();
+        let mut buf = vec![0u8; RECORD_SIZE];
+        loop {
+            match file.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    if let Some(entry) = parse_record(&String::from_utf8_lossy(&buf[..n]), self.boot) {
+                        entries.push(entry);
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                // Records were overwritten before they could be read; the
+                // next read continues with the oldest one left
+                Err(e) if e.raw_os_error() == Some(libc::EPIPE) => continue,
+                Err(e) => return Err(e).context("Failed to read /dev/kmsg"),
+            }
+        }
+
+        let cutoff = Local::now() - self.window;
+        for (entry, at) in &entries {
+            parse_kernel_line(&mut self.oom_kills, &entry.message, *at);
+            if *at < cutoff {
+                continue;
+            }
+            if let Some(kind) = HardwareErrorKind::classify(&entry.message) {
+                self.events.push_back(HardwareEvent { kind, at: *at, line: entry.message.clone() });
+            }
+        }
+        while self.events.front().is_some_and(|e| e.at < cutoff) {
+            self.events.pop_front();
+        }
+
+        let mut entries: Vec<LogEntry> = entries.into_iter().map(|(entry, _)| entry).collect();
+        if backlog && entries.len() > BACKLOG_LINES {
+            entries.drain(..entries.len() - BACKLOG_LINES);
+        }
+        Ok(entries)
+    }
+}
+
+/// Parse one /dev/kmsg record, "priority,sequence,usec,flags;message"
+/// followed by indented key=value continuation lines
+fn parse_record(record: &str, boot: DateTime<Local>) -> Option<(LogEntry, DateTime<Local>)> {
+    let (header, rest) = record.split_once(';')?;
+    let mut fields = header.split(',');
+    let priority: u32 = fields.next()?.parse().ok()?;
+    let usec: i64 = fields.nth(1)?.parse().ok()?;
+    let message = rest.lines().next()?.trim_end().to_string();
+    let at = boot + Duration::microseconds(usec);
+
+    // Same mapping as journald priorities
+    let level = match priority & 7 {
+        0..=3 => "ERROR",
+        4 => "WARN",
+        7 => "DEBUG",
+        _ => "INFO",
+    };
+    Some((
+        LogEntry {
+            timestamp: at.format("%b %d %H:%M:%S").to_string(),
+            level: level.to_string(),
+            service: "kernel".to_string(),
+            message,
+            ..Default::default()
+        },
+        at,
+    ))
+}
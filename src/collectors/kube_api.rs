@@ -0,0 +1,253 @@
+use crate::config::KubeApiConfig;
+use crate::types::ApiCallRate;
+use futures::TryStreamExt;
+use kube::api::{ListParams, WatchEvent, WatchParams};
+use kube::{Api, Resource, ResourceExt};
+use serde::de::DeserializeOwned;
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A watch stops once no change has arrived for this long (it has caught up)
+const WATCH_QUIET: Duration = Duration::from_millis(200);
+/// Upper bound on one delta fetch; the next one resumes where it stopped
+const WATCH_DEADLINE: Duration = Duration::from_secs(2);
+/// Server-side timeout so abandoned watches don't linger on the apiserver
+const WATCH_SERVER_TIMEOUT_SECS: u32 = 10;
+/// Window the debug readout counts calls over
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CallKind {
+    /// Full listing
+    List,
+    /// Delta fetch from the last resourceVersion
+    Watch,
+    /// Get/patch of a single object
+    Request,
+    /// Served from the cache without touching the apiserver
+    Cached,
+}
+
+/// A listing kept up to date by watching from its resourceVersion
+struct CachedList<K> {
+    /// By "namespace/name", matching the apiserver's listing order
+    objects: BTreeMap<String, K>,
+    resource_version: Option<String>,
+    fetched: Instant,
+}
+
+/// Token bucket allowing `burst` calls at once and `qps` sustained
+struct RateLimiter {
+    tokens: f64,
+    updated: Instant,
+    qps: f64,
+    burst: f64,
+}
+
+impl RateLimiter {
+    /// Take a token, or return how long until one is available
+    fn try_take(&mut self) -> Option<Duration> {
+        if self.qps <= 0.0 {
+            return None;
+        }
+        let now = Instant::now();
+        self.tokens = (self.tokens + (now - self.updated).as_secs_f64() * self.qps).min(self.burst);
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / self.qps))
+        }
+    }
+}
+
+type CacheSlot = Arc<tokio::sync::Mutex<Option<Box<dyn Any + Send>>>>;
+
+/// Client-side caching and rate limiting in front of the apiserver, so
+/// repeated full listings on a large cluster don't hammer it. Listings are
+/// reused for a per-resource TTL; once stale, watchable resources only
+/// fetch what changed since their resourceVersion, and everything else is
+/// listed again.
+#[derive(Clone)]
+pub struct KubeApiLayer {
+    config: Arc<KubeApiConfig>,
+    limiter: Arc<Mutex<RateLimiter>>,
+    /// One slot per listing; holding its lock across the fetch makes
+    /// concurrent callers wait for that fetch instead of repeating it
+    cache: Arc<Mutex<HashMap<String, CacheSlot>>>,
+    calls: Arc<Mutex<VecDeque<(Instant, String, CallKind)>>>,
+}
+
+impl KubeApiLayer {
+    pub fn new(config: KubeApiConfig) -> Self {
+        let burst = f64::from(config.burst.max(1));
+        Self {
+            limiter: Arc::new(Mutex::new(RateLimiter {
+                tokens: burst,
+                updated: Instant::now(),
+                qps: config.qps,
+                burst,
+            })),
+            config: Arc::new(config),
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            calls: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// List `resource` through the cache. `watchable` is false for APIs
+    /// without watch support (metrics.k8s.io), which are always relisted.
+    pub async fn list<K>(
+        &self,
+        api: &Api<K>,
+        resource: &str,
+        params: &ListParams,
+        watchable: bool,
+    ) -> kube::Result<Vec<K>>
+    where
+        K: Resource + Clone + DeserializeOwned + Debug + Send + 'static,
+    {
+        let key = format!(
+            "{}?{}&{}",
+            resource,
+            params.label_selector.as_deref().unwrap_or_default(),
+            params.field_selector.as_deref().unwrap_or_default()
+        );
+        let slot = self.cache.lock().unwrap().entry(key).or_default().clone();
+        let mut slot = slot.lock().await;
+
+        let cached = slot.as_mut().and_then(|entry| entry.downcast_mut::<CachedList<K>>());
+        if let Some(cached) = cached {
+            if cached.fetched.elapsed() < self.ttl(resource) {
+                self.record(resource, CallKind::Cached);
+                return Ok(cached.objects.values().cloned().collect());
+            }
+            if let (true, Some(version)) = (watchable, cached.resource_version.clone()) {
+                match self.watch_changes(api, resource, params, &version, cached).await {
+                    Ok(()) => {
+                        cached.fetched = Instant::now();
+                        return Ok(cached.objects.values().cloned().collect());
+                    }
+                    // Usually 410 Gone: the version was compacted away
+                    Err(e) => tracing::debug!("Watch of {} failed, relisting: {}", resource, e),
+                }
+            }
+        }
+
+        self.acquire(resource, CallKind::List).await;
+        let list = api.list(params).await?;
+        let cached = CachedList {
+            objects: list.items.into_iter().map(|o| (object_key(&o), o)).collect(),
+            resource_version: list.metadata.resource_version,
+            fetched: Instant::now(),
+        };
+        let objects = cached.objects.values().cloned().collect();
+        *slot = Some(Box::new(cached));
+        Ok(objects)
+    }
+
+    /// Apply the changes since `version` to a cached listing, until the
+    /// watch goes quiet or the deadline passes
+    async fn watch_changes<K>(
+        &self,
+        api: &Api<K>,
+        resource: &str,
+        params: &ListParams,
+        version: &str,
+        cached: &mut CachedList<K>,
+    ) -> kube::Result<()>
+    where
+        K: Resource + Clone + DeserializeOwned + Debug + Send + 'static,
+    {
+        let mut watch = WatchParams::default().timeout(WATCH_SERVER_TIMEOUT_SECS);
+        watch.label_selector = params.label_selector.clone();
+        watch.field_selector = params.field_selector.clone();
+
+        self.acquire(resource, CallKind::Watch).await;
+        let stream = api.watch(&watch, version).await?;
+        futures::pin_mut!(stream);
+
+        let deadline = Instant::now() + WATCH_DEADLINE;
+        while Instant::now() < deadline {
+            let event = match tokio::time::timeout(WATCH_QUIET, stream.try_next()).await {
+                Ok(event) => event?,
+                Err(_) => break,
+            };
+            match event {
+                Some(WatchEvent::Added(object)) | Some(WatchEvent::Modified(object)) => {
+                    cached.resource_version = object.resource_version();
+                    cached.objects.insert(object_key(&object), object);
+                }
+                Some(WatchEvent::Deleted(object)) => {
+                    cached.resource_version = object.resource_version();
+                    cached.objects.remove(&object_key(&object));
+                }
+                Some(WatchEvent::Bookmark(bookmark)) => {
+                    cached.resource_version = Some(bookmark.metadata.resource_version);
+                }
+                Some(WatchEvent::Error(e)) => return Err(kube::Error::Api(e)),
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Wait for the rate limiter before a get or patch
+    pub async fn request(&self, resource: &str) {
+        self.acquire(resource, CallKind::Request).await;
+    }
+
+    async fn acquire(&self, resource: &str, kind: CallKind) {
+        loop {
+            let wait = self.limiter.lock().unwrap().try_take();
+            match wait {
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => break,
+            }
+        }
+        self.record(resource, kind);
+    }
+
+    fn ttl(&self, resource: &str) -> Duration {
+        let secs = self.config.ttl_secs.get(resource).copied().unwrap_or(self.config.cache_ttl_secs);
+        Duration::from_secs(secs)
+    }
+
+    fn record(&self, resource: &str, kind: CallKind) {
+        let mut calls = self.calls.lock().unwrap();
+        let now = Instant::now();
+        while calls.front().is_some_and(|(at, _, _)| now - *at > RATE_WINDOW) {
+            calls.pop_front();
+        }
+        calls.push_back((now, resource.to_string(), kind));
+    }
+
+    /// Calls per resource over the last minute, busiest first
+    pub fn call_rates(&self) -> Vec<ApiCallRate> {
+        let calls = self.calls.lock().unwrap();
+        let now = Instant::now();
+        let mut rates: BTreeMap<&str, ApiCallRate> = BTreeMap::new();
+        for (_, resource, kind) in calls.iter().filter(|(at, _, _)| now - *at <= RATE_WINDOW) {
+            let rate = rates.entry(resource).or_insert_with(|| ApiCallRate {
+                resource: resource.clone(),
+                ..ApiCallRate::default()
+            });
+            match kind {
+                CallKind::List => rate.lists += 1,
+                CallKind::Watch => rate.watches += 1,
+                CallKind::Request => rate.requests += 1,
+                CallKind::Cached => rate.cached += 1,
+            }
+        }
+        let mut rates: Vec<ApiCallRate> = rates.into_values().collect();
+        rates.sort_by_key(|rate| std::cmp::Reverse(rate.api_calls()));
+        rates
+    }
+}
+
+fn object_key<K: Resource>(object: &K) -> String {
+    format!("{}/{}", object.namespace().unwrap_or_default(), object.name_any())
+}
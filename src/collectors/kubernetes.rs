@@ -1,21 +1,62 @@
-use anyhow::Result;
-use crate::types::{K8sClusterInfo, KubeVirtInfo};
-use kube::{Client, Api, config::{Config, KubeConfigOptions}};
-use k8s_openapi::api::core::v1::{Node, Pod, Service};
-use std::path::PathBuf;
+use anyhow::{Result, Context};
+use crate::types::{ApiHealth, BootRecord, DataVolume, ImagePull, MigrationRecord, ClusterFlavor, K8sClusterInfo, KubeVirtInfo, K8sResource, ResourceKind, GuestClock, GuestInfo, GuestUser, GuestFilesystem, CpuTuning, VmHotplug, VmUsage, KubeletStatus, NodeConditionInfo, NodeResource, NodeStatus, NodeReachability, QuotaUsage, ClaimInfo, DiskReferences, ServiceHealth, ServiceEndpoints, IngressRoute, VmAction, NodeMetadata, NodePod, NodeTaint, LabelSelector, Permission, PermissionCheck, NamespacePolicies, PodHealth, PodWaiting, PolicyAccess, TenantUsage, VmNetworkPolicy};
+use kube::{Client, Api, ResourceExt, config::{Config, KubeConfigOptions}};
+use k8s_openapi::api::core::v1::{Event, Namespace, Node, PersistentVolume, PersistentVolumeClaim, Pod, ResourceQuota, Secret, Service};
+use k8s_openapi::api::discovery::v1::EndpointSlice;
+use k8s_openapi::api::networking::v1::{Ingress, NetworkPolicy, NetworkPolicyPeer, NetworkPolicyPort, NetworkPolicySpec};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use super::kube_api::KubeApiLayer;
+use super::vm_console::VmConsoles;
+use super::vm_vnc::{console_endpoints, VncForwards};
+use super::virt_backend::ExternalVirt;
+use crate::config::{ConsoleConfig, KubeApiConfig, VirtualizationConfig};
+use crate::types::{ApiCallRate, ConsoleCapture, ConsoleEndpoint, ConsoleKind, VncForward};
 
+/// Waiting reasons of a container that keeps failing to start
+const POD_BACKOFF_REASONS: [&str; 3] = ["CrashLoopBackOff", "ImagePullBackOff", "ErrImagePull"];
+
+/// Guest agents queried at once by the clock and filesystem checks
+const GUEST_AGENT_CONCURRENCY: usize = 4;
+
+#[derive(Clone)]
 pub struct KubernetesCollector {
     client: Option<Client>,
     use_mock: bool,
     kubeconfig_path: Option<PathBuf>,
+    flavor: ClusterFlavor,
+    /// Cache and rate limiter every listing goes through
+    api: KubeApiLayer,
+    /// Background serial console capture per VM
+    consoles: VmConsoles,
+    /// `virtctl vnc` port-forwards started from the VM detail popup
+    vnc: VncForwards,
+    /// Answers for VMs instead of KubeVirt when another backend is configured
+    virt: Option<ExternalVirt>,
+    /// notAfter per TLS secret (namespace, name), with the resourceVersion
+    /// it was read from
+    cert_expiries: CertExpiries,
 }
 
+type CertExpiries = Arc<Mutex<HashMap<(String, String), (String, Option<chrono::DateTime<chrono::Utc>>)>>>;
+
 impl KubernetesCollector {
     pub fn new() -> Self {
         Self {
             client: None,
             use_mock: false,
             kubeconfig_path: None,
+            flavor: ClusterFlavor::K3s,
+            api: KubeApiLayer::new(KubeApiConfig::default()),
+            consoles: VmConsoles::new(ConsoleConfig::default()),
+            vnc: VncForwards::new(ConsoleConfig::default()),
+            virt: None,
+            cert_expiries: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -24,10 +65,49 @@ impl KubernetesCollector {
         self
     }
 
+    pub fn with_flavor(mut self, flavor: ClusterFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    pub fn with_api_config(mut self, config: KubeApiConfig) -> Self {
+        self.api = KubeApiLayer::new(config);
+        self
+    }
+
+    pub fn with_console_config(mut self, config: ConsoleConfig) -> Self {
+        self.vnc = VncForwards::new(config.clone());
+        self.consoles = VmConsoles::new(config);
+        self
+    }
+
+    pub fn with_virtualization(mut self, config: &VirtualizationConfig) -> Self {
+        self.virt = ExternalVirt::from_config(config);
+        self
+    }
+
+    /// Whether VMs come from Proxmox or QMP rather than KubeVirt
+    pub fn has_external_virt(&self) -> bool {
+        self.virt.is_some()
+    }
+
+    /// API calls per resource over the last minute, for the debug overlay
+    pub fn api_call_rates(&self) -> Vec<ApiCallRate> {
+        self.api.call_rates()
+    }
+
+    /// Whether a cluster was reached; the collector serves mock data otherwise
+    pub fn is_connected(&self) -> bool {
+        self.client.is_some() && !self.use_mock
+    }
+
     pub async fn init(&mut self) -> Result<()> {
         // Try to initialize k8s client
         match self.init_client().await {
             Ok(client) => {
+                if self.consoles.capture_all() {
+                    self.consoles.discover(client.clone(), self.api.clone());
+                }
                 self.client = Some(client);
                 Ok(())
             }
@@ -52,11 +132,11 @@ impl KubernetesCollector {
             }
         }
 
-        // Try default k3s kubeconfig
-        let k3s_path = PathBuf::from("/etc/rancher/k3s/k3s.yaml");
-        if k3s_path.exists() {
+        // Try the distribution's default admin kubeconfig
+        let flavor_path = PathBuf::from(self.flavor.kubeconfig_path());
+        if flavor_path.exists() {
             let config = Config::from_custom_kubeconfig(
-                kube::config::Kubeconfig::read_from(k3s_path)?,
+                kube::config::Kubeconfig::read_from(flavor_path)?,
                 &KubeConfigOptions::default(),
             )
             .await?;
@@ -73,6 +153,57 @@ impl KubernetesCollector {
         Ok(Client::try_from(config)?)
     }
 
+    /// Time a GET /healthz and a one-item list (which has to reach storage),
+    /// and read the apiserver's etcd check. Probes bypass the listing cache;
+    /// the rate limiter is waited on before each timer starts.
+    pub async fn probe_api_health(&self) -> Result<ApiHealth> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(Self::mock_api_health());
+        }
+        let client = self.client.as_ref().unwrap();
+        let mut health = ApiHealth::default();
+        let mut errors = Vec::new();
+
+        self.api.request("healthz").await;
+        let started = std::time::Instant::now();
+        match client.request_text(http::Request::get("/healthz").body(Vec::new())?).await {
+            Ok(_) => health.healthz_ms = Some(started.elapsed().as_secs_f64() * 1000.0),
+            Err(e) => errors.push(format!("/healthz: {}", e)),
+        }
+
+        let namespaces: Api<Namespace> = Api::all(client.clone());
+        self.api.request("namespaces").await;
+        let started = std::time::Instant::now();
+        match namespaces.list(&kube::api::ListParams::default().limit(1)).await {
+            Ok(_) => health.list_ms = Some(started.elapsed().as_secs_f64() * 1000.0),
+            Err(e) => errors.push(format!("list namespaces: {}", e)),
+        }
+
+        self.api.request("healthz").await;
+        health.etcd_ok = match client.request_text(http::Request::get("/healthz/etcd").body(Vec::new())?).await {
+            Ok(_) => Some(true),
+            // Not every distribution registers an etcd check (e.g. kine/sqlite)
+            Err(kube::Error::Api(e)) if e.code == 404 => None,
+            Err(e) => {
+                errors.push(format!("etcd: {}", e));
+                Some(false)
+            }
+        };
+
+        health.error = (!errors.is_empty()).then(|| errors.join("; "));
+        Ok(health)
+    }
+
+    fn mock_api_health() -> ApiHealth {
+        ApiHealth {
+            healthz_ms: Some(4.2 + rand::random::<f64>() * 3.0),
+            list_ms: Some(11.5 + rand::random::<f64>() * 8.0),
+            etcd_ok: Some(true),
+            p99_ms: None,
+            error: None,
+        }
+    }
+
     pub async fn collect_cluster_info(&self) -> Result<K8sClusterInfo> {
         if self.use_mock || self.client.is_none() {
             return Ok(self.mock_cluster_info());
@@ -82,11 +213,10 @@ impl KubernetesCollector {
 
         // Get nodes
         let nodes: Api<Node> = Api::all(client.clone());
-        let node_list = nodes.list(&Default::default()).await?;
+        let node_list = self.api.list(&nodes, "nodes", &Default::default(), true).await?;
 
-        let nodes_total = node_list.items.len() as u32;
+        let nodes_total = node_list.len() as u32;
         let nodes_ready = node_list
-            .items
             .iter()
             .filter(|node| {
                 node.status
@@ -103,10 +233,9 @@ impl KubernetesCollector {
 
         // Get pods
         let pods: Api<Pod> = Api::all(client.clone());
-        let pod_list = pods.list(&Default::default()).await?;
+        let pod_list = self.api.list(&pods, "pods", &Default::default(), true).await?;
 
         let pods_running = pod_list
-            .items
             .iter()
             .filter(|pod| {
                 pod.status
@@ -119,8 +248,8 @@ impl KubernetesCollector {
 
         // Get services
         let services: Api<Service> = Api::all(client.clone());
-        let service_list = services.list(&Default::default()).await?;
-        let services_count = service_list.items.len() as u32;
+        let service_list = self.api.list(&services, "services", &Default::default(), true).await?;
+        let services_count = service_list.len() as u32;
 
         Ok(K8sClusterInfo {
             nodes_ready,
@@ -131,6 +260,9 @@ impl KubernetesCollector {
     }
 
     pub async fn collect_kubevirt_info(&self) -> Result<KubeVirtInfo> {
+        if let Some(ref virt) = self.virt {
+            return virt.collect_info().await;
+        }
         if self.use_mock || self.client.is_none() {
             return Ok(self.mock_kubevirt_info());
         }
@@ -164,13 +296,16 @@ impl KubernetesCollector {
 
         if let Some((ar, _caps)) = discovery.resolve_gvk(&gvk) {
             let api: Api<DynamicObject> = Api::all_with(client.clone(), &ar);
-            let vmi_list = api.list(&Default::default()).await?;
+            let vmi_list = self
+                .api
+                .list(&api, "virtualmachineinstances", &Default::default(), true)
+                .await?;
 
             let mut running = 0;
             let mut stopped = 0;
             let mut migrating = 0;
 
-            for vmi in vmi_list.items {
+            for vmi in vmi_list {
                 if let Some(status) = vmi.data.get("status") {
                     if let Some(phase) = status.get("phase").and_then(|p| p.as_str()) {
                         match phase {
@@ -198,20 +333,3218 @@ impl KubernetesCollector {
         }
     }
 
-    fn mock_cluster_info(&self) -> K8sClusterInfo {
-        K8sClusterInfo {
-            nodes_ready: 3,
-            nodes_total: 3,
-            pods_running: 45,
-            services: 23,
+    /// Ready endpoints per Service and the hostnames routed to them
+    pub async fn collect_service_health(&self) -> Result<ServiceHealth> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_service_health());
         }
+
+        let client = self.client.as_ref().unwrap();
+
+        let services: Api<Service> = Api::all(client.clone());
+        let slices: Api<EndpointSlice> = Api::all(client.clone());
+        let params = Default::default();
+        let (service_list, slice_list) = tokio::try_join!(
+            self.api.list(&services, "services", &params, true),
+            self.api.list(&slices, "endpointslices", &params, true)
+        )?;
+
+        // (namespace, service) -> (ready, not ready)
+        let mut counts: HashMap<(String, String), (u32, u32)> = HashMap::new();
+        for slice in slice_list {
+            let Some(service) = slice.labels().get("kubernetes.io/service-name").cloned() else {
+                continue;
+            };
+            let entry = counts.entry((slice.namespace().unwrap_or_default(), service)).or_default();
+            for endpoint in &slice.endpoints {
+                // A missing ready condition means ready, per the EndpointSlice API
+                let ready = endpoint.conditions.as_ref().and_then(|c| c.ready).unwrap_or(true);
+                if ready {
+                    entry.0 += 1;
+                } else {
+                    entry.1 += 1;
+                }
+            }
+        }
+
+        let mut endpoints: Vec<ServiceEndpoints> = service_list
+            .iter()
+            .filter(|svc| {
+                // ExternalName services and selector-less services have no managed endpoints
+                svc.spec.as_ref().is_some_and(|s| {
+                    s.type_.as_deref() != Some("ExternalName") && s.selector.is_some()
+                })
+            })
+            .map(|svc| {
+                let namespace = svc.namespace().unwrap_or_default();
+                let name = svc.name_any();
+                let (ready, not_ready) = counts.get(&(namespace.clone(), name.clone())).copied().unwrap_or_default();
+                ServiceEndpoints { namespace, name, ready, not_ready, vips: service_vips(svc) }
+            })
+            .collect();
+        endpoints.sort_by(|a, b| (a.ready, &a.namespace, &a.name).cmp(&(b.ready, &b.namespace, &b.name)));
+
+        let mut routes = self.collect_ingresses(client).await?;
+        routes.extend(self.collect_http_routes(client).await.unwrap_or_default());
+        routes.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+
+        Ok(ServiceHealth { endpoints, routes })
     }
 
-    fn mock_kubevirt_info(&self) -> KubeVirtInfo {
-        KubeVirtInfo {
-            vms_running: 12,
-            vms_stopped: 3,
-            vms_migrating: 0,
+    async fn collect_ingresses(&self, client: &Client) -> Result<Vec<IngressRoute>> {
+        let ingresses: Api<Ingress> = Api::all(client.clone());
+        let mut routes = Vec::new();
+
+        for ingress in self.api.list(&ingresses, "ingresses", &Default::default(), true).await? {
+            let namespace = ingress.namespace().unwrap_or_default();
+            let spec = ingress.spec.clone().unwrap_or_default();
+
+            let mut hosts: Vec<String> = spec
+                .rules
+                .iter()
+                .flatten()
+                .filter_map(|r| r.host.clone())
+                .collect();
+            hosts.sort_unstable();
+            hosts.dedup();
+
+            // First TLS secret wins; most ingresses use a single certificate
+            let secret = spec.tls.iter().flatten().find_map(|t| t.secret_name.clone());
+            let cert_expiry = match secret {
+                Some(secret) => self.certificate_expiry(client, &namespace, &secret).await,
+                None => None,
+            };
+
+            routes.push(IngressRoute {
+                kind: "Ingress",
+                namespace,
+                name: ingress.name_any(),
+                hosts,
+                cert_expiry,
+            });
+        }
+
+        Ok(routes)
+    }
+
+    async fn collect_http_routes(&self, client: &Client) -> Result<Vec<IngressRoute>> {
+        use kube::api::{ApiResource, DynamicObject, GroupVersionKind};
+
+        let gvk = GroupVersionKind::gvk("gateway.networking.k8s.io", "v1", "HTTPRoute");
+        let api: Api<DynamicObject> = Api::all_with(client.clone(), &ApiResource::from_gvk(&gvk));
+
+        // Gateway API is optional; a 404 here just means it isn't installed
+        let list = self.api.list(&api, "httproutes", &Default::default(), true).await?;
+        Ok(list
+            .into_iter()
+            .map(|route| IngressRoute {
+                kind: "HTTPRoute",
+                namespace: route.namespace().unwrap_or_default(),
+                name: route.name_any(),
+                hosts: route
+                    .data
+                    .pointer("/spec/hostnames")
+                    .and_then(|h| h.as_array())
+                    .map(|h| h.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                    .unwrap_or_default(),
+                // Certificates live on the parent Gateway listener, not the route
+                cert_expiry: None,
+            })
+            .collect())
+    }
+
+    /// notAfter of the certificate in a kubernetes.io/tls secret, read
+    /// again only when the secret's resourceVersion changes
+    async fn certificate_expiry(
+        &self,
+        client: &Client,
+        namespace: &str,
+        secret: &str,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        let secrets: Api<Secret> = Api::namespaced(client.clone(), namespace);
+        self.api.request("secrets").await;
+        let secret = secrets.get(secret).await.ok()?;
+        let key = (namespace.to_string(), secret.name_any());
+        let version = secret.resource_version().unwrap_or_default();
+
+        let mut expiries = self.cert_expiries.lock().unwrap();
+        if let Some((cached, expiry)) = expiries.get(&key) {
+            if *cached == version {
+                return *expiry;
+            }
+        }
+        let expiry = secret
+            .data
+            .as_ref()
+            .and_then(|data| data.get("tls.crt"))
+            .and_then(|pem| certificate_not_after(&pem.0));
+        expiries.insert(key, (version, expiry));
+        expiry
+    }
+
+    /// List objects of a given kind for the resource browser
+    pub async fn collect_resources(&self, kind: ResourceKind) -> Result<Vec<K8sResource>> {
+        if let (ResourceKind::VirtualMachine, Some(virt)) = (kind, &self.virt) {
+            let mut vms = virt.collect_vms().await?;
+            vms.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+            return Ok(vms);
+        }
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_resources(kind));
+        }
+
+        let client = self.client.as_ref().unwrap();
+
+        let mut resources = match kind {
+            ResourceKind::Node => {
+                let api: Api<Node> = Api::all(client.clone());
+                self.api
+                    .list(&api, "nodes", &Default::default(), true)
+                    .await?
+                    .into_iter()
+                    .map(|node| {
+                        let ready = node.status
+                            .as_ref()
+                            .and_then(|s| s.conditions.as_ref())
+                            .map(|conditions| {
+                                conditions.iter().any(|c| c.type_ == "Ready" && c.status == "True")
+                            })
+                            .unwrap_or(false);
+                        K8sResource {
+                            kind,
+                            name: node.name_any(),
+                            namespace: None,
+                            status: if ready { "Ready" } else { "NotReady" }.to_string(),
+                            labels: node.labels().clone(),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            }
+            ResourceKind::Pod => {
+                let api: Api<Pod> = Api::all(client.clone());
+                self.api
+                    .list(&api, "pods", &Default::default(), true)
+                    .await?
+                    .into_iter()
+                    .map(|pod| K8sResource {
+                        kind,
+                        name: pod.name_any(),
+                        namespace: pod.namespace(),
+                        status: pod.status
+                            .as_ref()
+                            .and_then(|s| s.phase.clone())
+                            .unwrap_or_else(|| "Unknown".to_string()),
+                        labels: pod.labels().clone(),
+                    })
+                    .collect()
+            }
+            ResourceKind::Service => {
+                let api: Api<Service> = Api::all(client.clone());
+                self.api
+                    .list(&api, "services", &Default::default(), true)
+                    .await?
+                    .into_iter()
+                    .map(|svc| K8sResource {
+                        kind,
+                        name: svc.name_any(),
+                        namespace: svc.namespace(),
+                        status: svc.spec
+                            .as_ref()
+                            .and_then(|s| s.type_.clone())
+                            .unwrap_or_else(|| "ClusterIP".to_string()),
+                        labels: svc.labels().clone(),
+                    })
+                    .collect()
+            }
+            ResourceKind::VirtualMachine => self.collect_virtual_machines(client).await?,
+        };
+
+        resources.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+        Ok(resources)
+    }
+
+    /// CPU and memory utilization of every VM's virt-launcher pod from
+    /// metrics-server, as a percentage of the compute container's requests
+    pub async fn collect_vm_usage(&self) -> Result<Vec<VmUsage>> {
+        use kube::api::{ApiResource, DynamicObject, GroupVersionKind, ListParams};
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_vm_usage());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let vms = self.collect_virtual_machines(client).await?;
+
+        let launchers = ListParams::default().labels("kubevirt.io=virt-launcher");
+        let pods: Api<Pod> = Api::all(client.clone());
+
+        // (namespace, vm) -> (requested cores, requested bytes)
+        let mut requests: HashMap<(String, String), (f64, f64)> = HashMap::new();
+        // pod name -> vm name, to join metrics (which only carry the pod name)
+        let mut pod_vm: HashMap<(String, String), String> = HashMap::new();
+        for pod in self.api.list(&pods, "pods", &launchers, true).await? {
+            let Some(vm) = pod.labels().get("vm.kubevirt.io/name").cloned() else {
+                continue;
+            };
+            let namespace = pod.namespace().unwrap_or_default();
+            let compute = pod
+                .spec
+                .as_ref()
+                .and_then(|s| s.containers.iter().find(|c| c.name == "compute"))
+                .and_then(|c| c.resources.clone())
+                .unwrap_or_default();
+            let request = |key: &str| {
+                compute
+                    .requests
+                    .as_ref()
+                    .and_then(|r| r.get(key))
+                    .or_else(|| compute.limits.as_ref().and_then(|l| l.get(key)))
+                    .map(|q| q.0.clone())
+            };
+            let cpu = request("cpu").and_then(|q| parse_cpu_quantity(&q)).unwrap_or(0.0);
+            let memory = request("memory").and_then(|q| parse_memory_quantity(&q)).unwrap_or(0.0);
+
+            pod_vm.insert((namespace.clone(), pod.name_any()), vm.clone());
+            requests.insert((namespace, vm), (cpu, memory));
+        }
+
+        // metrics-server is optional; without it VMs are shown without values
+        let gvk = GroupVersionKind::gvk("metrics.k8s.io", "v1beta1", "PodMetrics");
+        let metrics_api: Api<DynamicObject> =
+            Api::all_with(client.clone(), &ApiResource::from_gvk_with_plural(&gvk, "pods"));
+        let mut usage: HashMap<(String, String), (f64, f64)> = HashMap::new();
+        match self.api.list(&metrics_api, "pods.metrics.k8s.io", &launchers, false).await {
+            Ok(list) => {
+                for metrics in list {
+                    let namespace = metrics.namespace().unwrap_or_default();
+                    let Some(vm) = pod_vm.get(&(namespace.clone(), metrics.name_any())) else {
+                        continue;
+                    };
+                    let containers = metrics.data.get("containers").and_then(|c| c.as_array());
+                    let (mut cpu, mut memory) = (0.0, 0.0);
+                    for container in containers.into_iter().flatten() {
+                        let value = |key: &str| {
+                            container.pointer(&format!("/usage/{}", key)).and_then(|v| v.as_str())
+                        };
+                        cpu += value("cpu").and_then(parse_cpu_quantity).unwrap_or(0.0);
+                        memory += value("memory").and_then(parse_memory_quantity).unwrap_or(0.0);
+                    }
+                    usage.insert((namespace, vm.clone()), (cpu, memory));
+                }
+            }
+            Err(e) => tracing::debug!("Pod metrics unavailable: {}", e),
+        }
+
+        let percent = |used: f64, requested: f64| (requested > 0.0).then(|| used / requested * 100.0);
+        Ok(vms
+            .into_iter()
+            .map(|vm| {
+                let key = (vm.namespace.clone().unwrap_or_default(), vm.name.clone());
+                let (cpu_percent, memory_percent) = match (usage.get(&key), requests.get(&key)) {
+                    (Some(&(cpu, memory)), Some(&(cpu_req, memory_req))) => {
+                        (percent(cpu, cpu_req), percent(memory, memory_req))
+                    }
+                    _ => (None, None),
+                };
+                VmUsage {
+                    namespace: key.0,
+                    name: key.1,
+                    status: vm.status,
+                    cpu_percent,
+                    memory_percent,
+                }
+            })
+            .collect())
+    }
+
+    /// VMs and pods summed per value of the `label` label, taken from the
+    /// object or else its namespace; unlabeled ones go to "(none)"
+    pub async fn collect_tenant_usage(&self, label: &str) -> Result<Vec<TenantUsage>> {
+        use kube::api::{ApiResource, DynamicObject, GroupVersionKind};
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_tenant_usage());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let params = Default::default();
+        let gvk = GroupVersionKind::gvk("kubevirt.io", "v1", "VirtualMachine");
+        let vms: Api<DynamicObject> =
+            Api::all_with(client.clone(), &ApiResource::from_gvk_with_plural(&gvk, "virtualmachines"));
+        let namespaces: Api<Namespace> = Api::all(client.clone());
+        let claims: Api<PersistentVolumeClaim> = Api::all(client.clone());
+        let pods: Api<Pod> = Api::all(client.clone());
+        let (namespace_list, claim_list, pod_list) = tokio::try_join!(
+            self.api.list(&namespaces, "namespaces", &params, true),
+            self.api.list(&claims, "persistentvolumeclaims", &params, true),
+            self.api.list(&pods, "pods", &params, true)
+        )
+        .context("Failed to list tenant workloads")?;
+        // Without KubeVirt tenants only have pods
+        let vm_list = self.api.list(&vms, "virtualmachines", &params, true).await.unwrap_or_default();
+
+        let namespace_tenant: HashMap<String, String> = namespace_list
+            .iter()
+            .filter_map(|ns| Some((ns.name_any(), ns.labels().get(label)?.clone())))
+            .collect();
+        let tenant_of = |labels: &BTreeMap<String, String>, namespace: &str| {
+            labels
+                .get(label)
+                .or_else(|| namespace_tenant.get(namespace))
+                .cloned()
+                .unwrap_or_else(|| "(none)".to_string())
+        };
+        let claim_size: HashMap<String, u64> = claim_list
+            .iter()
+            .map(|claim| {
+                let size = claim
+                    .status
+                    .as_ref()
+                    .and_then(|s| s.capacity.as_ref())
+                    .and_then(|c| c.get("storage"))
+                    .or_else(|| {
+                        claim.spec.as_ref().and_then(|s| s.resources.as_ref()).and_then(|r| r.requests.as_ref()).and_then(|r| r.get("storage"))
+                    })
+                    .and_then(|q| parse_memory_quantity(&q.0))
+                    .unwrap_or(0.0);
+                (format!("{}/{}", claim.namespace().unwrap_or_default(), claim.name_any()), size as u64)
+            })
+            .collect();
+
+        let mut tenants: BTreeMap<String, TenantUsage> = BTreeMap::new();
+        // Claims already counted, as a VM's disk is also mounted by its launcher
+        let mut counted: HashSet<String> = HashSet::new();
+        let mut add_claim = |usage: &mut TenantUsage, key: String| {
+            if let Some(&size) = claim_size.get(&key) {
+                if counted.insert(key) {
+                    usage.storage_bytes += size;
+                }
+            }
+        };
+
+        for vm in &vm_list {
+            let namespace = vm.namespace().unwrap_or_default();
+            let tenant = tenant_of(vm.labels(), &namespace);
+            let usage = tenants.entry(tenant.clone()).or_insert_with(|| TenantUsage { tenant, ..Default::default() });
+            let spec = vm.data.pointer("/spec/template/spec");
+            let domain = spec.and_then(|s| s.get("domain"));
+            let topology = |key: &str| {
+                domain.and_then(|d| d.pointer(&format!("/cpu/{}", key))).and_then(|v| v.as_u64()).unwrap_or(1) as u32
+            };
+            let requested = |key: &str| {
+                domain
+                    .and_then(|d| d.pointer(&format!("/resources/requests/{}", key)))
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            };
+            usage.vcpus += if domain.is_some_and(|d| d.get("cpu").is_some()) {
+                topology("sockets") * topology("cores") * topology("threads")
+            } else {
+                requested("cpu").and_then(|q| parse_cpu_quantity(&q)).map_or(1, |cores| cores.ceil() as u32)
+            };
+            let memory = domain
+                .and_then(|d| d.pointer("/memory/guest"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .or_else(|| requested("memory"));
+            usage.memory_bytes += memory.and_then(|q| parse_memory_quantity(&q)).unwrap_or(0.0) as u64;
+            usage.vms += 1;
+            if vm.data.pointer("/status/printableStatus").and_then(|s| s.as_str()) == Some("Running") {
+                usage.running_vms += 1;
+            }
+            for volume in spec.and_then(|s| s.get("volumes")).and_then(|v| v.as_array()).into_iter().flatten() {
+                let claim = volume
+                    .pointer("/persistentVolumeClaim/claimName")
+                    .or_else(|| volume.pointer("/dataVolume/name"))
+                    .and_then(|n| n.as_str());
+                if let Some(claim) = claim {
+                    add_claim(usage, format!("{}/{}", namespace, claim));
+                }
+            }
+        }
+
+        for pod in &pod_list {
+            let phase = pod.status.as_ref().and_then(|s| s.phase.as_deref()).unwrap_or("");
+            if matches!(phase, "Succeeded" | "Failed") {
+                continue;
+            }
+            let namespace = pod.namespace().unwrap_or_default();
+            let launcher = pod.labels().get("kubevirt.io").is_some_and(|v| v == "virt-launcher");
+            let tenant = tenant_of(pod.labels(), &namespace);
+            let usage = tenants.entry(tenant.clone()).or_insert_with(|| TenantUsage { tenant, ..Default::default() });
+            for volume in pod.spec.iter().flat_map(|s| s.volumes.iter().flatten()) {
+                if let Some(claim) = volume.persistent_volume_claim.as_ref() {
+                    add_claim(usage, format!("{}/{}", namespace, claim.claim_name));
+                }
+            }
+            // A launcher's requests are its VM's, counted above
+            if launcher {
+                continue;
+            }
+            usage.pods += 1;
+            for container in pod.spec.iter().flat_map(|s| s.containers.iter()) {
+                let requests = container.resources.as_ref().and_then(|r| r.requests.as_ref());
+                let request = |key: &str| requests.and_then(|r| r.get(key)).map(|q| q.0.clone());
+                usage.pod_cpu_cores += request("cpu").and_then(|q| parse_cpu_quantity(&q)).unwrap_or(0.0);
+                usage.pod_memory_bytes += request("memory").and_then(|q| parse_memory_quantity(&q)).unwrap_or(0.0) as u64;
+            }
+        }
+
+        Ok(tenants.into_values().collect())
+    }
+
+    /// Hard limits and current usage of every ResourceQuota, one entry per
+    /// quota resource
+    pub async fn collect_quotas(&self) -> Result<Vec<QuotaUsage>> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_quotas());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let quotas: Api<ResourceQuota> = Api::all(client.clone());
+        let list = self
+            .api
+            .list(&quotas, "resourcequotas", &Default::default(), true)
+            .await
+            .context("Failed to list resource quotas")?;
+
+        let mut usage = Vec::new();
+        for quota in list {
+            let namespace = quota.namespace().unwrap_or_default();
+            let name = quota.name_any();
+            let Some(status) = quota.status else {
+                continue;
+            };
+            let used = status.used.unwrap_or_default();
+            for (resource, hard) in status.hard.unwrap_or_default() {
+                let mut entry = QuotaUsage {
+                    namespace: namespace.clone(),
+                    quota: name.clone(),
+                    resource,
+                    used: 0.0,
+                    hard: 0.0,
+                };
+                let parse = if entry.is_cpu() { parse_cpu_quantity } else { parse_memory_quantity };
+                let Some(hard) = parse(&hard.0) else {
+                    tracing::debug!("Unparseable quota {}/{} {}: {}", namespace, name, entry.resource, hard.0);
+                    continue;
+                };
+                entry.hard = hard;
+                entry.used = used.get(&entry.resource).and_then(|q| parse(&q.0)).unwrap_or(0.0);
+                usage.push(entry);
+            }
+        }
+
+        Ok(usage)
+    }
+
+    /// Image pulls from Pod events (kubelet's Pulled and Failed), per image
+    /// with the failing ones first
+    pub async fn collect_image_pulls(&self) -> Result<Vec<ImagePull>> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_image_pulls());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let events: Api<Event> = Api::all(client.clone());
+        let params = kube::api::ListParams::default().fields("involvedObject.kind=Pod");
+        let list = self
+            .api
+            .list(&events, "events", &params, true)
+            .await
+            .context("Failed to list events")?;
+
+        let mut pulls: BTreeMap<String, ImagePull> = BTreeMap::new();
+        for event in list {
+            let (Some(reason), Some(message)) = (event.reason.as_deref(), event.message.as_deref()) else {
+                continue;
+            };
+            let succeeded = reason == "Pulled" && message.starts_with("Successfully pulled image");
+            let failed = reason == "Failed" && message.starts_with("Failed to pull image");
+            if !succeeded && !failed {
+                continue;
+            }
+            let Some(image) = message.split('"').nth(1) else {
+                continue;
+            };
+            let at = event
+                .last_timestamp
+                .as_ref()
+                .map(|t| t.0)
+                .or_else(|| event.event_time.as_ref().map(|t| t.0))
+                .or_else(|| event.metadata.creation_timestamp.as_ref().map(|t| t.0))
+                .map(|t| t.with_timezone(&chrono::Local));
+            let count = event.count.unwrap_or(1).max(1) as u32;
+            let node = event.source.as_ref().and_then(|s| s.host.clone()).or(event.reporting_instance.clone());
+
+            let pull = pulls.entry(image.to_string()).or_insert_with(|| ImagePull {
+                image: image.to_string(),
+                registry: image_registry(image).to_string(),
+                pulls: 0,
+                failures: 0,
+                last_pulled: None,
+                last_duration: None,
+                last_failed: None,
+                last_error: None,
+                nodes: Vec::new(),
+            });
+            if let Some(node) = node.filter(|n| !pull.nodes.contains(n)) {
+                pull.nodes.push(node);
+            }
+            if succeeded {
+                pull.pulls += count;
+                if at >= pull.last_pulled {
+                    pull.last_pulled = at;
+                    // "... in 1.234s (1.234s including waiting)"
+                    pull.last_duration = message
+                        .rsplit_once(" in ")
+                        .and_then(|(_, rest)| rest.split_whitespace().next())
+                        .and_then(parse_go_duration);
+                }
+            } else {
+                pull.failures += count;
+                if at >= pull.last_failed {
+                    pull.last_failed = at;
+                    let error = message.split_once("\": ").map_or(message, |(_, error)| error);
+                    pull.last_error = Some(error.to_string());
+                }
+            }
+        }
+
+        let mut pulls: Vec<ImagePull> = pulls.into_values().collect();
+        pulls.sort_by_key(|pull| (!pull.is_failing(), std::cmp::Reverse(pull.failures)));
+        Ok(pulls)
+    }
+
+    /// Pods that have restarted or are stuck in CrashLoopBackOff or an
+    /// image pull backoff; healthy pods are left out
+    pub async fn collect_pod_health(&self) -> Result<Vec<PodHealth>> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_pod_health());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let pods: Api<Pod> = Api::all(client.clone());
+        let list = self
+            .api
+            .list(&pods, "pods", &Default::default(), true)
+            .await
+            .context("Failed to list pods")?;
+
+        let mut unhealthy: Vec<PodHealth> = list
+            .iter()
+            .filter_map(|pod| {
+                let status = pod.status.as_ref()?;
+                // Finished Jobs keep their restart counts but aren't failing
+                if status.phase.as_deref() == Some("Succeeded") {
+                    return None;
+                }
+                let containers = status
+                    .init_container_statuses
+                    .iter()
+                    .flatten()
+                    .chain(status.container_statuses.iter().flatten());
+                let mut restarts = 0;
+                let mut waiting = None;
+                for container in containers {
+                    restarts += container.restart_count.max(0) as u32;
+                    let Some(state) = container.state.as_ref().and_then(|s| s.waiting.as_ref()) else {
+                        continue;
+                    };
+                    let reason = state.reason.as_deref().unwrap_or_default();
+                    if waiting.is_none() && POD_BACKOFF_REASONS.contains(&reason) {
+                        waiting = Some(PodWaiting {
+                            container: container.name.clone(),
+                            reason: reason.to_string(),
+                            message: state.message.clone(),
+                        });
+                    }
+                }
+                if restarts == 0 && waiting.is_none() {
+                    return None;
+                }
+                Some(PodHealth {
+                    namespace: pod.namespace().unwrap_or_default(),
+                    name: pod.name_any(),
+                    node: pod.spec.as_ref().and_then(|s| s.node_name.clone()),
+                    restarts,
+                    waiting,
+                    recent_restarts: 0,
+                })
+            })
+            .collect();
+        unhealthy.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+        Ok(unhealthy)
+    }
+
+    /// Labels of one node, for matching per-node alert overrides
+    pub async fn node_labels(&self, node: &str) -> Result<BTreeMap<String, String>> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(BTreeMap::from([
+                ("kubernetes.io/hostname".to_string(), node.to_string()),
+                ("node-role.kubernetes.io/control-plane".to_string(), "true".to_string()),
+            ]));
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let nodes: Api<Node> = Api::all(client.clone());
+        self.api.request("nodes").await;
+        let node = nodes.get(node).await.with_context(|| format!("Failed to get node {}", node))?;
+        Ok(node.labels().clone())
+    }
+
+    /// ClusterIP of a Service; None without a cluster or for headless ones
+    pub async fn service_cluster_ip(&self, namespace: &str, name: &str) -> Result<Option<std::net::IpAddr>> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(None);
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let services: Api<Service> = Api::namespaced(client.clone(), namespace);
+        self.api.request("services").await;
+        let service = services
+            .get(name)
+            .await
+            .with_context(|| format!("Failed to get service {}/{}", namespace, name))?;
+        Ok(service.spec.and_then(|spec| spec.cluster_ip).and_then(|ip| ip.parse().ok()))
+    }
+
+    /// virt-launcher pods by UID, as namespace/name, to resolve the pod
+    /// owning a qemu process's cgroup
+    pub async fn collect_launcher_pods(&self) -> Result<HashMap<String, String>> {
+        use kube::api::ListParams;
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_launcher_pods());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let pods: Api<Pod> = Api::all(client.clone());
+        let launchers = ListParams::default().labels("kubevirt.io=virt-launcher");
+        let list = self
+            .api
+            .list(&pods, "pods", &launchers, true)
+            .await
+            .context("Failed to list virt-launcher pods")?;
+
+        Ok(list
+            .iter()
+            .filter_map(|pod| {
+                let uid = pod.uid()?;
+                Some((uid, format!("{}/{}", pod.namespace().unwrap_or_default(), pod.name_any())))
+            })
+            .collect())
+    }
+
+    /// Every pod by UID as namespace/name, with the VM (namespace/name) of
+    /// virt-launcher pods, for naming the pods behind kubepods cgroups
+    pub async fn collect_pods_by_uid(&self) -> Result<HashMap<String, (String, Option<String>)>> {
+        use kube::api::ListParams;
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(HashMap::new());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let pods: Api<Pod> = Api::all(client.clone());
+        let list = self
+            .api
+            .list(&pods, "pods", &ListParams::default(), true)
+            .await
+            .context("Failed to list pods")?;
+
+        Ok(list
+            .iter()
+            .filter_map(|pod| {
+                let namespace = pod.namespace().unwrap_or_default();
+                let vm = pod.labels().get("vm.kubevirt.io/name").map(|vm| format!("{}/{}", namespace, vm));
+                Some((pod.uid()?, (format!("{}/{}", namespace, pod.name_any()), vm)))
+            })
+            .collect())
+    }
+
+    /// NetworkPolicies of every namespace running VMs, with what they
+    /// allow in and out of each virt-launcher pod they select
+    pub async fn collect_vm_network_policies(&self) -> Result<Vec<NamespacePolicies>> {
+        use kube::api::ListParams;
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_vm_network_policies());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let pods: Api<Pod> = Api::all(client.clone());
+        let launchers = ListParams::default().labels("kubevirt.io=virt-launcher");
+        let pods = self
+            .api
+            .list(&pods, "pods", &launchers, true)
+            .await
+            .context("Failed to list virt-launcher pods")?;
+        let policies: Api<NetworkPolicy> = Api::all(client.clone());
+        let policies = self
+            .api
+            .list(&policies, "networkpolicies", &Default::default(), true)
+            .await
+            .context("Failed to list network policies")?;
+
+        let mut namespaces: BTreeMap<String, NamespacePolicies> = BTreeMap::new();
+        for pod in &pods {
+            let namespace = pod.namespace().unwrap_or_default();
+            namespaces
+                .entry(namespace.clone())
+                .or_insert_with(|| NamespacePolicies {
+                    namespace,
+                    policies: Vec::new(),
+                    vm_count: 0,
+                    vms: Vec::new(),
+                })
+                .vm_count += 1;
+        }
+        for policy in &policies {
+            if let Some(entry) = namespaces.get_mut(&policy.namespace().unwrap_or_default()) {
+                entry.policies.push(policy.name_any());
+            }
+        }
+
+        for pod in &pods {
+            let namespace = pod.namespace().unwrap_or_default();
+            let labels = pod.labels();
+            let selecting: Vec<_> = policies
+                .iter()
+                .filter(|policy| policy.namespace().as_deref() == Some(namespace.as_str()))
+                .filter_map(|policy| Some((policy.name_any(), policy.spec.as_ref()?)))
+                .filter(|(name, spec)| match LabelSelector::parse(&selector_text(&spec.pod_selector)) {
+                    Ok(selector) => selector.matches(labels),
+                    Err(e) => {
+                        tracing::debug!("Unusable podSelector in {}/{}: {}", namespace, name, e);
+                        false
+                    }
+                })
+                .collect();
+            if selecting.is_empty() {
+                continue;
+            }
+
+            let applies = |spec: &NetworkPolicySpec, kind: &str| match spec.policy_types {
+                Some(ref types) => types.iter().any(|t| t == kind),
+                // Ingress always applies; Egress once there are egress rules
+                None => kind == "Ingress" || spec.egress.as_ref().is_some_and(|rules| !rules.is_empty()),
+            };
+            let mut ingress: Option<Vec<(Vec<String>, Vec<String>)>> = None;
+            let mut egress: Option<Vec<(Vec<String>, Vec<String>)>> = None;
+            let mut dns_allowed = false;
+            for (_, spec) in &selecting {
+                if applies(spec, "Ingress") {
+                    let rules = ingress.get_or_insert_with(Vec::new);
+                    for rule in spec.ingress.iter().flatten() {
+                        rules.push((peers_text(rule.from.as_deref()), ports_text(rule.ports.as_deref())));
+                    }
+                }
+                if applies(spec, "Egress") {
+                    let rules = egress.get_or_insert_with(Vec::new);
+                    for rule in spec.egress.iter().flatten() {
+                        rules.push((peers_text(rule.to.as_deref()), ports_text(rule.ports.as_deref())));
+                        dns_allowed |= allows_dns(rule.ports.as_deref());
+                    }
+                }
+            }
+
+            let access = |rules: Option<Vec<(Vec<String>, Vec<String>)>>, preposition: &str| match rules {
+                None => PolicyAccess::Unrestricted,
+                Some(rules) if rules.is_empty() => PolicyAccess::DenyAll,
+                Some(rules) if rules.iter().any(|(peers, ports)| peers.is_empty() && ports.is_empty()) => {
+                    PolicyAccess::Unrestricted
+                }
+                Some(rules) => PolicyAccess::Allow(
+                    rules
+                        .into_iter()
+                        .map(|(peers, ports)| {
+                            let peers = if peers.is_empty() { "anywhere".to_string() } else { peers.join(", ") };
+                            let ports = if ports.is_empty() { "any port".to_string() } else { ports.join(", ") };
+                            format!("{} {} on {}", preposition, peers, ports)
+                        })
+                        .collect(),
+                ),
+            };
+            let ingress = access(ingress, "from");
+            let egress = access(egress, "to");
+            let vm = labels
+                .get("vm.kubevirt.io/name")
+                .or_else(|| labels.get("kubevirt.io/domain"))
+                .cloned()
+                .unwrap_or_else(|| pod.name_any());
+
+            if let Some(entry) = namespaces.get_mut(&namespace) {
+                entry.vms.push(VmNetworkPolicy {
+                    vm,
+                    policies: selecting.into_iter().map(|(name, _)| name).collect(),
+                    dns_blocked: egress.is_restricted() && !dns_allowed,
+                    ingress,
+                    egress,
+                });
+            }
         }
+
+        Ok(namespaces
+            .into_values()
+            .filter(|ns| !ns.policies.is_empty())
+            .map(|mut ns| {
+                ns.policies.sort();
+                ns.vms.sort_by(|a, b| a.vm.cmp(&b.vm));
+                ns
+            })
+            .collect())
     }
+
+    /// When each VMI in the cluster was created, reached Running and had its
+    /// guest agent connect, from its phase transition timestamps and the
+    /// AgentConnected condition
+    pub async fn collect_vm_boots(&self) -> Result<Vec<BootRecord>> {
+        use kube::api::{ApiResource, DynamicObject, GroupVersionKind};
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_vm_boots());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let gvk = GroupVersionKind::gvk("kubevirt.io", "v1", "VirtualMachineInstance");
+        let api: Api<DynamicObject> = Api::all_with(
+            client.clone(),
+            &ApiResource::from_gvk_with_plural(&gvk, "virtualmachineinstances"),
+        );
+        let list = self
+            .api
+            .list(&api, "virtualmachineinstances", &Default::default(), true)
+            .await
+            .context("Failed to list VMIs")?;
+
+        let time = |value: Option<&serde_json::Value>| {
+            value
+                .and_then(|v| v.as_str())
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&chrono::Local))
+        };
+        Ok(list
+            .iter()
+            .filter_map(|vmi| {
+                let requested = vmi.metadata.creation_timestamp.as_ref()?.0.with_timezone(&chrono::Local);
+                let status = vmi.data.get("status");
+                let running = status
+                    .and_then(|s| s.get("phaseTransitionTimestamps"))
+                    .and_then(|t| t.as_array())
+                    .and_then(|transitions| {
+                        transitions.iter().find(|t| t.get("phase").and_then(|p| p.as_str()) == Some("Running"))
+                    })
+                    .and_then(|t| time(t.get("phaseTransitionTimestamp")));
+                // Failed or shut down without ever running: not a boot in progress
+                let phase = status.and_then(|s| s.get("phase")).and_then(|p| p.as_str());
+                if running.is_none() && matches!(phase, Some("Failed" | "Succeeded")) {
+                    return None;
+                }
+                let agent_ready = status
+                    .and_then(|s| s.get("conditions"))
+                    .and_then(|c| c.as_array())
+                    .and_then(|conditions| {
+                        conditions.iter().find(|c| {
+                            c.get("type").and_then(|t| t.as_str()) == Some("AgentConnected")
+                                && c.get("status").and_then(|s| s.as_str()) == Some("True")
+                        })
+                    })
+                    .and_then(|c| time(c.get("lastTransitionTime")));
+                Some(BootRecord {
+                    uid: vmi.uid().unwrap_or_else(|| vmi.name_any()),
+                    vm: format!("{}/{}", vmi.namespace().unwrap_or_default(), vmi.name_any()),
+                    node: status.and_then(|s| s.get("nodeName")).and_then(|n| n.as_str()).map(str::to_string),
+                    requested,
+                    running,
+                    agent_ready,
+                })
+            })
+            .collect())
+    }
+
+    /// VirtualMachineInstanceMigrations still in the cluster. Running ones
+    /// carry the bytes sent so far, scraped from the source node's
+    /// virt-handler, since the migration object itself doesn't record them.
+    pub async fn collect_migrations(&self) -> Result<Vec<MigrationRecord>> {
+        use kube::api::{ApiResource, DynamicObject, GroupVersionKind};
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_migrations());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let gvk = GroupVersionKind::gvk("kubevirt.io", "v1", "VirtualMachineInstanceMigration");
+        let api: Api<DynamicObject> = Api::all_with(
+            client.clone(),
+            &ApiResource::from_gvk_with_plural(&gvk, "virtualmachineinstancemigrations"),
+        );
+        let list = self
+            .api
+            .list(&api, "virtualmachineinstancemigrations", &Default::default(), true)
+            .await
+            .context("Failed to list migrations")?;
+
+        let text = |value: Option<&serde_json::Value>| value.and_then(|v| v.as_str()).map(|s| s.to_string());
+        let time = |value: Option<&serde_json::Value>| {
+            value
+                .and_then(|v| v.as_str())
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&chrono::Local))
+        };
+
+        let mut migrations: Vec<MigrationRecord> = list
+            .iter()
+            .map(|migration| {
+                let namespace = migration.namespace().unwrap_or_default();
+                let state = migration.data.pointer("/status/migrationState");
+                MigrationRecord {
+                    uid: migration.uid().unwrap_or_else(|| migration.name_any()),
+                    vmi: format!(
+                        "{}/{}",
+                        namespace,
+                        text(migration.data.pointer("/spec/vmiName")).unwrap_or_default()
+                    ),
+                    source_node: text(state.and_then(|s| s.get("sourceNode"))),
+                    target_node: text(state.and_then(|s| s.get("targetNode"))),
+                    phase: text(migration.data.pointer("/status/phase")).unwrap_or_else(|| "Pending".to_string()),
+                    started: time(state.and_then(|s| s.get("startTimestamp")))
+                        .or_else(|| migration.metadata.creation_timestamp.as_ref().map(|t| t.0.with_timezone(&chrono::Local))),
+                    ended: time(state.and_then(|s| s.get("endTimestamp"))),
+                    transferred_bytes: None,
+                    failure_reason: text(state.and_then(|s| s.get("failureReason"))),
+                }
+            })
+            .collect();
+
+        if migrations.iter().any(|m| m.phase == "Running") {
+            let transferred = self.migration_data_processed().await.unwrap_or_else(|e| {
+                tracing::debug!("Failed to read migration progress from virt-handler: {:#}", e);
+                HashMap::new()
+            });
+            for migration in migrations.iter_mut().filter(|m| m.phase == "Running") {
+                migration.transferred_bytes = transferred.get(&migration.vmi).copied();
+            }
+        }
+
+        Ok(migrations)
+    }
+
+    /// CDI DataVolumes in every namespace; none when CDI isn't installed
+    pub async fn collect_data_volumes(&self) -> Result<Vec<DataVolume>> {
+        use kube::api::{ApiResource, DynamicObject, GroupVersionKind};
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_data_volumes());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let gvk = GroupVersionKind::gvk("cdi.kubevirt.io", "v1beta1", "DataVolume");
+        let api: Api<DynamicObject> = Api::all_with(client.clone(), &ApiResource::from_gvk_with_plural(&gvk, "datavolumes"));
+        let list = match self.api.list(&api, "datavolumes", &Default::default(), true).await {
+            Ok(list) => list,
+            Err(kube::Error::Api(e)) if e.code == 404 => return Ok(Vec::new()),
+            Err(e) => return Err(e).context("Failed to list DataVolumes"),
+        };
+
+        let text = |value: Option<&serde_json::Value>| value.and_then(|v| v.as_str()).map(|s| s.to_string());
+        let mut volumes: Vec<DataVolume> = list
+            .iter()
+            .map(|dv| {
+                let source = dv
+                    .data
+                    .pointer("/spec/source")
+                    .or_else(|| dv.data.pointer("/spec/sourceRef"))
+                    .and_then(|s| s.as_object())
+                    .and_then(|s| s.keys().next().cloned())
+                    .unwrap_or_else(|| "-".to_string());
+                let message = dv
+                    .data
+                    .pointer("/status/conditions")
+                    .and_then(|c| c.as_array())
+                    .and_then(|conditions| {
+                        conditions.iter().find(|c| {
+                            c.get("status").and_then(|s| s.as_str()) == Some("False")
+                                && c.get("message").and_then(|m| m.as_str()).is_some_and(|m| !m.is_empty())
+                        })
+                    })
+                    .map(|c| {
+                        let reason = text(c.get("reason")).unwrap_or_default();
+                        let message = text(c.get("message")).unwrap_or_default();
+                        if reason.is_empty() { message } else { format!("{}: {}", reason, message) }
+                    });
+                DataVolume {
+                    namespace: dv.namespace().unwrap_or_default(),
+                    name: dv.name_any(),
+                    phase: text(dv.data.pointer("/status/phase")).unwrap_or_else(|| "Pending".to_string()),
+                    progress: text(dv.data.pointer("/status/progress"))
+                        .and_then(|p| p.trim_end_matches('%').parse().ok()),
+                    source,
+                    restarts: dv.data.pointer("/status/restartCount").and_then(|r| r.as_u64()).unwrap_or(0) as u32,
+                    message,
+                    owner: dv
+                        .owner_references()
+                        .iter()
+                        .find(|owner| owner.kind == "VirtualMachine")
+                        .map(|owner| owner.name.clone()),
+                    created: dv.metadata.creation_timestamp.as_ref().map(|t| t.0.with_timezone(&chrono::Local)),
+                    changed: None,
+                }
+            })
+            .collect();
+        volumes.sort_by_key(|dv| (dv.is_finished(), dv.key()));
+        Ok(volumes)
+    }
+
+    /// Bytes processed per migrating VMI (namespace/name), from every
+    /// virt-handler's Prometheus endpoint through the API server proxy
+    async fn migration_data_processed(&self) -> Result<HashMap<String, u64>> {
+        let client = self.client.as_ref().context("No cluster connection")?;
+        let pods: Api<Pod> = Api::all(client.clone());
+        let handlers = kube::api::ListParams::default().labels("kubevirt.io=virt-handler");
+        let list = self.api.list(&pods, "pods", &handlers, true).await?;
+
+        let mut transferred = HashMap::new();
+        for pod in list {
+            let uri = format!(
+                "/api/v1/namespaces/{}/pods/https:{}:8443/proxy/metrics",
+                pod.namespace().unwrap_or_default(),
+                pod.name_any()
+            );
+            let Ok(metrics) = client.request_text(http::Request::get(uri).body(Vec::new())?).await else {
+                continue;
+            };
+            transferred.extend(parse_migration_metrics(&metrics));
+        }
+        Ok(transferred)
+    }
+
+    /// Disks referenced by VMs, every claim with its owner and mounting pod,
+    /// and existing PersistentVolumes, for the disk image inventory
+    pub async fn collect_disk_references(&self) -> Result<DiskReferences> {
+        use kube::api::{ApiResource, DynamicObject, GroupVersionKind};
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_disk_references());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let params = Default::default();
+        let gvk = GroupVersionKind::gvk("kubevirt.io", "v1", "VirtualMachine");
+        let vms: Api<DynamicObject> =
+            Api::all_with(client.clone(), &ApiResource::from_gvk_with_plural(&gvk, "virtualmachines"));
+        let claims: Api<PersistentVolumeClaim> = Api::all(client.clone());
+        let volumes: Api<PersistentVolume> = Api::all(client.clone());
+        let pods: Api<Pod> = Api::all(client.clone());
+        let (claim_list, volume_list, pod_list) = tokio::try_join!(
+            self.api.list(&claims, "persistentvolumeclaims", &params, true),
+            self.api.list(&volumes, "persistentvolumes", &params, true),
+            self.api.list(&pods, "pods", &params, true)
+        )
+        .context("Failed to list volumes")?;
+        // Without KubeVirt nothing references disks, so everything unmounted shows up
+        let vm_list = self.api.list(&vms, "virtualmachines", &params, true).await.unwrap_or_default();
+
+        let mut refs = DiskReferences {
+            volumes: volume_list.iter().map(|pv| pv.name_any()).collect(),
+            ..DiskReferences::default()
+        };
+
+        for vm in &vm_list {
+            let namespace = vm.namespace().unwrap_or_default();
+            let owner = format!("{}/{}", namespace, vm.name_any());
+            let spec = vm.data.get("spec");
+            let volumes = spec
+                .and_then(|s| s.pointer("/template/spec/volumes"))
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+            for volume in &volumes {
+                if let Some(path) = volume.pointer("/hostDisk/path").and_then(|p| p.as_str()) {
+                    refs.host_paths.insert(path.to_string(), owner.clone());
+                }
+                let claim = volume
+                    .pointer("/persistentVolumeClaim/claimName")
+                    .or_else(|| volume.pointer("/dataVolume/name"))
+                    .and_then(|n| n.as_str());
+                if let Some(claim) = claim {
+                    refs.claims.insert(format!("{}/{}", namespace, claim), owner.clone());
+                }
+            }
+        }
+
+        let mut mounted: HashMap<String, String> = HashMap::new();
+        for pod in &pod_list {
+            let phase = pod.status.as_ref().and_then(|s| s.phase.as_deref()).unwrap_or("");
+            if !matches!(phase, "Running" | "Pending") {
+                continue;
+            }
+            let namespace = pod.namespace().unwrap_or_default();
+            for volume in pod.spec.iter().flat_map(|s| s.volumes.iter().flatten()) {
+                if let Some(claim) = volume.persistent_volume_claim.as_ref() {
+                    mounted.insert(
+                        format!("{}/{}", namespace, claim.claim_name),
+                        format!("{}/{}", namespace, pod.name_any()),
+                    );
+                }
+            }
+        }
+
+        for claim in claim_list {
+            let namespace = claim.namespace().unwrap_or_default();
+            let name = claim.name_any();
+            let size = claim
+                .status
+                .as_ref()
+                .and_then(|s| s.capacity.as_ref())
+                .and_then(|c| c.get("storage"))
+                .and_then(|q| parse_memory_quantity(&q.0))
+                .unwrap_or(0.0);
+            let data_volume = claim
+                .metadata
+                .owner_references
+                .iter()
+                .flatten()
+                .find(|o| o.kind == "DataVolume")
+                .map(|o| o.name.clone());
+            refs.all_claims.push(ClaimInfo {
+                mounted_by: mounted.get(&format!("{}/{}", namespace, name)).cloned(),
+                volume: claim.spec.as_ref().and_then(|s| s.volume_name.clone()),
+                size_bytes: size as u64,
+                data_volume,
+                namespace,
+                name,
+            });
+        }
+
+        Ok(refs)
+    }
+
+    /// Per-node readiness, utilization and workload counts for the Fleet view
+    pub async fn collect_fleet(&self) -> Result<Vec<NodeStatus>> {
+        use kube::api::{ApiResource, DynamicObject, GroupVersionKind};
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_fleet());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let nodes: Api<Node> = Api::all(client.clone());
+        let pods: Api<Pod> = Api::all(client.clone());
+        let params = Default::default();
+        let (node_list, pod_list) = tokio::try_join!(
+            self.api.list(&nodes, "nodes", &params, true),
+            self.api.list(&pods, "pods", &params, true)
+        )?;
+
+        // node -> (scheduled pods, running VMs)
+        let mut workloads: HashMap<String, (u32, Vec<String>)> = HashMap::new();
+        for pod in &pod_list {
+            let Some(node) = pod.spec.as_ref().and_then(|s| s.node_name.clone()) else {
+                continue;
+            };
+            let phase = pod.status.as_ref().and_then(|s| s.phase.as_deref()).unwrap_or("");
+            if phase == "Succeeded" || phase == "Failed" {
+                continue;
+            }
+            let entry = workloads.entry(node).or_default();
+            entry.0 += 1;
+            if let (Some(vm), "Running") = (pod.labels().get("vm.kubevirt.io/name"), phase) {
+                entry.1.push(format!("{}/{}", pod.namespace().unwrap_or_default(), vm));
+            }
+        }
+
+        // metrics-server is optional; without it nodes are shown without usage
+        let gvk = GroupVersionKind::gvk("metrics.k8s.io", "v1beta1", "NodeMetrics");
+        let metrics_api: Api<DynamicObject> =
+            Api::all_with(client.clone(), &ApiResource::from_gvk_with_plural(&gvk, "nodes"));
+        let mut usage: HashMap<String, (f64, f64)> = HashMap::new();
+        match self.api.list(&metrics_api, "nodes.metrics.k8s.io", &params, false).await {
+            Ok(list) => {
+                for metrics in list {
+                    let value = |key: &str| {
+                        metrics.data.pointer(&format!("/usage/{}", key)).and_then(|v| v.as_str())
+                    };
+                    let cpu = value("cpu").and_then(parse_cpu_quantity).unwrap_or(0.0);
+                    let memory = value("memory").and_then(parse_memory_quantity).unwrap_or(0.0);
+                    usage.insert(metrics.name_any(), (cpu, memory));
+                }
+            }
+            Err(e) => tracing::debug!("Node metrics unavailable: {}", e),
+        }
+
+        let percent = |used: f64, allocatable: f64| (allocatable > 0.0).then(|| used / allocatable * 100.0);
+        Ok(node_list
+            .into_iter()
+            .map(|node| {
+                let name = node.name_any();
+                let roles = node
+                    .labels()
+                    .keys()
+                    .filter_map(|label| label.strip_prefix("node-role.kubernetes.io/"))
+                    .map(String::from)
+                    .collect();
+                let status = node.status.unwrap_or_default();
+                let conditions = status.conditions.unwrap_or_default();
+                let reachability = match conditions.iter().find(|c| c.type_ == "Ready").map(|c| c.status.as_str()) {
+                    Some("True") => NodeReachability::Ready,
+                    Some("False") => NodeReachability::NotReady,
+                    _ => NodeReachability::Unreachable,
+                };
+                let pressures = conditions
+                    .iter()
+                    .filter(|c| c.type_ != "Ready" && c.status == "True")
+                    .map(|c| c.type_.clone())
+                    .collect();
+                let allocatable = status.allocatable.unwrap_or_default();
+                let cpu_allocatable = allocatable.get("cpu").and_then(|q| parse_cpu_quantity(&q.0)).unwrap_or(0.0);
+                let memory_allocatable_bytes =
+                    allocatable.get("memory").and_then(|q| parse_memory_quantity(&q.0)).unwrap_or(0.0);
+                let (cpu_percent, memory_percent) = match usage.get(&name) {
+                    Some(&(cpu, memory)) => (percent(cpu, cpu_allocatable), percent(memory, memory_allocatable_bytes)),
+                    None => (None, None),
+                };
+                let (pods, vms) = workloads.remove(&name).unwrap_or_default();
+                NodeStatus {
+                    name,
+                    roles,
+                    reachability,
+                    pressures,
+                    kubelet_version: status.node_info.map(|i| i.kubelet_version).unwrap_or_default(),
+                    cpu_percent,
+                    memory_percent,
+                    cpu_allocatable,
+                    memory_allocatable_bytes,
+                    pods,
+                    vms,
+                }
+            })
+            .collect())
+    }
+
+    async fn collect_virtual_machines(&self, client: &Client) -> Result<Vec<K8sResource>> {
+        use kube::api::{DynamicObject, GroupVersionKind};
+        use kube::discovery;
+
+        let discovery = discovery::Discovery::new(client.clone()).run().await?;
+        let gvk = GroupVersionKind {
+            group: "kubevirt.io".to_string(),
+            version: "v1".to_string(),
+            kind: "VirtualMachine".to_string(),
+        };
+
+        let Some((ar, _caps)) = discovery.resolve_gvk(&gvk) else {
+            // KubeVirt not installed
+            return Ok(Vec::new());
+        };
+
+        let api: Api<DynamicObject> = Api::all_with(client.clone(), &ar);
+        let vm_list = self.api.list(&api, "virtualmachines", &Default::default(), true).await?;
+
+        Ok(vm_list
+            .into_iter()
+            .map(|vm| {
+                let status = vm.data
+                    .get("status")
+                    .and_then(|s| s.get("printableStatus"))
+                    .and_then(|p| p.as_str())
+                    .unwrap_or("Unknown")
+                    .to_string();
+                K8sResource {
+                    kind: ResourceKind::VirtualMachine,
+                    name: vm.name_any(),
+                    namespace: vm.namespace(),
+                    status,
+                    labels: vm.labels().clone(),
+                }
+            })
+            .collect())
+    }
+
+    /// Fetch the full object as YAML via kubectl
+    pub async fn fetch_resource_yaml(&self, resource: &K8sResource) -> Result<String> {
+        let mut cmd = self.kubectl();
+        cmd.args(["get", resource.kind.kubectl_name(), &resource.name, "-o", "yaml"]);
+        if let Some(ns) = &resource.namespace {
+            cmd.args(["-n", ns]);
+        }
+
+        let output = cmd.output().await.context("Failed to execute kubectl")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "kubectl get failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Builds `kubectl get --watch` for one object, printing each event
+    /// with its type as JSON; called again whenever the watch restarts
+    pub fn watch_command(&self, resource: &K8sResource) -> impl Fn() -> Command + Send + 'static {
+        let kubeconfig = self.kubeconfig_path.clone().filter(|p| p.exists());
+        let mut args = vec![
+            "get".to_string(),
+            resource.kind.kubectl_name().to_string(),
+            resource.name.clone(),
+            "-o".to_string(),
+            "json".to_string(),
+            "--watch".to_string(),
+            "--output-watch-events".to_string(),
+        ];
+        if let Some(ref ns) = resource.namespace {
+            args.extend(["-n".to_string(), ns.clone()]);
+        }
+        move || {
+            let mut cmd = Command::new("kubectl");
+            if let Some(ref path) = kubeconfig {
+                cmd.arg("--kubeconfig").arg(path);
+            }
+            cmd.args(&args);
+            cmd
+        }
+    }
+
+    /// Apply an edited manifest back to the cluster using server-side
+    /// apply. Fields another manager owns fail the apply with a conflict
+    /// (see `is_apply_conflict`) unless `force` takes them over.
+    pub async fn apply_resource_yaml(&self, manifest: &str, force: bool) -> Result<String> {
+        let mut cmd = self.kubectl();
+        cmd.args(["apply", "--server-side", "--field-manager=hypervisor-tui"]);
+        if force {
+            cmd.arg("--force-conflicts");
+        }
+        // Through stdin, so no copy of a Secret is left on disk
+        let mut child = cmd
+            .args(["-f", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to execute kubectl")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(manifest.as_bytes()).await.context("Failed to send the manifest to kubectl")?;
+        }
+        let output = child.wait_with_output().await.context("Failed to execute kubectl")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "kubectl apply failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Whether `apply_resource_yaml` failed because the manifest changes
+    /// fields another field manager owns
+    pub fn is_apply_conflict(error: &anyhow::Error) -> bool {
+        let message = error.to_string();
+        message.contains("Apply failed with") && message.contains("conflict")
+    }
+
+    /// Current CPU placement/model/hugepages settings of a VirtualMachine
+    pub async fn fetch_vm_cpu_tuning(&self, namespace: &str, name: &str) -> Result<CpuTuning> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(CpuTuning {
+                model: Some("host-passthrough".to_string()),
+                ..CpuTuning::default()
+            });
+        }
+        Ok(CpuTuning::from_vm(&self.fetch_vm(namespace, name).await?))
+    }
+
+    /// A VirtualMachine object as JSON
+    pub async fn fetch_vm(&self, namespace: &str, name: &str) -> Result<serde_json::Value> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(Self::mock_vm(namespace, name));
+        }
+        self.fetch_object("virtualmachine", namespace, name).await
+    }
+
+    /// Any namespaced object as JSON, by its kubectl resource name
+    pub async fn fetch_object(&self, kind: &str, namespace: &str, name: &str) -> Result<serde_json::Value> {
+        let output = self
+            .kubectl()
+            .args(["get", kind, name, "-n", namespace, "-o", "json"])
+            .output()
+            .await
+            .context("Failed to execute kubectl")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "kubectl get failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        serde_json::from_slice(&output.stdout).with_context(|| format!("Failed to parse {}", kind))
+    }
+
+    /// Running VMIs on a node as namespace/name, from the node label
+    /// virt-controller puts on each VMI
+    pub async fn vmis_on_node(&self, node: &str) -> Result<Vec<String>> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(vec![
+                "default/vm-webserver-01".to_string(),
+                "default/vm-database-01".to_string(),
+                "tenant-a/vm-ci-runner".to_string(),
+            ]);
+        }
+
+        let selector = format!("kubevirt.io/nodeName={}", node);
+        let output = self
+            .run_kubectl(&["get", "virtualmachineinstances", "-A", "-l", &selector, "-o", "json"])
+            .await?;
+        let list: serde_json::Value = serde_json::from_str(&output).context("Failed to parse VMI list")?;
+        Ok(list
+            .get("items")
+            .and_then(|items| items.as_array())
+            .into_iter()
+            .flatten()
+            .filter(|vmi| vmi.pointer("/status/phase").and_then(|p| p.as_str()) == Some("Running"))
+            .filter_map(|vmi| {
+                let namespace = vmi.pointer("/metadata/namespace")?.as_str()?;
+                let name = vmi.pointer("/metadata/name")?.as_str()?;
+                Some(format!("{}/{}", namespace, name))
+            })
+            .collect())
+    }
+
+    /// Pods scheduled on a node, with the PodDisruptionBudgets covering
+    /// each, for draining it
+    pub async fn pods_on_node(&self, node: &str) -> Result<Vec<NodePod>> {
+        use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+        use kube::api::ListParams;
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(Self::mock_node_pods());
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let pods: Api<Pod> = Api::all(client.clone());
+        let on_node = ListParams::default().fields(&format!("spec.nodeName={}", node));
+        self.api.request("pods").await;
+        let pods = pods
+            .list(&on_node)
+            .await
+            .with_context(|| format!("Failed to list pods on {}", node))?;
+        let budgets: Api<PodDisruptionBudget> = Api::all(client.clone());
+        let budgets = self
+            .api
+            .list(&budgets, "poddisruptionbudgets", &ListParams::default(), true)
+            .await
+            .context("Failed to list PodDisruptionBudgets")?;
+
+        let mut listed: Vec<NodePod> = pods
+            .iter()
+            .map(|pod| {
+                let namespace = pod.namespace().unwrap_or_default();
+                let labels = pod.labels();
+                let spec = pod.spec.as_ref();
+                let phase = pod.status.as_ref().and_then(|s| s.phase.as_deref());
+                NodePod {
+                    namespace: namespace.clone(),
+                    name: pod.name_any(),
+                    uid: pod.uid().unwrap_or_default(),
+                    owner: pod.owner_references().iter().find(|o| o.controller == Some(true)).map(|o| o.kind.clone()),
+                    mirror: pod.annotations().contains_key("kubernetes.io/config.mirror"),
+                    finished: matches!(phase, Some("Succeeded" | "Failed")),
+                    launcher: labels.get("kubevirt.io").map(String::as_str) == Some("virt-launcher"),
+                    empty_dirs: spec
+                        .and_then(|s| s.volumes.as_ref())
+                        .into_iter()
+                        .flatten()
+                        .filter(|v| v.empty_dir.is_some())
+                        .map(|v| v.name.clone())
+                        .collect(),
+                    budgets: budgets
+                        .iter()
+                        .filter(|pdb| pdb.namespace().as_deref() == Some(namespace.as_str()))
+                        .filter(|pdb| {
+                            pdb.spec
+                                .as_ref()
+                                .and_then(|s| s.selector.as_ref())
+                                .and_then(|s| LabelSelector::parse(&selector_text(s)).ok())
+                                .is_some_and(|selector| selector.matches(labels))
+                        })
+                        .map(|pdb| pdb.name_any())
+                        .collect(),
+                }
+            })
+            .collect();
+        listed.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+        Ok(listed)
+    }
+
+    fn mock_node_pods() -> Vec<NodePod> {
+        let pod = |namespace: &str, name: &str, owner: Option<&str>| NodePod {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            uid: format!("mock-{}", name),
+            owner: owner.map(str::to_string),
+            ..Default::default()
+        };
+        vec![
+            NodePod { launcher: true, ..pod("default", "virt-launcher-vm-webserver-01-x7k2p", Some("VirtualMachineInstance")) },
+            pod("kube-system", "kube-proxy-9fz4d", Some("DaemonSet")),
+            NodePod { mirror: true, ..pod("kube-system", "kube-apiserver-node-01", Some("Node")) },
+            pod("monitoring", "grafana-6d8f7c9b5-2lq8w", Some("ReplicaSet")),
+            NodePod { budgets: vec!["postgres-pdb".to_string()], ..pod("tenant-a", "postgres-0", Some("StatefulSet")) },
+            NodePod { empty_dirs: vec!["cache".to_string()], ..pod("tenant-a", "builder-5c4f9-mnb7x", Some("ReplicaSet")) },
+            pod("default", "debug-shell", None),
+        ]
+    }
+
+    /// Evict a pod through the Eviction API, which honours its disruption
+    /// budgets. Ok(Some(message)) when a budget refused it for now; a pod
+    /// already gone counts as evicted.
+    pub async fn evict_pod(&self, namespace: &str, name: &str) -> Result<Option<String>> {
+        use kube::api::EvictParams;
+
+        let client = self.client.as_ref().context("Not connected to a cluster")?;
+        let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+        self.api.request("pods").await;
+        match pods.evict(name, &EvictParams::default()).await {
+            Ok(_) => Ok(None),
+            Err(kube::Error::Api(e)) if e.code == 404 => Ok(None),
+            Err(kube::Error::Api(e)) if e.code == 429 => Ok(Some(e.message)),
+            Err(e) => Err(e).with_context(|| format!("Failed to evict {}/{}", namespace, name)),
+        }
+    }
+
+    /// Whether the pod with this UID still exists
+    pub async fn pod_exists(&self, namespace: &str, name: &str, uid: &str) -> Result<bool> {
+        let client = self.client.as_ref().context("Not connected to a cluster")?;
+        let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+        self.api.request("pods").await;
+        match pods.get_opt(name).await {
+            Ok(pod) => Ok(pod.is_some_and(|pod| pod.uid().as_deref() == Some(uid))),
+            Err(e) => Err(e).with_context(|| format!("Failed to get pod {}/{}", namespace, name)),
+        }
+    }
+
+    /// Review every permission the UI relies on for the current
+    /// kubeconfig, across all namespaces. KubeVirt's are left out when
+    /// another backend answers for VMs, and reviews that fail are left
+    /// unreviewed.
+    pub async fn audit_permissions(&self) -> Result<Vec<PermissionCheck>> {
+        use futures::future::join_all;
+        use k8s_openapi::api::authorization::v1::{ResourceAttributes, SelfSubjectAccessReview, SelfSubjectAccessReviewSpec};
+        use kube::api::PostParams;
+
+        let permissions = Permission::ALL.into_iter().filter(|permission| self.virt.is_none() || !permission.is_kubevirt());
+        if self.use_mock || self.client.is_none() {
+            return Ok(permissions
+                .map(|permission| PermissionCheck { permission, allowed: true, reason: None })
+                .collect());
+        }
+        let reviews: Api<SelfSubjectAccessReview> = Api::all(self.client.clone().unwrap());
+        let checks = join_all(permissions.map(|permission| {
+            let reviews = reviews.clone();
+            async move {
+                let (verb, group, resource, subresource) = permission.attributes();
+                let review = SelfSubjectAccessReview {
+                    spec: SelfSubjectAccessReviewSpec {
+                        resource_attributes: Some(ResourceAttributes {
+                            verb: Some(verb.to_string()),
+                            group: Some(group.to_string()),
+                            resource: Some(resource.to_string()),
+                            subresource: subresource.map(str::to_string),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                };
+                self.api.request("selfsubjectaccessreviews").await;
+                let result = reviews.create(&PostParams::default(), &review).await;
+                (permission, result)
+            }
+        }))
+        .await;
+
+        let mut audit = Vec::new();
+        let mut last_error = None;
+        for (permission, result) in checks {
+            match result.map(|review| review.status.unwrap_or_default()) {
+                Ok(status) => audit.push(PermissionCheck {
+                    permission,
+                    allowed: status.allowed,
+                    reason: status.reason.or(status.evaluation_error).filter(|reason| !reason.is_empty()),
+                }),
+                Err(e) => {
+                    tracing::warn!("Failed to review permission to {}: {}", permission.label(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        match last_error {
+            Some(e) if audit.is_empty() => Err(e).context("Failed to review permissions"),
+            _ => Ok(audit),
+        }
+    }
+
+    /// Delete a namespaced object without waiting for finalizers; gone
+    /// already counts as deleted
+    pub async fn delete_object(&self, kind: &str, namespace: &str, name: &str) -> Result<String> {
+        self.run_kubectl(&["delete", kind, name, "-n", namespace, "--ignore-not-found", "--wait=false"])
+            .await
+    }
+
+    /// Output of a pod's (only) container
+    pub async fn pod_logs(&self, namespace: &str, name: &str) -> Result<String> {
+        self.run_kubectl(&["logs", name, "-n", namespace]).await
+    }
+
+    /// Create an object from a manifest, failing if it already exists
+    pub async fn create_object(&self, manifest: &serde_json::Value) -> Result<String> {
+        use std::process::Stdio;
+        use tokio::io::AsyncWriteExt;
+
+        if self.use_mock || self.client.is_none() {
+            let name = manifest.pointer("/metadata/name").and_then(|v| v.as_str()).unwrap_or_default();
+            return Ok(format!("virtualmachine.kubevirt.io/{} created (mock)", name));
+        }
+
+        let mut child = self
+            .kubectl()
+            .args(["create", "-f", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to execute kubectl")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(manifest.to_string().as_bytes()).await?;
+        }
+        let output = child.wait_with_output().await.context("Failed to execute kubectl")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "kubectl create failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Apply a JSON merge patch to a VirtualMachine
+    /// Trigger a rolling restart of a DaemonSet, e.g. virt-handler
+    pub async fn restart_daemonset(&self, namespace: &str, name: &str) -> Result<String> {
+        self.run_kubectl(&["rollout", "restart", &format!("daemonset/{}", name), "-n", namespace])
+            .await
+    }
+
+    /// Delete an orphaned VM disk claim, through its DataVolume if CDI
+    /// created it (deleting only the claim would have CDI recreate it)
+    pub async fn delete_claim(&self, namespace: &str, name: &str, data_volume: Option<&str>) -> Result<String> {
+        match data_volume {
+            Some(dv) => self.run_kubectl(&["delete", "datavolume", dv, "-n", namespace]).await,
+            None => self.run_kubectl(&["delete", "persistentvolumeclaim", name, "-n", namespace]).await,
+        }
+    }
+
+    /// Start, stop or migrate a VirtualMachine through the KubeVirt
+    /// subresource API (what virtctl does), or label it
+    pub async fn run_vm_action(&self, action: &VmAction, namespace: &str, name: &str) -> Result<String> {
+        if let Some(ref virt) = self.virt {
+            return virt.run_action(action, namespace, name).await;
+        }
+        let subresource = match action {
+            VmAction::Start => "start",
+            VmAction::Stop => "stop",
+            VmAction::Migrate => "migrate",
+            VmAction::AddLabel { key, value } => {
+                let label = format!("{}={}", key, value);
+                return self
+                    .run_kubectl(&["label", "virtualmachine", name, "-n", namespace, &label, "--overwrite"])
+                    .await;
+            }
+        };
+        let client = self.client.as_ref().context("Not connected to a cluster")?;
+        let uri = format!(
+            "/apis/subresources.kubevirt.io/v1/namespaces/{}/virtualmachines/{}/{}",
+            namespace, name, subresource
+        );
+        let request = http::Request::put(uri)
+            .header("Content-Type", "application/json")
+            .body(b"{}".to_vec())?;
+        client
+            .request_text(request)
+            .await
+            .with_context(|| format!("{} {}/{} failed", subresource, namespace, name))
+    }
+
+    /// Current labels and taints of a node
+    pub async fn fetch_node_metadata(&self, node: &str) -> Result<NodeMetadata> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(Self::mock_node_metadata(node));
+        }
+        let nodes: Api<Node> = Api::all(self.client.clone().unwrap());
+        self.api.request("nodes").await;
+        let object = nodes.get(node).await.with_context(|| format!("Failed to get node {}", node))?;
+        let taints = object
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.taints.as_ref())
+            .map(|taints| {
+                taints
+                    .iter()
+                    .map(|t| NodeTaint {
+                        key: t.key.clone(),
+                        value: t.value.clone(),
+                        effect: t.effect.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(NodeMetadata {
+            labels: object.metadata.labels.clone().unwrap_or_default(),
+            taints,
+            resource_version: object.metadata.resource_version.clone(),
+        })
+    }
+
+    /// Set (`Some`) or remove (`None`) a node label
+    pub async fn set_node_label(&self, node: &str, key: &str, value: Option<&str>) -> Result<()> {
+        let patch = serde_json::json!({ "metadata": { "labels": { key: value } } });
+        self.patch_node(node, &patch).await
+    }
+
+    /// Replace a node's taints. Taints are a list, which a merge patch
+    /// replaces wholesale, so the patch carries the resourceVersion they
+    /// were read at and fails rather than drop a taint added meanwhile.
+    pub async fn set_node_taints(&self, node: &str, taints: &[NodeTaint], resource_version: Option<&str>) -> Result<()> {
+        let taints: Vec<serde_json::Value> = taints
+            .iter()
+            .map(|t| match t.value {
+                Some(ref value) => serde_json::json!({ "key": t.key, "value": value, "effect": t.effect }),
+                None => serde_json::json!({ "key": t.key, "effect": t.effect }),
+            })
+            .collect();
+        let patch = serde_json::json!({
+            "metadata": { "resourceVersion": resource_version },
+            "spec": { "taints": taints },
+        });
+        self.patch_node(node, &patch).await
+    }
+
+    async fn patch_node(&self, node: &str, patch: &serde_json::Value) -> Result<()> {
+        use kube::api::{Patch, PatchParams};
+
+        let client = self.client.as_ref().context("Not connected to a cluster")?;
+        let nodes: Api<Node> = Api::all(client.clone());
+        self.api.request("nodes").await;
+        nodes
+            .patch(node, &PatchParams::default(), &Patch::Merge(patch))
+            .await
+            .with_context(|| format!("Failed to patch node {}", node))?;
+        Ok(())
+    }
+
+    fn mock_node_metadata(node: &str) -> NodeMetadata {
+        let labels = [
+            ("kubernetes.io/hostname", node),
+            ("kubernetes.io/os", "linux"),
+            ("node-role.kubernetes.io/control-plane", "true"),
+            ("kubevirt.io/schedulable", "true"),
+        ];
+        NodeMetadata {
+            labels: labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            taints: vec![NodeTaint {
+                key: "dedicated".to_string(),
+                value: Some("vms".to_string()),
+                effect: "PreferNoSchedule".to_string(),
+            }],
+            resource_version: None,
+        }
+    }
+
+    /// The local node's conditions, capacity and allocatable, plus the
+    /// eviction thresholds from the kubelet's live configuration. None when
+    /// this host isn't a node of the cluster.
+    pub async fn collect_kubelet_status(&self, node: &str) -> Result<Option<KubeletStatus>> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(Some(Self::mock_kubelet_status(node)));
+        }
+        let client = self.client.as_ref().unwrap();
+        let nodes: Api<Node> = Api::all(client.clone());
+        self.api.request("nodes").await;
+        let object = match nodes.get(node).await {
+            Ok(object) => object,
+            Err(kube::Error::Api(e)) if e.code == 404 => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to get node {}", node)),
+        };
+        let status = object.status.unwrap_or_default();
+
+        let conditions = status
+            .conditions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|c| NodeConditionInfo {
+                kind: c.type_,
+                status: c.status,
+                reason: c.reason.filter(|r| !r.is_empty()),
+                message: c.message.filter(|m| !m.is_empty()),
+                last_transition: c.last_transition_time.map(|t| t.0.with_timezone(&chrono::Local)),
+            })
+            .collect();
+
+        let capacity = status.capacity.unwrap_or_default();
+        let allocatable = status.allocatable.unwrap_or_default();
+        let mut resources: Vec<NodeResource> = capacity
+            .iter()
+            .filter(|(_, quantity)| quantity.0 != "0")
+            .map(|(name, quantity)| {
+                let allocatable = allocatable.get(name).map(|q| q.0.clone()).unwrap_or_else(|| "-".to_string());
+                let parse = if name == "cpu" { parse_cpu_quantity } else { parse_memory_quantity };
+                let reserved_percent = match (parse(&quantity.0), parse(&allocatable)) {
+                    (Some(capacity), Some(allocatable)) if capacity > 0.0 => {
+                        Some((capacity - allocatable) / capacity * 100.0)
+                    }
+                    _ => None,
+                };
+                NodeResource { name: name.clone(), capacity: quantity.0.clone(), allocatable, reserved_percent }
+            })
+            .collect();
+        // The common resources first, devices and hugepages after
+        let rank = |name: &str| {
+            ["cpu", "memory", "ephemeral-storage", "pods"].iter().position(|n| *n == name).unwrap_or(usize::MAX)
+        };
+        resources.sort_by(|a, b| (rank(&a.name), &a.name).cmp(&(rank(&b.name), &b.name)));
+
+        // configz is served by the kubelet through the API server proxy
+        let uri = format!("/api/v1/nodes/{}/proxy/configz", node);
+        let (eviction_hard, eviction_soft, config_error) =
+            match client.request_text(http::Request::get(uri).body(Vec::new())?).await {
+                Ok(text) => match serde_json::from_str::<serde_json::Value>(&text) {
+                    Ok(config) => {
+                        let thresholds = |key: &str| -> BTreeMap<String, String> {
+                            config["kubeletconfig"][key]
+                                .as_object()
+                                .map(|map| {
+                                    map.iter()
+                                        .filter_map(|(signal, value)| Some((signal.clone(), value.as_str()?.to_string())))
+                                        .collect()
+                                })
+                                .unwrap_or_default()
+                        };
+                        (thresholds("evictionHard"), thresholds("evictionSoft"), None)
+                    }
+                    Err(e) => (BTreeMap::new(), BTreeMap::new(), Some(format!("Unreadable kubelet configz: {}", e))),
+                },
+                Err(e) => (BTreeMap::new(), BTreeMap::new(), Some(format!("Kubelet configz unavailable: {}", e))),
+            };
+
+        Ok(Some(KubeletStatus {
+            node: node.to_string(),
+            kubelet_version: status.node_info.map(|i| i.kubelet_version).unwrap_or_default(),
+            conditions,
+            resources,
+            eviction_hard,
+            eviction_soft,
+            config_error,
+        }))
+    }
+
+    fn mock_kubelet_status(node: &str) -> KubeletStatus {
+        let since = |hours: i64| Some(chrono::Local::now() - chrono::Duration::hours(hours));
+        let condition = |kind: &str, status: &str, reason: &str, message: &str, hours: i64| NodeConditionInfo {
+            kind: kind.to_string(),
+            status: status.to_string(),
+            reason: Some(reason.to_string()),
+            message: Some(message.to_string()),
+            last_transition: since(hours),
+        };
+        let resource = |name: &str, capacity: &str, allocatable: &str, reserved_percent: Option<f64>| NodeResource {
+            name: name.to_string(),
+            capacity: capacity.to_string(),
+            allocatable: allocatable.to_string(),
+            reserved_percent,
+        };
+        let thresholds = |pairs: &[(&str, &str)]| pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        KubeletStatus {
+            node: node.to_string(),
+            kubelet_version: "v1.29.3+k3s1".to_string(),
+            conditions: vec![
+                condition("Ready", "True", "KubeletReady", "kubelet is posting ready status", 76),
+                condition("MemoryPressure", "False", "KubeletHasSufficientMemory", "kubelet has sufficient memory available", 76),
+                condition("DiskPressure", "True", "KubeletHasDiskPressure", "kubelet has disk pressure", 1),
+                condition("PIDPressure", "False", "KubeletHasSufficientPID", "kubelet has sufficient PID available", 76),
+            ],
+            resources: vec![
+                resource("cpu", "32", "31500m", Some(1.6)),
+                resource("memory", "131811556Ki", "129714404Ki", Some(1.6)),
+                resource("ephemeral-storage", "479151816Ki", "466117715257", Some(5.0)),
+                resource("pods", "110", "110", Some(0.0)),
+                resource("devices.kubevirt.io/kvm", "1k", "1k", Some(0.0)),
+            ],
+            eviction_hard: thresholds(&[("imagefs.available", "5%"), ("memory.available", "100Mi"), ("nodefs.available", "5%")]),
+            eviction_soft: BTreeMap::new(),
+            config_error: None,
+        }
+    }
+
+    /// Cordon (unschedulable) or uncordon a node
+    pub async fn set_node_schedulable(&self, node: &str, schedulable: bool) -> Result<String> {
+        let verb = if schedulable { "uncordon" } else { "cordon" };
+        self.run_kubectl(&[verb, node]).await
+    }
+
+    async fn run_kubectl(&self, args: &[&str]) -> Result<String> {
+        let output = self
+            .kubectl()
+            .args(args)
+            .output()
+            .await
+            .context("Failed to execute kubectl")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "kubectl {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    pub async fn patch_vm(&self, namespace: &str, name: &str, patch: &serde_json::Value) -> Result<String> {
+        let output = self
+            .kubectl()
+            .args(["patch", "virtualmachine", name, "-n", namespace, "--type", "merge", "-p"])
+            .arg(patch.to_string())
+            .output()
+            .await
+            .context("Failed to execute kubectl")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "kubectl patch failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// A VM's CPU and memory against what can be hot-plugged, and whether
+    /// the cluster allows it, from the VM, its VMI and the KubeVirt CR
+    pub async fn fetch_vm_hotplug(&self, namespace: &str, name: &str) -> Result<VmHotplug> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(VmHotplug {
+                running: true,
+                sockets: 2,
+                max_sockets: 8,
+                memory_mib: 4096,
+                max_memory_mib: 16384,
+                live_update: true,
+                hotplug_volumes: true,
+                not_migratable: None,
+                hotplugged: Vec::new(),
+            });
+        }
+        let vm = self.fetch_vm(namespace, name).await?;
+        let vmi = self.fetch_object("virtualmachineinstance", namespace, name).await.ok();
+        let kubevirt: serde_json::Value = serde_json::from_str(&self.run_kubectl(&["get", "kubevirt", "-A", "-o", "json"]).await?)
+            .context("Failed to parse KubeVirt CR")?;
+        let configuration = kubevirt.pointer("/items/0/spec/configuration").cloned().unwrap_or_default();
+
+        let domain = vm.pointer("/spec/template/spec/domain").cloned().unwrap_or_default();
+        let live_config = configuration.get("liveUpdateConfiguration");
+        let ratio = live_config
+            .and_then(|c| c.get("maxHotplugRatio"))
+            .and_then(|r| r.as_u64())
+            .unwrap_or(4);
+        let mib = |quantity: Option<&serde_json::Value>| {
+            quantity
+                .and_then(|q| q.as_str())
+                .and_then(parse_memory_quantity)
+                .map(|bytes| (bytes / (1024.0 * 1024.0)) as u64)
+        };
+
+        let sockets = domain.pointer("/cpu/sockets").and_then(|s| s.as_u64()).unwrap_or(1) as u32;
+        let max_sockets = domain
+            .pointer("/cpu/maxSockets")
+            .or_else(|| live_config.and_then(|c| c.get("maxCpuSockets")))
+            .and_then(|s| s.as_u64())
+            .map_or(sockets * ratio as u32, |s| s as u32);
+        let memory_mib = mib(domain.pointer("/memory/guest"))
+            .or_else(|| mib(domain.pointer("/resources/requests/memory")))
+            .unwrap_or(0);
+        let max_memory_mib = mib(domain.pointer("/memory/maxGuest"))
+            .or_else(|| mib(live_config.and_then(|c| c.get("maxGuest"))))
+            .unwrap_or(memory_mib * ratio);
+
+        let gates: Vec<&str> = configuration
+            .pointer("/developerConfiguration/featureGates")
+            .and_then(|g| g.as_array())
+            .map(|gates| gates.iter().filter_map(|g| g.as_str()).collect())
+            .unwrap_or_default();
+        let not_migratable = vmi.as_ref().and_then(|vmi| {
+            vmi.pointer("/status/conditions")?.as_array()?.iter().find_map(|c| {
+                let migratable = c.get("type")?.as_str()? == "LiveMigratable";
+                (migratable && c.get("status")?.as_str()? == "False").then(|| {
+                    c.get("message").or_else(|| c.get("reason")).and_then(|m| m.as_str()).unwrap_or("unknown reason").to_string()
+                })
+            })
+        });
+        let hotplugged = vmi
+            .as_ref()
+            .and_then(|vmi| vmi.pointer("/status/volumeStatus"))
+            .and_then(|v| v.as_array())
+            .map(|volumes| {
+                volumes
+                    .iter()
+                    .filter(|v| v.get("hotplugVolume").is_some())
+                    .filter_map(|v| v.get("name").and_then(|n| n.as_str()).map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(VmHotplug {
+            running: vmi.is_some(),
+            sockets,
+            max_sockets,
+            memory_mib,
+            max_memory_mib,
+            // LiveUpdate became the rollout strategy in KubeVirt 1.2; the
+            // releases before gated it as VMLiveUpdateFeatures
+            live_update: configuration.get("vmRolloutStrategy").and_then(|s| s.as_str()) == Some("LiveUpdate")
+                || gates.contains(&"VMLiveUpdateFeatures"),
+            hotplug_volumes: gates.contains(&"HotplugVolumes"),
+            not_migratable,
+            hotplugged,
+        })
+    }
+
+    /// Hot-plug a claim into a running VM as a SCSI disk through the
+    /// addvolume subresource, keeping it in the VM spec across restarts
+    pub async fn attach_volume(&self, namespace: &str, name: &str, claim: &str) -> Result<String> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(String::new());
+        }
+        let client = self.client.as_ref().context("Not connected to a cluster")?;
+        let uri = format!(
+            "/apis/subresources.kubevirt.io/v1/namespaces/{}/virtualmachines/{}/addvolume",
+            namespace, name
+        );
+        let body = serde_json::json!({
+            "name": claim,
+            "disk": { "disk": { "bus": "scsi" } },
+            "volumeSource": { "persistentVolumeClaim": { "claimName": claim, "hotpluggable": true } },
+        });
+        let request = http::Request::put(uri)
+            .header("Content-Type", "application/json")
+            .body(body.to_string().into_bytes())?;
+        client
+            .request_text(request)
+            .await
+            .with_context(|| format!("addvolume {}/{} failed", namespace, name))
+    }
+
+    /// The VM's RestartRequired condition, set when a spec change couldn't
+    /// be applied to the running VMI
+    pub async fn vm_restart_required(&self, namespace: &str, name: &str) -> Result<Option<String>> {
+        let vm = self.fetch_vm(namespace, name).await?;
+        Ok(vm.pointer("/status/conditions").and_then(|c| c.as_array()).and_then(|conditions| {
+            conditions.iter().find_map(|c| {
+                (c.get("type")?.as_str()? == "RestartRequired" && c.get("status")?.as_str()? == "True")
+                    .then(|| c.get("message").and_then(|m| m.as_str()).unwrap_or("restart required").to_string())
+            })
+        }))
+    }
+
+    /// Query qemu-guest-agent details for a running VMI via the KubeVirt
+    /// guestosinfo/userlist/filesystemlist subresources
+    pub async fn collect_guest_info(&self, namespace: &str, name: &str) -> Result<GuestInfo> {
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_guest_info(name));
+        }
+
+        let base = format!(
+            "/apis/subresources.kubevirt.io/v1/namespaces/{}/virtualmachineinstances/{}",
+            namespace, name
+        );
+        let (os_info, users, filesystems) = tokio::try_join!(
+            self.get_raw(&base, "guestosinfo"),
+            self.get_raw(&base, "userlist"),
+            self.get_raw(&base, "filesystemlist"),
+        )?;
+
+        let str_field = |v: &serde_json::Value, key: &str| {
+            v.get(key).and_then(|s| s.as_str()).unwrap_or_default().to_string()
+        };
+        let items = |v: &serde_json::Value| {
+            v.get("items").and_then(|i| i.as_array()).cloned().unwrap_or_default()
+        };
+
+        let os = os_info.get("os").cloned().unwrap_or_default();
+        let os_name = match str_field(&os, "prettyName") {
+            pretty if !pretty.is_empty() => pretty,
+            _ => format!("{} {}", str_field(&os, "name"), str_field(&os, "version")).trim().to_string(),
+        };
+
+        Ok(GuestInfo {
+            hostname: str_field(&os_info, "hostname"),
+            os: os_name,
+            kernel: str_field(&os, "kernelRelease"),
+            users: items(&users)
+                .iter()
+                .map(|u| GuestUser {
+                    name: str_field(u, "userName"),
+                    domain: u.get("domain").and_then(|d| d.as_str()).map(String::from),
+                    login_time: u.get("loginTime").and_then(|t| t.as_f64()),
+                })
+                .collect(),
+            filesystems: guest_filesystems(&filesystems),
+        })
+    }
+
+    /// Filesystems of each VM in `vms` (namespace/name) from its guest
+    /// agent, for the fill alerts; VMs whose agent doesn't answer are left out
+    pub async fn collect_guest_filesystems(&self, vms: &[String]) -> Result<Vec<(String, Vec<GuestFilesystem>)>> {
+        use futures::stream::{self, StreamExt};
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(vms
+                .iter()
+                .map(|vm| {
+                    let name = vm.rsplit('/').next().unwrap_or(vm);
+                    (vm.clone(), self.mock_guest_info(name).filesystems)
+                })
+                .collect());
+        }
+
+        let filesystems = stream::iter(vms)
+            .map(|vm| async move {
+                let (namespace, name) = vm.split_once('/').unwrap_or(("default", vm));
+                let base = format!(
+                    "/apis/subresources.kubevirt.io/v1/namespaces/{}/virtualmachineinstances/{}",
+                    namespace, name
+                );
+                match self.get_raw(&base, "filesystemlist").await {
+                    Ok(list) => Some((vm.clone(), guest_filesystems(&list))),
+                    Err(e) => {
+                        tracing::debug!("Guest filesystems of {}: {}", vm, e);
+                        None
+                    }
+                }
+            })
+            .buffer_unordered(GUEST_AGENT_CONCURRENCY)
+            .filter_map(|result| async move { result })
+            .collect::<Vec<_>>()
+            .await;
+        Ok(filesystems)
+    }
+
+    /// Each VM's clock against the host's, read with the guest agent's
+    /// guest-get-time from inside its virt-launcher pod. `vms` are
+    /// (namespace/name, launcher pod as namespace/name, libvirt domain).
+    pub async fn collect_guest_clocks(&self, vms: &[(String, String, String)]) -> Result<Vec<GuestClock>> {
+        use futures::stream::{self, StreamExt};
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(self.mock_guest_clocks(vms));
+        }
+
+        let clocks = stream::iter(vms)
+            .map(|(vm, pod, domain)| async move {
+                let (drift_secs, uncertainty_secs, error) = match self.guest_time(pod, domain).await {
+                    Ok((drift, uncertainty)) => (Some(drift), uncertainty, None),
+                    Err(e) => (None, 0.0, Some(e.to_string())),
+                };
+                GuestClock {
+                    vm: vm.clone(),
+                    drift_secs,
+                    uncertainty_secs,
+                    error,
+                    checked: chrono::Local::now(),
+                }
+            })
+            .buffer_unordered(GUEST_AGENT_CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await;
+        Ok(clocks)
+    }
+
+    /// (guest minus host seconds, uncertainty) for one domain, taking the
+    /// host time halfway through the exec
+    async fn guest_time(&self, pod: &str, domain: &str) -> Result<(f64, f64)> {
+        let (namespace, pod) = pod.split_once('/').context("Launcher pod without namespace")?;
+        let before = chrono::Utc::now();
+        let output = self
+            .kubectl()
+            .args(["exec", "-n", namespace, pod, "-c", "compute", "--"])
+            .args(["virsh", "qemu-agent-command", domain, r#"{"execute":"guest-get-time"}"#])
+            .output()
+            .await
+            .context("Failed to execute kubectl")?;
+        let after = chrono::Utc::now();
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("exec failed");
+            anyhow::bail!("{}", message.trim().trim_start_matches("error: "));
+        }
+
+        let reply: serde_json::Value =
+            serde_json::from_slice(&output.stdout).context("Failed to parse guest agent response")?;
+        let guest_ns = reply.get("return").and_then(|r| r.as_i64()).context("Guest agent returned no time")?;
+        let host_ns = |t: chrono::DateTime<chrono::Utc>| t.timestamp_nanos_opt().unwrap_or_default();
+        let midpoint = (host_ns(before) + host_ns(after)) / 2;
+        let uncertainty = (host_ns(after) - host_ns(before)) as f64 / 2e9;
+        Ok(((guest_ns - midpoint) as f64 / 1e9, uncertainty))
+    }
+
+    async fn get_raw(&self, base: &str, subresource: &str) -> Result<serde_json::Value> {
+        let output = self
+            .kubectl()
+            .args(["get", "--raw", &format!("{}/{}", base, subresource)])
+            .output()
+            .await
+            .context("Failed to execute kubectl")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "{} query failed: {}",
+                subresource,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        serde_json::from_slice(&output.stdout).context("Failed to parse guest agent response")
+    }
+
+    /// Kubeconfig kubectl is pointed at, when it exists
+    pub fn kubeconfig_path(&self) -> Option<&Path> {
+        self.kubeconfig_path.as_deref().filter(|p| p.exists())
+    }
+
+    /// kubectl on the configured kubeconfig, killed when a timed-out
+    /// collection drops it
+    fn kubectl(&self) -> Command {
+        let mut cmd = Command::new("kubectl");
+        cmd.kill_on_drop(true);
+        if let Some(path) = self.kubeconfig_path.as_ref().filter(|p| p.exists()) {
+            cmd.arg("--kubeconfig").arg(path);
+        }
+        cmd
+    }
+
+    fn mock_cluster_info(&self) -> K8sClusterInfo {
+        K8sClusterInfo {
+            nodes_ready: 3,
+            nodes_total: 3,
+            pods_running: 45,
+            services: 23,
+        }
+    }
+
+    fn mock_kubevirt_info(&self) -> KubeVirtInfo {
+        KubeVirtInfo {
+            vms_running: 12,
+            vms_stopped: 3,
+            vms_migrating: 0,
+        }
+    }
+
+    fn mock_service_health(&self) -> ServiceHealth {
+        let endpoints = |namespace: &str, name: &str, ready, not_ready, vips: &[&str]| ServiceEndpoints {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            ready,
+            not_ready,
+            vips: vips.iter().map(|vip| vip.to_string()).collect(),
+        };
+        ServiceHealth {
+            endpoints: vec![
+                endpoints("apps", "billing-api", 0, 2, &["127.0.0.1:30080"]),
+                endpoints("default", "kubernetes", 1, 0, &[]),
+                endpoints("kube-system", "kube-dns", 2, 0, &[]),
+                endpoints("apps", "frontend", 3, 0, &["192.168.1.240:443"]),
+            ],
+            routes: vec![
+                IngressRoute {
+                    kind: "Ingress",
+                    namespace: "apps".to_string(),
+                    name: "frontend".to_string(),
+                    hosts: vec!["app.example.com".to_string()],
+                    cert_expiry: Some(chrono::Utc::now() + chrono::Duration::days(9)),
+                },
+                IngressRoute {
+                    kind: "HTTPRoute",
+                    namespace: "apps".to_string(),
+                    name: "billing".to_string(),
+                    hosts: vec!["billing.example.com".to_string()],
+                    cert_expiry: None,
+                },
+            ],
+        }
+    }
+
+    /// Start capturing a VM's serial console in the background, if it
+    /// isn't already
+    pub fn capture_console(&self, namespace: &str, name: &str) {
+        if let Some(client) = self.client.as_ref().filter(|_| !self.use_mock) {
+            self.consoles.capture(client, namespace, name);
+        }
+    }
+
+    /// Serial console output captured for a VM so far
+    pub fn console_capture(&self, namespace: &str, name: &str) -> Option<ConsoleCapture> {
+        if self.use_mock || self.client.is_none() {
+            return Some(Self::mock_console_capture(name));
+        }
+        self.consoles.snapshot(namespace, name)
+    }
+
+    /// Host sockets and proxy ports a VM's consoles are reachable on
+    pub async fn collect_console_endpoints(&self, namespace: &str, name: &str) -> Result<Vec<ConsoleEndpoint>> {
+        use kube::api::ListParams;
+
+        if self.use_mock || self.client.is_none() {
+            return Ok(Self::mock_console_endpoints(name));
+        }
+
+        let client = self.client.as_ref().unwrap();
+        let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+        let launchers = ListParams::default().labels(&format!("kubevirt.io=virt-launcher,vm.kubevirt.io/name={}", name));
+        let pods = self
+            .api
+            .list(&pods, "pods", &launchers, true)
+            .await
+            .context("Failed to list virt-launcher pods")?;
+        let launchers: Vec<(String, String)> = pods
+            .iter()
+            .filter_map(|pod| Some((pod.uid()?, pod.name_any())))
+            .collect();
+
+        let (namespace, name) = (namespace.to_string(), name.to_string());
+        tokio::task::spawn_blocking(move || console_endpoints(&launchers, &namespace, &name))
+            .await
+            .context("Console endpoint scan failed")
+    }
+
+    /// Start a `virtctl vnc` port-forward for a VM, returning where it listens
+    pub fn start_vnc_forward(&self, namespace: &str, name: &str) -> Result<std::net::SocketAddr, String> {
+        self.vnc.start(self.kubeconfig_path(), namespace, name)
+    }
+
+    pub fn stop_vnc_forward(&self, namespace: &str, name: &str) -> bool {
+        self.vnc.stop(namespace, name)
+    }
+
+    pub fn vnc_forward(&self, namespace: &str, name: &str) -> Option<VncForward> {
+        self.vnc.status(namespace, name)
+    }
+
+    fn mock_console_endpoints(name: &str) -> Vec<ConsoleEndpoint> {
+        let private = "/var/lib/kubelet/pods/4f1c2a7e-93b1-4c0e-8d2a-1b6f0e4a9c11/volumes/kubernetes.io~empty-dir/private/0d6e1f52-8a7b-4c39-a2e4-5f9b3c1d7e80";
+        let pod = format!("virt-launcher-{}-abcde", name);
+        vec![
+            ConsoleEndpoint {
+                kind: ConsoleKind::Vnc,
+                address: format!("{}/virt-vnc", private),
+                source: pod.clone(),
+            },
+            ConsoleEndpoint {
+                kind: ConsoleKind::Serial,
+                address: format!("{}/virt-serial0", private),
+                source: pod,
+            },
+            ConsoleEndpoint {
+                kind: ConsoleKind::PortForward,
+                address: "127.0.0.1:2222".to_string(),
+                source: "virtctl (pid 48213)".to_string(),
+            },
+        ]
+    }
+
+    fn mock_console_capture(name: &str) -> ConsoleCapture {
+        let lines = [
+            "-- console attached --",
+            "[    0.000000] Linux version 6.6.32 (nixbld@localhost) (gcc 13.2.0) #1-NixOS SMP PREEMPT_DYNAMIC",
+            "[    0.000000] Command line: init=/nix/store/x9c2-nixos-system/init console=ttyS0,115200",
+            "[    0.412345] Run /init as init process",
+            "<<< NixOS Stage 1 >>>",
+            "loading module virtio_blk...",
+            "running udev...",
+            "mounting /dev/vda1 on /...",
+            "<<< NixOS Stage 2 >>>",
+            "[    2.104213] systemd[1]: Reached target Multi-User System.",
+            "",
+            "<<< Welcome to NixOS 24.05 (x86_64) - ttyS0 >>>",
+            "",
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .chain(std::iter::once(format!("{} login: ", name)))
+        .collect();
+        ConsoleCapture {
+            lines,
+            connected: true,
+            error: None,
+            version: 1,
+        }
+    }
+
+    fn mock_vm(namespace: &str, name: &str) -> serde_json::Value {
+        serde_json::json!({
+            "apiVersion": "kubevirt.io/v1",
+            "kind": "VirtualMachine",
+            "metadata": { "name": name, "namespace": namespace, "resourceVersion": "1" },
+            "spec": {
+                "runStrategy": "Always",
+                "dataVolumeTemplates": [{
+                    "metadata": { "name": format!("{}-rootdisk", name) },
+                    "spec": {
+                        "sourceRef": { "kind": "DataSource", "name": "nixos", "namespace": "kubevirt-os-images" },
+                        "storage": { "resources": { "requests": { "storage": "20Gi" } } },
+                    },
+                }],
+                "template": {
+                    "metadata": { "labels": { "kubevirt.io/vm": name } },
+                    "spec": {
+                        "domain": {
+                            "devices": {
+                                "disks": [
+                                    { "name": "rootdisk", "disk": { "bus": "virtio" } },
+                                    { "name": "cloudinitdisk", "disk": { "bus": "virtio" } },
+                                ],
+                                "interfaces": [{ "name": "default", "masquerade": {}, "macAddress": "02:00:00:3a:1f:09" }],
+                            },
+                            "firmware": { "uuid": "5d307ca9-b3ef-428c-8861-06e72d69f223" },
+                            "resources": { "requests": { "memory": "4Gi" } },
+                        },
+                        "networks": [{ "name": "default", "pod": {} }],
+                        "volumes": [
+                            { "name": "rootdisk", "dataVolume": { "name": format!("{}-rootdisk", name) } },
+                            { "name": "data", "persistentVolumeClaim": { "claimName": format!("{}-data", name) } },
+                            { "name": "cloudinitdisk", "cloudInitNoCloud": {
+                                "userData": format!(
+                                    "#cloud-config\nhostname: {}\nusers:\n  - name: admin\n    passwd: $6$rounds=4096$mock$hash\n    ssh_authorized_keys:\n      - ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAImock admin@workstation\nruncmd:\n  - systemctl enable --now qemu-guest-agent\n",
+                                    name
+                                ),
+                                "networkData": "version: 2\nethernets:\n  enp1s0:\n    dhcp4: true\n",
+                            } },
+                        ],
+                    },
+                },
+            },
+        })
+    }
+
+    fn mock_guest_info(&self, name: &str) -> GuestInfo {
+        let gib = 1_073_741_824;
+        GuestInfo {
+            hostname: name.to_string(),
+            os: "NixOS 24.05 (Uakari)".to_string(),
+            kernel: "6.6.32".to_string(),
+            users: vec![GuestUser {
+                name: "admin".to_string(),
+                domain: None,
+                login_time: Some(chrono::Local::now().timestamp() as f64 - 3600.0),
+            }],
+            filesystems: vec![
+                GuestFilesystem {
+                    disk_name: "vda2".to_string(),
+                    mount_point: "/".to_string(),
+                    fs_type: "ext4".to_string(),
+                    used_bytes: 14 * gib,
+                    total_bytes: 20 * gib,
+                },
+                GuestFilesystem {
+                    disk_name: "vdb1".to_string(),
+                    mount_point: "/var/lib/data".to_string(),
+                    fs_type: "xfs".to_string(),
+                    used_bytes: 92 * gib,
+                    total_bytes: 100 * gib,
+                },
+            ],
+        }
+    }
+
+    fn mock_vm_usage(&self) -> Vec<VmUsage> {
+        // Stable pseudo-random spread so cells don't flicker between cycles
+        let drift = (chrono::Local::now().timestamp() / 10 % 7) as f64;
+        (0..160)
+            .map(|i| {
+                let running = i % 13 != 0;
+                let cpu = ((i * 37 + 11) % 100) as f64;
+                let memory = ((i * 53 + 29) % 90) as f64 + 5.0;
+                VmUsage {
+                    namespace: ["default", "apps", "ci"][i % 3].to_string(),
+                    name: format!("vm-{:03}", i),
+                    status: if running { "Running" } else { "Stopped" }.to_string(),
+                    cpu_percent: running.then_some((cpu + drift).min(100.0)),
+                    memory_percent: running.then_some(memory),
+                }
+            })
+            .collect()
+    }
+
+    fn mock_fleet(&self) -> Vec<NodeStatus> {
+        let gib = 1024.0 * 1024.0 * 1024.0;
+        (1..=6)
+            .map(|i| {
+                let reachability = match i {
+                    5 => NodeReachability::Unreachable,
+                    _ => NodeReachability::Ready,
+                };
+                let reporting = reachability == NodeReachability::Ready;
+                NodeStatus {
+                    name: format!("hypervisor-{:02}", i),
+                    roles: if i <= 3 { vec!["control-plane".to_string()] } else { Vec::new() },
+                    reachability,
+                    pressures: if i == 4 { vec!["MemoryPressure".to_string()] } else { Vec::new() },
+                    kubelet_version: "v1.29.3+k3s1".to_string(),
+                    cpu_percent: reporting.then_some(((i * 23 + 7) % 90) as f64),
+                    memory_percent: reporting.then_some(if i == 4 { 93.0 } else { ((i * 31 + 20) % 80) as f64 }),
+                    cpu_allocatable: 64.0,
+                    memory_allocatable_bytes: 512.0 * gib,
+                    pods: 20 + i as u32 * 3,
+                    vms: (0..i * 4).map(|v| format!("default/vm-{:02}-{:02}", i, v)).collect(),
+                }
+            })
+            .collect()
+    }
+
+    fn mock_disk_references(&self) -> DiskReferences {
+        let gib = 1_073_741_824;
+        let claim = |namespace: &str, name: &str, volume: &str, size: u64, data_volume: bool, mounted_by: Option<&str>| {
+            ClaimInfo {
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+                volume: Some(volume.to_string()),
+                size_bytes: size * gib,
+                data_volume: data_volume.then(|| name.to_string()),
+                mounted_by: mounted_by.map(str::to_string),
+            }
+        };
+        DiskReferences {
+            host_paths: HashMap::from([(
+                "/var/lib/libvirt/images/vm-build-01.qcow2".to_string(),
+                "default/vm-build-01".to_string(),
+            )]),
+            claims: HashMap::from([
+                ("default/vm-webserver-01-rootdisk".to_string(), "default/vm-webserver-01".to_string()),
+                ("default/vm-database-01-rootdisk".to_string(), "default/vm-database-01".to_string()),
+            ]),
+            all_claims: vec![
+                claim("default", "vm-webserver-01-rootdisk", "pvc-4c1f0a", 20, true, Some("default/virt-launcher-vm-webserver-01-abcde")),
+                claim("default", "vm-database-01-rootdisk", "pvc-8d2e71", 100, true, Some("default/virt-launcher-vm-database-01-fghij")),
+                claim("default", "vm-legacy-02-rootdisk", "pvc-19ab3c", 80, true, None),
+                claim("tenant-a", "scratch-data", "pvc-5e0d92", 200, false, None),
+            ],
+            volumes: HashSet::from(["pvc-4c1f0a", "pvc-8d2e71", "pvc-19ab3c", "pvc-5e0d92"].map(str::to_string)),
+        }
+    }
+
+    fn mock_migrations(&self) -> Vec<MigrationRecord> {
+        let now = chrono::Local::now();
+        let migration = |uid: &str, vmi: &str, source: &str, target: &str, phase: &str, ago_min: i64, secs: i64, gib: u64| {
+            let started = now - chrono::Duration::minutes(ago_min);
+            MigrationRecord {
+                uid: uid.to_string(),
+                vmi: vmi.to_string(),
+                source_node: Some(source.to_string()),
+                target_node: Some(target.to_string()),
+                phase: phase.to_string(),
+                started: Some(started),
+                ended: (phase != "Running").then(|| started + chrono::Duration::seconds(secs)),
+                transferred_bytes: Some(gib * 1_073_741_824),
+                failure_reason: (phase == "Failed")
+                    .then(|| "Live migration failed: connection to target timed out".to_string()),
+            }
+        };
+        vec![
+            migration("mig-1", "default/vm-webserver-01", "node-01", "node-02", "Succeeded", 180, 42, 4),
+            migration("mig-2", "default/vm-database-01", "node-02", "node-03", "Failed", 95, 300, 11),
+            migration("mig-3", "default/vm-database-01", "node-02", "node-03", "Failed", 60, 300, 12),
+            migration("mig-4", "tenant-a/vm-ci-runner", "node-01", "node-03", "Succeeded", 20, 17, 2),
+            migration("mig-5", "default/vm-webserver-01", "node-02", "node-01", "Running", 1, 0, 1),
+        ]
+    }
+
+    fn mock_vm_boots(&self) -> Vec<BootRecord> {
+        let now = chrono::Local::now();
+        let boot = |uid: &str, vm: &str, node: &str, ago_min: i64, running_secs: Option<i64>, agent_secs: Option<i64>| {
+            let requested = now - chrono::Duration::minutes(ago_min);
+            BootRecord {
+                uid: uid.to_string(),
+                vm: vm.to_string(),
+                node: Some(node.to_string()),
+                requested,
+                running: running_secs.map(|secs| requested + chrono::Duration::seconds(secs)),
+                agent_ready: agent_secs.map(|secs| requested + chrono::Duration::seconds(secs)),
+            }
+        };
+        vec![
+            boot("vmi-1", "default/vm-webserver-01", "node-01", 600, Some(14), Some(41)),
+            boot("vmi-2", "default/vm-database-01", "node-02", 240, Some(95), Some(160)),
+            boot("vmi-3", "tenant-a/vm-ci-runner", "node-03", 30, Some(11), None),
+            boot("vmi-4", "tenant-a/vm-build-02", "node-01", 1, None, None),
+        ]
+    }
+
+    fn mock_data_volumes(&self) -> Vec<DataVolume> {
+        let now = chrono::Local::now();
+        let volume = |namespace: &str, name: &str, phase: &str, progress: Option<f64>, source: &str, restarts: u32, message: Option<&str>, owner: &str, ago_min: i64| DataVolume {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            phase: phase.to_string(),
+            progress,
+            source: source.to_string(),
+            restarts,
+            message: message.map(|m| m.to_string()),
+            owner: Some(owner.to_string()),
+            created: Some(now - chrono::Duration::minutes(ago_min)),
+            changed: None,
+        };
+        vec![
+            volume("default", "vm-webserver-02-rootdisk", "ImportInProgress", Some(42.7), "http", 0, None, "vm-webserver-02", 6),
+            volume(
+                "tenant-a",
+                "vm-ci-runner-02-rootdisk",
+                "ImportInProgress",
+                Some(0.0),
+                "registry",
+                4,
+                Some("Error: unable to pull image docker.io/example/ci-runner-disk:latest: manifest unknown"),
+                "vm-ci-runner-02",
+                25,
+            ),
+            volume("tenant-a", "vm-ci-runner-03-rootdisk", "CloneInProgress", Some(88.1), "pvc", 0, None, "vm-ci-runner-03", 3),
+            volume("default", "vm-database-01-rootdisk", "Succeeded", Some(100.0), "http", 0, None, "vm-database-01", 4320),
+        ]
+    }
+
+    fn mock_launcher_pods(&self) -> HashMap<String, String> {
+        [
+            ("4f1c2a7e-93b1-4c0e-8d2a-1b6f0e4a9c11", "default/virt-launcher-vm-webserver-01-abcde"),
+            ("9b07de14-5a2f-4e6b-b1c3-7d8e2f0a6b22", "default/virt-launcher-vm-database-01-fghij"),
+            ("c3d9a1f0-2e7b-4a58-9f61-0c4b8e7d5a33", "tenant-a/virt-launcher-vm-ci-runner-klmno"),
+        ]
+        .into_iter()
+        .map(|(uid, pod)| (uid.to_string(), pod.to_string()))
+        .collect()
+    }
+
+    fn mock_vm_network_policies(&self) -> Vec<NamespacePolicies> {
+        let allow = |rules: &[&str]| PolicyAccess::Allow(rules.iter().map(|r| r.to_string()).collect());
+        vec![
+            NamespacePolicies {
+                namespace: "default".to_string(),
+                policies: vec!["allow-web".to_string(), "db-from-web".to_string()],
+                vm_count: 2,
+                vms: vec![
+                    VmNetworkPolicy {
+                        vm: "vm-database-01".to_string(),
+                        policies: vec!["db-from-web".to_string()],
+                        ingress: allow(&["from pods vm.kubevirt.io/name=vm-webserver-01 on TCP/5432"]),
+                        egress: PolicyAccess::Unrestricted,
+                        dns_blocked: false,
+                    },
+                    VmNetworkPolicy {
+                        vm: "vm-webserver-01".to_string(),
+                        policies: vec!["allow-web".to_string()],
+                        ingress: allow(&["from anywhere on TCP/80, TCP/443"]),
+                        egress: allow(&["to 10.0.0.0/8 on TCP/5432", "to ns kubernetes.io/metadata.name=kube-system on UDP/53, TCP/53"]),
+                        dns_blocked: false,
+                    },
+                ],
+            },
+            NamespacePolicies {
+                namespace: "tenant-a".to_string(),
+                policies: vec!["default-deny".to_string()],
+                vm_count: 1,
+                vms: vec![VmNetworkPolicy {
+                    vm: "vm-ci-runner-03".to_string(),
+                    policies: vec!["default-deny".to_string()],
+                    ingress: PolicyAccess::DenyAll,
+                    egress: PolicyAccess::DenyAll,
+                    dns_blocked: true,
+                }],
+            },
+        ]
+    }
+
+    fn mock_image_pulls(&self) -> Vec<ImagePull> {
+        let now = chrono::Local::now();
+        vec![
+            ImagePull {
+                image: "registry.local:5000/tenant-a/app:2.4.1".to_string(),
+                registry: "registry.local:5000".to_string(),
+                pulls: 0,
+                failures: 6,
+                last_pulled: None,
+                last_duration: None,
+                last_failed: Some(now - chrono::Duration::minutes(2)),
+                last_error: Some("rpc error: code = Unavailable desc = failed to pull and unpack image: dial tcp 10.0.0.5:5000: connect: connection refused".to_string()),
+                nodes: vec!["hypervisor-01".to_string()],
+            },
+            ImagePull {
+                image: "quay.io/kubevirt/virt-launcher:v1.2.0".to_string(),
+                registry: "quay.io".to_string(),
+                pulls: 3,
+                failures: 0,
+                last_pulled: Some(now - chrono::Duration::minutes(14)),
+                last_duration: Some(std::time::Duration::from_millis(8420)),
+                last_failed: None,
+                last_error: None,
+                nodes: vec!["hypervisor-01".to_string(), "hypervisor-02".to_string()],
+            },
+        ]
+    }
+
+    fn mock_pod_health(&self) -> Vec<PodHealth> {
+        // Keeps crashing, about one restart every two minutes
+        let crashes = 40 + (chrono::Local::now().timestamp() / 120 % 10_000) as u32;
+        vec![
+            PodHealth {
+                namespace: "tenant-a".to_string(),
+                name: "app-api-7d9f8c6b5-x2k4p".to_string(),
+                node: Some("hypervisor-01".to_string()),
+                restarts: crashes,
+                waiting: Some(PodWaiting {
+                    container: "api".to_string(),
+                    reason: "CrashLoopBackOff".to_string(),
+                    message: Some("back-off 5m0s restarting failed container=api pod=app-api-7d9f8c6b5-x2k4p_tenant-a".to_string()),
+                }),
+                recent_restarts: 0,
+            },
+            PodHealth {
+                namespace: "tenant-a".to_string(),
+                name: "app-worker-5c8d7b9f4-m7q2z".to_string(),
+                node: Some("hypervisor-01".to_string()),
+                restarts: 0,
+                waiting: Some(PodWaiting {
+                    container: "worker".to_string(),
+                    reason: "ImagePullBackOff".to_string(),
+                    message: Some("Back-off pulling image \"registry.local:5000/tenant-a/app:2.4.1\"".to_string()),
+                }),
+                recent_restarts: 0,
+            },
+            PodHealth {
+                namespace: "kubevirt".to_string(),
+                name: "virt-handler-8kq2n".to_string(),
+                node: Some("hypervisor-02".to_string()),
+                restarts: 2,
+                waiting: None,
+                recent_restarts: 0,
+            },
+        ]
+    }
+
+    fn mock_guest_clocks(&self, vms: &[(String, String, String)]) -> Vec<GuestClock> {
+        vms.iter()
+            .enumerate()
+            .map(|(i, (vm, _, _))| {
+                // One guest with broken time sync, one without an agent
+                let (drift_secs, error) = match i % 5 {
+                    1 => (Some(47.3), None),
+                    3 => (None, Some("Guest agent is not responding: QEMU guest agent is not connected".to_string())),
+                    _ => (Some(0.02 * i as f64 - 0.03), None),
+                };
+                GuestClock {
+                    vm: vm.clone(),
+                    drift_secs,
+                    uncertainty_secs: 0.08,
+                    error,
+                    checked: chrono::Local::now(),
+                }
+            })
+            .collect()
+    }
+
+    fn mock_tenant_usage(&self) -> Vec<TenantUsage> {
+        let gib = 1024 * 1024 * 1024;
+        let entries: &[(&str, usize, usize, u32, u64, u64, usize)] = &[
+            ("(none)", 2, 2, 4, 8, 60, 14),
+            ("tenant-a", 12, 10, 48, 192, 1400, 6),
+            ("tenant-b", 3, 1, 8, 32, 250, 2),
+        ];
+        entries
+            .iter()
+            .map(|&(tenant, vms, running_vms, vcpus, memory_gib, storage_gib, pods)| TenantUsage {
+                tenant: tenant.to_string(),
+                vms,
+                running_vms,
+                vcpus,
+                memory_bytes: memory_gib * gib,
+                storage_bytes: storage_gib * gib,
+                pods,
+                pod_cpu_cores: pods as f64 * 0.25,
+                pod_memory_bytes: pods as u64 * 256 * 1024 * 1024,
+                growth_percent: None,
+            })
+            .collect()
+    }
+
+    fn mock_quotas(&self) -> Vec<QuotaUsage> {
+        let gib = 1024.0 * 1024.0 * 1024.0;
+        let entries: &[(&str, &str, f64, f64)] = &[
+            ("kubevirt", "requests.cpu", 6.5, 8.0),
+            ("kubevirt", "requests.memory", 14.0 * gib, 16.0 * gib),
+            ("tenant-a", "requests.cpu", 28.0, 32.0),
+            ("tenant-a", "requests.memory", 118.0 * gib, 128.0 * gib),
+            ("tenant-a", "persistentvolumeclaims", 19.0, 20.0),
+            ("tenant-a", "requests.storage", 1400.0 * gib, 2048.0 * gib),
+            ("tenant-b", "requests.cpu", 4.0, 16.0),
+            ("tenant-b", "requests.memory", 20.0 * gib, 64.0 * gib),
+            ("tenant-b", "persistentvolumeclaims", 3.0, 10.0),
+        ];
+
+        entries
+            .iter()
+            .map(|(namespace, resource, used, hard)| QuotaUsage {
+                namespace: namespace.to_string(),
+                quota: "compute-quota".to_string(),
+                resource: resource.to_string(),
+                used: *used,
+                hard: *hard,
+            })
+            .collect()
+    }
+
+    fn mock_resources(&self, kind: ResourceKind) -> Vec<K8sResource> {
+        let entries: &[(&str, Option<&str>, &str)] = match kind {
+            ResourceKind::Node => &[
+                ("hypervisor-01", None, "Ready"),
+                ("hypervisor-02", None, "Ready"),
+                ("hypervisor-03", None, "Ready"),
+            ],
+            ResourceKind::Pod => &[
+                ("coredns-6799fbcd5-x2k9p", Some("kube-system"), "Running"),
+                ("virt-handler-7zq4m", Some("kubevirt"), "Running"),
+                ("virt-launcher-vm-webserver-01-abcde", Some("default"), "Running"),
+            ],
+            ResourceKind::Service => &[
+                ("kubernetes", Some("default"), "ClusterIP"),
+                ("kube-dns", Some("kube-system"), "ClusterIP"),
+            ],
+            ResourceKind::VirtualMachine => &[
+                ("vm-webserver-01", Some("default"), "Running"),
+                ("vm-database-01", Some("default"), "Running"),
+                ("vm-build-01", Some("default"), "Stopped"),
+            ],
+        };
+        let labels = |name: &str| -> BTreeMap<String, String> {
+            let (tenant, environment) = match name {
+                "vm-webserver-01" => ("acme", "production"),
+                "vm-database-01" => ("acme", "staging"),
+                "vm-build-01" => ("globex", "ci"),
+                _ => return BTreeMap::new(),
+            };
+            [("tenant", tenant), ("environment", environment)]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+
+        entries
+            .iter()
+            .map(|(name, namespace, status)| K8sResource {
+                kind,
+                name: name.to_string(),
+                namespace: namespace.map(|ns| ns.to_string()),
+                status: status.to_string(),
+                labels: labels(name),
+            })
+            .collect()
+    }
+}
+
+/// Kubernetes CPU quantity ("250m", "2", "123456789n") in cores
+fn parse_cpu_quantity(quantity: &str) -> Option<f64> {
+    let (number, divisor) = if let Some(n) = quantity.strip_suffix('n') {
+        (n, 1e9)
+    } else if let Some(u) = quantity.strip_suffix('u') {
+        (u, 1e6)
+    } else if let Some(m) = quantity.strip_suffix('m') {
+        (m, 1e3)
+    } else {
+        (quantity, 1.0)
+    };
+    number.parse::<f64>().ok().map(|v| v / divisor)
+}
+
+/// Kubernetes memory quantity ("512Mi", "2Gi", "1G", "1048576") in bytes
+fn parse_memory_quantity(quantity: &str) -> Option<f64> {
+    const SUFFIXES: [(&str, f64); 10] = [
+        ("Ki", 1024.0),
+        ("Mi", 1024.0 * 1024.0),
+        ("Gi", 1024.0 * 1024.0 * 1024.0),
+        ("Ti", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        ("k", 1e3),
+        ("K", 1e3),
+        ("M", 1e6),
+        ("G", 1e9),
+        ("T", 1e12),
+        ("", 1.0),
+    ];
+    SUFFIXES.iter().find_map(|(suffix, multiplier)| {
+        quantity
+            .strip_suffix(suffix)
+            .and_then(|n| n.parse::<f64>().ok())
+            .map(|v| v * multiplier)
+    })
+}
+
+/// A Kubernetes label selector written the way `LabelSelector::parse`
+/// reads it; an empty one selects everything
+fn selector_text(selector: &k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector) -> String {
+    let mut terms: Vec<String> = selector
+        .match_labels
+        .iter()
+        .flatten()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+    for expression in selector.match_expressions.iter().flatten() {
+        let values = expression.values.clone().unwrap_or_default().join(",");
+        terms.push(match expression.operator.as_str() {
+            "In" => format!("{} in ({})", expression.key, values),
+            "NotIn" => format!("{} notin ({})", expression.key, values),
+            "DoesNotExist" => format!("!{}", expression.key),
+            _ => expression.key.clone(),
+        });
+    }
+    terms.join(",")
+}
+
+/// Who a policy rule's peers are; empty when the rule allows anyone
+fn peers_text(peers: Option<&[NetworkPolicyPeer]>) -> Vec<String> {
+    let selector = |selector: &Option<k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector>| {
+        selector.as_ref().map(selector_text)
+    };
+    peers
+        .into_iter()
+        .flatten()
+        .map(|peer| {
+            if let Some(ref block) = peer.ip_block {
+                return match block.except {
+                    Some(ref except) if !except.is_empty() => format!("{} except {}", block.cidr, except.join(" ")),
+                    _ => block.cidr.clone(),
+                };
+            }
+            match (selector(&peer.pod_selector), selector(&peer.namespace_selector)) {
+                (Some(pods), Some(ns)) if pods.is_empty() && ns.is_empty() => "all namespaces".to_string(),
+                (Some(pods), Some(ns)) if pods.is_empty() => format!("ns {}", ns),
+                (Some(pods), Some(ns)) if ns.is_empty() => format!("pods {} in any ns", pods),
+                (Some(pods), Some(ns)) => format!("pods {} in ns {}", pods, ns),
+                (None, Some(ns)) if ns.is_empty() => "all namespaces".to_string(),
+                (None, Some(ns)) => format!("ns {}", ns),
+                (Some(pods), None) if !pods.is_empty() => format!("pods {}", pods),
+                _ => "own namespace".to_string(),
+            }
+        })
+        .collect()
+}
+
+/// A rule's ports as "TCP/443"; empty when it allows any port
+fn ports_text(ports: Option<&[NetworkPolicyPort]>) -> Vec<String> {
+    ports
+        .into_iter()
+        .flatten()
+        .map(|port| {
+            let protocol = port.protocol.as_deref().unwrap_or("TCP");
+            match (&port.port, port.end_port) {
+                (Some(IntOrString::Int(start)), Some(end)) => format!("{}/{}-{}", protocol, start, end),
+                (Some(IntOrString::Int(number)), None) => format!("{}/{}", protocol, number),
+                (Some(IntOrString::String(name)), _) => format!("{}/{}", protocol, name),
+                (None, _) => format!("{}/any", protocol),
+            }
+        })
+        .collect()
+}
+
+/// Whether an egress rule's ports let DNS lookups through
+fn allows_dns(ports: Option<&[NetworkPolicyPort]>) -> bool {
+    let Some(ports) = ports.filter(|ports| !ports.is_empty()) else {
+        return true;
+    };
+    ports.iter().any(|port| match port.port {
+        None => true,
+        Some(IntOrString::Int(number)) => number == 53 || port.end_port.is_some_and(|end| number < 53 && end >= 53),
+        Some(IntOrString::String(ref name)) => name.starts_with("dns"),
+    })
+}
+
+/// TCP addresses a LoadBalancer or NodePort Service can be reached at
+/// from the host. LoadBalancer ingress IPs are used when assigned, node
+/// ports on loopback otherwise (kube-proxy accepts them there).
+fn service_vips(svc: &Service) -> Vec<String> {
+    let Some(spec) = svc.spec.as_ref() else {
+        return Vec::new();
+    };
+    let tcp_ports = || {
+        spec.ports
+            .iter()
+            .flatten()
+            .filter(|port| port.protocol.as_deref().unwrap_or("TCP") == "TCP")
+    };
+    let ingress_ips: Vec<&str> = svc
+        .status
+        .as_ref()
+        .and_then(|status| status.load_balancer.as_ref())
+        .and_then(|lb| lb.ingress.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|ingress| ingress.ip.as_deref())
+        .collect();
+
+    match spec.type_.as_deref() {
+        Some("LoadBalancer") if !ingress_ips.is_empty() => ingress_ips
+            .iter()
+            .flat_map(|ip| tcp_ports().map(move |port| format_address(ip, port.port)))
+            .collect(),
+        Some("LoadBalancer") | Some("NodePort") => tcp_ports()
+            .filter_map(|port| port.node_port)
+            .map(|node_port| format_address("127.0.0.1", node_port))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn format_address(ip: &str, port: i32) -> String {
+    if ip.contains(':') {
+        format!("[{}]:{}", ip, port)
+    } else {
+        format!("{}:{}", ip, port)
+    }
+}
+
+/// Registry host of an image reference; Docker Hub when the first path
+/// component doesn't look like a host
+fn image_registry(image: &str) -> &str {
+    match image.split_once('/') {
+        Some((host, _)) if host.contains('.') || host.contains(':') || host == "localhost" => host,
+        _ => "docker.io",
+    }
+}
+
+/// Parse a Go duration as kubelet prints it ("1.5s", "2m3.25s", "850ms")
+fn parse_go_duration(text: &str) -> Option<std::time::Duration> {
+    let mut total = 0.0;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let split = rest.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let value: f64 = rest[..split].parse().ok()?;
+        rest = &rest[split..];
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let seconds = match &rest[..unit_len] {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" => 1.0,
+            "ms" => 0.001,
+            "µs" | "us" => 0.000_001,
+            "ns" => 0.000_000_001,
+            _ => return None,
+        };
+        total += value * seconds;
+        rest = &rest[unit_len..];
+    }
+    Some(std::time::Duration::from_secs_f64(total))
+}
+
+/// `kubevirt_vmi_migration_data_processed_bytes` samples by VMI
+/// (namespace/name) from a virt-handler metrics page
+fn parse_migration_metrics(metrics: &str) -> HashMap<String, u64> {
+    // Anchored on the comma so "name" doesn't match inside "node_name"
+    let label = |labels: &str, key: &str| {
+        let labels = format!(",{}", labels);
+        let start = labels.find(&format!(",{}=\"", key))? + key.len() + 3;
+        let end = labels[start..].find('"')? + start;
+        Some(labels[start..end].to_string())
+    };
+    metrics
+        .lines()
+        .filter_map(|line| line.strip_prefix("kubevirt_vmi_migration_data_processed_bytes{"))
+        .filter_map(|rest| {
+            let (labels, value) = rest.split_once('}')?;
+            let value = value.split_whitespace().next()?.parse::<f64>().ok()?;
+            let vmi = format!("{}/{}", label(labels, "namespace")?, label(labels, "name")?);
+            Some((vmi, value as u64))
+        })
+        .collect()
+}
+
+/// The items of a guest agent filesystemlist response
+fn guest_filesystems(list: &serde_json::Value) -> Vec<GuestFilesystem> {
+    let str_field = |v: &serde_json::Value, key: &str| {
+        v.get(key).and_then(|s| s.as_str()).unwrap_or_default().to_string()
+    };
+    list.get("items")
+        .and_then(|i| i.as_array())
+        .into_iter()
+        .flatten()
+        .map(|fs| GuestFilesystem {
+            disk_name: str_field(fs, "diskName"),
+            mount_point: str_field(fs, "mountPoint"),
+            fs_type: str_field(fs, "fileSystemType"),
+            used_bytes: fs.get("usedBytes").and_then(|b| b.as_u64()).unwrap_or(0),
+            total_bytes: fs.get("totalBytes").and_then(|b| b.as_u64()).unwrap_or(0),
+        })
+        .collect()
+}
+
+/// notAfter of the first certificate in a PEM bundle, read straight from
+/// its DER: Certificate -> tbsCertificate -> validity, skipping the
+/// version, serial, signature algorithm and issuer before it
+fn certificate_not_after(pem: &[u8]) -> Option<chrono::DateTime<chrono::Utc>> {
+    use base64::Engine;
+
+    let text = std::str::from_utf8(pem).ok()?;
+    let body = text.split("-----BEGIN CERTIFICATE-----").nth(1)?.split("-----END CERTIFICATE-----").next()?;
+    let body: String = body.chars().filter(|c| !c.is_whitespace()).collect();
+    let der = base64::engine::general_purpose::STANDARD.decode(body).ok()?;
+
+    let (0x30, certificate, _) = der_element(&der)? else {
+        return None;
+    };
+    let (0x30, mut tbs, _) = der_element(certificate)? else {
+        return None;
+    };
+    // [0] version is absent on v1 certificates
+    if tbs.first() == Some(&0xa0) {
+        tbs = der_element(tbs)?.2;
+    }
+    for _ in ["serial", "signature", "issuer"] {
+        tbs = der_element(tbs)?.2;
+    }
+    let (0x30, validity, _) = der_element(tbs)? else {
+        return None;
+    };
+    let (tag, not_after, _) = der_element(der_element(validity)?.2)?;
+    let time = std::str::from_utf8(not_after).ok()?;
+    let time = match tag {
+        // UTCTime, YYMMDDHHMMSSZ: years from 50 are 19xx
+        0x17 => format!("{}{}", if time.get(..2)? >= "50" { "19" } else { "20" }, time),
+        // GeneralizedTime, YYYYMMDDHHMMSSZ
+        0x18 => time.to_string(),
+        _ => return None,
+    };
+    chrono::NaiveDateTime::parse_from_str(&time, "%Y%m%d%H%M%SZ").ok().map(|dt| dt.and_utc())
+}
+
+/// Tag, contents and what follows of the DER element at the start of `input`
+fn der_element(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (len, rest) = match first {
+        len @ 0..=0x7f => (len as usize, rest),
+        0x81..=0x84 => {
+            let count = (first & 0x7f) as usize;
+            let bytes = rest.get(..count)?;
+            (bytes.iter().fold(0, |len, &b| len << 8 | b as usize), &rest[count..])
+        }
+        _ => return None,
+    };
+    let contents = rest.get(..len)?;
+    Some((tag, contents, &rest[len..]))
 }
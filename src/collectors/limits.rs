@@ -0,0 +1,44 @@
+use anyhow::{Context, Result};
+use crate::types::HostLimits;
+use std::fs;
+
+/// Entropy available to the kernel's random pool, and tasks against the
+/// PID space (kernel.pid_max, or kernel.threads-max when lower). Open
+/// files against fs.file-max come from the socket collector.
+pub struct LimitsCollector;
+
+impl LimitsCollector {
+    pub fn new() -> Result<Self> {
+        Ok(Self)
+    }
+
+    pub async fn collect(&mut self) -> Result<HostLimits> {
+        // "0.19 0.46 0.51 2/72 27351": runnable/total scheduling entities,
+        // one per thread and each holding a PID
+        let loadavg = fs::read_to_string("/proc/loadavg").context("Failed to read /proc/loadavg")?;
+        let tasks = loadavg
+            .split_whitespace()
+            .nth(3)
+            .and_then(|field| field.split_once('/'))
+            .and_then(|(_, total)| total.parse().ok())
+            .context("Unexpected /proc/loadavg format")?;
+
+        let pid_max = read_number("/proc/sys/kernel/pid_max").context("Failed to read kernel.pid_max")?;
+        let (task_limit, task_limit_name) = match read_number("/proc/sys/kernel/threads-max") {
+            Some(threads_max) if threads_max < pid_max => (threads_max, "kernel.threads-max"),
+            _ => (pid_max, "kernel.pid_max"),
+        };
+
+        Ok(HostLimits {
+            entropy_bits: read_number("/proc/sys/kernel/random/entropy_avail"),
+            entropy_pool_bits: read_number("/proc/sys/kernel/random/poolsize"),
+            tasks,
+            task_limit,
+            task_limit_name,
+        })
+    }
+}
+
+fn read_number(path: &str) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
@@ -0,0 +1,149 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+// klog header: "E1017 05:48:53.123456    1234 controller.go:123] message"
+static KLOG_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([IWEF])\d{4} \d{2}:\d{2}:\d{2}\.\d+\s+\d+ ([^:\]\s]+):\d+\] (.*)$").unwrap()
+});
+
+/// Keys naming the emitting component in logfmt lines
+const COMPONENT_KEYS: [&str; 4] = ["component", "logger", "module", "controller"];
+
+/// Keys already shown elsewhere, not repeated as fields
+const HIDDEN_KEYS: [&str; 3] = ["time", "level", "msg"];
+
+/// Line formats a service is known to write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFormat {
+    /// Kubernetes klog, optionally with structured `"msg" key=value` text
+    Klog,
+    /// logrus/logfmt `time=... level=... msg=... key=value`
+    Logfmt,
+}
+
+/// A journal message decoded by its service's format
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuredLine {
+    /// Normalized to ERROR, WARN, INFO or DEBUG
+    pub level: String,
+    pub component: Option<String>,
+    pub message: String,
+    pub fields: Vec<(String, String)>,
+}
+
+/// k3s and RKE2 embed both klog (from the Kubernetes components) and
+/// logrus lines in one unit; containerd and docker use logfmt only
+fn formats_for(service: &str) -> &'static [LogFormat] {
+    match service {
+        s if s.starts_with("k3s") || s.starts_with("rke2") || s == "kubelet" => {
+            &[LogFormat::Klog, LogFormat::Logfmt]
+        }
+        "containerd" | "docker" => &[LogFormat::Logfmt],
+        _ => &[],
+    }
+}
+
+/// Parse a message with the formats its service writes; None for services
+/// without a known format or lines that match none of them
+pub fn parse_structured(service: &str, message: &str) -> Option<StructuredLine> {
+    formats_for(service).iter().find_map(|format| match format {
+        LogFormat::Klog => parse_klog(message),
+        LogFormat::Logfmt => parse_logfmt(message),
+    })
+}
+
+fn parse_klog(message: &str) -> Option<StructuredLine> {
+    let captures = KLOG_REGEX.captures(message)?;
+    let level = match &captures[1] {
+        "E" | "F" => "ERROR",
+        "W" => "WARN",
+        _ => "INFO",
+    };
+    let component = captures[2].trim_end_matches(".go").to_string();
+    let text = &captures[3];
+
+    // Structured klog: a quoted message followed by key=value pairs
+    let (message, fields) = match text.strip_prefix('"').and_then(|_| quoted(text)) {
+        Some((msg, rest)) => (msg, logfmt_pairs(rest)),
+        None => (text.to_string(), Vec::new()),
+    };
+
+    Some(StructuredLine {
+        level: level.to_string(),
+        component: Some(component),
+        message,
+        fields,
+    })
+}
+
+fn parse_logfmt(message: &str) -> Option<StructuredLine> {
+    let pairs = logfmt_pairs(message);
+    let get = |key: &str| pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+    let (level, msg) = (get("level")?, get("msg")?);
+
+    let component_key = COMPONENT_KEYS.iter().find(|key| get(key).is_some()).copied();
+    let level = match level.to_ascii_lowercase().as_str() {
+        "error" | "fatal" | "panic" => "ERROR",
+        "warning" | "warn" => "WARN",
+        "debug" | "trace" => "DEBUG",
+        _ => "INFO",
+    };
+
+    Some(StructuredLine {
+        level: level.to_string(),
+        component: component_key.and_then(get),
+        message: msg,
+        fields: pairs
+            .into_iter()
+            .filter(|(k, _)| !HIDDEN_KEYS.contains(&k.as_str()) && Some(k.as_str()) != component_key)
+            .collect(),
+    })
+}
+
+/// `key=value` and `key="quoted value"` pairs; stray words are skipped
+fn logfmt_pairs(text: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        let Some(eq) = rest.find(['=', ' ']).filter(|&i| rest.as_bytes()[i] == b'=') else {
+            // A word without '=', skip it
+            rest = rest.split_once(' ').map_or("", |(_, r)| r).trim_start();
+            continue;
+        };
+        let key = rest[..eq].to_string();
+        let after = &rest[eq + 1..];
+        let (value, remaining) = if after.starts_with('"') {
+            match quoted(after) {
+                Some(parsed) => parsed,
+                None => (after.to_string(), ""),
+            }
+        } else {
+            let end = after.find(' ').unwrap_or(after.len());
+            (after[..end].to_string(), &after[end..])
+        };
+        if !key.is_empty() {
+            pairs.push((key, value));
+        }
+        rest = remaining.trim_start();
+    }
+    pairs
+}
+
+/// Unescape a leading double-quoted string, returning it and what follows
+fn quoted(text: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, escaped)) => value.push(escaped),
+                None => return None,
+            },
+            '"' => return Some((value, &text[i + 1..])),
+            c => value.push(c),
+        }
+    }
+    None
+}
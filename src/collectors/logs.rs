@@ -1,17 +1,78 @@
 use anyhow::{Result, Context};
+use super::log_format::parse_structured;
+use crate::redact::LogRedactor;
 use crate::types::LogEntry;
-use std::collections::VecDeque;
-use std::process::Command;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use tokio::process::Command;
 use std::io::{BufRead, BufReader};
 use serde::Deserialize;
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone};
 use regex::Regex;
 use once_cell::sync::Lazy;
 
+// How far either side of an anchor line the journal is searched for context
+const CONTEXT_WINDOW_SECS: i64 = 300;
+
 static LEVEL_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?i)(error|err|critical|crit|warn|warning|info|debug)").unwrap()
 });
 
+// Values that vary between repeats of one message: UUIDs, hex IDs,
+// timestamps, clock times and durations. Bare numbers are left to
+// `repeat_key`, which can tell them from digits in names.
+static VARIABLE_TOKEN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(?i)[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}",
+        r"|0x[0-9a-f]+|\b[0-9a-f]{12,}\b",
+        r"|\d{4}-\d{2}-\d{2}[t ]\d{2}:\d{2}:\d{2}(?:[.,]\d+)?(?:z|[+-]\d{2}:?\d{2})?",
+        r"|\b\d{1,2}:\d{2}:\d{2}(?:[.,]\d+)?\b",
+        r"|\b(?:\d+(?:\.\d+)?(?:ns|us|µs|ms|s|m|h))+\b",
+    ))
+    .unwrap()
+});
+
+static NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+(?:\.\d+)*").unwrap());
+
+/// Groups of journald units in the log services picker, in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ServiceCategory {
+    Kubernetes,
+    Virtualization,
+    Storage,
+    Network,
+    Other,
+}
+
+impl ServiceCategory {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ServiceCategory::Kubernetes => "k8s",
+            ServiceCategory::Virtualization => "virtualization",
+            ServiceCategory::Storage => "storage",
+            ServiceCategory::Network => "network",
+            ServiceCategory::Other => "other",
+        }
+    }
+
+    /// Best guess from the unit name
+    pub fn classify(unit: &str) -> ServiceCategory {
+        let unit = unit.to_lowercase();
+        let has = |parts: &[&str]| parts.iter().any(|p| unit.contains(p));
+        if has(&["k3s", "rke2", "kube", "etcd", "containerd", "docker", "crio", "cri-o"]) {
+            ServiceCategory::Kubernetes
+        } else if has(&["virt", "qemu", "kvm", "libvirt", "xen"]) {
+            ServiceCategory::Virtualization
+        } else if has(&["zfs", "lvm", "iscsi", "nfs", "ceph", "multipath", "smartd", "mdmonitor", "longhorn", "btrfs", "fstrim"]) {
+            ServiceCategory::Storage
+        } else if has(&["network", "dhcp", "dns", "resolved", "wpa_supplicant", "firewall", "nftables", "iptables", "sshd", "ovs", "openvswitch", "wireguard", "chrony", "ntp"]) {
+            ServiceCategory::Network
+        } else {
+            ServiceCategory::Other
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct JournalEntry {
     #[serde(rename = "MESSAGE")]
@@ -24,13 +85,38 @@ struct JournalEntry {
     syslog_id: Option<String>,
     #[serde(rename = "PRIORITY")]
     priority: Option<String>,
+    #[serde(rename = "_PID")]
+    pid: Option<String>,
+    #[serde(rename = "__CURSOR")]
+    cursor: Option<String>,
 }
 
+/// Follows the journal incrementally: after the first read only entries past
+/// the last seen cursor are fetched. Entries are shared with the UI through
+/// `Arc` so handing out the buffer doesn't copy every message each cycle.
 pub struct LogCollector {
-    buffer: VecDeque<LogEntry>,
+    buffer: VecDeque<Arc<LogEntry>>,
     buffer_size: usize,
+    /// Approximate size of the buffered lines, kept under `max_bytes`
+    buffer_bytes: usize,
+    max_bytes: usize,
     services: Vec<String>,
+    cursor: Option<String>,
     use_mock: bool,
+    /// (lines, errors, warnings) per service read since the last `take_counts`
+    counts: BTreeMap<String, (u64, u64, u64)>,
+    /// Lines pushed out of the full buffer
+    evicted: u64,
+    /// Journal reads that returned a full buffer's worth after the cursor,
+    /// so older lines of that burst were never read
+    truncated_reads: u64,
+    redactor: Arc<LogRedactor>,
+    /// Lines buffered since the last `take_forwarded`, kept only while
+    /// `[[logging.forward]]` has sinks
+    forward: Option<VecDeque<Arc<LogEntry>>>,
+    /// Fold a line repeating the newest buffered one into it, so a burst
+    /// takes one slot instead of pushing older lines out
+    collapse_repeats: bool,
 }
 
 impl LogCollector {
@@ -38,6 +124,8 @@ impl LogCollector {
         Ok(Self {
             buffer: VecDeque::with_capacity(1000),
             buffer_size: 1000,
+            buffer_bytes: 0,
+            max_bytes: usize::MAX,
             services: vec![
                 "k3s".to_string(),
                 "k3s-agent".to_string(),
@@ -47,12 +135,78 @@ impl LogCollector {
                 "virt-launcher".to_string(),
                 "docker".to_string(),
             ],
+            cursor: None,
             use_mock: false,
+            counts: BTreeMap::new(),
+            evicted: 0,
+            truncated_reads: 0,
+            redactor: Arc::new(LogRedactor::default()),
+            forward: None,
+            collapse_repeats: false,
         })
     }
 
     pub fn with_services(mut self, services: Vec<String>) -> Self {
         self.services = services;
+        self.cursor = None;
+        self.buffer.clear();
+        self.buffer_bytes = 0;
+        self
+    }
+
+    pub fn services(&self) -> &[String] {
+        &self.services
+    }
+
+    /// Follow a different set of units from now on. The buffer is rebuilt
+    /// from the journal on the next collect, as it would be at startup.
+    pub fn set_services(&mut self, services: Vec<String>) {
+        self.services = services;
+        self.cursor = None;
+        self.buffer.clear();
+        self.buffer_bytes = 0;
+    }
+
+    /// Service units known to systemd, loaded or not, without the
+    /// `.service` suffix
+    pub async fn discover_units(&self) -> Result<Vec<String>> {
+        let output = systemctl()
+            .args(["list-units", "--type=service", "--all", "--no-legend", "--plain", "--no-pager"])
+            .output()
+            .await
+            .context("Failed to execute systemctl")?;
+        if !output.status.success() {
+            anyhow::bail!("systemctl failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        let mut units: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .filter_map(|unit| unit.strip_suffix(".service"))
+            // Template instances (getty@tty1) are rarely what anyone wants to follow
+            .filter(|unit| !unit.contains('@'))
+            .map(|unit| unit.to_string())
+            .collect();
+        units.sort();
+        units.dedup();
+        Ok(units)
+    }
+
+    /// Scrub every line read from the journal or pushed into the buffer
+    pub fn with_redactor(mut self, redactor: LogRedactor) -> Self {
+        self.redactor = Arc::new(redactor);
+        self
+    }
+
+    /// Keep newly buffered lines for `take_forwarded`
+    pub fn with_forwarding(mut self, enabled: bool) -> Self {
+        self.forward = enabled.then(VecDeque::new);
+        self
+    }
+
+    /// Count repeats of a line as it is read instead of buffering them
+    pub fn with_collapse_repeats(mut self, enabled: bool) -> Self {
+        self.collapse_repeats = enabled;
         self
     }
 
@@ -62,16 +216,27 @@ impl LogCollector {
         self
     }
 
-    pub async fn collect(&mut self) -> Result<Vec<LogEntry>> {
+    /// Evict the oldest lines once the buffer holds more than `bytes`,
+    /// whatever the line count
+    pub fn with_max_bytes(mut self, bytes: usize) -> Self {
+        self.max_bytes = bytes;
+        self
+    }
+
+    pub async fn collect(&mut self) -> Result<Vec<Arc<LogEntry>>> {
         // Try to collect real logs, fall back to mock on error
         match self.collect_real().await {
-            Ok(logs) => {
+            Ok((logs, cursor)) => {
+                if self.cursor.is_some() && logs.len() >= self.buffer_size {
+                    self.truncated_reads += 1;
+                }
+                if cursor.is_some() {
+                    self.cursor = cursor;
+                }
                 // Add to ring buffer
-                for log in logs.iter() {
-                    if self.buffer.len() >= self.buffer_size {
-                        self.buffer.pop_front();
-                    }
-                    self.buffer.push_back(log.clone());
+                for log in logs {
+                    self.count(&log);
+                    self.push_entry(log);
                 }
                 Ok(self.buffer.iter().cloned().collect())
             }
@@ -80,19 +245,121 @@ impl LogCollector {
                     tracing::warn!("Failed to collect real logs, using mock data: {}", e);
                     self.use_mock = true;
                 }
-                Ok(self.collect_mock())
+                let mut logs = self.collect_mock();
+                for log in &mut logs {
+                    self.redactor.redact_entry(log);
+                    self.count(log);
+                }
+                Ok(logs.into_iter().map(Arc::new).collect())
+            }
+        }
+    }
+
+    /// Lines in the buffer and their approximate size in bytes
+    pub fn buffer_usage(&self) -> (usize, usize) {
+        (self.buffer.len(), self.buffer_bytes)
+    }
+
+    /// The byte budget the buffer is kept under
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Lines pushed out of the full buffer, and journal reads that skipped
+    /// part of a burst, since startup
+    pub fn dropped(&self) -> (u64, u64) {
+        (self.evicted, self.truncated_reads)
+    }
+
+    /// Add lines produced outside the journal (e.g. upgrade output) to the
+    /// buffer, returning the buffer as `collect` would
+    pub fn push(&mut self, logs: impl IntoIterator<Item = LogEntry>) -> Vec<Arc<LogEntry>> {
+        for mut log in logs {
+            self.redactor.redact_entry(&mut log);
+            self.count(&log);
+            self.push_entry(log);
+        }
+        self.buffer.iter().cloned().collect()
+    }
+
+    /// Append to the ring buffer, evicting the oldest lines past either the
+    /// line count or the byte budget; the newest line is always kept.
+    /// Forwarding sees every line, repeats included.
+    fn push_entry(&mut self, log: LogEntry) {
+        if let Some(ref mut forward) = self.forward {
+            // Bounded like the buffer should nothing take them
+            if forward.len() >= self.buffer_size {
+                forward.pop_front();
+            }
+            forward.push_back(Arc::new(log.clone()));
+        }
+        if self.collapse_repeats && self.absorb_repeat(&log) {
+            return;
+        }
+
+        let bytes = log.approx_bytes();
+        while !self.buffer.is_empty()
+            && (self.buffer.len() >= self.buffer_size || self.buffer_bytes + bytes > self.max_bytes)
+        {
+            if let Some(oldest) = self.buffer.pop_front() {
+                self.buffer_bytes = self.buffer_bytes.saturating_sub(oldest.approx_bytes());
+                self.evicted += 1;
             }
         }
+        self.buffer_bytes += bytes;
+        self.buffer.push_back(Arc::new(log));
+    }
+
+    /// Count `log` as a repeat of the newest buffered line when it is one
+    fn absorb_repeat(&mut self, log: &LogEntry) -> bool {
+        let Some(last) = self.buffer.back_mut() else {
+            return false;
+        };
+        if !is_repeat(last, log) {
+            return false;
+        }
+        let last = Arc::make_mut(last);
+        last.repeats += log.repeats + 1;
+        last.last_timestamp = Some(log.last_timestamp.clone().unwrap_or_else(|| log.timestamp.clone()));
+        true
+    }
+
+    /// Journal and pushed lines buffered since the previous call, oldest
+    /// first; mock lines are never included
+    pub fn take_forwarded(&mut self) -> Vec<Arc<LogEntry>> {
+        self.forward.as_mut().map(|forward| std::mem::take(forward).into()).unwrap_or_default()
+    }
+
+    fn count(&mut self, log: &LogEntry) {
+        let (lines, errors, warnings) = self.counts.entry(log.service.clone()).or_default();
+        *lines += 1;
+        match log.level.as_str() {
+            "ERROR" | "CRITICAL" => *errors += 1,
+            "WARN" | "WARNING" => *warnings += 1,
+            _ => {}
+        }
     }
 
-    async fn collect_real(&self) -> Result<Vec<LogEntry>> {
-        // Build journalctl command with unit filters
-        let mut cmd = Command::new("journalctl");
+    /// Lines, errors and warnings per service read since the previous
+    /// call, for log rate tracking
+    pub fn take_counts(&mut self) -> BTreeMap<String, (u64, u64, u64)> {
+        std::mem::take(&mut self.counts)
+    }
+
+    /// New journal entries since the last cursor (the last `buffer_size`
+    /// entries on the first read), with the cursor of the newest one
+    async fn collect_real(&self) -> Result<(Vec<LogEntry>, Option<String>)> {
+        // Build journalctl command with unit filters. -n still bounds how
+        // much a single read can return during a burst.
+        let mut cmd = journalctl();
         cmd.args([
             "-n", &self.buffer_size.to_string(),
             "--output=json",
             "--no-pager",
         ]);
+        if let Some(ref cursor) = self.cursor {
+            cmd.arg(format!("--after-cursor={}", cursor));
+        }
 
         // Add unit filters
         for service in &self.services {
@@ -100,17 +367,23 @@ impl LogCollector {
         }
 
         let output = cmd.output()
+            .await
             .context("Failed to execute journalctl")?;
 
         if !output.status.success() {
             // Try without unit filters as fallback
-            let output = Command::new("journalctl")
-                .args([
-                    "-n", "100",
-                    "--output=json",
-                    "--no-pager",
-                ])
+            let mut cmd = journalctl();
+            cmd.args([
+                "-n", "100",
+                "--output=json",
+                "--no-pager",
+            ]);
+            if let Some(ref cursor) = self.cursor {
+                cmd.arg(format!("--after-cursor={}", cursor));
+            }
+            let output = cmd
                 .output()
+                .await
                 .context("Failed to execute journalctl fallback")?;
 
             if !output.status.success() {
@@ -123,85 +396,171 @@ impl LogCollector {
         self.parse_journal_output(&output.stdout)
     }
 
-    fn parse_journal_output(&self, output: &[u8]) -> Result<Vec<LogEntry>> {
-        let reader = BufReader::new(output);
-        let mut logs = Vec::new();
+    /// Entries from the anchor's service within a few minutes either side of
+    /// it, re-queried from the journal so context isn't limited to the buffer
+    pub async fn context(&self, anchor: &LogEntry) -> Result<Vec<Arc<LogEntry>>> {
+        if self.use_mock {
+            anyhow::bail!("journal not available");
+        }
 
-        for line in reader.lines() {
-            let line = line?;
-            if line.is_empty() {
-                continue;
-            }
+        let at = parse_log_timestamp(&anchor.timestamp)
+            .context("Unparseable log timestamp")?;
+        let window = chrono::Duration::seconds(CONTEXT_WINDOW_SECS);
+        let since = (at - window).format("%Y-%m-%d %H:%M:%S").to_string();
+        // Journal timestamps are truncated to the second; include the whole last one
+        let until = (at + window + chrono::Duration::seconds(1))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
 
-            match serde_json::from_str::<JournalEntry>(&line) {
-                Ok(entry) => {
-                    if let Some(log_entry) = self.convert_journal_entry(entry) {
-                        logs.push(log_entry);
-                    }
-                }
-                Err(e) => {
-                    tracing::debug!("Failed to parse journal line: {}", e);
-                    continue;
-                }
-            }
+        // Service names come from either the unit or the syslog identifier
+        let output = journalctl()
+            .args([
+                &format!("_SYSTEMD_UNIT={}.service", anchor.service),
+                "+",
+                &format!("SYSLOG_IDENTIFIER={}", anchor.service),
+                "--since", &since,
+                "--until", &until,
+                "--output=json",
+                "--no-pager",
+            ])
+            .output()
+            .await
+            .context("Failed to execute journalctl")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "journalctl failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
         }
 
-        Ok(logs)
+        let (entries, _) = self.parse_journal_output(&output.stdout)?;
+        Ok(entries.into_iter().map(Arc::new).collect())
     }
 
-    fn convert_journal_entry(&self, entry: JournalEntry) -> Option<LogEntry> {
-        let message = entry.message?;
+    /// Every field of the journal record at `cursor`, sorted by name, for
+    /// the log detail view. Binary and repeated fields are left out; values
+    /// are redacted like log lines.
+    pub async fn journal_fields(&self, cursor: &str) -> Result<Vec<(String, String)>> {
+        if self.use_mock {
+            anyhow::bail!("journal not available");
+        }
+        let output = journalctl()
+            .args([&format!("--cursor={}", cursor), "-n", "1", "--output=json", "--no-pager"])
+            .output()
+            .await
+            .context("Failed to execute journalctl")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "journalctl failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        let line = output
+            .stdout
+            .split(|b| *b == b'\n')
+            .next()
+            .filter(|line| !line.is_empty())
+            .context("Entry no longer in the journal")?;
+        let record: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(line).context("Failed to parse journal entry")?;
+        // Rotated away: journalctl starts from the nearest entry instead
+        if record.get("__CURSOR").and_then(|v| v.as_str()) != Some(cursor) {
+            anyhow::bail!("Entry no longer in the journal");
+        }
+        Ok(record
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, self.redactor.redact(value.as_str()?))))
+            .collect())
+    }
 
-        // Extract service name
-        let service = entry.unit
-            .or(entry.syslog_id)
-            .unwrap_or_else(|| "system".to_string())
-            .replace(".service", "");
+    fn parse_journal_output(&self, output: &[u8]) -> Result<(Vec<LogEntry>, Option<String>)> {
+        parse_journal(output, &self.redactor)
+    }
 
-        // Parse timestamp
-        let timestamp = if let Some(ts) = entry.timestamp {
-            // Timestamp is in microseconds since epoch
-            if let Ok(micros) = ts.parse::<i64>() {
-                let dt = DateTime::from_timestamp(micros / 1_000_000, ((micros % 1_000_000) * 1000) as u32)?;
-                dt.with_timezone(&Local).format("%b %d %H:%M:%S").to_string()
-            } else {
-                Local::now().format("%b %d %H:%M:%S").to_string()
+    /// Entries of the followed services between `since` and `until`
+    /// (anything journalctl accepts, e.g. "yesterday 22:00" or "-2h"), the
+    /// newest `limit` of them, and whether the range held more. Owns what it
+    /// needs, so it can run on its own task while the live tail goes on.
+    pub fn archive_query(
+        &self,
+        since: &str,
+        until: &str,
+        limit: usize,
+    ) -> impl std::future::Future<Output = Result<(Vec<LogEntry>, bool)>> + Send + 'static {
+        let (since, until) = (since.to_string(), until.to_string());
+        let services = self.services.clone();
+        let redactor = self.redactor.clone();
+        let use_mock = self.use_mock;
+        async move {
+            if use_mock {
+                anyhow::bail!("journal not available");
             }
-        } else {
-            Local::now().format("%b %d %H:%M:%S").to_string()
-        };
-
-        // Determine log level from priority or message content
-        let level = if let Some(priority) = entry.priority {
-            match priority.as_str() {
-                "0" | "1" | "2" => "ERROR",
-                "3" => "ERROR",
-                "4" => "WARN",
-                "5" | "6" => "INFO",
-                "7" => "DEBUG",
-                _ => "INFO",
+            // One extra line tells whether the range was cut off
+            let mut cmd = journalctl();
+            cmd.args([
+                "--since", &since,
+                "--until", &until,
+                "-n", &(limit + 1).to_string(),
+                "--output=json",
+                "--no-pager",
+            ]);
+            for service in &services {
+                cmd.args(["-u", service]);
             }
-        } else {
-            // Try to extract from message
-            if let Some(captures) = LEVEL_REGEX.captures(&message) {
-                let level_str = captures.get(1).unwrap().as_str().to_uppercase();
-                match level_str.as_str() {
-                    "ERROR" | "ERR" | "CRITICAL" | "CRIT" => "ERROR",
-                    "WARN" | "WARNING" => "WARN",
-                    "DEBUG" => "DEBUG",
-                    _ => "INFO",
-                }
-            } else {
-                "INFO"
+            let output = cmd.output().await.context("Failed to execute journalctl")?;
+            if !output.status.success() {
+                anyhow::bail!(
+                    "journalctl failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
             }
-        }.to_string();
 
-        Some(LogEntry {
-            timestamp,
-            level,
-            service,
-            message,
-        })
+            let (mut entries, _) = parse_journal(&output.stdout, &redactor)?;
+            let truncated = entries.len() > limit;
+            if truncated {
+                entries.drain(..entries.len() - limit);
+            }
+            Ok((entries, truncated))
+        }
+    }
+
+    /// The newest `lines` journal entries of one service, whether it is a
+    /// unit or only a syslog identifier (virt-launcher). Owns what it needs
+    /// like `archive_query`, so services can be read side by side.
+    pub fn service_journal(
+        &self,
+        service: &str,
+        lines: usize,
+    ) -> impl std::future::Future<Output = Result<Vec<LogEntry>>> + Send + 'static {
+        let service = service.to_string();
+        let redactor = self.redactor.clone();
+        let use_mock = self.use_mock;
+        async move {
+            if use_mock {
+                anyhow::bail!("journal not available");
+            }
+            let output = journalctl()
+                .args([
+                    &format!("_SYSTEMD_UNIT={}.service", service),
+                    "+",
+                    &format!("SYSLOG_IDENTIFIER={}", service),
+                    "-n", &lines.to_string(),
+                    "--output=json",
+                    "--no-pager",
+                ])
+                .output()
+                .await
+                .context("Failed to execute journalctl")?;
+            if !output.status.success() {
+                anyhow::bail!(
+                    "journalctl failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            let (entries, _) = parse_journal(&output.stdout, &redactor)?;
+            Ok(entries)
+        }
     }
 
     fn collect_mock(&self) -> Vec<LogEntry> {
@@ -211,42 +570,59 @@ impl LogCollector {
                 level: "INFO".to_string(),
                 service: "k3s".to_string(),
                 message: "Node registration successful".to_string(),
+                ..Default::default()
             },
             LogEntry {
                 timestamp: Local::now().format("%b %d %H:%M:%S").to_string(),
                 level: "INFO".to_string(),
                 service: "kubelet".to_string(),
                 message: "Node ready - all pods running".to_string(),
+                ..Default::default()
             },
             LogEntry {
                 timestamp: Local::now().format("%b %d %H:%M:%S").to_string(),
                 level: "INFO".to_string(),
                 service: "virt-handler".to_string(),
                 message: "VM vm-webserver-01 started successfully".to_string(),
+                ..Default::default()
             },
             LogEntry {
                 timestamp: Local::now().format("%b %d %H:%M:%S").to_string(),
                 level: "WARN".to_string(),
                 service: "containerd".to_string(),
                 message: "Image pull slow, retrying...".to_string(),
+                fields: vec![
+                    ("image".to_string(), "docker.io/library/nginx:latest".to_string()),
+                    ("attempt".to_string(), "2".to_string()),
+                ],
+                ..Default::default()
             },
             LogEntry {
                 timestamp: Local::now().format("%b %d %H:%M:%S").to_string(),
                 level: "INFO".to_string(),
                 service: "containerd".to_string(),
                 message: "Image pulled: docker.io/library/nginx:latest".to_string(),
+                ..Default::default()
             },
             LogEntry {
                 timestamp: Local::now().format("%b %d %H:%M:%S").to_string(),
                 level: "ERROR".to_string(),
                 service: "kubelet".to_string(),
-                message: "Failed to sync pod, will retry".to_string(),
+                message: "Error syncing pod, skipping".to_string(),
+                component: Some("pod_workers".to_string()),
+                fields: vec![
+                    ("pod".to_string(), "default/virt-launcher-vm-database-01-x7k2p".to_string()),
+                    ("err".to_string(), "failed to \"StartContainer\" with CrashLoopBackOff".to_string()),
+                ],
+                pid: Some("1187".to_string()),
+                ..Default::default()
             },
             LogEntry {
                 timestamp: Local::now().format("%b %d %H:%M:%S").to_string(),
                 level: "INFO".to_string(),
                 service: "k3s".to_string(),
                 message: "Starting k3s server v1.28.5+k3s1".to_string(),
+                ..Default::default()
             },
         ]
     }
@@ -260,7 +636,7 @@ impl LogCollector {
                     || log.service.to_lowercase().contains(&query_lower)
                     || log.level.to_lowercase().contains(&query_lower)
             })
-            .cloned()
+            .map(|log| (**log).clone())
             .collect()
     }
 
@@ -268,7 +644,299 @@ impl LogCollector {
         self.buffer
             .iter()
             .filter(|log| log.level.eq_ignore_ascii_case(level))
-            .cloned()
+            .map(|log| (**log).clone())
             .collect()
     }
 }
+
+fn parse_journal(output: &[u8], redactor: &LogRedactor) -> Result<(Vec<LogEntry>, Option<String>)> {
+    let reader = BufReader::new(output);
+    let mut logs = Vec::new();
+    let mut cursor = None;
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<JournalEntry>(&line) {
+            Ok(entry) => {
+                if entry.cursor.is_some() {
+                    cursor = entry.cursor.clone();
+                }
+                if let Some(mut log_entry) = convert_journal_entry(entry) {
+                    redactor.redact_entry(&mut log_entry);
+                    logs.push(log_entry);
+                }
+            }
+            Err(e) => {
+                tracing::debug!("Failed to parse journal line: {}", e);
+                continue;
+            }
+        }
+    }
+
+    Ok((logs, cursor))
+}
+
+fn convert_journal_entry(entry: JournalEntry) -> Option<LogEntry> {
+    let message = entry.message?;
+
+    // Extract service name
+    let service = entry.unit
+        .or(entry.syslog_id)
+        .unwrap_or_else(|| "system".to_string())
+        .replace(".service", "");
+
+    // Parse timestamp
+    let timestamp = if let Some(ts) = entry.timestamp {
+        // Timestamp is in microseconds since epoch
+        if let Ok(micros) = ts.parse::<i64>() {
+            let dt = DateTime::from_timestamp(micros / 1_000_000, ((micros % 1_000_000) * 1000) as u32)?;
+            dt.with_timezone(&Local).format("%b %d %H:%M:%S").to_string()
+        } else {
+            Local::now().format("%b %d %H:%M:%S").to_string()
+        }
+    } else {
+        Local::now().format("%b %d %H:%M:%S").to_string()
+    };
+
+    // Determine log level from priority or message content
+    let level = if let Some(priority) = entry.priority {
+        match priority.as_str() {
+            "0" | "1" | "2" => "ERROR",
+            "3" => "ERROR",
+            "4" => "WARN",
+            "5" | "6" => "INFO",
+            "7" => "DEBUG",
+            _ => "INFO",
+        }
+    } else {
+        // Try to extract from message
+        if let Some(captures) = LEVEL_REGEX.captures(&message) {
+            let level_str = captures.get(1).unwrap().as_str().to_uppercase();
+            match level_str.as_str() {
+                "ERROR" | "ERR" | "CRITICAL" | "CRIT" => "ERROR",
+                "WARN" | "WARNING" => "WARN",
+                "DEBUG" => "DEBUG",
+                _ => "INFO",
+            }
+        } else {
+            "INFO"
+        }
+    }.to_string();
+
+    // journald only knows the priority of the whole stream; the line
+    // itself says what it really is
+    if let Some(line) = parse_structured(&service, &message) {
+        return Some(LogEntry {
+            timestamp,
+            level: line.level,
+            service,
+            message: line.message,
+            component: line.component,
+            fields: line.fields,
+            pid: entry.pid,
+            cursor: entry.cursor,
+            ..Default::default()
+        });
+    }
+
+    Some(LogEntry {
+        timestamp,
+        level,
+        service,
+        message,
+        pid: entry.pid,
+        cursor: entry.cursor,
+        ..Default::default()
+    })
+}
+
+/// Journal timestamps are formatted "%b %d %H:%M:%S" without a year
+pub fn parse_log_timestamp(timestamp: &str) -> Option<DateTime<Local>> {
+    let now = Local::now();
+    let naive =
+        NaiveDateTime::parse_from_str(&format!("{} {}", now.year(), timestamp), "%Y %b %d %H:%M:%S").ok()?;
+    let at = Local.from_local_datetime(&naive).single()?;
+
+    // Entries from late December read in early January belong to last year
+    if at > now + chrono::Duration::days(1) {
+        let naive = NaiveDateTime::parse_from_str(
+            &format!("{} {}", now.year() - 1, timestamp),
+            "%Y %b %d %H:%M:%S",
+        )
+        .ok()?;
+        return Local.from_local_datetime(&naive).single();
+    }
+    Some(at)
+}
+
+/// Up to `lines` entries either side of `anchor` among the entries from its
+/// service, along with the anchor's index in the result
+pub fn context_window(
+    entries: &[Arc<LogEntry>],
+    anchor: &LogEntry,
+    lines: usize,
+) -> Option<(Vec<Arc<LogEntry>>, usize)> {
+    let same_service: Vec<&Arc<LogEntry>> = entries
+        .iter()
+        .filter(|e| e.service == anchor.service)
+        .collect();
+    let position = same_service
+        .iter()
+        .position(|e| e.timestamp == anchor.timestamp && e.message == anchor.message)?;
+
+    let start = position.saturating_sub(lines);
+    let end = (position + lines + 1).min(same_service.len());
+    let window = same_service[start..end].iter().map(|e| Arc::clone(e)).collect();
+    Some((window, position - start))
+}
+
+/// `message` with the values that vary between repeats of it masked.
+/// A bare number (a PID, a count, a percentage) is a value; digits inside
+/// a name such as vm-01 or web2, and dotted runs such as addresses and
+/// versions, are kept so different VMs' messages stay apart.
+fn repeat_key(message: &str) -> String {
+    let masked = VARIABLE_TOKEN_REGEX.replace_all(message, "#");
+    let in_name = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '-' || c == '_');
+
+    let mut key = String::with_capacity(masked.len());
+    let mut last = 0;
+    for number in NUMBER_REGEX.find_iter(&masked) {
+        let before = masked[..number.start()].chars().next_back();
+        let after = masked[number.end()..].chars().next();
+        if in_name(before) || in_name(after) || number.as_str().matches('.').count() > 1 {
+            continue;
+        }
+        key.push_str(&masked[last..number.start()]);
+        key.push('#');
+        last = number.end();
+    }
+    key.push_str(&masked[last..]);
+    key
+}
+
+/// Whether `entry` repeats `prev`: same service and level, and the same
+/// message once values are masked
+fn is_repeat(prev: &LogEntry, entry: &LogEntry) -> bool {
+    prev.service == entry.service && prev.level == entry.level && repeat_key(&prev.message) == repeat_key(&entry.message)
+}
+
+/// Collapse runs of consecutive repeats (see `is_repeat`) into a single
+/// entry carrying a repeat count and the last timestamp, for a filtered
+/// view that brings together repeats the buffer holds apart. Only entries
+/// that absorb repeats are copied; the rest stay shared.
+pub fn collapse_repeats(entries: &[Arc<LogEntry>]) -> Vec<Arc<LogEntry>> {
+    let mut collapsed: Vec<Arc<LogEntry>> = Vec::with_capacity(entries.len());
+    let mut last_key: Option<String> = None;
+
+    for entry in entries {
+        let key = repeat_key(&entry.message);
+        if let Some(prev) = collapsed.last_mut() {
+            if last_key.as_deref() == Some(key.as_str())
+                && prev.service == entry.service
+                && prev.level == entry.level
+            {
+                let prev = Arc::make_mut(prev);
+                prev.repeats += entry.repeats + 1;
+                prev.last_timestamp = Some(
+                    entry.last_timestamp.clone().unwrap_or_else(|| entry.timestamp.clone()),
+                );
+                continue;
+            }
+        }
+        collapsed.push(Arc::clone(entry));
+        last_key = Some(key);
+    }
+
+    collapsed
+}
+
+/// journalctl, killed when a timed-out collection drops it
+fn journalctl() -> Command {
+    let mut cmd = Command::new("journalctl");
+    cmd.kill_on_drop(true);
+    cmd
+}
+
+fn systemctl() -> Command {
+    let mut cmd = Command::new("systemctl");
+    cmd.kill_on_drop(true);
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: timestamp.to_string(),
+            level: "ERROR".to_string(),
+            service: "virt-handler".to_string(),
+            message: message.to_string(),
+            repeats: 0,
+            last_timestamp: None,
+            component: None,
+            fields: Vec::new(),
+            pid: None,
+            cursor: None,
+        }
+    }
+
+    #[test]
+    fn values_are_masked() {
+        assert_eq!(repeat_key("pid 1234 took 250ms"), repeat_key("pid 99 took 1.5s"));
+        assert_eq!(repeat_key("retry 3 of 5, 80% done"), repeat_key("retry 4 of 5, 81% done"));
+        assert_eq!(
+            repeat_key("at 2026-10-17T09:14:02.123Z container 0123456789abcdef failed"),
+            repeat_key("at 2026-10-17T09:15:40.002Z container fedcba9876543210 failed")
+        );
+        assert_eq!(repeat_key("sync at 09:14:02 failed"), repeat_key("sync at 11:00:59 failed"));
+    }
+
+    #[test]
+    fn names_are_kept() {
+        assert_ne!(repeat_key("failed to sync VMI default/vm-01"), repeat_key("failed to sync VMI default/vm-02"));
+        assert_ne!(repeat_key("web1 unreachable"), repeat_key("web2 unreachable"));
+        assert_ne!(repeat_key("no route to 10.0.0.5"), repeat_key("no route to 10.0.0.6"));
+    }
+
+    #[test]
+    fn repeats_collapse_as_read() {
+        let mut collector = LogCollector::new().unwrap().with_buffer_size(3).with_collapse_repeats(true);
+        collector.push([entry("09:14:00", "node hv-01 ready")]);
+        let logs = collector.push((1..=5).map(|n| entry(&format!("09:14:0{}", n), &format!("sync failed after {}ms", n * 10))));
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[1].repeats, 4);
+        assert_eq!(logs[1].last_timestamp.as_deref(), Some("09:14:05"));
+        // The burst took one slot, so the line before it is still there
+        assert_eq!(logs[0].message, "node hv-01 ready");
+        assert_eq!(collector.dropped().0, 0);
+    }
+
+    #[test]
+    fn different_vms_stay_separate() {
+        let mut collector = LogCollector::new().unwrap().with_collapse_repeats(true);
+        let logs = collector.push([
+            entry("09:14:01", "failed to sync VMI default/vm-01: domain not found"),
+            entry("09:14:02", "failed to sync VMI default/vm-02: domain not found"),
+            entry("09:14:03", "failed to sync VMI default/vm-02: domain not found"),
+        ]);
+        assert_eq!(logs.len(), 2);
+        assert!(logs[0].message.contains("vm-01"));
+        assert_eq!((logs[0].repeats, logs[1].repeats), (0, 1));
+
+        let collapsed = collapse_repeats(&logs);
+        assert_eq!(collapsed.len(), 2);
+    }
+
+    #[test]
+    fn off_keeps_every_line() {
+        let mut collector = LogCollector::new().unwrap();
+        let logs = collector.push([entry("09:14:01", "sync failed"), entry("09:14:02", "sync failed")]);
+        assert_eq!(logs.len(), 2);
+    }
+}
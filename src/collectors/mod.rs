@@ -1,9 +1,69 @@
 mod logs;
+mod log_format;
 mod system;
 mod network;
+mod mtu;
 mod kubernetes;
+mod kube_api;
+mod vm_console;
+mod vm_vnc;
+mod custom;
+mod dns;
+mod routes;
+mod registry;
+mod vip;
+mod cgroup;
+mod host;
+mod power;
+mod qemu;
+mod images;
+mod sriov;
+mod sockets;
+mod multipath;
+mod diskstats;
+mod kmsg;
+mod bridge_watch;
+mod sysctl;
+mod firmware;
+mod host_profile;
+mod process_memory;
+mod etcd;
+mod crashes;
+mod oom;
+mod prometheus;
+mod proxmox;
+mod qmp;
+mod virt_backend;
+mod agent;
+mod limits;
 
-pub use logs::LogCollector;
+pub use logs::{LogCollector, ServiceCategory, collapse_repeats, context_window, parse_log_timestamp};
 pub use system::SystemCollector;
 pub use network::NetworkCollector;
 pub use kubernetes::KubernetesCollector;
+pub use dns::DnsCollector;
+pub use routes::RouteCollector;
+pub use registry::RegistryCollector;
+pub use vip::VipCollector;
+pub use cgroup::CgroupCollector;
+pub use host::HostCollector;
+pub use power::PowerCollector;
+pub use qemu::{signal_qemu, QemuCollector};
+pub use images::{delete_host_image, resolve_inventory, ImageCollector};
+pub use sriov::SriovCollector;
+pub use sockets::SocketCollector;
+pub use limits::LimitsCollector;
+pub use multipath::MultipathCollector;
+pub use diskstats::DiskStatsCollector;
+pub use kmsg::KmsgCollector;
+pub use bridge_watch::BridgeWatchCollector;
+pub use sysctl::SysctlCollector;
+pub use firmware::FirmwareCollector;
+pub use host_profile::HostProfileCollector;
+pub use process_memory::ProcessMemoryCollector;
+pub use etcd::EtcdCollector;
+pub use crashes::CrashCollector;
+pub use oom::OomCollector;
+pub use prometheus::{check_selector, PrometheusCollector};
+pub use agent::AgentCollector;
+pub use custom::CustomCommandCollector;
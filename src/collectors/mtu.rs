@@ -0,0 +1,161 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::types::{MtuMismatch, VmNetns};
+
+const SYS_NET: &str = "/sys/class/net";
+/// flannel writes the MTU it gives pods here
+const FLANNEL_SUBNET_ENV: &str = "/run/flannel/subnet.env";
+/// CNI configs of a stock install and of k3s's bundled flannel
+const CNI_CONF_DIRS: [&str; 2] = ["/etc/cni/net.d", "/var/lib/rancher/k3s/agent/etc/cni/net.d"];
+/// Host interfaces the CNI creates and sizes to its MTU
+const CNI_INTERFACES: [&str; 4] = ["cni0", "flannel.1", "flannel-v6.1", "vxlan.calico"];
+/// A virt-launcher pod's pod network links: the pod's own interface and,
+/// for masquerade and bridge binding, the in-pod bridge and the VM's tap
+const POD_NETWORK_LINKS: [&str; 3] = ["eth0", "k6t-eth0", "tap0"];
+
+/// One host interface as the data path check needs it
+struct Link {
+    mtu: u32,
+    /// Bridge or bond it is enslaved to
+    master: Option<String>,
+    /// Devices a VLAN or macvlan sits on
+    lower: Vec<String>,
+}
+
+/// The MTU pods are given: flannel's, else the first "mtu" set in a CNI
+/// config
+pub fn cni_mtu() -> Option<u32> {
+    if let Ok(env) = fs::read_to_string(FLANNEL_SUBNET_ENV) {
+        if let Some(mtu) = env.lines().find_map(|line| line.strip_prefix("FLANNEL_MTU=")?.trim().parse().ok()) {
+            return Some(mtu);
+        }
+    }
+    let mut configs: Vec<_> = CNI_CONF_DIRS
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "conf" || ext == "conflist"))
+        .collect();
+    // The runtime uses the lexically first config
+    configs.sort();
+    configs.iter().find_map(|path| {
+        let json: Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+        find_mtu(&json)
+    })
+}
+
+/// The first positive "mtu" anywhere in a CNI config, plugins included
+fn find_mtu(value: &Value) -> Option<u32> {
+    match value {
+        Value::Object(map) => map
+            .get("mtu")
+            .and_then(Value::as_u64)
+            .filter(|&mtu| mtu > 0)
+            .map(|mtu| mtu as u32)
+            .or_else(|| map.values().find_map(find_mtu)),
+        Value::Array(items) => items.iter().find_map(find_mtu),
+        _ => None,
+    }
+}
+
+/// Every mismatch along the VM data path: bridge ports and bond members
+/// against their master, VLANs against the device under them, and the
+/// CNI's own interfaces and each VM's pod network links against
+/// `cni_mtu`. Host interfaces are read from sysfs whatever the
+/// `[network]` filters show.
+pub fn check(vm_namespaces: &[VmNetns], cni_mtu: Option<u32>) -> Vec<MtuMismatch> {
+    let links = read_links();
+    let mut mismatches = Vec::new();
+    let mut names: Vec<&String> = links.keys().collect();
+    names.sort();
+
+    for name in names {
+        let link = &links[name];
+        if let Some(ref master) = link.master {
+            let kind = if Path::new(SYS_NET).join(master).join("bonding").exists() {
+                "bond"
+            } else if Path::new(SYS_NET).join(master).join("bridge").exists() {
+                "bridge"
+            } else {
+                // An Open vSwitch datapath or VRF; not one to compare with
+                ""
+            };
+            if let Some(master_link) = links.get(master).filter(|_| !kind.is_empty()) {
+                if link.mtu != master_link.mtu {
+                    mismatches.push(MtuMismatch {
+                        interface: name.clone(),
+                        mtu: link.mtu,
+                        against: format!("{} {}", kind, master),
+                        expected: master_link.mtu,
+                    });
+                }
+            }
+        }
+        // A VLAN can't carry more than its parent; less is allowed
+        for lower in &link.lower {
+            if let Some(parent) = links.get(lower).filter(|parent| link.mtu > parent.mtu) {
+                mismatches.push(MtuMismatch {
+                    interface: name.clone(),
+                    mtu: link.mtu,
+                    against: format!("parent {}", lower),
+                    expected: parent.mtu,
+                });
+            }
+        }
+    }
+
+    let Some(cni_mtu) = cni_mtu else {
+        return mismatches;
+    };
+    for name in CNI_INTERFACES {
+        if let Some(link) = links.get(name).filter(|link| link.mtu != cni_mtu) {
+            mismatches.push(MtuMismatch {
+                interface: name.to_string(),
+                mtu: link.mtu,
+                against: "CNI".to_string(),
+                expected: cni_mtu,
+            });
+        }
+    }
+    for ns in vm_namespaces {
+        for iface in ns.interfaces.iter().filter(|iface| POD_NETWORK_LINKS.contains(&iface.name.as_str())) {
+            if let Some(mtu) = iface.mtu.filter(|&mtu| mtu != cni_mtu) {
+                mismatches.push(MtuMismatch {
+                    interface: format!("{} {}", ns.vm, iface.name),
+                    mtu,
+                    against: "CNI".to_string(),
+                    expected: cni_mtu,
+                });
+            }
+        }
+    }
+    mismatches
+}
+
+fn read_links() -> HashMap<String, Link> {
+    let Ok(entries) = fs::read_dir(SYS_NET) else {
+        return HashMap::new();
+    };
+    let mut links = HashMap::new();
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let Some(mtu) = fs::read_to_string(dir.join("mtu")).ok().and_then(|mtu| mtu.trim().parse().ok()) else {
+            continue;
+        };
+        let file_name = |path: &Path| path.file_name().map(|name| name.to_string_lossy().to_string());
+        let master = fs::read_link(dir.join("master")).ok().and_then(|target| file_name(&target));
+        let lower = fs::read_dir(&dir)
+            .map(|children| {
+                children
+                    .flatten()
+                    .filter_map(|child| child.file_name().to_str()?.strip_prefix("lower_").map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        links.insert(entry.file_name().to_string_lossy().to_string(), Link { mtu, master, lower });
+    }
+    links
+}
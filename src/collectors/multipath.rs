@@ -0,0 +1,199 @@
+use anyhow::{bail, Result};
+use crate::types::{HbaPort, MultipathInfo, MultipathMap, MultipathPath, PathGroup};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::process::Command;
+
+const FC_HOST_ROOT: &str = "/sys/class/fc_host";
+
+/// dm-multipath maps and their paths from `multipath -ll`, and the state of
+/// the FC HBA ports underneath. Hosts without multipath-tools installed
+/// simply have no maps.
+pub struct MultipathCollector {
+    use_mock: bool,
+}
+
+impl MultipathCollector {
+    pub fn new() -> Result<Self> {
+        Ok(Self { use_mock: false })
+    }
+
+    pub async fn collect(&mut self) -> Result<MultipathInfo> {
+        if self.use_mock {
+            return Ok(self.collect_mock());
+        }
+        if !Path::new("/sys/block").is_dir() {
+            tracing::warn!("No /sys/block, using mock multipath data");
+            self.use_mock = true;
+            return Ok(self.collect_mock());
+        }
+
+        // multipath -ll hangs on dead paths; a timed-out collection kills it
+        let output = Command::new("multipath")
+            .arg("-ll")
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await;
+        let maps = match output {
+            Ok(output) if output.status.success() => parse_multipath(&String::from_utf8_lossy(&output.stdout)),
+            Ok(output) => bail!("multipath -ll failed: {}", String::from_utf8_lossy(&output.stderr).trim()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(e) => bail!("Failed to run multipath: {}", e),
+        };
+        Ok(MultipathInfo { maps, hba_ports: read_hba_ports() })
+    }
+
+    fn collect_mock(&self) -> MultipathInfo {
+        let path = |hctl: &str, device: &str, failed: bool| MultipathPath {
+            hctl: hctl.to_string(),
+            device: device.to_string(),
+            dm_state: if failed { "failed" } else { "active" }.to_string(),
+            checker_state: if failed { "faulty" } else { "ready" }.to_string(),
+            device_state: if failed { "offline" } else { "running" }.to_string(),
+        };
+        let group = |priority: u32, status: &str, paths: Vec<MultipathPath>| PathGroup {
+            priority: Some(priority),
+            status: status.to_string(),
+            paths,
+        };
+        let port = |host: &str, port_name: &str, state: &str| HbaPort {
+            host: host.to_string(),
+            port_name: port_name.to_string(),
+            state: state.to_string(),
+            speed: Some("16 Gbit".to_string()),
+        };
+        MultipathInfo {
+            maps: vec![
+                MultipathMap {
+                    name: "vmstore01".to_string(),
+                    wwid: "3600a098038314c4a5a5d4f6a2f6b7a41".to_string(),
+                    dm: "dm-2".to_string(),
+                    vendor: "NETAPP,LUN C-Mode".to_string(),
+                    size: Some("2.0T".to_string()),
+                    groups: vec![
+                        group(50, "active", vec![path("3:0:0:1", "sdb", false), path("4:0:0:1", "sdd", false)]),
+                        group(10, "enabled", vec![path("3:0:1:1", "sdc", false), path("4:0:1:1", "sde", false)]),
+                    ],
+                },
+                MultipathMap {
+                    name: "vmstore02".to_string(),
+                    wwid: "3600a098038314c4a5a5d4f6a2f6b7a42".to_string(),
+                    dm: "dm-3".to_string(),
+                    vendor: "NETAPP,LUN C-Mode".to_string(),
+                    size: Some("2.0T".to_string()),
+                    groups: vec![
+                        group(50, "active", vec![path("3:0:0:2", "sdf", false), path("4:0:0:2", "sdh", true)]),
+                        group(10, "enabled", vec![path("3:0:1:2", "sdg", false), path("4:0:1:2", "sdi", true)]),
+                    ],
+                },
+            ],
+            hba_ports: vec![
+                port("host3", "0x21000024ff7a1b2c", "Online"),
+                port("host4", "0x21000024ff7a1b2d", "Linkdown"),
+            ],
+        }
+    }
+}
+
+/// Maps in `multipath -ll` output:
+///
+/// ```text
+/// mpatha (3600508b4000156d700012000000b0000) dm-0 HP,HSV210
+/// size=10G features='1 queue_if_no_path' hwhandler='0' wp=rw
+/// |-+- policy='service-time 0' prio=50 status=active
+/// | |- 0:0:0:1 sda 8:0   active ready running
+/// | `- 1:0:0:1 sdc 8:32  active ready running
+/// `-+- policy='service-time 0' prio=10 status=enabled
+///   `- 1:0:1:1 sdd 8:48  failed faulty offline
+/// ```
+///
+/// Maps without an alias start with the WWID; older versions draw the tree
+/// with `\_`.
+fn parse_multipath(output: &str) -> Vec<MultipathMap> {
+    let mut maps: Vec<MultipathMap> = Vec::new();
+    for line in output.lines() {
+        let stripped = line.trim_start_matches(['|', '`', '-', '+', '\\', '_', ' ']);
+        if stripped.is_empty() {
+            continue;
+        }
+
+        if line.starts_with("size=") {
+            if let Some(map) = maps.last_mut() {
+                map.size = line.split_whitespace().next().and_then(|s| s.strip_prefix("size=")).map(str::to_string);
+            }
+        } else if stripped.contains("policy=") || stripped.starts_with("status=") {
+            let Some(map) = maps.last_mut() else { continue };
+            map.groups.push(PathGroup {
+                priority: value(stripped, "prio=").and_then(|p| p.parse().ok()),
+                status: value(stripped, "status=").unwrap_or_default(),
+                paths: Vec::new(),
+            });
+        } else if line == stripped {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let Some(dm_index) = tokens.iter().position(|t| t.starts_with("dm-")) else {
+                continue;
+            };
+            let (name, wwid) = match tokens.get(1) {
+                Some(wwid) if wwid.starts_with('(') => (tokens[0], wwid.trim_matches(|c| c == '(' || c == ')')),
+                _ => (tokens[0], tokens[0]),
+            };
+            maps.push(MultipathMap {
+                name: name.to_string(),
+                wwid: wwid.to_string(),
+                dm: tokens[dm_index].to_string(),
+                vendor: tokens[dm_index + 1..].join(" "),
+                size: None,
+                groups: Vec::new(),
+            });
+        } else {
+            let tokens: Vec<&str> = stripped.split_whitespace().collect();
+            let [hctl, device, _, dm_state, checker_state, device_state, ..] = tokens[..] else {
+                continue;
+            };
+            if hctl.split(':').count() != 4 {
+                continue;
+            }
+            let Some(group) = maps.last_mut().and_then(|map| map.groups.last_mut()) else {
+                continue;
+            };
+            group.paths.push(MultipathPath {
+                hctl: hctl.to_string(),
+                device: device.to_string(),
+                dm_state: dm_state.to_string(),
+                checker_state: checker_state.to_string(),
+                device_state: device_state.to_string(),
+            });
+        }
+    }
+    maps
+}
+
+/// `key=value` up to the next space
+fn value(line: &str, key: &str) -> Option<String> {
+    let start = line.find(key)? + key.len();
+    line[start..].split_whitespace().next().map(str::to_string)
+}
+
+fn read_hba_ports() -> Vec<HbaPort> {
+    let Ok(entries) = fs::read_dir(FC_HOST_ROOT) else {
+        return Vec::new();
+    };
+    let read = |dir: &Path, file: &str| fs::read_to_string(dir.join(file)).ok().map(|s| s.trim().to_string());
+    let mut ports: Vec<HbaPort> = entries
+        .filter_map(|e| e.ok())
+        .map(|entry| {
+            let dir = entry.path();
+            HbaPort {
+                host: entry.file_name().to_string_lossy().to_string(),
+                port_name: read(&dir, "port_name").unwrap_or_default(),
+                state: read(&dir, "port_state").unwrap_or_else(|| "Unknown".to_string()),
+                speed: read(&dir, "speed").filter(|speed| speed != "unknown"),
+            }
+        })
+        .collect();
+    ports.sort_by(|a, b| a.host.cmp(&b.host));
+    ports
+}
@@ -1,21 +1,80 @@
 use anyhow::{Result, Context};
-use crate::types::{NetworkInfo, NetworkInterface};
+use super::mtu;
+use crate::config::NetworkConfig;
+use crate::types::{ClusterFlavor, InterfaceClass, NetnsInterface, NetworkInfo, NetworkInterface, VmNetns};
+use crate::units::format_bytes;
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
-use std::process::Command;
+use std::process::Stdio;
+use tokio::process::Command;
 use serde_json::Value;
 
 pub struct NetworkCollector {
     use_mock: bool,
+    flavor: ClusterFlavor,
+    /// Also look inside virt-launcher pods' network namespaces
+    vm_namespaces: bool,
+    /// Interfaces to show; empty shows all
+    only: Vec<String>,
+    show_bridges: bool,
+    show_virtual: bool,
+    show_loopback: bool,
+    /// `network.expected_mtu`, overriding the CNI's
+    expected_mtu: Option<u32>,
 }
 
+/// Shown interfaces, per-class counts and how many were hidden
+type Enumerated = (Vec<NetworkInterface>, Vec<(InterfaceClass, usize)>, usize);
+
+/// IFF_TAP in `tun_flags`; without it the device is a tun
+const IFF_TAP: u32 = 0x0002;
+
 impl NetworkCollector {
     pub fn new() -> Result<Self> {
         Ok(Self {
             use_mock: false,
+            flavor: ClusterFlavor::K3s,
+            vm_namespaces: false,
+            only: Vec::new(),
+            show_bridges: true,
+            show_virtual: true,
+            show_loopback: false,
+            expected_mtu: None,
         })
     }
 
+    /// Which interfaces to list, from `[network]`
+    pub fn with_interface_filter(mut self, config: &NetworkConfig) -> Self {
+        self.only = config.interfaces.clone();
+        self.show_bridges = config.show_bridges;
+        self.show_virtual = config.show_virtual;
+        self.show_loopback = config.show_loopback;
+        self.expected_mtu = config.expected_mtu;
+        self
+    }
+
+    fn shows(&self, name: &str, class: InterfaceClass) -> bool {
+        if !self.only.is_empty() {
+            return self.only.iter().any(|n| n == name);
+        }
+        match class {
+            InterfaceClass::Bridge => self.show_bridges,
+            InterfaceClass::Loopback => self.show_loopback,
+            class => self.show_virtual || !class.is_virtual(),
+        }
+    }
+
+    pub fn with_flavor(mut self, flavor: ClusterFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    pub fn with_vm_namespaces(mut self, enabled: bool) -> Self {
+        self.vm_namespaces = enabled;
+        self
+    }
+
     pub async fn collect(&mut self) -> Result<NetworkInfo> {
         match self.collect_real().await {
             Ok(info) => Ok(info),
@@ -30,10 +89,17 @@ impl NetworkCollector {
     }
 
     async fn collect_real(&self) -> Result<NetworkInfo> {
-        let interfaces = self.enumerate_interfaces()?;
+        let (interfaces, interface_counts, hidden_interfaces) = self.enumerate_interfaces().await?;
         let (pod_cidr, service_cidr, cni) = self.get_k8s_network_config().await;
         let active_connections = self.count_active_connections();
         let k8s_services = self.count_k8s_services().await;
+        let vm_namespaces = if self.vm_namespaces {
+            enumerate_vm_namespaces().await
+        } else {
+            Vec::new()
+        };
+        let cni_mtu = self.expected_mtu.or_else(mtu::cni_mtu);
+        let mtu_mismatches = mtu::check(&vm_namespaces, cni_mtu);
 
         Ok(NetworkInfo {
             interfaces,
@@ -42,48 +108,62 @@ impl NetworkCollector {
             cni,
             active_connections,
             k8s_services,
+            vm_namespaces,
+            interface_counts,
+            hidden_interfaces,
+            cni_mtu,
+            mtu_mismatches,
         })
     }
 
-    fn enumerate_interfaces(&self) -> Result<Vec<NetworkInterface>> {
+    /// The interfaces the filters let through, grouped by class and sorted
+    /// by name within it, with every class counted. Hidden ones are
+    /// classified from sysfs alone, without running `ip` for each.
+    async fn enumerate_interfaces(&self) -> Result<Enumerated> {
         let net_path = Path::new("/sys/class/net");
         if !net_path.exists() {
             anyhow::bail!("/sys/class/net not found");
         }
 
         let mut interfaces = Vec::new();
+        let mut counts = [0usize; InterfaceClass::ALL.len()];
+        let mut hidden = 0;
 
         for entry in fs::read_dir(net_path)? {
             let entry = entry?;
             let iface_name = entry.file_name().to_string_lossy().to_string();
-
-            // Skip loopback
-            if iface_name == "lo" {
+            let class = classify(&entry.path());
+            if let Some(i) = InterfaceClass::ALL.iter().position(|c| *c == class) {
+                counts[i] += 1;
+            }
+            if !self.shows(&iface_name, class) {
+                hidden += 1;
                 continue;
             }
 
-            if let Ok(iface) = self.read_interface_info(&iface_name) {
+            if let Ok(iface) = self.read_interface_info(&iface_name, class).await {
                 interfaces.push(iface);
             }
         }
 
-        // Sort by name for consistent display
-        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        // Sort by class, then name, for consistent display
+        interfaces.sort_by(|a, b| (a.class, &a.name).cmp(&(b.class, &b.name)));
+        let counts = InterfaceClass::ALL.into_iter().zip(counts).filter(|(_, n)| *n > 0).collect();
 
-        Ok(interfaces)
+        Ok((interfaces, counts, hidden))
     }
 
-    fn read_interface_info(&self, name: &str) -> Result<NetworkInterface> {
+    async fn read_interface_info(&self, name: &str, class: InterfaceClass) -> Result<NetworkInterface> {
         let base_path = format!("/sys/class/net/{}", name);
 
         // Check if interface is up
         let is_up = self.read_operstate(&base_path)?;
 
         // Get IP address using ip command
-        let ip_address = self.get_ip_address(name)?;
+        let ip_address = self.get_ip_address(name).await?;
 
         // Get link speed
-        let speed = self.get_link_speed(&base_path);
+        let speed = self.get_link_speed(&base_path).await;
 
         // Get statistics
         let (rx_bytes, tx_bytes) = self.get_interface_stats(&base_path)?;
@@ -93,6 +173,7 @@ impl NetworkCollector {
 
         Ok(NetworkInterface {
             name: name.to_string(),
+            class,
             ip_address,
             is_up,
             speed,
@@ -108,11 +189,12 @@ impl NetworkCollector {
         Ok(state.trim() == "up")
     }
 
-    fn get_ip_address(&self, name: &str) -> Result<String> {
+    async fn get_ip_address(&self, name: &str) -> Result<String> {
         // Try using ip command with JSON output
-        let output = Command::new("ip")
+        let output = command("ip")
             .args(["-j", "addr", "show", name])
-            .output();
+            .output()
+            .await;
 
         if let Ok(output) = output {
             if output.status.success() {
@@ -137,9 +219,10 @@ impl NetworkCollector {
         }
 
         // Fallback: try parsing ip addr show output
-        let output = Command::new("ip")
+        let output = command("ip")
             .args(["addr", "show", name])
             .output()
+            .await
             .context("Failed to execute ip command")?;
 
         let output_str = String::from_utf8_lossy(&output.stdout);
@@ -156,7 +239,7 @@ impl NetworkCollector {
         Ok("N/A".to_string())
     }
 
-    fn get_link_speed(&self, base_path: &str) -> String {
+    async fn get_link_speed(&self, base_path: &str) -> String {
         // Try to read speed from sysfs
         if let Ok(speed) = fs::read_to_string(format!("{}/speed", base_path)) {
             if let Ok(speed_mbps) = speed.trim().parse::<i32>() {
@@ -171,9 +254,10 @@ impl NetworkCollector {
         }
 
         // Try ethtool as fallback
-        if let Ok(output) = Command::new("ethtool")
+        if let Ok(output) = command("ethtool")
             .arg(base_path.split('/').last().unwrap_or(""))
             .output()
+            .await
         {
             let output_str = String::from_utf8_lossy(&output.stdout);
             for line in output_str.lines() {
@@ -207,19 +291,21 @@ impl NetworkCollector {
     }
 
     async fn get_k8s_network_config(&self) -> (String, String, String) {
-        // Try to read from k3s/k8s config files
-        let pod_cidr = self.read_k3s_pod_cidr().unwrap_or_else(|| "10.42.0.0/16".to_string());
-        let service_cidr = self.read_k3s_service_cidr().unwrap_or_else(|| "10.43.0.0/16".to_string());
-        let cni = self.detect_cni().unwrap_or_else(|| "Flannel".to_string());
+        // Ask the cluster, falling back to the distribution's defaults
+        let (default_pod_cidr, default_service_cidr) = self.flavor.default_cidrs();
+        let pod_cidr = self.read_pod_cidr().await.unwrap_or_else(|| default_pod_cidr.to_string());
+        let service_cidr = self.read_service_cidr().await.unwrap_or_else(|| default_service_cidr.to_string());
+        let cni = self.detect_cni().await.unwrap_or_else(|| self.flavor.default_cni().to_string());
 
         (pod_cidr, service_cidr, cni)
     }
 
-    fn read_k3s_pod_cidr(&self) -> Option<String> {
-        // Try to read from k3s config
-        if let Ok(output) = Command::new("kubectl")
+    async fn read_pod_cidr(&self) -> Option<String> {
+        // Try to read from the controller-manager flags
+        if let Ok(output) = kubectl()
             .args(["cluster-info", "dump"])
             .output()
+            .await
         {
             let output_str = String::from_utf8_lossy(&output.stdout);
             for line in output_str.lines() {
@@ -237,11 +323,12 @@ impl NetworkCollector {
         None
     }
 
-    fn read_k3s_service_cidr(&self) -> Option<String> {
+    async fn read_service_cidr(&self) -> Option<String> {
         // Similar to pod CIDR, but for services
-        if let Ok(output) = Command::new("kubectl")
+        if let Ok(output) = kubectl()
             .args(["cluster-info", "dump"])
             .output()
+            .await
         {
             let output_str = String::from_utf8_lossy(&output.stdout);
             for line in output_str.lines() {
@@ -257,7 +344,7 @@ impl NetworkCollector {
         None
     }
 
-    fn detect_cni(&self) -> Option<String> {
+    async fn detect_cni(&self) -> Option<String> {
         // Check for common CNI binaries/configs
         let cni_paths = [
             ("/opt/cni/bin/flannel", "Flannel"),
@@ -273,7 +360,7 @@ impl NetworkCollector {
         }
 
         // Check running processes
-        if let Ok(output) = Command::new("ps").args(["aux"]).output() {
+        if let Ok(output) = command("ps").arg("aux").output().await {
             let output_str = String::from_utf8_lossy(&output.stdout);
             if output_str.contains("flannel") {
                 return Some("Flannel".to_string());
@@ -299,9 +386,10 @@ impl NetworkCollector {
 
     async fn count_k8s_services(&self) -> u32 {
         // Try to count k8s services
-        if let Ok(output) = Command::new("kubectl")
+        if let Ok(output) = kubectl()
             .args(["get", "services", "--all-namespaces", "--no-headers"])
             .output()
+            .await
         {
             if output.status.success() {
                 return String::from_utf8_lossy(&output.stdout).lines().count() as u32;
@@ -315,6 +403,7 @@ impl NetworkCollector {
             interfaces: vec![
                 NetworkInterface {
                     name: "eth0".to_string(),
+                    class: InterfaceClass::Physical,
                     ip_address: "192.168.1.100/24".to_string(),
                     is_up: true,
                     speed: "10 Gbps".to_string(),
@@ -324,6 +413,7 @@ impl NetworkCollector {
                 },
                 NetworkInterface {
                     name: "eth1".to_string(),
+                    class: InterfaceClass::Physical,
                     ip_address: "10.0.0.50/24".to_string(),
                     is_up: true,
                     speed: "10 Gbps".to_string(),
@@ -337,25 +427,259 @@ impl NetworkCollector {
             cni: "Flannel".to_string(),
             active_connections: 2456,
             k8s_services: 23,
+            vm_namespaces: if self.vm_namespaces { mock_vm_namespaces() } else { Vec::new() },
+            interface_counts: vec![(InterfaceClass::Physical, 2)],
+            hidden_interfaces: 0,
+            cni_mtu: Some(self.expected_mtu.unwrap_or(1450)),
+            mtu_mismatches: Vec::new(),
         }
     }
 }
 
-fn format_bytes(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-    const TB: u64 = GB * 1024;
-
-    if bytes >= TB {
-        format!("{:.1} TB", bytes as f64 / TB as f64)
-    } else if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
+/// An interface's class from its sysfs directory. Bridges, bonds and
+/// tun/tap devices have their own attribute directories; vlans say so in
+/// `uevent`; anything else with a backing device is hardware, and a veth
+/// is the software device whose peer (`iflink`) is another interface.
+fn classify(dir: &Path) -> InterfaceClass {
+    let read = |file: &str| fs::read_to_string(dir.join(file)).map(|s| s.trim().to_string()).unwrap_or_default();
+    // ARPHRD_LOOPBACK
+    if read("type") == "772" {
+        return InterfaceClass::Loopback;
+    }
+    if dir.join("bridge").exists() {
+        return InterfaceClass::Bridge;
+    }
+    if dir.join("bonding").exists() {
+        return InterfaceClass::Bond;
+    }
+    if dir.join("tun_flags").exists() {
+        let flags = u32::from_str_radix(read("tun_flags").trim_start_matches("0x"), 16).unwrap_or(0);
+        return if flags & IFF_TAP != 0 { InterfaceClass::Tap } else { InterfaceClass::Tun };
+    }
+    let uevent = read("uevent");
+    let devtype = uevent.lines().find_map(|l| l.strip_prefix("DEVTYPE="));
+    match devtype {
+        Some("vlan") => return InterfaceClass::Vlan,
+        Some("bridge") => return InterfaceClass::Bridge,
+        Some("bond") => return InterfaceClass::Bond,
+        _ => {}
+    }
+    if dir.join("device").exists() {
+        return InterfaceClass::Physical;
+    }
+    let (ifindex, iflink) = (read("ifindex"), read("iflink"));
+    if devtype.is_none() && !ifindex.is_empty() && ifindex != iflink {
+        return InterfaceClass::Veth;
+    }
+    InterfaceClass::Virtual
+}
+
+/// One entry per virt-launcher pod network namespace other than the host's,
+/// sorted by VM
+async fn enumerate_vm_namespaces() -> Vec<VmNetns> {
+    let host_netns = fs::read_link("/proc/1/ns/net").ok();
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    let mut seen = HashSet::new();
+    let mut namespaces = Vec::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse::<u32>().ok()) else {
+            continue;
+        };
+        let dir = entry.path();
+        // virt-launcher-monitor shows up as "virt-launcher-m" and shares the netns
+        let is_launcher = fs::read_to_string(dir.join("comm")).is_ok_and(|comm| comm.trim_end() == "virt-launcher");
+        if !is_launcher {
+            continue;
+        }
+        let Ok(netns) = fs::read_link(dir.join("ns/net")) else {
+            continue;
+        };
+        if Some(&netns) == host_netns.as_ref() || !seen.insert(netns.clone()) {
+            continue;
+        }
+        let Some(mut interfaces) = read_netns_counters(&dir) else {
+            continue;
+        };
+        let error = match netns_links(pid).await {
+            Ok(links) => {
+                merge_links(&mut interfaces, &links);
+                None
+            }
+            Err(e) => Some(format!("{:#}", e)),
+        };
+        namespaces.push(VmNetns {
+            vm: launcher_vm(&dir).unwrap_or_else(|| format!("pid {}", pid)),
+            pid,
+            netns: netns.to_string_lossy().into_owned(),
+            interfaces,
+            error,
+        });
+    }
+    namespaces.sort_by(|a, b| a.vm.cmp(&b.vm));
+    namespaces
+}
+
+/// "namespace/name" from virt-launcher's --namespace and --name arguments
+fn launcher_vm(dir: &Path) -> Option<String> {
+    let cmdline = fs::read(dir.join("cmdline")).ok()?;
+    let args: Vec<String> = cmdline
+        .split(|b| *b == 0)
+        .map(|a| String::from_utf8_lossy(a).into_owned())
+        .collect();
+    // "--name vm-01" or "--name=vm-01"
+    let value = |flag: &str| {
+        let inline = format!("{}=", flag);
+        args.iter().enumerate().find_map(|(i, arg)| {
+            if arg == flag {
+                args.get(i + 1).cloned()
+            } else {
+                arg.strip_prefix(&inline).map(str::to_string)
+            }
+        })
+    };
+    Some(format!("{}/{}", value("--namespace")?, value("--name")?))
+}
+
+/// Interfaces and counters from /proc/<pid>/net/dev, which shows the
+/// process's namespace and needs no privileges beyond reading /proc
+fn read_netns_counters(dir: &Path) -> Option<Vec<NetnsInterface>> {
+    let dev = fs::read_to_string(dir.join("net/dev")).ok()?;
+    let mut interfaces: Vec<NetnsInterface> = dev
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (name, counters) = line.split_once(':')?;
+            let name = name.trim();
+            if name == "lo" {
+                return None;
+            }
+            // rx: bytes packets errs drop fifo frame compressed multicast, then tx
+            let values: Vec<u64> = counters.split_whitespace().filter_map(|v| v.parse().ok()).collect();
+            let value = |i: usize| values.get(i).copied().unwrap_or(0);
+            Some(NetnsInterface {
+                name: name.to_string(),
+                rx_bytes: value(0),
+                rx_errors: value(2),
+                rx_dropped: value(3),
+                tx_bytes: value(8),
+                tx_errors: value(10),
+                tx_dropped: value(11),
+                ..NetnsInterface::default()
+            })
+        })
+        .collect();
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    Some(interfaces)
+}
+
+/// `ip -j addr` run inside the namespace
+async fn netns_links(pid: u32) -> Result<Vec<Value>> {
+    let output = command("nsenter")
+        .arg(format!("--net=/proc/{}/ns/net", pid))
+        .args(["ip", "-j", "addr", "show"])
+        .output()
+        .await
+        .context("Failed to run nsenter")?;
+    if !output.status.success() {
+        anyhow::bail!("nsenter: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    let links: Value = serde_json::from_slice(&output.stdout).context("Failed to parse ip output")?;
+    Ok(links.as_array().cloned().unwrap_or_default())
+}
+
+fn merge_links(interfaces: &mut [NetnsInterface], links: &[Value]) {
+    for link in links {
+        let Some(iface) = interfaces
+            .iter_mut()
+            .find(|iface| link["ifname"].as_str() == Some(iface.name.as_str()))
+        else {
+            continue;
+        };
+        // Tap and veth ends without a carrier report UNKNOWN while working
+        iface.is_up = link["operstate"].as_str().map(|state| state != "DOWN");
+        iface.mtu = link["mtu"].as_u64().map(|mtu| mtu as u32);
+        iface.addresses = link["addr_info"]
+            .as_array()
+            .map(|addrs| {
+                addrs
+                    .iter()
+                    .filter(|addr| addr["scope"].as_str() != Some("link"))
+                    .filter_map(|addr| Some(format!("{}/{}", addr["local"].as_str()?, addr["prefixlen"].as_u64()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+}
+
+fn mock_vm_namespaces() -> Vec<VmNetns> {
+    let iface = |name: &str, address: Option<&str>, rx: u64, tx: u64| NetnsInterface {
+        name: name.to_string(),
+        is_up: Some(true),
+        mtu: Some(1450),
+        addresses: address.map(str::to_string).into_iter().collect(),
+        rx_bytes: rx,
+        tx_bytes: tx,
+        ..NetnsInterface::default()
+    };
+    vec![
+        VmNetns {
+            vm: "default/vm-01".to_string(),
+            pid: 4242,
+            netns: "net:[4026532890]".to_string(),
+            interfaces: vec![
+                iface("eth0", Some("10.42.0.17/24"), 812_000_000, 95_000_000),
+                iface("k6t-eth0", None, 790_000_000, 93_000_000),
+                iface("tap0", None, 93_000_000, 790_000_000),
+            ],
+            error: None,
+        },
+        VmNetns {
+            vm: "tenant-a/db-01".to_string(),
+            pid: 5120,
+            netns: "net:[4026533012]".to_string(),
+            interfaces: vec![
+                iface("eth0", Some("10.42.0.23/24"), 2_400_000_000, 1_100_000_000),
+                iface("tap0", None, 1_100_000_000, 2_400_000_000),
+            ],
+            error: None,
+        },
+    ]
+}
+
+/// `program`, killed when a timed-out collection drops it
+fn command(program: &str) -> Command {
+    let mut cmd = Command::new(program);
+    cmd.stdin(Stdio::null()).kill_on_drop(true);
+    cmd
+}
+
+fn kubectl() -> Command {
+    command("kubectl")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::timed;
+    use std::time::{Duration, Instant};
+
+    /// A command that never answers must not hold up the rest of the
+    /// round: the limit fires and the quick collector still reports
+    #[tokio::test]
+    async fn hung_command_times_out() {
+        let limit = Duration::from_millis(200);
+        let started = Instant::now();
+        let hung = async {
+            command("sleep").arg("30").output().await?;
+            Ok(())
+        };
+        let quick = async { Ok(3) };
+        let ((_, hung), (_, quick)) = tokio::join!(timed(limit, hung), timed(limit, quick));
+        assert!(hung.unwrap_err().to_string().contains("timed out"));
+        assert_eq!(quick.unwrap(), 3);
+        assert!(started.elapsed() < Duration::from_secs(2));
     }
 }
@@ -0,0 +1,237 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::cgroup::pod_uid;
+use crate::types::OomKill;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+/// Kills kept for the OOM card, however old
+const RECENT_KILLS: usize = 50;
+/// The kubepods tree can hold thousands of cgroups; counting them on every
+/// refresh would cost more than it finds
+const SCAN_INTERVAL: Duration = Duration::from_secs(10);
+/// Deep enough for kubepods.slice/<qos>/<pod>/<container>
+const MAX_DEPTH: usize = 6;
+
+/// Keeps the recent OOM kills on this host. The kernel log names the
+/// killed process and its cgroup; each cgroup's `memory.events` oom_kill
+/// count catches kills the log missed (no access to /dev/kmsg, or records
+/// overwritten), attributed to the cgroup alone.
+pub struct OomCollector {
+    root: PathBuf,
+    /// oom_kill per cgroup at the previous scan; None before the first,
+    /// which only sets the baseline
+    counts: Option<HashMap<String, u64>>,
+    scanned_at: Option<Instant>,
+    /// Kills from the kernel log since the last scan, by cgroup, so the
+    /// count rise they explain isn't reported twice
+    unmatched: HashMap<String, u64>,
+    /// Newest last
+    kills: VecDeque<OomKill>,
+    /// Pod UIDs already looked up in the cluster
+    looked_up: HashSet<String>,
+}
+
+impl OomCollector {
+    pub fn new() -> Self {
+        Self {
+            root: PathBuf::from(CGROUP_ROOT),
+            counts: None,
+            scanned_at: None,
+            unmatched: HashMap::new(),
+            kills: VecDeque::new(),
+            looked_up: HashSet::new(),
+        }
+    }
+
+    /// Newest first
+    pub fn kills(&self) -> impl Iterator<Item = &OomKill> {
+        self.kills.iter().rev()
+    }
+
+    /// Take in kills parsed from the kernel log
+    pub fn record(&mut self, kills: Vec<OomKill>) {
+        for kill in kills {
+            if !kill.cgroup.is_empty() {
+                *self.unmatched.entry(kill.cgroup.clone()).or_default() += 1;
+            }
+            self.push(kill);
+        }
+    }
+
+    /// Compare every cgroup's oom_kill count with the previous scan's,
+    /// at most every `SCAN_INTERVAL`
+    pub async fn collect(&mut self) -> Result<()> {
+        if self.scanned_at.is_some_and(|at| at.elapsed() < SCAN_INTERVAL) {
+            return Ok(());
+        }
+        self.scanned_at = Some(Instant::now());
+        if !self.root.join("cgroup.controllers").exists() {
+            return Ok(());
+        }
+
+        let mut counts = HashMap::new();
+        scan(&self.root, &self.root, 0, &mut counts);
+        if let Some(ref previous) = self.counts {
+            let now = Local::now();
+            let mut risen: Vec<(&String, u64)> = counts
+                .iter()
+                .filter_map(|(cgroup, count)| {
+                    let before = previous.get(cgroup).copied().unwrap_or(0);
+                    let explained = self.unmatched.get(cgroup).copied().unwrap_or(0);
+                    let unexplained = count.saturating_sub(before).saturating_sub(explained);
+                    (unexplained > 0).then_some((cgroup, unexplained))
+                })
+                .collect();
+            risen.sort();
+            let kills: Vec<OomKill> = risen
+                .into_iter()
+                .flat_map(|(cgroup, n)| (0..n).map(move |_| new_kill(now, cgroup.clone())))
+                .collect();
+            for kill in kills {
+                self.push(kill);
+            }
+        }
+        self.counts = Some(counts);
+        self.unmatched.clear();
+        Ok(())
+    }
+
+    /// Pod UIDs of kills not yet matched to a pod name
+    pub fn unresolved_pods(&self) -> Vec<String> {
+        let mut uids: Vec<String> = self
+            .kills
+            .iter()
+            .filter(|kill| kill.pod.is_none())
+            .filter_map(|kill| kill.pod_uid.clone())
+            .filter(|uid| !self.looked_up.contains(uid))
+            .collect();
+        uids.sort();
+        uids.dedup();
+        uids
+    }
+
+    /// Name kills' pods and VMs from `pods` (UID -> namespace/name and the
+    /// VM a virt-launcher pod runs). UIDs not found are not asked again.
+    pub fn resolve_pods(&mut self, uids: Vec<String>, pods: &HashMap<String, (String, Option<String>)>) {
+        for kill in self.kills.iter_mut().filter(|kill| kill.pod.is_none()) {
+            if let Some((pod, vm)) = kill.pod_uid.as_ref().and_then(|uid| pods.get(uid)) {
+                kill.pod = Some(pod.clone());
+                kill.vm = vm.clone().or(kill.vm.take());
+            }
+        }
+        self.looked_up.extend(uids);
+    }
+
+    fn push(&mut self, kill: OomKill) {
+        self.kills.push_back(kill);
+        while self.kills.len() > RECENT_KILLS {
+            self.kills.pop_front();
+        }
+    }
+}
+
+/// Pick up an OOM kill from a kernel log line. Since 4.19 the kernel logs
+/// "oom-kill:constraint=...,task_memcg=<cgroup>,task=<name>,pid=<pid>,..."
+/// and then "Killed process <pid> (<name>) ... anon-rss:<n>kB ..."; older
+/// kernels only log the latter.
+pub fn parse_kernel_line(kills: &mut Vec<OomKill>, message: &str, at: DateTime<Local>) {
+    if let Some(fields) = message.strip_prefix("oom-kill:") {
+        let fields: HashMap<&str, &str> = fields.split(',').filter_map(|field| field.split_once('=')).collect();
+        let mut kill = new_kill(at, fields.get("task_memcg").map(|c| c.trim_start_matches('/')).unwrap_or_default().to_string());
+        kill.task = fields.get("task").map(|task| task.to_string());
+        kill.pid = fields.get("pid").and_then(|pid| pid.parse().ok());
+        kill.cgroup_limit = Some(fields.get("constraint") == Some(&"CONSTRAINT_MEMCG"));
+        kills.push(kill);
+        return;
+    }
+
+    let Some(i) = message.find("Killed process ") else {
+        return;
+    };
+    let rest = &message[i + "Killed process ".len()..];
+    let pid: Option<u32> = rest.split_whitespace().next().and_then(|pid| pid.parse().ok());
+    let task = rest.split_once('(').and_then(|(_, rest)| rest.split_once(')')).map(|(task, _)| task.to_string());
+    let anon_rss_kb = rest
+        .split_once("anon-rss:")
+        .and_then(|(_, rest)| rest.split("kB").next())
+        .and_then(|kb| kb.trim().parse().ok());
+
+    match kills.iter_mut().rev().find(|kill| kill.pid.is_some() && kill.pid == pid) {
+        Some(kill) => kill.anon_rss_kb = anon_rss_kb,
+        None => {
+            let mut kill = new_kill(at, String::new());
+            kill.task = task;
+            kill.pid = pid;
+            kill.anon_rss_kb = anon_rss_kb;
+            kill.cgroup_limit = Some(message.contains("Memory cgroup out of memory"));
+            kills.push(kill);
+        }
+    }
+}
+
+/// A kill in `cgroup`, with the pod, libvirt domain or unit its path names
+fn new_kill(at: DateTime<Local>, cgroup: String) -> OomKill {
+    let unit = cgroup
+        .rsplit('/')
+        .find(|segment| segment.ends_with(".service"))
+        .map(str::to_string);
+    OomKill {
+        at,
+        task: None,
+        pid: None,
+        pod_uid: pod_uid(&cgroup).filter(|_| cgroup.contains("kubepods")),
+        pod: None,
+        vm: libvirt_domain(&cgroup),
+        unit,
+        cgroup_limit: None,
+        anon_rss_kb: None,
+        cgroup,
+    }
+}
+
+/// "machine.slice/machine-qemu\x2d3\x2dweb1.scope" -> "web1"
+fn libvirt_domain(cgroup: &str) -> Option<String> {
+    let scope = cgroup.rsplit('/').find_map(|segment| segment.strip_prefix("machine-qemu"))?;
+    let scope = scope.strip_suffix(".scope")?.replace("\\x2d", "-");
+    // "-<id>-<name>"
+    let (_, name) = scope.trim_start_matches('-').split_once('-')?;
+    Some(name.to_string())
+}
+
+/// oom_kill of every cgroup under `dir`: from `memory.events.local`, which
+/// counts a cgroup's own kills, or else `memory.events` of leaf cgroups,
+/// since it also counts descendants'
+fn scan(root: &Path, dir: &Path, depth: usize, counts: &mut HashMap<String, u64>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let children: Vec<PathBuf> = entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect();
+
+    let local = dir.join("memory.events.local");
+    let events = if local.exists() {
+        Some(local)
+    } else if children.is_empty() {
+        Some(dir.join("memory.events"))
+    } else {
+        None
+    };
+    let count = events
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| text.lines().find_map(|line| line.strip_prefix("oom_kill ")?.trim().parse().ok()));
+    if let (Some(count), Ok(relative)) = (count, dir.strip_prefix(root)) {
+        if count > 0 {
+            counts.insert(relative.to_string_lossy().to_string(), count);
+        }
+    }
+
+    if depth < MAX_DEPTH {
+        for child in children {
+            scan(root, &child, depth + 1, counts);
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use anyhow::{bail, Result};
+use crate::types::{CoreFreq, PowerInfo};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const POWERCAP_ROOT: &str = "/sys/class/powercap";
+const CPU_ROOT: &str = "/sys/devices/system/cpu";
+
+/// Powertop-style telemetry: RAPL energy counters, cpufreq and cpuidle
+/// residency, sampled as deltas between collections
+pub struct PowerCollector {
+    use_mock: bool,
+    // RAPL zone path -> (energy_uj, sampled at)
+    energy: HashMap<PathBuf, (u64, Instant)>,
+    // idle state name -> cumulative residency across CPUs (usec), sampled at
+    idle: Option<(HashMap<String, u64>, Instant)>,
+    // CPU number -> cumulative idle time over all its states (usec)
+    core_idle: HashMap<u32, u64>,
+    throttle_baseline: Option<u64>,
+}
+
+impl PowerCollector {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            use_mock: false,
+            energy: HashMap::new(),
+            idle: None,
+            core_idle: HashMap::new(),
+            throttle_baseline: None,
+        })
+    }
+
+    pub async fn collect(&mut self) -> Result<PowerInfo> {
+        if self.use_mock {
+            return Ok(self.collect_mock());
+        }
+        match self.collect_real() {
+            Ok(info) => Ok(info),
+            Err(e) => {
+                tracing::warn!("Failed to read power telemetry, using mock data: {}", e);
+                self.use_mock = true;
+                Ok(self.collect_mock())
+            }
+        }
+    }
+
+    fn collect_real(&mut self) -> Result<PowerInfo> {
+        let cpus = cpu_dirs();
+        if cpus.is_empty() {
+            bail!("no CPUs under {}", CPU_ROOT);
+        }
+
+        let mut info = PowerInfo::default();
+        let now = Instant::now();
+
+        // RAPL energy counters (energy_uj is often root-only; skip silently)
+        for zone in rapl_zones() {
+            let Some(energy) = read_u64(&zone.join("energy_uj")) else { continue };
+            let name = fs::read_to_string(zone.join("name"))
+                .map(|n| n.trim().to_string())
+                .unwrap_or_default();
+
+            if let Some(&(prev, prev_at)) = self.energy.get(&zone) {
+                // Counters wrap at max_energy_range_uj
+                let delta = if energy >= prev {
+                    energy - prev
+                } else {
+                    read_u64(&zone.join("max_energy_range_uj")).unwrap_or(0) - prev + energy
+                };
+                let elapsed = now.duration_since(prev_at).as_secs_f64();
+                if elapsed > 0.0 {
+                    let watts = delta as f64 / 1e6 / elapsed;
+                    if name.starts_with("package") {
+                        *info.package_watts.get_or_insert(0.0) += watts;
+                    }
+                    info.domains.push((name, watts));
+                }
+            }
+            self.energy.insert(zone, (energy, now));
+        }
+
+        // Frequency scaling, per core and overall
+        let mhz = |path: PathBuf| read_u64(&path).map(|khz| khz as f64 / 1000.0);
+        for cpu in &cpus {
+            let Some(cur_mhz) = mhz(cpu.join("cpufreq/scaling_cur_freq")) else { continue };
+            info.cores.push(CoreFreq {
+                cpu: cpu_number(cpu),
+                cur_mhz,
+                max_mhz: mhz(cpu.join("cpufreq/cpuinfo_max_freq")).unwrap_or(0.0),
+                governor: fs::read_to_string(cpu.join("cpufreq/scaling_governor"))
+                    .ok()
+                    .map(|g| g.trim().to_string()),
+                busy_percent: None,
+            });
+        }
+        info.cores.sort_by_key(|core| core.cpu);
+        let freqs: Vec<f64> = info.cores.iter().map(|core| core.cur_mhz).collect();
+        if !freqs.is_empty() {
+            info.freq_avg_mhz = freqs.iter().sum::<f64>() / freqs.len() as f64;
+            info.freq_min_mhz = freqs.iter().cloned().fold(f64::MAX, f64::min);
+            info.freq_max_mhz = freqs.iter().cloned().fold(0.0, f64::max);
+        }
+        info.governor = info.cores.first().and_then(|core| core.governor.clone());
+        info.base_mhz = mhz(cpus[0].join("cpufreq/base_frequency"));
+        info.turbo = turbo_enabled();
+
+        // Idle state residency, summed over CPUs
+        let mut idle: HashMap<String, u64> = HashMap::new();
+        let mut order: Vec<String> = Vec::new();
+        let mut core_idle: HashMap<u32, u64> = HashMap::new();
+        for cpu in &cpus {
+            for state in subdirs(&cpu.join("cpuidle")) {
+                let Ok(name) = fs::read_to_string(state.join("name")) else { continue };
+                let name = name.trim().to_string();
+                let time = read_u64(&state.join("time")).unwrap_or(0);
+                if !idle.contains_key(&name) {
+                    order.push(name.clone());
+                }
+                *idle.entry(name).or_default() += time;
+                *core_idle.entry(cpu_number(cpu)).or_default() += time;
+            }
+        }
+        if let Some((_, prev_at)) = self.idle {
+            let elapsed = now.duration_since(prev_at).as_micros() as f64;
+            for core in info.cores.iter_mut().filter(|_| elapsed > 0.0) {
+                let (Some(time), Some(prev)) = (core_idle.get(&core.cpu), self.core_idle.get(&core.cpu)) else {
+                    continue;
+                };
+                let idle_percent = (time.saturating_sub(*prev) as f64 / elapsed * 100.0).min(100.0);
+                core.busy_percent = Some(100.0 - idle_percent);
+            }
+        }
+        self.core_idle = core_idle;
+        if let Some((ref prev, prev_at)) = self.idle {
+            let capacity = now.duration_since(prev_at).as_micros() as f64 * cpus.len() as f64;
+            if capacity > 0.0 {
+                let residency: Vec<(String, f64)> = order
+                    .iter()
+                    .map(|name| {
+                        let delta = idle[name].saturating_sub(prev.get(name).copied().unwrap_or(0));
+                        (name.clone(), (delta as f64 / capacity * 100.0).min(100.0))
+                    })
+                    .collect();
+                let idle_total: f64 = residency.iter().map(|(_, p)| p).sum();
+                info.cstates.push(("C0".to_string(), (100.0 - idle_total).max(0.0)));
+                info.cstates.extend(residency);
+            }
+        }
+        self.idle = Some((idle, now));
+
+        // Thermal throttling, counted from when we started watching
+        let throttles: u64 = cpus
+            .iter()
+            .filter_map(|cpu| read_u64(&cpu.join("thermal_throttle/package_throttle_count")))
+            .sum();
+        let baseline = *self.throttle_baseline.get_or_insert(throttles);
+        info.throttle_events = throttles.saturating_sub(baseline);
+
+        Ok(info)
+    }
+
+    fn collect_mock(&self) -> PowerInfo {
+        PowerInfo {
+            domains: vec![
+                ("package-0".to_string(), 118.4),
+                ("package-1".to_string(), 109.7),
+                ("dram".to_string(), 21.3),
+            ],
+            package_watts: Some(228.1),
+            freq_avg_mhz: 2840.0,
+            freq_min_mhz: 1200.0,
+            freq_max_mhz: 3600.0,
+            governor: Some("performance".to_string()),
+            cstates: vec![
+                ("C0".to_string(), 38.0),
+                ("POLL".to_string(), 0.1),
+                ("C1".to_string(), 12.4),
+                ("C1E".to_string(), 20.2),
+                ("C6".to_string(), 29.3),
+            ],
+            throttle_events: 0,
+            cores: (0..8)
+                .map(|cpu| CoreFreq {
+                    cpu,
+                    cur_mhz: if cpu < 4 { 3400.0 } else { 1200.0 },
+                    max_mhz: 3600.0,
+                    governor: Some("performance".to_string()),
+                    busy_percent: Some(if cpu < 4 { 85.0 } else { 5.0 }),
+                })
+                .collect(),
+            base_mhz: Some(2400.0),
+            turbo: Some(true),
+        }
+    }
+}
+
+/// intel_pstate's no_turbo switch, or the generic cpufreq boost one
+fn turbo_enabled() -> Option<bool> {
+    if let Some(no_turbo) = read_u64(&Path::new(CPU_ROOT).join("intel_pstate/no_turbo")) {
+        return Some(no_turbo == 0);
+    }
+    read_u64(&Path::new(CPU_ROOT).join("cpufreq/boost")).map(|boost| boost == 1)
+}
+
+/// 12 for /sys/devices/system/cpu/cpu12
+fn cpu_number(cpu: &Path) -> u32 {
+    cpu.file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.strip_prefix("cpu"))
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Top-level RAPL zones (intel-rapl:0, intel-rapl:1) and their subzones
+fn rapl_zones() -> Vec<PathBuf> {
+    let mut zones: Vec<PathBuf> = subdirs(Path::new(POWERCAP_ROOT))
+        .into_iter()
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("intel-rapl:"))
+        })
+        .collect();
+    zones.sort();
+    zones
+}
+
+fn cpu_dirs() -> Vec<PathBuf> {
+    let mut cpus: Vec<PathBuf> = subdirs(Path::new(CPU_ROOT))
+        .into_iter()
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix("cpu"))
+                .is_some_and(|n| n.chars().all(|c| c.is_ascii_digit()) && !n.is_empty())
+        })
+        .collect();
+    cpus.sort();
+    cpus
+}
+
+fn subdirs(path: &Path) -> Vec<PathBuf> {
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
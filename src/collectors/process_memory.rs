@@ -0,0 +1,80 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::types::ProcessMemory;
+
+/// Longest process name the kernel keeps in /proc/<pid>/comm
+const COMM_LEN: usize = 15;
+
+/// Resident memory of long-lived host daemons, summed per name over all
+/// their processes, for telling slow leaks from normal use
+pub struct ProcessMemoryCollector {
+    processes: Vec<String>,
+    use_mock: bool,
+}
+
+impl ProcessMemoryCollector {
+    pub fn new(processes: Vec<String>) -> Self {
+        Self { processes, use_mock: false }
+    }
+
+    /// One entry per configured name that has processes running, in
+    /// config order
+    pub async fn collect(&mut self) -> Result<Vec<ProcessMemory>> {
+        if !self.use_mock && !Path::new("/proc/self/status").exists() {
+            tracing::warn!("No /proc, not tracking daemon memory");
+            self.use_mock = true;
+        }
+        if self.use_mock {
+            return Ok(Vec::new());
+        }
+
+        let mut found: BTreeMap<&str, ProcessMemory> = BTreeMap::new();
+        for entry in fs::read_dir("/proc")?.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|p| p.parse::<u32>().ok()) else {
+                continue;
+            };
+            let Ok(comm) = fs::read_to_string(entry.path().join("comm")) else {
+                continue;
+            };
+            let comm = comm.trim();
+            // comm is cut at 15 characters
+            let Some(name) = self
+                .processes
+                .iter()
+                .find(|name| *name == comm || (comm.len() == COMM_LEN && name.starts_with(comm)))
+            else {
+                continue;
+            };
+            // Kernel threads and processes that just exited have no VmRSS
+            let Some(rss_kb) = fs::read_to_string(entry.path().join("status")).ok().and_then(|status| {
+                status
+                    .lines()
+                    .find_map(|l| l.strip_prefix("VmRSS:"))
+                    .and_then(|v| v.split_whitespace().next())
+                    .and_then(|v| v.parse::<u64>().ok())
+            }) else {
+                continue;
+            };
+            let memory = found.entry(name).or_insert_with(|| ProcessMemory {
+                name: name.clone(),
+                pids: Vec::new(),
+                rss_bytes: 0,
+            });
+            memory.pids.push(pid);
+            memory.rss_bytes += rss_kb * 1024;
+        }
+
+        Ok(self
+            .processes
+            .iter()
+            .filter_map(|name| found.remove(name.as_str()))
+            .map(|mut memory| {
+                memory.pids.sort_unstable();
+                memory
+            })
+            .collect())
+    }
+}
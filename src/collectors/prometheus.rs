@@ -0,0 +1,311 @@
+use anyhow::{bail, Context, Result};
+use futures::future::try_join_all;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::{Duration, Instant};
+
+use crate::config::PrometheusConfig;
+use crate::secrets::Secrets;
+use crate::types::{EtcdStatus, PrometheusMetrics};
+
+/// Scrapes take at most this long each, well inside the collector timeout
+const REQUEST_TIMEOUT_SECS: u64 = 5;
+
+/// Cluster metrics from an existing Prometheus (instant queries against
+/// /api/v1/query) and from exporter endpoints scraped directly. Scrapes
+/// run at most every `interval_secs`; requests go through curl like the
+/// notifiers' do, with the token passed on stdin.
+pub struct PrometheusCollector {
+    config: PrometheusConfig,
+    bearer_token: Option<String>,
+    interval: Duration,
+    scraped_at: Option<Instant>,
+    latest: PrometheusMetrics,
+}
+
+impl PrometheusCollector {
+    pub fn new(config: &PrometheusConfig, secrets: &Secrets) -> Result<Self> {
+        let bearer_token = match (&config.bearer_token_secret, &config.bearer_token_file) {
+            (Some(name), _) => Some(secrets.get(name).context("Failed to look up the Prometheus bearer token")?),
+            (None, Some(path)) => Some(
+                fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read Prometheus bearer token file: {}", path))?
+                    .trim()
+                    .to_string(),
+            ),
+            (None, None) => None,
+        };
+        Ok(Self {
+            config: config.clone(),
+            bearer_token,
+            interval: Duration::from_secs(config.interval_secs.max(1)),
+            scraped_at: None,
+            latest: PrometheusMetrics::default(),
+        })
+    }
+
+    /// The last scrape's metrics, scraping again once `interval_secs` passed
+    pub async fn collect(&mut self) -> Result<PrometheusMetrics> {
+        if self.scraped_at.is_some_and(|at| at.elapsed() < self.interval) {
+            return Ok(self.latest.clone());
+        }
+        self.scraped_at = Some(Instant::now());
+
+        let mut metrics = PrometheusMetrics::default();
+        if !self.config.url.is_empty() {
+            self.query_server(&mut metrics).await?;
+        }
+        if !self.config.targets.is_empty() {
+            self.scrape_targets(&mut metrics).await?;
+        }
+        self.latest = metrics.clone();
+        Ok(metrics)
+    }
+
+    async fn query_server(&self, metrics: &mut PrometheusMetrics) -> Result<()> {
+        let config = &self.config;
+        if !config.api_latency_query.is_empty() {
+            metrics.api_latency_ms = first_value(&self.query(&config.api_latency_query).await?).map(|s| s * 1000.0);
+        }
+        if config.etcd {
+            metrics.etcd = self.etcd_status().await?;
+        }
+        for (query, is_cpu) in [(&config.vm_cpu_query, true), (&config.vm_memory_query, false)] {
+            if query.is_empty() {
+                continue;
+            }
+            for (labels, value) in series(&self.query(query).await?) {
+                let (Some(namespace), Some(name)) = (labels.get("namespace"), labels.get("name")) else {
+                    continue;
+                };
+                let usage = metrics.vm_usage.entry((namespace.clone(), name.clone())).or_default();
+                if is_cpu {
+                    usage.0 = Some(value);
+                } else {
+                    usage.1 = Some(value);
+                }
+            }
+        }
+        for (name, query) in &config.queries {
+            let result = self.query(query).await.with_context(|| format!("prometheus.queries.{}", name))?;
+            if let Some(value) = first_value(&result) {
+                metrics.variables.insert(name.clone(), value);
+            }
+        }
+        Ok(())
+    }
+
+    /// etcd as `EtcdCollector` reads it, from the members' series the
+    /// server scrapes; None when it has none
+    async fn etcd_status(&self) -> Result<Option<EtcdStatus>> {
+        let queries = [
+            "max(etcd_mvcc_db_total_size_in_bytes)",
+            "max(etcd_mvcc_db_total_size_in_use_in_bytes)",
+            "max(etcd_server_quota_backend_bytes)",
+            "min(etcd_server_has_leader)",
+            "max(etcd_server_leader_changes_seen_total)",
+            "max(increase(etcd_server_leader_changes_seen_total[1h]))",
+            "sum(etcd_server_proposals_failed_total)",
+            "sum(increase(etcd_server_proposals_failed_total[5m]))",
+            "max(etcd_server_proposals_pending)",
+        ];
+        let results = try_join_all(queries.iter().map(|query| self.query(query))).await?;
+        let values: Vec<Option<f64>> = results.iter().map(first_value).collect();
+        let get = |i: usize| values[i].map(|v| v.max(0.0).round() as u64);
+        let Some(db_size_bytes) = get(0) else {
+            return Ok(None);
+        };
+        Ok(Some(EtcdStatus {
+            endpoint: format!("prometheus {}", self.config.url),
+            db_size_bytes,
+            db_in_use_bytes: get(1),
+            quota_bytes: get(2),
+            has_leader: get(3).map(|v| v == 1),
+            is_leader: None,
+            leader_changes_total: get(4).unwrap_or(0),
+            leader_changes_last_hour: get(5).unwrap_or(0),
+            proposals_failed_total: get(6).unwrap_or(0),
+            proposals_failed_recent: get(7).unwrap_or(0),
+            proposals_pending: get(8),
+        }))
+    }
+
+    /// `metrics` summed over every target's samples
+    async fn scrape_targets(&self, metrics: &mut PrometheusMetrics) -> Result<()> {
+        let bodies = try_join_all(self.config.targets.iter().map(|url| self.get(url, None))).await?;
+        let samples: Vec<Sample> = bodies.iter().flat_map(|body| parse_samples(body)).collect();
+        for (name, selector) in &self.config.metrics {
+            let selector = Selector::parse(selector).with_context(|| format!("prometheus.metrics.{}", name))?;
+            let matching: Vec<f64> = samples.iter().filter(|s| selector.matches(s)).map(|s| s.value).collect();
+            if !matching.is_empty() {
+                metrics.variables.insert(name.clone(), matching.iter().sum());
+            }
+        }
+        Ok(())
+    }
+
+    /// An instant query's `data.result`
+    async fn query(&self, query: &str) -> Result<Value> {
+        let url = format!("{}/api/v1/query", self.config.url.trim_end_matches('/'));
+        let body = self.get(&url, Some(query)).await?;
+        let mut response: Value =
+            serde_json::from_str(&body).with_context(|| format!("Failed to parse the response to {}", query))?;
+        if response["status"] != "success" {
+            bail!("{}: {}", query, response["error"].as_str().unwrap_or("query failed"));
+        }
+        Ok(response["data"]["result"].take())
+    }
+
+    async fn get(&self, url: &str, query: Option<&str>) -> Result<String> {
+        let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut config = format!("url = {}\n", quote(url));
+        if let Some(query) = query {
+            config.push_str(&format!("get\ndata-urlencode = {}\n", quote(&format!("query={}", query))));
+        }
+        if let Some(ref token) = self.bearer_token {
+            config.push_str(&format!("header = {}\n", quote(&format!("Authorization: Bearer {}", token))));
+        }
+        if let Some(ref ca) = self.config.ca_file {
+            config.push_str(&format!("cacert = {}\n", quote(ca)));
+        }
+
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail-with-body", "--max-time"])
+            .arg(REQUEST_TIMEOUT_SECS.to_string())
+            .args(["--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("Failed to run curl")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(config.as_bytes()).await?;
+        }
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            bail!("{}: {} {}", url, stderr.trim().trim_start_matches("curl: "), stdout.trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Labels and value of each series of a vector or scalar result
+fn series(result: &Value) -> Vec<(BTreeMap<String, String>, f64)> {
+    let value = |pair: &Value| pair[1].as_str().and_then(|v| v.parse::<f64>().ok()).filter(|v| v.is_finite());
+    match result {
+        Value::Array(items) if items.first().is_some_and(|item| item.is_object()) => items
+            .iter()
+            .filter_map(|item| {
+                let labels = item["metric"]
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                    .collect();
+                Some((labels, value(&item["value"])?))
+            })
+            .collect(),
+        // A scalar or string result: [time, "value"]
+        Value::Array(_) => value(result).map(|v| (BTreeMap::new(), v)).into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn first_value(result: &Value) -> Option<f64> {
+    series(result).into_iter().next().map(|(_, value)| value)
+}
+
+/// One line of the Prometheus text format
+struct Sample {
+    name: String,
+    labels: HashMap<String, String>,
+    value: f64,
+}
+
+/// `name{labels} value [timestamp]` lines; comments and unparsable lines
+/// are skipped
+fn parse_samples(body: &str) -> Vec<Sample> {
+    body.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (name, rest) = line.split_at(line.find(['{', ' '])?);
+            let (labels, rest) = match rest.strip_prefix('{') {
+                Some(labelled) => {
+                    let (labels, rest) = labelled.rsplit_once('}')?;
+                    (parse_labels(labels), rest)
+                }
+                None => (HashMap::new(), rest),
+            };
+            let value = rest.split_whitespace().next()?.parse::<f64>().ok()?;
+            Some(Sample { name: name.to_string(), labels, value })
+        })
+        .collect()
+}
+
+/// `a="x",b="y"`, with `\"` and `\\` escapes in the values
+fn parse_labels(text: &str) -> HashMap<String, String> {
+    let mut labels = HashMap::new();
+    let mut rest = text;
+    while let Some((key, after)) = rest.split_once("=\"") {
+        let mut value = String::new();
+        let mut chars = after.char_indices();
+        let mut end = after.len();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    if let Some((_, escaped)) = chars.next() {
+                        value.push(if escaped == 'n' { '\n' } else { escaped });
+                    }
+                }
+                '"' => {
+                    end = i + 1;
+                    break;
+                }
+                c => value.push(c),
+            }
+        }
+        labels.insert(key.trim_start_matches(',').trim().to_string(), value);
+        rest = &after[end..];
+    }
+    labels
+}
+
+/// A metric name with `label="value"` matchers, as in `[prometheus] metrics`
+struct Selector {
+    name: String,
+    labels: HashMap<String, String>,
+}
+
+impl Selector {
+    fn parse(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let (name, labels) = match text.split_once('{') {
+            Some((name, labels)) => {
+                let labels = labels.strip_suffix('}').context("Unclosed '{' in the selector")?;
+                (name.trim(), parse_labels(labels))
+            }
+            None => (text, HashMap::new()),
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':') {
+            bail!("{:?} is not a metric name", name);
+        }
+        Ok(Self { name: name.to_string(), labels })
+    }
+
+    fn matches(&self, sample: &Sample) -> bool {
+        sample.name == self.name && self.labels.iter().all(|(k, v)| sample.labels.get(k) == Some(v))
+    }
+}
+
+/// Whether `text` is a usable `[prometheus] metrics` selector, for validation
+pub fn check_selector(text: &str) -> Result<()> {
+    Selector::parse(text).map(|_| ())
+}
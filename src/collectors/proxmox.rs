@@ -0,0 +1,171 @@
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::config::ProxmoxConfig;
+use crate::types::{K8sResource, KubeVirtInfo, ResourceKind, VmAction};
+
+/// Label carrying a Proxmox VM's numeric id
+pub const VMID_LABEL: &str = "proxmox.vmid";
+
+/// VMs of a Proxmox VE cluster through its REST API with an API token,
+/// shown like KubeVirt's: the Proxmox node as the namespace and the VM's
+/// tags as labels. Requests go through curl like the notifiers' do.
+#[derive(Clone)]
+pub struct ProxmoxBackend {
+    config: ProxmoxConfig,
+}
+
+impl ProxmoxBackend {
+    pub fn new(config: ProxmoxConfig) -> Self {
+        Self { config }
+    }
+
+    /// Running, stopped and migrating qemu VMs of the whole cluster
+    pub async fn collect_info(&self) -> Result<KubeVirtInfo> {
+        let mut info = KubeVirtInfo::default();
+        for vm in self.collect_vms().await? {
+            match vm.status.as_str() {
+                "Running" => info.vms_running += 1,
+                "Migrating" => info.vms_migrating += 1,
+                _ => info.vms_stopped += 1,
+            }
+        }
+        Ok(info)
+    }
+
+    pub async fn collect_vms(&self) -> Result<Vec<K8sResource>> {
+        let vms = self.request("GET", "/cluster/resources?type=vm", &[]).await?;
+        Ok(vms
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|vm| vm["type"] == "qemu" && vm["template"].as_u64() != Some(1))
+            .filter_map(|vm| {
+                let vmid = vm["vmid"].as_u64()?;
+                let status = match (vm["lock"].as_str(), vm["status"].as_str()) {
+                    (Some("migrate"), _) => "Migrating".to_string(),
+                    (_, Some("running")) => "Running".to_string(),
+                    (_, Some("stopped")) => "Stopped".to_string(),
+                    (_, Some("paused")) => "Paused".to_string(),
+                    (_, other) => other.unwrap_or("Unknown").to_string(),
+                };
+                let mut labels: BTreeMap<String, String> = vm["tags"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .split([';', ',', ' '])
+                    .filter(|tag| !tag.is_empty())
+                    .map(|tag| (format!("proxmox.tag/{}", tag), "true".to_string()))
+                    .collect();
+                labels.insert(VMID_LABEL.to_string(), vmid.to_string());
+                Some(K8sResource {
+                    kind: ResourceKind::VirtualMachine,
+                    name: vm["name"].as_str().map(str::to_string).unwrap_or_else(|| format!("vm-{}", vmid)),
+                    namespace: vm["node"].as_str().map(str::to_string),
+                    status,
+                    labels,
+                })
+            })
+            .collect())
+    }
+
+    /// Start, shut down (ACPI, like a KubeVirt stop) or live-migrate the VM
+    /// `name` on `node`. Migration picks the online node using the least
+    /// memory, since Proxmox has no scheduler to ask; labels have no
+    /// Proxmox equivalent.
+    pub async fn run_action(&self, action: &VmAction, node: &str, name: &str) -> Result<String> {
+        let vms = self.collect_vms().await?;
+        let vmid = vms
+            .iter()
+            .find(|vm| vm.namespace.as_deref() == Some(node) && vm.name == name)
+            .and_then(|vm| vm.labels.get(VMID_LABEL))
+            .with_context(|| format!("No VM {} on Proxmox node {}", name, node))?;
+        let base = format!("/nodes/{}/qemu/{}", node, vmid);
+
+        let data = match action {
+            VmAction::Start => self.request("POST", &format!("{}/status/start", base), &[]).await?,
+            VmAction::Stop => self.request("POST", &format!("{}/status/shutdown", base), &[]).await?,
+            VmAction::Migrate => {
+                let target = self.migration_target(node).await?;
+                self.request("POST", &format!("{}/migrate", base), &[("target", &target), ("online", "1")])
+                    .await?
+            }
+            VmAction::AddLabel { .. } => bail!("Labels aren't supported by the Proxmox backend"),
+        };
+        // Actions answer with the UPID of the task they started
+        Ok(data.as_str().map(str::to_string).unwrap_or_else(|| data.to_string()))
+    }
+
+    /// The online node other than `from` with the smallest share of its
+    /// memory in use
+    async fn migration_target(&self, from: &str) -> Result<String> {
+        let nodes = self.request("GET", "/cluster/resources?type=node", &[]).await?;
+        nodes
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|node| node["status"] == "online" && node["node"] != from)
+            .filter_map(|node| {
+                let used = node["mem"].as_f64()? / node["maxmem"].as_f64().filter(|max| *max > 0.0)?;
+                Some((node["node"].as_str()?.to_string(), used))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(node, _)| node)
+            .with_context(|| format!("No other online Proxmox node to migrate off {} to", from))
+    }
+
+    /// One API call; returns the response's `data`. The request is passed
+    /// to curl as a config on stdin so the token stays out of the process list.
+    async fn request(&self, method: &str, path: &str, form: &[(&str, &str)]) -> Result<Value> {
+        let secret = match (&self.config.token_secret, &self.config.token_secret_file) {
+            (_, Some(path)) => fs::read_to_string(path)
+                .with_context(|| format!("Failed to read Proxmox token secret file: {}", path))?
+                .trim()
+                .to_string(),
+            (Some(secret), None) => secret.clone(),
+            (None, None) => bail!("virtualization.proxmox.token_secret is not set"),
+        };
+        let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+        let url = format!("{}/api2/json{}", self.config.url.trim_end_matches('/'), path);
+        let mut config = format!(
+            "url = {}\nrequest = {}\nheader = {}\n",
+            quote(&url),
+            quote(method),
+            quote(&format!("Authorization: PVEAPIToken={}={}", self.config.token_id, secret))
+        );
+        for (key, value) in form {
+            config.push_str(&format!("data-urlencode = {}\n", quote(&format!("{}={}", key, value))));
+        }
+        if let Some(ref ca) = self.config.ca_file {
+            config.push_str(&format!("cacert = {}\n", quote(ca)));
+        }
+        if self.config.insecure {
+            config.push_str("insecure\n");
+        }
+
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail-with-body", "--max-time", "10", "--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("Failed to run curl")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(config.as_bytes()).await?;
+        }
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            bail!("Proxmox {} {} failed: {} {}", method, path, stderr.trim(), stdout.trim());
+        }
+        let mut body: Value = serde_json::from_slice(&output.stdout)
+            .with_context(|| format!("Failed to parse Proxmox response to {}", path))?;
+        Ok(body["data"].take())
+    }
+}
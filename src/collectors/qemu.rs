@@ -0,0 +1,341 @@
+use anyhow::{bail, Context, Result};
+use super::cgroup::pod_uid;
+use crate::types::{QemuProcess, QemuSignal, SchedLatency};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+const PROC_ROOT: &str = "/proc";
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+/// Scans a delay has to last through to count as sustained (30s at the
+/// default refresh interval)
+const SUSTAINED_SCANS: usize = 15;
+
+/// Finds qemu processes on this host and maps each to its VM, virt-launcher
+/// pod cgroup, tap devices and disk images
+pub struct QemuCollector {
+    use_mock: bool,
+    /// Scheduler counters from the previous scan, by pid
+    previous: HashMap<u32, SchedSample>,
+    /// Recent (wait, throttled) percentages, by pid, oldest first
+    history: HashMap<u32, VecDeque<(f64, f64)>>,
+}
+
+/// Cumulative scheduler counters of one qemu process
+struct SchedSample {
+    at: Instant,
+    vcpus: u32,
+    /// Nanoseconds spent runnable but not running, summed over vCPU threads
+    run_delay_ns: u64,
+    nr_periods: u64,
+    nr_throttled: u64,
+}
+
+impl QemuCollector {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            use_mock: false,
+            previous: HashMap::new(),
+            history: HashMap::new(),
+        })
+    }
+
+    pub async fn collect(&mut self) -> Result<Vec<QemuProcess>> {
+        if self.use_mock {
+            return Ok(self.collect_mock());
+        }
+        match self.collect_real() {
+            Ok(processes) => Ok(processes),
+            Err(e) => {
+                tracing::warn!("Failed to scan for qemu processes, using mock data: {}", e);
+                self.use_mock = true;
+                Ok(self.collect_mock())
+            }
+        }
+    }
+
+    fn collect_real(&mut self) -> Result<Vec<QemuProcess>> {
+        if !Path::new("/dev/kvm").exists() {
+            bail!("/dev/kvm not present, not a KVM host");
+        }
+
+        let mut processes = Vec::new();
+        for entry in fs::read_dir(PROC_ROOT).context("Failed to read /proc")? {
+            let entry = entry?;
+            let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse::<u32>().ok()) else {
+                continue;
+            };
+            let dir = entry.path();
+            let is_qemu = fs::read_to_string(dir.join("comm"))
+                .map(|comm| comm.starts_with("qemu"))
+                .unwrap_or(false);
+            if !is_qemu {
+                continue;
+            }
+            // The process may exit between listing and reading
+            if let Some(process) = read_process(pid, &dir) {
+                processes.push(process);
+            }
+        }
+
+        let mut previous = HashMap::new();
+        for process in &mut processes {
+            let Some(sample) = read_sched(process.pid, &process.cgroup) else {
+                continue;
+            };
+            if let Some(prev) = self.previous.remove(&process.pid) {
+                process.sched = Some(self.latency(process.pid, &prev, &sample));
+            }
+            previous.insert(process.pid, sample);
+        }
+        self.previous = previous;
+        self.history.retain(|pid, _| self.previous.contains_key(pid));
+
+        processes.sort_by(|a, b| a.domain.cmp(&b.domain));
+        Ok(processes)
+    }
+
+    /// Rates between two samples, recorded into the process's history
+    fn latency(&mut self, pid: u32, prev: &SchedSample, sample: &SchedSample) -> SchedLatency {
+        let elapsed_ns = sample.at.duration_since(prev.at).as_nanos() as f64 * sample.vcpus.max(1) as f64;
+        let wait_percent = if elapsed_ns > 0.0 {
+            (sample.run_delay_ns.saturating_sub(prev.run_delay_ns) as f64 / elapsed_ns * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+        let periods = sample.nr_periods.saturating_sub(prev.nr_periods);
+        let throttled_percent = if periods > 0 {
+            sample.nr_throttled.saturating_sub(prev.nr_throttled) as f64 / periods as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        let history = self.history.entry(pid).or_default();
+        history.push_back((wait_percent, throttled_percent));
+        if history.len() > SUSTAINED_SCANS {
+            history.pop_front();
+        }
+        let sustained = |value: fn(&(f64, f64)) -> f64| {
+            if history.len() < SUSTAINED_SCANS {
+                0.0
+            } else {
+                history.iter().map(value).fold(f64::INFINITY, f64::min)
+            }
+        };
+
+        SchedLatency {
+            vcpus: sample.vcpus,
+            wait_percent,
+            throttled_percent,
+            sustained_wait_percent: sustained(|(wait, _)| *wait),
+            sustained_throttled_percent: sustained(|(_, throttled)| *throttled),
+        }
+    }
+
+    fn collect_mock(&self) -> Vec<QemuProcess> {
+        let gib = 1_073_741_824;
+        let entries = [
+            (41822, "default", "vm-webserver-01", "4f1c2a7e-93b1-4c0e-8d2a-1b6f0e4a9c11", 4 * gib, 9, (2, 1.4, 0.0)),
+            (42310, "default", "vm-database-01", "9b07de14-5a2f-4e6b-b1c3-7d8e2f0a6b22", 16 * gib, 13, (8, 12.6, 3.1)),
+            (45077, "tenant-a", "vm-ci-runner", "c3d9a1f0-2e7b-4a58-9f61-0c4b8e7d5a33", 8 * gib, 11, (4, 4.2, 38.0)),
+        ];
+
+        entries
+            .iter()
+            .map(|&(pid, namespace, name, uid, rss_bytes, threads, (vcpus, wait, throttled))| {
+                let cgroup = format!(
+                    "/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod{}.slice/cri-containerd-5e1a.scope",
+                    uid.replace('-', "_")
+                );
+                QemuProcess {
+                    pid,
+                    domain: format!("{}_{}", namespace, name),
+                    vm: Some(format!("{}/{}", namespace, name)),
+                    pod_uid: pod_uid(&cgroup),
+                    cgroup,
+                    pod: None,
+                    taps: vec!["tap0".to_string()],
+                    disks: vec![
+                        "/var/run/kubevirt-private/vmi-disks/rootdisk/disk.img".to_string(),
+                        "/var/run/kubevirt-ephemeral-disks/cloud-init-data/default/noCloud.iso".to_string(),
+                    ],
+                    rss_bytes,
+                    threads,
+                    sched: Some(SchedLatency {
+                        vcpus,
+                        wait_percent: wait,
+                        throttled_percent: throttled,
+                        sustained_wait_percent: wait,
+                        sustained_throttled_percent: throttled,
+                    }),
+                    stopped: false,
+                }
+            })
+            .collect()
+    }
+}
+
+fn read_process(pid: u32, dir: &Path) -> Option<QemuProcess> {
+    let cmdline = fs::read(dir.join("cmdline")).ok()?;
+    let args: Vec<String> = cmdline
+        .split(|b| *b == 0)
+        .filter(|a| !a.is_empty())
+        .map(|a| String::from_utf8_lossy(a).into_owned())
+        .collect();
+
+    let domain = domain_name(&args).unwrap_or_else(|| format!("pid {}", pid));
+    // KubeVirt domains are "<namespace>_<vm>"; namespaces can't contain '_'
+    let vm = domain.split_once('_').map(|(ns, name)| format!("{}/{}", ns, name));
+
+    let cgroup = fs::read_to_string(dir.join("cgroup"))
+        .ok()
+        .and_then(|c| c.lines().find_map(|l| l.strip_prefix("0::").map(str::to_string)))
+        .unwrap_or_default();
+
+    let status = fs::read_to_string(dir.join("status")).unwrap_or_default();
+    let status_field = |key: &str| {
+        status
+            .lines()
+            .find_map(|l| l.strip_prefix(key))
+            .and_then(|v| v.split_whitespace().next())
+            .and_then(|v| v.parse::<u64>().ok())
+    };
+
+    Some(QemuProcess {
+        pid,
+        domain,
+        vm,
+        pod_uid: pod_uid(&cgroup),
+        cgroup,
+        pod: None,
+        taps: tap_devices(dir),
+        disks: disk_paths(&args),
+        rss_bytes: status_field("VmRSS:").unwrap_or(0) * 1024,
+        threads: status_field("Threads:").unwrap_or(0) as u32,
+        sched: None,
+        stopped: status.lines().any(|l| l.strip_prefix("State:").is_some_and(|v| v.trim_start().starts_with('T'))),
+    })
+}
+
+/// Send `signal` to the qemu process `pid`, checking first that the PID
+/// still is one: it may have exited and been reused since the last scan
+pub fn signal_qemu(pid: u32, signal: QemuSignal) -> Result<()> {
+    let comm = fs::read_to_string(Path::new(PROC_ROOT).join(pid.to_string()).join("comm"))
+        .with_context(|| format!("PID {} has exited", pid))?;
+    if !comm.starts_with("qemu") {
+        bail!("PID {} is now {}, not qemu", pid, comm.trim());
+    }
+    let number = match signal {
+        QemuSignal::Pause => libc::SIGSTOP,
+        QemuSignal::Resume => libc::SIGCONT,
+        QemuSignal::Terminate => libc::SIGTERM,
+        QemuSignal::Kill => libc::SIGKILL,
+    };
+    // SAFETY: sending a signal touches no memory of ours
+    if unsafe { libc::kill(pid as libc::pid_t, number) } != 0 {
+        return Err(std::io::Error::last_os_error()).with_context(|| format!("Failed to signal PID {}", pid));
+    }
+    Ok(())
+}
+
+/// Run delay of the vCPU threads ("CPU <n>/KVM") and CFS throttling of the
+/// process's cgroup. Without named threads every thread counts, so I/O
+/// threads waiting inflate the figure.
+fn read_sched(pid: u32, cgroup: &str) -> Option<SchedSample> {
+    let at = Instant::now();
+    let tasks = fs::read_dir(Path::new(PROC_ROOT).join(pid.to_string()).join("task")).ok()?;
+
+    let (mut vcpus, mut vcpu_delay, mut threads, mut thread_delay) = (0u32, 0u64, 0u32, 0u64);
+    for task in tasks.filter_map(|e| e.ok()) {
+        let dir = task.path();
+        // "<run time> <run delay> <timeslices>", all cumulative
+        let Some(delay) = fs::read_to_string(dir.join("schedstat"))
+            .ok()
+            .and_then(|stat| stat.split_whitespace().nth(1)?.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        let is_vcpu = fs::read_to_string(dir.join("comm"))
+            .is_ok_and(|comm| comm.starts_with("CPU ") && comm.trim_end().ends_with("/KVM"));
+        if is_vcpu {
+            vcpus += 1;
+            vcpu_delay += delay;
+        }
+        threads += 1;
+        thread_delay += delay;
+    }
+    let (vcpus, run_delay_ns) = if vcpus > 0 { (vcpus, vcpu_delay) } else { (threads, thread_delay) };
+    if vcpus == 0 {
+        return None;
+    }
+
+    // No cpu.stat (cgroup v1, or no CPU controller) reads as never throttled
+    let stat = fs::read_to_string(Path::new(CGROUP_ROOT).join(cgroup.trim_start_matches('/')).join("cpu.stat"))
+        .unwrap_or_default();
+    let field = |key: &str| {
+        stat.lines()
+            .find_map(|l| l.strip_prefix(key)?.trim().parse::<u64>().ok())
+            .unwrap_or(0)
+    };
+
+    Some(SchedSample {
+        at,
+        vcpus,
+        run_delay_ns,
+        nr_periods: field("nr_periods "),
+        nr_throttled: field("nr_throttled "),
+    })
+}
+
+/// "-name guest=default_vm-01,debug-threads=on" or plain "-name default_vm-01"
+fn domain_name(args: &[String]) -> Option<String> {
+    let value = args.iter().position(|a| a == "-name").and_then(|i| args.get(i + 1))?;
+    let name = value
+        .split(',')
+        .find_map(|part| part.strip_prefix("guest="))
+        .unwrap_or_else(|| value.split(',').next().unwrap_or(value));
+    Some(name.to_string())
+}
+
+/// Files backing -blockdev/-drive arguments, in JSON or key=value syntax
+fn disk_paths(args: &[String]) -> Vec<String> {
+    let mut disks = Vec::new();
+    for arg in args {
+        for (i, _) in arg.match_indices("\"filename\":\"") {
+            let rest = &arg[i + "\"filename\":\"".len()..];
+            if let Some(end) = rest.find('"') {
+                disks.push(rest[..end].to_string());
+            }
+        }
+        for part in arg.split(',') {
+            if let Some(path) = part.strip_prefix("file=").or_else(|| part.strip_prefix("filename=")) {
+                disks.push(path.to_string());
+            }
+        }
+    }
+    let mut seen = HashSet::new();
+    disks.retain(|disk| seen.insert(disk.clone()));
+    disks
+}
+
+/// Interface names of open tun/tap file descriptors ("iff:" in fdinfo).
+/// Needs the same privileges as reading the process's fds.
+fn tap_devices(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir.join("fdinfo")) else {
+        return Vec::new();
+    };
+    let mut taps: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| fs::read_to_string(e.path()).ok())
+        .filter_map(|info| {
+            info.lines()
+                .find_map(|l| l.strip_prefix("iff:"))
+                .map(|name| name.trim().to_string())
+        })
+        .collect();
+    taps.sort();
+    taps.dedup();
+    taps
+}
+
@@ -0,0 +1,151 @@
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use tokio::process::Command;
+
+use crate::config::QmpConfig;
+use crate::types::{K8sResource, KubeVirtInfo, ResourceKind, VmAction};
+
+/// Label carrying the QMP socket a VM was found through
+const SOCKET_LABEL: &str = "qmp.socket";
+
+/// qemu started without a manager, one QMP socket per VM, on hosts reached
+/// over SSH (or this one). Sockets are spoken to with socat on the host;
+/// the host is the namespace and the socket's name the VM's.
+#[derive(Clone)]
+pub struct QmpBackend {
+    config: QmpConfig,
+}
+
+impl QmpBackend {
+    pub fn new(config: QmpConfig) -> Self {
+        Self { config }
+    }
+
+    pub async fn collect_info(&self) -> Result<KubeVirtInfo> {
+        let mut info = KubeVirtInfo::default();
+        for vm in self.collect_vms().await? {
+            match vm.status.as_str() {
+                "Running" => info.vms_running += 1,
+                "Migrating" => info.vms_migrating += 1,
+                _ => info.vms_stopped += 1,
+            }
+        }
+        Ok(info)
+    }
+
+    /// Every socket matching the glob on every host, with qemu's run
+    /// state. Unreachable hosts are skipped unless all of them are.
+    pub async fn collect_vms(&self) -> Result<Vec<K8sResource>> {
+        let results = futures::future::join_all(self.config.hosts.iter().map(|host| self.host_vms(host))).await;
+        let mut vms = Vec::new();
+        let mut last_error = None;
+        for (host, result) in self.config.hosts.iter().zip(results) {
+            match result {
+                Ok(found) => vms.extend(found),
+                Err(e) => {
+                    tracing::debug!("Failed to list QMP sockets on {}: {:#}", host, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        match last_error {
+            Some(e) if vms.is_empty() => Err(e),
+            _ => Ok(vms),
+        }
+    }
+
+    async fn host_vms(&self, host: &str) -> Result<Vec<K8sResource>> {
+        let script = format!(
+            "for s in {}; do [ -S \"$s\" ] || continue; printf '%s\\t' \"$s\"; {} | tr -d '\\n'; echo; done",
+            self.config.sockets,
+            qmp_pipe("\"$s\"", "query-status")
+        );
+        let output = self.run(host, &script).await?;
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let (socket, replies) = line.split_once('\t')?;
+                let name = socket.rsplit('/').next()?;
+                let name = name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(name);
+                let status = serde_json::Deserializer::from_str(replies)
+                    .into_iter::<Value>()
+                    .flatten()
+                    .find_map(|reply| reply["return"]["status"].as_str().map(str::to_string));
+                let status = match status.as_deref() {
+                    Some("running") => "Running",
+                    Some("paused" | "suspended") => "Paused",
+                    Some("inmigrate" | "postmigrate" | "finish-migrate") => "Migrating",
+                    Some("shutdown") => "Stopped",
+                    Some(other) => other,
+                    None => "Unknown",
+                }
+                .to_string();
+                Some(K8sResource {
+                    kind: ResourceKind::VirtualMachine,
+                    name: name.to_string(),
+                    namespace: Some(host.to_string()),
+                    status,
+                    labels: BTreeMap::from([(SOCKET_LABEL.to_string(), socket.to_string())]),
+                })
+            })
+            .collect())
+    }
+
+    /// Resume a paused VM or ask its guest to power off. A VM that is off
+    /// has no qemu to talk to, so can't be started; migration and labels
+    /// need a manager.
+    pub async fn run_action(&self, action: &VmAction, host: &str, name: &str) -> Result<String> {
+        let command = match action {
+            VmAction::Start => "cont",
+            VmAction::Stop => "system_powerdown",
+            VmAction::Migrate => bail!("Migration isn't supported by the QMP backend"),
+            VmAction::AddLabel { .. } => bail!("Labels aren't supported by the QMP backend"),
+        };
+        let vms = self.host_vms(host).await?;
+        let socket = vms
+            .iter()
+            .find(|vm| vm.name == name)
+            .and_then(|vm| vm.labels.get(SOCKET_LABEL))
+            .with_context(|| format!("No QMP socket for {} on {}", name, host))?;
+        self.run(host, &qmp_pipe(&shell_quote(socket), command)).await
+    }
+
+    /// `script` through `sh -c` here, or as the remote command of ssh
+    async fn run(&self, host: &str, script: &str) -> Result<String> {
+        let mut command = if host == "localhost" {
+            let mut command = Command::new("sh");
+            command.arg("-c");
+            command
+        } else {
+            let mut command = Command::new("ssh");
+            command.args(["-o", "BatchMode=yes", "-o", "ConnectTimeout=5"]);
+            command.args(&self.config.ssh_options);
+            command.arg(host);
+            command
+        };
+        let output = command
+            .arg(script)
+            .kill_on_drop(true)
+            .output()
+            .await
+            .with_context(|| format!("Failed to run a command on {}", host))?;
+        if !output.status.success() {
+            bail!("{}: {}", host, String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Shell pipeline sending the capabilities handshake and `command` to the
+/// (already quoted) socket
+fn qmp_pipe(socket: &str, command: &str) -> String {
+    format!(
+        "printf '{{\"execute\":\"qmp_capabilities\"}}\\n{{\"execute\":\"{}\"}}\\n' | socat -t 2 - UNIX-CONNECT:{}",
+        command, socket
+    )
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+use crate::types::RegistryStatus;
+use std::collections::HashMap;
+use std::process::Stdio;
+use tokio::process::Command;
+use tokio::time::Duration;
+
+/// Probes the configured container registries' `/v2/` endpoint, the one
+/// every registry answers (200, or 401 when it wants a token) before any
+/// pull can succeed
+pub struct RegistryCollector {
+    registries: Vec<String>,
+    timeout: Duration,
+    consecutive_failures: HashMap<String, u32>,
+}
+
+impl RegistryCollector {
+    pub fn new(registries: Vec<String>) -> Self {
+        Self {
+            registries,
+            timeout: Duration::from_millis(3000),
+            consecutive_failures: HashMap::new(),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout = Duration::from_millis(timeout_ms);
+        self
+    }
+
+    pub async fn collect(&mut self) -> Result<Vec<RegistryStatus>> {
+        let probes = self.registries.iter().map(|registry| probe(registry, self.timeout));
+        let results = futures::future::join_all(probes).await;
+
+        let mut statuses = Vec::new();
+        for (registry, result) in self.registries.iter().zip(results) {
+            let failures = self.consecutive_failures.entry(registry.clone()).or_insert(0);
+            let status = match result {
+                Ok((code, latency_ms)) if code < 500 => {
+                    *failures = 0;
+                    RegistryStatus {
+                        registry: registry.clone(),
+                        reachable: true,
+                        latency_ms: Some(latency_ms),
+                        consecutive_failures: 0,
+                        last_error: None,
+                    }
+                }
+                Ok((code, latency_ms)) => {
+                    *failures += 1;
+                    RegistryStatus {
+                        registry: registry.clone(),
+                        reachable: false,
+                        latency_ms: Some(latency_ms),
+                        consecutive_failures: *failures,
+                        last_error: Some(format!("HTTP {}", code)),
+                    }
+                }
+                Err(e) => {
+                    *failures += 1;
+                    RegistryStatus {
+                        registry: registry.clone(),
+                        reachable: false,
+                        latency_ms: None,
+                        consecutive_failures: *failures,
+                        last_error: Some(e),
+                    }
+                }
+            };
+            statuses.push(status);
+        }
+
+        Ok(statuses)
+    }
+}
+
+/// GET `<registry>/v2/`, returning the HTTP status and total time in ms.
+/// Plain `host[:port]` entries are probed over HTTPS.
+async fn probe(registry: &str, timeout: Duration) -> Result<(u16, f64), String> {
+    let base = if registry.contains("://") {
+        registry.trim_end_matches('/').to_string()
+    } else {
+        format!("https://{}", registry.trim_end_matches('/'))
+    };
+    let output = Command::new("curl")
+        .args(["-sS", "-o", "/dev/null", "-w", "%{http_code} %{time_total}", "--max-time"])
+        .arg(format!("{:.1}", timeout.as_secs_f64()))
+        .arg(format!("{}/v2/", base))
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error = stderr.trim().trim_start_matches("curl: ");
+        return Err(if error.is_empty() { format!("curl exited with {}", output.status) } else { error.to_string() });
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut fields = stdout.split_whitespace();
+    let code = fields.next().and_then(|c| c.parse::<u16>().ok()).ok_or("No HTTP status from curl")?;
+    let seconds = fields.next().and_then(|t| t.parse::<f64>().ok()).unwrap_or(0.0);
+    Ok((code, seconds * 1000.0))
+}
@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use crate::types::{GatewayStatus, RouteEntry, RouteStatus};
+use chrono::Local;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use tokio::process::Command;
+use tokio::time::{timeout, Duration, Instant};
+
+const RECENT_CHANGES: usize = 20; // Route additions and removals kept for display
+
+/// Reads the main routing table of both families every cycle, pings the
+/// default gateways and counts how often the default routes change, since
+/// a default route deleted by a restarting CNI strands the host
+pub struct RouteCollector {
+    timeout: Duration,
+    flap_window: Duration,
+    previous: Option<BTreeSet<String>>,
+    previous_defaults: Option<BTreeSet<String>>,
+    had_default: bool,
+    default_changes: VecDeque<Instant>,
+    recent_changes: VecDeque<(chrono::DateTime<Local>, String)>,
+    consecutive_failures: HashMap<String, u32>,
+}
+
+impl RouteCollector {
+    pub fn new() -> Self {
+        Self {
+            timeout: Duration::from_millis(1000),
+            flap_window: Duration::from_secs(600),
+            previous: None,
+            previous_defaults: None,
+            had_default: false,
+            default_changes: VecDeque::new(),
+            recent_changes: VecDeque::new(),
+            consecutive_failures: HashMap::new(),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout = Duration::from_millis(timeout_ms);
+        self
+    }
+
+    pub fn with_flap_window(mut self, minutes: u64) -> Self {
+        self.flap_window = Duration::from_secs(minutes * 60);
+        self
+    }
+
+    pub async fn collect(&mut self) -> Result<RouteStatus> {
+        let (v4, v6) = tokio::join!(read_routes(&[]), read_routes(&["-6"]));
+        let mut routes = v4?;
+        // Hosts without IPv6 still have the v4 table
+        routes.extend(v6.unwrap_or_default().into_iter().filter(|route| !route.destination.starts_with("fe80:")));
+        self.track_changes(&routes);
+
+        let defaults: Vec<&RouteEntry> = routes.iter().filter(|route| route.is_default()).collect();
+        let targets: Vec<(String, String)> = defaults
+            .iter()
+            .filter_map(|route| Some((route.gateway.clone()?, route.device.clone())))
+            .collect();
+        let probes = targets.iter().map(|(address, _)| ping(address, self.timeout));
+        let results = futures::future::join_all(probes).await;
+
+        let mut gateways = Vec::new();
+        for ((address, device), result) in targets.into_iter().zip(results) {
+            let failures = self.consecutive_failures.entry(address.clone()).or_insert(0);
+            let (latency_ms, last_error) = match result {
+                Ok(Some(latency_ms)) => {
+                    *failures = 0;
+                    (Some(latency_ms), None)
+                }
+                // Without ping the gateway is listed but not judged
+                Ok(None) => (None, Some("ping not installed".to_string())),
+                Err(e) => {
+                    *failures += 1;
+                    (None, Some(e))
+                }
+            };
+            gateways.push(GatewayStatus {
+                address,
+                device,
+                latency_ms,
+                consecutive_failures: *failures,
+                last_error,
+            });
+        }
+        self.consecutive_failures
+            .retain(|address, _| gateways.iter().any(|gateway| &gateway.address == address));
+
+        Ok(RouteStatus {
+            routes,
+            gateways,
+            had_default: self.had_default,
+            default_changes: self.default_changes.len() as u32,
+            recent_changes: self.recent_changes.iter().cloned().collect(),
+        })
+    }
+
+    /// Record routes that appeared or went away since the last read, and
+    /// changes to the set of default routes within the flap window
+    fn track_changes(&mut self, routes: &[RouteEntry]) {
+        let current: BTreeSet<String> = routes.iter().map(RouteEntry::describe).collect();
+        let defaults: BTreeSet<String> =
+            routes.iter().filter(|route| route.is_default()).map(RouteEntry::describe).collect();
+        let now = Instant::now();
+
+        if let Some(ref previous) = self.previous {
+            let now = Local::now();
+            let removed = previous.difference(&current).map(|route| format!("- {}", route));
+            let added = current.difference(previous).map(|route| format!("+ {}", route));
+            for change in removed.chain(added) {
+                self.recent_changes.push_front((now, change));
+            }
+            self.recent_changes.truncate(RECENT_CHANGES);
+        }
+        if self.previous_defaults.as_ref().is_some_and(|previous| previous != &defaults) {
+            self.default_changes.push_back(now);
+        }
+        while self.default_changes.front().is_some_and(|at| now.duration_since(*at) > self.flap_window) {
+            self.default_changes.pop_front();
+        }
+
+        self.had_default |= !defaults.is_empty();
+        self.previous = Some(current);
+        self.previous_defaults = Some(defaults);
+    }
+}
+
+/// The main table of one address family
+async fn read_routes(family: &[&str]) -> Result<Vec<RouteEntry>> {
+    let output = Command::new("ip")
+        .args(family)
+        .args(["-j", "route", "show", "table", "main"])
+        .output()
+        .await
+        .context("Failed to execute ip route")?;
+    if !output.status.success() {
+        anyhow::bail!("ip route failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    let json: Value = serde_json::from_slice(&output.stdout).context("Failed to parse ip route output")?;
+    Ok(json.as_array().map(|routes| routes.iter().filter_map(parse_route).collect()).unwrap_or_default())
+}
+
+fn parse_route(route: &Value) -> Option<RouteEntry> {
+    let text = |key: &str| route[key].as_str().map(str::to_string);
+    Some(RouteEntry {
+        destination: text("dst")?,
+        gateway: text("gateway"),
+        device: text("dev").unwrap_or_else(|| "-".to_string()),
+        protocol: text("protocol"),
+        metric: route["metric"].as_u64().map(|metric| metric as u32),
+        source: text("prefsrc"),
+    })
+}
+
+/// Round trip of one ICMP echo to `address`, in milliseconds; None when
+/// there is no ping binary to send it
+async fn ping(address: &str, limit: Duration) -> Result<Option<f64>, String> {
+    let wait_secs = limit.as_secs().max(1).to_string();
+    let command = Command::new("ping")
+        .args(["-n", "-c", "1", "-W", &wait_secs, address])
+        .kill_on_drop(true)
+        .output();
+    // ping's own -W only has second resolution
+    let output = match timeout(limit + Duration::from_millis(500), command).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Ok(Err(e)) => return Err(format!("Failed to run ping: {}", e)),
+        Err(_) => return Err("timed out".to_string()),
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        return Err(stdout
+            .lines()
+            .chain(String::from_utf8_lossy(&output.stderr).lines())
+            .find(|line| line.contains("Unreachable") || line.contains("ping:"))
+            .map(|line| line.trim().to_string())
+            .unwrap_or_else(|| "no reply".to_string()));
+    }
+    stdout
+        .split("time=")
+        .nth(1)
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|ms| ms.parse().ok())
+        .map(Some)
+        .ok_or_else(|| "no reply".to_string())
+}
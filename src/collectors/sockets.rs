@@ -0,0 +1,215 @@
+use anyhow::{Context, Result};
+use crate::config::WatchedPort;
+use crate::types::{FdUsage, PortStatus, SocketUsage};
+use std::collections::HashSet;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// TCP states in /proc/net/tcp: a listener holds no ephemeral port
+const TCP_ESTABLISHED: &str = "01";
+const TCP_LISTEN: &str = "0A";
+const TCP_TIME_WAIT: &str = "06";
+/// Longest process name the kernel keeps in /proc/<pid>/comm
+const COMM_LEN: usize = 15;
+
+/// File descriptor use of the whole system and of watched processes, how
+/// much of the ephemeral port range is taken, so "too many open files" and
+/// failed outbound connects can be seen coming, and who listens on the
+/// ports of important services
+pub struct SocketCollector {
+    processes: Vec<String>,
+    ports: Vec<WatchedPort>,
+    expected_listeners: Vec<u16>,
+    /// Names of watched ports seen listening, to tell when one stops
+    listened: HashSet<String>,
+}
+
+impl SocketCollector {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            processes: Vec::new(),
+            ports: Vec::new(),
+            expected_listeners: Vec::new(),
+            listened: HashSet::new(),
+        })
+    }
+
+    pub fn with_processes(mut self, processes: Vec<String>) -> Self {
+        self.processes = processes;
+        self
+    }
+
+    pub fn with_ports(mut self, ports: Vec<WatchedPort>, expected_listeners: Vec<u16>) -> Self {
+        self.ports = ports;
+        self.expected_listeners = expected_listeners;
+        self
+    }
+
+    pub async fn collect(&mut self) -> Result<SocketUsage> {
+        // "allocated  unused  max"
+        let file_nr = fs::read_to_string("/proc/sys/fs/file-nr").context("Failed to read /proc/sys/fs/file-nr")?;
+        let mut fields = file_nr.split_whitespace().map(|f| f.parse::<u64>().unwrap_or(0));
+        let fds_allocated = fields.next().unwrap_or(0);
+        let fds_max = fields.nth(1).unwrap_or(0);
+
+        let range = fs::read_to_string("/proc/sys/net/ipv4/ip_local_port_range")
+            .context("Failed to read ip_local_port_range")?;
+        let mut bounds = range.split_whitespace().filter_map(|p| p.parse::<u16>().ok());
+        let ephemeral_range = (bounds.next().unwrap_or(32768), bounds.next().unwrap_or(60999));
+
+        let mut ports = HashSet::new();
+        let mut time_wait = 0;
+        let mut listeners = Vec::new();
+        let mut established = Vec::new();
+        for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+            let Ok(contents) = fs::read_to_string(table) else {
+                continue;
+            };
+            for line in contents.lines().skip(1) {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let (Some(local), Some(&state)) = (fields.get(1), fields.get(3)) else {
+                    continue;
+                };
+                if state == TCP_LISTEN {
+                    listeners.extend(parse_proc_address(local));
+                    continue;
+                }
+                if state == TCP_ESTABLISHED {
+                    established.extend(parse_proc_address(local).map(|address| address.port()));
+                }
+                if state == TCP_TIME_WAIT {
+                    time_wait += 1;
+                }
+                let port = local.rsplit(':').next().and_then(|p| u16::from_str_radix(p, 16).ok());
+                if let Some(port) = port.filter(|p| (ephemeral_range.0..=ephemeral_range.1).contains(p)) {
+                    ports.insert(port);
+                }
+            }
+        }
+
+        let mut processes = self.watched_processes();
+        processes.sort_by(|a, b| b.percent().unwrap_or(0.0).total_cmp(&a.percent().unwrap_or(0.0)));
+        listeners.sort();
+        listeners.dedup();
+        let (watched_ports, unexpected_listeners) = self.port_status(&listeners, &established);
+
+        Ok(SocketUsage {
+            fds_allocated,
+            fds_max,
+            ephemeral_in_use: ports.len() as u32,
+            ephemeral_range,
+            time_wait,
+            processes,
+            ports: watched_ports,
+            unexpected_listeners,
+        })
+    }
+
+    /// Listeners and connections of each watched port, and the listeners on
+    /// non-loopback addresses nobody expects
+    fn port_status(&mut self, listeners: &[SocketAddr], established: &[u16]) -> (Vec<PortStatus>, Vec<String>) {
+        let mut statuses = Vec::new();
+        for watched in &self.ports {
+            let listening: Vec<String> = listeners
+                .iter()
+                .filter(|address| watched.contains(address.port()))
+                .map(|address| address.to_string())
+                .collect();
+            let stopped = if listening.is_empty() {
+                self.listened.contains(&watched.name)
+            } else {
+                self.listened.insert(watched.name.clone());
+                false
+            };
+            statuses.push(PortStatus {
+                name: watched.name.clone(),
+                ports: watched.label(),
+                required: watched.required,
+                connections: established.iter().filter(|port| watched.contains(**port)).count() as u32,
+                listeners: listening,
+                stopped,
+            });
+        }
+
+        let unexpected = listeners
+            .iter()
+            .filter(|address| !address.ip().is_loopback() && !is_mapped_loopback(address.ip()))
+            .filter(|address| {
+                !self.expected_listeners.contains(&address.port())
+                    && !self.ports.iter().any(|watched| watched.contains(address.port()))
+            })
+            .map(|address| address.to_string())
+            .collect();
+        (statuses, unexpected)
+    }
+
+    /// Open fds and the soft limit of every process whose name is watched.
+    /// Other users' processes are skipped unless running as root.
+    fn watched_processes(&self) -> Vec<FdUsage> {
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return Vec::new();
+        };
+        let mut usage = Vec::new();
+        for entry in entries.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|p| p.parse::<u32>().ok()) else {
+                continue;
+            };
+            let Ok(comm) = fs::read_to_string(entry.path().join("comm")) else {
+                continue;
+            };
+            let comm = comm.trim();
+            // comm is cut at 15 characters ("qemu-system-x86")
+            let watched = self
+                .processes
+                .iter()
+                .any(|name| name == comm || (comm.len() == COMM_LEN && name.starts_with(comm)));
+            if !watched {
+                continue;
+            }
+            let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+                continue;
+            };
+            usage.push(FdUsage {
+                pid,
+                name: comm.to_string(),
+                open: fds.count() as u64,
+                limit: fs::read_to_string(entry.path().join("limits")).ok().and_then(|l| open_files_limit(&l)),
+            });
+        }
+        usage
+    }
+}
+
+/// Soft "Max open files" from /proc/<pid>/limits; None when unlimited
+fn open_files_limit(limits: &str) -> Option<u64> {
+    let line = limits.lines().find(|l| l.starts_with("Max open files"))?;
+    line.split_whitespace().nth(3)?.parse().ok()
+}
+
+/// "0100007F:1A0B" or the 32-digit IPv6 form from /proc/net/tcp{,6}. The
+/// kernel prints each 32-bit word of the address in host byte order.
+pub(super) fn parse_proc_address(field: &str) -> Option<SocketAddr> {
+    let (address, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let word = |hex: &str| u32::from_str_radix(hex, 16).ok().map(u32::to_ne_bytes);
+    let ip = match address.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(word(address)?)),
+        32 => {
+            let mut octets = [0u8; 16];
+            for (i, chunk) in octets.chunks_mut(4).enumerate() {
+                chunk.copy_from_slice(&word(address.get(i * 8..i * 8 + 8)?)?);
+            }
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+/// ::ffff:127.0.0.1 from a dual-stack socket
+fn is_mapped_loopback(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().is_some_and(|v4| v4.is_loopback()),
+        IpAddr::V4(_) => false,
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::{bail, Context, Result};
+use crate::types::{SriovNic, SriovVf};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const NET_ROOT: &str = "/sys/class/net";
+
+/// Where kubelet records which devices each device plugin handed to a pod
+const KUBELET_CHECKPOINT: &str = "/var/lib/kubelet/device-plugins/kubelet_internal_checkpoint";
+
+/// Finds SR-IOV capable NICs and their VFs, attributing VFs to pods through
+/// the kubelet device plugin checkpoint (the SR-IOV device plugin uses VF
+/// PCI addresses as device IDs). Pod names are filled in by the caller.
+pub struct SriovCollector {
+    use_mock: bool,
+}
+
+impl SriovCollector {
+    pub fn new() -> Result<Self> {
+        Ok(Self { use_mock: false })
+    }
+
+    pub async fn collect(&mut self) -> Result<Vec<SriovNic>> {
+        if self.use_mock {
+            return Ok(self.collect_mock());
+        }
+        match self.collect_real() {
+            Ok(nics) => Ok(nics),
+            Err(e) => {
+                tracing::warn!("Failed to scan SR-IOV NICs, using mock data: {}", e);
+                self.use_mock = true;
+                Ok(self.collect_mock())
+            }
+        }
+    }
+
+    fn collect_real(&self) -> Result<Vec<SriovNic>> {
+        if !Path::new(NET_ROOT).is_dir() {
+            bail!("{} not present", NET_ROOT);
+        }
+        // No checkpoint (no kubelet, or not readable) just means no attribution
+        let allocations = fs::read_to_string(KUBELET_CHECKPOINT)
+            .ok()
+            .map(|json| device_allocations(&json))
+            .unwrap_or_default();
+
+        let mut nics = Vec::new();
+        for entry in fs::read_dir(NET_ROOT).context("Failed to read /sys/class/net")? {
+            let entry = entry?;
+            let device = entry.path().join("device");
+            let Some(total_vfs) = read_u32(&device.join("sriov_totalvfs")).filter(|n| *n > 0) else {
+                continue;
+            };
+            let num_vfs = read_u32(&device.join("sriov_numvfs")).unwrap_or(0);
+
+            let vfs = (0..num_vfs)
+                .filter_map(|index| {
+                    let vf_device = fs::canonicalize(device.join(format!("virtfn{}", index))).ok()?;
+                    let pci_address = file_name(&vf_device)?;
+                    let driver = fs::read_link(vf_device.join("driver")).ok().and_then(|d| file_name(&d));
+                    let pod_uid = allocations.get(&pci_address).cloned();
+                    Some(SriovVf { index, pci_address, driver, pod_uid, pod: None })
+                })
+                .collect();
+
+            nics.push(SriovNic {
+                name: entry.file_name().to_string_lossy().to_string(),
+                pci_address: fs::canonicalize(&device).ok().and_then(|d| file_name(&d)).unwrap_or_default(),
+                total_vfs,
+                vfs,
+            });
+        }
+
+        nics.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(nics)
+    }
+
+    fn collect_mock(&self) -> Vec<SriovNic> {
+        // UIDs of the mock virt-launcher pods
+        let (webserver, database, ci_runner) = (
+            "4f1c2a7e-93b1-4c0e-8d2a-1b6f0e4a9c11",
+            "9b07de14-5a2f-4e6b-b1c3-7d8e2f0a6b22",
+            "c3d9a1f0-2e7b-4a58-9f61-0c4b8e7d5a33",
+        );
+        let vf = |slot: u32, index: u32, pod_uid: Option<&str>| SriovVf {
+            index,
+            pci_address: format!("0000:3b:{:02x}.{}", slot + index / 8, index % 8),
+            driver: Some("vfio-pci".to_string()),
+            pod_uid: pod_uid.map(str::to_string),
+            pod: None,
+        };
+        vec![
+            SriovNic {
+                name: "ens1f0".to_string(),
+                pci_address: "0000:3b:00.0".to_string(),
+                total_vfs: 64,
+                vfs: vec![
+                    vf(0x02, 0, Some(webserver)),
+                    vf(0x02, 1, Some(database)),
+                    vf(0x02, 2, Some(ci_runner)),
+                    vf(0x02, 3, Some(ci_runner)),
+                ],
+            },
+            SriovNic {
+                name: "ens1f1".to_string(),
+                pci_address: "0000:3b:00.1".to_string(),
+                total_vfs: 64,
+                vfs: (0..8).map(|i| vf(0x0a, i, (i < 2).then_some(database))).collect(),
+            },
+        ]
+    }
+}
+
+/// Device ID -> pod UID from the kubelet checkpoint. `DeviceIDs` is a list
+/// in older kubelets and a NUMA node -> list map since 1.20.
+fn device_allocations(checkpoint: &str) -> HashMap<String, String> {
+    let Ok(json) = serde_json::from_str::<Value>(checkpoint) else {
+        return HashMap::new();
+    };
+    let mut allocations = HashMap::new();
+    let entries = json["Data"]["PodDeviceEntries"].as_array().cloned().unwrap_or_default();
+    for entry in entries {
+        let Some(uid) = entry["PodUID"].as_str() else {
+            continue;
+        };
+        let ids: Vec<&Value> = match entry["DeviceIDs"] {
+            Value::Array(ref ids) => ids.iter().collect(),
+            Value::Object(ref numa) => numa.values().filter_map(Value::as_array).flatten().collect(),
+            _ => Vec::new(),
+        };
+        for id in ids.into_iter().filter_map(Value::as_str) {
+            allocations.insert(id.to_string(), uid.to_string());
+        }
+    }
+    allocations
+}
+
+fn read_u32(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn file_name(path: &Path) -> Option<String> {
+    path.file_name().map(|n| n.to_string_lossy().to_string())
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use crate::types::{Sysctl, SysctlExpectation};
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+const PROC_SYS: &str = "/proc/sys";
+
+/// Current values of the configured sysctls from /proc/sys, with their
+/// expected values
+pub struct SysctlCollector {
+    /// Name and expected value, in display order
+    sysctls: Vec<(String, Option<SysctlExpectation>)>,
+    use_mock: bool,
+}
+
+impl SysctlCollector {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            sysctls: Vec::new(),
+            use_mock: false,
+        })
+    }
+
+    /// Sysctls to read and what they should be; expectations that don't
+    /// parse are reported by config validation and shown without one
+    pub fn with_sysctls(mut self, names: Vec<String>, expected: &BTreeMap<String, String>) -> Self {
+        self.sysctls = names
+            .into_iter()
+            .map(|name| {
+                let expectation = expected.get(&name).and_then(|e| SysctlExpectation::parse(e).ok());
+                (name, expectation)
+            })
+            .collect();
+        self
+    }
+
+    pub async fn collect(&mut self) -> Result<Vec<Sysctl>> {
+        if !self.use_mock && !Path::new(PROC_SYS).is_dir() {
+            tracing::warn!("No {}, using mock sysctl values", PROC_SYS);
+            self.use_mock = true;
+        }
+        if self.use_mock {
+            return Ok(self.collect_mock());
+        }
+
+        let mut sysctls = Vec::with_capacity(self.sysctls.len());
+        for (name, expected) in &self.sysctls {
+            let value = match tokio::fs::read_to_string(sysctl_path(name)).await {
+                Ok(value) => Some(value.trim().to_string()),
+                Err(e) if e.kind() == ErrorKind::NotFound => None,
+                Err(e) => {
+                    tracing::debug!("Failed to read sysctl {}: {}", name, e);
+                    None
+                }
+            };
+            sysctls.push(Sysctl { name: name.clone(), value, expected: expected.clone() });
+        }
+        Ok(sysctls)
+    }
+
+    fn collect_mock(&self) -> Vec<Sysctl> {
+        self.sysctls
+            .iter()
+            .map(|(name, expected)| {
+                let value = match name.as_str() {
+                    "vm.nr_hugepages" => "1024",
+                    "vm.swappiness" => "60",
+                    "vm.overcommit_memory" => "1",
+                    "net.ipv4.ip_forward" => "1",
+                    "fs.inotify.max_user_instances" => "128",
+                    "fs.inotify.max_user_watches" => "1048576",
+                    // br_netfilter not loaded
+                    n if n.starts_with("net.bridge.") => return Sysctl { name: name.clone(), value: None, expected: expected.clone() },
+                    _ => "0",
+                };
+                Sysctl { name: name.clone(), value: Some(value.to_string()), expected: expected.clone() }
+            })
+            .collect()
+    }
+}
+
+/// /proc/sys path of a dotted sysctl name. Dots separate directories;
+/// names with a slash are taken as paths already (e.g. interface names
+/// containing dots: "net/ipv4/conf/eth0.100/forwarding").
+pub fn sysctl_path(name: &str) -> PathBuf {
+    let relative = if name.contains('/') { name.to_string() } else { name.replace('.', "/") };
+    Path::new(PROC_SYS).join(relative)
+}
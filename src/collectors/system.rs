@@ -1,15 +1,27 @@
 use anyhow::Result;
-use crate::types::SystemMetrics;
-use sysinfo::System;
+use crate::types::{FilesystemUsage, SensorReading, SystemMetrics};
+use sysinfo::{Components, Disks, System};
+
+/// Filesystem types that never fill with data worth forecasting
+const PSEUDO_FILESYSTEMS: &[&str] = &["tmpfs", "devtmpfs", "overlay", "squashfs", "ramfs", "efivarfs"];
 
 pub struct SystemCollector {
     sys: System,
+    components: Components,
+    /// Mount points to track; empty tracks every disk-backed filesystem
+    filesystems: Vec<String>,
 }
 
 impl SystemCollector {
     pub fn new() -> Result<Self> {
         let sys = System::new_all();
-        Ok(Self { sys })
+        let components = Components::new_with_refreshed_list();
+        Ok(Self { sys, components, filesystems: Vec::new() })
+    }
+
+    pub fn with_filesystems(mut self, mount_points: Vec<String>) -> Self {
+        self.filesystems = mount_points;
+        self
     }
 
     pub async fn collect(&mut self) -> Result<SystemMetrics> {
@@ -25,7 +37,7 @@ impl SystemCollector {
 
         // Disk information (simplified - just root partition)
         // Note: In sysinfo 0.30+, disks are handled separately via Disks type
-        let disks = sysinfo::Disks::new_with_refreshed_list();
+        let disks = Disks::new_with_refreshed_list();
         let (disk_read, disk_write, disk_usage) = if let Some(disk) = disks.first() {
             let total = disk.total_space() as f64;
             let available = disk.available_space() as f64;
@@ -38,6 +50,18 @@ impl SystemCollector {
         // Load average
         let load_avg = System::load_average().one;
 
+        // Temperature sensors
+        self.components.refresh();
+        let temperatures = self
+            .components
+            .iter()
+            .map(|c| SensorReading {
+                label: c.label().to_string(),
+                celsius: c.temperature(),
+                critical: c.critical(),
+            })
+            .collect();
+
         Ok(SystemMetrics {
             cpu_usage,
             memory_used_gb: used_memory,
@@ -47,6 +71,36 @@ impl SystemCollector {
             disk_usage_percent: disk_usage,
             load_avg,
             uptime_seconds: 0, // Would need to parse from /proc/uptime
+            temperatures,
+            filesystems: self.filesystems(&disks),
         })
     }
+
+    /// The configured mounts, or every disk filesystem once, since bind
+    /// mounts (NixOS's /nix/store) show the same device again
+    fn filesystems(&self, disks: &Disks) -> Vec<FilesystemUsage> {
+        let mut usage: Vec<FilesystemUsage> = Vec::new();
+        for disk in disks.list() {
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+            let device = disk.name().to_string_lossy().to_string();
+            let fs_type = disk.file_system().to_string_lossy().to_string();
+            let wanted = if self.filesystems.is_empty() {
+                !PSEUDO_FILESYSTEMS.contains(&fs_type.as_str())
+                    && disk.total_space() > 0
+                    && !usage.iter().any(|fs| fs.device == device)
+            } else {
+                self.filesystems.contains(&mount_point)
+            };
+            if wanted {
+                usage.push(FilesystemUsage {
+                    mount_point,
+                    device,
+                    total_bytes: disk.total_space(),
+                    available_bytes: disk.available_space(),
+                });
+            }
+        }
+        usage.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+        usage
+    }
 }
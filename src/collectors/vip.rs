@@ -0,0 +1,79 @@
+use anyhow::Result;
+use crate::types::{ServiceEndpoints, VipStatus};
+use std::collections::HashMap;
+use std::time::Instant;
+use tokio::net::TcpStream;
+use tokio::time::{timeout, Duration};
+
+/// Opens a TCP connection to each VIP of the configured Services
+/// ("namespace/name"), catching a LoadBalancer or NodePort that stopped
+/// answering while its endpoints still look ready
+pub struct VipCollector {
+    services: Vec<String>,
+    timeout: Duration,
+    consecutive_failures: HashMap<String, u32>,
+}
+
+impl VipCollector {
+    pub fn new(services: Vec<String>) -> Self {
+        Self {
+            services,
+            timeout: Duration::from_millis(2000),
+            consecutive_failures: HashMap::new(),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout = Duration::from_millis(timeout_ms);
+        self
+    }
+
+    /// Probe the VIPs of the watched Services among `endpoints`, as last
+    /// listed by the API server
+    pub async fn collect(&mut self, endpoints: &[ServiceEndpoints]) -> Result<Vec<VipStatus>> {
+        let targets: Vec<(&ServiceEndpoints, &String)> = endpoints
+            .iter()
+            .filter(|svc| self.services.contains(&format!("{}/{}", svc.namespace, svc.name)))
+            .flat_map(|svc| svc.vips.iter().map(move |vip| (svc, vip)))
+            .collect();
+        let probes = targets.iter().map(|(_, address)| probe(address, self.timeout));
+        let results = futures::future::join_all(probes).await;
+
+        let mut statuses = Vec::new();
+        for ((svc, address), result) in targets.into_iter().zip(results) {
+            let failures = self.consecutive_failures.entry(address.clone()).or_insert(0);
+            let (latency_ms, last_error) = match result {
+                Ok(latency_ms) => {
+                    *failures = 0;
+                    (Some(latency_ms), None)
+                }
+                Err(e) => {
+                    *failures += 1;
+                    (None, Some(e))
+                }
+            };
+            statuses.push(VipStatus {
+                namespace: svc.namespace.clone(),
+                name: svc.name.clone(),
+                address: address.clone(),
+                reachable: last_error.is_none(),
+                latency_ms,
+                consecutive_failures: *failures,
+                last_error,
+                ready_endpoints: svc.ready,
+            });
+        }
+
+        Ok(statuses)
+    }
+}
+
+/// Time to complete a TCP handshake with `address`, in milliseconds
+async fn probe(address: &str, limit: Duration) -> Result<f64, String> {
+    let start = Instant::now();
+    match timeout(limit, TcpStream::connect(address)).await {
+        Ok(Ok(_)) => Ok(start.elapsed().as_secs_f64() * 1000.0),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err("timed out".to_string()),
+    }
+}
@@ -0,0 +1,48 @@
+use anyhow::Result;
+
+use super::proxmox::ProxmoxBackend;
+use super::qmp::QmpBackend;
+use crate::config::{VirtBackend, VirtualizationConfig};
+use crate::types::{K8sResource, KubeVirtInfo, VmAction};
+
+/// A VM manager other than KubeVirt. The Kubernetes collector hands VM
+/// listings, counts and start/stop/migrate to it, so the Resources screen,
+/// batch actions and the header work unchanged.
+#[derive(Clone)]
+pub enum ExternalVirt {
+    Proxmox(ProxmoxBackend),
+    Qmp(QmpBackend),
+}
+
+impl ExternalVirt {
+    /// None for KubeVirt
+    pub fn from_config(config: &VirtualizationConfig) -> Option<Self> {
+        match config.backend {
+            VirtBackend::KubeVirt => None,
+            VirtBackend::Proxmox => Some(ExternalVirt::Proxmox(ProxmoxBackend::new(config.proxmox.clone()))),
+            VirtBackend::Qmp => Some(ExternalVirt::Qmp(QmpBackend::new(config.qmp.clone()))),
+        }
+    }
+
+    pub async fn collect_info(&self) -> Result<KubeVirtInfo> {
+        match self {
+            ExternalVirt::Proxmox(backend) => backend.collect_info().await,
+            ExternalVirt::Qmp(backend) => backend.collect_info().await,
+        }
+    }
+
+    pub async fn collect_vms(&self) -> Result<Vec<K8sResource>> {
+        match self {
+            ExternalVirt::Proxmox(backend) => backend.collect_vms().await,
+            ExternalVirt::Qmp(backend) => backend.collect_vms().await,
+        }
+    }
+
+    /// `namespace` is the Proxmox node or the SSH host
+    pub async fn run_action(&self, action: &VmAction, namespace: &str, name: &str) -> Result<String> {
+        match self {
+            ExternalVirt::Proxmox(backend) => backend.run_action(action, namespace, name).await,
+            ExternalVirt::Qmp(backend) => backend.run_action(action, namespace, name).await,
+        }
+    }
+}
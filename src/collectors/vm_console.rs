@@ -0,0 +1,276 @@
+use super::kube_api::KubeApiLayer;
+use crate::config::ConsoleConfig;
+use crate::types::ConsoleCapture;
+use anyhow::{bail, Context, Result};
+use futures::StreamExt;
+use kube::api::{ApiResource, DynamicObject, GroupVersionKind};
+use kube::{Api, Client, ResourceExt};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::{self, protocol::Role, Message};
+use tokio_tungstenite::WebSocketStream;
+
+/// Subprotocol KubeVirt's console endpoint speaks: raw serial bytes both ways
+const CONSOLE_PROTOCOL: &str = "plain.kubevirt.io";
+/// How often `capture_all` looks for newly started VMs
+const DISCOVERY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Terminal control sequences (colors, cursor movement) a serial console
+/// carries but a log view can't render
+static ESCAPE_SEQUENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07]*\x07|\x1b[()][0-9A-Za-z]|\x1b.").unwrap());
+
+struct ConsoleBuffer {
+    lines: VecDeque<String>,
+    /// Output since the last newline
+    partial: String,
+    connected: bool,
+    error: Option<String>,
+    version: u64,
+    task: Option<JoinHandle<()>>,
+}
+
+impl ConsoleBuffer {
+    fn new() -> Self {
+        Self {
+            lines: VecDeque::new(),
+            partial: String::new(),
+            connected: false,
+            error: None,
+            version: 0,
+            task: None,
+        }
+    }
+
+    fn append(&mut self, bytes: &[u8], limit: usize) {
+        let text = String::from_utf8_lossy(bytes);
+        let text = ESCAPE_SEQUENCE.replace_all(&text, "");
+        for c in text.chars() {
+            match c {
+                '\n' => {
+                    let line = std::mem::take(&mut self.partial);
+                    self.push_line(line, limit);
+                }
+                '\r' => {}
+                '\x08' => {
+                    self.partial.pop();
+                }
+                c if c.is_control() && c != '\t' => {}
+                c => self.partial.push(c),
+            }
+        }
+        self.version += 1;
+    }
+
+    fn push_line(&mut self, line: String, limit: usize) {
+        while self.lines.len() >= limit {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// Note a connect or disconnect in the output itself, so gaps in the
+    /// capture show where they happened
+    fn mark(&mut self, note: String, limit: usize) {
+        if !self.partial.is_empty() {
+            let line = std::mem::take(&mut self.partial);
+            self.push_line(line, limit);
+        }
+        self.push_line(format!("-- {} --", note), limit);
+        self.version += 1;
+    }
+
+    fn snapshot(&self) -> ConsoleCapture {
+        let mut lines: Vec<String> = self.lines.iter().cloned().collect();
+        if !self.partial.is_empty() {
+            lines.push(self.partial.clone());
+        }
+        ConsoleCapture {
+            lines,
+            connected: self.connected,
+            error: self.error.clone(),
+            version: self.version,
+        }
+    }
+}
+
+type Buffers = Arc<Mutex<HashMap<String, ConsoleBuffer>>>;
+
+/// Serial console output of VMs, captured by one background task per VM
+/// that stays attached to KubeVirt's console subresource and reconnects
+/// whenever the console closes. Output is there to read even if nobody was
+/// watching when the guest failed to boot.
+#[derive(Clone)]
+pub struct VmConsoles {
+    config: Arc<ConsoleConfig>,
+    buffers: Buffers,
+}
+
+impl VmConsoles {
+    pub fn new(config: ConsoleConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            buffers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn capture_all(&self) -> bool {
+        self.config.capture_all
+    }
+
+    /// Start capturing a VM's console unless it already is
+    pub fn capture(&self, client: &Client, namespace: &str, name: &str) {
+        let key = format!("{}/{}", namespace, name);
+        let mut buffers = self.buffers.lock().unwrap();
+        let buffer = buffers.entry(key.clone()).or_insert_with(ConsoleBuffer::new);
+        if buffer.task.as_ref().is_some_and(|task| !task.is_finished()) {
+            return;
+        }
+        let task = tokio::spawn(follow_console(
+            client.clone(),
+            namespace.to_string(),
+            name.to_string(),
+            key,
+            self.buffers.clone(),
+            self.config.clone(),
+        ));
+        buffer.task = Some(task);
+    }
+
+    /// What has been captured for a VM so far
+    pub fn snapshot(&self, namespace: &str, name: &str) -> Option<ConsoleCapture> {
+        let key = format!("{}/{}", namespace, name);
+        self.buffers.lock().unwrap().get(&key).map(ConsoleBuffer::snapshot)
+    }
+
+    /// Capture every running VMI, picking up new ones as they start and
+    /// dropping the buffers of VMIs that are gone
+    pub fn discover(&self, client: Client, api_layer: KubeApiLayer) {
+        let consoles = self.clone();
+        tokio::spawn(async move {
+            let gvk = GroupVersionKind::gvk("kubevirt.io", "v1", "VirtualMachineInstance");
+            let resource = ApiResource::from_gvk_with_plural(&gvk, "virtualmachineinstances");
+            let api: Api<DynamicObject> = Api::all_with(client.clone(), &resource);
+            loop {
+                match api_layer.list(&api, "virtualmachineinstances", &Default::default(), true).await {
+                    Ok(vmis) => {
+                        let mut present = HashSet::new();
+                        for vmi in vmis {
+                            let namespace = vmi.namespace().unwrap_or_default();
+                            present.insert(format!("{}/{}", namespace, vmi.name_any()));
+                            let phase = vmi.data.get("status").and_then(|s| s.get("phase")).and_then(|p| p.as_str());
+                            if phase == Some("Running") {
+                                consoles.capture(&client, &namespace, &vmi.name_any());
+                            }
+                        }
+                        consoles.buffers.lock().unwrap().retain(|key, buffer| {
+                            let keep = present.contains(key);
+                            if let (false, Some(task)) = (keep, buffer.task.as_ref()) {
+                                task.abort();
+                            }
+                            keep
+                        });
+                    }
+                    // KubeVirt not installed, or the apiserver is unreachable
+                    Err(e) => tracing::debug!("Failed to list VMIs for console capture: {}", e),
+                }
+                tokio::time::sleep(DISCOVERY_INTERVAL).await;
+            }
+        });
+    }
+}
+
+/// Stay attached to a VM's console, reconnecting after `retry_secs`
+/// whenever it closes, until the VM stops
+async fn follow_console(
+    client: Client,
+    namespace: String,
+    name: String,
+    key: String,
+    buffers: Buffers,
+    config: Arc<ConsoleConfig>,
+) {
+    let limit = config.buffer_lines.max(1);
+    let update = |f: &mut dyn FnMut(&mut ConsoleBuffer)| {
+        if let Some(buffer) = buffers.lock().unwrap().get_mut(&key) {
+            f(buffer);
+        }
+    };
+
+    loop {
+        let reason = match connect(&client, &namespace, &name).await {
+            Ok(mut stream) => {
+                update(&mut |buffer| {
+                    buffer.connected = true;
+                    buffer.error = None;
+                    buffer.mark("console attached".to_string(), limit);
+                });
+                let mut reason = "console closed".to_string();
+                while let Some(message) = stream.next().await {
+                    match message {
+                        Ok(Message::Binary(bytes)) => update(&mut |buffer| buffer.append(&bytes, limit)),
+                        Ok(Message::Text(text)) => update(&mut |buffer| buffer.append(text.as_bytes(), limit)),
+                        Ok(Message::Close(frame)) => {
+                            if let Some(frame) = frame.filter(|f| !f.reason.is_empty()) {
+                                reason = format!("console closed: {}", frame.reason);
+                            }
+                            break;
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            reason = format!("console read failed: {}", e);
+                            break;
+                        }
+                    }
+                }
+                update(&mut |buffer| buffer.mark(reason.clone(), limit));
+                reason
+            }
+            Err(e) => format!("{:#}", e),
+        };
+        update(&mut |buffer| {
+            buffer.connected = false;
+            buffer.error = Some(reason.clone());
+        });
+        tokio::time::sleep(Duration::from_secs(config.retry_secs.max(1))).await;
+    }
+}
+
+/// Open a WebSocket to the VMI's console subresource. kube's own `connect`
+/// insists on the exec/attach subprotocol, so the upgrade is done here.
+async fn connect(
+    client: &Client,
+    namespace: &str,
+    name: &str,
+) -> Result<WebSocketStream<hyper::upgrade::Upgraded>> {
+    use http::header;
+
+    let key = tungstenite::handshake::client::generate_key();
+    let uri = format!(
+        "/apis/subresources.kubevirt.io/v1/namespaces/{}/virtualmachineinstances/{}/console",
+        namespace, name
+    );
+    let request = http::Request::get(uri)
+        .header(header::CONNECTION, "Upgrade")
+        .header(header::UPGRADE, "websocket")
+        .header(header::SEC_WEBSOCKET_VERSION, "13")
+        .header(header::SEC_WEBSOCKET_KEY, &key)
+        .header(header::SEC_WEBSOCKET_PROTOCOL, CONSOLE_PROTOCOL)
+        .body(hyper::Body::empty())?;
+
+    let response = client
+        .send(request)
+        .await
+        .with_context(|| format!("Console of {}/{} unavailable", namespace, name))?;
+    if response.status() != http::StatusCode::SWITCHING_PROTOCOLS {
+        bail!("Console of {}/{} refused: {}", namespace, name, response.status());
+    }
+    let upgraded = hyper::upgrade::on(response)
+        .await
+        .context("Console connection upgrade failed")?;
+    Ok(WebSocketStream::from_raw_socket(upgraded, Role::Client, None).await)
+}
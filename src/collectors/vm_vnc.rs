@@ -0,0 +1,283 @@
+use super::sockets::parse_proc_address;
+use crate::config::ConsoleConfig;
+use crate::types::{ConsoleEndpoint, ConsoleKind, VncForward};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+use tokio::sync::oneshot;
+
+/// Where the kubelet keeps pod volumes; virt-launcher's "private" emptyDir
+/// holds a directory per VMI with qemu's VNC and serial sockets
+const KUBELET_PODS: &str = "/var/lib/kubelet/pods";
+const LAUNCHER_PRIVATE: &str = "volumes/kubernetes.io~empty-dir/private";
+const TCP_LISTEN: &str = "0A";
+/// Ports tried past `vnc_first_port` before giving up
+const PORT_SEARCH: u16 = 100;
+
+/// virtctl flags that take a separate value, to tell them from the
+/// subcommand's positional arguments
+const VALUE_FLAGS: [&str; 8] = ["-n", "--namespace", "--port", "--address", "--kubeconfig", "--context", "--server", "--user"];
+
+/// Console sockets of a VM's virt-launcher pods, and the ports virtctl
+/// processes on this host proxy its VNC display or ports on
+pub fn console_endpoints(launchers: &[(String, String)], namespace: &str, name: &str) -> Vec<ConsoleEndpoint> {
+    let mut endpoints = Vec::new();
+    for (uid, pod) in launchers {
+        let private = Path::new(KUBELET_PODS).join(uid).join(LAUNCHER_PRIVATE);
+        // One directory per VMI the pod ran
+        for vmi in fs::read_dir(&private).into_iter().flatten().flatten() {
+            let mut sockets: Vec<PathBuf> = fs::read_dir(vmi.path())
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .collect();
+            sockets.sort();
+            for socket in sockets {
+                let file = socket.file_name().and_then(|f| f.to_str()).unwrap_or_default();
+                let kind = if file == "virt-vnc" {
+                    ConsoleKind::Vnc
+                } else if file.starts_with("virt-serial") && !file.ends_with("-log") {
+                    ConsoleKind::Serial
+                } else {
+                    continue;
+                };
+                endpoints.push(ConsoleEndpoint {
+                    kind,
+                    address: socket.display().to_string(),
+                    source: pod.clone(),
+                });
+            }
+        }
+    }
+    endpoints.extend(virtctl_listeners(namespace, name));
+    endpoints
+}
+
+/// Ports listened on by `virtctl vnc` and `virtctl port-forward` processes
+/// pointed at the VM
+fn virtctl_listeners(namespace: &str, name: &str) -> Vec<ConsoleEndpoint> {
+    let mut endpoints = Vec::new();
+    let mut listeners: Option<HashMap<u64, SocketAddr>> = None;
+    for entry in fs::read_dir("/proc").into_iter().flatten().flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|p| p.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(cmdline) = fs::read(entry.path().join("cmdline")) else {
+            continue;
+        };
+        let args: Vec<String> = cmdline
+            .split(|&b| b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect();
+        let is_virtctl = args
+            .first()
+            .and_then(|program| Path::new(program).file_name())
+            .is_some_and(|program| program == "virtctl" || program == "kubectl-virt");
+        if !is_virtctl {
+            continue;
+        }
+        let Some((kind, target)) = virtctl_target(&args[1..]) else {
+            continue;
+        };
+        if target != (namespace.to_string(), name.to_string()) {
+            continue;
+        }
+        let listeners = listeners.get_or_insert_with(tcp_listeners);
+        for inode in socket_inodes(pid) {
+            if let Some(address) = listeners.get(&inode) {
+                endpoints.push(ConsoleEndpoint {
+                    kind,
+                    address: address.to_string(),
+                    source: format!("virtctl (pid {})", pid),
+                });
+            }
+        }
+    }
+    endpoints
+}
+
+/// The console kind and (namespace, VM) of a virtctl command line.
+/// port-forward names its target "vm/name.namespace" or "vmi/name".
+fn virtctl_target(args: &[String]) -> Option<(ConsoleKind, (String, String))> {
+    let mut namespace = None;
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(value) = arg.strip_prefix("--namespace=").or_else(|| arg.strip_prefix("-n=")) {
+            namespace = Some(value.to_string());
+        } else if arg == "-n" || arg == "--namespace" {
+            namespace = args.next().cloned();
+        } else if VALUE_FLAGS.contains(&arg.as_str()) {
+            args.next();
+        } else if !arg.starts_with('-') {
+            positional.push(arg.as_str());
+        }
+    }
+    let (kind, target) = match positional.as_slice() {
+        ["vnc", target, ..] => (ConsoleKind::Vnc, target.to_string()),
+        ["port-forward", target, ..] => {
+            let target = target.split_once('/').map_or(*target, |(_, name)| name);
+            match target.split_once('.') {
+                Some((name, ns)) => {
+                    namespace = Some(ns.to_string());
+                    (ConsoleKind::PortForward, name.to_string())
+                }
+                None => (ConsoleKind::PortForward, target.to_string()),
+            }
+        }
+        _ => return None,
+    };
+    Some((kind, (namespace.unwrap_or_else(|| "default".to_string()), target)))
+}
+
+/// Listening TCP sockets by inode
+fn tcp_listeners() -> HashMap<u64, SocketAddr> {
+    let mut listeners = HashMap::new();
+    for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        let Ok(contents) = fs::read_to_string(table) else {
+            continue;
+        };
+        for line in contents.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (Some(local), Some(&TCP_LISTEN), Some(inode)) = (fields.get(1), fields.get(3), fields.get(9)) else {
+                continue;
+            };
+            if let (Some(address), Ok(inode)) = (parse_proc_address(local), inode.parse()) {
+                listeners.insert(inode, address);
+            }
+        }
+    }
+    listeners
+}
+
+/// Inodes of a process's open sockets, from its "socket:[1234]" fd links
+fn socket_inodes(pid: u32) -> HashSet<u64> {
+    fs::read_dir(format!("/proc/{}/fd", pid))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|fd| fs::read_link(fd.path()).ok())
+        .filter_map(|link| {
+            let link = link.to_str()?;
+            link.strip_prefix("socket:[")?.strip_suffix(']')?.parse().ok()
+        })
+        .collect()
+}
+
+struct ForwardState {
+    address: SocketAddr,
+    error: Option<String>,
+    running: bool,
+    /// Dropping it stops virtctl
+    _stop: oneshot::Sender<()>,
+}
+
+/// `virtctl vnc --proxy-only` port-forwards by VM, so a viewer on a
+/// workstation can reach a VM's display through an SSH tunnel to this host.
+/// They keep running after the detail popup closes and stop on exit.
+#[derive(Clone)]
+pub struct VncForwards {
+    config: Arc<ConsoleConfig>,
+    forwards: Arc<Mutex<HashMap<String, ForwardState>>>,
+}
+
+impl VncForwards {
+    pub fn new(config: ConsoleConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            forwards: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Start forwarding a VM's VNC display on the first free port, replacing
+    /// a forward that exited
+    pub fn start(&self, kubeconfig: Option<&Path>, namespace: &str, name: &str) -> Result<SocketAddr, String> {
+        let key = format!("{}/{}", namespace, name);
+        let mut forwards = self.forwards.lock().unwrap();
+        if let Some(forward) = forwards.get(&key).filter(|f| f.running) {
+            return Ok(forward.address);
+        }
+        let ip: IpAddr = self.config.vnc_address.parse().unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let first = self.config.vnc_first_port;
+        let address = (first..first.saturating_add(PORT_SEARCH))
+            .map(|port| SocketAddr::new(ip, port))
+            .find(|address| TcpListener::bind(address).is_ok())
+            .ok_or_else(|| format!("No free port from {} on {}", first, ip))?;
+
+        let mut command = Command::new("virtctl");
+        if let Some(path) = kubeconfig {
+            command.arg("--kubeconfig").arg(path);
+        }
+        let mut child = command
+            .args(["vnc", "--proxy-only", "--address", &ip.to_string(), "--port", &address.port().to_string()])
+            .args([name, "-n", namespace])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to run virtctl: {}", e))?;
+
+        let (stop, stopped) = oneshot::channel();
+        let forwards_handle = self.forwards.clone();
+        let task_key = key.clone();
+        let mut stderr = child.stderr.take();
+        tokio::spawn(async move {
+            let reason = tokio::select! {
+                // The forward was replaced or the app is exiting
+                _ = stopped => {
+                    let _ = child.kill().await;
+                    return;
+                }
+                status = child.wait() => {
+                    let mut output = String::new();
+                    if let Some(stderr) = stderr.as_mut() {
+                        let _ = stderr.read_to_string(&mut output).await;
+                    }
+                    match (status, output.lines().rev().find(|l| !l.trim().is_empty())) {
+                        (_, Some(line)) => line.trim().to_string(),
+                        (Ok(status), None) => format!("virtctl exited with {}", status),
+                        (Err(e), None) => format!("Failed to wait for virtctl: {}", e),
+                    }
+                }
+            };
+            if let Some(forward) = forwards_handle.lock().unwrap().get_mut(&task_key) {
+                forward.running = false;
+                forward.error = Some(reason);
+            }
+        });
+
+        forwards.insert(
+            key,
+            ForwardState {
+                address,
+                error: None,
+                running: true,
+                _stop: stop,
+            },
+        );
+        Ok(address)
+    }
+
+    /// Stop a VM's forward; false when there was none
+    pub fn stop(&self, namespace: &str, name: &str) -> bool {
+        let key = format!("{}/{}", namespace, name);
+        self.forwards.lock().unwrap().remove(&key).is_some()
+    }
+
+    pub fn status(&self, namespace: &str, name: &str) -> Option<VncForward> {
+        let key = format!("{}/{}", namespace, name);
+        self.forwards.lock().unwrap().get(&key).map(|forward| VncForward {
+            address: forward.address,
+            running: forward.running,
+            error: forward.error.clone(),
+        })
+    }
+}
@@ -0,0 +1,231 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::types::{NodeReachability, NodeStatus};
+
+/// Percentage points a usage figure has to move to count as changed
+const PERCENT_TOLERANCE: f64 = 5.0;
+/// Load average difference that counts as changed
+const LOAD_TOLERANCE: f64 = 1.0;
+
+/// One node's state at one moment, the unit the compare view puts side by
+/// side. Fields a source can't provide are None or empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeSnapshot {
+    pub node: String,
+    pub taken_at: DateTime<Local>,
+    /// Of the host for this node's own snapshots, of allocatable for others
+    pub cpu_percent: Option<f64>,
+    pub memory_percent: Option<f64>,
+    #[serde(default)]
+    pub load: Option<f64>,
+    #[serde(default)]
+    pub disk_percent: Option<f64>,
+    #[serde(default)]
+    pub pods: Option<u32>,
+    /// Running VMs as namespace/name
+    #[serde(default)]
+    pub vms: Vec<String>,
+    /// Conditions that are bad: NotReady, MemoryPressure, ...
+    #[serde(default)]
+    pub conditions: Vec<String>,
+    /// Titles of the alerts active on the node
+    #[serde(default)]
+    pub alerts: Vec<String>,
+    #[serde(default)]
+    pub kubelet_version: Option<String>,
+}
+
+impl NodeSnapshot {
+    /// A node of the fleet as the Kubernetes API reports it, with the
+    /// titles of the alerts active on it
+    pub fn from_node(node: &NodeStatus, alerts: Vec<String>) -> Self {
+        let mut conditions = node.pressures.clone();
+        if node.reachability != NodeReachability::Ready {
+            conditions.insert(0, node.reachability.label().to_string());
+        }
+        Self {
+            node: node.name.clone(),
+            taken_at: Local::now(),
+            cpu_percent: node.cpu_percent,
+            memory_percent: node.memory_percent,
+            load: None,
+            disk_percent: None,
+            pods: Some(node.pods),
+            vms: sorted(node.vms.clone()),
+            conditions,
+            alerts: sorted(alerts),
+            kubelet_version: Some(node.kubelet_version.clone()).filter(|v| !v.is_empty()),
+        }
+    }
+}
+
+/// Sorted and deduplicated, so sides compare item by item
+pub fn sorted(mut items: Vec<String>) -> Vec<String> {
+    items.sort();
+    items.dedup();
+    items
+}
+
+/// One figure of two snapshots, and whether it differs enough to highlight
+#[derive(Debug, Clone)]
+pub struct CompareRow {
+    pub label: &'static str,
+    pub left: String,
+    pub right: String,
+    pub changed: bool,
+}
+
+/// What the two sides of the compare view are
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareKind {
+    /// Two nodes of the fleet, now
+    Nodes,
+    /// This node at a stored snapshot (left) and now (right)
+    History,
+}
+
+/// Two snapshots shown side by side
+#[derive(Debug, Clone)]
+pub struct CompareView {
+    pub kind: CompareKind,
+    pub left: NodeSnapshot,
+    pub right: NodeSnapshot,
+}
+
+impl CompareView {
+    /// The scalar figures of both sides, changed ones flagged
+    pub fn rows(&self) -> Vec<CompareRow> {
+        let (left, right) = (&self.left, &self.right);
+        let percent = |value: Option<f64>| value.map(|v| format!("{:.1}%", v)).unwrap_or_else(|| "-".to_string());
+        let differs = |a: Option<f64>, b: Option<f64>, tolerance: f64| match (a, b) {
+            (Some(a), Some(b)) => (a - b).abs() >= tolerance,
+            _ => false,
+        };
+        vec![
+            CompareRow {
+                label: "CPU",
+                left: percent(left.cpu_percent),
+                right: percent(right.cpu_percent),
+                changed: differs(left.cpu_percent, right.cpu_percent, PERCENT_TOLERANCE),
+            },
+            CompareRow {
+                label: "Memory",
+                left: percent(left.memory_percent),
+                right: percent(right.memory_percent),
+                changed: differs(left.memory_percent, right.memory_percent, PERCENT_TOLERANCE),
+            },
+            CompareRow {
+                label: "Root disk",
+                left: percent(left.disk_percent),
+                right: percent(right.disk_percent),
+                changed: differs(left.disk_percent, right.disk_percent, PERCENT_TOLERANCE),
+            },
+            CompareRow {
+                label: "Load",
+                left: left.load.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
+                right: right.load.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
+                changed: differs(left.load, right.load, LOAD_TOLERANCE),
+            },
+            CompareRow {
+                label: "Pods",
+                left: left.pods.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string()),
+                right: right.pods.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string()),
+                changed: left.pods.is_some() && right.pods.is_some() && left.pods != right.pods,
+            },
+            CompareRow {
+                label: "VMs",
+                left: left.vms.len().to_string(),
+                right: right.vms.len().to_string(),
+                changed: left.vms != right.vms,
+            },
+            CompareRow {
+                label: "Kubelet",
+                left: left.kubelet_version.clone().unwrap_or_else(|| "-".to_string()),
+                right: right.kubelet_version.clone().unwrap_or_else(|| "-".to_string()),
+                changed: left.kubelet_version.is_some()
+                    && right.kubelet_version.is_some()
+                    && left.kubelet_version != right.kubelet_version,
+            },
+        ]
+    }
+}
+
+/// Snapshots of this node taken every interval and kept on disk, so "now
+/// vs. an hour ago" still works after a restart
+pub struct SnapshotHistory {
+    /// Oldest first
+    snapshots: VecDeque<NodeSnapshot>,
+    /// None when snapshots are disabled
+    interval: Option<chrono::Duration>,
+    retention: chrono::Duration,
+    path: PathBuf,
+}
+
+impl SnapshotHistory {
+    /// Load persisted snapshots, starting fresh if none exist or the file is unreadable
+    pub fn load(path: PathBuf, interval_mins: u64, retention_hours: u64) -> Self {
+        let snapshots = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring corrupt snapshot history {:?}: {}", path, e);
+                VecDeque::new()
+            }),
+            Err(_) => VecDeque::new(),
+        };
+        Self {
+            snapshots,
+            interval: (interval_mins > 0).then(|| chrono::Duration::minutes(interval_mins as i64)),
+            retention: chrono::Duration::hours(retention_hours as i64),
+            path,
+        }
+    }
+
+    /// Keep `snapshot` if an interval has passed since the last one kept
+    pub fn record(&mut self, snapshot: NodeSnapshot) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        if self.snapshots.back().is_some_and(|last| snapshot.taken_at - last.taken_at < interval) {
+            return;
+        }
+        let cutoff = snapshot.taken_at - self.retention;
+        self.snapshots.push_back(snapshot);
+        while self.snapshots.front().is_some_and(|oldest| oldest.taken_at < cutoff) {
+            self.snapshots.pop_front();
+        }
+        if let Err(e) = self.save() {
+            tracing::warn!("Failed to persist snapshot history: {:#}", e);
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create snapshot history directory: {:?}", parent))?;
+        }
+        let contents = serde_json::to_string(&self.snapshots).context("Failed to serialize snapshot history")?;
+        fs::write(&self.path, contents).with_context(|| format!("Failed to write snapshot history: {:?}", self.path))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.interval.is_some()
+    }
+
+    /// The latest snapshot taken at or before `at`, else the oldest one kept
+    pub fn at(&self, at: DateTime<Local>) -> Option<&NodeSnapshot> {
+        self.snapshots.iter().rev().find(|s| s.taken_at <= at).or_else(|| self.snapshots.front())
+    }
+
+    /// The snapshot just before (`earlier`) or after the one taken at `at`
+    pub fn step(&self, at: DateTime<Local>, earlier: bool) -> Option<&NodeSnapshot> {
+        if earlier {
+            self.snapshots.iter().rev().find(|s| s.taken_at < at)
+        } else {
+            self.snapshots.iter().find(|s| s.taken_at > at)
+        }
+    }
+}
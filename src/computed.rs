@@ -0,0 +1,344 @@
+use std::collections::BTreeMap;
+
+use crate::config::ComputedMetricConfig;
+
+/// Collected values an expression can refer to, with what they are
+pub const VARIABLES: [(&str, &str); 17] = [
+    ("cpu_usage", "host CPU usage, percent"),
+    ("memory_used_gb", "host memory in use, GB"),
+    ("memory_total_gb", "host memory, GB"),
+    ("memory_percent", "host memory in use, percent"),
+    ("disk_usage_percent", "root filesystem usage, percent"),
+    ("disk_read_mb_s", "disk reads, MB/s"),
+    ("disk_write_mb_s", "disk writes, MB/s"),
+    ("load_avg", "1-minute load average"),
+    ("uptime_hours", "host uptime, hours"),
+    ("package_watts", "CPU package power, W (RAPL hosts only)"),
+    ("nodes_ready", "cluster nodes Ready"),
+    ("nodes_total", "cluster nodes"),
+    ("pods_running", "pods Running in the cluster"),
+    ("services", "Services in the cluster"),
+    ("vms_running", "VMs running"),
+    ("vms_stopped", "VMs stopped"),
+    ("vms_migrating", "VMs migrating"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// An arithmetic expression over collected values: numbers, variable
+/// names, + - * /, unary minus and parentheses
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Var(String),
+    Neg(Box<Expr>),
+    Binary(Box<Expr>, Op, Box<Expr>),
+}
+
+impl Expr {
+    pub fn parse(text: &str) -> Result<Expr, String> {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser { tokens: &tokens, pos: 0, depth: 0 };
+        let expr = parser.sum()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expr),
+            Some(token) => Err(format!("unexpected {}", token)),
+        }
+    }
+
+    /// None when a variable has no value yet or a divisor is zero
+    /// (`memory_used_gb / vms_running` with no VMs running)
+    pub fn eval(&self, values: &BTreeMap<&str, f64>) -> Option<f64> {
+        match self {
+            Expr::Number(n) => Some(*n),
+            Expr::Var(name) => values.get(name.as_str()).copied(),
+            Expr::Neg(inner) => inner.eval(values).map(|v| -v),
+            Expr::Binary(left, op, right) => {
+                let (a, b) = (left.eval(values)?, right.eval(values)?);
+                match op {
+                    Op::Add => Some(a + b),
+                    Op::Sub => Some(a - b),
+                    Op::Mul => Some(a * b),
+                    Op::Div if b == 0.0 => None,
+                    Op::Div => Some(a / b),
+                }
+            }
+        }
+    }
+
+    /// Variable names used, for validation
+    pub fn variables(&self) -> Vec<&str> {
+        match self {
+            Expr::Number(_) => Vec::new(),
+            Expr::Var(name) => vec![name.as_str()],
+            Expr::Neg(inner) => inner.variables(),
+            Expr::Binary(left, _, right) => {
+                let mut names = left.variables();
+                names.extend(right.variables());
+                names
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(Op),
+    Open,
+    Close,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "{}", n),
+            Token::Ident(name) => write!(f, "{:?}", name),
+            Token::Op(op) => write!(f, "'{}'", match op {
+                Op::Add => '+',
+                Op::Sub => '-',
+                Op::Mul => '*',
+                Op::Div => '/',
+            }),
+            Token::Open => write!(f, "'('"),
+            Token::Close => write!(f, "')'"),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '+' | '-' | '*' | '/' | '(' | ')' => {
+                chars.next();
+                tokens.push(match c {
+                    '+' => Token::Op(Op::Add),
+                    '-' => Token::Op(Op::Sub),
+                    '*' => Token::Op(Op::Mul),
+                    '/' => Token::Op(Op::Div),
+                    '(' => Token::Open,
+                    _ => Token::Close,
+                });
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let number = &text[start..end];
+                tokens.push(Token::Number(number.parse().map_err(|_| format!("{:?} is not a number", number))?));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                tokens.push(Token::Ident(text[start..end].to_string()));
+            }
+            other => return Err(format!("unexpected {:?}", other)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent: sum := product (('+'|'-') product)*,
+/// product := unary (('*'|'/') unary)*, unary := '-' unary | atom
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    /// Unary minuses and parentheses currently open, capped at `MAX_DEPTH`
+    depth: usize,
+}
+
+/// Deeper than any real expression; keeps `((((...` or `----...` from
+/// exhausting the stack while the config is loaded
+const MAX_DEPTH: usize = 64;
+
+impl Parser<'_> {
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn binary(&mut self, ops: [Op; 2], operand: fn(&mut Self) -> Result<Expr, String>) -> Result<Expr, String> {
+        let mut expr = operand(self)?;
+        while let Some(&Token::Op(op)) = self.tokens.get(self.pos) {
+            if !ops.contains(&op) {
+                break;
+            }
+            self.pos += 1;
+            expr = Expr::Binary(Box::new(expr), op, Box::new(operand(self)?));
+        }
+        Ok(expr)
+    }
+
+    fn sum(&mut self) -> Result<Expr, String> {
+        self.binary([Op::Add, Op::Sub], Self::product)
+    }
+
+    fn product(&mut self) -> Result<Expr, String> {
+        self.binary([Op::Mul, Op::Div], Self::unary)
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Expr, String>) -> Result<Expr, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err(format!("nested more than {} deep", MAX_DEPTH));
+        }
+        self.depth += 1;
+        let expr = parse(self);
+        self.depth -= 1;
+        expr
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.tokens.get(self.pos) == Some(&Token::Op(Op::Sub)) {
+            self.pos += 1;
+            return Ok(Expr::Neg(Box::new(self.nested(Self::unary)?)));
+        }
+        match self.next().cloned() {
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::Ident(name)) => Ok(Expr::Var(name)),
+            Some(Token::Open) => {
+                let expr = self.nested(Self::sum)?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    Some(token) => Err(format!("expected ')' but found {}", token)),
+                    None => Err("missing ')'".to_string()),
+                }
+            }
+            Some(token) => Err(format!("unexpected {}", token)),
+            None => Err("expression ends early".to_string()),
+        }
+    }
+}
+
+/// A `[[computed_metrics]]` entry with its parsed expression and latest value
+#[derive(Debug, Clone)]
+pub struct ComputedMetric {
+    pub config: ComputedMetricConfig,
+    expr: Expr,
+    pub value: Option<f64>,
+}
+
+impl ComputedMetric {
+    /// The entries whose expressions parse; config validation reports the rest
+    pub fn compile(configs: &[ComputedMetricConfig]) -> Vec<ComputedMetric> {
+        configs
+            .iter()
+            .filter_map(|config| {
+                let expr = Expr::parse(&config.expr)
+                    .map_err(|e| tracing::warn!("Skipping computed metric {}: {}", config.name, e))
+                    .ok()?;
+                Some(ComputedMetric { config: config.clone(), expr, value: None })
+            })
+            .collect()
+    }
+
+    pub fn update(&mut self, values: &BTreeMap<&str, f64>) {
+        self.value = self.expr.eval(values).filter(|v| v.is_finite());
+    }
+
+    /// How far the value is past its thresholds: 2 critical, 1 warning, 0 fine
+    pub fn severity(&self) -> u8 {
+        let Some(value) = self.value else {
+            return 0;
+        };
+        let past = |threshold: Option<f64>| {
+            threshold.is_some_and(|t| if self.config.below { value <= t } else { value >= t })
+        };
+        if past(self.config.critical) {
+            2
+        } else if past(self.config.warning) {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// "3.25 GB/VM", or "-" without a value
+    pub fn display(&self) -> String {
+        match self.value {
+            Some(value) => format!("{:.2}{}", value, self.config.unit.as_deref().unwrap_or_default()),
+            None => "-".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(text: &str, values: &[(&'static str, f64)]) -> Option<f64> {
+        Expr::parse(text).unwrap().eval(&values.iter().copied().collect())
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("1 + 2 * 3", &[]), Some(7.0));
+        assert_eq!(eval("(1 + 2) * 3", &[]), Some(9.0));
+        assert_eq!(eval("8 / 4 / 2", &[]), Some(1.0));
+        assert_eq!(eval("10 - 4 - 3", &[]), Some(3.0));
+    }
+
+    #[test]
+    fn unary_minus() {
+        assert_eq!(eval("-2 * 3", &[]), Some(-6.0));
+        assert_eq!(eval("--2", &[]), Some(2.0));
+        assert_eq!(eval("4 - -load_avg", &[("load_avg", 1.5)]), Some(5.5));
+        assert_eq!(eval("-(1 + 2)", &[]), Some(-3.0));
+    }
+
+    #[test]
+    fn missing_values_and_division_by_zero() {
+        assert_eq!(eval("memory_used_gb / vms_running", &[("memory_used_gb", 12.0), ("vms_running", 0.0)]), None);
+        assert_eq!(eval("memory_used_gb / vms_running", &[("memory_used_gb", 12.0), ("vms_running", 4.0)]), Some(3.0));
+        assert_eq!(eval("cpu_usage + 1", &[]), None);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Expr::parse("1 +"), Err("expression ends early".to_string()));
+        assert_eq!(Expr::parse("(1 + 2"), Err("missing ')'".to_string()));
+        assert_eq!(Expr::parse("(1 2)"), Err("expected ')' but found 2".to_string()));
+        assert_eq!(Expr::parse("1 2"), Err("unexpected 2".to_string()));
+        assert_eq!(Expr::parse("2 % 3"), Err("unexpected '%'".to_string()));
+        assert_eq!(Expr::parse("1..2"), Err("\"1..2\" is not a number".to_string()));
+        assert_eq!(Expr::parse(")"), Err("unexpected ')'".to_string()));
+    }
+
+    #[test]
+    fn nesting_is_capped() {
+        let deep = format!("{}1{}", "(".repeat(MAX_DEPTH), ")".repeat(MAX_DEPTH));
+        assert_eq!(eval(&deep, &[]), Some(1.0));
+        let too_deep = format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert_eq!(Expr::parse(&too_deep), Err(format!("nested more than {} deep", MAX_DEPTH)));
+        assert!(Expr::parse(&format!("{}1", "-".repeat(100_000))).is_err());
+    }
+
+    #[test]
+    fn variables() {
+        let expr = Expr::parse("(memory_used_gb + 1) / vms_running").unwrap();
+        assert_eq!(expr.variables(), vec!["memory_used_gb", "vms_running"]);
+    }
+}
@@ -1,8 +1,18 @@
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::fs;
 
+use crate::layout::DashboardLayout;
+use crate::report::ReportSection;
+use crate::i18n::Locale;
+use crate::theme::Theme;
+use crate::types::ClusterFlavor;
+use crate::config_layers::{layer_paths, LayeredConfig};
+use crate::validation::ConfigReport;
+use crate::views::SavedView;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default)]
@@ -17,11 +27,102 @@ pub struct Config {
     #[serde(default)]
     pub network: NetworkConfig,
 
+    #[serde(default)]
+    pub storage: StorageConfig,
+
+    /// Cloud images offered for download or import as DataVolumes
+    #[serde(default)]
+    pub image_catalog: ImageCatalogConfig,
+
     #[serde(default)]
     pub display: DisplayConfig,
 
+    /// Dashboard card grid; a layout saved from the in-app editor overrides it
+    #[serde(default)]
+    pub dashboard: DashboardLayout,
+
     #[serde(default)]
     pub alerts: AlertsConfig,
+
+    /// Weights and levels of the health score in the header
+    #[serde(default)]
+    pub health: HealthConfig,
+
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    /// Where `*_secret` key names in other sections are looked up
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+
+    #[serde(default)]
+    pub web: WebConfig,
+
+    /// Alertmanager and Grafana webhooks shown as alerts
+    #[serde(default)]
+    pub receiver: ReceiverConfig,
+
+    /// What `--agent` serves and to whom
+    #[serde(default)]
+    pub agent: AgentConfig,
+
+    /// Hosts running `--agent` that the Fleet screen reads from
+    #[serde(default)]
+    pub remote_agents: RemoteAgentsConfig,
+
+    #[serde(default)]
+    pub upgrade: UpgradeConfig,
+
+    /// Looking for newer releases of this program
+    #[serde(default)]
+    pub update_check: UpdateCheckConfig,
+
+    #[serde(default)]
+    pub sysctl: SysctlConfig,
+
+    #[serde(default)]
+    pub report: ReportConfig,
+
+    #[serde(default)]
+    pub selftest: SelfTestConfig,
+
+    #[serde(default)]
+    pub tenants: TenantsConfig,
+
+    #[serde(default)]
+    pub compare: CompareConfig,
+
+    #[serde(default)]
+    pub crashes: CrashConfig,
+
+    /// How a correctly provisioned host looks; deviations raise alerts
+    #[serde(default)]
+    pub host_profile: HostProfileConfig,
+
+    /// Long-lived daemons whose resident memory is tracked for leaks
+    #[serde(default)]
+    pub memory_leaks: MemoryLeakConfig,
+
+    /// Where VMs are listed and started or stopped: KubeVirt, a Proxmox VE
+    /// cluster or plain qemu reached over SSH
+    #[serde(default)]
+    pub virtualization: VirtualizationConfig,
+
+    /// An existing Prometheus or exporter endpoints read for cluster metrics
+    #[serde(default)]
+    pub prometheus: PrometheusConfig,
+
+    /// Site-specific tools whose output is shown on the Tools screen
+    #[serde(default)]
+    pub custom_commands: Vec<CustomCommandConfig>,
+
+    /// Values computed from collected ones, shown on the `computed` card
+    #[serde(default)]
+    pub computed_metrics: Vec<ComputedMetricConfig>,
+
+    /// Named views recalled from the palette or with Alt-1..9
+    #[serde(default)]
+    pub views: Vec<SavedView>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,113 +132,2273 @@ pub struct GeneralConfig {
 
     #[serde(default = "default_log_buffer_size")]
     pub log_buffer_size: usize,
+
+    /// Approximate memory, in MiB, the log buffer may use; the oldest lines
+    /// are evicted past it even when fewer than `log_buffer_size`
+    #[serde(default = "default_log_buffer_max_mb")]
+    pub log_buffer_max_mb: usize,
+
+    /// Upper bound on a single collector run before it is abandoned
+    #[serde(default = "default_collector_timeout_secs")]
+    pub collector_timeout_secs: u64,
+
+    /// Restart the event loop after a panic instead of exiting
+    #[serde(default)]
+    pub restart_on_panic: bool,
+
+    /// Disable all mutating actions (same as --read-only)
+    #[serde(default)]
+    pub read_only: bool,
+
+    /// Processes (by name) whose open file descriptors are tracked against
+    /// their limits
+    #[serde(default = "default_watched_processes")]
+    pub watched_processes: Vec<String>,
+
+    /// Seconds between snapshots of the app's own health appended to
+    /// telemetry.jsonl in the state directory; 0 disables the file
+    #[serde(default = "default_telemetry_interval_secs")]
+    pub telemetry_interval_secs: u64,
+
+    /// Seconds between updates while the terminal is unfocused or its tmux
+    /// session detached; 0 keeps the normal interval
+    #[serde(default = "default_idle_refresh_secs")]
+    pub idle_refresh_secs: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KubernetesConfig {
+    /// k3s, rke2 or kubeadm; detected from the host when unset
+    #[serde(default)]
+    pub flavor: Option<ClusterFlavor>,
+
+    /// Defaults to the flavor's admin kubeconfig
+    #[serde(default)]
+    pub kubeconfig_path: Option<String>,
+
+    #[serde(default)]
+    pub api_server: Option<String>,
+
+    /// Services ("namespace/name") that alert when they lose all ready
+    /// endpoints. When empty, every Service seen with ready endpoints is watched.
+    #[serde(default)]
+    pub watched_services: Vec<String>,
+
+    /// Client-side rate limit and listing cache for the API server
+    #[serde(default)]
+    pub api: KubeApiConfig,
+
+    /// Background capture of KubeVirt VM serial consoles
+    #[serde(default)]
+    pub console: ConsoleConfig,
+
+    /// Node evacuation (cordon and live-migrate every VM off it)
+    #[serde(default)]
+    pub evacuation: EvacuationConfig,
+
+    /// Datastore size, leadership and raft health from etcd's metrics
+    #[serde(default)]
+    pub etcd: EtcdConfig,
+
+    /// Guest clocks of this host's VMs compared against the host's
+    #[serde(default)]
+    pub guest_clock: GuestClockConfig,
+
+    /// Guest filesystem usage of this host's VMs, for the fill alerts
+    #[serde(default)]
+    pub guest_filesystems: GuestFilesystemsConfig,
+
+    /// Host-only view with emergency qemu actions while the API server is down
+    #[serde(default)]
+    pub local_mode: LocalModeConfig,
+
+    /// Where finished VM migrations are kept (defaults to the state directory)
+    #[serde(default)]
+    pub migration_history_path: Option<String>,
+
+    /// Where VM boot times are kept (defaults to the state directory)
+    #[serde(default)]
+    pub boot_history_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingConfig {
+    /// Units to follow; defaults to the cluster flavor's services
+    #[serde(default)]
+    pub services: Option<Vec<String>>,
+
+    #[serde(default = "default_level_filter")]
+    pub level_filter: String,
+
+    /// Count bursts of repeated messages as a single "×N" entry as they
+    /// are read, so they take one buffer slot
+    #[serde(default = "default_true")]
+    pub collapse_repeats: bool,
+
+    /// Lines shown either side of a selected line in the context view
+    #[serde(default = "default_context_lines")]
+    pub context_lines: usize,
+
+    /// Most lines an archived time range query ('H') reads; the newest
+    /// are kept when the range holds more
+    #[serde(default = "default_archive_max_lines")]
+    pub archive_max_lines: usize,
+
+    /// Newest journal lines of each service the Ctrl-F search looks through
+    #[serde(default = "default_search_lines")]
+    pub search_lines: usize,
+
+    /// Patterns styled wherever they appear in a log message
+    #[serde(default)]
+    pub highlights: Vec<HighlightRule>,
+
+    /// Patterns scrubbed from log messages before they are buffered, so
+    /// screenshots and exports can be shared
+    #[serde(default)]
+    pub redactions: Vec<RedactionRule>,
+
+    /// Follow the kernel ring buffer (/dev/kmsg) alongside the journal and
+    /// alert on hardware errors, NIC resets and OOM kills found in it
+    #[serde(default = "default_true")]
+    pub kernel_log: bool,
+
+    /// Sinks the followed log stream is copied to as it is read
+    #[serde(default)]
+    pub forward: Vec<LogForwardConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighlightRule {
+    /// Regular expression; prefix with (?i) to ignore case
+    pub pattern: String,
+
+    /// Foreground color name ("red", "lightmagenta"), index ("208") or "#rrggbb"
+    #[serde(default)]
+    pub color: Option<String>,
+
+    #[serde(default)]
+    pub background: Option<String>,
+
+    #[serde(default)]
+    pub bold: bool,
+
+    #[serde(default)]
+    pub underline: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionRule {
+    /// Regular expression; prefix with (?i) to ignore case
+    pub pattern: String,
+
+    /// Text put in place of each match; $1 or ${name} insert capture groups
+    #[serde(default = "default_redaction_replacement")]
+    pub replacement: String,
+}
+
+/// Where `[[logging.forward]]` sends lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogSinkKind {
+    /// RFC 5424 messages over UDP or TCP
+    Syslog,
+    /// Loki's push API
+    Loki,
+    /// A local file, rotated by size
+    File,
+}
+
+/// `[[logging.forward]]`: one external sink for the log stream, after
+/// redaction, narrowed by service and level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogForwardConfig {
+    pub sink: LogSinkKind,
+
+    /// syslog: "udp://host:514" or "tcp://host:601"; loki: the push URL
+    /// (".../loki/api/v1/push"); file: a path
+    pub target: String,
+
+    /// Services forwarded; empty forwards every followed service
+    #[serde(default)]
+    pub services: Vec<String>,
+
+    /// Least severe level forwarded: ERROR, WARN, INFO or DEBUG
+    #[serde(default = "default_level_filter")]
+    pub min_level: String,
+
+    /// syslog facility name ("daemon", "local0" ... "local7")
+    #[serde(default = "default_forward_facility")]
+    pub facility: String,
+
+    /// Extra Loki stream labels (e.g. cluster, datacenter)
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+
+    /// Bearer token file for Loki behind an authenticating proxy
+    #[serde(default)]
+    pub bearer_token_file: Option<String>,
+
+    /// Name of the Loki bearer token in `[secrets]`
+    #[serde(default)]
+    pub bearer_token_secret: Option<String>,
+
+    /// A file sink is rotated to <target>.1 past this size
+    #[serde(default = "default_forward_max_size_mb")]
+    pub max_size_mb: u64,
+
+    /// Rotated files kept (<target>.1 ... <target>.N)
+    #[serde(default = "default_forward_keep")]
+    pub keep: u32,
+}
+
+/// `[kubernetes.api]`: keeps full listings from hammering the API server
+/// on large clusters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KubeApiConfig {
+    /// Sustained API requests per second; 0 disables the limit
+    #[serde(default = "default_api_qps")]
+    pub qps: f64,
+
+    /// Requests allowed at once before the limit applies
+    #[serde(default = "default_api_burst")]
+    pub burst: u32,
+
+    /// Seconds a listing is reused before it is refreshed
+    #[serde(default = "default_api_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+
+    /// Per-resource TTLs ("nodes", "pods", "services", ...) overriding `cache_ttl_secs`
+    #[serde(default)]
+    pub ttl_secs: std::collections::BTreeMap<String, u64>,
+}
+
+/// `[kubernetes.console]`: serial console output kept per VM so boot
+/// failures can be read after the fact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsoleConfig {
+    /// Capture every running VM; otherwise capture starts when a VM's
+    /// detail popup is first opened and continues in the background
+    #[serde(default)]
+    pub capture_all: bool,
+
+    /// Console lines kept per VM
+    #[serde(default = "default_console_buffer_lines")]
+    pub buffer_lines: usize,
+
+    /// Seconds before reconnecting after the console closes (VM restart,
+    /// or someone else attaching)
+    #[serde(default = "default_console_retry_secs")]
+    pub retry_secs: u64,
+
+    /// Address `virtctl vnc` port-forwards started from the VM detail
+    /// popup listen on; loopback keeps them behind an SSH tunnel
+    #[serde(default = "default_vnc_address")]
+    pub vnc_address: String,
+
+    /// First port tried for a port-forward, counting up past ports in use
+    #[serde(default = "default_vnc_first_port")]
+    pub vnc_first_port: u16,
+}
+
+/// `[kubernetes.etcd]`: where etcd's Prometheus metrics are scraped. By
+/// default the plain-HTTP metrics listener (127.0.0.1:2381) is tried, then
+/// the client port with the flavor's etcd client certificates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EtcdConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Metrics URL to scrape instead of the defaults, e.g. an external etcd
+    #[serde(default)]
+    pub metrics_url: Option<String>,
+
+    /// TLS files for an https URL; default to the flavor's etcd client certificates
+    #[serde(default)]
+    pub ca_file: Option<String>,
+
+    #[serde(default)]
+    pub cert_file: Option<String>,
+
+    #[serde(default)]
+    pub key_file: Option<String>,
+}
+
+/// `[kubernetes.guest_clock]`: every interval, each VM running on this host
+/// is asked for its time through qemu-guest-agent (`virsh
+/// qemu-agent-command` in its virt-launcher pod) and compared to the host's
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuestClockConfig {
+    /// Seconds between checks; 0 disables them
+    #[serde(default = "default_guest_clock_interval_secs")]
+    pub interval_secs: u64,
+}
+
+/// `[kubernetes.guest_filesystems]`: every interval, each VM running on
+/// this host has its filesystems listed through the guest agent
+/// (KubeVirt's filesystemlist subresource) so fill alerts don't depend on
+/// its detail popup being open
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuestFilesystemsConfig {
+    /// Seconds between checks; 0 disables them
+    #[serde(default = "default_guest_filesystems_interval_secs")]
+    pub interval_secs: u64,
+}
+
+/// `[kubernetes.local_mode]`: when the API server stops answering, the
+/// host map of qemu processes becomes the main view, cluster collection
+/// is left to the health probe, and running VMs can be paused or killed
+/// by PID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalModeConfig {
+    /// Failed API health probes in a row before switching; 0 only enters
+    /// local mode with --local
+    #[serde(default = "default_local_mode_after_failures")]
+    pub after_failures: u32,
+
+    /// Allow pausing, resuming and killing qemu processes in local mode
+    #[serde(default = "default_true")]
+    pub emergency_actions: bool,
+}
+
+/// `[kubernetes.evacuation]`: how a node is drained of VMs before patching
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvacuationConfig {
+    /// Live migrations started at once
+    #[serde(default = "default_evacuation_concurrency")]
+    pub concurrency: usize,
+
+    /// A VM still on the node this long after its migration was requested,
+    /// or a pod still refused eviction or terminating, is reported stuck
+    /// and no longer waited for
+    #[serde(default = "default_evacuation_stuck_secs")]
+    pub stuck_secs: u64,
+}
+
+/// Read-only browser view served alongside the TUI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Address to listen on; there is no authentication, so only bind
+    /// beyond localhost on a trusted network or behind a proxy
+    #[serde(default = "default_web_listen")]
+    pub listen: String,
+}
+
+/// Listener for alert webhooks from Alertmanager or Grafana
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiverConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default = "default_receiver_listen")]
+    pub listen: String,
+
+    /// Bearer token senders must present; without one any client that
+    /// can reach `listen` may post alerts
+    #[serde(default)]
+    pub token: Option<String>,
+
+    #[serde(default)]
+    pub token_file: Option<String>,
+
+    /// How long a firing alert stays up without being re-sent, for
+    /// senders whose resolve notification never arrives
+    #[serde(default = "default_receiver_expire_mins")]
+    pub expire_mins: u64,
+}
+
+/// `--agent`: this host's collector data served over mutually
+/// authenticated TLS. Clients must present a certificate signed by
+/// `client_ca_file`, so all three files are required.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentConfig {
+    /// Overridden by `--listen`
+    #[serde(default = "default_agent_listen")]
+    pub listen: String,
+
+    /// PEM certificate chain the agent presents
+    #[serde(default)]
+    pub cert_file: Option<String>,
+
+    #[serde(default)]
+    pub key_file: Option<String>,
+
+    /// PEM CA bundle that client certificates must chain to
+    #[serde(default)]
+    pub client_ca_file: Option<String>,
+
+    /// Seconds between collections; requests in between get the last one
+    #[serde(default = "default_agent_interval_secs")]
+    pub interval_secs: u64,
+}
+
+/// Agents the central console reads host metrics, qemu processes and
+/// alerts from, instead of logging in to each host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteAgentsConfig {
+    /// "host:port" of each agent; the host must match its certificate
+    #[serde(default)]
+    pub endpoints: Vec<String>,
+
+    /// PEM client certificate presented to the agents
+    #[serde(default)]
+    pub cert_file: Option<String>,
+
+    #[serde(default)]
+    pub key_file: Option<String>,
+
+    /// PEM CA bundle the agents' certificates must chain to
+    #[serde(default)]
+    pub ca_file: Option<String>,
+
+    /// Seconds between polls; updates in between reuse the last snapshots
+    #[serde(default = "default_remote_agents_interval_secs")]
+    pub interval_secs: u64,
+
+    /// Consecutive failed polls before an agent raises an alert
+    #[serde(default = "default_remote_agents_failure_threshold")]
+    pub failure_threshold: u32,
+}
+
+/// How a custom command's output is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandParser {
+    /// As printed
+    #[default]
+    Raw,
+    /// Whitespace-separated columns under the first line as headers
+    Table,
+}
+
+/// `[[computed_metrics]]`: an expression over collected values such as
+/// `vms_running / nodes_ready`, recorded every update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComputedMetricConfig {
+    pub name: String,
+
+    /// Numbers, the variables listed in README, + - * / and parentheses
+    pub expr: String,
+
+    /// Appended to the value: "%", " GB/VM", ...
+    #[serde(default)]
+    pub unit: Option<String>,
+
+    #[serde(default)]
+    pub warning: Option<f64>,
+
+    #[serde(default)]
+    pub critical: Option<f64>,
+
+    /// Alert when the value falls to the thresholds rather than rises to them
+    #[serde(default)]
+    pub below: bool,
+}
+
+/// `[[custom_commands]]`: a tool run periodically with its output in a
+/// panel of the Tools screen (`zpool iostat`, `vdo status`, vendor CLIs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomCommandConfig {
+    /// Panel title
+    pub name: String,
+
+    /// Run with `sh -c`, so pipes and redirections work
+    pub command: String,
+
+    /// Seconds between runs
+    #[serde(default = "default_command_interval_secs")]
+    pub interval_secs: u64,
+
+    /// A run taking longer than this is killed and reported as failed
+    #[serde(default = "default_command_timeout_secs")]
+    pub timeout_secs: u64,
+
+    #[serde(default)]
+    pub parser: CommandParser,
+}
+
+/// Host upgrades run from the Upgrade panel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradeConfig {
+    /// Program and leading arguments; the action ("switch" or "boot") is appended
+    #[serde(default = "default_upgrade_command")]
+    pub command: Vec<String>,
+
+    /// Passed as `--flake` ("/etc/nixos#hypervisor")
+    #[serde(default)]
+    pub flake: Option<String>,
+
+    /// Appended after the flake (e.g. ["--print-build-logs"])
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+
+    /// Program and leading arguments collecting Nix store garbage from the
+    /// generations panel
+    #[serde(default = "default_gc_command")]
+    pub gc_command: Vec<String>,
+
+    /// Program and leading arguments deleting a single generation from the
+    /// generations panel (["sudo", "nix-env"] when not running as root)
+    #[serde(default = "default_nix_env_command")]
+    pub nix_env_command: Vec<String>,
+
+    /// Generations older than this ("14d") are deleted before collecting;
+    /// empty keeps them all and frees only unreferenced paths
+    #[serde(default = "default_gc_retention")]
+    pub gc_retention: String,
+}
+
+/// Periodic check for a newer release, shown as a footer badge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCheckConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// A GitHub "latest release" API URL, or any URL answering JSON with
+    /// `version`, `changelog` and `url` (a mirror for hosts without
+    /// internet access)
+    #[serde(default = "default_update_check_url")]
+    pub url: String,
+
+    #[serde(default = "default_update_check_interval_hours")]
+    pub interval_hours: u64,
+}
+
+/// The header's health score: each part scores 0-100 and they are
+/// averaged by weight; a weight of 0 leaves a part out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthConfig {
+    /// CPU, memory and root disk against their warning thresholds
+    #[serde(default = "default_health_metrics_weight")]
+    pub metrics_weight: u32,
+
+    /// Active alerts by level
+    #[serde(default = "default_health_alerts_weight")]
+    pub alerts_weight: u32,
+
+    /// systemd units that failed or aren't active
+    #[serde(default = "default_health_services_weight")]
+    pub services_weight: u32,
+
+    /// API server, etcd and nodes not Ready
+    #[serde(default = "default_health_cluster_weight")]
+    pub cluster_weight: u32,
+
+    /// Scores below this are amber
+    #[serde(default = "default_health_degraded_below")]
+    pub degraded_below: u32,
+
+    /// Scores below this are red
+    #[serde(default = "default_health_critical_below")]
+    pub critical_below: u32,
+}
+
+/// Status report written and copied by `R`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportConfig {
+    /// Blocks of the report, in order
+    #[serde(default = "default_report_sections")]
+    pub sections: Vec<ReportSection>,
+
+    /// Active alerts listed, most severe first
+    #[serde(default = "default_report_max_alerts")]
+    pub max_alerts: usize,
+
+    /// Where report files go (defaults to reports/ in the state directory)
+    #[serde(default)]
+    pub dir: Option<String>,
+
+    /// Print a summary to the terminal on quit, after leaving the
+    /// alternate screen, so the last state stays in scrollback
+    #[serde(default)]
+    pub exit_summary: bool,
+
+    /// Blocks of the exit summary, in order
+    #[serde(default = "default_report_exit_sections")]
+    pub exit_sections: Vec<ReportSection>,
+}
+
+/// VM and pod resources rolled up per tenant on the Fleet screen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantsConfig {
+    /// Label key naming the tenant or project of a VM, pod or namespace;
+    /// `display.group_by_label` when unset, and no rollup without either
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// Alert when a tenant's vCPUs or memory grew by this percentage within
+    /// `growth_window_hours`; 0 disables
+    #[serde(default = "default_tenant_growth_percent")]
+    pub growth_alert_percent: f64,
+
+    #[serde(default = "default_tenant_growth_window_hours")]
+    pub growth_window_hours: u64,
+
+    /// Per-tenant limits that alert when reached, e.g.
+    /// `[tenants.limits.acme]` with `vcpus = 64`
+    #[serde(default)]
+    pub limits: BTreeMap<String, TenantLimits>,
+}
+
+/// Snapshots of this node kept on disk for the "now vs. earlier" compare
+/// view (`c` on the Dashboard)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompareConfig {
+    /// Minutes between snapshots; 0 disables them
+    #[serde(default = "default_snapshot_interval_mins")]
+    pub snapshot_interval_mins: u64,
+
+    #[serde(default = "default_snapshot_retention_hours")]
+    pub retention_hours: u64,
+
+    /// How far back the compare view opens
+    #[serde(default = "default_compare_baseline_mins")]
+    pub baseline_mins: u64,
+
+    /// Defaults to `snapshots.json` in the state directory
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+/// Which system manages the VMs shown on the Resources screen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VirtBackend {
+    #[default]
+    KubeVirt,
+    /// The Proxmox VE API
+    Proxmox,
+    /// qemu processes' QMP sockets, over SSH
+    Qmp,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VirtualizationConfig {
+    #[serde(default)]
+    pub backend: VirtBackend,
+
+    #[serde(default)]
+    pub proxmox: ProxmoxConfig,
+
+    #[serde(default)]
+    pub qmp: QmpConfig,
+}
+
+/// `[virtualization.proxmox]`: a Proxmox VE cluster reached with an API token
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProxmoxConfig {
+    /// Base URL, e.g. "https://pve1.example.com:8006"
+    #[serde(default)]
+    pub url: String,
+
+    /// "user@realm!tokenname"
+    #[serde(default)]
+    pub token_id: String,
+
+    #[serde(default)]
+    pub token_secret: Option<String>,
+
+    /// Read the token secret from a file (e.g. an agenix/sops secret)
+    #[serde(default)]
+    pub token_secret_file: Option<String>,
+
+    /// CA bundle for a self-signed cluster certificate
+    #[serde(default)]
+    pub ca_file: Option<String>,
+
+    /// Skip certificate verification altogether
+    #[serde(default)]
+    pub insecure: bool,
+}
+
+/// `[virtualization.qmp]`: qemu started outside any manager, one QMP
+/// socket per VM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QmpConfig {
+    /// SSH destinations ("root@hv1"); "localhost" runs the commands directly
+    #[serde(default = "default_qmp_hosts")]
+    pub hosts: Vec<String>,
+
+    /// Shell glob of the QMP sockets; a VM is named after its socket
+    #[serde(default = "default_qmp_sockets")]
+    pub sockets: String,
+
+    /// Extra `ssh` arguments, e.g. ["-i", "/root/.ssh/hv_ed25519"]
+    #[serde(default)]
+    pub ssh_options: Vec<String>,
+}
+
+/// Core dumps and crash reports listed on the `crashes` dashboard card
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashConfig {
+    /// Read `coredumpctl` and the crash directories
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Binary name prefixes whose crashes raise an alert ("qemu" covers
+    /// qemu-system-x86_64 and qemu-kvm)
+    #[serde(default = "default_crash_processes")]
+    pub processes: Vec<String>,
+
+    /// Directories holding apport reports, core files or kdump vmcores
+    #[serde(default = "default_crash_dirs")]
+    pub crash_dirs: Vec<String>,
+
+    #[serde(default = "default_crash_scan_interval_secs")]
+    pub scan_interval_secs: u64,
+
+    /// How far back dumps are listed
+    #[serde(default = "default_crash_history_days")]
+    pub history_days: u64,
+}
+
+/// `[prometheus]`: cluster metrics from an existing Prometheus instead of
+/// the collectors' own approximations. Its apiserver p99 latency joins the
+/// API probes, its etcd metrics stand in where this host runs no etcd and
+/// kubevirt's VM metrics replace the launcher pods' usage on the heatmap.
+/// `queries` and `metrics` become variables for `[[computed_metrics]]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrometheusConfig {
+    /// Base URL of the Prometheus HTTP API, e.g.
+    /// "http://prometheus.monitoring:9090"; empty queries no server
+    #[serde(default)]
+    pub url: String,
+
+    /// Exporter `/metrics` URLs scraped directly (kube-state-metrics,
+    /// node-exporter, virt-handler), for `metrics`
+    #[serde(default)]
+    pub targets: Vec<String>,
+
+    /// Bearer token file for Prometheus behind an authenticating proxy
+    #[serde(default)]
+    pub bearer_token_file: Option<String>,
+
+    /// Name of the bearer token in `[secrets]`
+    #[serde(default)]
+    pub bearer_token_secret: Option<String>,
+
+    /// CA bundle for https URLs signed by a private CA
+    #[serde(default)]
+    pub ca_file: Option<String>,
+
+    /// Seconds between scrapes; updates in between reuse the last values
+    #[serde(default = "default_prometheus_interval_secs")]
+    pub interval_secs: u64,
+
+    /// PromQL for the apiserver's request latency, in seconds; empty skips it
+    #[serde(default = "default_prometheus_api_latency_query")]
+    pub api_latency_query: String,
+
+    /// Read etcd's database size, leader and proposals from the server
+    #[serde(default = "default_true")]
+    pub etcd: bool,
+
+    /// PromQL for each VM's CPU use in percent, by `namespace` and `name`
+    /// labels; empty keeps the launcher pods' usage
+    #[serde(default = "default_prometheus_vm_cpu_query")]
+    pub vm_cpu_query: String,
+
+    /// PromQL for each VM's memory use in percent, as `vm_cpu_query`
+    #[serde(default = "default_prometheus_vm_memory_query")]
+    pub vm_memory_query: String,
+
+    /// Variable name -> PromQL; the first series' value
+    #[serde(default)]
+    pub queries: BTreeMap<String, String>,
+
+    /// Variable name -> metric name with optional `{label="value"}`
+    /// matchers, summed over every series of every target
+    #[serde(default)]
+    pub metrics: BTreeMap<String, String>,
+}
+
+impl PrometheusConfig {
+    pub fn is_empty(&self) -> bool {
+        self.url.is_empty() && self.targets.is_empty()
+    }
+}
+
+/// The golden profile of a hypervisor host. The live host is compared
+/// against every expectation set here at startup and every
+/// `check_interval_secs`; nothing is checked while all are empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostProfileConfig {
+    /// Kernel release as `uname -r` prints it; a trailing `*` matches any
+    /// rest, e.g. "6.6.*"
+    #[serde(default)]
+    pub kernel: Option<String>,
+
+    /// Expected value per sysctl, written as in `[sysctl] expected`
+    #[serde(default)]
+    pub sysctls: BTreeMap<String, String>,
+
+    /// systemd units that must be active
+    #[serde(default)]
+    pub services: Vec<String>,
+
+    /// Least number of hugepages reserved per page size ("2M", "1G")
+    #[serde(default)]
+    pub hugepages: BTreeMap<String, u64>,
+
+    /// Least number of physical NICs
+    #[serde(default)]
+    pub nic_count: Option<u32>,
+
+    /// Least link speed of every physical NIC that has a link, in Mb/s
+    #[serde(default)]
+    pub nic_speed_mbps: Option<u32>,
+
+    /// Flags the CPU must advertise in /proc/cpuinfo, e.g. "avx2"
+    #[serde(default)]
+    pub cpu_flags: Vec<String>,
+
+    #[serde(default = "default_host_profile_interval_secs")]
+    pub check_interval_secs: u64,
+}
+
+impl HostProfileConfig {
+    pub fn is_empty(&self) -> bool {
+        self.kernel.is_none()
+            && self.sysctls.is_empty()
+            && self.services.is_empty()
+            && self.hugepages.is_empty()
+            && self.nic_count.is_none()
+            && self.nic_speed_mbps.is_none()
+            && self.cpu_flags.is_empty()
+    }
+}
+
+/// Resident memory of long-lived host daemons, sampled every
+/// `sample_interval_secs` and summed over each daemon's processes. A
+/// daemon whose memory climbed steadily over the last `window_hours` at
+/// `growth_mb_per_hour` or more raises a warning; one past its entry in
+/// `limits_mb` raises an error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryLeakConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Process names as in /proc/<pid>/comm
+    #[serde(default = "default_leak_processes")]
+    pub processes: Vec<String>,
+
+    #[serde(default = "default_leak_sample_interval_secs")]
+    pub sample_interval_secs: u64,
+
+    /// Hours the growth is judged over, at most 24
+    #[serde(default = "default_leak_window_hours")]
+    pub window_hours: u64,
+
+    #[serde(default = "default_leak_growth_mb_per_hour")]
+    pub growth_mb_per_hour: f64,
+
+    /// Resident memory per process name past which it's an error, in MB
+    #[serde(default)]
+    pub limits_mb: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TenantLimits {
+    /// Cores given to the tenant's VMs (vCPUs) and pods (requests)
+    #[serde(default)]
+    pub vcpus: Option<f64>,
+
+    #[serde(default)]
+    pub memory_gib: Option<f64>,
+
+    #[serde(default)]
+    pub storage_gib: Option<f64>,
+}
+
+/// Smoke test run by `T`: a throwaway VMI, a storage check and cleanup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfTestConfig {
+    /// Where the test VMI, claim and pod are created
+    #[serde(default = "default_selftest_namespace")]
+    pub namespace: String,
+
+    /// Node to pin the test to (any schedulable node when unset)
+    #[serde(default)]
+    pub node: Option<String>,
+
+    /// containerDisk image booted by the test VMI
+    #[serde(default = "default_selftest_image")]
+    pub image: String,
+
+    /// Multus NetworkAttachmentDefinition ("namespace/name") attached as a
+    /// bridge interface next to the pod network
+    #[serde(default)]
+    pub network: Option<String>,
+
+    /// Class of the test claim (the cluster default when unset)
+    #[serde(default)]
+    pub storage_class: Option<String>,
+
+    /// Image of the pod writing to and reading back from the test claim
+    #[serde(default = "default_selftest_storage_image")]
+    pub storage_image: String,
+
+    /// A step taking longer than this fails
+    #[serde(default = "default_selftest_step_timeout_secs")]
+    pub step_timeout_secs: u64,
+}
+
+/// Kernel tunables shown on the `sysctls` dashboard card
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SysctlConfig {
+    /// Dotted sysctl names shown whether or not they have an expected value
+    #[serde(default = "default_sysctl_watch")]
+    pub watch: Vec<String>,
+
+    /// Expected value per sysctl: "1" (exact), ">=8192" or "<=10"; a
+    /// mismatch raises an alert
+    #[serde(default = "default_sysctl_expected")]
+    pub expected: BTreeMap<String, String>,
+}
+
+impl SysctlConfig {
+    /// Watched and expected sysctls, watched ones first, without duplicates
+    pub fn names(&self) -> Vec<String> {
+        let mut names = self.watch.clone();
+        for name in self.expected.keys() {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+}
+
+/// Where VM disk images live on this host, for the disk inventory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// Directories holding image files (qcow2, raw, img, iso, ...)
+    #[serde(default = "default_image_dirs")]
+    pub image_dirs: Vec<String>,
+
+    /// local-path provisioner roots with one "pvc-..." directory per volume
+    #[serde(default = "default_local_path_dirs")]
+    pub local_path_dirs: Vec<String>,
+
+    /// Mount points whose usage is tracked and forecast; empty tracks every
+    /// local disk filesystem
+    #[serde(default)]
+    pub filesystems: Vec<String>,
+
+    /// Block devices (kernel or dm names) VM disks live on, whose I/O
+    /// scheduler is checked; empty uses the devices under `image_dirs` and
+    /// `local_path_dirs`
+    #[serde(default)]
+    pub datastore_devices: Vec<String>,
+}
+
+/// `[image_catalog]`: cloud images to start VMs from, downloaded into an
+/// image directory or imported by CDI as DataVolumes from the image catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageCatalogConfig {
+    /// Where downloads go; unset uses the first of `storage.image_dirs`
+    #[serde(default)]
+    pub download_dir: Option<String>,
+
+    /// Namespace DataVolumes are created in
+    #[serde(default = "default_catalog_namespace")]
+    pub namespace: String,
+
+    /// Storage class of created DataVolumes; unset uses the cluster default
+    #[serde(default)]
+    pub storage_class: Option<String>,
+
+    #[serde(default = "default_catalog_images")]
+    pub images: Vec<CatalogImageConfig>,
+}
+
+/// One image in the catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogImageConfig {
+    /// Shown in the catalog; also names the DataVolume ("ubuntu-24.04"
+    /// becomes "ubuntu-24-04")
+    pub name: String,
+
+    pub url: String,
+
+    /// Expected SHA-256 or SHA-512 of the file, in hex ("sha256:" prefix
+    /// allowed)
+    #[serde(default)]
+    pub checksum: Option<String>,
+
+    /// A checksum list (SHA256SUMS, Fedora's CHECKSUM) naming the file,
+    /// read at download time so `url` can point at a "latest" build
+    #[serde(default)]
+    pub checksum_url: Option<String>,
+
+    /// DataVolume size; must hold the image's virtual size
+    #[serde(default = "default_catalog_image_size")]
+    pub size: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    #[serde(default)]
+    pub interfaces: Vec<String>,
+
+    #[serde(default = "default_true")]
+    pub show_bridges: bool,
+
+    #[serde(default = "default_true")]
+    pub show_virtual: bool,
+
+    /// List `lo` as well
+    #[serde(default)]
+    pub show_loopback: bool,
+
+    /// Probe host (resolv.conf) and CoreDNS resolvers
+    #[serde(default = "default_true")]
+    pub dns_checks_enabled: bool,
+
+    /// Names resolved through the host's resolv.conf nameservers
+    #[serde(default = "default_dns_host_names")]
+    pub dns_host_names: Vec<String>,
+
+    /// Names resolved through CoreDNS; CoreDNS is not probed when empty
+    #[serde(default = "default_dns_cluster_names")]
+    pub dns_cluster_names: Vec<String>,
+
+    /// CoreDNS service IP (discovered from the kube-dns service when unset)
+    #[serde(default)]
+    pub coredns_address: Option<String>,
+
+    #[serde(default = "default_dns_timeout_ms")]
+    pub dns_timeout_ms: u64,
+
+    /// Consecutive failed probe rounds before a resolver alert fires
+    #[serde(default = "default_dns_failure_threshold")]
+    pub dns_failure_threshold: u32,
+
+    /// Read the routing table and ping the default gateways
+    #[serde(default = "default_true")]
+    pub route_checks_enabled: bool,
+
+    #[serde(default = "default_gateway_timeout_ms")]
+    pub gateway_timeout_ms: u64,
+
+    /// Consecutive failed pings before a gateway alert fires
+    #[serde(default = "default_gateway_failure_threshold")]
+    pub gateway_failure_threshold: u32,
+
+    /// Default route changes within `route_flap_window_minutes` before a
+    /// flapping alert fires
+    #[serde(default = "default_route_flap_threshold")]
+    pub route_flap_threshold: u32,
+
+    #[serde(default = "default_route_flap_window_minutes")]
+    pub route_flap_window_minutes: u64,
+
+    /// Container registries (host[:port] or URL) whose /v2/ endpoint is probed
+    #[serde(default)]
+    pub registries: Vec<String>,
+
+    #[serde(default = "default_registry_timeout_ms")]
+    pub registry_timeout_ms: u64,
+
+    /// Consecutive failed probes before a registry alert fires
+    #[serde(default = "default_registry_failure_threshold")]
+    pub registry_failure_threshold: u32,
+
+    /// Failed pulls of one image (with no success since) before alerting
+    #[serde(default = "default_image_pull_failure_threshold")]
+    pub image_pull_failure_threshold: u32,
+
+    /// LoadBalancer/NodePort Services ("namespace/name") whose VIPs are
+    /// probed from the host and whose DNS records are resolved via CoreDNS
+    #[serde(default)]
+    pub vip_services: Vec<String>,
+
+    #[serde(default = "default_vip_timeout_ms")]
+    pub vip_timeout_ms: u64,
+
+    /// Consecutive failed connection attempts before a VIP alert fires
+    #[serde(default = "default_vip_failure_threshold")]
+    pub vip_failure_threshold: u32,
+
+    /// VM bridges whose ARP and DHCP traffic is watched for IP conflicts
+    /// and lease churn (needs CAP_NET_RAW)
+    #[serde(default)]
+    pub conflict_watch_bridges: Vec<String>,
+
+    /// Service ports whose listeners and connections are shown on the
+    /// "ports" Dashboard card
+    #[serde(default = "default_watched_ports")]
+    pub watched_ports: Vec<WatchedPort>,
+
+    /// Ports allowed to listen on non-loopback addresses besides the
+    /// watched ones; any other listener is highlighted as unexpected
+    #[serde(default = "default_expected_listeners")]
+    pub expected_listeners: Vec<u16>,
+
+    /// List the interfaces inside virt-launcher pods' network namespaces
+    /// (through /proc/<pid>/ns/net) on the Network screen; link state and
+    /// addresses need root and nsenter, counters don't
+    #[serde(default)]
+    pub vm_namespaces: bool,
+
+    /// Friendly names for interfaces and bridges ("enp65s0f0" = "storage-10G"),
+    /// shown next to the kernel name on the Network screen and in alerts
+    #[serde(default)]
+    pub interface_labels: std::collections::BTreeMap<String, String>,
+
+    /// MTU the pod network should have; unset takes the CNI's own
+    /// (flannel's subnet.env, else "mtu" in /etc/cni/net.d). Bridge ports,
+    /// bond members and VLANs are always checked against their master or
+    /// parent.
+    #[serde(default)]
+    pub expected_mtu: Option<u32>,
+}
+
+/// `[[network.watched_ports]]`: one service port, or a range of them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedPort {
+    pub name: String,
+    pub port: u16,
+
+    /// Last port of a range starting at `port` (e.g. VNC 5900-5999)
+    #[serde(default)]
+    pub to: Option<u16>,
+
+    /// Raise a Service alert when nothing listens on it
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl WatchedPort {
+    pub fn contains(&self, port: u16) -> bool {
+        (self.port..=self.to.unwrap_or(self.port)).contains(&port)
+    }
+
+    /// "6443", or "5900-5999"
+    pub fn label(&self) -> String {
+        match self.to {
+            Some(to) if to != self.port => format!("{}-{}", self.port, to),
+            _ => self.port.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayConfig {
+    /// auto (detect the terminal background), dark or light
+    #[serde(default)]
+    pub theme: Theme,
+
+    #[serde(default = "default_true")]
+    pub show_graphs: bool,
+
+    #[serde(default = "default_animation_refresh")]
+    pub animation_refresh: u64,
+
+    /// ASCII-only glyphs, no color, textual level prefixes and reduced redraws
+    #[serde(default)]
+    pub accessibility: bool,
+
+    /// Label key the resource list (e.g. VMs by `tenant`) is grouped by at
+    /// startup; `l` on the Resources screen changes it
+    #[serde(default)]
+    pub group_by_label: Option<String>,
+
+    /// Time constant of the exponential moving average shown on the CPU
+    /// gauges, in seconds; 0 shows raw readings. History keeps raw values.
+    #[serde(default = "default_cpu_smoothing_secs")]
+    pub cpu_smoothing_secs: u64,
+
+    /// Colors and icons of alert levels and categories
+    #[serde(default)]
+    pub alert_styles: AlertStylesConfig,
+
+    /// Save the screen, filters, sort orders, theme and alert panel at quit
+    /// (ui-state.toml in the state directory) and restore them at start
+    #[serde(default = "default_true")]
+    pub remember_state: bool,
+
+    /// Error and warning rates of the last 10 minutes in the footer on
+    /// every screen; the journal is then read on each update wherever you are
+    #[serde(default = "default_true")]
+    pub footer_log_rates: bool,
+
+    /// Seconds each active alert's title stays in the banner before the
+    /// next one's, most severe first; 0 keeps the most severe one
+    #[serde(default = "default_banner_cycle_secs")]
+    pub banner_cycle_secs: u64,
+
+    /// Language of screen names, footer hints and host and cluster alerts:
+    /// auto (from `$LANG`), en or de
+    #[serde(default)]
+    pub locale: Locale,
+
+    /// Time zone of every timestamp shown: local, utc or a zoneinfo name
+    /// such as Europe/Berlin
+    #[serde(default = "default_time_zone")]
+    pub time_zone: String,
+
+    /// Timestamps in logs, alerts, the timeline and history charts:
+    /// default (each view's own), iso8601, relative ("2m ago") or a
+    /// strftime pattern
+    #[serde(default = "default_timestamp_format")]
+    pub timestamp_format: String,
+
+    /// Header widgets in order, flowed over the header's two lines; those
+    /// that don't fit the terminal's width are left out
+    #[serde(default = "default_header_widgets")]
+    pub header: Vec<HeaderWidgetConfig>,
+}
+
+/// What a header widget shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeaderWidget {
+    /// This host's node name, or its hostname when it isn't a node
+    Hostname,
+    Uptime,
+    Cpu,
+    Memory,
+    /// One-minute load average
+    Load,
+    /// Active alerts per level
+    Alerts,
+    /// Kubernetes distribution and whether its API answers
+    K8s,
+    /// Running VMs of all VMs
+    Vms,
+    /// First line of a command's output
+    Command,
+}
+
+/// `[[display.header]]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderWidgetConfig {
+    pub widget: HeaderWidget,
+
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Replaces the widget's own label; a command widget has none otherwise
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// For `command`: run with `sh -c`
+    #[serde(default)]
+    pub command: Option<String>,
+
+    /// For `command`: seconds between runs
+    #[serde(default = "default_command_interval_secs")]
+    pub interval_secs: u64,
+
+    /// For `command`: a run taking longer than this is killed
+    #[serde(default = "default_command_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl HeaderWidgetConfig {
+    fn new(widget: HeaderWidget) -> Self {
+        Self {
+            widget,
+            enabled: true,
+            label: None,
+            command: None,
+            interval_secs: default_command_interval_secs(),
+            timeout_secs: default_command_timeout_secs(),
+        }
+    }
+}
+
+/// `[display.alert_styles.levels.<level>]` and
+/// `[display.alert_styles.categories.<category>]`; a category's style wins
+/// over its level's on individual alerts, the level counts keep theirs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertStylesConfig {
+    /// Keyed by critical, error, warning or info
+    #[serde(default)]
+    pub levels: BTreeMap<String, AlertStyleConfig>,
+
+    /// Keyed by system, network, kubernetes, kubevirt or service
+    #[serde(default)]
+    pub categories: BTreeMap<String, AlertStyleConfig>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertStyleConfig {
+    /// Color name ("red", "lightblue"), "#rrggbb" or a 256-color index
+    #[serde(default)]
+    pub color: Option<String>,
+
+    /// Replaces the symbol, in both the Unicode and the ASCII glyph sets
+    #[serde(default)]
+    pub icon: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertsConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Seconds between collections of what alerts watch while their screen
+    /// isn't the one displayed; 0 leaves them to the screen on display
+    #[serde(default = "default_alert_background_interval_secs")]
+    pub background_interval_secs: u64,
+
+    /// Alert profile laid over this table at startup (--alert-profile wins)
+    #[serde(default)]
+    pub profile: Option<String>,
+
+    /// Where alert profiles are stored
+    /// (default: ~/.config/hypervisor-tui/alert-profiles)
+    #[serde(default)]
+    pub profiles_dir: Option<String>,
+
+    #[serde(default = "default_cpu_warning")]
+    pub cpu_warning_threshold: f64,
+
+    #[serde(default = "default_cpu_critical")]
+    pub cpu_critical_threshold: f64,
+
+    #[serde(default = "default_memory_warning")]
+    pub memory_warning_threshold: f64,
+
+    #[serde(default = "default_memory_critical")]
+    pub memory_critical_threshold: f64,
+
+    #[serde(default = "default_disk_warning")]
+    pub disk_warning_threshold: f64,
+
+    #[serde(default = "default_disk_critical")]
+    pub disk_critical_threshold: f64,
+
+    #[serde(default = "default_load_warning")]
+    pub load_warning_threshold: f64,
+
+    #[serde(default = "default_load_critical")]
+    pub load_critical_threshold: f64,
+
+    #[serde(default = "default_true")]
+    pub kubernetes_enabled: bool,
+
+    #[serde(default = "default_true")]
+    pub kubevirt_enabled: bool,
+
+    /// Thresholds for in-guest filesystems reported by qemu-guest-agent
+    #[serde(default = "default_disk_warning")]
+    pub guest_fs_warning_threshold: f64,
+
+    #[serde(default = "default_disk_critical")]
+    pub guest_fs_critical_threshold: f64,
+
+    /// Percent of a namespace ResourceQuota in use before alerting
+    #[serde(default = "default_quota_warning")]
+    pub quota_warning_threshold: f64,
+
+    #[serde(default = "default_quota_critical")]
+    pub quota_critical_threshold: f64,
+
+    /// Milliseconds the API server may take to answer /healthz or a small
+    /// list before alerting
+    #[serde(default = "default_api_latency_warning")]
+    pub api_latency_warning_threshold: f64,
+
+    #[serde(default = "default_api_latency_critical")]
+    pub api_latency_critical_threshold: f64,
+
+    /// Milliseconds per block device request, sustained for 30 seconds,
+    /// before alerting
+    #[serde(default = "default_disk_latency_warning")]
+    pub disk_latency_warning_threshold: f64,
+
+    #[serde(default = "default_disk_latency_critical")]
+    pub disk_latency_critical_threshold: f64,
+
+    /// Seconds a guest's clock (read through qemu-guest-agent) may be off
+    /// from the host's before alerting; Kerberos refuses 5 minutes of skew
+    #[serde(default = "default_guest_clock_warning")]
+    pub guest_clock_warning_secs: f64,
+
+    #[serde(default = "default_guest_clock_critical")]
+    pub guest_clock_critical_secs: f64,
+
+    /// Percent of etcd's backend quota the database may take up before
+    /// alerting; writes to the cluster fail once it is full
+    #[serde(default = "default_etcd_db_warning")]
+    pub etcd_db_warning_threshold: f64,
+
+    #[serde(default = "default_etcd_db_critical")]
+    pub etcd_db_critical_threshold: f64,
+
+    /// etcd leader elections within an hour before alerting
+    #[serde(default = "default_etcd_leader_changes_per_hour")]
+    pub etcd_leader_changes_per_hour: u64,
+
+    /// Restarts of one pod within an hour before alerting; pods in
+    /// CrashLoopBackOff or an image pull backoff alert regardless
+    #[serde(default = "default_pod_restarts_per_hour")]
+    pub pod_restarts_per_hour: u64,
+
+    /// Hours until a filesystem is forecast to be full before alerting
+    #[serde(default = "default_disk_full_warning_hours")]
+    pub disk_full_warning_hours: f64,
+
+    #[serde(default = "default_disk_full_critical_hours")]
+    pub disk_full_critical_hours: f64,
+
+    /// Percent of a file descriptor limit in use (system-wide or a watched
+    /// process's) before alerting
+    #[serde(default = "default_fd_warning")]
+    pub fd_warning_threshold: f64,
+
+    #[serde(default = "default_fd_critical")]
+    pub fd_critical_threshold: f64,
+
+    /// Percent of the ephemeral port range in use before alerting
+    #[serde(default = "default_ephemeral_port_warning")]
+    pub ephemeral_port_warning_threshold: f64,
+
+    #[serde(default = "default_ephemeral_port_critical")]
+    pub ephemeral_port_critical_threshold: f64,
+
+    /// Percent of the PID space (kernel.pid_max, or kernel.threads-max when
+    /// lower) taken by threads before alerting
+    #[serde(default = "default_pid_warning")]
+    pub pid_warning_threshold: f64,
+
+    #[serde(default = "default_pid_critical")]
+    pub pid_critical_threshold: f64,
+
+    /// Bits of entropy left in the kernel's random pool below which to
+    /// alert; kernels since 5.18 always report a full pool
+    #[serde(default = "default_entropy_warning_bits")]
+    pub entropy_warning_bits: u64,
+
+    #[serde(default = "default_entropy_critical_bits")]
+    pub entropy_critical_bits: u64,
+
+    /// Percent of time a VM's vCPUs spend waiting for a physical CPU,
+    /// sustained over the last 15 host map scans, before alerting
+    #[serde(default = "default_sched_wait_warning")]
+    pub sched_wait_warning_threshold: f64,
+
+    #[serde(default = "default_sched_wait_critical")]
+    pub sched_wait_critical_threshold: f64,
+
+    /// Percent of CFS periods a VM's cgroup is throttled by its CPU limit,
+    /// sustained the same way
+    #[serde(default = "default_cpu_throttle_warning")]
+    pub cpu_throttle_warning_threshold: f64,
+
+    #[serde(default = "default_cpu_throttle_critical")]
+    pub cpu_throttle_critical_threshold: f64,
+
+    /// Busy cores clocked below this percent of the base clock count as
+    /// throttled by heat or a power limit
+    #[serde(default = "default_freq_throttle_percent")]
+    pub freq_throttle_percent: f64,
+
+    /// Minutes the host may stay throttled before alerting
+    #[serde(default = "default_freq_throttle_mins")]
+    pub freq_throttle_mins: u64,
+
+    /// Learn per-metric baselines and alert on statistically significant deviations
+    #[serde(default)]
+    pub anomaly_detection: bool,
+
+    #[serde(default = "default_anomaly_sigma")]
+    pub anomaly_sigma: f64,
+
+    #[serde(default = "default_anomaly_min_samples")]
+    pub anomaly_min_samples: u64,
+
+    /// Where learned baselines are stored (defaults to the state directory)
+    #[serde(default)]
+    pub baseline_path: Option<String>,
+
+    /// Where resolved and dismissed alerts are kept for the statistics view
+    /// (defaults to the state directory)
+    #[serde(default)]
+    pub history_path: Option<String>,
+
+    /// Consecutive failed live migrations to or from a node before alerting
+    #[serde(default = "default_migration_failure_threshold")]
+    pub migration_failure_threshold: u32,
+
+    /// Minutes a CDI DataVolume import or clone may go without progress
+    /// before it is reported stuck
+    #[serde(default = "default_import_stall_mins")]
+    pub import_stall_mins: u64,
+
+    /// Times a VM's usual (median) boot to Running it may take before its
+    /// boot is reported slow...
+    #[serde(default = "default_boot_slow_factor")]
+    pub boot_slow_factor: f64,
+
+    /// ...and seconds it must run over the usual by, so VMs that boot in a
+    /// few seconds aren't flagged for a few more
+    #[serde(default = "default_boot_slow_min_secs")]
+    pub boot_slow_min_secs: u64,
+
+    /// Minutes a hardware error or OOM kill in the kernel log keeps its alert raised
+    #[serde(default = "default_kernel_event_window_mins")]
+    pub kernel_event_window_mins: u64,
+
+    /// Minutes an IP claimed by two MACs on a watched bridge stays in conflict,
+    /// and the window DHCP leases are counted over
+    #[serde(default = "default_ip_conflict_window_mins")]
+    pub ip_conflict_window_mins: u64,
+
+    /// Minutes a core dump of a `crashes.processes` binary keeps its alert raised
+    #[serde(default = "default_crash_window_mins")]
+    pub crash_window_mins: u64,
+
+    /// DHCP leases handed to one MAC within the window before alerting
+    #[serde(default = "default_dhcp_churn_threshold")]
+    pub dhcp_churn_threshold: u32,
+
+    /// Minutes 'z' in the alert panel silences an alert's source for
+    #[serde(default = "default_snooze_mins")]
+    pub snooze_mins: u64,
+
+    /// Minutes either side of an alert's trigger shown by 'l' in the alert panel
+    #[serde(default = "default_related_logs_mins")]
+    pub related_logs_mins: u64,
+
+    /// Seconds a dismissed or resolved alert stays suppressed at the same severity
+    #[serde(default = "default_dedup_window_secs")]
+    pub dedup_window_secs: u64,
+
+    /// Most new alerts raised a minute; the rest wait, folded into one
+    /// digest alert, until there is room. 0 for no limit
+    #[serde(default = "default_rate_limit_per_minute")]
+    pub rate_limit_per_minute: u32,
+
+    /// Most new alerts a minute from any one rule, held back the same way
+    #[serde(default = "default_rule_rate_limit_per_minute")]
+    pub rule_rate_limit_per_minute: u32,
+
+    /// Per-host-class thresholds, merged over the values above in order
+    #[serde(default)]
+    pub overrides: Vec<AlertOverride>,
+
+    /// Runbook links and remediation hints, the first match wins
+    #[serde(default)]
+    pub runbooks: Vec<AlertRunbook>,
+
+    /// Automatic responses to matching alerts; every match runs
+    #[serde(default)]
+    pub actions: Vec<AlertAction>,
+}
+
+/// `[[alerts.runbooks]]`: where the procedure for an alert is documented.
+/// Matches on the alert source ("cpu", "fds-*", ...) and/or title.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertRunbook {
+    /// Alert source, `*` matches any run of characters
+    #[serde(default)]
+    pub source: Option<String>,
+
+    /// Alert title, `*` matches any run of characters
+    #[serde(default)]
+    pub title: Option<String>,
+
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Short first step shown with the alert
+    #[serde(default)]
+    pub remediation: Option<String>,
+}
+
+impl AlertRunbook {
+    /// A runbook without a source or title pattern applies nowhere
+    pub fn matches(&self, source: &str, title: &str) -> bool {
+        if self.source.is_none() && self.title.is_none() {
+            return false;
+        }
+        self.source.as_deref().is_none_or(|pattern| wildcard_match(pattern, source))
+            && self.title.as_deref().is_none_or(|pattern| wildcard_match(pattern, title))
+    }
+}
+
+/// `[[alerts.actions]]`: what to do automatically when a matching alert
+/// fires. Matches like a runbook; exactly one of `restart_unit`,
+/// `rollout_restart` and `command` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertAction {
+    /// Alert source, `*` matches any run of characters
+    #[serde(default)]
+    pub source: Option<String>,
+
+    /// Alert title, `*` matches any run of characters
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// systemd unit to `systemctl restart`
+    #[serde(default)]
+    pub restart_unit: Option<String>,
+
+    /// Workload to `kubectl rollout restart`, as "namespace/kind/name"
+    #[serde(default)]
+    pub rollout_restart: Option<String>,
+
+    /// Shell command, run with ALERT_SOURCE, ALERT_TITLE and ALERT_LEVEL set
+    #[serde(default)]
+    pub command: Option<String>,
+
+    /// Most runs in any hour; further firings are only recorded
+    #[serde(default = "default_action_max_per_hour")]
+    pub max_per_hour: u32,
+}
+
+impl AlertAction {
+    /// An action without a source or title pattern applies nowhere
+    pub fn matches(&self, source: &str, title: &str) -> bool {
+        if self.source.is_none() && self.title.is_none() {
+            return false;
+        }
+        self.source.as_deref().is_none_or(|pattern| wildcard_match(pattern, source))
+            && self.title.as_deref().is_none_or(|pattern| wildcard_match(pattern, title))
+    }
+
+    /// How many of `restart_unit`, `rollout_restart` and `command` are set
+    pub fn kinds_set(&self) -> usize {
+        [self.restart_unit.is_some(), self.rollout_restart.is_some(), self.command.is_some()]
+            .iter()
+            .filter(|set| **set)
+            .count()
+    }
+}
+
+/// `[[alerts.overrides]]`: thresholds for nodes matching a hostname pattern
+/// and/or node labels. Unset thresholds keep the global value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertOverride {
+    /// Hostname, `*` matches any run of characters ("storage-*")
+    #[serde(default)]
+    pub hostname: Option<String>,
+
+    /// Kubernetes node labels that must all be present with these values
+    #[serde(default)]
+    pub labels: std::collections::BTreeMap<String, String>,
+
+    pub cpu_warning_threshold: Option<f64>,
+    pub cpu_critical_threshold: Option<f64>,
+    pub memory_warning_threshold: Option<f64>,
+    pub memory_critical_threshold: Option<f64>,
+    pub disk_warning_threshold: Option<f64>,
+    pub disk_critical_threshold: Option<f64>,
+    pub load_warning_threshold: Option<f64>,
+    pub load_critical_threshold: Option<f64>,
+    pub guest_fs_warning_threshold: Option<f64>,
+    pub guest_fs_critical_threshold: Option<f64>,
+    pub quota_warning_threshold: Option<f64>,
+    pub quota_critical_threshold: Option<f64>,
+    pub api_latency_warning_threshold: Option<f64>,
+    pub api_latency_critical_threshold: Option<f64>,
+    pub disk_latency_warning_threshold: Option<f64>,
+    pub disk_latency_critical_threshold: Option<f64>,
+    pub guest_clock_warning_secs: Option<f64>,
+    pub guest_clock_critical_secs: Option<f64>,
+    pub etcd_db_warning_threshold: Option<f64>,
+    pub etcd_db_critical_threshold: Option<f64>,
+    pub disk_full_warning_hours: Option<f64>,
+    pub disk_full_critical_hours: Option<f64>,
+    pub fd_warning_threshold: Option<f64>,
+    pub fd_critical_threshold: Option<f64>,
+    pub ephemeral_port_warning_threshold: Option<f64>,
+    pub ephemeral_port_critical_threshold: Option<f64>,
+    pub pid_warning_threshold: Option<f64>,
+    pub pid_critical_threshold: Option<f64>,
+    pub entropy_warning_bits: Option<u64>,
+    pub entropy_critical_bits: Option<u64>,
+    pub sched_wait_warning_threshold: Option<f64>,
+    pub sched_wait_critical_threshold: Option<f64>,
+    pub cpu_throttle_warning_threshold: Option<f64>,
+    pub cpu_throttle_critical_threshold: Option<f64>,
+}
+
+impl AlertOverride {
+    /// An override without a hostname or labels applies nowhere
+    pub fn matches(&self, hostname: &str, labels: &std::collections::BTreeMap<String, String>) -> bool {
+        if self.hostname.is_none() && self.labels.is_empty() {
+            return false;
+        }
+        self.hostname.as_deref().is_none_or(|pattern| wildcard_match(pattern, hostname))
+            && self.labels.iter().all(|(key, value)| labels.get(key) == Some(value))
+    }
+}
+
+impl AlertsConfig {
+    /// Thresholds for a node: every matching override applied in file order
+    pub fn for_node(&self, hostname: &str, labels: &std::collections::BTreeMap<String, String>) -> AlertsConfig {
+        let mut merged = self.clone();
+        for ov in self.overrides.iter().filter(|ov| ov.matches(hostname, labels)) {
+            merged.apply(ov);
+        }
+        merged
+    }
+
+    /// Overwrite the thresholds an override sets
+    pub fn apply(&mut self, ov: &AlertOverride) {
+        let fields = [
+            (&mut self.cpu_warning_threshold, ov.cpu_warning_threshold),
+            (&mut self.cpu_critical_threshold, ov.cpu_critical_threshold),
+            (&mut self.memory_warning_threshold, ov.memory_warning_threshold),
+            (&mut self.memory_critical_threshold, ov.memory_critical_threshold),
+            (&mut self.disk_warning_threshold, ov.disk_warning_threshold),
+            (&mut self.disk_critical_threshold, ov.disk_critical_threshold),
+            (&mut self.load_warning_threshold, ov.load_warning_threshold),
+            (&mut self.load_critical_threshold, ov.load_critical_threshold),
+            (&mut self.guest_fs_warning_threshold, ov.guest_fs_warning_threshold),
+            (&mut self.guest_fs_critical_threshold, ov.guest_fs_critical_threshold),
+            (&mut self.quota_warning_threshold, ov.quota_warning_threshold),
+            (&mut self.quota_critical_threshold, ov.quota_critical_threshold),
+            (&mut self.api_latency_warning_threshold, ov.api_latency_warning_threshold),
+            (&mut self.api_latency_critical_threshold, ov.api_latency_critical_threshold),
+            (&mut self.disk_latency_warning_threshold, ov.disk_latency_warning_threshold),
+            (&mut self.disk_latency_critical_threshold, ov.disk_latency_critical_threshold),
+            (&mut self.guest_clock_warning_secs, ov.guest_clock_warning_secs),
+            (&mut self.guest_clock_critical_secs, ov.guest_clock_critical_secs),
+            (&mut self.etcd_db_warning_threshold, ov.etcd_db_warning_threshold),
+            (&mut self.etcd_db_critical_threshold, ov.etcd_db_critical_threshold),
+            (&mut self.disk_full_warning_hours, ov.disk_full_warning_hours),
+            (&mut self.disk_full_critical_hours, ov.disk_full_critical_hours),
+            (&mut self.fd_warning_threshold, ov.fd_warning_threshold),
+            (&mut self.fd_critical_threshold, ov.fd_critical_threshold),
+            (&mut self.ephemeral_port_warning_threshold, ov.ephemeral_port_warning_threshold),
+            (&mut self.ephemeral_port_critical_threshold, ov.ephemeral_port_critical_threshold),
+            (&mut self.pid_warning_threshold, ov.pid_warning_threshold),
+            (&mut self.pid_critical_threshold, ov.pid_critical_threshold),
+            (&mut self.sched_wait_warning_threshold, ov.sched_wait_warning_threshold),
+            (&mut self.sched_wait_critical_threshold, ov.sched_wait_critical_threshold),
+            (&mut self.cpu_throttle_warning_threshold, ov.cpu_throttle_warning_threshold),
+            (&mut self.cpu_throttle_critical_threshold, ov.cpu_throttle_critical_threshold),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+        for (field, value) in [
+            (&mut self.entropy_warning_bits, ov.entropy_warning_bits),
+            (&mut self.entropy_critical_bits, ov.entropy_critical_bits),
+        ] {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+    }
+}
+
+/// Glob match where `*` is the only special character
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No '*': exact match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    #[serde(default)]
+    pub email: EmailConfig,
+
+    #[serde(default)]
+    pub pagerduty: PagerDutyConfig,
+
+    #[serde(default)]
+    pub alertmanager: AlertmanagerConfig,
+
+    /// Which channels alerts go to by category and level; the first
+    /// matching route decides and unmatched alerts go to every channel
+    #[serde(default)]
+    pub routes: Vec<NotificationRoute>,
+}
+
+/// `[secrets]`: credentials kept out of the config, referenced from it by
+/// key name (`password_secret = "smtp-password"`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretsConfig {
+    /// Where a key is looked up, in order: "credential" (a systemd
+    /// `LoadCredential=` file), "env" (`HYPERVISOR_TUI_SECRET_<KEY>`) and
+    /// "file"
+    #[serde(default = "default_secret_providers")]
+    pub providers: Vec<String>,
+
+    /// sops- or age-encrypted TOML or JSON of key = value secrets; files
+    /// ending in `.age` are decrypted with age, others with sops
+    #[serde(default)]
+    pub file: Option<String>,
+
+    /// age identity for an `.age` file (sops finds its own keys)
+    #[serde(default)]
+    pub age_identity: Option<String>,
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self {
+            providers: default_secret_providers(),
+            file: None,
+            age_identity: None,
+        }
+    }
+}
+
+fn default_secret_providers() -> Vec<String> {
+    vec!["credential".to_string(), "env".to_string(), "file".to_string()]
+}
+
+/// Sends the alerts it matches to `channels` only. Each channel's own
+/// `levels` and recipients still apply on top.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationRoute {
+    /// Alert categories matched, e.g. "kubevirt"; empty matches any
+    #[serde(default)]
+    pub categories: Vec<String>,
+
+    /// Alert levels matched: "critical", "error", "warning"; empty
+    /// matches any
+    #[serde(default)]
+    pub levels: Vec<String>,
+
+    /// "email", "pagerduty", "alertmanager"; empty drops what matches
+    #[serde(default)]
+    pub channels: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagerDutyConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Events API v2 integration (routing) key
+    #[serde(default)]
+    pub routing_key: Option<String>,
+
+    /// Read the routing key from a file (e.g. an agenix/sops secret)
+    #[serde(default)]
+    pub routing_key_file: Option<String>,
+
+    /// Name of the routing key in `[secrets]`
+    #[serde(default)]
+    pub routing_key_secret: Option<String>,
+
+    /// Alert levels that trigger incidents: "critical", "error", "warning"
+    #[serde(default = "default_pagerduty_levels")]
+    pub levels: Vec<String>,
+
+    #[serde(default = "default_pagerduty_url")]
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertmanagerConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Base URL, e.g. "http://alertmanager:9093"
+    #[serde(default)]
+    pub url: String,
+
+    /// Name of the base URL in `[secrets]`, for URLs carrying credentials
+    #[serde(default)]
+    pub url_secret: Option<String>,
+
+    /// Alert levels forwarded: "critical", "error", "warning"
+    #[serde(default = "default_alertmanager_levels")]
+    pub levels: Vec<String>,
+
+    /// Firing alerts are re-posted this often so Alertmanager doesn't
+    /// resolve them on its own after its resolve_timeout
+    #[serde(default = "default_alertmanager_resend_secs")]
+    pub resend_interval_secs: u64,
+
+    /// Bearer token file for an authenticating proxy in front of Alertmanager
+    #[serde(default)]
+    pub bearer_token_file: Option<String>,
+
+    /// Name of the bearer token in `[secrets]`
+    #[serde(default)]
+    pub bearer_token_secret: Option<String>,
+
+    /// Extra labels attached to every alert (e.g. cluster, datacenter)
+    #[serde(default)]
+    pub labels: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub smtp_server: String,
+
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+
+    /// "starttls", "tls" (implicit TLS) or "none"
+    #[serde(default = "default_smtp_security")]
+    pub security: String,
+
+    #[serde(default)]
+    pub username: Option<String>,
+
+    #[serde(default)]
+    pub password: Option<String>,
+
+    /// Read the SMTP password from a file (e.g. an agenix/sops secret)
+    #[serde(default)]
+    pub password_file: Option<String>,
+
+    /// Name of the SMTP password in `[secrets]`
+    #[serde(default)]
+    pub password_secret: Option<String>,
+
+    #[serde(default = "default_email_from")]
+    pub from: String,
+
+    /// Critical alerts are sent immediately
+    #[serde(default)]
+    pub critical_recipients: Vec<String>,
+
+    /// Error alerts are sent immediately
+    #[serde(default)]
+    pub error_recipients: Vec<String>,
+
+    /// Warning alerts are batched into periodic digests
+    #[serde(default)]
+    pub warning_recipients: Vec<String>,
+
+    #[serde(default = "default_digest_interval_secs")]
+    pub digest_interval_secs: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            general: GeneralConfig::default(),
+            kubernetes: KubernetesConfig::default(),
+            logging: LoggingConfig::default(),
+            network: NetworkConfig::default(),
+            storage: StorageConfig::default(),
+            image_catalog: ImageCatalogConfig::default(),
+            display: DisplayConfig::default(),
+            dashboard: DashboardLayout::default(),
+            alerts: AlertsConfig::default(),
+            health: HealthConfig::default(),
+            notifications: NotificationsConfig::default(),
+            secrets: SecretsConfig::default(),
+            web: WebConfig::default(),
+            receiver: ReceiverConfig::default(),
+            agent: AgentConfig::default(),
+            remote_agents: RemoteAgentsConfig::default(),
+            upgrade: UpgradeConfig::default(),
+            update_check: UpdateCheckConfig::default(),
+            sysctl: SysctlConfig::default(),
+            report: ReportConfig::default(),
+            selftest: SelfTestConfig::default(),
+            tenants: TenantsConfig::default(),
+            crashes: CrashConfig::default(),
+            host_profile: HostProfileConfig::default(),
+            memory_leaks: MemoryLeakConfig::default(),
+            virtualization: VirtualizationConfig::default(),
+            compare: CompareConfig::default(),
+            prometheus: PrometheusConfig::default(),
+            custom_commands: Vec::new(),
+            computed_metrics: Vec::new(),
+            views: Vec::new(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct KubernetesConfig {
-    #[serde(default = "default_kubeconfig_path")]
-    pub kubeconfig_path: String,
-
-    #[serde(default)]
-    pub api_server: Option<String>,
+impl Default for WebConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_web_listen(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LoggingConfig {
-    #[serde(default = "default_services")]
-    pub services: Vec<String>,
+impl Default for HostProfileConfig {
+    fn default() -> Self {
+        Self {
+            kernel: None,
+            sysctls: BTreeMap::new(),
+            services: Vec::new(),
+            hugepages: BTreeMap::new(),
+            nic_count: None,
+            nic_speed_mbps: None,
+            cpu_flags: Vec::new(),
+            check_interval_secs: default_host_profile_interval_secs(),
+        }
+    }
+}
 
-    #[serde(default = "default_level_filter")]
-    pub level_filter: String,
+impl Default for MemoryLeakConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            processes: default_leak_processes(),
+            sample_interval_secs: default_leak_sample_interval_secs(),
+            window_hours: default_leak_window_hours(),
+            growth_mb_per_hour: default_leak_growth_mb_per_hour(),
+            limits_mb: BTreeMap::new(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct NetworkConfig {
-    #[serde(default)]
-    pub interfaces: Vec<String>,
+impl Default for ReceiverConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_receiver_listen(),
+            token: None,
+            token_file: None,
+            expire_mins: default_receiver_expire_mins(),
+        }
+    }
+}
 
-    #[serde(default = "default_true")]
-    pub show_bridges: bool,
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self {
+            listen: default_agent_listen(),
+            cert_file: None,
+            key_file: None,
+            client_ca_file: None,
+            interval_secs: default_agent_interval_secs(),
+        }
+    }
+}
 
-    #[serde(default = "default_true")]
-    pub show_virtual: bool,
+impl Default for RemoteAgentsConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            cert_file: None,
+            key_file: None,
+            ca_file: None,
+            interval_secs: default_remote_agents_interval_secs(),
+            failure_threshold: default_remote_agents_failure_threshold(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DisplayConfig {
-    #[serde(default = "default_theme")]
-    pub theme: String,
+impl Default for KubeApiConfig {
+    fn default() -> Self {
+        Self {
+            qps: default_api_qps(),
+            burst: default_api_burst(),
+            cache_ttl_secs: default_api_cache_ttl_secs(),
+            ttl_secs: std::collections::BTreeMap::new(),
+        }
+    }
+}
 
-    #[serde(default = "default_true")]
-    pub show_graphs: bool,
+impl Default for ConsoleConfig {
+    fn default() -> Self {
+        Self {
+            capture_all: false,
+            buffer_lines: default_console_buffer_lines(),
+            retry_secs: default_console_retry_secs(),
+            vnc_address: default_vnc_address(),
+            vnc_first_port: default_vnc_first_port(),
+        }
+    }
+}
 
-    #[serde(default = "default_animation_refresh")]
-    pub animation_refresh: u64,
+impl Default for GuestClockConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_guest_clock_interval_secs(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AlertsConfig {
-    #[serde(default = "default_true")]
-    pub enabled: bool,
+impl Default for GuestFilesystemsConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_guest_filesystems_interval_secs(),
+        }
+    }
+}
 
-    #[serde(default = "default_cpu_warning")]
-    pub cpu_warning_threshold: f64,
+impl Default for LocalModeConfig {
+    fn default() -> Self {
+        Self {
+            after_failures: default_local_mode_after_failures(),
+            emergency_actions: true,
+        }
+    }
+}
 
-    #[serde(default = "default_cpu_critical")]
-    pub cpu_critical_threshold: f64,
+impl Default for EtcdConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            metrics_url: None,
+            ca_file: None,
+            cert_file: None,
+            key_file: None,
+        }
+    }
+}
 
-    #[serde(default = "default_memory_warning")]
-    pub memory_warning_threshold: f64,
+impl Default for EvacuationConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: default_evacuation_concurrency(),
+            stuck_secs: default_evacuation_stuck_secs(),
+        }
+    }
+}
 
-    #[serde(default = "default_memory_critical")]
-    pub memory_critical_threshold: f64,
+impl Default for UpgradeConfig {
+    fn default() -> Self {
+        Self {
+            command: default_upgrade_command(),
+            flake: None,
+            extra_args: Vec::new(),
+            gc_command: default_gc_command(),
+            nix_env_command: default_nix_env_command(),
+            gc_retention: default_gc_retention(),
+        }
+    }
+}
 
-    #[serde(default = "default_disk_warning")]
-    pub disk_warning_threshold: f64,
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            metrics_weight: default_health_metrics_weight(),
+            alerts_weight: default_health_alerts_weight(),
+            services_weight: default_health_services_weight(),
+            cluster_weight: default_health_cluster_weight(),
+            degraded_below: default_health_degraded_below(),
+            critical_below: default_health_critical_below(),
+        }
+    }
+}
 
-    #[serde(default = "default_disk_critical")]
-    pub disk_critical_threshold: f64,
+impl Default for UpdateCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: default_update_check_url(),
+            interval_hours: default_update_check_interval_hours(),
+        }
+    }
+}
 
-    #[serde(default = "default_load_warning")]
-    pub load_warning_threshold: f64,
+impl Default for SysctlConfig {
+    fn default() -> Self {
+        Self {
+            watch: default_sysctl_watch(),
+            expected: default_sysctl_expected(),
+        }
+    }
+}
 
-    #[serde(default = "default_load_critical")]
-    pub load_critical_threshold: f64,
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            image_dirs: default_image_dirs(),
+            local_path_dirs: default_local_path_dirs(),
+            filesystems: Vec::new(),
+            datastore_devices: Vec::new(),
+        }
+    }
+}
 
-    #[serde(default = "default_true")]
-    pub kubernetes_enabled: bool,
+impl Default for ImageCatalogConfig {
+    fn default() -> Self {
+        Self {
+            download_dir: None,
+            namespace: default_catalog_namespace(),
+            storage_class: None,
+            images: default_catalog_images(),
+        }
+    }
+}
 
-    #[serde(default = "default_true")]
-    pub kubevirt_enabled: bool,
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            smtp_server: String::new(),
+            smtp_port: default_smtp_port(),
+            security: default_smtp_security(),
+            username: None,
+            password: None,
+            password_file: None,
+            password_secret: None,
+            from: default_email_from(),
+            critical_recipients: Vec::new(),
+            error_recipients: Vec::new(),
+            warning_recipients: Vec::new(),
+            digest_interval_secs: default_digest_interval_secs(),
+        }
+    }
 }
 
-impl Default for Config {
+impl Default for PagerDutyConfig {
     fn default() -> Self {
         Self {
-            general: GeneralConfig::default(),
-            kubernetes: KubernetesConfig::default(),
-            logging: LoggingConfig::default(),
-            network: NetworkConfig::default(),
-            display: DisplayConfig::default(),
-            alerts: AlertsConfig::default(),
+            enabled: false,
+            routing_key: None,
+            routing_key_file: None,
+            routing_key_secret: None,
+            levels: default_pagerduty_levels(),
+            url: default_pagerduty_url(),
         }
     }
 }
 
-impl Default for GeneralConfig {
+impl Default for AlertmanagerConfig {
     fn default() -> Self {
         Self {
-            refresh_interval: default_refresh_interval(),
-            log_buffer_size: default_log_buffer_size(),
+            enabled: false,
+            url: String::new(),
+            url_secret: None,
+            levels: default_alertmanager_levels(),
+            resend_interval_secs: default_alertmanager_resend_secs(),
+            bearer_token_file: None,
+            bearer_token_secret: None,
+            labels: Default::default(),
         }
     }
 }
 
-impl Default for KubernetesConfig {
+impl Default for GeneralConfig {
     fn default() -> Self {
         Self {
-            kubeconfig_path: default_kubeconfig_path(),
-            api_server: None,
+            refresh_interval: default_refresh_interval(),
+            log_buffer_size: default_log_buffer_size(),
+            log_buffer_max_mb: default_log_buffer_max_mb(),
+            collector_timeout_secs: default_collector_timeout_secs(),
+            restart_on_panic: false,
+            read_only: false,
+            watched_processes: default_watched_processes(),
+            telemetry_interval_secs: default_telemetry_interval_secs(),
+            idle_refresh_secs: default_idle_refresh_secs(),
         }
     }
 }
@@ -145,8 +2406,16 @@ impl Default for KubernetesConfig {
 impl Default for LoggingConfig {
     fn default() -> Self {
         Self {
-            services: default_services(),
+            services: None,
             level_filter: default_level_filter(),
+            collapse_repeats: true,
+            context_lines: default_context_lines(),
+            archive_max_lines: default_archive_max_lines(),
+            search_lines: default_search_lines(),
+            highlights: Vec::new(),
+            redactions: Vec::new(),
+            kernel_log: true,
+            forward: Vec::new(),
         }
     }
 }
@@ -157,6 +2426,120 @@ impl Default for NetworkConfig {
             interfaces: Vec::new(),
             show_bridges: true,
             show_virtual: true,
+            show_loopback: false,
+            dns_checks_enabled: true,
+            dns_host_names: default_dns_host_names(),
+            dns_cluster_names: default_dns_cluster_names(),
+            coredns_address: None,
+            dns_timeout_ms: default_dns_timeout_ms(),
+            dns_failure_threshold: default_dns_failure_threshold(),
+            route_checks_enabled: true,
+            gateway_timeout_ms: default_gateway_timeout_ms(),
+            gateway_failure_threshold: default_gateway_failure_threshold(),
+            route_flap_threshold: default_route_flap_threshold(),
+            route_flap_window_minutes: default_route_flap_window_minutes(),
+            registries: Vec::new(),
+            registry_timeout_ms: default_registry_timeout_ms(),
+            registry_failure_threshold: default_registry_failure_threshold(),
+            image_pull_failure_threshold: default_image_pull_failure_threshold(),
+            vip_services: Vec::new(),
+            vip_timeout_ms: default_vip_timeout_ms(),
+            vip_failure_threshold: default_vip_failure_threshold(),
+            conflict_watch_bridges: Vec::new(),
+            watched_ports: default_watched_ports(),
+            expected_listeners: default_expected_listeners(),
+            vm_namespaces: false,
+            interface_labels: std::collections::BTreeMap::new(),
+            expected_mtu: None,
+        }
+    }
+}
+
+impl Default for QmpConfig {
+    fn default() -> Self {
+        Self {
+            hosts: default_qmp_hosts(),
+            sockets: default_qmp_sockets(),
+            ssh_options: Vec::new(),
+        }
+    }
+}
+
+impl Default for CrashConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            processes: default_crash_processes(),
+            crash_dirs: default_crash_dirs(),
+            scan_interval_secs: default_crash_scan_interval_secs(),
+            history_days: default_crash_history_days(),
+        }
+    }
+}
+
+impl Default for PrometheusConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            targets: Vec::new(),
+            bearer_token_file: None,
+            bearer_token_secret: None,
+            ca_file: None,
+            interval_secs: default_prometheus_interval_secs(),
+            api_latency_query: default_prometheus_api_latency_query(),
+            etcd: true,
+            vm_cpu_query: default_prometheus_vm_cpu_query(),
+            vm_memory_query: default_prometheus_vm_memory_query(),
+            queries: BTreeMap::new(),
+            metrics: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for CompareConfig {
+    fn default() -> Self {
+        Self {
+            snapshot_interval_mins: default_snapshot_interval_mins(),
+            retention_hours: default_snapshot_retention_hours(),
+            baseline_mins: default_compare_baseline_mins(),
+            path: None,
+        }
+    }
+}
+
+impl Default for TenantsConfig {
+    fn default() -> Self {
+        Self {
+            label: None,
+            growth_alert_percent: default_tenant_growth_percent(),
+            growth_window_hours: default_tenant_growth_window_hours(),
+            limits: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for SelfTestConfig {
+    fn default() -> Self {
+        Self {
+            namespace: default_selftest_namespace(),
+            node: None,
+            image: default_selftest_image(),
+            network: None,
+            storage_class: None,
+            storage_image: default_selftest_storage_image(),
+            step_timeout_secs: default_selftest_step_timeout_secs(),
+        }
+    }
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            sections: default_report_sections(),
+            max_alerts: default_report_max_alerts(),
+            dir: None,
+            exit_summary: false,
+            exit_sections: default_report_exit_sections(),
         }
     }
 }
@@ -164,9 +2547,20 @@ impl Default for NetworkConfig {
 impl Default for DisplayConfig {
     fn default() -> Self {
         Self {
-            theme: default_theme(),
+            theme: Theme::default(),
             show_graphs: true,
             animation_refresh: default_animation_refresh(),
+            accessibility: false,
+            group_by_label: None,
+            cpu_smoothing_secs: default_cpu_smoothing_secs(),
+            alert_styles: AlertStylesConfig::default(),
+            remember_state: true,
+            footer_log_rates: true,
+            banner_cycle_secs: default_banner_cycle_secs(),
+            locale: Locale::default(),
+            time_zone: default_time_zone(),
+            timestamp_format: default_timestamp_format(),
+            header: default_header_widgets(),
         }
     }
 }
@@ -175,6 +2569,9 @@ impl Default for AlertsConfig {
     fn default() -> Self {
         Self {
             enabled: true,
+            background_interval_secs: default_alert_background_interval_secs(),
+            profile: None,
+            profiles_dir: None,
             cpu_warning_threshold: default_cpu_warning(),
             cpu_critical_threshold: default_cpu_critical(),
             memory_warning_threshold: default_memory_warning(),
@@ -185,18 +2582,307 @@ impl Default for AlertsConfig {
             load_critical_threshold: default_load_critical(),
             kubernetes_enabled: true,
             kubevirt_enabled: true,
+            guest_fs_warning_threshold: default_disk_warning(),
+            guest_fs_critical_threshold: default_disk_critical(),
+            quota_warning_threshold: default_quota_warning(),
+            quota_critical_threshold: default_quota_critical(),
+            api_latency_warning_threshold: default_api_latency_warning(),
+            api_latency_critical_threshold: default_api_latency_critical(),
+            disk_latency_warning_threshold: default_disk_latency_warning(),
+            disk_latency_critical_threshold: default_disk_latency_critical(),
+            guest_clock_warning_secs: default_guest_clock_warning(),
+            guest_clock_critical_secs: default_guest_clock_critical(),
+            etcd_db_warning_threshold: default_etcd_db_warning(),
+            etcd_db_critical_threshold: default_etcd_db_critical(),
+            etcd_leader_changes_per_hour: default_etcd_leader_changes_per_hour(),
+            pod_restarts_per_hour: default_pod_restarts_per_hour(),
+            disk_full_warning_hours: default_disk_full_warning_hours(),
+            disk_full_critical_hours: default_disk_full_critical_hours(),
+            fd_warning_threshold: default_fd_warning(),
+            fd_critical_threshold: default_fd_critical(),
+            ephemeral_port_warning_threshold: default_ephemeral_port_warning(),
+            ephemeral_port_critical_threshold: default_ephemeral_port_critical(),
+            pid_warning_threshold: default_pid_warning(),
+            pid_critical_threshold: default_pid_critical(),
+            entropy_warning_bits: default_entropy_warning_bits(),
+            entropy_critical_bits: default_entropy_critical_bits(),
+            sched_wait_warning_threshold: default_sched_wait_warning(),
+            sched_wait_critical_threshold: default_sched_wait_critical(),
+            cpu_throttle_warning_threshold: default_cpu_throttle_warning(),
+            cpu_throttle_critical_threshold: default_cpu_throttle_critical(),
+            freq_throttle_percent: default_freq_throttle_percent(),
+            freq_throttle_mins: default_freq_throttle_mins(),
+            anomaly_detection: false,
+            anomaly_sigma: default_anomaly_sigma(),
+            anomaly_min_samples: default_anomaly_min_samples(),
+            baseline_path: None,
+            history_path: None,
+            migration_failure_threshold: default_migration_failure_threshold(),
+            import_stall_mins: default_import_stall_mins(),
+            boot_slow_factor: default_boot_slow_factor(),
+            boot_slow_min_secs: default_boot_slow_min_secs(),
+            kernel_event_window_mins: default_kernel_event_window_mins(),
+            ip_conflict_window_mins: default_ip_conflict_window_mins(),
+            crash_window_mins: default_crash_window_mins(),
+            dhcp_churn_threshold: default_dhcp_churn_threshold(),
+            snooze_mins: default_snooze_mins(),
+            related_logs_mins: default_related_logs_mins(),
+            dedup_window_secs: default_dedup_window_secs(),
+            rate_limit_per_minute: default_rate_limit_per_minute(),
+            rule_rate_limit_per_minute: default_rule_rate_limit_per_minute(),
+            overrides: Vec::new(),
+            runbooks: Vec::new(),
+            actions: Vec::new(),
         }
     }
 }
 
 // Default value functions
 fn default_refresh_interval() -> u64 { 2 }
+fn default_idle_refresh_secs() -> u64 { 30 }
+fn default_alert_background_interval_secs() -> u64 { 10 }
 fn default_log_buffer_size() -> usize { 10000 }
-fn default_kubeconfig_path() -> String { "/etc/rancher/k3s/k3s.yaml".to_string() }
+fn default_log_buffer_max_mb() -> usize { 64 }
+fn default_tenant_growth_percent() -> f64 { 100.0 }
+fn default_tenant_growth_window_hours() -> u64 { 24 }
+fn default_snapshot_interval_mins() -> u64 { 5 }
+fn default_snapshot_retention_hours() -> u64 { 48 }
+fn default_compare_baseline_mins() -> u64 { 60 }
+fn default_crash_scan_interval_secs() -> u64 { 30 }
+fn default_host_profile_interval_secs() -> u64 { 300 }
+fn default_crash_history_days() -> u64 { 7 }
+fn default_leak_sample_interval_secs() -> u64 { 60 }
+fn default_leak_window_hours() -> u64 { 6 }
+fn default_leak_growth_mb_per_hour() -> f64 { 20.0 }
+fn default_collector_timeout_secs() -> u64 { 10 }
+fn default_telemetry_interval_secs() -> u64 { 60 }
+
+fn default_report_sections() -> Vec<ReportSection> {
+    vec![ReportSection::Host, ReportSection::Alerts, ReportSection::Metrics, ReportSection::Workloads]
+}
+
+fn default_report_max_alerts() -> usize { 10 }
+
+fn default_report_exit_sections() -> Vec<ReportSection> {
+    vec![ReportSection::Alerts, ReportSection::Metrics]
+}
+
+fn default_selftest_namespace() -> String { "default".to_string() }
+fn default_selftest_image() -> String { "quay.io/kubevirt/cirros-container-disk-demo:latest".to_string() }
+fn default_selftest_storage_image() -> String { "busybox:1.36".to_string() }
+fn default_selftest_step_timeout_secs() -> u64 { 180 }
+
+fn default_sysctl_watch() -> Vec<String> {
+    ["vm.nr_hugepages", "vm.swappiness"].iter().map(|s| s.to_string()).collect()
+}
+
+/// What kubelet, kube-proxy/flannel and KubeVirt need
+fn default_sysctl_expected() -> BTreeMap<String, String> {
+    [
+        ("net.ipv4.ip_forward", "1"),
+        ("net.bridge.bridge-nf-call-iptables", "1"),
+        ("net.bridge.bridge-nf-call-ip6tables", "1"),
+        ("vm.overcommit_memory", "1"),
+        ("fs.inotify.max_user_instances", ">=512"),
+        ("fs.inotify.max_user_watches", ">=524288"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect()
+}
+
+fn default_watched_processes() -> Vec<String> {
+    [
+        "qemu-kvm",
+        "qemu-system-x86_64",
+        "virt-launcher",
+        "virt-handler",
+        "k3s",
+        "k3s-server",
+        "rke2",
+        "kubelet",
+        "containerd",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+fn default_crash_processes() -> Vec<String> {
+    ["qemu", "libvirtd", "virt-launcher", "virt-handler", "k3s", "rke2", "kubelet", "containerd"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+fn default_leak_processes() -> Vec<String> {
+    ["virt-handler", "containerd", "k3s-server", "k3s-agent", "rke2", "kubelet", "libvirtd"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+fn default_qmp_hosts() -> Vec<String> {
+    vec!["localhost".to_string()]
+}
+fn default_qmp_sockets() -> String { "/run/qemu/*.qmp".to_string() }
+fn default_crash_dirs() -> Vec<String> {
+    vec!["/var/crash".to_string()]
+}
+fn default_prometheus_interval_secs() -> u64 { 30 }
+fn default_prometheus_api_latency_query() -> String {
+    r#"histogram_quantile(0.99, sum by (le) (rate(apiserver_request_duration_seconds_bucket{verb!~"WATCH|CONNECT|LIST"}[5m])))"#
+        .to_string()
+}
+fn default_prometheus_vm_cpu_query() -> String {
+    "100 * sum by (namespace, name) (rate(kubevirt_vmi_vcpu_seconds_total[5m])) \
+     / count by (namespace, name) (count by (namespace, name, id) (kubevirt_vmi_vcpu_seconds_total))"
+        .to_string()
+}
+fn default_prometheus_vm_memory_query() -> String {
+    "100 * sum by (namespace, name) (kubevirt_vmi_memory_used_bytes) \
+     / sum by (namespace, name) (kubevirt_vmi_memory_domain_bytes)"
+        .to_string()
+}
+fn default_api_qps() -> f64 { 20.0 }
+fn default_api_burst() -> u32 { 40 }
+fn default_api_cache_ttl_secs() -> u64 { 5 }
+fn default_console_buffer_lines() -> usize { 2000 }
+fn default_command_interval_secs() -> u64 { 30 }
+fn default_header_widgets() -> Vec<HeaderWidgetConfig> {
+    [HeaderWidget::Hostname, HeaderWidget::Uptime, HeaderWidget::Cpu, HeaderWidget::K8s, HeaderWidget::Memory, HeaderWidget::Vms]
+        .into_iter()
+        .map(HeaderWidgetConfig::new)
+        .collect()
+}
+fn default_command_timeout_secs() -> u64 { 10 }
+fn default_console_retry_secs() -> u64 { 15 }
+fn default_vnc_address() -> String { "127.0.0.1".to_string() }
+fn default_vnc_first_port() -> u16 { 5901 }
+fn default_evacuation_concurrency() -> usize { 2 }
+fn default_evacuation_stuck_secs() -> u64 { 600 }
+fn default_smtp_port() -> u16 { 587 }
+fn default_smtp_security() -> String { "starttls".to_string() }
+fn default_email_from() -> String { "hypervisor-tui@localhost".to_string() }
+fn default_pagerduty_levels() -> Vec<String> { vec!["critical".to_string(), "error".to_string()] }
+fn default_pagerduty_url() -> String { "https://events.pagerduty.com/v2/enqueue".to_string() }
+fn default_alertmanager_levels() -> Vec<String> {
+    vec!["critical".to_string(), "error".to_string(), "warning".to_string()]
+}
+fn default_alertmanager_resend_secs() -> u64 { 60 }
+fn default_digest_interval_secs() -> u64 { 900 }
 fn default_level_filter() -> String { "INFO".to_string() }
-fn default_theme() -> String { "default".to_string() }
+fn default_context_lines() -> usize { 10 }
+
+fn default_archive_max_lines() -> usize { 50000 }
+fn default_search_lines() -> usize { 20000 }
 fn default_animation_refresh() -> u64 { 100 }
+fn default_banner_cycle_secs() -> u64 { 5 }
+fn default_time_zone() -> String { "local".to_string() }
+fn default_timestamp_format() -> String { "default".to_string() }
+fn default_cpu_smoothing_secs() -> u64 { 10 }
 fn default_true() -> bool { true }
+fn default_redaction_replacement() -> String { "<redacted>".to_string() }
+fn default_forward_facility() -> String { "daemon".to_string() }
+fn default_forward_max_size_mb() -> u64 { 10 }
+fn default_forward_keep() -> u32 { 5 }
+fn default_dns_host_names() -> Vec<String> { vec!["cache.nixos.org".to_string()] }
+fn default_dns_cluster_names() -> Vec<String> { vec!["kubernetes.default.svc.cluster.local".to_string()] }
+fn default_dns_timeout_ms() -> u64 { 1000 }
+fn default_dns_failure_threshold() -> u32 { 3 }
+fn default_gateway_timeout_ms() -> u64 { 1000 }
+fn default_gateway_failure_threshold() -> u32 { 3 }
+fn default_route_flap_threshold() -> u32 { 3 }
+fn default_route_flap_window_minutes() -> u64 { 10 }
+fn default_registry_timeout_ms() -> u64 { 3000 }
+fn default_registry_failure_threshold() -> u32 { 3 }
+fn default_vip_timeout_ms() -> u64 { 2000 }
+fn default_vip_failure_threshold() -> u32 { 3 }
+
+fn default_watched_ports() -> Vec<WatchedPort> {
+    let port = |name: &str, port: u16, to: Option<u16>, required: bool| WatchedPort {
+        name: name.to_string(),
+        port,
+        to,
+        required,
+    };
+    vec![
+        // Only servers run the API server and etcd, so they aren't required
+        port("kube-apiserver", 6443, None, false),
+        port("kubelet", 10250, None, true),
+        port("etcd client", 2379, None, false),
+        port("etcd peer", 2380, None, false),
+        port("vnc/console", 5900, Some(5999), false),
+    ]
+}
+
+fn default_expected_listeners() -> Vec<u16> {
+    // ssh, kube-proxy health
+    vec![22, 10256]
+}
+fn default_image_pull_failure_threshold() -> u32 { 3 }
+fn default_migration_failure_threshold() -> u32 { 3 }
+fn default_import_stall_mins() -> u64 { 15 }
+fn default_boot_slow_factor() -> f64 { 3.0 }
+fn default_boot_slow_min_secs() -> u64 { 60 }
+fn default_kernel_event_window_mins() -> u64 { 60 }
+fn default_crash_window_mins() -> u64 { 60 }
+fn default_ip_conflict_window_mins() -> u64 { 10 }
+fn default_dhcp_churn_threshold() -> u32 { 5 }
+fn default_snooze_mins() -> u64 { 60 }
+fn default_related_logs_mins() -> u64 { 5 }
+fn default_action_max_per_hour() -> u32 { 3 }
+
+fn default_web_listen() -> String { "127.0.0.1:8080".to_string() }
+fn default_receiver_listen() -> String { "127.0.0.1:9095".to_string() }
+fn default_receiver_expire_mins() -> u64 { 300 }
+fn default_agent_listen() -> String { "0.0.0.0:7443".to_string() }
+fn default_agent_interval_secs() -> u64 { 5 }
+fn default_remote_agents_interval_secs() -> u64 { 10 }
+fn default_remote_agents_failure_threshold() -> u32 { 3 }
+fn default_upgrade_command() -> Vec<String> { vec!["nixos-rebuild".to_string()] }
+fn default_update_check_url() -> String {
+    "https://api.github.com/repos/3whiskeywhiskey/nix-hypervisor-tui/releases/latest".to_string()
+}
+fn default_update_check_interval_hours() -> u64 { 24 }
+fn default_health_metrics_weight() -> u32 { 30 }
+fn default_health_alerts_weight() -> u32 { 30 }
+fn default_health_services_weight() -> u32 { 20 }
+fn default_health_cluster_weight() -> u32 { 20 }
+fn default_health_degraded_below() -> u32 { 80 }
+fn default_health_critical_below() -> u32 { 50 }
+fn default_gc_command() -> Vec<String> { vec!["nix-collect-garbage".to_string()] }
+fn default_nix_env_command() -> Vec<String> { vec!["nix-env".to_string()] }
+fn default_gc_retention() -> String { "14d".to_string() }
+fn default_image_dirs() -> Vec<String> { vec!["/var/lib/libvirt/images".to_string()] }
+fn default_local_path_dirs() -> Vec<String> {
+    vec!["/var/lib/rancher/k3s/storage".to_string(), "/opt/local-path-provisioner".to_string()]
+}
+fn default_catalog_namespace() -> String { "default".to_string() }
+fn default_catalog_image_size() -> String { "20Gi".to_string() }
+fn default_catalog_images() -> Vec<CatalogImageConfig> {
+    let image = |name: &str, url: &str, checksum_url: &str| CatalogImageConfig {
+        name: name.to_string(),
+        url: url.to_string(),
+        checksum: None,
+        checksum_url: Some(checksum_url.to_string()),
+        size: default_catalog_image_size(),
+    };
+    vec![
+        image(
+            "ubuntu-24.04",
+            "https://cloud-images.ubuntu.com/noble/current/noble-server-cloudimg-amd64.img",
+            "https://cloud-images.ubuntu.com/noble/current/SHA256SUMS",
+        ),
+        image(
+            "debian-12",
+            "https://cloud.debian.org/images/cloud/bookworm/latest/debian-12-genericcloud-amd64.qcow2",
+            "https://cloud.debian.org/images/cloud/bookworm/latest/SHA512SUMS",
+        ),
+        image(
+            "fedora-40",
+            "https://download.fedoraproject.org/pub/fedora/linux/releases/40/Cloud/x86_64/images/Fedora-Cloud-Base-Generic.x86_64-40-1.14.qcow2",
+            "https://download.fedoraproject.org/pub/fedora/linux/releases/40/Cloud/x86_64/images/Fedora-Cloud-40-1.14-x86_64-CHECKSUM",
+        ),
+    ]
+}
 
 // Alert thresholds
 fn default_cpu_warning() -> f64 { 80.0 }
@@ -207,48 +2893,59 @@ fn default_disk_warning() -> f64 { 85.0 }
 fn default_disk_critical() -> f64 { 95.0 }
 fn default_load_warning() -> f64 { 10.0 }
 fn default_load_critical() -> f64 { 20.0 }
-
-fn default_services() -> Vec<String> {
-    vec![
-        "k3s".to_string(),
-        "k3s-agent".to_string(),
-        "kubelet".to_string(),
-        "containerd".to_string(),
-        "virt-handler".to_string(),
-        "virt-launcher".to_string(),
-    ]
-}
+fn default_dedup_window_secs() -> u64 { 300 }
+fn default_rate_limit_per_minute() -> u32 { 20 }
+fn default_rule_rate_limit_per_minute() -> u32 { 5 }
+fn default_quota_warning() -> f64 { 80.0 }
+fn default_quota_critical() -> f64 { 95.0 }
+fn default_api_latency_warning() -> f64 { 500.0 }
+fn default_api_latency_critical() -> f64 { 2000.0 }
+fn default_disk_latency_warning() -> f64 { 50.0 }
+fn default_disk_latency_critical() -> f64 { 200.0 }
+fn default_guest_clock_warning() -> f64 { 5.0 }
+fn default_guest_clock_interval_secs() -> u64 { 300 }
+fn default_guest_filesystems_interval_secs() -> u64 { 300 }
+fn default_local_mode_after_failures() -> u32 { 3 }
+fn default_guest_clock_critical() -> f64 { 120.0 }
+fn default_etcd_db_warning() -> f64 { 80.0 }
+fn default_etcd_db_critical() -> f64 { 95.0 }
+fn default_etcd_leader_changes_per_hour() -> u64 { 3 }
+fn default_pod_restarts_per_hour() -> u64 { 5 }
+fn default_disk_full_warning_hours() -> f64 { 72.0 }
+fn default_disk_full_critical_hours() -> f64 { 12.0 }
+fn default_fd_warning() -> f64 { 80.0 }
+fn default_fd_critical() -> f64 { 95.0 }
+fn default_ephemeral_port_warning() -> f64 { 70.0 }
+fn default_ephemeral_port_critical() -> f64 { 90.0 }
+fn default_pid_warning() -> f64 { 80.0 }
+fn default_pid_critical() -> f64 { 95.0 }
+fn default_entropy_warning_bits() -> u64 { 128 }
+fn default_entropy_critical_bits() -> u64 { 32 }
+fn default_sched_wait_warning() -> f64 { 10.0 }
+fn default_sched_wait_critical() -> f64 { 25.0 }
+fn default_cpu_throttle_warning() -> f64 { 25.0 }
+fn default_cpu_throttle_critical() -> f64 { 50.0 }
+fn default_freq_throttle_percent() -> f64 { 70.0 }
+fn default_freq_throttle_mins() -> u64 { 5 }
+fn default_anomaly_sigma() -> f64 { 3.0 }
+fn default_anomaly_min_samples() -> u64 { 60 }
 
 impl Config {
-    pub fn load() -> Result<Self> {
-        // Try multiple config locations
-        let config_paths = vec![
-            PathBuf::from("config.toml"),
-            PathBuf::from("./hypervisor-tui.toml"),
-            Self::user_config_path(),
-            PathBuf::from("/etc/hypervisor-tui/config.toml"),
-        ];
-
-        for path in config_paths {
-            if path.exists() {
-                tracing::info!("Loading config from: {:?}", path);
-                return Self::load_from_path(&path);
-            }
-        }
-
-        // No config file found, use defaults
-        tracing::info!("No config file found, using defaults");
-        Ok(Self::default())
+    /// The configured cluster flavor, or the one detected on this host
+    pub fn cluster_flavor(&self) -> ClusterFlavor {
+        self.kubernetes.flavor.unwrap_or_else(ClusterFlavor::detect)
     }
 
-    pub fn load_from_path(path: &Path) -> Result<Self> {
-        let contents = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read config file: {:?}", path))?;
-
-        let config: Config = toml::from_str(&contents)
-            .with_context(|| format!("Failed to parse config file: {:?}", path))?;
+    /// Load the site, user and local config files merged in that order,
+    /// keeping every valid setting and reporting everything that had to be
+    /// ignored or looks wrong
+    pub fn load() -> (Self, ConfigReport) {
+        LayeredConfig::read().load()
+    }
 
-        Ok(config)
+    /// The highest-priority config file that exists
+    pub fn find_path() -> Option<PathBuf> {
+        layer_paths().pop().map(|(_, path)| path)
     }
 
     pub fn save(&self, path: &Path) -> Result<()> {
@@ -266,7 +2963,18 @@ impl Config {
         Ok(())
     }
 
-    fn user_config_path() -> PathBuf {
+    /// Directory for persisted runtime state ($XDG_STATE_HOME/hypervisor-tui)
+    pub fn state_dir() -> PathBuf {
+        if let Ok(state_home) = std::env::var("XDG_STATE_HOME") {
+            PathBuf::from(state_home).join("hypervisor-tui")
+        } else if let Ok(home) = std::env::var("HOME") {
+            PathBuf::from(home).join(".local/state/hypervisor-tui")
+        } else {
+            PathBuf::from("/var/lib/hypervisor-tui")
+        }
+    }
+
+    pub fn user_config_path() -> PathBuf {
         if let Ok(home) = std::env::var("HOME") {
             PathBuf::from(home).join(".config/hypervisor-tui/config.toml")
         } else {
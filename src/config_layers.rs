@@ -0,0 +1,324 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::CliArgs;
+use crate::config::Config;
+use crate::validation::{self, ConfigProblem, ConfigReport};
+
+/// Settings from the NixOS module's `services.hypervisor-tui.settings`
+pub const NIXOS_CONFIG: &str = "/etc/hypervisor-tui/config.json";
+/// Points at another JSON settings file instead of `NIXOS_CONFIG`
+const NIXOS_CONFIG_ENV: &str = "HYPERVISOR_TUI_CONFIG_JSON";
+/// Defaults for every user of the host, e.g. deployed by NixOS
+pub const SITE_CONFIG: &str = "/etc/hypervisor-tui/config.toml";
+/// Looked for in the working directory, first found wins
+const LOCAL_CONFIGS: [&str; 2] = ["config.toml", "hypervisor-tui.toml"];
+/// Keys whose values `--print-config` masks
+const SECRET_KEYS: [&str; 4] = ["password", "token", "secret", "routing_key"];
+
+/// One file, or the command line, contributing to the effective config
+pub struct ConfigLayer {
+    pub label: &'static str,
+    pub path: Option<PathBuf>,
+    contents: String,
+}
+
+impl ConfigLayer {
+    fn describe(&self) -> String {
+        match self.path {
+            Some(ref path) => format!("{} ({})", self.label, path.display()),
+            None => self.label.to_string(),
+        }
+    }
+}
+
+/// Config files that exist, lowest priority first: the NixOS module's
+/// settings, the site defaults, the user's overrides, then a file in the
+/// working directory
+pub fn layer_paths() -> Vec<(&'static str, PathBuf)> {
+    let local = LOCAL_CONFIGS.iter().map(PathBuf::from).find(|path| path.exists());
+    let nixos = std::env::var_os(NIXOS_CONFIG_ENV).map_or_else(|| PathBuf::from(NIXOS_CONFIG), PathBuf::from);
+    let mut paths: Vec<(&'static str, PathBuf)> = Vec::new();
+    for (label, path) in [
+        ("nixos", Some(nixos)),
+        ("site", Some(PathBuf::from(SITE_CONFIG))),
+        ("user", Some(Config::user_config_path())),
+        ("local", local),
+    ] {
+        let Some(path) = path.filter(|p| p.exists()) else {
+            continue;
+        };
+        // Started from ~/.config/hypervisor-tui, the user file is also local
+        let canonical = fs::canonicalize(&path).ok();
+        if paths.iter().any(|(_, seen)| fs::canonicalize(seen).ok() == canonical) {
+            continue;
+        }
+        paths.push((label, path));
+    }
+    paths
+}
+
+/// The config files merged in order: tables merge key by key, anything
+/// else (values, arrays, arrays of tables such as `custom_commands`) is
+/// replaced whole by the later layer
+pub struct LayeredConfig {
+    pub layers: Vec<ConfigLayer>,
+    merged: toml::Table,
+    /// Layer that set each dotted key
+    origins: BTreeMap<String, usize>,
+    /// Files that couldn't be read or parsed, and were skipped
+    problems: Vec<ConfigProblem>,
+}
+
+impl LayeredConfig {
+    pub fn read() -> Self {
+        let mut config = Self {
+            layers: Vec::new(),
+            merged: toml::Table::new(),
+            origins: BTreeMap::new(),
+            problems: Vec::new(),
+        };
+        for (label, path) in layer_paths() {
+            tracing::info!("Loading {} config from: {:?}", label, path);
+            let contents = match fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    config.file_problem(&path, None, format!("failed to read: {}; skipped", e));
+                    continue;
+                }
+            };
+            if is_json(&path) {
+                match json_table(&contents) {
+                    Ok(table) => config.push(label, Some(path), contents, table),
+                    Err(e) => config.file_problem(&path, None, format!("{}; file skipped", e)),
+                }
+                continue;
+            }
+            match toml::from_str::<toml::Table>(&contents) {
+                Ok(table) => config.push(label, Some(path), contents, table),
+                Err(e) => {
+                    let line = e.span().map(|span| contents[..span.start.min(contents.len())].matches('\n').count() + 1);
+                    let message = e.message().lines().next().unwrap_or_default().to_string();
+                    config.file_problem(&path, line, format!("{}; file skipped", message));
+                }
+            }
+        }
+        config
+    }
+
+    /// Lay the command-line flags that stand for config keys on top
+    pub fn with_args(mut self, args: &CliArgs) -> Self {
+        let mut table = toml::Table::new();
+        let mut flags = Vec::new();
+        if args.read_only {
+            let mut general = toml::Table::new();
+            general.insert("read_only".to_string(), toml::Value::Boolean(true));
+            table.insert("general".to_string(), toml::Value::Table(general));
+            flags.push("--read-only".to_string());
+        }
+        if let Some(ref profile) = args.alert_profile {
+            let mut alerts = toml::Table::new();
+            alerts.insert("profile".to_string(), toml::Value::String(profile.clone()));
+            table.insert("alerts".to_string(), toml::Value::Table(alerts));
+            flags.push(format!("--alert-profile {}", profile));
+        }
+        if let Some(ref listen) = args.listen {
+            let mut agent = toml::Table::new();
+            agent.insert("listen".to_string(), toml::Value::String(listen.clone()));
+            table.insert("agent".to_string(), toml::Value::Table(agent));
+            flags.push(format!("--listen {}", listen));
+        }
+        if !table.is_empty() {
+            self.push("command line", None, flags.join(" "), table);
+        }
+        self
+    }
+
+    fn push(&mut self, label: &'static str, path: Option<PathBuf>, contents: String, table: toml::Table) {
+        let index = self.layers.len();
+        self.layers.push(ConfigLayer { label, path, contents });
+        merge(&mut self.merged, table, "", index, &mut self.origins);
+    }
+
+    fn file_problem(&mut self, path: &Path, line: Option<usize>, message: String) {
+        self.problems.push(ConfigProblem {
+            file: Some(path.to_path_buf()),
+            key: "(file)".to_string(),
+            line,
+            message,
+        });
+    }
+
+    /// The effective config, keeping every valid setting and reporting each
+    /// problem against the file that set the key
+    pub fn load(&self) -> (Config, ConfigReport) {
+        let mut report = ConfigReport {
+            sources: self.layers.iter().filter_map(|layer| layer.path.clone()).collect(),
+            problems: self.problems.clone(),
+        };
+        if self.layers.is_empty() {
+            if report.problems.is_empty() {
+                tracing::info!("No config file found, using defaults");
+            }
+            return (Config::default(), report);
+        }
+
+        let merged = toml::to_string(&self.merged).unwrap_or_default();
+        let (config, mut problems) = validation::parse(&merged);
+        problems.extend(validation::validate(&config, &merged));
+        // Lines and files only mean something when there are several
+        let several = self.problems.len() + report.sources.len() > 1;
+        for problem in &mut problems {
+            let layer = self.origin(&problem.key).map(|index| &self.layers[index]);
+            // Generated JSON has no lines worth pointing at
+            let toml_file = layer.and_then(|layer| layer.path.as_ref()).is_some_and(|path| !is_json(path));
+            problem.line = layer.filter(|_| toml_file).and_then(|layer| validation::locate(&layer.contents, &problem.key));
+            if several {
+                problem.file = layer.and_then(|layer| layer.path.clone());
+            }
+        }
+        report.problems.extend(problems);
+        for problem in &report.problems {
+            tracing::warn!("Config: {}", problem);
+        }
+        (config, report)
+    }
+
+    /// Layer that set a key, or the table or array it is part of
+    fn origin(&self, key: &str) -> Option<usize> {
+        let mut key = key;
+        loop {
+            if let Some(&index) = self.origins.get(key) {
+                return Some(index);
+            }
+            // A whole section reported at once: the last layer touching it
+            let prefix = format!("{}.", key);
+            if let Some(index) = self.origins.range(prefix.clone()..).take_while(|(k, _)| k.starts_with(&prefix)).map(|(_, &i)| i).max() {
+                return Some(index);
+            }
+            key = key.rsplit_once('.')?.0;
+        }
+    }
+
+    /// The effective config as TOML with where each value came from, for
+    /// `--print-config`; passwords and tokens are masked
+    pub fn render(&self, config: &Config) -> String {
+        let mut out = vec!["# Effective configuration: built-in defaults, overridden in order by".to_string()];
+        if self.layers.is_empty() {
+            out.push("#   (no config files)".to_string());
+        }
+        for layer in &self.layers {
+            match layer.path {
+                Some(ref path) => out.push(format!("#   {:<12} {}", layer.label, path.display())),
+                None => out.push(format!("#   {:<12} {}", layer.label, layer.contents)),
+            }
+        }
+        for problem in &self.problems {
+            out.push(format!("# skipped: {}", problem));
+        }
+        out.push(String::new());
+
+        let text = toml::to_string_pretty(config).unwrap_or_default();
+        let mut table = String::new();
+        for line in text.lines() {
+            let header = line.trim_start_matches('[').trim_end_matches(']');
+            if line.starts_with('[') {
+                table = header.trim().to_string();
+                out.push(line.to_string());
+                continue;
+            }
+            // Keys start their line; array items and closing brackets don't
+            let Some((raw_key, value)) = line.split_once(" = ").filter(|_| !line.starts_with([' ', ']'])) else {
+                out.push(line.to_string());
+                continue;
+            };
+            let key = raw_key.trim_matches('"');
+            let dotted = if table.is_empty() { key.to_string() } else { format!("{}.{}", table, key) };
+            let index = match self.origins.get(&dotted) {
+                Some(&index) => Some(index),
+                None if self.in_array(&table) => self.origin(&table),
+                None => None,
+            };
+            let source = index.map_or("default".to_string(), |index| self.layers[index].describe());
+            let secret = !key.ends_with("_file") && SECRET_KEYS.iter().any(|s| key.contains(s));
+            let value = if secret { "\"<redacted>\"" } else { value };
+            out.push(format!("{} = {}  # {}", raw_key, value, source));
+        }
+        out.join("\n") + "\n"
+    }
+
+    /// Inside an array of tables, which a layer sets as a whole
+    fn in_array(&self, table: &str) -> bool {
+        let mut key = table;
+        loop {
+            if let Some(toml::Value::Array(_)) = lookup(&self.merged, key) {
+                return true;
+            }
+            match key.rsplit_once('.') {
+                Some((parent, _)) => key = parent,
+                None => return false,
+            }
+        }
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+/// JSON settings as a TOML table. Nix writes options left unset as null,
+/// which TOML has no value for, so they are dropped along with sections
+/// left empty.
+fn json_table(contents: &str) -> serde_json::Result<toml::Table> {
+    fn strip_nulls(value: &mut serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::Null => false,
+            serde_json::Value::Object(map) => {
+                map.retain(|_, v| strip_nulls(v));
+                !map.is_empty()
+            }
+            serde_json::Value::Array(items) => {
+                items.retain_mut(strip_nulls);
+                true
+            }
+            _ => true,
+        }
+    }
+    let mut value: serde_json::Value = serde_json::from_str(contents)?;
+    strip_nulls(&mut value);
+    serde_json::from_value(value)
+}
+
+/// Merge `over` into `base`, recording which layer set each key
+fn merge(base: &mut toml::Table, over: toml::Table, prefix: &str, layer: usize, origins: &mut BTreeMap<String, usize>) {
+    for (key, value) in over {
+        let dotted = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(table)) => {
+                merge(existing, table, &dotted, layer, origins);
+            }
+            (_, toml::Value::Table(table)) => {
+                let mut fresh = toml::Table::new();
+                // Anything below a replaced value came from an earlier layer
+                origins.retain(|k, _| !k.starts_with(&format!("{}.", dotted)) && k != &dotted);
+                merge(&mut fresh, table, &dotted, layer, origins);
+                base.insert(key, toml::Value::Table(fresh));
+            }
+            (_, value) => {
+                origins.retain(|k, _| !k.starts_with(&format!("{}.", dotted)));
+                origins.insert(dotted, layer);
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn lookup<'a>(table: &'a toml::Table, dotted: &str) -> Option<&'a toml::Value> {
+    let mut parts = dotted.split('.');
+    let mut value = table.get(parts.next()?)?;
+    for part in parts {
+        value = value.as_table()?.get(part)?;
+    }
+    Some(value)
+}
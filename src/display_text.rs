@@ -0,0 +1,142 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, Local};
+
+use crate::alerts::AlertLevel;
+use crate::app::{App, Screen};
+use crate::i18n;
+use crate::metrics_history::Trend;
+use crate::ui::Glyphs;
+
+/// Levels in the order the footer counts them
+const LEVELS: [AlertLevel; 4] = [AlertLevel::Critical, AlertLevel::Error, AlertLevel::Warning, AlertLevel::Info];
+
+/// Active alerts per level and whether glyphs are ASCII
+type AlertCountsKey = ([usize; 4], bool);
+
+/// Minute, last counted collection and whether glyphs are ASCII
+type LogRatesKey = (i64, Option<DateTime<Local>>, bool);
+
+/// A value kept ready for drawing, rebuilt in place only when what it
+/// shows changes so its buffers are reused between frames
+#[derive(Debug, Default)]
+pub struct Cached<K, T> {
+    key: Option<K>,
+    value: T,
+}
+
+impl<K: PartialEq, T> Cached<K, T> {
+    fn update(&mut self, key: K, build: impl FnOnce(&mut T)) {
+        if self.key.as_ref() != Some(&key) {
+            build(&mut self.value);
+            self.key = Some(key);
+        }
+    }
+
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+/// One footer sparkline: the last minutes' counts and their total
+#[derive(Debug)]
+pub struct LogRate {
+    pub sparkline: String,
+    pub total: String,
+    pub trend: Trend,
+}
+
+/// Header and footer text drawn on every frame, so the draw loop borrows
+/// strings instead of formatting them each time. `refresh` runs before
+/// every draw and only compares; text is rebuilt when its inputs change.
+#[derive(Debug, Default)]
+pub struct DisplayText {
+    /// " F1: Logs " and so on, by locale
+    pub tabs: Cached<u8, Vec<String>>,
+    /// CPU in tenths of a percent
+    pub cpu: Cached<i64, String>,
+    /// Used and total memory in tenths of a GB
+    pub memory: Cached<(i64, i64), String>,
+    /// Uptime in minutes
+    pub uptime: Cached<u64, String>,
+    /// Load average in hundredths
+    pub load: Cached<i64, String>,
+    /// Running and all VMs
+    pub vms: Cached<(u32, u32), String>,
+    /// Icon and count per level with any active
+    pub alert_counts: Cached<AlertCountsKey, Vec<(AlertLevel, String)>>,
+    /// Errors then warnings
+    pub log_rates: Cached<LogRatesKey, Option<[LogRate; 2]>>,
+}
+
+impl DisplayText {
+    pub fn refresh(&mut self, app: &App) {
+        let glyphs = Glyphs::for_mode(app.accessible);
+
+        self.tabs.update(i18n::locale_index(), |tabs| {
+            tabs.resize_with(Screen::ALL.len(), String::new);
+            for (i, (tab, screen)) in tabs.iter_mut().zip(Screen::ALL).enumerate() {
+                tab.clear();
+                let _ = write!(tab, " F{}: {} ", i + 1, screen.title());
+            }
+        });
+
+        let cpu = app.displayed_cpu();
+        self.cpu.update((cpu * 10.0).round() as i64, |text| {
+            text.clear();
+            let _ = write!(text, "{:.1}%", cpu);
+        });
+
+        let (used, total) = (app.system_metrics.memory_used_gb, app.system_metrics.memory_total_gb);
+        self.memory.update(((used * 10.0).round() as i64, (total * 10.0).round() as i64), |text| {
+            text.clear();
+            let _ = write!(text, "{:.1}/{:.1} GB", used, total);
+        });
+
+        let minutes = app.system_metrics.uptime_seconds / 60;
+        self.uptime.update(minutes, |text| {
+            text.clear();
+            let _ = write!(text, "{}d {}h {}m", minutes / 1440, minutes / 60 % 24, minutes % 60);
+        });
+
+        let load = app.system_metrics.load_avg;
+        self.load.update((load * 100.0).round() as i64, |text| {
+            text.clear();
+            let _ = write!(text, "{:.2}", load);
+        });
+
+        let info = &app.kubevirt_info;
+        let running = info.vms_running + info.vms_migrating;
+        self.vms.update((running, running + info.vms_stopped), |text| {
+            text.clear();
+            let _ = write!(text, "{}/{}", running, running + info.vms_stopped);
+        });
+
+        let (critical, error, warning, info) = app.alert_manager.get_alert_counts();
+        let counts = [critical, error, warning, info];
+        self.alert_counts.update((counts, app.accessible), |items| {
+            items.clear();
+            for (level, count) in LEVELS.into_iter().zip(counts) {
+                if count > 0 {
+                    items.push((level, format!("{} {}", glyphs.styled_level_icon(&app.alert_styles, level), count)));
+                }
+            }
+        });
+
+        if app.footer_log_rates {
+            let minute = Local::now().timestamp() / 60;
+            let key = (minute, app.metrics_history.log_levels_updated(), app.accessible);
+            self.log_rates.update(key, |rates| {
+                *rates = app.metrics_history.log_level_stats().map(|stats| {
+                    let rate = |counts: &[u64], trend| LogRate {
+                        sparkline: glyphs.sparkline(counts),
+                        total: format!(" {}", counts.iter().sum::<u64>()),
+                        trend,
+                    };
+                    [rate(&stats.errors, stats.error_trend), rate(&stats.warnings, stats.warning_trend)]
+                });
+            });
+        }
+    }
+}
+
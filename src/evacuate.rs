@@ -0,0 +1,444 @@
+use crate::collectors::KubernetesCollector;
+use crate::config::EvacuationConfig;
+use crate::types::NodePod;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Semaphore};
+
+/// How often a migrating VMI, a terminating pod or a refused eviction is
+/// looked at again
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Where one VM of an evacuation stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmEvacuation {
+    /// Waiting for a free migration slot
+    Queued,
+    Migrating,
+    /// Running on the named node now
+    Migrated(String),
+    Failed(String),
+    /// Still on the node after `stuck_secs`, with what the VMI reported
+    Stuck(String),
+}
+
+impl VmEvacuation {
+    pub fn is_final(&self) -> bool {
+        !matches!(self, VmEvacuation::Queued | VmEvacuation::Migrating)
+    }
+}
+
+/// Where one pod of an evacuation stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PodEviction {
+    Queued,
+    /// Eviction accepted, waiting for the pod to terminate
+    Evicting,
+    /// Eviction refused for now, retried until `stuck_secs`
+    Blocked(String),
+    Evicted,
+    /// Not evicted, for the same reasons `kubectl drain` refuses to
+    /// without extra flags (local storage, no controller)
+    Kept(String),
+    /// Not the drain's to evict: DaemonSet, mirror, finished and
+    /// virt-launcher pods
+    Skipped(String),
+    Failed(String),
+    /// Still blocked or terminating after `stuck_secs`
+    Stuck(String),
+}
+
+impl PodEviction {
+    pub fn is_final(&self) -> bool {
+        !matches!(self, PodEviction::Queued | PodEviction::Evicting | PodEviction::Blocked(_))
+    }
+
+    /// Still on the node when the drain is over
+    pub fn is_left_behind(&self) -> bool {
+        matches!(self, PodEviction::Kept(_) | PodEviction::Failed(_) | PodEviction::Stuck(_))
+    }
+
+    /// How the drain treats a pod before trying anything
+    fn initial(pod: &NodePod) -> Self {
+        if pod.launcher {
+            PodEviction::Skipped("leaves with its VM's migration".to_string())
+        } else if pod.mirror {
+            PodEviction::Skipped("static pod".to_string())
+        } else if pod.owner.as_deref() == Some("DaemonSet") {
+            PodEviction::Skipped("DaemonSet pod".to_string())
+        } else if pod.finished {
+            PodEviction::Skipped("finished".to_string())
+        } else if pod.owner.is_none() {
+            PodEviction::Kept("no controller would recreate it".to_string())
+        } else if !pod.empty_dirs.is_empty() {
+            PodEviction::Kept(format!("local storage: emptyDir {}", pod.empty_dirs.join(", ")))
+        } else {
+            PodEviction::Queued
+        }
+    }
+}
+
+enum EvacuationEvent {
+    Cordoned(Result<(), String>),
+    Listed(Result<(Vec<String>, Vec<NodePod>), String>),
+    Vm(usize, VmEvacuation),
+    Pod(usize, PodEviction),
+}
+
+/// A node being drained: cordoned, then every VM running on it live
+/// migrated at most `concurrency` at a time while its other pods are
+/// evicted, each VM's and pod's state as it changes
+pub struct Evacuation {
+    pub node: String,
+    /// None while the cordon is in flight
+    pub cordon: Option<Result<(), String>>,
+    /// None until the node's VMIs and pods are listed
+    pub listing: Option<Result<(), String>>,
+    /// (namespace/name, state)
+    pub vms: Vec<(String, VmEvacuation)>,
+    /// (namespace/name, state)
+    pub pods: Vec<(String, PodEviction)>,
+    /// Panel dismissed while migrations are still running
+    pub hidden: bool,
+    started: Instant,
+    rx: mpsc::UnboundedReceiver<EvacuationEvent>,
+}
+
+impl Evacuation {
+    pub fn start(k8s: &KubernetesCollector, node: String, config: &EvacuationConfig) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let (k8s, task_node) = (k8s.clone(), node.clone());
+        let stuck_after = Duration::from_secs(config.stuck_secs);
+        let permits = Arc::new(Semaphore::new(config.concurrency.max(1)));
+
+        tokio::spawn(async move {
+            let node = task_node;
+            // Cordon first so nothing migrated away lands back here
+            let cordon = if k8s.is_connected() {
+                k8s.set_node_schedulable(&node, false).await.map(|_| ()).map_err(|e| format!("{:#}", e))
+            } else {
+                Ok(())
+            };
+            let cordoned = cordon.is_ok();
+            let _ = tx.send(EvacuationEvent::Cordoned(cordon));
+            if !cordoned {
+                return;
+            }
+
+            let listed = match tokio::try_join!(k8s.vmis_on_node(&node), k8s.pods_on_node(&node)) {
+                Ok(listed) => listed,
+                Err(e) => {
+                    let _ = tx.send(EvacuationEvent::Listed(Err(format!("{:#}", e))));
+                    return;
+                }
+            };
+            let _ = tx.send(EvacuationEvent::Listed(Ok(listed.clone())));
+            let (vms, pods) = listed;
+
+            // Evictions are cheap next to migrations and go out at once,
+            // the way `kubectl drain` sends them
+            for (i, pod) in pods.into_iter().enumerate() {
+                if PodEviction::initial(&pod) != PodEviction::Queued {
+                    continue;
+                }
+                let (k8s, tx) = (k8s.clone(), tx.clone());
+                tokio::spawn(async move {
+                    let state = evict_off(&k8s, &pod, stuck_after, |state| {
+                        let _ = tx.send(EvacuationEvent::Pod(i, state));
+                    })
+                    .await;
+                    let _ = tx.send(EvacuationEvent::Pod(i, state));
+                });
+            }
+
+            for (i, vm) in vms.into_iter().enumerate() {
+                let (k8s, node, tx, permits) = (k8s.clone(), node.clone(), tx.clone(), permits.clone());
+                tokio::spawn(async move {
+                    let _permit = permits.acquire().await;
+                    let _ = tx.send(EvacuationEvent::Vm(i, VmEvacuation::Migrating));
+                    let state = migrate_off(&k8s, &node, &vm, stuck_after).await;
+                    let _ = tx.send(EvacuationEvent::Vm(i, state));
+                });
+            }
+        });
+
+        Self {
+            node,
+            cordon: None,
+            listing: None,
+            vms: Vec::new(),
+            pods: Vec::new(),
+            hidden: false,
+            started: Instant::now(),
+            rx,
+        }
+    }
+
+    /// Take in progress, returning whether any arrived
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(event) = self.rx.try_recv() {
+            match event {
+                EvacuationEvent::Cordoned(result) => self.cordon = Some(result),
+                EvacuationEvent::Listed(Ok((vms, pods))) => {
+                    self.vms = vms.into_iter().map(|vm| (vm, VmEvacuation::Queued)).collect();
+                    self.pods = pods
+                        .iter()
+                        .map(|pod| (format!("{}/{}", pod.namespace, pod.name), PodEviction::initial(pod)))
+                        .collect();
+                    self.listing = Some(Ok(()));
+                }
+                EvacuationEvent::Listed(Err(e)) => self.listing = Some(Err(e)),
+                EvacuationEvent::Vm(i, state) => {
+                    if let Some(entry) = self.vms.get_mut(i) {
+                        entry.1 = state;
+                    }
+                }
+                EvacuationEvent::Pod(i, state) => {
+                    if let Some(entry) = self.pods.get_mut(i) {
+                        entry.1 = state;
+                    }
+                }
+            }
+            changed = true;
+        }
+        changed
+    }
+
+    pub fn is_finished(&self) -> bool {
+        match (&self.cordon, &self.listing) {
+            (Some(Err(_)), _) | (_, Some(Err(_))) => true,
+            (_, Some(Ok(()))) => {
+                self.vms.iter().all(|(_, state)| state.is_final())
+                    && self.pods.iter().all(|(_, state)| state.is_final())
+            }
+            _ => false,
+        }
+    }
+
+    pub fn count(&self, matches: fn(&VmEvacuation) -> bool) -> usize {
+        self.vms.iter().filter(|(_, state)| matches(state)).count()
+    }
+
+    pub fn count_pods(&self, matches: fn(&PodEviction) -> bool) -> usize {
+        self.pods.iter().filter(|(_, state)| matches(state)).count()
+    }
+
+    /// VMs and pods the drain moves, as (settled, total)
+    pub fn progress(&self) -> (usize, usize) {
+        let moving = |s: &PodEviction| !matches!(s, PodEviction::Skipped(_) | PodEviction::Kept(_));
+        let pods: Vec<&PodEviction> = self.pods.iter().map(|(_, s)| s).filter(|s| moving(s)).collect();
+        (
+            self.count(VmEvacuation::is_final) + pods.iter().filter(|s| s.is_final()).count(),
+            self.vms.len() + pods.len(),
+        )
+    }
+
+    /// VMs and pods still on the node when the drain is over
+    pub fn left_behind(&self) -> Vec<String> {
+        let vms = self
+            .vms
+            .iter()
+            .filter(|(_, state)| matches!(state, VmEvacuation::Failed(_) | VmEvacuation::Stuck(_)));
+        let pods = self.pods.iter().filter(|(_, state)| state.is_left_behind());
+        vms.map(|(vm, _)| vm.clone()).chain(pods.map(|(pod, _)| pod.clone())).collect()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// "node-02: 5/7 VMs migrated, 3/4 pods evicted, 1 blocked, 1 kept"
+    pub fn summary(&self) -> String {
+        if let Some(Err(e)) = &self.cordon {
+            return format!("{}: cordon failed: {}", self.node, e);
+        }
+        if let Some(Err(e)) = &self.listing {
+            return format!("{}: listing VMs and pods failed: {}", self.node, e);
+        }
+        if self.listing.is_none() {
+            return format!("{}: cordoning", self.node);
+        }
+        let evictable = self.count_pods(|s| !matches!(s, PodEviction::Skipped(_) | PodEviction::Kept(_)));
+        if self.vms.is_empty() && evictable == 0 && self.count_pods(|s| matches!(s, PodEviction::Kept(_))) == 0 {
+            return format!("{}: cordoned, no VMs or pods to move", self.node);
+        }
+        let mut parts = Vec::new();
+        if !self.vms.is_empty() {
+            parts.push(format!(
+                "{}/{} VMs migrated",
+                self.count(|s| matches!(s, VmEvacuation::Migrated(_))),
+                self.vms.len()
+            ));
+        }
+        if evictable > 0 {
+            parts.push(format!("{}/{} pods evicted", self.count_pods(|s| *s == PodEviction::Evicted), evictable));
+        }
+        for (label, n) in [
+            ("failed", self.count(|s| matches!(s, VmEvacuation::Failed(_)))
+                + self.count_pods(|s| matches!(s, PodEviction::Failed(_)))),
+            ("stuck", self.count(|s| matches!(s, VmEvacuation::Stuck(_)))
+                + self.count_pods(|s| matches!(s, PodEviction::Stuck(_)))),
+            ("blocked", self.count_pods(|s| matches!(s, PodEviction::Blocked(_)))),
+            ("kept", self.count_pods(|s| matches!(s, PodEviction::Kept(_)))),
+            ("migrating", self.count(|s| matches!(s, VmEvacuation::Migrating))),
+        ] {
+            if n > 0 {
+                parts.push(format!("{} {}", n, label));
+            }
+        }
+        format!("{}: {}", self.node, parts.join(", "))
+    }
+}
+
+/// Request a live migration of one VMI and follow it until the VMI runs
+/// elsewhere, the migration fails, or it is stuck
+async fn migrate_off(k8s: &KubernetesCollector, node: &str, vm: &str, stuck_after: Duration) -> VmEvacuation {
+    let Some((namespace, name)) = vm.split_once('/') else {
+        return VmEvacuation::Failed("not a namespaced VMI".to_string());
+    };
+    if !k8s.is_connected() {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        return VmEvacuation::Migrated("another node (simulated)".to_string());
+    }
+
+    // A migration state left from an earlier migration must not count
+    let previous = match k8s.fetch_object("virtualmachineinstance", namespace, name).await {
+        Ok(vmi) => {
+            if let Some(reason) = not_migratable(&vmi) {
+                return VmEvacuation::Failed(reason);
+            }
+            migration_uid(&vmi)
+        }
+        Err(e) => return VmEvacuation::Failed(format!("{:#}", e)),
+    };
+
+    // What `virtctl migrate` creates; works for VMs and bare VMIs alike
+    let migration = json!({
+        "apiVersion": "kubevirt.io/v1",
+        "kind": "VirtualMachineInstanceMigration",
+        "metadata": { "generateName": format!("evacuate-{}-", name), "namespace": namespace },
+        "spec": { "vmiName": name },
+    });
+    if let Err(e) = k8s.create_object(&migration).await {
+        return VmEvacuation::Failed(format!("{:#}", e));
+    }
+
+    let start = Instant::now();
+    let mut last_seen = "migration requested".to_string();
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        match k8s.fetch_object("virtualmachineinstance", namespace, name).await {
+            Ok(vmi) => {
+                let state = vmi.pointer("/status/migrationState");
+                let flag = |key: &str| state.and_then(|s| s.get(key)).and_then(Value::as_bool).unwrap_or(false);
+                let current = migration_uid(&vmi);
+                let on = vmi.pointer("/status/nodeName").and_then(Value::as_str).unwrap_or_default();
+                if current.is_some() && current != previous {
+                    if flag("failed") {
+                        let reason = state
+                            .and_then(|s| s.get("failureReason"))
+                            .and_then(Value::as_str)
+                            .unwrap_or("migration failed");
+                        return VmEvacuation::Failed(reason.to_string());
+                    }
+                    if flag("completed") && !on.is_empty() && on != node {
+                        return VmEvacuation::Migrated(on.to_string());
+                    }
+                    let target = state.and_then(|s| s.get("targetNode")).and_then(Value::as_str);
+                    last_seen = match target {
+                        Some(target) => format!("migrating to {}", target),
+                        None => "migration pending".to_string(),
+                    };
+                }
+            }
+            // Deleted or stopped meanwhile: nothing left on the node
+            Err(e) if format!("{:#}", e).contains("NotFound") => {
+                return VmEvacuation::Migrated("gone (VMI deleted)".to_string());
+            }
+            Err(e) => last_seen = format!("{:#}", e),
+        }
+        if start.elapsed() >= stuck_after {
+            return VmEvacuation::Stuck(format!("{} after {}s", last_seen, start.elapsed().as_secs()));
+        }
+    }
+}
+
+/// Evict one pod, retrying while a disruption budget refuses, and follow
+/// it until it is gone or `stuck_after` passed. `update` is told about
+/// the states on the way.
+async fn evict_off(
+    k8s: &KubernetesCollector,
+    pod: &NodePod,
+    stuck_after: Duration,
+    update: impl Fn(PodEviction),
+) -> PodEviction {
+    if !k8s.is_connected() {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        if let Some(budget) = pod.budgets.first() {
+            update(PodEviction::Blocked(format!("PodDisruptionBudget {} allows no disruption (simulated)", budget)));
+            tokio::time::sleep(Duration::from_secs(4)).await;
+        }
+        update(PodEviction::Evicting);
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        return PodEviction::Evicted;
+    }
+
+    let start = Instant::now();
+    let mut blocked = None;
+    loop {
+        match k8s.evict_pod(&pod.namespace, &pod.name).await {
+            Ok(None) => break,
+            Ok(Some(refusal)) => {
+                let reason = match pod.budgets.as_slice() {
+                    [] => refusal,
+                    budgets => format!("PodDisruptionBudget {}: {}", budgets.join(", "), refusal),
+                };
+                if blocked.as_ref() != Some(&reason) {
+                    update(PodEviction::Blocked(reason.clone()));
+                    blocked = Some(reason.clone());
+                }
+                if start.elapsed() >= stuck_after {
+                    return PodEviction::Stuck(format!("{} after {}s", reason, start.elapsed().as_secs()));
+                }
+            }
+            Err(e) => return PodEviction::Failed(format!("{:#}", e)),
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+
+    update(PodEviction::Evicting);
+    loop {
+        match k8s.pod_exists(&pod.namespace, &pod.name, &pod.uid).await {
+            Ok(false) => return PodEviction::Evicted,
+            Ok(true) => {}
+            Err(e) => tracing::debug!("Following eviction of {}/{}: {:#}", pod.namespace, pod.name, e),
+        }
+        if start.elapsed() >= stuck_after {
+            return PodEviction::Stuck(format!("still terminating after {}s", start.elapsed().as_secs()));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+fn migration_uid(vmi: &Value) -> Option<String> {
+    vmi.pointer("/status/migrationState/migrationUid")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// The LiveMigratable=False condition's message, if the VMI has one
+fn not_migratable(vmi: &Value) -> Option<String> {
+    vmi.pointer("/status/conditions")
+        .and_then(Value::as_array)?
+        .iter()
+        .find(|c| {
+            c.get("type").and_then(Value::as_str) == Some("LiveMigratable")
+                && c.get("status").and_then(Value::as_str) == Some("False")
+        })
+        .map(|c| {
+            let message = c.get("message").and_then(Value::as_str).unwrap_or("no reason given");
+            format!("not live-migratable: {}", message)
+        })
+}
@@ -0,0 +1,159 @@
+use crate::alerts::AlertLevel;
+use crate::app::App;
+use crate::config::HealthConfig;
+
+/// Points an active alert of each level costs the alerts part
+const ALERT_PENALTY: [(AlertLevel, u32); 3] = [(AlertLevel::Critical, 50), (AlertLevel::Error, 25), (AlertLevel::Warning, 10)];
+/// Points a systemd unit that failed, or is otherwise not active, costs
+/// the services part
+const FAILED_UNIT_PENALTY: u32 = 40;
+const INACTIVE_UNIT_PENALTY: u32 = 15;
+
+/// Traffic-light reading of the score against `[health]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthLevel {
+    Healthy,
+    Degraded,
+    Critical,
+}
+
+/// One weighted part of the score
+#[derive(Debug, Clone)]
+pub struct HealthPart {
+    pub name: &'static str,
+    /// 0-100
+    pub score: u32,
+    pub weight: u32,
+    /// What cost it points
+    pub reasons: Vec<String>,
+}
+
+/// "Is this hypervisor okay?" as one number: host metrics, active alerts,
+/// systemd units and the cluster, each scored 0-100 and weighted
+#[derive(Debug, Clone)]
+pub struct HealthScore {
+    pub score: u32,
+    pub level: HealthLevel,
+    pub parts: Vec<HealthPart>,
+}
+
+impl HealthScore {
+    /// Reasons of every part that lost points, lowest part first
+    pub fn reasons(&self) -> Vec<&str> {
+        let mut parts: Vec<&HealthPart> = self.parts.iter().filter(|part| part.weight > 0).collect();
+        parts.sort_by_key(|part| part.score);
+        parts.iter().flat_map(|part| part.reasons.iter().map(String::as_str)).collect()
+    }
+}
+
+pub fn assess(app: &App, config: &HealthConfig) -> HealthScore {
+    let parts = vec![
+        metrics_part(app, config.metrics_weight),
+        alerts_part(app, config.alerts_weight),
+        services_part(app, config.services_weight),
+        cluster_part(app, config.cluster_weight),
+    ];
+    let total_weight: u32 = parts.iter().map(|part| part.weight).sum();
+    let score = match total_weight {
+        0 => 100,
+        total => {
+            let weighted: u32 = parts.iter().map(|part| part.score * part.weight).sum();
+            (weighted as f64 / total as f64).round() as u32
+        }
+    };
+    let level = if score < config.critical_below {
+        HealthLevel::Critical
+    } else if score < config.degraded_below {
+        HealthLevel::Degraded
+    } else {
+        HealthLevel::Healthy
+    };
+    HealthScore { score, level, parts }
+}
+
+/// CPU, memory and root disk: full marks up to their warning threshold,
+/// falling linearly to none at 100%; the worst of them counts
+fn metrics_part(app: &App, weight: u32) -> HealthPart {
+    let metrics = &app.system_metrics;
+    let memory = match metrics.memory_total_gb {
+        total if total > 0.0 => metrics.memory_used_gb / total * 100.0,
+        _ => 0.0,
+    };
+    let thresholds = app.warning_thresholds;
+    let mut score = 100;
+    let mut reasons = Vec::new();
+    for (name, value, warning) in [
+        ("CPU", metrics.cpu_usage, thresholds.cpu),
+        ("memory", memory, thresholds.memory),
+        ("disk", metrics.disk_usage_percent, thresholds.disk),
+    ] {
+        if value < warning || warning >= 100.0 {
+            continue;
+        }
+        let part = ((100.0 - value.min(100.0)) / (100.0 - warning) * 100.0).round() as u32;
+        score = score.min(part);
+        reasons.push(format!("{} {:.0}%", name, value));
+    }
+    HealthPart { name: "metrics", score, weight, reasons }
+}
+
+fn alerts_part(app: &App, weight: u32) -> HealthPart {
+    let active = app.alert_manager.get_active_alerts();
+    let mut penalty = 0;
+    let mut reasons = Vec::new();
+    for (level, points) in ALERT_PENALTY {
+        let count = active.iter().filter(|alert| alert.level == level).count() as u32;
+        if count > 0 {
+            penalty += count * points;
+            reasons.push(format!("{} {} alert{}", count, level.as_str().to_lowercase(), if count == 1 { "" } else { "s" }));
+        }
+    }
+    HealthPart { name: "alerts", score: 100u32.saturating_sub(penalty), weight, reasons }
+}
+
+/// The systemd units on the host card
+fn services_part(app: &App, weight: u32) -> HealthPart {
+    let mut penalty = 0;
+    let mut reasons = Vec::new();
+    for (unit, state) in &app.host_info.services {
+        match state.as_str() {
+            "active" | "reloading" | "activating" => {}
+            "failed" => {
+                penalty += FAILED_UNIT_PENALTY;
+                reasons.push(format!("{} failed", unit));
+            }
+            state => {
+                penalty += INACTIVE_UNIT_PENALTY;
+                reasons.push(format!("{} {}", unit, state));
+            }
+        }
+    }
+    HealthPart { name: "services", score: 100u32.saturating_sub(penalty), weight, reasons }
+}
+
+/// The API server, etcd and the share of nodes Ready; nothing is left
+/// while the console is in local mode
+fn cluster_part(app: &App, weight: u32) -> HealthPart {
+    if app.local_mode {
+        return HealthPart { name: "cluster", score: 0, weight, reasons: vec!["API server unreachable".to_string()] };
+    }
+    let mut score = 100u32;
+    let mut reasons = Vec::new();
+    if let Some(ref health) = app.api_health {
+        if health.error.is_some() {
+            score = score.saturating_sub(50);
+            reasons.push("API server probe failing".to_string());
+        }
+        if health.etcd_ok == Some(false) {
+            score = score.saturating_sub(30);
+            reasons.push("etcd unhealthy".to_string());
+        }
+    }
+    let info = &app.k8s_info;
+    if info.nodes_total > 0 && info.nodes_ready < info.nodes_total {
+        let not_ready = info.nodes_total - info.nodes_ready;
+        score = score.min(100 * info.nodes_ready / info.nodes_total);
+        reasons.push(format!("{}/{} nodes not ready", not_ready, info.nodes_total));
+    }
+    HealthPart { name: "cluster", score, weight, reasons }
+}
@@ -0,0 +1,96 @@
+use anyhow::{Context, Result};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+use regex::Regex;
+use std::str::FromStr;
+
+use crate::config::HighlightRule;
+
+/// User-defined log highlight patterns, compiled once from `[[logging.highlights]]`
+#[derive(Default)]
+pub struct LogHighlighter {
+    rules: Vec<(Regex, Style)>,
+}
+
+impl LogHighlighter {
+    /// Compile the configured rules, skipping (and reporting) invalid ones so
+    /// a typo in one pattern doesn't disable the rest
+    pub fn new(rules: &[HighlightRule]) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let rules = rules
+            .iter()
+            .filter_map(|rule| match compile(rule) {
+                Ok(compiled) => Some(compiled),
+                Err(e) => {
+                    errors.push(format!("{:#}", e));
+                    None
+                }
+            })
+            .collect();
+        (Self { rules }, errors)
+    }
+
+    /// Split `text` into spans, styling matches on top of `base`. Where
+    /// matches overlap, the earlier one (then the earlier rule) wins.
+    pub fn spans<'a>(&self, text: &'a str, base: Style) -> Vec<Span<'a>> {
+        if self.rules.is_empty() {
+            return vec![Span::styled(text, base)];
+        }
+
+        let mut matches: Vec<(usize, usize, usize)> = self
+            .rules
+            .iter()
+            .enumerate()
+            .flat_map(|(rule, (regex, _))| {
+                regex
+                    .find_iter(text)
+                    .filter(|m| !m.is_empty())
+                    .map(move |m| (m.start(), m.end(), rule))
+            })
+            .collect();
+        matches.sort_by_key(|&(start, _, rule)| (start, rule));
+
+        let mut spans = Vec::new();
+        let mut pos = 0;
+        for (start, end, rule) in matches {
+            if start < pos {
+                continue;
+            }
+            if start > pos {
+                spans.push(Span::styled(&text[pos..start], base));
+            }
+            spans.push(Span::styled(&text[start..end], base.patch(self.rules[rule].1)));
+            pos = end;
+        }
+        if pos < text.len() {
+            spans.push(Span::styled(&text[pos..], base));
+        }
+        spans
+    }
+}
+
+fn compile(rule: &HighlightRule) -> Result<(Regex, Style)> {
+    let regex = Regex::new(&rule.pattern)
+        .with_context(|| format!("Invalid highlight pattern {:?}", rule.pattern))?;
+
+    let mut style = Style::default();
+    if let Some(ref color) = rule.color {
+        style = style.fg(parse_color(color)?);
+    }
+    if let Some(ref color) = rule.background {
+        style = style.bg(parse_color(color)?);
+    }
+    if rule.bold {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if rule.underline {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+    Ok((regex, style))
+}
+
+fn parse_color(name: &str) -> Result<Color> {
+    Color::from_str(name).map_err(|_| anyhow::anyhow!("Unknown highlight color {:?}", name))
+}
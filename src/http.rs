@@ -0,0 +1,43 @@
+//! Just enough HTTP/1.1 for the web bridge, the webhook receiver and the
+//! agent: one request per connection, answered and closed.
+
+use anyhow::{bail, Result};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Upper bound on a request head; anything longer is rejected
+pub const MAX_REQUEST_HEAD: usize = 8192;
+
+/// The request head, without the blank line ending it, and whatever of
+/// the body arrived with it
+pub async fn read_head<S: AsyncRead + Unpin>(stream: &mut S) -> Result<(String, Vec<u8>)> {
+    let mut buf = Vec::new();
+    let mut chunk = [0; 4096];
+    loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            bail!("Connection closed before the request");
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(&buf[..end]).into_owned();
+            return Ok((head, buf.split_off(end + 4)));
+        }
+        if buf.len() > MAX_REQUEST_HEAD {
+            bail!("Request head larger than {} bytes", MAX_REQUEST_HEAD);
+        }
+    }
+}
+
+/// Send a complete response and close the connection
+pub async fn respond<S: AsyncWrite + Unpin>(stream: &mut S, status: &str, content_type: &str, body: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
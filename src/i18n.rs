@@ -0,0 +1,645 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// UI language, from `display.locale`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// From `$LC_ALL`, `$LC_MESSAGES` or `$LANG`, English when unset or
+    /// not translated
+    #[default]
+    Auto,
+    En,
+    De,
+}
+
+impl Locale {
+    pub fn resolve(self) -> Locale {
+        match self {
+            Locale::Auto => ["LC_ALL", "LC_MESSAGES", "LANG"]
+                .iter()
+                .filter_map(|var| std::env::var(var).ok())
+                .find(|value| !value.is_empty())
+                .map(|value| Locale::from_tag(&value))
+                .unwrap_or(Locale::En),
+            locale => locale,
+        }
+    }
+
+    /// "de_DE.UTF-8" -> De
+    fn from_tag(tag: &str) -> Locale {
+        match tag.split(['_', '.', '-', '@']).next().unwrap_or_default() {
+            "de" => Locale::De,
+            _ => Locale::En,
+        }
+    }
+
+    fn index(self) -> u8 {
+        match self {
+            Locale::Auto | Locale::En => 0,
+            Locale::De => 1,
+        }
+    }
+}
+
+/// The resolved locale; alert rules read it too, so it is process-wide
+static LOCALE: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale.resolve().index(), Ordering::Relaxed);
+}
+
+/// The resolved locale's index, for telling when translated text is stale
+pub fn locale_index() -> u8 {
+    LOCALE.load(Ordering::Relaxed)
+}
+
+/// Message id, English, German. `{0}`, `{1}`, ... are filled in by `tf`
+/// and may be reordered by a translation.
+const CATALOG: &[(&str, &str, &str)] = &[
+    // Screens, as named in the footer and the command palette
+    ("screen.logs", "Logs", "Protokolle"),
+    ("screen.dashboard", "Dashboard", "Übersicht"),
+    ("screen.network", "Network", "Netzwerk"),
+    ("screen.resources", "Resources", "Ressourcen"),
+    ("screen.timeline", "Timeline", "Zeitleiste"),
+    ("screen.charts", "Charts", "Diagramme"),
+    ("screen.heatmap", "Heatmap", "Heatmap"),
+    ("screen.fleet", "Fleet", "Flotte"),
+    ("screen.hostmap", "Host Map", "Host-Karte"),
+    ("screen.tools", "Tools", "Werkzeuge"),
+    ("palette.switch_to", "Switch to {0}", "Wechseln zu {0}"),
+    // Header
+    ("header.node", "Node", "Knoten"),
+    ("header.uptime", "Uptime", "Laufzeit"),
+    ("header.health", "Health", "Zustand"),
+    ("health.healthy", "OK", "OK"),
+    ("health.degraded", "DEGRADED", "EINGESCHRÄNKT"),
+    ("health.critical", "CRITICAL", "KRITISCH"),
+    ("header.cpu", "CPU", "CPU"),
+    ("header.memory", "Memory", "Speicher"),
+    ("header.vms", "VMs", "VMs"),
+    ("header.load", "Load", "Last"),
+    ("header.alerts", "Alerts", "Alarme"),
+    ("header.no_alerts", "none", "keine"),
+    ("header.running", "Running", "Läuft"),
+    ("header.unreachable", "Unreachable", "Nicht erreichbar"),
+    ("header.local_mode", "API unreachable, LOCAL MODE", "API nicht erreichbar, LOKALER MODUS"),
+    // Footer
+    ("footer.scroll", "Scroll", "Blättern"),
+    ("footer.alerts", "Alerts", "Alarme"),
+    ("footer.refresh", "Refresh", "Aktualisieren"),
+    ("footer.commands", "Commands", "Befehle"),
+    ("footer.quit", "Quit", "Beenden"),
+    ("footer.profile", "Profile", "Profil"),
+    ("footer.read_only", "READ-ONLY", "NUR LESEN"),
+    ("footer.update", "Update", "Update"),
+    // Footer hints for the screen's own keys (keymap.rs)
+    ("hint.search", "Search", "Suchen"),
+    ("hint.details", "Details", "Details"),
+    ("hint.collapse", "Collapse", "Zusammenfassen"),
+    ("hint.wrap", "Wrap", "Umbrechen"),
+    ("hint.services", "Services", "Dienste"),
+    ("hint.history", "History", "Verlauf"),
+    ("hint.search_all", "Search all", "Alle durchsuchen"),
+    ("hint.layout", "Layout", "Layout"),
+    ("hint.compare", "Compare", "Vergleichen"),
+    ("hint.kind", "Kind", "Art"),
+    ("hint.selector", "Selector", "Selektor"),
+    ("hint.mark", "Mark", "Markieren"),
+    ("hint.batch", "Batch", "Stapel"),
+    ("hint.yaml", "YAML", "YAML"),
+    ("hint.watch", "Watch", "Beobachten"),
+    ("hint.cpu", "CPU tuning", "CPU-Tuning"),
+    ("hint.clone", "Clone", "Klonen"),
+    ("hint.hotplug", "Hot-plug", "Hot-Plug"),
+    ("hint.node", "Node labels", "Knoten-Labels"),
+    ("hint.cloud_init", "Cloud-init", "Cloud-init"),
+    ("hint.group", "Group", "Gruppieren"),
+    ("hint.metric", "Metric", "Metrik"),
+    ("hint.cursor", "Cursor", "Cursor"),
+    ("hint.zoom", "Zoom", "Zoom"),
+    ("hint.move", "Move", "Bewegen"),
+    ("hint.open", "Open", "Öffnen"),
+    ("hint.sort", "Sort", "Sortieren"),
+    ("hint.reverse", "Reverse", "Umkehren"),
+    ("hint.evacuate", "Evacuate", "Evakuieren"),
+    ("hint.images", "Images", "Images"),
+    ("hint.processes", "Processes", "Prozesse"),
+    ("hint.memory", "Memory", "Speicher"),
+    ("hint.delete", "Delete", "Löschen"),
+    ("hint.catalog", "Catalog", "Katalog"),
+    ("hint.pause", "Pause/resume", "Anhalten/Fortsetzen"),
+    ("hint.kill", "Kill", "Beenden"),
+    ("hint.local_mode", "Local mode", "Lokaler Modus"),
+    ("hint.pane", "Pane", "Bereich"),
+    ("banner.hint", "[Press 'a' to view/dismiss]", "['a' zum Anzeigen/Verwerfen]"),
+    // Host alerts
+    ("alert.cpu.critical.title", "Critical CPU Usage", "Kritische CPU-Auslastung"),
+    ("alert.cpu.critical.message", "CPU usage is critically high at {0}%", "CPU-Auslastung ist kritisch hoch: {0}%"),
+    ("alert.cpu.high.title", "High CPU Usage", "Hohe CPU-Auslastung"),
+    ("alert.cpu.high.message", "CPU usage is high at {0}%", "CPU-Auslastung ist hoch: {0}%"),
+    ("alert.memory.critical.title", "Critical Memory Usage", "Kritische Speicherauslastung"),
+    (
+        "alert.memory.critical.message",
+        "Memory usage is critically high at {0}% ({1}/{2} GB)",
+        "Speicherauslastung ist kritisch hoch: {0}% ({1}/{2} GB)",
+    ),
+    ("alert.memory.high.title", "High Memory Usage", "Hohe Speicherauslastung"),
+    (
+        "alert.memory.high.message",
+        "Memory usage is high at {0}% ({1}/{2} GB)",
+        "Speicherauslastung ist hoch: {0}% ({1}/{2} GB)",
+    ),
+    ("alert.disk.critical.title", "Critical Disk Usage", "Kritische Festplattenbelegung"),
+    ("alert.disk.critical.message", "Disk usage is critically high at {0}%", "Festplattenbelegung ist kritisch hoch: {0}%"),
+    ("alert.disk.high.title", "High Disk Usage", "Hohe Festplattenbelegung"),
+    ("alert.disk.high.message", "Disk usage is high at {0}%", "Festplattenbelegung ist hoch: {0}%"),
+    ("alert.load.critical.title", "Critical Load Average", "Kritische Systemlast"),
+    ("alert.load.critical.message", "Load average is critically high at {0}", "Systemlast ist kritisch hoch: {0}"),
+    ("alert.load.high.title", "High Load Average", "Hohe Systemlast"),
+    ("alert.load.high.message", "Load average is high at {0}", "Systemlast ist hoch: {0}"),
+    // Cluster alerts
+    ("alert.nodes_not_ready.title", "{0} Nodes Not Ready", "{0} Knoten nicht bereit"),
+    (
+        "alert.nodes_not_ready.message",
+        "{0} of {1} cluster nodes are not in Ready state",
+        "{0} von {1} Cluster-Knoten sind nicht bereit",
+    ),
+    ("alert.cluster_unreachable.title", "Cluster Unreachable", "Cluster nicht erreichbar"),
+    (
+        "alert.cluster_unreachable.message",
+        "Unable to connect to Kubernetes cluster or cluster has no nodes",
+        "Keine Verbindung zum Kubernetes-Cluster oder der Cluster hat keine Knoten",
+    ),
+    ("alert.vms_migrating.title", "{0} VMs Migrating", "{0} VMs werden migriert"),
+    (
+        "alert.vms_migrating.message",
+        "{0} virtual machines are currently migrating",
+        "{0} virtuelle Maschinen werden gerade migriert",
+    ),
+    // Services and nodes
+    ("alert.svc_endpoints.title", "Service {0}/{1} Has No Endpoints", "Service {0}/{1} hat keine Endpunkte"),
+    (
+        "alert.svc_endpoints.message",
+        "All endpoints of {0}/{1} are gone ({2} not ready)",
+        "Alle Endpunkte von {0}/{1} sind weg ({2} nicht bereit)",
+    ),
+    ("alert.no_response", "no response", "keine Antwort"),
+    ("alert.svc_vip.title", "Service {0} VIP Not Responding", "VIP von Service {0} antwortet nicht"),
+    (
+        "alert.svc_vip.message",
+        "{0} failed {1} consecutive connection attempts ({2}) although {3} endpoints are ready; check kube-proxy, the load balancer and firewall rules",
+        "{0}: {1} Verbindungsversuche in Folge fehlgeschlagen ({2}), obwohl {3} Endpunkte bereit sind; kube-proxy, Load Balancer und Firewall-Regeln prüfen",
+    ),
+    ("alert.node_condition.title", "Node {0} {1}", "Knoten {0} {1}"),
+    (
+        "alert.node_unreachable.message",
+        "Node {0} is {1} with {2} VMs scheduled on it",
+        "Knoten {0} ist {1}, {2} VMs sind ihm zugewiesen",
+    ),
+    ("alert.node_pressure.message", "Node {0} reports {1}", "Knoten {0} meldet {1}"),
+    ("alert.kubelet.message", "Kubelet on {0} reports {1}", "Kubelet auf {0} meldet {1}"),
+    ("alert.kubelet.since", " (since {0})", " (seit {0})"),
+    ("alert.kubelet.evicted_at", "; pods are evicted at {0}", "; Pods werden bei {0} verdrängt"),
+    // Quotas and tenants
+    ("alert.quota.title", "Quota {0} {1} Near Limit", "Quota {0} {1} fast erschöpft"),
+    (
+        "alert.quota.message",
+        "Namespace {0} has used {1}% of its {2} quota ({3}, threshold: {4}%)",
+        "Namespace {0} hat {1}% seiner {2}-Quota verbraucht ({3}, Schwelle: {4}%)",
+    ),
+    ("alert.tenant.vcpus", "vCPU", "vCPU"),
+    ("alert.tenant.memory", "Memory", "Speicher"),
+    ("alert.tenant.storage", "Storage", "Datenspeicher"),
+    ("alert.tenant_limit.title", "Tenant {0} {1} Limit Reached", "Mandant {0}: {1}-Limit erreicht"),
+    (
+        "alert.tenant_limit.message",
+        "Tenant {0} has {1} of {2} against a limit of {3} ({4} VMs, {5} pods)",
+        "Mandant {0} hat {1} {2} bei einem Limit von {3} ({4} VMs, {5} Pods)",
+    ),
+    ("alert.tenant_growth.title", "Tenant {0} Footprint Grew {1}%", "Mandant {0} um {1}% gewachsen"),
+    (
+        "alert.tenant_growth.message",
+        "Tenant {0} now has {1} VMs, {2} cores and {3} GiB of memory, {4}% more than at the start of the growth window",
+        "Mandant {0} hat jetzt {1} VMs, {2} Kerne und {3} GiB Speicher, {4}% mehr als zu Beginn des Beobachtungsfensters",
+    ),
+    // File descriptors, ports and limits
+    ("alert.fds_system.title", "System File Descriptors Running Out", "Dateideskriptoren des Systems werden knapp"),
+    (
+        "alert.fds_system.message",
+        "{0} of {1} file handles allocated ({2}%, fs.file-max)",
+        "{0} von {1} Datei-Handles belegt ({2}%, fs.file-max)",
+    ),
+    ("alert.ephemeral_ports.title", "Ephemeral Ports Running Out", "Ephemere Ports werden knapp"),
+    (
+        "alert.ephemeral_ports.message",
+        "{0} of the ports {1}-{2} in use ({3}%, {4} in TIME_WAIT); outbound connections will start failing",
+        "{0} der Ports {1}-{2} belegt ({3}%, {4} in TIME_WAIT); ausgehende Verbindungen werden bald fehlschlagen",
+    ),
+    ("alert.fds_process.title", "{0} Near Open File Limit", "{0} nahe am Limit offener Dateien"),
+    (
+        "alert.fds_process.message",
+        "{0} (pid {1}) has {2} of {3} files open ({4}%); it fails with \"too many open files\" at the limit",
+        "{0} (PID {1}) hat {2} von {3} Dateien geöffnet ({4}%); am Limit schlägt es mit \"too many open files\" fehl",
+    ),
+    ("alert.port.title", "{0} Not Listening", "{0} lauscht nicht"),
+    (
+        "alert.port_stopped.message",
+        "{0} stopped listening on port {1}; check the service with `systemctl status` and its logs",
+        "{0} lauscht nicht mehr auf Port {1}; den Dienst mit `systemctl status` und seine Protokolle prüfen",
+    ),
+    (
+        "alert.port_closed.message",
+        "{0} is not listening on port {1}; check the service with `systemctl status` and its logs",
+        "{0} lauscht nicht auf Port {1}; den Dienst mit `systemctl status` und seine Protokolle prüfen",
+    ),
+    ("alert.pids.title", "PIDs Running Out", "PIDs werden knapp"),
+    (
+        "alert.pids.message",
+        "{0} of {1} PIDs in use ({2}%, {3}); new processes and threads fail with \"Resource temporarily unavailable\" at the limit",
+        "{0} von {1} PIDs belegt ({2}%, {3}); am Limit schlagen neue Prozesse und Threads mit \"Resource temporarily unavailable\" fehl",
+    ),
+    ("alert.entropy.title", "Entropy Pool Low", "Entropie-Pool niedrig"),
+    (
+        "alert.entropy.message",
+        "{0} bits of entropy available; reads from /dev/random and getrandom() may block (is rngd or virtio-rng set up?)",
+        "{0} Bit Entropie verfügbar; Lesen von /dev/random und getrandom() kann blockieren (ist rngd oder virtio-rng eingerichtet?)",
+    ),
+    // CPU frequency
+    ("alert.cpu_freq.title", "CPU Frequency Throttled", "CPU-Takt gedrosselt"),
+    (
+        "alert.cpu_freq.message",
+        "{0} of {1} busy cores below {2}% of {3} for {4} min (averaging {5} MHz); {6}",
+        "{0} von {1} ausgelasteten Kernen seit {4} min unter {2}% {3} (im Mittel {5} MHz); {6}",
+    ),
+    ("alert.cpu_freq.base_clock", "the {0} MHz base clock", "des Basistakts von {0} MHz"),
+    ("alert.cpu_freq.max_clock", "their maximum clock", "ihres Höchsttakts"),
+    (
+        "alert.cpu_freq.thermal",
+        "{0} thermal throttle events since startup; check cooling and fans",
+        "{0} thermische Drosselungen seit dem Start; Kühlung und Lüfter prüfen",
+    ),
+    ("alert.cpu_freq.powersave", "the governor is powersave", "der Governor ist powersave"),
+    (
+        "alert.cpu_freq.power_limit",
+        "no thermal events, so likely a power (RAPL/BIOS) limit",
+        "keine thermischen Ereignisse, also vermutlich ein Leistungslimit (RAPL/BIOS)",
+    ),
+    // API server and etcd
+    ("alert.api_healthz.title", "API Server Health Check Failing", "Health-Check des API-Servers schlägt fehl"),
+    ("alert.api_healthz.message", "GET /healthz failed: {0}", "GET /healthz fehlgeschlagen: {0}"),
+    ("alert.api_etcd.title", "etcd Unhealthy", "etcd fehlerhaft"),
+    (
+        "alert.api_etcd.message",
+        "The API server's etcd check is failing: {0}",
+        "Der etcd-Check des API-Servers schlägt fehl: {0}",
+    ),
+    ("alert.api_latency.title", "Slow API Server", "Langsamer API-Server"),
+    (
+        "alert.api_latency.message",
+        "API server took {0} ms to respond (healthz {1}, list {2}{3}; threshold: {4} ms)",
+        "API-Server brauchte {0} ms für eine Antwort (healthz {1}, list {2}{3}; Schwelle: {4} ms)",
+    ),
+    ("alert.failed", "failed", "fehlgeschlagen"),
+    ("alert.etcd_quota.title", "etcd Database Near Quota", "etcd-Datenbank nahe an der Quota"),
+    (
+        "alert.etcd_quota.message",
+        "etcd database is {0} MiB of its {1} MiB quota ({2}%, threshold: {3}%); writes fail once it is full{4}",
+        "etcd-Datenbank belegt {0} MiB ihrer Quota von {1} MiB ({2}%, Schwelle: {3}%); ist sie voll, schlagen Schreibzugriffe fehl{4}",
+    ),
+    (
+        "alert.etcd_quota.fragmented",
+        "; {0}% is free pages, so a defrag would reclaim much of it",
+        "; {0}% sind freie Seiten, ein Defrag würde viel davon zurückgewinnen",
+    ),
+    (
+        "alert.etcd_quota.defrag",
+        "; compact and defrag, or raise --quota-backend-bytes",
+        "; compact und defrag ausführen oder --quota-backend-bytes erhöhen",
+    ),
+    ("alert.etcd_leader.title", "etcd Has No Leader", "etcd hat keinen Leader"),
+    (
+        "alert.etcd_leader.message",
+        "etcd at {0} has no raft leader; the API server cannot write",
+        "etcd unter {0} hat keinen Raft-Leader; der API-Server kann nicht schreiben",
+    ),
+    ("alert.etcd_churn.title", "etcd Leader Churn", "Häufige etcd-Leaderwechsel"),
+    (
+        "alert.etcd_churn.message",
+        "etcd elected a new leader {0} times in the past hour (threshold: {1}); usually slow disks or a congested network between members",
+        "etcd hat in der letzten Stunde {0}-mal einen neuen Leader gewählt (Schwelle: {1}); meist langsame Platten oder ein überlastetes Netz zwischen den Mitgliedern",
+    ),
+    ("alert.etcd_proposals.title", "etcd Proposals Failing", "etcd-Proposals schlagen fehl"),
+    (
+        "alert.etcd_proposals.message",
+        "{0} raft proposals failed since the last check ({1} in total); writes are being lost to elections or a lost quorum",
+        "{0} Raft-Proposals seit der letzten Prüfung fehlgeschlagen ({1} insgesamt); Schreibzugriffe gehen durch Wahlen oder ein verlorenes Quorum verloren",
+    ),
+    // VMs and migrations
+    ("alert.migrations.title", "Migrations Failing on {0}", "Migrationen auf {0} schlagen fehl"),
+    (
+        "alert.migrations.message",
+        "The last {0} live migrations to or from {1} failed{2}",
+        "Die letzten {0} Live-Migrationen von oder nach {1} sind fehlgeschlagen{2}",
+    ),
+    ("alert.migrations.last_failure", "; last failure: {0}", "; letzter Fehler: {0}"),
+    ("alert.slow_boot.title", "Slow VM Boot: {0}", "Langsamer VM-Start: {0}"),
+    (
+        "alert.slow_boot.starting",
+        "{0} has been starting for {1}s{2} and isn't Running yet; it usually takes {3}s (median of its last {4} boots). Check its disks' storage and image pulls",
+        "{0} startet seit {1}s{2} und läuft noch nicht; normalerweise dauert es {3}s (Median der letzten {4} Starts). Speicher der Platten und Image-Pulls prüfen",
+    ),
+    (
+        "alert.slow_boot.message",
+        "{0} took {1}s to reach Running{2}, against its usual {3}s (median of its last {4} boots). Slow boots often come from slow storage or image pulls",
+        "{0} brauchte {1}s bis Running{2}, statt der üblichen {3}s (Median der letzten {4} Starts). Langsame Starts kommen oft von langsamem Speicher oder Image-Pulls",
+    ),
+    ("alert.slow_boot.on_node", " on {0}", " auf {0}"),
+    // DataVolumes and VM scheduling
+    ("alert.datavolume_failed.title", "DataVolume {0} Failed", "DataVolume {0} fehlgeschlagen"),
+    ("alert.datavolume_failed.message", "{0} from {1} failed{2}", "{0} aus {1} fehlgeschlagen{2}"),
+    ("alert.datavolume_failing.title", "DataVolume {0} Failing", "DataVolume {0} scheitert wiederholt"),
+    (
+        "alert.datavolume_failing.message",
+        "The {0} importer for {1} restarted {2} times{3}",
+        "Der {0}-Importer für {1} wurde {2}-mal neu gestartet{3}",
+    ),
+    ("alert.datavolume_stuck.title", "DataVolume {0} Stuck", "DataVolume {0} hängt"),
+    ("alert.datavolume_stuck.message", "{0} has been {1}{2} for {3} minutes{4}", "{0} ist seit {3} Minuten {1}{2}{4}"),
+    ("alert.datavolume_stuck.progress", " at {0}%", " bei {0}%"),
+    ("alert.sched_wait.title", "vCPUs Waiting for CPU: {0}", "vCPUs warten auf CPU: {0}"),
+    (
+        "alert.sched_wait.message",
+        "The {0} vCPUs of {1} have spent at least {2}% of their time waiting for a physical CPU; the host is overcommitted",
+        "Die {0} vCPUs von {1} haben mindestens {2}% ihrer Zeit auf eine physische CPU gewartet; der Host ist überbucht",
+    ),
+    ("alert.cpu_throttle.title", "VM CPU Throttled: {0}", "VM-CPU gedrosselt: {0}"),
+    (
+        "alert.cpu_throttle.message",
+        "{0} has hit its CPU limit in at least {1}% of scheduling periods; raise the limit or resize the VM",
+        "{0} hat in mindestens {1}% der Scheduling-Perioden sein CPU-Limit erreicht; Limit erhöhen oder die VM vergrößern",
+    ),
+    // Storage and network hardware
+    ("alert.sriov.title", "SR-IOV VF Pool Exhausted on {0}", "SR-IOV-VF-Pool auf {0} erschöpft"),
+    (
+        "alert.sriov.message",
+        "All {0} VFs of {1} ({2}) are assigned; the NIC supports up to {3} (sriov_numvfs)",
+        "Alle {0} VFs von {1} ({2}) sind vergeben; die NIC unterstützt bis zu {3} (sriov_numvfs)",
+    ),
+    ("alert.multipath_none.title", "No Working Paths to LUN {0}", "Keine funktionierenden Pfade zu LUN {0}"),
+    ("alert.multipath_half.title", "LUN {0} Lost Half Its Paths", "LUN {0} hat die Hälfte ihrer Pfade verloren"),
+    ("alert.multipath_degraded.title", "Multipath Degraded on LUN {0}", "Multipath auf LUN {0} beeinträchtigt"),
+    ("alert.multipath.hba_port", "{0} ({1}, HBA port {2})", "{0} ({1}, HBA-Port {2})"),
+    (
+        "alert.multipath.message",
+        "{0} of {1} paths failed, {2} of {3} path groups degraded ({4}, {5}, {6}): {7}",
+        "{0} von {1} Pfaden ausgefallen, {2} von {3} Pfadgruppen beeinträchtigt ({4}, {5}, {6}): {7}",
+    ),
+    ("alert.disk_latency.title", "Slow Disk: {0}", "Langsame Platte: {0}"),
+    (
+        "alert.disk_latency.message",
+        "Requests to {0} have taken at least {1} ms each for 30 seconds ({2}% busy, {3} MB/s read, {4} MB/s write{5}); the device is saturated or failing",
+        "Anfragen an {0} dauern seit 30 Sekunden jeweils mindestens {1} ms ({2}% ausgelastet, {3} MB/s lesen, {4} MB/s schreiben{5}); das Gerät ist überlastet oder fällt aus",
+    ),
+    ("alert.disk_latency.p99", ", p99 {0} ms over 5 minutes", ", p99 {0} ms über 5 Minuten"),
+    ("alert.io_scheduler.title", "Suboptimal I/O Scheduler: {0}", "Ungünstiger I/O-Scheduler: {0}"),
+    (
+        "alert.io_scheduler.message",
+        "VM datastore {0} ({1}) uses the {2} scheduler; {3} suits it better (echo {3} > /sys/block/{4}/queue/scheduler, or a udev rule in services.udev.extraRules to keep it)",
+        "VM-Datenspeicher {0} ({1}) nutzt den Scheduler {2}; {3} passt besser (echo {3} > /sys/block/{4}/queue/scheduler, oder dauerhaft per udev-Regel in services.udev.extraRules)",
+    ),
+    ("alert.io_scheduler.rotational", "rotational", "rotierend"),
+    ("alert.ip_conflict.title", "IP Conflict on {0}", "IP-Konflikt bei {0}"),
+    (
+        "alert.ip_conflict.message",
+        "{0} is claimed by {1} on {2} (last at {3}); a VM cloned without a new MAC or machine-id?",
+        "{0} wird von {1} auf {2} beansprucht (zuletzt um {3}); eine VM ohne neue MAC oder machine-id geklont?",
+    ),
+    ("alert.dhcp_churn.title", "DHCP Lease Churn for {0}", "Häufige DHCP-Leases für {0}"),
+    (
+        "alert.dhcp_churn.message",
+        "{0} DHCP leases for {1} on {2} in the last {3} minutes ({4})",
+        "{0} DHCP-Leases für {1} auf {2} in den letzten {3} Minuten ({4})",
+    ),
+    ("alert.mtu.title", "MTU Mismatch: {0}", "MTU-Abweichung: {0}"),
+    (
+        "alert.mtu.message_one",
+        "{0} has MTU {1} but {2} link differs: {3}; frames above the smaller MTU are dropped without an error",
+        "{0} hat MTU {1}, aber {2} Link weicht ab: {3}; Frames über der kleineren MTU werden ohne Fehler verworfen",
+    ),
+    (
+        "alert.mtu.message",
+        "{0} has MTU {1} but {2} links differ: {3}; frames above the smaller MTU are dropped without an error",
+        "{0} hat MTU {1}, aber {2} Links weichen ab: {3}; Frames über der kleineren MTU werden ohne Fehler verworfen",
+    ),
+    // Firmware, sysctls and host services
+    (
+        "alert.firmware.changed",
+        " since the firmware changed from {0} to {1}",
+        ", seit die Firmware von {0} auf {1} geändert wurde",
+    ),
+    ("alert.virtualization.title", "Virtualization Extensions Disabled", "Virtualisierungserweiterungen deaktiviert"),
+    (
+        "alert.virtualization.message",
+        "The CPU doesn't advertise VT-x or AMD-V{0}; KubeVirt can't run VMs with KVM. Enable them in firmware setup",
+        "Die CPU meldet kein VT-x oder AMD-V{0}; KubeVirt kann keine VMs mit KVM ausführen. Im Firmware-Setup aktivieren",
+    ),
+    (
+        "alert.virtualization.lost",
+        "The CPU doesn't advertise VT-x or AMD-V any more{0}; KubeVirt can't run VMs with KVM. Enable them in firmware setup",
+        "Die CPU meldet kein VT-x oder AMD-V mehr{0}; KubeVirt kann keine VMs mit KVM ausführen. Im Firmware-Setup aktivieren",
+    ),
+    ("alert.iommu.title", "IOMMU Disabled", "IOMMU deaktiviert"),
+    (
+        "alert.iommu.message",
+        "The IOMMU was enabled on this host before but isn't now{0}; PCI passthrough and SR-IOV VFs can't be given to VMs. Check VT-d/AMD-Vi in firmware setup and intel_iommu=on",
+        "Die IOMMU war auf diesem Host aktiv, ist es aber nicht mehr{0}; PCI-Passthrough und SR-IOV-VFs können nicht an VMs gegeben werden. VT-d/AMD-Vi im Firmware-Setup und intel_iommu=on prüfen",
+    ),
+    (
+        "alert.sysctl.hint.ip_forward",
+        "pod and VM traffic won't be routed off this node",
+        "Pod- und VM-Verkehr wird nicht von diesem Knoten weg geroutet",
+    ),
+    (
+        "alert.sysctl.hint.bridge_nf_call",
+        "service traffic over bridges skips iptables; is the br_netfilter module loaded?",
+        "Service-Verkehr über Bridges umgeht iptables; ist das Modul br_netfilter geladen?",
+    ),
+    (
+        "alert.sysctl.hint.overcommit_memory",
+        "the kubelet's protect-kernel-defaults check will refuse to start",
+        "der protect-kernel-defaults-Check des Kubelets verweigert den Start",
+    ),
+    (
+        "alert.sysctl.hint.inotify",
+        "kubelet, log tailing and VM file watches can fail with \"too many open files\"",
+        "Kubelet, Protokoll-Tailing und Dateiüberwachung in VMs können mit \"too many open files\" fehlschlagen",
+    ),
+    ("alert.sysctl.title", "Sysctl {0} Mismatch", "Sysctl {0} weicht ab"),
+    ("alert.sysctl.message", "{0} is {1}, expected {2}", "{0} ist {1}, erwartet {2}"),
+    ("alert.sysctl.missing", "{0} doesn't exist, expected {1}", "{0} existiert nicht, erwartet {1}"),
+    ("alert.unit.title", "Service {0} Failed", "Dienst {0} fehlgeschlagen"),
+    (
+        "alert.unit.message",
+        "systemd unit {0} is in the failed state; see `journalctl -u {0}`",
+        "systemd-Unit {0} ist im Zustand failed; siehe `journalctl -u {0}`",
+    ),
+    ("alert.upgrade.title", "NixOS Upgrade Failed", "NixOS-Upgrade fehlgeschlagen"),
+    // DNS, routes and registries
+    ("alert.coredns.title", "CoreDNS Not Answering", "CoreDNS antwortet nicht"),
+    ("alert.dns.title", "DNS Resolver {0} Failing", "DNS-Resolver {0} fällt aus"),
+    (
+        "alert.dns.message",
+        "{0} resolver {1} failed {2} consecutive probe rounds ({3})",
+        "{0}-Resolver {1} hat {2} Prüfrunden in Folge nicht bestanden ({3})",
+    ),
+    ("alert.route_lost.title", "Default Route Lost", "Standardroute verloren"),
+    (
+        "alert.route_lost.message",
+        "The host's default route disappeared; check the CNI, NetworkManager or networkd and `ip route`",
+        "Die Standardroute des Hosts ist verschwunden; CNI, NetworkManager oder networkd und `ip route` prüfen",
+    ),
+    ("alert.route_none.title", "No Default Route", "Keine Standardroute"),
+    (
+        "alert.route_none.message",
+        "The host has no default route, so nothing outside its directly connected networks is reachable",
+        "Der Host hat keine Standardroute, außerhalb seiner direkt verbundenen Netze ist nichts erreichbar",
+    ),
+    ("alert.none", "none", "keine"),
+    ("alert.route_flap.title", "Default Route Flapping", "Standardroute wechselt ständig"),
+    (
+        "alert.route_flap.message",
+        "The default route changed {0} times recently; now: {1}",
+        "Die Standardroute hat sich zuletzt {0}-mal geändert; jetzt: {1}",
+    ),
+    ("alert.no_reply", "no reply", "keine Antwort"),
+    ("alert.gateway.title", "Gateway {0} Unreachable", "Gateway {0} nicht erreichbar"),
+    (
+        "alert.gateway.message",
+        "Default gateway {0} on {1} failed {2} consecutive pings ({3})",
+        "Standard-Gateway {0} an {1} hat {2} Pings in Folge nicht beantwortet ({3})",
+    ),
+    ("alert.disk_forecast.title", "Filesystem {0} Filling Up", "Dateisystem {0} läuft voll"),
+    (
+        "alert.disk_forecast.message",
+        "{0} ({1}) is {2}% used and at its current {3} growth will be full in ~{4}",
+        "{0} ({1}) ist zu {2}% belegt und bei anhaltendem Wachstum ({3}) in ~{4} voll",
+    ),
+    ("alert.registry.title", "Registry {0} Unreachable", "Registry {0} nicht erreichbar"),
+    (
+        "alert.registry.message",
+        "{0} failed {1} consecutive probes of /v2/ ({2}); image pulls from it will fail",
+        "{0} hat {1} Prüfungen von /v2/ in Folge nicht bestanden ({2}); Image-Pulls von dort schlagen fehl",
+    ),
+    ("alert.unknown_error", "unknown error", "unbekannter Fehler"),
+    ("alert.image_pull.title", "Image Pull Failing", "Image-Pull schlägt fehl"),
+    ("alert.image_pull.message", "{0} failed to pull {1} times: {2}", "{0} konnte {1}-mal nicht geladen werden: {2}"),
+    ("alert.image_pull.registry_down", "registry {0} is unreachable", "Registry {0} ist nicht erreichbar"),
+    // Pods and guests
+    ("alert.pod_waiting.title", "Pod {0} {1}", "Pod {0} {1}"),
+    (
+        "alert.pod_waiting.message",
+        "Container {0} of {1} is in {2} ({3} restarts, {4} in the past hour){5}",
+        "Container {0} von {1} ist in {2} ({3} Neustarts, {4} in der letzten Stunde){5}",
+    ),
+    ("alert.pod_restarts.title", "Pod {0} Restarting", "Pod {0} startet ständig neu"),
+    (
+        "alert.pod_restarts.message",
+        "{0} restarted {1} times in the past hour ({2} in total)",
+        "{0} wurde in der letzten Stunde {1}-mal neu gestartet ({2} insgesamt)",
+    ),
+    ("alert.guest_fs.title", "Guest Filesystem {0} Full", "Gast-Dateisystem {0} voll"),
+    (
+        "alert.guest_fs.message",
+        "VM {0} filesystem {1} ({2}) is {3}% full (threshold: {4}%)",
+        "Dateisystem {1} ({2}) der VM {0} ist zu {3}% voll (Schwelle: {4}%)",
+    ),
+    ("alert.guest_clock.title", "VM {0} Clock Drift", "Uhrabweichung der VM {0}"),
+    (
+        "alert.guest_clock.ahead",
+        "VM {0}'s clock is {1}s ahead of the host's (threshold: {2}s); Kerberos and TLS in the guest fail once it is far enough off. Check the guest's time sync (w32time on Windows, chrony/timesyncd on Linux)",
+        "Die Uhr der VM {0} geht {1}s gegenüber dem Host vor (Schwelle: {2}s); Kerberos und TLS im Gast schlagen fehl, sobald sie weit genug abweicht. Zeitsynchronisierung im Gast prüfen (w32time unter Windows, chrony/timesyncd unter Linux)",
+    ),
+    (
+        "alert.guest_clock.behind",
+        "VM {0}'s clock is {1}s behind the host's (threshold: {2}s); Kerberos and TLS in the guest fail once it is far enough off. Check the guest's time sync (w32time on Windows, chrony/timesyncd on Linux)",
+        "Die Uhr der VM {0} geht {1}s gegenüber dem Host nach (Schwelle: {2}s); Kerberos und TLS im Gast schlagen fehl, sobald sie weit genug abweicht. Zeitsynchronisierung im Gast prüfen (w32time unter Windows, chrony/timesyncd unter Linux)",
+    ),
+    // Kernel, OOM kills and crashes
+    (
+        "alert.kernel.message",
+        "{0} kernel message(s), latest at {1}: {2}",
+        "{0} Kernel-Meldung(en), die letzte um {1}: {2}",
+    ),
+    ("alert.oom.title", "OOM Kill: {0}", "OOM-Kill: {0}"),
+    (
+        "alert.oom.message",
+        "{0} OOM kill(s) in the last {1}m, latest {2} at {3}{4}{5}{6}",
+        "{0} OOM-Kill(s) in den letzten {1} min, zuletzt {2} um {3}{4}{5}{6}",
+    ),
+    ("alert.oom.freed", ", {0} MiB freed", ", {0} MiB freigegeben"),
+    ("alert.oom.cgroup_limit", " for its cgroup's memory limit", " wegen des Speicherlimits seiner cgroup"),
+    ("alert.oom.host_memory", " for the host running out of memory", " weil dem Host der Speicher ausging"),
+    ("alert.computed.low.title", "{0} Low", "{0} niedrig"),
+    ("alert.computed.high.title", "{0} High", "{0} hoch"),
+    ("alert.computed.low.message", "{0} = {1} is at or below {2} ({3})", "{0} = {1} liegt bei oder unter {2} ({3})"),
+    ("alert.computed.high.message", "{0} = {1} is at or above {2} ({3})", "{0} = {1} liegt bei oder über {2} ({3})"),
+    ("alert.crash.title", "{0} Crashed", "{0} abgestürzt"),
+    (
+        "alert.crash.message",
+        "{0} crash(es) in the last {1}m, latest {2}{3} killed{4} at {5}{6} ({7})",
+        "{0} Absturz/Abstürze in den letzten {1} min, zuletzt {2}{3} beendet{4} um {5}{6} ({7})",
+    ),
+    ("alert.crash.signal", " by {0}", " durch {0}"),
+    // External alerts, remote agents and host processes
+    ("alert.external.message", "From {0}{1}{2}", "Von {0}{1}{2}"),
+    ("alert.external.place", " on {0}", " auf {0}"),
+    ("alert.agent.title", "Agent {0} Unreachable", "Agent {0} nicht erreichbar"),
+    (
+        "alert.agent.message",
+        "{0} consecutive polls failed ({1}); the host, its agent or the network between may be down",
+        "{0} Abfragen in Folge fehlgeschlagen ({1}); der Host, sein Agent oder das Netz dazwischen ist womöglich ausgefallen",
+    ),
+    ("alert.host_profile.title", "Host Profile Deviation: {0}", "Abweichung vom Host-Profil: {0}"),
+    (
+        "alert.host_profile.message",
+        "{0} is {1}; the host profile expects {2}",
+        "{0} ist {1}; das Host-Profil erwartet {2}",
+    ),
+    ("alert.memory_limit.title", "{0} Over Memory Limit", "{0} über dem Speicherlimit"),
+    (
+        "alert.memory_limit.message",
+        "{0} uses {1} MB, past its {2} MB limit{3}",
+        "{0} belegt {1} MB und liegt über seinem Limit von {2} MB{3}",
+    ),
+    ("alert.memory_limit.growing", ", growing {0} MB/h", ", wächst um {0} MB/h"),
+    ("alert.memory_leak.title", "Possible Memory Leak in {0}", "Mögliches Speicherleck in {0}"),
+    (
+        "alert.memory_leak.message",
+        "{0} grew steadily from {1} to {2} MB over the last {3}h ({4} MB/h, ~{5} MB a day)",
+        "{0} ist in den letzten {3} h stetig von {1} auf {2} MB gewachsen ({4} MB/h, ~{5} MB pro Tag)",
+    ),
+];
+
+/// The message `id` in the current locale. Unknown ids come back as
+/// themselves so a missing entry shows up rather than an empty label.
+pub fn t(id: &'static str) -> &'static str {
+    let Some(&(_, en, de)) = CATALOG.iter().find(|(key, _, _)| *key == id) else {
+        return id;
+    };
+    match LOCALE.load(Ordering::Relaxed) {
+        1 => de,
+        _ => en,
+    }
+}
+
+/// `t` with `{0}`, `{1}`, ... replaced by `args`
+pub fn tf(id: &'static str, args: &[&dyn Display]) -> String {
+    let mut text = t(id).to_string();
+    for (i, arg) in args.iter().enumerate() {
+        text = text.replace(&format!("{{{}}}", i), &arg.to_string());
+    }
+    text
+}
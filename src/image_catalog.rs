@@ -0,0 +1,408 @@
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::config::{CatalogImageConfig, ImageCatalogConfig};
+
+/// Appended to a download until its checksum is verified; a later download
+/// of the same image resumes it
+const PART_SUFFIX: &str = ".part";
+/// How often the size of a running download is read for its progress
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Something the catalog does after confirmation, for the entry at an index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogOp {
+    /// Download into the image directory and verify the checksum
+    Download(usize),
+    /// Create a DataVolume that has CDI import the image
+    Import(usize),
+}
+
+/// One catalog image, whether its URL answers and what is already here
+#[derive(Debug, Clone)]
+pub struct CatalogEntry {
+    pub image: CatalogImageConfig,
+    /// Ok(size, if the server said) or why the URL didn't answer
+    pub availability: Result<Option<u64>, String>,
+    /// Size of the verified download in the download directory
+    pub downloaded: Option<u64>,
+    /// Size of an interrupted download, resumed by the next one
+    pub partial: Option<u64>,
+}
+
+impl CatalogEntry {
+    /// The last segment of the URL, without a query
+    pub fn file_name(&self) -> String {
+        file_name(&self.image.url)
+    }
+
+    /// The image name as a DNS label: "ubuntu-24.04" -> "ubuntu-24-04"
+    pub fn volume_name(&self) -> String {
+        let name: String = self
+            .image
+            .name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        name.trim_matches('-').to_string()
+    }
+
+    /// Read what is in `dir` for this image again
+    fn scan(&mut self, dir: &Path) {
+        let path = dir.join(self.file_name());
+        self.downloaded = fs::metadata(&path).ok().map(|meta| meta.len());
+        self.partial = fs::metadata(part_path(&path)).ok().map(|meta| meta.len());
+    }
+}
+
+/// The catalog opened with `C` on the host map's images or from the
+/// palette: the configured images, the operation awaiting confirmation and
+/// the download running
+pub struct ImageCatalogPanel {
+    pub entries: Vec<CatalogEntry>,
+    pub selected: usize,
+    /// Where downloads go; None when no image directory is configured
+    pub dir: Option<PathBuf>,
+    pub namespace: String,
+    pub storage_class: Option<String>,
+    pub confirm: Option<CatalogOp>,
+    pub download: Option<ImageDownload>,
+    /// Panel dismissed while a download is still running
+    pub hidden: bool,
+}
+
+impl ImageCatalogPanel {
+    /// The configured images with what is on disk, after asking every URL
+    /// whether it answers, all at once
+    pub async fn load(config: &ImageCatalogConfig, dir: Option<PathBuf>, timeout: Duration) -> Self {
+        let checks = config.images.iter().map(|image| head(&image.url, timeout));
+        let availability = futures::future::join_all(checks).await;
+        let mut panel = Self {
+            entries: config
+                .images
+                .iter()
+                .cloned()
+                .zip(availability)
+                .map(|(image, availability)| CatalogEntry { image, availability, downloaded: None, partial: None })
+                .collect(),
+            selected: 0,
+            dir,
+            namespace: config.namespace.clone(),
+            storage_class: config.storage_class.clone(),
+            confirm: None,
+            download: None,
+            hidden: false,
+        };
+        panel.rescan();
+        panel
+    }
+
+    /// Look for finished and partial downloads again
+    pub fn rescan(&mut self) {
+        if let Some(ref dir) = self.dir {
+            for entry in &mut self.entries {
+                entry.scan(dir);
+            }
+        }
+    }
+
+    pub fn selected_entry(&self) -> Option<&CatalogEntry> {
+        self.entries.get(self.selected)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.download.as_ref().is_some_and(|download| !download.is_finished())
+    }
+
+    pub fn describe(&self, op: CatalogOp) -> String {
+        match op {
+            CatalogOp::Download(i) => {
+                let entry = &self.entries[i];
+                let dir = self.dir.as_ref().map_or("-".to_string(), |dir| dir.display().to_string());
+                let resume = if entry.partial.is_some() { " (resuming)" } else { "" };
+                format!("Download {} to {}{}", entry.image.name, dir, resume)
+            }
+            CatalogOp::Import(i) => {
+                let entry = &self.entries[i];
+                format!(
+                    "Import {} as DataVolume {}/{} ({})",
+                    entry.image.name,
+                    self.namespace,
+                    entry.volume_name(),
+                    entry.image.size
+                )
+            }
+        }
+    }
+
+    /// A DataVolume that has CDI download `entry`'s URL into a new claim
+    pub fn data_volume_manifest(&self, entry: &CatalogEntry) -> serde_json::Value {
+        let mut storage = json!({ "resources": { "requests": { "storage": entry.image.size } } });
+        if let Some(ref class) = self.storage_class {
+            storage["storageClassName"] = json!(class);
+        }
+        json!({
+            "apiVersion": "cdi.kubevirt.io/v1beta1",
+            "kind": "DataVolume",
+            "metadata": {
+                "name": entry.volume_name(),
+                "namespace": self.namespace,
+                "labels": { "app.kubernetes.io/managed-by": "hypervisor-tui" },
+                // Import now rather than waiting for a VM to claim it on a
+                // WaitForFirstConsumer storage class
+                "annotations": { "cdi.kubevirt.io/storage.bind.immediate.requested": "true" },
+            },
+            "spec": {
+                "source": { "http": { "url": entry.image.url } },
+                "storage": storage,
+            },
+        })
+    }
+}
+
+enum DownloadEvent {
+    Progress(u64),
+    Verifying,
+    Finished(Result<String, String>),
+}
+
+/// A download running in the background: curl into `<file>.part`,
+/// resuming what an earlier one left, then the checksum check and the
+/// rename to the final name. Dropping it stops curl.
+pub struct ImageDownload {
+    pub name: String,
+    pub path: PathBuf,
+    /// Size the server announced
+    pub total: Option<u64>,
+    pub bytes: u64,
+    pub verifying: bool,
+    pub outcome: Option<Result<String, String>>,
+    rx: mpsc::UnboundedReceiver<DownloadEvent>,
+    task: JoinHandle<()>,
+}
+
+impl ImageDownload {
+    pub fn start(entry: &CatalogEntry, dir: &Path) -> Self {
+        let image = entry.image.clone();
+        let path = dir.join(entry.file_name());
+        let total = entry.availability.clone().ok().flatten();
+        let (tx, rx) = mpsc::unbounded_channel();
+        let task = {
+            let (dir, path) = (dir.to_path_buf(), path.clone());
+            tokio::spawn(async move {
+                let outcome = download(&image, &dir, &path, total, &tx).await;
+                let _ = tx.send(DownloadEvent::Finished(outcome));
+            })
+        };
+
+        Self {
+            name: entry.image.name.clone(),
+            path,
+            total,
+            bytes: entry.partial.unwrap_or(0),
+            verifying: false,
+            outcome: None,
+            rx,
+            task,
+        }
+    }
+
+    /// Take in progress, returning whether any arrived
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(event) = self.rx.try_recv() {
+            changed = true;
+            match event {
+                DownloadEvent::Progress(bytes) => self.bytes = bytes,
+                DownloadEvent::Verifying => self.verifying = true,
+                DownloadEvent::Finished(outcome) => self.outcome = Some(outcome),
+            }
+        }
+        changed
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    /// Percent done, when the size is known
+    pub fn percent(&self) -> Option<f64> {
+        self.total.filter(|&total| total > 0).map(|total| (self.bytes as f64 / total as f64 * 100.0).min(100.0))
+    }
+}
+
+impl Drop for ImageDownload {
+    fn drop(&mut self) {
+        // curl is killed along with the task; the .part file stays for a resume
+        self.task.abort();
+    }
+}
+
+async fn download(
+    image: &CatalogImageConfig,
+    dir: &Path,
+    path: &Path,
+    total: Option<u64>,
+    tx: &mpsc::UnboundedSender<DownloadEvent>,
+) -> Result<String, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let part = part_path(path);
+    let size = || fs::metadata(&part).map(|meta| meta.len()).unwrap_or(0);
+
+    // Resuming a complete file would only get a 416 from the server
+    if total.is_none_or(|total| size() < total) {
+        let output = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--location", "--continue-at", "-", "--output"])
+            .arg(&part)
+            .arg(&image.url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .output();
+        tokio::pin!(output);
+        let mut ticker = tokio::time::interval(PROGRESS_INTERVAL);
+        let output = loop {
+            tokio::select! {
+                output = &mut output => break output,
+                _ = ticker.tick() => {
+                    let _ = tx.send(DownloadEvent::Progress(size()));
+                }
+            }
+        };
+        let output = output.map_err(|e| format!("Failed to run curl: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("curl failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+    }
+    let _ = tx.send(DownloadEvent::Progress(size()));
+
+    let _ = tx.send(DownloadEvent::Verifying);
+    let verified = match expected_checksum(image, &file_name(&image.url)).await? {
+        Some(expected) => {
+            let actual = file_checksum(&part, expected.len()).await?;
+            if actual != expected {
+                // A resume onto a file from an older build ends up here too
+                let _ = fs::remove_file(&part);
+                return Err(format!("checksum mismatch (expected {}, got {}); the download was removed", expected, actual));
+            }
+            true
+        }
+        None => false,
+    };
+    fs::rename(&part, path).map_err(|e| format!("Failed to rename {}: {}", part.display(), e))?;
+
+    let size = fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+    Ok(format!(
+        "{} ({:.1} GiB){}",
+        path.display(),
+        size as f64 / 1_073_741_824.0,
+        if verified { ", checksum verified" } else { ", no checksum configured" }
+    ))
+}
+
+/// Ask whether `url` answers, following redirects, yielding the size the
+/// server announced
+async fn head(url: &str, timeout: Duration) -> Result<Option<u64>, String> {
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--head", "--location", "--max-time"])
+        .arg(timeout.as_secs().max(1).to_string())
+        .arg(url)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().trim_start_matches("curl: ").to_string());
+    }
+    // Every hop of a redirect has its headers; the last one counts
+    Ok(String::from_utf8_lossy(&output.stdout).lines().rev().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.eq_ignore_ascii_case("content-length").then(|| value.trim().parse().ok())?
+    }))
+}
+
+/// The checksum `image` should have, in lowercase hex: the configured one,
+/// else the one its checksum list gives for `file_name`
+async fn expected_checksum(image: &CatalogImageConfig, file_name: &str) -> Result<Option<String>, String> {
+    if let Some(ref checksum) = image.checksum {
+        return parse_checksum(checksum).map(Some).ok_or_else(|| format!("{:?} is not a SHA-256 or SHA-512 checksum", checksum));
+    }
+    let Some(ref url) = image.checksum_url else {
+        return Ok(None);
+    };
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location", "--max-time", "30"])
+        .arg(url)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Fetching {} failed: {}", url, String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    find_checksum(&String::from_utf8_lossy(&output.stdout), file_name)
+        .map(Some)
+        .ok_or_else(|| format!("{} lists no checksum for {}", url, file_name))
+}
+
+/// `file_name`'s checksum in a list in GNU ("<hex>  name", "<hex> *name")
+/// or BSD ("SHA256 (name) = <hex>") format
+fn find_checksum(list: &str, file_name: &str) -> Option<String> {
+    list.lines().find_map(|line| {
+        let line = line.trim();
+        if let Some((label, hex)) = line.split_once(" = ") {
+            let name = label.split_once(" (")?.1.strip_suffix(')')?;
+            return (name == file_name).then(|| parse_checksum(hex)).flatten();
+        }
+        let (hex, name) = line.split_once(char::is_whitespace)?;
+        (name.trim_start().trim_start_matches('*') == file_name).then(|| parse_checksum(hex)).flatten()
+    })
+}
+
+/// Lowercase hex of a SHA-256 or SHA-512 checksum, "sha256:" prefix allowed
+pub fn parse_checksum(value: &str) -> Option<String> {
+    let hex = value.trim();
+    let hex = hex.split_once(':').map_or(hex, |(_, hex)| hex).to_lowercase();
+    (matches!(hex.len(), 64 | 128) && hex.chars().all(|c| c.is_ascii_hexdigit())).then_some(hex)
+}
+
+/// sha256sum or sha512sum of `path`, picked by the expected checksum's length
+async fn file_checksum(path: &Path, hex_len: usize) -> Result<String, String> {
+    let program = if hex_len == 128 { "sha512sum" } else { "sha256sum" };
+    let output = Command::new(program)
+        .arg(path)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if !output.status.success() {
+        return Err(format!("{} failed: {}", program, String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .map(str::to_lowercase)
+        .ok_or_else(|| format!("{} printed nothing", program))
+}
+
+fn file_name(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit('/').next().unwrap_or(path).to_string()
+}
+
+fn part_path(path: &Path) -> PathBuf {
+    let mut part = path.as_os_str().to_owned();
+    part.push(PART_SUFFIX);
+    PathBuf::from(part)
+}
@@ -0,0 +1,103 @@
+use crate::app::{App, HostMapView, Screen};
+use crate::i18n::t;
+
+/// When a screen key is offered beyond being on its screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum When {
+    Always,
+    /// Changes the cluster, so not in read-only mode
+    Writable,
+    HostMapProcesses,
+    HostMapImages,
+    HostMapMemory,
+    /// Signals qemu processes, so only in writable local mode
+    LocalMode,
+}
+
+/// A key a screen handles, as the footer hints it
+#[derive(Debug, Clone, Copy)]
+pub struct Binding {
+    pub screen: Screen,
+    pub key: &'static str,
+    /// Message id of what it does
+    pub label: &'static str,
+    pub when: When,
+}
+
+const fn bind(screen: Screen, key: &'static str, label: &'static str, when: When) -> Binding {
+    Binding { screen, key, label, when }
+}
+
+/// Each screen's own keys, most used first since the footer cuts off what
+/// doesn't fit. The keys are dispatched in update.rs; a binding added or
+/// changed there belongs here too.
+pub const BINDINGS: &[Binding] = &[
+    bind(Screen::Logs, "/", "hint.search", When::Always),
+    bind(Screen::Logs, "Enter", "hint.details", When::Always),
+    bind(Screen::Logs, "x", "hint.collapse", When::Always),
+    bind(Screen::Logs, "w", "hint.wrap", When::Always),
+    bind(Screen::Logs, "u", "hint.services", When::Always),
+    bind(Screen::Logs, "H", "hint.history", When::Always),
+    bind(Screen::Logs, "^F", "hint.search_all", When::Always),
+    bind(Screen::Dashboard, "L", "hint.layout", When::Writable),
+    bind(Screen::Dashboard, "c", "hint.compare", When::Always),
+    bind(Screen::Resources, "Tab", "hint.kind", When::Always),
+    bind(Screen::Resources, "Enter", "hint.details", When::Always),
+    bind(Screen::Resources, "/", "hint.selector", When::Always),
+    bind(Screen::Resources, "Space", "hint.mark", When::Writable),
+    bind(Screen::Resources, "b", "hint.batch", When::Always),
+    bind(Screen::Resources, "y", "hint.yaml", When::Always),
+    bind(Screen::Resources, "w", "hint.watch", When::Always),
+    bind(Screen::Resources, "c", "hint.cpu", When::Writable),
+    bind(Screen::Resources, "C", "hint.clone", When::Writable),
+    bind(Screen::Resources, "h", "hint.hotplug", When::Writable),
+    bind(Screen::Resources, "t", "hint.node", When::Always),
+    bind(Screen::Resources, "i", "hint.cloud_init", When::Always),
+    bind(Screen::Resources, "l", "hint.group", When::Always),
+    bind(Screen::Charts, "Tab", "hint.metric", When::Always),
+    bind(Screen::Charts, "Left/Right", "hint.cursor", When::Always),
+    bind(Screen::Charts, "+/-", "hint.zoom", When::Always),
+    bind(Screen::Heatmap, "Arrows", "hint.move", When::Always),
+    bind(Screen::Heatmap, "Enter", "hint.details", When::Always),
+    bind(Screen::Heatmap, "m", "hint.metric", When::Always),
+    bind(Screen::Fleet, "Enter", "hint.open", When::Always),
+    bind(Screen::Fleet, "s", "hint.sort", When::Always),
+    bind(Screen::Fleet, "o", "hint.reverse", When::Always),
+    bind(Screen::Fleet, "c", "hint.compare", When::Always),
+    bind(Screen::Fleet, "E", "hint.evacuate", When::Always),
+    bind(Screen::HostMap, "Tab", "hint.images", When::HostMapProcesses),
+    bind(Screen::HostMap, "Enter", "hint.details", When::HostMapProcesses),
+    bind(Screen::HostMap, "p", "hint.pause", When::LocalMode),
+    bind(Screen::HostMap, "K", "hint.kill", When::LocalMode),
+    bind(Screen::HostMap, "L", "hint.local_mode", When::HostMapProcesses),
+    bind(Screen::HostMap, "Tab", "hint.memory", When::HostMapImages),
+    bind(Screen::HostMap, "D", "hint.delete", When::HostMapImages),
+    bind(Screen::HostMap, "C", "hint.catalog", When::HostMapImages),
+    bind(Screen::HostMap, "Tab", "hint.processes", When::HostMapMemory),
+];
+
+/// What the footer hints for the screen `app` shows, as (key, label): its
+/// own keys, then Tab for panes when there are several and the screen
+/// doesn't take Tab itself
+pub fn hints(app: &App) -> Vec<(&'static str, &'static str)> {
+    let mut hints: Vec<(&str, &str)> = BINDINGS
+        .iter()
+        .filter(|binding| binding.screen == app.current_screen)
+        .filter(|binding| match binding.when {
+            When::Always => true,
+            When::Writable => !app.read_only,
+            When::HostMapProcesses => app.host_map_view == HostMapView::Processes,
+            When::HostMapImages => app.host_map_view == HostMapView::Images,
+            When::HostMapMemory => app.host_map_view == HostMapView::Memory,
+            When::LocalMode => {
+                app.local_mode && app.emergency_actions && !app.read_only && app.host_map_view == HostMapView::Processes
+            }
+        })
+        .map(|binding| (binding.key, t(binding.label)))
+        .collect();
+    let screen_takes_tab = hints.iter().any(|(key, _)| *key == "Tab");
+    if !screen_takes_tab && app.visible_panes().len() > 1 {
+        hints.push(("Tab", t("hint.pane")));
+    }
+    hints
+}
@@ -0,0 +1,322 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+
+const MIN_PERCENT: u16 = 10;
+const MAX_PERCENT: u16 = 90;
+const RESIZE_STEP: i32 = 5;
+
+/// A card that can be placed on the Dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DashboardWidget {
+    Cpu,
+    Memory,
+    Disk,
+    Cluster,
+    Vms,
+    Sensors,
+    K3s,
+    Nix,
+    Cgroups,
+    Heatmap,
+    Power,
+    Quotas,
+    Etcd,
+    Sockets,
+    Ports,
+    Limits,
+    Multipath,
+    Queues,
+    Sysctls,
+    Hardware,
+    Reservation,
+    Kubelet,
+    Crashes,
+    Ooms,
+    Computed,
+    Logs,
+}
+
+impl DashboardWidget {
+    pub const ALL: [DashboardWidget; 26] = [
+        DashboardWidget::Cpu,
+        DashboardWidget::Memory,
+        DashboardWidget::Disk,
+        DashboardWidget::Cluster,
+        DashboardWidget::Vms,
+        DashboardWidget::Sensors,
+        DashboardWidget::K3s,
+        DashboardWidget::Nix,
+        DashboardWidget::Cgroups,
+        DashboardWidget::Heatmap,
+        DashboardWidget::Power,
+        DashboardWidget::Quotas,
+        DashboardWidget::Etcd,
+        DashboardWidget::Sockets,
+        DashboardWidget::Ports,
+        DashboardWidget::Limits,
+        DashboardWidget::Multipath,
+        DashboardWidget::Queues,
+        DashboardWidget::Sysctls,
+        DashboardWidget::Hardware,
+        DashboardWidget::Reservation,
+        DashboardWidget::Kubelet,
+        DashboardWidget::Crashes,
+        DashboardWidget::Ooms,
+        DashboardWidget::Computed,
+        DashboardWidget::Logs,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DashboardWidget::Cpu => "cpu",
+            DashboardWidget::Memory => "memory",
+            DashboardWidget::Disk => "disk",
+            DashboardWidget::Cluster => "cluster",
+            DashboardWidget::Vms => "vms",
+            DashboardWidget::Sensors => "sensors",
+            DashboardWidget::K3s => "k3s",
+            DashboardWidget::Nix => "nix",
+            DashboardWidget::Cgroups => "cgroups",
+            DashboardWidget::Heatmap => "heatmap",
+            DashboardWidget::Power => "power",
+            DashboardWidget::Quotas => "quotas",
+            DashboardWidget::Etcd => "etcd",
+            DashboardWidget::Sockets => "sockets",
+            DashboardWidget::Ports => "ports",
+            DashboardWidget::Limits => "limits",
+            DashboardWidget::Multipath => "multipath",
+            DashboardWidget::Queues => "queues",
+            DashboardWidget::Sysctls => "sysctls",
+            DashboardWidget::Hardware => "hardware",
+            DashboardWidget::Reservation => "reservation",
+            DashboardWidget::Kubelet => "kubelet",
+            DashboardWidget::Crashes => "crashes",
+            DashboardWidget::Ooms => "ooms",
+            DashboardWidget::Computed => "computed",
+            DashboardWidget::Logs => "logs",
+        }
+    }
+
+    fn next(&self) -> DashboardWidget {
+        let index = Self::ALL.iter().position(|w| w == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DashboardRow {
+    /// Percentage of the dashboard height; rows without one share the rest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u16>,
+
+    /// Cards from left to right
+    pub cards: Vec<DashboardWidget>,
+
+    /// Relative card widths; cards share the row equally when omitted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub widths: Vec<u16>,
+}
+
+impl DashboardRow {
+    fn new(height: Option<u16>, cards: Vec<DashboardWidget>) -> Self {
+        Self { height, cards, widths: Vec::new() }
+    }
+
+    /// Width weight of each card, equal when unset or mismatched
+    pub fn card_widths(&self) -> Vec<u16> {
+        if self.widths.len() == self.cards.len() {
+            self.widths.clone()
+        } else {
+            vec![1; self.cards.len()]
+        }
+    }
+
+    /// Switch to explicit percentage widths so a single card can be resized
+    fn materialize_widths(&mut self) {
+        let widths = self.card_widths();
+        let total: u16 = widths.iter().sum::<u16>().max(1);
+        self.widths = widths.iter().map(|w| w * 100 / total).collect();
+    }
+}
+
+/// Grid of Dashboard cards, from `[dashboard]` in the config or the layout
+/// saved from the in-app editor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DashboardLayout {
+    #[serde(default)]
+    pub rows: Vec<DashboardRow>,
+}
+
+impl Default for DashboardLayout {
+    fn default() -> Self {
+        use DashboardWidget::*;
+        Self {
+            rows: vec![
+                DashboardRow::new(Some(25), vec![Cpu, Memory]),
+                DashboardRow::new(Some(35), vec![Disk, Cluster, Vms]),
+                DashboardRow::new(None, vec![Cgroups]),
+            ],
+        }
+    }
+}
+
+impl DashboardLayout {
+    /// The layout saved from the editor, falling back to the configured one
+    pub fn load(configured: &DashboardLayout) -> Self {
+        let path = Self::saved_path();
+        let saved = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| match toml::from_str::<DashboardLayout>(&contents) {
+                Ok(layout) => Some(layout),
+                Err(e) => {
+                    tracing::warn!("Ignoring saved dashboard layout {:?}: {}", path, e);
+                    None
+                }
+            });
+
+        let mut layout = saved.unwrap_or_else(|| configured.clone());
+        layout.rows.retain(|row| !row.cards.is_empty());
+        if layout.rows.is_empty() {
+            layout = Self::default();
+        }
+        layout
+    }
+
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = Self::saved_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create state directory: {:?}", parent))?;
+        }
+        let contents = toml::to_string_pretty(self).context("Failed to serialize layout")?;
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write layout: {:?}", path))?;
+        Ok(path)
+    }
+
+    fn saved_path() -> PathBuf {
+        Config::state_dir().join("dashboard.toml")
+    }
+
+    pub fn contains(&self, widget: DashboardWidget) -> bool {
+        self.rows.iter().any(|row| row.cards.contains(&widget))
+    }
+
+    /// Height percentage of each row; rows without one split the remainder
+    pub fn row_heights(&self) -> Vec<u16> {
+        let fixed: u16 = self.rows.iter().filter_map(|r| r.height).sum();
+        let flexible = self.rows.iter().filter(|r| r.height.is_none()).count() as u16;
+        let share = 100u16.saturating_sub(fixed).checked_div(flexible).unwrap_or(0);
+        self.rows.iter().map(|r| r.height.unwrap_or(share)).collect()
+    }
+
+    /// Move the selection, keeping it on an existing card
+    pub fn navigate(&self, (row, col): (usize, usize), d_row: i32, d_col: i32) -> (usize, usize) {
+        let row = (row as i32 + d_row).clamp(0, self.rows.len() as i32 - 1) as usize;
+        let last_col = self.rows[row].cards.len() as i32 - 1;
+        let col = (col as i32 + d_col).clamp(0, last_col) as usize;
+        (row, col)
+    }
+
+    /// Move the selected card within its row, or into the row above/below
+    pub fn move_card(&mut self, (row, col): (usize, usize), d_row: i32, d_col: i32) -> (usize, usize) {
+        if d_col != 0 {
+            let row_ref = &mut self.rows[row];
+            let target = (col as i32 + d_col).clamp(0, row_ref.cards.len() as i32 - 1) as usize;
+            row_ref.cards.swap(col, target);
+            if row_ref.widths.len() == row_ref.cards.len() {
+                row_ref.widths.swap(col, target);
+            }
+            return (row, target);
+        }
+
+        let target_row = row as i32 + d_row;
+        if target_row < 0 || target_row >= self.rows.len() as i32 {
+            return (row, col);
+        }
+        let target_row = target_row as usize;
+
+        let card = self.rows[row].cards.remove(col);
+        self.rows[row].widths.clear();
+        let target_col = col.min(self.rows[target_row].cards.len());
+        self.rows[target_row].cards.insert(target_col, card);
+        self.rows[target_row].widths.clear();
+
+        if self.rows[row].cards.is_empty() {
+            self.rows.remove(row);
+            let target_row = if target_row > row { target_row - 1 } else { target_row };
+            return (target_row, target_col);
+        }
+        (target_row, target_col)
+    }
+
+    /// Widen or narrow the selected card at the expense of its neighbours
+    pub fn resize_card(&mut self, (row, col): (usize, usize), grow: bool) {
+        let row = &mut self.rows[row];
+        if row.cards.len() < 2 {
+            return;
+        }
+        row.materialize_widths();
+        let delta = if grow { RESIZE_STEP } else { -RESIZE_STEP };
+        let width = (row.widths[col] as i32 + delta).clamp(MIN_PERCENT as i32, MAX_PERCENT as i32) as u16;
+
+        // Spread the remainder over the other cards in their current proportions
+        let others: u16 = row.widths.iter().enumerate().filter(|(i, _)| *i != col).map(|(_, w)| *w).sum();
+        let remaining = 100 - width;
+        for (i, w) in row.widths.iter_mut().enumerate() {
+            if i == col {
+                *w = width;
+            } else if let Some(scaled) = (*w * remaining).checked_div(others) {
+                *w = scaled.max(1);
+            }
+        }
+    }
+
+    /// Make the selected row taller or shorter
+    pub fn resize_row(&mut self, row: usize, grow: bool) {
+        let heights = self.row_heights();
+        for (r, height) in self.rows.iter_mut().zip(heights) {
+            r.height = Some(height);
+        }
+        let delta = if grow { RESIZE_STEP } else { -RESIZE_STEP };
+        let height = self.rows[row].height.unwrap_or(MIN_PERCENT) as i32 + delta;
+        self.rows[row].height = Some(height.clamp(MIN_PERCENT as i32, MAX_PERCENT as i32) as u16);
+    }
+
+    /// Replace the selected card with the next widget type
+    pub fn cycle_widget(&mut self, (row, col): (usize, usize)) {
+        let card = &mut self.rows[row].cards[col];
+        *card = card.next();
+    }
+
+    /// Add a card after the selection, or in a new row below it
+    pub fn add_card(&mut self, (row, col): (usize, usize), new_row: bool) -> (usize, usize) {
+        if new_row {
+            self.rows.insert(row + 1, DashboardRow::new(None, vec![DashboardWidget::Cpu]));
+            return (row + 1, 0);
+        }
+        self.rows[row].cards.insert(col + 1, DashboardWidget::Cpu);
+        self.rows[row].widths.clear();
+        (row, col + 1)
+    }
+
+    /// Remove the selected card; the last card can't be removed
+    pub fn remove_card(&mut self, (row, col): (usize, usize)) -> (usize, usize) {
+        if self.rows.len() == 1 && self.rows[0].cards.len() == 1 {
+            return (row, col);
+        }
+        self.rows[row].cards.remove(col);
+        self.rows[row].widths.clear();
+        if self.rows[row].cards.is_empty() {
+            self.rows.remove(row);
+            let row = row.min(self.rows.len() - 1);
+            return (row, 0);
+        }
+        (row, col.min(self.rows[row].cards.len() - 1))
+    }
+}
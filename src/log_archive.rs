@@ -0,0 +1,128 @@
+use crate::collectors::LogCollector;
+use crate::types::LogEntry;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Lines read and whether the range held more, or why journalctl failed
+type QueryResult = Result<(Vec<LogEntry>, bool), String>;
+
+/// The date/time range asked for before an archived log query, in
+/// journalctl's own syntax ("2024-03-02 22:00", "yesterday", "-2h", "now")
+pub struct LogRangePrompt {
+    pub since: String,
+    pub until: String,
+    /// Typing into `until` rather than `since`
+    pub editing_until: bool,
+}
+
+impl LogRangePrompt {
+    /// Start from the range shown last, or the past hour
+    pub fn new(previous: Option<&LogArchive>) -> Self {
+        match previous {
+            Some(archive) => Self {
+                since: archive.since.clone(),
+                until: archive.until.clone(),
+                editing_until: false,
+            },
+            None => Self {
+                since: (chrono::Local::now() - chrono::Duration::hours(1)).format("%Y-%m-%d %H:%M").to_string(),
+                until: "now".to_string(),
+                editing_until: false,
+            },
+        }
+    }
+
+    pub fn input(&mut self, c: char) {
+        self.field().push(c);
+    }
+
+    pub fn backspace(&mut self) {
+        self.field().pop();
+    }
+
+    pub fn switch_field(&mut self) {
+        self.editing_until = !self.editing_until;
+    }
+
+    fn field(&mut self) -> &mut String {
+        if self.editing_until {
+            &mut self.until
+        } else {
+            &mut self.since
+        }
+    }
+}
+
+/// Journal lines of a past time range, read into their own buffer so the
+/// live ring buffer keeps following meanwhile. Shown on the Logs screen, with
+/// the same filters, until closed.
+pub struct LogArchive {
+    pub since: String,
+    pub until: String,
+    pub entries: Vec<Arc<LogEntry>>,
+    /// The range held more than the line limit; only the newest were read
+    pub truncated: bool,
+    /// Why journalctl failed
+    pub error: Option<String>,
+    started: Instant,
+    rx: Option<mpsc::UnboundedReceiver<QueryResult>>,
+}
+
+impl LogArchive {
+    pub fn start(collector: &LogCollector, since: String, until: String, limit: usize) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let query = collector.archive_query(&since, &until, limit);
+        tokio::spawn(async move {
+            let _ = tx.send(query.await.map_err(|e| format!("{:#}", e)));
+        });
+
+        Self {
+            since,
+            until,
+            entries: Vec::new(),
+            truncated: false,
+            error: None,
+            started: Instant::now(),
+            rx: Some(rx),
+        }
+    }
+
+    /// Take in the query's result, returning whether it arrived
+    pub fn poll(&mut self) -> bool {
+        let Some(result) = self.rx.as_mut().and_then(|rx| rx.try_recv().ok()) else {
+            return false;
+        };
+        self.rx = None;
+        match result {
+            Ok((entries, truncated)) => {
+                self.entries = entries.into_iter().map(Arc::new).collect();
+                self.truncated = truncated;
+            }
+            Err(e) => self.error = Some(e),
+        }
+        true
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// "yesterday 22:00 .. today 02:00: 1234 lines"
+    pub fn summary(&self) -> String {
+        let range = format!("{} .. {}", self.since, self.until);
+        if let Some(ref e) = self.error {
+            format!("{}: {}", range, e)
+        } else if self.is_pending() {
+            format!("{}: querying ({}s)", range, self.elapsed().as_secs())
+        } else if self.truncated {
+            format!("{}: newest {} lines", range, self.entries.len())
+        } else {
+            format!("{}: {} lines", range, self.entries.len())
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::thread;
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use tokio::sync::mpsc;
+
+use crate::collectors::{collapse_repeats, parse_log_timestamp};
+use crate::types::LogEntry;
+
+/// Buffers at least this large are filtered off the UI thread; smaller
+/// ones are quicker to scan in place than to hand over
+pub const BACKGROUND_FILTER_MIN: usize = 5000;
+
+/// Format of `since=` and `until=` in the search box
+pub const WINDOW_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// The log view's search box, level filter and collapse toggle, parsed once
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    /// key=value words, the value lowercased
+    fields: Vec<(String, String)>,
+    /// The remaining words, lowercased, matched against message or service
+    text: String,
+    level: Option<String>,
+    collapse: bool,
+    /// Entries logged at or after this
+    since: Option<DateTime<Local>>,
+    /// Entries logged at or before this
+    until: Option<DateTime<Local>>,
+}
+
+impl LogQuery {
+    pub fn parse(query: &str, level: Option<&str>, collapse: bool) -> Self {
+        // key=value words match structured fields, the remaining text the
+        // message or service
+        // message or service; since= and until= bound the time
+        let (fields, words): (Vec<&str>, Vec<&str>) = query.split_whitespace().partition(|word| word.contains('='));
+        let mut parsed = Self {
+            text: words.join(" ").to_lowercase(),
+            level: level.map(str::to_string),
+            collapse,
+            ..Self::default()
+        };
+        for (key, value) in fields.iter().filter_map(|f| f.split_once('=')) {
+            match (key, window_time(value)) {
+                ("since", Some(at)) => parsed.since = Some(at),
+                ("until", Some(at)) => parsed.until = Some(at),
+                _ => parsed.fields.push((key.to_string(), value.to_lowercase())),
+            }
+        }
+        parsed
+    }
+
+    /// Matching entries of `logs` in order, collapsed if asked for
+    pub fn apply(&self, logs: &[Arc<LogEntry>]) -> Vec<Arc<LogEntry>> {
+        let filtered: Vec<Arc<LogEntry>> = logs
+            .iter()
+            .filter(|log| {
+                self.matches_fields(log)
+                    && (self.text.is_empty()
+                        || contains_ignore_case(&log.message, &self.text)
+                        || contains_ignore_case(&log.service, &self.text))
+            })
+            .cloned()
+            .collect();
+        self.finish(filtered)
+    }
+
+    /// Everything but the free text
+    fn matches_fields(&self, log: &LogEntry) -> bool {
+        self.level.as_deref().is_none_or(|level| log.level.eq_ignore_ascii_case(level))
+            && self
+                .fields
+                .iter()
+                .all(|(key, value)| log.field(key).is_some_and(|v| contains_ignore_case(v, value)))
+            && self.in_window(log)
+    }
+
+    /// Lines without a readable timestamp are left out of a time window
+    fn in_window(&self, log: &LogEntry) -> bool {
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        parse_log_timestamp(&log.timestamp).is_some_and(|at| {
+            self.since.is_none_or(|since| at >= since) && self.until.is_none_or(|until| at <= until)
+        })
+    }
+
+    fn finish(&self, filtered: Vec<Arc<LogEntry>>) -> Vec<Arc<LogEntry>> {
+        if self.collapse {
+            collapse_repeats(&filtered)
+        } else {
+            filtered
+        }
+    }
+}
+
+/// The log buffer with lowercased text and per-level and per-service
+/// positions, kept by the filter thread between requests
+#[derive(Default)]
+struct LogIndex {
+    entries: Vec<Arc<LogEntry>>,
+    /// "service\nmessage", lowercased, for each entry
+    lower: Vec<String>,
+    by_level: HashMap<String, Vec<usize>>,
+    by_service: HashMap<String, Vec<usize>>,
+}
+
+impl LogIndex {
+    /// Take in the current buffer. It only loses lines at the front and
+    /// gains them at the back, so the lowercased text of lines still in it
+    /// is reused rather than computed again.
+    fn update(&mut self, logs: Vec<Arc<LogEntry>>) {
+        let start = logs
+            .first()
+            .and_then(|first| self.entries.iter().position(|entry| Arc::ptr_eq(entry, first)));
+        let mut lower = Vec::with_capacity(logs.len());
+        if let Some(start) = start {
+            let kept = std::mem::take(&mut self.lower).into_iter().skip(start);
+            for ((old, text), new) in self.entries[start..].iter().zip(kept).zip(&logs) {
+                if !Arc::ptr_eq(old, new) {
+                    break;
+                }
+                lower.push(text);
+            }
+        }
+        for entry in &logs[lower.len()..] {
+            lower.push(format!("{}\n{}", entry.service, entry.message).to_lowercase());
+        }
+
+        self.by_level.clear();
+        self.by_service.clear();
+        for (i, entry) in logs.iter().enumerate() {
+            if let Some(positions) = self.by_level.get_mut(&entry.level) {
+                positions.push(i);
+            } else {
+                self.by_level.insert(entry.level.clone(), vec![i]);
+            }
+            if let Some(positions) = self.by_service.get_mut(&entry.service) {
+                positions.push(i);
+            } else {
+                self.by_service.insert(entry.service.clone(), vec![i]);
+            }
+        }
+        self.entries = logs;
+        self.lower = lower;
+    }
+
+    /// Same result as `LogQuery::apply`, looking only at the entries the
+    /// level or a service= filter narrow it down to
+    fn filter(&self, query: &LogQuery) -> Vec<Arc<LogEntry>> {
+        let mut candidates: Option<Vec<usize>> = None;
+        if let Some(ref level) = query.level {
+            candidates = Some(
+                self.by_level
+                    .iter()
+                    .filter(|(l, _)| l.eq_ignore_ascii_case(level))
+                    .flat_map(|(_, positions)| positions.iter().copied())
+                    .collect(),
+            );
+        }
+        if let Some((_, service)) = query.fields.iter().find(|(key, _)| key == "service") {
+            let matching: Vec<usize> = self
+                .by_service
+                .iter()
+                .filter(|(name, _)| contains_ignore_case(name, service))
+                .flat_map(|(_, positions)| positions.iter().copied())
+                .collect();
+            if candidates.as_ref().is_none_or(|c| matching.len() < c.len()) {
+                candidates = Some(matching);
+            }
+        }
+
+        let matches = |&i: &usize| {
+            let entry = &self.entries[i];
+            query.matches_fields(entry) && (query.text.is_empty() || self.text_matches(i, &query.text))
+        };
+        let filtered = match candidates {
+            Some(mut positions) => {
+                positions.sort_unstable();
+                positions.into_iter().filter(matches).map(|i| self.entries[i].clone()).collect()
+            }
+            None => (0..self.entries.len()).filter(matches).map(|i| self.entries[i].clone()).collect(),
+        };
+        query.finish(filtered)
+    }
+
+    /// The free text in the message or the service, but not spanning both
+    fn text_matches(&self, i: usize, text: &str) -> bool {
+        let (service, message) = self.lower[i].split_once('\n').unwrap_or(("", &self.lower[i]));
+        message.contains(text) || service.contains(text)
+    }
+}
+
+struct FilterRequest {
+    generation: u64,
+    logs: Vec<Arc<LogEntry>>,
+    query: LogQuery,
+}
+
+/// Filters large log buffers on a thread of its own so typing in the
+/// search box never waits on a scan. Only the newest request is worked
+/// on; older ones still queued are dropped.
+pub struct LogFilterWorker {
+    tx: mpsc::UnboundedSender<FilterRequest>,
+    rx: mpsc::UnboundedReceiver<(u64, Vec<Arc<LogEntry>>)>,
+    generation: u64,
+    pending: bool,
+}
+
+impl LogFilterWorker {
+    pub fn start() -> Self {
+        let (tx, mut requests) = mpsc::unbounded_channel::<FilterRequest>();
+        let (results, rx) = mpsc::unbounded_channel();
+        thread::spawn(move || {
+            let mut index = LogIndex::default();
+            while let Some(mut request) = requests.blocking_recv() {
+                while let Ok(newer) = requests.try_recv() {
+                    request = newer;
+                }
+                index.update(request.logs);
+                let filtered = index.filter(&request.query);
+                if results.send((request.generation, filtered)).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            tx,
+            rx,
+            generation: 0,
+            pending: false,
+        }
+    }
+
+    /// Queue a filter run, superseding any still outstanding
+    pub fn request(&mut self, logs: Vec<Arc<LogEntry>>, query: LogQuery) {
+        self.generation += 1;
+        self.pending = self
+            .tx
+            .send(FilterRequest {
+                generation: self.generation,
+                logs,
+                query,
+            })
+            .is_ok();
+    }
+
+    /// Forget an outstanding run, e.g. once filters are cleared
+    pub fn cancel(&mut self) {
+        self.generation += 1;
+        self.pending = false;
+    }
+
+    /// Results of the newest request, once they are in
+    pub fn poll(&mut self) -> Option<Vec<Arc<LogEntry>>> {
+        let mut latest = None;
+        while let Ok((generation, filtered)) = self.rx.try_recv() {
+            if generation == self.generation {
+                latest = Some(filtered);
+            }
+        }
+        if latest.is_some() {
+            self.pending = false;
+        }
+        latest
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending
+    }
+}
+
+/// A local `since=`/`until=` time
+fn window_time(value: &str) -> Option<DateTime<Local>> {
+    let naive = NaiveDateTime::parse_from_str(value, WINDOW_FORMAT).ok()?;
+    Local.from_local_datetime(&naive).earliest()
+}
+
+/// Case-insensitive substring match against an already lowercased needle,
+/// without lowercasing (allocating) every log line for ASCII needles
+fn contains_ignore_case(haystack: &str, needle_lower: &str) -> bool {
+    if !needle_lower.is_ascii() {
+        return haystack.to_lowercase().contains(needle_lower);
+    }
+    needle_lower.is_empty()
+        || haystack
+            .as_bytes()
+            .windows(needle_lower.len())
+            .any(|window| window.eq_ignore_ascii_case(needle_lower.as_bytes()))
+}
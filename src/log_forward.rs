@@ -0,0 +1,377 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::sync::mpsc;
+
+use crate::collectors::parse_log_timestamp;
+use crate::config::{LogForwardConfig, LogSinkKind};
+use crate::notify::{hostname, post_json, read_secret};
+use crate::secrets::Secrets;
+use crate::types::LogEntry;
+
+/// Batches waiting for a slow sink before new ones are dropped
+const QUEUE_BATCHES: usize = 64;
+
+/// Longest syslog message sent over UDP, leaving room under a 1500-byte MTU
+const MAX_DATAGRAM: usize = 1400;
+
+const FACILITIES: [(&str, u8); 12] = [
+    ("kern", 0),
+    ("user", 1),
+    ("daemon", 3),
+    ("syslog", 5),
+    ("local0", 16),
+    ("local1", 17),
+    ("local2", 18),
+    ("local3", 19),
+    ("local4", 20),
+    ("local5", 21),
+    ("local6", 22),
+    ("local7", 23),
+];
+
+/// Lines read in one update, shared by every sink's queue
+type Batch = Arc<Vec<Arc<LogEntry>>>;
+
+/// Delivery counts of one sink, for the diagnostics overlay
+#[derive(Debug, Clone, Default)]
+pub struct SinkStatus {
+    /// "loki https://logs.example/..." as configured
+    pub name: String,
+    pub sent: u64,
+    /// Lines lost to failed deliveries or a full queue
+    pub dropped: u64,
+    pub last_error: Option<String>,
+}
+
+/// Copies the followed log stream to `[[logging.forward]]` sinks. Each sink
+/// runs in its own task with a bounded queue, so an unreachable one neither
+/// stalls the UI nor the other sinks.
+pub struct LogForwarder {
+    sinks: Vec<(mpsc::Sender<Batch>, Arc<Mutex<SinkStatus>>)>,
+}
+
+impl LogForwarder {
+    /// None without any usable sink; a sink that can't be set up is left
+    /// out with a warning
+    pub fn from_config(configs: &[LogForwardConfig], secrets: &Secrets) -> Option<Self> {
+        let mut sinks = Vec::new();
+        for config in configs {
+            let name = format!("{} {}", sink_name(config.sink), config.target);
+            let sink = match Sink::new(config, secrets) {
+                Ok(sink) => sink,
+                Err(e) => {
+                    tracing::warn!("Log forwarding to {} disabled: {:#}", name, e);
+                    continue;
+                }
+            };
+            let status = Arc::new(Mutex::new(SinkStatus { name, ..Default::default() }));
+            let (tx, rx) = mpsc::channel(QUEUE_BATCHES);
+            tokio::spawn(run(sink, rx, status.clone()));
+            sinks.push((tx, status));
+        }
+        (!sinks.is_empty()).then_some(Self { sinks })
+    }
+
+    /// Queue newly read lines for every sink
+    pub fn send(&self, entries: Vec<Arc<LogEntry>>) {
+        if entries.is_empty() {
+            return;
+        }
+        let batch = Arc::new(entries);
+        for (tx, status) in &self.sinks {
+            if tx.try_send(batch.clone()).is_err() {
+                status.lock().unwrap_or_else(PoisonError::into_inner).dropped += batch.len() as u64;
+            }
+        }
+    }
+
+    pub fn statuses(&self) -> Vec<SinkStatus> {
+        self.sinks
+            .iter()
+            .map(|(_, status)| status.lock().unwrap_or_else(PoisonError::into_inner).clone())
+            .collect()
+    }
+}
+
+async fn run(mut sink: Sink, mut rx: mpsc::Receiver<Batch>, status: Arc<Mutex<SinkStatus>>) {
+    while let Some(batch) = rx.recv().await {
+        let entries: Vec<&LogEntry> = batch.iter().map(Arc::as_ref).filter(|entry| sink.accepts(entry)).collect();
+        if entries.is_empty() {
+            continue;
+        }
+        let result = sink.deliver(&entries).await;
+        let mut status = status.lock().unwrap_or_else(PoisonError::into_inner);
+        match result {
+            Ok(()) => {
+                status.sent += entries.len() as u64;
+                status.last_error = None;
+            }
+            Err(e) => {
+                let error = format!("{:#}", e);
+                // Once per outage, not once per batch
+                if status.last_error.is_none() {
+                    tracing::warn!("Log forwarding to {} failed: {}", status.name, error);
+                }
+                status.dropped += entries.len() as u64;
+                status.last_error = Some(error);
+            }
+        }
+    }
+}
+
+fn sink_name(kind: LogSinkKind) -> &'static str {
+    match kind {
+        LogSinkKind::Syslog => "syslog",
+        LogSinkKind::Loki => "loki",
+        LogSinkKind::File => "file",
+    }
+}
+
+/// ERROR 3, WARN 2, INFO 1, DEBUG 0
+fn level_rank(level: &str) -> u8 {
+    match level.to_uppercase().as_str() {
+        "ERROR" | "CRITICAL" => 3,
+        "WARN" | "WARNING" => 2,
+        "DEBUG" => 0,
+        _ => 1,
+    }
+}
+
+/// When a line was logged; lines without a readable timestamp count as now
+fn logged_at(entry: &LogEntry) -> DateTime<Local> {
+    parse_log_timestamp(&entry.timestamp).unwrap_or_else(Local::now)
+}
+
+struct Sink {
+    services: Vec<String>,
+    min_level: u8,
+    host: String,
+    target: Target,
+}
+
+enum Target {
+    Udp { address: String, facility: u8 },
+    Tcp { address: String, facility: u8, stream: Option<TcpStream> },
+    Loki { url: String, bearer_token: Option<String>, labels: BTreeMap<String, String> },
+    File { path: PathBuf, max_bytes: u64, keep: u32 },
+}
+
+impl Sink {
+    fn new(config: &LogForwardConfig, secrets: &Secrets) -> Result<Self> {
+        let target = match config.sink {
+            LogSinkKind::Syslog => {
+                let facility = facility(&config.facility)?;
+                let (scheme, address) = syslog_address(&config.target)?;
+                match scheme {
+                    "tcp" => Target::Tcp { address, facility, stream: None },
+                    _ => Target::Udp { address, facility },
+                }
+            }
+            LogSinkKind::Loki => Target::Loki {
+                url: config.target.clone(),
+                bearer_token: read_secret(
+                    secrets,
+                    &config.bearer_token_secret,
+                    &None,
+                    &config.bearer_token_file,
+                    "Loki bearer token",
+                )?,
+                labels: config.labels.clone(),
+            },
+            LogSinkKind::File => Target::File {
+                path: PathBuf::from(&config.target),
+                max_bytes: config.max_size_mb.max(1).saturating_mul(1024 * 1024),
+                keep: config.keep,
+            },
+        };
+        Ok(Self {
+            services: config.services.clone(),
+            min_level: level_rank(&config.min_level),
+            host: hostname(),
+            target,
+        })
+    }
+
+    fn accepts(&self, entry: &LogEntry) -> bool {
+        level_rank(&entry.level) >= self.min_level
+            && (self.services.is_empty() || self.services.contains(&entry.service))
+    }
+
+    async fn deliver(&mut self, entries: &[&LogEntry]) -> Result<()> {
+        match self.target {
+            Target::Udp { ref address, facility } => {
+                let bind = if address.starts_with('[') { "[::]:0" } else { "0.0.0.0:0" };
+                let socket = UdpSocket::bind(bind).await.context("Failed to open a UDP socket")?;
+                socket.connect(address.as_str()).await.with_context(|| format!("Failed to reach {}", address))?;
+                for entry in entries {
+                    let mut message = syslog_message(entry, facility, &self.host).into_bytes();
+                    message.truncate(MAX_DATAGRAM);
+                    socket.send(&message).await.with_context(|| format!("Failed to send to {}", address))?;
+                }
+                Ok(())
+            }
+            Target::Tcp { ref address, facility, ref mut stream } => {
+                // Octet-counted framing (RFC 6587), so messages may hold newlines
+                let mut frames = Vec::new();
+                for entry in entries {
+                    let message = syslog_message(entry, facility, &self.host);
+                    frames.extend_from_slice(format!("{} {}", message.len(), message).as_bytes());
+                }
+                if stream.is_none() {
+                    *stream = Some(
+                        TcpStream::connect(address.as_str())
+                            .await
+                            .with_context(|| format!("Failed to connect to {}", address))?,
+                    );
+                }
+                let Some(connection) = stream.as_mut() else {
+                    return Ok(());
+                };
+                if let Err(e) = connection.write_all(&frames).await {
+                    // Reconnect with the next batch
+                    *stream = None;
+                    return Err(e).with_context(|| format!("Failed to send to {}", address));
+                }
+                Ok(())
+            }
+            Target::Loki { ref url, ref bearer_token, ref labels } => {
+                post_json(url, bearer_token.as_deref(), &loki_push(entries, labels, &self.host)).await
+            }
+            Target::File { ref path, max_bytes, keep } => {
+                let mut text = String::new();
+                for entry in entries {
+                    text.push_str(&file_line(entry, &self.host));
+                    text.push('\n');
+                }
+                append_rotating(path, &text, max_bytes, keep)
+            }
+        }
+    }
+}
+
+fn facility(name: &str) -> Result<u8> {
+    match FACILITIES.iter().find(|(facility, _)| facility.eq_ignore_ascii_case(name.trim())) {
+        Some(&(_, code)) => Ok(code),
+        None => bail!("unknown syslog facility {:?}", name),
+    }
+}
+
+/// ("udp" or "tcp", "host:port") from "udp://host:514", "tcp://host:601" or
+/// a bare "host[:port]" (UDP); the port defaults to 514
+fn syslog_address(target: &str) -> Result<(&'static str, String)> {
+    let (scheme, rest) = match target.split_once("://") {
+        Some(("udp", rest)) => ("udp", rest),
+        Some(("tcp", rest)) => ("tcp", rest),
+        Some((scheme, _)) => bail!("{:?} is not udp:// or tcp://", scheme),
+        None => ("udp", target),
+    };
+    let rest = rest.trim_end_matches('/');
+    if rest.is_empty() {
+        bail!("no syslog host in {:?}", target);
+    }
+    // "host", "[v6]" and "[v6]:port" need the default port added
+    let has_port = match rest.rsplit_once(':') {
+        Some((host, port)) => port.parse::<u16>().is_ok() && (!host.contains(':') || host.ends_with(']')),
+        None => false,
+    };
+    Ok((scheme, if has_port { rest.to_string() } else { format!("{}:514", rest) }))
+}
+
+/// RFC 5424: `<PRI>1 TIMESTAMP HOST APP PROCID - - MESSAGE`
+fn syslog_message(entry: &LogEntry, facility: u8, host: &str) -> String {
+    let severity = match level_rank(&entry.level) {
+        3 => 3,
+        2 => 4,
+        0 => 7,
+        _ => 6,
+    };
+    let app: String = entry.service.chars().filter(|c| c.is_ascii_graphic()).take(48).collect();
+    format!(
+        "<{}>1 {} {} {} {} - - {}",
+        facility * 8 + severity,
+        logged_at(entry).to_rfc3339(),
+        host,
+        if app.is_empty() { "-" } else { &app },
+        entry.pid.as_deref().unwrap_or("-"),
+        entry.message
+    )
+}
+
+/// One Loki stream per service and level, values oldest first
+fn loki_push(entries: &[&LogEntry], labels: &BTreeMap<String, String>, host: &str) -> Value {
+    let mut streams: BTreeMap<(&str, &str), Vec<Value>> = BTreeMap::new();
+    for entry in entries {
+        let nanos = logged_at(entry).timestamp_nanos_opt().unwrap_or_default();
+        streams
+            .entry((entry.service.as_str(), entry.level.as_str()))
+            .or_default()
+            .push(json!([nanos.to_string(), entry.message]));
+    }
+    let streams: Vec<Value> = streams
+        .into_iter()
+        .map(|((service, level), values)| {
+            let mut stream: BTreeMap<&str, &str> = labels.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+            stream.insert("job", "hypervisor-tui");
+            stream.insert("host", host);
+            stream.insert("service", service);
+            stream.insert("level", level);
+            json!({ "stream": stream, "values": values })
+        })
+        .collect();
+    json!({ "streams": streams })
+}
+
+/// "2024-05-01T13:02:11+02:00 host service[pid]: WARN message"
+fn file_line(entry: &LogEntry, host: &str) -> String {
+    let pid = entry.pid.as_ref().map(|pid| format!("[{}]", pid)).unwrap_or_default();
+    format!("{} {} {}{}: {} {}", logged_at(entry).to_rfc3339(), host, entry.service, pid, entry.level, entry.message)
+}
+
+/// Append to `path`, first shifting it to path.1 (and path.1 to path.2, up
+/// to path.`keep`) when the text would take it past `max_bytes`
+fn append_rotating(path: &PathBuf, text: &str, max_bytes: u64, keep: u32) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+    }
+    let size = fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+    if size > 0 && size + text.len() as u64 > max_bytes {
+        let rotated = |n: u32| PathBuf::from(format!("{}.{}", path.display(), n));
+        if keep == 0 {
+            fs::remove_file(path).with_context(|| format!("Failed to rotate {:?}", path))?;
+        } else {
+            let _ = fs::remove_file(rotated(keep));
+            for n in (1..keep).rev() {
+                let _ = fs::rename(rotated(n), rotated(n + 1));
+            }
+            fs::rename(path, rotated(1)).with_context(|| format!("Failed to rotate {:?}", path))?;
+        }
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {:?}", path))?;
+    file.write_all(text.as_bytes()).with_context(|| format!("Failed to write {:?}", path))
+}
+
+/// Why a `[[logging.forward]]` entry can't be used, if it can't
+pub fn forward_problem(config: &LogForwardConfig) -> Option<String> {
+    let problem = match config.sink {
+        LogSinkKind::Syslog => facility(&config.facility).and(syslog_address(&config.target)).err().map(|e| e.to_string()),
+        LogSinkKind::Loki => (!config.target.starts_with("http://") && !config.target.starts_with("https://"))
+            .then(|| format!("{:?} should be an http:// or https:// URL", config.target)),
+        LogSinkKind::File => config.target.trim().is_empty().then(|| "needs a file path as target".to_string()),
+    };
+    problem.or_else(|| {
+        (!["ERROR", "WARN", "INFO", "DEBUG"].contains(&config.min_level.to_uppercase().as_str()))
+            .then(|| format!("min_level {:?} is not one of ERROR, WARN, INFO, DEBUG", config.min_level))
+    })
+}
@@ -0,0 +1,201 @@
+use crate::collectors::{parse_log_timestamp, LogCollector};
+use crate::log_filter::LogQuery;
+use crate::types::LogEntry;
+use std::cmp::Reverse;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Hits kept per service, the best ranked ones
+const MAX_HITS: usize = 200;
+
+/// A service's position among the searched ones and its journal lines, or
+/// why they couldn't be read
+type ServiceResult = (usize, Result<Vec<LogEntry>, String>);
+
+/// The hits of one service, best first
+pub struct SearchGroup {
+    pub service: String,
+    pub hits: Vec<Arc<LogEntry>>,
+    /// Matches before `MAX_HITS` cut them down
+    pub matches: usize,
+    /// Lines looked through
+    pub scanned: usize,
+    /// Where the lines came from ("journal" or "buffer")
+    pub source: &'static str,
+    /// Why the journal couldn't be read; the buffered lines were searched
+    pub error: Option<String>,
+    pub done: bool,
+}
+
+impl SearchGroup {
+    /// Rank of the best hit, for ordering the groups
+    fn best(&self) -> Option<(u8, i64)> {
+        self.hits.first().map(|hit| rank(hit))
+    }
+}
+
+/// Ctrl-F: one query run against each followed service's own journal at
+/// once, rather than the merged buffer the log view shows, with the hits
+/// grouped per service and ranked by level, then recency
+pub struct LogSearch {
+    pub query: String,
+    /// Typing the query rather than browsing the results
+    pub editing: bool,
+    pub groups: Vec<SearchGroup>,
+    /// Index of the selected hit across all groups, in display order
+    pub selected: usize,
+    /// The live buffer as of the search, searched for services whose
+    /// journal can't be read
+    buffered: Vec<Arc<LogEntry>>,
+    started: Instant,
+    rx: Option<mpsc::UnboundedReceiver<ServiceResult>>,
+}
+
+impl LogSearch {
+    pub fn new(query: String) -> Self {
+        Self {
+            query,
+            editing: true,
+            groups: Vec::new(),
+            selected: 0,
+            buffered: Vec::new(),
+            started: Instant::now(),
+            rx: None,
+        }
+    }
+
+    /// Read the newest `lines` of each service's journal concurrently and
+    /// search them as they arrive
+    pub fn start(&mut self, collector: &LogCollector, buffered: Vec<Arc<LogEntry>>, lines: usize) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.groups = collector
+            .services()
+            .iter()
+            .enumerate()
+            .map(|(index, service)| {
+                let read = collector.service_journal(service, lines);
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    let _ = tx.send((index, read.await.map_err(|e| format!("{:#}", e))));
+                });
+                SearchGroup {
+                    service: service.clone(),
+                    hits: Vec::new(),
+                    matches: 0,
+                    scanned: 0,
+                    source: "journal",
+                    error: None,
+                    done: false,
+                }
+            })
+            .collect();
+        self.buffered = buffered;
+        self.selected = 0;
+        self.editing = false;
+        self.started = Instant::now();
+        self.rx = Some(rx);
+    }
+
+    /// Take in the services that finished, returning whether any did
+    pub fn poll(&mut self) -> bool {
+        let Some(rx) = self.rx.as_mut() else {
+            return false;
+        };
+        let mut finished = Vec::new();
+        while let Ok(result) = rx.try_recv() {
+            finished.push(result);
+        }
+        if finished.is_empty() {
+            return false;
+        }
+
+        let query = LogQuery::parse(&self.query, None, false);
+        for (index, result) in finished {
+            let Some(group) = self.groups.get_mut(index) else {
+                continue;
+            };
+            let entries: Vec<Arc<LogEntry>> = match result {
+                Ok(entries) => entries.into_iter().map(Arc::new).collect(),
+                Err(e) => {
+                    group.error = Some(e);
+                    group.source = "buffer";
+                    self.buffered.iter().filter(|e| e.service == group.service).cloned().collect()
+                }
+            };
+            let mut hits = query.apply(&entries);
+            // Newest first, then the most severe of those first
+            hits.reverse();
+            hits.sort_by_key(|hit| Reverse(level_weight(&hit.level)));
+            group.scanned = entries.len();
+            group.matches = hits.len();
+            hits.truncate(MAX_HITS);
+            group.hits = hits;
+            group.done = true;
+        }
+        if self.groups.iter().all(|g| g.done) {
+            self.rx = None;
+        }
+
+        // Services with the best hits first, then those without any
+        self.groups.sort_by_key(|g| Reverse(g.best()));
+        self.selected = self.selected.min(self.hit_count().saturating_sub(1));
+        true
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn hit_count(&self) -> usize {
+        self.groups.iter().map(|g| g.hits.len()).sum()
+    }
+
+    pub fn selected_hit(&self) -> Option<Arc<LogEntry>> {
+        self.groups.iter().flat_map(|g| g.hits.iter()).nth(self.selected).cloned()
+    }
+
+    pub fn navigate(&mut self, down: bool) {
+        let last = self.hit_count().saturating_sub(1);
+        self.selected = if down { (self.selected + 1).min(last) } else { self.selected.saturating_sub(1) };
+    }
+
+    pub fn input(&mut self, c: char) {
+        self.query.push(c);
+    }
+
+    pub fn backspace(&mut self) {
+        self.query.pop();
+    }
+
+    /// "37 hits in 4 of 7 services (2 searching)"
+    pub fn summary(&self) -> String {
+        let matched = self.groups.iter().filter(|g| g.matches > 0).count();
+        let matches: usize = self.groups.iter().map(|g| g.matches).sum();
+        let mut summary = format!("{} hits in {} of {} services", matches, matched, self.groups.len());
+        if self.is_pending() {
+            let pending = self.groups.iter().filter(|g| !g.done).count();
+            summary.push_str(&format!(" ({} searching, {}s)", pending, self.elapsed().as_secs()));
+        }
+        summary
+    }
+}
+
+fn level_weight(level: &str) -> u8 {
+    match level {
+        "ERROR" => 3,
+        "WARN" => 2,
+        "INFO" => 1,
+        _ => 0,
+    }
+}
+
+/// (level weight, timestamp) of a hit; unparseable timestamps rank oldest
+fn rank(entry: &LogEntry) -> (u8, i64) {
+    let at = parse_log_timestamp(&entry.timestamp).map_or(i64::MIN, |at| at.timestamp());
+    (level_weight(&entry.level), at)
+}
@@ -1,14 +1,69 @@
+mod actions;
+mod agent;
+mod alert_profiles;
+mod alert_sweep;
 mod alerts;
 mod app;
+mod audit;
+mod batch;
+mod boots;
+mod cli;
 mod collectors;
+mod compare;
+mod computed;
 mod config;
+mod config_layers;
+mod display_text;
+mod evacuate;
+mod health;
+mod highlight;
+mod http;
+mod i18n;
+mod image_catalog;
+mod keymap;
+mod layout;
+mod log_archive;
+mod log_filter;
+mod log_forward;
+mod log_search;
 mod metrics_history;
+mod migrations;
+mod navigation;
+mod nixos;
+mod nix_store;
+mod notify;
+mod object_watch;
+mod oneshot;
+mod palette;
+mod panes;
+mod permissions;
+mod presence;
+mod receiver;
+mod redact;
+mod report;
+mod secrets;
+mod selftest;
+mod status_line;
+mod telemetry;
+mod theme;
+mod timeline;
+mod timestamps;
 mod types;
 mod ui;
+mod ui_state;
+mod units;
+mod update;
+mod upgrade;
+mod validation;
+mod version_check;
+mod views;
+mod watchdog;
+mod web;
+mod wizard;
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -17,14 +72,48 @@ use ratatui::{
     backend::CrosstermBackend,
     Terminal,
 };
-use std::io;
-use tokio::time::{Duration, interval};
+use std::collections::VecDeque;
+use std::{env, fs, io};
+use std::process::Command;
+use tokio::time::{Duration, Instant, MissedTickBehavior, interval};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use crate::app::{App, Screen};
+use crate::alert_profiles::AlertProfiles;
+use crate::app::App;
+use crate::cli::CliArgs;
+use crate::config::Config;
+use crate::config_layers::LayeredConfig;
+use crate::update::{update, Msg};
+
+const MAX_RESTARTS: u32 = 5; // Panics tolerated before giving up when restart_on_panic is set
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = CliArgs::parse()?;
+
+    if args.check_config {
+        return check_config();
+    }
+    if args.print_config {
+        return print_config(&args);
+    }
+    if args.generate_nixos_module {
+        print!("{}", nixos::generate_module()?);
+        return Ok(());
+    }
+    if let Some(command) = args.subcommand {
+        return oneshot::run(command, args.json).await;
+    }
+    if args.status_line {
+        return status_line::print().await;
+    }
+    if let Some(ref name) = args.export_alert_profile {
+        return export_alert_profile(name, args.alert_profile.as_deref());
+    }
+    if let Some(ref file) = args.import_alert_profile {
+        return import_alert_profile(file);
+    }
+
     // Initialize logging
     tracing_subscriber::registry()
         .with(
@@ -34,28 +123,72 @@ async fn main() -> Result<()> {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    if args.agent {
+        return agent::run(args.listen.as_deref()).await;
+    }
+
+    // Always leave the console usable, even on panic or SIGTERM/SIGHUP
+    watchdog::install_panic_hook(Config::state_dir().join("crash.log"));
+    watchdog::spawn_signal_handler();
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableFocusChange)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    // First launch: ask for the basics instead of guessing them
+    if !args.skip_setup && !args.read_only && Config::find_path().is_none() {
+        if let Err(e) = wizard::run(&mut terminal, Config::user_config_path()).await {
+            watchdog::restore_terminal();
+            return Err(e);
+        }
+        terminal.clear()?;
+    }
+
     // Create app state
-    let mut app = App::new().await?;
+    let mut app = match App::new(&args).await {
+        Ok(app) => app,
+        Err(e) => {
+            watchdog::restore_terminal();
+            return Err(e);
+        }
+    };
+
+    // Run the application, optionally restarting the event loop after a panic
+    let mut restarts = 0;
+    let result = loop {
+        match std::panic::AssertUnwindSafe(run_app(&mut terminal, &mut app))
+            .catch_unwind()
+            .await
+        {
+            Ok(result) => break result,
+            Err(_) if app.restart_on_panic && restarts < MAX_RESTARTS => {
+                restarts += 1;
+                tracing::error!("Event loop panicked, restarting ({}/{})", restarts, MAX_RESTARTS);
+                // The panic hook restored the terminal; take it over again
+                take_over_terminal(&mut terminal)?;
+            }
+            Err(_) => break Err(anyhow::anyhow!("event loop panicked")),
+        }
+    };
 
-    // Run the application
-    let result = run_app(&mut terminal, &mut app).await;
+    app.save_ui_state();
 
     // Restore terminal
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableFocusChange
     )?;
     terminal.show_cursor()?;
 
+    if let Some(summary) = app.exit_summary() {
+        print!("{}", summary);
+    }
     if let Err(err) = result {
         eprintln!("Error: {}", err);
     }
@@ -63,48 +196,310 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn run_app<B: ratatui::backend::Backend>(
+async fn run_app<B: ratatui::backend::Backend + io::Write>(
     terminal: &mut Terminal<B>,
     app: &mut App,
 ) -> Result<()> {
     let mut update_interval = interval(Duration::from_secs(2));
+    // An update that overran its interval isn't followed by a burst of
+    // catch-up ticks
+    update_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut needs_redraw = true;
+    let size = terminal.size()?;
+    update(app, Msg::Resized { width: size.width, height: size.height });
 
     loop {
-        terminal.draw(|f| ui::draw(f, app))?;
+        if watchdog::shutdown_requested() {
+            return Ok(());
+        }
+        if watchdog::take_suspend_request() {
+            suspend(terminal, app).await?;
+            needs_redraw = true;
+        }
+        if watchdog::take_repaint_request() {
+            take_over_terminal(terminal)?;
+            needs_redraw = true;
+        }
 
-        // Check for user input
+        // Accessible mode only redraws when something changed, so screen
+        // readers and serial consoles aren't flooded with identical frames;
+        // neither does idle mode, with nobody looking
+        if needs_redraw || !(app.accessible || app.presence.is_idle()) {
+            let started = Instant::now();
+            app.refresh_display_text();
+            terminal.draw(|f| ui::draw(f, app))?;
+            app.telemetry.record_draw(started.elapsed());
+            needs_redraw = false;
+        }
+
+        let mut messages = VecDeque::from([Msg::Poll]);
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                // Handle alert panel navigation if open
-                if app.alert_panel_open {
-                    match key.code {
-                        KeyCode::Esc => app.toggle_alert_panel(),
-                        KeyCode::Up => app.alert_navigate_up(),
-                        KeyCode::Down => app.alert_navigate_down(),
-                        KeyCode::Char('d') => app.dismiss_selected_alert(),
-                        KeyCode::Char('D') => app.dismiss_all_alerts(),
-                        _ => {}
+            match event::read()? {
+                Event::Key(key) => messages.push_back(Msg::KeyPressed(key)),
+                Event::Resize(width, height) => messages.push_back(Msg::Resized { width, height }),
+                Event::FocusGained => messages.push_back(Msg::Focus(true)),
+                Event::FocusLost => messages.push_back(Msg::Focus(false)),
+                _ => needs_redraw = true,
+            }
+        }
+        if update_interval.tick().now_or_never().is_some() && app.presence.update_due() {
+            messages.push_back(Msg::Tick);
+        }
+
+        // Tasks feed their outcome back as further messages
+        while let Some(msg) = messages.pop_front() {
+            let mut commands = vec![update(app, msg)];
+            while let Some(command) = commands.pop() {
+                match command {
+                    update::Command::None => {}
+                    update::Command::Batch(batch) => commands.extend(batch.into_iter().rev()),
+                    update::Command::Run(task) => {
+                        if let Some(msg) = task.run(app).await {
+                            messages.push_back(msg);
+                        }
+                    }
+                    update::Command::Redraw => needs_redraw = true,
+                    update::Command::Quit => return Ok(()),
+                    update::Command::CopyRunbook => {
+                        if let Some(url) = app.selected_alert_runbook() {
+                            copy_to_clipboard(terminal.backend_mut(), &url)?;
+                        }
+                        needs_redraw = true;
+                    }
+                    update::Command::CopyReport => {
+                        if let Some(ref report) = app.last_report {
+                            copy_to_clipboard(terminal.backend_mut(), report)?;
+                        }
+                        needs_redraw = true;
+                    }
+                    update::Command::Suspend => {
+                        suspend(terminal, app).await?;
+                        needs_redraw = true;
+                    }
+                    update::Command::Repaint => {
+                        take_over_terminal(terminal)?;
+                        needs_redraw = true;
+                    }
+                    update::Command::CopyLogLine => {
+                        if let Some(text) = app.log_detail_text() {
+                            copy_to_clipboard(terminal.backend_mut(), &text)?;
+                        }
+                        needs_redraw = true;
+                    }
+                    update::Command::EditYaml => {
+                        if let Err(e) = edit_yaml_in_editor(terminal, app).await {
+                            app.report_error("Editing manifest", &e);
+                        }
+                        needs_redraw = true;
                     }
-                } else {
-                    // Normal navigation
-                    match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                        KeyCode::F(1) => app.current_screen = Screen::Logs,
-                        KeyCode::F(2) => app.current_screen = Screen::Dashboard,
-                        KeyCode::F(3) => app.current_screen = Screen::Network,
-                        KeyCode::Up => app.scroll_up(),
-                        KeyCode::Down => app.scroll_down(),
-                        KeyCode::Char('a') => app.toggle_alert_panel(),
-                        KeyCode::Char('r') => app.refresh().await?,
-                        _ => {}
+                    update::Command::EditCloudInit => {
+                        if let Err(e) = edit_cloud_init_in_editor(terminal, app).await {
+                            app.report_error("Editing cloud-init", &e);
+                        }
+                        needs_redraw = true;
                     }
                 }
             }
         }
+    }
+}
+
+/// Print config problems for `--check-config`, failing if there are any
+fn check_config() -> Result<()> {
+    let (_, report) = Config::load();
+    let source = report.describe_sources("no config file (defaults)");
+
+    if report.is_clean() {
+        println!("{}: OK", source);
+        return Ok(());
+    }
+    println!("{}: {} problem(s)", source, report.problems.len());
+    for problem in &report.problems {
+        println!("  {}", problem);
+    }
+    std::process::exit(1);
+}
+
+/// Print the merged config for `--print-config`, with each value's source
+fn print_config(args: &CliArgs) -> Result<()> {
+    let layers = LayeredConfig::read().with_args(args);
+    let (config, report) = layers.load();
+    print!("{}", layers.render(&config));
+    for problem in &report.problems {
+        eprintln!("warning: {}", problem);
+    }
+    Ok(())
+}
+
+/// Save the effective [alerts] settings (the config's, with the starting
+/// profile laid over it) as a profile for `--export-alert-profile`
+fn export_alert_profile(name: &str, profile: Option<&str>) -> Result<()> {
+    let (config, _) = Config::load();
+    let profiles = AlertProfiles::for_config(&config);
+    let alerts = profiles.resolve(&config.alerts, profile.or(config.alerts.profile.as_deref()))?;
+    let path = profiles.export(name, &alerts)?;
+    println!("Exported alert profile {:?} to {}", name, path.display());
+    Ok(())
+}
+
+/// Add a shared profile file for `--import-alert-profile`
+fn import_alert_profile(file: &str) -> Result<()> {
+    let (config, _) = Config::load();
+    let profiles = AlertProfiles::for_config(&config);
+    let name = profiles.import(std::path::Path::new(file))?;
+    println!("Imported alert profile {:?} to {}", name, profiles.path(&name).display());
+    Ok(())
+}
+
+/// Copy through the terminal (OSC 52), which also works over SSH. Terminals
+/// without support ignore it; the URL (or report path) is shown in a toast
+/// either way.
+fn copy_to_clipboard(out: &mut impl io::Write, text: &str) -> io::Result<()> {
+    use base64::Engine;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    write!(out, "\x1b]52;c;{}\x07", encoded)?;
+    out.flush()
+}
+
+/// Hand the inspected manifest to $EDITOR and server-side apply it if it changed
+async fn edit_yaml_in_editor<B: ratatui::backend::Backend + io::Write>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+) -> Result<()> {
+    let Some(inspector) = app.yaml_inspector.as_ref() else {
+        return Ok(());
+    };
+
+    let original = inspector.content.clone();
+    // Secrets are edited this way too
+    let path = write_private_file(
+        &format!("{}-{}", inspector.resource.kind.kubectl_name(), inspector.resource.name),
+        &original,
+    )?;
+
+    let message = match run_editor(terminal, &path)? {
+        Ok(()) => {
+            let edited = fs::read_to_string(&path)?;
+            if edited != original {
+                app.apply_yaml_edit(edited).await;
+                None
+            } else {
+                Some("No changes made".to_string())
+            }
+        }
+        Err(message) => Some(message),
+    };
+
+    if let (Some(message), Some(inspector)) = (message, app.yaml_inspector.as_mut()) {
+        inspector.status = Some(message);
+    }
+
+    let _ = fs::remove_file(&path);
+    Ok(())
+}
+
+/// Hand the viewed VM's cloud-init user data, unredacted, to $EDITOR and
+/// save it into the VM spec if it changed
+async fn edit_cloud_init_in_editor<B: ratatui::backend::Backend + io::Write>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+) -> Result<()> {
+    let Some(original) = app.cloud_init_user_data() else {
+        return Ok(());
+    };
+    let Some(viewer) = app.cloud_init.as_ref() else {
+        return Ok(());
+    };
+
+    // User data can hold passwords and keys
+    let path = write_private_file(&format!("{}-user-data", viewer.resource.name), &original)?;
 
-        // Periodic updates
-        if update_interval.tick().now_or_never().is_some() {
-            app.update().await?;
+    let message = match run_editor(terminal, &path)? {
+        Ok(()) => {
+            let edited = fs::read_to_string(&path)?;
+            if edited != original {
+                app.apply_cloud_init_edit(edited).await;
+                None
+            } else {
+                Some("No changes made".to_string())
+            }
         }
+        Err(message) => Some(message),
+    };
+
+    if let (Some(message), Some(viewer)) = (message, app.cloud_init.as_mut()) {
+        viewer.status = Some(message);
     }
+
+    let _ = fs::remove_file(&path);
+    Ok(())
+}
+
+/// A file for $EDITOR only this user can reach: a fresh, unguessable
+/// name under a 0700 directory in the state directory, opened 0600 and
+/// never over an existing file or symlink
+fn write_private_file(stem: &str, content: &str) -> Result<std::path::PathBuf> {
+    use std::io::Write;
+    use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+
+    let dir = Config::state_dir().join("edit");
+    fs::DirBuilder::new().recursive(true).mode(0o700).create(&dir)?;
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+
+    let stem: String = stem.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' }).collect();
+    let path = dir.join(format!("{}-{:016x}.yaml", stem, rand::random::<u64>()));
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&path)?
+        .write_all(content.as_bytes())?;
+    Ok(path)
+}
+
+/// Put the terminal (back) into the state the TUI draws in and make the
+/// next frame repaint every cell rather than what changed, since after a
+/// suspend, a panic or a reattach the screen holds anything
+fn take_over_terminal<B: ratatui::backend::Backend + io::Write>(terminal: &mut Terminal<B>) -> Result<()> {
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture, EnableFocusChange)?;
+    terminal.clear()?;
+    Ok(())
+}
+
+/// Stop like a shell job on Ctrl-Z or SIGTSTP and take the terminal over
+/// again on `fg`, at whatever size it has by then
+async fn suspend<B: ratatui::backend::Backend + io::Write>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+    tracing::info!("Suspending");
+    watchdog::suspend();
+    take_over_terminal(terminal)?;
+    // Continuing raises a repaint request that is already served
+    watchdog::take_repaint_request();
+    let size = terminal.size()?;
+    update(app, Msg::Resized { width: size.width, height: size.height });
+    Ok(())
+}
+
+/// Run $EDITOR on `path` with the terminal handed over for the duration of
+/// the edit. The inner error says why the edit didn't happen.
+fn run_editor<B: ratatui::backend::Backend + io::Write>(
+    terminal: &mut Terminal<B>,
+    path: &std::path::Path,
+) -> Result<std::result::Result<(), String>> {
+    let editor = env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    let mut editor_args = editor.split_whitespace();
+    let program = editor_args.next().unwrap_or("vi");
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture, DisableFocusChange)?;
+    let status = Command::new(program).args(editor_args).arg(path).status();
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture, EnableFocusChange)?;
+    terminal.clear()?;
+
+    Ok(match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!("Editor exited with {}", status)),
+        Err(e) => Err(format!("Failed to launch editor '{}': {}", program, e)),
+    })
 }
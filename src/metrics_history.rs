@@ -1,6 +1,527 @@
-use std::collections::VecDeque;
+use crate::alerts::AlertLevel;
+use crate::types::FilesystemUsage;
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
 
 const MAX_HISTORY: usize = 60; // Keep last 60 data points
+const POINTS_PER_ZOOM: usize = 60; // Buckets kept at each resolution
+
+/// Slopes flatter than this (percentage points per minute) count as steady
+const STEADY_SLOPE_PER_MIN: f64 = 0.2;
+/// Fewest buckets a trend is fitted to
+const MIN_TREND_SAMPLES: usize = 6;
+/// Raw CPU readings at or above this are marked on the CPU chart
+pub const CPU_SPIKE_PERCENT: f64 = 95.0;
+/// Spikes kept for the chart, the longest zoom level's worth at most
+const MAX_CPU_SPIKES: usize = 512;
+/// Alert markers kept for the charts, the longest zoom level's worth at most
+const MAX_ALERT_MARKERS: usize = 512;
+/// Filesystem growth slower than this (percentage points per hour) is
+/// treated as not filling up
+const MIN_FILL_RATE_PER_HOUR: f64 = 0.01;
+
+/// Time window shown by the charts view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Zoom {
+    FiveMinutes,
+    ThirtyMinutes,
+    TwoHours,
+    Day,
+}
+
+impl Zoom {
+    pub const ALL: [Zoom; 4] = [Zoom::FiveMinutes, Zoom::ThirtyMinutes, Zoom::TwoHours, Zoom::Day];
+
+    pub fn label(&self) -> &str {
+        match self {
+            Zoom::FiveMinutes => "5m",
+            Zoom::ThirtyMinutes => "30m",
+            Zoom::TwoHours => "2h",
+            Zoom::Day => "24h",
+        }
+    }
+
+    /// Width of one bucket at this zoom level
+    pub fn resolution_secs(&self) -> i64 {
+        match self {
+            Zoom::FiveMinutes => 5,
+            Zoom::ThirtyMinutes => 30,
+            Zoom::TwoHours => 120,
+            Zoom::Day => 1440,
+        }
+    }
+
+    pub fn zoom_in(&self) -> Self {
+        let idx = Self::ALL.iter().position(|z| z == self).unwrap_or(0);
+        Self::ALL[idx.saturating_sub(1)]
+    }
+
+    pub fn zoom_out(&self) -> Self {
+        let idx = Self::ALL.iter().position(|z| z == self).unwrap_or(0);
+        Self::ALL[(idx + 1).min(Self::ALL.len() - 1)]
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|z| z == self).unwrap_or(0)
+    }
+}
+
+/// Metrics available in the charts view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChartMetric {
+    Cpu,
+    Memory,
+    DiskRead,
+    DiskWrite,
+    Power,
+    ApiLatency,
+    /// The header's health score
+    Health,
+}
+
+impl ChartMetric {
+    pub const ALL: [ChartMetric; 7] = [
+        ChartMetric::Cpu,
+        ChartMetric::Memory,
+        ChartMetric::DiskRead,
+        ChartMetric::DiskWrite,
+        ChartMetric::Power,
+        ChartMetric::ApiLatency,
+        ChartMetric::Health,
+    ];
+
+    pub fn title(&self) -> &str {
+        match self {
+            ChartMetric::Cpu => "CPU",
+            ChartMetric::Memory => "Memory",
+            ChartMetric::DiskRead => "Disk Read",
+            ChartMetric::DiskWrite => "Disk Write",
+            ChartMetric::Power => "Package Power",
+            ChartMetric::ApiLatency => "API Latency",
+            ChartMetric::Health => "Health",
+        }
+    }
+
+    /// Alert sources (by prefix) whose firing and resolving is marked on
+    /// this metric's chart
+    pub fn alert_sources(&self) -> &'static [&'static str] {
+        match self {
+            ChartMetric::Cpu => &["cpu", "load"],
+            ChartMetric::Memory => &["memory"],
+            ChartMetric::DiskRead | ChartMetric::DiskWrite => &["disk"],
+            ChartMetric::Power => &["cpu-frequency", "power"],
+            ChartMetric::ApiLatency => &["k8s-api"],
+            // Every alert weighs on it
+            ChartMetric::Health => &[""],
+        }
+    }
+
+    pub fn unit(&self) -> &str {
+        match self {
+            ChartMetric::Cpu | ChartMetric::Memory => "%",
+            ChartMetric::DiskRead | ChartMetric::DiskWrite => " MB/s",
+            ChartMetric::Power => " W",
+            ChartMetric::ApiLatency => " ms",
+            ChartMetric::Health => "",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let idx = Self::ALL.iter().position(|m| m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Percentage gauges on the dashboard that get a trend and breach forecast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrendMetric {
+    Cpu,
+    Memory,
+    DiskUsage,
+}
+
+impl TrendMetric {
+    /// Fit window: the last hour for disk usage, which moves slowly and
+    /// would look flat over minutes, the last five minutes otherwise
+    fn zoom(&self) -> Zoom {
+        match self {
+            TrendMetric::DiskUsage => Zoom::TwoHours,
+            _ => Zoom::FiveMinutes,
+        }
+    }
+
+    /// Furthest out a breach is still worth announcing
+    fn horizon_mins(&self) -> f64 {
+        match self {
+            TrendMetric::DiskUsage => 24.0 * 60.0,
+            _ => 60.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Rising,
+    Falling,
+    Steady,
+}
+
+/// Direction of a metric and, if it is heading for its warning threshold,
+/// roughly when it will get there
+#[derive(Debug, Clone, Copy)]
+pub struct Forecast {
+    pub trend: Trend,
+    pub breach_in_mins: Option<f64>,
+}
+
+impl Forecast {
+    /// Least-squares fit over the samples, extrapolated from the latest one
+    fn from_samples(samples: &[Sample], threshold: f64, horizon_mins: f64) -> Option<Self> {
+        if samples.len() < MIN_TREND_SAMPLES {
+            return None;
+        }
+        let origin = samples[0].at.timestamp() as f64;
+        let points: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|s| ((s.at.timestamp() as f64 - origin) / 60.0, s.value))
+            .collect();
+        let (slope, _) = fit_line(&points)?;
+
+        let trend = if slope > STEADY_SLOPE_PER_MIN {
+            Trend::Rising
+        } else if slope < -STEADY_SLOPE_PER_MIN {
+            Trend::Falling
+        } else {
+            Trend::Steady
+        };
+        let current = samples[samples.len() - 1].value;
+        let breach_in_mins = (trend == Trend::Rising && current < threshold)
+            .then(|| (threshold - current) / slope)
+            .filter(|mins| *mins <= horizon_mins);
+
+        Some(Self { trend, breach_in_mins })
+    }
+}
+
+/// Least-squares line through the points as (slope, intercept); None
+/// when all x are equal
+fn fit_line(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let var_x: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    if var_x == 0.0 {
+        return None;
+    }
+    let slope = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum::<f64>() / var_x;
+    Some((slope, mean_y - slope * mean_x))
+}
+
+/// Shape of the growth a time-to-full forecast was extrapolated from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrowthModel {
+    Linear,
+    /// Usage growing by a steady factor, such as logs of a growing fleet
+    Exponential,
+}
+
+impl GrowthModel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GrowthModel::Linear => "linear",
+            GrowthModel::Exponential => "exponential",
+        }
+    }
+}
+
+/// When a filesystem is projected to be full at its current growth
+#[derive(Debug, Clone, Copy)]
+pub struct TimeToFull {
+    pub hours: f64,
+    pub model: GrowthModel,
+}
+
+impl TimeToFull {
+    /// "45m", "7.5h" or "3.2 days"
+    pub fn eta(&self) -> String {
+        if self.hours < 1.0 {
+            format!("{:.0}m", self.hours * 60.0)
+        } else if self.hours < 48.0 {
+            format!("{:.1}h", self.hours)
+        } else {
+            format!("{:.1} days", self.hours / 24.0)
+        }
+    }
+
+    /// Fit used-percent samples both linearly and exponentially and keep
+    /// whichever follows them more closely; None when usage isn't growing
+    fn from_samples(samples: &[Sample]) -> Option<Self> {
+        if samples.len() < MIN_TREND_SAMPLES {
+            return None;
+        }
+        let origin = samples[0].at.timestamp() as f64;
+        let points: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|s| ((s.at.timestamp() as f64 - origin) / 3600.0, s.value))
+            .collect();
+        let (last_x, current) = points[points.len() - 1];
+        if current >= 100.0 {
+            return Some(Self { hours: 0.0, model: GrowthModel::Linear });
+        }
+
+        let (slope, intercept) = fit_line(&points)?;
+        if slope < MIN_FILL_RATE_PER_HOUR {
+            return None;
+        }
+        let linear_error: f64 = points.iter().map(|(x, y)| (y - (slope * x + intercept)).powi(2)).sum();
+        let linear = Self { hours: (100.0 - current) / slope, model: GrowthModel::Linear };
+
+        // ln(y) = rate * x + c, only defined while usage is above zero
+        if current <= 0.0 || points.iter().any(|p| p.1 <= 0.0) {
+            return Some(linear);
+        }
+        let logs: Vec<(f64, f64)> = points.iter().map(|(x, y)| (*x, y.ln())).collect();
+        let Some((rate, c)) = fit_line(&logs).filter(|(rate, _)| *rate > 0.0) else {
+            return Some(linear);
+        };
+        let exponential_error: f64 = points.iter().map(|(x, y)| (y - (rate * x + c).exp()).powi(2)).sum();
+        if exponential_error < linear_error {
+            // From the fitted curve's value now, not the noisy last sample
+            let fitted_now = (rate * last_x + c).exp().min(100.0);
+            let hours = (100.0 / fitted_now).ln() / rate;
+            return Some(Self { hours, model: GrowthModel::Exponential });
+        }
+        Some(linear)
+    }
+}
+
+/// How a daemon's resident memory moved over a leak detection window
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryGrowth {
+    /// Slope of the least-squares fit
+    pub mb_per_hour: f64,
+    /// Each quarter of the window averaged more than the one before, so
+    /// the growth isn't a single spike or a sawtooth of caches filling and
+    /// being dropped
+    pub steady: bool,
+    pub start_mb: f64,
+    pub current_mb: f64,
+}
+
+impl MemoryGrowth {
+    /// None unless the samples cover most of the window from `start`, so a
+    /// freshly (re)started daemon warming up isn't judged on its first hour
+    fn from_samples(samples: &[Sample], start: DateTime<Local>, window: chrono::Duration) -> Option<Self> {
+        if samples.len() < MIN_TREND_SAMPLES || samples[0].at > start + window / 10 {
+            return None;
+        }
+        let origin = samples[0].at.timestamp() as f64;
+        let points: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|s| ((s.at.timestamp() as f64 - origin) / 3600.0, s.value))
+            .collect();
+        let (slope, _) = fit_line(&points)?;
+
+        let quarter = samples.len() / 4;
+        let means: Vec<f64> = (0..4)
+            .map(|i| {
+                let end = if i == 3 { samples.len() } else { (i + 1) * quarter };
+                let chunk = &samples[i * quarter..end];
+                chunk.iter().map(|s| s.value).sum::<f64>() / chunk.len() as f64
+            })
+            .collect();
+
+        Some(Self {
+            mb_per_hour: slope,
+            steady: means.windows(2).all(|pair| pair[1] > pair[0]),
+            start_mb: samples[0].value,
+            current_mb: samples[samples.len() - 1].value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub at: DateTime<Local>,
+    pub value: f64,
+}
+
+/// Fixed-width buckets averaging the samples that fall into them
+#[derive(Debug, Clone)]
+struct Tier {
+    resolution_secs: i64,
+    points: VecDeque<Sample>,
+    // (bucket start, sum, count) of the bucket still being filled
+    pending: Option<(i64, f64, u32)>,
+}
+
+impl Tier {
+    fn new(resolution_secs: i64) -> Self {
+        Self {
+            resolution_secs,
+            points: VecDeque::with_capacity(POINTS_PER_ZOOM),
+            pending: None,
+        }
+    }
+
+    fn record(&mut self, at: DateTime<Local>, value: f64) {
+        let bucket = at.timestamp() / self.resolution_secs * self.resolution_secs;
+        match self.pending {
+            Some((start, ref mut sum, ref mut count)) if start == bucket => {
+                *sum += value;
+                *count += 1;
+            }
+            _ => {
+                self.flush();
+                self.pending = Some((bucket, value, 1));
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(sample) = self.pending_sample() {
+            if self.points.len() >= POINTS_PER_ZOOM {
+                self.points.pop_front();
+            }
+            self.points.push_back(sample);
+        }
+    }
+
+    fn pending_sample(&self) -> Option<Sample> {
+        let (start, sum, count) = self.pending?;
+        Some(Sample {
+            at: Local.timestamp_opt(start, 0).single()?,
+            value: sum / count as f64,
+        })
+    }
+
+    /// Completed buckets plus the partially filled current one
+    fn samples(&self) -> Vec<Sample> {
+        self.points.iter().copied().chain(self.pending_sample()).collect()
+    }
+}
+
+/// One metric kept at every zoom level's resolution, so long windows stay
+/// cheap while short windows keep fine detail
+#[derive(Debug, Clone)]
+struct MultiResolutionSeries {
+    tiers: Vec<Tier>,
+}
+
+impl MultiResolutionSeries {
+    fn new() -> Self {
+        Self {
+            tiers: Zoom::ALL.iter().map(|z| Tier::new(z.resolution_secs())).collect(),
+        }
+    }
+
+    fn record(&mut self, at: DateTime<Local>, value: f64) {
+        for tier in &mut self.tiers {
+            tier.record(at, value);
+        }
+    }
+
+    fn samples(&self, zoom: Zoom) -> Vec<Sample> {
+        self.tiers[zoom.index()].samples()
+    }
+}
+
+/// Minutes the noisiest log sources are ranked over
+pub const NOISY_WINDOW_MINS: i64 = 15;
+
+/// Lines and errors per minute logged by one service, one point per
+/// log collection
+#[derive(Debug, Clone, Default)]
+pub struct LogRate {
+    lines: VecDeque<f64>,
+    errors: VecDeque<f64>,
+    /// Errors read per collection over the last two noisy-source windows
+    error_counts: VecDeque<(DateTime<Local>, u64)>,
+}
+
+impl LogRate {
+    fn push(&mut self, lines: f64, errors: f64) {
+        if self.lines.len() >= MAX_HISTORY {
+            self.lines.pop_front();
+            self.errors.pop_front();
+        }
+        self.lines.push_back(lines);
+        self.errors.push_back(errors);
+    }
+
+    fn push_errors(&mut self, at: DateTime<Local>, errors: u64) {
+        let oldest = at - chrono::Duration::minutes(2 * NOISY_WINDOW_MINS);
+        while self.error_counts.front().is_some_and(|(time, _)| *time <= oldest) {
+            self.error_counts.pop_front();
+        }
+        if errors > 0 {
+            self.error_counts.push_back((at, errors));
+        }
+    }
+
+    pub fn lines_per_min(&self) -> Vec<f64> {
+        self.lines.iter().copied().collect()
+    }
+
+    pub fn errors_per_min(&self) -> f64 {
+        self.errors.back().copied().unwrap_or(0.0)
+    }
+
+    pub fn current(&self) -> f64 {
+        self.lines.back().copied().unwrap_or(0.0)
+    }
+
+    /// Mean of the points before the latest one, what "normal" looks like
+    pub fn baseline(&self) -> Option<f64> {
+        let earlier = self.lines.len().checked_sub(1).filter(|n| *n > 0)?;
+        Some(self.lines.iter().take(earlier).sum::<f64>() / earlier as f64)
+    }
+}
+
+/// Minutes of error and warning rates shown in the footer
+pub const LEVEL_WINDOW_MINS: i64 = 10;
+
+/// Errors and warnings logged by all services per minute over the last
+/// `LEVEL_WINDOW_MINS`, oldest first, with the trend against the window before
+#[derive(Debug, Clone)]
+pub struct LogLevelStats {
+    pub errors: Vec<u64>,
+    pub warnings: Vec<u64>,
+    pub error_trend: Trend,
+    pub warning_trend: Trend,
+}
+
+/// A service ranked by errors logged in the last `NOISY_WINDOW_MINS`
+#[derive(Debug, Clone)]
+pub struct NoisySource {
+    pub service: String,
+    pub errors: u64,
+    /// Errors in the window before, for the trend
+    pub previous: u64,
+}
+
+impl NoisySource {
+    pub fn trend(&self) -> Trend {
+        match self.errors.cmp(&self.previous) {
+            std::cmp::Ordering::Greater => Trend::Rising,
+            std::cmp::Ordering::Less => Trend::Falling,
+            std::cmp::Ordering::Equal => Trend::Steady,
+        }
+    }
+}
+
+/// An alert firing or resolving, drawn across the charts as a vertical line
+#[derive(Debug, Clone)]
+pub struct AlertMarker {
+    pub at: DateTime<Local>,
+    pub level: AlertLevel,
+    pub source: String,
+    pub title: String,
+    pub resolved: bool,
+}
 
 #[derive(Debug, Clone)]
 pub struct MetricsHistory {
@@ -10,6 +531,36 @@ pub struct MetricsHistory {
     disk_write_history: VecDeque<f64>,
     network_rx_history: VecDeque<u64>,
     network_tx_history: VecDeque<u64>,
+
+    cpu_series: MultiResolutionSeries,
+    /// Time constant of the displayed CPU average; zero for none
+    cpu_smoothing: chrono::Duration,
+    /// Exponential moving average of CPU usage as of its last update
+    cpu_smoothed: Option<Sample>,
+    /// Raw readings at or above `CPU_SPIKE_PERCENT`, which bucket averages
+    /// and smoothing flatten
+    cpu_spikes: VecDeque<Sample>,
+    memory_series: MultiResolutionSeries,
+    disk_read_series: MultiResolutionSeries,
+    disk_write_series: MultiResolutionSeries,
+    power_series: MultiResolutionSeries,
+    api_latency_series: MultiResolutionSeries,
+    health_series: MultiResolutionSeries,
+    disk_usage_series: MultiResolutionSeries,
+    /// Used percent per tracked mount point
+    filesystem_series: BTreeMap<String, MultiResolutionSeries>,
+    /// Per `[[computed_metrics]]` entry
+    computed_series: BTreeMap<String, MultiResolutionSeries>,
+    /// Resident MB per daemon watched by `[memory_leaks]`
+    process_rss_series: BTreeMap<String, MultiResolutionSeries>,
+    /// Alerts fired and resolved, oldest first
+    alert_markers: VecDeque<AlertMarker>,
+
+    log_rates: BTreeMap<String, LogRate>,
+    last_log_counts: Option<DateTime<Local>>,
+    /// (read at, errors, warnings) across all services per collection,
+    /// over the last two `LEVEL_WINDOW_MINS`
+    log_levels: VecDeque<(DateTime<Local>, u64, u64)>,
 }
 
 impl Default for MetricsHistory {
@@ -27,14 +578,86 @@ impl MetricsHistory {
             disk_write_history: VecDeque::with_capacity(MAX_HISTORY),
             network_rx_history: VecDeque::with_capacity(MAX_HISTORY),
             network_tx_history: VecDeque::with_capacity(MAX_HISTORY),
+            cpu_series: MultiResolutionSeries::new(),
+            cpu_smoothing: chrono::Duration::zero(),
+            cpu_smoothed: None,
+            cpu_spikes: VecDeque::new(),
+            memory_series: MultiResolutionSeries::new(),
+            disk_read_series: MultiResolutionSeries::new(),
+            disk_write_series: MultiResolutionSeries::new(),
+            power_series: MultiResolutionSeries::new(),
+            api_latency_series: MultiResolutionSeries::new(),
+            health_series: MultiResolutionSeries::new(),
+            disk_usage_series: MultiResolutionSeries::new(),
+            filesystem_series: BTreeMap::new(),
+            computed_series: BTreeMap::new(),
+            process_rss_series: BTreeMap::new(),
+            alert_markers: VecDeque::new(),
+            log_rates: BTreeMap::new(),
+            last_log_counts: None,
+            log_levels: VecDeque::new(),
         }
     }
 
+    pub fn with_cpu_smoothing(mut self, secs: u64) -> Self {
+        self.cpu_smoothing = chrono::Duration::seconds(secs as i64);
+        self
+    }
+
     pub fn record_cpu(&mut self, value: f64) {
         if self.cpu_history.len() >= MAX_HISTORY {
             self.cpu_history.pop_front();
         }
         self.cpu_history.push_back(value);
+        let now = Local::now();
+        self.cpu_series.record(now, value);
+
+        // Weighted by the time since the last reading, so the average
+        // covers the same span whatever the refresh interval
+        let smoothed = match self.cpu_smoothed {
+            Some(last) if self.cpu_smoothing > chrono::Duration::zero() => {
+                let elapsed = (now - last.at).num_milliseconds().max(0) as f64;
+                let weight = 1.0 - (-elapsed / self.cpu_smoothing.num_milliseconds() as f64).exp();
+                last.value + weight * (value - last.value)
+            }
+            _ => value,
+        };
+        self.cpu_smoothed = Some(Sample { at: now, value: smoothed });
+
+        if value >= CPU_SPIKE_PERCENT {
+            let oldest = now - chrono::Duration::seconds(Zoom::Day.resolution_secs() * POINTS_PER_ZOOM as i64);
+            while self.cpu_spikes.front().is_some_and(|s| s.at < oldest) || self.cpu_spikes.len() >= MAX_CPU_SPIKES {
+                self.cpu_spikes.pop_front();
+            }
+            self.cpu_spikes.push_back(Sample { at: now, value });
+        }
+    }
+
+    /// CPU usage as shown on gauges: the moving average, or the latest
+    /// reading when smoothing is off
+    pub fn smoothed_cpu(&self) -> Option<f64> {
+        self.cpu_smoothed.map(|s| s.value)
+    }
+
+    /// Raw CPU readings at or above `CPU_SPIKE_PERCENT`, oldest first
+    pub fn cpu_spikes(&self) -> impl Iterator<Item = &Sample> {
+        self.cpu_spikes.iter()
+    }
+
+    pub fn record_alert(&mut self, marker: AlertMarker) {
+        let oldest = marker.at - chrono::Duration::seconds(Zoom::Day.resolution_secs() * POINTS_PER_ZOOM as i64);
+        while self.alert_markers.front().is_some_and(|m| m.at < oldest) || self.alert_markers.len() >= MAX_ALERT_MARKERS {
+            self.alert_markers.pop_front();
+        }
+        self.alert_markers.push_back(marker);
+    }
+
+    /// Alerts fired and resolved that relate to `metric`, oldest first
+    pub fn alert_markers(&self, metric: ChartMetric) -> impl Iterator<Item = &AlertMarker> {
+        let sources = metric.alert_sources();
+        self.alert_markers
+            .iter()
+            .filter(move |m| sources.iter().any(|prefix| m.source.starts_with(prefix)))
     }
 
     pub fn record_memory(&mut self, value: f64) {
@@ -42,6 +665,7 @@ impl MetricsHistory {
             self.memory_history.pop_front();
         }
         self.memory_history.push_back(value);
+        self.memory_series.record(Local::now(), value);
     }
 
     pub fn record_disk_io(&mut self, read: f64, write: f64) {
@@ -54,6 +678,160 @@ impl MetricsHistory {
             self.disk_write_history.pop_front();
         }
         self.disk_write_history.push_back(write);
+
+        let now = Local::now();
+        self.disk_read_series.record(now, read);
+        self.disk_write_series.record(now, write);
+    }
+
+    pub fn record_power(&mut self, watts: f64) {
+        self.power_series.record(Local::now(), watts);
+    }
+
+    /// Slower of the API server probes, in milliseconds
+    pub fn record_api_latency(&mut self, ms: f64) {
+        self.api_latency_series.record(Local::now(), ms);
+    }
+
+    /// Health score, 0-100
+    pub fn record_health(&mut self, score: f64) {
+        self.health_series.record(Local::now(), score);
+    }
+
+    /// Root filesystem usage percent, only used for its trend
+    pub fn record_filesystems(&mut self, filesystems: &[FilesystemUsage]) {
+        let now = Local::now();
+        for fs in filesystems {
+            self.filesystem_series
+                .entry(fs.mount_point.clone())
+                .or_insert_with(MultiResolutionSeries::new)
+                .record(now, fs.used_percent());
+        }
+    }
+
+    pub fn record_computed(&mut self, name: &str, value: f64) {
+        self.computed_series
+            .entry(name.to_string())
+            .or_insert_with(MultiResolutionSeries::new)
+            .record(Local::now(), value);
+    }
+
+    /// A computed metric's samples at the given zoom level
+    pub fn computed(&self, name: &str, zoom: Zoom) -> Vec<Sample> {
+        self.computed_series.get(name).map(|series| series.samples(zoom)).unwrap_or_default()
+    }
+
+    pub fn record_process_rss(&mut self, name: &str, mb: f64) {
+        self.process_rss_series
+            .entry(name.to_string())
+            .or_insert_with(MultiResolutionSeries::new)
+            .record(Local::now(), mb);
+    }
+
+    /// A watched daemon's resident MB at the given zoom level
+    pub fn process_rss(&self, name: &str, zoom: Zoom) -> Vec<Sample> {
+        self.process_rss_series.get(name).map(|series| series.samples(zoom)).unwrap_or_default()
+    }
+
+    pub fn record_disk_usage(&mut self, percent: f64) {
+        self.disk_usage_series.record(Local::now(), percent);
+    }
+
+    /// Turn per-service line and error counts read since the previous call
+    /// into per-minute rates. The first call only starts the clock, as it
+    /// covers the journal backlog rather than an interval; services that
+    /// logged before but not now record zero, so silence shows up.
+    pub fn record_log_counts(&mut self, counts: &BTreeMap<String, (u64, u64, u64)>) {
+        let now = Local::now();
+        let Some(last) = self.last_log_counts.replace(now) else {
+            return;
+        };
+        let mins = (now - last).num_milliseconds() as f64 / 60_000.0;
+        if mins <= 0.0 {
+            return;
+        }
+        for service in counts.keys() {
+            self.log_rates.entry(service.clone()).or_default();
+        }
+        for (service, rate) in &mut self.log_rates {
+            let (lines, errors, _) = counts.get(service).copied().unwrap_or_default();
+            rate.push(lines as f64 / mins, errors as f64 / mins);
+            rate.push_errors(now, errors);
+        }
+
+        let oldest = now - chrono::Duration::minutes(2 * LEVEL_WINDOW_MINS);
+        while self.log_levels.front().is_some_and(|(at, _, _)| *at <= oldest) {
+            self.log_levels.pop_front();
+        }
+        let (errors, warnings) = counts.values().fold((0, 0), |(e, w), (_, errors, warnings)| (e + errors, w + warnings));
+        self.log_levels.push_back((now, errors, warnings));
+    }
+
+    /// When the last collection's errors and warnings were counted
+    pub fn log_levels_updated(&self) -> Option<DateTime<Local>> {
+        self.log_levels.back().map(|(at, _, _)| *at)
+    }
+
+    /// Per-minute errors and warnings for the footer; None until a
+    /// collection after the first has been counted
+    pub fn log_level_stats(&self) -> Option<LogLevelStats> {
+        if self.log_levels.is_empty() {
+            return None;
+        }
+        let now = Local::now();
+        let window = LEVEL_WINDOW_MINS as usize;
+        let mut errors = vec![0; window];
+        let mut warnings = vec![0; window];
+        let (mut previous_errors, mut previous_warnings) = (0, 0);
+        for (at, e, w) in &self.log_levels {
+            let age = (now - *at).num_minutes().max(0) as usize;
+            if age < window {
+                errors[window - 1 - age] += e;
+                warnings[window - 1 - age] += w;
+            } else {
+                previous_errors += e;
+                previous_warnings += w;
+            }
+        }
+        let trend = |current: u64, previous: u64| match current.cmp(&previous) {
+            std::cmp::Ordering::Greater => Trend::Rising,
+            std::cmp::Ordering::Less => Trend::Falling,
+            std::cmp::Ordering::Equal => Trend::Steady,
+        };
+        Some(LogLevelStats {
+            error_trend: trend(errors.iter().sum(), previous_errors),
+            warning_trend: trend(warnings.iter().sum(), previous_warnings),
+            errors,
+            warnings,
+        })
+    }
+
+    pub fn log_rates(&self) -> &BTreeMap<String, LogRate> {
+        &self.log_rates
+    }
+
+    /// Up to `limit` services with the most errors in the last
+    /// `NOISY_WINDOW_MINS`, most first
+    pub fn noisy_sources(&self, limit: usize) -> Vec<NoisySource> {
+        let window_start = Local::now() - chrono::Duration::minutes(NOISY_WINDOW_MINS);
+        let mut sources: Vec<NoisySource> = self
+            .log_rates
+            .iter()
+            .map(|(service, rate)| {
+                let (errors, previous) = rate.error_counts.iter().fold((0, 0), |(errors, previous), (at, n)| {
+                    if *at > window_start {
+                        (errors + n, previous)
+                    } else {
+                        (errors, previous + n)
+                    }
+                });
+                NoisySource { service: service.clone(), errors, previous }
+            })
+            .filter(|source| source.errors > 0)
+            .collect();
+        sources.sort_by(|a, b| b.errors.cmp(&a.errors).then_with(|| a.service.cmp(&b.service)));
+        sources.truncate(limit);
+        sources
     }
 
     pub fn record_network(&mut self, rx: u64, tx: u64) {
@@ -100,4 +878,57 @@ impl MetricsHistory {
     pub fn memory_sparkline_data(&self) -> Vec<u64> {
         self.memory_history.iter().map(|&v| v as u64).collect()
     }
+
+    /// Timestamped samples for the charts view at the given zoom level
+    pub fn series(&self, metric: ChartMetric, zoom: Zoom) -> Vec<Sample> {
+        let series = match metric {
+            ChartMetric::Cpu => &self.cpu_series,
+            ChartMetric::Memory => &self.memory_series,
+            ChartMetric::DiskRead => &self.disk_read_series,
+            ChartMetric::DiskWrite => &self.disk_write_series,
+            ChartMetric::Power => &self.power_series,
+            ChartMetric::ApiLatency => &self.api_latency_series,
+            ChartMetric::Health => &self.health_series,
+        };
+        series.samples(zoom)
+    }
+
+    /// Trend of a percentage metric and when it is predicted to reach
+    /// `threshold`; None until enough history has been recorded
+    pub fn forecast(&self, metric: TrendMetric, threshold: f64) -> Option<Forecast> {
+        let series = match metric {
+            TrendMetric::Cpu => &self.cpu_series,
+            TrendMetric::Memory => &self.memory_series,
+            TrendMetric::DiskUsage => &self.disk_usage_series,
+        };
+        Forecast::from_samples(&series.samples(metric.zoom()), threshold, metric.horizon_mins())
+    }
+
+    /// Forecast of when a mount point fills up, fitted over the longest
+    /// window with enough history so slow growth isn't lost in noise
+    pub fn time_to_full(&self, mount_point: &str) -> Option<TimeToFull> {
+        let series = self.filesystem_series.get(mount_point)?;
+        Zoom::ALL
+            .iter()
+            .rev()
+            .map(|zoom| series.samples(*zoom))
+            .find(|samples| samples.len() >= MIN_TREND_SAMPLES)
+            .and_then(|samples| TimeToFull::from_samples(&samples))
+    }
+
+    /// Growth of a daemon's resident memory over the last `window_hours`,
+    /// from the finest zoom level that spans them; None until the history
+    /// covers the window
+    pub fn memory_growth(&self, name: &str, window_hours: u64) -> Option<MemoryGrowth> {
+        let series = self.process_rss_series.get(name)?;
+        let window = chrono::Duration::hours(window_hours as i64);
+        let zoom = *Zoom::ALL
+            .iter()
+            .find(|zoom| zoom.resolution_secs() * POINTS_PER_ZOOM as i64 >= window.num_seconds())?;
+        let start = Local::now() - window;
+        // Including the bucket the window starts in
+        let resolution = chrono::Duration::seconds(zoom.resolution_secs());
+        let samples: Vec<Sample> = series.samples(zoom).into_iter().filter(|s| s.at + resolution > start).collect();
+        MemoryGrowth::from_samples(&samples, start, window)
+    }
 }
@@ -0,0 +1,175 @@
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::types::MigrationRecord;
+
+/// Finished migrations kept on disk, oldest dropped first
+const MAX_RECORDS: usize = 500;
+
+/// Finished and failed migrations between one source and target node
+#[derive(Debug, Clone)]
+pub struct MigrationPairStats {
+    pub source: String,
+    pub target: String,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub mean_duration: Option<chrono::Duration>,
+}
+
+impl MigrationPairStats {
+    /// 0.0 to 1.0
+    pub fn success_rate(&self) -> f64 {
+        self.succeeded as f64 / (self.succeeded + self.failed).max(1) as f64
+    }
+}
+
+/// A node whose most recent migrations, in or out, all failed
+#[derive(Debug, Clone)]
+pub struct NodeMigrationFailures {
+    pub node: String,
+    pub consecutive_failures: usize,
+    pub last_reason: Option<String>,
+}
+
+/// Migrations seen over time. KubeVirt garbage-collects finished migration
+/// objects, so finished ones are kept here (and on disk) to build success
+/// rates from more than what the cluster still remembers.
+pub struct MigrationHistory {
+    /// Finished migrations, oldest first
+    records: Vec<MigrationRecord>,
+    /// Migrations not yet finished as of the last collection
+    in_flight: Vec<MigrationRecord>,
+    path: PathBuf,
+}
+
+impl MigrationHistory {
+    /// Load persisted history, starting fresh if none exists or the file is unreadable
+    pub fn load(path: PathBuf) -> Self {
+        let records = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring corrupt migration history {:?}: {}", path, e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self { records, in_flight: Vec::new(), path }
+    }
+
+    /// Take in the migrations currently in the cluster. Newly finished ones
+    /// keep the transfer size last sampled while they ran.
+    pub fn record(&mut self, migrations: Vec<MigrationRecord>) {
+        let sampled: HashMap<String, u64> = self
+            .in_flight
+            .iter()
+            .filter_map(|m| Some((m.uid.clone(), m.transferred_bytes?)))
+            .collect();
+
+        let mut finished = false;
+        let mut in_flight = Vec::new();
+        for mut migration in migrations {
+            if migration.transferred_bytes.is_none() {
+                migration.transferred_bytes = sampled.get(&migration.uid).copied();
+            }
+            if !migration.is_finished() {
+                in_flight.push(migration);
+            } else if !self.records.iter().any(|r| r.uid == migration.uid) {
+                self.records.push(migration);
+                finished = true;
+            }
+        }
+        self.in_flight = in_flight;
+
+        if finished {
+            self.records.sort_by_key(|r| r.at());
+            let excess = self.records.len().saturating_sub(MAX_RECORDS);
+            self.records.drain(..excess);
+            if let Err(e) = self.save() {
+                tracing::warn!("Failed to persist migration history: {:#}", e);
+            }
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create migration history directory: {:?}", parent))?;
+        }
+        let contents = serde_json::to_string(&self.records).context("Failed to serialize migration history")?;
+        fs::write(&self.path, contents)
+            .with_context(|| format!("Failed to write migration history: {:?}", self.path))
+    }
+
+    /// Running migrations first, then finished ones newest first
+    pub fn recent(&self) -> impl Iterator<Item = &MigrationRecord> {
+        self.in_flight.iter().chain(self.records.iter().rev())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty() && self.in_flight.is_empty()
+    }
+
+    /// Success rate per source/target pair, least successful first
+    pub fn pair_stats(&self) -> Vec<MigrationPairStats> {
+        let mut pairs: BTreeMap<(String, String), (usize, usize, Vec<chrono::Duration>)> = BTreeMap::new();
+        for record in &self.records {
+            let (Some(source), Some(target)) = (&record.source_node, &record.target_node) else {
+                continue;
+            };
+            let (succeeded, failed, durations) = pairs.entry((source.clone(), target.clone())).or_default();
+            if record.succeeded() {
+                *succeeded += 1;
+                durations.extend(record.duration());
+            } else {
+                *failed += 1;
+            }
+        }
+
+        let mut stats: Vec<MigrationPairStats> = pairs
+            .into_iter()
+            .map(|((source, target), (succeeded, failed, durations))| MigrationPairStats {
+                source,
+                target,
+                succeeded,
+                failed,
+                mean_duration: (!durations.is_empty()).then(|| {
+                    durations.iter().fold(chrono::Duration::zero(), |sum, d| sum + *d) / durations.len() as i32
+                }),
+            })
+            .collect();
+        stats.sort_by(|a, b| a.success_rate().total_cmp(&b.success_rate()));
+        stats
+    }
+
+    /// Nodes whose last `threshold` or more finished migrations, as source
+    /// or target, all failed
+    pub fn failing_nodes(&self, threshold: usize) -> Vec<NodeMigrationFailures> {
+        let mut streaks: BTreeMap<&str, NodeMigrationFailures> = BTreeMap::new();
+        let mut ended: BTreeMap<&str, bool> = BTreeMap::new();
+        for record in self.records.iter().rev() {
+            let nodes = [record.source_node.as_deref(), record.target_node.as_deref()];
+            for node in nodes.into_iter().flatten() {
+                // Only the unbroken run of failures since the last success counts
+                let done = ended.entry(node).or_insert(false);
+                if *done {
+                    continue;
+                }
+                if record.succeeded() {
+                    *done = true;
+                    continue;
+                }
+                let streak = streaks.entry(node).or_insert_with(|| NodeMigrationFailures {
+                    node: node.to_string(),
+                    consecutive_failures: 0,
+                    last_reason: record.failure_reason.clone(),
+                });
+                streak.consecutive_failures += 1;
+            }
+        }
+        streaks
+            .into_values()
+            .filter(|s| s.consecutive_failures >= threshold)
+            .collect()
+    }
+}
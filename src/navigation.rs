@@ -0,0 +1,109 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::time::{Duration, Instant};
+
+/// Presses of the same key closer together than this are the terminal's
+/// autorepeat, i.e. the key is being held
+const REPEAT_GAP: Duration = Duration::from_millis(100);
+/// How long a key has to be held before moves get bigger
+const ACCELERATE_AFTER: Duration = Duration::from_millis(300);
+/// The step doubles this often while the key stays down...
+const DOUBLE_EVERY: Duration = Duration::from_millis(500);
+/// ...up to this many rows per repeat
+const MAX_STEP: usize = 16;
+/// Largest count taken in front of a move
+const MAX_COUNT: usize = 99_999;
+
+/// A move through a list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Up(usize),
+    Down(usize),
+    Top,
+    Bottom,
+    /// A 1-based row, from a count in front of `G` or `gg`
+    Row(usize),
+}
+
+impl Motion {
+    /// Where a cursor at `index` in a list of `len` rows ends up
+    pub fn apply(self, index: usize, len: usize) -> usize {
+        let last = len.saturating_sub(1);
+        match self {
+            Motion::Up(n) => index.saturating_sub(n),
+            Motion::Down(n) => index.saturating_add(n).min(last),
+            Motion::Top => 0,
+            Motion::Bottom => last,
+            Motion::Row(row) => row.saturating_sub(1).min(last),
+        }
+    }
+}
+
+/// A count or `g` typed in front of the next key
+#[derive(Debug, Default)]
+pub struct Pending {
+    count: Option<usize>,
+    g: bool,
+}
+
+/// Turns list keys into motions: ↑/↓ and k/j, taking bigger steps the
+/// longer they are held, a vim-style count in front (`10j`), and `gg`/`G`
+/// for the top and bottom
+#[derive(Debug, Default)]
+pub struct Navigator {
+    pending: Pending,
+    /// Key being held, when it was last repeated and since when it is down
+    held: Option<(KeyCode, Instant, Instant)>,
+}
+
+impl Navigator {
+    /// Take what was typed in front of the key being handled. It only
+    /// applies if that key is a list key; anything else drops it.
+    pub fn begin_key(&mut self) -> Pending {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// The motion for a list key, or None for anything else. Digits and a
+    /// first `g` are kept for the next key and also give None.
+    pub fn motion(&mut self, key: &KeyEvent, pending: Pending) -> Option<Motion> {
+        if key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            return None;
+        }
+        match key.code {
+            // A leading 0 isn't a count
+            KeyCode::Char(c @ '0'..='9') if c != '0' || pending.count.is_some() => {
+                let digit = c.to_digit(10).unwrap_or(0) as usize;
+                let count = pending.count.unwrap_or(0).saturating_mul(10).saturating_add(digit);
+                self.pending = Pending { count: Some(count.min(MAX_COUNT)), g: false };
+                None
+            }
+            KeyCode::Char('g') if pending.g => Some(pending.count.map_or(Motion::Top, Motion::Row)),
+            KeyCode::Char('g') => {
+                self.pending = Pending { count: pending.count, g: true };
+                None
+            }
+            KeyCode::Char('G') => Some(pending.count.map_or(Motion::Bottom, Motion::Row)),
+            KeyCode::Up | KeyCode::Char('k') => Some(Motion::Up(pending.count.unwrap_or_else(|| self.step(key.code)))),
+            KeyCode::Down | KeyCode::Char('j') => {
+                Some(Motion::Down(pending.count.unwrap_or_else(|| self.step(key.code))))
+            }
+            _ => None,
+        }
+    }
+
+    /// Rows to move for one press: 1, then doubling while the key is held
+    fn step(&mut self, code: KeyCode) -> usize {
+        let now = Instant::now();
+        let since = match self.held {
+            Some((held, last, since)) if held == code && now.duration_since(last) < REPEAT_GAP => since,
+            _ => now,
+        };
+        self.held = Some((code, now, since));
+
+        let held_for = now.duration_since(since);
+        if held_for < ACCELERATE_AFTER {
+            return 1;
+        }
+        let doublings = ((held_for - ACCELERATE_AFTER).as_millis() / DOUBLE_EVERY.as_millis()) as u32 + 1;
+        2usize.saturating_pow(doublings).min(MAX_STEP)
+    }
+}
@@ -0,0 +1,342 @@
+use crate::config::UpgradeConfig;
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+const PROFILES_DIR: &str = "/nix/var/nix/profiles";
+/// Profile nixos-rebuild registers new generations in
+const SYSTEM_PROFILE: &str = "/nix/var/nix/profiles/system";
+const CURRENT_SYSTEM: &str = "/run/current-system";
+const BOOTED_SYSTEM: &str = "/run/booted-system";
+/// Output lines kept for the panel
+const MAX_OUTPUT_LINES: usize = 200;
+
+/// One NixOS system generation (system-42-link)
+#[derive(Debug, Clone)]
+pub struct Generation {
+    pub number: u64,
+    pub created: Option<DateTime<Local>>,
+    pub store_path: PathBuf,
+    /// Size of everything it references in the store, shared paths
+    /// included; None until `nix path-info` answered
+    pub closure_bytes: Option<u64>,
+    /// The profile points at it: the boot default
+    pub default: bool,
+    pub current: bool,
+    pub booted: bool,
+}
+
+impl Generation {
+    /// The running, booted or default system can't be deleted
+    pub fn in_use(&self) -> bool {
+        self.default || self.current || self.booted
+    }
+}
+
+/// Something the generations panel runs after confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NixStoreOp {
+    CollectGarbage,
+    DeleteGeneration(u64),
+}
+
+impl NixStoreOp {
+    /// Full command line: `gc_command` with the retention for garbage
+    /// collection, `nix_env_command` for a generation
+    pub fn command(&self, config: &UpgradeConfig) -> Vec<String> {
+        match self {
+            NixStoreOp::CollectGarbage if config.gc_command.is_empty() => Vec::new(),
+            NixStoreOp::CollectGarbage => {
+                let mut args = config.gc_command.clone();
+                if !config.gc_retention.is_empty() {
+                    args.push("--delete-older-than".to_string());
+                    args.push(config.gc_retention.clone());
+                }
+                args
+            }
+            NixStoreOp::DeleteGeneration(_) if config.nix_env_command.is_empty() => Vec::new(),
+            NixStoreOp::DeleteGeneration(number) => {
+                let mut args = config.nix_env_command.clone();
+                args.extend(["--profile", SYSTEM_PROFILE, "--delete-generations"].map(String::from));
+                args.push(number.to_string());
+                args
+            }
+        }
+    }
+
+    pub fn describe(&self, config: &UpgradeConfig) -> String {
+        match self {
+            NixStoreOp::CollectGarbage if config.gc_retention.is_empty() => {
+                "Collect garbage, keeping every generation".to_string()
+            }
+            NixStoreOp::CollectGarbage => format!(
+                "Delete generations older than {} and collect garbage",
+                config.gc_retention
+            ),
+            NixStoreOp::DeleteGeneration(number) => format!("Delete system generation {}", number),
+        }
+    }
+}
+
+/// System generations, newest first. Sizes are filled in separately by
+/// `closure_sizes` since asking nix for them takes a moment.
+pub fn list_generations() -> std::io::Result<Vec<Generation>> {
+    let resolve = |path: &str| fs::canonicalize(path).ok();
+    let (default, current, booted) = (resolve(SYSTEM_PROFILE), resolve(CURRENT_SYSTEM), resolve(BOOTED_SYSTEM));
+
+    let mut generations = Vec::new();
+    for entry in fs::read_dir(PROFILES_DIR)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(number) = name
+            .to_str()
+            .and_then(|n| n.strip_prefix("system-"))
+            .and_then(|n| n.strip_suffix("-link"))
+            .and_then(|n| n.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        let Ok(store_path) = fs::canonicalize(entry.path()) else {
+            continue;
+        };
+        let created = fs::symlink_metadata(entry.path())
+            .and_then(|meta| meta.modified())
+            .ok()
+            .map(DateTime::<Local>::from);
+        generations.push(Generation {
+            number,
+            created,
+            default: default.as_ref() == Some(&store_path),
+            current: current.as_ref() == Some(&store_path),
+            booted: booted.as_ref() == Some(&store_path),
+            store_path,
+            closure_bytes: None,
+        });
+    }
+    generations.sort_by_key(|g| std::cmp::Reverse(g.number));
+    Ok(generations)
+}
+
+/// Closure size of each store path, from one `nix path-info -S` call
+pub async fn closure_sizes(paths: &[PathBuf], timeout: Duration) -> Result<HashMap<PathBuf, u64>, String> {
+    if paths.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let output = Command::new("nix")
+        .args(["--extra-experimental-features", "nix-command", "path-info", "-S", "--"])
+        .args(paths)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = match tokio::time::timeout(timeout, output).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return Err(format!("Failed to run nix path-info: {}", e)),
+        Err(_) => return Err("nix path-info timed out".to_string()),
+    };
+    if !output.status.success() {
+        return Err(format!("nix path-info failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let path = PathBuf::from(fields.next()?);
+            let size = fields.next()?.parse().ok()?;
+            Some((path, size))
+        })
+        .collect())
+}
+
+enum RunEvent {
+    Line(String),
+    Finished(Result<String, String>),
+}
+
+/// A garbage collection or generation deletion running in the background
+pub struct NixStoreRun {
+    pub op: NixStoreOp,
+    pub command: String,
+    pub output: Vec<String>,
+    pub outcome: Option<Result<String, String>>,
+    rx: mpsc::UnboundedReceiver<RunEvent>,
+}
+
+impl NixStoreRun {
+    pub fn start(config: &UpgradeConfig, op: NixStoreOp) -> Result<Self, String> {
+        let args = op.command(config);
+        let Some((program, rest)) = args.split_first() else {
+            return Err(match op {
+                NixStoreOp::CollectGarbage => "upgrade.gc_command is empty".to_string(),
+                NixStoreOp::DeleteGeneration(_) => "upgrade.nix_env_command is empty".to_string(),
+            });
+        };
+        let mut child = Command::new(program)
+            .args(rest)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let readers: Vec<_> = [
+            child.stdout.take().map(|out| forward_lines(BufReader::new(out), tx.clone())),
+            child.stderr.take().map(|err| forward_lines(BufReader::new(err), tx.clone())),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let name = program.clone();
+        tokio::spawn(async move {
+            for reader in readers {
+                let _ = reader.await;
+            }
+            let outcome = match child.wait().await {
+                Ok(status) if status.success() => Ok(String::new()),
+                Ok(status) => Err(format!("{} exited with {}", name, status)),
+                Err(e) => Err(format!("Failed to wait for {}: {}", name, e)),
+            };
+            let _ = tx.send(RunEvent::Finished(outcome));
+        });
+
+        Ok(Self {
+            op,
+            command: args.join(" "),
+            output: Vec::new(),
+            outcome: None,
+            rx,
+        })
+    }
+
+    /// Take in new output, returning whether anything arrived. A success's
+    /// summary is nix's own last line ("1234 store paths deleted, 5.2 GiB
+    /// freed") when there is one.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(event) = self.rx.try_recv() {
+            changed = true;
+            match event {
+                RunEvent::Line(line) => {
+                    if self.output.len() >= MAX_OUTPUT_LINES {
+                        self.output.remove(0);
+                    }
+                    self.output.push(line);
+                }
+                RunEvent::Finished(Ok(_)) => {
+                    let summary = self
+                        .output
+                        .iter()
+                        .rev()
+                        .find(|line| line.contains("freed"))
+                        .cloned()
+                        .unwrap_or_else(|| "done".to_string());
+                    self.outcome = Some(Ok(summary));
+                }
+                RunEvent::Finished(Err(reason)) => self.outcome = Some(Err(reason)),
+            }
+        }
+        changed
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+}
+
+fn forward_lines<R>(reader: BufReader<R>, tx: mpsc::UnboundedSender<RunEvent>) -> tokio::task::JoinHandle<()>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut lines = reader.lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tx.send(RunEvent::Line(line)).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// The generations panel opened with `G`: generations with their sizes,
+/// the operation awaiting confirmation and the one running
+pub struct NixStorePanel {
+    pub generations: Vec<Generation>,
+    pub selected: usize,
+    /// Why the list or sizes couldn't be read
+    pub error: Option<String>,
+    pub confirm: Option<NixStoreOp>,
+    pub run: Option<NixStoreRun>,
+    /// Panel dismissed while an operation is still running
+    pub hidden: bool,
+}
+
+impl NixStorePanel {
+    pub async fn load(timeout: Duration) -> Self {
+        let mut panel = Self {
+            generations: Vec::new(),
+            selected: 0,
+            error: None,
+            confirm: None,
+            run: None,
+            hidden: false,
+        };
+        panel.reload(timeout).await;
+        panel
+    }
+
+    /// Read the generations again, e.g. after deleting some
+    pub async fn reload(&mut self, timeout: Duration) {
+        self.error = None;
+        if !Path::new(PROFILES_DIR).exists() {
+            self.generations = mock_generations();
+            return;
+        }
+        match list_generations() {
+            Ok(generations) => self.generations = generations,
+            Err(e) => {
+                self.error = Some(format!("Failed to read {}: {}", PROFILES_DIR, e));
+                self.generations.clear();
+            }
+        }
+        let paths: Vec<PathBuf> = self.generations.iter().map(|g| g.store_path.clone()).collect();
+        match closure_sizes(&paths, timeout).await {
+            Ok(sizes) => {
+                for generation in &mut self.generations {
+                    generation.closure_bytes = sizes.get(&generation.store_path).copied();
+                }
+            }
+            Err(e) => self.error = Some(e),
+        }
+        self.selected = self.selected.min(self.generations.len().saturating_sub(1));
+    }
+
+    pub fn selected_generation(&self) -> Option<&Generation> {
+        self.generations.get(self.selected)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.run.as_ref().is_some_and(|run| !run.is_finished())
+    }
+}
+
+fn mock_generations() -> Vec<Generation> {
+    let gib = 1024 * 1024 * 1024;
+    [(57, 0, 6 * gib + gib / 3, true), (56, 3, 6 * gib + gib / 5, false), (55, 12, 5 * gib + gib / 2, false), (54, 40, 5 * gib, false)]
+        .into_iter()
+        .map(|(number, days_ago, bytes, newest)| Generation {
+            number,
+            created: Some(Local::now() - chrono::Duration::days(days_ago)),
+            store_path: PathBuf::from(format!("/nix/store/mock-nixos-system-hv01-{}", number)),
+            closure_bytes: Some(bytes),
+            default: newest,
+            current: newest,
+            booted: newest,
+        })
+        .collect()
+}
@@ -0,0 +1,100 @@
+use anyhow::{Context, Result};
+
+use crate::config::Config;
+
+/// The NixOS options for `services.hypervisor-tui.settings`, printed by
+/// `--generate-nixos-module`. Every key of the default config becomes a
+/// typed option defaulting to null, so only what a system sets ends up in
+/// the JSON the module writes; keys without a default (optional settings,
+/// map entries) are still accepted as free-form values.
+pub fn generate_module() -> Result<String> {
+    let defaults = toml::Table::try_from(Config::default()).context("Failed to serialize the default config")?;
+
+    let mut out = vec![
+        "# Generated by `hypervisor-tui --generate-nixos-module`; do not edit.".to_string(),
+        "# Typed options for `services.hypervisor-tui.settings`, mirroring config.toml.".to_string(),
+        "{ lib, pkgs, ... }:".to_string(),
+        String::new(),
+        "let".to_string(),
+        "  inherit (lib) mkOption types;".to_string(),
+        "  json = (pkgs.formats.json { }).type;".to_string(),
+        "  section = description: options: mkOption {".to_string(),
+        "    type = types.submodule { freeformType = json; inherit options; };".to_string(),
+        "    default = { };".to_string(),
+        "    inherit description;".to_string(),
+        "  };".to_string(),
+        "  setting = type: default: mkOption {".to_string(),
+        "    type = types.nullOr type;".to_string(),
+        "    default = null;".to_string(),
+        "    description = \"Unset uses the built-in default: `${default}`\";".to_string(),
+        "  };".to_string(),
+        "in".to_string(),
+        "{".to_string(),
+        "  options.services.hypervisor-tui.settings = section \"hypervisor-tui settings, written to /etc/hypervisor-tui/config.json\" {".to_string(),
+    ];
+    options(&defaults, "", 2, &mut out);
+    out.push("  };".to_string());
+    out.push("}".to_string());
+    Ok(out.join("\n") + "\n")
+}
+
+/// One option per key of `table`, nested sections for its tables
+fn options(table: &toml::Table, prefix: &str, depth: usize, out: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    for (key, value) in table {
+        let dotted = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            // An empty table is a map, such as `memory_leaks.limits_mb`
+            toml::Value::Table(section) if !section.is_empty() => {
+                out.push(format!("{}{} = section {} {{", indent, attr(key), string(&format!("`[{}]` settings", dotted))));
+                options(section, &dotted, depth + 1, out);
+                out.push(format!("{}}};", indent));
+            }
+            _ => {
+                let default = serde_json::to_string(value).unwrap_or_default();
+                out.push(format!("{}{} = setting {} {};", indent, attr(key), nix_type(value), string(&default)));
+            }
+        }
+    }
+}
+
+fn nix_type(value: &toml::Value) -> String {
+    match value {
+        toml::Value::Boolean(_) => "types.bool".to_string(),
+        toml::Value::Integer(_) => "types.int".to_string(),
+        toml::Value::Float(_) => "types.float".to_string(),
+        toml::Value::String(_) => "types.str".to_string(),
+        toml::Value::Array(items) => match items.first() {
+            Some(first @ (toml::Value::Boolean(_) | toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::String(_)))
+                if items.iter().all(|item| item.same_type(first)) =>
+            {
+                format!("(types.listOf {})", nix_type(first))
+            }
+            _ => "(types.listOf json)".to_string(),
+        },
+        toml::Value::Table(_) => "(types.attrsOf json)".to_string(),
+        toml::Value::Datetime(_) => "types.str".to_string(),
+    }
+}
+
+/// Words that can't be bare attribute names
+const KEYWORDS: [&str; 10] = ["assert", "else", "if", "in", "inherit", "let", "or", "rec", "then", "with"];
+
+/// A key as a Nix attribute name, quoted unless it is an identifier
+fn attr(key: &str) -> String {
+    let mut chars = key.chars();
+    let identifier = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '\'')
+        && !KEYWORDS.contains(&key);
+    if identifier {
+        key.to_string()
+    } else {
+        string(key)
+    }
+}
+
+/// A Nix string literal
+fn string(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"").replace("${", "\\${");
+    format!("\"{}\"", escaped)
+}
@@ -0,0 +1,128 @@
+use anyhow::{bail, Result};
+use chrono::Local;
+use crate::alerts::{Alert, AlertLevel};
+use crate::config::AlertmanagerConfig;
+use crate::secrets::Secrets;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use super::{dedup_key, hostname, parse_levels, post_json, read_secret, spawn_delivery, DeliveryFailure};
+
+/// Prometheus Alertmanager v2 API. Alertmanager resolves alerts that stop
+/// being re-posted, so firing alerts are re-sent periodically and cleared
+/// alerts are posted once more with an end time.
+pub struct AlertmanagerNotifier {
+    url: String,
+    bearer_token: Option<String>,
+    levels: Vec<AlertLevel>,
+    labels: BTreeMap<String, String>,
+    resend_interval: Duration,
+    last_resend: Instant,
+}
+
+impl AlertmanagerNotifier {
+    pub fn new(config: &AlertmanagerConfig, secrets: &Secrets) -> Result<Self> {
+        let url = read_secret(secrets, &config.url_secret, &Some(config.url.clone()), &None, "Alertmanager URL")?
+            .unwrap_or_default();
+        if url.is_empty() {
+            bail!("notifications.alertmanager.url is not set");
+        }
+        Ok(Self {
+            url: format!("{}/api/v2/alerts", url.trim_end_matches('/')),
+            bearer_token: read_secret(
+                secrets,
+                &config.bearer_token_secret,
+                &None,
+                &config.bearer_token_file,
+                "bearer token",
+            )?,
+            levels: parse_levels(&config.levels)?,
+            labels: config.labels.clone(),
+            resend_interval: Duration::from_secs(config.resend_interval_secs.max(1)),
+            last_resend: Instant::now(),
+        })
+    }
+
+    pub fn fire(&mut self, alert: &Alert, failures: &mpsc::UnboundedSender<DeliveryFailure>) {
+        if self.levels.contains(&alert.level) {
+            self.send(vec![self.payload(alert, false)], failures);
+        }
+    }
+
+    pub fn resolve(&mut self, alert: &Alert, failures: &mpsc::UnboundedSender<DeliveryFailure>) {
+        if self.levels.contains(&alert.level) {
+            self.send(vec![self.payload(alert, true)], failures);
+        }
+    }
+
+    /// Re-post every firing alert once the resend interval has elapsed
+    pub fn resend(&mut self, active: &[&Alert], failures: &mpsc::UnboundedSender<DeliveryFailure>) {
+        if self.last_resend.elapsed() < self.resend_interval {
+            return;
+        }
+        self.last_resend = Instant::now();
+        let alerts: Vec<Value> = active
+            .iter()
+            .filter(|a| self.levels.contains(&a.level))
+            .map(|a| self.payload(a, false))
+            .collect();
+        if !alerts.is_empty() {
+            self.send(alerts, failures);
+        }
+    }
+
+    fn payload(&self, alert: &Alert, resolved: bool) -> Value {
+        let mut labels: Map<String, Value> = self
+            .labels
+            .iter()
+            .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+            .collect();
+        labels.insert("alertname".to_string(), dedup_key(alert).into());
+        labels.insert("severity".to_string(), alert.level.as_str().to_lowercase().into());
+        labels.insert("category".to_string(), alert.category.as_str().into());
+        labels.insert("instance".to_string(), hostname().into());
+        let optional = [
+            ("node", &alert.metadata.node_name),
+            ("namespace", &alert.metadata.namespace),
+            ("pod", &alert.metadata.pod_name),
+            ("vm", &alert.metadata.vm_name),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                labels.insert(key.to_string(), value.clone().into());
+            }
+        }
+
+        let mut payload = json!({
+            "labels": labels,
+            "annotations": {
+                "summary": alert.title,
+                "description": alert.message,
+            },
+            "startsAt": alert.triggered_at.to_rfc3339(),
+        });
+        // The annotations Prometheus alerting rules use, so receivers link them
+        if let Some(ref url) = alert.metadata.runbook_url {
+            payload["annotations"]["runbook_url"] = url.clone().into();
+        }
+        if let Some(ref remediation) = alert.metadata.remediation {
+            payload["annotations"]["remediation"] = remediation.clone().into();
+        }
+        if resolved {
+            let ended = alert.resolved_at.unwrap_or_else(Local::now);
+            payload["endsAt"] = ended.to_rfc3339().into();
+        }
+        payload
+    }
+
+    fn send(&self, alerts: Vec<Value>, failures: &mpsc::UnboundedSender<DeliveryFailure>) {
+        let url = self.url.clone();
+        let token = self.bearer_token.clone();
+        let body = Value::Array(alerts);
+        spawn_delivery("Alertmanager", failures, async move {
+            post_json(&url, token.as_deref(), &body).await
+        });
+    }
+}
@@ -0,0 +1,155 @@
+use anyhow::{bail, Context, Result};
+use crate::alerts::{Alert, AlertLevel};
+use crate::config::EmailConfig;
+use crate::secrets::Secrets;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use super::{hostname, read_secret, DeliveryFailure};
+
+/// SMTP delivery: Critical/Error alerts immediately, Warning alerts as a
+/// periodic digest
+pub struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    critical_recipients: Vec<Mailbox>,
+    error_recipients: Vec<Mailbox>,
+    warning_recipients: Vec<Mailbox>,
+    digest_interval: Duration,
+    pending_digest: Vec<Alert>,
+    last_digest: Instant,
+}
+
+impl EmailNotifier {
+    pub fn new(config: &EmailConfig, secrets: &Secrets) -> Result<Self> {
+        if config.smtp_server.is_empty() {
+            bail!("notifications.email.smtp_server is not set");
+        }
+
+        let mut builder = match config.security.as_str() {
+            "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_server)?,
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_server)?,
+            "none" => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_server),
+            other => bail!("Unknown SMTP security mode: {}", other),
+        }
+        .port(config.smtp_port);
+
+        if let Some(ref username) = config.username {
+            let password = read_secret(
+                secrets,
+                &config.password_secret,
+                &config.password,
+                &config.password_file,
+                "SMTP password",
+            )?
+            .unwrap_or_default();
+            builder = builder.credentials(Credentials::new(username.clone(), password));
+        }
+
+        let parse = |addrs: &[String]| -> Result<Vec<Mailbox>> {
+            addrs
+                .iter()
+                .map(|a| a.parse().with_context(|| format!("Invalid email address: {}", a)))
+                .collect()
+        };
+
+        Ok(Self {
+            transport: builder.build(),
+            from: config
+                .from
+                .parse()
+                .with_context(|| format!("Invalid from address: {}", config.from))?,
+            critical_recipients: parse(&config.critical_recipients)?,
+            error_recipients: parse(&config.error_recipients)?,
+            warning_recipients: parse(&config.warning_recipients)?,
+            digest_interval: Duration::from_secs(config.digest_interval_secs.max(1)),
+            pending_digest: Vec::new(),
+            last_digest: Instant::now(),
+        })
+    }
+
+    pub fn notify(&mut self, alert: &Alert, failures: &mpsc::UnboundedSender<DeliveryFailure>) {
+        let recipients = match alert.level {
+            AlertLevel::Critical => &self.critical_recipients,
+            AlertLevel::Error => &self.error_recipients,
+            AlertLevel::Warning => {
+                if !self.warning_recipients.is_empty() {
+                    self.pending_digest.push(alert.clone());
+                }
+                return;
+            }
+            AlertLevel::Info => return,
+        };
+
+        let subject = format!("[{}] {}", alert.level.as_str(), alert.title);
+        self.send(recipients.clone(), subject, format_alert(alert), failures);
+    }
+
+    /// Send the Warning digest once the digest interval has elapsed
+    pub fn flush_digest(&mut self, failures: &mpsc::UnboundedSender<DeliveryFailure>) {
+        if self.last_digest.elapsed() < self.digest_interval {
+            return;
+        }
+        self.last_digest = Instant::now();
+        if self.pending_digest.is_empty() {
+            return;
+        }
+
+        let alerts = std::mem::take(&mut self.pending_digest);
+        let subject = format!("[WARNING] {} alert(s) on {}", alerts.len(), hostname());
+        let body = alerts.iter().map(format_alert).collect::<Vec<_>>().join("\n\n");
+        self.send(self.warning_recipients.clone(), subject, body, failures);
+    }
+
+    fn send(
+        &self,
+        recipients: Vec<Mailbox>,
+        subject: String,
+        body: String,
+        failures: &mpsc::UnboundedSender<DeliveryFailure>,
+    ) {
+        if recipients.is_empty() {
+            return;
+        }
+
+        let mut builder = Message::builder().from(self.from.clone()).subject(subject);
+        for recipient in recipients {
+            builder = builder.to(recipient);
+        }
+
+        let transport = self.transport.clone();
+        let failures = failures.clone();
+        tokio::spawn(async move {
+            let result = match builder.body(body) {
+                Ok(message) => transport.send(message).await.map(|_| ()).map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(error) = result {
+                tracing::warn!("Email notification failed: {}", error);
+                let _ = failures.send(DeliveryFailure { channel: "Email", error });
+            }
+        });
+    }
+}
+
+fn format_alert(alert: &Alert) -> String {
+    let mut text = format!(
+        "{} [{}] {}\n{}\nSource: {}\nHost: {}",
+        alert.triggered_at.format("%Y-%m-%d %H:%M:%S"),
+        alert.level.as_str(),
+        alert.title,
+        alert.message,
+        alert.metadata.source,
+        hostname()
+    );
+    if let Some(ref remediation) = alert.metadata.remediation {
+        text.push_str(&format!("\nFix: {}", remediation));
+    }
+    if let Some(ref url) = alert.metadata.runbook_url {
+        text.push_str(&format!("\nRunbook: {}", url));
+    }
+    text
+}
@@ -0,0 +1,255 @@
+mod alertmanager;
+mod email;
+mod pagerduty;
+mod routing;
+
+pub use alertmanager::AlertmanagerNotifier;
+pub use email::EmailNotifier;
+pub use pagerduty::PagerDutyNotifier;
+pub use routing::{Channel, Router};
+
+use anyhow::{bail, Context, Result};
+use crate::alerts::{Alert, AlertCategory, AlertLevel};
+use crate::config::NotificationsConfig;
+use crate::secrets::Secrets;
+use std::collections::HashMap;
+use std::fs;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+/// A delivery failure reported back by a notification channel
+pub struct DeliveryFailure {
+    pub channel: &'static str,
+    pub error: String,
+}
+
+/// Hands newly fired and resolved alerts to the delivery channels their
+/// route picks. Deliveries run in the background; failures are collected
+/// and turned into Info alerts on the next call to `process`.
+pub struct Notifier {
+    email: Option<EmailNotifier>,
+    pagerduty: Option<PagerDutyNotifier>,
+    alertmanager: Option<AlertmanagerNotifier>,
+    router: Router,
+    /// Alerts already dispatched and the channels they went to, kept so
+    /// their resolution goes to the same ones
+    notified: HashMap<String, (Alert, Vec<Channel>)>,
+    failures_tx: mpsc::UnboundedSender<DeliveryFailure>,
+    failures_rx: mpsc::UnboundedReceiver<DeliveryFailure>,
+}
+
+impl Notifier {
+    pub fn from_config(config: &NotificationsConfig, secrets: &Secrets) -> Self {
+        let email = enabled_channel("Email", config.email.enabled, || EmailNotifier::new(&config.email, secrets));
+        let pagerduty = enabled_channel("PagerDuty", config.pagerduty.enabled, || {
+            PagerDutyNotifier::new(&config.pagerduty, secrets)
+        });
+        let alertmanager = enabled_channel("Alertmanager", config.alertmanager.enabled, || {
+            AlertmanagerNotifier::new(&config.alertmanager, secrets)
+        });
+
+        let (failures_tx, failures_rx) = mpsc::unbounded_channel();
+        Self {
+            email,
+            pagerduty,
+            alertmanager,
+            router: Router::new(&config.routes),
+            notified: HashMap::new(),
+            failures_tx,
+            failures_rx,
+        }
+    }
+
+    /// Dispatch alerts not seen before, send resolutions for alerts that
+    /// went away, and return alerts describing any deliveries that failed
+    /// since the last call
+    pub fn process(&mut self, active: &[&Alert]) -> Vec<Alert> {
+        for alert in active {
+            if self.notified.contains_key(&alert.id) {
+                continue;
+            }
+            let channels = self.router.channels(alert);
+            self.notified.insert(alert.id.clone(), ((*alert).clone(), channels.clone()));
+            // Info alerts (including our own delivery failures) are never sent
+            if alert.level == AlertLevel::Info {
+                continue;
+            }
+            if let Some(email) = self.email.as_mut().filter(|_| channels.contains(&Channel::Email)) {
+                email.notify(alert, &self.failures_tx);
+            }
+            if let Some(pagerduty) = self.pagerduty.as_ref().filter(|_| channels.contains(&Channel::PagerDuty)) {
+                pagerduty.trigger(alert, &self.failures_tx);
+            }
+            if let Some(alertmanager) = self.alertmanager.as_mut().filter(|_| channels.contains(&Channel::Alertmanager)) {
+                alertmanager.fire(alert, &self.failures_tx);
+            }
+        }
+
+        let gone: Vec<String> = self
+            .notified
+            .keys()
+            .filter(|id| !active.iter().any(|a| &&a.id == id))
+            .cloned()
+            .collect();
+        for id in gone {
+            let Some((alert, channels)) = self.notified.remove(&id) else {
+                continue;
+            };
+            // An alert re-raised at another level shares its dedup key, so
+            // resolving the old one would close the new incident
+            if alert.level == AlertLevel::Info
+                || active.iter().any(|a| a.metadata.source == alert.metadata.source)
+            {
+                continue;
+            }
+            if let Some(pagerduty) = self.pagerduty.as_ref().filter(|_| channels.contains(&Channel::PagerDuty)) {
+                pagerduty.resolve(&alert, &self.failures_tx);
+            }
+            if let Some(alertmanager) = self.alertmanager.as_mut().filter(|_| channels.contains(&Channel::Alertmanager)) {
+                alertmanager.resolve(&alert, &self.failures_tx);
+            }
+        }
+
+        if let Some(email) = self.email.as_mut() {
+            email.flush_digest(&self.failures_tx);
+        }
+        if let Some(alertmanager) = self.alertmanager.as_mut() {
+            let routed: Vec<&Alert> = active
+                .iter()
+                .copied()
+                .filter(|alert| {
+                    self.notified
+                        .get(&alert.id)
+                        .is_some_and(|(_, channels)| channels.contains(&Channel::Alertmanager))
+                })
+                .collect();
+            alertmanager.resend(&routed, &self.failures_tx);
+        }
+
+        let mut failures = Vec::new();
+        while let Ok(failure) = self.failures_rx.try_recv() {
+            failures.push(Alert::new(
+                AlertLevel::Info,
+                AlertCategory::Service,
+                format!("{} Delivery Failed", failure.channel),
+                failure.error,
+                format!("notify-{}", failure.channel.to_lowercase()),
+            ));
+        }
+        failures
+    }
+}
+
+/// Build a channel if it is enabled, logging why it was disabled on error
+fn enabled_channel<T>(name: &str, enabled: bool, build: impl FnOnce() -> Result<T>) -> Option<T> {
+    if !enabled {
+        return None;
+    }
+    match build() {
+        Ok(channel) => Some(channel),
+        Err(e) => {
+            tracing::warn!("{} notifications disabled: {:#}", name, e);
+            None
+        }
+    }
+}
+
+/// Parse configured level names ("critical", "error", "warning")
+fn parse_levels(levels: &[String]) -> Result<Vec<AlertLevel>> {
+    levels
+        .iter()
+        .map(|level| match level.to_lowercase().as_str() {
+            "critical" => Ok(AlertLevel::Critical),
+            "error" => Ok(AlertLevel::Error),
+            "warning" => Ok(AlertLevel::Warning),
+            other => bail!("Unknown alert level: {}", other),
+        })
+        .collect()
+}
+
+/// Read a secret from a file, falling back to an inline value
+/// A credential from `[secrets]` by name, else from a file, else inline
+pub(crate) fn read_secret(
+    secrets: &Secrets,
+    name: &Option<String>,
+    inline: &Option<String>,
+    file: &Option<String>,
+    what: &str,
+) -> Result<Option<String>> {
+    if let Some(name) = name {
+        return secrets.get(name).map(Some).with_context(|| format!("Failed to look up the {}", what));
+    }
+    match (inline, file) {
+        (_, Some(path)) => Ok(Some(
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {} file: {}", what, path))?
+                .trim()
+                .to_string(),
+        )),
+        (Some(value), None) => Ok(Some(value.clone())),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Stable identity of an alert across re-fires, used as the PagerDuty
+/// dedup key and the Alertmanager alertname
+fn dedup_key(alert: &Alert) -> String {
+    format!("hypervisor-tui/{}/{}", hostname(), alert.metadata.source)
+}
+
+/// POST a JSON body with curl. The request is passed as a curl config on
+/// stdin so routing keys and tokens don't show up in the process list.
+pub(crate) async fn post_json(url: &str, bearer_token: Option<&str>, body: &serde_json::Value) -> Result<()> {
+    let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut config = format!(
+        "url = {}\nrequest = \"POST\"\nheader = \"Content-Type: application/json\"\ndata-binary = {}\n",
+        quote(url),
+        quote(&body.to_string())
+    );
+    if let Some(token) = bearer_token {
+        config.push_str(&format!("header = {}\n", quote(&format!("Authorization: Bearer {}", token))));
+    }
+
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail-with-body", "--max-time", "10", "--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run curl")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(config.as_bytes()).await?;
+    }
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        bail!("{} {}", stderr.trim(), stdout.trim());
+    }
+    Ok(())
+}
+
+/// Run a delivery in the background, reporting failure on the channel
+fn spawn_delivery(
+    channel: &'static str,
+    failures: &mpsc::UnboundedSender<DeliveryFailure>,
+    delivery: impl std::future::Future<Output = Result<()>> + Send + 'static,
+) {
+    let failures = failures.clone();
+    tokio::spawn(async move {
+        if let Err(e) = delivery.await {
+            let error = format!("{:#}", e);
+            tracing::warn!("{} notification failed: {}", channel, error);
+            let _ = failures.send(DeliveryFailure { channel, error });
+        }
+    });
+}
+
+pub(crate) fn hostname() -> String {
+    fs::read_to_string("/proc/sys/kernel/hostname")
+        .map(|h| h.trim().to_string())
+        .unwrap_or_else(|_| "unknown".to_string())
+}
@@ -0,0 +1,92 @@
+use anyhow::{bail, Result};
+use crate::alerts::{Alert, AlertLevel};
+use crate::config::PagerDutyConfig;
+use crate::secrets::Secrets;
+use serde_json::json;
+use tokio::sync::mpsc;
+
+use super::{dedup_key, hostname, parse_levels, post_json, read_secret, spawn_delivery, DeliveryFailure};
+
+/// PagerDuty Events API v2: alerts at the configured levels trigger an
+/// incident keyed by their source, and resolve it when they clear
+pub struct PagerDutyNotifier {
+    url: String,
+    routing_key: String,
+    levels: Vec<AlertLevel>,
+}
+
+impl PagerDutyNotifier {
+    pub fn new(config: &PagerDutyConfig, secrets: &Secrets) -> Result<Self> {
+        let routing_key = read_secret(
+            secrets,
+            &config.routing_key_secret,
+            &config.routing_key,
+            &config.routing_key_file,
+            "routing key",
+        )?;
+        let Some(routing_key) = routing_key else {
+            bail!("notifications.pagerduty.routing_key is not set");
+        };
+        Ok(Self {
+            url: config.url.clone(),
+            routing_key,
+            levels: parse_levels(&config.levels)?,
+        })
+    }
+
+    pub fn trigger(&self, alert: &Alert, failures: &mpsc::UnboundedSender<DeliveryFailure>) {
+        if !self.levels.contains(&alert.level) {
+            return;
+        }
+        let severity = match alert.level {
+            AlertLevel::Critical => "critical",
+            AlertLevel::Error => "error",
+            AlertLevel::Warning => "warning",
+            AlertLevel::Info => "info",
+        };
+        let mut event = json!({
+            "routing_key": self.routing_key,
+            "event_action": "trigger",
+            "dedup_key": dedup_key(alert),
+            "payload": {
+                "summary": format!("{}: {}", alert.title, alert.message),
+                "source": hostname(),
+                "severity": severity,
+                "timestamp": alert.triggered_at.to_rfc3339(),
+                "component": alert.metadata.source,
+                "class": alert.category.as_str(),
+                "custom_details": {
+                    "message": alert.message,
+                    "value": alert.metadata.value,
+                    "threshold": alert.metadata.threshold,
+                    "node": alert.metadata.node_name,
+                    "namespace": alert.metadata.namespace,
+                    "pod": alert.metadata.pod_name,
+                    "vm": alert.metadata.vm_name,
+                    "remediation": alert.metadata.remediation,
+                },
+            },
+        });
+        if let Some(ref url) = alert.metadata.runbook_url {
+            event["links"] = json!([{ "href": url, "text": "Runbook" }]);
+        }
+        self.send(event, failures);
+    }
+
+    pub fn resolve(&self, alert: &Alert, failures: &mpsc::UnboundedSender<DeliveryFailure>) {
+        if !self.levels.contains(&alert.level) {
+            return;
+        }
+        let event = json!({
+            "routing_key": self.routing_key,
+            "event_action": "resolve",
+            "dedup_key": dedup_key(alert),
+        });
+        self.send(event, failures);
+    }
+
+    fn send(&self, event: serde_json::Value, failures: &mpsc::UnboundedSender<DeliveryFailure>) {
+        let url = self.url.clone();
+        spawn_delivery("PagerDuty", failures, async move { post_json(&url, None, &event).await });
+    }
+}
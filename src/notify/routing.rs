@@ -0,0 +1,55 @@
+use crate::alerts::Alert;
+use crate::config::NotificationRoute;
+
+/// A delivery channel alerts can be routed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Email,
+    PagerDuty,
+    Alertmanager,
+}
+
+impl Channel {
+    pub const ALL: [Channel; 3] = [Channel::Email, Channel::PagerDuty, Channel::Alertmanager];
+
+    /// Name used in `[[notifications.routes]] channels`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Channel::Email => "email",
+            Channel::PagerDuty => "pagerduty",
+            Channel::Alertmanager => "alertmanager",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|channel| channel.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Picks the channels each alert goes to from `[[notifications.routes]]`:
+/// the first route matching its category and level decides, and alerts no
+/// route matches go to every channel. Unknown names are reported by config
+/// validation and never match here.
+pub struct Router {
+    routes: Vec<NotificationRoute>,
+}
+
+impl Router {
+    pub fn new(routes: &[NotificationRoute]) -> Self {
+        Self { routes: routes.to_vec() }
+    }
+
+    pub fn channels(&self, alert: &Alert) -> Vec<Channel> {
+        let matches = |wanted: &[String], actual: &str| {
+            wanted.is_empty() || wanted.iter().any(|name| name.eq_ignore_ascii_case(actual))
+        };
+        match self
+            .routes
+            .iter()
+            .find(|route| matches(&route.categories, alert.category.as_str()) && matches(&route.levels, alert.level.as_str()))
+        {
+            Some(route) => route.channels.iter().filter_map(|name| Channel::parse(name)).collect(),
+            None => Channel::ALL.to_vec(),
+        }
+    }
+}
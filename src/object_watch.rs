@@ -0,0 +1,281 @@
+use chrono::{DateTime, Local};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::types::K8sResource;
+
+/// Updates kept, newest first
+const MAX_UPDATES: usize = 200;
+/// Wait before starting kubectl again after its watch ended or failed
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+/// Bookkeeping the apiserver rewrites on every update; left out of diffs
+const IGNORED_PATHS: [&str; 2] = ["metadata.managedFields", "metadata.resourceVersion"];
+/// Fields whose value names an array item, in order of preference
+const ITEM_KEYS: [&str; 2] = ["type", "name"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One leaf field that differs from the previous version of the object,
+/// by its path ("status.conditions[Ready].status")
+#[derive(Debug, Clone)]
+pub struct FieldChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// One watch event and what it changed
+#[derive(Debug, Clone)]
+pub struct WatchUpdate {
+    pub at: DateTime<Local>,
+    /// ADDED, MODIFIED or DELETED, as the watch reported it
+    pub event: String,
+    pub resource_version: Option<String>,
+    /// Field manager of the newest managedFields entry, i.e. who wrote
+    /// this version, with "/status" for a status subresource update
+    pub manager: Option<String>,
+    pub changes: Vec<FieldChange>,
+}
+
+enum WatchMessage {
+    Event(String, Value),
+    Error(String),
+}
+
+/// A single Kubernetes object followed through `kubectl get --watch`,
+/// each update diffed field by field against the one before, so
+/// controllers fighting over it show up as alternating writes. kubectl is
+/// started again whenever its watch ends.
+pub struct ObjectWatch {
+    pub resource: K8sResource,
+    /// Newest first
+    pub updates: VecDeque<WatchUpdate>,
+    /// Why the watch last failed; cleared by the next event
+    pub error: Option<String>,
+    pub scroll: usize,
+    /// Leaf fields of the last version seen
+    last: Option<BTreeMap<String, String>>,
+    rx: mpsc::UnboundedReceiver<WatchMessage>,
+    task: JoinHandle<()>,
+}
+
+impl ObjectWatch {
+    /// Start watching; `command` builds the kubectl invocation, once per
+    /// (re)start
+    pub fn start<F>(resource: K8sResource, command: F) -> Self
+    where
+        F: Fn() -> Command + Send + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let task = tokio::spawn(async move {
+            loop {
+                let result = stream_events(command(), &tx).await;
+                if let Err(e) = result {
+                    if tx.send(WatchMessage::Error(e)).is_err() {
+                        return;
+                    }
+                }
+                if tx.is_closed() {
+                    return;
+                }
+                tokio::time::sleep(RESTART_DELAY).await;
+            }
+        });
+
+        Self {
+            resource,
+            updates: VecDeque::new(),
+            error: None,
+            scroll: 0,
+            last: None,
+            rx,
+            task,
+        }
+    }
+
+    /// Take in the events that arrived, returning whether any did
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(message) = self.rx.try_recv() {
+            changed = true;
+            match message {
+                WatchMessage::Event(event, object) => {
+                    self.error = None;
+                    self.record(event, &object);
+                }
+                WatchMessage::Error(e) => self.error = Some(e),
+            }
+        }
+        changed
+    }
+
+    fn record(&mut self, event: String, object: &Value) {
+        let fields = flatten(object);
+        let changes = self.last.as_ref().map(|last| diff(last, &fields)).unwrap_or_default();
+        // A restarted watch begins with the object as it is; only what
+        // changed while kubectl was away is news
+        let repeated = self.last.is_some() && changes.is_empty() && event != "DELETED";
+        self.last = Some(fields);
+        if repeated {
+            return;
+        }
+
+        self.updates.push_front(WatchUpdate {
+            at: Local::now(),
+            event,
+            resource_version: object
+                .pointer("/metadata/resourceVersion")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            manager: last_manager(object),
+            changes,
+        });
+        self.updates.truncate(MAX_UPDATES);
+    }
+}
+
+impl Drop for ObjectWatch {
+    fn drop(&mut self) {
+        // kubectl is killed along with the task
+        self.task.abort();
+    }
+}
+
+/// Run one kubectl watch, passing on each event until it exits
+async fn stream_events(mut command: Command, tx: &mpsc::UnboundedSender<WatchMessage>) -> Result<(), String> {
+    command.stdout(Stdio::piped()).stderr(Stdio::piped()).kill_on_drop(true);
+    let mut child = command.spawn().map_err(|e| format!("Failed to execute kubectl: {}", e))?;
+    let Some(mut stdout) = child.stdout.take() else {
+        return Err("kubectl has no output".to_string());
+    };
+
+    // kubectl prints one pretty-printed JSON event after another
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+    loop {
+        let read = stdout.read(&mut chunk).await.map_err(|e| format!("Reading kubectl output: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+
+        let mut events = serde_json::Deserializer::from_slice(&buffer).into_iter::<Value>();
+        let mut consumed = 0;
+        loop {
+            match events.next() {
+                Some(Ok(event)) => {
+                    consumed = events.byte_offset();
+                    let kind = event.get("type").and_then(Value::as_str).unwrap_or("MODIFIED").to_string();
+                    let object = event.get("object").cloned().unwrap_or(event);
+                    if tx.send(WatchMessage::Event(kind, object)).is_err() {
+                        return Ok(());
+                    }
+                }
+                // The rest of the event is still on its way
+                Some(Err(e)) if e.is_eof() => break,
+                Some(Err(e)) => return Err(format!("Unreadable kubectl output: {}", e)),
+                None => break,
+            }
+        }
+        buffer.drain(..consumed);
+    }
+
+    let output = child.wait_with_output().await.map_err(|e| format!("Waiting for kubectl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("kubectl get --watch failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+/// Every leaf of `object` by its path. Array items are named by their
+/// `type` or `name` when those tell them apart (conditions, containers),
+/// so an item inserted in front doesn't shift the rest; otherwise by index.
+fn flatten(object: &Value) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    flatten_into(object, String::new(), &mut fields);
+    fields
+}
+
+fn flatten_into(value: &Value, path: String, fields: &mut BTreeMap<String, String>) {
+    if IGNORED_PATHS.contains(&path.as_str()) {
+        return;
+    }
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                flatten_into(value, path, fields);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            let keys = item_keys(items);
+            for (i, item) in items.iter().enumerate() {
+                let key = keys.as_ref().map_or_else(|| i.to_string(), |keys| keys[i].clone());
+                flatten_into(item, format!("{}[{}]", path, key), fields);
+            }
+        }
+        Value::String(text) => {
+            fields.insert(path, text.clone());
+        }
+        value => {
+            fields.insert(path, value.to_string());
+        }
+    }
+}
+
+/// Each item's `type` or `name`, when every item has one and they differ
+fn item_keys(items: &[Value]) -> Option<Vec<String>> {
+    ITEM_KEYS.iter().find_map(|field| {
+        let keys: Vec<String> = items
+            .iter()
+            .map(|item| item.get(field).and_then(Value::as_str).map(str::to_string))
+            .collect::<Option<_>>()?;
+        let unique: HashSet<&String> = keys.iter().collect();
+        (unique.len() == keys.len()).then_some(keys)
+    })
+}
+
+fn diff(old: &BTreeMap<String, String>, new: &BTreeMap<String, String>) -> Vec<FieldChange> {
+    let mut changes: Vec<FieldChange> = new
+        .iter()
+        .filter_map(|(path, value)| {
+            let kind = match old.get(path) {
+                None => ChangeKind::Added,
+                Some(previous) if previous != value => ChangeKind::Changed,
+                Some(_) => return None,
+            };
+            Some(FieldChange { path: path.clone(), kind, old: old.get(path).cloned(), new: Some(value.clone()) })
+        })
+        .collect();
+    changes.extend(old.iter().filter(|(path, _)| !new.contains_key(*path)).map(|(path, value)| FieldChange {
+        path: path.clone(),
+        kind: ChangeKind::Removed,
+        old: Some(value.clone()),
+        new: None,
+    }));
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+/// Manager of the most recently written managedFields entry
+fn last_manager(object: &Value) -> Option<String> {
+    let entries = object.pointer("/metadata/managedFields")?.as_array()?;
+    let newest = entries.iter().max_by_key(|entry| entry.get("time").and_then(Value::as_str).unwrap_or_default())?;
+    let manager = newest.get("manager")?.as_str()?;
+    Some(match newest.get("subresource").and_then(Value::as_str) {
+        Some(subresource) => format!("{}/{}", manager, subresource),
+        None => manager.to_string(),
+    })
+}
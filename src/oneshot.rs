@@ -0,0 +1,201 @@
+use anyhow::Result;
+use std::time::Duration;
+
+use crate::alerts::{Alert, AlertLevel, AlertManager};
+use crate::alert_profiles::AlertProfiles;
+use crate::app::{kubernetes_collector, node_alerts_config, system_alert_config, timed};
+use crate::cli::Subcommand;
+use crate::collectors::SystemCollector;
+use crate::config::Config;
+use crate::types::{K8sClusterInfo, KubeVirtInfo, SystemMetrics, VmUsage};
+use crate::web::{WebCluster, WebSystem};
+
+/// One collection round with the TUI's collectors and alert thresholds,
+/// for the status line and the scripting subcommands
+pub struct Snapshot {
+    pub config: Config,
+    pub system: SystemMetrics,
+    /// None when the cluster can't be reached; mock data would mislead scripts
+    pub cluster: Option<K8sClusterInfo>,
+    pub kubevirt: Option<KubeVirtInfo>,
+    /// Active alerts from the checks made on every refresh; those that need
+    /// history (trends, flapping, leaks) are only raised by the TUI
+    pub alerts: Vec<Alert>,
+    /// Only collected for `vms list`
+    pub vms: Option<Vec<VmUsage>>,
+}
+
+impl Snapshot {
+    pub async fn collect(with_vms: bool) -> Result<Self> {
+        let (config, _) = Config::load();
+        let limit = Duration::from_secs(config.general.collector_timeout_secs.max(1));
+
+        let mut system_collector = SystemCollector::new()?.with_filesystems(config.storage.filesystems.clone());
+        // CPU usage is the difference between two refreshes
+        tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+
+        let k8s = kubernetes_collector(&config).await?;
+        let (system, cluster, kubevirt) = tokio::join!(
+            timed(limit, system_collector.collect()),
+            timed(limit, k8s.collect_cluster_info()),
+            timed(limit, k8s.collect_kubevirt_info()),
+        );
+        let system = system.1?;
+        let cluster = cluster.1.ok().filter(|_| k8s.is_connected());
+        let kubevirt = kubevirt.1.ok().filter(|_| k8s.is_connected() || k8s.has_external_virt());
+        let vms = if with_vms && k8s.is_connected() {
+            Some(timed(limit, k8s.collect_vm_usage()).await.1?)
+        } else {
+            None
+        };
+
+        let node = sysinfo::System::host_name();
+        // A broken profile is reported by the TUI; one-shot runs fall back
+        let alerts = AlertProfiles::for_config(&config)
+            .resolve(&config.alerts, config.alerts.profile.as_deref())
+            .unwrap_or_else(|_| config.alerts.clone());
+        let alerts = node_alerts_config(&alerts, &k8s, node.as_deref()).await;
+        let mut alert_manager = AlertManager::new()
+            .with_system_config(system_alert_config(&alerts))
+            .with_kubernetes_enabled(alerts.kubernetes_enabled && cluster.is_some())
+            .with_kubevirt_enabled(alerts.kubevirt_enabled && kubevirt.is_some());
+        alert_manager.evaluate(
+            &system,
+            &cluster.clone().unwrap_or_default(),
+            &kubevirt.clone().unwrap_or_default(),
+        );
+        let alerts = alert_manager.get_active_alerts().into_iter().cloned().collect();
+
+        Ok(Self { config, system, cluster, kubevirt, alerts, vms })
+    }
+
+    /// Same shape as the web bridge's `system` object
+    fn web_system(&self) -> WebSystem {
+        WebSystem {
+            cpu_percent: self.system.cpu_usage,
+            memory_used_gb: self.system.memory_used_gb,
+            memory_total_gb: self.system.memory_total_gb,
+            disk_percent: self.system.disk_usage_percent,
+            load_avg: self.system.load_avg,
+            uptime_seconds: self.system.uptime_seconds,
+        }
+    }
+
+    fn web_cluster(&self) -> Option<WebCluster> {
+        let cluster = self.cluster.clone()?;
+        let kubevirt = self.kubevirt.clone().unwrap_or_default();
+        Some(WebCluster {
+            flavor: self.config.cluster_flavor().label().to_string(),
+            nodes_ready: cluster.nodes_ready,
+            nodes_total: cluster.nodes_total,
+            pods_running: cluster.pods_running,
+            vms_running: kubevirt.vms_running,
+            vms_stopped: kubevirt.vms_stopped,
+            vms_migrating: kubevirt.vms_migrating,
+        })
+    }
+}
+
+/// Run a scripting subcommand and print its result, as JSON with `--json`.
+/// Exits non-zero from `alerts list` when a Critical or Error alert is active,
+/// so cron jobs can act on the status alone.
+pub async fn run(command: Subcommand, json: bool) -> Result<()> {
+    let snapshot = Snapshot::collect(command == Subcommand::VmsList).await?;
+    match command {
+        Subcommand::AlertsList => {
+            print_alerts(&snapshot.alerts, json)?;
+            if snapshot.alerts.iter().any(|a| matches!(a.level, AlertLevel::Critical | AlertLevel::Error)) {
+                std::process::exit(2);
+            }
+        }
+        Subcommand::MetricsSnapshot => print_metrics(&snapshot, json)?,
+        Subcommand::VmsList => match snapshot.vms {
+            Some(ref vms) => print_vms(vms, json)?,
+            None => anyhow::bail!("Kubernetes API unreachable, no VMs to list"),
+        },
+    }
+    Ok(())
+}
+
+fn print_alerts(alerts: &[Alert], json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(alerts)?);
+        return Ok(());
+    }
+    if alerts.is_empty() {
+        println!("No active alerts");
+    }
+    for alert in alerts {
+        println!(
+            "{:<8} {:<10} {}  {}: {}",
+            alert.level.as_str(),
+            alert.category.as_str(),
+            alert.triggered_at.format("%Y-%m-%d %H:%M:%S"),
+            alert.title,
+            alert.message
+        );
+    }
+    Ok(())
+}
+
+fn print_metrics(snapshot: &Snapshot, json: bool) -> Result<()> {
+    let system = snapshot.web_system();
+    let cluster = snapshot.web_cluster();
+    if json {
+        let value = serde_json::json!({
+            "host": sysinfo::System::host_name(),
+            "system": system,
+            "cluster": cluster,
+            "filesystems": snapshot.system.filesystems.iter().map(|fs| serde_json::json!({
+                "mount_point": fs.mount_point,
+                "device": fs.device,
+                "total_bytes": fs.total_bytes,
+                "available_bytes": fs.available_bytes,
+                "used_percent": fs.used_percent(),
+            })).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+    println!("cpu        {:.1}%", system.cpu_percent);
+    println!("memory     {:.1} / {:.1} GB", system.memory_used_gb, system.memory_total_gb);
+    println!("disk       {:.1}%", system.disk_percent);
+    println!("load       {:.2}", system.load_avg);
+    println!("uptime     {}s", system.uptime_seconds);
+    for fs in &snapshot.system.filesystems {
+        println!("fs         {} {:.1}%", fs.mount_point, fs.used_percent());
+    }
+    match cluster {
+        Some(cluster) => {
+            println!("cluster    {}", cluster.flavor);
+            println!("nodes      {}/{}", cluster.nodes_ready, cluster.nodes_total);
+            println!("pods       {}", cluster.pods_running);
+            println!(
+                "vms        {} running, {} stopped, {} migrating",
+                cluster.vms_running, cluster.vms_stopped, cluster.vms_migrating
+            );
+        }
+        None => println!("cluster    unreachable"),
+    }
+    Ok(())
+}
+
+fn print_vms(vms: &[VmUsage], json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(vms)?);
+        return Ok(());
+    }
+    let percent = |value: Option<f64>| value.map(|v| format!("{:.0}%", v)).unwrap_or_else(|| "-".to_string());
+    println!("{:<20} {:<32} {:<12} {:>5} {:>5}", "NAMESPACE", "NAME", "STATUS", "CPU", "MEM");
+    for vm in vms {
+        println!(
+            "{:<20} {:<32} {:<12} {:>5} {:>5}",
+            vm.namespace,
+            vm.name,
+            vm.status,
+            percent(vm.cpu_percent),
+            percent(vm.memory_percent)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use crate::alerts::AlertLevel;
+use crate::app::Screen;
+use crate::types::Permission;
+use crate::upgrade::UpgradeAction;
+
+/// Something the command palette can run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteAction {
+    SwitchScreen(Screen),
+    Refresh,
+    ToggleAlertPanel,
+    AlertStatistics,
+    /// Query the journal for a past time range
+    ArchivedLogs,
+    /// Search every followed service's journal at once
+    SearchAllLogs,
+    /// Dismiss active alerts of one level, or all of them
+    DismissAlerts(Option<AlertLevel>),
+    DismissMessages,
+    EditLayout,
+    ToggleDebugOverlay,
+    /// Dark or light colors, remembered for the next start
+    ToggleTheme,
+    RestartVirtHandler,
+    CordonNode(String),
+    UncordonNode(String),
+    EvacuateNode(String),
+    Upgrade(UpgradeAction),
+    /// Create, check and delete a throwaway VMI and claim
+    SelfTest,
+    /// List system generations, collect garbage or delete old ones
+    NixStore,
+    /// Download cloud images or import them as DataVolumes
+    ImageCatalog,
+    /// Lay a named alert profile over [alerts], or go back to it as configured
+    AlertProfile(Option<String>),
+    ExportAlertProfile,
+    /// Release notes of a newer hypervisor-tui
+    ShowUpdate,
+    /// Review the kubeconfig's RBAC permissions again
+    CheckPermissions,
+    /// Switch to the `[[views]]` entry at this index
+    ApplyView(usize),
+    /// Ask for a name and save the current view to the config file
+    SaveView,
+    Quit,
+}
+
+impl PaletteAction {
+    /// Actions that change cluster or alert state, hidden in read-only mode
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            PaletteAction::DismissAlerts(_)
+                | PaletteAction::RestartVirtHandler
+                | PaletteAction::CordonNode(_)
+                | PaletteAction::UncordonNode(_)
+                | PaletteAction::EvacuateNode(_)
+                | PaletteAction::Upgrade(_)
+                | PaletteAction::SelfTest
+                | PaletteAction::AlertProfile(_)
+        )
+    }
+
+    /// What the kubeconfig needs for it; entries it lacks are hidden
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            PaletteAction::RestartVirtHandler => &[Permission::RestartDaemonSets],
+            PaletteAction::CordonNode(_) | PaletteAction::UncordonNode(_) => &[Permission::PatchNodes],
+            PaletteAction::EvacuateNode(_) => EVACUATION_PERMISSIONS,
+            PaletteAction::SelfTest => SELFTEST_PERMISSIONS,
+            _ => &[],
+        }
+    }
+}
+
+/// Cordon, migrate the VMs off and evict the pods
+pub const EVACUATION_PERMISSIONS: &[Permission] =
+    &[Permission::PatchNodes, Permission::CreateMigrations, Permission::EvictPods];
+
+/// Create the throwaway VMI and claim
+pub const SELFTEST_PERMISSIONS: &[Permission] = &[Permission::CreateVmis, Permission::CreateClaims];
+
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    pub label: String,
+    pub action: PaletteAction,
+}
+
+impl PaletteEntry {
+    pub fn new(label: impl Into<String>, action: PaletteAction) -> Self {
+        Self { label: label.into(), action }
+    }
+}
+
+/// Ctrl-P popup: a query typed against every available action
+#[derive(Debug, Clone)]
+pub struct CommandPalette {
+    pub query: String,
+    pub selected: usize,
+    entries: Vec<PaletteEntry>,
+}
+
+impl CommandPalette {
+    pub fn new(entries: Vec<PaletteEntry>) -> Self {
+        Self { query: String::new(), selected: 0, entries }
+    }
+
+    /// Entries matching the query, best first, with the matched character
+    /// positions for highlighting. An empty query lists everything in order.
+    pub fn matches(&self) -> Vec<(&PaletteEntry, Vec<usize>)> {
+        let mut scored: Vec<(i64, usize, &PaletteEntry, Vec<usize>)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| {
+                fuzzy_match(&self.query, &entry.label).map(|(score, positions)| (score, i, entry, positions))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        scored.into_iter().map(|(_, _, entry, positions)| (entry, positions)).collect()
+    }
+
+    pub fn input(&mut self, c: char) {
+        self.query.push(c);
+        self.selected = 0;
+    }
+
+    pub fn backspace(&mut self) {
+        self.query.pop();
+        self.selected = 0;
+    }
+
+    pub fn navigate(&mut self, down: bool) {
+        let count = self.matches().len();
+        if count == 0 {
+            return;
+        }
+        self.selected = if down {
+            (self.selected + 1).min(count - 1)
+        } else {
+            self.selected.saturating_sub(1)
+        };
+    }
+
+    pub fn selected_action(&self) -> Option<PaletteAction> {
+        self.matches().get(self.selected).map(|(entry, _)| entry.action.clone())
+    }
+}
+
+/// Case-insensitive subsequence match. Consecutive characters and matches
+/// at the start of a word score higher, gaps and late matches lower.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let text: Vec<char> = text.chars().collect();
+    let mut positions = Vec::new();
+    let mut score = 0i64;
+    let mut next = 0;
+
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let q = q.to_ascii_lowercase();
+        let found = (next..text.len()).find(|&i| text[i].to_ascii_lowercase() == q)?;
+
+        let word_start = found == 0 || !text[found - 1].is_alphanumeric();
+        score += match positions.last() {
+            Some(&last) if last + 1 == found => 8,
+            _ if word_start => 6,
+            _ => 1,
+        };
+        score -= (found - next) as i64;
+        positions.push(found);
+        next = found + 1;
+    }
+
+    Some((score, positions))
+}
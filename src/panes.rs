@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use crate::app::Screen;
+use crate::navigation::Motion;
+
+/// A scrollable region of a screen that can hold keyboard focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pane {
+    Logs,
+    /// Services logging the most errors, above the log list
+    NoisySources,
+    Timeline,
+    Interfaces,
+    /// Routing table and default gateways
+    Routes,
+    /// Interfaces inside virt-launcher pods' network namespaces
+    VmInterfaces,
+    Sriov,
+    K8sNetwork,
+    Dns,
+    Registries,
+    /// NetworkPolicies selecting virt-launcher pods
+    NetworkPolicies,
+    Endpoints,
+    Ingress,
+    /// A custom command's panel on the Tools screen, by config index
+    Command(usize),
+}
+
+impl Pane {
+    /// Panes of a screen in Tab order; the first one starts out focused.
+    /// The Tools screen's panes depend on the config, see `App::visible_panes`.
+    pub fn for_screen(screen: Screen) -> &'static [Pane] {
+        match screen {
+            Screen::Logs => &[Pane::Logs, Pane::NoisySources],
+            Screen::Timeline => &[Pane::Timeline],
+            Screen::Network => &[
+                Pane::Interfaces,
+                Pane::Routes,
+                Pane::VmInterfaces,
+                Pane::Sriov,
+                Pane::K8sNetwork,
+                Pane::Dns,
+                Pane::Registries,
+                Pane::NetworkPolicies,
+                Pane::Endpoints,
+                Pane::Ingress,
+            ],
+            _ => &[],
+        }
+    }
+}
+
+/// The focused pane of each screen and how far each pane is scrolled, so
+/// switching panes or screens doesn't lose anyone's place
+#[derive(Debug, Default)]
+pub struct PaneFocus {
+    focused: HashMap<Screen, Pane>,
+    offsets: HashMap<Pane, usize>,
+}
+
+impl PaneFocus {
+    /// The focused pane among `panes` (the ones currently shown), falling
+    /// back to the first when the focused one is hidden
+    pub fn focused(&self, screen: Screen, panes: &[Pane]) -> Option<Pane> {
+        self.focused
+            .get(&screen)
+            .filter(|pane| panes.contains(pane))
+            .or_else(|| panes.first())
+            .copied()
+    }
+
+    /// Move focus to the next (or previous) of `panes`, wrapping around
+    pub fn cycle(&mut self, screen: Screen, panes: &[Pane], forward: bool) {
+        let Some(current) = self.focused(screen, panes) else {
+            return;
+        };
+        let index = panes.iter().position(|&p| p == current).unwrap_or(0);
+        let next = if forward {
+            (index + 1) % panes.len()
+        } else {
+            (index + panes.len() - 1) % panes.len()
+        };
+        self.focused.insert(screen, panes[next]);
+    }
+
+    pub fn focus(&mut self, screen: Screen, pane: Pane) {
+        self.focused.insert(screen, pane);
+    }
+
+    pub fn offset(&self, pane: Pane) -> usize {
+        self.offsets.get(&pane).copied().unwrap_or(0)
+    }
+
+    pub fn set_offset(&mut self, pane: Pane, offset: usize) {
+        self.offsets.insert(pane, offset);
+    }
+
+    /// Pull a pane's offset back to at most `max`, e.g. after a resize
+    pub fn clamp_offset(&mut self, pane: Pane, max: usize) {
+        if self.offset(pane) > max {
+            self.set_offset(pane, max);
+        }
+    }
+
+    /// Scroll a pane of `len` rows, keeping at least its last row in view
+    pub fn scroll(&mut self, pane: Pane, motion: Motion, len: usize) {
+        let offset = motion.apply(self.offset(pane), len);
+        self.set_offset(pane, offset);
+    }
+}
@@ -0,0 +1,59 @@
+use tokio::sync::oneshot;
+
+use crate::collectors::KubernetesCollector;
+use crate::types::{Permission, PermissionAudit, PermissionCheck};
+
+/// The RBAC self-audit: run at startup and again from the command palette,
+/// in the background, keeping the last complete result
+#[derive(Default)]
+pub struct Permissions {
+    pending: Option<oneshot::Receiver<Result<Vec<PermissionCheck>, String>>>,
+    pub audit: Option<PermissionAudit>,
+    /// Why the last audit failed, for the diagnostics overlay
+    pub error: Option<String>,
+}
+
+impl Permissions {
+    /// Review every permission again; an audit still running is replaced
+    pub fn start(&mut self, k8s: &KubernetesCollector) {
+        let (tx, rx) = oneshot::channel();
+        let k8s = k8s.clone();
+        tokio::spawn(async move {
+            let _ = tx.send(k8s.audit_permissions().await.map_err(|e| format!("{:#}", e)));
+        });
+        self.pending = Some(rx);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Take in a finished audit, returning whether one arrived
+    pub fn poll(&mut self) -> bool {
+        let Some(rx) = self.pending.as_mut() else {
+            return false;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(oneshot::error::TryRecvError::Empty) => return false,
+            Err(oneshot::error::TryRecvError::Closed) => Err("audit was cancelled".to_string()),
+        };
+        self.pending = None;
+        match result {
+            Ok(checks) => {
+                self.error = None;
+                self.audit = Some(PermissionAudit { checks, checked_at: chrono::Local::now() });
+            }
+            Err(e) => {
+                tracing::warn!("Permission audit failed: {}", e);
+                self.error = Some(e);
+            }
+        }
+        true
+    }
+
+    /// Whether the kubeconfig may do this; true until reviewed otherwise
+    pub fn allows(&self, permission: Permission) -> bool {
+        self.audit.as_ref().is_none_or(|audit| audit.allows(permission))
+    }
+}
@@ -0,0 +1,102 @@
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+/// How often a tmux client being attached is checked
+const TMUX_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Whether anyone is looking at the TUI: terminal focus events and, inside
+/// tmux, whether the session still has a client attached. While nobody is,
+/// collection slows to `general.idle_refresh_secs` and frames are only
+/// drawn when something changed, so a TUI left running on every host
+/// doesn't keep journalctl and the API server busy.
+pub struct Presence {
+    /// Slow update interval; zero turns idle mode off
+    idle_interval: Duration,
+    focused: bool,
+    /// `$TMUX_PANE` when running inside tmux
+    tmux_pane: Option<String>,
+    tmux_attached: bool,
+    tmux_checked: Option<Instant>,
+    last_update: Option<Instant>,
+}
+
+impl Presence {
+    pub fn new(idle_refresh_secs: u64) -> Self {
+        Self {
+            idle_interval: Duration::from_secs(idle_refresh_secs),
+            focused: true,
+            tmux_pane: std::env::var("TMUX_PANE").ok().filter(|_| std::env::var_os("TMUX").is_some()),
+            tmux_attached: true,
+            tmux_checked: None,
+            last_update: None,
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        !self.idle_interval.is_zero() && (!self.focused || !self.tmux_attached)
+    }
+
+    /// Whether the next update interval should collect: always while
+    /// someone is looking, once per idle interval otherwise
+    pub fn update_due(&self) -> bool {
+        !self.is_idle() || self.last_update.is_none_or(|at| at.elapsed() >= self.idle_interval)
+    }
+
+    pub fn record_update(&mut self) {
+        self.last_update = Some(Instant::now());
+    }
+
+    /// Focus gained or lost, returning whether this ended idle mode
+    pub fn set_focused(&mut self, focused: bool) -> bool {
+        let was_idle = self.is_idle();
+        self.focused = focused;
+        if focused {
+            // Focus comes back through the client that just attached
+            self.tmux_attached = true;
+        }
+        was_idle && !self.is_idle()
+    }
+
+    /// A key was pressed, so someone is there whatever the terminal said;
+    /// returns whether this ended idle mode
+    pub fn wake(&mut self) -> bool {
+        self.set_focused(true)
+    }
+
+    /// The tmux pane to ask about a client being attached, every few
+    /// seconds; `None` outside tmux or between checks
+    pub fn tmux_check_due(&mut self) -> Option<String> {
+        let pane = self.tmux_pane.as_ref()?;
+        if self.idle_interval.is_zero() || self.tmux_checked.is_some_and(|at| at.elapsed() < TMUX_CHECK_INTERVAL) {
+            return None;
+        }
+        self.tmux_checked = Some(Instant::now());
+        Some(pane.clone())
+    }
+
+    /// Record what tmux answered, returning whether this ended idle mode
+    pub fn set_tmux_attached(&mut self, attached: bool) -> bool {
+        let was_idle = self.is_idle();
+        self.tmux_attached = attached;
+        was_idle && !self.is_idle()
+    }
+}
+
+/// Ask tmux whether the session holding `pane` has a client attached;
+/// `None` when tmux doesn't answer
+pub async fn tmux_attached(pane: &str) -> Option<bool> {
+    let output = Command::new("tmux")
+        .args(["display-message", "-p", "-t", pane, "#{session_attached}"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let Ok(Ok(output)) = tokio::time::timeout(Duration::from_secs(1), output).await else {
+        return None;
+    };
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim() != "0")
+}
@@ -0,0 +1,209 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+use crate::config::ReceiverConfig;
+use crate::http::{read_head, respond};
+use crate::types::ExternalAlert;
+
+/// Upper bound on a webhook body. Alertmanager batches every alert of a
+/// group into one post, so this is generous.
+const MAX_BODY: usize = 1024 * 1024;
+
+/// How long a sender gets to deliver its whole request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Accepts webhook posts from Alertmanager (and anything speaking its
+/// format, such as Grafana's unified alerting) or legacy Grafana alerting
+/// in the background, and hands the alerts in them to the app.
+pub struct WebhookReceiver {
+    alerts: mpsc::UnboundedReceiver<ExternalAlert>,
+}
+
+impl WebhookReceiver {
+    /// Bind `config.listen` and start accepting posts
+    pub async fn start(config: &ReceiverConfig) -> Result<Self> {
+        let token = match (&config.token_file, &config.token) {
+            (Some(path), _) => Some(
+                fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read receiver token file: {}", path))?
+                    .trim()
+                    .to_string(),
+            ),
+            (None, token) => token.clone(),
+        };
+        let listener = TcpListener::bind(&config.listen)
+            .await
+            .with_context(|| format!("Failed to listen on {}", config.listen))?;
+        tracing::info!("Alert webhook receiver listening on http://{}", config.listen);
+
+        let (tx, alerts) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, peer)) => {
+                        let tx = tx.clone();
+                        let token = token.clone();
+                        tokio::spawn(async move {
+                            let handled = tokio::time::timeout(REQUEST_TIMEOUT, handle(stream, token.as_deref(), &tx));
+                            match handled.await {
+                                Ok(Ok(())) => {}
+                                Ok(Err(e)) => tracing::debug!("Webhook from {} failed: {:#}", peer, e),
+                                Err(_) => tracing::debug!("Webhook from {} timed out", peer),
+                            }
+                        });
+                    }
+                    Err(e) => tracing::warn!("Webhook receiver accept failed: {}", e),
+                }
+            }
+        });
+
+        Ok(Self { alerts })
+    }
+
+    /// Alerts received since the last call, oldest first
+    pub fn drain(&mut self) -> Vec<ExternalAlert> {
+        let mut received = Vec::new();
+        while let Ok(alert) = self.alerts.try_recv() {
+            received.push(alert);
+        }
+        received
+    }
+}
+
+async fn handle(mut stream: TcpStream, token: Option<&str>, tx: &mpsc::UnboundedSender<ExternalAlert>) -> Result<()> {
+    let (head, mut body) = read_head(&mut stream).await?;
+    let mut lines = head.lines();
+    let method = lines.next().unwrap_or_default().split_whitespace().next().unwrap_or_default();
+    let headers: BTreeMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    if method != "POST" {
+        return respond(&mut stream, "405 Method Not Allowed", "text/plain", "POST alerts here\n").await;
+    }
+    if let Some(token) = token {
+        let presented = headers.get("authorization").and_then(|value| value.strip_prefix("Bearer "));
+        if presented != Some(token) {
+            return respond(&mut stream, "401 Unauthorized", "text/plain", "bad or missing bearer token\n").await;
+        }
+    }
+    let Some(length) = headers.get("content-length").and_then(|value| value.parse::<usize>().ok()) else {
+        return respond(&mut stream, "411 Length Required", "text/plain", "Content-Length required\n").await;
+    };
+    if length > MAX_BODY {
+        return respond(&mut stream, "413 Payload Too Large", "text/plain", "body too large\n").await;
+    }
+
+    if body.len() < length {
+        let already = body.len();
+        body.resize(length, 0);
+        stream.read_exact(&mut body[already..]).await?;
+    }
+    body.truncate(length);
+
+    let payload: Value = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(e) => return respond(&mut stream, "400 Bad Request", "text/plain", &format!("invalid JSON: {}\n", e)).await,
+    };
+    let alerts = parse_payload(&payload);
+    if alerts.is_empty() {
+        return respond(&mut stream, "400 Bad Request", "text/plain", "no alerts in the payload\n").await;
+    }
+    tracing::debug!("Received {} webhook alert(s)", alerts.len());
+    for alert in alerts {
+        // The app only goes away on exit
+        let _ = tx.send(alert);
+    }
+    respond(&mut stream, "200 OK", "text/plain", "ok\n").await
+}
+
+/// Alertmanager's webhook format (also sent by Grafana's unified alerting,
+/// which adds `orgId`), or a legacy Grafana alert notification
+fn parse_payload(payload: &Value) -> Vec<ExternalAlert> {
+    if let Some(alerts) = payload["alerts"].as_array() {
+        let sender = if payload.get("orgId").is_some() { "grafana" } else { "alertmanager" };
+        let group_status = payload["status"].as_str().unwrap_or("firing");
+        return alerts.iter().filter_map(|alert| parse_alertmanager(alert, group_status, sender)).collect();
+    }
+    parse_legacy_grafana(payload).into_iter().collect()
+}
+
+fn parse_alertmanager(alert: &Value, group_status: &str, sender: &str) -> Option<ExternalAlert> {
+    let labels = string_map(&alert["labels"]);
+    let annotations = string_map(&alert["annotations"]);
+    let name = labels.get("alertname").cloned()?;
+    let key = match alert["fingerprint"].as_str() {
+        Some(fingerprint) if !fingerprint.is_empty() => fingerprint.to_string(),
+        _ => label_fingerprint(&labels),
+    };
+    let summary = ["summary", "description", "message"]
+        .iter()
+        .find_map(|key| annotations.get(*key))
+        .cloned()
+        .unwrap_or_default();
+    // Firing alerts carry the zero time, or one in the future, as endsAt
+    let ends_at = alert["endsAt"]
+        .as_str()
+        .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+        .map(|at| at.with_timezone(&Local))
+        .filter(|at| at.timestamp() > 0);
+    Some(ExternalAlert {
+        key,
+        firing: alert["status"].as_str().unwrap_or(group_status) == "firing",
+        name,
+        labels,
+        summary,
+        sender: sender.to_string(),
+        received_at: Local::now(),
+        ends_at,
+    })
+}
+
+/// Grafana's legacy alerting: one rule per post, with `state` alerting,
+/// ok, no_data, paused or pending
+fn parse_legacy_grafana(payload: &Value) -> Option<ExternalAlert> {
+    let name = payload["ruleName"].as_str().or_else(|| payload["title"].as_str())?.to_string();
+    let state = payload["state"].as_str()?;
+    let labels = string_map(&payload["tags"]);
+    let key = match payload["ruleId"].as_u64() {
+        Some(id) => format!("grafana-{}", id),
+        None => format!("grafana-{}", name),
+    };
+    Some(ExternalAlert {
+        key,
+        firing: matches!(state, "alerting" | "no_data"),
+        name,
+        labels,
+        summary: payload["message"].as_str().unwrap_or_default().to_string(),
+        sender: "grafana".to_string(),
+        received_at: Local::now(),
+        ends_at: None,
+    })
+}
+
+/// String values of a JSON object; others are skipped
+fn string_map(value: &Value) -> BTreeMap<String, String> {
+    value
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+        .collect()
+}
+
+/// Stand-in for Alertmanager's fingerprint when a sender leaves it out:
+/// the same labels always give the same key
+fn label_fingerprint(labels: &BTreeMap<String, String>) -> String {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    labels.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
@@ -0,0 +1,53 @@
+use regex::Regex;
+
+use crate::config::RedactionRule;
+use crate::types::LogEntry;
+
+/// User-defined log scrubbing patterns, compiled once from
+/// `[[logging.redactions]]`. Applied as lines enter the log buffer, so
+/// nothing shown, exported or persisted afterwards carries the original.
+#[derive(Default)]
+pub struct LogRedactor {
+    rules: Vec<(Regex, String)>,
+}
+
+impl LogRedactor {
+    /// Compile the configured rules, skipping (and reporting) invalid ones
+    /// so a typo in one pattern doesn't disable the rest
+    pub fn new(rules: &[RedactionRule]) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let rules = rules
+            .iter()
+            .filter_map(|rule| match Regex::new(&rule.pattern) {
+                Ok(regex) => Some((regex, rule.replacement.clone())),
+                Err(e) => {
+                    errors.push(format!("Invalid redaction pattern {:?}: {}", rule.pattern, e));
+                    None
+                }
+            })
+            .collect();
+        (Self { rules }, errors)
+    }
+
+    /// Apply every rule in order; replacements may use $1 or ${name}
+    pub fn redact(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for (regex, replacement) in &self.rules {
+            if regex.is_match(&text) {
+                text = regex.replace_all(&text, replacement.as_str()).into_owned();
+            }
+        }
+        text
+    }
+
+    /// Scrub the message and the values of structured fields
+    pub fn redact_entry(&self, entry: &mut LogEntry) {
+        if self.rules.is_empty() {
+            return;
+        }
+        entry.message = self.redact(&entry.message);
+        for (_, value) in &mut entry.fields {
+            *value = self.redact(value);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::alerts::AlertLevel;
+use crate::app::App;
+use crate::config::{Config, ReportConfig};
+use crate::types::NodeReachability;
+
+/// Statuses that aren't worth calling out in a handoff
+const HEALTHY_STATUSES: [&str; 7] = ["Running", "Succeeded", "Stopped", "Bound", "Active", "Ready", "Completed"];
+/// Filesystems listed under the metrics once they are this full
+const FULL_FILESYSTEM_PERCENT: f64 = 80.0;
+
+/// A block of the status report, in the order `[report].sections` lists them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportSection {
+    Host,
+    Alerts,
+    Metrics,
+    Workloads,
+}
+
+/// Plain-text status report for pasting into chat during an incident
+/// handoff: a title line, then each configured section separated by a
+/// blank line
+pub fn render(app: &App, config: &ReportConfig) -> String {
+    let title = format!(
+        "Status report: {} at {}",
+        app.local_node.as_deref().unwrap_or("host"),
+        Local::now().format("%Y-%m-%d %H:%M %Z")
+    );
+    render_sections(title, app, &config.sections, config.max_alerts)
+}
+
+/// What `[report].exit_sections` shows, printed after the TUI leaves the
+/// alternate screen so the last state stays in scrollback; None unless
+/// `[report].exit_summary` is on
+pub fn exit_summary(app: &App, config: &ReportConfig) -> Option<String> {
+    if !config.exit_summary {
+        return None;
+    }
+    let title = format!(
+        "Last state of {} at {}",
+        app.local_node.as_deref().unwrap_or("host"),
+        Local::now().format("%Y-%m-%d %H:%M %Z")
+    );
+    Some(render_sections(title, app, &config.exit_sections, config.max_alerts))
+}
+
+fn render_sections(title: String, app: &App, sections: &[ReportSection], max_alerts: usize) -> String {
+    let mut blocks = vec![title];
+    for section in sections {
+        let lines = match section {
+            ReportSection::Host => host(app),
+            ReportSection::Alerts => alerts(app, max_alerts),
+            ReportSection::Metrics => metrics(app),
+            ReportSection::Workloads => workloads(app),
+        };
+        blocks.push(lines.join("\n"));
+    }
+    let mut report = blocks.join("\n\n");
+    report.push('\n');
+    report
+}
+
+/// Write the report to `[report].dir` (the state directory's reports/ by
+/// default), returning the file's path
+pub fn save(report: &str, config: &ReportConfig) -> Result<PathBuf> {
+    let dir = config
+        .dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Config::state_dir().join("reports"));
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {:?}", dir))?;
+    let path = dir.join(format!("report-{}.txt", Local::now().format("%Y%m%d-%H%M%S")));
+    fs::write(&path, report).with_context(|| format!("Failed to write {:?}", path))?;
+    Ok(path)
+}
+
+fn host(app: &App) -> Vec<String> {
+    let info = &app.host_info;
+    let uptime = app.system_metrics.uptime_seconds;
+    let mut summary = format!(
+        "Host: up {}d {}h {}m",
+        uptime / 86400,
+        uptime % 86400 / 3600,
+        uptime % 3600 / 60
+    );
+    if let Some(ref version) = info.nixos_version {
+        summary.push_str(&format!(" | NixOS {}", version));
+        if let Some(generation) = info.generation {
+            summary.push_str(&format!(" gen {}", generation));
+        }
+        if info.reboot_pending() {
+            summary.push_str(" (reboot pending)");
+        }
+    }
+    let mut lines = vec![summary];
+    if !info.services.is_empty() {
+        let services: Vec<String> = info.services.iter().map(|(unit, state)| format!("{} {}", unit, state)).collect();
+        lines.push(format!("  {}: {}", app.cluster_flavor.label(), services.join(", ")));
+    }
+    lines
+}
+
+fn alerts(app: &App, limit: usize) -> Vec<String> {
+    let active = app.alert_manager.get_active_alerts();
+    let count = |levels: &[AlertLevel]| active.iter().filter(|a| levels.contains(&a.level)).count();
+    let mut lines = vec![format!(
+        "Alerts: {} critical/error, {} warning, {} info",
+        count(&[AlertLevel::Critical, AlertLevel::Error]),
+        count(&[AlertLevel::Warning]),
+        count(&[AlertLevel::Info])
+    )];
+    for alert in active.iter().take(limit) {
+        lines.push(format!(
+            "  [{}] {} - {} (since {})",
+            alert.level.as_str(),
+            alert.title,
+            alert.message,
+            alert.triggered_at.format("%H:%M")
+        ));
+    }
+    if active.len() > limit {
+        lines.push(format!("  ... and {} more", active.len() - limit));
+    }
+    lines
+}
+
+fn metrics(app: &App) -> Vec<String> {
+    let system = &app.system_metrics;
+    let memory = if system.memory_total_gb > 0.0 {
+        system.memory_used_gb / system.memory_total_gb * 100.0
+    } else {
+        0.0
+    };
+    let mut lines = vec![format!(
+        "Metrics: cpu {:.0}% | mem {:.0}% ({:.1}/{:.1} GB) | load {:.2} | disk {:.0}%",
+        app.displayed_cpu(),
+        memory,
+        system.memory_used_gb,
+        system.memory_total_gb,
+        system.load_avg,
+        system.disk_usage_percent
+    )];
+    for fs in system.filesystems.iter().filter(|fs| fs.used_percent() >= FULL_FILESYSTEM_PERCENT) {
+        lines.push(format!("  {} {:.0}% used", fs.mount_point, fs.used_percent()));
+    }
+    lines
+}
+
+fn workloads(app: &App) -> Vec<String> {
+    let vms = &app.kubevirt_info;
+    let cluster = &app.k8s_info;
+    let mut lines = vec![format!(
+        "Workloads: {} VMs running, {} stopped, {} migrating | nodes {}/{} ready | {} pods running",
+        vms.vms_running, vms.vms_stopped, vms.vms_migrating, cluster.nodes_ready, cluster.nodes_total, cluster.pods_running
+    )];
+    for node in app.fleet.iter().filter(|n| n.reachability != NodeReachability::Ready || !n.pressures.is_empty()) {
+        let mut problems = vec![node.reachability.label().to_string()];
+        problems.extend(node.pressures.iter().cloned());
+        lines.push(format!("  node {}: {}", node.name, problems.join(", ")));
+    }
+    for resource in app.resources.iter().filter(|r| !HEALTHY_STATUSES.contains(&r.status.as_str())) {
+        let name = match resource.namespace {
+            Some(ref namespace) => format!("{}/{}", namespace, resource.name),
+            None => resource.name.clone(),
+        };
+        lines.push(format!("  {:?} {}: {}", resource.kind, name, resource.status));
+    }
+    for dv in app.data_volumes.iter().filter(|dv| dv.phase == "Failed" || (!dv.is_finished() && dv.restarts > 0)) {
+        lines.push(format!("  DataVolume {}: {} ({} restarts)", dv.key(), dv.phase, dv.restarts));
+    }
+    if lines.len() == 1 {
+        lines.push("  nothing degraded".to_string());
+    }
+    lines
+}
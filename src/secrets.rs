@@ -0,0 +1,161 @@
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::config::SecretsConfig;
+
+/// Prefix of the environment variables the "env" provider reads
+pub const ENV_PREFIX: &str = "HYPERVISOR_TUI_SECRET_";
+
+/// Where a secret's key name is looked up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    /// `$CREDENTIALS_DIRECTORY/<key>`, from systemd's `LoadCredential=`
+    Credential,
+    /// `HYPERVISOR_TUI_SECRET_<KEY>`
+    Env,
+    /// The sops- or age-encrypted `[secrets] file`
+    File,
+}
+
+impl Provider {
+    pub const NAMES: [&'static str; 3] = ["credential", "env", "file"];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "credential" => Some(Provider::Credential),
+            "env" => Some(Provider::Env),
+            "file" => Some(Provider::File),
+            _ => None,
+        }
+    }
+}
+
+/// Resolves `*_secret` key names through the configured providers. The
+/// encrypted file is decrypted once, on the first lookup that reaches it.
+pub struct Secrets {
+    providers: Vec<Provider>,
+    file: Option<String>,
+    age_identity: Option<String>,
+    decrypted: OnceLock<Result<HashMap<String, String>, String>>,
+}
+
+impl Secrets {
+    pub fn from_config(config: &SecretsConfig) -> Self {
+        Self {
+            providers: config.providers.iter().filter_map(|name| Provider::parse(name)).collect(),
+            file: config.file.clone(),
+            age_identity: config.age_identity.clone(),
+            decrypted: OnceLock::new(),
+        }
+    }
+
+    /// The value of `key` from the first provider that has it
+    pub fn get(&self, key: &str) -> Result<String> {
+        for provider in &self.providers {
+            let value = match provider {
+                Provider::Credential => credential(key),
+                Provider::Env => std::env::var(env_name(key)).ok(),
+                Provider::File if self.file.is_some() => self.file_secret(key)?,
+                Provider::File => None,
+            };
+            if let Some(value) = value {
+                return Ok(value.trim().to_string());
+            }
+        }
+        bail!("Secret {:?} not found (looked in {})", key, self.describe())
+    }
+
+    fn file_secret(&self, key: &str) -> Result<Option<String>> {
+        let secrets = self.decrypted.get_or_init(|| self.decrypt().map_err(|e| format!("{:#}", e)));
+        match secrets {
+            Ok(secrets) => Ok(secrets.get(key).cloned()),
+            Err(e) => Err(anyhow!("{}", e)),
+        }
+    }
+
+    /// The secrets file's keys, nested tables flattened to dotted names
+    fn decrypt(&self) -> Result<HashMap<String, String>> {
+        let Some(ref path) = self.file else {
+            return Ok(HashMap::new());
+        };
+        let output = if path.ends_with(".age") {
+            let identity = self
+                .age_identity
+                .as_deref()
+                .context("secrets.age_identity is required to decrypt an .age file")?;
+            Command::new("age").args(["--decrypt", "--identity", identity, path]).output()
+        } else {
+            Command::new("sops").args(["--decrypt", "--output-type", "json", path]).output()
+        }
+        .with_context(|| format!("Failed to run the decryption tool for {}", path))?;
+        if !output.status.success() {
+            bail!("Failed to decrypt {}: {}", path, String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        let text = String::from_utf8(output.stdout).context("Decrypted secrets are not UTF-8")?;
+        let value: serde_json::Value = match serde_json::from_str(&text) {
+            Ok(value) => value,
+            Err(_) => {
+                let table: toml::Table =
+                    toml::from_str(&text).with_context(|| format!("Decrypted {} is neither JSON nor TOML", path))?;
+                serde_json::to_value(table)?
+            }
+        };
+        let mut secrets = HashMap::new();
+        flatten("", &value, &mut secrets);
+        Ok(secrets)
+    }
+
+    fn describe(&self) -> String {
+        let names: Vec<String> = self
+            .providers
+            .iter()
+            .map(|provider| match provider {
+                Provider::Credential => "$CREDENTIALS_DIRECTORY".to_string(),
+                Provider::Env => format!("{}*", ENV_PREFIX),
+                Provider::File => self.file.clone().unwrap_or_else(|| "no secrets file".to_string()),
+            })
+            .collect();
+        if names.is_empty() {
+            "no providers".to_string()
+        } else {
+            names.join(", ")
+        }
+    }
+}
+
+/// "smtp-password" -> HYPERVISOR_TUI_SECRET_SMTP_PASSWORD
+pub fn env_name(key: &str) -> String {
+    let key: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    format!("{}{}", ENV_PREFIX, key)
+}
+
+fn credential(key: &str) -> Option<String> {
+    let dir = std::env::var_os("CREDENTIALS_DIRECTORY")?;
+    fs::read_to_string(Path::new(&dir).join(key)).ok()
+}
+
+fn flatten(prefix: &str, value: &serde_json::Value, out: &mut HashMap<String, String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                let dotted = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(&dotted, value, out);
+            }
+        }
+        serde_json::Value::String(s) => {
+            out.insert(prefix.to_string(), s.clone());
+        }
+        serde_json::Value::Null => {}
+        other => {
+            out.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
@@ -0,0 +1,402 @@
+use crate::collectors::KubernetesCollector;
+use crate::config::SelfTestConfig;
+use serde_json::{json, Value};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+/// How often a waiting step looks at its object again
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Label on everything the self-test creates, for finding leftovers when
+/// the TUI exits halfway through a run
+const SELFTEST_LABEL: &str = "hypervisor-tui/selftest";
+
+/// What one self-test run checks, in order. Cleanup always runs; the
+/// others are skipped once a step before them failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfTestStep {
+    CreateVmi,
+    Schedule,
+    Boot,
+    Network,
+    Storage,
+    Cleanup,
+}
+
+impl SelfTestStep {
+    pub const ALL: [SelfTestStep; 6] = [
+        SelfTestStep::CreateVmi,
+        SelfTestStep::Schedule,
+        SelfTestStep::Boot,
+        SelfTestStep::Network,
+        SelfTestStep::Storage,
+        SelfTestStep::Cleanup,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SelfTestStep::CreateVmi => "Create test VMI",
+            SelfTestStep::Schedule => "Schedule",
+            SelfTestStep::Boot => "Boot",
+            SelfTestStep::Network => "Network connectivity",
+            SelfTestStep::Storage => "Storage write/read",
+            SelfTestStep::Cleanup => "Clean up",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepState {
+    Pending,
+    Running,
+    /// Passed, with what was observed
+    Passed(String),
+    Failed(String),
+    /// Not run because an earlier step failed
+    Skipped,
+}
+
+/// A running (or finished) self-test and each step's outcome as it completes
+pub struct SelfTestRun {
+    pub steps: Vec<(SelfTestStep, StepState)>,
+    /// Name of the test VMI, claim and pod
+    pub name: String,
+    pub namespace: String,
+    /// Panel dismissed while the test is still running
+    pub hidden: bool,
+    started: Instant,
+    rx: mpsc::UnboundedReceiver<(usize, StepState)>,
+}
+
+impl SelfTestRun {
+    /// Spawn the steps one after another against the cluster; without one
+    /// they pass straight away as a demo
+    pub fn start(k8s: &KubernetesCollector, config: &SelfTestConfig) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let name = format!("hv-selftest-{}", chrono::Local::now().format("%Y%m%d%H%M%S"));
+        let test = SelfTest {
+            k8s: k8s.clone(),
+            config: config.clone(),
+            name: name.clone(),
+            timeout: Duration::from_secs(config.step_timeout_secs),
+        };
+
+        tokio::spawn(async move {
+            let mut failed = false;
+            for (i, step) in SelfTestStep::ALL.into_iter().enumerate() {
+                if failed && step != SelfTestStep::Cleanup {
+                    let _ = tx.send((i, StepState::Skipped));
+                    continue;
+                }
+                let _ = tx.send((i, StepState::Running));
+                let state = match test.run(step).await {
+                    Ok(detail) => StepState::Passed(detail),
+                    Err(reason) => {
+                        failed = true;
+                        StepState::Failed(reason)
+                    }
+                };
+                if tx.send((i, state)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            steps: SelfTestStep::ALL.into_iter().map(|step| (step, StepState::Pending)).collect(),
+            name,
+            namespace: config.namespace.clone(),
+            hidden: false,
+            started: Instant::now(),
+            rx,
+        }
+    }
+
+    /// Take in step updates, returning whether any arrived
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok((i, state)) = self.rx.try_recv() {
+            if let Some(entry) = self.steps.get_mut(i) {
+                entry.1 = state;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !self.steps.iter().any(|(_, state)| matches!(state, StepState::Pending | StepState::Running))
+    }
+
+    pub fn failed(&self) -> usize {
+        self.steps.iter().filter(|(_, state)| matches!(state, StepState::Failed(_))).count()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// "Self-test passed" or "Self-test failed at Boot"
+    pub fn summary(&self) -> String {
+        match self.steps.iter().find(|(_, state)| matches!(state, StepState::Failed(_))) {
+            Some((step, _)) => format!("Self-test failed at {}", step.label()),
+            None if self.is_finished() => "Self-test passed".to_string(),
+            None => "Self-test running".to_string(),
+        }
+    }
+}
+
+struct SelfTest {
+    k8s: KubernetesCollector,
+    config: SelfTestConfig,
+    name: String,
+    timeout: Duration,
+}
+
+impl SelfTest {
+    async fn run(&self, step: SelfTestStep) -> Result<String, String> {
+        if !self.k8s.is_connected() {
+            tokio::time::sleep(Duration::from_millis(400)).await;
+            return Ok("no cluster, simulated".to_string());
+        }
+        match step {
+            SelfTestStep::CreateVmi => self.create_vmi().await,
+            SelfTestStep::Schedule => {
+                let (node, secs) = self
+                    .wait_for("virtualmachineinstance", |vmi| {
+                        match vmi.pointer("/status/phase").and_then(Value::as_str) {
+                            Some("Failed") => Some(Err(vmi_failure(vmi))),
+                            _ => vmi
+                                .pointer("/status/nodeName")
+                                .and_then(Value::as_str)
+                                .filter(|node| !node.is_empty())
+                                .map(|node| Ok(node.to_string())),
+                        }
+                    })
+                    .await?;
+                Ok(format!("on {} after {}s", node, secs))
+            }
+            SelfTestStep::Boot => {
+                let ((), secs) = self
+                    .wait_for("virtualmachineinstance", |vmi| {
+                        match vmi.pointer("/status/phase").and_then(Value::as_str) {
+                            Some("Running") => Some(Ok(())),
+                            Some("Failed") | Some("Succeeded") => Some(Err(vmi_failure(vmi))),
+                            _ => None,
+                        }
+                    })
+                    .await?;
+                Ok(format!("running after {}s", secs))
+            }
+            SelfTestStep::Network => self.check_network().await,
+            SelfTestStep::Storage => self.check_storage().await,
+            SelfTestStep::Cleanup => self.cleanup().await,
+        }
+    }
+
+    fn metadata(&self) -> Value {
+        json!({
+            "name": self.name,
+            "namespace": self.config.namespace,
+            "labels": { SELFTEST_LABEL: "true" },
+        })
+    }
+
+    /// A tiny containerDisk VMI on the pod network, plus a bridge
+    /// interface on the configured Multus network
+    async fn create_vmi(&self) -> Result<String, String> {
+        let mut interfaces = vec![json!({ "name": "default", "masquerade": {} })];
+        let mut networks = vec![json!({ "name": "default", "pod": {} })];
+        if let Some(network) = &self.config.network {
+            interfaces.push(json!({ "name": "bridge", "bridge": {} }));
+            networks.push(json!({ "name": "bridge", "multus": { "networkName": network } }));
+        }
+        let mut spec = json!({
+            "domain": {
+                "resources": { "requests": { "memory": "128Mi" } },
+                "devices": {
+                    "disks": [{ "name": "containerdisk", "disk": { "bus": "virtio" } }],
+                    "interfaces": interfaces,
+                },
+            },
+            "networks": networks,
+            "volumes": [{ "name": "containerdisk", "containerDisk": { "image": self.config.image } }],
+            "terminationGracePeriodSeconds": 0,
+        });
+        if let Some(node) = &self.config.node {
+            spec["nodeSelector"] = json!({ "kubernetes.io/hostname": node });
+        }
+        let manifest = json!({
+            "apiVersion": "kubevirt.io/v1",
+            "kind": "VirtualMachineInstance",
+            "metadata": self.metadata(),
+            "spec": spec,
+        });
+        self.k8s.create_object(&manifest).await.map_err(|e| format!("{:#}", e))
+    }
+
+    /// Ping every address the VMI reports until each answers
+    async fn check_network(&self) -> Result<String, String> {
+        let (addresses, _) = self
+            .wait_for("virtualmachineinstance", |vmi| {
+                let addresses: Vec<(String, String)> = vmi
+                    .pointer("/status/interfaces")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|iface| {
+                        let ip = iface.get("ipAddress").and_then(Value::as_str).filter(|ip| !ip.is_empty())?;
+                        let name = iface.get("name").and_then(Value::as_str).unwrap_or("?");
+                        Some((name.to_string(), ip.to_string()))
+                    })
+                    .collect();
+                (!addresses.is_empty()).then_some(Ok(addresses))
+            })
+            .await?;
+
+        let mut answered = Vec::new();
+        for (name, ip) in &addresses {
+            let start = Instant::now();
+            loop {
+                if ping(ip).await {
+                    answered.push(format!("{} {}", name, ip));
+                    break;
+                }
+                if start.elapsed() >= self.timeout {
+                    return Err(format!("{} ({}) does not answer ping", ip, name));
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+
+        let mut detail = format!("{} answered", answered.join(", "));
+        if self.config.network.is_some() && !answered.iter().any(|a| a.starts_with("bridge ")) {
+            detail.push_str("; bridge reports no IP without a guest agent");
+        }
+        Ok(detail)
+    }
+
+    /// A small claim of the configured class, written and read back by a pod
+    async fn check_storage(&self) -> Result<String, String> {
+        let mut claim_spec = json!({
+            "accessModes": ["ReadWriteOnce"],
+            "resources": { "requests": { "storage": "64Mi" } },
+        });
+        if let Some(class) = &self.config.storage_class {
+            claim_spec["storageClassName"] = json!(class);
+        }
+        let claim = json!({
+            "apiVersion": "v1",
+            "kind": "PersistentVolumeClaim",
+            "metadata": self.metadata(),
+            "spec": claim_spec,
+        });
+        self.k8s.create_object(&claim).await.map_err(|e| format!("{:#}", e))?;
+
+        let token = format!("{}-ok", self.name);
+        let mut pod_spec = json!({
+            "restartPolicy": "Never",
+            "containers": [{
+                "name": "check",
+                "image": self.config.storage_image,
+                "command": ["sh", "-c", format!("echo {} > /data/selftest && sync && cat /data/selftest", token)],
+                "volumeMounts": [{ "name": "data", "mountPath": "/data" }],
+            }],
+            "volumes": [{ "name": "data", "persistentVolumeClaim": { "claimName": self.name } }],
+        });
+        if let Some(node) = &self.config.node {
+            pod_spec["nodeSelector"] = json!({ "kubernetes.io/hostname": node });
+        }
+        let pod = json!({
+            "apiVersion": "v1",
+            "kind": "Pod",
+            "metadata": self.metadata(),
+            "spec": pod_spec,
+        });
+        self.k8s.create_object(&pod).await.map_err(|e| format!("{:#}", e))?;
+
+        let ((), secs) = self
+            .wait_for("pod", |pod| match pod.pointer("/status/phase").and_then(Value::as_str) {
+                Some("Succeeded") => Some(Ok(())),
+                Some("Failed") => Some(Err("the check pod failed".to_string())),
+                _ => None,
+            })
+            .await?;
+        let output = self
+            .k8s
+            .pod_logs(&self.config.namespace, &self.name)
+            .await
+            .map_err(|e| format!("{:#}", e))?;
+        if output.trim() != token {
+            return Err(format!("read back {:?} instead of what was written", output.trim()));
+        }
+        let class = self.config.storage_class.as_deref().unwrap_or("default class");
+        Ok(format!("{}: written and read back after {}s", class, secs))
+    }
+
+    /// Delete everything the run created, whether or not it got that far
+    async fn cleanup(&self) -> Result<String, String> {
+        let mut errors = Vec::new();
+        for kind in ["virtualmachineinstance", "pod", "persistentvolumeclaim"] {
+            if let Err(e) = self.k8s.delete_object(kind, &self.config.namespace, &self.name).await {
+                errors.push(format!("{}: {:#}", kind, e));
+            }
+        }
+        if errors.is_empty() {
+            Ok("test objects deleted".to_string())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// Poll the test object of `kind` until `check` decides, returning
+    /// what it found and the seconds that took
+    async fn wait_for<T, F>(&self, kind: &str, check: F) -> Result<(T, u64), String>
+    where
+        F: Fn(&Value) -> Option<Result<T, String>>,
+    {
+        let start = Instant::now();
+        loop {
+            let last_error = match self.k8s.fetch_object(kind, &self.config.namespace, &self.name).await {
+                Ok(object) => match check(&object) {
+                    Some(outcome) => {
+                        return outcome.map(|found| (found, start.elapsed().as_secs()));
+                    }
+                    None => None,
+                },
+                Err(e) => Some(format!("{:#}", e)),
+            };
+            if start.elapsed() >= self.timeout {
+                return Err(last_error.unwrap_or_else(|| format!("timed out after {}s", self.timeout.as_secs())));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Why a VMI failed, from its Ready condition or phase
+fn vmi_failure(vmi: &Value) -> String {
+    vmi.pointer("/status/conditions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .find_map(|condition| condition.get("message").and_then(Value::as_str))
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            let phase = vmi.pointer("/status/phase").and_then(Value::as_str).unwrap_or("unknown");
+            format!("VMI ended in phase {}", phase)
+        })
+}
+
+/// One ICMP echo with a two second wait
+async fn ping(ip: &str) -> bool {
+    Command::new("ping")
+        .args(["-c", "1", "-W", "2", ip])
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .is_ok_and(|output| output.status.success())
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+
+use crate::alerts::AlertLevel;
+use crate::oneshot::Snapshot;
+
+/// One collection round with the TUI's collectors and thresholds, printed
+/// as a single colorless line for tmux status bars and shell prompts:
+///
+/// `cpu 23% mem 41% disk 62% | alerts 1C 2W | vms 12/14 | nodes 3/3`
+pub async fn print() -> Result<()> {
+    let Snapshot { system, cluster, kubevirt, alerts: active, .. } = Snapshot::collect(false).await?;
+    let count = |level: AlertLevel| active.iter().filter(|a| a.level == level).count();
+
+    let memory = if system.memory_total_gb > 0.0 {
+        system.memory_used_gb / system.memory_total_gb * 100.0
+    } else {
+        0.0
+    };
+    let mut line = format!(
+        "cpu {:.0}% mem {:.0}% disk {:.0}%",
+        system.cpu_usage, memory, system.disk_usage_percent
+    );
+    let (critical, warning) = (count(AlertLevel::Critical) + count(AlertLevel::Error), count(AlertLevel::Warning));
+    if critical + warning == 0 {
+        line.push_str(" | alerts 0");
+    } else {
+        line.push_str(&format!(" | alerts {}C {}W", critical, warning));
+    }
+    match kubevirt {
+        Some(vms) => line.push_str(&format!(
+            " | vms {}/{}",
+            vms.vms_running,
+            vms.vms_running + vms.vms_stopped + vms.vms_migrating
+        )),
+        None => line.push_str(" | vms -"),
+    }
+    match cluster {
+        Some(cluster) => line.push_str(&format!(" | nodes {}/{}", cluster.nodes_ready, cluster.nodes_total)),
+        None => line.push_str(" | nodes -"),
+    }
+
+    println!("{}", line);
+    Ok(())
+}
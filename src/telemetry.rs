@@ -0,0 +1,154 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::types::CollectorTiming;
+
+/// telemetry.jsonl is rotated to telemetry.jsonl.1 past this size
+const MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Last and slowest duration of something that happens repeatedly
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Span {
+    pub last_ms: u64,
+    pub max_ms: u64,
+}
+
+impl Span {
+    fn record(&mut self, elapsed: Duration) {
+        self.last_ms = elapsed.as_millis() as u64;
+        self.max_ms = self.max_ms.max(self.last_ms);
+    }
+}
+
+/// Entries held by one in-memory buffer and their approximate size
+#[derive(Debug, Clone, Serialize)]
+pub struct BufferUsage {
+    pub name: &'static str,
+    pub entries: usize,
+    pub bytes: usize,
+    /// Budget the buffer is kept under, for those that have one
+    pub limit_bytes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectorSample {
+    pub name: &'static str,
+    pub last_ms: u64,
+    pub max_ms: u64,
+    pub runs: u64,
+    pub failures: u64,
+    pub error: Option<String>,
+}
+
+impl From<&CollectorTiming> for CollectorSample {
+    fn from(timing: &CollectorTiming) -> Self {
+        Self {
+            name: timing.name,
+            last_ms: timing.last.as_millis() as u64,
+            max_ms: timing.max.as_millis() as u64,
+            runs: timing.runs,
+            failures: timing.failures,
+            error: timing.last_error.clone(),
+        }
+    }
+}
+
+/// The app's own health at one point in time, one line of telemetry.jsonl
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetrySnapshot {
+    /// RFC 3339 local time
+    pub time: String,
+    pub uptime_secs: u64,
+    /// Resident memory of the whole process
+    pub rss_bytes: Option<u64>,
+    /// Rendering one frame
+    pub draw: Span,
+    /// One update tick, i.e. running the screen's collectors
+    pub update: Span,
+    pub collectors: Vec<CollectorSample>,
+    pub buffers: Vec<BufferUsage>,
+    /// Lines pushed out of the full log buffer
+    pub log_lines_evicted: u64,
+    /// Journal reads that hit the buffer size, skipping older lines of a burst
+    pub journal_reads_truncated: u64,
+}
+
+/// Tracks frame and update times and appends a snapshot of the app's
+/// health to telemetry.jsonl in the state directory every interval, so a
+/// slow or growing TUI can be diagnosed after the fact
+pub struct Telemetry {
+    started: Instant,
+    pub draw: Span,
+    pub update: Span,
+    path: PathBuf,
+    /// None disables the file
+    interval: Option<Duration>,
+    last_write: Instant,
+}
+
+impl Telemetry {
+    pub fn new(interval_secs: u64) -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            draw: Span::default(),
+            update: Span::default(),
+            path: Config::state_dir().join("telemetry.jsonl"),
+            interval: (interval_secs > 0).then(|| Duration::from_secs(interval_secs)),
+            last_write: now,
+        }
+    }
+
+    pub fn record_draw(&mut self, elapsed: Duration) {
+        self.draw.record(elapsed);
+    }
+
+    pub fn record_update(&mut self, elapsed: Duration) {
+        self.update.record(elapsed);
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Whether a snapshot is due; restarts the interval when it is
+    pub fn due(&mut self) -> bool {
+        match self.interval {
+            Some(interval) if self.last_write.elapsed() >= interval => {
+                self.last_write = Instant::now();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn write(&self, snapshot: &TelemetrySnapshot) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+        }
+        if fs::metadata(&self.path).is_ok_and(|meta| meta.len() > MAX_FILE_BYTES) {
+            let rotated = self.path.with_extension("jsonl.1");
+            fs::rename(&self.path, &rotated).with_context(|| format!("Failed to rotate {:?}", self.path))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {:?}", self.path))?;
+        writeln!(file, "{}", serde_json::to_string(snapshot)?)
+            .with_context(|| format!("Failed to write {:?}", self.path))
+    }
+}
+
+/// Resident set size from /proc/self/statm
+pub fn rss_bytes() -> Option<u64> {
+    let statm = fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    Some(pages * page_size.max(0) as u64)
+}
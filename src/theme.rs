@@ -0,0 +1,208 @@
+use crate::alerts::{Alert, AlertCategory, AlertLevel};
+use crate::config::{AlertStyleConfig, AlertStylesConfig};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::str::FromStr;
+use std::io::{Read, Write};
+use std::os::fd::AsRawFd;
+use std::time::{Duration, Instant};
+
+/// How long to wait for the terminal to answer the background color query
+const QUERY_TIMEOUT: Duration = Duration::from_millis(150);
+
+/// Color scheme, from `display.theme`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    /// Detect the terminal background at startup
+    #[default]
+    #[serde(alias = "default")]
+    Auto,
+    Dark,
+    Light,
+}
+
+impl Theme {
+    /// Resolve `Auto` by asking the terminal (OSC 11), then `$COLORFGBG`,
+    /// falling back to dark. Must run in raw mode, before input is read.
+    pub fn resolve(self) -> Theme {
+        match self {
+            Theme::Auto => detect().unwrap_or(Theme::Dark),
+            theme => theme,
+        }
+    }
+}
+
+fn detect() -> Option<Theme> {
+    match query_background() {
+        Some(luminance) => {
+            tracing::debug!("Terminal background luminance {:.2}", luminance);
+            Some(if luminance > 0.5 { Theme::Light } else { Theme::Dark })
+        }
+        None => from_colorfgbg(),
+    }
+}
+
+/// Ask the terminal for its background color and return its relative
+/// luminance (0 black .. 1 white). Terminals that don't support OSC 11
+/// simply don't answer, so the read is bounded by `QUERY_TIMEOUT`.
+fn query_background() -> Option<f64> {
+    let mut tty = OpenOptions::new().read(true).write(true).open("/dev/tty").ok()?;
+    tty.write_all(b"\x1b]11;?\x07").ok()?;
+    tty.flush().ok()?;
+
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    let mut response = Vec::new();
+    let mut buf = [0u8; 64];
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return None;
+        }
+        let mut pollfd = libc::pollfd { fd: tty.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+        // SAFETY: one valid pollfd for the lifetime of the call
+        let ready = unsafe { libc::poll(&mut pollfd, 1, remaining.as_millis() as libc::c_int) };
+        if ready <= 0 {
+            return None;
+        }
+        let n = tty.read(&mut buf).ok()?;
+        if n == 0 {
+            return None;
+        }
+        response.extend_from_slice(&buf[..n]);
+        // Answers end with BEL or ST (ESC \)
+        if response.ends_with(b"\x07") || response.ends_with(b"\x1b\\") {
+            break;
+        }
+    }
+
+    parse_osc11(&String::from_utf8_lossy(&response))
+}
+
+/// "ESC ] 11 ; rgb:RRRR/GGGG/BBBB BEL", with 1-4 hex digits per channel
+fn parse_osc11(response: &str) -> Option<f64> {
+    let rgb = response.split("rgb:").nth(1)?;
+    let rgb = rgb.trim_end_matches(['\x07', '\\', '\x1b']);
+    let channels: Vec<f64> = rgb
+        .split('/')
+        .map(|hex| {
+            let max = 16f64.powi(hex.len() as i32) - 1.0;
+            u32::from_str_radix(hex, 16).ok().map(|v| v as f64 / max)
+        })
+        .collect::<Option<_>>()?;
+    let [r, g, b] = channels[..] else {
+        return None;
+    };
+    Some(0.2126 * r + 0.7152 * g + 0.0722 * b)
+}
+
+/// rxvt-style "fg;bg" palette indices; 7 and 9-15 are light backgrounds
+fn from_colorfgbg() -> Option<Theme> {
+    let value = std::env::var("COLORFGBG").ok()?;
+    let bg: u8 = value.rsplit(';').next()?.parse().ok()?;
+    Some(if bg == 7 || bg >= 9 { Theme::Light } else { Theme::Dark })
+}
+
+/// Alert colors and icons after `[display.alert_styles]`, used by the
+/// banner, the alert panels and the footer badges alike
+#[derive(Debug, Clone)]
+pub struct AlertStyles {
+    levels: Vec<(AlertLevel, Color, Option<String>)>,
+    categories: Vec<(AlertCategory, Option<Color>, Option<String>)>,
+}
+
+impl AlertStyles {
+    /// Unknown keys and colors that don't parse are reported by config
+    /// validation and left at the defaults here
+    pub fn new(config: &AlertStylesConfig) -> Self {
+        let lookup = |map: &std::collections::BTreeMap<String, AlertStyleConfig>, key: &str| {
+            map.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, style)| style.clone())
+        };
+        let levels = [
+            (AlertLevel::Critical, Color::Red),
+            (AlertLevel::Error, Color::LightRed),
+            (AlertLevel::Warning, Color::Yellow),
+            (AlertLevel::Info, Color::Cyan),
+        ]
+        .into_iter()
+        .map(|(level, default)| {
+            let style = lookup(&config.levels, level.as_str()).unwrap_or_default();
+            let color = style.color.as_deref().and_then(parse_color).unwrap_or(default);
+            (level, color, style.icon)
+        })
+        .collect();
+        let categories = ALERT_CATEGORIES
+            .into_iter()
+            .filter_map(|category| {
+                let style = lookup(&config.categories, category.as_str())?;
+                Some((category, style.color.as_deref().and_then(parse_color), style.icon))
+            })
+            .collect();
+        Self { levels, categories }
+    }
+
+    pub fn level_color(&self, level: AlertLevel) -> Color {
+        self.levels.iter().find(|(l, _, _)| *l == level).map(|(_, color, _)| *color).unwrap_or(Color::Gray)
+    }
+
+    /// Configured icon of a level; None keeps the glyph set's
+    pub fn level_icon(&self, level: AlertLevel) -> Option<&str> {
+        self.levels.iter().find(|(l, _, _)| *l == level).and_then(|(_, _, icon)| icon.as_deref())
+    }
+
+    /// Color of one alert: its category's if set, else its level's
+    pub fn color(&self, alert: &Alert) -> Color {
+        self.category(alert.category)
+            .and_then(|(_, color, _)| *color)
+            .unwrap_or_else(|| self.level_color(alert.level))
+    }
+
+    /// Icon of one alert: its category's if set, else its level's
+    pub fn icon(&self, alert: &Alert) -> Option<&str> {
+        self.category(alert.category)
+            .and_then(|(_, _, icon)| icon.as_deref())
+            .or_else(|| self.level_icon(alert.level))
+    }
+
+    fn category(&self, category: AlertCategory) -> Option<&(AlertCategory, Option<Color>, Option<String>)> {
+        self.categories.iter().find(|(c, _, _)| *c == category)
+    }
+}
+
+const ALERT_CATEGORIES: [AlertCategory; 6] = [
+    AlertCategory::System,
+    AlertCategory::Network,
+    AlertCategory::Kubernetes,
+    AlertCategory::KubeVirt,
+    AlertCategory::Service,
+    AlertCategory::External,
+];
+
+/// Color name, "#rrggbb" or 256-color index, as ratatui spells them
+pub fn parse_color(value: &str) -> Option<Color> {
+    Color::from_str(value.trim()).ok()
+}
+
+/// Readable text on a badge filled with `background`
+pub fn badge_text(background: Color) -> Color {
+    let light = match background {
+        Color::Rgb(r, g, b) => 0.2126 * r as f64 + 0.7152 * g as f64 + 0.0722 * b as f64 > 140.0,
+        Color::Yellow
+        | Color::Cyan
+        | Color::Green
+        | Color::White
+        | Color::Gray
+        | Color::LightYellow
+        | Color::LightCyan
+        | Color::LightGreen
+        | Color::LightBlue
+        | Color::LightMagenta => true,
+        _ => false,
+    };
+    if light {
+        Color::Black
+    } else {
+        Color::White
+    }
+}
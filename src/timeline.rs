@@ -0,0 +1,181 @@
+use crate::alerts::{Alert, AlertLevel};
+use crate::collectors::parse_log_timestamp;
+use crate::types::{LogEntry, SystemMetrics};
+use chrono::{DateTime, Local};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+const MAX_EVENTS: usize = 2000; // Log and metric events kept for the timeline
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineSource {
+    Alert,
+    Log,
+    Metric,
+}
+
+impl TimelineSource {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TimelineSource::Alert => "ALERT",
+            TimelineSource::Log => "LOG",
+            TimelineSource::Metric => "METRIC",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TimelineEvent {
+    pub at: DateTime<Local>,
+    pub source: TimelineSource,
+    pub level: AlertLevel,
+    pub summary: String,
+}
+
+/// Warning thresholds whose crossings are recorded as metric events
+#[derive(Debug, Clone, Copy)]
+pub struct MetricThresholds {
+    pub cpu: f64,
+    pub memory: f64,
+    pub disk: f64,
+    pub load: f64,
+}
+
+/// Chronological record of ERROR logs and metric threshold crossings.
+/// Alert transitions are merged in from the alert manager when read, so
+/// they are never stored twice.
+pub struct Timeline {
+    events: VecDeque<TimelineEvent>,
+    seen_logs: HashSet<String>,
+    above: HashMap<&'static str, bool>,
+    thresholds: MetricThresholds,
+}
+
+impl Timeline {
+    pub fn new(thresholds: MetricThresholds) -> Self {
+        Self {
+            events: VecDeque::with_capacity(MAX_EVENTS),
+            seen_logs: HashSet::new(),
+            above: HashMap::new(),
+            thresholds,
+        }
+    }
+
+    /// Stored events and their approximate size in bytes
+    pub fn usage(&self) -> (usize, usize) {
+        let bytes = self.events.iter().map(|e| std::mem::size_of::<TimelineEvent>() + e.summary.len()).sum::<usize>()
+            + self.seen_logs.iter().map(|key| std::mem::size_of::<String>() + key.len()).sum::<usize>();
+        (self.events.len(), bytes)
+    }
+
+    /// Thresholds changed, e.g. by switching alert profile
+    pub fn set_thresholds(&mut self, thresholds: MetricThresholds) {
+        self.thresholds = thresholds;
+    }
+
+    /// Record ERROR-level entries not seen before
+    pub fn record_logs(&mut self, logs: &[Arc<LogEntry>]) {
+        for entry in logs {
+            if !matches!(entry.level.as_str(), "ERROR" | "CRITICAL") {
+                continue;
+            }
+
+            let key = format!("{}|{}|{}", entry.timestamp, entry.service, entry.message);
+            if !self.seen_logs.insert(key) {
+                continue;
+            }
+
+            self.push(TimelineEvent {
+                at: parse_log_timestamp(&entry.timestamp).unwrap_or_else(Local::now),
+                source: TimelineSource::Log,
+                level: AlertLevel::Error,
+                summary: format!("{}: {}", entry.service, entry.message),
+            });
+        }
+
+        // The dedup set only needs to cover what the log buffer can still return
+        if self.seen_logs.len() > MAX_EVENTS * 4 {
+            self.seen_logs.clear();
+            for entry in logs {
+                self.seen_logs
+                    .insert(format!("{}|{}|{}", entry.timestamp, entry.service, entry.message));
+            }
+        }
+    }
+
+    /// Record metrics crossing their warning threshold in either direction
+    pub fn record_metrics(&mut self, metrics: &SystemMetrics) {
+        let memory_percent = if metrics.memory_total_gb > 0.0 {
+            (metrics.memory_used_gb / metrics.memory_total_gb) * 100.0
+        } else {
+            0.0
+        };
+
+        let samples = [
+            ("cpu", "CPU", metrics.cpu_usage, self.thresholds.cpu, "%"),
+            ("memory", "Memory", memory_percent, self.thresholds.memory, "%"),
+            ("disk", "Disk", metrics.disk_usage_percent, self.thresholds.disk, "%"),
+            ("load", "Load", metrics.load_avg, self.thresholds.load, ""),
+        ];
+
+        for (metric, label, value, threshold, unit) in samples {
+            let is_above = value >= threshold;
+            let was_above = self.above.insert(metric, is_above).unwrap_or(false);
+            if is_above == was_above {
+                continue;
+            }
+
+            let (level, summary) = if is_above {
+                (
+                    AlertLevel::Warning,
+                    format!("{} rose to {:.1}{} (threshold {:.1}{})", label, value, unit, threshold, unit),
+                )
+            } else {
+                (
+                    AlertLevel::Info,
+                    format!("{} back to {:.1}{} (threshold {:.1}{})", label, value, unit, threshold, unit),
+                )
+            };
+
+            self.push(TimelineEvent {
+                at: Local::now(),
+                source: TimelineSource::Metric,
+                level,
+                summary,
+            });
+        }
+    }
+
+    /// All events in chronological order, including alert fire/resolve
+    /// transitions from the given active and historical alerts
+    pub fn events<'a>(&self, alerts: impl IntoIterator<Item = &'a Alert>) -> Vec<TimelineEvent> {
+        let mut events: Vec<TimelineEvent> = self.events.iter().cloned().collect();
+
+        for alert in alerts {
+            events.push(TimelineEvent {
+                at: alert.triggered_at,
+                source: TimelineSource::Alert,
+                level: alert.level,
+                summary: format!("{} fired: {}", alert.title, alert.message),
+            });
+            if let Some(resolved_at) = alert.resolved_at {
+                events.push(TimelineEvent {
+                    at: resolved_at,
+                    source: TimelineSource::Alert,
+                    level: AlertLevel::Info,
+                    summary: format!("{} resolved", alert.title),
+                });
+            }
+        }
+
+        events.sort_by_key(|e| e.at);
+        events
+    }
+
+    fn push(&mut self, event: TimelineEvent) {
+        if self.events.len() >= MAX_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+}
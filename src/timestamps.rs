@@ -0,0 +1,117 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use std::path::Path;
+use std::sync::{PoisonError, RwLock};
+
+use crate::collectors::parse_log_timestamp;
+use crate::config::DisplayConfig;
+
+/// Where chrono looks up `display.time_zone` names
+const ZONEINFO_DIRS: [&str; 4] = ["/usr/share/zoneinfo", "/share/zoneinfo", "/etc/zoneinfo", "/usr/share/lib/zoneinfo"];
+
+/// How timestamps in logs, alerts, the timeline and history charts are
+/// written, from `display.timestamp_format`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimestampFormat {
+    /// Each view's own: "%b %d %H:%M:%S" for logs, full dates in details
+    Default,
+    /// "2024-05-01T13:02:11+00:00"
+    Iso8601,
+    /// "2m ago"
+    Relative,
+    /// A strftime pattern
+    Custom(String),
+}
+
+impl TimestampFormat {
+    pub fn parse(value: &str) -> Self {
+        match value.trim() {
+            "" | "default" => TimestampFormat::Default,
+            "iso8601" | "iso" => TimestampFormat::Iso8601,
+            "relative" => TimestampFormat::Relative,
+            pattern => TimestampFormat::Custom(pattern.to_string()),
+        }
+    }
+}
+
+/// The configured format; like the locale it is process-wide, so views
+/// don't each carry it
+static FORMAT: RwLock<TimestampFormat> = RwLock::new(TimestampFormat::Default);
+
+/// Take `display.time_zone` and `display.timestamp_format`. The zone is
+/// set as `$TZ`, which every local time (and child process) then follows.
+pub fn apply(display: &DisplayConfig) {
+    match display.time_zone.trim() {
+        "" | "local" => {}
+        zone if zone.eq_ignore_ascii_case("utc") => std::env::set_var("TZ", "UTC0"),
+        zone => std::env::set_var("TZ", format!(":{}", zone)),
+    }
+    // A bad pattern would panic when formatting; --check-config names it
+    let format = match timestamp_format_problem(&display.timestamp_format) {
+        Some(_) => TimestampFormat::Default,
+        None => TimestampFormat::parse(&display.timestamp_format),
+    };
+    *FORMAT.write().unwrap_or_else(PoisonError::into_inner) = format;
+}
+
+/// `at` in the configured format, or in the view's `default` pattern
+pub fn format_time(at: DateTime<Local>, default: &str) -> String {
+    match &*FORMAT.read().unwrap_or_else(PoisonError::into_inner) {
+        TimestampFormat::Default => at.format(default).to_string(),
+        TimestampFormat::Iso8601 => at.format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+        TimestampFormat::Relative => relative(Local::now() - at),
+        TimestampFormat::Custom(pattern) => at.format(pattern).to_string(),
+    }
+}
+
+/// Timestamps already read as an age
+pub fn is_relative() -> bool {
+    *FORMAT.read().unwrap_or_else(PoisonError::into_inner) == TimestampFormat::Relative
+}
+
+/// A log entry's journal timestamp in the configured format; kept as
+/// collected when that is the default or it doesn't parse
+pub fn format_log_time(timestamp: &str) -> String {
+    if *FORMAT.read().unwrap_or_else(PoisonError::into_inner) == TimestampFormat::Default {
+        return timestamp.to_string();
+    }
+    match parse_log_timestamp(timestamp) {
+        Some(at) => format_time(at, "%b %d %H:%M:%S"),
+        None => timestamp.to_string(),
+    }
+}
+
+/// "45s ago", "2m ago", "3h ago", "5d ago"
+fn relative(age: chrono::Duration) -> String {
+    let secs = age.num_seconds();
+    match secs {
+        i64::MIN..=0 => "now".to_string(),
+        1..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86_399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+/// Why `display.time_zone` can't be used, if it can't
+pub fn time_zone_problem(zone: &str) -> Option<String> {
+    let zone = zone.trim();
+    if zone.is_empty() || zone == "local" || zone.eq_ignore_ascii_case("utc") {
+        return None;
+    }
+    if zone.split('/').any(|part| part.is_empty() || part == "..") {
+        return Some(format!("\"{}\" is not a time zone name", zone));
+    }
+    let found = ZONEINFO_DIRS.iter().any(|dir| Path::new(dir).join(zone).is_file());
+    (!found).then(|| format!("unknown time zone \"{}\" (use local, utc or a name like Europe/Berlin)", zone))
+}
+
+/// Why `display.timestamp_format` can't be used, if it can't
+pub fn timestamp_format_problem(format: &str) -> Option<String> {
+    let TimestampFormat::Custom(pattern) = TimestampFormat::parse(format) else {
+        return None;
+    };
+    StrftimeItems::new(&pattern)
+        .any(|item| matches!(item, Item::Error))
+        .then(|| format!("\"{}\" is not a strftime pattern (or default, iso8601, relative)", pattern))
+}
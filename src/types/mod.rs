@@ -1,11 +1,64 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LogEntry {
     pub timestamp: String,
     pub level: String,
     pub service: String,
     pub message: String,
+
+    /// Additional identical entries folded into this one by burst collapsing
+    #[serde(default)]
+    pub repeats: u32,
+
+    /// Timestamp of the last folded entry, when `repeats > 0`
+    #[serde(default)]
+    pub last_timestamp: Option<String>,
+
+    /// Emitting component of a klog/logfmt line (Go file or logger name)
+    #[serde(default)]
+    pub component: Option<String>,
+
+    /// key=value fields of a klog/logfmt line, in order
+    #[serde(default)]
+    pub fields: Vec<(String, String)>,
+
+    /// Process that logged the line (journald's `_PID`)
+    #[serde(default)]
+    pub pid: Option<String>,
+
+    /// Journal cursor of the line, to look up its full record
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+impl LogEntry {
+    /// Value of a structured field; `level`, `service` and `component`
+    /// are addressable the same way
+    pub fn field(&self, key: &str) -> Option<&str> {
+        match key {
+            "level" => Some(&self.level),
+            "service" => Some(&self.service),
+            "component" => self.component.as_deref(),
+            "pid" => self.pid.as_deref(),
+            _ => self.fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str()),
+        }
+    }
+
+    /// Heap and inline size, for the telemetry buffer figures
+    pub fn approx_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.timestamp.len()
+            + self.level.len()
+            + self.service.len()
+            + self.message.len()
+            + self.last_timestamp.as_ref().map_or(0, String::len)
+            + self.component.as_ref().map_or(0, String::len)
+            + self.pid.as_ref().map_or(0, String::len)
+            + self.cursor.as_ref().map_or(0, String::len)
+            + self.fields.iter().map(|(k, v)| std::mem::size_of::<(String, String)>() + k.len() + v.len()).sum::<usize>()
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -18,11 +71,224 @@ pub struct SystemMetrics {
     pub disk_usage_percent: f64,
     pub load_avg: f64,
     pub uptime_seconds: u64,
+    pub temperatures: Vec<SensorReading>,
+    /// Tracked filesystems, for per-mount forecasts
+    pub filesystems: Vec<FilesystemUsage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FilesystemUsage {
+    pub mount_point: String,
+    pub device: String,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl FilesystemUsage {
+    pub fn used_percent(&self) -> f64 {
+        if self.total_bytes == 0 {
+            return 0.0;
+        }
+        (self.total_bytes - self.available_bytes.min(self.total_bytes)) as f64 / self.total_bytes as f64 * 100.0
+    }
+}
+
+/// A hardware temperature sensor (hwmon/thermal zone)
+#[derive(Debug, Clone)]
+pub struct SensorReading {
+    pub label: String,
+    pub celsius: f32,
+    pub critical: Option<f32>,
+}
+
+/// Package power draw, CPU frequency scaling and idle-state residency
+#[derive(Debug, Clone, Default)]
+pub struct PowerInfo {
+    /// RAPL domain name (package-0, dram, ...) and its draw in watts
+    pub domains: Vec<(String, f64)>,
+    /// Sum of the package domains; None without RAPL access
+    pub package_watts: Option<f64>,
+    pub freq_avg_mhz: f64,
+    pub freq_min_mhz: f64,
+    pub freq_max_mhz: f64,
+    pub governor: Option<String>,
+    /// Share of time spent in each idle state across all CPUs, busy ("C0") first
+    pub cstates: Vec<(String, f64)>,
+    /// Thermal throttle events since startup
+    pub throttle_events: u64,
+    /// Each logical CPU, in order
+    pub cores: Vec<CoreFreq>,
+    /// Guaranteed (non-turbo) clock, where the cpufreq driver reports it
+    pub base_mhz: Option<f64>,
+    /// Turbo/boost enabled; None when the driver doesn't say
+    pub turbo: Option<bool>,
+}
+
+impl PowerInfo {
+    /// Cores busy over half of the last interval, running below `percent`
+    /// of the base clock (or of their maximum when the base is unknown)
+    pub fn throttled_cores(&self, percent: f64) -> Vec<&CoreFreq> {
+        self.cores
+            .iter()
+            .filter(|core| core.is_busy())
+            .filter(|core| {
+                let reference = self.base_mhz.unwrap_or(core.max_mhz);
+                reference > 0.0 && core.cur_mhz < reference * percent / 100.0
+            })
+            .collect()
+    }
+
+    pub fn busy_cores(&self) -> usize {
+        self.cores.iter().filter(|core| core.is_busy()).count()
+    }
+
+    /// At least half of the busy cores are held below `percent` of base
+    /// clock; an idle host clocking down is not throttled
+    pub fn is_throttled(&self, percent: f64) -> bool {
+        let throttled = self.throttled_cores(percent).len();
+        throttled > 0 && throttled * 2 >= self.busy_cores()
+    }
+}
+
+/// Frequency scaling state of one logical CPU
+#[derive(Debug, Clone, Default)]
+pub struct CoreFreq {
+    pub cpu: u32,
+    pub cur_mhz: f64,
+    /// cpuinfo_max_freq, turbo included where enabled
+    pub max_mhz: f64,
+    pub governor: Option<String>,
+    /// Share of the last interval not spent idle; None on the first sample
+    pub busy_percent: Option<f64>,
+}
+
+impl CoreFreq {
+    pub fn is_busy(&self) -> bool {
+        self.busy_percent.is_some_and(|busy| busy >= 50.0)
+    }
+}
+
+/// Host-level state: cluster service units and the NixOS system generation
+#[derive(Debug, Clone, Default)]
+pub struct HostInfo {
+    /// systemd unit name and its `systemctl is-active` state
+    pub services: Vec<(String, String)>,
+    pub nixos_version: Option<String>,
+    pub generation: Option<u32>,
+    /// Store paths of /run/current-system and /run/booted-system
+    pub current_system: Option<String>,
+    pub booted_system: Option<String>,
+}
+
+impl HostInfo {
+    /// A newer system has been activated than the one that was booted
+    pub fn reboot_pending(&self) -> bool {
+        matches!((&self.current_system, &self.booted_system), (Some(c), Some(b)) if c != b)
+    }
+}
+
+/// Firmware, microcode and virtualization support of this host, from DMI,
+/// /proc/cpuinfo and sysfs
+#[derive(Debug, Clone, Default)]
+pub struct HardwareInfo {
+    /// Vendor and product, e.g. "Dell Inc. PowerEdge R650"
+    pub system: Option<String>,
+    pub bios_vendor: Option<String>,
+    pub bios_version: Option<String>,
+    pub bios_date: Option<String>,
+    pub cpu_model: Option<String>,
+    /// Microcode revision loaded on the first CPU, e.g. "0xd0003a5"
+    pub microcode: Option<String>,
+    /// "VT-x" or "AMD-V"; None when the CPU doesn't advertise either,
+    /// usually because firmware has it switched off
+    pub virtualization: Option<&'static str>,
+    /// /dev/kvm exists
+    pub kvm: bool,
+    /// The kernel registered an IOMMU (DMAR/AMD-Vi)
+    pub iommu: bool,
+    /// kvm_intel/kvm_amd `nested` parameter; None without the module
+    pub nested: Option<bool>,
+    /// What the previous run of the TUI recorded, to tell a setting lost in
+    /// a firmware update from one that was never on
+    pub baseline: Option<HardwareBaseline>,
+}
+
+impl HardwareInfo {
+    /// The BIOS version before the last change seen, if it changed
+    pub fn previous_bios(&self) -> Option<&str> {
+        self.baseline.as_ref()?.previous_bios_version.as_deref()
+    }
+}
+
+/// Firmware version and virtualization features as last recorded in
+/// hardware.json. Features stay recorded as enabled once seen, so losing
+/// them keeps alerting until they are back.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareBaseline {
+    pub bios_version: Option<String>,
+    /// The version before `bios_version`, once it has changed
+    #[serde(default)]
+    pub previous_bios_version: Option<String>,
+    #[serde(default)]
+    pub virtualization: bool,
+    #[serde(default)]
+    pub iommu: bool,
+}
+
+/// What kind of link an interface is, read from sysfs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InterfaceClass {
+    Physical,
+    Bond,
+    Vlan,
+    Bridge,
+    Veth,
+    Tap,
+    Tun,
+    /// Any other software device: vxlan, dummy, macvlan, wireguard...
+    Virtual,
+    Loopback,
+}
+
+impl InterfaceClass {
+    /// Display order on the Network screen, the same as `Ord`
+    pub const ALL: [InterfaceClass; 9] = [
+        InterfaceClass::Physical,
+        InterfaceClass::Bond,
+        InterfaceClass::Vlan,
+        InterfaceClass::Bridge,
+        InterfaceClass::Veth,
+        InterfaceClass::Tap,
+        InterfaceClass::Tun,
+        InterfaceClass::Virtual,
+        InterfaceClass::Loopback,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InterfaceClass::Physical => "physical",
+            InterfaceClass::Bond => "bond",
+            InterfaceClass::Vlan => "vlan",
+            InterfaceClass::Bridge => "bridge",
+            InterfaceClass::Veth => "veth",
+            InterfaceClass::Tap => "tap",
+            InterfaceClass::Tun => "tun",
+            InterfaceClass::Loopback => "loopback",
+            InterfaceClass::Virtual => "virtual",
+        }
+    }
+
+    /// Hidden by `network.show_virtual = false`: the per-pod and per-VM
+    /// plumbing rather than links an admin configured
+    pub fn is_virtual(&self) -> bool {
+        matches!(self, InterfaceClass::Veth | InterfaceClass::Tap | InterfaceClass::Tun | InterfaceClass::Virtual)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct NetworkInterface {
     pub name: String,
+    pub class: InterfaceClass,
     pub ip_address: String,
     pub is_up: bool,
     pub speed: String,
@@ -31,6 +297,53 @@ pub struct NetworkInterface {
     pub mtu: u32,
 }
 
+/// Friendly names for host interfaces and bridges (`network.interface_labels`),
+/// so "enp65s0f0" reads as "storage-10G" on the Network screen and in alerts
+#[derive(Debug, Clone, Default)]
+pub struct InterfaceLabels(BTreeMap<String, String>);
+
+impl InterfaceLabels {
+    pub fn new(labels: BTreeMap<String, String>) -> Self {
+        Self(labels)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// "storage-10G (enp65s0f0)", or the name alone when it has no label
+    pub fn describe(&self, name: &str) -> String {
+        match self.get(name) {
+            Some(label) => format!("{} ({})", label, name),
+            None => name.to_string(),
+        }
+    }
+
+    /// `text` with each labelled interface it names, as a whole word,
+    /// followed by its label in brackets: "enp65s0f0 [storage-10G]: Reset adapter"
+    pub fn annotate(&self, text: &str) -> String {
+        let is_name_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '@');
+        let mut annotated = text.to_string();
+        for (name, label) in &self.0 {
+            let mut from = 0;
+            while let Some(found) = annotated[from..].find(name.as_str()) {
+                let start = from + found;
+                let end = start + name.len();
+                let whole_word = !annotated[..start].ends_with(is_name_char)
+                    && !annotated[end..].starts_with(is_name_char);
+                if whole_word {
+                    let tag = format!(" [{}]", label);
+                    annotated.insert_str(end, &tag);
+                    from = end + tag.len();
+                } else {
+                    from = end;
+                }
+            }
+        }
+        annotated
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct NetworkInfo {
     pub interfaces: Vec<NetworkInterface>,
@@ -39,9 +352,378 @@ pub struct NetworkInfo {
     pub cni: String,
     pub active_connections: u32,
     pub k8s_services: u32,
+    /// Network namespaces of virt-launcher pods, when `network.vm_namespaces`
+    pub vm_namespaces: Vec<VmNetns>,
+    /// Interfaces of each class on the host, including those the
+    /// `[network]` filters hide, in `InterfaceClass::ALL` order
+    pub interface_counts: Vec<(InterfaceClass, usize)>,
+    /// Interfaces left out of `interfaces` by the filters
+    pub hidden_interfaces: usize,
+    /// MTU pods (and VMs on the pod network) get: `network.expected_mtu`,
+    /// else what the CNI was configured with
+    pub cni_mtu: Option<u32>,
+    /// Links along the VM data path whose MTU doesn't match the link they
+    /// depend on, checked across every host interface whatever the filters
+    pub mtu_mismatches: Vec<MtuMismatch>,
+}
+
+/// An interface whose MTU differs from the one it has to match: a bridge
+/// port or bond member from its master, a VLAN above its parent, or a pod
+/// network link from the CNI's
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MtuMismatch {
+    /// Host interface, or "namespace/vm eth0" inside a virt-launcher pod
+    pub interface: String,
+    pub mtu: u32,
+    /// "bridge br0", "bond bond0", "parent enp65s0f0" or "CNI"
+    pub against: String,
+    pub expected: u32,
+}
+
+/// Network namespace of a virt-launcher pod and the interfaces inside it,
+/// which the host's /sys/class/net doesn't show
+#[derive(Debug, Clone)]
+pub struct VmNetns {
+    /// VM as namespace/name, from virt-launcher's command line
+    pub vm: String,
+    /// virt-launcher process the namespace was found through
+    pub pid: u32,
+    /// "net:[4026532890]"
+    pub netns: String,
+    pub interfaces: Vec<NetnsInterface>,
+    /// Why link state and addresses are missing (the namespace couldn't be
+    /// entered); counters are read regardless
+    pub error: Option<String>,
+}
+
+/// An interface as seen from inside a namespace; counters are cumulative
+#[derive(Debug, Clone, Default)]
+pub struct NetnsInterface {
+    pub name: String,
+    /// None when the namespace couldn't be entered
+    pub is_up: Option<bool>,
+    pub mtu: Option<u32>,
+    /// IPv4 and IPv6 addresses with prefix length
+    pub addresses: Vec<String>,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_dropped: u64,
+    pub tx_dropped: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct DnsResolverStatus {
+    pub label: String,  // "host" (resolv.conf) or "coredns"
+    pub server: String,
+    pub latency_ms: Option<f64>,
+    pub success_rate: f64,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+/// One route of the host's main table, from `ip -j route`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteEntry {
+    /// "default" or a prefix such as "10.42.0.0/24"
+    pub destination: String,
+    pub gateway: Option<String>,
+    pub device: String,
+    /// "kernel", "static", "dhcp", "bird", ...
+    pub protocol: Option<String>,
+    pub metric: Option<u32>,
+    pub source: Option<String>,
+}
+
+impl RouteEntry {
+    pub fn is_default(&self) -> bool {
+        self.destination == "default"
+    }
+
+    /// "default via 192.168.1.1 dev br0", as `ip route` would print it
+    pub fn describe(&self) -> String {
+        let mut text = self.destination.clone();
+        if let Some(ref gateway) = self.gateway {
+            text.push_str(&format!(" via {}", gateway));
+        }
+        text.push_str(&format!(" dev {}", self.device));
+        text
+    }
+}
+
+/// A default route's gateway, pinged every cycle
+#[derive(Debug, Clone)]
+pub struct GatewayStatus {
+    pub address: String,
+    pub device: String,
+    pub latency_ms: Option<f64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+/// The host's routes, its default gateways and how often they changed
+#[derive(Debug, Clone, Default)]
+pub struct RouteStatus {
+    pub routes: Vec<RouteEntry>,
+    pub gateways: Vec<GatewayStatus>,
+    /// A default route was seen since startup, so its absence is a loss
+    pub had_default: bool,
+    /// Times the set of default routes changed within the flap window
+    pub default_changes: u32,
+    /// Most recent additions and removals, newest first, e.g.
+    /// "- default via 192.168.1.1 dev br0"
+    pub recent_changes: Vec<(chrono::DateTime<chrono::Local>, String)>,
+}
+
+impl RouteStatus {
+    pub fn default_routes(&self) -> impl Iterator<Item = &RouteEntry> {
+        self.routes.iter().filter(|route| route.is_default())
+    }
+}
+
+/// Result of probing a configured container registry
+#[derive(Debug, Clone)]
+pub struct RegistryStatus {
+    pub registry: String,
+    pub reachable: bool,
+    pub latency_ms: Option<f64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+/// Pulls of one image seen in Pod events. Events expire after about an
+/// hour, so the counts cover roughly that window.
+#[derive(Debug, Clone)]
+pub struct ImagePull {
+    pub image: String,
+    /// Registry host the image comes from ("docker.io" when unqualified)
+    pub registry: String,
+    pub pulls: u32,
+    pub failures: u32,
+    pub last_pulled: Option<chrono::DateTime<chrono::Local>>,
+    /// How long the last successful pull took
+    pub last_duration: Option<std::time::Duration>,
+    pub last_failed: Option<chrono::DateTime<chrono::Local>>,
+    pub last_error: Option<String>,
+    /// Nodes the pulls happened on
+    pub nodes: Vec<String>,
+}
+
+impl ImagePull {
+    /// Failing now: the latest attempt failed rather than succeeded
+    pub fn is_failing(&self) -> bool {
+        match (self.last_failed, self.last_pulled) {
+            (Some(failed), Some(pulled)) => failed > pulled,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+/// Restarts and waiting state of one pod's containers
+#[derive(Debug, Clone)]
+pub struct PodHealth {
+    pub namespace: String,
+    pub name: String,
+    pub node: Option<String>,
+    /// Restarts summed over its containers
+    pub restarts: u32,
+    /// Container stuck waiting in CrashLoopBackOff, ImagePullBackOff or
+    /// ErrImagePull, with the reason and the kubelet's message
+    pub waiting: Option<PodWaiting>,
+    /// Restarts within the past hour, or since it was first seen; filled
+    /// in by the app, which keeps the history
+    pub recent_restarts: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct PodWaiting {
+    pub container: String,
+    pub reason: String,
+    pub message: Option<String>,
+}
+
+impl PodHealth {
+    pub fn key(&self) -> String {
+        format!("{}/{}", self.namespace, self.name)
+    }
+}
+
+/// What one direction of a VM's traffic is limited to by the
+/// NetworkPolicies selecting its virt-launcher pod
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyAccess {
+    /// No policy restricts this direction, or one allows everything
+    Unrestricted,
+    /// Policies apply to this direction but none allows anything
+    DenyAll,
+    /// Only what these rules allow, e.g. "ns team=web on TCP/443"
+    Allow(Vec<String>),
+}
+
+impl PolicyAccess {
+    pub fn is_restricted(&self) -> bool {
+        *self != PolicyAccess::Unrestricted
+    }
+
+    /// Lines it takes on the Network screen: one per allowed rule
+    pub fn row_count(&self) -> usize {
+        match self {
+            PolicyAccess::Allow(rules) => rules.len().max(1),
+            _ => 1,
+        }
+    }
+}
+
+/// A VM whose virt-launcher pod at least one NetworkPolicy selects
+#[derive(Debug, Clone)]
+pub struct VmNetworkPolicy {
+    /// VM name, or the pod's when it carries no VM label
+    pub vm: String,
+    /// Policies selecting the pod
+    pub policies: Vec<String>,
+    pub ingress: PolicyAccess,
+    pub egress: PolicyAccess,
+    /// Egress is restricted and no rule allows port 53
+    pub dns_blocked: bool,
+}
+
+impl VmNetworkPolicy {
+    /// Nothing gets in or nothing gets out
+    pub fn is_isolated(&self) -> bool {
+        self.ingress == PolicyAccess::DenyAll || self.egress == PolicyAccess::DenyAll
+    }
+}
+
+/// The NetworkPolicies of a namespace running VMs, and what they do to
+/// each VM they select
+#[derive(Debug, Clone)]
+pub struct NamespacePolicies {
+    pub namespace: String,
+    pub policies: Vec<String>,
+    /// virt-launcher pods in the namespace
+    pub vm_count: usize,
+    /// VMs at least one policy selects, by name
+    pub vms: Vec<VmNetworkPolicy>,
+}
+
+/// A CDI DataVolume: a VM disk being imported, cloned or uploaded
+#[derive(Debug, Clone)]
+pub struct DataVolume {
+    pub namespace: String,
+    pub name: String,
+    /// Pending, ImportScheduled, ImportInProgress, CloneInProgress,
+    /// WaitForFirstConsumer, Succeeded, Failed, ...
+    pub phase: String,
+    /// Percent done as reported by CDI; None while it says "N/A"
+    pub progress: Option<f64>,
+    /// http, registry, pvc (a clone), upload, blank, ...
+    pub source: String,
+    /// Importer/cloner pod restarts; CDI retries a bad source forever
+    pub restarts: u32,
+    /// Reason and message of the first false condition, e.g. why the
+    /// importer keeps failing
+    pub message: Option<String>,
+    /// VM whose dataVolumeTemplates created it
+    pub owner: Option<String>,
+    pub created: Option<chrono::DateTime<chrono::Local>>,
+    /// When the phase or progress last changed, tracked across collections
+    pub changed: Option<chrono::DateTime<chrono::Local>>,
+}
+
+impl DataVolume {
+    pub fn key(&self) -> String {
+        format!("{}/{}", self.namespace, self.name)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self.phase.as_str(), "Succeeded" | "Failed")
+    }
+
+    /// Waiting on something outside CDI (a VM to be scheduled), so not
+    /// stuck however long it sits
+    pub fn is_waiting(&self) -> bool {
+        matches!(self.phase.as_str(), "WaitForFirstConsumer" | "PendingPopulation" | "Paused")
+    }
+}
+
+/// One VirtualMachineInstanceMigration, finished or still in flight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationRecord {
+    pub uid: String,
+    /// VMI as namespace/name
+    pub vmi: String,
+    pub source_node: Option<String>,
+    pub target_node: Option<String>,
+    /// Pending, Scheduling, Running, Succeeded, Failed, ...
+    pub phase: String,
+    pub started: Option<chrono::DateTime<chrono::Local>>,
+    pub ended: Option<chrono::DateTime<chrono::Local>>,
+    /// Memory and disk data sent, as last reported by the source node's
+    /// virt-handler while the migration ran
+    pub transferred_bytes: Option<u64>,
+    pub failure_reason: Option<String>,
+}
+
+impl MigrationRecord {
+    pub fn is_finished(&self) -> bool {
+        matches!(self.phase.as_str(), "Succeeded" | "Failed")
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.phase == "Succeeded"
+    }
+
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        Some(self.ended? - self.started?)
+    }
+
+    /// When it finished, or started for ones still running
+    pub fn at(&self) -> Option<chrono::DateTime<chrono::Local>> {
+        self.ended.or(self.started)
+    }
+}
+
+/// One start of a VM: when its VirtualMachineInstance was created, when it
+/// reached Running and when its guest agent connected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootRecord {
+    /// Of the VMI; every start creates a new one
+    pub uid: String,
+    /// VM as namespace/name
+    pub vm: String,
+    pub node: Option<String>,
+    pub requested: chrono::DateTime<chrono::Local>,
+    pub running: Option<chrono::DateTime<chrono::Local>>,
+    /// None for guests without the agent, or whose agent hasn't connected yet
+    pub agent_ready: Option<chrono::DateTime<chrono::Local>>,
+}
+
+impl BootRecord {
+    pub fn to_running(&self) -> Option<chrono::Duration> {
+        Some(self.running? - self.requested)
+    }
+
+    pub fn to_agent(&self) -> Option<chrono::Duration> {
+        Some(self.agent_ready? - self.requested)
+    }
 }
 
+/// A boot that took much longer than the same VM's usual
 #[derive(Debug, Clone)]
+pub struct SlowBoot {
+    pub vm: String,
+    pub node: Option<String>,
+    /// So far, for a VM still not Running
+    pub took: chrono::Duration,
+    pub still_booting: bool,
+    /// Median time to Running of its earlier boots
+    pub baseline: chrono::Duration,
+    pub boots: usize,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct K8sClusterInfo {
     pub nodes_ready: u32,
     pub nodes_total: u32,
@@ -49,9 +731,2336 @@ pub struct K8sClusterInfo {
     pub services: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct KubeVirtInfo {
     pub vms_running: u32,
     pub vms_stopped: u32,
     pub vms_migrating: u32,
 }
+
+/// Kubernetes distribution running on the host
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClusterFlavor {
+    K3s,
+    Rke2,
+    Kubeadm,
+}
+
+impl ClusterFlavor {
+    /// Guess the distribution from the files it leaves on the host
+    pub fn detect() -> Self {
+        let exists = |p: &str| std::path::Path::new(p).exists();
+        if exists("/etc/rancher/rke2/rke2.yaml") || exists("/var/lib/rancher/rke2") {
+            ClusterFlavor::Rke2
+        } else if exists("/etc/rancher/k3s/k3s.yaml") || exists("/var/lib/rancher/k3s") {
+            ClusterFlavor::K3s
+        } else if exists("/etc/kubernetes/admin.conf") || exists("/etc/kubernetes/kubelet.conf") {
+            ClusterFlavor::Kubeadm
+        } else {
+            ClusterFlavor::K3s
+        }
+    }
+
+    /// Header label
+    pub fn label(&self) -> &str {
+        match self {
+            ClusterFlavor::K3s => "K3s",
+            ClusterFlavor::Rke2 => "RKE2",
+            ClusterFlavor::Kubeadm => "K8s",
+        }
+    }
+
+    pub fn kubeconfig_path(&self) -> &str {
+        match self {
+            ClusterFlavor::K3s => "/etc/rancher/k3s/k3s.yaml",
+            ClusterFlavor::Rke2 => "/etc/rancher/rke2/rke2.yaml",
+            ClusterFlavor::Kubeadm => "/etc/kubernetes/admin.conf",
+        }
+    }
+
+    /// systemd units whose journal is shown on the Logs screen
+    pub fn services(&self) -> Vec<String> {
+        let units: &[&str] = match self {
+            ClusterFlavor::K3s => &["k3s", "k3s-agent", "kubelet", "containerd"],
+            ClusterFlavor::Rke2 => &["rke2-server", "rke2-agent", "containerd"],
+            ClusterFlavor::Kubeadm => &["kubelet", "containerd", "crio"],
+        };
+        units
+            .iter()
+            .chain(["virt-handler", "virt-launcher"].iter())
+            .map(|u| u.to_string())
+            .collect()
+    }
+
+    /// The unit logging for the kubelet, matching the agent unit too
+    pub fn kubelet_service(&self) -> &str {
+        match self {
+            ClusterFlavor::K3s => "k3s",
+            ClusterFlavor::Rke2 => "rke2",
+            ClusterFlavor::Kubeadm => "kubelet",
+        }
+    }
+
+    /// Distribution default (pod CIDR, service CIDR) when the cluster can't be asked
+    pub fn default_cidrs(&self) -> (&str, &str) {
+        match self {
+            ClusterFlavor::K3s | ClusterFlavor::Rke2 => ("10.42.0.0/16", "10.43.0.0/16"),
+            ClusterFlavor::Kubeadm => ("10.244.0.0/16", "10.96.0.0/12"),
+        }
+    }
+
+    /// Embedded etcd's data directory; absent when the server runs on
+    /// kine (SQLite) or this is an agent node
+    pub fn etcd_data_dir(&self) -> &str {
+        match self {
+            ClusterFlavor::K3s => "/var/lib/rancher/k3s/server/db/etcd",
+            ClusterFlavor::Rke2 => "/var/lib/rancher/rke2/server/db/etcd",
+            ClusterFlavor::Kubeadm => "/var/lib/etcd",
+        }
+    }
+
+    /// (CA, client certificate, client key) for etcd's TLS client port
+    pub fn etcd_client_certs(&self) -> (&str, &str, &str) {
+        match self {
+            ClusterFlavor::K3s => (
+                "/var/lib/rancher/k3s/server/tls/etcd/server-ca.crt",
+                "/var/lib/rancher/k3s/server/tls/etcd/client.crt",
+                "/var/lib/rancher/k3s/server/tls/etcd/client.key",
+            ),
+            ClusterFlavor::Rke2 => (
+                "/var/lib/rancher/rke2/server/tls/etcd/server-ca.crt",
+                "/var/lib/rancher/rke2/server/tls/etcd/server-client.crt",
+                "/var/lib/rancher/rke2/server/tls/etcd/server-client.key",
+            ),
+            ClusterFlavor::Kubeadm => (
+                "/etc/kubernetes/pki/etcd/ca.crt",
+                "/etc/kubernetes/pki/etcd/healthcheck-client.crt",
+                "/etc/kubernetes/pki/etcd/healthcheck-client.key",
+            ),
+        }
+    }
+
+    /// Default CNI when no CNI config can be found
+    pub fn default_cni(&self) -> &str {
+        match self {
+            ClusterFlavor::K3s => "Flannel",
+            ClusterFlavor::Rke2 => "Canal",
+            ClusterFlavor::Kubeadm => "Unknown",
+        }
+    }
+}
+
+/// An operation applied to many VirtualMachines at once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmAction {
+    Start,
+    Stop,
+    /// Live migrate to whichever node the scheduler picks
+    Migrate,
+    AddLabel { key: String, value: String },
+}
+
+impl VmAction {
+    pub fn label(&self) -> String {
+        match self {
+            VmAction::Start => "Start".to_string(),
+            VmAction::Stop => "Stop".to_string(),
+            VmAction::Migrate => "Migrate".to_string(),
+            VmAction::AddLabel { key, value } => format!("Label {}={}", key, value),
+        }
+    }
+
+    /// What the kubeconfig needs for it
+    pub fn permission(&self) -> Permission {
+        match self {
+            VmAction::Start | VmAction::Stop => Permission::StartStopVms,
+            VmAction::Migrate => Permission::MigrateVms,
+            VmAction::AddLabel { .. } => Permission::PatchVms,
+        }
+    }
+}
+
+/// A cluster operation the UI offers, checked for the current kubeconfig
+/// with a SelfSubjectAccessReview
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    ListNodes,
+    ListPods,
+    ReadPodLogs,
+    ListVms,
+    ListVmis,
+    StartStopVms,
+    MigrateVms,
+    PatchVms,
+    CreateVms,
+    HotplugVolumes,
+    CreateVmis,
+    CreateMigrations,
+    VmConsole,
+    VmVnc,
+    PatchNodes,
+    EvictPods,
+    CreateClaims,
+    DeleteClaims,
+    CreateDataVolumes,
+    DeleteDataVolumes,
+    RestartDaemonSets,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 21] = [
+        Permission::ListNodes,
+        Permission::ListPods,
+        Permission::ReadPodLogs,
+        Permission::ListVms,
+        Permission::ListVmis,
+        Permission::StartStopVms,
+        Permission::MigrateVms,
+        Permission::PatchVms,
+        Permission::CreateVms,
+        Permission::HotplugVolumes,
+        Permission::CreateVmis,
+        Permission::CreateMigrations,
+        Permission::VmConsole,
+        Permission::VmVnc,
+        Permission::PatchNodes,
+        Permission::EvictPods,
+        Permission::CreateClaims,
+        Permission::DeleteClaims,
+        Permission::CreateDataVolumes,
+        Permission::DeleteDataVolumes,
+        Permission::RestartDaemonSets,
+    ];
+
+    /// Verb, API group, resource and subresource reviewed
+    pub fn attributes(self) -> (&'static str, &'static str, &'static str, Option<&'static str>) {
+        match self {
+            Permission::ListNodes => ("list", "", "nodes", None),
+            Permission::ListPods => ("list", "", "pods", None),
+            Permission::ReadPodLogs => ("get", "", "pods", Some("log")),
+            Permission::ListVms => ("list", "kubevirt.io", "virtualmachines", None),
+            Permission::ListVmis => ("list", "kubevirt.io", "virtualmachineinstances", None),
+            // Stop's subresource is checked the same way
+            Permission::StartStopVms => ("update", "subresources.kubevirt.io", "virtualmachines", Some("start")),
+            Permission::MigrateVms => ("update", "subresources.kubevirt.io", "virtualmachines", Some("migrate")),
+            Permission::PatchVms => ("patch", "kubevirt.io", "virtualmachines", None),
+            Permission::CreateVms => ("create", "kubevirt.io", "virtualmachines", None),
+            Permission::HotplugVolumes => ("update", "subresources.kubevirt.io", "virtualmachines", Some("addvolume")),
+            Permission::CreateVmis => ("create", "kubevirt.io", "virtualmachineinstances", None),
+            Permission::CreateMigrations => ("create", "kubevirt.io", "virtualmachineinstancemigrations", None),
+            Permission::VmConsole => ("get", "subresources.kubevirt.io", "virtualmachineinstances", Some("console")),
+            Permission::VmVnc => ("get", "subresources.kubevirt.io", "virtualmachineinstances", Some("vnc")),
+            Permission::PatchNodes => ("patch", "", "nodes", None),
+            Permission::EvictPods => ("create", "", "pods", Some("eviction")),
+            Permission::CreateClaims => ("create", "", "persistentvolumeclaims", None),
+            Permission::DeleteClaims => ("delete", "", "persistentvolumeclaims", None),
+            Permission::CreateDataVolumes => ("create", "cdi.kubevirt.io", "datavolumes", None),
+            Permission::DeleteDataVolumes => ("delete", "cdi.kubevirt.io", "datavolumes", None),
+            Permission::RestartDaemonSets => ("patch", "apps", "daemonsets", None),
+        }
+    }
+
+    /// What is allowed, as read in the UI: "start and stop VMs"
+    pub fn label(self) -> &'static str {
+        match self {
+            Permission::ListNodes => "list nodes",
+            Permission::ListPods => "list pods",
+            Permission::ReadPodLogs => "read pod logs",
+            Permission::ListVms => "list VMs",
+            Permission::ListVmis => "list VM instances",
+            Permission::StartStopVms => "start and stop VMs",
+            Permission::MigrateVms => "migrate VMs",
+            Permission::PatchVms => "edit VMs",
+            Permission::CreateVms => "create VMs",
+            Permission::HotplugVolumes => "hot-plug VM volumes",
+            Permission::CreateVmis => "create VM instances",
+            Permission::CreateMigrations => "create VM migrations",
+            Permission::VmConsole => "open VM serial consoles",
+            Permission::VmVnc => "open VM VNC consoles",
+            Permission::PatchNodes => "cordon and edit nodes",
+            Permission::EvictPods => "evict pods",
+            Permission::CreateClaims => "create volume claims",
+            Permission::DeleteClaims => "delete volume claims",
+            Permission::CreateDataVolumes => "import images as DataVolumes",
+            Permission::DeleteDataVolumes => "delete DataVolumes",
+            Permission::RestartDaemonSets => "restart DaemonSets",
+        }
+    }
+
+    /// Whether it concerns KubeVirt VMs, which another virtualization
+    /// backend answers for instead
+    pub fn is_kubevirt(self) -> bool {
+        self.attributes().1.contains("kubevirt.io")
+    }
+
+    /// "update virtualmachines/start.subresources.kubevirt.io", as RBAC rules name it
+    pub fn rule(self) -> String {
+        let (verb, group, resource, subresource) = self.attributes();
+        let mut rule = format!("{} {}", verb, resource);
+        if let Some(subresource) = subresource {
+            rule.push_str(&format!("/{}", subresource));
+        }
+        if !group.is_empty() {
+            rule.push_str(&format!(".{}", group));
+        }
+        rule
+    }
+}
+
+/// The outcome of reviewing one permission
+#[derive(Debug, Clone)]
+pub struct PermissionCheck {
+    pub permission: Permission,
+    pub allowed: bool,
+    /// The authorizer's reason, or why the review itself failed
+    pub reason: Option<String>,
+}
+
+/// Every reviewed permission, from startup or the last recheck
+#[derive(Debug, Clone)]
+pub struct PermissionAudit {
+    pub checks: Vec<PermissionCheck>,
+    pub checked_at: chrono::DateTime<chrono::Local>,
+}
+
+impl PermissionAudit {
+    /// Unreviewed permissions count as allowed, so a failed audit doesn't
+    /// lock the UI
+    pub fn allows(&self, permission: Permission) -> bool {
+        self.checks
+            .iter()
+            .find(|check| check.permission == permission)
+            .is_none_or(|check| check.allowed)
+    }
+
+    pub fn denied(&self) -> impl Iterator<Item = &PermissionCheck> {
+        self.checks.iter().filter(|check| !check.allowed)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    Node,
+    Pod,
+    Service,
+    VirtualMachine,
+}
+
+impl ResourceKind {
+    pub const ALL: [ResourceKind; 4] = [
+        ResourceKind::Node,
+        ResourceKind::Pod,
+        ResourceKind::Service,
+        ResourceKind::VirtualMachine,
+    ];
+
+    pub fn title(&self) -> &str {
+        match self {
+            ResourceKind::Node => "Nodes",
+            ResourceKind::Pod => "Pods",
+            ResourceKind::Service => "Services",
+            ResourceKind::VirtualMachine => "VMs",
+        }
+    }
+
+    /// Resource name as understood by `kubectl get`
+    pub fn kubectl_name(&self) -> &str {
+        match self {
+            ResourceKind::Node => "node",
+            ResourceKind::Pod => "pod",
+            ResourceKind::Service => "service",
+            ResourceKind::VirtualMachine => "virtualmachines.kubevirt.io",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let idx = Self::ALL.iter().position(|k| k == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn prev(&self) -> Self {
+        let idx = Self::ALL.iter().position(|k| k == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct K8sResource {
+    pub kind: ResourceKind,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub status: String,
+    pub labels: BTreeMap<String, String>,
+}
+
+/// One term of a label selector
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LabelRequirement {
+    Equals(String, String),
+    NotEquals(String, String),
+    In(String, Vec<String>),
+    NotIn(String, Vec<String>),
+    Exists(String),
+    NotExists(String),
+}
+
+/// A Kubernetes label selector as typed into a filter box:
+/// comma-separated `key=value`, `key!=value`, `key in (a,b)`,
+/// `key notin (a,b)`, `key` and `!key`, all of which must hold
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelSelector {
+    requirements: Vec<LabelRequirement>,
+}
+
+impl LabelSelector {
+    pub fn parse(selector: &str) -> Result<Self, String> {
+        // Split on commas outside of `in (...)` value sets
+        let mut terms = Vec::new();
+        let (mut depth, mut start) = (0, 0);
+        for (i, c) in selector.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    terms.push(&selector[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        terms.push(&selector[start..]);
+
+        let requirements = terms
+            .into_iter()
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+            .map(parse_requirement)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { requirements })
+    }
+
+    /// Whether every requirement holds; `!=` and `notin` also hold for
+    /// objects without the label, as in Kubernetes
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.requirements.iter().all(|requirement| match requirement {
+            LabelRequirement::Equals(key, value) => labels.get(key) == Some(value),
+            LabelRequirement::NotEquals(key, value) => labels.get(key) != Some(value),
+            LabelRequirement::In(key, values) => labels.get(key).is_some_and(|v| values.contains(v)),
+            LabelRequirement::NotIn(key, values) => labels.get(key).is_none_or(|v| !values.contains(v)),
+            LabelRequirement::Exists(key) => labels.contains_key(key),
+            LabelRequirement::NotExists(key) => !labels.contains_key(key),
+        })
+    }
+}
+
+fn parse_requirement(term: &str) -> Result<LabelRequirement, String> {
+    let key = |key: &str| {
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            Err(format!("{:?} has no valid label key", term))
+        } else {
+            Ok(key.to_string())
+        }
+    };
+    let values = |set: &str| {
+        let set = set.trim();
+        let inner = set
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or_else(|| format!("{:?} needs a value set like (a,b)", term))?;
+        Ok::<_, String>(inner.split(',').map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect())
+    };
+
+    if let Some(name) = term.strip_prefix('!') {
+        return Ok(LabelRequirement::NotExists(key(name)?));
+    }
+    if let Some((name, set)) = term.split_once(" notin ") {
+        return Ok(LabelRequirement::NotIn(key(name)?, values(set)?));
+    }
+    if let Some((name, set)) = term.split_once(" in ") {
+        return Ok(LabelRequirement::In(key(name)?, values(set)?));
+    }
+    if let Some((name, value)) = term.split_once("!=") {
+        return Ok(LabelRequirement::NotEquals(key(name)?, value.trim().to_string()));
+    }
+    if let Some((name, value)) = term.split_once("==").or_else(|| term.split_once('=')) {
+        return Ok(LabelRequirement::Equals(key(name)?, value.trim().to_string()));
+    }
+    Ok(LabelRequirement::Exists(key(term)?))
+}
+
+/// Ready/not-ready endpoint counts for a Service, from its EndpointSlices
+#[derive(Debug, Clone)]
+pub struct ServiceEndpoints {
+    pub namespace: String,
+    pub name: String,
+    pub ready: u32,
+    pub not_ready: u32,
+    /// TCP addresses the Service answers on from the host: LoadBalancer
+    /// ingress IP and port, or 127.0.0.1 and the node port
+    pub vips: Vec<String>,
+}
+
+/// An Ingress or Gateway API HTTPRoute exposing hostnames
+#[derive(Debug, Clone)]
+pub struct IngressRoute {
+    pub kind: &'static str,
+    pub namespace: String,
+    pub name: String,
+    pub hosts: Vec<String>,
+    /// Expiry of the TLS certificate referenced by the route, if any
+    pub cert_expiry: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// One probe of a Service VIP from the host
+#[derive(Debug, Clone)]
+pub struct VipStatus {
+    pub namespace: String,
+    pub name: String,
+    pub address: String,
+    pub reachable: bool,
+    pub latency_ms: Option<f64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    /// Ready endpoints behind the Service when it was probed
+    pub ready_endpoints: u32,
+}
+
+impl VipStatus {
+    pub fn service(&self) -> String {
+        format!("{}/{}", self.namespace, self.name)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ServiceHealth {
+    pub endpoints: Vec<ServiceEndpoints>,
+    pub routes: Vec<IngressRoute>,
+}
+
+/// Utilization of a VM's virt-launcher pod relative to what it requested
+#[derive(Debug, Clone, Serialize)]
+pub struct VmUsage {
+    pub namespace: String,
+    pub name: String,
+    pub status: String,
+    /// None when the VM isn't running or pod metrics are unavailable
+    pub cpu_percent: Option<f64>,
+    pub memory_percent: Option<f64>,
+}
+
+impl VmUsage {
+    pub fn resource(&self) -> K8sResource {
+        K8sResource {
+            kind: ResourceKind::VirtualMachine,
+            name: self.name.clone(),
+            namespace: Some(self.namespace.clone()),
+            status: self.status.clone(),
+            labels: BTreeMap::new(),
+        }
+    }
+}
+
+/// Whether a node's kubelet is reporting in, from its Ready condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NodeReachability {
+    Ready,
+    NotReady,
+    /// Ready is Unknown: the kubelet stopped posting status
+    Unreachable,
+}
+
+impl NodeReachability {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NodeReachability::Ready => "Ready",
+            NodeReachability::NotReady => "NotReady",
+            NodeReachability::Unreachable => "Unreachable",
+        }
+    }
+}
+
+/// One node of the cluster as seen through the Kubernetes API
+#[derive(Debug, Clone)]
+pub struct NodeStatus {
+    pub name: String,
+    pub roles: Vec<String>,
+    pub reachability: NodeReachability,
+    /// Pressure conditions currently True (MemoryPressure, DiskPressure, ...)
+    pub pressures: Vec<String>,
+    pub kubelet_version: String,
+    /// Of allocatable; None without metrics-server
+    pub cpu_percent: Option<f64>,
+    pub memory_percent: Option<f64>,
+    pub cpu_allocatable: f64,
+    pub memory_allocatable_bytes: f64,
+    pub pods: u32,
+    /// Running VMs as namespace/name
+    pub vms: Vec<String>,
+}
+
+/// One condition the kubelet reports on its node
+#[derive(Debug, Clone)]
+pub struct NodeConditionInfo {
+    /// Ready, MemoryPressure, DiskPressure, PIDPressure, NetworkUnavailable, ...
+    pub kind: String,
+    /// True, False or Unknown
+    pub status: String,
+    pub reason: Option<String>,
+    pub message: Option<String>,
+    pub last_transition: Option<chrono::DateTime<chrono::Local>>,
+}
+
+impl NodeConditionInfo {
+    /// Whether the condition is bad right now: Ready anything but True, any
+    /// other condition True
+    pub fn is_problem(&self) -> bool {
+        if self.kind == "Ready" {
+            self.status != "True"
+        } else {
+            self.status == "True"
+        }
+    }
+}
+
+/// A node resource the kubelet advertises, and what it keeps back from pods
+#[derive(Debug, Clone)]
+pub struct NodeResource {
+    pub name: String,
+    pub capacity: String,
+    pub allocatable: String,
+    /// Percent of capacity held back by system/kube reservations and the
+    /// hard eviction threshold; None when the quantities don't parse
+    pub reserved_percent: Option<f64>,
+}
+
+/// The local node as its kubelet reports it: every condition with when it
+/// last changed, capacity against allocatable and the eviction thresholds
+#[derive(Debug, Clone, Default)]
+pub struct KubeletStatus {
+    pub node: String,
+    pub kubelet_version: String,
+    pub conditions: Vec<NodeConditionInfo>,
+    pub resources: Vec<NodeResource>,
+    /// Signal -> threshold ("memory.available" -> "100Mi")
+    pub eviction_hard: BTreeMap<String, String>,
+    pub eviction_soft: BTreeMap<String, String>,
+    /// Why the kubelet configuration couldn't be read (configz needs
+    /// nodes/proxy access)
+    pub config_error: Option<String>,
+}
+
+/// An SR-IOV physical function and its virtual function pool
+#[derive(Debug, Clone)]
+pub struct SriovNic {
+    /// Netdev name of the physical function
+    pub name: String,
+    pub pci_address: String,
+    /// Most VFs the NIC supports (sriov_totalvfs)
+    pub total_vfs: u32,
+    pub vfs: Vec<SriovVf>,
+}
+
+impl SriovNic {
+    pub fn assigned(&self) -> usize {
+        self.vfs.iter().filter(|vf| vf.pod_uid.is_some()).count()
+    }
+
+    pub fn free(&self) -> usize {
+        self.vfs.len() - self.assigned()
+    }
+}
+
+/// One created virtual function
+#[derive(Debug, Clone)]
+pub struct SriovVf {
+    pub index: u32,
+    pub pci_address: String,
+    /// Kernel driver bound to the VF, "vfio-pci" when set up for passthrough
+    pub driver: Option<String>,
+    /// UID of the pod the device plugin allocated it to
+    pub pod_uid: Option<String>,
+    /// That pod's namespace/name, when the cluster lookup found it
+    pub pod: Option<String>,
+}
+
+/// A multipathed LUN (dm-multipath map) as listed by `multipath -ll`
+#[derive(Debug, Clone)]
+pub struct MultipathMap {
+    /// User-friendly alias, or the WWID when there is none
+    pub name: String,
+    pub wwid: String,
+    /// dm device, e.g. "dm-3"
+    pub dm: String,
+    /// "vendor,product" of the array
+    pub vendor: String,
+    pub size: Option<String>,
+    pub groups: Vec<PathGroup>,
+}
+
+impl MultipathMap {
+    pub fn paths(&self) -> impl Iterator<Item = &MultipathPath> {
+        self.groups.iter().flat_map(|group| &group.paths)
+    }
+
+    pub fn failed_paths(&self) -> usize {
+        self.paths().filter(|path| path.failed()).count()
+    }
+
+    /// Path groups that have lost at least one path
+    pub fn degraded_groups(&self) -> usize {
+        self.groups.iter().filter(|group| group.paths.iter().any(|path| path.failed())).count()
+    }
+}
+
+/// Paths of a map that are used together; the map switches groups on failover
+#[derive(Debug, Clone)]
+pub struct PathGroup {
+    pub priority: Option<u32>,
+    /// "active", "enabled" (standby) or "disabled"
+    pub status: String,
+    pub paths: Vec<MultipathPath>,
+}
+
+/// One SCSI path to a LUN, through an HBA port or iSCSI session
+#[derive(Debug, Clone)]
+pub struct MultipathPath {
+    /// host:channel:target:lun; the host is the HBA or iSCSI session
+    pub hctl: String,
+    /// Block device, e.g. "sdb"
+    pub device: String,
+    /// dm state: "active" or "failed"
+    pub dm_state: String,
+    /// Path checker state: "ready", "ghost" (ALUA standby), "faulty", ...
+    pub checker_state: String,
+    /// SCSI device state: "running", "offline", ...
+    pub device_state: String,
+}
+
+impl MultipathPath {
+    pub fn failed(&self) -> bool {
+        self.dm_state == "failed"
+            || matches!(self.checker_state.as_str(), "faulty" | "shaky" | "undef")
+            || self.device_state == "offline"
+    }
+}
+
+/// A Fibre Channel HBA port (/sys/class/fc_host)
+#[derive(Debug, Clone)]
+pub struct HbaPort {
+    /// SCSI host, e.g. "host3"
+    pub host: String,
+    /// WWPN
+    pub port_name: String,
+    /// "Online", "Linkdown", ...
+    pub state: String,
+    pub speed: Option<String>,
+}
+
+/// Multipath LUNs and the FC HBA ports their paths run through
+#[derive(Debug, Clone, Default)]
+pub struct MultipathInfo {
+    pub maps: Vec<MultipathMap>,
+    pub hba_ports: Vec<HbaPort>,
+}
+
+/// Throughput and request latency of one block device, from the deltas
+/// of /proc/diskstats between two scans
+#[derive(Debug, Clone)]
+pub struct DiskLatency {
+    pub device: String,
+    /// device-mapper name of a dm-N device: the multipath LUN or LVM volume
+    pub label: Option<String>,
+    pub read_mb_s: f64,
+    pub write_mb_s: f64,
+    /// Share of the interval the device had requests in flight
+    pub util_percent: f64,
+    /// Average time per completed request over the last interval; None
+    /// when nothing completed
+    pub await_ms: Option<f64>,
+    /// Percentiles of the per-interval averages over the last 5 minutes
+    pub p50_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    /// Lowest latency over the last 30s of scans, 0 if any was idle
+    pub sustained_ms: f64,
+    /// Requests issued to the device and not yet completed
+    pub in_flight: u64,
+    pub queue: BlockQueue,
+    /// Holds VM disks: under `storage.image_dirs`/`local_path_dirs`, or
+    /// listed in `storage.datastore_devices`
+    pub datastore: bool,
+}
+
+impl DiskLatency {
+    /// "dm-3 (vmstore02)", or just the kernel name
+    pub fn name(&self) -> String {
+        match self.label {
+            Some(ref label) => format!("{} ({})", self.device, label),
+            None => self.device.clone(),
+        }
+    }
+}
+
+/// Request queue settings of a block device, from /sys/block/<dev>/queue
+#[derive(Debug, Clone, Default)]
+pub struct BlockQueue {
+    /// Active I/O scheduler: "none", "mq-deadline", "bfq" or "kyber"
+    pub scheduler: Option<String>,
+    /// Schedulers the device can be switched to
+    pub available: Vec<String>,
+    /// Requests the scheduler queues per hardware queue (nr_requests)
+    pub depth: Option<u64>,
+    /// Spinning disk rather than flash
+    pub rotational: bool,
+}
+
+impl BlockQueue {
+    /// The scheduler `device` should use for VM I/O when its current one is
+    /// a poor fit: none on NVMe, where the drive's own queues do better than
+    /// any kernel scheduler; not bfq on other SSDs, whose per-request cost
+    /// caps IOPS; and not none on spinning disks, which then seek for every
+    /// request in arrival order. Stacked devices (dm, md) offer only none
+    /// and are judged by the disks below them.
+    pub fn better_scheduler(&self, device: &str) -> Option<&'static str> {
+        let current = self.scheduler.as_deref()?;
+        let wanted = if self.rotational {
+            (current == "none").then_some("mq-deadline")
+        } else if device.starts_with("nvme") {
+            (current != "none").then_some("none")
+        } else {
+            (current == "bfq").then_some("mq-deadline")
+        }?;
+        self.available.iter().any(|s| s == wanted).then_some(wanted)
+    }
+}
+
+/// Open file descriptors of one watched process against its soft limit
+#[derive(Debug, Clone)]
+pub struct FdUsage {
+    pub pid: u32,
+    pub name: String,
+    pub open: u64,
+    /// None when unlimited
+    pub limit: Option<u64>,
+}
+
+impl FdUsage {
+    pub fn percent(&self) -> Option<f64> {
+        self.limit.filter(|l| *l > 0).map(|l| self.open as f64 / l as f64 * 100.0)
+    }
+}
+
+/// Resident memory of a daemon watched for leaks, summed over every
+/// process running under its name
+#[derive(Debug, Clone)]
+pub struct ProcessMemory {
+    pub name: String,
+    pub pids: Vec<u32>,
+    pub rss_bytes: u64,
+}
+
+impl ProcessMemory {
+    pub fn rss_mb(&self) -> f64 {
+        self.rss_bytes as f64 / (1024.0 * 1024.0)
+    }
+}
+
+/// Hardware and kernel trouble recognized in the kernel log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HardwareErrorKind {
+    MachineCheck,
+    UncorrectedMemory,
+    CorrectedMemory,
+    Io,
+    NicReset,
+    OomKill,
+}
+
+impl HardwareErrorKind {
+    pub const ALL: [HardwareErrorKind; 6] = [
+        HardwareErrorKind::MachineCheck,
+        HardwareErrorKind::UncorrectedMemory,
+        HardwareErrorKind::CorrectedMemory,
+        HardwareErrorKind::Io,
+        HardwareErrorKind::NicReset,
+        HardwareErrorKind::OomKill,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HardwareErrorKind::MachineCheck => "Machine Check Exception",
+            HardwareErrorKind::UncorrectedMemory => "Uncorrected Memory Error",
+            HardwareErrorKind::CorrectedMemory => "Corrected Memory Errors",
+            HardwareErrorKind::Io => "Disk I/O Errors",
+            HardwareErrorKind::NicReset => "NIC Reset",
+            HardwareErrorKind::OomKill => "OOM Kill",
+        }
+    }
+
+    /// Alert source, one per kind
+    pub fn source(&self) -> &'static str {
+        match self {
+            HardwareErrorKind::MachineCheck => "kernel-mce",
+            HardwareErrorKind::UncorrectedMemory => "kernel-edac-ue",
+            HardwareErrorKind::CorrectedMemory => "kernel-edac-ce",
+            HardwareErrorKind::Io => "kernel-io",
+            HardwareErrorKind::NicReset => "kernel-nic-reset",
+            HardwareErrorKind::OomKill => "kernel-oom",
+        }
+    }
+
+    /// What a kernel message reports, if it is one of the recognized kinds
+    pub fn classify(message: &str) -> Option<Self> {
+        let lower = message.to_lowercase();
+        if lower.contains("edac") {
+            if message.contains(" UE ") || lower.contains("uncorrect") || lower.contains("fatal") {
+                return Some(HardwareErrorKind::UncorrectedMemory);
+            }
+            if message.contains(" CE ") || lower.contains("corrected") {
+                return Some(HardwareErrorKind::CorrectedMemory);
+            }
+        }
+        if lower.contains("machine check") || lower.starts_with("mce:") || lower.contains("[hardware error]") {
+            Some(HardwareErrorKind::MachineCheck)
+        } else if lower.contains("out of memory: kill") || lower.contains("memory cgroup out of memory") {
+            Some(HardwareErrorKind::OomKill)
+        } else if lower.contains("i/o error") || lower.contains("medium error") || lower.contains("exception emask") {
+            Some(HardwareErrorKind::Io)
+        } else if lower.contains("netdev watchdog")
+            || lower.contains("tx unit hang")
+            || lower.contains("tx hang")
+            || lower.contains("reset adapter")
+            || lower.contains("adapter reset")
+        {
+            Some(HardwareErrorKind::NicReset)
+        } else {
+            None
+        }
+    }
+}
+
+/// A process the kernel OOM killer ended, from /dev/kmsg or a rise in a
+/// cgroup's `memory.events` oom_kill count
+#[derive(Debug, Clone)]
+pub struct OomKill {
+    pub at: chrono::DateTime<chrono::Local>,
+    /// Killed process; `memory.events` only counts kills
+    pub task: Option<String>,
+    pub pid: Option<u32>,
+    /// cgroup v2 path of the victim, relative to the cgroup root; empty
+    /// when the kernel didn't say
+    pub cgroup: String,
+    /// Killed for its cgroup's memory.max rather than the host running
+    /// out; None when only `memory.events` saw it
+    pub cgroup_limit: Option<bool>,
+    /// Anonymous memory the kill freed
+    pub anon_rss_kb: Option<u64>,
+    pub pod_uid: Option<String>,
+    /// namespace/name, resolved through the cluster
+    pub pod: Option<String>,
+    /// KubeVirt VM of a virt-launcher pod (namespace/name) or libvirt domain
+    pub vm: Option<String>,
+    /// systemd unit owning the cgroup
+    pub unit: Option<String>,
+}
+
+impl OomKill {
+    /// The most specific thing that was killed: "VM default/web1",
+    /// "pod kube-system/coredns-abc", "unit libvirtd.service", ...
+    pub fn victim(&self) -> String {
+        if let Some(ref vm) = self.vm {
+            format!("VM {}", vm)
+        } else if let Some(ref pod) = self.pod {
+            format!("pod {}", pod)
+        } else if let Some(ref uid) = self.pod_uid {
+            format!("pod {}", uid)
+        } else if let Some(ref unit) = self.unit {
+            format!("unit {}", unit)
+        } else if let Some(ref task) = self.task {
+            task.clone()
+        } else if !self.cgroup.is_empty() {
+            format!("cgroup {}", self.cgroup)
+        } else {
+            "unknown process".to_string()
+        }
+    }
+
+    /// "qemu-kvm [1234]", or what is known of it
+    pub fn process(&self) -> String {
+        match (&self.task, self.pid) {
+            (Some(task), Some(pid)) => format!("{} [{}]", task, pid),
+            (Some(task), None) => task.clone(),
+            (None, Some(pid)) => format!("[{}]", pid),
+            (None, None) => "-".to_string(),
+        }
+    }
+}
+
+/// A recognized kernel log line
+#[derive(Debug, Clone)]
+pub struct HardwareEvent {
+    pub kind: HardwareErrorKind,
+    pub at: chrono::DateTime<chrono::Local>,
+    pub line: String,
+}
+
+/// A core dump or crash report found through systemd-coredump or in a
+/// crash directory
+#[derive(Debug, Clone)]
+pub struct CoreDump {
+    pub at: chrono::DateTime<chrono::Local>,
+    /// Path of the crashed executable, or only its name when that is all
+    /// the dump tells
+    pub binary: String,
+    pub pid: Option<u32>,
+    pub signal: Option<i32>,
+    /// "coredumpctl" or the file under the crash directory
+    pub origin: String,
+    /// Whether the core itself is still there: present, missing, ...
+    pub core: Option<String>,
+    pub size_bytes: Option<u64>,
+    /// The binary is one of `crashes.processes`
+    pub critical: bool,
+}
+
+impl CoreDump {
+    /// File name of the binary: "qemu-system-x86_64"
+    pub fn name(&self) -> &str {
+        self.binary.rsplit('/').next().unwrap_or(&self.binary)
+    }
+
+    /// "SIGSEGV", or the number for signals without a usual name
+    pub fn signal_name(&self) -> Option<String> {
+        let signal = self.signal?;
+        let name = match signal {
+            3 => "SIGQUIT",
+            4 => "SIGILL",
+            5 => "SIGTRAP",
+            6 => "SIGABRT",
+            7 => "SIGBUS",
+            8 => "SIGFPE",
+            11 => "SIGSEGV",
+            24 => "SIGXCPU",
+            25 => "SIGXFSZ",
+            31 => "SIGSYS",
+            other => return Some(format!("signal {}", other)),
+        };
+        Some(name.to_string())
+    }
+}
+
+/// One way the live host differs from `[host_profile]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileDeviation {
+    /// What was checked: "kernel", "sysctl vm.swappiness", "service k3s",
+    /// "hugepages 1G", "NICs", "NIC eno1 speed", "CPU flag avx2"
+    pub check: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Hugepage size such as "2M", "1G" or "2048kB", in kB
+pub fn page_size_kb(size: &str) -> Option<u64> {
+    let size = size.trim().trim_end_matches(['B', 'b']).trim_end_matches('i');
+    let split = size.find(|c: char| !c.is_ascii_digit()).unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let number: u64 = number.parse().ok()?;
+    let factor = match unit.to_ascii_lowercase().as_str() {
+        "k" => 1,
+        "m" => 1024,
+        "g" => 1024 * 1024,
+        _ => return None,
+    };
+    Some(number * factor).filter(|kb| *kb > 0)
+}
+
+/// What a sysctl is expected to be set to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SysctlExpectation {
+    /// Exactly this value (whitespace between fields ignored)
+    Equals(String),
+    AtLeast(i64),
+    AtMost(i64),
+}
+
+impl SysctlExpectation {
+    /// "1", ">=8192" or "<=10"
+    pub fn parse(expected: &str) -> Result<Self, String> {
+        let bound = |n: &str| {
+            n.trim()
+                .parse::<i64>()
+                .map_err(|_| format!("{:?} needs a whole number after the comparison", expected))
+        };
+        if let Some(n) = expected.strip_prefix(">=") {
+            Ok(SysctlExpectation::AtLeast(bound(n)?))
+        } else if let Some(n) = expected.strip_prefix("<=") {
+            Ok(SysctlExpectation::AtMost(bound(n)?))
+        } else if expected.trim().is_empty() {
+            Err("expected value is empty".to_string())
+        } else {
+            Ok(SysctlExpectation::Equals(expected.split_whitespace().collect::<Vec<_>>().join(" ")))
+        }
+    }
+
+    pub fn holds(&self, value: &str) -> bool {
+        match self {
+            SysctlExpectation::Equals(expected) => value.split_whitespace().collect::<Vec<_>>().join(" ") == *expected,
+            SysctlExpectation::AtLeast(n) => value.trim().parse::<i64>().is_ok_and(|v| v >= *n),
+            SysctlExpectation::AtMost(n) => value.trim().parse::<i64>().is_ok_and(|v| v <= *n),
+        }
+    }
+}
+
+impl std::fmt::Display for SysctlExpectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SysctlExpectation::Equals(value) => write!(f, "{}", value),
+            SysctlExpectation::AtLeast(n) => write!(f, ">={}", n),
+            SysctlExpectation::AtMost(n) => write!(f, "<={}", n),
+        }
+    }
+}
+
+/// A kernel tunable from /proc/sys
+#[derive(Debug, Clone)]
+pub struct Sysctl {
+    /// Dotted name, e.g. "net.ipv4.ip_forward"
+    pub name: String,
+    /// None when the sysctl doesn't exist, e.g. net.bridge.* without the
+    /// br_netfilter module
+    pub value: Option<String>,
+    pub expected: Option<SysctlExpectation>,
+}
+
+impl Sysctl {
+    /// Has an expected value that the current one (or its absence) misses
+    pub fn mismatched(&self) -> bool {
+        self.expected
+            .as_ref()
+            .is_some_and(|expected| self.value.as_deref().is_none_or(|value| !expected.holds(value)))
+    }
+}
+
+/// Two or more MACs seen claiming one IPv4 address on a watched bridge,
+/// by ARP or by being handed it in a DHCP lease
+#[derive(Debug, Clone)]
+pub struct IpConflict {
+    pub ip: std::net::Ipv4Addr,
+    pub bridge: String,
+    /// Claiming MACs, most recent claim last
+    pub macs: Vec<String>,
+    pub last_seen: chrono::DateTime<chrono::Local>,
+}
+
+/// DHCP leases acknowledged for one MAC within the window
+#[derive(Debug, Clone)]
+pub struct LeaseChurn {
+    pub mac: String,
+    pub bridge: String,
+    pub leases: u32,
+    /// Distinct addresses leased, in order
+    pub ips: Vec<std::net::Ipv4Addr>,
+}
+
+/// System-wide file descriptors, ephemeral ports in use, and the fds of
+/// watched processes (highest share of their limit first)
+#[derive(Debug, Clone, Default)]
+pub struct SocketUsage {
+    pub fds_allocated: u64,
+    pub fds_max: u64,
+    /// Distinct local ports of non-listening TCP sockets inside the range
+    pub ephemeral_in_use: u32,
+    /// net.ipv4.ip_local_port_range, inclusive
+    pub ephemeral_range: (u16, u16),
+    pub time_wait: u32,
+    pub processes: Vec<FdUsage>,
+    /// Watched service ports, in configured order
+    pub ports: Vec<PortStatus>,
+    /// "address:port" of TCP listeners on non-loopback addresses that are
+    /// neither watched nor expected
+    pub unexpected_listeners: Vec<String>,
+}
+
+/// Kernel-wide limits that fail in odd ways when hit: the random pool's
+/// entropy and the PID space. Open files are in `SocketUsage`.
+#[derive(Debug, Clone, Default)]
+pub struct HostLimits {
+    /// None where the kernel doesn't expose it
+    pub entropy_bits: Option<u64>,
+    pub entropy_pool_bits: Option<u64>,
+    /// Threads on the system, each holding a PID
+    pub tasks: u64,
+    /// The lower of kernel.pid_max and kernel.threads-max
+    pub task_limit: u64,
+    /// The sysctl `task_limit` comes from
+    pub task_limit_name: &'static str,
+}
+
+impl HostLimits {
+    pub fn task_percent(&self) -> f64 {
+        if self.task_limit == 0 {
+            return 0.0;
+        }
+        self.tasks as f64 / self.task_limit as f64 * 100.0
+    }
+}
+
+/// Listeners and inbound connections of one watched port or port range
+#[derive(Debug, Clone, Default)]
+pub struct PortStatus {
+    pub name: String,
+    /// "6443", or "5900-5999" for a range
+    pub ports: String,
+    /// Alert when nothing listens
+    pub required: bool,
+    /// "address:port" of each listening socket
+    pub listeners: Vec<String>,
+    /// Established connections to it
+    pub connections: u32,
+    /// Listened at an earlier collection but no longer does
+    pub stopped: bool,
+}
+
+impl SocketUsage {
+    pub fn fd_percent(&self) -> f64 {
+        if self.fds_max == 0 {
+            return 0.0;
+        }
+        self.fds_allocated as f64 / self.fds_max as f64 * 100.0
+    }
+
+    pub fn ephemeral_percent(&self) -> f64 {
+        let size = u32::from(self.ephemeral_range.1.saturating_sub(self.ephemeral_range.0)) + 1;
+        self.ephemeral_in_use as f64 / size as f64 * 100.0
+    }
+}
+
+/// One resource of a namespace ResourceQuota, hard limit against current use
+#[derive(Debug, Clone, Serialize)]
+pub struct QuotaUsage {
+    pub namespace: String,
+    /// Name of the ResourceQuota object
+    pub quota: String,
+    /// Quota key, e.g. "requests.cpu", "limits.memory", "persistentvolumeclaims"
+    pub resource: String,
+    /// Cores for CPU resources, bytes for memory/storage, a count otherwise
+    pub used: f64,
+    pub hard: f64,
+}
+
+impl QuotaUsage {
+    pub fn percent(&self) -> f64 {
+        if self.hard > 0.0 {
+            self.used / self.hard * 100.0
+        } else if self.used > 0.0 {
+            // A zero quota with anything in use is fully exhausted
+            100.0
+        } else {
+            0.0
+        }
+    }
+
+    pub fn is_cpu(&self) -> bool {
+        self.resource == "cpu" || self.resource.ends_with(".cpu")
+    }
+
+    /// Memory, hugepages and (ephemeral-)storage quotas are byte quantities;
+    /// per-storage-class PVC counts also mention "storage" but aren't
+    pub fn is_bytes(&self) -> bool {
+        self.resource.ends_with("memory")
+            || self.resource.ends_with("storage")
+            || self.resource.contains("hugepages-")
+    }
+}
+
+/// VMs and pods of one tenant (the value of the configured label) and the
+/// resources they were given, for chargeback
+#[derive(Debug, Clone, Default)]
+pub struct TenantUsage {
+    pub tenant: String,
+    pub vms: usize,
+    pub running_vms: usize,
+    /// Guest vCPUs (sockets x cores x threads) of every VM, running or not
+    pub vcpus: u32,
+    /// Guest memory of every VM
+    pub memory_bytes: u64,
+    /// Capacity of the claims used by the tenant's VMs and pods
+    pub storage_bytes: u64,
+    /// Pods other than virt-launchers, and what their containers request
+    pub pods: usize,
+    pub pod_cpu_cores: f64,
+    pub pod_memory_bytes: u64,
+    /// vCPU or memory growth, whichever is larger, against the oldest
+    /// sample within the growth window
+    pub growth_percent: Option<f64>,
+}
+
+impl TenantUsage {
+    /// Cores and bytes given to VMs and pods together
+    pub fn cpu_cores(&self) -> f64 {
+        self.vcpus as f64 + self.pod_cpu_cores
+    }
+
+    pub fn total_memory_bytes(&self) -> u64 {
+        self.memory_bytes + self.pod_memory_bytes
+    }
+}
+
+/// CPU placement, model and hugepages settings from a VirtualMachine template
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuTuning {
+    pub dedicated_cpu_placement: bool,
+    /// Mirror the host NUMA topology into the guest (guestMappingPassthrough)
+    pub numa_passthrough: bool,
+    /// None uses the cluster default CPU model
+    pub model: Option<String>,
+    /// Hugepage size backing guest memory, e.g. "2Mi" or "1Gi"
+    pub hugepages: Option<String>,
+}
+
+impl CpuTuning {
+    pub fn from_vm(vm: &serde_json::Value) -> Self {
+        let domain = vm.pointer("/spec/template/spec/domain");
+        let str_at = |path: &str| {
+            domain
+                .and_then(|d| d.pointer(path))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        };
+        Self {
+            dedicated_cpu_placement: domain
+                .and_then(|d| d.pointer("/cpu/dedicatedCpuPlacement"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            numa_passthrough: domain
+                .and_then(|d| d.pointer("/cpu/numa/guestMappingPassthrough"))
+                .is_some(),
+            model: str_at("/cpu/model"),
+            hugepages: str_at("/memory/hugepages/pageSize"),
+        }
+    }
+
+    /// JSON merge patch turning `original` into `self`; unchanged settings
+    /// are left out and cleared ones are set to null
+    pub fn merge_patch(&self, original: &CpuTuning) -> serde_json::Value {
+        use serde_json::{json, Map, Value};
+
+        let mut cpu = Map::new();
+        let mut memory = Map::new();
+
+        if self.dedicated_cpu_placement != original.dedicated_cpu_placement {
+            cpu.insert(
+                "dedicatedCpuPlacement".into(),
+                if self.dedicated_cpu_placement { Value::Bool(true) } else { Value::Null },
+            );
+        }
+        if self.numa_passthrough != original.numa_passthrough {
+            cpu.insert(
+                "numa".into(),
+                if self.numa_passthrough {
+                    json!({ "guestMappingPassthrough": {} })
+                } else {
+                    Value::Null
+                },
+            );
+        }
+        if self.model != original.model {
+            cpu.insert("model".into(), self.model.clone().map_or(Value::Null, Value::String));
+        }
+        if self.hugepages != original.hugepages {
+            memory.insert(
+                "hugepages".into(),
+                match self.hugepages {
+                    Some(ref size) => json!({ "pageSize": size }),
+                    None => Value::Null,
+                },
+            );
+        }
+
+        let mut domain = Map::new();
+        if !cpu.is_empty() {
+            domain.insert("cpu".into(), Value::Object(cpu));
+        }
+        if !memory.is_empty() {
+            domain.insert("memory".into(), Value::Object(memory));
+        }
+        json!({ "spec": { "template": { "spec": { "domain": domain } } } })
+    }
+
+    /// Settings KubeVirt would reject, checked before applying
+    pub fn validate(&self) -> Option<&'static str> {
+        if self.numa_passthrough && (!self.dedicated_cpu_placement || self.hugepages.is_none()) {
+            return Some("NUMA passthrough requires dedicated CPU placement and hugepages");
+        }
+        None
+    }
+}
+
+/// What a running VM can be resized to without a restart, and what the
+/// cluster allows: CPU and memory follow the VM spec live only with
+/// `vmRolloutStrategy: LiveUpdate`, volumes need the HotplugVolumes gate
+#[derive(Debug, Clone, Default)]
+pub struct VmHotplug {
+    /// A VMI exists; a stopped VM is simply edited instead
+    pub running: bool,
+    pub sockets: u32,
+    /// Upper bound for hot-plugged sockets, from the VM or the cluster default
+    pub max_sockets: u32,
+    /// Guest memory in MiB
+    pub memory_mib: u64,
+    pub max_memory_mib: u64,
+    /// The cluster's vmRolloutStrategy is LiveUpdate
+    pub live_update: bool,
+    /// The HotplugVolumes feature gate is on
+    pub hotplug_volumes: bool,
+    /// Why the VMI can't be live migrated, which applying CPU and memory
+    /// changes relies on
+    pub not_migratable: Option<String>,
+    /// Volumes already hot-plugged into the VMI
+    pub hotplugged: Vec<String>,
+}
+
+impl VmHotplug {
+    /// Sockets and memory are plugged in with a migration to a larger
+    /// domain, so both need the same things
+    pub fn resize_problem(&self) -> Option<String> {
+        if !self.running {
+            return Some("VM isn't running; stop-state changes belong in the VM spec".to_string());
+        }
+        if !self.live_update {
+            return Some("Cluster doesn't apply changes live: set vmRolloutStrategy: LiveUpdate in the KubeVirt CR".to_string());
+        }
+        self.not_migratable.as_ref().map(|reason| format!("VM can't be live migrated: {}", reason))
+    }
+
+    pub fn volume_problem(&self) -> Option<String> {
+        if !self.running {
+            return Some("VM isn't running".to_string());
+        }
+        if !self.hotplug_volumes {
+            return Some("HotplugVolumes feature gate is off in the KubeVirt CR".to_string());
+        }
+        None
+    }
+
+    /// JSON merge patch to `sockets` and `memory_mib`, unchanged ones left out
+    pub fn merge_patch(&self, sockets: u32, memory_mib: u64) -> serde_json::Value {
+        use serde_json::{json, Map, Value};
+
+        let mut domain = Map::new();
+        if sockets != self.sockets {
+            domain.insert("cpu".into(), json!({ "sockets": sockets }));
+        }
+        if memory_mib != self.memory_mib {
+            let guest = if memory_mib.is_multiple_of(1024) {
+                format!("{}Gi", memory_mib / 1024)
+            } else {
+                format!("{}Mi", memory_mib)
+            };
+            domain.insert("memory".into(), json!({ "guest": guest }));
+        }
+        json!({ "spec": { "template": { "spec": { "domain": Value::Object(domain) } } } })
+    }
+}
+
+/// Where a cloud-init volume keeps its user data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserDataSource {
+    /// userData
+    Inline,
+    /// userDataBase64, decoded for display and saved back as userData
+    Base64,
+    /// secretRef; the Secret has to be edited instead
+    Secret(String),
+}
+
+/// First-boot configuration of a VirtualMachine, from its
+/// cloudInitNoCloud or cloudInitConfigDrive volume
+#[derive(Debug, Clone)]
+pub struct CloudInit {
+    pub volume: String,
+    /// "cloudInitNoCloud" or "cloudInitConfigDrive"
+    pub datasource: String,
+    pub user_data: String,
+    pub source: UserDataSource,
+    pub network_data: Option<String>,
+}
+
+impl CloudInit {
+    const DATASOURCES: [&'static str; 2] = ["cloudInitNoCloud", "cloudInitConfigDrive"];
+
+    /// The VM's first cloud-init volume, if it has one
+    pub fn from_vm(vm: &serde_json::Value) -> Option<Self> {
+        use base64::Engine;
+
+        let volumes = vm.pointer("/spec/template/spec/volumes")?.as_array()?;
+        volumes.iter().find_map(|volume| {
+            let (datasource, spec) = Self::DATASOURCES
+                .iter()
+                .find_map(|ds| volume.get(*ds).map(|spec| (*ds, spec)))?;
+            let str_at = |key: &str| spec.get(key).and_then(|v| v.as_str());
+
+            let (user_data, source) = if let Some(secret) = spec.pointer("/secretRef/name").and_then(|v| v.as_str()) {
+                (String::new(), UserDataSource::Secret(secret.to_string()))
+            } else if let Some(encoded) = str_at("userDataBase64") {
+                let decoded = base64::engine::general_purpose::STANDARD.decode(encoded.trim()).unwrap_or_default();
+                (String::from_utf8_lossy(&decoded).into_owned(), UserDataSource::Base64)
+            } else {
+                (str_at("userData").unwrap_or_default().to_string(), UserDataSource::Inline)
+            };
+
+            Some(Self {
+                volume: volume.get("name").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                datasource: datasource.to_string(),
+                user_data,
+                source,
+                network_data: str_at("networkData").map(String::from),
+            })
+        })
+    }
+
+    /// JSON merge patch setting new user data on `vm`. Volumes are a list,
+    /// which merge patches replace whole, so every volume is sent; the
+    /// resourceVersion makes the patch fail if the VM changed meanwhile.
+    pub fn merge_patch(&self, vm: &serde_json::Value, user_data: &str) -> Option<serde_json::Value> {
+        let mut volumes = vm.pointer("/spec/template/spec/volumes")?.as_array()?.clone();
+        let spec = volumes
+            .iter_mut()
+            .find(|v| v.get("name").and_then(|n| n.as_str()) == Some(self.volume.as_str()))?
+            .get_mut(self.datasource.as_str())?
+            .as_object_mut()?;
+        spec.remove("userDataBase64");
+        spec.insert("userData".to_string(), serde_json::Value::String(user_data.to_string()));
+
+        Some(serde_json::json!({
+            "metadata": { "resourceVersion": vm.pointer("/metadata/resourceVersion") },
+            "spec": { "template": { "spec": { "volumes": volumes } } },
+        }))
+    }
+}
+
+/// Cloud-config with passwords, tokens and private keys masked for display:
+/// values of secret-looking keys (and block scalars under them), private
+/// key blocks and the "user:password" entries of chpasswd
+pub fn redact_secrets(text: &str) -> String {
+    const SECRET_KEYS: [&str; 5] = ["passw", "token", "secret", "api_key", "private_key"];
+    const REDACTED: &str = "<redacted>";
+
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let mut lines = Vec::new();
+    // Indentation of the secret key whose block scalar is being skipped
+    let mut block: Option<usize> = None;
+    // Indentation of the chpasswd key while inside it
+    let mut chpasswd: Option<usize> = None;
+    let mut in_private_key = false;
+
+    for line in text.lines().map(str::trim_end) {
+        let trimmed = line.trim_start();
+        if in_private_key {
+            if trimmed.starts_with("-----END") {
+                in_private_key = false;
+                lines.push(line.to_string());
+            }
+            continue;
+        }
+        if let Some(depth) = block {
+            if trimmed.is_empty() || indent(line) > depth {
+                continue;
+            }
+            block = None;
+        }
+        if chpasswd.is_some_and(|depth| !trimmed.is_empty() && indent(line) <= depth) {
+            chpasswd = None;
+        }
+        if trimmed.starts_with("-----BEGIN") && trimmed.contains("PRIVATE KEY") {
+            in_private_key = true;
+            lines.push(line.to_string());
+            lines.push(format!("{}{}", &line[..indent(line)], REDACTED));
+            continue;
+        }
+
+        let item = trimmed.trim_start_matches("- ");
+        let prefix = &line[..line.len() - item.len()];
+        if let Some((key, rest)) = item.split_once(':') {
+            let value = rest.trim();
+            if rest.is_empty() || rest.starts_with(' ') {
+                let key_lower = key.trim_matches('"').to_lowercase();
+                if key_lower == "chpasswd" {
+                    chpasswd = Some(indent(line));
+                }
+                let secret = SECRET_KEYS.iter().any(|secret| key_lower.contains(secret));
+                if secret && !value.is_empty() && value != "true" && value != "false" {
+                    if value.starts_with('|') || value.starts_with('>') {
+                        block = Some(indent(line));
+                    }
+                    lines.push(format!("{}{}: {}", prefix, key, REDACTED));
+                    continue;
+                }
+            } else if chpasswd.is_some() {
+                lines.push(format!("{}{}:{}", prefix, key, REDACTED));
+                continue;
+            }
+        }
+        lines.push(line.to_string());
+    }
+    lines.join("\n")
+}
+
+/// What a cloned VM's disk is backed by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CloneDisk {
+    /// DataVolume template recreated under a new name from its original source
+    Fresh { volume: String, data_volume: String },
+    /// New DataVolume that CDI fills by cloning the source VM's claim
+    Cloned { volume: String, source: String, data_volume: String },
+    /// Claim attached to both VMs; only safe for read-only disks
+    Shared { volume: String, claim: String },
+}
+
+/// A VirtualMachine manifest copied from an existing one under a new name
+#[derive(Debug, Clone)]
+pub struct VmClone {
+    pub manifest: serde_json::Value,
+    pub disks: Vec<CloneDisk>,
+}
+
+impl VmClone {
+    /// Copy `vm` as `name`, halted, without the source's identity: server
+    /// fields, firmware UUID/serial and explicit MAC addresses are dropped
+    /// or regenerated. With `clone_disks` every claim-backed disk becomes a
+    /// DataVolume template cloning the source claim via CDI.
+    pub fn from_vm(vm: &serde_json::Value, name: &str, clone_disks: bool) -> Self {
+        use serde_json::{json, Value};
+
+        let source = vm.pointer("/metadata/name").and_then(Value::as_str).unwrap_or_default();
+        let namespace = vm.pointer("/metadata/namespace").cloned().unwrap_or(Value::Null);
+        // Disks are usually named after their VM ("web-rootdisk")
+        let renamed = |old: &str| {
+            if !source.is_empty() && old.contains(source) {
+                old.replacen(source, name, 1)
+            } else {
+                format!("{}-{}", name, old)
+            }
+        };
+
+        let mut metadata = json!({ "name": name, "namespace": namespace });
+        if let Some(labels) = vm.pointer("/metadata/labels") {
+            metadata["labels"] = labels.clone();
+        }
+        if let Some(Value::Object(annotations)) = vm.pointer("/metadata/annotations") {
+            let mut annotations = annotations.clone();
+            annotations.remove("kubectl.kubernetes.io/last-applied-configuration");
+            metadata["annotations"] = Value::Object(annotations);
+        }
+        let mut spec = vm.get("spec").cloned().unwrap_or_else(|| json!({}));
+
+        // Never boot next to the source before it has been looked at
+        if spec.get("runStrategy").is_some() {
+            spec["runStrategy"] = "Halted".into();
+        } else {
+            spec["running"] = false.into();
+        }
+
+        if let Some(Value::Object(labels)) = spec.pointer_mut("/template/metadata/labels") {
+            for key in ["kubevirt.io/vm", "vm.kubevirt.io/name"] {
+                if labels.get(key).and_then(Value::as_str) == Some(source) {
+                    labels.insert(key.to_string(), name.into());
+                }
+            }
+        }
+        if let Some(Value::Object(firmware)) = spec.pointer_mut("/template/spec/domain/firmware") {
+            firmware.remove("uuid");
+            firmware.remove("serial");
+        }
+        if let Some(Value::Array(interfaces)) = spec.pointer_mut("/template/spec/domain/devices/interfaces") {
+            for interface in interfaces.iter_mut().filter(|i| i.get("macAddress").is_some()) {
+                interface["macAddress"] = random_mac().into();
+            }
+        }
+
+        let mut disks = Vec::new();
+        let mut templates: Vec<Value> = match spec.get("dataVolumeTemplates") {
+            Some(Value::Array(templates)) => templates.clone(),
+            _ => Vec::new(),
+        };
+        let mut template_names = HashMap::new();
+        for template in &mut templates {
+            let Some(old) = template.pointer("/metadata/name").and_then(Value::as_str).map(String::from) else {
+                continue;
+            };
+            let new = renamed(&old);
+            template["metadata"]["name"] = new.clone().into();
+            if clone_disks {
+                if let Some(Value::Object(dv_spec)) = template.get_mut("spec") {
+                    dv_spec.remove("sourceRef");
+                    dv_spec.insert("source".into(), json!({ "pvc": { "namespace": namespace, "name": old } }));
+                }
+            }
+            template_names.insert(old, new);
+        }
+
+        if let Some(Value::Array(volumes)) = spec.pointer_mut("/template/spec/volumes") {
+            for volume in volumes.iter_mut() {
+                let volume_name = volume.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+                let claim = volume
+                    .pointer("/dataVolume/name")
+                    .or_else(|| volume.pointer("/persistentVolumeClaim/claimName"))
+                    .and_then(Value::as_str)
+                    .map(String::from);
+                let Some(claim) = claim else {
+                    continue;
+                };
+
+                if let Some(new) = template_names.get(&claim) {
+                    // Replaced whole: a persistentVolumeClaim source naming
+                    // the template would otherwise stay next to it
+                    *volume = json!({ "name": volume_name, "dataVolume": { "name": new } });
+                    disks.push(if clone_disks {
+                        CloneDisk::Cloned { volume: volume_name, source: claim, data_volume: new.clone() }
+                    } else {
+                        CloneDisk::Fresh { volume: volume_name, data_volume: new.clone() }
+                    });
+                } else if clone_disks {
+                    let new = renamed(&claim);
+                    templates.push(json!({
+                        "metadata": { "name": new },
+                        "spec": {
+                            "source": { "pvc": { "namespace": namespace, "name": claim } },
+                            // CDI takes the size from the source claim
+                            "storage": {},
+                        },
+                    }));
+                    *volume = json!({ "name": volume_name, "dataVolume": { "name": new } });
+                    disks.push(CloneDisk::Cloned { volume: volume_name, source: claim, data_volume: new });
+                } else {
+                    disks.push(CloneDisk::Shared { volume: volume_name, claim });
+                }
+            }
+        }
+        if !templates.is_empty() {
+            spec["dataVolumeTemplates"] = Value::Array(templates);
+        }
+
+        let manifest = json!({
+            "apiVersion": vm.get("apiVersion").cloned().unwrap_or_else(|| "kubevirt.io/v1".into()),
+            "kind": "VirtualMachine",
+            "metadata": metadata,
+            "spec": spec,
+        });
+        Self { manifest, disks }
+    }
+}
+
+/// Locally administered unicast MAC, the range meant for generated addresses
+fn random_mac() -> String {
+    let mut bytes: [u8; 6] = rand::random();
+    bytes[0] = (bytes[0] & 0xfc) | 0x02;
+    bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(":")
+}
+
+/// Why `name` can't name a Kubernetes object (RFC 1123 label), if it can't
+pub fn object_name_problem(name: &str) -> Option<&'static str> {
+    if name.is_empty() {
+        Some("name is empty")
+    } else if name.len() > 63 {
+        Some("name is longer than 63 characters")
+    } else if !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+        Some("name may only contain lowercase letters, digits and '-'")
+    } else if name.starts_with('-') || name.ends_with('-') {
+        Some("name must start and end with a letter or digit")
+    } else {
+        None
+    }
+}
+
+/// Labels and taints of a node, as edited from the resource browser
+#[derive(Debug, Clone, Default)]
+pub struct NodeMetadata {
+    pub labels: BTreeMap<String, String>,
+    pub taints: Vec<NodeTaint>,
+    /// Sent with taint patches so a concurrent change isn't overwritten
+    pub resource_version: Option<String>,
+}
+
+/// A pod on a node being drained, with what decides how the drain treats it
+#[derive(Debug, Clone, Default)]
+pub struct NodePod {
+    pub namespace: String,
+    pub name: String,
+    /// Tells a pod recreated under the same name (StatefulSets) apart
+    pub uid: String,
+    /// Kind of the controller owning it; None for a bare pod
+    pub owner: Option<String>,
+    /// Static pod the kubelet runs from a manifest
+    pub mirror: bool,
+    /// Succeeded or Failed, nothing left running
+    pub finished: bool,
+    /// virt-launcher pod, which leaves with its VM's live migration
+    pub launcher: bool,
+    /// emptyDir volumes, whose data goes with the pod
+    pub empty_dirs: Vec<String>,
+    /// PodDisruptionBudgets selecting it
+    pub budgets: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeTaint {
+    pub key: String,
+    pub value: Option<String>,
+    /// NoSchedule, PreferNoSchedule or NoExecute
+    pub effect: String,
+}
+
+impl NodeTaint {
+    pub const EFFECTS: [&'static str; 3] = ["NoSchedule", "PreferNoSchedule", "NoExecute"];
+
+    /// Parse kubectl's `key[=value]:Effect` syntax
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (spec, effect) = text
+            .trim()
+            .rsplit_once(':')
+            .ok_or_else(|| "Taint must be key[=value]:Effect".to_string())?;
+        let effect = Self::EFFECTS
+            .iter()
+            .find(|e| e.eq_ignore_ascii_case(effect))
+            .ok_or_else(|| format!("Effect must be one of {}", Self::EFFECTS.join(", ")))?;
+        let (key, value) = match spec.split_once('=') {
+            Some((key, value)) => (key, Some(value.to_string())),
+            None => (spec, None),
+        };
+        if key.is_empty() {
+            return Err("Taint key is empty".to_string());
+        }
+        Ok(Self {
+            key: key.to_string(),
+            value: value.filter(|v| !v.is_empty()),
+            effect: effect.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for NodeTaint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.value {
+            Some(ref value) => write!(f, "{}={}:{}", self.key, value, self.effect),
+            None => write!(f, "{}:{}", self.key, self.effect),
+        }
+    }
+}
+
+/// Guest details reported by qemu-guest-agent through KubeVirt subresources
+#[derive(Debug, Clone, Default)]
+pub struct GuestInfo {
+    pub hostname: String,
+    pub os: String,
+    pub kernel: String,
+    pub users: Vec<GuestUser>,
+    pub filesystems: Vec<GuestFilesystem>,
+}
+
+/// A VM's clock as read through qemu-guest-agent against the host's
+#[derive(Debug, Clone)]
+pub struct GuestClock {
+    /// namespace/name
+    pub vm: String,
+    /// Guest minus host time; positive when the guest runs ahead. None when
+    /// the agent couldn't be asked
+    pub drift_secs: Option<f64>,
+    /// Half the round trip of the query, the most the reading can be off by
+    pub uncertainty_secs: f64,
+    pub error: Option<String>,
+    pub checked: chrono::DateTime<chrono::Local>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GuestUser {
+    pub name: String,
+    pub domain: Option<String>,
+    /// Unix timestamp of the login
+    pub login_time: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GuestFilesystem {
+    pub disk_name: String,
+    pub mount_point: String,
+    pub fs_type: String,
+    pub used_bytes: u64,
+    pub total_bytes: u64,
+}
+
+impl GuestFilesystem {
+    pub fn usage_percent(&self) -> f64 {
+        if self.total_bytes == 0 {
+            return 0.0;
+        }
+        self.used_bytes as f64 / self.total_bytes as f64 * 100.0
+    }
+}
+
+/// Responsiveness of the API server, from timed probes
+#[derive(Debug, Clone, Default)]
+pub struct ApiHealth {
+    /// Round trip of GET /healthz, None when it failed
+    pub healthz_ms: Option<f64>,
+    /// Round trip of a one-item namespace list, which goes through storage
+    pub list_ms: Option<f64>,
+    /// The apiserver's etcd check, None where it isn't exposed
+    pub etcd_ok: Option<bool>,
+    /// The apiserver's own request latency from `[prometheus]`, covering
+    /// every client rather than just this one
+    pub p99_ms: Option<f64>,
+    /// Why a probe failed
+    pub error: Option<String>,
+}
+
+impl ApiHealth {
+    /// The slowest of the probes and the Prometheus p99, what alerts are
+    /// raised on
+    pub fn latency_ms(&self) -> Option<f64> {
+        [self.healthz_ms, self.list_ms, self.p99_ms].into_iter().flatten().reduce(f64::max)
+    }
+}
+
+/// Cluster metrics read from `[prometheus]` on its last scrape
+#[derive(Debug, Clone, Default)]
+pub struct PrometheusMetrics {
+    /// apiserver request latency from `api_latency_query`
+    pub api_latency_ms: Option<f64>,
+    /// None when etcd isn't read or the server has no etcd metrics
+    pub etcd: Option<EtcdStatus>,
+    /// (namespace, name) -> (CPU percent, memory percent)
+    pub vm_usage: HashMap<(String, String), (Option<f64>, Option<f64>)>,
+    /// `queries` and `metrics` that had a value, for computed metrics
+    pub variables: BTreeMap<String, f64>,
+}
+
+/// The cluster datastore as read from etcd's Prometheus metrics
+#[derive(Debug, Clone, Default)]
+pub struct EtcdStatus {
+    /// Where the metrics were read from
+    pub endpoint: String,
+    /// Size of the backend database file, including free pages
+    pub db_size_bytes: u64,
+    /// Bytes of it holding live data; the rest is reclaimed by a defrag
+    pub db_in_use_bytes: Option<u64>,
+    /// Backend quota; writes fail with "mvcc: database space exceeded" past it
+    pub quota_bytes: Option<u64>,
+    pub has_leader: Option<bool>,
+    pub is_leader: Option<bool>,
+    pub leader_changes_total: u64,
+    /// Leader changes seen over the past hour (or since the TUI started)
+    pub leader_changes_last_hour: u64,
+    pub proposals_failed_total: u64,
+    /// Raft proposals that failed since the previous collection
+    pub proposals_failed_recent: u64,
+    pub proposals_pending: Option<u64>,
+}
+
+impl EtcdStatus {
+    /// Percent of the backend quota the database file takes up
+    pub fn quota_percent(&self) -> Option<f64> {
+        self.quota_bytes
+            .filter(|&quota| quota > 0)
+            .map(|quota| self.db_size_bytes as f64 / quota as f64 * 100.0)
+    }
+
+    /// Percent of the database file that is free pages a defrag would return
+    pub fn fragmentation_percent(&self) -> Option<f64> {
+        let in_use = self.db_in_use_bytes?;
+        if self.db_size_bytes == 0 {
+            return None;
+        }
+        Some(self.db_size_bytes.saturating_sub(in_use) as f64 / self.db_size_bytes as f64 * 100.0)
+    }
+}
+
+/// Latest result of a config-defined custom command
+#[derive(Debug, Clone)]
+pub struct CommandOutput {
+    pub ran_at: chrono::DateTime<chrono::Local>,
+    pub duration: std::time::Duration,
+    /// Exit code, or None when it was killed or couldn't be started
+    pub exit_code: Option<i32>,
+    /// Why the run failed to start or was killed
+    pub error: Option<String>,
+    /// stdout followed by stderr
+    pub lines: Vec<String>,
+    /// Header and rows, for commands with `parser = "table"`
+    pub table: Option<(Vec<String>, Vec<Vec<String>>)>,
+}
+
+impl CommandOutput {
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// Serial console output captured from a VM
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleCapture {
+    /// Oldest first; an unterminated last line (a login prompt) is included
+    pub lines: Vec<String>,
+    pub connected: bool,
+    /// Why the console last disconnected or failed to connect
+    pub error: Option<String>,
+    /// Bumped whenever the capture changes, to tell when to redraw
+    pub version: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleKind {
+    Vnc,
+    Serial,
+    /// `virtctl port-forward`, usually SSH or a VNC server in the guest
+    PortForward,
+}
+
+impl ConsoleKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConsoleKind::Vnc => "VNC",
+            ConsoleKind::Serial => "serial",
+            ConsoleKind::PortForward => "forward",
+        }
+    }
+}
+
+/// A host socket or port a VM's graphical or serial console is reachable on
+#[derive(Debug, Clone)]
+pub struct ConsoleEndpoint {
+    pub kind: ConsoleKind,
+    /// Socket path, or the address a proxy listens on
+    pub address: String,
+    /// What serves it: the virt-launcher pod, or a virtctl process
+    pub source: String,
+}
+
+/// The `virtctl vnc` port-forward started for a VM from its detail popup
+#[derive(Debug, Clone)]
+pub struct VncForward {
+    pub address: std::net::SocketAddr,
+    pub running: bool,
+    /// Why virtctl exited
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CgroupKind {
+    Slice,
+    Pod,
+    Container,
+}
+
+/// Resource usage of a single cgroup v2 node
+#[derive(Debug, Clone)]
+pub struct CgroupUsage {
+    /// Path relative to the cgroup root
+    pub path: String,
+    pub name: String,
+    pub kind: CgroupKind,
+    /// Share of total host CPU capacity
+    pub cpu_percent: f64,
+    pub memory_bytes: u64,
+    /// Pod cgroups only
+    pub pod_uid: Option<String>,
+}
+
+/// Share of host capacity (percent) on each side of the line between the
+/// host's own services and its workloads
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResourceSplit {
+    /// Every top-level slice other than kubepods (k3s, system.slice, ...)
+    pub system: f64,
+    /// virt-launcher pods
+    pub vms: f64,
+    /// The rest of kubepods
+    pub pods: f64,
+    pub free: f64,
+}
+
+/// CPU and memory split between the host and its workloads, from cgroups
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HostReservation {
+    pub cpu: ResourceSplit,
+    pub memory: ResourceSplit,
+}
+
+impl HostReservation {
+    /// `launcher_pods` holds the UIDs of virt-launcher pods
+    pub fn from_cgroups(cgroups: &[CgroupUsage], launcher_pods: &HashSet<String>, memory_total_bytes: u64) -> Self {
+        let is_kubepods = |u: &CgroupUsage| u.path == "kubepods" || u.path == "kubepods.slice";
+        let sum = |filter: &dyn Fn(&CgroupUsage) -> bool| {
+            cgroups
+                .iter()
+                .filter(|u| filter(u))
+                .fold((0.0, 0u64), |(cpu, memory), u| (cpu + u.cpu_percent, memory + u.memory_bytes))
+        };
+
+        let system = sum(&|u| u.kind == CgroupKind::Slice && !is_kubepods(u));
+        let kubepods = sum(&|u| u.kind == CgroupKind::Slice && is_kubepods(u));
+        let vms = sum(&|u| u.pod_uid.as_ref().is_some_and(|uid| launcher_pods.contains(uid)));
+
+        let split = |system: f64, kubepods: f64, vms: f64| {
+            let pods = (kubepods - vms).max(0.0);
+            ResourceSplit { system, vms, pods, free: (100.0 - system - vms - pods).max(0.0) }
+        };
+        let memory = |bytes: u64| {
+            if memory_total_bytes == 0 {
+                0.0
+            } else {
+                bytes as f64 / memory_total_bytes as f64 * 100.0
+            }
+        };
+        HostReservation {
+            cpu: split(system.0, kubepods.0, vms.0),
+            memory: split(memory(system.1), memory(kubepods.1), memory(vms.1)),
+        }
+    }
+}
+
+/// A qemu process on this host and the cluster objects and host resources
+/// it maps to
+#[derive(Debug, Clone)]
+pub struct QemuProcess {
+    pub pid: u32,
+    /// libvirt domain name; KubeVirt uses "<namespace>_<vm>"
+    pub domain: String,
+    /// VM as namespace/name, parsed from the domain name
+    pub vm: Option<String>,
+    /// cgroup v2 path of the process
+    pub cgroup: String,
+    /// UID of the pod owning the cgroup (the virt-launcher pod)
+    pub pod_uid: Option<String>,
+    /// virt-launcher pod as namespace/name, resolved through the cluster
+    pub pod: Option<String>,
+    /// Tap interfaces the process holds open (inside the pod's netns)
+    pub taps: Vec<String>,
+    /// Disk images and block devices as given on the qemu command line
+    pub disks: Vec<String>,
+    pub rss_bytes: u64,
+    pub threads: u32,
+    /// vCPU scheduling delay; None until the process has been seen twice
+    pub sched: Option<SchedLatency>,
+    /// Stopped by a signal (state T), e.g. paused from local mode
+    pub stopped: bool,
+}
+
+/// What local mode can do to a qemu process with the control plane down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QemuSignal {
+    /// SIGSTOP: the guest freezes with its memory kept
+    Pause,
+    /// SIGCONT
+    Resume,
+    /// SIGTERM: qemu shuts down, the guest as if its power was cut
+    Terminate,
+    /// SIGKILL, for a qemu that ignores SIGTERM
+    Kill,
+}
+
+impl QemuSignal {
+    pub fn label(&self) -> &'static str {
+        match self {
+            QemuSignal::Pause => "Pause",
+            QemuSignal::Resume => "Resume",
+            QemuSignal::Terminate => "Terminate",
+            QemuSignal::Kill => "Kill",
+        }
+    }
+
+    /// What the guest goes through, for the confirmation prompt
+    pub fn warning(&self) -> &'static str {
+        match self {
+            QemuSignal::Pause => "The guest stops until resumed; its clock, TCP sessions and watchdogs will notice.",
+            QemuSignal::Resume => "The guest continues where it was stopped.",
+            QemuSignal::Terminate => "The guest loses power without shutting down; unflushed writes are lost.",
+            QemuSignal::Kill => "qemu exits at once without cleanup; disk images may be left locked or inconsistent.",
+        }
+    }
+}
+
+/// How long a VM waited for physical CPUs between two scans
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SchedLatency {
+    pub vcpus: u32,
+    /// Share of time the average vCPU thread was runnable but waiting for a
+    /// physical CPU (run_delay in /proc/<pid>/task/<tid>/schedstat)
+    pub wait_percent: f64,
+    /// Share of CFS periods in which the process's cgroup hit its CPU limit
+    /// (nr_throttled / nr_periods in cpu.stat)
+    pub throttled_percent: f64,
+    /// Lowest of each over the recent scans: above a threshold only when
+    /// every one of them was
+    pub sustained_wait_percent: f64,
+    pub sustained_throttled_percent: f64,
+}
+
+impl QemuProcess {
+    /// Disk path reachable from the host, through the launcher's mount namespace
+    pub fn host_path(&self, disk: &str) -> String {
+        format!("/proc/{}/root{}", self.pid, disk)
+    }
+}
+
+/// Where a disk image lives
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiskImageKind {
+    /// Image file in a configured image directory
+    HostFile,
+    /// local-path provisioner directory ("pvc-<uid>_<namespace>_<claim>")
+    LocalPathVolume { pv: String },
+    /// PersistentVolumeClaim, optionally created by a CDI DataVolume
+    Claim { namespace: String, name: String, data_volume: Option<String> },
+}
+
+impl DiskImageKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiskImageKind::HostFile => "file",
+            DiskImageKind::LocalPathVolume { .. } => "local-path",
+            DiskImageKind::Claim { .. } => "pvc",
+        }
+    }
+}
+
+/// One VM disk image and what, if anything, still uses it
+#[derive(Debug, Clone)]
+pub struct DiskImage {
+    pub kind: DiskImageKind,
+    /// Host path, or namespace/name for claims
+    pub location: String,
+    pub size_bytes: u64,
+    pub modified: Option<chrono::DateTime<chrono::Local>>,
+    /// VM (namespace/name) or other owner keeping the image in use
+    pub used_by: Option<String>,
+}
+
+impl DiskImage {
+    pub fn is_orphan(&self) -> bool {
+        self.used_by.is_none()
+    }
+}
+
+/// A PersistentVolumeClaim as relevant to the disk inventory
+#[derive(Debug, Clone)]
+pub struct ClaimInfo {
+    pub namespace: String,
+    pub name: String,
+    /// Bound PersistentVolume name
+    pub volume: Option<String>,
+    pub size_bytes: u64,
+    /// DataVolume that created the claim (CDI ownerReference)
+    pub data_volume: Option<String>,
+    /// Running pod mounting the claim, as namespace/name
+    pub mounted_by: Option<String>,
+}
+
+/// Cluster-side view of which disks are referenced, to find orphans
+#[derive(Debug, Clone, Default)]
+pub struct DiskReferences {
+    /// hostDisk paths used by VMs, path -> VM (namespace/name)
+    pub host_paths: HashMap<String, String>,
+    /// Claims and DataVolumes used by VMs, namespace/name -> VM
+    pub claims: HashMap<String, String>,
+    pub all_claims: Vec<ClaimInfo>,
+    /// Existing PersistentVolume names
+    pub volumes: HashSet<String>,
+}
+
+/// Kubernetes API calls for one resource over the last minute, shown in
+/// the debug overlay
+#[derive(Debug, Clone, Default)]
+pub struct ApiCallRate {
+    pub resource: String,
+    pub lists: u32,
+    pub watches: u32,
+    pub requests: u32,
+    /// Listings answered from the cache
+    pub cached: u32,
+}
+
+impl ApiCallRate {
+    /// Calls that reached the apiserver
+    pub fn api_calls(&self) -> u32 {
+        self.lists + self.watches + self.requests
+    }
+}
+
+/// Per-collector run statistics shown in the debug overlay
+#[derive(Debug, Clone, Default)]
+pub struct CollectorTiming {
+    pub name: &'static str,
+    pub last: std::time::Duration,
+    pub max: std::time::Duration,
+    pub runs: u64,
+    pub failures: u64,
+    pub last_error: Option<String>,
+}
+
+/// Wire format of `GET /v1/snapshot` on a host running `--agent`; bumped
+/// when a field changes meaning
+pub const AGENT_PROTOCOL_VERSION: u32 = 1;
+
+/// One collection on a host running `--agent`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentSnapshot {
+    pub version: u32,
+    pub host: String,
+    pub collected_at: chrono::DateTime<chrono::Local>,
+    pub cpu_percent: f64,
+    pub memory_used_gb: f64,
+    pub memory_total_gb: f64,
+    pub disk_percent: f64,
+    pub load_avg: f64,
+    pub uptime_seconds: u64,
+    pub vms: Vec<AgentVm>,
+    /// Active alerts from the agent's host checks
+    pub alerts: Vec<crate::alerts::Alert>,
+}
+
+/// A qemu process as an agent reports it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentVm {
+    pub pid: u32,
+    /// VM as namespace/name when the domain name gives it, else the domain
+    pub name: String,
+    pub rss_bytes: u64,
+    pub threads: u32,
+    #[serde(default)]
+    pub stopped: bool,
+}
+
+/// The last poll of one agent in `[remote_agents]`
+#[derive(Debug, Clone)]
+pub struct AgentStatus {
+    pub endpoint: String,
+    /// The latest snapshot, kept through failed polls
+    pub snapshot: Option<AgentSnapshot>,
+    /// Why the last poll failed
+    pub error: Option<String>,
+    pub consecutive_failures: u32,
+    /// Connect, handshake and response, for the last successful poll
+    pub round_trip_ms: Option<f64>,
+}
+
+/// An alert posted to the webhook receiver by Alertmanager or Grafana
+#[derive(Debug, Clone)]
+pub struct ExternalAlert {
+    /// The sender's fingerprint, or one made from the labels
+    pub key: String,
+    /// False for a resolve notification
+    pub firing: bool,
+    /// alertname label, or the Grafana rule name
+    pub name: String,
+    pub labels: BTreeMap<String, String>,
+    /// summary, description or message annotation
+    pub summary: String,
+    /// "alertmanager" or "grafana"
+    pub sender: String,
+    pub received_at: chrono::DateTime<chrono::Local>,
+    /// When the sender said the alert ends, if it did
+    pub ends_at: Option<chrono::DateTime<chrono::Local>>,
+}
@@ -2,69 +2,75 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Clear},
+    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Clear, Row, Table, Wrap},
     Frame,
 };
 
-use crate::alerts::{Alert, AlertLevel};
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::alerts::{Alert, AlertLevel, AlertManager, AlertStatistics, AlertStatus, DIGEST_SOURCE};
+use crate::app::App;
+use crate::i18n::t;
+use crate::theme::{badge_text, AlertStyles};
+use crate::timestamps::{format_time, is_relative};
 
-/// Draw alert banner at the top of the screen
-pub fn draw_alert_banner(f: &mut Frame, alerts: &[&Alert], area: Rect) {
+/// Draw alert banner at the top of the screen: a count per level, then
+/// the active alerts' titles in turn, `cycle_secs` each, most severe first
+pub fn draw_alert_banner(
+    f: &mut Frame,
+    alerts: &[&Alert],
+    area: Rect,
+    glyphs: &Glyphs,
+    styles: &AlertStyles,
+    cycle_secs: u64,
+) {
     if alerts.is_empty() {
         return;
     }
 
-    // Count alerts by level
-    let critical_count = alerts.iter().filter(|a| a.level == AlertLevel::Critical).count();
-    let error_count = alerts.iter().filter(|a| a.level == AlertLevel::Error).count();
-    let warning_count = alerts.iter().filter(|a| a.level == AlertLevel::Warning).count();
-
-    // Build banner text
+    // Build banner text, one badge per level with alerts
     let mut spans = vec![];
 
-    if critical_count > 0 {
-        spans.push(Span::styled(
-            format!(" ⚠ {} CRITICAL ", critical_count),
-            Style::default()
-                .fg(Color::White)
-                .bg(Color::Red)
-                .add_modifier(Modifier::BOLD),
-        ));
-        spans.push(Span::raw(" "));
-    }
-
-    if error_count > 0 {
-        spans.push(Span::styled(
-            format!(" ✖ {} ERROR ", error_count),
-            Style::default()
-                .fg(Color::White)
-                .bg(Color::LightRed)
-                .add_modifier(Modifier::BOLD),
-        ));
-        spans.push(Span::raw(" "));
-    }
-
-    if warning_count > 0 {
+    for level in [AlertLevel::Critical, AlertLevel::Error, AlertLevel::Warning] {
+        let count = alerts.iter().filter(|a| a.level == level).count();
+        if count == 0 {
+            continue;
+        }
+        let color = styles.level_color(level);
         spans.push(Span::styled(
-            format!(" ⚡ {} WARNING ", warning_count),
+            format!(" {} {} {} ", glyphs.styled_level_icon(styles, level), count, level.as_str()),
             Style::default()
-                .fg(Color::Black)
-                .bg(Color::Yellow)
+                .fg(badge_text(color))
+                .bg(color)
                 .add_modifier(Modifier::BOLD),
         ));
         spans.push(Span::raw(" "));
     }
 
-    // Show most recent alert message
-    if let Some(alert) = alerts.first() {
+    // Alerts come sorted by level, newest first within one; the position
+    // follows the clock so every redraw agrees on it
+    let index = match cycle_secs {
+        0 => 0,
+        secs => (chrono::Local::now().timestamp().max(0) as u64 / secs) as usize % alerts.len(),
+    };
+    let alert = alerts[index];
+    if alerts.len() > 1 {
         spans.push(Span::styled(
-            format!("  {}  ", alert.title),
-            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            format!(" {}/{}", index + 1, alerts.len()),
+            Style::default().fg(Color::Gray),
         ));
     }
+    spans.push(Span::styled(
+        format!(" {} ", glyphs.alert_icon(styles, alert)),
+        Style::default().fg(styles.color(alert)),
+    ));
+    spans.push(Span::styled(
+        format!("{}  ", alert.title),
+        Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+    ));
 
     spans.push(Span::styled(
-        " [Press 'a' to view/dismiss] ",
+        format!(" {} ", t("banner.hint")),
         Style::default().fg(Color::DarkGray),
     ));
 
@@ -76,7 +82,8 @@ pub fn draw_alert_banner(f: &mut Frame, alerts: &[&Alert], area: Rect) {
 }
 
 /// Draw alert panel/popup showing all active alerts
-pub fn draw_alert_panel(f: &mut Frame, alerts: &[&Alert], area: Rect, selected_index: usize) {
+pub fn draw_alert_panel(f: &mut Frame, app: &App, alerts: &[&Alert], area: Rect, glyphs: &Glyphs) {
+    let styles = &app.alert_styles;
     // Create a centered popup area
     let popup_area = centered_rect(80, 60, area);
 
@@ -111,14 +118,17 @@ pub fn draw_alert_panel(f: &mut Frame, alerts: &[&Alert], area: Rect, selected_i
         .iter()
         .enumerate()
         .map(|(i, alert)| {
-            let (icon, level_color) = match alert.level {
-                AlertLevel::Critical => ("⚠", Color::Red),
-                AlertLevel::Error => ("✖", Color::LightRed),
-                AlertLevel::Warning => ("⚡", Color::Yellow),
-                AlertLevel::Info => ("ℹ", Color::Cyan),
+            let icon = glyphs.alert_icon(styles, alert);
+            let level_color = styles.color(alert);
+
+            // Spell out the level when color can't be relied on
+            let level_prefix = if app.accessible {
+                format!("[{}] ", alert.level.as_str())
+            } else {
+                String::new()
             };
 
-            let style = if i == selected_index {
+            let style = if i == app.alert_selected_index {
                 Style::default().bg(Color::DarkGray)
             } else {
                 Style::default()
@@ -131,19 +141,38 @@ pub fn draw_alert_panel(f: &mut Frame, alerts: &[&Alert], area: Rect, selected_i
                 format!("{}h", duration / 60)
             };
 
-            let content = vec![
+            let mut content = vec![
                 Line::from(vec![
                     Span::styled(format!(" {} ", icon), Style::default().fg(level_color).add_modifier(Modifier::BOLD)),
+                    Span::raw(level_prefix),
                     Span::styled(&alert.title, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
                     Span::raw("  "),
                     Span::styled(format!("({})", time_str), Style::default().fg(Color::DarkGray)),
+                    Span::styled(
+                        if alert.status == AlertStatus::Acknowledged { "  ack" } else { "" },
+                        Style::default().fg(Color::Green),
+                    ),
                 ]),
                 Line::from(vec![
                     Span::raw("    "),
                     Span::styled(&alert.message, Style::default().fg(Color::Gray)),
                 ]),
-                Line::from(""),
             ];
+            if let Some(ref remediation) = alert.metadata.remediation {
+                content.push(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled("Fix: ", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                    Span::styled(remediation, Style::default().fg(Color::Green)),
+                ]));
+            }
+            if let Some(ref url) = alert.metadata.runbook_url {
+                content.push(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled("Runbook: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                    Span::styled(url, Style::default().fg(Color::Cyan).add_modifier(Modifier::UNDERLINED)),
+                ]));
+            }
+            content.push(Line::from(""));
 
             ListItem::new(content).style(style)
         })
@@ -155,7 +184,11 @@ pub fn draw_alert_panel(f: &mut Frame, alerts: &[&Alert], area: Rect, selected_i
     f.render_widget(alert_list, chunks[1]);
 
     // Help text
-    let help = Paragraph::new(" ↑↓: Navigate  d: Dismiss  D: Dismiss All  Esc: Close ")
+    let help = Paragraph::new(if app.read_only {
+        format!(" {}: Navigate  Enter: Details  l: Logs  o: Runbook  s: Statistics  Esc: Close  (read-only) ", glyphs.up_down)
+    } else {
+        format!(" {}: Navigate  Enter: Details  a: Ack  d: Dismiss  z: Snooze  D: Dismiss All  l: Logs  s: Statistics  Esc: Close ", glyphs.up_down)
+    })
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT));
@@ -163,25 +196,205 @@ pub fn draw_alert_panel(f: &mut Frame, alerts: &[&Alert], area: Rect, selected_i
     f.render_widget(help, chunks[2]);
 }
 
-/// Helper function to create a centered rectangle
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
+/// Full metadata of one alert opened from the panel, with the actions that
+/// apply to it; the rate limit digest lists the alerts it holds back
+pub fn draw_alert_detail(
+    f: &mut Frame,
+    alert: Option<&Alert>,
+    suppressed: &[&Alert],
+    area: Rect,
+    glyphs: &Glyphs,
+    styles: &AlertStyles,
+    read_only: bool,
+) {
+    let popup_area = centered_rect(70, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(" Alert Details ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Min(0),     // Metadata
+            Constraint::Length(1),  // Actions
         ])
-        .split(r);
+        .split(inner);
 
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
+    let Some(alert) = alert else {
+        let gone = Paragraph::new("This alert is no longer active")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(gone, chunks[0]);
+        f.render_widget(
+            Paragraph::new(" Esc: Back ").style(Style::default().fg(Color::DarkGray)).alignment(Alignment::Center),
+            chunks[1],
+        );
+        return;
+    };
+
+    let level_color = styles.color(alert);
+    let label = |text: &'static str| Span::styled(format!(" {:<14}", text), Style::default().fg(Color::Gray));
+    let field = |name: &'static str, value: String| Line::from(vec![label(name), Span::raw(value)]);
+    let time = |at: chrono::DateTime<chrono::Local>| format_time(at, "%Y-%m-%d %H:%M:%S");
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!(" {} {} ", glyphs.alert_icon(styles, alert), alert.level.as_str()),
+                Style::default().fg(level_color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(&alert.title, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+        field("Category:", alert.category.as_str().to_string()),
+        field("Status:", format!("{:?}", alert.status)),
+        field("Source:", alert.metadata.source.clone()),
+        field("Rule:", alert.metadata.rule.clone().unwrap_or_else(|| "-".to_string())),
+        field("Dedup key:", AlertManager::dedup_key(alert)),
+    ];
+    if let (Some(value), Some(threshold)) = (alert.metadata.value, alert.metadata.threshold) {
+        lines.push(field("Value:", format!("{:.2} (threshold {:.2})", value, threshold)));
+    }
+    for (name, value) in [
+        ("Node:", &alert.metadata.node_name),
+        ("Namespace:", &alert.metadata.namespace),
+        ("Pod:", &alert.metadata.pod_name),
+        ("VM:", &alert.metadata.vm_name),
+    ] {
+        if let Some(value) = value {
+            lines.push(field(name, value.clone()));
+        }
+    }
+    lines.push(field(
+        "Triggered:",
+        match is_relative() {
+            true => time(alert.triggered_at),
+            false => format!("{} ({}m ago)", time(alert.triggered_at), alert.duration_minutes()),
+        },
+    ));
+    if let Some(at) = alert.acknowledged_at {
+        lines.push(field("Acknowledged:", time(at)));
+    }
+    if let Some(ref remediation) = alert.metadata.remediation {
+        lines.push(Line::from(vec![label("Fix:"), Span::styled(remediation, Style::default().fg(Color::Green))]));
+    }
+    if let Some(ref url) = alert.metadata.runbook_url {
+        lines.push(Line::from(vec![
+            label("Runbook:"),
+            Span::styled(url, Style::default().fg(Color::Cyan).add_modifier(Modifier::UNDERLINED)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(format!(" {}", alert.message), Style::default().fg(Color::Gray))));
+    if alert.metadata.source == DIGEST_SOURCE {
+        lines.push(Line::from(""));
+        for held in suppressed {
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {} ", glyphs.alert_icon(styles, held)), Style::default().fg(styles.color(held))),
+                Span::styled(&held.title, Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("  {}: {}", held.metadata.rule.as_deref().unwrap_or("-"), held.message),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    }
+
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+    let pod = if alert.metadata.pod_name.is_some() { "p: Go to pod  " } else { "" };
+    let actions = if read_only {
+        format!(" {}l: Logs  o: Runbook  Esc: Back  (read-only) ", pod)
+    } else {
+        format!(" a: Ack  d: Dismiss  z: Snooze  {}l: Logs  o: Runbook  Esc: Back ", pod)
+    };
+    f.render_widget(
+        Paragraph::new(actions).style(Style::default().fg(Color::DarkGray)).alignment(Alignment::Center),
+        chunks[1],
+    );
+}
+
+/// Per-source firing counts, resolve times and flappiness over the last
+/// week, flappiest first, for spotting thresholds that need tuning
+pub fn draw_alert_statistics(
+    f: &mut Frame,
+    stats: &[AlertStatistics],
+    area: Rect,
+    glyphs: &Glyphs,
+    styles: &AlertStyles,
+) {
+    let popup_area = centered_rect(90, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let flapping = stats.iter().filter(|s| s.is_flapping()).count();
+    let block = Block::default()
+        .title(format!(" Alert Statistics: {} sources, {} flapping (last 7 days) ", stats.len(), flapping))
+        .title_bottom(" Esc: Close ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    if stats.is_empty() {
+        let empty = Paragraph::new("\n  No alerts in the last 7 days")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(empty, popup_area);
+        return;
+    }
+
+    let header = Row::new(["Alert", "Source", "24h", "7d", "MTTR", "Flaps", "Flappiness"].map(|title| {
+        Cell::from(title).style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+    }));
+    let rows: Vec<Row> = stats
+        .iter()
+        .map(|stat| {
+            let level_color = styles.level_color(stat.level);
+            let mttr = match stat.mean_time_to_resolve {
+                Some(d) if d.num_minutes() >= 60 => format!("{}h{:02}m", d.num_hours(), d.num_minutes() % 60),
+                Some(d) if d.num_seconds() >= 60 => format!("{}m", d.num_minutes()),
+                Some(d) => format!("{}s", d.num_seconds()),
+                None => "-".to_string(),
+            };
+            let (flappiness, flap_style) = if stat.is_flapping() {
+                (
+                    format!("{} {:.0}%", glyphs.warning, stat.flappiness * 100.0),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (format!("{:.0}%", stat.flappiness * 100.0), Style::default().fg(Color::Gray))
+            };
+            Row::new(vec![
+                Cell::from(format!("{} {}", glyphs.styled_level_icon(styles, stat.level), stat.title))
+                    .style(Style::default().fg(level_color)),
+                Cell::from(stat.source.clone()).style(Style::default().fg(Color::DarkGray)),
+                Cell::from(stat.fires_24h.to_string()),
+                Cell::from(stat.fires_7d.to_string()),
+                Cell::from(mttr),
+                Cell::from(stat.flaps_7d.to_string()),
+                Cell::from(flappiness).style(flap_style),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(30),
+            Constraint::Length(28),
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(11),
+        ],
+    )
+    .header(header)
+    .block(block);
+    f.render_widget(table, popup_area);
 }
 
 fn count_alerts_by_level(alerts: &[&Alert]) -> (usize, usize, usize, usize) {
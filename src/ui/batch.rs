@@ -0,0 +1,94 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::batch::{BatchPrompt, BatchRun};
+
+/// Choose the action to apply to the marked VMs
+pub fn draw_batch_prompt(f: &mut Frame, prompt: &BatchPrompt, marked: usize, area: Rect) {
+    let popup_area = centered_rect(50, 30, area);
+    f.render_widget(Clear, popup_area);
+
+    let key = |k: &'static str| Span::styled(k, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let mut lines = vec![Line::from(""), Line::from(format!("  {} VM(s) marked", marked)), Line::from("")];
+    match prompt.label {
+        None => {
+            lines.push(Line::from(vec![Span::raw("  "), key("s"), Span::raw("  Stop")]));
+            lines.push(Line::from(vec![Span::raw("  "), key("t"), Span::raw("  Start")]));
+            lines.push(Line::from(vec![Span::raw("  "), key("m"), Span::raw("  Migrate off current node")]));
+            lines.push(Line::from(vec![Span::raw("  "), key("l"), Span::raw("  Add label")]));
+        }
+        Some(ref label) => {
+            lines.push(Line::from("  Label (key=value):"));
+            lines.push(Line::from(Span::styled(
+                format!("  {}_", label),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+    }
+
+    let block = Block::default()
+        .title(" Batch Action ")
+        .title_bottom(if prompt.label.is_some() { " Enter: Apply  Esc: Cancel " } else { " Esc: Cancel " })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+/// Progress of a running batch: overall gauge, then each VM's outcome
+pub fn draw_batch_progress(f: &mut Frame, run: &BatchRun, area: Rect, glyphs: &Glyphs) {
+    let popup_area = centered_rect(70, 60, area);
+    f.render_widget(Clear, popup_area);
+
+    let finished = run.is_finished();
+    let block = Block::default()
+        .title(format!(" Batch: {} ", run.action.label()))
+        .title_bottom(if finished { " Enter/Esc: Close " } else { " Esc: Hide (keeps running) " })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if run.failed() > 0 { Color::Yellow } else { Color::Cyan }));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+    if inner.height < 2 {
+        return;
+    }
+
+    let total = run.results.len().max(1);
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(Color::Green))
+        .ratio(run.done() as f64 / total as f64)
+        .label(run.summary());
+    f.render_widget(gauge, Rect { height: 1, ..inner });
+
+    // Failures first, since they are what needs attention
+    let mut rows: Vec<&(String, Option<Result<(), String>>)> = run.results.iter().collect();
+    rows.sort_by_key(|(_, result)| match result {
+        Some(Err(_)) => 0,
+        None => 1,
+        Some(Ok(())) => 2,
+    });
+    let lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(vm, result)| {
+            let (symbol, color, detail) = match result {
+                None => ("-", Color::Gray, String::new()),
+                Some(Ok(())) => (glyphs.check, Color::Green, String::new()),
+                Some(Err(e)) => (glyphs.error, Color::Red, format!("  {}", e)),
+            };
+            Line::from(vec![
+                Span::styled(format!("{:>2} ", symbol), Style::default().fg(color)),
+                Span::styled(vm.clone(), Style::default().fg(Color::Cyan)),
+                Span::styled(detail, Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+    f.render_widget(
+        Paragraph::new(lines),
+        Rect { y: inner.y + 2, height: inner.height - 2, ..inner },
+    );
+}
@@ -0,0 +1,335 @@
+use chrono::{DateTime, Duration, Local};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+use super::glyphs::Glyphs;
+use crate::alerts::AlertLevel;
+use crate::app::App;
+use crate::metrics_history::{AlertMarker, ChartMetric, Zoom, CPU_SPIKE_PERCENT};
+use crate::timestamps::format_time;
+
+/// Alert markers drawn at most, the newest ones; each is its own dataset
+const MAX_MARKERS: usize = 40;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Metric and zoom selectors
+            Constraint::Min(0),    // Chart
+            Constraint::Length(1), // Cursor readout
+        ])
+        .split(area);
+
+    draw_selectors(f, app, chunks[0]);
+
+    let samples = app.metrics_history.series(app.chart_metric, app.chart_zoom);
+    let now = Local::now();
+    let span_secs = app.chart_zoom.resolution_secs() as f64 * 60.0;
+    let start = now - Duration::seconds(span_secs as i64);
+    let x_of = |at: chrono::DateTime<Local>| (at - start).num_milliseconds() as f64 / 1000.0;
+
+    let data: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|s| s.at >= start)
+        .map(|s| (x_of(s.at), s.value))
+        .collect();
+
+    let max_value = data.iter().map(|&(_, v)| v).fold(0.0, f64::max);
+    let y_max = match app.chart_metric {
+        ChartMetric::Cpu | ChartMetric::Memory | ChartMetric::Health => 100.0,
+        ChartMetric::DiskRead | ChartMetric::DiskWrite | ChartMetric::Power | ChartMetric::ApiLatency => (max_value * 1.2).max(1.0),
+    };
+
+    // The cursor sample, or the newest one when following live data
+    let cursor = match app.chart_cursor {
+        Some(at) => samples.iter().find(|s| s.at == at).copied(),
+        None => samples.last().copied(),
+    };
+    let cursor_line: Vec<(f64, f64)> = match (app.chart_cursor, cursor) {
+        (Some(_), Some(sample)) => vec![(x_of(sample.at), 0.0), (x_of(sample.at), y_max)],
+        _ => Vec::new(),
+    };
+
+    // Raw readings above the spike threshold, which the bucket averages
+    // (and the smoothed gauges) can hide entirely
+    let spikes: Vec<(f64, f64)> = match app.chart_metric {
+        ChartMetric::Cpu => app
+            .metrics_history
+            .cpu_spikes()
+            .filter(|s| s.at >= start)
+            .map(|s| (x_of(s.at), s.value))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    // Where related alerts fired and resolved, as vertical lines
+    let markers: Vec<&AlertMarker> = app
+        .metrics_history
+        .alert_markers(app.chart_metric)
+        .filter(|m| m.at >= start)
+        .collect();
+    let markers = &markers[markers.len().saturating_sub(MAX_MARKERS)..];
+    let marker_lines: Vec<[(f64, f64); 2]> = markers
+        .iter()
+        .map(|m| [(x_of(m.at), 0.0), (x_of(m.at), y_max)])
+        .collect();
+
+    let point = Glyphs::for_mode(app.accessible).marker;
+    let mut datasets = vec![
+        Dataset::default()
+            .name(app.chart_metric.title())
+            .marker(point)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&data),
+        Dataset::default()
+            .marker(point)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&cursor_line),
+    ];
+    if !spikes.is_empty() {
+        datasets.push(
+            Dataset::default()
+                .name(format!("spikes ≥{:.0}%", CPU_SPIKE_PERCENT))
+                .marker(symbols::Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(Color::Red))
+                .data(&spikes),
+        );
+    }
+
+    let (mut fired_named, mut resolved_named) = (false, false);
+    for (marker, line) in markers.iter().zip(&marker_lines) {
+        let mut dataset = Dataset::default()
+            .marker(point)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(if marker.resolved {
+                Color::Green
+            } else {
+                app.alert_styles.level_color(marker.level)
+            }))
+            .data(line);
+        // One legend entry per kind
+        if marker.resolved && !resolved_named {
+            dataset = dataset.name("alert resolved");
+            resolved_named = true;
+        } else if !marker.resolved && !fired_named {
+            dataset = dataset.name("alert fired");
+            fired_named = true;
+        }
+        datasets.push(dataset);
+    }
+
+    let time_label = |secs: f64| {
+        Span::styled(
+            (start + Duration::seconds(secs as i64)).format("%H:%M").to_string(),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    let unit = app.chart_metric.unit();
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(format!(
+                    "{} — last {} ({}s buckets)",
+                    app.chart_metric.title(),
+                    app.chart_zoom.label(),
+                    app.chart_zoom.resolution_secs()
+                ))
+                .borders(Borders::ALL),
+        )
+        .x_axis(
+            Axis::default()
+                .bounds([0.0, span_secs])
+                .labels(vec![time_label(0.0), time_label(span_secs / 2.0), time_label(span_secs)]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, y_max])
+                .labels(vec![
+                    Span::raw(format!("0{}", unit)),
+                    Span::raw(format!("{:.0}{}", y_max / 2.0, unit)),
+                    Span::raw(format!("{:.0}{}", y_max, unit)),
+                ]),
+        );
+    f.render_widget(chart, chunks[1]);
+
+    let windows = alert_windows(app, now);
+    if !app.accessible {
+        shade_alert_windows(f, app, Block::default().borders(Borders::ALL).inner(chunks[1]), start, span_secs, &windows);
+    }
+
+    // Alerts within the cursor's bucket, so a marker can be read off
+    let bucket = Duration::seconds(app.chart_zoom.resolution_secs());
+    let mut readout = match cursor {
+        Some(sample) => Line::from(vec![
+            Span::styled(
+                if app.chart_cursor.is_some() { " Cursor " } else { " Live " },
+                Style::default().fg(Color::Black).bg(Color::Yellow),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                format_time(sample.at, "%Y-%m-%d %H:%M:%S"),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("{:.1}{}", sample.value, unit),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ),
+        ]
+        .into_iter()
+        .chain(markers.iter().filter(|m| m.at >= sample.at && m.at < sample.at + bucket).map(|m| {
+            let (verb, color) = if m.resolved {
+                ("resolved", Color::Green)
+            } else {
+                ("fired", app.alert_styles.level_color(m.level))
+            };
+            Span::styled(format!("  {} {}", m.title, verb), Style::default().fg(color))
+        }))
+        .collect::<Vec<_>>()),
+        None => Line::from(Span::styled(" No samples yet", Style::default().fg(Color::DarkGray))),
+    };
+    if let Some(level) = cursor.and_then(|sample| worst_level(&windows, sample.at, sample.at + bucket)) {
+        readout.spans.push(Span::styled(
+            format!("  during {} window", level.as_str()),
+            Style::default().fg(app.alert_styles.level_color(level)),
+        ));
+    }
+    // What the live score is made of and what cost it points
+    if let Some(health) = app.health.as_ref().filter(|_| app.chart_metric == ChartMetric::Health && app.chart_cursor.is_none()) {
+        for part in health.parts.iter().filter(|part| part.weight > 0) {
+            readout.spans.push(Span::styled(format!("  {} {}", part.name, part.score), Style::default().fg(Color::Gray)));
+        }
+        let reasons = health.reasons();
+        if !reasons.is_empty() {
+            readout.spans.push(Span::styled(format!("  ({})", reasons.join(", ")), Style::default().fg(Color::Yellow)));
+        }
+    }
+    f.render_widget(Paragraph::new(readout), chunks[2]);
+}
+
+/// When alerts related to the chart's metric were open, from the persisted
+/// history plus what is active now. Dismissed alerts never resolved, so
+/// they only mark the moment they fired.
+fn alert_windows(app: &App, now: DateTime<Local>) -> Vec<(DateTime<Local>, DateTime<Local>, AlertLevel)> {
+    let sources = app.chart_metric.alert_sources();
+    app.alert_manager
+        .get_history()
+        .iter()
+        .chain(app.alert_manager.get_active_alerts())
+        .filter(|alert| alert.level != AlertLevel::Info)
+        .filter(|alert| sources.iter().any(|prefix| alert.metadata.source.starts_with(prefix)))
+        .map(|alert| {
+            let end = match alert.resolved_at {
+                Some(at) => at,
+                None if alert.is_active() => now,
+                None => alert.triggered_at,
+            };
+            (alert.triggered_at, end, alert.level)
+        })
+        .collect()
+}
+
+/// Worst level of the windows overlapping `from`..`to`
+fn worst_level(windows: &[(DateTime<Local>, DateTime<Local>, AlertLevel)], from: DateTime<Local>, to: DateTime<Local>) -> Option<AlertLevel> {
+    windows
+        .iter()
+        .filter(|(start, end, _)| *start < to && *end >= from)
+        .map(|&(_, _, level)| level)
+        .max_by_key(|&level| level as u8)
+}
+
+/// Tint the plot behind each column by the worst alert level open during
+/// its slice of time. The plot starts right of the axis corner, which is
+/// the lowest `└` in the chart (the legend's box sits above it).
+fn shade_alert_windows(
+    f: &mut Frame,
+    app: &App,
+    inner: Rect,
+    start: DateTime<Local>,
+    span_secs: f64,
+    windows: &[(DateTime<Local>, DateTime<Local>, AlertLevel)],
+) {
+    if windows.is_empty() {
+        return;
+    }
+    let buf = f.buffer_mut();
+    let Some((corner_x, corner_y)) = (inner.top()..inner.bottom())
+        .rev()
+        .find_map(|y| (inner.left()..inner.right()).find(|&x| buf.get(x, y).symbol() == "└").map(|x| (x, y)))
+    else {
+        return;
+    };
+    let width = inner.right().saturating_sub(corner_x + 1);
+    if width == 0 {
+        return;
+    }
+
+    let column_secs = span_secs / width as f64;
+    for column in 0..width {
+        let from = start + Duration::milliseconds((column as f64 * column_secs * 1000.0) as i64);
+        let to = from + Duration::milliseconds((column_secs * 1000.0) as i64);
+        let Some(level) = worst_level(windows, from, to) else {
+            continue;
+        };
+        let tint = tint(app.alert_styles.level_color(level), level, app.light_theme);
+        for y in inner.top()..corner_y {
+            buf.get_mut(corner_x + 1 + column, y).set_bg(tint);
+        }
+    }
+}
+
+/// A background faint enough to keep the data line readable: the level's
+/// color scaled toward the theme's background when it is an RGB one, a
+/// fixed shade per level otherwise
+fn tint(color: Color, level: AlertLevel, light: bool) -> Color {
+    match (color, light) {
+        (Color::Rgb(r, g, b), false) => Color::Rgb(r / 4, g / 4, b / 4),
+        (Color::Rgb(r, g, b), true) => Color::Rgb(255 - (255 - r) / 4, 255 - (255 - g) / 4, 255 - (255 - b) / 4),
+        _ => match (level, light) {
+            (AlertLevel::Critical, false) => Color::Rgb(72, 0, 0),
+            (AlertLevel::Error, false) => Color::Rgb(64, 24, 0),
+            (AlertLevel::Warning, false) => Color::Rgb(56, 48, 0),
+            (AlertLevel::Critical, true) => Color::Rgb(255, 205, 205),
+            (AlertLevel::Error, true) => Color::Rgb(255, 222, 200),
+            (AlertLevel::Warning, true) => Color::Rgb(255, 245, 190),
+            (AlertLevel::Info, _) => Color::Reset,
+        },
+    }
+}
+
+fn draw_selectors(f: &mut Frame, app: &App, area: Rect) {
+    let selected = Style::default().fg(Color::Black).bg(Color::Cyan);
+    let normal = Style::default().fg(Color::Gray);
+
+    let mut spans = Vec::new();
+    for metric in ChartMetric::ALL {
+        spans.push(Span::styled(
+            format!(" {} ", metric.title()),
+            if metric == app.chart_metric { selected } else { normal },
+        ));
+    }
+    spans.push(Span::raw("   "));
+    for zoom in Zoom::ALL {
+        spans.push(Span::styled(
+            format!(" {} ", zoom.label()),
+            if zoom == app.chart_zoom { selected } else { normal },
+        ));
+    }
+    spans.push(Span::styled(
+        "   Tab: Metric  +/-: Zoom  ←/→: Cursor",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
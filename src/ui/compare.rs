@@ -0,0 +1,108 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::compare::{CompareKind, CompareRow, CompareView, NodeSnapshot};
+
+/// Two nodes, or this node then and now, side by side. Figures that moved
+/// and conditions, alerts and VMs found on only one side are highlighted.
+pub fn draw_compare(f: &mut Frame, view: &CompareView, area: Rect) {
+    let popup_area = centered_rect(90, 85, area);
+    f.render_widget(Clear, popup_area);
+
+    let (title, help) = match view.kind {
+        CompareKind::Nodes => (format!(" Compare {} / {} ", view.left.node, view.right.node), " Esc: close "),
+        CompareKind::History => (
+            format!(" Compare {}: {} / now ", view.right.node, ago(&view.left, &view.right)),
+            " ←/→: earlier/later snapshot  Esc: close ",
+        ),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_bottom(help)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+    let rows = view.rows();
+    draw_side(f, &view.left, &view.right, &rows, true, view.kind, columns[0]);
+    draw_side(f, &view.right, &view.left, &rows, false, view.kind, columns[1]);
+}
+
+fn draw_side(
+    f: &mut Frame,
+    side: &NodeSnapshot,
+    other: &NodeSnapshot,
+    rows: &[CompareRow],
+    left: bool,
+    kind: CompareKind,
+    area: Rect,
+) {
+    let title = match kind {
+        CompareKind::Nodes => format!(" {} ", side.node),
+        CompareKind::History if left => format!(" {} ", side.taken_at.format("%Y-%m-%d %H:%M")),
+        CompareKind::History => " now ".to_string(),
+    };
+    let changed = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let heading = |text: String| {
+        Line::from(Span::styled(text, Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)))
+    };
+
+    let mut lines: Vec<Line> = rows
+        .iter()
+        .map(|row| {
+            let value = if left { &row.left } else { &row.right };
+            Line::from(vec![
+                Span::styled(format!("{:<11}", row.label), Style::default().fg(Color::Gray)),
+                Span::styled(value.clone(), if row.changed { changed } else { Style::default() }),
+            ])
+        })
+        .collect();
+
+    // Items only this side has are the difference worth reading
+    for (name, items, others) in [
+        ("Conditions", &side.conditions, &other.conditions),
+        ("Alerts", &side.alerts, &other.alerts),
+        ("VMs", &side.vms, &other.vms),
+    ] {
+        let unique = items.iter().filter(|item| !others.contains(item)).count();
+        lines.push(Line::from(""));
+        lines.push(heading(match unique {
+            0 => format!("{} ({})", name, items.len()),
+            _ => format!("{} ({}, {} only here)", name, items.len(), unique),
+        }));
+        if items.is_empty() {
+            lines.push(Line::from(Span::styled("  none", Style::default().fg(Color::DarkGray))));
+        }
+        for item in items {
+            lines.push(if others.contains(item) {
+                Line::from(format!("  {}", item))
+            } else {
+                Line::from(Span::styled(format!("+ {}", item), changed))
+            });
+        }
+    }
+
+    let block = Block::default().title(title).borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), area);
+}
+
+/// "1h05m ago" between the earlier snapshot and now
+fn ago(earlier: &NodeSnapshot, now: &NodeSnapshot) -> String {
+    let mins = (now.taken_at - earlier.taken_at).num_minutes().max(0);
+    if mins >= 60 {
+        format!("{}h{:02}m ago", mins / 60, mins % 60)
+    } else {
+        format!("{}m ago", mins)
+    }
+}
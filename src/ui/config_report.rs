@@ -0,0 +1,58 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::validation::ConfigReport;
+
+/// Startup screen listing every config problem and what was used instead
+pub fn draw_config_report(f: &mut Frame, report: &ConfigReport, area: Rect, glyphs: &Glyphs) {
+    let popup_area = centered_rect(80, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let source = report.describe_sources("defaults");
+    let block = Block::default()
+        .title(format!(" Configuration Problems: {} ", source))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "{} problem(s) found. Valid settings are in effect; the values below were ignored or look wrong.",
+                report.problems.len()
+            ),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(""),
+    ];
+
+    for problem in &report.problems {
+        let mut location = problem.line.map(|line| format!("line {:<4} ", line)).unwrap_or_else(|| " ".repeat(10));
+        if let Some(ref file) = problem.file {
+            location = format!("{} {}", file.display(), location);
+        }
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", glyphs.error), Style::default().fg(Color::Red)),
+            Span::styled(location, Style::default().fg(Color::DarkGray)),
+            Span::styled(problem.key.clone(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::raw(": "),
+            Span::raw(problem.message.clone()),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        " Enter/Esc: Continue   (hypervisor-tui --check-config re-checks without starting) ",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
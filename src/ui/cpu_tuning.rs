@@ -0,0 +1,113 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::app::{CpuTuningEditor, CpuTuningField};
+
+/// Draw the CPU placement / NUMA / hugepages editor for a VirtualMachine
+pub fn draw_cpu_tuning(f: &mut Frame, editor: &CpuTuningEditor, area: Rect, glyphs: &Glyphs) {
+    let popup_area = centered_rect(70, 60, area);
+    f.render_widget(Clear, popup_area);
+
+    let title = match editor.resource.namespace {
+        Some(ref ns) => format!(" CPU Tuning {}/{} ", ns, editor.resource.name),
+        None => format!(" CPU Tuning {} ", editor.resource.name),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(CpuTuningField::ALL.len() as u16 + 1),  // Fields
+            Constraint::Min(0),                                       // Patch preview
+            Constraint::Length(3),                                    // Status + help
+        ])
+        .split(inner);
+
+    let tuning = &editor.tuning;
+    let original = &editor.original;
+    let on_off = |v: bool| if v { "on" } else { "off" }.to_string();
+    let or_default = |v: &Option<String>| v.clone().unwrap_or_else(|| "(default)".to_string());
+
+    let fields: Vec<Line> = CpuTuningField::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let (label, value, changed) = match field {
+                CpuTuningField::DedicatedCpus => (
+                    "Dedicated CPU placement",
+                    on_off(tuning.dedicated_cpu_placement),
+                    tuning.dedicated_cpu_placement != original.dedicated_cpu_placement,
+                ),
+                CpuTuningField::NumaPassthrough => (
+                    "NUMA guest mapping passthrough",
+                    on_off(tuning.numa_passthrough),
+                    tuning.numa_passthrough != original.numa_passthrough,
+                ),
+                CpuTuningField::Model => ("CPU model", or_default(&tuning.model), tuning.model != original.model),
+                CpuTuningField::Hugepages => (
+                    "Hugepages page size",
+                    tuning.hugepages.clone().unwrap_or_else(|| "none".to_string()),
+                    tuning.hugepages != original.hugepages,
+                ),
+            };
+
+            let marker = if i == editor.selected { glyphs.selected } else { " " };
+            let row_style = if i == editor.selected {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(format!("{} {:<32}", marker, label), row_style.fg(Color::Gray)),
+                Span::styled(
+                    value,
+                    row_style.fg(if changed { Color::Yellow } else { Color::White }),
+                ),
+                Span::styled(if changed { " *" } else { "" }, Style::default().fg(Color::Yellow)),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(fields), chunks[0]);
+
+    let preview = if editor.is_modified() {
+        serde_json::to_string_pretty(&tuning.merge_patch(original)).unwrap_or_default()
+    } else {
+        "No changes".to_string()
+    };
+    let preview = Paragraph::new(preview)
+        .style(Style::default().fg(Color::DarkGray))
+        .block(Block::default().title(" Merge patch ").borders(Borders::TOP));
+    f.render_widget(preview, chunks[1]);
+
+    let status = match (editor.status.as_deref(), tuning.validate()) {
+        (Some(status), _) => Span::styled(status.to_string(), Style::default().fg(Color::Yellow)),
+        (None, Some(problem)) => Span::styled(problem, Style::default().fg(Color::Red)),
+        (None, None) if editor.is_modified() => Span::styled(
+            "Changes take effect after the VM is restarted",
+            Style::default().fg(Color::Yellow),
+        ),
+        (None, None) => Span::raw(""),
+    };
+    let help = Paragraph::new(vec![
+        Line::from(status),
+        Line::from(Span::styled(
+            format!(" {}: Select  Space: Change  a: Apply patch  Esc: Close ", glyphs.up_down),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+    f.render_widget(help, chunks[2]);
+}
@@ -1,45 +1,117 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, Paragraph},
+    widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Sparkline, Table, Wrap},
     Frame,
 };
 
+use crate::units::format_bytes;
+use super::glyphs::Glyphs;
 use crate::app::App;
+use crate::layout::DashboardWidget;
+use crate::metrics_history::{ChartMetric, Forecast, LogRate, Trend, TrendMetric, Zoom};
+use crate::types::{ApiHealth, CgroupKind, CgroupUsage, CoreDump, DiskLatency, EtcdStatus, HardwareInfo, HostReservation, KubeletStatus, MultipathInfo, OomKill, QuotaUsage, ResourceSplit, SocketUsage, Sysctl};
 
+/// Draw the cards of the configured layout, row by row
 pub fn draw(f: &mut Frame, app: &App, area: Rect) {
-    let chunks = Layout::default()
+    let area = match app.layout_edit {
+        Some(_) => {
+            let [grid, help] = split_help(area);
+            draw_edit_help(f, app, help);
+            grid
+        }
+        None => area,
+    };
+
+    let layout = &app.dashboard_layout;
+    let rows = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage(50),  // Top half
-            Constraint::Percentage(50),  // Bottom half
-        ])
+        .constraints(layout.row_heights().into_iter().map(Constraint::Percentage))
         .split(area);
 
-    // Top half - CPU and Memory
-    let top_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(chunks[0]);
+    for (r, (row, row_area)) in layout.rows.iter().zip(rows.iter()).enumerate() {
+        let widths = row.card_widths();
+        let total: u32 = widths.iter().map(|w| *w as u32).sum::<u32>().max(1);
+        let cards = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(widths.iter().map(|w| Constraint::Ratio(*w as u32, total)))
+            .split(*row_area);
+
+        for (c, (widget, card_area)) in row.cards.iter().zip(cards.iter()).enumerate() {
+            draw_widget(f, app, *widget, *card_area);
+            if app.layout_edit == Some((r, c)) {
+                draw_selection(f, app, *widget, *card_area);
+            }
+        }
+    }
+}
+
+fn draw_widget(f: &mut Frame, app: &App, widget: DashboardWidget, area: Rect) {
+    match widget {
+        DashboardWidget::Cpu => draw_cpu(f, app, area),
+        DashboardWidget::Memory => draw_memory(f, app, area),
+        DashboardWidget::Disk => draw_disk(f, app, area),
+        DashboardWidget::Cluster => draw_cluster(f, app, area),
+        DashboardWidget::Vms => draw_vms(f, app, area),
+        DashboardWidget::Sensors => draw_sensors(f, app, area),
+        DashboardWidget::K3s => draw_services(f, app, area),
+        DashboardWidget::Nix => draw_nix(f, app, area),
+        DashboardWidget::Cgroups => draw_cgroups(f, &app.cgroup_usage, area),
+        DashboardWidget::Heatmap => super::heatmap::draw_card(f, app, area),
+        DashboardWidget::Power => draw_power(f, app, area),
+        DashboardWidget::Quotas => draw_quotas(f, &app.quotas, area),
+        DashboardWidget::Etcd => draw_etcd(f, app.etcd_status.as_ref(), app.etcd_db_thresholds, area),
+        DashboardWidget::Sockets => draw_sockets(f, &app.socket_usage, area),
+        DashboardWidget::Ports => draw_ports(f, &app.socket_usage, area),
+        DashboardWidget::Limits => draw_limits(f, app, area),
+        DashboardWidget::Multipath => draw_multipath(f, &app.multipath, area),
+        DashboardWidget::Queues => draw_block_queues(f, &app.disk_latency, area),
+        DashboardWidget::Sysctls => draw_sysctls(f, &app.sysctls, Glyphs::for_mode(app.accessible), area),
+        DashboardWidget::Hardware => draw_hardware(f, &app.hardware, area),
+        DashboardWidget::Reservation => draw_reservation(f, app, area),
+        DashboardWidget::Kubelet => draw_kubelet(f, app.kubelet_status.as_ref(), area),
+        DashboardWidget::Crashes => draw_crashes(f, &app.crash_dumps, area),
+        DashboardWidget::Ooms => draw_oom_kills(f, &app.oom_kills, area),
+        DashboardWidget::Computed => draw_computed(f, app, area),
+        DashboardWidget::Logs => draw_log_volume(f, app, area),
+    }
+}
 
-    draw_cpu(f, app, top_chunks[0]);
-    draw_memory(f, app, top_chunks[1]);
+fn split_help(area: Rect) -> [Rect; 2] {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+    [chunks[0], chunks[1]]
+}
 
-    // Bottom half - Disk and Network
-    let bottom_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(chunks[1]);
+/// Outline the card selected in layout edit mode, over its own border
+fn draw_selection(f: &mut Frame, app: &App, widget: DashboardWidget, area: Rect) {
+    let glyphs = Glyphs::for_mode(app.accessible);
+    let block = Block::default()
+        .title(format!(" {} {} ", glyphs.selected, widget.name()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    f.render_widget(block, area);
+}
 
-    draw_disk(f, app, bottom_chunks[0]);
-    draw_cluster(f, app, bottom_chunks[1]);
+fn draw_edit_help(f: &mut Frame, app: &App, area: Rect) {
+    let text = match app.layout_status {
+        Some(ref status) => Span::styled(format!(" {} ", status), Style::default().fg(Color::Yellow)),
+        None => Span::styled(
+            " LAYOUT: Arrows: Select  Shift+Arrows: Move  +/-: Width  [/]: Height  Tab: Widget  n/N: Add card/row  d: Remove  s: Save  Esc: Done ",
+            Style::default().fg(Color::Black).bg(Color::Yellow),
+        ),
+    };
+    f.render_widget(Paragraph::new(Line::from(text)), area);
 }
 
 fn draw_cpu(f: &mut Frame, app: &App, area: Rect) {
-    let cpu_usage = app.system_metrics.cpu_usage;
+    let cpu_usage = app.displayed_cpu();
+    let forecast = app.metrics_history.forecast(TrendMetric::Cpu, app.warning_thresholds.cpu);
     let gauge = Gauge::default()
-        .block(Block::default().title("CPU Usage").borders(Borders::ALL))
+        .block(Block::default().title(trend_title(app, "CPU Usage", forecast)).borders(Borders::ALL))
         .gauge_style(Style::default().fg(Color::Yellow))
         .percent(cpu_usage as u16)
         .label(format!("{:.1}%", cpu_usage));
@@ -54,8 +126,9 @@ fn draw_memory(f: &mut Frame, app: &App, area: Rect) {
         0
     };
 
+    let forecast = app.metrics_history.forecast(TrendMetric::Memory, app.warning_thresholds.memory);
     let gauge = Gauge::default()
-        .block(Block::default().title("Memory Usage").borders(Borders::ALL))
+        .block(Block::default().title(trend_title(app, "Memory Usage", forecast)).borders(Borders::ALL))
         .gauge_style(Style::default().fg(Color::Cyan))
         .percent(mem_percent)
         .label(format!(
@@ -67,7 +140,7 @@ fn draw_memory(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn draw_disk(f: &mut Frame, app: &App, area: Rect) {
-    let text = vec![
+    let mut text = vec![
         Line::from(vec![
             Span::styled("Disk I/O", Style::default().fg(Color::Green)),
         ]),
@@ -89,21 +162,124 @@ fn draw_disk(f: &mut Frame, app: &App, area: Rect) {
         Line::from(vec![
             Span::styled("Root Partition", Style::default().fg(Color::Green)),
         ]),
-        Line::from(vec![
-            Span::styled("  Used: ", Style::default().fg(Color::Gray)),
-            Span::styled(
-                format!("{:.1}%", app.system_metrics.disk_usage_percent),
-                Style::default().fg(Color::Yellow)
-            ),
-        ]),
+        Line::from(
+            [
+                vec![
+                    Span::styled("  Used: ", Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        format!("{:.1}%", app.system_metrics.disk_usage_percent),
+                        Style::default().fg(Color::Yellow)
+                    ),
+                ],
+                trend_spans(
+                    app,
+                    app.metrics_history.forecast(TrendMetric::DiskUsage, app.warning_thresholds.disk),
+                ),
+            ]
+            .concat(),
+        ),
     ];
 
+    // Per-device rates with request latency right after the totals, since
+    // throughput alone hides a saturated or dying disk
+    let devices: Vec<Line> = app.disk_latency.iter().map(|disk| disk_latency_line(app, disk)).collect();
+    text.splice(3..3, devices);
+
+    if !app.system_metrics.filesystems.is_empty() {
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled("Filesystems", Style::default().fg(Color::Green))));
+    }
+    for fs in &app.system_metrics.filesystems {
+        let mut spans = vec![
+            Span::styled(format!("  {:<16}", fs.mount_point), Style::default().fg(Color::Gray)),
+            Span::styled(format!("{:>5.1}%", fs.used_percent()), Style::default().fg(Color::Yellow)),
+        ];
+        // Only worth a mention within a month; further out it is noise
+        if let Some(forecast) = app.metrics_history.time_to_full(&fs.mount_point).filter(|f| f.hours < 24.0 * 30.0) {
+            let (warning_hours, critical_hours) = app.disk_full_hours;
+            let color = if forecast.hours <= critical_hours {
+                Color::Red
+            } else if forecast.hours <= warning_hours {
+                Color::Yellow
+            } else {
+                Color::Gray
+            };
+            spans.push(Span::styled(
+                format!("  full in ~{}", forecast.eta()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ));
+        }
+        text.push(Line::from(spans));
+    }
+
     let paragraph = Paragraph::new(text)
         .block(Block::default().title("Storage").borders(Borders::ALL));
 
     f.render_widget(paragraph, area);
 }
 
+/// One block device: read/write rates, busy share, and request latency
+/// now and its percentiles, colored against the disk latency thresholds
+fn disk_latency_line<'a>(app: &App, disk: &'a DiskLatency) -> Line<'a> {
+    let (warning_ms, critical_ms) = app.disk_latency_thresholds;
+    let color = |ms: Option<f64>| match ms {
+        Some(ms) if ms >= critical_ms => Color::Red,
+        Some(ms) if ms >= warning_ms => Color::Yellow,
+        Some(_) => Color::Green,
+        None => Color::DarkGray,
+    };
+    let ms = |ms: Option<f64>| ms.map_or("-".to_string(), |ms| format!("{:.1}", ms));
+    Line::from(vec![
+        Span::styled(format!("  {:<18}", disk.name()), Style::default().fg(Color::Gray)),
+        Span::styled(
+            format!("R {:>6.1} W {:>6.1} MB/s {:>3.0}%", disk.read_mb_s, disk.write_mb_s, disk.util_percent),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled("  now ", Style::default().fg(Color::Gray)),
+        Span::styled(ms(disk.await_ms), Style::default().fg(color(disk.await_ms))),
+        Span::styled(" p50 ", Style::default().fg(Color::Gray)),
+        Span::styled(ms(disk.p50_ms), Style::default().fg(color(disk.p50_ms))),
+        Span::styled(" p99 ", Style::default().fg(Color::Gray)),
+        Span::styled(ms(disk.p99_ms), Style::default().fg(color(disk.p99_ms))),
+        Span::styled(" ms", Style::default().fg(Color::Gray)),
+    ])
+}
+
+/// Gauge title followed by the trend arrow and any breach forecast
+fn trend_title<'a>(app: &App, title: &'a str, forecast: Option<Forecast>) -> Line<'a> {
+    Line::from([vec![Span::raw(title)], trend_spans(app, forecast)].concat())
+}
+
+fn trend_spans(app: &App, forecast: Option<Forecast>) -> Vec<Span<'static>> {
+    let Some(forecast) = forecast else {
+        return Vec::new();
+    };
+    let glyphs = Glyphs::for_mode(app.accessible);
+    let arrow = match forecast.trend {
+        Trend::Rising => Span::styled(glyphs.rising, Style::default().fg(Color::Red)),
+        Trend::Falling => Span::styled(glyphs.falling, Style::default().fg(Color::Green)),
+        Trend::Steady => Span::styled(glyphs.steady, Style::default().fg(Color::Gray)),
+    };
+    let mut spans = vec![Span::raw(" "), arrow];
+    if let Some(mins) = forecast.breach_in_mins {
+        spans.push(Span::styled(
+            format!(" warning in {}", format_eta(mins)),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans
+}
+
+fn format_eta(mins: f64) -> String {
+    if mins < 1.0 {
+        "<1m".to_string()
+    } else if mins < 90.0 {
+        format!("~{:.0}m", mins)
+    } else {
+        format!("~{:.0}h", mins / 60.0)
+    }
+}
+
 fn draw_cluster(f: &mut Frame, app: &App, area: Rect) {
     let nodes_color = if app.k8s_info.nodes_ready == app.k8s_info.nodes_total && app.k8s_info.nodes_total > 0 {
         Color::Green
@@ -113,7 +289,7 @@ fn draw_cluster(f: &mut Frame, app: &App, area: Rect) {
         Color::Red
     };
 
-    let text = vec![
+    let mut text = vec![
         Line::from(vec![
             Span::styled("Kubernetes Cluster", Style::default().fg(Color::Green)),
         ]),
@@ -138,7 +314,48 @@ fn draw_cluster(f: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(Color::Cyan)
             ),
         ]),
-        Line::from(""),
+    ];
+    if let Some(ref health) = app.api_health {
+        text.push(api_health_line(health, app.api_latency_thresholds));
+    }
+
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().title("Cluster Status").borders(Borders::ALL));
+
+    f.render_widget(paragraph, area);
+}
+
+fn api_health_line(health: &ApiHealth, (warning_ms, critical_ms): (f64, f64)) -> Line<'static> {
+    let (latency, color) = match (health.healthz_ms, health.latency_ms()) {
+        (None, _) | (_, None) => ("unreachable".to_string(), Color::Red),
+        (Some(healthz), Some(slowest)) => {
+            let color = if slowest >= critical_ms {
+                Color::Red
+            } else if slowest >= warning_ms {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+            let list = health.list_ms.map(|ms| format!("{:.0} ms", ms)).unwrap_or_else(|| "failed".to_string());
+            let p99 = health.p99_ms.map(|ms| format!(", p99 {:.0} ms", ms)).unwrap_or_default();
+            (format!("{:.0} ms (list {}{})", healthz, list, p99), color)
+        }
+    };
+    let (etcd, etcd_color) = match health.etcd_ok {
+        Some(true) => ("ok", Color::Green),
+        Some(false) => ("failing", Color::Red),
+        None => ("n/a", Color::DarkGray),
+    };
+    Line::from(vec![
+        Span::styled("  API: ", Style::default().fg(Color::Gray)),
+        Span::styled(latency, Style::default().fg(color)),
+        Span::styled("  etcd: ", Style::default().fg(Color::Gray)),
+        Span::styled(etcd, Style::default().fg(etcd_color)),
+    ])
+}
+
+fn draw_vms(f: &mut Frame, app: &App, area: Rect) {
+    let text = vec![
         Line::from(vec![
             Span::styled("KubeVirt VMs", Style::default().fg(Color::Green)),
         ]),
@@ -166,7 +383,1102 @@ fn draw_cluster(f: &mut Frame, app: &App, area: Rect) {
     ];
 
     let paragraph = Paragraph::new(text)
-        .block(Block::default().title("Cluster Status").borders(Borders::ALL));
+        .block(Block::default().title("Virtual Machines").borders(Borders::ALL));
+
+    f.render_widget(paragraph, area);
+}
+
+fn draw_sensors(f: &mut Frame, app: &App, area: Rect) {
+    let mut text: Vec<Line> = app
+        .system_metrics
+        .temperatures
+        .iter()
+        .map(|sensor| {
+            let color = match sensor.critical {
+                Some(critical) if sensor.celsius >= critical => Color::Red,
+                Some(critical) if sensor.celsius >= critical - 10.0 => Color::Yellow,
+                _ => Color::Green,
+            };
+            Line::from(vec![
+                Span::styled(format!("  {:<24}", sensor.label), Style::default().fg(Color::Gray)),
+                Span::styled(format!("{:>5.1}°C", sensor.celsius), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    if text.is_empty() {
+        text.push(Line::from(Span::styled(
+            "No temperature sensors found",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().title("Sensors").borders(Borders::ALL));
+
+    f.render_widget(paragraph, area);
+}
+
+/// systemd state of the cluster flavor's service units
+fn draw_services(f: &mut Frame, app: &App, area: Rect) {
+    let mut text: Vec<Line> = app
+        .host_info
+        .services
+        .iter()
+        .map(|(unit, state)| {
+            let color = match state.as_str() {
+                "active" => Color::Green,
+                "activating" | "reloading" => Color::Yellow,
+                "inactive" => Color::DarkGray,
+                _ => Color::Red,
+            };
+            Line::from(vec![
+                Span::styled(format!("  {:<20}", unit), Style::default().fg(Color::Gray)),
+                Span::styled(state.clone(), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    if text.is_empty() {
+        text.push(Line::from(Span::styled(
+            "Service states unavailable",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .title(format!("{} Services", app.cluster_flavor.label()))
+            .borders(Borders::ALL),
+    );
 
     f.render_widget(paragraph, area);
 }
+
+/// RAPL power draw, frequency scaling and C-state residency
+fn draw_power(f: &mut Frame, app: &App, area: Rect) {
+    let power = &app.power_info;
+    let label = |text: &str| Span::styled(format!("  {:<11}", text), Style::default().fg(Color::Gray));
+
+    let mut text = vec![Line::from(vec![
+        label("Package:"),
+        match power.package_watts {
+            Some(watts) => Span::styled(format!("{:.1} W", watts), Style::default().fg(Color::Yellow)),
+            None => Span::styled("n/a (RAPL unreadable)", Style::default().fg(Color::DarkGray)),
+        },
+        Span::styled(
+            power
+                .domains
+                .iter()
+                .map(|(name, watts)| format!("  {} {:.0}W", name, watts))
+                .collect::<String>(),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])];
+
+    text.push(Line::from(vec![
+        label("Frequency:"),
+        Span::styled(format!("{:.0} MHz", power.freq_avg_mhz), Style::default().fg(Color::Cyan)),
+        Span::styled(
+            format!("  ({:.0}-{:.0})", power.freq_min_mhz, power.freq_max_mhz),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::raw("  "),
+        Span::raw(power.governor.clone().unwrap_or_default()),
+        Span::styled(
+            power.base_mhz.map(|base| format!("  base {:.0}", base)).unwrap_or_default(),
+            Style::default().fg(Color::DarkGray),
+        ),
+        match power.turbo {
+            Some(true) => Span::styled("  turbo on", Style::default().fg(Color::Green)),
+            Some(false) => Span::styled("  turbo off", Style::default().fg(Color::Yellow)),
+            None => Span::raw(""),
+        },
+    ]));
+
+    // Per-core clocks, busy cores held below the throttle threshold in red
+    let (percent, _) = app.freq_throttle;
+    let throttled: Vec<u32> = power.throttled_cores(percent).iter().map(|core| core.cpu).collect();
+    if !power.cores.is_empty() {
+        let cores: Vec<Span> = power
+            .cores
+            .iter()
+            .map(|core| {
+                let color = if throttled.contains(&core.cpu) {
+                    Color::Red
+                } else if core.is_busy() {
+                    Color::Cyan
+                } else {
+                    Color::DarkGray
+                };
+                Span::styled(format!("{}:{:.0} ", core.cpu, core.cur_mhz), Style::default().fg(color))
+            })
+            .collect();
+        text.push(Line::from([vec![label("Cores:")], cores].concat()));
+    }
+    if power.is_throttled(percent) {
+        text.push(Line::from(vec![
+            label("Slowed:"),
+            Span::styled(
+                format!(
+                    "{} of {} busy cores under {:.0}% of base clock",
+                    throttled.len(),
+                    power.busy_cores(),
+                    percent
+                ),
+                Style::default().fg(Color::Red),
+            ),
+        ]));
+    }
+
+    if power.throttle_events > 0 {
+        text.push(Line::from(vec![
+            label("Throttled:"),
+            Span::styled(
+                format!("{} thermal events", power.throttle_events),
+                Style::default().fg(Color::Red),
+            ),
+        ]));
+    }
+
+    let residency: Vec<Span> = power
+        .cstates
+        .iter()
+        .filter(|(_, percent)| *percent >= 0.1)
+        .map(|(name, percent)| Span::raw(format!("{} {:.0}%  ", name, percent)))
+        .collect();
+    if !residency.is_empty() {
+        text.push(Line::from([vec![label("C-states:")], residency].concat()));
+    }
+
+    let sparkline_width = area.width.saturating_sub(2) as usize;
+    let history: Vec<u64> = app
+        .metrics_history
+        .series(ChartMetric::Power, Zoom::FiveMinutes)
+        .iter()
+        .rev()
+        .take(sparkline_width)
+        .rev()
+        .map(|s| s.value as u64)
+        .collect();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(2)])
+        .split(area);
+
+    let paragraph = Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title("Power")
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT),
+    );
+    f.render_widget(paragraph, chunks[0]);
+
+    let sparkline = Sparkline::default()
+        .data(&history)
+        .style(Style::default().fg(Color::Yellow))
+        .block(Block::default().borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT));
+    f.render_widget(sparkline, chunks[1]);
+}
+
+fn draw_nix(f: &mut Frame, app: &App, area: Rect) {
+    let host = &app.host_info;
+    let label = |text: &str| Span::styled(format!("  {:<12}", text), Style::default().fg(Color::Gray));
+    let store_name = |path: &Option<String>| {
+        path.as_deref()
+            .and_then(|p| p.rsplit('/').next())
+            .unwrap_or("-")
+            .to_string()
+    };
+
+    let text = match host.nixos_version {
+        None => vec![Line::from(Span::styled(
+            "Not a NixOS host",
+            Style::default().fg(Color::DarkGray),
+        ))],
+        Some(ref version) => {
+            let mut lines = vec![
+                Line::from(vec![label("Version:"), Span::styled(version.clone(), Style::default().fg(Color::Cyan))]),
+                Line::from(vec![
+                    label("Generation:"),
+                    Span::styled(
+                        host.generation.map(|g| g.to_string()).unwrap_or_else(|| "-".to_string()),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                ]),
+                Line::from(vec![label("Current:"), Span::raw(store_name(&host.current_system))]),
+                Line::from(vec![label("Booted:"), Span::raw(store_name(&host.booted_system))]),
+            ];
+            if host.reboot_pending() {
+                lines.push(Line::from(Span::styled(
+                    "  Reboot pending: booted system differs from current",
+                    Style::default().fg(Color::Yellow),
+                )));
+            }
+            lines
+        }
+    };
+
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().title("NixOS").borders(Borders::ALL));
+
+    f.render_widget(paragraph, area);
+}
+
+/// Per-slice and per-pod resource attribution from cgroup v2
+fn draw_cgroups(f: &mut Frame, usage: &[CgroupUsage], area: Rect) {
+    let by_cpu = |kind: CgroupKind| {
+        let mut entries: Vec<&CgroupUsage> = usage.iter().filter(|u| u.kind == kind).collect();
+        entries.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+        entries
+    };
+
+    // Slices first, then pods with their containers nested below
+    let mut ordered: Vec<&CgroupUsage> = by_cpu(CgroupKind::Slice);
+    for pod in by_cpu(CgroupKind::Pod) {
+        ordered.push(pod);
+        let prefix = format!("{}/", pod.path);
+        ordered.extend(
+            usage
+                .iter()
+                .filter(|u| u.kind == CgroupKind::Container && u.path.starts_with(&prefix)),
+        );
+    }
+
+    let rows: Vec<Row> = ordered
+        .into_iter()
+        .map(|u| {
+            let (label, color) = match u.kind {
+                CgroupKind::Slice => (u.name.clone(), Color::Green),
+                CgroupKind::Pod => (format!("  {}", u.name), Color::Cyan),
+                CgroupKind::Container => (format!("    {}", u.name), Color::DarkGray),
+            };
+            Row::new(vec![
+                Cell::from(label).style(Style::default().fg(color)),
+                Cell::from(format!("{:>6.1}%", u.cpu_percent)).style(Style::default().fg(Color::Yellow)),
+                Cell::from(format_bytes(u.memory_bytes)).style(Style::default().fg(Color::Yellow)),
+            ])
+        })
+        .collect();
+
+    let header = Row::new(vec!["cgroup", "   CPU", "Memory"])
+        .style(Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD));
+
+    let table = Table::new(
+        rows,
+        [Constraint::Min(30), Constraint::Length(8), Constraint::Length(10)],
+    )
+    .header(header)
+    .block(Block::default().title("Resource Attribution (cgroups)").borders(Borders::ALL));
+
+    f.render_widget(table, area);
+}
+
+/// Host CPU and memory as stacked bars: system services, KubeVirt VMs,
+/// other pods and what is left, so the virtualization stack's own headroom
+/// is visible at a glance
+fn draw_reservation(f: &mut Frame, app: &App, area: Rect) {
+    let memory_total = (app.system_metrics.memory_total_gb * 1_073_741_824.0) as u64;
+    let reservation = HostReservation::from_cgroups(&app.cgroup_usage, &app.launcher_pods, memory_total);
+
+    let block = Block::default().title("Host Reservation (system / VMs / pods / free)").borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let bar_width = inner.width.saturating_sub(8) as usize;
+    let mut lines = Vec::new();
+    for (label, split) in [("CPU", reservation.cpu), ("Memory", reservation.memory)] {
+        let mut bar = vec![Span::styled(format!("{:<7}", label), Style::default().fg(Color::Cyan))];
+        bar.extend(split_bar(&split, bar_width, app.accessible));
+        lines.push(Line::from(bar));
+        lines.push(Line::from(vec![
+            Span::raw("       "),
+            Span::styled(format!("system {:.0}%  ", split.system), Style::default().fg(Color::Magenta)),
+            Span::styled(format!("vms {:.0}%  ", split.vms), Style::default().fg(Color::Blue)),
+            Span::styled(format!("pods {:.0}%  ", split.pods), Style::default().fg(Color::Yellow)),
+            Span::styled(format!("free {:.0}%", split.free), Style::default().fg(Color::Green)),
+        ]));
+    }
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// One bar of `width` cells, each segment sized to its share
+fn split_bar(split: &ResourceSplit, width: usize, ascii: bool) -> Vec<Span<'static>> {
+    let (full, empty) = if ascii { ("#", ".") } else { ("█", "░") };
+    let segments = [
+        (split.system, Color::Magenta),
+        (split.vms, Color::Blue),
+        (split.pods, Color::Yellow),
+    ];
+    let mut spans = Vec::new();
+    let mut used = 0;
+    for (percent, color) in segments {
+        let cells = ((percent / 100.0 * width as f64).round() as usize).min(width - used);
+        used += cells;
+        spans.push(Span::styled(full.repeat(cells), Style::default().fg(color)));
+    }
+    spans.push(Span::styled(empty.repeat(width - used), Style::default().fg(Color::DarkGray)));
+    spans
+}
+
+/// Namespace quota resources, closest to their hard limit first
+fn draw_quotas(f: &mut Frame, quotas: &[QuotaUsage], area: Rect) {
+    let amount = |quota: &QuotaUsage, value: f64| {
+        if quota.is_cpu() {
+            format!("{:.1}", value)
+        } else if quota.is_bytes() {
+            format_bytes(value as u64)
+        } else {
+            format!("{:.0}", value)
+        }
+    };
+
+    let rows: Vec<Row> = quotas
+        .iter()
+        .map(|q| {
+            let percent = q.percent();
+            let color = if percent >= 95.0 {
+                Color::Red
+            } else if percent >= 80.0 {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+            Row::new(vec![
+                Cell::from(q.namespace.clone()).style(Style::default().fg(Color::Cyan)),
+                Cell::from(q.resource.clone()),
+                Cell::from(format!("{} / {}", amount(q, q.used), amount(q, q.hard)))
+                    .style(Style::default().fg(Color::Gray)),
+                Cell::from(format!("{:>5.1}%", percent)).style(Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    let header = Row::new(vec!["Namespace", "Resource", "Used / Hard", "  Used"])
+        .style(Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD));
+
+    let title = if quotas.is_empty() {
+        "Resource Quotas (none defined)".to_string()
+    } else {
+        format!("Resource Quotas ({})", quotas.len())
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(12),
+            Constraint::Min(16),
+            Constraint::Length(22),
+            Constraint::Length(7),
+        ],
+    )
+    .header(header)
+    .block(Block::default().title(title).borders(Borders::ALL));
+
+    f.render_widget(table, area);
+}
+
+/// Database size against the quota and how much of it a defrag would
+/// free, then leadership and raft proposal health
+fn draw_etcd(f: &mut Frame, status: Option<&EtcdStatus>, (warning, critical): (f64, f64), area: Rect) {
+    let block = Block::default().title("etcd").borders(Borders::ALL);
+    let Some(status) = status else {
+        let text = Paragraph::new("No etcd on this host (agent node, or kine/SQLite datastore)")
+            .style(Style::default().fg(Color::DarkGray))
+            .wrap(Wrap { trim: true })
+            .block(block);
+        f.render_widget(text, area);
+        return;
+    };
+
+    let row = |label: &str, value: String, color: Color| {
+        Line::from(vec![
+            Span::styled(format!("{:<11}", label), Style::default().fg(Color::Gray)),
+            Span::styled(value, Style::default().fg(color)),
+        ])
+    };
+    let size = match (status.quota_bytes, status.quota_percent()) {
+        (Some(quota), Some(percent)) => {
+            let color = if percent >= critical {
+                Color::Red
+            } else if percent >= warning {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+            row(
+                "DB size",
+                format!("{} / {} ({:.1}%)", format_bytes(status.db_size_bytes), format_bytes(quota), percent),
+                color,
+            )
+        }
+        _ => row("DB size", format_bytes(status.db_size_bytes), Color::White),
+    };
+    let fragmentation = match (status.db_in_use_bytes, status.fragmentation_percent()) {
+        (Some(in_use), Some(percent)) => row(
+            "In use",
+            format!("{} ({:.0}% free pages)", format_bytes(in_use), percent),
+            if percent >= 50.0 { Color::Yellow } else { Color::White },
+        ),
+        _ => row("In use", "n/a".to_string(), Color::DarkGray),
+    };
+    let leader = match (status.has_leader, status.is_leader) {
+        (Some(false), _) => row("Leader", "none".to_string(), Color::Red),
+        (_, Some(true)) => row("Leader", "this member".to_string(), Color::Green),
+        (Some(true), _) => row("Leader", "elected".to_string(), Color::Green),
+        _ => row("Leader", "unknown".to_string(), Color::DarkGray),
+    };
+    let lines = vec![
+        size,
+        fragmentation,
+        leader,
+        row(
+            "Elections",
+            format!("{} in the past hour, {} total", status.leader_changes_last_hour, status.leader_changes_total),
+            if status.leader_changes_last_hour > 0 { Color::Yellow } else { Color::White },
+        ),
+        row(
+            "Proposals",
+            format!(
+                "{} failed ({} new), {} pending",
+                status.proposals_failed_total,
+                status.proposals_failed_recent,
+                status.proposals_pending.map_or_else(|| "?".to_string(), |p| p.to_string())
+            ),
+            if status.proposals_failed_recent > 0 { Color::Red } else { Color::White },
+        ),
+        row("Endpoint", status.endpoint.clone(), Color::DarkGray),
+    ];
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// System fds and ephemeral ports as gauges-in-text, then the watched
+/// processes closest to their open file limit
+fn draw_sockets(f: &mut Frame, usage: &SocketUsage, area: Rect) {
+    let color = |percent: f64| {
+        if percent >= 90.0 {
+            Color::Red
+        } else if percent >= 70.0 {
+            Color::Yellow
+        } else {
+            Color::Green
+        }
+    };
+
+    let fd_percent = usage.fd_percent();
+    let port_percent = usage.ephemeral_percent();
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("fds   ", Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{:>5.1}%", fd_percent), Style::default().fg(color(fd_percent))),
+            Span::styled(
+                format!("  {} / {}", usage.fds_allocated, usage.fds_max),
+                Style::default().fg(Color::Gray),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("ports ", Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{:>5.1}%", port_percent), Style::default().fg(color(port_percent))),
+            Span::styled(
+                format!(
+                    "  {} of {}-{}, {} TIME_WAIT",
+                    usage.ephemeral_in_use, usage.ephemeral_range.0, usage.ephemeral_range.1, usage.time_wait
+                ),
+                Style::default().fg(Color::Gray),
+            ),
+        ]),
+    ];
+    for process in &usage.processes {
+        let (percent, limit) = match (process.percent(), process.limit) {
+            (Some(percent), Some(limit)) => (format!("{:>5.1}%", percent), limit.to_string()),
+            _ => ("    -".to_string(), "unlimited".to_string()),
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!("{:<16}", format!("{}[{}]", process.name, process.pid))),
+            Span::styled(percent, Style::default().fg(color(process.percent().unwrap_or(0.0)))),
+            Span::styled(format!("  {} / {}", process.open, limit), Style::default().fg(Color::Gray)),
+        ]));
+    }
+
+    let block = Block::default().title("Sockets & File Descriptors").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Kernel limits that break things in odd ways when hit, as one bar each:
+/// entropy left in the random pool, open files against fs.file-max and
+/// threads against the PID space
+fn draw_limits(f: &mut Frame, app: &App, area: Rect) {
+    let limits = &app.host_limits;
+    let usage = &app.socket_usage;
+    let used_color = |percent: f64, (warning, critical): (f64, f64)| {
+        if percent >= critical {
+            Color::Red
+        } else if percent >= warning {
+            Color::Yellow
+        } else {
+            Color::Green
+        }
+    };
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let bar_width = inner_width.saturating_sub(30).clamp(4, 30);
+    let (full, empty) = if app.accessible { ("#", ".") } else { ("█", "░") };
+    let gauge = |label: &str, fill: f64, color: Color, detail: String| {
+        let cells = ((fill / 100.0 * bar_width as f64).round() as usize).min(bar_width);
+        Line::from(vec![
+            Span::styled(format!("{:<8}", label), Style::default().fg(Color::Cyan)),
+            Span::styled(full.repeat(cells), Style::default().fg(color)),
+            Span::styled(empty.repeat(bar_width - cells), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!(" {}", detail), Style::default().fg(color)),
+        ])
+    };
+
+    let mut lines = Vec::new();
+    match (limits.entropy_bits, limits.entropy_pool_bits) {
+        (Some(bits), pool) => {
+            let (warning, critical) = app.entropy_thresholds;
+            let color = if bits < critical {
+                Color::Red
+            } else if bits < warning {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+            let pool = pool.filter(|&pool| pool > 0).unwrap_or(bits.max(1));
+            let fill = bits as f64 / pool as f64 * 100.0;
+            lines.push(gauge("entropy", fill, color, format!("{} / {} bits", bits, pool)));
+        }
+        (None, _) => lines.push(Line::from(vec![
+            Span::styled(format!("{:<8}", "entropy"), Style::default().fg(Color::Cyan)),
+            Span::styled("not exposed by this kernel", Style::default().fg(Color::DarkGray)),
+        ])),
+    }
+    let percent = usage.fd_percent();
+    lines.push(gauge(
+        "fds",
+        percent,
+        used_color(percent, app.fd_thresholds),
+        format!("{:.1}%  {} / {}", percent, usage.fds_allocated, usage.fds_max),
+    ));
+    let percent = limits.task_percent();
+    lines.push(gauge(
+        "pids",
+        percent,
+        used_color(percent, app.pid_thresholds),
+        format!("{:.1}%  {} / {}", percent, limits.tasks, limits.task_limit),
+    ));
+    if !limits.task_limit_name.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("        limit is {}", limits.task_limit_name),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let block = Block::default().title("System Limits").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Who listens on each watched service port and how many connections it
+/// has, then listeners nobody expects
+fn draw_ports(f: &mut Frame, usage: &SocketUsage, area: Rect) {
+    let mut lines: Vec<Line> = usage
+        .ports
+        .iter()
+        .map(|port| {
+            let (state, color) = if !port.listeners.is_empty() {
+                (port.listeners.join(" "), Color::Green)
+            } else if port.stopped {
+                ("stopped listening".to_string(), Color::Red)
+            } else if port.required {
+                ("not listening".to_string(), Color::Red)
+            } else {
+                ("not listening".to_string(), Color::DarkGray)
+            };
+            Line::from(vec![
+                Span::styled(format!("{:<15}", port.name), Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:<10}", port.ports), Style::default().fg(Color::Gray)),
+                Span::styled(format!("{:>5} conn  ", port.connections), Style::default().fg(Color::Gray)),
+                Span::styled(state, Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    if usage.ports.is_empty() {
+        lines.push(Line::from(Span::styled("No watched ports", Style::default().fg(Color::DarkGray))));
+    }
+    if !usage.unexpected_listeners.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("unexpected     ", Style::default().fg(Color::Yellow)),
+            Span::styled(usage.unexpected_listeners.join(" "), Style::default().fg(Color::Yellow)),
+        ]));
+    }
+
+    let block = Block::default().title("Service Ports").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), area);
+}
+
+/// Working paths of each multipath LUN and the state of each path group,
+/// then the FC HBA ports
+fn draw_multipath(f: &mut Frame, info: &MultipathInfo, area: Rect) {
+    let mut lines = Vec::new();
+    if info.maps.is_empty() {
+        lines.push(Line::from(Span::styled("No multipath devices", Style::default().fg(Color::DarkGray))));
+    }
+    for map in &info.maps {
+        let total = map.paths().count();
+        let working = total - map.failed_paths();
+        let color = if working == 0 {
+            Color::Red
+        } else if working < total {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        let mut spans = vec![
+            Span::styled(format!("{:<14}", map.name), Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{:>2}/{:<2} paths", working, total), Style::default().fg(color)),
+        ];
+        for group in &map.groups {
+            let group_working = group.paths.iter().filter(|path| !path.failed()).count();
+            let group_color = if group_working < group.paths.len() { Color::Yellow } else { Color::Gray };
+            let priority = group.priority.map(|p| format!(" prio {}", p)).unwrap_or_default();
+            spans.push(Span::styled(
+                format!("  {}{} {}/{}", group.status, priority, group_working, group.paths.len()),
+                Style::default().fg(group_color),
+            ));
+        }
+        if let Some(ref size) = map.size {
+            spans.push(Span::styled(format!("  {}", size), Style::default().fg(Color::DarkGray)));
+        }
+        lines.push(Line::from(spans));
+    }
+    for port in &info.hba_ports {
+        let color = if port.state == "Online" { Color::Green } else { Color::Red };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<14}", port.host), Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{:<9}", port.state), Style::default().fg(color)),
+            Span::styled(
+                format!(" {} {}", port.port_name, port.speed.as_deref().unwrap_or("")),
+                Style::default().fg(Color::Gray),
+            ),
+        ]));
+    }
+
+    let block = Block::default().title("Storage Paths").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Each block device's I/O scheduler, queue depth, disk type and requests
+/// in flight, VM datastores starred and a poorly suited scheduler in
+/// yellow with the one to use instead
+fn draw_block_queues(f: &mut Frame, disks: &[DiskLatency], area: Rect) {
+    let mut lines = Vec::new();
+    if disks.is_empty() {
+        lines.push(Line::from(Span::styled("No block devices", Style::default().fg(Color::DarkGray))));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!("  {:<18}{:<13}{:>6} {:<5}{:>9}", "Device", "Scheduler", "Depth", "Type", "In flight"),
+            Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD),
+        )));
+    }
+    for disk in disks {
+        let queue = &disk.queue;
+        let kind = if queue.rotational {
+            "HDD"
+        } else if disk.device.starts_with("nvme") {
+            "NVMe"
+        } else {
+            "SSD"
+        };
+        let better = queue.better_scheduler(&disk.device);
+        let mut spans = vec![
+            Span::styled(if disk.datastore { "* " } else { "  " }, Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{:<18}", disk.name()), Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("{:<13}", queue.scheduler.as_deref().unwrap_or("-")),
+                Style::default().fg(if better.is_some() { Color::Yellow } else { Color::Green }),
+            ),
+            Span::styled(
+                format!("{:>6} {:<5}", queue.depth.map_or("-".to_string(), |depth| depth.to_string()), kind),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::styled(
+                format!("{:>9}", disk.in_flight),
+                Style::default().fg(if disk.in_flight > 0 { Color::White } else { Color::DarkGray }),
+            ),
+        ];
+        if let Some(better) = better {
+            spans.push(Span::styled(format!("  use {}", better), Style::default().fg(Color::Yellow)));
+        }
+        lines.push(Line::from(spans));
+    }
+    if disks.iter().any(|disk| disk.datastore) {
+        lines.push(Line::from(Span::styled("  * VM datastore", Style::default().fg(Color::DarkGray))));
+    }
+
+    let block = Block::default().title("Block Queues").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Current value of each watched sysctl, with its expected value in green
+/// when met and red when not, marked so the difference isn't in color alone
+fn draw_sysctls(f: &mut Frame, sysctls: &[Sysctl], glyphs: &Glyphs, area: Rect) {
+    let mut lines = Vec::new();
+    if sysctls.is_empty() {
+        lines.push(Line::from(Span::styled("No sysctls watched", Style::default().fg(Color::DarkGray))));
+    }
+    for sysctl in sysctls {
+        let value = sysctl.value.as_deref().unwrap_or("missing");
+        let mut spans = vec![
+            Span::styled(format!("{:<36}", sysctl.name), Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{:>10}", value), Style::default().fg(Color::White)),
+        ];
+        if let Some(ref expected) = sysctl.expected {
+            let (note, color) = if sysctl.mismatched() {
+                (glyphs.error, Color::Red)
+            } else {
+                (glyphs.check, Color::Green)
+            };
+            spans.push(Span::styled(format!("  {} want {}", note, expected), Style::default().fg(color)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let block = Block::default().title("Kernel Tunables").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// System, BIOS and microcode versions with the virtualization features
+/// VMs depend on
+fn draw_hardware(f: &mut Frame, info: &HardwareInfo, area: Rect) {
+    let unknown = || "unknown".to_string();
+    let row = |label: &str, value: String, color: Color| {
+        Line::from(vec![
+            Span::styled(format!("{:<11}", label), Style::default().fg(Color::Gray)),
+            Span::styled(value, Style::default().fg(color)),
+        ])
+    };
+    // Nested virtualization is optional; the rest are needed
+    let feature = |label: &str, enabled: Option<bool>, detail: &str, off: Color| {
+        let (text, color) = match enabled {
+            Some(true) => (format!("enabled{}", detail), Color::Green),
+            Some(false) => ("disabled".to_string(), off),
+            None => ("n/a".to_string(), Color::DarkGray),
+        };
+        row(label, text, color)
+    };
+
+    let mut bios = [info.bios_vendor.clone(), info.bios_version.clone(), info.bios_date.clone()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if let Some(previous) = info.previous_bios() {
+        bios.push_str(&format!(" (was {})", previous));
+    }
+    let virtualization = info.virtualization.map(|v| format!(" ({})", v)).unwrap_or_default();
+    let lines = vec![
+        row("System", info.system.clone().unwrap_or_else(unknown), Color::Cyan),
+        row("CPU", info.cpu_model.clone().unwrap_or_else(unknown), Color::White),
+        row("Firmware", if bios.is_empty() { unknown() } else { bios }, Color::White),
+        row("Microcode", info.microcode.clone().unwrap_or_else(unknown), Color::White),
+        feature("Virt ext", Some(info.virtualization.is_some()), &virtualization, Color::Red),
+        feature("KVM", Some(info.kvm), " (/dev/kvm)", Color::Red),
+        feature("IOMMU", Some(info.iommu), "", Color::Yellow),
+        feature("Nested", info.nested, "", Color::Gray),
+    ];
+
+    let block = Block::default().title("Hardware & Firmware").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Every condition of the local node with how long it has held, capacity
+/// against allocatable per resource, and the eviction thresholds
+fn draw_kubelet(f: &mut Frame, status: Option<&KubeletStatus>, area: Rect) {
+    let Some(status) = status else {
+        let block = Block::default().title("Kubelet").borders(Borders::ALL);
+        let text = Paragraph::new("  This host is not a node of the cluster")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(text, area);
+        return;
+    };
+
+    let gray = Style::default().fg(Color::Gray);
+    let now = chrono::Local::now();
+    let mut lines = Vec::new();
+    for condition in &status.conditions {
+        let color = if !condition.is_problem() {
+            Color::Green
+        } else if condition.kind == "Ready" || condition.kind == "NetworkUnavailable" {
+            Color::Red
+        } else {
+            Color::Yellow
+        };
+        let since = condition
+            .last_transition
+            .map(|at| format!(" for {}", format_age(now - at)))
+            .unwrap_or_default();
+        let mut spans = vec![
+            Span::styled(format!("{:<19}", condition.kind), gray),
+            Span::styled(format!("{:<6}", condition.status), Style::default().fg(color)),
+            Span::styled(since, Style::default().fg(Color::DarkGray)),
+        ];
+        if condition.is_problem() {
+            if let Some(ref message) = condition.message {
+                spans.push(Span::styled(format!("  {}", message), Style::default().fg(color)));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(Span::styled(
+        format!("{:<19}{:>14}  {:>14}  {:>8}", "Resource", "Capacity", "Allocatable", "Reserved"),
+        gray.add_modifier(Modifier::BOLD),
+    )));
+    for resource in &status.resources {
+        let reserved = resource.reserved_percent.map(|p| format!("{:.1}%", p)).unwrap_or_else(|| "-".to_string());
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<19}", resource.name), gray),
+            Span::raw(format!("{:>14}  {:>14}  {:>8}", resource.capacity, resource.allocatable, reserved)),
+        ]));
+    }
+
+    let thresholds = |map: &std::collections::BTreeMap<String, String>| {
+        map.iter().map(|(signal, value)| format!("{}<{}", signal, value)).collect::<Vec<_>>().join(", ")
+    };
+    match status.config_error {
+        Some(ref error) => lines.push(Line::from(Span::styled(error.clone(), Style::default().fg(Color::DarkGray)))),
+        None => {
+            let hard = if status.eviction_hard.is_empty() {
+                "kubelet defaults".to_string()
+            } else {
+                thresholds(&status.eviction_hard)
+            };
+            lines.push(Line::from(vec![Span::styled(format!("{:<19}", "Evict (hard)"), gray), Span::raw(hard)]));
+            if !status.eviction_soft.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{:<19}", "Evict (soft)"), gray),
+                    Span::raw(thresholds(&status.eviction_soft)),
+                ]));
+            }
+        }
+    }
+
+    let block = Block::default()
+        .title(format!("Kubelet: {} {}", status.node, status.kubelet_version))
+        .borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), area);
+}
+
+/// Recent core dumps and crash reports, newest first; watched processes
+/// in red
+fn draw_crashes(f: &mut Frame, dumps: &[CoreDump], area: Rect) {
+    let critical = dumps.iter().filter(|dump| dump.critical).count();
+    let title = match critical {
+        0 => format!("Crashes ({})", dumps.len()),
+        _ => format!("Crashes ({}, {} critical)", dumps.len(), critical),
+    };
+    let block = Block::default().title(title).borders(Borders::ALL);
+    if dumps.is_empty() {
+        let text = Paragraph::new("  No core dumps or crash reports")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(text, area);
+        return;
+    }
+
+    let now = chrono::Local::now();
+    let lines: Vec<Line> = dumps
+        .iter()
+        .map(|dump| {
+            let color = if dump.critical { Color::Red } else { Color::White };
+            let pid = dump.pid.map(|pid| format!(" [{}]", pid)).unwrap_or_default();
+            let mut spans = vec![
+                Span::styled(format!("{:>4} ago  ", format_age(now - dump.at)), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("{}{}", dump.name(), pid), Style::default().fg(color)),
+                Span::styled(
+                    format!("  {}", dump.signal_name().unwrap_or_else(|| "-".to_string())),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ];
+            let core = match (dump.core.as_deref(), dump.size_bytes) {
+                (Some("present"), Some(size)) => Some(format!("core {}", format_bytes(size))),
+                (Some(core), _) => Some(format!("core {}", core)),
+                (None, _) => None,
+            };
+            if let Some(core) = core {
+                spans.push(Span::styled(format!("  {}", core), Style::default().fg(Color::Gray)));
+            }
+            if dump.origin != "coredumpctl" {
+                spans.push(Span::styled(format!("  {}", dump.origin), Style::default().fg(Color::DarkGray)));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Recent OOM kills, newest first, with the pod, VM or unit killed
+fn draw_oom_kills(f: &mut Frame, kills: &[OomKill], area: Rect) {
+    let block = Block::default().title(format!("OOM Kills ({})", kills.len())).borders(Borders::ALL);
+    if kills.is_empty() {
+        let text = Paragraph::new("  No OOM kills since startup")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(text, area);
+        return;
+    }
+
+    let now = chrono::Local::now();
+    let lines: Vec<Line> = kills
+        .iter()
+        .map(|kill| {
+            let mut spans = vec![
+                Span::styled(format!("{:>4} ago  ", format_age(now - kill.at)), Style::default().fg(Color::DarkGray)),
+                Span::styled(kill.victim(), Style::default().fg(Color::Red)),
+                Span::styled(format!("  {}", kill.process()), Style::default().fg(Color::White)),
+            ];
+            if let Some(kb) = kill.anon_rss_kb {
+                spans.push(Span::styled(format!("  {}", format_bytes(kb * 1024)), Style::default().fg(Color::Yellow)));
+            }
+            match kill.cgroup_limit {
+                Some(true) => spans.push(Span::styled("  cgroup limit", Style::default().fg(Color::Gray))),
+                Some(false) => spans.push(Span::styled("  host OOM", Style::default().fg(Color::Gray))),
+                None => {}
+            }
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// `[[computed_metrics]]`: each value against its thresholds with the last
+/// 30 minutes as a sparkline
+fn draw_computed(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default().title("Computed Metrics").borders(Borders::ALL);
+    if app.computed_metrics.is_empty() {
+        let text = Paragraph::new("  No [[computed_metrics]] configured")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(text, area);
+        return;
+    }
+
+    let glyphs = Glyphs::for_mode(app.accessible);
+    let width = app.computed_metrics.iter().map(|m| m.config.name.len()).max().unwrap_or(0);
+    let lines: Vec<Line> = app
+        .computed_metrics
+        .iter()
+        .map(|metric| {
+            let color = match metric.severity() {
+                2 => Color::Red,
+                1 => Color::Yellow,
+                _ if metric.value.is_none() => Color::DarkGray,
+                _ => Color::Green,
+            };
+            // Scaled between the window's low and high so small moves show
+            let samples = app.metrics_history.computed(&metric.config.name, Zoom::ThirtyMinutes);
+            let low = samples.iter().map(|s| s.value).fold(f64::INFINITY, f64::min);
+            let high = samples.iter().map(|s| s.value).fold(f64::NEG_INFINITY, f64::max);
+            let scaled: Vec<u64> = samples
+                .iter()
+                .map(|s| if high > low { ((s.value - low) / (high - low) * 100.0) as u64 + 1 } else { 1 })
+                .collect();
+            let thresholds = [("warn", metric.config.warning), ("crit", metric.config.critical)]
+                .iter()
+                .filter_map(|(label, t)| t.map(|t| format!("{} {}{}", label, t, if metric.config.below { glyphs.falling } else { "" })))
+                .collect::<Vec<_>>()
+                .join("  ");
+            Line::from(vec![
+                Span::styled(format!("{:<width$}  ", metric.config.name, width = width), Style::default().fg(Color::Gray)),
+                Span::styled(format!("{:>12}  ", metric.display()), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                Span::styled(glyphs.sparkline(&scaled), Style::default().fg(color)),
+                Span::styled(format!("  {}", thresholds), Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// "45s", "12m", "5h" or "3d"
+fn format_age(age: chrono::Duration) -> String {
+    let secs = age.num_seconds().max(0);
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+/// A rate this many times its baseline is flagged as a spike
+const LOG_SPIKE_FACTOR: f64 = 10.0;
+/// ...as long as it is at least this many lines per minute
+const LOG_SPIKE_MIN_RATE: f64 = 60.0;
+
+/// Lines per minute of each logging service as a sparkline, with the
+/// current line and error rates. Spikes and services gone quiet are
+/// called out, since either is often the first sign of trouble.
+fn draw_log_volume(f: &mut Frame, app: &App, area: Rect) {
+    let rates = app.metrics_history.log_rates();
+    let block = Block::default().title("Log Volume (lines/min)").borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if rates.is_empty() {
+        let text = Paragraph::new(Span::styled(
+            "  Measuring... (rates appear after the second log read)",
+            Style::default().fg(Color::DarkGray),
+        ));
+        f.render_widget(text, inner);
+        return;
+    }
+
+    for (i, (service, rate)) in rates.iter().enumerate().take(inner.height as usize) {
+        let row = Rect { y: inner.y + i as u16, height: 1, ..inner };
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(14), Constraint::Min(8), Constraint::Length(26)])
+            .split(row);
+
+        let (note, color) = log_rate_note(rate);
+        f.render_widget(
+            Paragraph::new(Span::styled(format!("{:<13}", service), Style::default().fg(Color::Cyan))),
+            columns[0],
+        );
+
+        let history: Vec<u64> = rate
+            .lines_per_min()
+            .iter()
+            .rev()
+            .take(columns[1].width as usize)
+            .rev()
+            .map(|v| v.round() as u64)
+            .collect();
+        f.render_widget(
+            Sparkline::default().data(&history).style(Style::default().fg(color)),
+            columns[1],
+        );
+
+        let errors = rate.errors_per_min();
+        let error_color = if errors > 0.0 { Color::Red } else { Color::DarkGray };
+        let stats = Line::from(vec![
+            Span::styled(format!(" {:>7.0}", rate.current()), Style::default().fg(color)),
+            Span::styled(format!("  err {:>5.0}", errors), Style::default().fg(error_color)),
+            Span::styled(format!(" {}", note), Style::default().fg(Color::Yellow)),
+        ]);
+        f.render_widget(Paragraph::new(stats), columns[2]);
+    }
+}
+
+fn log_rate_note(rate: &LogRate) -> (&'static str, Color) {
+    let Some(baseline) = rate.baseline() else {
+        return ("", Color::Green);
+    };
+    let current = rate.current();
+    if current == 0.0 && baseline >= 1.0 {
+        ("silent", Color::Yellow)
+    } else if baseline > 0.0 && current >= (baseline * LOG_SPIKE_FACTOR).max(LOG_SPIKE_MIN_RATE) {
+        ("spike", Color::Yellow)
+    } else {
+        ("", Color::Green)
+    }
+}
@@ -0,0 +1,305 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::log_forward::SinkStatus;
+use crate::permissions::Permissions;
+use crate::telemetry::TelemetrySnapshot;
+use crate::types::{ApiCallRate, CollectorTiming};
+use crate::units::{format_bytes, format_duration};
+
+/// Draw the collector timing overlay in the top-right corner, with the
+/// app's own telemetry, Kubernetes API call rates, log forwarding and
+/// missing RBAC permissions below it
+pub fn draw_debug_overlay(
+    f: &mut Frame,
+    timings: &[CollectorTiming],
+    telemetry: &TelemetrySnapshot,
+    api_rates: &[ApiCallRate],
+    log_sinks: &[SinkStatus],
+    permissions: &Permissions,
+    area: Rect,
+) {
+    let width = area.width.min(72);
+    let height = (timings.len() as u16 + 4).min(area.height);
+    let overlay_area = Rect {
+        x: area.right().saturating_sub(width),
+        y: area.y,
+        width,
+        height,
+    };
+    f.render_widget(Clear, overlay_area);
+
+    let header = Row::new(vec!["Collector", "Last", "Max", "Runs", "Fail", "Status"])
+        .style(Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD));
+
+    let rows: Vec<Row> = timings
+        .iter()
+        .map(|t| {
+            let (status, color) = match t.last_error {
+                Some(ref e) => (e.lines().next().unwrap_or_default().to_string(), Color::Red),
+                None => ("ok".to_string(), Color::Green),
+            };
+            Row::new(vec![
+                Cell::from(t.name),
+                Cell::from(format_duration(chrono::Duration::from_std(t.last).unwrap_or_default())),
+                Cell::from(format_duration(chrono::Duration::from_std(t.max).unwrap_or_default())),
+                Cell::from(t.runs.to_string()),
+                Cell::from(t.failures.to_string()),
+                Cell::from(status).style(Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Min(10),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(" Collector Timings (F12) ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+
+    f.render_widget(table, overlay_area);
+
+    let telemetry_area = Rect {
+        y: overlay_area.bottom(),
+        height: (telemetry.buffers.len() as u16 + 6).min(area.bottom().saturating_sub(overlay_area.bottom())),
+        ..overlay_area
+    };
+    draw_telemetry(f, telemetry, telemetry_area);
+
+    let mut bottom = telemetry_area.bottom();
+    if !api_rates.is_empty() {
+        let api_area = Rect {
+            y: bottom,
+            height: (api_rates.len() as u16 + 3).min(area.bottom().saturating_sub(bottom)),
+            ..overlay_area
+        };
+        draw_api_rates(f, api_rates, api_area);
+        bottom = api_area.bottom();
+    }
+
+    if !log_sinks.is_empty() {
+        let sinks_area = Rect {
+            y: bottom,
+            height: (log_sinks.len() as u16 + 3).min(area.bottom().saturating_sub(bottom)),
+            ..overlay_area
+        };
+        draw_log_sinks(f, log_sinks, sinks_area);
+        bottom = sinks_area.bottom();
+    }
+
+    if permissions.audit.is_some() || permissions.error.is_some() || permissions.is_running() {
+        draw_permissions(f, permissions, Rect { y: bottom, height: area.bottom().saturating_sub(bottom), ..overlay_area });
+    }
+}
+
+/// What the RBAC self-audit found missing, with the rule to grant and the
+/// authorizer's reason
+fn draw_permissions(f: &mut Frame, permissions: &Permissions, area: Rect) {
+    if area.height < 3 {
+        return;
+    }
+
+    let mut lines = Vec::new();
+    if let Some(ref error) = permissions.error {
+        lines.push(Line::from(Span::styled(format!("Audit failed: {}", error), Style::default().fg(Color::Red))));
+    }
+    let title = match permissions.audit {
+        Some(ref audit) => {
+            let denied: Vec<_> = audit.denied().collect();
+            if denied.is_empty() {
+                lines.push(Line::from(Span::styled("Every checked operation is allowed", Style::default().fg(Color::Green))));
+            }
+            for check in &denied {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{:<24}", check.permission.label()), Style::default().fg(Color::Red)),
+                    Span::styled(check.permission.rule(), Style::default().fg(Color::Gray)),
+                ]));
+                if let Some(ref reason) = check.reason {
+                    lines.push(Line::from(Span::styled(format!("  {}", reason), Style::default().fg(Color::DarkGray))));
+                }
+            }
+            format!(
+                " Permissions: {}/{} allowed, {} ",
+                audit.checks.len() - denied.len(),
+                audit.checks.len(),
+                audit.checked_at.format("%H:%M:%S")
+            )
+        }
+        None => " Permissions ".to_string(),
+    };
+    if permissions.is_running() {
+        lines.push(Line::from(Span::styled("Checking...", Style::default().fg(Color::Yellow))));
+    }
+
+    let height = (lines.len() as u16 + 2).min(area.height);
+    f.render_widget(Clear, Rect { height, ..area });
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+    f.render_widget(paragraph, Rect { height, ..area });
+}
+
+/// Frame and update times, memory, buffer sizes and lost log lines; the
+/// figures written to telemetry.jsonl
+fn draw_telemetry(f: &mut Frame, telemetry: &TelemetrySnapshot, area: Rect) {
+    if area.height < 3 {
+        return;
+    }
+    f.render_widget(Clear, area);
+
+    let label = |text: &str| Span::styled(format!("{:<14}", text), Style::default().fg(Color::Gray));
+    let span_line = |name: &str, span: crate::telemetry::Span| {
+        let color = if span.last_ms >= 250 { Color::Yellow } else { Color::Reset };
+        Line::from(vec![
+            label(name),
+            Span::styled(format!("{}ms", span.last_ms), Style::default().fg(color)),
+            Span::raw(format!("  max {}ms", span.max_ms)),
+        ])
+    };
+
+    let mut lines = vec![
+        span_line("Frame", telemetry.draw),
+        span_line("Update tick", telemetry.update),
+        Line::from(vec![
+            label("Memory (RSS)"),
+            Span::raw(telemetry.rss_bytes.map(format_bytes).unwrap_or_else(|| "-".to_string())),
+            Span::raw(format!("  up {}m", telemetry.uptime_secs / 60)),
+        ]),
+    ];
+    for buffer in &telemetry.buffers {
+        let mut usage = format!("{} entries, {}", buffer.entries, format_bytes(buffer.bytes as u64));
+        if let Some(limit) = buffer.limit_bytes {
+            usage.push_str(&format!(" of {}", format_bytes(limit as u64)));
+        }
+        let full = buffer.limit_bytes.is_some_and(|limit| buffer.bytes.saturating_mul(10) >= limit.saturating_mul(9));
+        lines.push(Line::from(vec![
+            label(buffer.name),
+            Span::styled(usage, Style::default().fg(if full { Color::Yellow } else { Color::Reset })),
+        ]));
+    }
+    let dropped = telemetry.log_lines_evicted > 0 || telemetry.journal_reads_truncated > 0;
+    lines.push(Line::from(vec![
+        label("Logs dropped"),
+        Span::styled(
+            format!(
+                "{} evicted, {} truncated reads",
+                telemetry.log_lines_evicted, telemetry.journal_reads_truncated
+            ),
+            Style::default().fg(if dropped { Color::Yellow } else { Color::Reset }),
+        ),
+    ]));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Telemetry ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+/// Calls per resource over the last minute: full lists, watch deltas,
+/// single-object requests and listings served from the cache
+/// Lines each `[[logging.forward]]` sink took and lost
+fn draw_log_sinks(f: &mut Frame, sinks: &[SinkStatus], area: Rect) {
+    if area.height < 3 {
+        return;
+    }
+    f.render_widget(Clear, area);
+
+    let header = Row::new(vec!["Sink", "Sent", "Lost", "Status"])
+        .style(Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD));
+    let rows: Vec<Row> = sinks
+        .iter()
+        .map(|sink| {
+            let (status, color) = match sink.last_error {
+                Some(ref e) => (e.lines().next().unwrap_or_default().to_string(), Color::Red),
+                None if sink.dropped > 0 => ("ok".to_string(), Color::Yellow),
+                None => ("ok".to_string(), Color::Green),
+            };
+            Row::new(vec![
+                Cell::from(sink.name.clone()),
+                Cell::from(sink.sent.to_string()),
+                Cell::from(sink.dropped.to_string()),
+                Cell::from(status).style(Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [Constraint::Min(24), Constraint::Length(8), Constraint::Length(6), Constraint::Min(10)],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(" Log Forwarding ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+
+    f.render_widget(table, area);
+}
+
+fn draw_api_rates(f: &mut Frame, rates: &[ApiCallRate], area: Rect) {
+    if area.height < 3 {
+        return;
+    }
+    f.render_widget(Clear, area);
+
+    let header = Row::new(vec!["Resource", "Lists", "Watch", "Get", "Cached"])
+        .style(Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD));
+    let rows: Vec<Row> = rates
+        .iter()
+        .map(|rate| {
+            Row::new(vec![
+                Cell::from(rate.resource.clone()),
+                Cell::from(rate.lists.to_string()),
+                Cell::from(rate.watches.to_string()),
+                Cell::from(rate.requests.to_string()),
+                Cell::from(rate.cached.to_string()).style(Style::default().fg(Color::Green)),
+            ])
+        })
+        .collect();
+    let total: u32 = rates.iter().map(|rate| rate.api_calls()).sum();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(20),
+            Constraint::Length(6),
+            Constraint::Length(6),
+            Constraint::Length(6),
+            Constraint::Length(7),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(format!(" API Calls / min: {} ", total))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+
+    f.render_widget(table, area);
+}
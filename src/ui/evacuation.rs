@@ -0,0 +1,156 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::evacuate::{Evacuation, PodEviction, VmEvacuation};
+
+/// Confirm draining a node, which moves every VM and pod on it
+pub fn draw_evacuation_prompt(f: &mut Frame, node: &str, vms: Option<usize>, area: Rect) {
+    let popup_area = centered_rect(60, 30, area);
+    f.render_widget(Clear, popup_area);
+
+    let what = match vms {
+        Some(n) => format!("  live-migrate its {} running VM(s) elsewhere and evict its other pods.", n),
+        None => "  live-migrate every VM running on it elsewhere and evict its other pods.".to_string(),
+    };
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Cordon "),
+            Span::styled(node.to_string(), Style::default().fg(Color::Cyan)),
+        ]),
+        Line::from(what),
+        Line::from("  DaemonSet and static pods stay; pods with local storage or no controller are kept and listed."),
+        Line::from("  Uncordon it from the command palette when maintenance is done."),
+        Line::from(""),
+        Line::from(Span::styled("  y: Evacuate   any other key: Cancel", Style::default().fg(Color::DarkGray))),
+    ];
+    let block = Block::default()
+        .title(" Evacuate Node ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), popup_area);
+}
+
+/// Cordon outcome, overall progress, then each VM and each pod with the
+/// ones stuck, failed or blocked first
+pub fn draw_evacuation(f: &mut Frame, run: &Evacuation, area: Rect, glyphs: &Glyphs) {
+    let popup_area = centered_rect(75, 65, area);
+    f.render_widget(Clear, popup_area);
+
+    let finished = run.is_finished();
+    let troubled = run.count(|s| matches!(s, VmEvacuation::Failed(_) | VmEvacuation::Stuck(_)))
+        + run.count_pods(|s| s.is_left_behind() || matches!(s, PodEviction::Blocked(_)));
+    let failed = matches!(run.cordon, Some(Err(_))) || matches!(run.listing, Some(Err(_)));
+    let color = if failed || troubled > 0 {
+        Color::Yellow
+    } else if finished {
+        Color::Green
+    } else {
+        Color::Cyan
+    };
+    let block = Block::default()
+        .title(format!(" Evacuate: {} ({}s) ", run.node, run.elapsed().as_secs()))
+        .title_bottom(if finished { " Enter/Esc: Close " } else { " Esc: Hide (keeps running) " })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+    if inner.height < 3 {
+        return;
+    }
+
+    let cordon = match &run.cordon {
+        None => Line::from(Span::styled("   Cordoning...", Style::default().fg(Color::Yellow))),
+        Some(Ok(())) => Line::from(Span::styled(format!("{:>2} Cordoned", glyphs.check), Style::default().fg(Color::Green))),
+        Some(Err(e)) => Line::from(Span::styled(
+            format!("{:>2} Cordon failed: {}", glyphs.error, e),
+            Style::default().fg(Color::Red),
+        )),
+    };
+    f.render_widget(Paragraph::new(cordon), Rect { height: 1, ..inner });
+
+    let (done, total) = run.progress();
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(Color::Green))
+        .ratio(if total == 0 { 0.0 } else { done as f64 / total as f64 })
+        .label(run.summary());
+    f.render_widget(gauge, Rect { y: inner.y + 1, height: 1, ..inner });
+
+    let row = |symbol: &str, color: Color, name: &str, detail: String| {
+        Line::from(vec![
+            Span::styled(format!("{:>2} ", symbol), Style::default().fg(color)),
+            Span::styled(format!("{:<36}", name), Style::default().fg(Color::Cyan)),
+            Span::styled(detail, Style::default().fg(Color::Gray)),
+        ])
+    };
+    let mut rows: Vec<&(String, VmEvacuation)> = run.vms.iter().collect();
+    rows.sort_by_key(|(_, state)| match state {
+        VmEvacuation::Stuck(_) => 0,
+        VmEvacuation::Failed(_) => 1,
+        VmEvacuation::Migrating => 2,
+        VmEvacuation::Queued => 3,
+        VmEvacuation::Migrated(_) => 4,
+    });
+    let mut lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(vm, state)| {
+            let (symbol, color, detail) = match state {
+                VmEvacuation::Queued => ("-", Color::DarkGray, "queued".to_string()),
+                VmEvacuation::Migrating => ("..", Color::Yellow, "migrating".to_string()),
+                VmEvacuation::Migrated(target) => (glyphs.check, Color::Green, format!("now on {}", target)),
+                VmEvacuation::Failed(reason) => (glyphs.error, Color::Red, reason.clone()),
+                VmEvacuation::Stuck(seen) => (glyphs.warning, Color::Yellow, format!("stuck: {}", seen)),
+            };
+            row(symbol, color, vm, detail)
+        })
+        .collect();
+
+    let mut pods: Vec<&(String, PodEviction)> = run.pods.iter().collect();
+    pods.sort_by_key(|(_, state)| match state {
+        PodEviction::Stuck(_) => 0,
+        PodEviction::Failed(_) => 1,
+        PodEviction::Blocked(_) => 2,
+        PodEviction::Kept(_) => 3,
+        PodEviction::Evicting => 4,
+        PodEviction::Queued => 5,
+        PodEviction::Evicted => 6,
+        PodEviction::Skipped(_) => 7,
+    });
+    if !pods.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("Pods ({} skipped)", run.count_pods(|s| matches!(s, PodEviction::Skipped(_)))),
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    // Skipped pods only fill the space left at the bottom
+    lines.extend(pods.into_iter().map(|(pod, state)| {
+        let (symbol, color, detail) = match state {
+            PodEviction::Queued => ("-", Color::DarkGray, "queued".to_string()),
+            PodEviction::Evicting => ("..", Color::Yellow, "terminating".to_string()),
+            PodEviction::Blocked(reason) => (glyphs.warning, Color::Yellow, format!("blocked: {}", reason)),
+            PodEviction::Evicted => (glyphs.check, Color::Green, "evicted".to_string()),
+            PodEviction::Kept(reason) => (glyphs.warning, Color::Yellow, format!("kept: {}", reason)),
+            PodEviction::Skipped(reason) => ("-", Color::DarkGray, format!("skipped: {}", reason)),
+            PodEviction::Failed(reason) => (glyphs.error, Color::Red, reason.clone()),
+            PodEviction::Stuck(seen) => (glyphs.warning, Color::Yellow, format!("stuck: {}", seen)),
+        };
+        row(symbol, color, pod, detail)
+    }));
+    if let Some(Err(e)) = &run.listing {
+        lines.push(Line::from(Span::styled(
+            format!("Listing VMs and pods failed: {}", e),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    f.render_widget(
+        Paragraph::new(lines),
+        Rect { y: inner.y + 3, height: inner.height - 3, ..inner },
+    );
+}
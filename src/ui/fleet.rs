@@ -0,0 +1,540 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::alerts::Alert;
+use crate::app::{App, FleetSort};
+use crate::types::{NodeReachability, NodeStatus};
+use crate::units::format_duration;
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Cluster totals above a sortable table of every node, the remote
+/// agents, the tenant rollup, recent migrations and the slowest recent VM
+/// boots
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let agents = if app.agents.is_empty() { 0 } else { (app.agents.len() as u16 + 3).min(10) };
+    let migrations = if app.migration_history.is_empty() { 0 } else { 10 };
+    let boots = if app.boot_history.is_empty() { 0 } else { 8 };
+    let tenants = if app.tenant_usage.is_empty() { 0 } else { (app.tenant_usage.len() as u16 + 3).min(12) };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(0),
+            Constraint::Length(agents),
+            Constraint::Length(tenants),
+            Constraint::Length(migrations),
+            Constraint::Length(boots),
+        ])
+        .split(area);
+
+    let rows = app.fleet_rows();
+    draw_totals(f, app, &rows, chunks[0]);
+    draw_nodes(f, app, &rows, chunks[1]);
+    if agents > 0 {
+        draw_agents(f, app, chunks[2]);
+    }
+    if tenants > 0 {
+        draw_tenants(f, app, chunks[3]);
+    }
+    if migrations > 0 {
+        draw_migrations(f, app, chunks[4]);
+    }
+    if boots > 0 {
+        draw_boots(f, app, chunks[5]);
+    }
+}
+
+/// Each `[remote_agents]` host's last snapshot, with why the last poll
+/// failed in place of its age when it did
+fn draw_agents(f: &mut Frame, app: &App, area: Rect) {
+    let header_style = Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD);
+    let now = chrono::Local::now();
+    let rows: Vec<Row> = app
+        .agents
+        .iter()
+        .map(|agent| {
+            let (status, status_color) = match (&agent.error, &agent.snapshot) {
+                (Some(error), _) => (error.clone(), Color::Red),
+                (None, Some(snapshot)) => (format!("{} ago", format_duration(now - snapshot.collected_at)), Color::DarkGray),
+                (None, None) => ("waiting".to_string(), Color::DarkGray),
+            };
+            let mut cells = vec![Cell::from(agent.endpoint.clone()).style(Style::default().fg(Color::Cyan))];
+            match agent.snapshot {
+                Some(ref snapshot) => {
+                    let memory = (snapshot.memory_total_gb > 0.0)
+                        .then(|| snapshot.memory_used_gb / snapshot.memory_total_gb * 100.0);
+                    let paused = snapshot.vms.iter().filter(|vm| vm.stopped).count();
+                    let alert_color = if snapshot.alerts.is_empty() { Color::Reset } else { Color::Yellow };
+                    cells.extend([
+                        Cell::from(snapshot.host.clone()),
+                        Cell::from(percent_text(Some(snapshot.cpu_percent)))
+                            .style(Style::default().fg(usage_color(Some(snapshot.cpu_percent)))),
+                        Cell::from(percent_text(memory)).style(Style::default().fg(usage_color(memory))),
+                        Cell::from(percent_text(Some(snapshot.disk_percent)))
+                            .style(Style::default().fg(usage_color(Some(snapshot.disk_percent)))),
+                        Cell::from(format!("{:.2}", snapshot.load_avg)),
+                        Cell::from(if paused > 0 {
+                            format!("{} ({} paused)", snapshot.vms.len(), paused)
+                        } else {
+                            snapshot.vms.len().to_string()
+                        }),
+                        Cell::from(snapshot.alerts.len().to_string()).style(Style::default().fg(alert_color)),
+                    ]);
+                }
+                None => cells.extend((0..7).map(|_| Cell::from("-"))),
+            }
+            cells.push(Cell::from(
+                agent.round_trip_ms.map(|ms| format!("{:.0} ms", ms)).unwrap_or_else(|| "-".to_string()),
+            ));
+            cells.push(Cell::from(status).style(Style::default().fg(status_color)));
+            Row::new(cells)
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(18),
+            Constraint::Min(12),
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Length(6),
+            Constraint::Length(14),
+            Constraint::Length(6),
+            Constraint::Length(7),
+            Constraint::Min(12),
+        ],
+    )
+    .header(
+        Row::new(vec!["Agent", "Host", "CPU", "Mem", "Disk", "Load", "VMs", "Alerts", "RTT", "Status"])
+            .style(header_style),
+    )
+    .block(Block::default().title("Agents").borders(Borders::ALL));
+    f.render_widget(table, area);
+}
+
+/// VMs still starting, then the last day's boots slowest first, each
+/// against the VM's usual time to Running
+fn draw_boots(f: &mut Frame, app: &App, area: Rect) {
+    let history = &app.boot_history;
+    let header_style = Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD);
+    let now = chrono::Local::now();
+    let rows: Vec<Row> = history
+        .slowest_recent()
+        .into_iter()
+        .take(area.height.saturating_sub(3) as usize)
+        .map(|boot| {
+            let baseline = history.baseline(&boot.vm, boot.requested);
+            let (running, color) = match boot.to_running() {
+                Some(took) => {
+                    let slow = baseline.is_some_and(|(usual, _)| took > usual * 2);
+                    (format_duration(took), if slow { Color::Yellow } else { Color::Reset })
+                }
+                None => (format!("{}…", format_duration(now - boot.requested)), Color::Yellow),
+            };
+            Row::new(vec![
+                Cell::from(boot.vm.clone()).style(Style::default().fg(Color::Cyan)),
+                Cell::from(boot.node.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(running).style(Style::default().fg(color)),
+                Cell::from(boot.to_agent().map(format_duration).unwrap_or_else(|| "-".to_string())),
+                Cell::from(baseline.map(|(usual, _)| format_duration(usual)).unwrap_or_else(|| "-".to_string())),
+                Cell::from(boot.requested.format("%m-%d %H:%M").to_string()).style(Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(24),
+            Constraint::Min(12),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(12),
+        ],
+    )
+    .header(Row::new(vec!["VM", "Node", "Running", "Agent", "Usual", "Started"]).style(header_style))
+    .block(Block::default().title("Slowest Boots (24h)").borders(Borders::ALL));
+    f.render_widget(table, area);
+}
+
+/// VMs, vCPUs, memory and storage per tenant, with pods' requests beside
+/// and growth over the window; largest footprint first
+fn draw_tenants(f: &mut Frame, app: &App, area: Rect) {
+    let header_style = Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD);
+    let gib = |bytes: u64| format!("{:.0} GiB", bytes as f64 / GIB);
+    let limits = app.tenant_limits();
+    let rows: Vec<Row> = app
+        .tenant_usage
+        .iter()
+        .map(|tenant| {
+            let limit = limits.get(&tenant.tenant);
+            let over = |used: f64, limit: Option<f64>| {
+                let color = match limit {
+                    Some(limit) if used >= limit => Color::Red,
+                    Some(limit) if used >= limit * 0.9 => Color::Yellow,
+                    _ => Color::Reset,
+                };
+                Style::default().fg(color)
+            };
+            let (growth, growth_color) = match tenant.growth_percent {
+                Some(growth) if growth >= 1.0 => (format!("+{:.0}%", growth), Color::Yellow),
+                Some(growth) if growth <= -1.0 => (format!("{:.0}%", growth), Color::Green),
+                Some(_) => ("0%".to_string(), Color::DarkGray),
+                None => ("-".to_string(), Color::DarkGray),
+            };
+            Row::new(vec![
+                Cell::from(tenant.tenant.clone()).style(Style::default().fg(Color::Cyan)),
+                Cell::from(format!("{}/{}", tenant.running_vms, tenant.vms)),
+                Cell::from(tenant.vcpus.to_string())
+                    .style(over(tenant.cpu_cores(), limit.and_then(|l| l.vcpus))),
+                Cell::from(gib(tenant.memory_bytes))
+                    .style(over(tenant.total_memory_bytes() as f64 / GIB, limit.and_then(|l| l.memory_gib))),
+                Cell::from(gib(tenant.storage_bytes))
+                    .style(over(tenant.storage_bytes as f64 / GIB, limit.and_then(|l| l.storage_gib))),
+                Cell::from(tenant.pods.to_string()),
+                Cell::from(format!("{:.1} / {}", tenant.pod_cpu_cores, gib(tenant.pod_memory_bytes))),
+                Cell::from(growth).style(Style::default().fg(growth_color)),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(16),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(16),
+            Constraint::Length(7),
+        ],
+    )
+    .header(
+        Row::new(vec!["Tenant", "VMs", "vCPU", "Memory", "Storage", "Pods", "Pod CPU / Mem", "Growth"])
+            .style(header_style),
+    )
+    .block(
+        Block::default()
+            .title(format!("Tenants by {}", app.tenant_label.as_deref().unwrap_or_default()))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, area);
+}
+
+/// Recent live migrations beside the success rate of each node pair
+fn draw_migrations(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
+    let history = &app.migration_history;
+    let node = |n: &Option<String>| n.clone().unwrap_or_else(|| "?".to_string());
+    let header_style = Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD);
+
+    let recent: Vec<Row> = history
+        .recent()
+        .take(chunks[0].height.saturating_sub(3) as usize)
+        .map(|m| {
+            let color = match m.phase.as_str() {
+                "Succeeded" => Color::Green,
+                "Failed" => Color::Red,
+                _ => Color::Yellow,
+            };
+            let duration = m.duration().map(format_duration).unwrap_or_else(|| "-".to_string());
+            let transferred = m
+                .transferred_bytes
+                .map(|b| format!("{:.1} GiB", b as f64 / GIB))
+                .unwrap_or_else(|| "-".to_string());
+            let when = m.at().map(|t| t.format("%m-%d %H:%M").to_string()).unwrap_or_default();
+            Row::new(vec![
+                Cell::from(m.vmi.clone()).style(Style::default().fg(Color::Cyan)),
+                Cell::from(format!("{} > {}", node(&m.source_node), node(&m.target_node))),
+                Cell::from(m.phase.clone()).style(Style::default().fg(color)),
+                Cell::from(duration),
+                Cell::from(transferred),
+                Cell::from(when).style(Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        recent,
+        [
+            Constraint::Min(20),
+            Constraint::Min(18),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(12),
+        ],
+    )
+    .header(Row::new(vec!["VMI", "Source > Target", "Phase", "Took", "Sent", "When"]).style(header_style))
+    .block(Block::default().title("Migrations").borders(Borders::ALL));
+    f.render_widget(table, chunks[0]);
+
+    let pairs: Vec<Row> = history
+        .pair_stats()
+        .into_iter()
+        .map(|pair| {
+            let rate = pair.success_rate() * 100.0;
+            let color = if rate >= 90.0 {
+                Color::Green
+            } else if rate >= 50.0 {
+                Color::Yellow
+            } else {
+                Color::Red
+            };
+            Row::new(vec![
+                Cell::from(format!("{} > {}", pair.source, pair.target)),
+                Cell::from(format!("{}/{}", pair.succeeded, pair.succeeded + pair.failed)),
+                Cell::from(format!("{:.0}%", rate)).style(Style::default().fg(color)),
+                Cell::from(pair.mean_duration.map(format_duration).unwrap_or_else(|| "-".to_string())),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        pairs,
+        [Constraint::Min(18), Constraint::Length(7), Constraint::Length(5), Constraint::Length(8)],
+    )
+    .header(Row::new(vec!["Node pair", "OK", "Rate", "Mean"]).style(header_style))
+    .block(Block::default().title("Success Rate by Node Pair").borders(Borders::ALL));
+    f.render_widget(table, chunks[1]);
+}
+
+fn draw_totals(f: &mut Frame, app: &App, rows: &[(&NodeStatus, usize)], area: Rect) {
+    let ready = rows.iter().filter(|(n, _)| n.reachability == NodeReachability::Ready).count();
+    let cores: f64 = rows.iter().map(|(n, _)| n.cpu_allocatable).sum();
+    let memory: f64 = rows.iter().map(|(n, _)| n.memory_allocatable_bytes).sum();
+    let vms: usize = rows.iter().map(|(n, _)| n.vms.len()).sum();
+    let pods: u32 = rows.iter().map(|(n, _)| n.pods).sum();
+    let alerts = app.alert_manager.active_count();
+
+    // Weight by allocatable so a small node at 90% doesn't skew the total
+    let weighted = |value: fn(&NodeStatus) -> (Option<f64>, f64)| {
+        let (used, total) = rows
+            .iter()
+            .filter_map(|(n, _)| {
+                let (percent, capacity) = value(n);
+                percent.map(|p| (p * capacity, capacity))
+            })
+            .fold((0.0, 0.0), |(u, t), (pu, pt)| (u + pu, t + pt));
+        (total > 0.0).then(|| used / total)
+    };
+    let cpu = weighted(|n| (n.cpu_percent, n.cpu_allocatable));
+    let mem = weighted(|n| (n.memory_percent, n.memory_allocatable_bytes));
+
+    let label = |text: &str| Span::styled(format!("  {}", text), Style::default().fg(Color::Gray));
+    let ready_color = if ready == rows.len() { Color::Green } else { Color::Red };
+    let text = vec![
+        Line::from(vec![
+            label("Nodes: "),
+            Span::styled(format!("{}/{} ready", ready, rows.len()), Style::default().fg(ready_color)),
+            label("CPU: "),
+            Span::styled(format!("{} of {:.0} cores", percent_text(cpu), cores), Style::default().fg(Color::Yellow)),
+            label("Memory: "),
+            Span::styled(
+                format!("{} of {:.0} GiB", percent_text(mem), memory / GIB),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]),
+        Line::from(vec![
+            label("VMs: "),
+            Span::styled(vms.to_string(), Style::default().fg(Color::Cyan)),
+            label("Pods: "),
+            Span::styled(pods.to_string(), Style::default().fg(Color::Cyan)),
+            label("Alerts: "),
+            Span::styled(
+                alerts.to_string(),
+                Style::default().fg(if alerts > 0 { Color::Red } else { Color::Green }),
+            ),
+        ]),
+    ];
+
+    let widget = Paragraph::new(text).block(Block::default().title("Cluster Totals").borders(Borders::ALL));
+    f.render_widget(widget, area);
+}
+
+fn draw_nodes(f: &mut Frame, app: &App, rows: &[(&NodeStatus, usize)], area: Rect) {
+    let columns = [
+        (FleetSort::Name, "Node"),
+        (FleetSort::Status, "Status"),
+        (FleetSort::Cpu, "CPU%"),
+        (FleetSort::Memory, "Mem%"),
+        (FleetSort::Vms, "VMs"),
+        (FleetSort::Alerts, "Alerts"),
+    ];
+    let arrow = match (app.accessible, app.fleet_sort_desc) {
+        (false, true) => "▼",
+        (false, false) => "▲",
+        (true, true) => " v",
+        (true, false) => " ^",
+    };
+    let header = Row::new(columns.iter().map(|(sort, title)| {
+        if *sort == app.fleet_sort {
+            Cell::from(format!("{}{}", title, arrow))
+                .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        } else {
+            Cell::from(*title).style(Style::default().fg(Color::Gray))
+        }
+    }));
+
+    let table_rows: Vec<Row> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, (node, alerts))| {
+            let mut name = node.name.clone();
+            if app.local_node.as_deref() == Some(node.name.as_str()) {
+                name.push_str(" (this host)");
+            }
+            let status_color = match node.reachability {
+                NodeReachability::Ready if node.pressures.is_empty() => Color::Green,
+                NodeReachability::Ready => Color::Yellow,
+                _ => Color::Red,
+            };
+            let status = match node.pressures.first() {
+                Some(pressure) if node.reachability == NodeReachability::Ready => pressure.clone(),
+                _ => node.reachability.label().to_string(),
+            };
+
+            let row = Row::new(vec![
+                Cell::from(name).style(Style::default().fg(Color::Cyan)),
+                Cell::from(status).style(Style::default().fg(status_color)),
+                Cell::from(percent_text(node.cpu_percent)).style(Style::default().fg(usage_color(node.cpu_percent))),
+                Cell::from(percent_text(node.memory_percent))
+                    .style(Style::default().fg(usage_color(node.memory_percent))),
+                Cell::from(node.vms.len().to_string()),
+                Cell::from(alerts.to_string())
+                    .style(Style::default().fg(if *alerts > 0 { Color::Red } else { Color::Gray })),
+            ]);
+            if i == app.fleet_selected {
+                row.style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Min(24),
+            Constraint::Length(16),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(7),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(format!(
+                "Fleet [{} nodes] | s: Sort column  o: Reverse  Enter: Drill down  E: Evacuate  {}",
+                rows.len(),
+                match app.compare_mark {
+                    Some(ref mark) => format!("c: Compare with {}", mark),
+                    None => "c: Compare".to_string(),
+                }
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green)),
+    );
+
+    f.render_widget(table, area);
+}
+
+/// Detail popup for a node other than this host
+pub fn draw_node_detail(f: &mut Frame, app: &App, name: &str, area: Rect) {
+    let popup_area = centered_rect(70, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" Node {} ", name))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let Some(node) = app.fleet.iter().find(|n| n.name == name) else {
+        let text = Paragraph::new("Node is no longer reported by the cluster")
+            .style(Style::default().fg(Color::DarkGray));
+        f.render_widget(text, inner);
+        return;
+    };
+
+    let label = |text: &str| Span::styled(format!("{:<11}", text), Style::default().fg(Color::Gray));
+    let heading = |text: String| {
+        Line::from(Span::styled(text, Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)))
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            label("Status:"),
+            Span::styled(node.reachability.label(), Style::default().fg(Color::Yellow)),
+            Span::raw("  "),
+            Span::styled(node.pressures.join(", "), Style::default().fg(Color::Red)),
+        ]),
+        Line::from(vec![
+            label("Roles:"),
+            Span::raw(if node.roles.is_empty() { "worker".to_string() } else { node.roles.join(", ") }),
+            Span::styled(format!("  kubelet {}", node.kubelet_version), Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(vec![
+            label("CPU:"),
+            Span::raw(format!("{} of {:.0} cores", percent_text(node.cpu_percent), node.cpu_allocatable)),
+        ]),
+        Line::from(vec![
+            label("Memory:"),
+            Span::raw(format!(
+                "{} of {:.0} GiB",
+                percent_text(node.memory_percent),
+                node.memory_allocatable_bytes / GIB
+            )),
+        ]),
+        Line::from(vec![label("Pods:"), Span::raw(node.pods.to_string())]),
+        Line::from(""),
+    ];
+
+    let alerts: Vec<&Alert> = app
+        .alert_manager
+        .get_active_alerts()
+        .into_iter()
+        .filter(|a| a.metadata.node_name.as_deref() == Some(name))
+        .collect();
+    lines.push(heading(format!("Alerts ({})", alerts.len())));
+    for alert in alerts {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<9}", alert.level.as_str()), Style::default().fg(Color::Red)),
+            Span::raw(alert.title.clone()),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    lines.push(heading(format!("Running VMs ({})", node.vms.len())));
+    lines.push(Line::from(Span::raw(format!("  {}", node.vms.join("  ")))));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(" Esc: Close ", Style::default().fg(Color::DarkGray))));
+
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+fn percent_text(value: Option<f64>) -> String {
+    value.map(|v| format!("{:.0}%", v)).unwrap_or_else(|| "-".to_string())
+}
+
+fn usage_color(value: Option<f64>) -> Color {
+    match value {
+        Some(v) if v >= 90.0 => Color::Red,
+        Some(v) if v >= 75.0 => Color::Yellow,
+        Some(_) => Color::Green,
+        None => Color::DarkGray,
+    }
+}
@@ -0,0 +1,155 @@
+use ratatui::buffer::Buffer;
+use ratatui::symbols::{border, Marker};
+
+use crate::alerts::{Alert, AlertLevel};
+use crate::theme::AlertStyles;
+
+/// Symbols used across the UI, with an ASCII-only variant for serial
+/// consoles and screen readers
+pub struct Glyphs {
+    pub critical: &'static str,
+    pub error: &'static str,
+    pub warning: &'static str,
+    pub info: &'static str,
+    pub check: &'static str,
+    pub up_down: &'static str,
+    pub times: &'static str,
+    pub selected: &'static str,
+    pub rising: &'static str,
+    pub falling: &'static str,
+    pub steady: &'static str,
+    /// Eighths, lowest first, for one-line sparklines
+    pub bars: [char; 8],
+    /// Points of line charts
+    pub marker: Marker,
+}
+
+pub const UNICODE: Glyphs = Glyphs {
+    critical: "⚠",
+    error: "✖",
+    warning: "⚡",
+    info: "ℹ",
+    check: "✓",
+    up_down: "↑↓",
+    times: "×",
+    selected: "▶",
+    rising: "↑",
+    falling: "↓",
+    steady: "→",
+    bars: ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'],
+    marker: Marker::Braille,
+};
+
+pub const ASCII: Glyphs = Glyphs {
+    critical: "!!",
+    error: "X",
+    warning: "!",
+    info: "i",
+    check: "OK",
+    up_down: "Up/Down",
+    times: "x",
+    selected: ">",
+    rising: "^",
+    falling: "v",
+    steady: "-",
+    bars: ['_', '.', ':', '-', '=', '+', '*', '#'],
+    // One point per cell, turned into '*' by `to_ascii`
+    marker: Marker::Dot,
+};
+
+/// Block borders in ASCII mode
+pub const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+impl Glyphs {
+    pub fn for_mode(ascii: bool) -> &'static Glyphs {
+        if ascii {
+            &ASCII
+        } else {
+            &UNICODE
+        }
+    }
+
+    /// `values` as one character each, scaled to the largest
+    pub fn sparkline(&self, values: &[u64]) -> String {
+        let max = values.iter().copied().max().unwrap_or(0);
+        values
+            .iter()
+            .map(|&v| if max == 0 { self.bars[0] } else { self.bars[(v * 7).div_ceil(max) as usize] })
+            .collect()
+    }
+
+    pub fn level_icon(&self, level: AlertLevel) -> &'static str {
+        match level {
+            AlertLevel::Critical => self.critical,
+            AlertLevel::Error => self.error,
+            AlertLevel::Warning => self.warning,
+            AlertLevel::Info => self.info,
+        }
+    }
+
+    /// A level's icon, as overridden in `[display.alert_styles]`
+    pub fn styled_level_icon<'a>(&self, styles: &'a AlertStyles, level: AlertLevel) -> &'a str {
+        styles.level_icon(level).unwrap_or(self.level_icon(level))
+    }
+
+    /// One alert's icon, its category's override first
+    pub fn alert_icon<'a>(&self, styles: &'a AlertStyles, alert: &Alert) -> &'a str {
+        styles.icon(alert).unwrap_or(self.level_icon(alert.level))
+    }
+}
+
+/// Rewrite a drawn frame for ASCII mode: box-drawing borders and lines
+/// (whatever border type a block was given) become `ASCII_BORDER`, chart
+/// points and block elements become '*' and '#', and dashes and arrows
+/// in labels become their one-character ASCII look-alikes. Letters, such
+/// as German umlauts, are left alone.
+pub fn to_ascii(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        let mut chars = cell.symbol().chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        if let Some(ascii) = ascii_symbol(c) {
+            cell.set_symbol(ascii);
+        }
+    }
+}
+
+fn ascii_symbol(c: char) -> Option<&'static str> {
+    Some(match c {
+        c if c.is_ascii() => return None,
+        // Box drawing: light, heavy, dashed and double lines
+        '─' | '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '═' | '╴' | '╶' | '╸' | '╺' | '╼' | '╾' => {
+            ASCII_BORDER.horizontal_top
+        }
+        '│' | '┃' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '║' | '╵' | '╷' | '╹' | '╻' | '╽' | '╿' => {
+            ASCII_BORDER.vertical_left
+        }
+        '\u{2500}'..='\u{257f}' => ASCII_BORDER.top_left,
+        // Block elements, from full and partial bars and shading
+        '░' | '▒' => ".",
+        '\u{2580}'..='\u{259f}' => "#",
+        // Braille chart points; the empty pattern is a blank
+        '\u{2800}' => " ",
+        '\u{2801}'..='\u{28ff}' | '•' | '●' => "*",
+        '·' | '…' => ".",
+        '—' | '–' | '−' => "-",
+        '←' | '◀' => "<",
+        '→' | '▶' => ">",
+        '↑' => "^",
+        '↓' => "v",
+        '≥' => ">",
+        '≤' => "<",
+        '✗' | '✘' | '✖' | '×' => "x",
+        _ => return None,
+    })
+}
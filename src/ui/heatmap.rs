@@ -0,0 +1,150 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, HeatmapMetric};
+use crate::types::VmUsage;
+
+// Each VM is a two-character cell followed by a one-character gap
+const CELL_WIDTH: u16 = 3;
+
+/// Utilization bands from coolest to hottest, with their legend labels
+const BANDS: [(f64, Color, &str); 5] = [
+    (30.0, Color::Green, "<30"),
+    (50.0, Color::LightGreen, "<50"),
+    (70.0, Color::Yellow, "<70"),
+    (85.0, Color::LightRed, "<85"),
+    (f64::INFINITY, Color::Red, "85+"),
+];
+
+/// Cells per grid row for a heatmap drawn at the given outer width
+pub fn columns_for(width: u16) -> usize {
+    (width.saturating_sub(2) / CELL_WIDTH).max(1) as usize
+}
+
+/// Full-screen heatmap with a cursor and details of the VM under it
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(4)])
+        .split(area);
+
+    draw_grid(f, app, chunks[0], Some(app.heatmap_cursor));
+    draw_details(f, app, chunks[1]);
+}
+
+/// Compact, cursor-less heatmap for the Dashboard
+pub fn draw_card(f: &mut Frame, app: &App, area: Rect) {
+    draw_grid(f, app, area, None);
+}
+
+fn draw_grid(f: &mut Frame, app: &App, area: Rect, cursor: Option<usize>) {
+    let metric = app.heatmap_metric;
+    let block = Block::default()
+        .title(format!("VM Heatmap: {} ({} VMs)", metric.label(), app.vm_usage.len()))
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let columns = columns_for(area.width);
+    let visible_rows = inner.height.max(1) as usize;
+
+    // Keep the cursor's row on screen when there are more VMs than fit
+    let first_row = cursor
+        .map(|c| (c / columns).saturating_sub(visible_rows - 1))
+        .unwrap_or(0);
+
+    let lines: Vec<Line> = app
+        .vm_usage
+        .chunks(columns)
+        .enumerate()
+        .skip(first_row)
+        .take(visible_rows)
+        .map(|(row, vms)| {
+            let spans = vms
+                .iter()
+                .enumerate()
+                .flat_map(|(col, vm)| {
+                    let selected = cursor == Some(row * columns + col);
+                    [cell(vm, metric, selected, app.accessible), Span::raw(" ")]
+                })
+                .collect::<Vec<_>>();
+            Line::from(spans)
+        })
+        .collect();
+
+    if lines.is_empty() {
+        f.render_widget(
+            Paragraph::new(Span::styled("No virtual machines", Style::default().fg(Color::DarkGray))),
+            inner,
+        );
+    } else {
+        f.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+fn cell(vm: &VmUsage, metric: HeatmapMetric, selected: bool, accessible: bool) -> Span<'static> {
+    let value = metric.value(vm);
+    let color = value.map(band_color).unwrap_or(Color::DarkGray);
+
+    // Accessible mode has no colors, so cells show the tens digit instead
+    let text = match (selected, accessible, value) {
+        (true, _, _) => "<>".to_string(),
+        (false, true, Some(v)) => format!("{:>2}", (v / 10.0).min(9.0) as u8),
+        (false, true, None) => "--".to_string(),
+        (false, false, Some(_)) => "██".to_string(),
+        (false, false, None) => "··".to_string(),
+    };
+
+    let style = if selected {
+        Style::default().fg(Color::Black).bg(color).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(color)
+    };
+    Span::styled(text, style)
+}
+
+fn band_color(value: f64) -> Color {
+    BANDS
+        .iter()
+        .find(|(limit, _, _)| value < *limit)
+        .map(|(_, color, _)| *color)
+        .unwrap_or(Color::Red)
+}
+
+fn draw_details(f: &mut Frame, app: &App, area: Rect) {
+    let format_value = |v: Option<f64>| v.map(|v| format!("{:.1}%", v)).unwrap_or_else(|| "-".to_string());
+
+    let details = match app.vm_usage.get(app.heatmap_cursor) {
+        Some(vm) => Line::from(vec![
+            Span::styled(format!("{}/{}", vm.namespace, vm.name), Style::default().fg(Color::Cyan)),
+            Span::raw("  "),
+            Span::styled(vm.status.clone(), Style::default().fg(Color::Yellow)),
+            Span::raw("  CPU "),
+            Span::raw(format_value(vm.cpu_percent)),
+            Span::raw("  Memory "),
+            Span::raw(format_value(vm.memory_percent)),
+        ]),
+        None => Line::from(""),
+    };
+
+    let mut legend = vec![Span::styled("% of requests: ", Style::default().fg(Color::Gray))];
+    for (_, color, label) in BANDS {
+        legend.push(Span::styled("██", Style::default().fg(color)));
+        legend.push(Span::raw(format!(" {}  ", label)));
+    }
+    legend.push(Span::styled("·· no data", Style::default().fg(Color::DarkGray)));
+
+    let help = Line::from(Span::styled(
+        " Arrows: Move  m: CPU/Memory  Enter: VM details ",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let paragraph = Paragraph::new(vec![details, Line::from(legend), help])
+        .block(Block::default().borders(Borders::TOP));
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,568 @@
+use chrono::{Duration, Local};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Cell, Chart, Clear, Dataset, GraphType, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::units::format_bytes;
+use super::glyphs::Glyphs;
+use crate::app::{App, HostMapView};
+use crate::metrics_history::Zoom;
+use crate::types::{DataVolume, DiskImage, GuestClock, QemuProcess, QemuSignal};
+
+/// qemu processes on this host, with the selected one's host-side paths
+/// below, the disk image inventory under any CDI imports in progress, or
+/// the daemons watched for memory leaks over the selected one's RSS chart
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    if app.host_map_view == HostMapView::Memory {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(area);
+        draw_memory(f, app, chunks[0]);
+        draw_memory_chart(f, app, chunks[1]);
+        return;
+    }
+    if app.host_map_view == HostMapView::Images {
+        let provisioning = if app.data_volumes.is_empty() { 0 } else { app.data_volumes.len().min(8) as u16 + 3 };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(provisioning), Constraint::Min(0)])
+            .split(area);
+        if provisioning > 0 {
+            draw_data_volumes(f, &app.data_volumes, chunks[0]);
+        }
+        draw_images(f, app, chunks[1]);
+        if let Some(ref image) = app.disk_delete {
+            draw_delete_confirm(f, image, area);
+        }
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    draw_processes(f, app, chunks[0]);
+    let process = app.qemu_processes.get(app.host_map_selected);
+    let clock = process.and_then(|p| p.vm.as_ref()).and_then(|vm| app.guest_clocks.get(vm));
+    draw_detail(f, process, clock, chunks[1]);
+    if let Some((ref process, signal)) = app.qemu_signal {
+        draw_signal_confirm(f, process, signal, area);
+    }
+}
+
+fn draw_processes(f: &mut Frame, app: &App, area: Rect) {
+    let header = Row::new(vec!["VM", "virt-launcher pod", "PID", "RSS", "Threads", "vCPU wait", "Throttled", "Clock", "Taps"])
+        .style(Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD));
+
+    let rows: Vec<Row> = app
+        .qemu_processes
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let row = Row::new(vec![
+                if p.stopped {
+                    Cell::from(format!("{} (paused)", p.vm.as_deref().unwrap_or(&p.domain)))
+                        .style(Style::default().fg(Color::Magenta))
+                } else {
+                    Cell::from(p.vm.clone().unwrap_or_else(|| p.domain.clone())).style(Style::default().fg(Color::Cyan))
+                },
+                match p.pod {
+                    Some(ref pod) => Cell::from(pod.clone()),
+                    None => Cell::from("-").style(Style::default().fg(Color::DarkGray)),
+                },
+                Cell::from(p.pid.to_string()).style(Style::default().fg(Color::Yellow)),
+                Cell::from(format_bytes(p.rss_bytes)),
+                Cell::from(p.threads.to_string()),
+                sched_cell(p.sched.map(|s| s.wait_percent), 10.0),
+                sched_cell(p.sched.map(|s| s.throttled_percent), 25.0),
+                clock_cell(p.vm.as_ref().and_then(|vm| app.guest_clocks.get(vm)), app.guest_clock_thresholds),
+                Cell::from(p.taps.join(" ")),
+            ]);
+            if i == app.host_map_selected {
+                row.style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let mut title = format!(
+        "Host Map [{} qemu processes{}] | Enter: VM details  Tab: Disk images",
+        app.qemu_processes.len(),
+        worst_wait(&app.qemu_processes)
+    );
+    if app.local_mode {
+        title = format!("LOCAL MODE: {}", title);
+        if app.emergency_actions && !app.read_only {
+            title.push_str("  p: Pause/resume  K: Kill");
+        }
+        title.push_str("  L: Leave");
+    }
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(24),
+            Constraint::Min(30),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(9),
+            Constraint::Length(12),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if app.local_mode { Color::Red } else { Color::Green })),
+    );
+
+    f.render_widget(table, area);
+}
+
+/// The VM waiting longest for physical CPUs, for the title
+fn worst_wait(processes: &[QemuProcess]) -> String {
+    processes
+        .iter()
+        .filter_map(|p| Some((p, p.sched?.wait_percent)))
+        .filter(|(_, wait)| *wait >= 0.1)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(p, wait)| format!(", worst vCPU wait {} {:.1}%", p.vm.as_deref().unwrap_or(&p.domain), wait))
+        .unwrap_or_default()
+}
+
+/// A scheduling percentage, yellow from `warn` and red from twice that;
+/// "-" until the process has been scanned twice
+fn sched_cell(percent: Option<f64>, warn: f64) -> Cell<'static> {
+    let Some(percent) = percent else {
+        return Cell::from("-").style(Style::default().fg(Color::DarkGray));
+    };
+    let color = if percent >= warn * 2.0 {
+        Color::Red
+    } else if percent >= warn {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    Cell::from(format!("{:.1}%", percent)).style(Style::default().fg(color))
+}
+
+/// Guest clock offset from the host, colored against the drift alert
+/// thresholds; "?" when the guest agent didn't answer
+fn clock_cell(clock: Option<&GuestClock>, (warning, critical): (f64, f64)) -> Cell<'static> {
+    let Some(clock) = clock else {
+        return Cell::from("-").style(Style::default().fg(Color::DarkGray));
+    };
+    let Some(drift) = clock.drift_secs else {
+        return Cell::from("?").style(Style::default().fg(Color::DarkGray));
+    };
+    let skew = (drift.abs() - clock.uncertainty_secs).max(0.0);
+    let color = if skew >= critical {
+        Color::Red
+    } else if skew >= warning {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    Cell::from(format!("{:+.1}s", drift)).style(Style::default().fg(color))
+}
+
+fn draw_detail(f: &mut Frame, process: Option<&QemuProcess>, clock: Option<&GuestClock>, area: Rect) {
+    let block = Block::default().title("Host Side").borders(Borders::ALL);
+    let Some(p) = process else {
+        let text = Paragraph::new("  No qemu processes on this host")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(text, area);
+        return;
+    };
+
+    let label = |text: &str| Span::styled(format!("  {:<9}", text), Style::default().fg(Color::Gray));
+    let mut lines = vec![
+        Line::from(vec![label("Domain:"), Span::raw(p.domain.clone())]),
+        Line::from(vec![label("PID:"), Span::styled(p.pid.to_string(), Style::default().fg(Color::Yellow))]),
+        Line::from(vec![label("Cgroup:"), Span::raw(p.cgroup.clone())]),
+        Line::from(vec![label("Pod UID:"), Span::raw(p.pod_uid.clone().unwrap_or_else(|| "-".to_string()))]),
+        Line::from(vec![
+            label("Sched:"),
+            Span::raw(match p.sched {
+                Some(s) => format!(
+                    "{} vCPUs waiting {:.1}% for a pCPU, cgroup throttled in {:.1}% of periods",
+                    s.vcpus, s.wait_percent, s.throttled_percent
+                ),
+                None => "- (measured from the second scan)".to_string(),
+            }),
+        ]),
+        Line::from(vec![
+            label("Clock:"),
+            Span::raw(match clock {
+                Some(GuestClock { drift_secs: Some(drift), uncertainty_secs, checked, .. }) => format!(
+                    "guest {:+.3}s from host (±{:.3}s), checked {}",
+                    drift,
+                    uncertainty_secs,
+                    checked.format("%H:%M:%S")
+                ),
+                Some(GuestClock { error: Some(error), checked, .. }) => {
+                    format!("- ({}, {})", error, checked.format("%H:%M:%S"))
+                }
+                _ => "- (not checked yet)".to_string(),
+            }),
+        ]),
+        Line::from(vec![
+            label("Taps:"),
+            Span::raw(if p.taps.is_empty() { "- (needs root to read fdinfo)".to_string() } else { p.taps.join(", ") }),
+            Span::styled("  (in the pod network namespace)", Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(vec![label("Disks:")]),
+    ];
+    for disk in &p.disks {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(p.host_path(disk), Style::default().fg(Color::Cyan)),
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), area);
+}
+
+fn draw_memory(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title(format!(" Daemon Memory — growth over the last {}h ", app.leak_window_hours))
+        .borders(Borders::ALL);
+    if app.process_memory.is_empty() {
+        let text = Paragraph::new("  None of the processes in [memory_leaks] are running, or it is disabled")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(text, area);
+        return;
+    }
+
+    let header = Row::new(vec!["Process", "PIDs", "RSS", "Limit", "Growth/h", "Status"])
+        .style(Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD));
+    let rows: Vec<Row> = app
+        .process_memory
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let limit = app.leak_limits_mb.get(&p.name);
+            let growth = app.metrics_history.memory_growth(&p.name, app.leak_window_hours);
+            let (status, color) = match growth {
+                _ if limit.is_some_and(|limit| p.rss_mb() >= *limit as f64) => ("over limit", Color::Red),
+                Some(g) if g.steady && g.mb_per_hour >= app.leak_growth_mb_per_hour => ("leaking?", Color::Yellow),
+                Some(g) if g.steady && g.mb_per_hour > 0.0 => ("growing", Color::White),
+                Some(_) => ("ok", Color::Green),
+                None => ("learning", Color::DarkGray),
+            };
+            let pids = match p.pids.as_slice() {
+                [pid] => pid.to_string(),
+                pids => format!("{} ({})", pids[0], pids.len()),
+            };
+            let row = Row::new(vec![
+                Cell::from(p.name.clone()).style(Style::default().fg(Color::Cyan)),
+                Cell::from(pids).style(Style::default().fg(Color::Yellow)),
+                Cell::from(format_bytes(p.rss_bytes)),
+                Cell::from(limit.map(|limit| format!("{} MB", limit)).unwrap_or_else(|| "-".to_string())),
+                Cell::from(growth.map(|g| format!("{:+.1} MB", g.mb_per_hour)).unwrap_or_else(|| "-".to_string())),
+                Cell::from(status).style(Style::default().fg(color)),
+            ]);
+            if i == app.leak_selected {
+                row.style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(16),
+            Constraint::Length(14),
+            Constraint::Length(10),
+            Constraint::Length(11),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ],
+    )
+    .header(header)
+    .block(block);
+    f.render_widget(table, area);
+}
+
+/// The selected daemon's RSS over the last day, with its limit if it has one
+fn draw_memory_chart(f: &mut Frame, app: &App, area: Rect) {
+    let Some(process) = app.process_memory.get(app.leak_selected) else {
+        f.render_widget(Block::default().title(" RSS ").borders(Borders::ALL), area);
+        return;
+    };
+    let zoom = Zoom::Day;
+    let span_secs = zoom.resolution_secs() as f64 * 60.0;
+    let start = Local::now() - Duration::seconds(span_secs as i64);
+    let data: Vec<(f64, f64)> = app
+        .metrics_history
+        .process_rss(&process.name, zoom)
+        .iter()
+        .filter(|s| s.at >= start)
+        .map(|s| ((s.at - start).num_seconds() as f64, s.value))
+        .collect();
+    let limit = app.leak_limits_mb.get(&process.name).map(|limit| *limit as f64);
+    let limit_line: Vec<(f64, f64)> = limit.map(|limit| vec![(0.0, limit), (span_secs, limit)]).unwrap_or_default();
+
+    let max_value = data.iter().map(|&(_, v)| v).chain(limit).fold(0.0, f64::max);
+    let min_value = data.iter().map(|&(_, v)| v).fold(max_value, f64::min);
+    // Leaks are slow; a zero-based axis would flatten them out
+    let pad = ((max_value - min_value) * 0.1).max(1.0);
+    let (y_min, y_max) = ((min_value - pad).max(0.0), max_value + pad);
+
+    let point = Glyphs::for_mode(app.accessible).marker;
+    let mut datasets = vec![Dataset::default()
+        .name("RSS")
+        .marker(point)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::Cyan))
+        .data(&data)];
+    if !limit_line.is_empty() {
+        datasets.push(
+            Dataset::default()
+                .name("limit")
+                .marker(point)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Red))
+                .data(&limit_line),
+        );
+    }
+    let time_label = |secs: f64| Span::raw((start + Duration::seconds(secs as i64)).format("%H:%M").to_string());
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(format!(" {} RSS — last {} ({}s buckets) ", process.name, zoom.label(), zoom.resolution_secs()))
+                .borders(Borders::ALL),
+        )
+        .x_axis(
+            Axis::default()
+                .bounds([0.0, span_secs])
+                .labels(vec![time_label(0.0), time_label(span_secs / 2.0), time_label(span_secs)]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([y_min, y_max])
+                .labels(vec![
+                    Span::raw(format!("{:.0} MB", y_min)),
+                    Span::raw(format!("{:.0} MB", (y_min + y_max) / 2.0)),
+                    Span::raw(format!("{:.0} MB", y_max)),
+                ]),
+        );
+    f.render_widget(chart, area);
+}
+
+fn draw_images(f: &mut Frame, app: &App, area: Rect) {
+    let header = Row::new(vec!["Kind", "Location", "Size", "Modified", "Used by"])
+        .style(Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD));
+
+    let rows: Vec<Row> = app
+        .disk_images
+        .iter()
+        .enumerate()
+        .map(|(i, image)| {
+            let row = Row::new(vec![
+                Cell::from(image.kind.label()),
+                Cell::from(image.location.clone()).style(Style::default().fg(Color::Cyan)),
+                Cell::from(format_bytes(image.size_bytes)),
+                Cell::from(image.modified.map(|m| m.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "-".to_string())),
+                match image.used_by {
+                    Some(ref owner) => Cell::from(owner.clone()),
+                    None => Cell::from("ORPHAN").style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                },
+            ]);
+            if i == app.disk_selected {
+                row.style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let orphans: Vec<&DiskImage> = app.disk_images.iter().filter(|i| i.is_orphan()).collect();
+    let reclaimable: u64 = orphans.iter().map(|i| i.size_bytes).sum();
+    let mut title = format!(
+        "Disk Images [{} images, {} orphaned, {} reclaimable] | Tab: Processes",
+        app.disk_images.len(),
+        orphans.len(),
+        format_bytes(reclaimable)
+    );
+    if !app.read_only {
+        title.push_str("  D: Delete orphan");
+    }
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Min(40),
+            Constraint::Length(10),
+            Constraint::Length(11),
+            Constraint::Min(24),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if orphans.is_empty() { Color::Green } else { Color::Yellow })),
+    );
+
+    f.render_widget(table, area);
+}
+
+/// Width of the text progress bar in the DataVolume list
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// CDI DataVolumes with their phase and import/clone progress, unfinished
+/// ones first
+fn draw_data_volumes(f: &mut Frame, volumes: &[DataVolume], area: Rect) {
+    let header = Row::new(vec!["DataVolume", "VM", "Source", "Phase", "Progress", "Restarts", "Age", "Message"])
+        .style(Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD));
+
+    let rows: Vec<Row> = volumes
+        .iter()
+        .map(|dv| {
+            let color = match dv.phase.as_str() {
+                "Succeeded" => Color::Green,
+                "Failed" => Color::Red,
+                _ if dv.restarts > 0 => Color::Yellow,
+                _ => Color::Cyan,
+            };
+            let progress = match dv.progress {
+                Some(percent) => {
+                    let filled = (percent.clamp(0.0, 100.0) / 100.0 * PROGRESS_BAR_WIDTH as f64).round() as usize;
+                    format!(
+                        "{}{} {:>5.1}%",
+                        "█".repeat(filled),
+                        "░".repeat(PROGRESS_BAR_WIDTH - filled),
+                        percent
+                    )
+                }
+                None => "-".to_string(),
+            };
+            Row::new(vec![
+                Cell::from(dv.key()).style(Style::default().fg(Color::Cyan)),
+                Cell::from(dv.owner.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(dv.source.clone()),
+                Cell::from(dv.phase.clone()).style(Style::default().fg(color)),
+                Cell::from(progress).style(Style::default().fg(color)),
+                Cell::from(dv.restarts.to_string())
+                    .style(Style::default().fg(if dv.restarts > 0 { Color::Yellow } else { Color::Gray })),
+                Cell::from(dv.created.map(|at| format_age(chrono::Local::now() - at)).unwrap_or_else(|| "-".to_string())),
+                Cell::from(dv.message.clone().unwrap_or_default()).style(Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+
+    let unfinished = volumes.iter().filter(|dv| !dv.is_finished()).count();
+    let failing = volumes.iter().any(|dv| dv.phase == "Failed" || dv.restarts > 0);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(36),
+            Constraint::Length(20),
+            Constraint::Length(9),
+            Constraint::Length(18),
+            Constraint::Length(PROGRESS_BAR_WIDTH as u16 + 7),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Min(20),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(format!("Provisioning [{} DataVolumes, {} in progress]", volumes.len(), unfinished))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if failing { Color::Yellow } else { Color::Green })),
+    );
+
+    f.render_widget(table, area);
+}
+
+/// "45s", "12m", "5h" or "3d"
+fn format_age(age: chrono::Duration) -> String {
+    let secs = age.num_seconds().max(0);
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+fn draw_delete_confirm(f: &mut Frame, image: &DiskImage, area: Rect) {
+    let popup_area = centered_rect(60, 30, area);
+    f.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Delete "),
+            Span::styled(image.kind.label(), Style::default().fg(Color::Gray)),
+            Span::raw(" "),
+            Span::styled(image.location.clone(), Style::default().fg(Color::Cyan)),
+        ]),
+        Line::from(format!("  {} will be freed. This cannot be undone.", format_bytes(image.size_bytes))),
+        Line::from(""),
+        Line::from(Span::styled("  y: Delete   any other key: Cancel", Style::default().fg(Color::DarkGray))),
+    ];
+    let block = Block::default()
+        .title(" Delete Orphaned Disk ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), popup_area);
+}
+
+/// Local mode's emergency action prompt: what the signal does to the
+/// guest, and that nothing in the cluster will know about it
+fn draw_signal_confirm(f: &mut Frame, process: &QemuProcess, signal: QemuSignal, area: Rect) {
+    let popup_area = centered_rect(64, 40, area);
+    f.render_widget(Clear, popup_area);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(format!("  {} ", signal.label())),
+            Span::styled(process.vm.clone().unwrap_or_else(|| process.domain.clone()), Style::default().fg(Color::Cyan)),
+            Span::raw(" (PID "),
+            Span::styled(process.pid.to_string(), Style::default().fg(Color::Yellow)),
+            Span::raw(")"),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(format!("  {}", signal.warning()), Style::default().fg(Color::Yellow))),
+        Line::from("  KubeVirt is not told: once the API server is back it may restart or"),
+        Line::from("  report the VM as failed. The action is recorded in the audit log."),
+        Line::from(""),
+    ];
+    lines.push(Line::from(Span::styled(
+        if signal == QemuSignal::Terminate {
+            "  y: Terminate (SIGTERM)   K: Kill (SIGKILL)   any other key: Cancel"
+        } else {
+            "  y: Confirm   any other key: Cancel"
+        },
+        Style::default().fg(Color::DarkGray),
+    )));
+    let block = Block::default()
+        .title(" Emergency Action ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), popup_area);
+}
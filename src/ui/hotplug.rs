@@ -0,0 +1,131 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::app::{HotplugEditor, HotplugField};
+
+/// Draw the live resize / volume hot-plug popup for a running VM
+pub fn draw_hotplug(f: &mut Frame, editor: &HotplugEditor, area: Rect, glyphs: &Glyphs) {
+    let popup_area = centered_rect(70, 50, area);
+    f.render_widget(Clear, popup_area);
+
+    let title = match editor.resource.namespace {
+        Some(ref ns) => format!(" Hot-plug {}/{} ", ns, editor.resource.name),
+        None => format!(" Hot-plug {} ", editor.resource.name),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(HotplugField::ALL.len() as u16 + 1), // Fields
+            Constraint::Min(0),                                    // Cluster support
+            Constraint::Length(3),                                 // Status + help
+        ])
+        .split(inner);
+
+    let Some(ref hotplug) = editor.hotplug else {
+        let status = editor.status.clone().unwrap_or_default();
+        f.render_widget(
+            Paragraph::new(status).style(Style::default().fg(Color::Red)).wrap(Wrap { trim: true }),
+            inner,
+        );
+        return;
+    };
+
+    let gib = |mib: u64| format!("{:.1} GiB", mib as f64 / 1024.0);
+    let fields: Vec<Line> = HotplugField::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let (label, value, limit, changed) = match field {
+                HotplugField::Sockets => (
+                    "CPU sockets",
+                    editor.sockets.to_string(),
+                    format!("(max {})", hotplug.max_sockets),
+                    editor.sockets != hotplug.sockets,
+                ),
+                HotplugField::Memory => (
+                    "Guest memory",
+                    gib(editor.memory_mib),
+                    format!("(max {})", gib(hotplug.max_memory_mib)),
+                    editor.memory_mib != hotplug.memory_mib,
+                ),
+                HotplugField::Volume => {
+                    let typing = i == editor.selected;
+                    let value = if typing {
+                        format!("{}_", editor.claim)
+                    } else if editor.claim.is_empty() {
+                        "(none)".to_string()
+                    } else {
+                        editor.claim.clone()
+                    };
+                    ("Attach claim", value, "(SCSI disk)".to_string(), !editor.claim.is_empty())
+                }
+            };
+
+            let marker = if i == editor.selected { glyphs.selected } else { " " };
+            let row_style = if i == editor.selected {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(format!("{} {:<16}", marker, label), row_style.fg(Color::Gray)),
+                Span::styled(value, row_style.fg(if changed { Color::Yellow } else { Color::White })),
+                Span::styled(format!("  {}", limit), Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(fields), chunks[0]);
+
+    let support = |problem: Option<String>| match problem {
+        None => Span::styled("available", Style::default().fg(Color::Green)),
+        Some(problem) => Span::styled(problem, Style::default().fg(Color::Red)),
+    };
+    let mut lines = vec![
+        Line::from(vec![Span::styled("CPU/memory: ", Style::default().fg(Color::Gray)), support(hotplug.resize_problem())]),
+        Line::from(vec![Span::styled("Volumes:    ", Style::default().fg(Color::Gray)), support(hotplug.volume_problem())]),
+    ];
+    if !hotplug.hotplugged.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("Attached: {}", hotplug.hotplugged.join(", ")),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    let support = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().title(" Cluster support ").borders(Borders::TOP));
+    f.render_widget(support, chunks[1]);
+
+    let status = match (editor.status.as_deref(), editor.problem()) {
+        (Some(status), _) => Span::styled(status.to_string(), Style::default().fg(Color::Yellow)),
+        (None, Some(problem)) => Span::styled(problem, Style::default().fg(Color::Red)),
+        (None, None) if editor.resized() => Span::styled(
+            "Applied by live migrating the VM to a larger domain",
+            Style::default().fg(Color::Yellow),
+        ),
+        (None, None) => Span::raw(""),
+    };
+    let help = Paragraph::new(vec![
+        Line::from(status),
+        Line::from(Span::styled(
+            format!(" {}: Select  ←/→: Change  Enter: Apply  Esc: Close ", glyphs.up_down),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+    f.render_widget(help, chunks[2]);
+}
@@ -0,0 +1,157 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::units::format_bytes;
+use super::glyphs::Glyphs;
+use crate::app::App;
+use crate::image_catalog::{CatalogEntry, ImageCatalogPanel};
+
+/// Width of the download progress bar
+const BAR_WIDTH: usize = 30;
+
+/// Catalog images with whether their URL answers, what is downloaded here
+/// and imported into the cluster, and the download running
+pub fn draw_image_catalog(f: &mut Frame, app: &App, panel: &ImageCatalogPanel, area: Rect, glyphs: &Glyphs) {
+    let popup_area = centered_rect(85, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let hints = if app.read_only {
+        " j/k: Select  Esc: Close "
+    } else if panel.is_running() {
+        " v: Import as DataVolume  x: Stop download  Esc: Hide (keeps running) "
+    } else {
+        " d: Download  v: Import as DataVolume  Esc: Close "
+    };
+    let block = Block::default()
+        .title(" Cloud Image Catalog ")
+        .title_bottom(hints)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if panel.confirm.is_some() { Color::Yellow } else { Color::Cyan }));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(if panel.download.is_some() {
+            vec![Constraint::Min(4), Constraint::Length(4)]
+        } else {
+            vec![Constraint::Min(4)]
+        })
+        .split(inner);
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "  Downloads: {}    DataVolumes: {}",
+            panel.dir.as_ref().map_or("none configured".to_string(), |dir| dir.display().to_string()),
+            panel.namespace
+        ),
+        Style::default().fg(Color::Gray),
+    ))];
+    match panel.confirm {
+        Some(op) => lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {}? ", panel.describe(op)),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("y: Confirm   any other key: Cancel", Style::default().fg(Color::DarkGray)),
+        ])),
+        None => lines.push(Line::from("")),
+    }
+    if panel.entries.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No images configured: add [[image_catalog.images]] to config.toml",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    // Two lines per image; keep the selection in view below the header
+    let height = (chunks[0].height as usize).saturating_sub(lines.len()) / 2;
+    let skip = (panel.selected + 1).saturating_sub(height.max(1));
+    for (i, entry) in panel.entries.iter().enumerate().skip(skip) {
+        let selected = i == panel.selected;
+        let name_style = if selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+        };
+        let mut first = vec![
+            Span::raw("  "),
+            Span::styled(format!("{:<20}", entry.image.name), name_style),
+            Span::raw("  "),
+        ];
+        first.push(match entry.availability {
+            Ok(size) => Span::styled(
+                format!("{} available{}", glyphs.check, size.map_or(String::new(), |bytes| format!(", {}", format_bytes(bytes)))),
+                Style::default().fg(Color::Green),
+            ),
+            Err(ref reason) => Span::styled(format!("{} {}", glyphs.error, reason), Style::default().fg(Color::Red)),
+        });
+        lines.push(Line::from(first));
+
+        let mut second = vec![Span::styled(format!("    {}", entry.file_name()), Style::default().fg(Color::DarkGray))];
+        second.push(Span::raw("  "));
+        second.push(host_status(entry));
+        second.push(Span::raw("  "));
+        second.push(volume_status(app, panel, entry));
+        lines.push(Line::from(second));
+    }
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let (Some(download), Some(&area)) = (panel.download.as_ref(), chunks.get(1)) else {
+        return;
+    };
+    let status = match &download.outcome {
+        None if download.verifying => Span::styled("Verifying checksum...", Style::default().fg(Color::Yellow)),
+        None => Span::styled("Downloading...", Style::default().fg(Color::Yellow)),
+        Some(Ok(summary)) => Span::styled(format!("{} {}", glyphs.check, summary), Style::default().fg(Color::Green)),
+        Some(Err(reason)) => Span::styled(format!("{} {}", glyphs.error, reason), Style::default().fg(Color::Red)),
+    };
+    let progress = match download.percent() {
+        Some(percent) => {
+            let filled = (percent / 100.0 * BAR_WIDTH as f64).round() as usize;
+            format!(
+                "  [{}{}] {:.0}%  {} of {}",
+                "#".repeat(filled),
+                " ".repeat(BAR_WIDTH - filled),
+                percent,
+                format_bytes(download.bytes),
+                format_bytes(download.total.unwrap_or_default())
+            )
+        }
+        None => format!("  {}", format_bytes(download.bytes)),
+    };
+    let lines = vec![
+        Line::from(vec![Span::raw(format!("  {} -> {}: ", download.name, download.path.display())), status]),
+        Line::from(Span::styled(progress, Style::default().fg(Color::Cyan))),
+    ];
+    f.render_widget(Paragraph::new(lines).block(Block::default().borders(Borders::TOP)), area);
+}
+
+fn host_status(entry: &CatalogEntry) -> Span<'static> {
+    match (entry.downloaded, entry.partial) {
+        (Some(bytes), _) => Span::styled(format!("downloaded ({})", format_bytes(bytes)), Style::default().fg(Color::Green)),
+        (None, Some(bytes)) => Span::styled(format!("partial ({})", format_bytes(bytes)), Style::default().fg(Color::Yellow)),
+        (None, None) => Span::styled("not downloaded", Style::default().fg(Color::DarkGray)),
+    }
+}
+
+/// The DataVolume an import created, from the last collection
+fn volume_status(app: &App, panel: &ImageCatalogPanel, entry: &CatalogEntry) -> Span<'static> {
+    let key = format!("{}/{}", panel.namespace, entry.volume_name());
+    let Some(volume) = app.data_volumes.iter().find(|volume| volume.key() == key) else {
+        return Span::styled("no DataVolume", Style::default().fg(Color::DarkGray));
+    };
+    let color = match volume.phase.as_str() {
+        "Succeeded" => Color::Green,
+        "Failed" => Color::Red,
+        _ => Color::Yellow,
+    };
+    let progress = volume.progress.filter(|_| volume.phase != "Succeeded").map_or(String::new(), |p| format!(" {:.0}%", p));
+    Span::styled(format!("DataVolume {}: {}{}", volume.name, volume.phase, progress), Style::default().fg(color))
+}
@@ -0,0 +1,48 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::log_archive::LogRangePrompt;
+
+/// Ask for the time range of an archived log query
+pub fn draw_log_range_prompt(f: &mut Frame, prompt: &LogRangePrompt, area: Rect) {
+    let popup_area = centered_rect(60, 30, area);
+    f.render_widget(Clear, popup_area);
+
+    let field = |label: &str, value: &str, editing: bool| {
+        let style = if editing {
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        Line::from(vec![
+            Span::styled(format!("  {:<7}", label), Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{}{}", value, if editing { "_" } else { "" }), style),
+        ])
+    };
+    let lines = vec![
+        Line::from(""),
+        field("Since:", &prompt.since, !prompt.editing_until),
+        field("Until:", &prompt.until, prompt.editing_until),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  e.g. \"2024-03-02 22:00\", \"yesterday\", \"-2h\", \"now\"",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(Span::styled(
+            "  Same filters as the live view; the live tail keeps following",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let block = Block::default()
+        .title(" Archived Logs ")
+        .title_bottom(" Tab: Switch field  Enter: Query  Esc: Cancel ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
@@ -0,0 +1,62 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::LogContext;
+use crate::timestamps::format_log_time;
+
+/// Draw the surrounding lines of a selected log entry, anchor highlighted
+pub fn draw_log_context(f: &mut Frame, context: &LogContext, area: Rect) {
+    let popup_area = centered_rect(85, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(
+            " Context: {} @ {} ({}) ",
+            context.anchor.service, format_log_time(&context.anchor.timestamp), context.source
+        ))
+        .title_bottom(" Esc: Close ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let lines: Vec<Line> = context
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let level_color = match entry.level.as_str() {
+                "ERROR" | "CRITICAL" => Color::Red,
+                "WARN" | "WARNING" => Color::Yellow,
+                "INFO" => Color::Green,
+                _ => Color::Gray,
+            };
+            let line = Line::from(vec![
+                Span::styled(format_log_time(&entry.timestamp), Style::default().fg(Color::DarkGray)),
+                Span::raw(" "),
+                Span::styled(format!("{:<5}", entry.level), Style::default().fg(level_color)),
+                Span::raw(" "),
+                Span::raw(entry.message.clone()),
+            ]);
+            if i == context.anchor_index {
+                line.style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    // Keep the anchor vertically centered when there are more lines than fit
+    let scroll = context
+        .anchor_index
+        .saturating_sub(inner.height as usize / 2)
+        .min(lines.len().saturating_sub(inner.height as usize));
+
+    f.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), inner);
+}
@@ -0,0 +1,100 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::LogDetail;
+use crate::timestamps::format_log_time;
+
+/// Draw one log line in full: its message wrapped rather than truncated,
+/// its structured fields and the journal record it came from
+pub fn draw_log_detail(f: &mut Frame, detail: &LogDetail, area: Rect) {
+    let popup_area = centered_rect(80, 75, area);
+    f.render_widget(Clear, popup_area);
+
+    let entry = &detail.entry;
+    let block = Block::default()
+        .title(format!(" Log Line: {} @ {} ", entry.service, format_log_time(&entry.timestamp)))
+        .title_bottom(" c: Context  u: Filter unit  p: Filter PID  y: Copy  ↑/↓: Scroll  Esc: Close ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let level_color = match entry.level.as_str() {
+        "ERROR" | "CRITICAL" => Color::Red,
+        "WARN" | "WARNING" => Color::Yellow,
+        "INFO" => Color::Green,
+        _ => Color::Gray,
+    };
+    let label = |text: String| Span::styled(format!(" {:<24}", text), Style::default().fg(Color::Gray));
+    let heading = |text: &'static str| {
+        Line::from(Span::styled(format!(" {}", text), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)))
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            label("Level:".to_string()),
+            Span::styled(entry.level.clone(), Style::default().fg(level_color).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(vec![label("Service:".to_string()), Span::raw(entry.service.clone())]),
+    ];
+    for (name, value) in [("Component:", &entry.component), ("PID:", &entry.pid)] {
+        if let Some(value) = value {
+            lines.push(Line::from(vec![label(name.to_string()), Span::raw(value.clone())]));
+        }
+    }
+    if entry.repeats > 0 {
+        lines.push(Line::from(vec![
+            label("Repeated:".to_string()),
+            Span::raw(format!(
+                "{} more time(s), last at {}",
+                entry.repeats,
+                entry.last_timestamp.as_deref().unwrap_or("-")
+            )),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(heading("Message"));
+    lines.extend(entry.message.lines().map(|line| Line::from(format!(" {}", line))));
+
+    if !entry.fields.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(heading("Fields"));
+        for (key, value) in &entry.fields {
+            lines.push(Line::from(vec![label(format!("{}:", key)), Span::raw(value.clone())]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(heading("Journal"));
+    match (&detail.journal_error, detail.journal.is_empty()) {
+        (Some(error), _) => lines.push(Line::from(Span::styled(
+            format!(" Record unavailable: {}", error),
+            Style::default().fg(Color::DarkGray),
+        ))),
+        (None, true) => lines.push(Line::from(Span::styled(
+            " Not from the journal",
+            Style::default().fg(Color::DarkGray),
+        ))),
+        (None, false) => {
+            for (key, value) in &detail.journal {
+                // The message is shown in full above
+                if key == "MESSAGE" {
+                    continue;
+                }
+                lines.push(Line::from(vec![label(format!("{}:", key)), Span::raw(value.clone())]));
+            }
+        }
+    }
+
+    f.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }).scroll((detail.scroll.min(u16::MAX as usize) as u16, 0)),
+        inner,
+    );
+}
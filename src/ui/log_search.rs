@@ -0,0 +1,134 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::log_search::LogSearch;
+use crate::timestamps::format_log_time;
+
+/// The query, then each service's hits under a header, best ranked
+/// services first and those without hits summed up last
+pub fn draw_log_search(f: &mut Frame, search: &LogSearch, area: Rect) {
+    let popup_area = centered_rect(85, 80, area);
+    f.render_widget(Clear, popup_area);
+
+    let query_style = if search.editing {
+        Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Gray)
+    };
+    let header = vec![
+        Line::from(vec![
+            Span::styled(" Query: ", Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{}{}", search.query, if search.editing { "_" } else { "" }), query_style),
+        ]),
+        Line::from(Span::styled(
+            if search.groups.is_empty() {
+                " Words match message or service, key=value a structured field".to_string()
+            } else {
+                format!(" {}", search.summary())
+            },
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    let mut hit_index = 0;
+    let mut without_hits = Vec::new();
+    for group in &search.groups {
+        if group.hits.is_empty() {
+            if group.done {
+                without_hits.push(group.service.as_str());
+            }
+            continue;
+        }
+        let errors = group.hits.iter().filter(|h| h.level == "ERROR").count();
+        let warnings = group.hits.iter().filter(|h| h.level == "WARN").count();
+        let mut heading = vec![
+            Span::styled(group.service.clone(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::raw(format!("  {} hits", group.matches)),
+        ];
+        if errors > 0 {
+            heading.push(Span::styled(format!("  {} errors", errors), Style::default().fg(Color::Red)));
+        }
+        if warnings > 0 {
+            heading.push(Span::styled(format!("  {} warnings", warnings), Style::default().fg(Color::Yellow)));
+        }
+        heading.push(Span::styled(
+            format!("  {} lines of the {}", group.scanned, group.source),
+            Style::default().fg(Color::DarkGray),
+        ));
+        if group.matches > group.hits.len() {
+            heading.push(Span::styled(
+                format!(", best {} shown", group.hits.len()),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines.push(Line::from(heading));
+        if let Some(ref e) = group.error {
+            lines.push(Line::from(Span::styled(
+                format!("  journal unavailable: {}", e),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+
+        for hit in &group.hits {
+            let level_color = match hit.level.as_str() {
+                "ERROR" => Color::Red,
+                "WARN" => Color::Yellow,
+                "DEBUG" => Color::DarkGray,
+                _ => Color::Green,
+            };
+            let selected = !search.editing && hit_index == search.selected;
+            if selected {
+                selected_line = lines.len();
+            }
+            let line = Line::from(vec![
+                Span::styled(format!("  {} ", format_log_time(&hit.timestamp)), Style::default().fg(Color::Gray)),
+                Span::styled(format!("{:<5} ", hit.level), Style::default().fg(level_color)),
+                Span::raw(hit.message.clone()),
+            ]);
+            lines.push(if selected { line.style(Style::default().bg(Color::DarkGray)) } else { line });
+            hit_index += 1;
+        }
+    }
+    let searching: Vec<&str> = search.groups.iter().filter(|g| !g.done).map(|g| g.service.as_str()).collect();
+    if !searching.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("Searching: {}", searching.join(", ")),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    if !without_hits.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("No hits: {}", without_hits.join(", ")),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let block = Block::default()
+        .title(" Search All Services ")
+        .title_bottom(if search.editing {
+            " Enter: Search  Esc: Close "
+        } else {
+            " ↑/↓: Select  Enter: Context  /: Edit query  Esc: Close "
+        })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+    if inner.height < 4 {
+        return;
+    }
+
+    f.render_widget(Paragraph::new(header), Rect { height: 2, ..inner });
+    // Keep the selected hit in view
+    let results_area = Rect { y: inner.y + 3, height: inner.height - 3, ..inner };
+    let offset = selected_line.saturating_sub(results_area.height.saturating_sub(1) as usize);
+    f.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), results_area);
+}
@@ -0,0 +1,79 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::app::LogServicePicker;
+
+/// Draw the journald units grouped by category, followed ones checked
+pub fn draw_log_services(f: &mut Frame, picker: &LogServicePicker, area: Rect, glyphs: &Glyphs) {
+    let popup_area = centered_rect(60, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let following = picker.entries.iter().filter(|e| e.enabled).count();
+    let block = Block::default()
+        .title(format!(" Log Services ({} followed) ", following))
+        .title_bottom(format!(" {}: Select  Space: Toggle  Esc: Close ", glyphs.up_down))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+
+    let status = match picker.status {
+        Some(ref status) => Span::styled(status.clone(), Style::default().fg(Color::Yellow)),
+        None if following == 0 => Span::styled(
+            "Nothing checked: following every unit",
+            Style::default().fg(Color::Yellow),
+        ),
+        None => Span::styled(
+            "Changes apply immediately and are not saved to the config",
+            Style::default().fg(Color::DarkGray),
+        ),
+    };
+    f.render_widget(Paragraph::new(status), chunks[0]);
+
+    // A header line before the first unit of each category
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    for (i, entry) in picker.entries.iter().enumerate() {
+        if i == 0 || picker.entries[i - 1].category != entry.category {
+            lines.push(Line::from(Span::styled(
+                entry.category.label(),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            )));
+        }
+        if i == picker.selected {
+            selected_line = lines.len();
+        }
+
+        let marker = if i == picker.selected { glyphs.selected } else { " " };
+        let checkbox = if entry.enabled { "[x]" } else { "[ ]" };
+        let style = if i == picker.selected {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} {} ", marker, checkbox), style.fg(Color::Gray)),
+            Span::styled(
+                entry.name.clone(),
+                style.fg(if entry.enabled { Color::Green } else { Color::White }),
+            ),
+        ]));
+    }
+
+    // Keep the selection in view
+    let height = chunks[1].height as usize;
+    let offset = selected_line.saturating_sub(height.saturating_sub(1));
+    f.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), chunks[1]);
+}
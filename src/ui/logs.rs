@@ -1,57 +1,284 @@
 use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
-    text::Line,
-    widgets::{Block, Borders, List, ListItem},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
 
-use crate::app::App;
+use super::focus_block;
+use super::glyphs::Glyphs;
+use crate::app::{App, NOISY_SOURCES};
+use crate::metrics_history::{Trend, NOISY_WINDOW_MINS};
+use crate::panes::Pane;
+use crate::timestamps::format_log_time;
+use crate::types::LogEntry;
+
+/// Continuation rows of a wrapped entry are indented at most this far
+const MAX_HANGING_INDENT: usize = 32;
+
+/// Columns ←/→ move the unwrapped log list by
+pub const HSCROLL_STEP: usize = 8;
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(NOISY_SOURCES as u16 + 2), Constraint::Min(3)])
+        .split(area);
+    draw_noisy_sources(f, app, chunks[0]);
+    draw_logs(f, app, chunks[1]);
+}
+
+/// The services logging the most errors lately; Enter filters the log
+/// list down to the selected one
+fn draw_noisy_sources(f: &mut Frame, app: &App, area: Rect) {
+    let glyphs = Glyphs::for_mode(app.accessible);
+    let sources = app.metrics_history.noisy_sources(NOISY_SOURCES);
+    let selected = app.pane_offset(Pane::NoisySources);
+    let focused = app.focused_pane() == Some(Pane::NoisySources);
+
+    let lines: Vec<Line> = if sources.is_empty() {
+        vec![Line::from(Span::styled(
+            format!("  No errors logged in the last {} minutes", NOISY_WINDOW_MINS),
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        sources
+            .iter()
+            .enumerate()
+            .map(|(i, source)| {
+                let arrow = match source.trend() {
+                    Trend::Rising => Span::styled(glyphs.rising, Style::default().fg(Color::Red)),
+                    Trend::Falling => Span::styled(glyphs.falling, Style::default().fg(Color::Green)),
+                    Trend::Steady => Span::styled(glyphs.steady, Style::default().fg(Color::Gray)),
+                };
+                let line = Line::from(vec![
+                    Span::styled(format!(" {:<24}", source.service), Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("{:>7} errors ", source.errors), Style::default().fg(Color::Red)),
+                    arrow,
+                    Span::styled(
+                        format!(" {} in the {} min before", source.previous, NOISY_WINDOW_MINS),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]);
+                if focused && i == selected {
+                    line.style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+                } else {
+                    line
+                }
+            })
+            .collect()
+    };
+
+    let block = Block::default()
+        .title(format!("Noisy Sources (last {} min) | Tab: Select | Enter: Filter", NOISY_WINDOW_MINS))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+    f.render_widget(Paragraph::new(lines).block(focus_block(app, Pane::NoisySources, block)), area);
+}
+
+fn draw_logs(f: &mut Frame, app: &App, area: Rect) {
+    let glyphs = Glyphs::for_mode(app.accessible);
     let displayed_logs = app.get_displayed_logs();
-    let logs: Vec<ListItem> = displayed_logs
-        .iter()
-        .skip(app.scroll_offset)
-        .map(|entry| {
-            let style = match entry.level.as_str() {
-                "ERROR" | "CRITICAL" => Style::default().fg(Color::Red),
-                "WARN" | "WARNING" => Style::default().fg(Color::Yellow),
-                "INFO" => Style::default().fg(Color::Green),
-                _ => Style::default().fg(Color::Gray),
-            };
-
-            ListItem::new(Line::from(vec![
-                ratatui::text::Span::styled(&entry.timestamp, Style::default().fg(Color::DarkGray)),
-                ratatui::text::Span::raw(" "),
-                ratatui::text::Span::styled(&entry.service, Style::default().fg(Color::Cyan)),
-                ratatui::text::Span::raw(": "),
-                ratatui::text::Span::styled(&entry.message, style),
-            ]))
-        })
-        .collect();
-
-    let title = if !app.search_query.is_empty() || app.filter_level.is_some() {
-        let mut parts = vec!["System Logs".to_string()];
+    // Only the lines that fit are built; the buffer can hold thousands
+    let visible = area.height.saturating_sub(2) as usize;
+    let width = area.width.saturating_sub(2) as usize;
+    let offset = app.pane_offset(Pane::Logs).min(displayed_logs.len());
+    let highlight = |i: usize, item: ListItem<'static>| {
+        // The top entry is the selection for the detail view (Enter)
+        if i == 0 {
+            item.style(Style::default().bg(Color::DarkGray))
+        } else {
+            item
+        }
+    };
+
+    let mut logs: Vec<ListItem> = Vec::new();
+    if app.logs_wrapped {
+        let mut rows_left = visible;
+        let mut skip = app.log_rows_scrolled();
+        for (i, entry) in displayed_logs[offset..].iter().enumerate() {
+            if rows_left == 0 {
+                break;
+            }
+            let (line, indent) = entry_line(app, entry, glyphs);
+            let rows: Vec<Line> = wrap_line(line, width, indent)
+                .into_iter()
+                .skip(std::mem::take(&mut skip))
+                .take(rows_left)
+                .collect();
+            rows_left -= rows.len();
+            logs.push(highlight(i, ListItem::new(rows)));
+        }
+    } else {
+        for (i, entry) in displayed_logs[offset..].iter().take(visible).enumerate() {
+            let (line, prefix) = entry_line(app, entry, glyphs);
+            logs.push(highlight(i, ListItem::new(shift_line(line, prefix, app.log_hscroll))));
+        }
+    }
+
+    // An archived range replaces the live buffer until Esc
+    let (name, total) = match app.log_archive {
+        Some(ref archive) => (format!("Archived Logs {}", archive.summary()), archive.entries.len()),
+        None => ("System Logs".to_string(), app.logs.len()),
+    };
+    let mut title = if app.search_active {
+        format!("{} | Search: {}_ [{}/{}]", name, app.search_query, displayed_logs.len(), total)
+    } else if !app.search_query.is_empty() || app.filter_level.is_some() {
+        let mut parts = vec![name];
         if !app.search_query.is_empty() {
             parts.push(format!("Search: {}", app.search_query));
         }
         if let Some(ref level) = app.filter_level {
             parts.push(format!("Level: {}", level));
         }
-        parts.push(format!("[{}/{}]", displayed_logs.len(), app.logs.len()));
+        parts.push(format!("[{}/{}]", displayed_logs.len(), total));
         parts.join(" | ")
+    } else if app.log_archive.is_some() {
+        name
     } else {
-        format!("System Logs [{} entries]", displayed_logs.len())
+        format!("{} [{} entries]", name, displayed_logs.len())
     };
+    if app.log_filtering() {
+        title.push_str(" (filtering…)");
+    }
+    title.push_str(if app.logs_collapsed { " | x: Expand repeats" } else { " | x: Collapse repeats" });
+    title.push_str(if app.logs_wrapped { " | w: Unwrap" } else { " | w: Wrap" });
+    if !app.logs_wrapped && app.log_hscroll > 0 {
+        title.push_str(&format!(" | ←/→: Col {}", app.log_hscroll + 1));
+    }
+    title.push_str(" | /: Search | Enter: Details | u: Services");
+    title.push_str(if app.log_archive.is_some() { " | H: Range | Esc: Live" } else { " | H: Range" });
 
-    let logs_widget = List::new(logs)
-        .block(
-            Block::default()
-                .title(title)
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Green)),
-        );
+    let logs_widget = List::new(logs).block(focus_block(
+        app,
+        Pane::Logs,
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if app.log_archive.is_some() { Color::Magenta } else { Color::Green })),
+    ));
 
     f.render_widget(logs_widget, area);
 }
+
+/// An entry as one line: timestamp, service, message and fields, and how
+/// many columns come before the message
+fn entry_line(app: &App, entry: &LogEntry, glyphs: &Glyphs) -> (Line<'static>, usize) {
+    let style = match entry.level.as_str() {
+        "ERROR" | "CRITICAL" => Style::default().fg(Color::Red),
+        "WARN" | "WARNING" => Style::default().fg(Color::Yellow),
+        "INFO" => Style::default().fg(Color::Green),
+        _ => Style::default().fg(Color::Gray),
+    };
+
+    let mut spans = vec![
+        Span::styled(format_log_time(&entry.timestamp), Style::default().fg(Color::DarkGray)),
+        Span::raw(" "),
+    ];
+    if app.accessible {
+        spans.push(Span::raw(format!("[{}] ", entry.level)));
+    }
+    spans.push(Span::styled(entry.service.clone(), Style::default().fg(Color::Cyan)));
+    if let Some(ref component) = entry.component {
+        spans.push(Span::styled(format!("/{}", component), Style::default().fg(Color::Blue)));
+    }
+    spans.push(Span::raw(": "));
+    let prefix = spans.iter().map(|span| span.content.chars().count()).sum();
+    spans.extend(
+        app.log_highlighter
+            .spans(&entry.message, style)
+            .into_iter()
+            .map(|span| Span::styled(span.content.into_owned(), span.style)),
+    );
+    for (key, value) in &entry.fields {
+        spans.push(Span::styled(format!(" {}={}", key, value), Style::default().fg(Color::DarkGray)));
+    }
+
+    if entry.repeats > 0 {
+        spans.push(Span::styled(
+            format!(
+                "  {}{} (last {})",
+                glyphs.times,
+                entry.repeats + 1,
+                format_log_time(entry.last_timestamp.as_deref().unwrap_or(&entry.timestamp))
+            ),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    (Line::from(spans), prefix)
+}
+
+/// Rows `entry` takes in the wrapped log list, `width` columns wide
+pub fn wrapped_rows(app: &App, entry: &LogEntry, width: usize) -> usize {
+    let (line, indent) = entry_line(app, entry, Glyphs::for_mode(app.accessible));
+    wrap_line(line, width, indent).len()
+}
+
+/// Soft-wrap `line` into rows of `width` columns, breaking after the last
+/// space of a row when it has one, with rows after the first indented by
+/// `indent` (up to half the width) so the message stands apart from the
+/// timestamp and service
+fn wrap_line(line: Line<'static>, width: usize, indent: usize) -> Vec<Line<'static>> {
+    let width = width.max(1);
+    let indent = indent.min(MAX_HANGING_INDENT).min(width / 2);
+    let chars = styled_chars(&line);
+    let mut rows = Vec::new();
+    let mut start = 0;
+    while start < chars.len() || rows.is_empty() {
+        let room = if rows.is_empty() { width } else { width - indent };
+        let mut end = (start + room).min(chars.len());
+        if end < chars.len() {
+            if let Some(space) = chars[start..end].iter().rposition(|(c, _)| *c == ' ').filter(|&at| at > 0) {
+                end = start + space + 1;
+            }
+        }
+        let mut spans = Vec::new();
+        if !rows.is_empty() {
+            spans.push(Span::raw(" ".repeat(indent)));
+        }
+        spans.extend(group_spans(&chars[start..end]));
+        rows.push(Line::from(spans));
+        start = end;
+    }
+    rows
+}
+
+/// `line` with `columns` characters after its first `prefix` ones left
+/// out, so the timestamp and service stay put while the message scrolls
+fn shift_line(line: Line<'static>, prefix: usize, columns: usize) -> Line<'static> {
+    if columns == 0 {
+        return line;
+    }
+    let chars = styled_chars(&line);
+    let prefix = prefix.min(chars.len());
+    let rest = (prefix + columns).min(chars.len());
+    let mut spans = group_spans(&chars[..prefix]);
+    spans.extend(group_spans(&chars[rest..]));
+    Line::from(spans)
+}
+
+fn styled_chars(line: &Line) -> Vec<(char, Style)> {
+    line.spans
+        .iter()
+        .flat_map(|span| span.content.chars().map(move |c| (c, span.style)))
+        .collect()
+}
+
+/// Runs of equally styled characters back into spans
+fn group_spans(chars: &[(char, Style)]) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut text = String::new();
+    let mut style = None;
+    for &(c, char_style) in chars {
+        if style.is_some_and(|style| style != char_style) {
+            spans.push(Span::styled(std::mem::take(&mut text), style.unwrap_or_default()));
+        }
+        style = Some(char_style);
+        text.push(c);
+    }
+    if let Some(style) = style {
+        spans.push(Span::styled(text, style));
+    }
+    spans
+}
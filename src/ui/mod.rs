@@ -1,19 +1,79 @@
-mod logs;
+pub mod logs;
 mod dashboard;
 mod network;
+mod resources;
+mod timeline;
+mod charts;
+mod compare;
+pub mod heatmap;
+mod fleet;
+mod hostmap;
+mod tools;
+mod yaml;
+mod object_watch;
+mod debug;
+mod vm;
+mod log_archive;
+mod log_context;
+mod log_detail;
+mod log_search;
+mod log_services;
+mod cpu_tuning;
+mod hotplug;
+mod vm_clone;
+mod node_editor;
+mod batch;
+mod upgrade;
+mod update_check;
+mod nix_store;
+mod image_catalog;
+mod selftest;
+mod evacuation;
+mod toasts;
+mod config_report;
+mod palette;
+mod glyphs;
 pub mod alerts;
+pub mod wizard;
+#[cfg(test)]
+pub mod snapshot;
 
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
     Frame,
 };
 
 use crate::app::{App, Screen};
+use crate::config::{HeaderWidget, HeaderWidgetConfig};
+use crate::health::{HealthLevel, HealthScore};
+use crate::i18n::t;
+use crate::keymap;
+use crate::log_forward::LogForwarder;
+use crate::metrics_history::Trend;
+use crate::panes::Pane;
+use crate::types::CommandOutput;
+pub use glyphs::Glyphs;
+
+/// Smallest terminal the layouts are drawn in; below it they overlap
+pub const MIN_WIDTH: u16 = 70;
+pub const MIN_HEIGHT: u16 = 20;
 
 pub fn draw(f: &mut Frame, app: &App) {
+    draw_screen(f, app);
+    if app.accessible {
+        glyphs::to_ascii(f.buffer_mut());
+    }
+}
+
+fn draw_screen(f: &mut Frame, app: &App) {
+    if too_small(f) {
+        return;
+    }
+    let glyphs = Glyphs::for_mode(app.accessible);
+
     // Check if we have active alerts
     let active_alerts = app.alert_manager.get_active_alerts();
     let has_alerts = !active_alerts.is_empty();
@@ -42,7 +102,9 @@ pub fn draw(f: &mut Frame, app: &App) {
 
     // Draw alert banner if there are active alerts
     if has_alerts {
-        alerts::draw_alert_banner(f, &active_alerts, chunks[chunk_idx]);
+        // Accessible mode redraws on changes only, so it doesn't cycle
+        let cycle_secs = if app.accessible { 0 } else { app.banner_cycle_secs };
+        alerts::draw_alert_banner(f, &active_alerts, chunks[chunk_idx], glyphs, &app.alert_styles, cycle_secs);
         chunk_idx += 1;
     }
 
@@ -55,6 +117,13 @@ pub fn draw(f: &mut Frame, app: &App) {
         Screen::Logs => logs::draw(f, app, chunks[chunk_idx]),
         Screen::Dashboard => dashboard::draw(f, app, chunks[chunk_idx]),
         Screen::Network => network::draw(f, app, chunks[chunk_idx]),
+        Screen::Resources => resources::draw(f, app, chunks[chunk_idx]),
+        Screen::Timeline => timeline::draw(f, app, chunks[chunk_idx]),
+        Screen::Charts => charts::draw(f, app, chunks[chunk_idx]),
+        Screen::Heatmap => heatmap::draw(f, app, chunks[chunk_idx]),
+        Screen::Fleet => fleet::draw(f, app, chunks[chunk_idx]),
+        Screen::HostMap => hostmap::draw(f, app, chunks[chunk_idx]),
+        Screen::Tools => tools::draw(f, app, chunks[chunk_idx]),
     }
     chunk_idx += 1;
 
@@ -63,84 +132,506 @@ pub fn draw(f: &mut Frame, app: &App) {
 
     // Draw alert panel if in alert view mode
     if app.alert_panel_open {
-        alerts::draw_alert_panel(f, &active_alerts, f.size(), app.alert_selected_index);
+        alerts::draw_alert_panel(f, app, &active_alerts, f.size(), glyphs);
+        if app.alert_detail.is_some() {
+            alerts::draw_alert_detail(
+                f,
+                app.selected_alert(),
+                &app.alert_manager.suppressed_alerts(),
+                f.size(),
+                glyphs,
+                &app.alert_styles,
+                app.read_only,
+            );
+        }
+    }
+
+    if app.alert_stats_open {
+        alerts::draw_alert_statistics(f, &app.alert_manager.statistics(), f.size(), glyphs, &app.alert_styles);
+    }
+
+    if let Some(ref node) = app.fleet_detail {
+        fleet::draw_node_detail(f, app, node, f.size());
+    }
+
+    if let Some(ref view) = app.compare {
+        compare::draw_compare(f, view, f.size());
+    }
+
+    if let Some(ref search) = app.log_search {
+        log_search::draw_log_search(f, search, f.size());
+    }
+
+    if let Some(ref detail) = app.log_detail {
+        log_detail::draw_log_detail(f, detail, f.size());
+    }
+
+    if let Some(ref context) = app.log_context {
+        log_context::draw_log_context(f, context, f.size());
+    }
+
+    if let Some(ref prompt) = app.log_range_prompt {
+        log_archive::draw_log_range_prompt(f, prompt, f.size());
+    }
+
+    if let Some(ref picker) = app.log_services {
+        log_services::draw_log_services(f, picker, f.size(), glyphs);
+    }
+
+    if let Some(ref detail) = app.vm_detail {
+        vm::draw_vm_detail(f, detail, f.size(), app.guest_fs_warning_threshold(), app.read_only, glyphs);
+    }
+
+    if let Some(ref editor) = app.cpu_tuning {
+        cpu_tuning::draw_cpu_tuning(f, editor, f.size(), glyphs);
+    }
+
+    if let Some(ref editor) = app.hotplug {
+        hotplug::draw_hotplug(f, editor, f.size(), glyphs);
+    }
+
+    if let Some(ref editor) = app.vm_clone {
+        vm_clone::draw_vm_clone(f, editor, f.size());
+    }
+
+    if let Some(ref editor) = app.node_editor {
+        node_editor::draw_node_editor(f, editor, f.size(), glyphs, app.read_only);
+    }
+
+    if let Some(ref prompt) = app.batch_prompt {
+        batch::draw_batch_prompt(f, prompt, app.vm_marked.len(), f.size());
+    }
+
+    if let Some(run) = app.batch_run.as_ref().filter(|run| !run.hidden) {
+        batch::draw_batch_progress(f, run, f.size(), glyphs);
+    }
+
+    if app.upgrade_prompt {
+        upgrade::draw_upgrade_prompt(f, f.size());
+    }
+
+    if let Some(checker) = app.update_checker.as_ref().filter(|_| app.update_popup) {
+        update_check::draw_update_popup(f, checker, f.size());
+    }
+
+    if let Some(run) = app.upgrade_run.as_ref().filter(|run| !run.hidden) {
+        upgrade::draw_upgrade_run(f, run, f.size(), glyphs);
+    }
+
+    if let Some(panel) = app.nix_store.as_ref().filter(|panel| !panel.hidden) {
+        nix_store::draw_nix_store(f, app, panel, f.size(), glyphs);
+    }
+
+    if let Some(panel) = app.image_catalog.as_ref().filter(|panel| !panel.hidden) {
+        image_catalog::draw_image_catalog(f, app, panel, f.size(), glyphs);
+    }
+
+    if let Some(ref node) = app.evacuation_prompt {
+        evacuation::draw_evacuation_prompt(f, node, app.fleet_vm_count(node), f.size());
+    }
+
+    if let Some(run) = app.evacuation.as_ref().filter(|run| !run.hidden) {
+        evacuation::draw_evacuation(f, run, f.size(), glyphs);
+    }
+
+    if let Some(run) = app.selftest_run.as_ref().filter(|run| !run.hidden) {
+        selftest::draw_selftest_run(f, run, f.size(), glyphs);
+    }
+
+    // Draw YAML inspector on top of the resource browser
+    if let Some(ref inspector) = app.yaml_inspector {
+        yaml::draw_yaml_inspector(f, inspector, f.size(), glyphs, app.read_only);
+    }
+
+    if let Some(ref watch) = app.object_watch {
+        object_watch::draw_object_watch(f, watch, f.size(), glyphs);
+    }
+
+    if let Some(ref viewer) = app.cloud_init {
+        yaml::draw_cloud_init(f, viewer, f.size(), glyphs, app.read_only);
+    }
+
+    if let Some(ref palette) = app.palette {
+        palette::draw_palette(f, palette, f.size());
+    }
+
+    if let Some(ref name) = app.view_name_prompt {
+        palette::draw_view_name_prompt(f, name, f.size());
+    }
+
+    if let Some(ref report) = app.config_report {
+        config_report::draw_config_report(f, report, f.size(), glyphs);
+    }
+
+    if !app.messages.is_empty() {
+        toasts::draw_toasts(f, &app.messages, f.size(), glyphs);
+    }
+
+    if app.debug_overlay_open {
+        debug::draw_debug_overlay(
+            f,
+            &app.collector_timings,
+            &app.telemetry_snapshot(),
+            &app.k8s_collector.api_call_rates(),
+            &app.log_forwarder.as_ref().map(LogForwarder::statuses).unwrap_or_default(),
+            &app.permissions,
+            f.size(),
+        );
+    }
+
+    if app.accessible {
+        strip_colors(f);
+    } else if app.light_theme {
+        adapt_to_light_background(f);
     }
 }
 
-fn draw_header(f: &mut Frame, app: &App, area: Rect) {
-    let header_text = vec![
-        Line::from(vec![
-            Span::styled("Node: ", Style::default().fg(Color::Gray)),
-            Span::styled("hypervisor-01", Style::default().fg(Color::Green)),
-            Span::raw("    "),
-            Span::styled("Uptime: ", Style::default().fg(Color::Gray)),
-            Span::styled("15d 7h 32m", Style::default().fg(Color::Cyan)),
-            Span::raw("    "),
-            Span::styled("CPU: ", Style::default().fg(Color::Gray)),
-            Span::styled(
-                format!("{:.1}%", app.system_metrics.cpu_usage),
-                Style::default().fg(Color::Yellow)
-            ),
-        ]),
-        Line::from(vec![
-            Span::styled("K3s: ", Style::default().fg(Color::Gray)),
-            Span::styled("Running ✓", Style::default().fg(Color::Green)),
-            Span::raw("    "),
-            Span::styled("Memory: ", Style::default().fg(Color::Gray)),
-            Span::styled(
-                format!("{:.1}/{:.1} GB",
-                    app.system_metrics.memory_used_gb,
-                    app.system_metrics.memory_total_gb
-                ),
-                Style::default().fg(Color::Yellow)
-            ),
-            Span::raw("    "),
-            Span::styled("VMs: ", Style::default().fg(Color::Gray)),
-            Span::styled("12/50", Style::default().fg(Color::Green)),
-        ]),
+/// Swap the colors chosen for dark terminals (gray text, yellow/cyan
+/// values, dark gray selection bars) for darker shades that stay legible
+/// on a light background
+fn adapt_to_light_background(f: &mut Frame) {
+    let area = f.size();
+    let buffer = f.buffer_mut();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = buffer.get_mut(x, y);
+            cell.fg = match cell.fg {
+                Color::White | Color::Gray => Color::Black,
+                Color::DarkGray => Color::Indexed(242),
+                Color::Yellow | Color::LightYellow => Color::Indexed(130),
+                Color::Cyan | Color::LightCyan => Color::Indexed(25),
+                Color::Green | Color::LightGreen => Color::Indexed(28),
+                Color::LightRed => Color::Red,
+                Color::LightMagenta => Color::Magenta,
+                Color::LightBlue => Color::Blue,
+                other => other,
+            };
+            if cell.bg == Color::DarkGray {
+                cell.bg = Color::Indexed(252);
+            }
+        }
+    }
+}
+
+/// Remove all colors from the rendered frame for no-color/screen-reader use.
+/// Cells that relied on a background color (selections, badges) are
+/// rendered reversed instead so they remain distinguishable.
+fn strip_colors(f: &mut Frame) {
+    let area = f.size();
+    let buffer = f.buffer_mut();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = buffer.get_mut(x, y);
+            if cell.bg != Color::Reset {
+                cell.modifier.insert(Modifier::REVERSED);
+            }
+            cell.fg = Color::Reset;
+            cell.bg = Color::Reset;
+        }
+    }
+}
+
+/// Helper function to create a centered rectangle
+/// Draw a placeholder instead of the layouts when the terminal is below
+/// the minimum size, returning whether it did
+pub fn too_small(f: &mut Frame) -> bool {
+    let area = f.size();
+    if area.width >= MIN_WIDTH && area.height >= MIN_HEIGHT {
+        return false;
+    }
+    let text = vec![
+        Line::from(Span::styled(
+            format!("terminal too small (min {}x{})", MIN_WIDTH, MIN_HEIGHT),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!("currently {}x{}", area.width, area.height)),
     ];
+    // Vertically centered when there is room for it
+    let top = area.height.saturating_sub(text.len() as u16) / 2;
+    let area = Rect { y: area.y + top, height: area.height - top, ..area };
+    f.render_widget(Paragraph::new(text).alignment(Alignment::Center).wrap(Wrap { trim: true }), area);
+    true
+}
+
+pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+/// Thick, bold-titled border on the pane holding keyboard focus
+pub fn focus_block<'a>(app: &App, pane: Pane, block: Block<'a>) -> Block<'a> {
+    if app.pane_highlighted(pane) {
+        block
+            .border_type(BorderType::Thick)
+            .title_style(Style::default().add_modifier(Modifier::BOLD))
+    } else {
+        block
+    }
+}
+
+/// "Label: " as two borrowed spans, so nothing is formatted per frame
+fn label(text: &str) -> [Span<'_>; 2] {
+    let style = Style::default().fg(Color::Gray);
+    [Span::styled(text, style), Span::styled(": ", style)]
+}
+
+/// Gap between header widgets
+const HEADER_GAP: &str = "    ";
 
-    let header = Paragraph::new(header_text)
+/// The enabled `[[display.header]]` widgets flowed over the header's lines,
+/// about as many on each and wrapped early where the terminal is narrow; a
+/// widget wider than what is left of the last line is left out
+fn draw_header(f: &mut Frame, app: &App, area: Rect) {
+    let glyphs = Glyphs::for_mode(app.accessible);
+    let lines = area.height.saturating_sub(1).max(1) as usize;
+    // Keep clear of the health light
+    let width = area.width.saturating_sub(if app.health.is_some() { 19 } else { 0 }) as usize;
+    let per_line = app.header_widgets.len().div_ceil(lines);
+
+    let mut outputs = app.header_commands.outputs.iter();
+    let mut rows: Vec<Vec<Span>> = vec![Vec::new(); lines];
+    let (mut row, mut used, mut count) = (0, 0, 0);
+    for widget in &app.header_widgets {
+        let output = match widget.widget {
+            HeaderWidget::Command => outputs.next().and_then(Option::as_ref),
+            _ => None,
+        };
+        let spans = header_widget(app, widget, output, glyphs);
+        let widget_width: usize = spans.iter().map(Span::width).sum();
+        if count > 0 && row + 1 < lines && (count >= per_line || used + HEADER_GAP.len() + widget_width > width) {
+            (row, used, count) = (row + 1, 0, 0);
+        }
+        let needed = if count > 0 { HEADER_GAP.len() + widget_width } else { widget_width };
+        if used + needed > width {
+            continue;
+        }
+        if count > 0 {
+            rows[row].push(Span::raw(HEADER_GAP));
+        }
+        rows[row].extend(spans);
+        used += needed;
+        count += 1;
+    }
+
+    let header = Paragraph::new(rows.into_iter().map(Line::from).collect::<Vec<_>>())
         .block(Block::default().borders(Borders::BOTTOM));
     f.render_widget(header, area);
+
+    if let Some(ref health) = app.health {
+        draw_health_light(f, health, area);
+    }
 }
 
-fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
-    let footer_items = vec![
-        Span::styled(
-            " F1: Logs ",
-            if app.current_screen == Screen::Logs {
-                Style::default().fg(Color::Black).bg(Color::Green)
-            } else {
-                Style::default().fg(Color::Gray)
-            },
-        ),
-        Span::styled(
-            " F2: Dashboard ",
-            if app.current_screen == Screen::Dashboard {
-                Style::default().fg(Color::Black).bg(Color::Green)
-            } else {
-                Style::default().fg(Color::Gray)
-            },
-        ),
-        Span::styled(
-            " F3: Network ",
-            if app.current_screen == Screen::Network {
-                Style::default().fg(Color::Black).bg(Color::Green)
-            } else {
-                Style::default().fg(Color::Gray)
-            },
-        ),
-        Span::raw("  "),
-        Span::styled("↑↓: Scroll", Style::default().fg(Color::DarkGray)),
-        Span::raw("  "),
-        Span::styled("a: Alerts", Style::default().fg(Color::DarkGray)),
-        Span::raw("  "),
-        Span::styled("r: Refresh", Style::default().fg(Color::DarkGray)),
-        Span::raw("  "),
-        Span::styled("q: Quit", Style::default().fg(Color::DarkGray)),
+/// One header widget: its label, unless a command widget has none
+/// configured, and its value
+fn header_widget<'a>(app: &'a App, widget: &'a HeaderWidgetConfig, output: Option<&'a CommandOutput>, glyphs: &Glyphs) -> Vec<Span<'a>> {
+    let text = &app.display_text;
+    let own_label = match widget.widget {
+        HeaderWidget::Hostname => Some(t("header.node")),
+        HeaderWidget::Uptime => Some(t("header.uptime")),
+        HeaderWidget::Cpu => Some(t("header.cpu")),
+        HeaderWidget::Memory => Some(t("header.memory")),
+        HeaderWidget::Load => Some(t("header.load")),
+        HeaderWidget::Alerts => Some(t("header.alerts")),
+        HeaderWidget::K8s => Some(app.cluster_flavor.label()),
+        HeaderWidget::Vms => Some(t("header.vms")),
+        HeaderWidget::Command => None,
+    };
+    let mut spans: Vec<Span> = widget.label.as_deref().or(own_label).map(|text| label(text).to_vec()).unwrap_or_default();
+
+    let value = |value: &'a str, color: Color| Span::styled(value, Style::default().fg(color));
+    match widget.widget {
+        HeaderWidget::Hostname => spans.push(value(app.local_node.as_deref().unwrap_or(&app.hostname), Color::Green)),
+        HeaderWidget::Uptime => spans.push(value(text.uptime.get(), Color::Cyan)),
+        HeaderWidget::Cpu => spans.push(value(text.cpu.get(), Color::Yellow)),
+        HeaderWidget::Memory => spans.push(value(text.memory.get(), Color::Yellow)),
+        HeaderWidget::Load => spans.push(value(text.load.get(), Color::Yellow)),
+        HeaderWidget::Alerts => {
+            let counts = text.alert_counts.get();
+            if counts.is_empty() {
+                spans.push(value(t("header.no_alerts"), Color::Green));
+            }
+            for (i, (level, count)) in counts.iter().enumerate() {
+                if i > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(Span::styled(
+                    count.as_str(),
+                    Style::default().fg(app.alert_styles.level_color(*level)).add_modifier(Modifier::BOLD),
+                ));
+            }
+        }
+        HeaderWidget::K8s if app.local_mode => spans.push(Span::styled(
+            t("header.local_mode"),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )),
+        // Not connected, or the last API server probe failed
+        HeaderWidget::K8s
+            if !app.k8s_collector.is_connected() || app.api_health.as_ref().is_some_and(|health| health.error.is_some()) =>
+        {
+            spans.extend([
+                value(t("header.unreachable"), Color::Red),
+                Span::raw(" "),
+                value(glyphs.error, Color::Red),
+            ])
+        }
+        HeaderWidget::K8s => spans.extend([
+            value(t("header.running"), Color::Green),
+            Span::raw(" "),
+            value(glyphs.check, Color::Green),
+        ]),
+        HeaderWidget::Vms => spans.push(value(text.vms.get(), Color::Green)),
+        HeaderWidget::Command => {
+            let first_line = |output: &'a CommandOutput| {
+                output.error.as_deref().or_else(|| output.lines.iter().map(String::as_str).find(|line| !line.trim().is_empty()))
+            };
+            spans.push(match output {
+                None => value("...", Color::DarkGray),
+                Some(output) if output.succeeded() => value(first_line(output).unwrap_or_default(), Color::White),
+                Some(output) => value(first_line(output).unwrap_or("failed"), Color::Red),
+            });
+        }
+    }
+    spans
+}
+
+/// The health score as a two-line traffic light at the header's right end
+fn draw_health_light(f: &mut Frame, health: &HealthScore, area: Rect) {
+    let (color, level) = match health.level {
+        HealthLevel::Healthy => (Color::Green, t("health.healthy")),
+        HealthLevel::Degraded => (Color::Yellow, t("health.degraded")),
+        HealthLevel::Critical => (Color::Red, t("health.critical")),
+    };
+    let width = 18.min(area.width);
+    let light = Rect {
+        x: area.right().saturating_sub(width),
+        width,
+        height: area.height.saturating_sub(1).min(2),
+        ..area
+    };
+    let style = Style::default().fg(Color::Black).bg(color).add_modifier(Modifier::BOLD);
+    let lines = vec![
+        Line::from(format!("{} {}", t("header.health"), health.score)),
+        Line::from(level),
     ];
+    f.render_widget(Paragraph::new(lines).style(style).alignment(Alignment::Center), light);
+}
+
+/// "E ▁▂▅█ 37↑  W ▁▁▂▃ 12→": errors and warnings per minute over the
+/// last 10 minutes with their total, the sparkline colored by the trend
+/// against the 10 minutes before
+fn log_rate_spans<'a>(app: &'a App, glyphs: &Glyphs) -> Vec<Span<'a>> {
+    let Some(rates) = app.display_text.log_rates.get() else {
+        return Vec::new();
+    };
+    let mut spans = Vec::new();
+    for ((label, label_color), rate) in [("E", Color::Red), ("W", Color::Yellow)].into_iter().zip(rates) {
+        let (arrow, trend_color) = match rate.trend {
+            Trend::Rising => (glyphs.rising, Color::Red),
+            Trend::Falling => (glyphs.falling, Color::Green),
+            Trend::Steady => (glyphs.steady, Color::Gray),
+        };
+        spans.extend([
+            Span::raw("  "),
+            Span::styled(label, Style::default().fg(label_color)),
+            Span::raw(" "),
+            Span::styled(rate.sparkline.as_str(), Style::default().fg(trend_color)),
+            Span::styled(rate.total.as_str(), Style::default().fg(Color::Gray)),
+            Span::styled(arrow, Style::default().fg(trend_color)),
+        ]);
+    }
+    spans
+}
+
+fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
+    let glyphs = Glyphs::for_mode(app.accessible);
+    let mut footer_items: Vec<Span> = Screen::ALL
+        .iter()
+        .zip(app.display_text.tabs.get())
+        .map(|(screen, tab)| {
+            Span::styled(
+                tab.as_str(),
+                if app.current_screen == *screen {
+                    Style::default().fg(Color::Black).bg(Color::Green)
+                } else {
+                    Style::default().fg(Color::Gray)
+                },
+            )
+        })
+        .collect();
+    if app.footer_log_rates {
+        footer_items.extend(log_rate_spans(app, glyphs));
+    }
+    // The screen's own keys first, the shared ones after them
+    let hint = |key: &'static str, label: &'static str, color: Color| {
+        let style = Style::default().fg(color);
+        [Span::raw("  "), Span::styled(key, style), Span::styled(": ", style), Span::styled(label, style)]
+    };
+    for (key, label) in keymap::hints(app) {
+        footer_items.extend(hint(key, label, Color::Gray));
+    }
+    for (key, label) in [
+        (glyphs.up_down, "footer.scroll"),
+        ("a", "footer.alerts"),
+        ("r", "footer.refresh"),
+        ("^P", "footer.commands"),
+        ("q", "footer.quit"),
+    ] {
+        footer_items.extend(hint(key, t(label), Color::DarkGray));
+    }
+    // Active alert counts, in the same colors and icons as the banner, and
+    // the profile and read-only badges stay on the right however many
+    // keys the screen has
+    let mut status_items: Vec<Span> = Vec::new();
+    for (level, count) in app.display_text.alert_counts.get() {
+        status_items.push(Span::raw(" "));
+        status_items.push(Span::styled(
+            count.as_str(),
+            Style::default().fg(app.alert_styles.level_color(*level)).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(ref profile) = app.alert_profile {
+        let style = Style::default().fg(Color::Cyan);
+        status_items.extend([
+            Span::raw("  "),
+            Span::styled(t("footer.profile"), style),
+            Span::styled(": ", style),
+            Span::styled(profile.as_str(), style),
+        ]);
+    }
+    if let Some(release) = app.update_checker.as_ref().and_then(|checker| checker.newer.as_ref()) {
+        let style = Style::default().fg(Color::Magenta);
+        status_items.extend([
+            Span::raw("  "),
+            Span::styled(Glyphs::for_mode(app.accessible).rising, style),
+            Span::styled(format!(" {} {}", t("footer.update"), release.version), style),
+        ]);
+    }
+    if app.read_only {
+        let style = Style::default().fg(Color::Black).bg(Color::Yellow);
+        status_items.extend([Span::raw("  "), Span::styled(" ", style), Span::styled(t("footer.read_only"), style), Span::styled(" ", style)]);
+    }
 
-    let footer = Paragraph::new(Line::from(footer_items));
-    f.render_widget(footer, area);
+    let status = Line::from(status_items);
+    let [keys, status_area] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(status.width() as u16)])
+        .areas(area);
+    f.render_widget(Paragraph::new(Line::from(footer_items)), keys);
+    f.render_widget(Paragraph::new(status), status_area);
 }
@@ -1,78 +1,308 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
 
+use super::focus_block;
 use crate::app::App;
+use crate::panes::Pane;
+use crate::types::{NetworkInterface, PolicyAccess};
+use crate::units::format_bytes;
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(60),  // Interfaces
-            Constraint::Percentage(40),  // K8s networking
+            Constraint::Percentage(40),  // Interfaces
+            Constraint::Percentage(30),  // K8s networking + DNS
+            Constraint::Percentage(30),  // Service endpoints + ingress
         ])
         .split(area);
 
-    draw_interfaces(f, app, chunks[0]);
-    draw_k8s_network(f, app, chunks[1]);
+    // Routes show unless disabled; VM namespaces and VF pools only take
+    // room once there is any
+    let mut top: Vec<fn(&mut Frame, &App, Rect)> = vec![draw_interfaces];
+    if app.route_collector.is_some() {
+        top.push(draw_routes);
+    }
+    if !app.network_info.vm_namespaces.is_empty() {
+        top.push(draw_vm_interfaces);
+    }
+    if !app.sriov_nics.is_empty() {
+        top.push(draw_sriov);
+    }
+    let top_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, top.len() as u32); top.len()])
+        .split(chunks[0]);
+    for (draw_panel, &area) in top.iter().zip(top_chunks.iter()) {
+        draw_panel(f, app, area);
+    }
+
+    // Registries and image pulls, and NetworkPolicies selecting VMs, share
+    // the row once there is anything to show
+    let mut middle: Vec<fn(&mut Frame, &App, Rect)> = vec![draw_k8s_network, draw_dns];
+    if app.shows_image_pulls() {
+        middle.push(draw_registries);
+    }
+    if !app.vm_network_policies.is_empty() {
+        middle.push(draw_network_policies);
+    }
+    let columns = match middle.len() {
+        2 => vec![Constraint::Percentage(50), Constraint::Percentage(50)],
+        3 => vec![Constraint::Percentage(30), Constraint::Percentage(35), Constraint::Percentage(35)],
+        _ => vec![Constraint::Percentage(20), Constraint::Percentage(25), Constraint::Percentage(25), Constraint::Percentage(30)],
+    };
+    let bottom_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(columns)
+        .split(chunks[1]);
+    for (draw_panel, &area) in middle.iter().zip(bottom_chunks.iter()) {
+        draw_panel(f, app, area);
+    }
+
+    // Probed VIPs get a column once any watched Service has one
+    let columns = if app.vip_status.is_empty() {
+        vec![Constraint::Percentage(50), Constraint::Percentage(50)]
+    } else {
+        vec![Constraint::Percentage(35), Constraint::Percentage(30), Constraint::Percentage(35)]
+    };
+    let service_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(columns)
+        .split(chunks[2]);
+
+    draw_endpoints(f, app, service_chunks[0]);
+    draw_ingress(f, app, service_chunks[1]);
+    if let Some(&area) = service_chunks.get(2) {
+        draw_vips(f, app, area);
+    }
 }
 
+/// Host interfaces grouped by class, each group under a header line
 fn draw_interfaces(f: &mut Frame, app: &App, area: Rect) {
-    let interfaces: Vec<ListItem> = app
-        .network_info
-        .interfaces
-        .iter()
-        .map(|iface| {
-            let state_style = if iface.is_up {
-                Style::default().fg(Color::Green)
-            } else {
-                Style::default().fg(Color::Red)
-            };
+    let mut items = Vec::new();
+    let interfaces = &app.network_info.interfaces;
+    for (i, iface) in interfaces.iter().enumerate() {
+        if i == 0 || interfaces[i - 1].class != iface.class {
+            let count = interfaces.iter().filter(|other| other.class == iface.class).count();
+            items.push(ListItem::new(Line::from(Span::styled(
+                format!("{} ({})", iface.class.label(), count),
+                Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD),
+            ))));
+        }
+        items.push(interface_item(app, iface));
+    }
+    let items: Vec<ListItem> = items.into_iter().skip(app.pane_offset(Pane::Interfaces)).collect();
 
-            ListItem::new(vec![
-                Line::from(vec![
-                    Span::styled(&iface.name, Style::default().fg(Color::Cyan)),
-                    Span::raw("  "),
-                    Span::styled(
-                        if iface.is_up { "UP" } else { "DOWN" },
-                        state_style,
-                    ),
-                ]),
-                Line::from(vec![
-                    Span::styled("  IP: ", Style::default().fg(Color::Gray)),
-                    Span::raw(&iface.ip_address),
-                    Span::raw("    "),
-                    Span::styled("Speed: ", Style::default().fg(Color::Gray)),
-                    Span::raw(&iface.speed),
-                ]),
-                Line::from(vec![
-                    Span::styled("  RX: ", Style::default().fg(Color::Gray)),
-                    Span::styled(&iface.rx_bytes, Style::default().fg(Color::Yellow)),
-                    Span::raw("    "),
-                    Span::styled("TX: ", Style::default().fg(Color::Gray)),
-                    Span::styled(&iface.tx_bytes, Style::default().fg(Color::Yellow)),
-                ]),
-                Line::from(""),
-            ])
-        })
-        .collect();
-
-    let widget = List::new(interfaces).block(
+    let title = match app.network_info.hidden_interfaces {
+        0 => "Interfaces".to_string(),
+        hidden => format!("Interfaces ({} hidden)", hidden),
+    };
+    let widget = List::new(items).block(focus_block(
+        app,
+        Pane::Interfaces,
         Block::default()
-            .title("Physical Interfaces")
+            .title(title)
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Green)),
-    );
+    ));
+
+    f.render_widget(widget, area);
+}
+
+fn interface_item<'a>(app: &'a App, iface: &'a NetworkInterface) -> ListItem<'a> {
+    let state_style = if iface.is_up {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default().fg(Color::Red)
+    };
+
+    let mut name = interface_name(app, &iface.name);
+    name.extend([
+        Span::raw("  "),
+        Span::styled(
+            if iface.is_up { "UP" } else { "DOWN" },
+            state_style,
+        ),
+    ]);
+    // Yellow when it doesn't match its bridge, bond, parent or the CNI
+    let mtu_style = if app.network_info.mtu_mismatches.iter().any(|m| m.interface == iface.name) {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    ListItem::new(vec![
+        Line::from(name),
+        Line::from(vec![
+            Span::styled("  IP: ", Style::default().fg(Color::Gray)),
+            Span::raw(&iface.ip_address),
+            Span::raw("    "),
+            Span::styled("Speed: ", Style::default().fg(Color::Gray)),
+            Span::raw(&iface.speed),
+            Span::raw("    "),
+            Span::styled("MTU: ", Style::default().fg(Color::Gray)),
+            Span::styled(iface.mtu.to_string(), mtu_style),
+        ]),
+        Line::from(vec![
+            Span::styled("  RX: ", Style::default().fg(Color::Gray)),
+            Span::styled(&iface.rx_bytes, Style::default().fg(Color::Yellow)),
+            Span::raw("    "),
+            Span::styled("TX: ", Style::default().fg(Color::Gray)),
+            Span::styled(&iface.tx_bytes, Style::default().fg(Color::Yellow)),
+        ]),
+        Line::from(""),
+    ])
+}
+
+/// The interface's friendly name from `network.interface_labels` with the
+/// kernel name after it dimmed, or just the kernel name
+fn interface_name<'a>(app: &'a App, name: &'a str) -> Vec<Span<'a>> {
+    match app.interface_labels.get(name) {
+        Some(label) => vec![
+            Span::styled(label, Style::default().fg(Color::Cyan)),
+            Span::styled(format!(" {}", name), Style::default().fg(Color::DarkGray)),
+        ],
+        None => vec![Span::styled(name, Style::default().fg(Color::Cyan))],
+    }
+}
+
+/// Each virt-launcher pod's namespace, then the interfaces inside it with
+/// addresses and cumulative counters
+fn draw_vm_interfaces(f: &mut Frame, app: &App, area: Rect) {
+    let mut lines = Vec::new();
+    for ns in &app.network_info.vm_namespaces {
+        lines.push(Line::from(vec![
+            Span::styled(&ns.vm, Style::default().fg(Color::Cyan)),
+            Span::styled(format!("  pid {} {}", ns.pid, ns.netns), Style::default().fg(Color::DarkGray)),
+        ]));
+        if let Some(ref e) = ns.error {
+            lines.push(Line::from(Span::styled(
+                format!("  no link state or addresses: {}", e),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+        for iface in &ns.interfaces {
+            let (state, state_style) = match iface.is_up {
+                Some(true) => ("UP", Style::default().fg(Color::Green)),
+                Some(false) => ("DOWN", Style::default().fg(Color::Red)),
+                None => ("?", Style::default().fg(Color::DarkGray)),
+            };
+            let faults = iface.rx_errors + iface.tx_errors + iface.rx_dropped + iface.tx_dropped;
+            let mut spans = vec![
+                Span::styled(format!("  {:<10} ", iface.name), Style::default().fg(Color::Gray)),
+                Span::styled(format!("{:<5}", state), state_style),
+                Span::styled(
+                    format!("RX {:>9}  TX {:>9}", format_bytes(iface.rx_bytes), format_bytes(iface.tx_bytes)),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ];
+            if faults > 0 {
+                spans.push(Span::styled(
+                    format!(
+                        "  err {}/{} drop {}/{}",
+                        iface.rx_errors, iface.tx_errors, iface.rx_dropped, iface.tx_dropped
+                    ),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            if let Some(mtu) = iface.mtu {
+                spans.push(Span::styled(format!("  mtu {}", mtu), Style::default().fg(Color::DarkGray)));
+            }
+            if !iface.addresses.is_empty() {
+                spans.push(Span::raw(format!("  {}", iface.addresses.join(" "))));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+
+    let widget = Paragraph::new(lines)
+        .scroll((app.pane_offset(Pane::VmInterfaces) as u16, 0))
+        .block(focus_block(
+            app,
+            Pane::VmInterfaces,
+            Block::default()
+                .title(format!("VM Interfaces ({} namespaces)", app.network_info.vm_namespaces.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green)),
+        ));
+
+    f.render_widget(widget, area);
+}
+
+fn draw_sriov(f: &mut Frame, app: &App, area: Rect) {
+    let mut lines = Vec::new();
+    for nic in &app.sriov_nics {
+        let free = nic.free();
+        let free_style = if nic.vfs.is_empty() {
+            Style::default().fg(Color::DarkGray)
+        } else if free == 0 {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default().fg(Color::Green)
+        };
+        let mut heading = interface_name(app, &nic.name);
+        heading.extend([
+            Span::styled(format!(" {}  ", nic.pci_address), Style::default().fg(Color::DarkGray)),
+            Span::raw(format!("{}/{} VFs assigned  ", nic.assigned(), nic.vfs.len())),
+            Span::styled(format!("{} free", free), free_style),
+            Span::styled(format!("  (max {})", nic.total_vfs), Style::default().fg(Color::DarkGray)),
+        ]);
+        lines.push(Line::from(heading));
+        for vf in nic.vfs.iter().filter(|vf| vf.pod_uid.is_some()) {
+            let owner = vf.pod.clone().or_else(|| vf.pod_uid.as_ref().map(|uid| format!("pod {}", uid)));
+            lines.push(Line::from(vec![
+                Span::styled(format!("  vf{:<3} {} ", vf.index, vf.pci_address), Style::default().fg(Color::Gray)),
+                Span::styled(
+                    format!("{:<9}", vf.driver.as_deref().unwrap_or("-")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(owner.unwrap_or_default(), Style::default().fg(Color::Yellow)),
+            ]));
+        }
+    }
+
+    let exhausted = app.sriov_nics.iter().any(|nic| !nic.vfs.is_empty() && nic.free() == 0);
+    let widget = Paragraph::new(lines)
+        .scroll((app.pane_offset(Pane::Sriov) as u16, 0))
+        .block(focus_block(
+            app,
+            Pane::Sriov,
+            Block::default()
+                .title("SR-IOV VFs")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if exhausted { Color::Red } else { Color::Green })),
+        ));
 
     f.render_widget(widget, area);
 }
 
+/// "2 physical, 1 bridge, 14 veth", counting hidden interfaces too
+fn interface_counts(app: &App) -> String {
+    app.network_info
+        .interface_counts
+        .iter()
+        .map(|(class, count)| format!("{} {}", count, class.label()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn draw_k8s_network(f: &mut Frame, app: &App, area: Rect) {
-    let text = vec![
+    let mut cni = vec![
+        Span::styled("CNI: ", Style::default().fg(Color::Gray)),
+        Span::styled(&app.network_info.cni, Style::default().fg(Color::Green)),
+    ];
+    if let Some(mtu) = app.network_info.cni_mtu {
+        cni.extend([
+            Span::raw("    "),
+            Span::styled("MTU: ", Style::default().fg(Color::Gray)),
+            Span::raw(mtu.to_string()),
+        ]);
+    }
+    let mut text = vec![
         Line::from(vec![
             Span::styled("Pod CIDR: ", Style::default().fg(Color::Gray)),
             Span::styled(&app.network_info.pod_cidr, Style::default().fg(Color::Cyan)),
@@ -80,10 +310,7 @@ fn draw_k8s_network(f: &mut Frame, app: &App, area: Rect) {
             Span::styled("Service CIDR: ", Style::default().fg(Color::Gray)),
             Span::styled(&app.network_info.service_cidr, Style::default().fg(Color::Cyan)),
         ]),
-        Line::from(vec![
-            Span::styled("CNI: ", Style::default().fg(Color::Gray)),
-            Span::styled(&app.network_info.cni, Style::default().fg(Color::Green)),
-        ]),
+        Line::from(cni),
         Line::from(""),
         Line::from(vec![
             Span::styled("Active Connections: ", Style::default().fg(Color::Gray)),
@@ -99,14 +326,432 @@ fn draw_k8s_network(f: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(Color::Green)
             ),
         ]),
+        Line::from(vec![
+            Span::styled("Host Interfaces: ", Style::default().fg(Color::Gray)),
+            Span::raw(interface_counts(app)),
+        ]),
     ];
+    if !app.network_info.mtu_mismatches.is_empty() {
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled("MTU Mismatches:", Style::default().fg(Color::Red))));
+        for m in &app.network_info.mtu_mismatches {
+            text.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(app.interface_labels.describe(&m.interface), Style::default().fg(Color::Cyan)),
+                Span::raw(format!(" {}", m.mtu)),
+                Span::styled(format!("  {} is {}", m.against, m.expected), Style::default().fg(Color::Yellow)),
+            ]));
+        }
+    }
 
     let paragraph = Paragraph::new(text)
-        .block(
+        .scroll((app.pane_offset(Pane::K8sNetwork) as u16, 0))
+        .block(focus_block(
+            app,
+            Pane::K8sNetwork,
             Block::default()
                 .title("Kubernetes Network")
+                .borders(Borders::ALL),
+        ));
+
+    f.render_widget(paragraph, area);
+}
+
+fn draw_dns(f: &mut Frame, app: &App, area: Rect) {
+    let mut text = Vec::new();
+
+    if app.dns_status.is_empty() {
+        text.push(Line::from(Span::styled(
+            "No resolvers probed yet",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    for resolver in &app.dns_status {
+        let (state, state_color) = if resolver.consecutive_failures > 0 {
+            ("FAIL", Color::Red)
+        } else if resolver.success_rate < 100.0 {
+            ("FLAKY", Color::Yellow)
+        } else {
+            ("OK", Color::Green)
+        };
+
+        let latency = resolver
+            .latency_ms
+            .map(|ms| format!("{:.1} ms", ms))
+            .unwrap_or_else(|| "-".to_string());
+
+        text.push(Line::from(vec![
+            Span::styled(format!("{:<8}", resolver.label), Style::default().fg(Color::Gray)),
+            Span::styled(format!("{:<16}", resolver.server), Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{:<6}", state), Style::default().fg(state_color)),
+            Span::styled(format!("{:>9}", latency), Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!("  {:.0}% ok", resolver.success_rate),
+                Style::default().fg(Color::Gray),
+            ),
+        ]));
+
+        if let Some(ref error) = resolver.last_error {
+            text.push(Line::from(Span::styled(
+                format!("  {}", error),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+    }
+
+    let paragraph = Paragraph::new(text)
+        .scroll((app.pane_offset(Pane::Dns) as u16, 0))
+        .block(focus_block(
+            app,
+            Pane::Dns,
+            Block::default()
+                .title("DNS Health")
+                .borders(Borders::ALL),
+        ));
+
+    f.render_widget(paragraph, area);
+}
+
+/// Default gateways and their ping, the main routing table of both
+/// families, then routes added and removed since startup
+fn draw_routes(f: &mut Frame, app: &App, area: Rect) {
+    let mut text = Vec::new();
+    let gray = Style::default().fg(Color::Gray);
+
+    match app.route_status {
+        None => text.push(Line::from(Span::styled(
+            "Routing table not read yet",
+            Style::default().fg(Color::DarkGray),
+        ))),
+        Some(ref status) => {
+            if status.default_routes().next().is_none() {
+                let message = if status.had_default { "Default route lost" } else { "No default route" };
+                text.push(Line::from(Span::styled(message, Style::default().fg(Color::Red))));
+            }
+            for gateway in &status.gateways {
+                let (state, color) = match gateway.latency_ms {
+                    Some(ms) => (format!("OK {:.1} ms", ms), Color::Green),
+                    None if gateway.consecutive_failures > 0 => (
+                        format!("FAIL x{} {}", gateway.consecutive_failures, gateway.last_error.as_deref().unwrap_or("")),
+                        Color::Red,
+                    ),
+                    None => (gateway.last_error.clone().unwrap_or_else(|| "-".to_string()), Color::DarkGray),
+                };
+                text.push(Line::from(vec![
+                    Span::styled("gateway ", gray),
+                    Span::styled(format!("{} ", gateway.address), Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("({})  ", gateway.device), gray),
+                    Span::styled(state, Style::default().fg(color)),
+                ]));
+            }
+            if status.default_changes > 0 {
+                text.push(Line::from(Span::styled(
+                    format!("default route changed {} times recently", status.default_changes),
+                    Style::default().fg(Color::Yellow),
+                )));
+            }
+
+            for route in &status.routes {
+                let style = if route.is_default() { Style::default().fg(Color::Cyan) } else { Style::default() };
+                let mut details = Vec::new();
+                if let Some(ref protocol) = route.protocol {
+                    details.push(protocol.clone());
+                }
+                if let Some(metric) = route.metric {
+                    details.push(format!("metric {}", metric));
+                }
+                if let Some(ref source) = route.source {
+                    details.push(format!("src {}", source));
+                }
+                text.push(Line::from(vec![
+                    Span::styled(route.describe(), style),
+                    Span::styled(format!("  {}", details.join(" ")), Style::default().fg(Color::DarkGray)),
+                ]));
+            }
+
+            if !status.recent_changes.is_empty() {
+                text.push(Line::from(Span::styled("Changes", gray)));
+            }
+            for (at, change) in &status.recent_changes {
+                let color = if change.starts_with('-') { Color::Red } else { Color::Green };
+                text.push(Line::from(vec![
+                    Span::styled(format!("{} ", at.format("%H:%M:%S")), Style::default().fg(Color::DarkGray)),
+                    Span::styled(change.clone(), Style::default().fg(color)),
+                ]));
+            }
+        }
+    }
+
+    let paragraph = Paragraph::new(text)
+        .scroll((app.pane_offset(Pane::Routes) as u16, 0))
+        .block(focus_block(
+            app,
+            Pane::Routes,
+            Block::default()
+                .title("Routes")
                 .borders(Borders::ALL)
-        );
+                .border_style(Style::default().fg(Color::Green)),
+        ));
+
+    f.render_widget(paragraph, area);
+}
+
+/// One line per probed registry, then one per image seen in pull events
+fn draw_registries(f: &mut Frame, app: &App, area: Rect) {
+    let mut text = Vec::new();
+
+    for registry in &app.registry_status {
+        let (state, color) = if registry.reachable {
+            ("OK", Color::Green)
+        } else if registry.consecutive_failures > 1 {
+            ("DOWN", Color::Red)
+        } else {
+            ("FAIL", Color::Yellow)
+        };
+        let detail = match (&registry.last_error, registry.latency_ms) {
+            (Some(error), _) => error.clone(),
+            (None, Some(ms)) => format!("{:.0} ms", ms),
+            (None, None) => "-".to_string(),
+        };
+        text.push(Line::from(vec![
+            Span::styled(format!("{:<6}", state), Style::default().fg(color)),
+            Span::styled(format!("{:<28} ", registry.registry), Style::default().fg(Color::Cyan)),
+            Span::styled(detail, Style::default().fg(Color::Gray)),
+        ]));
+    }
+
+    for pull in &app.image_pulls {
+        let mut spans = Vec::new();
+        if pull.is_failing() {
+            spans.push(Span::styled(format!("{:<6}", format!("x{}", pull.failures)), Style::default().fg(Color::Red)));
+        } else {
+            let took = pull
+                .last_duration
+                .map(|d| format!("{:.1}s", d.as_secs_f64()))
+                .unwrap_or_else(|| "-".to_string());
+            spans.push(Span::styled(format!("{:<6}", took), Style::default().fg(Color::Green)));
+        }
+        spans.push(Span::styled(pull.image.clone(), Style::default().fg(Color::White)));
+        if let (true, Some(error)) = (pull.is_failing(), pull.last_error.as_ref()) {
+            spans.push(Span::styled(format!("  {}", error), Style::default().fg(Color::DarkGray)));
+        }
+        text.push(Line::from(spans));
+    }
+
+    let failing = app.image_pulls.iter().filter(|p| p.is_failing()).count();
+    let title = if failing > 0 {
+        format!("Registries & Pulls ({} failing)", failing)
+    } else {
+        "Registries & Pulls".to_string()
+    };
+    let paragraph = Paragraph::new(text)
+        .scroll((app.pane_offset(Pane::Registries) as u16, 0))
+        .block(focus_block(app, Pane::Registries, Block::default().title(title).borders(Borders::ALL)));
 
     f.render_widget(paragraph, area);
 }
+
+/// Per namespace running VMs, its NetworkPolicies, then what traffic each
+/// VM they select may still send and receive
+fn draw_network_policies(f: &mut Frame, app: &App, area: Rect) {
+    let mut lines = Vec::new();
+    let mut isolated = 0;
+    for ns in &app.vm_network_policies {
+        lines.push(Line::from(vec![
+            Span::styled(&ns.namespace, Style::default().fg(Color::Cyan)),
+            Span::raw(format!("  {}/{} VMs selected  ", ns.vms.len(), ns.vm_count)),
+            Span::styled(ns.policies.join(" "), Style::default().fg(Color::DarkGray)),
+        ]));
+        for vm in &ns.vms {
+            isolated += usize::from(vm.is_isolated() || vm.dns_blocked);
+            let vm_style = if vm.is_isolated() || vm.dns_blocked {
+                Style::default().fg(Color::Red)
+            } else if vm.ingress.is_restricted() || vm.egress.is_restricted() {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::Green)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}  ", vm.vm), vm_style),
+                Span::styled(vm.policies.join(" "), Style::default().fg(Color::DarkGray)),
+            ]));
+            for (direction, access) in [("in ", &vm.ingress), ("out", &vm.egress)] {
+                let rows: Vec<(String, Color)> = match access {
+                    PolicyAccess::Unrestricted => vec![("anything".to_string(), Color::Green)],
+                    PolicyAccess::DenyAll => vec![("nothing (deny all)".to_string(), Color::Red)],
+                    PolicyAccess::Allow(rules) => rules.iter().map(|rule| (rule.clone(), Color::Yellow)).collect(),
+                };
+                for (i, (rule, color)) in rows.into_iter().enumerate() {
+                    let label = if i == 0 { direction } else { "   " };
+                    let mut spans = vec![
+                        Span::styled(format!("    {} ", label), Style::default().fg(Color::Gray)),
+                        Span::styled(rule, Style::default().fg(color)),
+                    ];
+                    if i == 0 && direction == "out" && vm.dns_blocked {
+                        spans.push(Span::styled("  DNS blocked", Style::default().fg(Color::Red)));
+                    }
+                    lines.push(Line::from(spans));
+                }
+            }
+        }
+    }
+
+    let title = if isolated > 0 {
+        format!("Network Policies ({} VMs cut off)", isolated)
+    } else {
+        "Network Policies".to_string()
+    };
+    let widget = Paragraph::new(lines)
+        .scroll((app.pane_offset(Pane::NetworkPolicies) as u16, 0))
+        .block(focus_block(
+            app,
+            Pane::NetworkPolicies,
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if isolated > 0 { Color::Red } else { Color::Green })),
+        ));
+
+    f.render_widget(widget, area);
+}
+
+/// Pass/fail and connect latency per VIP of the watched Services
+fn draw_vips(f: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .vip_status
+        .iter()
+        .map(|vip| {
+            let (state, color) = if vip.reachable {
+                ("PASS", Color::Green)
+            } else if vip.consecutive_failures > 1 {
+                ("FAIL", Color::Red)
+            } else {
+                ("FAIL", Color::Yellow)
+            };
+            let detail = match (&vip.last_error, vip.latency_ms) {
+                (Some(error), _) => error.clone(),
+                (None, Some(ms)) => format!("{:.0} ms", ms),
+                (None, None) => "-".to_string(),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<6}", state), Style::default().fg(color)),
+                Span::styled(format!("{:<24} ", vip.service()), Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:<22} ", vip.address), Style::default().fg(Color::Gray)),
+                Span::styled(detail, Style::default().fg(Color::Gray)),
+            ]))
+        })
+        .collect();
+
+    let failing = app.vip_status.iter().filter(|vip| !vip.reachable).count();
+    let title = if failing > 0 {
+        format!("Service VIPs ({} failing)", failing)
+    } else {
+        "Service VIPs".to_string()
+    };
+    f.render_widget(List::new(items).block(Block::default().title(title).borders(Borders::ALL)), area);
+}
+
+fn draw_endpoints(f: &mut Frame, app: &App, area: Rect) {
+    // Services without endpoints sort first so they stay visible
+    let items: Vec<ListItem> = app
+        .service_health
+        .endpoints
+        .iter()
+        .skip(app.pane_offset(Pane::Endpoints))
+        .map(|svc| {
+            let ready_color = if svc.ready == 0 {
+                Color::Red
+            } else if svc.not_ready > 0 {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<40}", format!("{}/{}", svc.namespace, svc.name)),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(format!("{:>3} ready", svc.ready), Style::default().fg(ready_color)),
+                Span::styled(
+                    if svc.not_ready > 0 {
+                        format!("  {} not ready", svc.not_ready)
+                    } else {
+                        String::new()
+                    },
+                    Style::default().fg(Color::Gray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let empty = app.service_health.endpoints.iter().filter(|s| s.ready == 0).count();
+    let title = if empty > 0 {
+        format!("Service Endpoints ({} without endpoints)", empty)
+    } else {
+        "Service Endpoints".to_string()
+    };
+
+    let widget = List::new(items).block(focus_block(
+        app,
+        Pane::Endpoints,
+        Block::default().title(title).borders(Borders::ALL),
+    ));
+    f.render_widget(widget, area);
+}
+
+fn draw_ingress(f: &mut Frame, app: &App, area: Rect) {
+    let now = chrono::Utc::now();
+
+    let items: Vec<ListItem> = app
+        .service_health
+        .routes
+        .iter()
+        .skip(app.pane_offset(Pane::Ingress))
+        .map(|route| {
+            let (cert, cert_color) = match route.cert_expiry {
+                Some(expiry) => {
+                    let days = (expiry - now).num_days();
+                    let color = if days < 7 {
+                        Color::Red
+                    } else if days < 30 {
+                        Color::Yellow
+                    } else {
+                        Color::Green
+                    };
+                    let text = if expiry < now {
+                        "cert expired".to_string()
+                    } else {
+                        format!("cert {}d", days)
+                    };
+                    (text, color)
+                }
+                None => ("no tls".to_string(), Color::DarkGray),
+            };
+
+            let hosts = if route.hosts.is_empty() {
+                "*".to_string()
+            } else {
+                route.hosts.join(", ")
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<10}", route.kind), Style::default().fg(Color::Gray)),
+                Span::styled(format!("{:<32}", hosts), Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:<14}", cert), Style::default().fg(cert_color)),
+                Span::styled(
+                    format!("{}/{}", route.namespace, route.name),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let widget = List::new(items).block(focus_block(
+        app,
+        Pane::Ingress,
+        Block::default().title("Ingress / Routes").borders(Borders::ALL),
+    ));
+    f.render_widget(widget, area);
+}
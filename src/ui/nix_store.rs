@@ -0,0 +1,138 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::units::format_bytes;
+use super::glyphs::Glyphs;
+use crate::app::App;
+use crate::nix_store::NixStorePanel;
+
+/// System generations with their closure sizes, how full the store's
+/// filesystem is, and the garbage collection or deletion running
+pub fn draw_nix_store(f: &mut Frame, app: &App, panel: &NixStorePanel, area: Rect, glyphs: &Glyphs) {
+    let popup_area = centered_rect(75, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let hints = if app.read_only {
+        " j/k: Select  Esc: Close "
+    } else if panel.is_running() {
+        " Esc: Hide (keeps running) "
+    } else {
+        " g: Collect garbage  d: Delete generation  Esc: Close "
+    };
+    let block = Block::default()
+        .title(" Nix Store Generations ")
+        .title_bottom(hints)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if panel.confirm.is_some() { Color::Yellow } else { Color::Cyan }));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(if panel.run.is_some() {
+            vec![Constraint::Min(4), Constraint::Length(8)]
+        } else {
+            vec![Constraint::Min(4)]
+        })
+        .split(inner);
+
+    let mut lines = Vec::new();
+    // The filesystem holding the store: the mount point that is the longest
+    // prefix of /nix/store
+    if let Some(fs) = app
+        .system_metrics
+        .filesystems
+        .iter()
+        .filter(|fs| "/nix/store".starts_with(fs.mount_point.as_str()))
+        .max_by_key(|fs| fs.mount_point.len())
+    {
+        let color = if fs.used_percent() >= app.warning_thresholds.disk { Color::Red } else { Color::Green };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  /nix on {}: ", fs.mount_point), Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!(
+                    "{:.0}% used, {} free",
+                    fs.used_percent(),
+                    format_bytes(fs.available_bytes)
+                ),
+                Style::default().fg(color),
+            ),
+        ]));
+    }
+    if let Some(ref error) = panel.error {
+        lines.push(Line::from(Span::styled(format!("  {}", error), Style::default().fg(Color::Yellow))));
+    }
+    match panel.confirm {
+        Some(op) => lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {}? ", op.describe(&app.upgrade_config)),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("y: Confirm   any other key: Cancel", Style::default().fg(Color::DarkGray)),
+        ])),
+        None => lines.push(Line::from("")),
+    }
+
+    // Keep the selection in view below the header
+    let height = (chunks[0].height as usize).saturating_sub(lines.len());
+    let skip = (panel.selected + 1).saturating_sub(height);
+    for (i, generation) in panel.generations.iter().enumerate().skip(skip) {
+        let mut tags = Vec::new();
+        if generation.current {
+            tags.push("current");
+        }
+        if generation.booted {
+            tags.push("booted");
+        }
+        if generation.default && !generation.current {
+            tags.push("default");
+        }
+        let selected = i == panel.selected;
+        let style = if selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else if generation.in_use() {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {:>5}  {:<16}  {:>9}  {}",
+                generation.number,
+                generation.created.map_or("-".to_string(), |at| at.format("%Y-%m-%d %H:%M").to_string()),
+                generation.closure_bytes.map_or("-".to_string(), format_bytes),
+                tags.join(" ")
+            ),
+            style,
+        )));
+    }
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let (Some(run), Some(&area)) = (panel.run.as_ref(), chunks.get(1)) else {
+        return;
+    };
+    let status = match &run.outcome {
+        None => Span::styled("Running...", Style::default().fg(Color::Yellow)),
+        Some(Ok(summary)) => Span::styled(format!("{} {}", glyphs.check, summary), Style::default().fg(Color::Green)),
+        Some(Err(reason)) => Span::styled(format!("{} {}", glyphs.error, reason), Style::default().fg(Color::Red)),
+    };
+    let mut output = vec![
+        Line::from(Span::styled(format!("$ {}", run.command), Style::default().fg(Color::DarkGray))),
+        Line::from(status),
+    ];
+    // Follow the end of the output, below the separator
+    let tail = (area.height as usize).saturating_sub(output.len() + 1);
+    let start = run.output.len().saturating_sub(tail);
+    output.extend(
+        run.output[start..]
+            .iter()
+            .map(|line| Line::from(Span::styled(line.clone(), Style::default().fg(Color::Gray)))),
+    );
+    f.render_widget(Paragraph::new(output).block(Block::default().borders(Borders::TOP)), area);
+}
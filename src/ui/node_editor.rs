@@ -0,0 +1,112 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::app::{NodeEditField, NodeEditor};
+
+/// Draw a node's labels and taints, with the prompt for adding one
+pub fn draw_node_editor(f: &mut Frame, editor: &NodeEditor, area: Rect, glyphs: &Glyphs, read_only: bool) {
+    let popup_area = centered_rect(75, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" Node {} ", editor.node))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Labels and taints
+            Constraint::Length(3),  // Prompt/status + help
+        ])
+        .split(inner);
+
+    let heading = |text: &'static str| {
+        Line::from(Span::styled(text, Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD)))
+    };
+    let row = |i: usize, text: String, color: Color| {
+        let selected = i == editor.selected;
+        let marker = if selected { glyphs.selected } else { " " };
+        let style = if selected {
+            Style::default().fg(color).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        };
+        Line::from(Span::styled(format!("{} {}", marker, text), style))
+    };
+
+    let metadata = &editor.metadata;
+    let mut lines = vec![heading(" Labels")];
+    lines.extend(
+        metadata
+            .labels
+            .iter()
+            .enumerate()
+            .map(|(i, (key, value))| row(i, format!("{}={}", key, value), Color::White)),
+    );
+    lines.push(Line::from(""));
+    lines.push(heading(" Taints"));
+    if metadata.taints.is_empty() {
+        lines.push(Line::from(Span::styled("   none", Style::default().fg(Color::DarkGray))));
+    }
+    lines.extend(
+        metadata
+            .taints
+            .iter()
+            .enumerate()
+            .map(|(i, taint)| row(metadata.labels.len() + i, taint.to_string(), Color::Yellow)),
+    );
+
+    // Keep the selected row in view
+    let selected_line = if editor.selected < metadata.labels.len() {
+        editor.selected + 1
+    } else {
+        editor.selected + 4
+    };
+    let height = chunks[0].height as usize;
+    let scroll = selected_line.saturating_sub(height.saturating_sub(1));
+    f.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), chunks[0]);
+
+    let (status, help) = match editor.input {
+        Some((field, ref text)) => {
+            let label = match field {
+                NodeEditField::Label => "Add label (key=value): ",
+                NodeEditField::Taint => "Add taint (key[=value]:NoSchedule|PreferNoSchedule|NoExecute): ",
+            };
+            (
+                Line::from(vec![
+                    Span::styled(label, Style::default().fg(Color::Gray)),
+                    Span::styled(format!("{}_", text), Style::default().fg(Color::Yellow)),
+                ]),
+                " Enter: Apply  Esc: Cancel ".to_string(),
+            )
+        }
+        None => (
+            Line::from(Span::styled(
+                editor.status.clone().unwrap_or_default(),
+                Style::default().fg(Color::Yellow),
+            )),
+            if read_only {
+                format!(" {}: Select  Esc: Close ", glyphs.up_down)
+            } else {
+                format!(" {}: Select  l: Add label  t: Add taint  d: Remove  Esc: Close ", glyphs.up_down)
+            },
+        ),
+    };
+    let footer = Paragraph::new(vec![
+        status,
+        Line::from(Span::styled(help, Style::default().fg(Color::DarkGray))),
+    ])
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+    f.render_widget(footer, chunks[1]);
+}
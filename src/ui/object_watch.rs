@@ -0,0 +1,121 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::object_watch::{ChangeKind, ObjectWatch};
+use crate::timestamps::format_time;
+
+/// Values longer than this are cut short in the diff
+const MAX_VALUE_CHARS: usize = 60;
+
+/// Every update of the watched object, newest first, with the fields it
+/// added, removed or changed
+pub fn draw_object_watch(f: &mut Frame, watch: &ObjectWatch, area: Rect, glyphs: &Glyphs) {
+    let popup_area = centered_rect(85, 80, area);
+    f.render_widget(Clear, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Updates
+            Constraint::Length(2),  // Status + help text
+        ])
+        .split(popup_area);
+
+    let title = match watch.resource.namespace {
+        Some(ref ns) => format!(" Watching {} {}/{} ", watch.resource.kind.title(), ns, watch.resource.name),
+        None => format!(" Watching {} {} ", watch.resource.kind.title(), watch.resource.name),
+    };
+
+    let mut lines = Vec::new();
+    if watch.updates.is_empty() {
+        lines.push(Line::from(Span::styled("Waiting for the object...", Style::default().fg(Color::DarkGray))));
+    }
+    for update in &watch.updates {
+        let event_color = match update.event.as_str() {
+            "DELETED" => Color::Red,
+            "ADDED" => Color::Green,
+            _ => Color::Cyan,
+        };
+        let mut header = vec![
+            Span::styled(format_time(update.at, "%H:%M:%S"), Style::default().fg(Color::Gray)),
+            Span::raw("  "),
+            Span::styled(update.event.clone(), Style::default().fg(event_color).add_modifier(Modifier::BOLD)),
+        ];
+        if let Some(ref version) = update.resource_version {
+            header.push(Span::styled(format!("  rv {}", version), Style::default().fg(Color::DarkGray)));
+        }
+        if let Some(ref manager) = update.manager {
+            header.push(Span::raw("  by "));
+            header.push(Span::styled(manager.clone(), Style::default().fg(Color::Yellow)));
+        }
+        lines.push(Line::from(header));
+
+        if update.changes.is_empty() && update.event == "ADDED" {
+            lines.push(Line::from(Span::styled("  watching from here", Style::default().fg(Color::DarkGray))));
+        }
+        for change in &update.changes {
+            let value = |value: &Option<String>| truncate(value.as_deref().unwrap_or_default());
+            lines.push(Line::from(match change.kind {
+                ChangeKind::Added => vec![
+                    Span::styled("  + ", Style::default().fg(Color::Green)),
+                    Span::raw(format!("{}: ", change.path)),
+                    Span::styled(value(&change.new), Style::default().fg(Color::Green)),
+                ],
+                ChangeKind::Removed => vec![
+                    Span::styled("  - ", Style::default().fg(Color::Red)),
+                    Span::raw(format!("{}: ", change.path)),
+                    Span::styled(value(&change.old), Style::default().fg(Color::Red)),
+                ],
+                ChangeKind::Changed => vec![
+                    Span::styled("  ~ ", Style::default().fg(Color::Yellow)),
+                    Span::raw(format!("{}: ", change.path)),
+                    Span::styled(value(&change.old), Style::default().fg(Color::DarkGray)),
+                    Span::raw(" -> "),
+                    Span::styled(value(&change.new), Style::default().fg(Color::Yellow)),
+                ],
+            }));
+        }
+        lines.push(Line::from(""));
+    }
+
+    let scroll = watch.scroll.min(lines.len().saturating_sub(1));
+    let body = Paragraph::new(lines).scroll((scroll as u16, 0)).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(body, chunks[0]);
+
+    let mut footer = Vec::new();
+    if let Some(ref error) = watch.error {
+        footer.push(Line::from(Span::styled(
+            format!("{} (retrying)", error.lines().next().unwrap_or_default()),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    footer.push(Line::from(Span::styled(
+        format!(" {}/PgUp/PgDn: Scroll  g: Newest  Esc: Stop watching ", glyphs.up_down),
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let help = Paragraph::new(footer)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT));
+    f.render_widget(help, chunks[1]);
+}
+
+fn truncate(value: &str) -> String {
+    if value.chars().count() <= MAX_VALUE_CHARS {
+        return value.to_string();
+    }
+    let cut: String = value.chars().take(MAX_VALUE_CHARS).collect();
+    format!("{}...", cut)
+}
@@ -0,0 +1,84 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::palette::CommandPalette;
+
+/// Query line above the fuzzy-filtered action list
+pub fn draw_palette(f: &mut Frame, palette: &CommandPalette, area: Rect) {
+    let popup_area = centered_rect(60, 50, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(" Commands | Enter: Run  Esc: Close ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    let query = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Cyan)),
+        Span::raw(palette.query.as_str()),
+        Span::styled("_", Style::default().fg(Color::DarkGray)),
+    ]));
+    f.render_widget(query, chunks[0]);
+
+    let matches = palette.matches();
+    if matches.is_empty() {
+        let text = Paragraph::new("  No matching commands").style(Style::default().fg(Color::DarkGray));
+        f.render_widget(text, chunks[1]);
+        return;
+    }
+
+    let items: Vec<ListItem> = matches
+        .iter()
+        .map(|(entry, positions)| {
+            let spans: Vec<Span> = entry
+                .label
+                .chars()
+                .enumerate()
+                .map(|(i, c)| {
+                    if positions.contains(&i) {
+                        Span::styled(c.to_string(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+                    } else {
+                        Span::raw(c.to_string())
+                    }
+                })
+                .collect();
+            ListItem::new(Line::from([vec![Span::raw("  ")], spans].concat()))
+        })
+        .collect();
+
+    let list = List::new(items).highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+    let mut state = ListState::default().with_selected(Some(palette.selected));
+    f.render_stateful_widget(list, chunks[1], &mut state);
+}
+
+/// Name for the view about to be saved
+pub fn draw_view_name_prompt(f: &mut Frame, name: &str, area: Rect) {
+    let popup_area = centered_rect(50, 20, area);
+    f.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::from(""),
+        Line::from("  Save the screen, filters, sort orders and followed"),
+        Line::from("  log services as a view named:"),
+        Line::from(Span::styled(format!("  {}_", name), Style::default().fg(Color::Yellow))),
+    ];
+    let block = Block::default()
+        .title(" Save View ")
+        .title_bottom(" Enter: Save  Esc: Cancel ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
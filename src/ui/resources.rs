@@ -0,0 +1,151 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, ResourceGroup, ResourceInput};
+use crate::types::{K8sResource, ResourceKind};
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // Kind tabs
+            Constraint::Min(0),     // Object list
+        ])
+        .split(area);
+
+    draw_kind_tabs(f, app, chunks[0]);
+    draw_resource_list(f, app, chunks[1]);
+}
+
+fn draw_kind_tabs(f: &mut Frame, app: &App, area: Rect) {
+    let mut spans = Vec::new();
+    for kind in ResourceKind::ALL {
+        let style = if kind == app.resource_kind {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        spans.push(Span::styled(format!(" {} ", kind.title()), style));
+        spans.push(Span::raw(" "));
+    }
+    let mut help = String::from(" Tab: Next kind  /: Label selector  l: Group by label  y: Inspect YAML  Enter: VM details ");
+    if app.resource_kind == ResourceKind::Node {
+        help.push_str(" t: Labels/taints ");
+    }
+    if app.resource_kind == ResourceKind::VirtualMachine {
+        help.push_str(" i: Cloud-init ");
+    }
+    if !app.read_only {
+        help.push_str(" c: CPU tuning ");
+        if app.resource_kind == ResourceKind::VirtualMachine {
+            help.push_str(" C: Clone  Space: Mark  *: All  b: Batch ");
+        }
+    }
+    spans.push(Span::styled(help, Style::default().fg(Color::DarkGray)));
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn status_color(status: &str) -> Color {
+    match status {
+        "Ready" | "Running" => Color::Green,
+        "Pending" | "Migrating" | "Starting" | "Provisioning" => Color::Yellow,
+        "NotReady" | "Failed" | "CrashLoopBackOff" | "ErrorUnschedulable" => Color::Red,
+        _ => Color::Gray,
+    }
+}
+
+fn draw_resource_list(f: &mut Frame, app: &App, area: Rect) {
+    let groups = app.resource_groups();
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut selected_row = None;
+    for (i, resource) in app.resources.iter().enumerate() {
+        if let Some(group) = groups.iter().find(|group| group.start == i) {
+            items.push(group_header(app, group));
+        }
+        if i == app.resource_selected_index {
+            selected_row = Some(items.len());
+        }
+        items.push(resource_item(app, resource, i == app.resource_selected_index));
+    }
+
+    let mut title = if app.resources.len() == app.resource_total() {
+        format!("{} [{}]", app.resource_kind.title(), app.resources.len())
+    } else {
+        format!("{} [{}/{}]", app.resource_kind.title(), app.resources.len(), app.resource_total())
+    };
+    match app.resource_input {
+        Some(ResourceInput::Selector) => title.push_str(&format!(" | Selector: {}_", app.resource_selector)),
+        _ if !app.resource_selector.is_empty() => title.push_str(&format!(" | Selector: {}", app.resource_selector)),
+        _ => {}
+    }
+    if let Some(ref error) = app.resource_selector_error {
+        title.push_str(&format!(" ({})", error));
+    }
+    match app.resource_input {
+        Some(ResourceInput::GroupBy) => title.push_str(&format!(" | Group by: {}_", app.resource_group_by)),
+        _ if !app.resource_group_by.is_empty() => title.push_str(&format!(" | Group by: {}", app.resource_group_by)),
+        _ => {}
+    }
+    if !app.vm_marked.is_empty() {
+        title.push_str(&format!(" | {} marked", app.vm_marked.len()));
+    }
+    let widget = List::new(items).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green)),
+    );
+
+    let mut state = ListState::default().with_selected(selected_row);
+    f.render_stateful_widget(widget, area, &mut state);
+}
+
+/// "tenant=acme  12: 10 Running, 2 Stopped"
+fn group_header<'a>(app: &App, group: &ResourceGroup) -> ListItem<'a> {
+    let name = match group.value {
+        Some(ref value) => format!("{}={}", app.resource_group_by, value),
+        None => format!("no {} label", app.resource_group_by),
+    };
+    let mut spans = vec![
+        Span::styled(name, Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("  {}: ", group.len), Style::default().fg(Color::Gray)),
+    ];
+    for (i, (status, count)) in group.statuses.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(", ", Style::default().fg(Color::Gray)));
+        }
+        spans.push(Span::styled(format!("{} {}", count, status), Style::default().fg(status_color(status))));
+    }
+    ListItem::new(Line::from(spans))
+}
+
+fn resource_item<'a>(app: &App, resource: &'a K8sResource, selected: bool) -> ListItem<'a> {
+    let style = if selected {
+        Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+
+    let mut spans = Vec::new();
+    if !app.resource_group_by.is_empty() {
+        spans.push(Span::raw("  "));
+    }
+    if app.resource_kind == ResourceKind::VirtualMachine && !app.read_only {
+        let (mark, color) = if app.is_vm_marked(resource) { ("[x] ", Color::Yellow) } else { ("[ ] ", Color::DarkGray) };
+        spans.push(Span::styled(mark, Style::default().fg(color)));
+    }
+    if let Some(ref ns) = resource.namespace {
+        spans.push(Span::styled(format!("{}/", ns), Style::default().fg(Color::DarkGray)));
+    }
+    spans.push(Span::styled(&resource.name, Style::default().fg(Color::Cyan)));
+    spans.push(Span::raw("  "));
+    spans.push(Span::styled(&resource.status, Style::default().fg(status_color(&resource.status))));
+
+    ListItem::new(Line::from(spans)).style(style)
+}
@@ -0,0 +1,55 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::selftest::{SelfTestRun, StepState};
+
+/// Each step of the smoke test with its outcome, in the order they run
+pub fn draw_selftest_run(f: &mut Frame, run: &SelfTestRun, area: Rect, glyphs: &Glyphs) {
+    let popup_area = centered_rect(70, 50, area);
+    f.render_widget(Clear, popup_area);
+
+    let finished = run.is_finished();
+    let color = if run.failed() > 0 {
+        Color::Red
+    } else if finished {
+        Color::Green
+    } else {
+        Color::Cyan
+    };
+    let block = Block::default()
+        .title(format!(" Self-test: {}/{} ", run.namespace, run.name))
+        .title_bottom(if finished { " Enter/Esc: Close " } else { " Esc: Hide (keeps running) " })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color));
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("{} ({}s)", run.summary(), run.elapsed().as_secs()),
+            Style::default().fg(color),
+        )),
+        Line::from(""),
+    ];
+    for (step, state) in &run.steps {
+        let (symbol, color, detail) = match state {
+            StepState::Pending => ("-", Color::DarkGray, String::new()),
+            StepState::Running => ("..", Color::Yellow, "running".to_string()),
+            StepState::Passed(detail) => (glyphs.check, Color::Green, detail.clone()),
+            StepState::Failed(reason) => (glyphs.error, Color::Red, reason.clone()),
+            StepState::Skipped => ("-", Color::DarkGray, "skipped".to_string()),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:>2} ", symbol), Style::default().fg(color)),
+            Span::styled(format!("{:<22}", step.label()), Style::default().fg(Color::Cyan)),
+            Span::styled(detail, Style::default().fg(Color::Gray)),
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), popup_area);
+}
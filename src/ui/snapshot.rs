@@ -0,0 +1,242 @@
+//! Screens rendered to text through ratatui's `TestBackend` and compared
+//! against golden files in `src/ui/snapshots/`. A new screen gets its
+//! snapshot by building an app with `fixture_app`, putting it on the
+//! screen and calling `assert_snapshot` with the output of `render`; run
+//! `UPDATE_SNAPSHOTS=1 cargo test` to write new or changed golden files,
+//! then review them in the diff.
+
+use ratatui::{backend::TestBackend, Terminal};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Once};
+
+use crate::alerts::{Alert, AlertCategory, AlertLevel};
+use crate::app::{App, Screen};
+use crate::cli::CliArgs;
+use crate::collectors::KubernetesCollector;
+use crate::config::Config;
+use crate::i18n::{set_locale, Locale};
+use crate::types::{ClusterFlavor, FilesystemUsage, InterfaceClass, LogEntry, MtuMismatch, NetworkInfo, NetworkInterface, SensorReading, SystemMetrics};
+use crate::ui_state::UiState;
+use crate::validation::ConfigReport;
+
+/// Terminal sizes every screen is snapshotted at: the smallest console
+/// the layout is meant for and a common larger one
+pub const SIZES: [(u16, u16); 2] = [(80, 24), (120, 40)];
+
+/// Set to write golden files instead of comparing against them
+const UPDATE_ENV: &str = "UPDATE_SNAPSHOTS";
+
+/// An app on the default config with no cluster, its state files in a
+/// scratch directory, and fixed host metrics, logs, interfaces and
+/// alerts, so nothing on screen depends on the machine running the test
+pub async fn fixture_app() -> App {
+    static ISOLATE: Once = Once::new();
+    ISOLATE.call_once(|| {
+        let state = std::env::temp_dir().join(format!("hypervisor-tui-snapshots-{}", std::process::id()));
+        std::env::set_var("XDG_STATE_HOME", state);
+        set_locale(Locale::En);
+    });
+
+    let mut config = Config::default();
+    config.kubernetes.flavor = Some(ClusterFlavor::K3s);
+    config.display.remember_state = false;
+    let mut app = App::with_config(
+        &CliArgs::default(),
+        config,
+        ConfigReport::default(),
+        KubernetesCollector::new(),
+        UiState::default(),
+    )
+    .await
+    .expect("fixture app");
+
+    // The banner cycles with the clock
+    app.banner_cycle_secs = 0;
+    app.local_node = Some("hv-test-01".to_string());
+    app.system_metrics = SystemMetrics {
+        cpu_usage: 42.5,
+        memory_used_gb: 96.0,
+        memory_total_gb: 256.0,
+        disk_read_mb_s: 12.4,
+        disk_write_mb_s: 3.1,
+        disk_usage_percent: 61.0,
+        load_avg: 7.25,
+        uptime_seconds: 1_324_800,
+        temperatures: vec![SensorReading { label: "Package id 0".to_string(), celsius: 58.0, critical: Some(100.0) }],
+        filesystems: vec![FilesystemUsage {
+            mount_point: "/".to_string(),
+            device: "/dev/nvme0n1p2".to_string(),
+            total_bytes: 1_000_000_000_000,
+            available_bytes: 390_000_000_000,
+        }],
+    };
+    app.logs = [
+        ("2026-10-17 09:14:02", "INFO", "k3s", "Starting k3s v1.29.4+k3s1"),
+        ("2026-10-17 09:14:05", "WARN", "containerd", "failed to reserve sandbox name, retrying"),
+        ("2026-10-17 09:14:09", "ERROR", "virt-handler", "failed to sync VMI default/web1: domain not found"),
+        ("2026-10-17 09:14:11", "INFO", "kubelet", "Successfully pulled image quay.io/kubevirt/virt-launcher"),
+    ]
+    .into_iter()
+    .map(|(timestamp, level, service, message)| {
+        Arc::new(LogEntry {
+            timestamp: timestamp.to_string(),
+            level: level.to_string(),
+            service: service.to_string(),
+            message: message.to_string(),
+            repeats: 0,
+            last_timestamp: None,
+            component: None,
+            fields: Vec::new(),
+            pid: None,
+            cursor: None,
+        })
+    })
+    .collect();
+    app.apply_log_filters();
+    app.network_info = NetworkInfo {
+        interfaces: vec![
+            interface("enp65s0f0", InterfaceClass::Physical, "10.0.0.11/24", "25000Mb/s", 9000),
+            interface("br0", InterfaceClass::Bridge, "192.168.10.1/24", "-", 1500),
+        ],
+        pod_cidr: "10.42.0.0/16".to_string(),
+        service_cidr: "10.43.0.0/16".to_string(),
+        cni: "flannel".to_string(),
+        active_connections: 128,
+        k8s_services: 14,
+        vm_namespaces: Vec::new(),
+        interface_counts: vec![(InterfaceClass::Physical, 1), (InterfaceClass::Bridge, 1)],
+        hidden_interfaces: 0,
+        cni_mtu: Some(1450),
+        mtu_mismatches: vec![MtuMismatch {
+            interface: "vnet3".to_string(),
+            mtu: 9000,
+            against: "bridge br0".to_string(),
+            expected: 1500,
+        }],
+    };
+    for (level, title) in [
+        (AlertLevel::Warning, "High Memory Usage"),
+        (AlertLevel::Critical, "Disk Almost Full"),
+    ] {
+        app.alert_manager.add_alert(Alert::new(
+            level,
+            AlertCategory::System,
+            title.to_string(),
+            format!("{} on hv-test-01", title),
+            title.to_lowercase().replace(' ', "-"),
+        ));
+    }
+    app
+}
+
+fn interface(name: &str, class: InterfaceClass, ip_address: &str, speed: &str, mtu: u32) -> NetworkInterface {
+    NetworkInterface {
+        name: name.to_string(),
+        class,
+        ip_address: ip_address.to_string(),
+        is_up: true,
+        speed: speed.to_string(),
+        rx_bytes: "1.2 TB".to_string(),
+        tx_bytes: "845.3 GB".to_string(),
+        mtu,
+    }
+}
+
+/// The whole UI as drawn for `app` at `width`x`height`: one line per row,
+/// trailing spaces trimmed, colors and styles left out
+pub fn render(app: &mut App, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("test terminal");
+    app.refresh_display_text();
+    terminal.draw(|f| super::draw(f, app)).expect("draw");
+
+    let buffer = terminal.backend().buffer();
+    let mut text = String::new();
+    for y in 0..buffer.area.height {
+        let row: String = (0..buffer.area.width).map(|x| buffer.get(x, y).symbol()).collect();
+        text.push_str(row.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Compare `actual` with `src/ui/snapshots/<name>.txt`, or write it there
+/// when `UPDATE_SNAPSHOTS` is set
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/ui/snapshots").join(format!("{}.txt", name));
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        fs::create_dir_all(path.parent().unwrap()).expect("snapshot directory");
+        fs::write(&path, actual).expect("write snapshot");
+        return;
+    }
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("no snapshot {}; run with {}=1 to create it", path.display(), UPDATE_ENV));
+    if expected != actual {
+        panic!(
+            "{} differs from {}; run with {}=1 to accept the change\n--- expected\n{}--- actual\n{}",
+            name,
+            path.display(),
+            UPDATE_ENV,
+            expected,
+            actual
+        );
+    }
+}
+
+mod tests {
+    use super::*;
+
+    /// Render `screen` at every size in `SIZES` against `<name>-<w>x<h>`
+    async fn check_screen(name: &str, screen: Screen, alert_panel: bool) {
+        let mut app = fixture_app().await;
+        app.current_screen = screen;
+        app.alert_panel_open = alert_panel;
+        for (width, height) in SIZES {
+            let text = render(&mut app, width, height);
+            assert_snapshot(&format!("{}-{}x{}", name, width, height), &text);
+        }
+    }
+
+    #[tokio::test]
+    async fn dashboard() {
+        check_screen("dashboard", Screen::Dashboard, false).await;
+    }
+
+    #[tokio::test]
+    async fn logs() {
+        check_screen("logs", Screen::Logs, false).await;
+    }
+
+    #[tokio::test]
+    async fn logs_wrapped() {
+        let mut app = fixture_app().await;
+        app.current_screen = Screen::Logs;
+        app.logs_wrapped = true;
+        for (width, height) in SIZES {
+            let text = render(&mut app, width, height);
+            assert_snapshot(&format!("logs-wrapped-{}x{}", width, height), &text);
+        }
+    }
+
+    #[tokio::test]
+    async fn network() {
+        check_screen("network", Screen::Network, false).await;
+    }
+
+    #[tokio::test]
+    async fn alerts() {
+        check_screen("alerts", Screen::Dashboard, true).await;
+    }
+
+    #[tokio::test]
+    async fn ascii() {
+        let mut app = fixture_app().await;
+        app.accessible = true;
+        app.alert_panel_open = true;
+        for (width, height) in SIZES {
+            let text = render(&mut app, width, height);
+            assert!(text.is_ascii(), "non-ASCII cell in accessible mode:\n{}", text);
+            assert_snapshot(&format!("ascii-{}x{}", width, height), &text);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+
+use super::glyphs::Glyphs;
+use crate::app::App;
+use crate::panes::Pane;
+use crate::timeline::TimelineSource;
+use crate::timestamps::format_time;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let glyphs = Glyphs::for_mode(app.accessible);
+    let events = app.timeline_events();
+
+    let items: Vec<ListItem> = events
+        .iter()
+        .skip(app.pane_offset(Pane::Timeline))
+        .map(|event| {
+            let source_color = match event.source {
+                TimelineSource::Alert => Color::Magenta,
+                TimelineSource::Log => Color::Cyan,
+                TimelineSource::Metric => Color::Blue,
+            };
+            let level_color = app.alert_styles.level_color(event.level);
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format_time(event.at, "%b %d %H:%M:%S"),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:<2}", glyphs.styled_level_icon(&app.alert_styles, event.level)),
+                    Style::default().fg(level_color),
+                ),
+                Span::raw(" "),
+                Span::styled(format!("{:<6}", event.source.as_str()), Style::default().fg(source_color)),
+                Span::raw(" "),
+                Span::styled(event.summary.clone(), Style::default().fg(level_color)),
+            ]))
+        })
+        .collect();
+
+    let title = format!("Incident Timeline [{} events] | alerts, error logs, threshold crossings", events.len());
+    let list = List::new(items).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green)),
+    );
+
+    f.render_widget(list, area);
+}
@@ -0,0 +1,49 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::collections::VecDeque;
+
+use super::glyphs::Glyphs;
+use crate::app::{Message, MessageLevel};
+
+const TOAST_WIDTH: u16 = 60;
+
+/// Stack status messages in the bottom-right corner, newest at the bottom
+pub fn draw_toasts(f: &mut Frame, messages: &VecDeque<Message>, area: Rect, glyphs: &Glyphs) {
+    let width = TOAST_WIDTH.min(area.width);
+    let text_width = width.saturating_sub(4).max(1) as usize;
+    // Leave the footer row visible
+    let mut bottom = area.bottom().saturating_sub(1);
+
+    for message in messages.iter().rev() {
+        let (icon, color) = match message.level {
+            MessageLevel::Info => (glyphs.info, Color::Cyan),
+            MessageLevel::Warning => (glyphs.warning, Color::Yellow),
+            MessageLevel::Error => (glyphs.error, Color::Red),
+        };
+
+        // Icon plus a space, then wrapped text, inside a border
+        let chars = message.text.chars().count() + icon.chars().count() + 1;
+        let height = (chars.div_ceil(text_width) as u16).clamp(1, 4) + 2;
+        if bottom < area.y + height {
+            break;
+        }
+        let toast_area = Rect::new(area.right() - width, bottom - height, width, height);
+        bottom -= height;
+
+        let line = Line::from(vec![
+            Span::styled(format!("{} ", icon), Style::default().fg(color)),
+            Span::raw(message.text.as_str()),
+        ]);
+        let toast = Paragraph::new(line)
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(color)));
+
+        f.render_widget(Clear, toast_area);
+        f.render_widget(toast, toast_area);
+    }
+}
@@ -0,0 +1,115 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use super::focus_block;
+use crate::app::App;
+use crate::config::CustomCommandConfig;
+use crate::panes::Pane;
+use crate::types::CommandOutput;
+
+/// One panel per `[[custom_commands]]` entry, stacked top to bottom
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let commands = &app.custom_commands.commands;
+    if commands.is_empty() {
+        let lines = vec![
+            Line::from(""),
+            Line::from("  No custom commands configured."),
+            Line::from(""),
+            Line::from(Span::styled(
+                "  Add [[custom_commands]] entries (name, command, interval_secs, parser) to the config",
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(Span::styled(
+                "  to show the output of site-specific tools such as `zpool iostat` here.",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        let block = Block::default().title("Tools").borders(Borders::ALL);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, commands.len() as u32); commands.len()])
+        .split(area);
+
+    for (index, (command, chunk)) in commands.iter().zip(chunks.iter()).enumerate() {
+        let output = app.custom_commands.outputs.get(index).and_then(|o| o.as_ref());
+        draw_command(f, app, index, command, output, *chunk);
+    }
+}
+
+fn draw_command(
+    f: &mut Frame,
+    app: &App,
+    index: usize,
+    command: &CustomCommandConfig,
+    output: Option<&CommandOutput>,
+    area: Rect,
+) {
+    let pane = Pane::Command(index);
+    let (status, color) = match output {
+        None => ("running...".to_string(), Color::Gray),
+        Some(output) => {
+            let age = (chrono::Local::now() - output.ran_at).num_seconds().max(0);
+            let result = match (&output.error, output.exit_code) {
+                (Some(error), _) => error.clone(),
+                (None, Some(0)) => format!("{:.1}s", output.duration.as_secs_f64()),
+                (None, Some(code)) => format!("exit {}", code),
+                (None, None) => "killed by signal".to_string(),
+            };
+            let color = if output.succeeded() { Color::Green } else { Color::Red };
+            (format!("{}s ago, {}", age, result), color)
+        }
+    };
+    let block = focus_block(
+        app,
+        pane,
+        Block::default()
+            .title(format!("{} | every {}s, {}", command.name, command.interval_secs, status))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color)),
+    );
+
+    let Some(output) = output else {
+        f.render_widget(
+            Paragraph::new(Span::styled(command.command.clone(), Style::default().fg(Color::DarkGray)))
+                .block(block),
+            area,
+        );
+        return;
+    };
+
+    let offset = app.pane_offset(pane);
+    match output.table {
+        Some((ref header, ref rows)) => {
+            // Size columns to their widest cell, header included
+            let mut widths: Vec<u16> = header.iter().map(|h| h.len() as u16).collect();
+            for row in rows {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.len() as u16);
+                }
+            }
+            let widths: Vec<Constraint> = widths.into_iter().map(Constraint::Length).collect();
+            let header = Row::new(header.iter().map(|h| {
+                Cell::from(h.clone()).style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            }));
+            let rows: Vec<Row> = rows
+                .iter()
+                .skip(offset)
+                .map(|row| Row::new(row.iter().map(|cell| Cell::from(cell.clone()))))
+                .collect();
+            f.render_widget(Table::new(rows, widths).header(header).block(block), area);
+        }
+        None => {
+            let lines: Vec<Line> = output.lines.iter().skip(offset).map(|line| Line::from(line.clone())).collect();
+            f.render_widget(Paragraph::new(lines).block(block), area);
+        }
+    }
+}
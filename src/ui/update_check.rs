@@ -0,0 +1,50 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::version_check::{UpdateChecker, CURRENT_VERSION};
+
+/// The newer release's version, date, page and the start of its notes
+pub fn draw_update_popup(f: &mut Frame, checker: &UpdateChecker, area: Rect) {
+    let popup_area = centered_rect(70, 60, area);
+    f.render_widget(Clear, popup_area);
+
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::DarkGray));
+    let mut lines = vec![Line::from(vec![label("Running:   "), Span::raw(CURRENT_VERSION)])];
+    match &checker.newer {
+        Some(release) => {
+            lines.push(Line::from(vec![
+                label("Available: "),
+                Span::styled(release.version.as_str(), Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+            ]));
+            if let Some(ref published) = release.published {
+                lines.push(Line::from(vec![label("Published: "), Span::raw(published.as_str())]));
+            }
+            if let Some(ref url) = release.url {
+                lines.push(Line::from(vec![label("Page:      "), Span::styled(url.as_str(), Style::default().fg(Color::Cyan))]));
+            }
+            lines.push(Line::from(""));
+            if release.notes.iter().all(|line| line.trim().is_empty()) {
+                lines.push(Line::from(label("No release notes")));
+            }
+            lines.extend(release.notes.iter().map(|line| Line::from(line.as_str())));
+        }
+        None => lines.push(Line::from(label("This is the latest release"))),
+    }
+    if let Some(ref error) = checker.error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!("Last check failed: {}", error), Style::default().fg(Color::Red))));
+    }
+
+    let block = Block::default()
+        .title(" Update available ")
+        .title_bottom(" Enter/Esc: Close ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), popup_area);
+}
@@ -0,0 +1,84 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::upgrade::UpgradeRun;
+
+/// Choose how to apply the new configuration
+pub fn draw_upgrade_prompt(f: &mut Frame, area: Rect) {
+    let popup_area = centered_rect(50, 30, area);
+    f.render_widget(Clear, popup_area);
+
+    let key = |k: &'static str| Span::styled(k, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let lines = vec![
+        Line::from(""),
+        Line::from("  Rebuild this host with nixos-rebuild"),
+        Line::from(""),
+        Line::from(vec![Span::raw("  "), key("s"), Span::raw("  Switch (activate now)")]),
+        Line::from(vec![Span::raw("  "), key("b"), Span::raw("  Boot (activate on next reboot)")]),
+    ];
+
+    let block = Block::default()
+        .title(" Upgrade ")
+        .title_bottom(" Esc: Cancel ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+/// The running rebuild's command, outcome and the tail of its output
+pub fn draw_upgrade_run(f: &mut Frame, run: &UpgradeRun, area: Rect, glyphs: &Glyphs) {
+    let popup_area = centered_rect(80, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let color = match run.outcome {
+        None => Color::Cyan,
+        Some(Ok(_)) => Color::Green,
+        Some(Err(_)) => Color::Red,
+    };
+    let block = Block::default()
+        .title(format!(" Upgrade: {} ", run.action.arg()))
+        .title_bottom(if run.is_finished() { " Enter/Esc: Close " } else { " Esc: Hide (keeps running) " })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+    if inner.height < 3 {
+        return;
+    }
+
+    let status = match &run.outcome {
+        None => Line::from(Span::styled("Running...", Style::default().fg(Color::Yellow))),
+        Some(Ok(summary)) => Line::from(Span::styled(
+            format!("{} {}", glyphs.check, summary),
+            Style::default().fg(Color::Green),
+        )),
+        Some(Err(reason)) => Line::from(Span::styled(
+            format!("{} {}", glyphs.error, reason),
+            Style::default().fg(Color::Red),
+        )),
+    };
+    let header = vec![
+        Line::from(Span::styled(format!("$ {}", run.command), Style::default().fg(Color::DarkGray))),
+        status,
+    ];
+    f.render_widget(Paragraph::new(header), Rect { height: 2, ..inner });
+
+    // Follow the end of the output
+    let height = (inner.height - 3) as usize;
+    let start = run.output.len().saturating_sub(height);
+    let lines: Vec<Line> = run.output[start..]
+        .iter()
+        .map(|line| Line::from(Span::styled(line.clone(), Style::default().fg(Color::Gray))))
+        .collect();
+    f.render_widget(
+        Paragraph::new(lines),
+        Rect { y: inner.y + 3, height: inner.height - 3, ..inner },
+    );
+}
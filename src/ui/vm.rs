@@ -0,0 +1,255 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::app::VmDetail;
+use crate::types::ConsoleKind;
+
+/// Draw the VM detail popup with qemu-guest-agent information
+pub fn draw_vm_detail(f: &mut Frame, detail: &VmDetail, area: Rect, fs_warning: f64, read_only: bool, glyphs: &Glyphs) {
+    let popup_area = centered_rect(70, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let title = match detail.resource.namespace {
+        Some(ref ns) => format!(" VM {}/{} ", ns, detail.resource.name),
+        None => format!(" VM {} ", detail.resource.name),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    if detail.show_console {
+        draw_console(f, detail, inner, glyphs);
+        return;
+    }
+
+    let label = |text: &str| Span::styled(format!("{:<10}", text), Style::default().fg(Color::Gray));
+    let heading = |text: &str| {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        ))
+    };
+
+    let mut lines = vec![Line::from(vec![
+        label("Status:"),
+        Span::styled(detail.resource.status.clone(), Style::default().fg(Color::Yellow)),
+    ])];
+
+    if let Some(ref error) = detail.error {
+        lines.push(Line::from(vec![
+            label("Agent:"),
+            Span::styled(
+                error.lines().next().unwrap_or_default().to_string(),
+                Style::default().fg(Color::Red),
+            ),
+        ]));
+    }
+
+    console_lines(&mut lines, detail, glyphs);
+    let help = if read_only {
+        " c: Console  Esc: Close "
+    } else if detail.vnc_forward.as_ref().is_some_and(|f| f.running) {
+        " c: Console  v: Stop VNC forward  Esc: Close "
+    } else {
+        " c: Console  v: VNC forward  Esc: Close "
+    };
+
+    let Some(ref guest) = detail.guest else {
+        if detail.error.is_none() {
+            lines.push(Line::from(Span::styled(
+                "Querying guest agent...",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(help, Style::default().fg(Color::DarkGray))));
+        f.render_widget(Paragraph::new(lines), inner);
+        return;
+    };
+
+    lines.push(Line::from(vec![label("Hostname:"), Span::raw(guest.hostname.clone())]));
+    lines.push(Line::from(vec![label("OS:"), Span::raw(guest.os.clone())]));
+    lines.push(Line::from(vec![label("Kernel:"), Span::raw(guest.kernel.clone())]));
+    lines.push(Line::from(""));
+
+    lines.push(heading(&format!("Logged-in users ({})", guest.users.len())));
+    if guest.users.is_empty() {
+        lines.push(Line::from(Span::styled("  none", Style::default().fg(Color::DarkGray))));
+    }
+    for user in &guest.users {
+        let name = match user.domain {
+            Some(ref domain) if !domain.is_empty() => format!("{}\\{}", domain, user.name),
+            _ => user.name.clone(),
+        };
+        let since = user
+            .login_time
+            .and_then(|t| chrono::DateTime::from_timestamp(t as i64, 0))
+            .map(|t| t.with_timezone(&chrono::Local).format("%b %d %H:%M").to_string())
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<20}", name), Style::default().fg(Color::Cyan)),
+            Span::styled(since, Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(heading("Filesystems"));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(lines.len() as u16),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    // One gauge row per guest filesystem
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            guest
+                .filesystems
+                .iter()
+                .map(|_| Constraint::Length(1))
+                .chain(std::iter::once(Constraint::Min(0)))
+                .collect::<Vec<_>>(),
+        )
+        .split(chunks[1]);
+
+    for (fs, row) in guest.filesystems.iter().zip(rows.iter()) {
+        let usage = fs.usage_percent();
+        let color = if usage >= fs_warning { Color::Red } else { Color::Green };
+        let gib = 1_073_741_824.0;
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(color))
+            .ratio((usage / 100.0).clamp(0.0, 1.0))
+            .label(format!(
+                "{} ({}, {}) {:.1}/{:.1} GiB {:.0}%",
+                fs.mount_point,
+                fs.disk_name,
+                fs.fs_type,
+                fs.used_bytes as f64 / gib,
+                fs.total_bytes as f64 / gib,
+                usage
+            ));
+        f.render_widget(gauge, *row);
+    }
+
+    let help = Paragraph::new(Line::from(Span::styled(help, Style::default().fg(Color::DarkGray))))
+        .alignment(Alignment::Center);
+    f.render_widget(help, chunks[2]);
+}
+
+/// Where the VM's consoles can be reached from this host, and the VNC
+/// port-forward started from here with how to connect a viewer to it
+fn console_lines(lines: &mut Vec<Line>, detail: &VmDetail, glyphs: &Glyphs) {
+    let label = |text: &str| Span::styled(format!("{:<10}", text), Style::default().fg(Color::Gray));
+    for (i, endpoint) in detail.endpoints.iter().enumerate() {
+        let color = match endpoint.kind {
+            ConsoleKind::Vnc => Color::Cyan,
+            ConsoleKind::Serial => Color::White,
+            ConsoleKind::PortForward => Color::Magenta,
+        };
+        lines.push(Line::from(vec![
+            label(if i == 0 { "Consoles:" } else { "" }),
+            Span::styled(format!("{:<8}", endpoint.kind.label()), Style::default().fg(color)),
+            Span::raw(endpoint.address.clone()),
+            Span::styled(format!("  {}", endpoint.source), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+
+    let Some(ref forward) = detail.vnc_forward else {
+        return;
+    };
+    let status = if forward.running {
+        Span::styled(
+            format!("{} listening on {}", glyphs.check, forward.address),
+            Style::default().fg(Color::Green),
+        )
+    } else {
+        Span::styled(
+            format!(
+                "{} {}",
+                glyphs.error,
+                forward.error.clone().unwrap_or_else(|| "virtctl exited".to_string())
+            ),
+            Style::default().fg(Color::Red),
+        )
+    };
+    lines.push(Line::from(vec![label("VNC:"), status]));
+    if forward.running {
+        let port = forward.address.port();
+        let hint = if forward.address.ip().is_loopback() {
+            format!("ssh -L {0}:{1}:{0} <this host>, then point a viewer at localhost:{0}", port, forward.address.ip())
+        } else {
+            format!("point a viewer at {}", forward.address)
+        };
+        lines.push(Line::from(vec![
+            label(""),
+            Span::styled(hint, Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+}
+
+/// Captured serial console output, following the end unless scrolled back
+fn draw_console(f: &mut Frame, detail: &VmDetail, area: Rect, glyphs: &Glyphs) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let status = match detail.console {
+        None => Span::styled("Not captured", Style::default().fg(Color::DarkGray)),
+        Some(ref console) if console.connected => Span::styled("Attached", Style::default().fg(Color::Green)),
+        Some(ref console) => Span::styled(
+            console.error.clone().unwrap_or_else(|| "Connecting...".to_string()),
+            Style::default().fg(Color::Red),
+        ),
+    };
+    let mut header = vec![Span::styled("Console:  ", Style::default().fg(Color::Gray)), status];
+    if detail.console_scroll > 0 {
+        header.push(Span::styled(
+            format!("  ({} lines back)", detail.console_scroll),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(header)), chunks[0]);
+
+    let lines: &[String] = detail.console.as_ref().map_or(&[], |c| &c.lines);
+    let height = chunks[1].height as usize;
+    let end = lines.len().saturating_sub(detail.console_scroll);
+    let start = end.saturating_sub(height);
+    let body: Vec<Line> = lines[start..end]
+        .iter()
+        .map(|line| {
+            let style = if line.starts_with("-- ") && line.ends_with(" --") {
+                Style::default().fg(Color::DarkGray)
+            } else if line.contains("Kernel panic") || line.contains("emergency mode") {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(line.clone(), style))
+        })
+        .collect();
+    f.render_widget(Paragraph::new(body), chunks[1]);
+
+    let help = Paragraph::new(Line::from(Span::styled(
+        format!(" {}/PgUp/PgDn: Scroll  G: Follow  c: Guest info  Esc: Close ", glyphs.up_down),
+        Style::default().fg(Color::DarkGray),
+    )))
+    .alignment(Alignment::Center);
+    f.render_widget(help, chunks[2]);
+}
@@ -0,0 +1,106 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::VmCloneEditor;
+use crate::types::{object_name_problem, CloneDisk};
+
+/// Confirm cloning a VirtualMachine: new name, disk handling and what each
+/// disk of the clone will be backed by
+pub fn draw_vm_clone(f: &mut Frame, editor: &VmCloneEditor, area: Rect) {
+    let popup_area = centered_rect(70, 60, area);
+    f.render_widget(Clear, popup_area);
+
+    let title = match editor.resource.namespace {
+        Some(ref ns) => format!(" Clone VM {}/{} ", ns, editor.resource.name),
+        None => format!(" Clone VM {} ", editor.resource.name),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Name + disk mode
+            Constraint::Min(0),     // Disks
+            Constraint::Length(3),  // Status + help
+        ])
+        .split(inner);
+
+    let label = |text: &'static str| Span::styled(format!(" {:<14}", text), Style::default().fg(Color::Gray));
+    let fields = vec![
+        Line::from(vec![
+            label("New name:"),
+            Span::styled(format!("{}_", editor.name), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(vec![
+            label("Disks:"),
+            Span::raw(if editor.clone_disks {
+                "clone every claim via CDI"
+            } else {
+                "share claims with the source"
+            }),
+        ]),
+    ];
+    f.render_widget(Paragraph::new(fields), chunks[0]);
+
+    let lines: Vec<Line> = match editor.plan() {
+        None => vec![Line::from(Span::styled(" Source VM could not be read", Style::default().fg(Color::Red)))],
+        Some(plan) if plan.disks.is_empty() => {
+            vec![Line::from(Span::styled(" No claim-backed disks", Style::default().fg(Color::DarkGray)))]
+        }
+        Some(plan) => plan
+            .disks
+            .iter()
+            .map(|disk| {
+                let (volume, text, color) = match disk {
+                    CloneDisk::Fresh { volume, data_volume } => {
+                        (volume, format!("new DataVolume {} from the original source", data_volume), Color::White)
+                    }
+                    CloneDisk::Cloned { volume, source, data_volume } => {
+                        (volume, format!("{} cloned from {}", data_volume, source), Color::Green)
+                    }
+                    CloneDisk::Shared { volume, claim } => {
+                        (volume, format!("shares {} (both VMs write to it)", claim), Color::Yellow)
+                    }
+                };
+                Line::from(vec![
+                    Span::styled(format!(" {:<14}", volume), Style::default().fg(Color::Gray)),
+                    Span::styled(text, Style::default().fg(color)),
+                ])
+            })
+            .collect(),
+    };
+    f.render_widget(
+        Paragraph::new(lines).block(Block::default().title(" Disks of the clone ").borders(Borders::TOP)),
+        chunks[1],
+    );
+
+    let status = match (editor.status.as_deref(), object_name_problem(editor.name.trim())) {
+        (Some(status), _) => Span::styled(status.to_string(), Style::default().fg(Color::Yellow)),
+        (None, Some(problem)) => Span::styled(problem, Style::default().fg(Color::Red)),
+        (None, None) => Span::styled(
+            "The clone is created halted, with new MAC addresses and firmware UUID",
+            Style::default().fg(Color::DarkGray),
+        ),
+    };
+    let help = Paragraph::new(vec![
+        Line::from(status),
+        Line::from(Span::styled(
+            " Type: Name  Tab: Clone/share disks  Enter: Create  Esc: Cancel ",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+    f.render_widget(help, chunks[2]);
+}
@@ -0,0 +1,161 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::theme::Theme;
+use crate::wizard::{Wizard, WizardStep, THEMES};
+
+/// Draw the current page of the first-run setup wizard
+pub fn draw(f: &mut Frame, wizard: &Wizard) {
+    if super::too_small(f) {
+        return;
+    }
+    let area = centered_rect(70, 80, f.size());
+    let step = wizard.current_step();
+    let block = Block::default()
+        .title(format!(
+            " hypervisor-tui setup ({}/{}): {} ",
+            wizard.step + 1,
+            WizardStep::ALL.len(),
+            step.title()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(2)])
+        .split(inner);
+
+    let intro = match step {
+        WizardStep::Kubeconfig => format!(
+            "No config file was found. Which kubeconfig should be used? Detected distribution: {}",
+            wizard.flavor.label()
+        ),
+        WizardStep::Services => "journald units whose logs are followed (F1). More can be picked later with 'u'.".to_string(),
+        WizardStep::Thresholds => "Usage at which Warning and Critical alerts are raised.".to_string(),
+        WizardStep::Theme => "Colors for dark or light terminals; auto asks the terminal.".to_string(),
+        WizardStep::Review => format!("Enter writes {} and starts the console.", wizard.path.display()),
+    };
+    f.render_widget(
+        Paragraph::new(intro).style(Style::default().fg(Color::Gray)).wrap(Wrap { trim: true }),
+        chunks[0],
+    );
+
+    let row = |i: usize, text: String, style: Style| {
+        let selected = i == wizard.selected;
+        let marker = if selected { "> " } else { "  " };
+        let style = if selected { style.add_modifier(Modifier::BOLD) } else { style };
+        Line::from(Span::styled(format!("{}{}", marker, text), style))
+    };
+
+    let mut lines: Vec<Line> = Vec::new();
+    match step {
+        WizardStep::Kubeconfig => {
+            for (i, choice) in wizard.kubeconfigs.iter().enumerate() {
+                let radio = if i == wizard.kubeconfig { "(*)" } else { "( )" };
+                let found = if choice.exists { "" } else { "  (not found)" };
+                let color = if choice.exists { Color::White } else { Color::DarkGray };
+                lines.push(row(i, format!("{} {} [{}]{}", radio, choice.path, choice.source, found), Style::default().fg(color)));
+            }
+        }
+        WizardStep::Services => {
+            for (i, (category, name, checked)) in wizard.services.iter().enumerate() {
+                if i == 0 || wizard.services[i - 1].0 != *category {
+                    lines.push(Line::from(Span::styled(
+                        category.label(),
+                        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                    )));
+                }
+                let checkbox = if *checked { "[x]" } else { "[ ]" };
+                let color = if *checked { Color::Green } else { Color::White };
+                lines.push(row(i, format!("{} {}", checkbox, name), Style::default().fg(color)));
+            }
+        }
+        WizardStep::Thresholds => {
+            for (i, (label, value)) in wizard.thresholds.iter().enumerate() {
+                lines.push(row(i, format!("{:<20} {:>5.0}", label, value), Style::default().fg(Color::White)));
+            }
+        }
+        WizardStep::Theme => {
+            for (i, theme) in THEMES.iter().enumerate() {
+                let radio = if *theme == wizard.theme { "(*)" } else { "( )" };
+                let name = match theme {
+                    Theme::Auto => "auto",
+                    Theme::Dark => "dark",
+                    Theme::Light => "light",
+                };
+                lines.push(row(i, format!("{} {}", radio, name), Style::default().fg(Color::White)));
+            }
+        }
+        WizardStep::Review => {
+            let config = wizard.config();
+            let label = |text: &str| Span::styled(format!("{:<14}", text), Style::default().fg(Color::Gray));
+            let kubeconfig = config
+                .kubernetes
+                .kubeconfig_path
+                .clone()
+                .unwrap_or_else(|| format!("{} (default)", wizard.flavor.kubeconfig_path()));
+            let services = config.logging.services.clone().unwrap_or_default();
+            let alerts = &config.alerts;
+            lines.push(Line::from(vec![label("Kubeconfig:"), Span::raw(kubeconfig)]));
+            lines.push(Line::from(vec![
+                label("Services:"),
+                Span::raw(if services.is_empty() { "all units".to_string() } else { services.join(", ") }),
+            ]));
+            lines.push(Line::from(vec![
+                label("Alerts:"),
+                Span::raw(format!(
+                    "CPU {:.0}/{:.0}%  memory {:.0}/{:.0}%  disk {:.0}/{:.0}%",
+                    alerts.cpu_warning_threshold,
+                    alerts.cpu_critical_threshold,
+                    alerts.memory_warning_threshold,
+                    alerts.memory_critical_threshold,
+                    alerts.disk_warning_threshold,
+                    alerts.disk_critical_threshold
+                )),
+            ]));
+            lines.push(Line::from(vec![label("Theme:"), Span::raw(format!("{:?}", config.display.theme).to_lowercase())]));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Everything else keeps its default; see config.example.toml for the rest.",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+    }
+
+    // Keep the cursor row in view on long service lists
+    let height = chunks[1].height as usize;
+    let cursor = lines
+        .iter()
+        .position(|l| l.spans.first().is_some_and(|s| s.content.starts_with("> ")))
+        .unwrap_or(0);
+    let offset = cursor.saturating_sub(height.saturating_sub(1));
+    f.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), chunks[1]);
+
+    let status = match wizard.status {
+        Some(ref status) => Span::styled(status.clone(), Style::default().fg(Color::Red)),
+        None => Span::raw(""),
+    };
+    let help = match step {
+        WizardStep::Services => "Up/Down: Select  Space: Toggle  Enter: Next  Backspace: Back  Esc: Skip setup",
+        WizardStep::Thresholds => "Up/Down: Select  Left/Right: -/+5  Enter: Next  Backspace: Back  Esc: Skip setup",
+        WizardStep::Review => "Enter: Save  Backspace: Back  Esc: Skip setup (defaults, nothing written)",
+        _ => "Up/Down: Select  Enter: Next  Backspace: Back  Esc: Skip setup",
+    };
+    f.render_widget(
+        Paragraph::new(vec![
+            Line::from(status),
+            Line::from(Span::styled(help, Style::default().fg(Color::DarkGray))),
+        ])
+        .alignment(Alignment::Center),
+        chunks[2],
+    );
+}
@@ -0,0 +1,174 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use super::glyphs::Glyphs;
+use crate::app::{CloudInitViewer, YamlInspector};
+
+/// Draw the YAML inspector popup for a single Kubernetes object
+pub fn draw_yaml_inspector(f: &mut Frame, inspector: &YamlInspector, area: Rect, glyphs: &Glyphs, read_only: bool) {
+    let popup_area = centered_rect(85, 80, area);
+    f.render_widget(Clear, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // YAML body
+            Constraint::Length(2),  // Status + help text
+        ])
+        .split(popup_area);
+
+    let title = match inspector.resource.namespace {
+        Some(ref ns) => format!(" {} {}/{} ", inspector.resource.kind.title(), ns, inspector.resource.name),
+        None => format!(" {} {} ", inspector.resource.kind.title(), inspector.resource.name),
+    };
+
+    let lines: Vec<Line> = inspector
+        .content
+        .lines()
+        .skip(inspector.scroll)
+        .take(chunks[0].height as usize)
+        .map(highlight_yaml_line)
+        .collect();
+
+    let body = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(body, chunks[0]);
+
+    let mut footer = Vec::new();
+    if let Some(ref status) = inspector.status {
+        footer.push(Line::from(Span::styled(
+            status.lines().next().unwrap_or_default().to_string(),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    footer.push(Line::from(Span::styled(
+        if read_only {
+            format!(" {}/PgUp/PgDn: Scroll  Esc: Close ", glyphs.up_down)
+        } else if inspector.conflicted.is_some() {
+            format!(" {}/PgUp/PgDn: Scroll  e: Edit in $EDITOR  F: Force apply  Esc: Close ", glyphs.up_down)
+        } else {
+            format!(" {}/PgUp/PgDn: Scroll  e: Edit in $EDITOR  Esc: Close ", glyphs.up_down)
+        },
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let help = Paragraph::new(footer)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT));
+    f.render_widget(help, chunks[1]);
+}
+
+/// Draw a VM's cloud-init user and network data, secrets redacted
+pub fn draw_cloud_init(f: &mut Frame, viewer: &CloudInitViewer, area: Rect, glyphs: &Glyphs, read_only: bool) {
+    let popup_area = centered_rect(85, 80, area);
+    f.render_widget(Clear, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // User data
+            Constraint::Length(2),  // Status + help text
+        ])
+        .split(popup_area);
+
+    let name = match viewer.resource.namespace {
+        Some(ref ns) => format!("{}/{}", ns, viewer.resource.name),
+        None => viewer.resource.name.clone(),
+    };
+    let title = match viewer.cloud_init {
+        Some(ref cloud_init) => format!(" Cloud-init {} ({}, volume {}) ", name, cloud_init.datasource, cloud_init.volume),
+        None => format!(" Cloud-init {} ", name),
+    };
+
+    let content = viewer.content();
+    let lines: Vec<Line> = content
+        .lines()
+        .skip(viewer.scroll)
+        .take(chunks[0].height as usize)
+        .map(highlight_yaml_line)
+        .collect();
+
+    let body = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(body, chunks[0]);
+
+    let mut footer = Vec::new();
+    if let Some(ref status) = viewer.status {
+        footer.push(Line::from(Span::styled(
+            status.lines().next().unwrap_or_default().to_string(),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    footer.push(Line::from(Span::styled(
+        if read_only {
+            format!(" {}/PgUp/PgDn: Scroll  Esc: Close ", glyphs.up_down)
+        } else {
+            format!(" {}/PgUp/PgDn: Scroll  e: Edit user data in $EDITOR  Esc: Close ", glyphs.up_down)
+        },
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let help = Paragraph::new(footer)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT));
+    f.render_widget(help, chunks[1]);
+}
+
+/// Minimal YAML syntax highlighting: comments, keys, list markers and scalar values
+fn highlight_yaml_line(line: &str) -> Line<'_> {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    if trimmed.starts_with('#') {
+        return Line::from(Span::styled(line, Style::default().fg(Color::DarkGray)));
+    }
+
+    let mut spans = vec![Span::raw(indent)];
+    let mut rest = trimmed;
+
+    if let Some(item) = rest.strip_prefix("- ") {
+        spans.push(Span::styled("- ", Style::default().fg(Color::Magenta)));
+        rest = item;
+    }
+
+    match rest.find(": ").or_else(|| rest.strip_suffix(':').map(|k| k.len())) {
+        Some(idx) if !rest.starts_with('"') && !rest.starts_with('\'') => {
+            let (key, value) = rest.split_at(idx);
+            spans.push(Span::styled(
+                key,
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::raw(":"));
+            let value = value.strip_prefix(':').unwrap_or(value);
+            if !value.is_empty() {
+                spans.push(Span::styled(value, scalar_style(value.trim())));
+            }
+        }
+        _ => spans.push(Span::styled(rest, scalar_style(rest))),
+    }
+
+    Line::from(spans)
+}
+
+fn scalar_style(value: &str) -> Style {
+    match value {
+        "true" | "false" | "null" | "~" => Style::default().fg(Color::Magenta),
+        v if v.parse::<f64>().is_ok() => Style::default().fg(Color::Yellow),
+        v if v.starts_with('"') || v.starts_with('\'') => Style::default().fg(Color::Green),
+        _ => Style::default().fg(Color::White),
+    }
+}
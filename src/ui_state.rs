@@ -0,0 +1,96 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::app::{FleetSort, HeatmapMetric, Screen};
+use crate::config::Config;
+use crate::metrics_history::{ChartMetric, Zoom};
+use crate::theme::Theme;
+use crate::types::ResourceKind;
+
+/// The working view as it was at the last quit: screen, filters, sort
+/// orders, theme and alert panel, restored at the next start unless
+/// `display.remember_state` is off. Anything missing keeps its default.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screen: Option<Screen>,
+
+    /// Dark or light, as switched from the palette
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<Theme>,
+
+    // Logs
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub search_query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_level: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logs_collapsed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logs_wrapped: Option<bool>,
+
+    // Resources
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_kind: Option<ResourceKind>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub resource_selector: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_group_by: Option<String>,
+
+    // Sort orders and chart views
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fleet_sort: Option<FleetSort>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub fleet_sort_desc: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heatmap_metric: Option<HeatmapMetric>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chart_metric: Option<ChartMetric>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chart_zoom: Option<Zoom>,
+
+    // Alerts
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub alert_panel_open: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub alert_stats_open: bool,
+    /// Profile in effect; --alert-profile still wins
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alert_profile: Option<String>,
+}
+
+impl UiState {
+    /// The state saved at the last quit, or the defaults
+    pub fn load() -> Self {
+        let path = Self::saved_path();
+        fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| match toml::from_str::<UiState>(&contents) {
+                Ok(state) => Some(state),
+                Err(e) => {
+                    tracing::warn!("Ignoring saved UI state {:?}: {}", path, e);
+                    None
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = Self::saved_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create state directory: {:?}", parent))?;
+        }
+        let contents = toml::to_string_pretty(self).context("Failed to serialize UI state")?;
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write UI state: {:?}", path))?;
+        Ok(path)
+    }
+
+    fn saved_path() -> PathBuf {
+        Config::state_dir().join("ui-state.toml")
+    }
+}
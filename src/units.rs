@@ -0,0 +1,35 @@
+/// Sizes in binary units: whole KiB and MiB below a GiB, one decimal from
+/// there, so collectors and screens write the same value the same way
+pub fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = KIB * 1024.0;
+    const GIB: f64 = MIB * 1024.0;
+    const TIB: f64 = GIB * 1024.0;
+
+    let value = bytes as f64;
+    if value >= TIB {
+        format!("{:.1} TiB", value / TIB)
+    } else if value >= GIB {
+        format!("{:.1} GiB", value / GIB)
+    } else if value >= MIB {
+        format!("{:.0} MiB", value / MIB)
+    } else if value >= KIB {
+        format!("{:.0} KiB", value / KIB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// Durations as "250ms", "4.2s", "45s" or "2m05s"; negative ones as zero
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let millis = duration.num_milliseconds().max(0);
+    match millis {
+        0..=999 => format!("{}ms", millis),
+        1_000..=9_999 => format!("{:.1}s", millis as f64 / 1000.0),
+        10_000..=59_999 => format!("{}s", millis / 1000),
+        _ => {
+            let secs = millis / 1000;
+            format!("{}m{:02}s", secs / 60, secs % 60)
+        }
+    }
+}
@@ -0,0 +1,908 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::time::{Duration, Instant};
+
+use crate::app::{App, HostMapView, MessageLevel, NodeEditField, ResourceInput, RunProgress, Screen};
+use crate::palette::PaletteAction;
+use crate::panes::Pane;
+use crate::presence;
+use crate::types::{Permission, QemuSignal, VmAction};
+use crate::ui;
+use crate::upgrade::UpgradeAction;
+use crate::nix_store::NixStoreOp;
+
+/// Everything that can change the app's state, fed to `update` one at a
+/// time by the event loop
+#[derive(Debug)]
+pub enum Msg {
+    KeyPressed(KeyEvent),
+    Resized { width: u16, height: u16 },
+    /// The update interval elapsed: time to collect fresh data
+    Tick,
+    /// The terminal gained or lost focus
+    Focus(bool),
+    /// Take in results of background work (batches, upgrades, consoles,
+    /// custom commands) and expire toasts
+    Poll,
+    /// A collection finished: how long it took and whether it failed
+    MetricsUpdated { elapsed: Duration, result: Result<()> },
+    /// What tmux answered about a client being attached
+    TmuxAttached(bool),
+    /// A task started for a key finished
+    TaskDone,
+    /// A task started for a key failed; `context` heads the error toast
+    TaskFailed { context: &'static str, error: anyhow::Error },
+}
+
+/// What the event loop has to do after an update: the effects that need
+/// the terminal, end the program or wait on I/O
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    None,
+    Redraw,
+    Quit,
+    /// Hand the YAML inspector's manifest to $EDITOR, then redraw
+    EditYaml,
+    /// Hand the cloud-init viewer's user data to $EDITOR, then redraw
+    EditCloudInit,
+    /// Put the selected alert's runbook URL on the clipboard
+    CopyRunbook,
+    /// Put the status report just written on the clipboard
+    CopyReport,
+    /// Put the log line in the detail view on the clipboard
+    CopyLogLine,
+    /// Hand the terminal back and stop until `fg`, then take it over again
+    Suspend,
+    /// Re-enter the alternate screen and redraw every cell, for a
+    /// terminal that may show anything, such as one just reattached
+    Repaint,
+    /// Run a task and feed its outcome back to `update`
+    Run(Task),
+    /// Several of the above, in order
+    Batch(Vec<Command>),
+}
+
+/// Work that talks to the cluster, the journal or other processes, run
+/// by the event loop so `update` itself never waits on I/O
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Task {
+    /// Collect for the current screen and the alerts
+    Collect,
+    WriteTelemetry,
+    /// Ask tmux whether this pane's session has a client attached
+    CheckTmux(String),
+    ReloadResources,
+    ReloadNixStore,
+    Refresh,
+    RunPaletteAction(PaletteAction),
+    ApplyView(usize),
+    NextResourceKind,
+    PrevResourceKind,
+    ConfirmDiskDelete,
+    ConfirmCatalogOp,
+    OpenLogDetail,
+    OpenLogDetailContext,
+    OpenLogSearchContext,
+    OpenLogServices,
+    ToggleLogService,
+    OpenVmDetail,
+    OpenHostMapVmDetail,
+    OpenHeatmapVmDetail,
+    OpenYamlInspector,
+    ForceYamlApply,
+    OpenCloudInit,
+    OpenVmClone,
+    SubmitVmClone,
+    OpenCpuTuning,
+    ApplyCpuTuning,
+    OpenHotplug,
+    ApplyHotplug,
+    OpenNodeEditor,
+    SubmitNodeEditorInput,
+    RemoveNodeEditorSelected,
+    JumpToAlertPod,
+    ToggleHostMapView,
+    OpenImageCatalog,
+    OpenNixStore,
+}
+
+impl Task {
+    /// Do the work, returning the message that reports its outcome
+    pub async fn run(self, app: &mut App) -> Option<Msg> {
+        match self {
+            Task::Collect => {
+                let started = Instant::now();
+                let result = app.update().await;
+                return Some(Msg::MetricsUpdated { elapsed: started.elapsed(), result });
+            }
+            Task::WriteTelemetry => {
+                app.write_telemetry();
+                return None;
+            }
+            Task::CheckTmux(pane) => return presence::tmux_attached(&pane).await.map(Msg::TmuxAttached),
+            Task::Refresh => {
+                if let Err(error) = app.refresh().await {
+                    return Some(Msg::TaskFailed { context: "Refresh", error });
+                }
+            }
+            Task::NextResourceKind => {
+                if let Err(error) = app.next_resource_kind().await {
+                    return Some(Msg::TaskFailed { context: "Loading resources", error });
+                }
+            }
+            Task::PrevResourceKind => {
+                if let Err(error) = app.prev_resource_kind().await {
+                    return Some(Msg::TaskFailed { context: "Loading resources", error });
+                }
+            }
+            Task::ReloadResources => app.reload_resources().await,
+            Task::ReloadNixStore => app.reload_nix_store().await,
+            Task::RunPaletteAction(action) => app.run_palette_action(action).await,
+            Task::ApplyView(index) => app.apply_view(index).await,
+            Task::ConfirmDiskDelete => app.confirm_disk_delete().await,
+            Task::ConfirmCatalogOp => app.confirm_catalog_op().await,
+            Task::OpenLogDetail => app.open_log_detail().await,
+            Task::OpenLogDetailContext => app.open_log_detail_context().await,
+            Task::OpenLogSearchContext => app.open_log_search_context().await,
+            Task::OpenLogServices => app.open_log_services().await,
+            Task::ToggleLogService => app.toggle_log_service().await,
+            Task::OpenVmDetail => app.open_vm_detail().await,
+            Task::OpenHostMapVmDetail => app.open_host_map_vm_detail().await,
+            Task::OpenHeatmapVmDetail => app.open_heatmap_vm_detail().await,
+            Task::OpenYamlInspector => app.open_yaml_inspector().await,
+            Task::ForceYamlApply => app.force_yaml_apply().await,
+            Task::OpenCloudInit => app.open_cloud_init().await,
+            Task::OpenVmClone => app.open_vm_clone().await,
+            Task::SubmitVmClone => app.submit_vm_clone().await,
+            Task::OpenCpuTuning => app.open_cpu_tuning().await,
+            Task::ApplyCpuTuning => app.apply_cpu_tuning().await,
+            Task::OpenHotplug => app.open_hotplug().await,
+            Task::ApplyHotplug => app.apply_hotplug().await,
+            Task::OpenNodeEditor => app.open_node_editor().await,
+            Task::SubmitNodeEditorInput => app.submit_node_editor_input().await,
+            Task::RemoveNodeEditorSelected => app.remove_node_editor_selected().await,
+            Task::JumpToAlertPod => app.jump_to_alert_pod().await,
+            Task::ToggleHostMapView => app.toggle_host_map_view().await,
+            Task::OpenImageCatalog => app.open_image_catalog().await,
+            Task::OpenNixStore => app.open_nix_store().await,
+        }
+        Some(Msg::TaskDone)
+    }
+}
+
+/// Apply one message to the app. Nothing here touches the terminal or
+/// waits on I/O; that is left to the returned command, so updates can be
+/// driven without either.
+pub fn update(app: &mut App, msg: Msg) -> Command {
+    match msg {
+        Msg::KeyPressed(key) => {
+            // Data collected at the idle cadence is stale; catch up
+            let woke = app.presence.wake();
+            let command = handle_key(app, key);
+            if woke {
+                Command::Batch(vec![Command::Run(Task::Collect), command])
+            } else {
+                command
+            }
+        }
+        Msg::Resized { width, height } => {
+            app.resize(width, height);
+            Command::Redraw
+        }
+        Msg::Tick => Command::Run(Task::Collect),
+        Msg::Focus(focused) => {
+            if app.presence.set_focused(focused) {
+                Command::Batch(vec![Command::Run(Task::Collect), Command::Repaint])
+            } else {
+                Command::None
+            }
+        }
+        // A tmux client reattached
+        Msg::TmuxAttached(attached) => {
+            if app.presence.set_tmux_attached(attached) {
+                Command::Batch(vec![Command::Run(Task::Collect), Command::Repaint])
+            } else {
+                Command::None
+            }
+        }
+        Msg::MetricsUpdated { elapsed, result } => {
+            if let Err(e) = result {
+                app.report_error("Update", &e);
+            }
+            app.presence.record_update();
+            app.telemetry.record_update(elapsed);
+            if app.telemetry.due() {
+                Command::Batch(vec![Command::Run(Task::WriteTelemetry), Command::Redraw])
+            } else {
+                Command::Redraw
+            }
+        }
+        Msg::TaskDone => Command::Redraw,
+        Msg::TaskFailed { context, error } => {
+            app.report_error(context, &error);
+            Command::Redraw
+        }
+        Msg::Poll => {
+            let mut commands = Vec::new();
+            if let Some(pane) = app.presence.tmux_check_due() {
+                commands.push(Command::Run(Task::CheckTmux(pane)));
+            }
+            let mut changed = app.expire_messages();
+            match app.poll_batch() {
+                RunProgress::Unchanged => {}
+                RunProgress::Changed => changed = true,
+                RunProgress::Finished => commands.push(Command::Run(Task::ReloadResources)),
+            }
+            changed |= app.poll_upgrade();
+            match app.poll_nix_store() {
+                RunProgress::Unchanged => {}
+                RunProgress::Changed => changed = true,
+                RunProgress::Finished => commands.push(Command::Run(Task::ReloadNixStore)),
+            }
+            changed |= app.poll_image_catalog();
+            changed |= app.poll_selftest();
+            changed |= app.poll_evacuation();
+            changed |= app.poll_update_check();
+            changed |= app.poll_permissions();
+            changed |= app.poll_auto_actions();
+            changed |= app.poll_vm_console();
+            changed |= app.poll_vnc_forward();
+            changed |= app.poll_log_filter();
+            changed |= app.poll_log_archive();
+            changed |= app.poll_log_search();
+            changed |= app.poll_object_watch();
+            changed |= app.custom_commands.poll();
+            changed |= app.header_commands.poll();
+            if changed {
+                commands.push(Command::Redraw);
+            }
+            match commands.len() {
+                0 => Command::None,
+                1 => commands.remove(0),
+                _ => Command::Batch(commands),
+            }
+        }
+    }
+}
+
+/// Route a key to the topmost popup that captures input, or to the
+/// current screen
+fn handle_key(app: &mut App, key: KeyEvent) -> Command {
+    // Raw mode leaves these to the app; they work the same everywhere
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        match key.code {
+            KeyCode::Char('z') => return Command::Suspend,
+            KeyCode::Char('l') => return Command::Repaint,
+            _ => {}
+        }
+    }
+    let pending = app.navigator.begin_key();
+    // The debug overlay doesn't capture input, so F12 works everywhere
+    if key.code == KeyCode::F(12) {
+        app.toggle_debug_overlay();
+    } else if app.config_report.is_some() {
+        // Startup config problems must be acknowledged first
+        if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) {
+            app.close_config_report();
+        }
+    } else if app.disk_delete.is_some() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => return Command::Run(Task::ConfirmDiskDelete),
+            _ => app.cancel_disk_delete(),
+        }
+    } else if app.qemu_signal.is_some() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_qemu_signal(false),
+            KeyCode::Char('K') => app.confirm_qemu_signal(true),
+            _ => app.cancel_qemu_signal(),
+        }
+    } else if let Some(prompt) = app.batch_prompt.as_ref() {
+        if prompt.label.is_some() {
+            match key.code {
+                KeyCode::Esc => app.close_batch_prompt(),
+                KeyCode::Enter => app.submit_batch_label(),
+                KeyCode::Backspace => app.batch_label_backspace(),
+                KeyCode::Char(c) => app.batch_label_input(c),
+                _ => {}
+            }
+        } else {
+            match key.code {
+                KeyCode::Char('s') => app.start_batch(VmAction::Stop),
+                KeyCode::Char('t') => app.start_batch(VmAction::Start),
+                KeyCode::Char('m') => app.start_batch(VmAction::Migrate),
+                KeyCode::Char('l') => app.start_batch_label(),
+                KeyCode::Esc | KeyCode::Char('q') => app.close_batch_prompt(),
+                _ => {}
+            }
+        }
+    } else if app.batch_run.as_ref().is_some_and(|run| !run.hidden) {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter) {
+            app.close_batch_run();
+        }
+    } else if app.upgrade_prompt {
+        match key.code {
+            KeyCode::Char('s') => app.start_upgrade(UpgradeAction::Switch),
+            KeyCode::Char('b') => app.start_upgrade(UpgradeAction::Boot),
+            KeyCode::Esc | KeyCode::Char('q') => app.close_upgrade_prompt(),
+            _ => {}
+        }
+    } else if app.upgrade_run.as_ref().is_some_and(|run| !run.hidden) {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter) {
+            app.close_upgrade_run();
+        }
+    } else if app.nix_store.as_ref().is_some_and(|panel| panel.confirm.is_some()) {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_nix_store_op(),
+            _ => app.cancel_nix_store_op(),
+        }
+    } else if app.nix_store.as_ref().is_some_and(|panel| !panel.hidden) {
+        let selected = app.nix_store.as_ref().and_then(|panel| panel.selected_generation()).map(|g| g.number);
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => app.move_nix_store_selection(true),
+            KeyCode::Up | KeyCode::Char('k') => app.move_nix_store_selection(false),
+            KeyCode::Char('g') => app.request_nix_store_op(NixStoreOp::CollectGarbage),
+            KeyCode::Char('d') => {
+                if let Some(number) = selected {
+                    app.request_nix_store_op(NixStoreOp::DeleteGeneration(number));
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.close_nix_store(),
+            _ => {}
+        }
+    } else if app.image_catalog.as_ref().is_some_and(|panel| panel.confirm.is_some()) {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => return Command::Run(Task::ConfirmCatalogOp),
+            _ => app.cancel_catalog_op(),
+        }
+    } else if app.image_catalog.as_ref().is_some_and(|panel| !panel.hidden) {
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => app.move_image_catalog_selection(true),
+            KeyCode::Up | KeyCode::Char('k') => app.move_image_catalog_selection(false),
+            KeyCode::Char('d') => app.request_catalog_op(false),
+            KeyCode::Char('v') => app.request_catalog_op(true),
+            KeyCode::Char('x') => app.cancel_image_download(),
+            KeyCode::Esc | KeyCode::Char('q') => app.close_image_catalog(),
+            _ => {}
+        }
+    } else if app.evacuation_prompt.is_some() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_evacuation(),
+            _ => app.cancel_evacuation(),
+        }
+    } else if app.evacuation.as_ref().is_some_and(|run| !run.hidden) {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter) {
+            app.close_evacuation();
+        }
+    } else if app.selftest_run.as_ref().is_some_and(|run| !run.hidden) {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter) {
+            app.close_selftest_run();
+        }
+    } else if let Some(palette) = app.palette.as_mut() {
+        match key.code {
+            KeyCode::Esc => app.close_palette(),
+            KeyCode::Enter => match app.palette_accept() {
+                Some(PaletteAction::Quit) => return Command::Quit,
+                Some(action) => return Command::Run(Task::RunPaletteAction(action)),
+                None => {}
+            },
+            KeyCode::Up => palette.navigate(false),
+            KeyCode::Down => palette.navigate(true),
+            KeyCode::Backspace => palette.backspace(),
+            KeyCode::Char(c) => palette.input(c),
+            _ => {}
+        }
+    } else if app.view_name_prompt.is_some() {
+        match key.code {
+            KeyCode::Esc => app.close_view_name_prompt(),
+            KeyCode::Enter => app.submit_view_name(),
+            KeyCode::Backspace => app.view_name_backspace(),
+            KeyCode::Char(c) => app.view_name_input(c),
+            _ => {}
+        }
+    } else if app.yaml_inspector.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.close_yaml_inspector(),
+            KeyCode::Up => app.yaml_scroll_up(1),
+            KeyCode::Down => app.yaml_scroll_down(1),
+            KeyCode::PageUp => app.yaml_scroll_up(20),
+            KeyCode::PageDown => app.yaml_scroll_down(20),
+            KeyCode::Char('e') if !app.read_only => return Command::EditYaml,
+            KeyCode::Char('F') if app.yaml_inspector.as_ref().is_some_and(|inspector| inspector.conflicted.is_some()) => {
+                return Command::Run(Task::ForceYamlApply)
+            }
+            _ => {}
+        }
+    } else if app.object_watch.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.close_object_watch(),
+            KeyCode::Up | KeyCode::Char('k') => app.scroll_object_watch(false, 1),
+            KeyCode::Down | KeyCode::Char('j') => app.scroll_object_watch(true, 1),
+            KeyCode::PageUp => app.scroll_object_watch(false, 20),
+            KeyCode::PageDown => app.scroll_object_watch(true, 20),
+            KeyCode::Home | KeyCode::Char('g') => app.scroll_object_watch(false, usize::MAX),
+            _ => {}
+        }
+    } else if app.cloud_init.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.close_cloud_init(),
+            KeyCode::Up => app.cloud_init_scroll(false, 1),
+            KeyCode::Down => app.cloud_init_scroll(true, 1),
+            KeyCode::PageUp => app.cloud_init_scroll(false, 20),
+            KeyCode::PageDown => app.cloud_init_scroll(true, 20),
+            KeyCode::Char('e') if !app.read_only && !app.lacks(&[Permission::PatchVms]) => return Command::EditCloudInit,
+            _ => {}
+        }
+    } else if app.log_context.is_some() {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter) {
+            app.close_log_context();
+        }
+    } else if app.log_detail.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => app.close_log_detail(),
+            KeyCode::Up | KeyCode::Char('k') => app.scroll_log_detail(false, 1),
+            KeyCode::Down | KeyCode::Char('j') => app.scroll_log_detail(true, 1),
+            KeyCode::PageUp => app.scroll_log_detail(false, 10),
+            KeyCode::PageDown => app.scroll_log_detail(true, 10),
+            KeyCode::Char('c') => return Command::Run(Task::OpenLogDetailContext),
+            KeyCode::Char('u') => app.filter_log_detail(false),
+            KeyCode::Char('p') => app.filter_log_detail(true),
+            KeyCode::Char('y') => {
+                app.push_message(MessageLevel::Info, "Log line copied");
+                return Command::CopyLogLine;
+            }
+            _ => {}
+        }
+    } else if app.log_services.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('u') => app.close_log_services(),
+            KeyCode::Up => app.log_services_navigate(false),
+            KeyCode::Down => app.log_services_navigate(true),
+            KeyCode::Char(' ') | KeyCode::Enter => return Command::Run(Task::ToggleLogService),
+            _ => {}
+        }
+    } else if app.log_range_prompt.is_some() {
+        match key.code {
+            KeyCode::Esc => app.close_log_range_prompt(),
+            KeyCode::Enter => app.confirm_log_range(),
+            KeyCode::Tab | KeyCode::BackTab => {
+                if let Some(prompt) = app.log_range_prompt.as_mut() {
+                    prompt.switch_field();
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(prompt) = app.log_range_prompt.as_mut() {
+                    prompt.backspace();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(prompt) = app.log_range_prompt.as_mut() {
+                    prompt.input(c);
+                }
+            }
+            _ => {}
+        }
+    } else if let Some(search) = app.log_search.as_mut() {
+        if search.editing {
+            match key.code {
+                KeyCode::Esc => app.close_log_search(),
+                KeyCode::Enter => app.run_log_search(),
+                KeyCode::Backspace => search.backspace(),
+                KeyCode::Char(c) => search.input(c),
+                _ => {}
+            }
+        } else {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => app.close_log_search(),
+                KeyCode::Up | KeyCode::Char('k') => search.navigate(false),
+                KeyCode::Down | KeyCode::Char('j') => search.navigate(true),
+                KeyCode::Enter => return Command::Run(Task::OpenLogSearchContext),
+                KeyCode::Char('/') => search.editing = true,
+                KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => search.editing = true,
+                _ => {}
+            }
+        }
+    } else if app.search_active {
+        match key.code {
+            KeyCode::Esc => app.finish_search(false),
+            KeyCode::Enter => app.finish_search(true),
+            KeyCode::Backspace => app.search_backspace(),
+            KeyCode::Char(c) => app.search_input(c),
+            _ => {}
+        }
+    } else if app.resource_input.is_some() {
+        match key.code {
+            KeyCode::Esc => app.finish_resource_input(false),
+            KeyCode::Enter => app.finish_resource_input(true),
+            KeyCode::Backspace => app.resource_input_backspace(),
+            KeyCode::Char(c) => app.resource_input_char(c),
+            _ => {}
+        }
+    } else if app.vm_clone.is_some() {
+        match key.code {
+            KeyCode::Esc => app.close_vm_clone(),
+            KeyCode::Enter => return Command::Run(Task::SubmitVmClone),
+            KeyCode::Tab => app.toggle_vm_clone_disks(),
+            KeyCode::Backspace => app.vm_clone_backspace(),
+            KeyCode::Char(c) => app.vm_clone_input(c),
+            _ => {}
+        }
+    } else if app.cpu_tuning.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.close_cpu_tuning(),
+            KeyCode::Up => app.cpu_tuning_navigate(false),
+            KeyCode::Down => app.cpu_tuning_navigate(true),
+            KeyCode::Char(' ') | KeyCode::Enter | KeyCode::Left | KeyCode::Right => {
+                app.cpu_tuning_change()
+            }
+            KeyCode::Char('a') => return Command::Run(Task::ApplyCpuTuning),
+            _ => {}
+        }
+    } else if app.hotplug.is_some() {
+        match key.code {
+            KeyCode::Esc => app.close_hotplug(),
+            KeyCode::Up => app.hotplug_navigate(false),
+            KeyCode::Down | KeyCode::Tab => app.hotplug_navigate(true),
+            KeyCode::Left => app.hotplug_adjust(false),
+            KeyCode::Right => app.hotplug_adjust(true),
+            KeyCode::Enter => return Command::Run(Task::ApplyHotplug),
+            KeyCode::Backspace => app.hotplug_backspace(),
+            KeyCode::Char(c) => app.hotplug_input(c),
+            _ => {}
+        }
+    } else if let Some(editor) = app.node_editor.as_ref() {
+        if editor.input.is_some() {
+            match key.code {
+                KeyCode::Esc => app.node_editor_cancel_input(),
+                KeyCode::Enter => return Command::Run(Task::SubmitNodeEditorInput),
+                KeyCode::Backspace => app.node_editor_backspace(),
+                KeyCode::Char(c) => app.node_editor_input(c),
+                _ => {}
+            }
+        } else {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => app.close_node_editor(),
+                KeyCode::Up => app.node_editor_navigate(false),
+                KeyCode::Down => app.node_editor_navigate(true),
+                KeyCode::Char('l') => app.node_editor_start_input(NodeEditField::Label),
+                KeyCode::Char('t') => app.node_editor_start_input(NodeEditField::Taint),
+                KeyCode::Char('d') | KeyCode::Delete => return Command::Run(Task::RemoveNodeEditorSelected),
+                _ => {}
+            }
+        }
+    } else if app.layout_edit.is_some() && app.current_screen == Screen::Dashboard {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('L') => app.toggle_layout_edit(),
+            KeyCode::Up if shift => app.layout_move_card(-1, 0),
+            KeyCode::Down if shift => app.layout_move_card(1, 0),
+            KeyCode::Left if shift => app.layout_move_card(0, -1),
+            KeyCode::Right if shift => app.layout_move_card(0, 1),
+            KeyCode::Up => app.layout_navigate(-1, 0),
+            KeyCode::Down => app.layout_navigate(1, 0),
+            KeyCode::Left => app.layout_navigate(0, -1),
+            KeyCode::Right => app.layout_navigate(0, 1),
+            KeyCode::Char('+') | KeyCode::Char('=') => app.layout_resize_card(true),
+            KeyCode::Char('-') => app.layout_resize_card(false),
+            KeyCode::Char(']') => app.layout_resize_row(true),
+            KeyCode::Char('[') => app.layout_resize_row(false),
+            KeyCode::Tab => app.layout_cycle_widget(),
+            KeyCode::Char('n') => app.layout_add_card(false),
+            KeyCode::Char('N') => app.layout_add_card(true),
+            KeyCode::Char('d') => app.layout_remove_card(),
+            KeyCode::Char('s') => app.save_layout(),
+            _ => {}
+        }
+    } else if app.vm_detail.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => app.close_vm_detail(),
+            KeyCode::Char('c') => app.toggle_vm_console(),
+            KeyCode::Char('v') => app.toggle_vnc_forward(),
+            KeyCode::Up | KeyCode::Char('k') => app.scroll_vm_console(true, 1),
+            KeyCode::Down | KeyCode::Char('j') => app.scroll_vm_console(false, 1),
+            KeyCode::PageUp => app.scroll_vm_console(true, 10),
+            KeyCode::PageDown => app.scroll_vm_console(false, 10),
+            KeyCode::End | KeyCode::Char('G') => app.scroll_vm_console(false, usize::MAX),
+            _ => {}
+        }
+    } else if app.compare.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('c') => app.close_compare(),
+            KeyCode::Left => app.step_compare(true),
+            KeyCode::Right => app.step_compare(false),
+            _ => {}
+        }
+    } else if app.fleet_detail.is_some() {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter) {
+            app.close_fleet_detail();
+        }
+    } else if app.update_popup {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter) {
+            app.update_popup = false;
+        }
+    } else if app.alert_stats_open {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('s')) {
+            app.toggle_alert_stats();
+        }
+    } else if app.alert_panel_open {
+        // Actions apply to the alert in the detail view when it is open
+        let motion = match app.alert_detail {
+            Some(_) => None,
+            None => app.navigator.motion(&key, pending),
+        };
+        if let Some(motion) = motion {
+            app.alert_navigate(motion);
+            return Command::Redraw;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter if app.alert_detail.is_some() => app.close_alert_detail(),
+            KeyCode::Esc => app.toggle_alert_panel(),
+            KeyCode::Enter => app.open_alert_detail(),
+            KeyCode::Char('a') => app.acknowledge_selected_alert(),
+            KeyCode::Char('d') => app.dismiss_selected_alert(),
+            KeyCode::Char('z') => app.snooze_selected_alert(),
+            KeyCode::Char('D') if app.alert_detail.is_none() => app.dismiss_all_alerts(),
+            KeyCode::Char('s') if app.alert_detail.is_none() => app.toggle_alert_stats(),
+            KeyCode::Char('p') => return Command::Run(Task::JumpToAlertPod),
+            KeyCode::Char('l') => app.show_alert_logs(),
+            KeyCode::Char('o') => match app.selected_alert_runbook() {
+                Some(url) => {
+                    app.push_message(MessageLevel::Info, format!("Runbook (copied): {}", url));
+                    return Command::CopyRunbook;
+                }
+                None => app.push_message(MessageLevel::Info, "No runbook configured for this alert"),
+            },
+            _ => {}
+        }
+    } else {
+        // ↑/↓ and j/k (with a count, faster while held), gg and G move
+        // through the screen's list; the heatmap moves in two dimensions
+        let motion = match app.current_screen {
+            Screen::Heatmap => None,
+            _ => app.navigator.motion(&key, pending),
+        };
+        if let Some(motion) = motion {
+            app.navigate(motion);
+            return Command::Redraw;
+        }
+
+        // Normal navigation
+        match key.code {
+            // Esc clears toasts first so dismissing one doesn't quit
+            KeyCode::Esc if !app.messages.is_empty() => app.dismiss_messages(),
+            // Esc on an archived range goes back to the live tail first
+            KeyCode::Esc if app.current_screen == Screen::Logs && app.log_archive.is_some() => {
+                app.close_log_archive()
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return Command::Quit,
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => app.open_palette(),
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => app.open_log_search(),
+            KeyCode::Char(c @ '1'..='9') if key.modifiers.contains(KeyModifiers::ALT) => {
+                return Command::Run(Task::ApplyView(c as usize - '1' as usize))
+            }
+            KeyCode::F(1) => app.current_screen = Screen::Logs,
+            KeyCode::F(2) => app.current_screen = Screen::Dashboard,
+            KeyCode::F(3) => app.current_screen = Screen::Network,
+            KeyCode::F(4) => app.current_screen = Screen::Resources,
+            KeyCode::F(5) => app.current_screen = Screen::Timeline,
+            KeyCode::F(6) => app.current_screen = Screen::Charts,
+            KeyCode::F(7) => app.current_screen = Screen::Heatmap,
+            KeyCode::F(8) => app.current_screen = Screen::Fleet,
+            KeyCode::F(9) => app.current_screen = Screen::HostMap,
+            KeyCode::F(10) => app.current_screen = Screen::Tools,
+            KeyCode::Tab if app.current_screen == Screen::HostMap => return Command::Run(Task::ToggleHostMapView),
+            KeyCode::Char('D') if app.current_screen == Screen::HostMap && app.host_map_view == HostMapView::Images => {
+                app.request_disk_delete()
+            }
+            KeyCode::Char('C') if app.current_screen == Screen::HostMap && app.host_map_view == HostMapView::Images => {
+                return Command::Run(Task::OpenImageCatalog)
+            }
+            // Only qemu processes belong to a VM with a detail view
+            KeyCode::Enter if app.current_screen == Screen::HostMap && app.host_map_view == HostMapView::Processes => {
+                return Command::Run(Task::OpenHostMapVmDetail)
+            }
+            KeyCode::Enter if app.current_screen == Screen::HostMap => {}
+            KeyCode::Char('L') if app.current_screen == Screen::HostMap => app.toggle_local_mode(),
+            KeyCode::Char('p') if app.current_screen == Screen::HostMap && app.host_map_view == HostMapView::Processes => {
+                app.request_qemu_signal(QemuSignal::Pause)
+            }
+            KeyCode::Char('K') if app.current_screen == Screen::HostMap && app.host_map_view == HostMapView::Processes => {
+                app.request_qemu_signal(QemuSignal::Terminate)
+            }
+            KeyCode::Char('s') if app.current_screen == Screen::Fleet => app.next_fleet_sort(),
+            KeyCode::Char('o') if app.current_screen == Screen::Fleet => app.reverse_fleet_sort(),
+            KeyCode::Enter if app.current_screen == Screen::Fleet => app.open_fleet_node(),
+            KeyCode::Char('E') if app.current_screen == Screen::Fleet => app.open_fleet_evacuation(),
+            KeyCode::Char('c') if app.current_screen == Screen::Fleet => app.mark_fleet_compare(),
+            KeyCode::Char('c') if app.current_screen == Screen::Dashboard => app.open_history_compare(),
+            KeyCode::Left if app.current_screen == Screen::Charts => app.chart_cursor_left(),
+            KeyCode::Right if app.current_screen == Screen::Charts => app.chart_cursor_right(),
+            KeyCode::Char('+') | KeyCode::Char('=') if app.current_screen == Screen::Charts => {
+                app.chart_zoom_in()
+            }
+            KeyCode::Char('-') if app.current_screen == Screen::Charts => app.chart_zoom_out(),
+            KeyCode::Tab if app.current_screen == Screen::Charts => app.next_chart_metric(),
+            KeyCode::Char('m') if app.current_screen == Screen::Heatmap => app.toggle_heatmap_metric(),
+            KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right
+                if app.current_screen == Screen::Heatmap =>
+            {
+                let columns = ui::heatmap::columns_for(app.terminal_width);
+                match key.code {
+                    KeyCode::Up => app.heatmap_move(-1, 0, columns),
+                    KeyCode::Down => app.heatmap_move(1, 0, columns),
+                    KeyCode::Left => app.heatmap_move(0, -1, columns),
+                    _ => app.heatmap_move(0, 1, columns),
+                }
+            }
+            KeyCode::Enter if app.current_screen == Screen::Heatmap => return Command::Run(Task::OpenHeatmapVmDetail),
+            KeyCode::Tab | KeyCode::Right if app.current_screen == Screen::Resources => return Command::Run(Task::NextResourceKind),
+            KeyCode::BackTab | KeyCode::Left if app.current_screen == Screen::Resources => return Command::Run(Task::PrevResourceKind),
+            KeyCode::Char('y') if app.current_screen == Screen::Resources => {
+                return Command::Run(Task::OpenYamlInspector)
+            }
+            KeyCode::Char('w') if app.current_screen == Screen::Resources => app.open_object_watch(),
+            KeyCode::Enter if app.current_screen == Screen::Resources => {
+                return Command::Run(Task::OpenVmDetail)
+            }
+            KeyCode::Char('c') if app.current_screen == Screen::Resources && !app.read_only => {
+                return Command::Run(Task::OpenCpuTuning)
+            }
+            KeyCode::Char('C') if app.current_screen == Screen::Resources && !app.read_only => {
+                return Command::Run(Task::OpenVmClone)
+            }
+            KeyCode::Char('h') if app.current_screen == Screen::Resources && !app.read_only => {
+                return Command::Run(Task::OpenHotplug)
+            }
+            KeyCode::Char('t') if app.current_screen == Screen::Resources => return Command::Run(Task::OpenNodeEditor),
+            KeyCode::Char('i') if app.current_screen == Screen::Resources => return Command::Run(Task::OpenCloudInit),
+            KeyCode::Char(' ') if app.current_screen == Screen::Resources && !app.read_only => {
+                app.toggle_vm_mark()
+            }
+            KeyCode::Char('*') if app.current_screen == Screen::Resources && !app.read_only => {
+                app.mark_all_vms()
+            }
+            KeyCode::Char('b') if app.current_screen == Screen::Resources => app.open_batch_prompt(),
+            KeyCode::Char('/') if app.current_screen == Screen::Resources => {
+                app.start_resource_input(ResourceInput::Selector)
+            }
+            KeyCode::Char('l') if app.current_screen == Screen::Resources => {
+                app.start_resource_input(ResourceInput::GroupBy)
+            }
+            KeyCode::Char('L') if app.current_screen == Screen::Dashboard => app.toggle_layout_edit(),
+            KeyCode::Char('x') if app.current_screen == Screen::Logs => app.toggle_log_collapse(),
+            KeyCode::Char('w') if app.current_screen == Screen::Logs => app.toggle_log_wrap(),
+            KeyCode::Left if app.current_screen == Screen::Logs => app.scroll_log_columns(false),
+            KeyCode::Right if app.current_screen == Screen::Logs => app.scroll_log_columns(true),
+            KeyCode::Char('/') if app.current_screen == Screen::Logs => app.start_search(),
+            KeyCode::Enter if app.focused_pane() == Some(Pane::NoisySources) => app.filter_noisy_source(),
+            KeyCode::Enter if app.current_screen == Screen::Logs => return Command::Run(Task::OpenLogDetail),
+            KeyCode::Char('u') if app.current_screen == Screen::Logs => return Command::Run(Task::OpenLogServices),
+            KeyCode::Char('H') if app.current_screen == Screen::Logs => app.open_log_range_prompt(),
+            KeyCode::Tab if app.visible_panes().len() > 1 => app.cycle_pane(true),
+            KeyCode::BackTab if app.visible_panes().len() > 1 => app.cycle_pane(false),
+            KeyCode::Char('a') => app.toggle_alert_panel(),
+            KeyCode::Char('U') => app.open_upgrade_prompt(),
+            KeyCode::Char('N') => return Command::Run(Task::OpenNixStore),
+            KeyCode::Char('T') => app.start_selftest(),
+            KeyCode::Char('R') => {
+                app.write_report();
+                return Command::CopyReport;
+            }
+            KeyCode::Char('r') => return Command::Run(Task::Refresh),
+            _ => {}
+        }
+    }
+    Command::Redraw
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::snapshot::fixture_app;
+
+    fn press(app: &mut App, code: KeyCode) -> Command {
+        update(app, Msg::KeyPressed(KeyEvent::new(code, KeyModifiers::NONE)))
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    #[tokio::test]
+    async fn list_navigation() {
+        let mut app = fixture_app().await;
+        app.current_screen = Screen::Logs;
+        let offset = |app: &App| app.panes.offset(Pane::Logs);
+
+        assert_eq!(press(&mut app, KeyCode::Char('j')), Command::Redraw);
+        assert_eq!(offset(&app), 1);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(offset(&app), 2);
+        press(&mut app, KeyCode::Char('k'));
+        assert_eq!(offset(&app), 1);
+        press(&mut app, KeyCode::Char('G'));
+        assert_eq!(offset(&app), 3);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(offset(&app), 2);
+        type_text(&mut app, "gg");
+        assert_eq!(offset(&app), 0);
+        // A count moves to that row
+        type_text(&mut app, "3G");
+        assert_eq!(offset(&app), 2);
+    }
+
+    #[tokio::test]
+    async fn log_filter() {
+        let mut app = fixture_app().await;
+        app.current_screen = Screen::Logs;
+
+        press(&mut app, KeyCode::Char('/'));
+        assert!(app.search_active);
+        // Typing goes to the query, not to the screen's keys
+        type_text(&mut app, "containerq");
+        press(&mut app, KeyCode::Backspace);
+        type_text(&mut app, "d");
+        assert_eq!(app.search_query, "containerd");
+        assert_eq!(app.get_displayed_logs().len(), 1);
+
+        // Enter keeps the query as a filter
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.search_active);
+        assert_eq!(app.get_displayed_logs().len(), 1);
+
+        // Esc while typing drops it
+        press(&mut app, KeyCode::Char('/'));
+        type_text(&mut app, "x");
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.search_active);
+        assert!(app.search_query.is_empty());
+        assert_eq!(app.get_displayed_logs().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn alert_panel() {
+        let mut app = fixture_app().await;
+        app.current_screen = Screen::Dashboard;
+        assert_eq!(app.alert_manager.active_count(), 2);
+
+        press(&mut app, KeyCode::Char('a'));
+        assert!(app.alert_panel_open);
+        assert_eq!(app.alert_selected_index, 0);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.alert_selected_index, 1);
+        press(&mut app, KeyCode::Char('k'));
+        assert_eq!(app.alert_selected_index, 0);
+
+        // Enter opens the selected alert, Esc goes back to the list
+        let first = app.selected_alert().map(|alert| alert.id.clone());
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.alert_detail, first);
+        // The list doesn't move under an open detail view
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.alert_selected_index, 0);
+        press(&mut app, KeyCode::Esc);
+        assert!(app.alert_detail.is_none());
+        assert!(app.alert_panel_open);
+
+        press(&mut app, KeyCode::Char('d'));
+        assert_eq!(app.alert_manager.active_count(), 1);
+
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.alert_panel_open);
+    }
+
+    #[tokio::test]
+    async fn io_is_left_to_commands() {
+        let mut app = fixture_app().await;
+        assert_eq!(update(&mut app, Msg::Tick), Command::Run(Task::Collect));
+
+        app.current_screen = Screen::Logs;
+        assert_eq!(press(&mut app, KeyCode::Enter), Command::Run(Task::OpenLogDetail));
+        assert_eq!(press(&mut app, KeyCode::Char('r')), Command::Run(Task::Refresh));
+
+        let error = anyhow::anyhow!("connection refused");
+        assert_eq!(update(&mut app, Msg::MetricsUpdated { elapsed: Duration::ZERO, result: Err(error) }), Command::Redraw);
+        assert!(app.messages.iter().any(|message| message.text == "Update failed: connection refused"));
+    }
+}
@@ -0,0 +1,187 @@
+use crate::config::UpgradeConfig;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+/// Profile nixos-rebuild registers new generations in
+const SYSTEM_PROFILE: &str = "/nix/var/nix/profiles/system";
+/// The configuration that is actually active
+const CURRENT_SYSTEM: &str = "/run/current-system";
+
+/// Output lines kept for the Upgrade panel; everything also goes to the log viewer
+const MAX_OUTPUT_LINES: usize = 500;
+
+/// How a rebuild installs the new generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeAction {
+    /// Build, make it the boot default and activate it now
+    Switch,
+    /// Build and make it the boot default, activating on next reboot
+    Boot,
+}
+
+impl UpgradeAction {
+    pub fn arg(&self) -> &'static str {
+        match self {
+            UpgradeAction::Switch => "switch",
+            UpgradeAction::Boot => "boot",
+        }
+    }
+}
+
+/// Full command line for an action: the configured command, the action,
+/// `--flake` when set and any extra arguments
+pub fn upgrade_command(config: &UpgradeConfig, action: UpgradeAction) -> Vec<String> {
+    let mut args = config.command.clone();
+    args.push(action.arg().to_string());
+    if let Some(flake) = &config.flake {
+        args.push("--flake".to_string());
+        args.push(flake.clone());
+    }
+    args.extend(config.extra_args.iter().cloned());
+    args
+}
+
+enum UpgradeEvent {
+    /// An output line, and whether it came from stderr
+    Line(String, bool),
+    /// Ok(summary) once the new generation is in place, Err(reason) otherwise
+    Finished(Result<String, String>),
+}
+
+/// A running (or finished) nixos-rebuild and the output it produced
+pub struct UpgradeRun {
+    pub action: UpgradeAction,
+    pub command: String,
+    pub output: Vec<String>,
+    pub outcome: Option<Result<String, String>>,
+    /// Panel dismissed while the rebuild is still running
+    pub hidden: bool,
+    rx: mpsc::UnboundedReceiver<UpgradeEvent>,
+}
+
+impl UpgradeRun {
+    /// Spawn the rebuild. It is not killed if the TUI exits, since
+    /// interrupting an activation halfway is worse than letting it finish.
+    pub fn start(config: &UpgradeConfig, action: UpgradeAction) -> Result<Self, String> {
+        let args = upgrade_command(config, action);
+        let Some((program, rest)) = args.split_first() else {
+            return Err("upgrade.command is empty".to_string());
+        };
+
+        let mut child = Command::new(program)
+            .args(rest)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let stdout = child.stdout.take().map(|out| forward_lines(BufReader::new(out), false, tx.clone()));
+        let stderr = child.stderr.take().map(|err| forward_lines(BufReader::new(err), true, tx.clone()));
+        let before = generation(SYSTEM_PROFILE);
+
+        tokio::spawn(async move {
+            // Drain output before reporting so the last lines aren't lost
+            for task in [stdout, stderr].into_iter().flatten() {
+                let _ = task.await;
+            }
+            let outcome = match child.wait().await {
+                Ok(status) if status.success() => check_generation(action, before),
+                Ok(status) => Err(format!("nixos-rebuild {} exited with {}", action.arg(), status)),
+                Err(e) => Err(format!("Failed to wait for nixos-rebuild: {}", e)),
+            };
+            let _ = tx.send(UpgradeEvent::Finished(outcome));
+        });
+
+        Ok(Self {
+            action,
+            command: args.join(" "),
+            output: Vec::new(),
+            outcome: None,
+            hidden: false,
+            rx,
+        })
+    }
+
+    /// Take in new output, returning the lines that arrived (with whether
+    /// each is from stderr) so they can be forwarded to the log viewer
+    pub fn poll(&mut self) -> Vec<(String, bool)> {
+        let mut lines = Vec::new();
+        while let Ok(event) = self.rx.try_recv() {
+            match event {
+                UpgradeEvent::Line(line, stderr) => {
+                    if self.output.len() >= MAX_OUTPUT_LINES {
+                        self.output.remove(0);
+                    }
+                    self.output.push(line.clone());
+                    lines.push((line, stderr));
+                }
+                UpgradeEvent::Finished(outcome) => self.outcome = Some(outcome),
+            }
+        }
+        lines
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+}
+
+fn forward_lines<R>(reader: R, stderr: bool, tx: mpsc::UnboundedSender<UpgradeEvent>) -> tokio::task::JoinHandle<()>
+where
+    R: AsyncBufRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut lines = reader.lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tx.send(UpgradeEvent::Line(line, stderr)).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Generation number the system profile points at ("system-42-link" -> 42)
+fn generation(profile: &str) -> Option<u64> {
+    let target = std::fs::read_link(profile).ok()?;
+    let name = target.file_name()?.to_str()?;
+    name.strip_prefix("system-")?.strip_suffix("-link")?.parse().ok()
+}
+
+/// A zero exit isn't proof the new generation is live: after a switch the
+/// running system must be the one the profile points at
+fn check_generation(action: UpgradeAction, before: Option<u64>) -> Result<String, String> {
+    let after = generation(SYSTEM_PROFILE);
+    let change = match (before, after) {
+        (Some(before), Some(after)) if before != after => format!("generation {} -> {}", before, after),
+        (_, Some(after)) => format!("generation {} (unchanged)", after),
+        _ => "generation unknown".to_string(),
+    };
+
+    if action == UpgradeAction::Switch {
+        let profile = std::fs::canonicalize(SYSTEM_PROFILE);
+        let current = std::fs::canonicalize(CURRENT_SYSTEM);
+        if let (Ok(profile), Ok(current)) = (profile, current) {
+            if profile != current {
+                return Err(format!(
+                    "{} is active instead of {}; the new generation failed to activate",
+                    display_store_path(&current),
+                    display_store_path(&profile)
+                ));
+            }
+        }
+        Ok(format!("Switched to {}", change))
+    } else {
+        Ok(format!("Boot default set to {}, active after reboot", change))
+    }
+}
+
+fn display_store_path(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
@@ -0,0 +1,1185 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+use crate::alert_profiles::{profile_name_problem, AlertProfiles};
+use crate::collectors::check_selector;
+use crate::computed::{Expr, VARIABLES};
+use crate::config::{AlertsConfig, Config, HeaderWidget, VirtBackend};
+use crate::highlight::LogHighlighter;
+use crate::image_catalog::parse_checksum;
+use crate::log_forward::forward_problem;
+use crate::notify::Channel;
+use crate::secrets::Provider;
+use crate::redact::LogRedactor;
+use crate::theme::parse_color;
+use crate::timestamps::{time_zone_problem, timestamp_format_problem};
+use crate::types::{object_name_problem, page_size_kb, LabelSelector, SysctlExpectation};
+
+/// Top-level tables understood by `Config`
+const SECTIONS: [&str; 31] = [
+    "general",
+    "kubernetes",
+    "logging",
+    "network",
+    "storage",
+    "image_catalog",
+    "display",
+    "dashboard",
+    "alerts",
+    "health",
+    "notifications",
+    "secrets",
+    "web",
+    "receiver",
+    "agent",
+    "remote_agents",
+    "upgrade",
+    "update_check",
+    "sysctl",
+    "report",
+    "selftest",
+    "tenants",
+    "compare",
+    "crashes",
+    "host_profile",
+    "memory_leaks",
+    "virtualization",
+    "prometheus",
+    "custom_commands",
+    "computed_metrics",
+    "views",
+];
+
+/// Guard against pathological files; each retry drops one bad key
+const MAX_PARSE_RETRIES: usize = 64;
+
+const LEVEL_FILTERS: [&str; 4] = ["ERROR", "WARN", "INFO", "DEBUG"];
+const SMTP_SECURITY: [&str; 3] = ["starttls", "tls", "none"];
+const NOTIFY_LEVELS: [&str; 3] = ["critical", "error", "warning"];
+const ALERT_LEVELS: [&str; 4] = ["critical", "error", "warning", "info"];
+const ALERT_CATEGORIES: [&str; 6] = ["system", "network", "kubernetes", "kubevirt", "service", "external"];
+
+/// One problem found in the config file
+#[derive(Debug, Clone)]
+pub struct ConfigProblem {
+    /// File the key was set in, when the config was merged from several
+    pub file: Option<PathBuf>,
+    /// Dotted key, e.g. "alerts.cpu_warning_threshold"
+    pub key: String,
+    /// 1-based line in the config file, when it can be located
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref file) = self.file {
+            write!(f, "{}: ", file.display())?;
+        }
+        match self.line {
+            Some(line) => write!(f, "line {}: {}: {}", line, self.key, self.message),
+            None => write!(f, "{}: {}", self.key, self.message),
+        }
+    }
+}
+
+/// Everything wrong with the loaded config, shown at startup
+#[derive(Debug, Clone, Default)]
+pub struct ConfigReport {
+    /// Files the config was merged from, lowest priority first; empty when
+    /// running on defaults
+    pub sources: Vec<PathBuf>,
+    pub problems: Vec<ConfigProblem>,
+}
+
+impl ConfigReport {
+    pub fn is_clean(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// "a.toml + b.toml", or `defaults` without a config file
+    pub fn describe_sources(&self, defaults: &str) -> String {
+        if self.sources.is_empty() {
+            return defaults.to_string();
+        }
+        self.sources.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(" + ")
+    }
+}
+
+/// Parse a config file, keeping every valid setting. Syntax errors fall
+/// back to defaults for the whole file; a bad value only resets that key.
+pub fn parse(contents: &str) -> (Config, Vec<ConfigProblem>) {
+    let mut problems = Vec::new();
+
+    let mut table: toml::Table = match toml::from_str(contents) {
+        Ok(table) => table,
+        Err(e) => {
+            let line = e.span().map(|span| line_of_offset(contents, span.start));
+            problems.push(ConfigProblem {
+                file: None,
+                key: "(file)".to_string(),
+                line,
+                message: format!("{}; using built-in defaults for everything", one_line(e.message())),
+            });
+            return (Config::default(), problems);
+        }
+    };
+
+    let unknown: Vec<String> = table.keys().filter(|k| !SECTIONS.contains(&k.as_str())).cloned().collect();
+    for key in unknown {
+        table.remove(&key);
+        problems.push(ConfigProblem {
+            file: None,
+            line: locate(contents, &key),
+            message: "unknown section, ignored".to_string(),
+            key,
+        });
+    }
+
+    for _ in 0..MAX_PARSE_RETRIES {
+        match Config::deserialize(toml::Value::Table(table.clone())) {
+            Ok(config) => return (config, problems),
+            Err(e) => {
+                let (message, key) = split_error(&e.to_string());
+                // Errors without a key (e.g. a missing required field) are
+                // pinned to a section by parsing each one on its own
+                let key = if key.is_empty() { failing_section(&table).unwrap_or_default() } else { key };
+                let Some(removed) = remove_key(&mut table, &key) else {
+                    break;
+                };
+                problems.push(ConfigProblem {
+                    file: None,
+                    line: locate(contents, &removed),
+                    message: format!("{}; using the default", message),
+                    key: removed,
+                });
+            }
+        }
+    }
+
+    problems.push(ConfigProblem {
+        file: None,
+        key: "(file)".to_string(),
+        line: None,
+        message: "could not recover from errors; using built-in defaults for everything".to_string(),
+    });
+    (Config::default(), problems)
+}
+
+/// Range, enum and path checks on a parsed config
+pub fn validate(config: &Config, contents: &str) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    let mut problem = |key: &str, message: String| {
+        problems.push(ConfigProblem {
+            file: None,
+            key: key.to_string(),
+            line: locate(contents, key),
+            message,
+        });
+    };
+
+    if config.general.refresh_interval == 0 {
+        problem("general.refresh_interval", "must be at least 1 second".to_string());
+    }
+    if config.general.collector_timeout_secs == 0 {
+        problem("general.collector_timeout_secs", "must be at least 1 second; 1 is used".to_string());
+    }
+    if config.general.log_buffer_size == 0 {
+        problem("general.log_buffer_size", "must be at least 1 line".to_string());
+    }
+    if config.general.log_buffer_max_mb == 0 {
+        problem("general.log_buffer_max_mb", "must be at least 1 MiB".to_string());
+    }
+
+    if let Some(ref path) = config.kubernetes.kubeconfig_path {
+        check_file(&mut problem, "kubernetes.kubeconfig_path", path);
+    }
+    for service in &config.kubernetes.watched_services {
+        if service.split('/').filter(|part| !part.is_empty()).count() != 2 {
+            problem(
+                "kubernetes.watched_services",
+                format!("{:?} should be \"namespace/name\"", service),
+            );
+        }
+    }
+
+    if config.kubernetes.evacuation.concurrency == 0 {
+        problem("kubernetes.evacuation.concurrency", "must be at least 1 migration".to_string());
+    }
+    if config.kubernetes.evacuation.stuck_secs == 0 {
+        problem("kubernetes.evacuation.stuck_secs", "must be greater than 0".to_string());
+    }
+    if let Some(ref url) = config.kubernetes.etcd.metrics_url {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            problem("kubernetes.etcd.metrics_url", format!("{:?} should be an http:// or https:// URL", url));
+        }
+    }
+
+    if !LEVEL_FILTERS.contains(&config.logging.level_filter.to_uppercase().as_str()) {
+        problem(
+            "logging.level_filter",
+            format!("{:?} is not one of {}", config.logging.level_filter, LEVEL_FILTERS.join(", ")),
+        );
+    }
+    if config.logging.archive_max_lines == 0 {
+        problem("logging.archive_max_lines", "must be at least 1 line".to_string());
+    }
+    if config.logging.search_lines == 0 {
+        problem("logging.search_lines", "must be at least 1 line".to_string());
+    }
+    let (_, highlight_errors) = LogHighlighter::new(&config.logging.highlights);
+    for error in highlight_errors {
+        problem("logging.highlights", one_line(&error));
+    }
+    let (_, redaction_errors) = LogRedactor::new(&config.logging.redactions);
+    for error in redaction_errors {
+        problem("logging.redactions", one_line(&error));
+    }
+    for (i, forward) in config.logging.forward.iter().enumerate() {
+        if let Some(error) = forward_problem(forward) {
+            problem("logging.forward", format!("sink #{}: {}", i + 1, error));
+        }
+    }
+
+    for (table, names, styles) in [
+        ("levels", &ALERT_LEVELS[..], &config.display.alert_styles.levels),
+        ("categories", &ALERT_CATEGORIES[..], &config.display.alert_styles.categories),
+    ] {
+        for (name, style) in styles {
+            let key = format!("display.alert_styles.{}.{}", table, name);
+            if !names.contains(&name.to_lowercase().as_str()) {
+                problem(&key, format!("{:?} is not one of {}", name, names.join(", ")));
+            }
+            if let Some(ref color) = style.color {
+                if parse_color(color).is_none() {
+                    problem(&format!("{}.color", key), format!("{:?} is not a color name, #rrggbb or 0-255", color));
+                }
+            }
+            if style.icon.as_deref().is_some_and(|icon| icon.trim().is_empty()) {
+                problem(&format!("{}.icon", key), "must not be empty".to_string());
+            }
+        }
+    }
+
+    if let Some(message) = time_zone_problem(&config.display.time_zone) {
+        problem("display.time_zone", message);
+    }
+    if let Some(message) = timestamp_format_problem(&config.display.timestamp_format) {
+        problem("display.timestamp_format", message);
+    }
+    for (i, widget) in config.display.header.iter().enumerate() {
+        match (widget.widget, widget.command.as_deref()) {
+            (HeaderWidget::Command, None) => {
+                problem("display.header.command", format!("widget #{} has nothing to run and is left out", i + 1))
+            }
+            (HeaderWidget::Command, Some(command)) if command.trim().is_empty() => {
+                problem("display.header.command", format!("widget #{} has nothing to run", i + 1))
+            }
+            (HeaderWidget::Command, Some(_)) => {
+                if widget.interval_secs == 0 {
+                    problem("display.header.interval_secs", format!("widget #{} must run at most once a second", i + 1));
+                }
+                if widget.timeout_secs == 0 {
+                    problem("display.header.timeout_secs", format!("widget #{} must be given at least 1 second", i + 1));
+                }
+            }
+            (_, Some(_)) => problem("display.header.command", format!("widget #{} isn't a command widget and won't run it", i + 1)),
+            (_, None) => {}
+        }
+    }
+
+    if config.kubernetes.api.qps < 0.0 {
+        problem("kubernetes.api.qps", "must be 0 (unlimited) or more".to_string());
+    }
+    if config.kubernetes.console.buffer_lines == 0 {
+        problem("kubernetes.console.buffer_lines", "must be greater than 0".to_string());
+    }
+    if config.kubernetes.console.vnc_address.parse::<IpAddr>().is_err() {
+        problem(
+            "kubernetes.console.vnc_address",
+            format!("{:?} is not an IP address", config.kubernetes.console.vnc_address),
+        );
+    }
+    if config.kubernetes.console.vnc_first_port == 0 {
+        problem("kubernetes.console.vnc_first_port", "must be greater than 0".to_string());
+    }
+
+    if config.network.dns_timeout_ms == 0 {
+        problem("network.dns_timeout_ms", "must be greater than 0".to_string());
+    }
+    if config.network.dns_failure_threshold == 0 {
+        problem("network.dns_failure_threshold", "must be at least 1 probe round".to_string());
+    }
+    if config.network.gateway_timeout_ms == 0 {
+        problem("network.gateway_timeout_ms", "must be greater than 0".to_string());
+    }
+    if config.network.gateway_failure_threshold == 0 {
+        problem("network.gateway_failure_threshold", "must be at least 1 ping".to_string());
+    }
+    if config.network.route_flap_threshold == 0 {
+        problem("network.route_flap_threshold", "must be at least 1 change".to_string());
+    }
+    if config.network.route_flap_window_minutes == 0 {
+        problem("network.route_flap_window_minutes", "must be at least 1 minute".to_string());
+    }
+    if config.network.registry_timeout_ms == 0 {
+        problem("network.registry_timeout_ms", "must be greater than 0".to_string());
+    }
+    if config.network.registry_failure_threshold == 0 {
+        problem("network.registry_failure_threshold", "must be at least 1 probe".to_string());
+    }
+    if config.network.vip_timeout_ms == 0 {
+        problem("network.vip_timeout_ms", "must be greater than 0".to_string());
+    }
+    if config.network.vip_failure_threshold == 0 {
+        problem("network.vip_failure_threshold", "must be at least 1 probe".to_string());
+    }
+    for service in &config.network.vip_services {
+        if service.split('/').filter(|part| !part.is_empty()).count() != 2 {
+            problem("network.vip_services", format!("{:?} should be \"namespace/name\"", service));
+        }
+    }
+    for watched in &config.network.watched_ports {
+        if watched.to.is_some_and(|to| to < watched.port) {
+            problem(
+                "network.watched_ports",
+                format!("{:?}: to ({}) is below port ({})", watched.name, watched.to.unwrap_or(0), watched.port),
+            );
+        }
+    }
+    if config.network.image_pull_failure_threshold == 0 {
+        problem("network.image_pull_failure_threshold", "must be at least 1 failed pull".to_string());
+    }
+    for bridge in &config.network.conflict_watch_bridges {
+        // IFNAMSIZ, including the terminating NUL
+        if bridge.is_empty() || bridge.len() > 15 || bridge.contains(['/', ' ']) {
+            problem("network.conflict_watch_bridges", format!("{:?} is not an interface name", bridge));
+        }
+    }
+    for (name, label) in &config.network.interface_labels {
+        if name.is_empty() || name.len() > 15 || name.contains(['/', ' ']) {
+            problem("network.interface_labels", format!("{:?} is not an interface name", name));
+        } else if label.trim().is_empty() {
+            problem("network.interface_labels", format!("{:?} has an empty label", name));
+        }
+    }
+    if config.alerts.migration_failure_threshold == 0 {
+        problem("alerts.migration_failure_threshold", "must be at least 1 failed migration".to_string());
+    }
+    if config.alerts.etcd_leader_changes_per_hour == 0 {
+        problem("alerts.etcd_leader_changes_per_hour", "must be at least 1 leader change".to_string());
+    }
+    if config.alerts.pod_restarts_per_hour == 0 {
+        problem("alerts.pod_restarts_per_hour", "must be at least 1 restart".to_string());
+    }
+    if !(config.alerts.freq_throttle_percent > 0.0 && config.alerts.freq_throttle_percent <= 100.0) {
+        problem("alerts.freq_throttle_percent", "must be a percentage above 0 and at most 100".to_string());
+    }
+    if config.alerts.freq_throttle_mins == 0 {
+        problem("alerts.freq_throttle_mins", "must be at least 1 minute".to_string());
+    }
+    if config.alerts.import_stall_mins == 0 {
+        problem("alerts.import_stall_mins", "must be at least 1 minute".to_string());
+    }
+    if config.alerts.boot_slow_factor <= 1.0 {
+        problem("alerts.boot_slow_factor", "must be above 1, the usual boot time".to_string());
+    }
+    if config.alerts.kernel_event_window_mins == 0 {
+        problem("alerts.kernel_event_window_mins", "must be at least 1 minute".to_string());
+    }
+    if config.alerts.crash_window_mins == 0 {
+        problem("alerts.crash_window_mins", "must be at least 1 minute".to_string());
+    }
+    if config.alerts.ip_conflict_window_mins == 0 {
+        problem("alerts.ip_conflict_window_mins", "must be at least 1 minute".to_string());
+    }
+    if config.alerts.dhcp_churn_threshold < 2 {
+        problem("alerts.dhcp_churn_threshold", "must be at least 2 leases".to_string());
+    }
+    if let Some(ref name) = config.alerts.profile {
+        match profile_name_problem(name) {
+            Some(message) => problem("alerts.profile", message),
+            None => {
+                let path = AlertProfiles::for_config(config).path(name);
+                if !path.is_file() {
+                    problem("alerts.profile", format!("{} does not exist; the [alerts] table is used as is", path.display()));
+                }
+            }
+        }
+    }
+    if config.alerts.related_logs_mins == 0 {
+        problem("alerts.related_logs_mins", "must be at least 1 minute".to_string());
+    }
+    if config.alerts.snooze_mins == 0 {
+        problem("alerts.snooze_mins", "must be at least 1 minute".to_string());
+    }
+    for registry in &config.network.registries {
+        if registry.trim().is_empty() || registry.contains(char::is_whitespace) {
+            problem("network.registries", format!("'{}' is not a registry host or URL", registry));
+        }
+    }
+
+    let alerts = &config.alerts;
+    let global = alert_threshold_problems(alerts);
+    for (key, message) in &global {
+        problem(key, message.clone());
+    }
+    for (i, ov) in alerts.overrides.iter().enumerate() {
+        if ov.hostname.is_none() && ov.labels.is_empty() {
+            problem(
+                "alerts.overrides",
+                format!("override #{} has neither hostname nor labels and never applies", i + 1),
+            );
+        }
+        // Only what the override itself breaks, not the global problems again
+        let mut merged = alerts.clone();
+        merged.apply(ov);
+        for (key, message) in alert_threshold_problems(&merged) {
+            if !global.contains(&(key.clone(), message.clone())) {
+                let key = key.replacen("alerts.", "alerts.overrides.", 1);
+                problem(&key, format!("{} (override #{})", message, i + 1));
+            }
+        }
+    }
+    for (i, runbook) in alerts.runbooks.iter().enumerate() {
+        if runbook.source.is_none() && runbook.title.is_none() {
+            problem(
+                "alerts.runbooks",
+                format!("runbook #{} has neither source nor title and never applies", i + 1),
+            );
+        }
+        if runbook.url.is_none() && runbook.remediation.is_none() {
+            problem("alerts.runbooks", format!("runbook #{} has neither url nor remediation", i + 1));
+        }
+    }
+    for (i, action) in alerts.actions.iter().enumerate() {
+        if action.source.is_none() && action.title.is_none() {
+            problem(
+                "alerts.actions",
+                format!("action #{} has neither source nor title and never applies", i + 1),
+            );
+        }
+        if action.kinds_set() != 1 {
+            problem(
+                "alerts.actions",
+                format!("action #{} needs exactly one of restart_unit, rollout_restart and command", i + 1),
+            );
+        }
+        if let Some(ref target) = action.rollout_restart {
+            if target.split('/').filter(|part| !part.is_empty()).count() != 3 {
+                problem(
+                    "alerts.actions.rollout_restart",
+                    format!("'{}' is not namespace/kind/name (action #{})", target, i + 1),
+                );
+            }
+        }
+        if action.max_per_hour == 0 {
+            problem("alerts.actions.max_per_hour", format!("0 never runs action #{}", i + 1));
+        }
+    }
+    if alerts.anomaly_sigma <= 0.0 {
+        problem("alerts.anomaly_sigma", "must be greater than 0".to_string());
+    }
+    if let Some(parent) = alerts.baseline_path.as_deref().map(Path::new).and_then(Path::parent) {
+        if !parent.as_os_str().is_empty() && !parent.is_dir() {
+            problem("alerts.baseline_path", format!("directory {} does not exist", parent.display()));
+        }
+    }
+
+    let fixed_height: u16 = config.dashboard.rows.iter().filter_map(|r| r.height).sum();
+    if fixed_height > 100 {
+        problem("dashboard.rows", format!("row heights add up to {}%", fixed_height));
+    }
+    for row in &config.dashboard.rows {
+        if !row.widths.is_empty() && row.widths.len() != row.cards.len() {
+            problem(
+                "dashboard.rows",
+                format!("{} widths for {} cards; cards share the row equally", row.widths.len(), row.cards.len()),
+            );
+        }
+    }
+
+    let email = &config.notifications.email;
+    if email.enabled {
+        if email.smtp_server.is_empty() {
+            problem("notifications.email.smtp_server", "required when email is enabled".to_string());
+        }
+        if !SMTP_SECURITY.contains(&email.security.as_str()) {
+            problem(
+                "notifications.email.security",
+                format!("{:?} is not one of {}", email.security, SMTP_SECURITY.join(", ")),
+            );
+        }
+        if let Some(ref path) = email.password_file {
+            check_file(&mut problem, "notifications.email.password_file", path);
+        }
+    }
+
+    let pagerduty = &config.notifications.pagerduty;
+    if pagerduty.enabled {
+        match pagerduty.routing_key_file {
+            Some(ref path) => check_file(&mut problem, "notifications.pagerduty.routing_key_file", path),
+            None if pagerduty.routing_key.is_none() && pagerduty.routing_key_secret.is_none() => problem(
+                "notifications.pagerduty.routing_key",
+                "routing_key, routing_key_file or routing_key_secret is required".to_string(),
+            ),
+            None => {}
+        }
+        check_levels(&mut problem, "notifications.pagerduty.levels", &pagerduty.levels);
+    }
+
+    let alertmanager = &config.notifications.alertmanager;
+    if alertmanager.enabled {
+        if alertmanager.url_secret.is_none()
+            && !alertmanager.url.starts_with("http://")
+            && !alertmanager.url.starts_with("https://")
+        {
+            problem("notifications.alertmanager.url", "must be an http:// or https:// URL".to_string());
+        }
+        if let Some(ref path) = alertmanager.bearer_token_file {
+            check_file(&mut problem, "notifications.alertmanager.bearer_token_file", path);
+        }
+        check_levels(&mut problem, "notifications.alertmanager.levels", &alertmanager.levels);
+    }
+
+    let secrets = &config.secrets;
+    for provider in &secrets.providers {
+        if Provider::parse(provider).is_none() {
+            problem(
+                "secrets.providers",
+                format!("{:?} is not one of {}", provider, Provider::NAMES.join(", ")),
+            );
+        }
+    }
+    if let Some(ref path) = secrets.file {
+        check_file(&mut problem, "secrets.file", path);
+        if path.ends_with(".age") && secrets.age_identity.is_none() {
+            problem("secrets.age_identity", "required to decrypt an .age secrets file".to_string());
+        }
+    }
+    if let Some(ref path) = secrets.age_identity {
+        check_file(&mut problem, "secrets.age_identity", path);
+    }
+    for (key, name) in [
+        ("notifications.email.password_secret", &email.password_secret),
+        ("notifications.pagerduty.routing_key_secret", &pagerduty.routing_key_secret),
+        ("notifications.alertmanager.url_secret", &alertmanager.url_secret),
+        ("notifications.alertmanager.bearer_token_secret", &alertmanager.bearer_token_secret),
+        ("prometheus.bearer_token_secret", &config.prometheus.bearer_token_secret),
+    ] {
+        if name.as_deref().is_some_and(|name| name.trim().is_empty()) {
+            problem(key, "must name a key in [secrets]".to_string());
+        }
+    }
+
+    for (i, route) in config.notifications.routes.iter().enumerate() {
+        for category in &route.categories {
+            if !ALERT_CATEGORIES.contains(&category.to_lowercase().as_str()) {
+                problem(
+                    "notifications.routes.categories",
+                    format!("route #{}: {:?} is not one of {}", i + 1, category, ALERT_CATEGORIES.join(", ")),
+                );
+            }
+        }
+        check_levels(&mut problem, "notifications.routes.levels", &route.levels);
+        for channel in &route.channels {
+            if Channel::parse(channel).is_none() {
+                let names: Vec<&str> = Channel::ALL.iter().map(|channel| channel.as_str()).collect();
+                problem(
+                    "notifications.routes.channels",
+                    format!("route #{}: {:?} is not one of {}", i + 1, channel, names.join(", ")),
+                );
+            }
+        }
+    }
+
+    if config.web.enabled && config.web.listen.parse::<SocketAddr>().is_err() {
+        problem(
+            "web.listen",
+            format!("{:?} is not an address:port such as \"127.0.0.1:8080\"", config.web.listen),
+        );
+    }
+
+    let health = &config.health;
+    if health.metrics_weight + health.alerts_weight + health.services_weight + health.cluster_weight == 0 {
+        problem("health", "at least one weight must be above 0".to_string());
+    }
+    if health.degraded_below > 100 {
+        problem("health.degraded_below", "must be 100 or less".to_string());
+    }
+    if health.critical_below > health.degraded_below {
+        problem("health.critical_below", "must not be above health.degraded_below".to_string());
+    }
+
+    let update_check = &config.update_check;
+    if update_check.enabled {
+        if !update_check.url.starts_with("https://") && !update_check.url.starts_with("http://") {
+            problem("update_check.url", format!("{:?} is not an http(s) URL", update_check.url));
+        }
+        if update_check.interval_hours == 0 {
+            problem("update_check.interval_hours", "must be at least 1 hour".to_string());
+        }
+    }
+
+    let receiver = &config.receiver;
+    if receiver.enabled {
+        if receiver.listen.parse::<SocketAddr>().is_err() {
+            problem(
+                "receiver.listen",
+                format!("{:?} is not an address:port such as \"127.0.0.1:9095\"", receiver.listen),
+            );
+        }
+        if let Some(ref path) = receiver.token_file {
+            check_file(&mut problem, "receiver.token_file", path);
+        }
+        if receiver.expire_mins == 0 {
+            problem("receiver.expire_mins", "must be at least 1 minute".to_string());
+        }
+    }
+
+    let agent = &config.agent;
+    if agent.listen.parse::<SocketAddr>().is_err() {
+        problem("agent.listen", format!("{:?} is not an address:port such as \"0.0.0.0:7443\"", agent.listen));
+    }
+    for (key, path) in [
+        ("agent.cert_file", &agent.cert_file),
+        ("agent.key_file", &agent.key_file),
+        ("agent.client_ca_file", &agent.client_ca_file),
+    ] {
+        if let Some(path) = path {
+            check_file(&mut problem, key, path);
+        }
+    }
+    if agent.interval_secs == 0 {
+        problem("agent.interval_secs", "must be at least 1 second".to_string());
+    }
+
+    let remote_agents = &config.remote_agents;
+    if !remote_agents.endpoints.is_empty() {
+        for (key, path) in [
+            ("remote_agents.cert_file", &remote_agents.cert_file),
+            ("remote_agents.key_file", &remote_agents.key_file),
+            ("remote_agents.ca_file", &remote_agents.ca_file),
+        ] {
+            match path {
+                Some(path) => check_file(&mut problem, key, path),
+                None => problem(key, "is required to reach remote_agents.endpoints".to_string()),
+            }
+        }
+    }
+    for endpoint in &remote_agents.endpoints {
+        if !endpoint.rsplit_once(':').is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok()) {
+            problem("remote_agents.endpoints", format!("{:?} is not host:port such as \"hv-02:7443\"", endpoint));
+        }
+    }
+    if remote_agents.interval_secs == 0 {
+        problem("remote_agents.interval_secs", "must be at least 1 second".to_string());
+    }
+    if remote_agents.failure_threshold == 0 {
+        problem("remote_agents.failure_threshold", "must be at least 1".to_string());
+    }
+
+    if config.upgrade.command.is_empty() {
+        problem("upgrade.command", "must name a program such as \"nixos-rebuild\"".to_string());
+    }
+    if config.upgrade.gc_command.is_empty() {
+        problem("upgrade.gc_command", "must name a program such as \"nix-collect-garbage\"".to_string());
+    }
+    if config.upgrade.nix_env_command.is_empty() {
+        problem("upgrade.nix_env_command", "must name a program such as \"nix-env\"".to_string());
+    }
+    let retention = &config.upgrade.gc_retention;
+    if !retention.is_empty() && retention.strip_suffix('d').and_then(|days| days.parse::<u32>().ok()).is_none() {
+        problem("upgrade.gc_retention", format!("{:?} is not a number of days such as \"14d\"", retention));
+    }
+
+    if config.report.sections.is_empty() {
+        problem("report.sections", "lists no sections; the report would only have its title".to_string());
+    }
+    if config.report.exit_summary && config.report.exit_sections.is_empty() {
+        problem("report.exit_sections", "lists no sections; the exit summary would only have its title".to_string());
+    }
+
+    if let Some(reason) = object_name_problem(&config.selftest.namespace) {
+        problem("selftest.namespace", format!("{:?}: {}", config.selftest.namespace, reason));
+    }
+    if let Some(ref network) = config.selftest.network {
+        if network.split('/').filter(|part| !part.is_empty()).count() != 2 {
+            problem("selftest.network", format!("{:?} should be \"namespace/name\"", network));
+        }
+    }
+    if config.selftest.step_timeout_secs == 0 {
+        problem("selftest.step_timeout_secs", "must be greater than 0".to_string());
+    }
+
+    if config.tenants.growth_alert_percent < 0.0 {
+        problem("tenants.growth_alert_percent", "must be 0 (disabled) or more".to_string());
+    }
+    if config.tenants.growth_window_hours == 0 {
+        problem("tenants.growth_window_hours", "must be at least 1 hour".to_string());
+    }
+    for (tenant, limits) in &config.tenants.limits {
+        for (key, value) in [("vcpus", limits.vcpus), ("memory_gib", limits.memory_gib), ("storage_gib", limits.storage_gib)] {
+            if value.is_some_and(|value| value <= 0.0) {
+                problem(&format!("tenants.limits.{}.{}", tenant, key), "must be greater than 0".to_string());
+            }
+        }
+    }
+
+    if config.crashes.enabled {
+        if config.crashes.scan_interval_secs == 0 {
+            problem("crashes.scan_interval_secs", "must be at least 1 second".to_string());
+        }
+        if config.crashes.history_days == 0 {
+            problem("crashes.history_days", "must be at least 1 day".to_string());
+        }
+        if config.crashes.processes.iter().any(|process| process.trim().is_empty()) {
+            problem("crashes.processes", "contains an empty name, which would match every binary".to_string());
+        }
+    }
+
+    match config.virtualization.backend {
+        VirtBackend::KubeVirt => {}
+        VirtBackend::Proxmox => {
+            let proxmox = &config.virtualization.proxmox;
+            if !proxmox.url.starts_with("https://") && !proxmox.url.starts_with("http://") {
+                problem("virtualization.proxmox.url", format!("{:?} is not an http(s) URL", proxmox.url));
+            }
+            if !proxmox.token_id.contains('!') {
+                problem("virtualization.proxmox.token_id", "must look like user@realm!tokenname".to_string());
+            }
+            match proxmox.token_secret_file {
+                Some(ref path) => check_file(&mut problem, "virtualization.proxmox.token_secret_file", path),
+                None if proxmox.token_secret.is_none() => problem(
+                    "virtualization.proxmox.token_secret",
+                    "token_secret or token_secret_file is required".to_string(),
+                ),
+                None => {}
+            }
+            if let Some(ref path) = proxmox.ca_file {
+                check_file(&mut problem, "virtualization.proxmox.ca_file", path);
+            }
+        }
+        VirtBackend::Qmp => {
+            if config.virtualization.qmp.hosts.is_empty() {
+                problem("virtualization.qmp.hosts", "at least one host is required".to_string());
+            }
+            if config.virtualization.qmp.sockets.trim().is_empty() {
+                problem("virtualization.qmp.sockets", "must not be empty".to_string());
+            }
+        }
+    }
+
+    if config.compare.retention_hours == 0 {
+        problem("compare.retention_hours", "must be at least 1 hour".to_string());
+    }
+    if config.compare.baseline_mins == 0 {
+        problem("compare.baseline_mins", "must be at least 1 minute".to_string());
+    } else if config.compare.baseline_mins > config.compare.retention_hours * 60 {
+        problem(
+            "compare.baseline_mins",
+            format!("{} is older than the {} hours snapshots are kept", config.compare.baseline_mins, config.compare.retention_hours),
+        );
+    }
+
+    let bad_sysctl = |name: &str| {
+        name.is_empty() || name.starts_with(['.', '/']) || name.contains("..") || name.contains(char::is_whitespace)
+    };
+    for name in &config.sysctl.watch {
+        if bad_sysctl(name) {
+            problem("sysctl.watch", format!("{:?} is not a sysctl name such as \"vm.swappiness\"", name));
+        }
+    }
+    for (name, expected) in &config.sysctl.expected {
+        if bad_sysctl(name) {
+            problem("sysctl.expected", format!("{:?} is not a sysctl name such as \"vm.swappiness\"", name));
+        } else if let Err(message) = SysctlExpectation::parse(expected) {
+            problem("sysctl.expected", format!("{}: {}", name, message));
+        }
+    }
+
+    let profile = &config.host_profile;
+    if profile.kernel.as_deref().is_some_and(|kernel| kernel.trim().is_empty()) {
+        problem("host_profile.kernel", "is empty; leave it out to not check the kernel".to_string());
+    }
+    for (name, expected) in &profile.sysctls {
+        if bad_sysctl(name) {
+            problem("host_profile.sysctls", format!("{:?} is not a sysctl name such as \"vm.swappiness\"", name));
+        } else if let Err(message) = SysctlExpectation::parse(expected) {
+            problem("host_profile.sysctls", format!("{}: {}", name, message));
+        }
+    }
+    if profile.services.iter().any(|service| service.trim().is_empty()) {
+        problem("host_profile.services", "contains an empty unit name".to_string());
+    }
+    for size in profile.hugepages.keys() {
+        if page_size_kb(size).is_none() {
+            problem("host_profile.hugepages", format!("{:?} is not a page size such as \"2M\" or \"1G\"", size));
+        }
+    }
+    if profile.cpu_flags.iter().any(|flag| flag.is_empty() || flag.contains(char::is_whitespace)) {
+        problem("host_profile.cpu_flags", "flags are single words such as \"avx2\"".to_string());
+    }
+    if profile.check_interval_secs == 0 {
+        problem("host_profile.check_interval_secs", "must be at least 1 second".to_string());
+    }
+
+    let leaks = &config.memory_leaks;
+    if leaks.processes.iter().any(|name| name.trim().is_empty()) {
+        problem("memory_leaks.processes", "contains an empty process name".to_string());
+    }
+    if leaks.sample_interval_secs == 0 {
+        problem("memory_leaks.sample_interval_secs", "must be at least 1 second".to_string());
+    }
+    if !(1..=24).contains(&leaks.window_hours) {
+        problem("memory_leaks.window_hours", "must be at least 1 and at most 24, the history kept".to_string());
+    }
+    if leaks.growth_mb_per_hour <= 0.0 {
+        problem("memory_leaks.growth_mb_per_hour", "must be above 0".to_string());
+    }
+    for name in leaks.limits_mb.keys() {
+        if !leaks.processes.contains(name) {
+            problem("memory_leaks.limits_mb", format!("{:?} is not in memory_leaks.processes", name));
+        }
+    }
+
+    let prometheus = &config.prometheus;
+    let is_url = |url: &str| url.starts_with("http://") || url.starts_with("https://");
+    if !prometheus.url.is_empty() && !is_url(&prometheus.url) {
+        problem("prometheus.url", format!("{:?} should be an http:// or https:// URL", prometheus.url));
+    }
+    for url in prometheus.targets.iter().filter(|url| !is_url(url)) {
+        problem("prometheus.targets", format!("{:?} should be an http:// or https:// URL", url));
+    }
+    if let Some(ref path) = prometheus.bearer_token_file {
+        check_file(&mut problem, "prometheus.bearer_token_file", path);
+    }
+    if let Some(ref path) = prometheus.ca_file {
+        check_file(&mut problem, "prometheus.ca_file", path);
+    }
+    if prometheus.interval_secs == 0 {
+        problem("prometheus.interval_secs", "must be at least 1 second".to_string());
+    }
+    if !prometheus.queries.is_empty() && prometheus.url.is_empty() {
+        problem("prometheus.queries", "need prometheus.url to run against".to_string());
+    }
+    if !prometheus.metrics.is_empty() && prometheus.targets.is_empty() {
+        problem("prometheus.metrics", "need prometheus.targets to scrape".to_string());
+    }
+    for (name, selector) in &prometheus.metrics {
+        if let Err(e) = check_selector(selector) {
+            problem("prometheus.metrics", format!("{}: {}", name, e));
+        }
+    }
+    let mut variable_names = HashSet::new();
+    for (key, names) in [("prometheus.queries", &prometheus.queries), ("prometheus.metrics", &prometheus.metrics)] {
+        for name in names.keys().map(String::as_str) {
+            let identifier = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !identifier {
+                problem(key, format!("{:?} must be letters, digits and '_' to be used as a variable", name));
+            } else if VARIABLES.iter().any(|(known, _)| *known == name) {
+                problem(key, format!("{:?} is already a built-in variable", name));
+            } else if !variable_names.insert(name) {
+                problem(key, format!("{:?} is also a query name", name));
+            }
+        }
+    }
+
+    let mut command_names = HashSet::new();
+    for (i, command) in config.custom_commands.iter().enumerate() {
+        if command.name.trim().is_empty() {
+            problem("custom_commands.name", format!("command #{} has no name", i + 1));
+        } else if !command_names.insert(command.name.as_str()) {
+            problem("custom_commands.name", format!("{:?} is used by more than one command", command.name));
+        }
+        if command.command.trim().is_empty() {
+            problem("custom_commands.command", format!("command #{} has nothing to run", i + 1));
+        }
+        if command.interval_secs == 0 {
+            problem("custom_commands.interval_secs", format!("{:?} must run at most once a second", command.name));
+        }
+        if command.timeout_secs == 0 {
+            problem("custom_commands.timeout_secs", format!("{:?} must be given at least 1 second", command.name));
+        }
+    }
+
+    let mut metric_names = HashSet::new();
+    for (i, metric) in config.computed_metrics.iter().enumerate() {
+        if metric.name.is_empty() || !metric.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            problem(
+                "computed_metrics.name",
+                format!("metric #{} needs a name of letters, digits, '_' and '-'", i + 1),
+            );
+        } else if !metric_names.insert(metric.name.as_str()) {
+            problem("computed_metrics.name", format!("{:?} is used by more than one metric", metric.name));
+        }
+        match Expr::parse(&metric.expr) {
+            Ok(expr) => {
+                for name in expr.variables() {
+                    if !VARIABLES.iter().any(|(known, _)| *known == name) && !variable_names.contains(name) {
+                        problem("computed_metrics.expr", format!("{}: unknown variable {:?}", metric.name, name));
+                    }
+                }
+            }
+            Err(e) => problem("computed_metrics.expr", format!("{}: {}", metric.name, e)),
+        }
+        if let (Some(warning), Some(critical)) = (metric.warning, metric.critical) {
+            let ordered = if metric.below { critical <= warning } else { critical >= warning };
+            if !ordered {
+                problem(
+                    "computed_metrics.critical",
+                    format!("{}: critical {} is less severe than warning {}", metric.name, critical, warning),
+                );
+            }
+        }
+    }
+
+    let mut view_names = HashSet::new();
+    for (i, view) in config.views.iter().enumerate() {
+        if view.name.trim().is_empty() {
+            problem("views.name", format!("view #{} has no name", i + 1));
+        } else if !view_names.insert(view.name.as_str()) {
+            problem("views.name", format!("{:?} is used by more than one view", view.name));
+        }
+        if let Some(ref level) = view.state.filter_level {
+            if !LEVEL_FILTERS.contains(&level.to_uppercase().as_str()) {
+                problem(
+                    "views.filter_level",
+                    format!("{}: {:?} is not one of {}", view.name, level, LEVEL_FILTERS.join(", ")),
+                );
+            }
+        }
+        if let Err(e) = LabelSelector::parse(&view.state.resource_selector) {
+            problem("views.resource_selector", format!("{}: {}", view.name, e));
+        }
+    }
+
+    if let Some(reason) = object_name_problem(&config.image_catalog.namespace) {
+        problem("image_catalog.namespace", format!("{:?}: {}", config.image_catalog.namespace, reason));
+    }
+    let mut image_names = HashSet::new();
+    for (i, image) in config.image_catalog.images.iter().enumerate() {
+        if image.name.trim().is_empty() {
+            problem("image_catalog.images.name", format!("image #{} has no name", i + 1));
+        } else if !image_names.insert(image.name.as_str()) {
+            problem("image_catalog.images.name", format!("{:?} is used by more than one image", image.name));
+        }
+        for (key, url) in [("url", Some(&image.url)), ("checksum_url", image.checksum_url.as_ref())] {
+            if let Some(url) = url.filter(|url| !url.starts_with("http://") && !url.starts_with("https://")) {
+                problem(&format!("image_catalog.images.{}", key), format!("{}: {:?} is not an http(s) URL", image.name, url));
+            }
+        }
+        if let Some(checksum) = image.checksum.as_ref().filter(|checksum| parse_checksum(checksum).is_none()) {
+            problem(
+                "image_catalog.images.checksum",
+                format!("{}: {:?} is not a SHA-256 or SHA-512 checksum in hex", image.name, checksum),
+            );
+        }
+    }
+
+    problems
+}
+
+/// Percentages in range and warning below critical, as (key, message)
+pub fn alert_threshold_problems(alerts: &AlertsConfig) -> Vec<(String, String)> {
+    let mut problems = Vec::new();
+    let percent_pairs = [
+        ("cpu", alerts.cpu_warning_threshold, alerts.cpu_critical_threshold),
+        ("memory", alerts.memory_warning_threshold, alerts.memory_critical_threshold),
+        ("disk", alerts.disk_warning_threshold, alerts.disk_critical_threshold),
+        ("guest_fs", alerts.guest_fs_warning_threshold, alerts.guest_fs_critical_threshold),
+        ("quota", alerts.quota_warning_threshold, alerts.quota_critical_threshold),
+        ("etcd_db", alerts.etcd_db_warning_threshold, alerts.etcd_db_critical_threshold),
+        ("fd", alerts.fd_warning_threshold, alerts.fd_critical_threshold),
+        ("ephemeral_port", alerts.ephemeral_port_warning_threshold, alerts.ephemeral_port_critical_threshold),
+        ("pid", alerts.pid_warning_threshold, alerts.pid_critical_threshold),
+        ("sched_wait", alerts.sched_wait_warning_threshold, alerts.sched_wait_critical_threshold),
+        ("cpu_throttle", alerts.cpu_throttle_warning_threshold, alerts.cpu_throttle_critical_threshold),
+    ];
+    for (name, warning, critical) in percent_pairs {
+        for (level, value) in [("warning", warning), ("critical", critical)] {
+            if !(0.0..=100.0).contains(&value) {
+                problems.push((
+                    format!("alerts.{}_{}_threshold", name, level),
+                    format!("{} is not a percentage (0-100)", value),
+                ));
+            }
+        }
+        if warning >= critical {
+            problems.push((
+                format!("alerts.{}_warning_threshold", name),
+                format!("warning ({}) should be below critical ({})", warning, critical),
+            ));
+        }
+    }
+    if alerts.api_latency_warning_threshold <= 0.0
+        || alerts.api_latency_warning_threshold >= alerts.api_latency_critical_threshold
+    {
+        problems.push((
+            "alerts.api_latency_warning_threshold".to_string(),
+            format!(
+                "warning ({} ms) should be above 0 and below critical ({} ms)",
+                alerts.api_latency_warning_threshold, alerts.api_latency_critical_threshold
+            ),
+        ));
+    }
+    if alerts.disk_latency_warning_threshold <= 0.0
+        || alerts.disk_latency_warning_threshold >= alerts.disk_latency_critical_threshold
+    {
+        problems.push((
+            "alerts.disk_latency_warning_threshold".to_string(),
+            format!(
+                "warning ({} ms) should be above 0 and below critical ({} ms)",
+                alerts.disk_latency_warning_threshold, alerts.disk_latency_critical_threshold
+            ),
+        ));
+    }
+    if alerts.guest_clock_warning_secs <= 0.0 || alerts.guest_clock_warning_secs >= alerts.guest_clock_critical_secs {
+        problems.push((
+            "alerts.guest_clock_warning_secs".to_string(),
+            format!(
+                "warning ({} s) should be above 0 and below critical ({} s)",
+                alerts.guest_clock_warning_secs, alerts.guest_clock_critical_secs
+            ),
+        ));
+    }
+    if alerts.rate_limit_per_minute > 0 && alerts.rule_rate_limit_per_minute > alerts.rate_limit_per_minute {
+        problems.push((
+            "alerts.rule_rate_limit_per_minute".to_string(),
+            format!(
+                "per-rule limit ({}) is above the overall limit ({}) and never reached",
+                alerts.rule_rate_limit_per_minute, alerts.rate_limit_per_minute
+            ),
+        ));
+    }
+    // A longer horizon is the earlier warning, so here warning > critical
+    if alerts.disk_full_critical_hours <= 0.0 || alerts.disk_full_warning_hours <= alerts.disk_full_critical_hours {
+        problems.push((
+            "alerts.disk_full_warning_hours".to_string(),
+            format!(
+                "warning ({} h) should be above critical ({} h), which should be above 0",
+                alerts.disk_full_warning_hours, alerts.disk_full_critical_hours
+            ),
+        ));
+    }
+    // Less entropy is worse, so here warning > critical as well
+    if alerts.entropy_warning_bits <= alerts.entropy_critical_bits {
+        problems.push((
+            "alerts.entropy_warning_bits".to_string(),
+            format!(
+                "warning ({} bits) should be above critical ({} bits)",
+                alerts.entropy_warning_bits, alerts.entropy_critical_bits
+            ),
+        ));
+    }
+    if alerts.load_warning_threshold <= 0.0 || alerts.load_warning_threshold >= alerts.load_critical_threshold {
+        problems.push((
+            "alerts.load_warning_threshold".to_string(),
+            format!(
+                "warning ({}) should be above 0 and below critical ({})",
+                alerts.load_warning_threshold, alerts.load_critical_threshold
+            ),
+        ));
+    }
+    problems
+}
+
+fn check_file(problem: &mut impl FnMut(&str, String), key: &str, path: &str) {
+    if !Path::new(path).is_file() {
+        problem(key, format!("{} does not exist", path));
+    }
+}
+
+fn check_levels(problem: &mut impl FnMut(&str, String), key: &str, levels: &[String]) {
+    for level in levels {
+        if !NOTIFY_LEVELS.contains(&level.to_lowercase().as_str()) {
+            problem(key, format!("{:?} is not one of {}", level, NOTIFY_LEVELS.join(", ")));
+        }
+    }
+}
+
+/// First top-level section that fails to deserialize by itself
+fn failing_section(table: &toml::Table) -> Option<String> {
+    table.iter().find_map(|(name, value)| {
+        let mut single = toml::Table::new();
+        single.insert(name.clone(), value.clone());
+        Config::deserialize(toml::Value::Table(single)).is_err().then(|| name.clone())
+    })
+}
+
+fn one_line(message: &str) -> String {
+    message.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Split a value deserialization error into its message and the dotted key
+/// it names ("invalid type ...\nin `general.refresh_interval`")
+fn split_error(error: &str) -> (String, String) {
+    match error.trim_end().rsplit_once("\nin `") {
+        Some((message, key)) => (one_line(message), key.trim_end_matches('`').to_string()),
+        None => (one_line(error), String::new()),
+    }
+}
+
+/// Remove the deepest existing table entry along `key`, returning the path
+/// that was removed. Falls back to the whole top-level section.
+fn remove_key(table: &mut toml::Table, key: &str) -> Option<String> {
+    let parts: Vec<&str> = key.split('.').filter(|p| !p.is_empty()).collect();
+    let depth = remove_path(table, &parts)?;
+    Some(parts[..depth].join("."))
+}
+
+/// Remove the deepest entry of `parts` present in `table`, returning how
+/// many path segments it spanned
+fn remove_path(table: &mut toml::Table, parts: &[&str]) -> Option<usize> {
+    let (first, rest) = parts.split_first()?;
+    if !rest.is_empty() {
+        if let Some(depth) = table
+            .get_mut(*first)
+            .and_then(|v| v.as_table_mut())
+            .and_then(|child| remove_path(child, rest))
+        {
+            return Some(depth + 1);
+        }
+    }
+    table.remove(*first).map(|_| 1)
+}
+
+/// Best-effort line of a dotted key: the `key =` line inside its table,
+/// the table header itself, or the header of the table it belongs in
+pub fn locate(contents: &str, key: &str) -> Option<usize> {
+    let (table, leaf) = match key.rsplit_once('.') {
+        Some((table, leaf)) => (table, leaf),
+        None => ("", key),
+    };
+    let mut current = String::new();
+    let mut header = None;
+    for (index, raw) in contents.lines().enumerate() {
+        let line = raw.trim();
+        if line.starts_with('[') {
+            current = line.trim_matches(|c| c == '[' || c == ']').trim().to_string();
+            if current == key {
+                return Some(index + 1);
+            }
+            if current == table && header.is_none() {
+                header = Some(index + 1);
+            }
+            continue;
+        }
+        let assigned = line
+            .split_once('=')
+            .map(|(name, _)| name.trim().trim_matches('"'))
+            .filter(|name| *name == leaf);
+        if assigned.is_some() && current == table {
+            return Some(index + 1);
+        }
+    }
+    header
+}
+
+fn line_of_offset(contents: &str, offset: usize) -> usize {
+    contents[..offset.min(contents.len())].matches('\n').count() + 1
+}
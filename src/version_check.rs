@@ -0,0 +1,159 @@
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tokio::sync::oneshot;
+
+use crate::config::UpdateCheckConfig;
+
+/// This build, as compared against the latest release
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Lines of the release notes kept for the popup
+const MAX_NOTES_LINES: usize = 40;
+
+/// A failed check is tried again after this long instead of the interval
+const RETRY_AFTER: Duration = Duration::from_secs(3600);
+
+/// A published release newer than this build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    /// Without a leading "v"
+    pub version: String,
+    /// The start of the release notes
+    pub notes: Vec<String>,
+    /// Release page
+    pub url: Option<String>,
+    pub published: Option<String>,
+}
+
+/// Asks `update_check.url` for the latest release every `interval_hours`,
+/// in the background, keeping it when it is newer than this build
+pub struct UpdateChecker {
+    url: String,
+    interval: Duration,
+    next_check: Instant,
+    pending: Option<oneshot::Receiver<Result<Option<Release>, String>>>,
+    pub newer: Option<Release>,
+    /// Why the last check failed, for the popup
+    pub error: Option<String>,
+}
+
+impl UpdateChecker {
+    pub fn new(config: &UpdateCheckConfig) -> Self {
+        Self {
+            url: config.url.clone(),
+            interval: Duration::from_secs(config.interval_hours.max(1) * 3600),
+            next_check: Instant::now(),
+            pending: None,
+            newer: None,
+            error: None,
+        }
+    }
+
+    /// Start a check when one is due and take in a finished one, returning
+    /// a release the first time it is found newer
+    pub fn poll(&mut self) -> Option<Release> {
+        if let Some(rx) = self.pending.as_mut() {
+            let result = match rx.try_recv() {
+                Ok(result) => result,
+                Err(oneshot::error::TryRecvError::Empty) => return None,
+                Err(oneshot::error::TryRecvError::Closed) => Err("check was cancelled".to_string()),
+            };
+            self.pending = None;
+            return match result {
+                Ok(newer) => {
+                    self.error = None;
+                    self.next_check = Instant::now() + self.interval;
+                    let found = newer.clone().filter(|release| self.newer.as_ref() != Some(release));
+                    self.newer = newer;
+                    found
+                }
+                Err(e) => {
+                    tracing::debug!("Update check against {} failed: {}", self.url, e);
+                    self.error = Some(e);
+                    self.next_check = Instant::now() + RETRY_AFTER.min(self.interval);
+                    None
+                }
+            };
+        }
+
+        if Instant::now() >= self.next_check {
+            let (tx, rx) = oneshot::channel();
+            let url = self.url.clone();
+            tokio::spawn(async move {
+                let result = latest_release(&url).await.map_err(|e| format!("{:#}", e));
+                let _ = tx.send(result.map(|release| release.filter(|r| is_newer(&r.version, CURRENT_VERSION))));
+            });
+            self.pending = Some(rx);
+        }
+        None
+    }
+}
+
+/// The latest release at `url`: GitHub's release JSON (`tag_name`, `body`,
+/// `html_url`) or a plain `version`, `changelog`, `url` object
+async fn latest_release(url: &str) -> Result<Option<Release>> {
+    let output = Command::new("curl")
+        .args(["-sSfL", "--max-time", "15", "-H", "Accept: application/vnd.github+json", "-A"])
+        .arg(format!("hypervisor-tui/{}", CURRENT_VERSION))
+        .arg(url)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .context("Failed to run curl")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("{}", stderr.trim().trim_start_matches("curl: "));
+    }
+
+    let json: Value = serde_json::from_slice(&output.stdout).context("Release info is not JSON")?;
+    let field = |keys: &[&str]| keys.iter().find_map(|key| json.get(key).and_then(Value::as_str)).map(str::to_string);
+    let Some(version) = field(&["tag_name", "version"]) else {
+        bail!("Release info has neither tag_name nor version");
+    };
+    // Drafts and prereleases aren't offered
+    if json.get("draft").and_then(Value::as_bool) == Some(true)
+        || json.get("prerelease").and_then(Value::as_bool) == Some(true)
+    {
+        return Ok(None);
+    }
+    let notes = field(&["body", "changelog"]).unwrap_or_default();
+    Ok(Some(Release {
+        version: version.trim_start_matches('v').to_string(),
+        notes: notes.lines().map(|line| line.trim_end().to_string()).take(MAX_NOTES_LINES).collect(),
+        url: field(&["html_url", "url"]),
+        published: field(&["published_at", "date"]),
+    }))
+}
+
+/// `candidate` is a later version than `current`, comparing dotted numbers
+/// and ranking a pre-release ("1.2.0-rc1") below its release
+fn is_newer(candidate: &str, current: &str) -> bool {
+    compare_versions(candidate, current) == Ordering::Greater
+}
+
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let split = |version: &str| {
+        // Build metadata ("+nix") doesn't order versions
+        let version = version.trim().trim_start_matches('v').split('+').next().unwrap_or_default();
+        let (release, pre) = match version.split_once('-') {
+            Some((release, pre)) => (release, Some(pre.to_string())),
+            None => (version, None),
+        };
+        let numbers: Vec<u64> = release.split('.').map(|part| part.parse().unwrap_or(0)).collect();
+        (numbers, pre)
+    };
+    let ((a_numbers, a_pre), (b_numbers, b_pre)) = (split(a), split(b));
+    let len = a_numbers.len().max(b_numbers.len());
+    let pad = |numbers: &[u64]| (0..len).map(|i| numbers.get(i).copied().unwrap_or(0)).collect::<Vec<_>>();
+    pad(&a_numbers).cmp(&pad(&b_numbers)).then_with(|| match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.cmp(&b),
+    })
+}
@@ -0,0 +1,75 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::config_layers::layer_paths;
+use crate::ui_state::UiState;
+
+/// Views recalled with Alt-1 to Alt-9, in config order
+pub const SHORTCUT_VIEWS: usize = 9;
+
+/// `[[views]]`: a named investigation setup (screen, log search and
+/// followed services, resource kind and selector, sort orders and chart
+/// views) recalled from the command palette or with Alt-<n>. A search,
+/// level filter or selector left out is cleared; anything else stays.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedView {
+    pub name: String,
+
+    /// Journal units the log view follows; empty keeps the current ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub services: Vec<String>,
+
+    #[serde(flatten)]
+    pub state: UiState,
+}
+
+impl SavedView {
+    /// Alt-<n> when the view is among the first nine
+    pub fn shortcut(index: usize) -> Option<String> {
+        (index < SHORTCUT_VIEWS).then(|| format!("Alt-{}", index + 1))
+    }
+}
+
+/// Append `view` to the config file with the last word: the one in the
+/// working directory if there is one, else the user's (created if need be).
+/// Other files are left alone, the NixOS settings and site file being
+/// shared. Since an array set in a later file replaces the earlier one,
+/// `current` (the views in effect) is written along when that file has no
+/// views of its own yet, so none of them get lost.
+pub fn save_view(view: &SavedView, current: &[SavedView]) -> Result<PathBuf> {
+    if current.iter().any(|saved| saved.name == view.name) {
+        bail!("a view named {:?} already exists", view.name);
+    }
+    let path = layer_paths()
+        .into_iter()
+        .rev()
+        .find(|(label, _)| matches!(*label, "local" | "user"))
+        .map_or_else(Config::user_config_path, |(_, path)| path);
+
+    let mut contents = fs::read_to_string(&path).unwrap_or_default();
+    let table: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("{} doesn't parse, not touching it", path.display()))?;
+    let inherited = if table.contains_key("views") { &[][..] } else { current };
+
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    for saved in inherited.iter().chain([view]) {
+        let entry = toml::to_string(saved).context("Failed to serialize view")?;
+        if !contents.is_empty() {
+            contents.push('\n');
+        }
+        contents.push_str(&format!("[[views]]\n{}", entry));
+    }
+
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {:?}", parent))?;
+    }
+    fs::write(&path, contents).with_context(|| format!("Failed to write config file: {:?}", path))?;
+    Ok(path)
+}
+
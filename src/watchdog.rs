@@ -0,0 +1,129 @@
+use crossterm::{
+    cursor::Show,
+    event::{DisableFocusChange, DisableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, LeaveAlternateScreen},
+};
+use std::backtrace::Backtrace;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, ThreadId};
+
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+static SUSPEND: AtomicBool = AtomicBool::new(false);
+static REPAINT: AtomicBool = AtomicBool::new(false);
+
+/// Put the console back into a usable state: cooked mode, main screen,
+/// no mouse capture, visible cursor. Safe to call more than once.
+pub fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, DisableFocusChange, Show);
+}
+
+/// Append every panic with a backtrace to `crash_log`. Call from the
+/// thread running the event loop: only its panics restore the terminal and
+/// print the default message. A panic elsewhere, in a spawned task, ends
+/// just that task, so the terminal stays as the UI still draws it and the
+/// panic goes to the crash log and the tracing log.
+pub fn install_panic_hook(crash_log: PathBuf) {
+    let ui_thread: ThreadId = thread::current().id();
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let on_ui_thread = thread::current().id() == ui_thread;
+        if on_ui_thread {
+            restore_terminal();
+        }
+
+        let report = format!(
+            "=== {} hypervisor-tui {} panicked in {} ===\n{}\n\n{}\n",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            env!("CARGO_PKG_VERSION"),
+            thread::current().name().unwrap_or("an unnamed thread"),
+            info,
+            Backtrace::force_capture()
+        );
+        if let Some(parent) = crash_log.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&crash_log)
+            .and_then(|mut file| file.write_all(report.as_bytes()));
+
+        if !on_ui_thread {
+            tracing::error!("Background task panicked: {} (see {})", info, crash_log.display());
+            return;
+        }
+        if written.is_ok() {
+            eprintln!("Crash report written to {}", crash_log.display());
+        }
+        default_hook(info);
+    }));
+}
+
+/// Ask the event loop to exit on SIGTERM or SIGHUP so the terminal is
+/// restored through the normal shutdown path, to suspend on SIGTSTP, and
+/// to repaint everything on SIGCONT or SIGWINCH: after `fg`, or a screen
+/// or tmux client reattaching, the terminal may show anything
+pub fn spawn_signal_handler() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    tokio::spawn(async move {
+        let (Ok(mut term), Ok(mut hup), Ok(mut tstp), Ok(mut cont), Ok(mut winch)) = (
+            signal(SignalKind::terminate()),
+            signal(SignalKind::hangup()),
+            signal(SignalKind::from_raw(libc::SIGTSTP)),
+            signal(SignalKind::from_raw(libc::SIGCONT)),
+            signal(SignalKind::window_change()),
+        ) else {
+            tracing::warn!("Failed to install signal handlers");
+            return;
+        };
+
+        loop {
+            tokio::select! {
+                _ = term.recv() => {
+                    tracing::info!("Received SIGTERM, shutting down");
+                    break;
+                }
+                _ = hup.recv() => {
+                    tracing::info!("Received SIGHUP, shutting down");
+                    break;
+                }
+                _ = tstp.recv() => SUSPEND.store(true, Ordering::SeqCst),
+                _ = cont.recv() => REPAINT.store(true, Ordering::SeqCst),
+                _ = winch.recv() => REPAINT.store(true, Ordering::SeqCst),
+            }
+        }
+        SHUTDOWN.store(true, Ordering::SeqCst);
+    });
+}
+
+pub fn shutdown_requested() -> bool {
+    SHUTDOWN.load(Ordering::SeqCst)
+}
+
+/// Whether a SIGTSTP arrived since the last call
+pub fn take_suspend_request() -> bool {
+    SUSPEND.swap(false, Ordering::SeqCst)
+}
+
+/// Whether the screen needs repainting from scratch since the last call
+pub fn take_repaint_request() -> bool {
+    REPAINT.swap(false, Ordering::SeqCst)
+}
+
+/// Restore the terminal and stop the process group like Ctrl-Z in a
+/// cooked terminal would, returning once it is continued. SIGTSTP is
+/// caught to get here, so this stops with SIGSTOP, which the shell reports
+/// as a stopped job all the same.
+pub fn suspend() {
+    restore_terminal();
+    // SAFETY: sending a signal touches no memory of ours
+    unsafe {
+        libc::kill(0, libc::SIGSTOP);
+    }
+}
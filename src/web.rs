@@ -0,0 +1,190 @@
+use anyhow::{Context, Result};
+use crate::alerts::Alert;
+use crate::http::{respond, MAX_REQUEST_HEAD};
+use crate::types::{LogEntry, QuotaUsage};
+use futures::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio_tungstenite::tungstenite::Message;
+
+/// How long a client gets to send its request line and headers
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+const INDEX_HTML: &str = include_str!("web/index.html");
+
+/// What the browser view shows, published after every update
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WebSnapshot {
+    pub host: String,
+    pub node: Option<String>,
+    pub updated: String,
+    pub read_only: bool,
+    pub system: WebSystem,
+    pub cluster: WebCluster,
+    pub alerts: Vec<Alert>,
+    pub quotas: Vec<QuotaUsage>,
+    pub logs: Vec<LogEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WebSystem {
+    pub cpu_percent: f64,
+    pub memory_used_gb: f64,
+    pub memory_total_gb: f64,
+    pub disk_percent: f64,
+    pub load_avg: f64,
+    pub uptime_seconds: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WebCluster {
+    pub flavor: String,
+    pub nodes_ready: u32,
+    pub nodes_total: u32,
+    pub pods_running: u32,
+    pub vms_running: u32,
+    pub vms_stopped: u32,
+    pub vms_migrating: u32,
+}
+
+/// Serves the latest snapshot over HTTP and WebSocket in the background.
+/// The bridge never reads commands from clients, so it is read-only
+/// regardless of `--read-only`.
+pub struct WebBridge {
+    state: watch::Sender<Arc<String>>,
+}
+
+impl WebBridge {
+    /// Bind `listen` and start accepting connections
+    pub async fn start(listen: &str) -> Result<Self> {
+        let listener = TcpListener::bind(listen)
+            .await
+            .with_context(|| format!("Failed to listen on {}", listen))?;
+        tracing::info!("Web bridge listening on http://{}", listen);
+
+        let (state, rx) = watch::channel(Arc::new("{}".to_string()));
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, peer)) => {
+                        let rx = rx.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = handle(stream, rx).await {
+                                tracing::debug!("Web client {} failed: {:#}", peer, e);
+                            }
+                        });
+                    }
+                    Err(e) => tracing::warn!("Web bridge accept failed: {}", e),
+                }
+            }
+        });
+
+        Ok(Self { state })
+    }
+
+    /// Hand a new snapshot to connected and future clients
+    pub fn publish(&self, snapshot: &WebSnapshot) {
+        match serde_json::to_string(snapshot) {
+            Ok(json) => {
+                self.state.send_replace(Arc::new(json));
+            }
+            Err(e) => tracing::warn!("Failed to serialize web snapshot: {}", e),
+        }
+    }
+}
+
+async fn handle(mut stream: TcpStream, rx: watch::Receiver<Arc<String>>) -> Result<()> {
+    // Peek rather than read, so a WebSocket handshake can still be
+    // handed to tungstenite with the request intact
+    let head = tokio::time::timeout(REQUEST_TIMEOUT, peek_head(&stream))
+        .await
+        .context("Timed out reading request")??;
+    let request_line = head.lines().next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default().split('?').next().unwrap_or_default();
+
+    if method != "GET" {
+        consume(&mut stream, head.len()).await?;
+        return respond(&mut stream, "405 Method Not Allowed", "text/plain", "read-only\n").await;
+    }
+
+    match path {
+        "/ws" => serve_websocket(stream, rx).await,
+        "/state" => {
+            let json = rx.borrow().clone();
+            consume(&mut stream, head.len()).await?;
+            respond(&mut stream, "200 OK", "application/json", &json).await
+        }
+        "/" | "/index.html" => {
+            consume(&mut stream, head.len()).await?;
+            respond(&mut stream, "200 OK", "text/html; charset=utf-8", INDEX_HTML).await
+        }
+        _ => {
+            consume(&mut stream, head.len()).await?;
+            respond(&mut stream, "404 Not Found", "text/plain", "not found\n").await
+        }
+    }
+}
+
+/// Wait until the full request head (up to the blank line) has arrived
+async fn peek_head(stream: &TcpStream) -> Result<String> {
+    let mut buf = vec![0; MAX_REQUEST_HEAD];
+    let mut last = 0;
+    loop {
+        let n = stream.peek(&mut buf).await?;
+        if n == 0 {
+            anyhow::bail!("Connection closed before the request");
+        }
+        if let Some(end) = buf[..n].windows(4).position(|w| w == b"\r\n\r\n") {
+            return Ok(String::from_utf8_lossy(&buf[..end + 4]).into_owned());
+        }
+        if n == buf.len() {
+            anyhow::bail!("Request head larger than {} bytes", MAX_REQUEST_HEAD);
+        }
+        // peek returns immediately while no new data arrived
+        if n == last {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        last = n;
+    }
+}
+
+/// Read the peeked request head off the socket before responding
+async fn consume(stream: &mut TcpStream, len: usize) -> Result<()> {
+    let mut buf = vec![0; len];
+    tokio::io::AsyncReadExt::read_exact(stream, &mut buf).await?;
+    Ok(())
+}
+
+/// Send the current snapshot, then every new one until the client leaves.
+/// Anything the client sends other than close is ignored.
+async fn serve_websocket(stream: TcpStream, mut rx: watch::Receiver<Arc<String>>) -> Result<()> {
+    let mut ws = tokio_tungstenite::accept_async(stream)
+        .await
+        .context("WebSocket handshake failed")?;
+
+    let current = rx.borrow_and_update().clone();
+    ws.send(Message::Text(current.to_string())).await?;
+
+    loop {
+        tokio::select! {
+            changed = rx.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                let json = rx.borrow_and_update().clone();
+                ws.send(Message::Text(json.to_string())).await?;
+            }
+            incoming = ws.next() => match incoming {
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+            },
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,272 @@
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::{backend::Backend, Terminal};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::collectors::{LogCollector, ServiceCategory};
+use crate::config::Config;
+use crate::theme::Theme;
+use crate::types::ClusterFlavor;
+use crate::ui;
+use crate::watchdog;
+
+/// Step a threshold moves per Left/Right press
+const THRESHOLD_STEP: f64 = 5.0;
+
+/// Pages of the setup wizard, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStep {
+    Kubeconfig,
+    Services,
+    Thresholds,
+    Theme,
+    Review,
+}
+
+impl WizardStep {
+    pub const ALL: [WizardStep; 5] = [
+        WizardStep::Kubeconfig,
+        WizardStep::Services,
+        WizardStep::Thresholds,
+        WizardStep::Theme,
+        WizardStep::Review,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            WizardStep::Kubeconfig => "Cluster connection",
+            WizardStep::Services => "Services to monitor",
+            WizardStep::Thresholds => "Alert thresholds",
+            WizardStep::Theme => "Theme",
+            WizardStep::Review => "Review and save",
+        }
+    }
+}
+
+/// A kubeconfig the wizard offers, and whether the file is there
+pub struct KubeconfigChoice {
+    pub path: String,
+    pub source: &'static str,
+    pub exists: bool,
+}
+
+/// State of the first-run setup wizard. Everything it asks for ends up in
+/// a regular `Config`, written to the user config path.
+pub struct Wizard {
+    pub step: usize,
+    pub flavor: ClusterFlavor,
+    pub kubeconfigs: Vec<KubeconfigChoice>,
+    pub kubeconfig: usize,
+    /// Discovered units, grouped like the log services picker
+    pub services: Vec<(ServiceCategory, String, bool)>,
+    /// Label and value of each threshold, from the alert defaults
+    pub thresholds: Vec<(&'static str, f64)>,
+    pub theme: Theme,
+    /// Row under the cursor on the current step
+    pub selected: usize,
+    pub path: PathBuf,
+    pub status: Option<String>,
+}
+
+impl Wizard {
+    pub async fn new(path: PathBuf) -> Self {
+        let flavor = ClusterFlavor::detect();
+        let defaults = Config::default();
+
+        let mut kubeconfigs = Vec::new();
+        if let Ok(path) = std::env::var("KUBECONFIG") {
+            kubeconfigs.push((path, "$KUBECONFIG"));
+        }
+        for (flavor, source) in [
+            (ClusterFlavor::K3s, "k3s"),
+            (ClusterFlavor::Rke2, "RKE2"),
+            (ClusterFlavor::Kubeadm, "kubeadm"),
+        ] {
+            kubeconfigs.push((flavor.kubeconfig_path().to_string(), source));
+        }
+        if let Ok(home) = std::env::var("HOME") {
+            kubeconfigs.push((format!("{}/.kube/config", home), "user"));
+        }
+        let kubeconfigs: Vec<KubeconfigChoice> = kubeconfigs
+            .into_iter()
+            .map(|(path, source)| KubeconfigChoice { exists: Path::new(&path).exists(), path, source })
+            .collect();
+        // The detected flavor's file, or the first one that is there
+        let kubeconfig = kubeconfigs
+            .iter()
+            .position(|k| k.exists && k.path == flavor.kubeconfig_path())
+            .or_else(|| kubeconfigs.iter().position(|k| k.exists))
+            .unwrap_or(0);
+
+        let following = flavor.services();
+        let mut units = match LogCollector::new() {
+            Ok(collector) => collector.discover_units().await.unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+        units.extend(following.iter().cloned());
+        units.sort();
+        units.dedup();
+        let mut services: Vec<(ServiceCategory, String, bool)> = units
+            .into_iter()
+            .map(|unit| (ServiceCategory::classify(&unit), following.contains(&unit), unit))
+            // Hosts run hundreds of units; only offer the ones this tool is about
+            .filter(|(category, checked, _)| *checked || *category != ServiceCategory::Other)
+            .map(|(category, checked, unit)| (category, unit, checked))
+            .collect();
+        services.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+        let alerts = &defaults.alerts;
+        Self {
+            step: 0,
+            flavor,
+            kubeconfigs,
+            kubeconfig,
+            services,
+            thresholds: vec![
+                ("CPU warning %", alerts.cpu_warning_threshold),
+                ("CPU critical %", alerts.cpu_critical_threshold),
+                ("Memory warning %", alerts.memory_warning_threshold),
+                ("Memory critical %", alerts.memory_critical_threshold),
+                ("Disk warning %", alerts.disk_warning_threshold),
+                ("Disk critical %", alerts.disk_critical_threshold),
+            ],
+            theme: defaults.display.theme,
+            selected: 0,
+            path,
+            status: None,
+        }
+    }
+
+    pub fn current_step(&self) -> WizardStep {
+        WizardStep::ALL[self.step]
+    }
+
+    fn rows(&self) -> usize {
+        match self.current_step() {
+            WizardStep::Kubeconfig => self.kubeconfigs.len(),
+            WizardStep::Services => self.services.len(),
+            WizardStep::Thresholds => self.thresholds.len(),
+            WizardStep::Theme => THEMES.len(),
+            WizardStep::Review => 0,
+        }
+    }
+
+    fn navigate(&mut self, down: bool) {
+        let rows = self.rows().max(1);
+        self.selected = if down { (self.selected + 1) % rows } else { (self.selected + rows - 1) % rows };
+        if self.current_step() == WizardStep::Kubeconfig {
+            self.kubeconfig = self.selected;
+        } else if self.current_step() == WizardStep::Theme {
+            self.theme = THEMES[self.selected];
+        }
+    }
+
+    fn change(&mut self, up: bool) {
+        match self.current_step() {
+            WizardStep::Services => {
+                if let Some(service) = self.services.get_mut(self.selected) {
+                    service.2 = !service.2;
+                }
+            }
+            WizardStep::Thresholds => {
+                if let Some((_, value)) = self.thresholds.get_mut(self.selected) {
+                    let step = if up { THRESHOLD_STEP } else { -THRESHOLD_STEP };
+                    *value = (*value + step).clamp(THRESHOLD_STEP, 100.0);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn go_to(&mut self, step: usize) {
+        self.step = step.min(WizardStep::ALL.len() - 1);
+        self.status = None;
+        self.selected = match self.current_step() {
+            WizardStep::Kubeconfig => self.kubeconfig,
+            WizardStep::Theme => THEMES.iter().position(|t| *t == self.theme).unwrap_or(0),
+            _ => 0,
+        };
+    }
+
+    /// The first threshold pair whose warning isn't below its critical
+    pub fn threshold_problem(&self) -> Option<String> {
+        self.thresholds.chunks(2).find_map(|pair| {
+            let [(warning, w), (critical, c)] = pair else { return None };
+            (w >= c).then(|| format!("{} ({}) must be below {} ({})", warning, w, critical, c))
+        })
+    }
+
+    /// The defaults with the wizard's answers applied
+    pub fn config(&self) -> Config {
+        let mut config = Config::default();
+        if let Some(choice) = self.kubeconfigs.get(self.kubeconfig) {
+            // Leave it unset when it is what the flavor would pick anyway
+            if choice.path != self.flavor.kubeconfig_path() {
+                config.kubernetes.kubeconfig_path = Some(choice.path.clone());
+            }
+        }
+        config.logging.services =
+            Some(self.services.iter().filter(|s| s.2).map(|s| s.1.clone()).collect());
+        let values: Vec<f64> = self.thresholds.iter().map(|t| t.1).collect();
+        let alerts = &mut config.alerts;
+        alerts.cpu_warning_threshold = values[0];
+        alerts.cpu_critical_threshold = values[1];
+        alerts.memory_warning_threshold = values[2];
+        alerts.memory_critical_threshold = values[3];
+        alerts.disk_warning_threshold = values[4];
+        alerts.disk_critical_threshold = values[5];
+        config.display.theme = self.theme;
+        config
+    }
+}
+
+/// Themes offered, in display order
+pub const THEMES: [Theme; 3] = [Theme::Auto, Theme::Dark, Theme::Light];
+
+/// Ask for the basics and write them to `path`. Esc skips the wizard and
+/// runs on defaults, leaving nothing written so it is offered again.
+pub async fn run<B: Backend>(terminal: &mut Terminal<B>, path: PathBuf) -> Result<()> {
+    let mut wizard = Wizard::new(path).await;
+
+    loop {
+        terminal.draw(|f| ui::wizard::draw(f, &wizard))?;
+        if watchdog::shutdown_requested() {
+            return Ok(());
+        }
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Esc => return Ok(()),
+            KeyCode::Up => wizard.navigate(false),
+            KeyCode::Down => wizard.navigate(true),
+            KeyCode::Char(' ') => wizard.change(true),
+            KeyCode::Right | KeyCode::Char('+') => wizard.change(true),
+            KeyCode::Left | KeyCode::Char('-') => wizard.change(false),
+            KeyCode::BackTab | KeyCode::Backspace => wizard.go_to(wizard.step.saturating_sub(1)),
+            KeyCode::Enter | KeyCode::Tab if wizard.current_step() == WizardStep::Review => {
+                if let Some(problem) = wizard.threshold_problem() {
+                    wizard.status = Some(problem);
+                    continue;
+                }
+                match wizard.config().save(&wizard.path) {
+                    Ok(()) => {
+                        tracing::info!("Setup wizard wrote {:?}", wizard.path);
+                        return Ok(());
+                    }
+                    Err(e) => wizard.status = Some(format!("{:#}", e)),
+                }
+            }
+            KeyCode::Enter | KeyCode::Tab => wizard.go_to(wizard.step + 1),
+            _ => {}
+        }
+    }
+}